@@ -0,0 +1,32 @@
+//! Print native search results for a catalog and query list, one JSON array
+//! per line. `tests/node/parity.mjs` runs the same queries through the wasm
+//! build and diffs the output.
+//!
+//! Usage: `cargo run -p ms-search-core --example parity -- <catalog.json> <queries.txt>`
+//!
+//! Query lines of the form `similar:<skill-id>` call `similar` instead of
+//! `search`; blank lines and `#` comments are skipped.
+
+use ms_search_core::SearchCatalog;
+
+const LIMIT: usize = 10;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = std::env::args().skip(1);
+    let (Some(catalog_path), Some(queries_path)) = (args.next(), args.next()) else {
+        return Err("usage: parity <catalog.json> <queries.txt>".into());
+    };
+    let catalog = SearchCatalog::from_json(&std::fs::read_to_string(catalog_path)?)?;
+    for line in std::fs::read_to_string(queries_path)?.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let json = match line.strip_prefix("similar:") {
+            Some(skill_id) => serde_json::to_string(&catalog.similar(skill_id, LIMIT))?,
+            None => serde_json::to_string(&catalog.search(line, LIMIT))?,
+        };
+        println!("{json}");
+    }
+    Ok(())
+}
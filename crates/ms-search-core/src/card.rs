@@ -0,0 +1,181 @@
+//! Exported skill-card catalog format
+//!
+//! `ms export-site` writes a [`CardCatalog`]: the same skill summary fields as
+//! the `ms show` JSON card, plus tags and the precomputed embedding vector, so
+//! client-side search only has to embed queries.
+
+use serde::{Deserialize, Serialize};
+
+use crate::CoreError;
+use crate::rrf::DEFAULT_RRF_K;
+
+/// Version of the catalog format; bumped on incompatible changes.
+pub const CATALOG_FORMAT_VERSION: u32 = 1;
+
+/// A skill card as exported for the docs site.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Card {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub layer: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(default)]
+    pub quality_score: f64,
+    #[serde(default)]
+    pub is_deprecated: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecation_reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Precomputed embedding; cards without one are lexical-only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<Vec<f32>>,
+}
+
+/// How the card embeddings were produced.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmbeddingInfo {
+    /// Embedding backend (`hash`, `api`, ...). Queries can only be embedded
+    /// client-side for `hash`.
+    pub backend: String,
+    pub dims: usize,
+}
+
+/// RRF parameters, mirroring `[search]` weights in the exporting config.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RrfWeights {
+    pub k: f32,
+    pub lexical_weight: f32,
+    pub semantic_weight: f32,
+}
+
+impl Default for RrfWeights {
+    fn default() -> Self {
+        Self {
+            k: DEFAULT_RRF_K,
+            lexical_weight: 1.0,
+            semantic_weight: 1.0,
+        }
+    }
+}
+
+/// The exported catalog file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CardCatalog {
+    pub format_version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<EmbeddingInfo>,
+    #[serde(default)]
+    pub rrf: RrfWeights,
+    pub cards: Vec<Card>,
+}
+
+impl CardCatalog {
+    /// Parse a catalog, or a bare JSON array of cards.
+    pub fn from_json(json: &str) -> Result<Self, CoreError> {
+        let value: serde_json::Value =
+            serde_json::from_str(json).map_err(|err| CoreError::InvalidCatalog(err.to_string()))?;
+        let catalog = if value.is_array() {
+            let cards: Vec<Card> = serde_json::from_value(value)
+                .map_err(|err| CoreError::InvalidCatalog(err.to_string()))?;
+            Self {
+                format_version: CATALOG_FORMAT_VERSION,
+                embedding: None,
+                rrf: RrfWeights::default(),
+                cards,
+            }
+        } else {
+            serde_json::from_value(value)
+                .map_err(|err| CoreError::InvalidCatalog(err.to_string()))?
+        };
+        if catalog.format_version > CATALOG_FORMAT_VERSION {
+            return Err(CoreError::InvalidCatalog(format!(
+                "format_version {} is newer than supported {CATALOG_FORMAT_VERSION}",
+                catalog.format_version
+            )));
+        }
+        catalog.validate()?;
+        Ok(catalog)
+    }
+
+    /// Serialize to JSON.
+    pub fn to_json(&self) -> Result<String, CoreError> {
+        serde_json::to_string(self).map_err(|err| CoreError::InvalidCatalog(err.to_string()))
+    }
+
+    fn validate(&self) -> Result<(), CoreError> {
+        let Some(info) = &self.embedding else {
+            return Ok(());
+        };
+        for card in &self.cards {
+            if let Some(embedding) = &card.embedding
+                && embedding.len() != info.dims
+            {
+                return Err(CoreError::DimensionMismatch {
+                    skill_id: card.id.clone(),
+                    expected: info.dims,
+                    actual: embedding.len(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_show_json_cards_as_bare_array() {
+        let json = r#"[{"id":"git","name":"Git","description":"d","layer":"project",
+            "version":null,"author":null,"quality_score":0.8,"is_deprecated":false}]"#;
+        let catalog = CardCatalog::from_json(json).unwrap();
+        assert_eq!(catalog.cards[0].id, "git");
+        assert!(catalog.cards[0].embedding.is_none());
+        assert!(catalog.embedding.is_none());
+    }
+
+    #[test]
+    fn round_trips_and_checks_dims() {
+        let mut catalog = CardCatalog {
+            format_version: CATALOG_FORMAT_VERSION,
+            embedding: Some(EmbeddingInfo {
+                backend: "hash".to_string(),
+                dims: 2,
+            }),
+            rrf: RrfWeights::default(),
+            cards: vec![Card {
+                id: "a".to_string(),
+                name: "A".to_string(),
+                description: String::new(),
+                layer: "base".to_string(),
+                version: None,
+                author: None,
+                quality_score: 0.5,
+                is_deprecated: false,
+                deprecation_reason: None,
+                body: None,
+                tags: vec!["x".to_string()],
+                embedding: Some(vec![1.0, 0.0]),
+            }],
+        };
+        let json = catalog.to_json().unwrap();
+        assert_eq!(CardCatalog::from_json(&json).unwrap(), catalog);
+
+        catalog.cards[0].embedding = Some(vec![1.0]);
+        let err = CardCatalog::from_json(&catalog.to_json().unwrap()).unwrap_err();
+        assert!(matches!(
+            err,
+            CoreError::DimensionMismatch { actual: 1, .. }
+        ));
+    }
+}
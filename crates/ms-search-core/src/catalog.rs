@@ -0,0 +1,390 @@
+//! In-memory search over an exported card catalog
+//!
+//! Ranking mirrors `ms search --search-type hybrid`: a lexical ranking and a
+//! semantic (hash embedding) ranking fused with RRF. The lexical side is a
+//! weighted term count over name, tags, description, and body rather than
+//! Tantivy BM25, since Tantivy is not available client-side.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::CoreError;
+use crate::card::{Card, CardCatalog, RrfWeights};
+use crate::embedding::{cosine_similarity, hash_embed, tokenize};
+use crate::query::{ParsedQuery, normalize_layer};
+use crate::rrf::{fuse_ranked, sort_ranked};
+
+const NAME_WEIGHT: f32 = 3.0;
+const TAG_WEIGHT: f32 = 2.0;
+const DESCRIPTION_WEIGHT: f32 = 1.5;
+const BODY_WEIGHT: f32 = 1.0;
+const PHRASE_WEIGHT: f32 = 2.0;
+
+/// A ranked search result.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SearchHit {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub layer: String,
+    /// Fused RRF score (or quality score for filter-only queries).
+    pub score: f32,
+    /// 1-indexed lexical rank, if the card matched lexically.
+    pub lexical_rank: Option<usize>,
+    /// 1-indexed semantic rank, if the card was ranked semantically.
+    pub semantic_rank: Option<usize>,
+}
+
+/// A card similar to another, by embedding cosine similarity.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SimilarHit {
+    pub id: String,
+    pub name: String,
+    pub similarity: f32,
+}
+
+/// Pre-tokenized card text for lexical scoring.
+#[derive(Debug, Clone)]
+struct CardText {
+    name: Vec<String>,
+    tags: Vec<String>,
+    description: Vec<String>,
+    body: Vec<String>,
+    /// Lowercased concatenation of all fields, for phrase matching.
+    full: String,
+}
+
+impl CardText {
+    fn new(card: &Card) -> Self {
+        let body = card.body.as_deref().unwrap_or("");
+        let tags: Vec<String> = card.tags.iter().map(|tag| tag.to_lowercase()).collect();
+        Self {
+            name: tokenize(&card.name),
+            description: tokenize(&card.description),
+            body: tokenize(body),
+            full: format!(
+                "{} {} {} {}",
+                card.name.to_lowercase(),
+                tags.join(" "),
+                card.description.to_lowercase(),
+                body.to_lowercase()
+            ),
+            tags,
+        }
+    }
+
+    fn contains_word(&self, word: &str) -> bool {
+        [&self.name, &self.tags, &self.description, &self.body]
+            .iter()
+            .any(|tokens| tokens.iter().any(|token| token == word))
+    }
+
+    fn score(&self, query: &ParsedQuery) -> f32 {
+        let count = |tokens: &[String], term: &str| {
+            tokens.iter().filter(|token| token.as_str() == term).count() as f32
+        };
+        let mut score = 0.0;
+        for term in &query.terms {
+            score += count(&self.name, term) * NAME_WEIGHT
+                + count(&self.tags, term) * TAG_WEIGHT
+                + count(&self.description, term) * DESCRIPTION_WEIGHT
+                + count(&self.body, term) * BODY_WEIGHT;
+        }
+        score + query.phrases.len() as f32 * PHRASE_WEIGHT
+    }
+}
+
+/// A loaded catalog ready for searching.
+#[derive(Debug, Clone)]
+pub struct SearchCatalog {
+    cards: Vec<Card>,
+    texts: Vec<CardText>,
+    by_id: HashMap<String, usize>,
+    /// Query embedding dims; `None` when queries cannot be embedded here.
+    query_dims: Option<usize>,
+    rrf: RrfWeights,
+}
+
+impl SearchCatalog {
+    /// Load a catalog from its JSON export.
+    pub fn from_json(json: &str) -> Result<Self, CoreError> {
+        Ok(Self::new(CardCatalog::from_json(json)?))
+    }
+
+    /// Build from a parsed catalog.
+    #[must_use]
+    pub fn new(catalog: CardCatalog) -> Self {
+        // Only hash embeddings can be reproduced without a backend.
+        let query_dims = catalog
+            .embedding
+            .as_ref()
+            .filter(|info| info.backend == "hash" && info.dims > 0)
+            .map(|info| info.dims);
+        let texts = catalog.cards.iter().map(CardText::new).collect();
+        let by_id = catalog
+            .cards
+            .iter()
+            .enumerate()
+            .map(|(idx, card)| (card.id.clone(), idx))
+            .collect();
+        Self {
+            cards: catalog.cards,
+            texts,
+            by_id,
+            query_dims,
+            rrf: catalog.rrf,
+        }
+    }
+
+    /// Number of cards.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    /// Whether the catalog has no cards.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    /// Look up a card by id.
+    #[must_use]
+    pub fn card(&self, skill_id: &str) -> Option<&Card> {
+        self.by_id.get(skill_id).map(|idx| &self.cards[*idx])
+    }
+
+    /// Search with the client-side query syntax (see [`ParsedQuery`]).
+    #[must_use]
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchHit> {
+        let query = ParsedQuery::parse(query);
+        let candidates: Vec<usize> = (0..self.cards.len())
+            .filter(|idx| self.passes_filters(*idx, &query))
+            .collect();
+
+        if !query.has_text() {
+            let mut ranked: Vec<(String, f32)> = candidates
+                .iter()
+                .map(|idx| {
+                    let card = &self.cards[*idx];
+                    (card.id.clone(), card.quality_score as f32)
+                })
+                .collect();
+            sort_ranked(&mut ranked);
+            ranked.truncate(limit);
+            return self.to_hits(ranked, &[], &[]);
+        }
+
+        let mut lexical: Vec<(String, f32)> = candidates
+            .iter()
+            .filter_map(|idx| {
+                let score = self.texts[*idx].score(&query);
+                (score > 0.0).then(|| (self.cards[*idx].id.clone(), score))
+            })
+            .collect();
+        sort_ranked(&mut lexical);
+
+        let mut semantic: Vec<(String, f32)> = Vec::new();
+        if let Some(dims) = self.query_dims {
+            let query_embedding = hash_embed(&query.text(), dims);
+            semantic = candidates
+                .iter()
+                .filter_map(|idx| {
+                    let card = &self.cards[*idx];
+                    let similarity = cosine_similarity(&query_embedding, card.embedding.as_ref()?);
+                    (similarity > 0.0).then(|| (card.id.clone(), similarity))
+                })
+                .collect();
+            sort_ranked(&mut semantic);
+        }
+
+        let mut fused = fuse_ranked(
+            &[
+                (lexical.as_slice(), self.rrf.lexical_weight),
+                (semantic.as_slice(), self.rrf.semantic_weight),
+            ],
+            self.rrf.k,
+        );
+        fused.truncate(limit);
+        self.to_hits(fused, &lexical, &semantic)
+    }
+
+    /// Cards most similar to `skill_id` by embedding; empty if it has none.
+    #[must_use]
+    pub fn similar(&self, skill_id: &str, limit: usize) -> Vec<SimilarHit> {
+        let Some(target) = self.card(skill_id).and_then(|card| card.embedding.as_ref()) else {
+            return Vec::new();
+        };
+        let mut ranked: Vec<(String, f32)> = self
+            .cards
+            .iter()
+            .filter(|card| card.id != skill_id)
+            .filter_map(|card| {
+                let similarity = cosine_similarity(target, card.embedding.as_ref()?);
+                Some((card.id.clone(), similarity))
+            })
+            .collect();
+        sort_ranked(&mut ranked);
+        ranked.truncate(limit);
+        ranked
+            .into_iter()
+            .filter_map(|(id, similarity)| {
+                let card = self.card(&id)?;
+                Some(SimilarHit {
+                    name: card.name.clone(),
+                    id,
+                    similarity,
+                })
+            })
+            .collect()
+    }
+
+    fn passes_filters(&self, idx: usize, query: &ParsedQuery) -> bool {
+        let card = &self.cards[idx];
+        let text = &self.texts[idx];
+        if card.is_deprecated && !query.include_deprecated {
+            return false;
+        }
+        if let Some(layer) = &query.layer
+            && normalize_layer(&card.layer.to_lowercase()) != layer.as_str()
+        {
+            return false;
+        }
+        if query
+            .min_quality
+            .is_some_and(|min| (card.quality_score as f32) < min)
+        {
+            return false;
+        }
+        if !query.tags.iter().all(|tag| text.tags.contains(tag)) {
+            return false;
+        }
+        if !query
+            .phrases
+            .iter()
+            .all(|phrase| text.full.contains(phrase))
+        {
+            return false;
+        }
+        !query.excluded.iter().any(|excluded| {
+            if excluded.contains(char::is_whitespace) {
+                text.full.contains(excluded.as_str())
+            } else {
+                text.contains_word(excluded)
+            }
+        })
+    }
+
+    fn to_hits(
+        &self,
+        ranked: Vec<(String, f32)>,
+        lexical: &[(String, f32)],
+        semantic: &[(String, f32)],
+    ) -> Vec<SearchHit> {
+        let rank_of = |list: &[(String, f32)], id: &str| {
+            list.iter()
+                .position(|(other, _)| other == id)
+                .map(|pos| pos + 1)
+        };
+        ranked
+            .into_iter()
+            .filter_map(|(id, score)| {
+                let card = self.card(&id)?;
+                Some(SearchHit {
+                    name: card.name.clone(),
+                    description: card.description.clone(),
+                    layer: card.layer.clone(),
+                    score,
+                    lexical_rank: rank_of(lexical, &id),
+                    semantic_rank: rank_of(semantic, &id),
+                    id,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = include_str!("../tests/fixtures/catalog.json");
+
+    fn catalog() -> SearchCatalog {
+        SearchCatalog::from_json(FIXTURE).unwrap()
+    }
+
+    #[test]
+    fn fixture_loads_with_embeddings() {
+        let catalog = catalog();
+        assert!(!catalog.is_empty());
+        assert_eq!(catalog.query_dims, Some(128));
+        assert!(catalog.cards.iter().all(|card| card.embedding.is_some()));
+    }
+
+    #[test]
+    fn lexical_and_semantic_agree_on_top_hit() {
+        let hits = catalog().search("git commit", 5);
+        assert_eq!(hits[0].id, "git-workflow");
+        assert_eq!(hits[0].lexical_rank, Some(1));
+        assert!(hits[0].semantic_rank.is_some());
+    }
+
+    #[test]
+    fn filters_apply_before_ranking() {
+        let catalog = catalog();
+        let hits = catalog.search("tag:rust", 10);
+        assert!(!hits.is_empty());
+        assert!(hits.iter().all(|hit| {
+            catalog
+                .card(&hit.id)
+                .is_some_and(|card| card.tags.iter().any(|tag| tag == "rust"))
+        }));
+
+        assert!(
+            catalog
+                .search("errors -rust", 10)
+                .iter()
+                .all(|hit| hit.id != "rust-errors")
+        );
+        assert!(
+            catalog
+                .search("legacy", 10)
+                .iter()
+                .all(|hit| hit.id != "legacy-deploy")
+        );
+        assert!(
+            catalog
+                .search("legacy is:deprecated", 10)
+                .iter()
+                .any(|hit| hit.id == "legacy-deploy")
+        );
+        assert!(
+            catalog
+                .search("layer:base", 10)
+                .iter()
+                .all(|hit| hit.layer == "base")
+        );
+    }
+
+    #[test]
+    fn similar_excludes_self_and_sorts() {
+        let hits = catalog().similar("rust-errors", 3);
+        assert_eq!(hits.len(), 3);
+        assert!(hits.iter().all(|hit| hit.id != "rust-errors"));
+        assert!(hits[0].similarity >= hits[1].similarity);
+        assert!(catalog().similar("missing", 3).is_empty());
+    }
+
+    #[test]
+    fn api_embeddings_fall_back_to_lexical() {
+        let mut parsed = CardCatalog::from_json(FIXTURE).unwrap();
+        if let Some(info) = parsed.embedding.as_mut() {
+            info.backend = "api".to_string();
+        }
+        let hits = SearchCatalog::new(parsed).search("git commit", 5);
+        assert!(hits.iter().all(|hit| hit.semantic_rank.is_none()));
+        assert_eq!(hits[0].id, "git-workflow");
+    }
+}
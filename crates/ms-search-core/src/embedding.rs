@@ -0,0 +1,141 @@
+//! FNV-1a hash embeddings
+//!
+//! Each token (and each adjacent-token bigram at half weight) is hashed into
+//! every dimension, then the vector is L2 normalized. Deterministic across
+//! platforms, including wasm32.
+
+/// Split text into lowercase tokens, keeping `+` and `#` (e.g. "c++", "c#").
+#[must_use]
+pub fn tokenize(text: &str) -> Vec<String> {
+    let lowered = text.to_lowercase();
+    lowered
+        .split(|c: char| !(c.is_alphanumeric() || c == '+' || c == '#'))
+        .filter(|token| !token.is_empty()) // Allow 1-char tokens (like "c", "r")
+        .map(std::string::ToString::to_string)
+        .collect()
+}
+
+/// Embed text into an L2-normalized vector of `dims` dimensions.
+#[must_use]
+pub fn hash_embed(text: &str, dims: usize) -> Vec<f32> {
+    if dims == 0 {
+        return Vec::new();
+    }
+
+    let tokens = tokenize(text);
+    let mut embedding = vec![0.0; dims];
+
+    if tokens.is_empty() {
+        return embedding;
+    }
+
+    for token in &tokens {
+        accumulate_embedding(&mut embedding, token, 1.0);
+    }
+
+    for window in tokens.windows(2) {
+        let bigram = format!("{} {}", window[0], window[1]);
+        accumulate_embedding(&mut embedding, &bigram, 0.5);
+    }
+
+    l2_normalize(&mut embedding);
+    embedding
+}
+
+/// Cosine similarity; 0.0 for mismatched lengths or zero vectors.
+#[must_use]
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+
+    let dot = dot_product(a, b);
+    let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+/// Dot product (equals cosine similarity for normalized vectors).
+#[must_use]
+pub fn dot_product(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
+}
+
+/// Scale a vector to unit length in place; zero vectors are left as is.
+pub fn l2_normalize(vec: &mut [f32]) {
+    let norm = vec.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        for value in vec.iter_mut() {
+            *value /= norm;
+        }
+    }
+}
+
+fn accumulate_embedding(embedding: &mut [f32], token: &str, weight: f32) {
+    let token_hash = fnv1a_hash(token.as_bytes());
+
+    for (i, slot) in embedding.iter_mut().enumerate() {
+        let dim_hash = fnv1a_hash_with_salt(token_hash, i as u64);
+        // Use 16 bits to create a continuous value in [-weight, weight]
+        // This reduces the probability of exact cancellation from ~1/2^n to ~0
+        let bits = (dim_hash & 0xFFFF) as f32;
+        let normalized = (bits / 32767.5) - 1.0; // Maps [0, 65535] to [-1, 1]
+        *slot += normalized * weight;
+    }
+}
+
+fn fnv1a_hash_with_salt(seed: u64, salt: u64) -> u64 {
+    let mut bytes = [0u8; 16];
+    bytes[..8].copy_from_slice(&seed.to_le_bytes());
+    bytes[8..].copy_from_slice(&salt.to_le_bytes());
+    fnv1a_hash(&bytes)
+}
+
+/// 64-bit FNV-1a hash.
+#[must_use]
+pub fn fnv1a_hash(data: &[u8]) -> u64 {
+    const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+    let mut hash = FNV_OFFSET;
+    for byte in data {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fnv1a_known_value() {
+        assert_eq!(fnv1a_hash(b"hello"), 0xa430_d846_80aa_bd0b);
+    }
+
+    #[test]
+    fn embedding_is_normalized_and_deterministic() {
+        let a = hash_embed("git commit workflow", 64);
+        let b = hash_embed("git commit workflow", 64);
+        assert_eq!(a, b);
+        let norm: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+        assert!((norm - 1.0).abs() < 1e-5);
+        assert!(hash_embed("", 64).iter().all(|v| *v == 0.0));
+        assert!(hash_embed("anything", 0).is_empty());
+    }
+
+    #[test]
+    fn related_text_is_more_similar() {
+        let base = hash_embed("rust error handling", 128);
+        let close = hash_embed("error handling in rust", 128);
+        let far = hash_embed("kubernetes canary deploy", 128);
+        assert!(cosine_similarity(&base, &close) > cosine_similarity(&base, &far));
+        assert!(cosine_similarity(&base, &[0.0; 3]).abs() < f32::EPSILON);
+    }
+}
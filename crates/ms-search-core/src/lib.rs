@@ -0,0 +1,66 @@
+//! Pure-computation core of ms skill search.
+//!
+//! Everything here is deterministic and free of filesystem, SQLite, Tantivy,
+//! and network dependencies so it can run in the browser:
+//!
+//! - [`embedding`]: FNV-1a hash embeddings and cosine similarity
+//! - [`rrf`]: Reciprocal Rank Fusion of ranked lists
+//! - [`query`]: the client-side query syntax (`tag:`, `layer:`, `-term`, quotes)
+//! - [`card`]: the exported skill-card catalog format
+//! - [`catalog`]: in-memory search and similarity over a card catalog
+//!
+//! The `ms` binary uses the same functions for its hash embedder and RRF
+//! fusion, so client-side results match native ones for the same catalog.
+//! With the `wasm` feature, `init`, `search`, and `similar` are exported via
+//! wasm-bindgen.
+
+pub mod card;
+pub mod catalog;
+pub mod embedding;
+pub mod query;
+pub mod rrf;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use card::{CATALOG_FORMAT_VERSION, Card, CardCatalog, EmbeddingInfo, RrfWeights};
+pub use catalog::{SearchCatalog, SearchHit, SimilarHit};
+pub use embedding::{cosine_similarity, hash_embed, tokenize};
+pub use query::ParsedQuery;
+pub use rrf::{DEFAULT_RRF_K, fuse_ranked, rrf_contribution};
+
+use std::fmt;
+
+/// Errors from loading or querying a card catalog.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CoreError {
+    /// The catalog JSON could not be parsed.
+    InvalidCatalog(String),
+    /// A card's embedding does not match the catalog's declared dimensions.
+    DimensionMismatch {
+        skill_id: String,
+        expected: usize,
+        actual: usize,
+    },
+    /// No catalog has been loaded yet.
+    NotInitialized,
+}
+
+impl fmt::Display for CoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidCatalog(msg) => write!(f, "invalid card catalog: {msg}"),
+            Self::DimensionMismatch {
+                skill_id,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "embedding for {skill_id} has {actual} dims, catalog declares {expected}"
+            ),
+            Self::NotInitialized => write!(f, "catalog not initialized; call init first"),
+        }
+    }
+}
+
+impl std::error::Error for CoreError {}
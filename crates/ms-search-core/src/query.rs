@@ -0,0 +1,178 @@
+//! Client-side query syntax
+//!
+//! ```text
+//! rust errors tag:cli layer:project -async "error handling"
+//! ```
+//!
+//! - bare words are search terms
+//! - `"quoted text"` is a phrase that must appear verbatim
+//! - `-word` (or `-"phrase"`) excludes skills containing it
+//! - `tag:x` requires tag `x` (repeatable, all must match)
+//! - `layer:x` restricts to a layer (base, org, project, user)
+//! - `quality:>0.7` requires a quality score of at least 0.7
+//! - `is:deprecated` includes deprecated skills (hidden by default)
+//!
+//! Unknown `key:value` pairs are kept as plain terms.
+
+use serde::Serialize;
+
+use crate::embedding::tokenize;
+
+/// A parsed search query.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ParsedQuery {
+    /// Lowercased search terms.
+    pub terms: Vec<String>,
+    /// Lowercased phrases that must appear verbatim.
+    pub phrases: Vec<String>,
+    /// Lowercased words or phrases that must not appear.
+    pub excluded: Vec<String>,
+    /// Required tags (lowercased).
+    pub tags: Vec<String>,
+    /// Required layer, normalized (`system` -> `base`, `global` -> `org`, `local` -> `user`).
+    pub layer: Option<String>,
+    /// Minimum quality score (`quality:>0.7`).
+    pub min_quality: Option<f32>,
+    /// Include deprecated skills.
+    pub include_deprecated: bool,
+}
+
+impl ParsedQuery {
+    /// Parse query syntax; never fails, unbalanced quotes run to the end.
+    #[must_use]
+    pub fn parse(input: &str) -> Self {
+        let mut query = Self::default();
+        for (negated, raw, quoted) in split_tokens(input) {
+            if quoted {
+                let phrase = raw.to_lowercase();
+                if phrase.trim().is_empty() {
+                    continue;
+                }
+                if negated {
+                    query.excluded.push(phrase);
+                } else {
+                    query.phrases.push(phrase);
+                }
+                continue;
+            }
+
+            let lowered = raw.to_lowercase();
+            if negated {
+                if !lowered.is_empty() {
+                    query.excluded.push(lowered);
+                }
+                continue;
+            }
+            match lowered.split_once(':') {
+                Some(("tag", value)) if !value.is_empty() => query.tags.push(value.to_string()),
+                Some(("layer", value)) if !value.is_empty() => {
+                    query.layer = Some(normalize_layer(value).to_string());
+                }
+                Some(("quality", value)) if parse_min_quality(value).is_some() => {
+                    query.min_quality = parse_min_quality(value);
+                }
+                Some(("is", "deprecated")) => query.include_deprecated = true,
+                _ => query.terms.extend(tokenize(&lowered)),
+            }
+        }
+        query
+    }
+
+    /// Free text used for embedding the query (terms and phrases).
+    #[must_use]
+    pub fn text(&self) -> String {
+        self.terms
+            .iter()
+            .chain(self.phrases.iter())
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Whether the query has any text to rank by.
+    #[must_use]
+    pub fn has_text(&self) -> bool {
+        !self.terms.is_empty() || !self.phrases.is_empty()
+    }
+}
+
+/// Map layer aliases onto the canonical names used by `ms search --layer`.
+#[must_use]
+pub fn normalize_layer(layer: &str) -> &str {
+    match layer {
+        "system" => "base",
+        "global" => "org",
+        "local" => "user",
+        other => other,
+    }
+}
+
+/// The bound of a `quality:>0.7` filter, clamped to `0.0..=1.0`.
+fn parse_min_quality(value: &str) -> Option<f32> {
+    let min: f32 = value.strip_prefix('>')?.parse().ok()?;
+    Some(min.clamp(0.0, 1.0))
+}
+
+/// Split into `(negated, text, quoted)` tokens.
+fn split_tokens(input: &str) -> Vec<(bool, String, bool)> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        let mut negated = false;
+        if c == '-' {
+            chars.next();
+            negated = true;
+        }
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            let phrase: String = chars.by_ref().take_while(|c| *c != '"').collect();
+            tokens.push((negated, phrase, true));
+            continue;
+        }
+        let mut word = String::new();
+        while let Some(&c) = chars.peek() {
+            if c.is_whitespace() {
+                break;
+            }
+            word.push(c);
+            chars.next();
+        }
+        if !word.is_empty() {
+            tokens.push((negated, word, false));
+        }
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_all_syntax() {
+        let query = ParsedQuery::parse(
+            r#"Rust errors tag:CLI layer:local quality:>0.5 -async "error handling" -"unsafe code" is:deprecated"#,
+        );
+        assert_eq!(query.terms, vec!["rust", "errors"]);
+        assert_eq!(query.phrases, vec!["error handling"]);
+        assert_eq!(query.excluded, vec!["async", "unsafe code"]);
+        assert_eq!(query.tags, vec!["cli"]);
+        assert_eq!(query.layer.as_deref(), Some("user"));
+        assert_eq!(query.min_quality, Some(0.5));
+        assert!(query.include_deprecated);
+        assert_eq!(query.text(), "rust errors error handling");
+    }
+
+    #[test]
+    fn unknown_keys_and_unbalanced_quotes_are_text() {
+        let query = ParsedQuery::parse(r#"lang:go "open phrase"#);
+        assert_eq!(query.terms, vec!["lang", "go"]);
+        assert_eq!(query.phrases, vec!["open phrase"]);
+        assert!(ParsedQuery::parse("   ").text().is_empty());
+        assert!(!ParsedQuery::parse("tag:x").has_text());
+    }
+}
@@ -0,0 +1,93 @@
+//! Reciprocal Rank Fusion
+//!
+//! ```text
+//! RRF(d) = Σ weight_i / (k + rank_i(d))
+//! ```
+//!
+//! with 1-indexed ranks and smoothing constant `k` (typically 60).
+
+use std::collections::HashMap;
+
+/// Default RRF smoothing constant.
+pub const DEFAULT_RRF_K: f32 = 60.0;
+
+/// Contribution of a document at `rank` (1-indexed) in a list with `weight`.
+#[must_use]
+pub fn rrf_contribution(weight: f32, k: f32, rank: usize) -> f32 {
+    weight / (k + rank as f32)
+}
+
+/// Fuse ranked `(id, score)` lists, each paired with its weight.
+///
+/// Lists are expected sorted best-first. The output is sorted by fused score
+/// (descending), with ties broken by id so results are deterministic.
+#[must_use]
+pub fn fuse_ranked(lists: &[(&[(String, f32)], f32)], k: f32) -> Vec<(String, f32)> {
+    let mut scores: HashMap<&str, f32> = HashMap::new();
+    for (list, weight) in lists {
+        for (rank, (id, _)) in list.iter().enumerate() {
+            *scores.entry(id.as_str()).or_insert(0.0) += rrf_contribution(*weight, k, rank + 1);
+        }
+    }
+
+    let mut fused: Vec<(String, f32)> = scores
+        .into_iter()
+        .map(|(id, score)| (id.to_string(), score))
+        .collect();
+    sort_ranked(&mut fused);
+    fused
+}
+
+/// Sort `(id, score)` pairs best-first, breaking ties by id.
+pub fn sort_ranked(results: &mut [(String, f32)]) {
+    results.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranked(ids: &[&str]) -> Vec<(String, f32)> {
+        ids.iter()
+            .enumerate()
+            .map(|(i, id)| ((*id).to_string(), 1.0 / (i + 1) as f32))
+            .collect()
+    }
+
+    #[test]
+    fn documents_in_both_lists_rank_first() {
+        let lexical = ranked(&["a", "b", "c"]);
+        let semantic = ranked(&["b", "d"]);
+        let fused = fuse_ranked(
+            &[(lexical.as_slice(), 1.0), (semantic.as_slice(), 1.0)],
+            DEFAULT_RRF_K,
+        );
+        assert_eq!(fused[0].0, "b");
+        assert_eq!(fused.len(), 4);
+        let expected = 1.0 / 62.0 + 1.0 / 61.0;
+        assert!((fused[0].1 - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn ties_break_by_id() {
+        let first = ranked(&["z"]);
+        let second = ranked(&["a"]);
+        let fused = fuse_ranked(
+            &[(first.as_slice(), 1.0), (second.as_slice(), 1.0)],
+            DEFAULT_RRF_K,
+        );
+        assert_eq!(fused[0].0, "a");
+        assert_eq!(fused[1].0, "z");
+    }
+
+    #[test]
+    fn weights_scale_contributions() {
+        let lexical = ranked(&["a"]);
+        let semantic = ranked(&["b"]);
+        let fused = fuse_ranked(
+            &[(lexical.as_slice(), 0.5), (semantic.as_slice(), 2.0)],
+            DEFAULT_RRF_K,
+        );
+        assert_eq!(fused[0].0, "b");
+    }
+}
@@ -0,0 +1,55 @@
+//! wasm-bindgen exports for client-side search.
+//!
+//! ```js
+//! import init_wasm, { init, search, similar } from "./ms_search_core.js";
+//! await init_wasm();
+//! init(await (await fetch("skills.cards.json")).text());
+//! const hits = JSON.parse(search("rust errors tag:cli", 10));
+//! ```
+//!
+//! Results are returned as JSON strings to keep the binding dependency-free.
+
+use std::cell::RefCell;
+
+use wasm_bindgen::prelude::*;
+
+use crate::CoreError;
+use crate::catalog::SearchCatalog;
+
+thread_local! {
+    static CATALOG: RefCell<Option<SearchCatalog>> = const { RefCell::new(None) };
+}
+
+/// Load (or replace) the card catalog; returns the number of cards.
+#[wasm_bindgen]
+pub fn init(cards_json: &str) -> Result<usize, JsError> {
+    let catalog = SearchCatalog::from_json(cards_json)?;
+    let len = catalog.len();
+    CATALOG.with(|slot| *slot.borrow_mut() = Some(catalog));
+    Ok(len)
+}
+
+/// Search the loaded catalog; returns a JSON array of hits.
+#[wasm_bindgen]
+pub fn search(query: &str, limit: usize) -> Result<String, JsError> {
+    with_catalog(|catalog| to_json(&catalog.search(query, limit)))
+}
+
+/// Cards similar to `skill_id`; returns a JSON array of hits.
+#[wasm_bindgen]
+pub fn similar(skill_id: &str, limit: usize) -> Result<String, JsError> {
+    with_catalog(|catalog| to_json(&catalog.similar(skill_id, limit)))
+}
+
+fn with_catalog(
+    f: impl FnOnce(&SearchCatalog) -> Result<String, JsError>,
+) -> Result<String, JsError> {
+    CATALOG.with(|slot| match slot.borrow().as_ref() {
+        Some(catalog) => f(catalog),
+        None => Err(CoreError::NotInitialized.into()),
+    })
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> Result<String, JsError> {
+    serde_json::to_string(value).map_err(|err| JsError::new(&err.to_string()))
+}
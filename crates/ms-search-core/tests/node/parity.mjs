@@ -0,0 +1,55 @@
+// Headless parity check: run the fixture queries through the wasm build and
+// compare with the native `parity` example byte for byte.
+//
+// Usage: node parity.mjs <wasm-pkg-dir> <native-parity-binary> <catalog.json> <queries.txt>
+//
+// <wasm-pkg-dir> is wasm-bindgen output built with `--target nodejs`.
+
+import { execFileSync } from "node:child_process";
+import { readFileSync } from "node:fs";
+import { createRequire } from "node:module";
+import path from "node:path";
+
+const LIMIT = 10;
+
+const [pkgDir, nativeBin, catalogPath, queriesPath] = process.argv.slice(2);
+if (!queriesPath) {
+  console.error("usage: node parity.mjs <wasm-pkg-dir> <native-parity-binary> <catalog.json> <queries.txt>");
+  process.exit(2);
+}
+
+const require = createRequire(import.meta.url);
+const wasm = require(path.resolve(pkgDir, "ms_search_core.js"));
+
+const loaded = wasm.init(readFileSync(catalogPath, "utf8"));
+const queries = readFileSync(queriesPath, "utf8")
+  .split("\n")
+  .map((line) => line.trim())
+  .filter((line) => line && !line.startsWith("#"));
+
+const wasmLines = queries.map((query) =>
+  query.startsWith("similar:")
+    ? wasm.similar(query.slice("similar:".length), LIMIT)
+    : wasm.search(query, LIMIT),
+);
+const nativeLines = execFileSync(nativeBin, [catalogPath, queriesPath], { encoding: "utf8" })
+  .split("\n")
+  .filter((line) => line);
+
+let failures = 0;
+queries.forEach((query, i) => {
+  if (wasmLines[i] !== nativeLines[i]) {
+    failures += 1;
+    console.error(`mismatch for ${JSON.stringify(query)}:\n  wasm:   ${wasmLines[i]}\n  native: ${nativeLines[i]}`);
+  }
+});
+if (nativeLines.length !== queries.length) {
+  failures += 1;
+  console.error(`native produced ${nativeLines.length} lines for ${queries.length} queries`);
+}
+
+if (failures > 0) {
+  console.error(`${failures} of ${queries.length} queries differ`);
+  process.exit(1);
+}
+console.log(`wasm matches native for ${queries.length} queries over ${loaded} cards`);
@@ -0,0 +1,15 @@
+-- Migration 014: Correlate skill loads with CASS session outcomes
+ALTER TABLE skill_usage ADD COLUMN session_id TEXT;
+
+CREATE INDEX idx_usage_session ON skill_usage(session_id);
+
+CREATE TABLE skill_efficacy (
+    skill_id TEXT PRIMARY KEY,
+    sessions_with INTEGER NOT NULL,
+    mean_quality_with REAL NOT NULL,
+    failure_rate_with REAL NOT NULL,
+    sessions_without INTEGER NOT NULL,
+    mean_quality_without REAL,
+    failure_rate_without REAL,
+    computed_at TEXT NOT NULL
+);
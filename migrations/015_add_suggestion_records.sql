@@ -0,0 +1,14 @@
+-- Migration 015: Persist shown suggestions with their explanations (ms why-suggested)
+CREATE TABLE suggestion_records (
+    id TEXT PRIMARY KEY,
+    skill_id TEXT NOT NULL,
+    session_id TEXT,
+    shown_at TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    context_fingerprint TEXT,
+    outcome TEXT NOT NULL DEFAULT 'pending',
+    explanation_json TEXT
+);
+
+CREATE INDEX idx_suggestion_records_skill ON suggestion_records(skill_id, shown_at);
+CREATE INDEX idx_suggestion_records_session ON suggestion_records(session_id);
@@ -0,0 +1,12 @@
+-- Migration 016: Track indexed SKILL.md files for incremental `ms index`
+CREATE TABLE skill_source_files (
+    path TEXT PRIMARY KEY,
+    skill_id TEXT NOT NULL,
+    layer TEXT NOT NULL,
+    content_hash TEXT NOT NULL,
+    mtime_ms INTEGER NOT NULL,
+    size INTEGER NOT NULL,
+    indexed_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_skill_source_files_skill ON skill_source_files(skill_id);
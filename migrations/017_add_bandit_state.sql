@@ -0,0 +1,7 @@
+-- Migration 017: Persist learned suggestion bandit state across runs
+CREATE TABLE bandit_state (
+    name TEXT PRIMARY KEY,
+    state_json TEXT NOT NULL,
+    version INTEGER NOT NULL,
+    updated_at TEXT NOT NULL
+);
@@ -0,0 +1,9 @@
+-- Migration 018: Reviewable quarantine store
+-- Raw quarantined content and its source, kept so a reviewer can inspect
+-- injection spans and release false positives back into extraction.
+CREATE TABLE injection_quarantine_content (
+    quarantine_id TEXT PRIMARY KEY,
+    source TEXT NOT NULL,
+    content TEXT NOT NULL,
+    released_at TEXT
+);
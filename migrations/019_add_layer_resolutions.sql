@@ -0,0 +1,11 @@
+-- Migration 019: Remembered layer conflict resolutions
+-- One row per skill id and set of conflicting candidates (hashed from each
+-- candidate's layer and file content hash), so `ms index` re-applies a choice
+-- until one of the candidates changes.
+CREATE TABLE layer_resolutions (
+    skill_id TEXT NOT NULL,
+    candidates_hash TEXT NOT NULL,
+    strategy TEXT NOT NULL,
+    resolved_at TEXT NOT NULL,
+    PRIMARY KEY (skill_id, candidates_hash)
+);
@@ -0,0 +1,15 @@
+-- Migration 020: Share suggestion cooldowns across processes
+-- One row per skill and context fingerprint (16 hex digits; all zeros matches
+-- any context). `response` is `shown` or `dismissed`; expired rows are
+-- deleted when cooldowns are read.
+CREATE TABLE suggestion_cooldowns (
+    skill_id TEXT NOT NULL,
+    context_fingerprint TEXT NOT NULL,
+    response TEXT NOT NULL,
+    dismissals INTEGER NOT NULL DEFAULT 0,
+    started_at TEXT NOT NULL,
+    expires_at TEXT NOT NULL,
+    PRIMARY KEY (skill_id, context_fingerprint)
+);
+
+CREATE INDEX idx_suggestion_cooldowns_expires ON suggestion_cooldowns(expires_at);
@@ -0,0 +1,12 @@
+-- Migration 021: Denormalized skill tags for `ms list --tag`
+-- One row per skill and lowercased tag, kept in sync with `metadata_json`
+-- whenever a skill is upserted; `ms index` backfills skills indexed before
+-- this table existed.
+CREATE TABLE skill_tags (
+    skill_id TEXT NOT NULL,
+    tag TEXT NOT NULL,
+    PRIMARY KEY (skill_id, tag)
+);
+
+CREATE INDEX idx_skill_tags_tag ON skill_tags(tag);
+CREATE INDEX idx_skills_quality ON skills(quality_score);
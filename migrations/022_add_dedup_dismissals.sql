@@ -0,0 +1,10 @@
+-- Migration 022: Skill pairs reviewed and marked "not duplicates"
+-- `skill_a` sorts before `skill_b`, so each pair is stored once; `ms dedup`
+-- suppresses these pairs in later scans.
+CREATE TABLE dedup_dismissals (
+    skill_a TEXT NOT NULL,
+    skill_b TEXT NOT NULL,
+    reason TEXT,
+    dismissed_at TEXT NOT NULL,
+    PRIMARY KEY (skill_a, skill_b)
+);
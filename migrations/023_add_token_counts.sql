@@ -0,0 +1,9 @@
+-- Migration 023: Cached token counts
+-- Counts from `[packing].tokenizer`, keyed by tokenizer name and the SHA-256
+-- of the counted text, so unchanged blocks are not re-tokenized on every load.
+CREATE TABLE token_counts (
+    tokenizer TEXT NOT NULL,
+    content_hash TEXT NOT NULL,
+    tokens INTEGER NOT NULL,
+    PRIMARY KEY (tokenizer, content_hash)
+);
@@ -0,0 +1,8 @@
+-- Migration 024: Reviewable uncertainty queue
+-- `item_json` holds the full UncertaintyItem shown by `ms review`;
+-- `instance_key` identifies the generalized instance, so a rejected one is
+-- not queued again by later builds.
+ALTER TABLE uncertainty_queue ADD COLUMN item_json TEXT NOT NULL DEFAULT '{}';
+ALTER TABLE uncertainty_queue ADD COLUMN instance_key TEXT;
+ALTER TABLE uncertainty_queue ADD COLUMN updated_at TEXT;
+CREATE INDEX idx_uncertainty_instance ON uncertainty_queue(instance_key);
@@ -0,0 +1,12 @@
+-- Migration 025: Feedback mined from session outcomes
+-- One row per (session, skill) that `ms feedback mine` has scored, so mining
+-- the same session again does not feed the bandits twice.
+CREATE TABLE mined_feedback (
+    session_id TEXT NOT NULL,
+    skill_id TEXT NOT NULL,
+    verdict TEXT NOT NULL,
+    reward REAL NOT NULL,
+    signals_json TEXT NOT NULL,
+    mined_at TEXT NOT NULL,
+    PRIMARY KEY (session_id, skill_id)
+);
@@ -0,0 +1,16 @@
+-- Migration 026: Skill trials
+-- One row per `ms load --trial`. A trial stays open (outcome NULL) until it
+-- is reported with `ms trial report` or expired by `ms prune --expired-trials`.
+CREATE TABLE skill_trials (
+    id TEXT PRIMARY KEY,
+    skill_id TEXT NOT NULL,
+    context_fingerprint TEXT NOT NULL,
+    disclosure_level TEXT NOT NULL,
+    started_at TEXT NOT NULL,
+    outcome TEXT,
+    notes TEXT,
+    closed_at TEXT
+);
+
+CREATE INDEX idx_skill_trials_skill ON skill_trials(skill_id);
+CREATE INDEX idx_skill_trials_open ON skill_trials(closed_at, started_at);
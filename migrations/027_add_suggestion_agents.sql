@@ -0,0 +1,39 @@
+-- Migration 027: Agent identity on suggestions and cooldowns
+-- `agent_id` is the `--agent-id`/`MS_AGENT_ID` of the CLI or the MCP client
+-- name; '' (cooldowns) or NULL (records) when unknown. Cooldowns are keyed by
+-- agent so each agent keeps its own, while other agents honour them for
+-- `[suggestions] cross_agent_cooldown_secs`.
+CREATE TABLE suggestion_cooldowns_old (
+    skill_id TEXT NOT NULL,
+    context_fingerprint TEXT NOT NULL,
+    response TEXT NOT NULL,
+    dismissals INTEGER NOT NULL DEFAULT 0,
+    started_at TEXT NOT NULL,
+    expires_at TEXT NOT NULL
+);
+INSERT INTO suggestion_cooldowns_old
+SELECT skill_id, context_fingerprint, response, dismissals, started_at, expires_at
+FROM suggestion_cooldowns;
+DROP TABLE suggestion_cooldowns;
+
+CREATE TABLE suggestion_cooldowns (
+    skill_id TEXT NOT NULL,
+    context_fingerprint TEXT NOT NULL,
+    agent_id TEXT NOT NULL DEFAULT '',
+    response TEXT NOT NULL,
+    dismissals INTEGER NOT NULL DEFAULT 0,
+    started_at TEXT NOT NULL,
+    expires_at TEXT NOT NULL,
+    PRIMARY KEY (skill_id, context_fingerprint, agent_id)
+);
+
+INSERT INTO suggestion_cooldowns (
+    skill_id, context_fingerprint, response, dismissals, started_at, expires_at
+)
+SELECT skill_id, context_fingerprint, response, dismissals, started_at, expires_at
+FROM suggestion_cooldowns_old;
+DROP TABLE suggestion_cooldowns_old;
+
+CREATE INDEX idx_suggestion_cooldowns_expires ON suggestion_cooldowns(expires_at);
+
+ALTER TABLE suggestion_records ADD COLUMN agent_id TEXT;
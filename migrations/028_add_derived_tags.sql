@@ -0,0 +1,12 @@
+-- Migration 028: Tags derived by `ms index` (languages, frameworks)
+-- Kept apart from the author tags in `skill_tags`. `ms index` replaces a
+-- skill's rows whenever it re-indexes the skill, so runs never duplicate them.
+CREATE TABLE skill_derived_tags (
+    skill_id TEXT NOT NULL,
+    tag TEXT NOT NULL,
+    -- `language` or `framework`
+    kind TEXT NOT NULL,
+    PRIMARY KEY (skill_id, tag)
+);
+
+CREATE INDEX idx_skill_derived_tags_tag ON skill_derived_tags(tag);
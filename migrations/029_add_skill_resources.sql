@@ -0,0 +1,13 @@
+-- Migration 029: Resource files that travel with a skill
+-- Files under a skill's `resources/` folder and files its body links to,
+-- keyed by their path relative to the skill directory. `ms index` replaces a
+-- skill's rows whenever it re-indexes the skill.
+CREATE TABLE skill_resources (
+    skill_id TEXT NOT NULL,
+    -- Relative to the skill directory, `/`-separated
+    path TEXT NOT NULL,
+    size INTEGER NOT NULL,
+    -- Hex SHA-256 of the content
+    content_hash TEXT NOT NULL,
+    PRIMARY KEY (skill_id, path)
+);
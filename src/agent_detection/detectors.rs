@@ -159,7 +159,12 @@ impl AgentDetector for ClaudeCodeDetector {
     }
 
     fn get_integration_paths(&self) -> Vec<PathBuf> {
-        vec![PathBuf::from("SKILL.md"), PathBuf::from(".claude/SKILL.md")]
+        let mut paths = vec![PathBuf::from("SKILL.md"), PathBuf::from(".claude/SKILL.md")];
+        // Native skill directories: ~/.claude/skills/<name>/SKILL.md
+        if let Some(home) = self.home_dir() {
+            paths.push(home.join(".claude/skills"));
+        }
+        paths
     }
 }
 
@@ -0,0 +1,562 @@
+//! Agent hooks that suggest skills automatically.
+//!
+//! Claude Code runs `UserPromptSubmit` hooks before each prompt and adds
+//! their output to the model's context. [`ClaudeHook`] writes a small shell
+//! script that runs `ms suggest` for the current directory and registers it
+//! in `~/.claude/settings.json`, next to any hooks the user already has.
+//!
+//! The script must never get in the agent's way: `ms suggest` runs under a
+//! watchdog that kills it after [`SUGGEST_TIMEOUT_SECS`], Claude Code's own
+//! hook timeout backs that up, and any failure (missing binary, no ms root,
+//! bad output) prints nothing and exits 0.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::{Map as JsonMap, Value as JsonValue};
+use tracing::debug;
+
+use super::AgentType;
+use super::integration::{backup_path, parse_json, write_atomic};
+use crate::error::{MsError, Result};
+
+/// File name of the generated script, also how its settings entry is found.
+pub const HOOK_SCRIPT_NAME: &str = "ms-suggest.sh";
+
+/// Claude Code event the hook runs on.
+pub const HOOK_EVENT: &str = "UserPromptSubmit";
+
+/// Seconds `ms suggest` may run before the script kills it.
+pub const SUGGEST_TIMEOUT_SECS: u64 = 3;
+
+/// Claude Code's timeout for the whole hook, a backstop for the watchdog.
+pub const HOOK_TIMEOUT_SECS: u64 = 5;
+
+/// Suggestions the hook asks for.
+pub const HOOK_SUGGESTION_LIMIT: usize = 3;
+
+/// What `install` or `remove` did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookAction {
+    Installed,
+    AlreadyInstalled,
+    Removed,
+    NotInstalled,
+}
+
+/// Outcome of installing or removing a hook.
+#[derive(Debug, Clone, Serialize)]
+pub struct HookChange {
+    pub agent: AgentType,
+    pub action: HookAction,
+    pub settings: PathBuf,
+    pub script: PathBuf,
+    /// Previous settings file, when it was replaced
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backup: Option<PathBuf>,
+}
+
+/// State of an installed hook, as reported by `ms doctor`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "status", content = "detail")]
+pub enum HookStatus {
+    /// Neither the script nor a settings entry exists.
+    NotInstalled,
+    Installed,
+    /// The script exists but settings.json does not run it.
+    Unregistered,
+    /// settings.json runs the script but it is gone.
+    MissingScript,
+    NotExecutable,
+    /// The script runs a different ms binary (the path it runs).
+    WrongBinary(String),
+}
+
+impl HookStatus {
+    #[must_use]
+    pub const fn is_problem(&self) -> bool {
+        !matches!(self, Self::NotInstalled | Self::Installed)
+    }
+}
+
+/// The Claude Code `UserPromptSubmit` hook.
+#[derive(Debug, Clone)]
+pub struct ClaudeHook {
+    settings_path: PathBuf,
+    script_path: PathBuf,
+    ms_bin: PathBuf,
+}
+
+impl ClaudeHook {
+    /// Hook under `claude_dir` (usually `~/.claude`) that runs `ms_bin`.
+    #[must_use]
+    pub fn new(claude_dir: &Path, ms_bin: PathBuf) -> Self {
+        Self {
+            settings_path: claude_dir.join("settings.json"),
+            script_path: claude_dir.join("hooks").join(HOOK_SCRIPT_NAME),
+            ms_bin,
+        }
+    }
+
+    /// Hook in `~/.claude` that runs the running ms binary.
+    pub fn for_current_user() -> Result<Self> {
+        let home = dirs::home_dir()
+            .ok_or_else(|| MsError::Config("cannot determine home directory".to_string()))?;
+        let ms_bin = std::env::current_exe()?;
+        Ok(Self::new(&home.join(".claude"), ms_bin))
+    }
+
+    #[must_use]
+    pub fn settings_path(&self) -> &Path {
+        &self.settings_path
+    }
+
+    #[must_use]
+    pub fn script_path(&self) -> &Path {
+        &self.script_path
+    }
+
+    /// The hook script.
+    #[must_use]
+    pub fn script(&self) -> String {
+        let ms_bin = shell_quote(&self.ms_bin.display().to_string());
+        format!(
+            r#"#!/bin/sh
+# ms skill suggestions for Claude Code ({HOOK_EVENT} hook).
+# Generated by `ms init --install-hooks claude-code`; remove it with
+# `ms init --remove-hooks`. Fails open: on any error it prints nothing.
+MS_BIN={ms_bin}
+LIMIT_SECS={SUGGEST_TIMEOUT_SECS}
+
+cat >/dev/null 2>&1
+[ -x "$MS_BIN" ] || exit 0
+out=$(mktemp 2>/dev/null) || exit 0
+trap 'rm -f "$out"' EXIT
+
+"$MS_BIN" suggest --robot --limit {HOOK_SUGGESTION_LIMIT} --cwd "$PWD" >"$out" 2>/dev/null &
+pid=$!
+( sleep "$LIMIT_SECS"; kill -9 "$pid" ) >/dev/null 2>&1 &
+watchdog=$!
+if wait "$pid"; then
+    kill "$watchdog" 2>/dev/null
+    "$MS_BIN" __hook claude-code <"$out" 2>/dev/null
+else
+    kill "$watchdog" 2>/dev/null
+fi
+exit 0
+"#
+        )
+    }
+
+    /// Write the script and register it in settings.json. Other hooks and
+    /// settings are kept; an existing ms entry is updated in place.
+    pub fn install(&self) -> Result<HookChange> {
+        let script = self.script();
+        let script_current = fs::read_to_string(&self.script_path)
+            .is_ok_and(|existing| existing == script)
+            && is_executable(&self.script_path);
+        if !script_current {
+            let dir = parent_dir(&self.script_path)?;
+            fs::create_dir_all(dir)?;
+            write_atomic(dir, &self.script_path, &script)?;
+            set_executable(&self.script_path)?;
+            debug!(path = %self.script_path.display(), "wrote hook script");
+        }
+
+        let before = read_settings(&self.settings_path)?;
+        let mut doc = parse_json(before.as_deref().unwrap_or(""))
+            .map_err(|err| MsError::Config(format!("{}: {err}", self.settings_path.display())))?;
+        let settings_changed = merge_hook(&mut doc, &self.hook_entry())
+            .map_err(|err| MsError::Config(format!("{}: {err}", self.settings_path.display())))?;
+        let backup = if settings_changed {
+            self.write_settings(before.is_some(), &doc)?
+        } else {
+            None
+        };
+
+        let action = if script_current && !settings_changed {
+            HookAction::AlreadyInstalled
+        } else {
+            HookAction::Installed
+        };
+        Ok(self.change(action, backup))
+    }
+
+    /// Unregister the hook and delete the script. Other hooks are kept.
+    pub fn remove(&self) -> Result<HookChange> {
+        let mut backup = None;
+        let mut removed = false;
+        if let Some(before) = read_settings(&self.settings_path)? {
+            let mut doc = parse_json(&before).map_err(|err| {
+                MsError::Config(format!("{}: {err}", self.settings_path.display()))
+            })?;
+            if remove_hook(&mut doc) {
+                backup = self.write_settings(true, &doc)?;
+                removed = true;
+            }
+        }
+        match fs::remove_file(&self.script_path) {
+            Ok(()) => removed = true,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err.into()),
+        }
+
+        let action = if removed {
+            HookAction::Removed
+        } else {
+            HookAction::NotInstalled
+        };
+        Ok(self.change(action, backup))
+    }
+
+    /// Whether the hook is registered, executable and runs this ms binary.
+    #[must_use]
+    pub fn status(&self) -> HookStatus {
+        let registered = fs::read_to_string(&self.settings_path)
+            .ok()
+            .and_then(|raw| parse_json(&raw).ok())
+            .is_some_and(|doc| has_hook(&doc));
+        let script = fs::read_to_string(&self.script_path).ok();
+        match (registered, script) {
+            (false, None) => HookStatus::NotInstalled,
+            (false, Some(_)) => HookStatus::Unregistered,
+            (true, None) => HookStatus::MissingScript,
+            (true, Some(_)) if !is_executable(&self.script_path) => HookStatus::NotExecutable,
+            (true, Some(script)) => {
+                let expected = shell_quote(&self.ms_bin.display().to_string());
+                match script.lines().find_map(|line| line.strip_prefix("MS_BIN=")) {
+                    Some(bin) if bin == expected => HookStatus::Installed,
+                    bin => HookStatus::WrongBinary(bin.unwrap_or_default().to_string()),
+                }
+            }
+        }
+    }
+
+    fn hook_entry(&self) -> JsonValue {
+        serde_json::json!({
+            "type": "command",
+            "command": self.script_path.display().to_string(),
+            "timeout": HOOK_TIMEOUT_SECS,
+        })
+    }
+
+    fn write_settings(&self, existed: bool, doc: &JsonValue) -> Result<Option<PathBuf>> {
+        let dir = parent_dir(&self.settings_path)?;
+        fs::create_dir_all(dir)?;
+        let backup = if existed {
+            let backup = backup_path(&self.settings_path);
+            fs::copy(&self.settings_path, &backup)?;
+            Some(backup)
+        } else {
+            None
+        };
+        write_atomic(
+            dir,
+            &self.settings_path,
+            &(serde_json::to_string_pretty(doc)? + "\n"),
+        )?;
+        debug!(path = %self.settings_path.display(), "wrote Claude Code hooks");
+        Ok(backup)
+    }
+
+    fn change(&self, action: HookAction, backup: Option<PathBuf>) -> HookChange {
+        HookChange {
+            agent: AgentType::ClaudeCode,
+            action,
+            settings: self.settings_path.clone(),
+            script: self.script_path.clone(),
+            backup,
+        }
+    }
+}
+
+/// Context block for Claude Code from `ms suggest --robot` output, or
+/// `None` when there is nothing to suggest.
+#[must_use]
+pub fn claude_context(suggest_output: &JsonValue) -> Option<String> {
+    let lines: Vec<String> = ["suggestions", "discovery_suggestions"]
+        .iter()
+        .filter_map(|key| suggest_output.get(key)?.as_array())
+        .flatten()
+        .filter_map(|item| {
+            let skill_id = item.get("skill_id")?.as_str()?;
+            let description = item
+                .get("description")
+                .and_then(JsonValue::as_str)
+                .unwrap_or_default();
+            let mut line = format!("- {skill_id}");
+            if !description.is_empty() {
+                line.push_str(&format!(": {description}"));
+            }
+            if let Some(reason) = item.get("reason").and_then(JsonValue::as_str) {
+                line.push_str(&format!(" ({reason})"));
+            }
+            Some(line)
+        })
+        .collect();
+    if lines.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "ms skills that may help with this project:\n{}\nLoad one with `ms load <skill-id>` before starting if it applies.",
+        lines.join("\n")
+    ))
+}
+
+/// Output a `UserPromptSubmit` hook prints to add `context`.
+#[must_use]
+pub fn claude_hook_output(context: &str) -> JsonValue {
+    serde_json::json!({
+        "hookSpecificOutput": {
+            "hookEventName": HOOK_EVENT,
+            "additionalContext": context,
+        }
+    })
+}
+
+/// Whether a hook handler runs the ms script.
+fn is_ms_handler(handler: &JsonValue) -> bool {
+    handler
+        .get("command")
+        .and_then(JsonValue::as_str)
+        .is_some_and(|command| {
+            Path::new(command)
+                .file_name()
+                .is_some_and(|name| name == HOOK_SCRIPT_NAME)
+        })
+}
+
+/// Matcher groups registered for [`HOOK_EVENT`].
+fn event_groups(doc: &JsonValue) -> impl Iterator<Item = &JsonValue> {
+    doc.get("hooks")
+        .and_then(|hooks| hooks.get(HOOK_EVENT))
+        .and_then(JsonValue::as_array)
+        .into_iter()
+        .flatten()
+}
+
+fn has_hook(doc: &JsonValue) -> bool {
+    event_groups(doc)
+        .filter_map(|group| group.get("hooks")?.as_array())
+        .flatten()
+        .any(is_ms_handler)
+}
+
+/// Put `entry` into the settings' hooks, replacing an existing ms handler
+/// or adding a new matcher group. Returns whether anything changed.
+fn merge_hook(doc: &mut JsonValue, entry: &JsonValue) -> Result<bool> {
+    let before = doc.clone();
+    let root = doc
+        .as_object_mut()
+        .ok_or_else(|| MsError::Config("settings are not a JSON object".to_string()))?;
+    let groups = root
+        .entry("hooks")
+        .or_insert_with(|| JsonValue::Object(JsonMap::new()))
+        .as_object_mut()
+        .ok_or_else(|| MsError::Config("hooks is not an object".to_string()))?
+        .entry(HOOK_EVENT)
+        .or_insert_with(|| JsonValue::Array(Vec::new()))
+        .as_array_mut()
+        .ok_or_else(|| MsError::Config(format!("hooks.{HOOK_EVENT} is not a list")))?;
+
+    let existing = groups
+        .iter_mut()
+        .filter_map(|group| group.get_mut("hooks")?.as_array_mut())
+        .flatten()
+        .find(|handler| is_ms_handler(handler));
+    match existing {
+        Some(handler) => *handler = entry.clone(),
+        None => groups.push(serde_json::json!({ "hooks": [entry] })),
+    }
+    Ok(*doc != before)
+}
+
+/// Drop the ms handler, and any group, event or `hooks` object it leaves
+/// empty. Returns whether anything was removed.
+fn remove_hook(doc: &mut JsonValue) -> bool {
+    let Some(hooks) = doc.get_mut("hooks").and_then(JsonValue::as_object_mut) else {
+        return false;
+    };
+    let Some(groups) = hooks.get_mut(HOOK_EVENT).and_then(JsonValue::as_array_mut) else {
+        return false;
+    };
+    let mut removed = false;
+    groups.retain_mut(|group| {
+        let Some(handlers) = group.get_mut("hooks").and_then(JsonValue::as_array_mut) else {
+            return true;
+        };
+        let count = handlers.len();
+        handlers.retain(|handler| !is_ms_handler(handler));
+        if handlers.len() == count {
+            return true;
+        }
+        removed = true;
+        !handlers.is_empty()
+    });
+    if groups.is_empty() {
+        hooks.remove(HOOK_EVENT);
+    }
+    if hooks.is_empty()
+        && let Some(root) = doc.as_object_mut()
+    {
+        root.remove("hooks");
+    }
+    removed
+}
+
+fn read_settings(path: &Path) -> Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(raw) => Ok(Some(raw)),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(MsError::Config(format!("read {}: {err}", path.display()))),
+    }
+}
+
+fn parent_dir(path: &Path) -> Result<&Path> {
+    path.parent()
+        .ok_or_else(|| MsError::Config(format!("invalid path {}", path.display())))
+}
+
+/// Single-quote `value` for sh.
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+#[cfg(unix)]
+fn set_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn set_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path).is_ok_and(|meta| meta.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hook_in(dir: &Path) -> ClaudeHook {
+        ClaudeHook::new(dir, PathBuf::from("/opt/ms/bin/ms"))
+    }
+
+    #[test]
+    fn install_merges_with_existing_hooks_and_remove_restores_them() {
+        let dir = tempfile::tempdir().unwrap();
+        let hook = hook_in(dir.path());
+        let original = serde_json::json!({
+            "model": "opus",
+            "hooks": {
+                "UserPromptSubmit": [
+                    { "hooks": [{ "type": "command", "command": "/usr/bin/audit-prompt" }] }
+                ],
+                "PreToolUse": [
+                    { "matcher": "Bash", "hooks": [{ "type": "command", "command": "guard" }] }
+                ]
+            }
+        });
+        fs::write(hook.settings_path(), original.to_string()).unwrap();
+
+        let change = hook.install().unwrap();
+        assert_eq!(change.action, HookAction::Installed);
+        assert!(change.backup.is_some());
+        assert!(is_executable(hook.script_path()));
+        assert_eq!(hook.status(), HookStatus::Installed);
+
+        let doc = parse_json(&fs::read_to_string(hook.settings_path()).unwrap()).unwrap();
+        let groups = doc["hooks"][HOOK_EVENT].as_array().unwrap();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[1]["hooks"][0]["timeout"], HOOK_TIMEOUT_SECS);
+        assert_eq!(doc["hooks"]["PreToolUse"], original["hooks"]["PreToolUse"]);
+        assert_eq!(doc["model"], "opus");
+
+        assert_eq!(hook.install().unwrap().action, HookAction::AlreadyInstalled);
+
+        assert_eq!(hook.remove().unwrap().action, HookAction::Removed);
+        assert!(!hook.script_path().exists());
+        let doc = parse_json(&fs::read_to_string(hook.settings_path()).unwrap()).unwrap();
+        assert_eq!(doc, original);
+        assert_eq!(hook.status(), HookStatus::NotInstalled);
+        assert_eq!(hook.remove().unwrap().action, HookAction::NotInstalled);
+    }
+
+    #[test]
+    fn remove_drops_the_hooks_object_it_created() {
+        let dir = tempfile::tempdir().unwrap();
+        let hook = hook_in(dir.path());
+        hook.install().unwrap();
+        hook.remove().unwrap();
+        let doc = parse_json(&fs::read_to_string(hook.settings_path()).unwrap()).unwrap();
+        assert_eq!(doc, serde_json::json!({}));
+    }
+
+    #[test]
+    fn status_flags_a_hook_for_another_binary() {
+        let dir = tempfile::tempdir().unwrap();
+        hook_in(dir.path()).install().unwrap();
+        let moved = ClaudeHook::new(dir.path(), PathBuf::from("/usr/local/bin/ms"));
+        assert_eq!(
+            moved.status(),
+            HookStatus::WrongBinary("'/opt/ms/bin/ms'".to_string())
+        );
+        moved.install().unwrap();
+        assert_eq!(moved.status(), HookStatus::Installed);
+
+        fs::remove_file(moved.script_path()).unwrap();
+        assert_eq!(moved.status(), HookStatus::MissingScript);
+    }
+
+    #[test]
+    fn refuses_to_clobber_invalid_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        let hook = hook_in(dir.path());
+        fs::write(hook.settings_path(), "{ not json").unwrap();
+        assert!(hook.install().is_err());
+        assert_eq!(
+            fs::read_to_string(hook.settings_path()).unwrap(),
+            "{ not json"
+        );
+    }
+
+    #[test]
+    fn script_quotes_the_binary_and_fails_open() {
+        let hook = ClaudeHook::new(Path::new("/h/.claude"), PathBuf::from("/it's/ms"));
+        let script = hook.script();
+        assert!(script.contains(r"MS_BIN='/it'\''s/ms'"));
+        assert!(script.contains(r#"suggest --robot --limit 3 --cwd "$PWD""#));
+        assert!(script.trim_end().ends_with("exit 0"));
+    }
+
+    #[test]
+    fn context_lists_suggestions_or_nothing() {
+        let output = serde_json::json!({
+            "status": "ok",
+            "suggestions": [
+                { "skill_id": "rust-errors", "description": "Error handling", "reason": "Favorite" }
+            ],
+            "discovery_suggestions": [{ "skill_id": "tokio-pitfalls", "description": "" }]
+        });
+        let context = claude_context(&output).unwrap();
+        assert!(context.contains("- rust-errors: Error handling (Favorite)\n- tokio-pitfalls\n"));
+        assert_eq!(
+            claude_hook_output(&context)["hookSpecificOutput"]["hookEventName"],
+            HOOK_EVENT
+        );
+        assert!(claude_context(&serde_json::json!({ "suggestions": [] })).is_none());
+    }
+}
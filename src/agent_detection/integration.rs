@@ -0,0 +1,614 @@
+//! Integration writer that registers the ms MCP server with detected agents.
+//!
+//! Each supported agent keeps its MCP servers in a JSON or TOML config. The
+//! writer parses that config, merges an `ms` server entry into it, and writes
+//! it back, so unrelated keys (including other MCP servers) survive. The
+//! previous file is kept next to it as `<name>.bak`.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::{Map as JsonMap, Value as JsonValue};
+use tracing::debug;
+
+use super::{AgentType, DetectedAgent, IntegrationStatus};
+use crate::error::{MsError, Result};
+
+/// Name the ms server is registered under in agent configs.
+pub const MCP_SERVER_NAME: &str = "ms";
+
+/// Command an agent runs to start the ms MCP server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct McpServerEntry {
+    pub command: String,
+    pub args: Vec<String>,
+}
+
+impl Default for McpServerEntry {
+    fn default() -> Self {
+        Self {
+            command: "ms".to_string(),
+            args: vec!["mcp".to_string(), "serve".to_string()],
+        }
+    }
+}
+
+/// Where and how an agent's MCP servers are declared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum McpConfigShape {
+    /// JSON `{"mcpServers": {"<name>": {"command", "args"}}}`
+    JsonServers,
+    /// Continue's JSON `experimental.modelContextProtocolServers` list
+    ContinueServers,
+    /// TOML `[mcp_servers.<name>]` table (Codex)
+    TomlServers,
+}
+
+/// Config file the writer edits for one agent.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct IntegrationTarget {
+    pub path: PathBuf,
+    pub shape: McpConfigShape,
+}
+
+/// A pending (or applied) change to one agent config.
+#[derive(Debug, Clone)]
+pub struct IntegrationPlan {
+    pub agent: AgentType,
+    pub path: PathBuf,
+    /// File contents before the change (`None` if the file does not exist)
+    pub before: Option<String>,
+    pub after: String,
+    /// False when the config already has the expected entry
+    pub changed: bool,
+}
+
+impl IntegrationPlan {
+    /// Line diff between the current and the merged config.
+    #[must_use]
+    pub fn diff(&self) -> String {
+        let path = self.path.display();
+        let mut out = format!("--- {path}\n+++ {path}\n");
+        out.push_str(&line_diff(
+            self.before.as_deref().unwrap_or(""),
+            &self.after,
+        ));
+        out
+    }
+}
+
+/// What happened to one agent during `configure_agents`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrationAction {
+    Configured,
+    WouldConfigure,
+    AlreadyConfigured,
+    Unsupported,
+    Failed(String),
+}
+
+/// Per-agent outcome of `configure_agents`.
+#[derive(Debug, Clone, Serialize)]
+pub struct IntegrationOutcome {
+    pub agent: AgentType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    pub action: IntegrationAction,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backup: Option<PathBuf>,
+    /// Diff of the change (dry runs only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<String>,
+}
+
+/// Writes the ms MCP server entry into agent configs.
+#[derive(Debug, Clone, Default)]
+pub struct IntegrationWriter {
+    entry: McpServerEntry,
+}
+
+impl IntegrationWriter {
+    /// Create a writer that registers `ms mcp serve`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a different server command (e.g. an absolute `ms` path).
+    #[must_use]
+    pub fn with_entry(mut self, entry: McpServerEntry) -> Self {
+        self.entry = entry;
+        self
+    }
+
+    /// Config file to edit for `agent`, or `None` if the agent has no MCP
+    /// config the writer knows how to edit.
+    #[must_use]
+    pub fn target(&self, agent: &DetectedAgent) -> Option<IntegrationTarget> {
+        let (default_dir, file, shape) = match agent.agent_type {
+            AgentType::ClaudeCode => (".claude", "settings.json", McpConfigShape::JsonServers),
+            AgentType::Cursor => (".cursor", "mcp.json", McpConfigShape::JsonServers),
+            AgentType::Windsurf => (".windsurf", "mcp_config.json", McpConfigShape::JsonServers),
+            AgentType::GeminiCli => (".gemini", "settings.json", McpConfigShape::JsonServers),
+            AgentType::Continue => (".continue", "config.json", McpConfigShape::ContinueServers),
+            AgentType::Codex => (".codex", "config.toml", McpConfigShape::TomlServers),
+            AgentType::Cline | AgentType::OpenCode | AgentType::Aider => return None,
+        };
+        let dir = agent
+            .config_path
+            .as_deref()
+            .and_then(Path::parent)
+            .map(Path::to_path_buf)
+            .or_else(|| dirs::home_dir().map(|home| home.join(default_dir)))?;
+        Some(IntegrationTarget {
+            path: dir.join(file),
+            shape,
+        })
+    }
+
+    /// Integration status as recorded in the agent's MCP config.
+    #[must_use]
+    pub fn status(&self, agent: &DetectedAgent) -> IntegrationStatus {
+        let Some(target) = self.target(agent) else {
+            return agent.integration_status;
+        };
+        let Ok(raw) = fs::read_to_string(&target.path) else {
+            return IntegrationStatus::NotConfigured;
+        };
+        match self.merge(&raw, target.shape) {
+            Ok((_, false)) => IntegrationStatus::FullyConfigured,
+            Ok((_, true)) if has_server(&raw, target.shape) => IntegrationStatus::Outdated,
+            _ => IntegrationStatus::NotConfigured,
+        }
+    }
+
+    /// Compute the change that registers ms with `agent`.
+    pub fn plan(&self, agent: &DetectedAgent) -> Result<IntegrationPlan> {
+        let target = self.target(agent).ok_or_else(|| {
+            MsError::Config(format!(
+                "no MCP config writer for {}",
+                agent.agent_type.display_name()
+            ))
+        })?;
+        let before = match fs::read_to_string(&target.path) {
+            Ok(raw) => Some(raw),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+            Err(err) => {
+                return Err(MsError::Config(format!(
+                    "read {}: {err}",
+                    target.path.display()
+                )));
+            }
+        };
+        let (after, changed) = self
+            .merge(before.as_deref().unwrap_or(""), target.shape)
+            .map_err(|err| MsError::Config(format!("{}: {err}", target.path.display())))?;
+        Ok(IntegrationPlan {
+            agent: agent.agent_type,
+            path: target.path,
+            before,
+            after,
+            changed,
+        })
+    }
+
+    /// Write a plan to disk. Returns the backup path if a file was replaced.
+    pub fn apply(&self, plan: &IntegrationPlan) -> Result<Option<PathBuf>> {
+        if !plan.changed {
+            return Ok(None);
+        }
+        let dir = plan
+            .path
+            .parent()
+            .ok_or_else(|| MsError::Config(format!("invalid path {}", plan.path.display())))?;
+        fs::create_dir_all(dir)?;
+
+        let backup = if plan.before.is_some() {
+            let backup = backup_path(&plan.path);
+            fs::copy(&plan.path, &backup)?;
+            Some(backup)
+        } else {
+            None
+        };
+
+        write_atomic(dir, &plan.path, &plan.after)?;
+        debug!(agent = ?plan.agent, path = %plan.path.display(), "wrote MCP config");
+        Ok(backup)
+    }
+
+    /// Plan and (unless `dry_run`) apply the change for one agent, updating
+    /// its integration status.
+    pub fn configure(&self, agent: &mut DetectedAgent, dry_run: bool) -> Result<IntegrationPlan> {
+        let plan = self.plan(agent)?;
+        if !dry_run {
+            self.apply(&plan)?;
+            agent.integration_status = IntegrationStatus::FullyConfigured;
+        }
+        Ok(plan)
+    }
+
+    /// Merge the ms entry into `raw`; returns the new text and whether it
+    /// differs semantically from the input.
+    fn merge(&self, raw: &str, shape: McpConfigShape) -> Result<(String, bool)> {
+        match shape {
+            McpConfigShape::JsonServers | McpConfigShape::ContinueServers => {
+                let mut doc = parse_json(raw)?;
+                let before = doc.clone();
+                let root = doc
+                    .as_object_mut()
+                    .ok_or_else(|| MsError::Config("config is not a JSON object".to_string()))?;
+                if shape == McpConfigShape::JsonServers {
+                    self.merge_json_servers(root)?;
+                } else {
+                    self.merge_continue_servers(root)?;
+                }
+                let changed = doc != before;
+                Ok((serde_json::to_string_pretty(&doc)? + "\n", changed))
+            }
+            McpConfigShape::TomlServers => {
+                let mut doc: toml::Value = if raw.trim().is_empty() {
+                    toml::Value::Table(toml::map::Map::new())
+                } else {
+                    toml::from_str(raw).map_err(|err| MsError::Config(format!("parse: {err}")))?
+                };
+                let before = doc.clone();
+                self.merge_toml_servers(&mut doc)?;
+                let changed = doc != before;
+                let rendered = toml::to_string_pretty(&doc)
+                    .map_err(|err| MsError::Config(format!("render: {err}")))?;
+                Ok((rendered, changed))
+            }
+        }
+    }
+
+    fn merge_json_servers(&self, root: &mut JsonMap<String, JsonValue>) -> Result<()> {
+        let servers = root
+            .entry("mcpServers")
+            .or_insert_with(|| JsonValue::Object(JsonMap::new()))
+            .as_object_mut()
+            .ok_or_else(|| MsError::Config("mcpServers is not an object".to_string()))?;
+        let server = servers
+            .entry(MCP_SERVER_NAME)
+            .or_insert_with(|| JsonValue::Object(JsonMap::new()));
+        // Keep any extra settings (env, cwd) the user added to the entry
+        let server = server
+            .as_object_mut()
+            .ok_or_else(|| MsError::Config("mcpServers.ms is not an object".to_string()))?;
+        server.insert("command".to_string(), self.entry.command.clone().into());
+        server.insert("args".to_string(), self.entry.args.clone().into());
+        Ok(())
+    }
+
+    fn merge_continue_servers(&self, root: &mut JsonMap<String, JsonValue>) -> Result<()> {
+        let servers = root
+            .entry("experimental")
+            .or_insert_with(|| JsonValue::Object(JsonMap::new()))
+            .as_object_mut()
+            .ok_or_else(|| MsError::Config("experimental is not an object".to_string()))?
+            .entry("modelContextProtocolServers")
+            .or_insert_with(|| JsonValue::Array(Vec::new()))
+            .as_array_mut()
+            .ok_or_else(|| {
+                MsError::Config("modelContextProtocolServers is not an array".to_string())
+            })?;
+        let transport = serde_json::json!({
+            "type": "stdio",
+            "command": self.entry.command,
+            "args": self.entry.args,
+        });
+        match servers
+            .iter_mut()
+            .find(|server| is_continue_ms_server(server))
+        {
+            Some(existing) => {
+                if let Some(object) = existing.as_object_mut() {
+                    object.insert("transport".to_string(), transport);
+                }
+            }
+            None => servers.push(serde_json::json!({ "transport": transport })),
+        }
+        Ok(())
+    }
+
+    fn merge_toml_servers(&self, doc: &mut toml::Value) -> Result<()> {
+        let root = doc
+            .as_table_mut()
+            .ok_or_else(|| MsError::Config("config is not a TOML table".to_string()))?;
+        let servers = root
+            .entry("mcp_servers")
+            .or_insert_with(|| toml::Value::Table(toml::map::Map::new()))
+            .as_table_mut()
+            .ok_or_else(|| MsError::Config("mcp_servers is not a table".to_string()))?;
+        let server = servers
+            .entry(MCP_SERVER_NAME)
+            .or_insert_with(|| toml::Value::Table(toml::map::Map::new()))
+            .as_table_mut()
+            .ok_or_else(|| MsError::Config("mcp_servers.ms is not a table".to_string()))?;
+        server.insert(
+            "command".to_string(),
+            toml::Value::String(self.entry.command.clone()),
+        );
+        server.insert(
+            "args".to_string(),
+            toml::Value::Array(
+                self.entry
+                    .args
+                    .iter()
+                    .cloned()
+                    .map(toml::Value::String)
+                    .collect(),
+            ),
+        );
+        Ok(())
+    }
+}
+
+/// Register ms with every agent that needs it (or every agent, with `force`).
+pub fn configure_agents(
+    writer: &IntegrationWriter,
+    agents: &mut [DetectedAgent],
+    dry_run: bool,
+    force: bool,
+) -> Vec<IntegrationOutcome> {
+    agents
+        .iter_mut()
+        .map(|agent| {
+            let path = writer.target(agent).map(|target| target.path);
+            let mut outcome = IntegrationOutcome {
+                agent: agent.agent_type,
+                path,
+                action: IntegrationAction::Unsupported,
+                backup: None,
+                diff: None,
+            };
+            if outcome.path.is_none() {
+                return outcome;
+            }
+            agent.integration_status = writer.status(agent);
+            if !agent.integration_status.needs_configuration() && !force {
+                outcome.action = IntegrationAction::AlreadyConfigured;
+                return outcome;
+            }
+            let planned = writer.plan(agent).and_then(|plan| {
+                if dry_run || !plan.changed {
+                    return Ok((plan, None));
+                }
+                let backup = writer.apply(&plan)?;
+                Ok((plan, backup))
+            });
+            match planned {
+                Ok((plan, _)) if !plan.changed => {
+                    agent.integration_status = IntegrationStatus::FullyConfigured;
+                    outcome.action = IntegrationAction::AlreadyConfigured;
+                }
+                Ok((plan, _)) if dry_run => {
+                    outcome.action = IntegrationAction::WouldConfigure;
+                    outcome.diff = Some(plan.diff());
+                }
+                Ok((_, backup)) => {
+                    agent.integration_status = IntegrationStatus::FullyConfigured;
+                    outcome.action = IntegrationAction::Configured;
+                    outcome.backup = backup;
+                }
+                Err(err) => outcome.action = IntegrationAction::Failed(err.to_string()),
+            }
+            outcome
+        })
+        .collect()
+}
+
+pub(super) fn parse_json(raw: &str) -> Result<JsonValue> {
+    if raw.trim().is_empty() {
+        return Ok(JsonValue::Object(JsonMap::new()));
+    }
+    serde_json::from_str(raw).map_err(|err| MsError::Config(format!("parse: {err}")))
+}
+
+fn has_server(raw: &str, shape: McpConfigShape) -> bool {
+    match shape {
+        McpConfigShape::JsonServers => parse_json(raw)
+            .ok()
+            .and_then(|doc| doc.get("mcpServers")?.get(MCP_SERVER_NAME).cloned())
+            .is_some(),
+        McpConfigShape::ContinueServers => parse_json(raw)
+            .ok()
+            .and_then(|doc| {
+                doc.get("experimental")?
+                    .get("modelContextProtocolServers")?
+                    .as_array()
+                    .map(|servers| servers.iter().any(is_continue_ms_server))
+            })
+            .unwrap_or(false),
+        McpConfigShape::TomlServers => toml::from_str::<toml::Value>(raw)
+            .ok()
+            .and_then(|doc| doc.get("mcp_servers")?.get(MCP_SERVER_NAME).cloned())
+            .is_some(),
+    }
+}
+
+fn is_continue_ms_server(server: &JsonValue) -> bool {
+    server
+        .get("transport")
+        .and_then(|transport| transport.get("command"))
+        .and_then(JsonValue::as_str)
+        .is_some_and(|command| {
+            Path::new(command)
+                .file_name()
+                .is_some_and(|name| name == MCP_SERVER_NAME)
+        })
+}
+
+pub(super) fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".bak");
+    path.with_file_name(name)
+}
+
+/// Replace `path` (in `dir`) with `contents` via a temp file and rename.
+pub(super) fn write_atomic(dir: &Path, path: &Path, contents: &str) -> Result<()> {
+    let mut staged = tempfile::NamedTempFile::new_in(dir)?;
+    staged.write_all(contents.as_bytes())?;
+    staged.persist(path).map_err(|err| MsError::Io(err.error))?;
+    Ok(())
+}
+
+/// Minimal line diff (longest common subsequence) showing changed lines only.
+pub(crate) fn line_diff(old: &str, new: &str) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut out = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            i += 1;
+            j += 1;
+        } else if j < new.len() && (i == old.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            out.push_str(&format!("+{}\n", new[j]));
+            j += 1;
+        } else {
+            out.push_str(&format!("-{}\n", old[i]));
+            i += 1;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent_detection::DetectionMethod;
+
+    fn agent_in(agent_type: AgentType, dir: &Path, file: &str) -> DetectedAgent {
+        DetectedAgent::new(agent_type, DetectionMethod::ConfigFile).with_config_path(dir.join(file))
+    }
+
+    #[test]
+    fn merges_into_existing_mcp_servers() {
+        let dir = tempfile::tempdir().unwrap();
+        let settings = dir.path().join("settings.json");
+        fs::write(
+            &settings,
+            r#"{"theme":"dark","mcpServers":{"other":{"command":"other-mcp","args":[]}}}"#,
+        )
+        .unwrap();
+        let mut agent = agent_in(AgentType::ClaudeCode, dir.path(), "config.json");
+        let writer = IntegrationWriter::new();
+        assert_eq!(writer.status(&agent), IntegrationStatus::NotConfigured);
+
+        let outcomes = configure_agents(&writer, std::slice::from_mut(&mut agent), false, false);
+        assert_eq!(outcomes[0].action, IntegrationAction::Configured);
+        assert_eq!(agent.integration_status, IntegrationStatus::FullyConfigured);
+
+        let doc: JsonValue = serde_json::from_str(&fs::read_to_string(&settings).unwrap()).unwrap();
+        assert_eq!(doc["theme"], "dark");
+        assert_eq!(doc["mcpServers"]["other"]["command"], "other-mcp");
+        assert_eq!(doc["mcpServers"]["ms"]["command"], "ms");
+        assert_eq!(
+            doc["mcpServers"]["ms"]["args"],
+            serde_json::json!(["mcp", "serve"])
+        );
+
+        let backup = outcomes[0].backup.clone().unwrap();
+        assert_eq!(backup, dir.path().join("settings.json.bak"));
+        assert!(fs::read_to_string(backup).unwrap().contains("other-mcp"));
+
+        // Second run is a no-op
+        let again = configure_agents(&writer, std::slice::from_mut(&mut agent), false, true);
+        assert_eq!(again[0].action, IntegrationAction::AlreadyConfigured);
+    }
+
+    #[test]
+    fn dry_run_reports_diff_without_writing() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("mcp.json");
+        fs::write(&config, "{\n  \"mcpServers\": {}\n}\n").unwrap();
+        let mut agent = agent_in(AgentType::Cursor, dir.path(), "settings.json");
+
+        let outcomes = configure_agents(
+            &IntegrationWriter::new(),
+            std::slice::from_mut(&mut agent),
+            true,
+            false,
+        );
+        assert_eq!(outcomes[0].action, IntegrationAction::WouldConfigure);
+        let diff = outcomes[0].diff.as_deref().unwrap();
+        assert!(diff.contains("+    \"ms\": {"));
+        assert!(diff.contains("-  \"mcpServers\": {}"));
+        assert_eq!(
+            fs::read_to_string(&config).unwrap(),
+            "{\n  \"mcpServers\": {}\n}\n"
+        );
+        assert!(!dir.path().join("mcp.json.bak").exists());
+        assert_eq!(agent.integration_status, IntegrationStatus::NotConfigured);
+    }
+
+    #[test]
+    fn merges_codex_toml_and_continue_lists() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("config.toml"),
+            "model = \"o3\"\n\n[mcp_servers.other]\ncommand = \"other-mcp\"\n",
+        )
+        .unwrap();
+        let codex = agent_in(AgentType::Codex, dir.path(), "config.json");
+        let writer = IntegrationWriter::new();
+        writer.configure(&mut codex.clone(), false).unwrap();
+        let doc: toml::Value =
+            toml::from_str(&fs::read_to_string(dir.path().join("config.toml")).unwrap()).unwrap();
+        assert_eq!(doc["model"].as_str(), Some("o3"));
+        assert_eq!(
+            doc["mcp_servers"]["other"]["command"].as_str(),
+            Some("other-mcp")
+        );
+        assert_eq!(doc["mcp_servers"]["ms"]["command"].as_str(), Some("ms"));
+
+        let cont = tempfile::tempdir().unwrap();
+        fs::write(
+            cont.path().join("config.json"),
+            r#"{"models":[],"experimental":{"modelContextProtocolServers":[{"transport":{"type":"stdio","command":"/usr/local/bin/ms","args":["old"]}}]}}"#,
+        )
+        .unwrap();
+        let agent = agent_in(AgentType::Continue, cont.path(), "config.json");
+        assert_eq!(writer.status(&agent), IntegrationStatus::Outdated);
+        writer.configure(&mut agent.clone(), false).unwrap();
+        let doc: JsonValue =
+            serde_json::from_str(&fs::read_to_string(cont.path().join("config.json")).unwrap())
+                .unwrap();
+        let servers = doc["experimental"]["modelContextProtocolServers"]
+            .as_array()
+            .unwrap();
+        assert_eq!(servers.len(), 1);
+        assert_eq!(
+            servers[0]["transport"]["args"],
+            serde_json::json!(["mcp", "serve"])
+        );
+        assert_eq!(writer.status(&agent), IntegrationStatus::FullyConfigured);
+    }
+
+    #[test]
+    fn refuses_to_clobber_invalid_config_and_skips_unsupported_agents() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("settings.json"), "{ not json").unwrap();
+        let agent = agent_in(AgentType::GeminiCli, dir.path(), "config.json");
+        assert!(IntegrationWriter::new().plan(&agent).is_err());
+
+        let mut agents = vec![agent_in(AgentType::Aider, dir.path(), ".aider.conf.yml")];
+        let outcomes = configure_agents(&IntegrationWriter::new(), &mut agents, false, false);
+        assert_eq!(outcomes[0].action, IntegrationAction::Unsupported);
+    }
+}
@@ -1,7 +1,10 @@
 //! Agent detection module for identifying installed AI coding agents.
 //!
 //! This module provides functionality to detect which AI coding agents are installed
-//! on the user's system, enabling automatic configuration and integration.
+//! on the user's system, [`IntegrationWriter`] registers the ms MCP server in
+//! their configs, [`ClaudeHook`] installs a prompt hook that suggests skills,
+//! and [`SkillExportTarget`] writes skills into their native skill
+//! directories.
 //!
 //! # Supported Agents
 //!
@@ -29,7 +32,10 @@
 //! ```
 
 mod detectors;
+mod hooks;
+mod integration;
 mod service;
+mod skill_export;
 
 use std::path::PathBuf;
 
@@ -39,7 +45,20 @@ pub use detectors::{
     AiderDetector, ClaudeCodeDetector, ClineDetector, CodexDetector, ContinueDetector,
     CursorDetector, GeminiCliDetector, OpenCodeDetector, WindsurfDetector,
 };
+pub use hooks::{
+    ClaudeHook, HOOK_EVENT, HOOK_SCRIPT_NAME, HookAction, HookChange, HookStatus, claude_context,
+    claude_hook_output,
+};
+pub(crate) use integration::line_diff;
+pub use integration::{
+    IntegrationAction, IntegrationOutcome, IntegrationPlan, IntegrationTarget, IntegrationWriter,
+    MCP_SERVER_NAME, McpConfigShape, McpServerEntry, configure_agents,
+};
 pub use service::{AgentDetectionService, DetectionSummary};
+pub use skill_export::{
+    ClaudeCodeExport, CursorExport, EXPORT_MANIFEST, ExportManifest, ExportOutcome, ExportSkill,
+    ExportedSkill, SkillExportTarget, export_skill, export_target,
+};
 
 /// Supported AI coding agents.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
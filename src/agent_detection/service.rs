@@ -150,6 +150,26 @@ impl AgentDetectionService {
         self.detect_by_type(agent_type).is_some()
     }
 
+    /// Find the model configured in an agent's config file, if any.
+    ///
+    /// Only config files are read (no binaries are spawned), so this is cheap
+    /// enough to call on every load. Agents are checked in detector order and
+    /// the first JSON config with a string `model` key wins.
+    #[must_use]
+    pub fn configured_model(&self) -> Option<(AgentType, String)> {
+        self.detectors.iter().find_map(|detector| {
+            let path = detector.get_config_path()?;
+            let content = std::fs::read_to_string(&path).ok()?;
+            let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+            let model = json.get("model")?.as_str()?.trim();
+            if model.is_empty() {
+                return None;
+            }
+            debug!(agent = ?detector.agent_type(), model, "Model found in agent config");
+            Some((detector.agent_type(), model.to_string()))
+        })
+    }
+
     /// Get a summary of detection results.
     #[must_use]
     pub fn summary(&self) -> DetectionSummary {
@@ -231,6 +251,24 @@ mod tests {
         assert_eq!(service.detectors.len(), 9); // All 9 agents
     }
 
+    #[test]
+    fn test_configured_model_reads_agent_config() {
+        let temp = TempDir::new().unwrap();
+        let service = AgentDetectionService::with_home(temp.path());
+        assert!(service.configured_model().is_none());
+
+        std::fs::create_dir_all(temp.path().join(".codex")).unwrap();
+        std::fs::write(
+            temp.path().join(".codex/config.json"),
+            r#"{"model": "gpt-4o"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            service.configured_model(),
+            Some((AgentType::Codex, "gpt-4o".to_string()))
+        );
+    }
+
     #[test]
     fn test_service_with_custom_home() {
         let temp = TempDir::new().unwrap();
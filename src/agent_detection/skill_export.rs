@@ -0,0 +1,515 @@
+//! Export skills into an agent's native skill layout.
+//!
+//! Claude Code reads `~/.claude/skills/<name>/SKILL.md` (plus supporting
+//! files); Cursor reads `.cursor/rules/<name>.mdc`. [`SkillExportTarget`]
+//! holds the per-agent path and format logic. Every export records the hash
+//! of each file it wrote in `.ms-export.json` at the target root, so a later
+//! export can tell files edited in place from its own output and refuses to
+//! overwrite them unless forced.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::{AgentDetector, ClaudeCodeDetector, CursorDetector};
+use crate::bundler::local_safety::{ModificationStatus, detect_modifications, hash_bytes};
+use crate::core::SkillSpec;
+use crate::core::spec_lens::compile_markdown;
+use crate::error::{MsError, Result};
+
+/// File at the target root recording what ms exported there.
+pub const EXPORT_MANIFEST: &str = ".ms-export.json";
+
+/// A skill ready to be exported.
+#[derive(Debug, Clone)]
+pub struct ExportSkill {
+    pub id: String,
+    pub spec: SkillSpec,
+    /// Directory the skill was indexed from, for supporting files.
+    pub source_dir: Option<PathBuf>,
+    /// Resources kept in the archive, relative to the skill directory; they
+    /// fill in for files gone from `source_dir`.
+    pub resources: Vec<(PathBuf, Vec<u8>)>,
+}
+
+/// Where and how one agent keeps its skills.
+pub trait SkillExportTarget {
+    /// Name used on the command line (`--target`).
+    fn name(&self) -> &'static str;
+
+    /// Directory skills are exported under, when not given explicitly.
+    fn default_root(&self) -> Result<PathBuf>;
+
+    /// Directory, relative to the root, holding one skill's files.
+    fn skill_dir(&self, skill_id: &str) -> PathBuf;
+
+    /// Files to write for a skill, relative to [`skill_dir`](Self::skill_dir).
+    fn render(&self, skill: &ExportSkill) -> Result<Vec<(PathBuf, Vec<u8>)>>;
+}
+
+/// `~/.claude/skills/<id>/SKILL.md` with the skill's supporting files.
+#[derive(Debug, Default)]
+pub struct ClaudeCodeExport {
+    detector: ClaudeCodeDetector,
+}
+
+impl ClaudeCodeExport {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Export under a custom home directory (for testing).
+    #[must_use]
+    pub fn with_home(home: impl Into<PathBuf>) -> Self {
+        Self {
+            detector: ClaudeCodeDetector::with_home(home),
+        }
+    }
+}
+
+impl SkillExportTarget for ClaudeCodeExport {
+    fn name(&self) -> &'static str {
+        "claude-code"
+    }
+
+    fn default_root(&self) -> Result<PathBuf> {
+        self.detector
+            .get_integration_paths()
+            .into_iter()
+            .find(|path| path.is_absolute() && path.ends_with("skills"))
+            .ok_or_else(|| {
+                MsError::Config(
+                    "cannot locate the Claude Code skills directory (no home directory); \
+                     pass --dir"
+                        .to_string(),
+                )
+            })
+    }
+
+    fn skill_dir(&self, skill_id: &str) -> PathBuf {
+        PathBuf::from(file_stem(skill_id))
+    }
+
+    fn render(&self, skill: &ExportSkill) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+        let mut files = vec![(
+            PathBuf::from("SKILL.md"),
+            compile_markdown(&skill.spec).into_bytes(),
+        )];
+        if let Some(source_dir) = &skill.source_dir {
+            files.extend(supporting_files(source_dir)?);
+        }
+        for (path, bytes) in &skill.resources {
+            if !files.iter().any(|(existing, _)| existing == path) {
+                files.push((path.clone(), bytes.clone()));
+            }
+        }
+        Ok(files)
+    }
+}
+
+/// `.cursor/rules/<id>.mdc` in the current project.
+#[derive(Debug, Default)]
+pub struct CursorExport {
+    detector: CursorDetector,
+}
+
+impl CursorExport {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SkillExportTarget for CursorExport {
+    fn name(&self) -> &'static str {
+        "cursor"
+    }
+
+    fn default_root(&self) -> Result<PathBuf> {
+        let rules = self
+            .detector
+            .get_integration_paths()
+            .into_iter()
+            .find(|path| path.ends_with("rules"))
+            .unwrap_or_else(|| PathBuf::from(".cursor/rules"));
+        Ok(std::env::current_dir()?.join(rules))
+    }
+
+    fn skill_dir(&self, _skill_id: &str) -> PathBuf {
+        PathBuf::new()
+    }
+
+    fn render(&self, skill: &ExportSkill) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+        let description = skill.spec.metadata.description.replace('\n', " ");
+        let rule = format!(
+            "---\ndescription: {}\nalwaysApply: false\n---\n\n{}",
+            serde_json::to_string(description.trim())?,
+            without_frontmatter(&compile_markdown(&skill.spec))
+        );
+        Ok(vec![(
+            PathBuf::from(format!("{}.mdc", file_stem(&skill.id))),
+            rule.into_bytes(),
+        )])
+    }
+}
+
+/// Export target for a `--target` name.
+pub fn export_target(name: &str) -> Result<Box<dyn SkillExportTarget>> {
+    match name {
+        "claude-code" | "claude" => Ok(Box::new(ClaudeCodeExport::new())),
+        "cursor" => Ok(Box::new(CursorExport::new())),
+        other => Err(MsError::ValidationFailed(format!(
+            "unknown export target '{other}' (expected claude-code or cursor)"
+        ))),
+    }
+}
+
+/// What ms exported into one target root.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExportManifest {
+    #[serde(default)]
+    pub target: String,
+    #[serde(default)]
+    pub skills: BTreeMap<String, ExportedSkill>,
+}
+
+/// Files written for one skill, relative to its skill directory, with the
+/// hash of what was written.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExportedSkill {
+    pub files: BTreeMap<PathBuf, String>,
+    pub exported_at: String,
+}
+
+impl ExportManifest {
+    /// Read the manifest at `root`, or an empty one if there is none.
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = root.join(EXPORT_MANIFEST);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        serde_json::from_str(&fs::read_to_string(&path)?).map_err(|err| {
+            MsError::Config(format!("invalid export manifest {}: {err}", path.display()))
+        })
+    }
+
+    pub fn save(&self, root: &Path) -> Result<()> {
+        fs::create_dir_all(root)?;
+        fs::write(
+            root.join(EXPORT_MANIFEST),
+            serde_json::to_string_pretty(self)?,
+        )?;
+        Ok(())
+    }
+}
+
+/// Result of exporting one skill.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ExportOutcome {
+    /// Files written (new or changed).
+    Written { files: Vec<PathBuf> },
+    /// Output already matches what is on disk.
+    Unchanged,
+    /// Local edits (or files ms did not write) would be overwritten.
+    Conflict { files: Vec<PathBuf> },
+}
+
+/// Render `skill` for `target` and write it under `root`, recording it in
+/// `manifest`. Files changed since the last export, or present without
+/// having been exported by ms, are left alone unless `force` is set.
+pub fn export_skill(
+    target: &dyn SkillExportTarget,
+    root: &Path,
+    manifest: &mut ExportManifest,
+    skill: &ExportSkill,
+    force: bool,
+) -> Result<ExportOutcome> {
+    let rendered = target.render(skill)?;
+    let dir = root.join(target.skill_dir(&skill.id));
+    let expected: HashMap<PathBuf, String> = manifest
+        .skills
+        .get(&skill.id)
+        .map(|entry| entry.files.clone().into_iter().collect())
+        .unwrap_or_default();
+    let report = detect_modifications(&dir, &skill.id, &expected)?;
+    // Only files about to be overwritten with different content matter;
+    // edits that already match the new output are not conflicts.
+    let conflicts: Vec<PathBuf> = report
+        .files
+        .iter()
+        .filter(|file| {
+            matches!(
+                file.status,
+                ModificationStatus::Modified
+                    | ModificationStatus::New
+                    | ModificationStatus::Conflict
+            )
+        })
+        .filter(|file| {
+            rendered.iter().any(|(path, bytes)| {
+                *path == file.path && file.current_hash.as_deref() != Some(&hash_bytes(bytes))
+            })
+        })
+        .map(|file| file.path.clone())
+        .collect();
+    if !conflicts.is_empty() && !force {
+        return Ok(ExportOutcome::Conflict { files: conflicts });
+    }
+
+    let mut files = BTreeMap::new();
+    let mut written = Vec::new();
+    for (path, bytes) in &rendered {
+        let hash = hash_bytes(bytes);
+        let full = dir.join(path);
+        let on_disk = report
+            .files
+            .iter()
+            .find(|file| file.path == *path)
+            .and_then(|file| file.current_hash.as_deref());
+        if on_disk != Some(hash.as_str()) {
+            if let Some(parent) = full.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&full, bytes)?;
+            written.push(path.clone());
+        }
+        files.insert(path.clone(), hash);
+    }
+
+    let unchanged = written.is_empty()
+        && manifest
+            .skills
+            .get(&skill.id)
+            .is_some_and(|entry| entry.files == files);
+    manifest.target = target.name().to_string();
+    if unchanged {
+        return Ok(ExportOutcome::Unchanged);
+    }
+    manifest.skills.insert(
+        skill.id.clone(),
+        ExportedSkill {
+            files,
+            exported_at: chrono::Utc::now().to_rfc3339(),
+        },
+    );
+    Ok(ExportOutcome::Written { files: written })
+}
+
+/// Files next to a skill's SKILL.md (scripts, references), excluding ms's
+/// own test fixtures and hidden files.
+fn supporting_files(source_dir: &Path) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+    let mut files = Vec::new();
+    if !source_dir.is_dir() {
+        return Ok(files);
+    }
+    for entry in walkdir::WalkDir::new(source_dir)
+        .min_depth(1)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            !name.starts_with('.') && !(entry.depth() == 1 && name == "tests")
+        })
+        .filter_map(std::result::Result::ok)
+    {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let rel = entry
+            .path()
+            .strip_prefix(source_dir)
+            .map_err(|err| MsError::Config(format!("strip prefix: {err}")))?
+            .to_path_buf();
+        if rel == Path::new("SKILL.md") {
+            continue;
+        }
+        files.push((rel, fs::read(entry.path())?));
+    }
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(files)
+}
+
+/// Markdown after its YAML frontmatter block, if it has one. Cursor rules
+/// carry their own frontmatter, so ms's metadata block is dropped there.
+fn without_frontmatter(markdown: &str) -> &str {
+    markdown
+        .strip_prefix("---\n")
+        .and_then(|rest| rest.split_once("\n---\n"))
+        .map_or(markdown, |(_, body)| body.trim_start_matches('\n'))
+}
+
+/// Skill id as a single safe path component.
+fn file_stem(skill_id: &str) -> String {
+    skill_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>()
+        .trim_matches('.')
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::spec_lens::parse_markdown;
+
+    fn skill(id: &str, source_dir: Option<PathBuf>) -> ExportSkill {
+        let mut spec =
+            parse_markdown("# Deploy\n\nShip safely.\n\n## Steps\n\n- Run the checks\n").unwrap();
+        spec.metadata.id = id.to_string();
+        spec.metadata.name = "Deploy".to_string();
+        spec.metadata.description = "Ship safely".to_string();
+        ExportSkill {
+            id: id.to_string(),
+            spec,
+            source_dir,
+            resources: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn claude_code_root_comes_from_the_detector() {
+        let home = tempfile::tempdir().unwrap();
+        let target = ClaudeCodeExport::with_home(home.path());
+        assert_eq!(
+            target.default_root().unwrap(),
+            home.path().join(".claude/skills")
+        );
+        assert!(export_target("windsurf").is_err());
+    }
+
+    #[test]
+    fn exports_skill_md_and_supporting_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let source = tmp.path().join("src/deploy");
+        fs::create_dir_all(source.join("scripts")).unwrap();
+        fs::create_dir_all(source.join("tests")).unwrap();
+        fs::write(source.join("SKILL.md"), "# Deploy\n").unwrap();
+        fs::write(source.join("scripts/check.sh"), "echo ok\n").unwrap();
+        fs::write(source.join("tests/basic.yaml"), "name: basic\n").unwrap();
+
+        let root = tmp.path().join("skills");
+        let target = ClaudeCodeExport::new();
+        let mut manifest = ExportManifest::default();
+        let outcome = export_skill(
+            &target,
+            &root,
+            &mut manifest,
+            &skill("deploy", Some(source)),
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            outcome,
+            ExportOutcome::Written {
+                files: vec![PathBuf::from("SKILL.md"), PathBuf::from("scripts/check.sh")]
+            }
+        );
+        let markdown = fs::read_to_string(root.join("deploy/SKILL.md")).unwrap();
+        assert!(markdown.starts_with("---\n"), "{markdown}");
+        assert!(markdown.contains("## Steps"), "{markdown}");
+        assert!(!root.join("deploy/tests").exists());
+        assert_eq!(manifest.target, "claude-code");
+        assert_eq!(manifest.skills["deploy"].files.len(), 2);
+    }
+
+    #[test]
+    fn exports_archived_resources_missing_from_source() {
+        let tmp = tempfile::tempdir().unwrap();
+        let source = tmp.path().join("src/deploy");
+        fs::create_dir_all(source.join("resources")).unwrap();
+        fs::write(source.join("resources/checklist.md"), "- [ ] fresh\n").unwrap();
+
+        let mut deploy = skill("deploy", Some(source));
+        deploy.resources = vec![
+            (
+                PathBuf::from("resources/checklist.md"),
+                b"- [ ] archived\n".to_vec(),
+            ),
+            (PathBuf::from("schemas/input.json"), b"{}".to_vec()),
+        ];
+        let root = tmp.path().join("skills");
+        let mut manifest = ExportManifest::default();
+        export_skill(
+            &ClaudeCodeExport::new(),
+            &root,
+            &mut manifest,
+            &deploy,
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(root.join("deploy/resources/checklist.md")).unwrap(),
+            "- [ ] fresh\n"
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("deploy/schemas/input.json")).unwrap(),
+            "{}"
+        );
+    }
+
+    #[test]
+    fn refuses_to_clobber_local_edits_without_force() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().join("rules");
+        let target = CursorExport::new();
+        let mut manifest = ExportManifest::default();
+        let deploy = skill("deploy", None);
+
+        export_skill(&target, &root, &mut manifest, &deploy, false).unwrap();
+        manifest.save(&root).unwrap();
+        let mut manifest = ExportManifest::load(&root).unwrap();
+        let rule = fs::read_to_string(root.join("deploy.mdc")).unwrap();
+        assert!(
+            rule.starts_with(
+                "---\ndescription: \"Ship safely\"\nalwaysApply: false\n---\n\n# Deploy"
+            )
+        );
+        assert_eq!(rule.matches("---\n").count(), 2, "{rule}");
+        assert_eq!(
+            export_skill(&target, &root, &mut manifest, &deploy, false).unwrap(),
+            ExportOutcome::Unchanged
+        );
+
+        fs::write(root.join("deploy.mdc"), "my own notes\n").unwrap();
+        assert_eq!(
+            export_skill(&target, &root, &mut manifest, &deploy, false).unwrap(),
+            ExportOutcome::Conflict {
+                files: vec![PathBuf::from("deploy.mdc")]
+            }
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("deploy.mdc")).unwrap(),
+            "my own notes\n"
+        );
+
+        // A file ms never wrote is not overwritten either.
+        fs::write(root.join("release.mdc"), "hand written\n").unwrap();
+        assert!(matches!(
+            export_skill(
+                &target,
+                &root,
+                &mut manifest,
+                &skill("release", None),
+                false
+            )
+            .unwrap(),
+            ExportOutcome::Conflict { .. }
+        ));
+
+        let forced = export_skill(&target, &root, &mut manifest, &deploy, true).unwrap();
+        assert!(matches!(forced, ExportOutcome::Written { .. }));
+        assert_eq!(fs::read_to_string(root.join("deploy.mdc")).unwrap(), rule);
+    }
+}
@@ -1,9 +1,10 @@
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::config::AgentMailConfig;
+use crate::core::recovery::{RetryConfig, with_retry_if};
 use crate::error::{MsError, Result};
 
 const MCP_PROTOCOL_VERSION: &str = "2024-11-05";
@@ -37,7 +38,12 @@ impl AgentMailClient {
                 "agent mail agent_name is empty; set [agent_mail].agent_name".to_string(),
             ));
         }
-        let mcp = McpClient::new(&config.endpoint, config.timeout_secs)?;
+        let mcp = McpClient::new(
+            &config.endpoint,
+            config.timeout_secs,
+            RetryConfig::default(),
+            RateLimiter::per_minute(config.max_requests_per_minute),
+        )?;
         Ok(Self {
             mcp,
             project_key: config.project_key.clone(),
@@ -62,7 +68,7 @@ impl AgentMailClient {
             "limit": limit,
             "include_bodies": include_bodies,
         });
-        let value = self.mcp.call_tool("fetch_inbox", args)?;
+        let value = self.mcp.call_tool("fetch_inbox", args, Retry::Idempotent)?;
         let value = unwrap_tool_result(value)?;
         let messages: Vec<InboxMessage> = serde_json::from_value(value)?;
         Ok(messages)
@@ -74,7 +80,15 @@ impl AgentMailClient {
             "agent_name": self.agent_name,
             "message_id": message_id,
         });
-        let value = self.mcp.call_tool("acknowledge_message", args)?;
+        // Acknowledging twice is only safe when the server can tell the
+        // attempts apart, so retries carry a key derived from the message.
+        let key = format!(
+            "ms-ack-{}-{}-{message_id}",
+            self.project_key, self.agent_name
+        );
+        let value = self
+            .mcp
+            .call_tool("acknowledge_message", args, Retry::WithKey(key))?;
         let _ = unwrap_tool_result(value)?;
         Ok(())
     }
@@ -128,11 +142,103 @@ struct JsonRpcError {
     data: Option<Value>,
 }
 
+/// Whether a request may be sent again after a transient failure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Retry {
+    /// Safe to repeat (reads, `initialize`).
+    Idempotent,
+    /// Has side effects; repeated only with this `Idempotency-Key` header.
+    WithKey(String),
+    /// Has side effects and no key: sent exactly once.
+    Never,
+}
+
+/// A failed attempt, split by whether trying again could help.
+#[derive(Debug)]
+enum Attempt {
+    /// 5xx, 429, timeouts and connection failures.
+    Transient(MsError),
+    Fatal(MsError),
+}
+
+impl Attempt {
+    fn into_error(self, retries_exhausted: bool) -> MsError {
+        match self {
+            Self::Transient(MsError::AgentMail {
+                status, message, ..
+            }) => MsError::AgentMail {
+                status,
+                retries_exhausted,
+                message,
+            },
+            Self::Transient(err) | Self::Fatal(err) => err,
+        }
+    }
+}
+
+fn agent_mail_error(status: Option<u16>, message: impl Into<String>) -> MsError {
+    MsError::AgentMail {
+        status,
+        retries_exhausted: false,
+        message: message.into(),
+    }
+}
+
+/// Token bucket allowing bursts of up to a minute's budget.
+#[derive(Debug)]
+struct RateLimiter {
+    capacity: f64,
+    tokens: f64,
+    per_sec: f64,
+    last: Instant,
+}
+
+impl RateLimiter {
+    /// `0` disables limiting.
+    fn per_minute(max_requests: u32) -> Self {
+        let capacity = f64::from(max_requests);
+        Self {
+            capacity,
+            tokens: capacity,
+            per_sec: capacity / 60.0,
+            last: Instant::now(),
+        }
+    }
+
+    /// Take a token, returning how long the caller must wait first.
+    fn reserve(&mut self, now: Instant) -> Duration {
+        if self.capacity <= 0.0 {
+            return Duration::ZERO;
+        }
+        let elapsed = now.saturating_duration_since(self.last).as_secs_f64();
+        self.tokens = elapsed
+            .mul_add(self.per_sec, self.tokens)
+            .min(self.capacity);
+        self.last = now;
+        self.tokens -= 1.0;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.per_sec)
+        }
+    }
+
+    fn acquire(&mut self) {
+        let wait = self.reserve(Instant::now());
+        if !wait.is_zero() {
+            tracing::debug!(wait_ms = wait.as_millis() as u64, "agent mail rate limited");
+            std::thread::sleep(wait);
+        }
+    }
+}
+
 struct McpClient {
     endpoint: String,
     client: reqwest::blocking::Client,
     next_id: u64,
     initialized: bool,
+    retry: RetryConfig,
+    limiter: RateLimiter,
 }
 
 impl std::fmt::Debug for McpClient {
@@ -146,7 +252,12 @@ impl std::fmt::Debug for McpClient {
 }
 
 impl McpClient {
-    fn new(endpoint: &str, timeout_secs: u64) -> Result<Self> {
+    fn new(
+        endpoint: &str,
+        timeout_secs: u64,
+        retry: RetryConfig,
+        limiter: RateLimiter,
+    ) -> Result<Self> {
         if endpoint.starts_with("http://") {
             tracing::warn!(
                 "Agent mail endpoint uses unencrypted HTTP. Credentials will be sent in plain text."
@@ -163,10 +274,12 @@ impl McpClient {
             client,
             next_id: 1,
             initialized: false,
+            retry,
+            limiter,
         })
     }
 
-    fn call_tool(&mut self, name: &str, arguments: Value) -> Result<Value> {
+    fn call_tool(&mut self, name: &str, arguments: Value, retry: Retry) -> Result<Value> {
         self.ensure_initialized()?;
         self.call_method(
             "tools/call",
@@ -174,6 +287,7 @@ impl McpClient {
                 "name": name,
                 "arguments": arguments,
             }),
+            &retry,
         )
     }
 
@@ -193,13 +307,15 @@ impl McpClient {
                 }
             }
         });
-        let _ = self.call_method("initialize", params)?;
+        let _ = self.call_method("initialize", params, &Retry::Idempotent)?;
         self.send_notification("initialized", serde_json::json!({}))?;
         self.initialized = true;
         Ok(())
     }
 
-    fn call_method(&mut self, method: &str, params: Value) -> Result<Value> {
+    /// Send a request, retrying transient failures when `retry` allows it.
+    /// Every attempt reuses the same JSON-RPC id.
+    fn call_method(&mut self, method: &str, params: Value, retry: &Retry) -> Result<Value> {
         let request = JsonRpcRequest {
             jsonrpc: "2.0".to_string(),
             id: self.next_id,
@@ -208,53 +324,106 @@ impl McpClient {
         };
         self.next_id = self.next_id.saturating_add(1);
 
-        let response = self
-            .client
-            .post(&self.endpoint)
-            .json(&request)
-            .send()
-            .map_err(|err| MsError::Config(format!("agent mail request failed: {err}")))?;
+        let key = match retry {
+            Retry::WithKey(key) => Some(key.as_str()),
+            Retry::Idempotent | Retry::Never => None,
+        };
+        let config = match retry {
+            Retry::Never => RetryConfig {
+                max_attempts: 1,
+                ..self.retry.clone()
+            },
+            Retry::Idempotent | Retry::WithKey(_) => self.retry.clone(),
+        };
 
-        if !response.status().is_success() {
-            return Err(MsError::Config(format!(
-                "agent mail HTTP {}",
-                response.status()
-            )));
+        let mut attempts = 0u32;
+        with_retry_if(
+            &config,
+            || {
+                attempts += 1;
+                self.send_once(&request, key)
+            },
+            |err| matches!(err, Attempt::Transient(_)),
+        )
+        .map_err(|err| {
+            if attempts > 1 {
+                tracing::warn!(method, attempts, "agent mail request failed after retries");
+            }
+            err.into_error(attempts > 1)
+        })
+    }
+
+    fn send_once(
+        &mut self,
+        request: &JsonRpcRequest,
+        idempotency_key: Option<&str>,
+    ) -> std::result::Result<Value, Attempt> {
+        self.limiter.acquire();
+        let mut builder = self.client.post(&self.endpoint).json(request);
+        if let Some(key) = idempotency_key {
+            builder = builder.header("Idempotency-Key", key);
         }
+        let response = builder.send().map_err(|err| {
+            let error = agent_mail_error(None, format!("request failed: {err}"));
+            if err.is_timeout() || err.is_connect() {
+                Attempt::Transient(error)
+            } else {
+                Attempt::Fatal(error)
+            }
+        })?;
 
-        let response: JsonRpcResponse = response
-            .json()
-            .map_err(|err| MsError::Config(format!("agent mail response parse: {err}")))?;
+        let status = response.status();
+        if !status.is_success() {
+            let error = agent_mail_error(
+                Some(status.as_u16()),
+                format!("{} returned {status}", request.method),
+            );
+            return Err(
+                if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                    Attempt::Transient(error)
+                } else {
+                    Attempt::Fatal(error)
+                },
+            );
+        }
+
+        let response: JsonRpcResponse = response.json().map_err(|err| {
+            Attempt::Fatal(agent_mail_error(None, format!("response parse: {err}")))
+        })?;
 
         if let Some(error) = response.error {
-            return Err(MsError::Config(format!(
-                "agent mail error {}: {}",
-                error.code, error.message
+            return Err(Attempt::Fatal(agent_mail_error(
+                None,
+                format!("error {}: {}", error.code, error.message),
             )));
         }
 
-        response
-            .result
-            .ok_or_else(|| MsError::Config(format!("agent mail empty response for {method}")))
+        response.result.ok_or_else(|| {
+            Attempt::Fatal(agent_mail_error(
+                None,
+                format!("empty response for {}", request.method),
+            ))
+        })
     }
 
-    fn send_notification(&self, method: &str, params: Value) -> Result<()> {
+    fn send_notification(&mut self, method: &str, params: Value) -> Result<()> {
         let request = JsonRpcNotification {
             jsonrpc: "2.0".to_string(),
             method: method.to_string(),
             params,
         };
+        self.limiter.acquire();
         let response = self
             .client
             .post(&self.endpoint)
             .json(&request)
             .send()
-            .map_err(|err| MsError::Config(format!("agent mail notify failed: {err}")))?;
+            .map_err(|err| agent_mail_error(None, format!("notify failed: {err}")))?;
         if !response.status().is_success() {
-            return Err(MsError::Config(format!(
-                "agent mail notify HTTP {}",
-                response.status()
-            )));
+            return Err(agent_mail_error(
+                Some(response.status().as_u16()),
+                format!("notify {method} returned {}", response.status()),
+            ));
         }
         Ok(())
     }
@@ -272,11 +441,12 @@ fn unwrap_tool_result(value: Value) -> Result<Value> {
             .and_then(|items| items.iter().find_map(|item| item.get("text")))
             .and_then(|text| text.as_str())
             .unwrap_or("agent mail tool error");
-        return Err(MsError::Config(message.to_string()));
+        return Err(agent_mail_error(None, message));
     }
     let Some(content) = value.get("content").and_then(|c| c.as_array()) else {
-        return Err(MsError::Config(
-            "agent mail response missing content array".to_string(),
+        return Err(agent_mail_error(
+            None,
+            "agent mail response missing content array",
         ));
     };
     for item in content {
@@ -290,8 +460,9 @@ fn unwrap_tool_result(value: Value) -> Result<Value> {
 
     // If we reach here, we found content but no valid JSON in text fields.
     // This is unexpected for our tools which should return JSON.
-    Err(MsError::Config(
-        "agent mail response contained no valid JSON payload".to_string(),
+    Err(agent_mail_error(
+        None,
+        "agent mail response contained no valid JSON payload",
     ))
 }
 
@@ -693,6 +864,7 @@ mod tests {
             project_key: "test".to_string(),
             agent_name: "test-agent".to_string(),
             timeout_secs: 10,
+            max_requests_per_minute: 60,
         };
         let result = AgentMailClient::from_config(&config);
         assert!(result.is_err());
@@ -708,6 +880,7 @@ mod tests {
             project_key: "test".to_string(),
             agent_name: "test-agent".to_string(),
             timeout_secs: 10,
+            max_requests_per_minute: 60,
         };
         let result = AgentMailClient::from_config(&config);
         assert!(result.is_err());
@@ -723,6 +896,7 @@ mod tests {
             project_key: "test".to_string(),
             agent_name: "test-agent".to_string(),
             timeout_secs: 10,
+            max_requests_per_minute: 60,
         };
         let result = AgentMailClient::from_config(&config);
         assert!(result.is_err());
@@ -738,6 +912,7 @@ mod tests {
             project_key: "".to_string(),
             agent_name: "test-agent".to_string(),
             timeout_secs: 10,
+            max_requests_per_minute: 60,
         };
         let result = AgentMailClient::from_config(&config);
         assert!(result.is_err());
@@ -753,6 +928,7 @@ mod tests {
             project_key: "  \t ".to_string(),
             agent_name: "test-agent".to_string(),
             timeout_secs: 10,
+            max_requests_per_minute: 60,
         };
         let result = AgentMailClient::from_config(&config);
         assert!(result.is_err());
@@ -768,6 +944,7 @@ mod tests {
             project_key: "test".to_string(),
             agent_name: "".to_string(),
             timeout_secs: 10,
+            max_requests_per_minute: 60,
         };
         let result = AgentMailClient::from_config(&config);
         assert!(result.is_err());
@@ -783,6 +960,7 @@ mod tests {
             project_key: "test".to_string(),
             agent_name: "\n\t".to_string(),
             timeout_secs: 10,
+            max_requests_per_minute: 60,
         };
         let result = AgentMailClient::from_config(&config);
         assert!(result.is_err());
@@ -804,4 +982,200 @@ mod tests {
         assert_eq!(parts[1].len(), 2); // Month
         assert_eq!(parts[2].len(), 2); // Day
     }
+
+    // ============================================
+    // Retry and Rate Limiting Tests
+    // ============================================
+
+    /// Serves one scripted `(status, body)` response per connection and
+    /// returns the raw requests it received.
+    fn scripted_server(
+        responses: Vec<(u16, String)>,
+    ) -> (String, std::thread::JoinHandle<Vec<String>>) {
+        use std::io::{BufRead, BufReader, Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}/mcp", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut head = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                    head.push_str(&line);
+                }
+                let mut payload = vec![0; content_length];
+                reader.read_exact(&mut payload).unwrap();
+                head.push_str(&String::from_utf8_lossy(&payload));
+                requests.push(head);
+
+                let mut stream = reader.into_inner();
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} Scripted\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+            requests
+        });
+        (endpoint, handle)
+    }
+
+    fn rpc_ok(result: &Value) -> (u16, String) {
+        (
+            200,
+            serde_json::json!({"jsonrpc": "2.0", "id": 1, "result": result}).to_string(),
+        )
+    }
+
+    fn fast_client(endpoint: &str) -> McpClient {
+        let retry = RetryConfig {
+            max_attempts: 3,
+            initial_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+            backoff_multiplier: 2.0,
+            jitter_factor: 0.0,
+        };
+        let mut client = McpClient::new(endpoint, 5, retry, RateLimiter::per_minute(0)).unwrap();
+        client.initialized = true;
+        client
+    }
+
+    #[test]
+    fn call_method_retries_server_errors_then_succeeds() {
+        let (endpoint, server) = scripted_server(vec![
+            (502, String::new()),
+            (503, String::new()),
+            rpc_ok(&serde_json::json!({"ok": true})),
+        ]);
+        let mut client = fast_client(&endpoint);
+
+        let value = client
+            .call_method("tools/call", serde_json::json!({}), &Retry::Idempotent)
+            .unwrap();
+        assert_eq!(value["ok"], true);
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 3);
+        // Every attempt carries the same JSON-RPC id.
+        assert!(requests.iter().all(|r| r.contains("\"id\":1")));
+    }
+
+    #[test]
+    fn call_method_reports_exhausted_retries() {
+        let (endpoint, server) = scripted_server(vec![(500, String::new()); 3]);
+        let mut client = fast_client(&endpoint);
+
+        let err = client
+            .call_method("tools/call", serde_json::json!({}), &Retry::Idempotent)
+            .unwrap_err();
+        match err {
+            MsError::AgentMail {
+                status,
+                retries_exhausted,
+                ..
+            } => {
+                assert_eq!(status, Some(500));
+                assert!(retries_exhausted);
+            }
+            other => panic!("expected AgentMail error, got {other}"),
+        }
+        assert_eq!(server.join().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn call_method_does_not_retry_client_errors() {
+        let (endpoint, server) = scripted_server(vec![(404, String::new())]);
+        let mut client = fast_client(&endpoint);
+
+        let err = client
+            .call_method("tools/call", serde_json::json!({}), &Retry::Idempotent)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            MsError::AgentMail {
+                status: Some(404),
+                retries_exhausted: false,
+                ..
+            }
+        ));
+        assert_eq!(server.join().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn call_method_without_key_is_sent_once() {
+        let (endpoint, server) = scripted_server(vec![(502, String::new())]);
+        let mut client = fast_client(&endpoint);
+
+        let err = client
+            .call_method("tools/call", serde_json::json!({}), &Retry::Never)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            MsError::AgentMail {
+                status: Some(502),
+                retries_exhausted: false,
+                ..
+            }
+        ));
+        assert_eq!(server.join().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn call_method_sends_idempotency_key_on_every_attempt() {
+        let (endpoint, server) =
+            scripted_server(vec![(503, String::new()), rpc_ok(&serde_json::json!({}))]);
+        let mut client = fast_client(&endpoint);
+
+        client
+            .call_method(
+                "tools/call",
+                serde_json::json!({}),
+                &Retry::WithKey("ms-ack-p-a-7".to_string()),
+            )
+            .unwrap();
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests.iter().all(|r| {
+            r.to_ascii_lowercase()
+                .contains("idempotency-key: ms-ack-p-a-7")
+        }));
+    }
+
+    #[test]
+    fn rate_limiter_allows_burst_then_waits() {
+        let mut limiter = RateLimiter::per_minute(2);
+        let start = limiter.last;
+        assert_eq!(limiter.reserve(start), Duration::ZERO);
+        assert_eq!(limiter.reserve(start), Duration::ZERO);
+        // Bucket empty: the next token arrives after 30s at 2/min.
+        let wait = limiter.reserve(start);
+        assert!((wait.as_secs_f64() - 30.0).abs() < 0.01, "wait = {wait:?}");
+        // After a minute the deficit is repaid and one token remains.
+        assert_eq!(
+            limiter.reserve(start + Duration::from_secs(60)),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn rate_limiter_zero_is_unlimited() {
+        let mut limiter = RateLimiter::per_minute(0);
+        let now = Instant::now();
+        for _ in 0..100 {
+            assert_eq!(limiter.reserve(now), Duration::ZERO);
+        }
+    }
 }
@@ -1,24 +1,43 @@
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, PoisonError, RwLock};
 
 use crate::cli::OutputFormat;
 use crate::config::Config;
+use crate::core::lookup;
+use crate::core::overrides::ProjectOverrides;
+use crate::core::tokenizer::{self, CachedCounter, HeuristicCounter, TokenCounter, TokenizerKind};
 use crate::error::{MsError, Result};
+use crate::lint::BoxedRule;
+use crate::lint::rules::configured_rules;
+use crate::output::{NdjsonSink, PhaseProgress, RichOutput};
 use crate::search::SearchIndex;
-use crate::storage::{Database, GitArchive};
+use crate::security::command_safety::selected_profile;
+use crate::security::{SafetyGate, SecretScanner};
+use crate::storage::{Database, GitArchive, SkillRecord};
+use crate::suggestions::bandit::ContextualBandit;
+use crate::utils::path::expand_path;
 
 #[derive(Clone)]
 pub struct AppContext {
     pub ms_root: PathBuf,
     pub config_path: PathBuf,
+    /// Config files merged into `config`, in load order (existing or not)
+    pub config_sources: Vec<PathBuf>,
     pub config: Config,
     pub db: Arc<Database>,
     pub git: Arc<GitArchive>,
     pub search: Arc<SearchIndex>,
+    /// Secret scanner with the `[security.secrets]` patterns and allowlist
+    pub secrets: Arc<SecretScanner>,
+    /// Shared contextual bandit, read from the database once per process and
+    /// kept current by [`update_bandit`](Self::update_bandit)
+    bandit: Arc<RwLock<ContextualBandit>>,
     /// Deprecated: use output_format instead
     pub robot_mode: bool,
     pub output_format: OutputFormat,
     pub verbosity: u8,
+    /// `--progress-events`: stream NDJSON progress to stderr in machine modes
+    pub progress_events: bool,
 }
 
 /// Inode-level identity of a single filesystem path (device + inode on Unix).
@@ -89,37 +108,107 @@ fn path_inode(path: &Path) -> Option<InodeId> {
     std::fs::metadata(path).ok().map(|m| inode_of(&m))
 }
 
+/// The shared bandit from the database. Unreadable state must not keep
+/// unrelated commands from running, so it falls back to a fresh bandit;
+/// writes re-read the row and still report the error.
+fn load_shared_bandit(db: &Database) -> ContextualBandit {
+    ContextualBandit::load_from_db(db, None, Some(&ContextualBandit::legacy_path())).unwrap_or_else(
+        |e| {
+            tracing::warn!(error = %e, "failed to load bandit state");
+            ContextualBandit::default()
+        },
+    )
+}
+
 impl AppContext {
     pub fn from_cli(cli: &crate::cli::Cli) -> Result<Self> {
+        Self::build(cli, |index_path| {
+            // Try writable first (the writer itself is only taken on the first
+            // write); fall back to read-only mode when the index directory
+            // cannot be opened for writing, e.g. on a read-only mount.
+            SearchIndex::open(index_path).or_else(|_| SearchIndex::open_readonly(index_path))
+        })
+    }
+
+    /// Build a context for `ms doctor`, which must start even when the search
+    /// index is corrupt or locked: the index is opened read-only, and an index that cannot
+    /// be opened at all is replaced by an empty in-memory one.
+    pub fn for_doctor(cli: &crate::cli::Cli) -> Result<Self> {
+        Self::build(cli, |index_path| {
+            SearchIndex::open_readonly(index_path).or_else(|_| SearchIndex::open_in_memory())
+        })
+    }
+
+    fn build(
+        cli: &crate::cli::Cli,
+        open_search: impl FnOnce(&Path) -> Result<SearchIndex>,
+    ) -> Result<Self> {
         let ms_root = Self::find_ms_root()?;
         let config_path = cli
             .config
             .clone()
             .unwrap_or_else(|| default_config_path(&ms_root));
+        let config_sources = Config::source_paths(cli.config.as_deref(), &ms_root);
+        if cli.strict_config {
+            Config::check_strict(&config_sources)?;
+        }
         let config = Config::load(cli.config.as_deref(), &ms_root)?;
+        if let Some(profile) = cli.safety_profile.clone().or_else(selected_profile) {
+            SafetyGate::validate_profile(&config.safety, &profile)?;
+        }
+        let secrets = Arc::new(SecretScanner::from_config(&config.security.secrets)?);
+        let db = Arc::new(Database::open(ms_root.join("ms.db"))?);
+        let bandit = Arc::new(RwLock::new(load_shared_bandit(&db)));
 
         Ok(Self {
             ms_root: ms_root.clone(),
             config_path,
+            config_sources,
             config,
-            db: Arc::new(Database::open(ms_root.join("ms.db"))?),
+            db,
             git: Arc::new(GitArchive::open(ms_root.join("archive"))?),
-            search: Arc::new({
-                let index_path = ms_root.join("index");
-                // Try writable first; if the write lock is busy (another process),
-                // fall back to read-only mode so concurrent MCP servers and CLI
-                // commands can coexist without "LockBusy" errors.
-                SearchIndex::open(&index_path)
-                    .or_else(|_| SearchIndex::open_readonly(&index_path))?
-            }),
+            search: Arc::new(open_search(&ms_root.join("index"))?),
+            secrets,
+            bandit,
             robot_mode: cli.robot,
             output_format: cli.output_format(),
             verbosity: cli.verbose,
+            progress_events: cli.progress_events,
         })
     }
 
+    /// Context rooted at `ms_root` with default config, opening the DB, git
+    /// archive, and search index there (mirrors the store fields of
+    /// `from_cli`). For unit tests that need a whole context.
+    #[cfg(test)]
+    #[must_use]
+    pub(crate) fn for_tests(ms_root: &Path) -> Self {
+        std::fs::create_dir_all(ms_root).unwrap();
+        let index_path = ms_root.join("index");
+        let db = Arc::new(Database::open(ms_root.join("ms.db")).unwrap());
+        Self {
+            ms_root: ms_root.to_path_buf(),
+            config_path: ms_root.join("config.toml"),
+            config_sources: vec![ms_root.join("config.toml")],
+            config: Config::default(),
+            bandit: Arc::new(RwLock::new(load_shared_bandit(&db))),
+            db,
+            git: Arc::new(GitArchive::open(ms_root.join("archive")).unwrap()),
+            search: Arc::new(
+                SearchIndex::open(&index_path)
+                    .or_else(|_| SearchIndex::open_readonly(&index_path))
+                    .unwrap(),
+            ),
+            secrets: Arc::new(SecretScanner::default()),
+            robot_mode: false,
+            output_format: OutputFormat::default(),
+            verbosity: 0,
+            progress_events: false,
+        }
+    }
+
     /// Path of the SQLite database backing this context.
-    fn db_path(&self) -> PathBuf {
+    pub fn db_path(&self) -> PathBuf {
         self.ms_root.join("ms.db")
     }
 
@@ -151,38 +240,103 @@ impl AppContext {
     /// handles are built into locals first and only swapped in once all three
     /// succeed, so a failure (e.g. a half-written rebuild) leaves the context
     /// on its current, consistent handles rather than half-reopened. The
-    /// previous `Arc`s — and their open file descriptors / Tantivy writer lock
-    /// on the now-renamed directory — are released once their last in-flight
-    /// reference is dropped.
+    /// previous `Arc`s — and their open file descriptors on the now-renamed
+    /// directory — are released once their last in-flight reference is
+    /// dropped.
     pub fn reopen_stores(&mut self) -> Result<()> {
         let index_path = self.index_path();
         let db = Arc::new(Database::open(self.db_path())?);
         let git = Arc::new(GitArchive::open(self.ms_root.join("archive"))?);
-        // Match `from_cli`: prefer a writable index, fall back to read-only if
-        // the writer lock is held (e.g. by a concurrent rebuild still running).
+        // Match `from_cli`: prefer a writable index, fall back to read-only
         let search = Arc::new(
             SearchIndex::open(&index_path).or_else(|_| SearchIndex::open_readonly(&index_path))?,
         );
+        *self.bandit.write().unwrap_or_else(PoisonError::into_inner) = load_shared_bandit(&db);
         self.db = db;
         self.git = git;
         self.search = search;
         Ok(())
     }
 
+    /// `agent_id`'s contextual bandit. The shared one comes from the state
+    /// loaded with this context; an agent's own state is read on demand.
+    pub fn bandit(&self, agent_id: Option<&str>) -> Result<ContextualBandit> {
+        match agent_id {
+            None => Ok(self
+                .bandit
+                .read()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clone()),
+            Some(_) => ContextualBandit::load_from_db(
+                &self.db,
+                agent_id,
+                Some(&ContextualBandit::legacy_path()),
+            ),
+        }
+    }
+
+    /// Apply `change` to `agent_id`'s persisted bandit; see
+    /// [`ContextualBandit::update_in_db`].
+    pub fn update_bandit<F>(&self, agent_id: Option<&str>, change: F) -> Result<ContextualBandit>
+    where
+        F: FnMut(&mut ContextualBandit),
+    {
+        let bandit = ContextualBandit::update_in_db(
+            &self.db,
+            agent_id,
+            Some(&ContextualBandit::legacy_path()),
+            change,
+        )?;
+        if agent_id.is_none() {
+            *self.bandit.write().unwrap_or_else(PoisonError::into_inner) = bandit.clone();
+        }
+        Ok(bandit)
+    }
+
+    /// Replace `agent_id`'s persisted bandit with a fresh one.
+    pub fn reset_bandit(
+        &self,
+        agent_id: Option<&str>,
+        feature_dim: usize,
+    ) -> Result<ContextualBandit> {
+        self.update_bandit(agent_id, |bandit| {
+            *bandit = ContextualBandit::with_feature_dim(feature_dim);
+        })
+    }
+
+    /// Progress reporting for long-running commands: a progress bar for
+    /// human output, NDJSON events on stderr for machine output with
+    /// `--progress-events`, and nothing otherwise.
+    #[must_use]
+    pub fn progress(&self) -> PhaseProgress {
+        if self.output_format == OutputFormat::Human {
+            PhaseProgress::new(Box::new(RichOutput::new(
+                &self.config,
+                &self.output_format,
+                self.robot_mode,
+            )))
+        } else if self.progress_events && self.output_format.is_machine_readable() {
+            PhaseProgress::new(Box::new(NdjsonSink::stderr()))
+        } else {
+            PhaseProgress::disabled()
+        }
+    }
+
     /// Ensure the search index was opened for writing.
     ///
     /// [`AppContext::from_cli`] transparently falls back to a **read-only**
     /// search index when the writable open fails, so that read-only commands
-    /// (`search`, `load`, `list`, …) keep working alongside a live
-    /// `ms mcp serve` that holds the Tantivy writer lock. Commands that mutate
-    /// the index (`index`, and the other re-indexing paths) must call this
+    /// (`search`, `load`, `list`, …) keep working on a read-only mount.
+    /// Commands that mutate the index (`index`, and the other re-indexing
+    /// paths) must call this
     /// first: without it they would perform partial SQLite/Git writes and then
     /// abort mid-run with an opaque Tantivy "read-only mode" error — exactly the
     /// failure reported in issue #133.
     ///
     /// Performs no side effects; on failure returns a clear, actionable error
-    /// naming the concrete cause (held writer lock vs. read-only filesystem)
-    /// and the index that was selected.
+    /// naming the concrete cause and the index that was selected. A writer
+    /// held by another process is not this error: writes wait for it and then
+    /// fail with [`MsError::Busy`].
     pub fn require_writable_search(&self) -> Result<()> {
         if self.search.is_readonly() {
             Err(MsError::SearchIndexReadOnly(
@@ -193,26 +347,75 @@ impl AppContext {
         }
     }
 
+    /// Resolve what a user typed (id, alias, or unique id prefix) to an
+    /// indexed skill. Every command that takes a skill reference goes through
+    /// here so aliases work the same everywhere; see [`crate::core::lookup`].
+    pub fn resolve_skill(&self, input: &str) -> Result<SkillRecord> {
+        lookup::resolve_skill(&self.db, input)
+    }
+
+    /// [`resolve_skill`](Self::resolve_skill), returning only the canonical id.
+    pub fn resolve_skill_id(&self, input: &str) -> Result<String> {
+        self.resolve_skill(input).map(|record| record.id)
+    }
+
+    /// Canonical id for `input`, or `input` unchanged when it names no indexed
+    /// skill (for data keyed by ids that may not be indexed, such as archived
+    /// skills or recorded evidence). Alias cycles are still errors.
+    pub fn canonical_skill_id(&self, input: &str) -> Result<String> {
+        match self.resolve_skill_id(input) {
+            Err(MsError::SkillNotFound(_)) => Ok(lookup::follow_alias(&self.db, input)?
+                .map_or_else(|| input.to_string(), |alias| alias.canonical_id)),
+            other => other,
+        }
+    }
+
+    /// Skill overrides from `overrides.toml` in the ms root (empty when the
+    /// project has none); see [`crate::core::overrides`].
+    pub fn project_overrides(&self) -> Result<ProjectOverrides> {
+        ProjectOverrides::load(&self.ms_root)
+    }
+
+    /// A `skill_paths.project` entry as a path. Relative entries in a
+    /// project's `.ms/config.toml` are relative to the project root (the
+    /// directory holding `.ms`), so they resolve the same from any
+    /// subdirectory.
+    #[must_use]
+    pub fn project_skill_path(&self, raw: &str) -> PathBuf {
+        let path = expand_path(raw);
+        match self.ms_root.parent() {
+            Some(root) if path.is_relative() && self.ms_root.ends_with(".ms") => root.join(path),
+            _ => path,
+        }
+    }
+
+    /// Token counter for `[packing].tokenizer`. BPE counts are cached in the
+    /// database by content hash; the heuristic is cheaper than a lookup.
+    pub fn token_counter(&self) -> Result<Box<dyn TokenCounter + '_>> {
+        match self.config.packing.tokenizer {
+            TokenizerKind::Heuristic => Ok(Box::new(HeuristicCounter)),
+            kind => Ok(Box::new(CachedCounter::new(
+                tokenizer::token_counter(kind)?,
+                &self.db,
+            ))),
+        }
+    }
+
+    /// Built-in lint rules, configured by `[security.secrets]` and
+    /// `[packing].tokenizer`.
+    pub fn lint_rules(&self) -> Result<Vec<BoxedRule>> {
+        let counter = tokenizer::token_counter(self.config.packing.tokenizer)?;
+        Ok(configured_rules(&self.secrets, &counter))
+    }
+
     fn readonly_search_diagnostic(&self) -> String {
         let index_dir = self.ms_root.join("index");
-        let writer_lock = index_dir.join(".tantivy-writer.lock");
 
         let cause = if dir_is_writable(&index_dir) {
-            if writer_lock.exists() {
-                format!(
-                    "another `ms` process is holding the search-index writer lock at {}. \
-                     A running `ms mcp serve` keeps this lock for its entire lifetime; \
-                     stop it (or any other `ms` writing to this index) and retry. \
-                     If you are certain no such process is running, the lock is stale \
-                     and can be deleted.",
-                    writer_lock.display()
-                )
-            } else {
-                format!(
-                    "the search index could not be opened for writing: {}",
-                    index_dir.display()
-                )
-            }
+            format!(
+                "the search index could not be opened for writing: {}",
+                index_dir.display()
+            )
         } else {
             format!(
                 "the index directory is on a read-only filesystem or you lack \
@@ -231,7 +434,9 @@ impl AppContext {
         )
     }
 
-    fn find_ms_root() -> Result<PathBuf> {
+    /// Locate the ms root: `$MS_ROOT`, the nearest `.ms` directory, or the
+    /// global data dir.
+    pub fn find_ms_root() -> Result<PathBuf> {
         if let Ok(root) = std::env::var("MS_ROOT") {
             return Ok(PathBuf::from(root));
         }
@@ -276,7 +481,8 @@ fn default_config_path(ms_root: &Path) -> PathBuf {
     } else {
         dirs::config_dir()
             .unwrap_or_else(|| ms_root.to_path_buf())
-            .join("ms/config.toml")
+            .join("ms")
+            .join("config.toml")
     }
 }
 
@@ -295,7 +501,6 @@ fn find_upwards(start: &Path, name: &str) -> Result<Option<PathBuf>> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::storage::sqlite::SkillRecord;
 
     /// A minimal, self-consistent skill row for exercising the `skills` table
     /// (the root table — no foreign keys — so it is safe to insert standalone).
@@ -323,26 +528,53 @@ mod tests {
         }
     }
 
-    /// Build an `AppContext` rooted at `ms_root`, opening the DB, git archive,
-    /// and search index there (mirrors the store fields of `from_cli`).
-    fn ctx_at(ms_root: &Path) -> AppContext {
-        std::fs::create_dir_all(ms_root).unwrap();
-        let index_path = ms_root.join("index");
-        AppContext {
-            ms_root: ms_root.to_path_buf(),
-            config_path: ms_root.join("config.toml"),
-            config: Config::default(),
-            db: Arc::new(Database::open(ms_root.join("ms.db")).unwrap()),
-            git: Arc::new(GitArchive::open(ms_root.join("archive")).unwrap()),
-            search: Arc::new(
-                SearchIndex::open(&index_path)
-                    .or_else(|_| SearchIndex::open_readonly(&index_path))
-                    .unwrap(),
-            ),
-            robot_mode: false,
-            output_format: OutputFormat::default(),
-            verbosity: 0,
-        }
+    #[test]
+    fn bandit_state_is_loaded_with_the_context() {
+        let tmp = tempfile::tempdir().unwrap();
+        let ctx = AppContext::for_tests(tmp.path());
+        ctx.update_bandit(None, |bandit| bandit.register_skill("rust-errors"))
+            .unwrap();
+        assert!(ctx.bandit(None).unwrap().has_skill("rust-errors"));
+
+        // A later process starts from the persisted state
+        let next = AppContext::for_tests(tmp.path());
+        assert!(next.bandit(None).unwrap().has_skill("rust-errors"));
+        next.reset_bandit(None, 10).unwrap();
+        assert!(!next.bandit(None).unwrap().has_skill("rust-errors"));
+    }
+
+    #[test]
+    fn skill_references_resolve_through_aliases() {
+        let tmp = tempfile::tempdir().unwrap();
+        let ctx = AppContext::for_tests(tmp.path());
+        ctx.db
+            .upsert_skill(&sample_skill("deploy-checklist"))
+            .unwrap();
+        let mut legacy = sample_skill("deploy-legacy");
+        legacy.is_deprecated = true;
+        ctx.db.upsert_skill(&legacy).unwrap();
+        let at = "2026-01-01T00:00:00Z";
+        ctx.db
+            .upsert_alias("dc", "deploy-checklist", "short", at)
+            .unwrap();
+        // Left behind by `ms prune`: the deprecated id forwards to its
+        // replacement, and an older alias still points at the deprecated id.
+        ctx.db
+            .upsert_alias("deploy-legacy", "deploy-checklist", "deprecated", at)
+            .unwrap();
+        ctx.db
+            .upsert_alias("deploy", "deploy-legacy", "short", at)
+            .unwrap();
+
+        assert_eq!(ctx.resolve_skill_id("dc").unwrap(), "deploy-checklist");
+        assert_eq!(ctx.resolve_skill_id("DC").unwrap(), "deploy-checklist");
+        assert_eq!(ctx.resolve_skill_id("deploy").unwrap(), "deploy-checklist");
+        assert_eq!(
+            ctx.resolve_skill_id("Deploy-Checklist").unwrap(),
+            "deploy-checklist"
+        );
+        assert_eq!(ctx.canonical_skill_id("removed").unwrap(), "removed");
+        assert_eq!(ctx.canonical_skill_id("dc").unwrap(), "deploy-checklist");
     }
 
     /// Issue #135: after the state directory is renamed out from under a
@@ -355,7 +587,7 @@ mod tests {
         let state = tmp.path().join("state");
 
         // Original store A, with one skill written through it.
-        let mut ctx = ctx_at(&state);
+        let mut ctx = AppContext::for_tests(&state);
         ctx.db.upsert_skill(&sample_skill("skill-a")).unwrap();
         assert_eq!(ctx.db.list_skills(100, 0).unwrap().len(), 1);
 
@@ -369,7 +601,7 @@ mod tests {
         std::fs::rename(&state, &backup).unwrap();
         // Fresh, empty store B at the original path. Building it here also
         // ensures ms.db exists so the reopen does not skip on `db_present`.
-        drop(ctx_at(&state));
+        drop(AppContext::for_tests(&state));
 
         // The on-disk identity must now differ from what the context has open.
         let id_b = ctx.store_identity();
@@ -407,7 +639,7 @@ mod tests {
     fn store_identity_is_stable_without_rebuild() {
         let tmp = tempfile::tempdir().unwrap();
         let state = tmp.path().join("state");
-        let ctx = ctx_at(&state);
+        let ctx = AppContext::for_tests(&state);
 
         let before = ctx.store_identity();
         // An ordinary write must not change the store identity.
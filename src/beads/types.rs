@@ -390,6 +390,12 @@ impl UpdateIssueRequest {
         self
     }
 
+    /// Set the description.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
     /// Set the priority.
     #[must_use]
     pub const fn with_priority(mut self, priority: Priority) -> Self {
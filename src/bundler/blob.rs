@@ -48,6 +48,49 @@ impl BlobStore {
         self.blob_path(hash).map(|p| p.exists()).unwrap_or(false)
     }
 
+    /// Every stored blob as `(hash, size in bytes)`, sorted by hash.
+    pub fn list_blobs(&self) -> Result<Vec<(String, u64)>> {
+        let blobs = self.root.join("blobs");
+        let mut found = Vec::new();
+        let mut push = |hash: String, path: &Path| -> Result<()> {
+            let size = fs::metadata(path)
+                .map_err(|err| MsError::Config(format!("stat blob {}: {err}", path.display())))?
+                .len();
+            found.push((hash, size));
+            Ok(())
+        };
+        // Legacy flat layout: blobs/sha256:<hex>
+        for entry in read_dir_sorted(&blobs)? {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with("sha256:") && entry.path().is_file() {
+                push(name, &entry.path())?;
+            }
+        }
+        // Nested layout: blobs/sha256/<hex>
+        let nested = blobs.join("sha256");
+        if nested.is_dir() {
+            for entry in read_dir_sorted(&nested)? {
+                let hash = format!("sha256:{}", entry.file_name().to_string_lossy());
+                if entry.path().is_file() && self.blob_path(&hash).is_ok() {
+                    push(hash, &entry.path())?;
+                }
+            }
+        }
+        found.sort();
+        Ok(found)
+    }
+
+    /// Delete a blob, returning the bytes freed (0 if it was not stored).
+    pub fn remove_blob(&self, hash: &str) -> Result<u64> {
+        let path = self.blob_path(hash)?;
+        let Ok(meta) = fs::metadata(&path) else {
+            return Ok(0);
+        };
+        fs::remove_file(&path)
+            .map_err(|err| MsError::Config(format!("remove blob {}: {err}", path.display())))?;
+        Ok(meta.len())
+    }
+
     pub fn verify_blob(&self, hash: &str) -> Result<bool> {
         let data = self.read_blob(hash)?;
         Ok(hash == hash_bytes(&data))
@@ -181,6 +224,15 @@ pub(crate) fn collect_files_for_bundle(
     Ok(())
 }
 
+fn read_dir_sorted(dir: &Path) -> Result<Vec<fs::DirEntry>> {
+    let mut entries = fs::read_dir(dir)
+        .map_err(|err| MsError::Config(format!("read dir {}: {err}", dir.display())))?
+        .collect::<std::io::Result<Vec<_>>>()
+        .map_err(|err| MsError::Config(format!("read dir {}: {err}", dir.display())))?;
+    entries.sort_by_key(fs::DirEntry::file_name);
+    Ok(entries)
+}
+
 fn hash_bytes(bytes: &[u8]) -> String {
     let mut hasher = Sha256::new();
     hasher.update(bytes);
@@ -209,6 +261,22 @@ mod tests {
         assert!(store.verify_blob(&hash).unwrap());
     }
 
+    #[test]
+    fn list_and_remove_blobs() {
+        let dir = tempdir().unwrap();
+        let store = BlobStore::open(dir.path()).unwrap();
+        let a = store.write_blob(b"first").unwrap();
+        let b = store.write_blob(b"second blob").unwrap();
+
+        let mut expected = vec![(a.clone(), 5), (b.clone(), 11)];
+        expected.sort();
+        assert_eq!(store.list_blobs().unwrap(), expected);
+
+        assert_eq!(store.remove_blob(&a).unwrap(), 5);
+        assert_eq!(store.remove_blob(&a).unwrap(), 0);
+        assert_eq!(store.list_blobs().unwrap(), vec![(b, 11)]);
+    }
+
     #[test]
     fn blob_path_rejects_path_traversal() {
         let dir = tempdir().unwrap();
@@ -4,9 +4,12 @@ use std::collections::HashMap;
 use std::fs::OpenOptions;
 use std::path::{Component, Path, PathBuf};
 
+use semver::{Version, VersionReq};
+
 use crate::bundler::blob::BlobStore;
-use crate::bundler::manifest::SignatureVerifier;
+use crate::bundler::manifest::{BundleDependency, BundleManifest, SignatureVerifier};
 use crate::bundler::package::BundlePackage;
+use crate::bundler::registry::BundleRegistry;
 use crate::error::{MsError, Result};
 
 #[derive(Debug, Clone, serde::Serialize)]
@@ -16,6 +19,8 @@ pub struct InstallReport {
     pub skipped: Vec<String>,
     pub blobs_written: usize,
     pub signature_verified: bool,
+    /// Bundles installed first because this one depends on them
+    pub dependencies_installed: Vec<InstallReport>,
 }
 
 /// Options for bundle installation.
@@ -152,9 +157,226 @@ pub fn install_with_options<V: SignatureVerifier>(
         skipped,
         blobs_written,
         signature_verified,
+        dependencies_installed: Vec::new(),
+    })
+}
+
+/// How a declared dependency compares with what is installed.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum DependencyStatus {
+    /// An installed version matches the requirement
+    Satisfied { installed: String },
+    /// Not installed
+    Missing,
+    /// Installed, but at a version outside the requirement
+    Conflict { installed: String },
+}
+
+/// One entry of `BundleManifest.dependencies` checked against the registry.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct DependencyCheck {
+    pub id: String,
+    pub required: String,
+    pub optional: bool,
+    pub source: Option<String>,
+    #[serde(flatten)]
+    pub status: DependencyStatus,
+}
+
+impl DependencyCheck {
+    /// Whether this prevents the dependent bundle from installing.
+    #[must_use]
+    pub const fn is_blocking(&self) -> bool {
+        match self.status {
+            DependencyStatus::Satisfied { .. } => false,
+            DependencyStatus::Missing => !self.optional,
+            DependencyStatus::Conflict { .. } => true,
+        }
+    }
+}
+
+impl std::fmt::Display for DependencyCheck {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.status {
+            DependencyStatus::Satisfied { installed } => {
+                write!(
+                    f,
+                    "{} {} (satisfied by {installed})",
+                    self.id, self.required
+                )
+            }
+            DependencyStatus::Missing => write!(f, "{} {} (missing)", self.id, self.required),
+            DependencyStatus::Conflict { installed } => write!(
+                f,
+                "{} {} (conflict: {installed} is installed)",
+                self.id, self.required
+            ),
+        }
+    }
+}
+
+/// Check each dependency declared by `manifest` against installed bundles.
+pub fn check_dependencies(
+    manifest: &BundleManifest,
+    registry: &BundleRegistry,
+) -> Result<Vec<DependencyCheck>> {
+    manifest
+        .dependencies
+        .iter()
+        .map(|dep| {
+            let installed = registry.get(&dep.id).map(|bundle| bundle.version.as_str());
+            check_dependency(dep, installed)
+        })
+        .collect()
+}
+
+fn check_dependency(dep: &BundleDependency, installed: Option<&str>) -> Result<DependencyCheck> {
+    let req = VersionReq::parse(&dep.version).map_err(|err| {
+        MsError::ValidationFailed(format!(
+            "invalid version requirement for dependency {}: {err}",
+            dep.id
+        ))
+    })?;
+    let status = match installed {
+        None => DependencyStatus::Missing,
+        Some(version) if Version::parse(version).is_ok_and(|v| req.matches(&v)) => {
+            DependencyStatus::Satisfied {
+                installed: version.to_string(),
+            }
+        }
+        Some(version) => DependencyStatus::Conflict {
+            installed: version.to_string(),
+        },
+    };
+    Ok(DependencyCheck {
+        id: dep.id.clone(),
+        required: dep.version.clone(),
+        optional: dep.optional,
+        source: dep.source.clone(),
+        status,
     })
 }
 
+/// Fetches the bundle a dependency's `source` points at.
+pub type DependencyFetcher<'a> = dyn FnMut(&BundleDependency, &str) -> Result<BundlePackage> + 'a;
+
+/// Resolve the dependency graph of `manifest` against installed bundles.
+///
+/// Without `fetch`, every required dependency must already be installed at a
+/// matching version. With it, missing required dependencies that declare a
+/// `source` are fetched (along with their own dependencies) and returned in
+/// install order, dependencies first. Cycles are reported with their path;
+/// any other unresolved dependency fails the whole resolution, listing all
+/// of them at once.
+pub fn resolve_dependencies(
+    manifest: &BundleManifest,
+    registry: &BundleRegistry,
+    fetch: Option<&mut DependencyFetcher<'_>>,
+) -> Result<Vec<BundlePackage>> {
+    let mut resolver = Resolver {
+        registry,
+        fetch,
+        stack: Vec::new(),
+        planned: HashMap::new(),
+        order: Vec::new(),
+        problems: Vec::new(),
+    };
+    resolver.visit(manifest)?;
+    if resolver.problems.is_empty() {
+        return Ok(resolver.order);
+    }
+    let hint = if resolver.fetch.is_none()
+        && manifest
+            .dependencies
+            .iter()
+            .any(|dep| dep.source.is_some() && registry.get(&dep.id).is_none())
+    {
+        " (dependencies that declare a source can be installed with --with-deps)"
+    } else {
+        ""
+    };
+    Err(MsError::ValidationFailed(format!(
+        "unresolved dependencies for bundle {}: {}{hint}",
+        manifest.bundle.id,
+        resolver.problems.join("; ")
+    )))
+}
+
+struct Resolver<'r, 'f> {
+    registry: &'r BundleRegistry,
+    fetch: Option<&'r mut DependencyFetcher<'f>>,
+    /// Bundles on the current path from the root, for cycle reporting
+    stack: Vec<String>,
+    /// Fetched bundles by id, with their versions
+    planned: HashMap<String, String>,
+    order: Vec<BundlePackage>,
+    problems: Vec<String>,
+}
+
+impl Resolver<'_, '_> {
+    fn visit(&mut self, manifest: &BundleManifest) -> Result<()> {
+        self.stack.push(manifest.bundle.id.clone());
+        for dep in &manifest.dependencies {
+            if let Some(pos) = self.stack.iter().position(|id| *id == dep.id) {
+                let mut path = self.stack[pos..].to_vec();
+                path.push(dep.id.clone());
+                return Err(MsError::ValidationFailed(format!(
+                    "bundle dependency cycle: {}",
+                    path.join(" -> ")
+                )));
+            }
+
+            let installed = self
+                .registry
+                .get(&dep.id)
+                .map(|bundle| bundle.version.as_str())
+                .or_else(|| self.planned.get(&dep.id).map(String::as_str));
+            let check = check_dependency(dep, installed)?;
+            match check.status {
+                DependencyStatus::Satisfied { .. } => continue,
+                DependencyStatus::Conflict { .. } => {
+                    self.problems
+                        .push(format!("{} requires {check}", manifest.bundle.id));
+                    continue;
+                }
+                DependencyStatus::Missing if dep.optional => continue,
+                DependencyStatus::Missing => {}
+            }
+
+            let (Some(fetch), Some(source)) = (self.fetch.as_deref_mut(), dep.source.as_deref())
+            else {
+                self.problems
+                    .push(format!("{} requires {check}", manifest.bundle.id));
+                continue;
+            };
+            let package = fetch(dep, source)?;
+            let fetched = &package.manifest.bundle;
+            if fetched.id != dep.id {
+                self.problems.push(format!(
+                    "{source} provides bundle {} instead of {}",
+                    fetched.id, dep.id
+                ));
+                continue;
+            }
+            let check = check_dependency(dep, Some(&fetched.version))?;
+            if check.is_blocking() {
+                self.problems.push(format!(
+                    "{} requires {} {} but {source} provides {}",
+                    manifest.bundle.id, dep.id, dep.version, fetched.version
+                ));
+                continue;
+            }
+
+            self.planned.insert(dep.id.clone(), fetched.version.clone());
+            self.visit(&package.manifest)?;
+            self.order.push(package);
+        }
+        self.stack.pop();
+        Ok(())
+    }
+}
+
 fn perform_install(target: &Path, bytes: &[u8]) -> Result<()> {
     // Ensure parent directory exists
     let parent = target.parent().unwrap_or_else(|| Path::new("."));
@@ -403,7 +625,7 @@ fn read_slice<'a>(input: &'a [u8], cursor: &mut usize, len: usize) -> Result<&'a
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::bundler::manifest::{BundleInfo, BundleManifest, BundledSkill};
+    use crate::bundler::manifest::{BundleInfo, BundledSkill};
     use crate::bundler::package::Bundle;
     use tempfile::tempdir;
 
@@ -432,6 +654,8 @@ mod tests {
                 version: Some("1.0.0".to_string()),
                 hash: None,
                 optional: false,
+                changelog: Vec::new(),
+                resources: Vec::new(),
             }],
             dependencies: vec![],
             checksum: None,
@@ -448,6 +672,153 @@ mod tests {
         assert!(installed_path.exists());
     }
 
+    fn dep_package(root: &Path, id: &str, version: &str, deps: &[(&str, &str)]) -> BundlePackage {
+        let skill_dir = root.join(id).join("skills/by-id").join(id);
+        std::fs::create_dir_all(&skill_dir).unwrap();
+        std::fs::write(skill_dir.join("SKILL.md"), id).unwrap();
+        let manifest = BundleManifest {
+            bundle: BundleInfo {
+                id: id.to_string(),
+                name: id.to_string(),
+                version: version.to_string(),
+                description: None,
+                authors: vec![],
+                license: None,
+                repository: None,
+                keywords: vec![],
+                ms_version: None,
+            },
+            skills: vec![BundledSkill {
+                name: id.to_string(),
+                path: PathBuf::from("skills/by-id").join(id),
+                version: None,
+                hash: None,
+                optional: false,
+                changelog: Vec::new(),
+                resources: Vec::new(),
+            }],
+            dependencies: deps
+                .iter()
+                .map(|(dep, req)| BundleDependency {
+                    id: (*dep).to_string(),
+                    version: (*req).to_string(),
+                    optional: false,
+                    source: Some(format!("./{dep}.msb")),
+                })
+                .collect(),
+            checksum: None,
+            signatures: vec![],
+        };
+        Bundle::new(manifest, root.join(id)).package().unwrap()
+    }
+
+    fn installed(registry: &mut BundleRegistry, id: &str, version: &str) {
+        registry
+            .register(crate::bundler::registry::InstalledBundle {
+                id: id.to_string(),
+                version: version.to_string(),
+                source: crate::bundler::registry::InstallSource::File {
+                    path: format!("./{id}.msb"),
+                },
+                installed_at: chrono::Utc::now(),
+                skills: vec![id.to_string()],
+                checksum: None,
+                blobs: Vec::new(),
+                pinned_key: None,
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn dependencies_are_checked_against_installed_bundles() {
+        let dir = tempdir().unwrap();
+        let mut registry = BundleRegistry::open(dir.path()).unwrap();
+        installed(&mut registry, "base", "1.4.0");
+        installed(&mut registry, "old", "0.9.0");
+        let package = dep_package(
+            dir.path(),
+            "app",
+            "1.0.0",
+            &[("base", "^1.2"), ("old", "^1.0"), ("absent", "*")],
+        );
+
+        let checks = check_dependencies(&package.manifest, &registry).unwrap();
+        let statuses: Vec<_> = checks.iter().map(|check| check.status.clone()).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                DependencyStatus::Satisfied {
+                    installed: "1.4.0".to_string()
+                },
+                DependencyStatus::Conflict {
+                    installed: "0.9.0".to_string()
+                },
+                DependencyStatus::Missing,
+            ]
+        );
+
+        let err = resolve_dependencies(&package.manifest, &registry, None)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("old ^1.0 (conflict: 0.9.0 is installed)"),
+            "{err}"
+        );
+        assert!(err.contains("absent * (missing)"), "{err}");
+        assert!(err.contains("--with-deps"), "{err}");
+    }
+
+    #[test]
+    fn with_fetch_dependencies_resolve_in_topological_order() {
+        let dir = tempdir().unwrap();
+        let registry = BundleRegistry::open(dir.path()).unwrap();
+        let app = dep_package(dir.path(), "app", "1.0.0", &[("web", "^2"), ("core", "^1")]);
+        let mut fetched = Vec::new();
+        let root = dir.path().to_path_buf();
+        let mut fetch = |dep: &BundleDependency, _source: &str| -> Result<BundlePackage> {
+            fetched.push(dep.id.clone());
+            Ok(match dep.id.as_str() {
+                "web" => dep_package(&root, "web", "2.1.0", &[("core", "^1.1")]),
+                _ => dep_package(&root, "core", "1.3.0", &[]),
+            })
+        };
+
+        let order = resolve_dependencies(&app.manifest, &registry, Some(&mut fetch)).unwrap();
+        let ids: Vec<_> = order
+            .iter()
+            .map(|p| p.manifest.bundle.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["core", "web"]);
+        // core is fetched once even though two bundles depend on it
+        assert_eq!(fetched, vec!["web".to_string(), "core".to_string()]);
+
+        let install_root = tempdir().unwrap();
+        let reports: Vec<_> = order
+            .iter()
+            .map(|package| install(package, install_root.path(), &[]).unwrap())
+            .collect();
+        assert_eq!(reports[0].installed, vec!["core".to_string()]);
+    }
+
+    #[test]
+    fn dependency_cycles_report_their_path() {
+        let dir = tempdir().unwrap();
+        let registry = BundleRegistry::open(dir.path()).unwrap();
+        let app = dep_package(dir.path(), "app", "1.0.0", &[("a", "*")]);
+        let root = dir.path().to_path_buf();
+        let mut fetch = |dep: &BundleDependency, _source: &str| -> Result<BundlePackage> {
+            Ok(match dep.id.as_str() {
+                "a" => dep_package(&root, "a", "1.0.0", &[("b", "*")]),
+                _ => dep_package(&root, "b", "1.0.0", &[("a", "*")]),
+            })
+        };
+
+        let err = resolve_dependencies(&app.manifest, &registry, Some(&mut fetch))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("cycle: a -> b -> a"), "{err}");
+    }
+
     #[test]
     fn ensure_safe_id_blocks_path_traversal() {
         // Path traversal with ..
@@ -532,6 +903,8 @@ mod tests {
                 version: Some("1.0.0".to_string()),
                 hash: None,
                 optional: false,
+                changelog: Vec::new(),
+                resources: Vec::new(),
             }],
             dependencies: vec![],
             checksum: None,
@@ -587,6 +960,8 @@ mod tests {
                 version: Some("1.0.0".to_string()),
                 hash: None,
                 optional: false,
+                changelog: Vec::new(),
+                resources: Vec::new(),
             }],
             dependencies: vec![],
             checksum: None,
@@ -229,6 +229,26 @@ pub fn hash_directory(path: &Path) -> Result<HashMap<PathBuf, String>> {
     Ok(hashes)
 }
 
+/// Whether the file at `path` hashes to `expected_hash` once CRLF line
+/// endings become LF, so a checkout that converted line endings (git
+/// `core.autocrlf` on Windows) does not count as a local edit.
+fn matches_with_lf_endings(path: &Path, expected_hash: &str) -> bool {
+    let Ok(content) = std::fs::read(path) else {
+        return false;
+    };
+    if !content.windows(2).any(|pair| pair == b"\r\n") {
+        return false;
+    }
+    let mut normalized = Vec::with_capacity(content.len());
+    for (i, &byte) in content.iter().enumerate() {
+        if byte == b'\r' && content.get(i + 1) == Some(&b'\n') {
+            continue;
+        }
+        normalized.push(byte);
+    }
+    hash_bytes(&normalized) == expected_hash
+}
+
 /// Detect local modifications in a skill directory
 pub fn detect_modifications(
     skill_path: &Path,
@@ -244,7 +264,9 @@ pub fn detect_modifications(
         let file_path = skill_path.join(rel_path);
 
         if let Some(current_hash) = current_hashes.get(rel_path) {
-            let status = if current_hash == expected_hash {
+            let status = if current_hash == expected_hash
+                || matches_with_lf_endings(&file_path, expected_hash)
+            {
                 summary.clean += 1;
                 ModificationStatus::Clean
             } else {
@@ -556,6 +578,23 @@ mod tests {
         assert_eq!(report.summary.modified, 1);
     }
 
+    #[test]
+    fn test_detect_modifications_ignores_crlf_checkout() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("SKILL.md"), "# Skill\r\n\r\nBody\r\n").unwrap();
+
+        let mut expected = HashMap::new();
+        expected.insert(PathBuf::from("SKILL.md"), hash_bytes(b"# Skill\n\nBody\n"));
+
+        let report = detect_modifications(dir.path(), "test-skill", &expected).unwrap();
+        assert_eq!(report.status, ModificationStatus::Clean);
+
+        // A real edit is still one
+        std::fs::write(dir.path().join("SKILL.md"), "# Skill\r\n\r\nEdited\r\n").unwrap();
+        let report = detect_modifications(dir.path(), "test-skill", &expected).unwrap();
+        assert_eq!(report.status, ModificationStatus::Modified);
+    }
+
     #[test]
     fn test_detect_modifications_new_file() {
         let dir = tempdir().unwrap();
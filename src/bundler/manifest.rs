@@ -47,6 +47,33 @@ pub struct BundledSkill {
     pub hash: Option<String>,
     #[serde(default)]
     pub optional: bool,
+    /// What changed in the skill since an earlier bundle, newest release
+    /// first (`ms bundle create --changelog-since`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changelog: Vec<SkillReleaseNotes>,
+    /// Files that travel with the skill, as stored in its blob
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resources: Vec<BundledResource>,
+}
+
+/// A resource file inside a skill's blob, keyed by its path relative to the
+/// skill directory.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BundledResource {
+    pub path: String,
+    pub size: u64,
+    /// `sha256:<hex>` of the file content
+    pub hash: String,
+}
+
+/// Changes one skill release made, as one-line notes.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SkillReleaseNotes {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Date of the newest change in the release (YYYY-MM-DD)
+    pub date: String,
+    pub notes: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -55,6 +82,9 @@ pub struct BundleDependency {
     pub version: String,
     #[serde(default)]
     pub optional: bool,
+    /// Where to fetch the dependency from (same forms as `ms bundle install`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -151,6 +181,14 @@ impl BundleManifest {
                     )));
                 }
             }
+            for resource in &skill.resources {
+                if crate::core::resources::normalize_link(&resource.path).is_err() {
+                    return Err(MsError::ValidationFailed(format!(
+                        "resource path must stay inside the skill for {}: {}",
+                        skill.name, resource.path
+                    )));
+                }
+            }
         }
 
         let mut seen_deps = HashSet::new();
@@ -616,12 +654,45 @@ checksum = "sha256:abc123"
             version: Some("1.2.0".to_string()),
             hash: Some("sha256:abc123".to_string()),
             optional: false,
+            changelog: Vec::new(),
+            resources: Vec::new(),
         });
         let err = manifest.validate().unwrap_err();
         let message = err.to_string();
         assert!(message.contains("duplicate skill name"));
     }
 
+    #[test]
+    fn validate_rejects_escaping_resource_paths() {
+        let mut manifest = BundleManifest::from_toml_str(SAMPLE_TOML).unwrap();
+        manifest.skills[0].resources.push(BundledResource {
+            path: "resources/template.md".to_string(),
+            size: 4,
+            hash: "sha256:abc123".to_string(),
+        });
+        manifest.validate().unwrap();
+        let toml = manifest.to_toml_string().unwrap();
+        assert_eq!(BundleManifest::from_toml_str(&toml).unwrap(), manifest);
+
+        manifest.skills[0].resources[0].path = "../../etc/passwd".to_string();
+        let err = manifest.validate().unwrap_err();
+        assert!(err.to_string().contains("inside the skill"));
+    }
+
+    #[test]
+    fn changelog_roundtrips_and_is_omitted_when_empty() {
+        let mut manifest = BundleManifest::from_toml_str(SAMPLE_TOML).unwrap();
+        assert!(!manifest.to_toml_string().unwrap().contains("changelog"));
+
+        manifest.skills[0].changelog.push(SkillReleaseNotes {
+            version: Some("1.3.0".to_string()),
+            date: "2026-05-01".to_string(),
+            notes: vec!["+ rule rule-2: Pin provider versions".to_string()],
+        });
+        let reparsed = BundleManifest::from_toml_str(&manifest.to_toml_string().unwrap()).unwrap();
+        assert_eq!(manifest, reparsed);
+    }
+
     #[test]
     fn validate_rejects_invalid_versions() {
         let mut manifest = BundleManifest::from_toml_str(SAMPLE_TOML).unwrap();
@@ -634,6 +705,7 @@ checksum = "sha256:abc123"
             id: "bad-dep".to_string(),
             version: "nope".to_string(),
             optional: false,
+            source: None,
         });
         let err = manifest.validate().unwrap_err();
         assert!(err.to_string().contains("dependencies.version"));
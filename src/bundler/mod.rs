@@ -6,18 +6,26 @@ pub mod install;
 pub mod local_safety;
 pub mod manifest;
 pub mod package;
+pub mod pinning;
 pub mod registry;
+pub mod remote;
+pub mod select;
 
 pub use blob::BlobStore;
-pub use install::{InstallOptions, InstallReport, install, install_with_options};
+pub use install::{
+    DependencyCheck, DependencyFetcher, DependencyStatus, InstallOptions, InstallReport,
+    check_dependencies, install, install_with_options, resolve_dependencies,
+};
 pub use local_safety::{
     ConflictDetail, ConflictStrategy, FileStatus, ModificationStatus, ModificationSummary,
     ResolutionResult, SkillModificationReport, detect_conflicts, detect_modifications,
     hash_directory, hash_file,
 };
 pub use manifest::{
-    BundleDependency, BundleInfo, BundleManifest, BundleSignature, BundledSkill, Ed25519Signer,
-    Ed25519Verifier, SignatureVerifier,
+    BundleDependency, BundleInfo, BundleManifest, BundleSignature, BundledResource, BundledSkill,
+    Ed25519Signer, Ed25519Verifier, SignatureVerifier, SkillReleaseNotes,
 };
 pub use package::{Bundle, BundleBlob, BundlePackage, missing_blobs};
+pub use pinning::{KeyCheck, PinnedKey};
 pub use registry::{BundleRegistry, InstallSource, InstalledBundle, ParsedSource};
+pub use select::{DependencyClosure, SkillDependency, dependency_closure};
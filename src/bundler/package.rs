@@ -193,6 +193,7 @@ impl BundlePackage {
                 }
             }
         }
+        self.verify_resources()?;
 
         if let Some(expected) = self.manifest.checksum.as_ref() {
             let actual = bundle_checksum(&self.manifest, &self.blobs)?;
@@ -206,6 +207,41 @@ impl BundlePackage {
         Ok(())
     }
 
+    /// Check that every resource a skill declares is in its blob with the
+    /// declared size and hash.
+    fn verify_resources(&self) -> Result<()> {
+        for skill in &self.manifest.skills {
+            if skill.resources.is_empty() {
+                continue;
+            }
+            let blob = skill
+                .hash
+                .as_ref()
+                .and_then(|hash| self.blobs.iter().find(|blob| &blob.hash == hash))
+                .ok_or_else(|| {
+                    MsError::ValidationFailed(format!(
+                        "skill {} declares resources but has no blob",
+                        skill.name
+                    ))
+                })?;
+            let entries = blob_entries(&blob.bytes)?;
+            for resource in &skill.resources {
+                let matches = entries.iter().any(|(path, data)| {
+                    path == &resource.path
+                        && data.len() as u64 == resource.size
+                        && hash_bytes(data) == resource.hash
+                });
+                if !matches {
+                    return Err(MsError::ValidationFailed(format!(
+                        "resource {} of skill {} is missing or altered",
+                        resource.path, skill.name
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub fn verify_signatures(&self, verifier: &impl SignatureVerifier) -> Result<()> {
         if self.manifest.signatures.is_empty() {
             return Ok(());
@@ -214,6 +250,12 @@ impl BundlePackage {
         self.manifest.verify_signatures(&payload, verifier)
     }
 
+    /// Hashes of the blobs in this package.
+    #[must_use]
+    pub fn blob_hashes(&self) -> Vec<String> {
+        self.blobs.iter().map(|blob| blob.hash.clone()).collect()
+    }
+
     pub fn write_missing_blobs(&self, store: &BlobStore) -> Result<usize> {
         let mut written = 0;
         for blob in &self.blobs {
@@ -267,6 +309,25 @@ fn build_blob_bytes(path: &Path) -> Result<Vec<u8>> {
     Ok(out)
 }
 
+/// Files of a directory blob as (relative path, content) pairs.
+pub(crate) fn blob_entries(bytes: &[u8]) -> Result<Vec<(String, &[u8])>> {
+    let mut cursor = 0;
+    let mut entries = Vec::new();
+    while cursor < bytes.len() {
+        let name_len = usize::try_from(read_u64(bytes, &mut cursor)?)
+            .map_err(|_| MsError::ValidationFailed("bundle parse overflow".to_string()))?;
+        let name =
+            std::str::from_utf8(read_slice(bytes, &mut cursor, name_len)?).map_err(|_| {
+                MsError::ValidationFailed("bundle entry path is invalid UTF-8".to_string())
+            })?;
+        let data_len = usize::try_from(read_u64(bytes, &mut cursor)?)
+            .map_err(|_| MsError::ValidationFailed("bundle parse overflow".to_string()))?;
+        let data = read_slice(bytes, &mut cursor, data_len)?;
+        entries.push((name.to_string(), data));
+    }
+    Ok(entries)
+}
+
 fn bundle_checksum(manifest: &BundleManifest, blobs: &[BundleBlob]) -> Result<String> {
     let mut manifest = manifest.clone();
     manifest.checksum = None;
@@ -329,7 +390,7 @@ fn read_slice<'a>(input: &'a [u8], cursor: &mut usize, len: usize) -> Result<&'a
 mod tests {
     use super::*;
     use crate::bundler::BlobStore;
-    use crate::bundler::manifest::{BundleDependency, BundleInfo, BundledSkill};
+    use crate::bundler::manifest::{BundleDependency, BundleInfo, BundledResource, BundledSkill};
     use tempfile::tempdir;
 
     #[test]
@@ -357,11 +418,14 @@ mod tests {
                 version: Some("1.0.0".to_string()),
                 hash: None,
                 optional: false,
+                changelog: Vec::new(),
+                resources: Vec::new(),
             }],
             dependencies: vec![BundleDependency {
                 id: "dep".to_string(),
                 version: "^1.0".to_string(),
                 optional: true,
+                source: None,
             }],
             checksum: None,
             signatures: vec![],
@@ -399,11 +463,14 @@ mod tests {
                 version: Some("1.0.0".to_string()),
                 hash: None,
                 optional: false,
+                changelog: Vec::new(),
+                resources: Vec::new(),
             }],
             dependencies: vec![BundleDependency {
                 id: "dep".to_string(),
                 version: "^1.0".to_string(),
                 optional: true,
+                source: None,
             }],
             checksum: None,
             signatures: vec![],
@@ -443,6 +510,8 @@ mod tests {
                 version: Some("1.0.0".to_string()),
                 hash: None,
                 optional: false,
+                changelog: Vec::new(),
+                resources: Vec::new(),
             }],
             dependencies: vec![],
             checksum: None,
@@ -456,6 +525,62 @@ mod tests {
         parsed.verify().unwrap();
     }
 
+    #[test]
+    fn verify_checks_declared_resources_against_blob() {
+        let dir = tempdir().unwrap();
+        let skill_dir = dir.path().join("skill");
+        std::fs::create_dir_all(skill_dir.join("resources")).unwrap();
+        std::fs::write(skill_dir.join("SKILL.md"), "[t](resources/t.md)").unwrap();
+        std::fs::write(skill_dir.join("resources/t.md"), "# T").unwrap();
+
+        let resource = BundledResource {
+            path: "resources/t.md".to_string(),
+            size: 3,
+            hash: hash_bytes(b"# T"),
+        };
+        let manifest = BundleManifest {
+            bundle: BundleInfo {
+                id: "bundle".to_string(),
+                name: "Bundle".to_string(),
+                version: "1.0.0".to_string(),
+                description: None,
+                authors: vec![],
+                license: None,
+                repository: None,
+                keywords: vec![],
+                ms_version: None,
+            },
+            skills: vec![BundledSkill {
+                name: "skill".to_string(),
+                path: PathBuf::from("skill"),
+                version: Some("1.0.0".to_string()),
+                hash: None,
+                optional: false,
+                changelog: Vec::new(),
+                resources: vec![resource.clone()],
+            }],
+            dependencies: vec![],
+            checksum: None,
+            signatures: vec![],
+        };
+
+        let package = Bundle::new(manifest.clone(), dir.path()).package().unwrap();
+        let parsed = BundlePackage::from_bytes(&package.to_bytes().unwrap()).unwrap();
+        parsed.verify().unwrap();
+        let entries = blob_entries(&parsed.blobs[0].bytes).unwrap();
+        assert!(
+            entries
+                .iter()
+                .any(|(path, data)| path == "resources/t.md" && *data == b"# T")
+        );
+
+        let mut manifest = manifest;
+        manifest.skills[0].resources[0].hash = hash_bytes(b"other");
+        let package = Bundle::new(manifest, dir.path()).package().unwrap();
+        let err = package.verify().unwrap_err();
+        assert!(err.to_string().contains("resources/t.md"));
+    }
+
     #[test]
     fn rejects_oversized_manifest() {
         let mut bytes = Vec::new();
@@ -0,0 +1,245 @@
+//! Trust-on-first-use pinning of bundle signing keys.
+//!
+//! The first signed install of a bundle records which trusted key signed it
+//! in the registry. Updates must carry a valid signature from that same key;
+//! a bundle signed by another key (or no longer signed at all) is a key
+//! change, which `ms update --bundles` only accepts with `--accept-new-key`.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::bundler::manifest::{BundleManifest, Ed25519Verifier, SignatureVerifier};
+use crate::error::{MsError, Result};
+
+/// Signing key recorded for an installed bundle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PinnedKey {
+    pub key_id: String,
+    /// Hex-encoded Ed25519 public key.
+    pub public_key: String,
+}
+
+/// How a bundle's signatures relate to its pinned key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyCheck {
+    /// Validly signed by the pinned key, or, with nothing pinned yet, by a
+    /// trusted key that should now be pinned.
+    Pinned(PinnedKey),
+    /// Unsigned, with nothing pinned.
+    Unsigned,
+    /// Not signed by the pinned key.
+    Changed {
+        pinned: PinnedKey,
+        /// Key ids the bundle is signed with instead.
+        found: Vec<String>,
+    },
+}
+
+/// Check the signatures on `manifest` (over `payload`) against `pinned`.
+///
+/// With no pin, a signed bundle must verify against `trusted_keys`
+/// (`[bundles.trusted_keys]`) and the first trusted signer is returned for
+/// pinning. A signature by the pinned key that fails to verify is an error,
+/// not a key change.
+pub fn check_signing_key(
+    manifest: &BundleManifest,
+    payload: &[u8],
+    pinned: Option<&PinnedKey>,
+    trusted_keys: &BTreeMap<String, String>,
+) -> Result<KeyCheck> {
+    let Some(pin) = pinned else {
+        return trusted_signer(manifest, payload, trusted_keys);
+    };
+    let Some(signature) = manifest
+        .signatures
+        .iter()
+        .find(|signature| signature.key_id == pin.key_id)
+    else {
+        return Ok(KeyCheck::Changed {
+            pinned: pin.clone(),
+            found: manifest
+                .signatures
+                .iter()
+                .map(|signature| signature.key_id.clone())
+                .collect(),
+        });
+    };
+    let verifier = Ed25519Verifier::from_keys([(
+        pin.key_id.clone(),
+        decode_key(&pin.key_id, &pin.public_key)?,
+    )]);
+    verifier.verify(payload, signature)?;
+    Ok(KeyCheck::Pinned(pin.clone()))
+}
+
+/// The key to pin for a bundle whose signing key changed, once the user has
+/// accepted the change: a trusted key that validly signed it.
+pub fn accept_new_key(
+    manifest: &BundleManifest,
+    payload: &[u8],
+    trusted_keys: &BTreeMap<String, String>,
+) -> Result<PinnedKey> {
+    match trusted_signer(manifest, payload, trusted_keys)? {
+        KeyCheck::Pinned(key) => Ok(key),
+        KeyCheck::Unsigned | KeyCheck::Changed { .. } => Err(MsError::ValidationFailed(format!(
+            "bundle {} is unsigned; there is no new key to accept",
+            manifest.bundle.id
+        ))),
+    }
+}
+
+fn trusted_signer(
+    manifest: &BundleManifest,
+    payload: &[u8],
+    trusted_keys: &BTreeMap<String, String>,
+) -> Result<KeyCheck> {
+    if manifest.signatures.is_empty() {
+        return Ok(KeyCheck::Unsigned);
+    }
+    let mut verifier = Ed25519Verifier::new();
+    for (key_id, key) in trusted_keys {
+        verifier.add_key(key_id.clone(), decode_key(key_id, key)?);
+    }
+    manifest.verify_signatures(payload, &verifier)?;
+    let signature = manifest
+        .signatures
+        .iter()
+        .find(|signature| verifier.is_trusted(&signature.key_id))
+        .ok_or_else(|| {
+            MsError::ValidationFailed("no trusted signatures found on bundle".to_string())
+        })?;
+    Ok(KeyCheck::Pinned(PinnedKey {
+        key_id: signature.key_id.clone(),
+        public_key: trusted_keys[&signature.key_id].trim().to_lowercase(),
+    }))
+}
+
+fn decode_key(key_id: &str, key: &str) -> Result<Vec<u8>> {
+    hex::decode(key.trim())
+        .map_err(|err| MsError::Config(format!("signing key {key_id} is not valid hex: {err}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundler::manifest::BundleSignature;
+
+    const MANIFEST_TOML: &str = r#"
+[bundle]
+id = "rust-patterns"
+name = "Rust Coding Patterns"
+version = "1.1.0"
+
+[[skills]]
+name = "error-handling"
+path = "skills/error-handling"
+"#;
+
+    /// Hex public key and a signer for a fresh keypair.
+    fn keypair() -> (String, impl Fn(&[u8]) -> String) {
+        use ring::rand::SystemRandom;
+        use ring::signature::{Ed25519KeyPair, KeyPair};
+
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        let public_key = {
+            let keypair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+            hex::encode(keypair.public_key().as_ref())
+        };
+        let pkcs8 = pkcs8.as_ref().to_vec();
+        let sign = move |payload: &[u8]| {
+            let keypair = Ed25519KeyPair::from_pkcs8(&pkcs8).unwrap();
+            hex::encode(keypair.sign(payload).as_ref())
+        };
+        (public_key, sign)
+    }
+
+    fn signed(key_id: &str, signature: String) -> BundleManifest {
+        let mut manifest = BundleManifest::from_toml_str(MANIFEST_TOML).unwrap();
+        manifest.signatures.push(BundleSignature {
+            signer: "Publisher".to_string(),
+            key_id: key_id.to_string(),
+            signature,
+        });
+        manifest
+    }
+
+    #[test]
+    fn first_signed_install_pins_the_trusted_signer() {
+        let (public_key, sign) = keypair();
+        let payload = b"bundle v1";
+        let trusted = BTreeMap::from([("publisher".to_string(), public_key.clone())]);
+        let manifest = signed("publisher", sign(payload));
+
+        let check = check_signing_key(&manifest, payload, None, &trusted).unwrap();
+        assert_eq!(
+            check,
+            KeyCheck::Pinned(PinnedKey {
+                key_id: "publisher".to_string(),
+                public_key,
+            })
+        );
+
+        let unsigned = BundleManifest::from_toml_str(MANIFEST_TOML).unwrap();
+        assert_eq!(
+            check_signing_key(&unsigned, payload, None, &trusted).unwrap(),
+            KeyCheck::Unsigned
+        );
+        // Signed, but not by a trusted key
+        assert!(check_signing_key(&manifest, payload, None, &BTreeMap::new()).is_err());
+    }
+
+    #[test]
+    fn updates_verify_against_the_pin_not_the_config() {
+        let (public_key, sign) = keypair();
+        let pin = PinnedKey {
+            key_id: "publisher".to_string(),
+            public_key,
+        };
+        let payload = b"bundle v2";
+        let manifest = signed("publisher", sign(payload));
+
+        // The pin alone is enough, even once the key left the config
+        assert_eq!(
+            check_signing_key(&manifest, payload, Some(&pin), &BTreeMap::new()).unwrap(),
+            KeyCheck::Pinned(pin.clone())
+        );
+        // A bad signature by the pinned key is an error, not a key change
+        assert!(check_signing_key(&manifest, b"tampered", Some(&pin), &BTreeMap::new()).is_err());
+    }
+
+    #[test]
+    fn another_key_is_a_change_until_accepted() {
+        let (old_key, _) = keypair();
+        let (new_key, sign_new) = keypair();
+        let pin = PinnedKey {
+            key_id: "publisher".to_string(),
+            public_key: old_key,
+        };
+        let payload = b"bundle v3";
+        let manifest = signed("rotated", sign_new(payload));
+        let trusted = BTreeMap::from([("rotated".to_string(), new_key.clone())]);
+
+        assert_eq!(
+            check_signing_key(&manifest, payload, Some(&pin), &trusted).unwrap(),
+            KeyCheck::Changed {
+                pinned: pin.clone(),
+                found: vec!["rotated".to_string()],
+            }
+        );
+        assert_eq!(
+            accept_new_key(&manifest, payload, &trusted).unwrap(),
+            PinnedKey {
+                key_id: "rotated".to_string(),
+                public_key: new_key,
+            }
+        );
+
+        let unsigned = BundleManifest::from_toml_str(MANIFEST_TOML).unwrap();
+        assert!(matches!(
+            check_signing_key(&unsigned, payload, Some(&pin), &trusted).unwrap(),
+            KeyCheck::Changed { found, .. } if found.is_empty()
+        ));
+        assert!(accept_new_key(&unsigned, payload, &trusted).is_err());
+    }
+}
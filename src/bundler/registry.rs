@@ -6,7 +6,9 @@ use std::path::{Path, PathBuf};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::bundler::pinning::PinnedKey;
 use crate::error::{MsError, Result};
+use crate::utils::path::{expand_path, looks_like_path};
 
 /// Information about an installed bundle.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,6 +19,15 @@ pub struct InstalledBundle {
     pub installed_at: DateTime<Utc>,
     pub skills: Vec<String>,
     pub checksum: Option<String>,
+    /// Blob-store hashes written for this bundle; `ms prune --unused-blobs`
+    /// keeps every blob listed by an installed bundle. Empty for bundles
+    /// installed before blobs were tracked.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blobs: Vec<String>,
+    /// Key that signed the bundle when it was first installed signed;
+    /// updates must be signed by it (see [`crate::bundler::pinning`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinned_key: Option<PinnedKey>,
 }
 
 /// Source from which a bundle was installed.
@@ -31,9 +42,23 @@ pub enum InstallSource {
     File {
         path: String,
     },
+    /// Plain `http://` URL (and bundles recorded before `Https` existed)
     Url {
         url: String,
     },
+    /// Bundle archive on an HTTPS server, downloaded with size and time limits
+    Https {
+        url: String,
+    },
+}
+
+impl InstallSource {
+    /// Whether the source can offer newer versions of a bundle (GitHub
+    /// releases and HTTPS URLs); `ms update --bundles` skips the others.
+    #[must_use]
+    pub const fn supports_versioning(&self) -> bool {
+        matches!(self, Self::GitHub { .. } | Self::Https { .. })
+    }
 }
 
 impl std::fmt::Display for InstallSource {
@@ -47,7 +72,7 @@ impl std::fmt::Display for InstallSource {
                 Ok(())
             }
             Self::File { path } => write!(f, "file:{path}"),
-            Self::Url { url } => write!(f, "{url}"),
+            Self::Url { url } | Self::Https { url } => write!(f, "{url}"),
         }
     }
 }
@@ -69,7 +94,8 @@ impl ParsedSource {
     /// - `github:owner/repo/asset@tag` - GitHub repo, specific asset and tag
     /// - `owner/repo` - GitHub shorthand
     /// - `owner/repo@tag` - GitHub shorthand with tag
-    /// - `http://...` or `https://...` - Direct URL
+    /// - `https://...` - Bundle archive on an HTTPS server
+    /// - `http://...` - Direct URL
     /// - `./path` or `../path` or `/path` or `~/path` - Local file
     pub fn parse(input: &str) -> Result<Self> {
         // GitHub explicit prefix
@@ -78,7 +104,15 @@ impl ParsedSource {
         }
 
         // URL
-        if input.starts_with("http://") || input.starts_with("https://") {
+        if input.starts_with("https://") {
+            return Ok(Self {
+                source: InstallSource::Https {
+                    url: input.to_string(),
+                },
+                asset_name: None,
+            });
+        }
+        if input.starts_with("http://") {
             return Ok(Self {
                 source: InstallSource::Url {
                     url: input.to_string(),
@@ -88,8 +122,8 @@ impl ParsedSource {
         }
 
         // Local path
-        if Self::looks_like_path(input) {
-            let expanded = Self::expand_path(input);
+        if looks_like_path(input) {
+            let expanded = expand_path(input);
             return Ok(Self {
                 source: InstallSource::File {
                     path: expanded.display().to_string(),
@@ -100,7 +134,7 @@ impl ParsedSource {
 
         // Check if file exists locally (even without path prefix)
         if Path::new(input).exists() {
-            let expanded = Self::expand_path(input);
+            let expanded = expand_path(input);
             return Ok(Self {
                 source: InstallSource::File {
                     path: expanded.display().to_string(),
@@ -157,28 +191,6 @@ impl ParsedSource {
             asset_name: asset,
         })
     }
-
-    fn looks_like_path(input: &str) -> bool {
-        input == "~"
-            || input.starts_with("~/")
-            || input.starts_with("./")
-            || input.starts_with("../")
-            || input.starts_with('/')
-    }
-
-    fn expand_path(input: &str) -> PathBuf {
-        if input == "~" {
-            if let Some(home) = dirs::home_dir() {
-                return home;
-            }
-        }
-        if let Some(stripped) = input.strip_prefix("~/") {
-            if let Some(home) = dirs::home_dir() {
-                return home.join(stripped);
-            }
-        }
-        PathBuf::from(input)
-    }
 }
 
 /// Registry for tracking installed bundles.
@@ -196,17 +208,38 @@ impl BundleRegistry {
         std::fs::create_dir_all(&bundles_dir)?;
 
         let path = bundles_dir.join(Self::REGISTRY_FILE);
-        let bundles = if path.exists() {
-            let content = std::fs::read_to_string(&path)?;
-            serde_json::from_str(&content)
-                .map_err(|e| MsError::ValidationFailed(format!("invalid bundle registry: {e}")))?
-        } else {
-            HashMap::new()
-        };
+        let bundles = Self::read(&path)?;
 
         Ok(Self { path, bundles })
     }
 
+    /// Ids of the bundles installed under `root`, sorted, without creating
+    /// the registry when it does not exist yet.
+    pub fn installed_ids(root: &Path) -> Result<Vec<String>> {
+        let path = root.join("bundles").join(Self::REGISTRY_FILE);
+        let mut ids: Vec<String> = Self::read(&path)?.into_keys().collect();
+        ids.sort();
+        Ok(ids)
+    }
+
+    /// Whether a bundle installed under `root` provides `skill_id`, without
+    /// creating the registry when it does not exist yet.
+    pub fn provides_skill(root: &Path, skill_id: &str) -> Result<bool> {
+        let path = root.join("bundles").join(Self::REGISTRY_FILE);
+        Ok(Self::read(&path)?
+            .values()
+            .any(|bundle| bundle.skills.iter().any(|skill| skill == skill_id)))
+    }
+
+    fn read(path: &Path) -> Result<HashMap<String, InstalledBundle>> {
+        if !path.exists() {
+            return Ok(HashMap::new());
+        }
+        let content = std::fs::read_to_string(path)?;
+        serde_json::from_str(&content)
+            .map_err(|e| MsError::ValidationFailed(format!("invalid bundle registry: {e}")))
+    }
+
     /// Register an installed bundle.
     pub fn register(&mut self, bundle: InstalledBundle) -> Result<()> {
         self.bundles.insert(bundle.id.clone(), bundle);
@@ -333,11 +366,34 @@ mod tests {
     fn parse_url() {
         let parsed = ParsedSource::parse("https://example.com/bundle.msb").unwrap();
         match parsed.source {
-            InstallSource::Url { url } => {
+            InstallSource::Https { url } => {
                 assert_eq!(url, "https://example.com/bundle.msb");
             }
+            _ => panic!("expected HTTPS source"),
+        }
+    }
+
+    #[test]
+    fn parse_plain_http_url() {
+        let parsed = ParsedSource::parse("http://example.com/bundle.msb").unwrap();
+        match &parsed.source {
+            InstallSource::Url { url } => {
+                assert_eq!(url, "http://example.com/bundle.msb");
+            }
             _ => panic!("expected URL source"),
         }
+        assert_eq!(parsed.source.to_string(), "http://example.com/bundle.msb");
+    }
+
+    #[test]
+    fn https_source_roundtrips_through_registry_json() {
+        let source = InstallSource::Https {
+            url: "https://bundles.internal/foo-1.2.0.msb".to_string(),
+        };
+        let json = serde_json::to_string(&source).unwrap();
+        assert!(json.contains("\"type\":\"https\""));
+        let back: InstallSource = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.to_string(), "https://bundles.internal/foo-1.2.0.msb");
     }
 
     #[test]
@@ -0,0 +1,245 @@
+//! Bundle downloads from plain HTTPS servers.
+//!
+//! `ms bundle install https://host/bundles/foo-1.2.0.msb` streams the package
+//! into a staging file under `bundles/downloads/`, checks its checksum and
+//! signatures there, and only then renames it into place. A failed check drops
+//! the staging file, so nothing partial is left on disk.
+
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::bundler::package::BundlePackage;
+use crate::config::BundlesConfig;
+use crate::error::{MsError, Result};
+
+const USER_AGENT: &str = "ms-cli";
+
+/// Limits applied to a remote download.
+#[derive(Debug, Clone, Copy)]
+pub struct RemoteOptions {
+    pub timeout: Duration,
+    pub max_bytes: u64,
+}
+
+impl RemoteOptions {
+    #[must_use]
+    pub const fn from_config(config: &BundlesConfig) -> Self {
+        Self {
+            timeout: Duration::from_secs(config.download_timeout_secs),
+            max_bytes: config.max_download_mb.saturating_mul(1024 * 1024),
+        }
+    }
+}
+
+/// A downloaded bundle that passed its checks, and where the package was kept.
+#[derive(Debug)]
+pub struct FetchedBundle {
+    pub package: BundlePackage,
+    pub path: PathBuf,
+}
+
+/// Download a bundle over HTTPS into `staging_dir` and verify it.
+///
+/// `check` runs after the checksum is verified (typically the signature
+/// check); the package is moved to its final name only if it passes.
+pub fn fetch_verified<F>(
+    url: &str,
+    staging_dir: &Path,
+    options: &RemoteOptions,
+    check: F,
+) -> Result<FetchedBundle>
+where
+    F: FnOnce(&BundlePackage) -> Result<()>,
+{
+    if !url.to_ascii_lowercase().starts_with("https://") {
+        return Err(MsError::ValidationFailed(format!(
+            "remote bundles must use https: {url}"
+        )));
+    }
+    let client = reqwest::blocking::Client::builder()
+        .timeout(options.timeout)
+        .build()
+        .map_err(|err| MsError::Config(format!("http client: {err}")))?;
+    let response = client
+        .get(url)
+        .header("User-Agent", USER_AGENT)
+        .send()
+        .map_err(|err| MsError::Config(format!("download failed: {err}")))?;
+    if !response.status().is_success() {
+        return Err(MsError::ValidationFailed(format!(
+            "download failed: HTTP {}",
+            response.status()
+        )));
+    }
+    if let Some(content_length) = response.content_length() {
+        if content_length > options.max_bytes {
+            return Err(too_large(options.max_bytes));
+        }
+    }
+    stage_verified(response, staging_dir, options.max_bytes, check)
+}
+
+/// Stream `reader` into a staging file, verify it, and move it into place.
+fn stage_verified<R, F>(
+    reader: R,
+    staging_dir: &Path,
+    max_bytes: u64,
+    check: F,
+) -> Result<FetchedBundle>
+where
+    R: Read,
+    F: FnOnce(&BundlePackage) -> Result<()>,
+{
+    std::fs::create_dir_all(staging_dir)
+        .map_err(|err| MsError::Config(format!("create {}: {err}", staging_dir.display())))?;
+    // Dropped (and deleted) on every early return below
+    let mut staged = tempfile::Builder::new()
+        .prefix(".download-")
+        .suffix(".msb")
+        .tempfile_in(staging_dir)
+        .map_err(|err| MsError::Config(format!("create staging file: {err}")))?;
+
+    let written = std::io::copy(&mut reader.take(max_bytes + 1), staged.as_file_mut())
+        .map_err(|err| MsError::Config(format!("download read failed: {err}")))?;
+    if written > max_bytes {
+        return Err(too_large(max_bytes));
+    }
+    staged
+        .as_file_mut()
+        .flush()
+        .map_err(|err| MsError::Config(format!("write staging file: {err}")))?;
+
+    let bytes = std::fs::read(staged.path())
+        .map_err(|err| MsError::Config(format!("read staging file: {err}")))?;
+    let package = BundlePackage::from_bytes(&bytes)?;
+    package.verify()?;
+    check(&package)?;
+
+    let path = staging_dir.join(package_file_name(&package));
+    staged
+        .persist(&path)
+        .map_err(|err| MsError::Config(format!("move {}: {}", path.display(), err.error)))?;
+    Ok(FetchedBundle { package, path })
+}
+
+fn package_file_name(package: &BundlePackage) -> String {
+    let name = format!(
+        "{}-{}",
+        package.manifest.bundle.id, package.manifest.bundle.version
+    );
+    let safe: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{safe}.msb")
+}
+
+fn too_large(max_bytes: u64) -> MsError {
+    MsError::ValidationFailed(format!(
+        "download exceeded size limit ({} MB)",
+        max_bytes / (1024 * 1024)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundler::manifest::{BundleInfo, BundleManifest, BundledSkill};
+    use crate::bundler::package::Bundle;
+    use std::io::Cursor;
+
+    fn package_bytes() -> Vec<u8> {
+        let dir = tempfile::tempdir().unwrap();
+        let skill_dir = dir.path().join("demo");
+        std::fs::create_dir_all(&skill_dir).unwrap();
+        std::fs::write(skill_dir.join("SKILL.md"), "# Demo\n").unwrap();
+        let manifest = BundleManifest {
+            bundle: BundleInfo {
+                id: "demo".to_string(),
+                name: "Demo".to_string(),
+                version: "1.2.0".to_string(),
+                description: None,
+                authors: Vec::new(),
+                license: None,
+                repository: None,
+                keywords: Vec::new(),
+                ms_version: None,
+            },
+            skills: vec![BundledSkill {
+                name: "demo".to_string(),
+                path: "demo".into(),
+                version: None,
+                hash: None,
+                optional: false,
+                changelog: Vec::new(),
+                resources: Vec::new(),
+            }],
+            dependencies: Vec::new(),
+            checksum: None,
+            signatures: Vec::new(),
+        };
+        Bundle::new(manifest, dir.path())
+            .package()
+            .unwrap()
+            .to_bytes()
+            .unwrap()
+    }
+
+    fn entries(dir: &Path) -> Vec<String> {
+        std::fs::read_dir(dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn stages_and_moves_verified_package() {
+        let dir = tempfile::tempdir().unwrap();
+        let fetched = stage_verified(
+            Cursor::new(package_bytes()),
+            dir.path(),
+            1 << 20,
+            |_| Ok(()),
+        )
+        .unwrap();
+        assert_eq!(fetched.package.manifest.bundle.id, "demo");
+        assert_eq!(fetched.path, dir.path().join("demo-1.2.0.msb"));
+        assert_eq!(entries(dir.path()), vec!["demo-1.2.0.msb".to_string()]);
+    }
+
+    #[test]
+    fn failed_checks_leave_no_files() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let rejected = stage_verified(Cursor::new(package_bytes()), dir.path(), 1 << 20, |_| {
+            Err(MsError::ValidationFailed("untrusted".to_string()))
+        });
+        assert!(rejected.is_err());
+
+        let mut corrupt = package_bytes();
+        let last = corrupt.len() - 2;
+        corrupt[last] ^= 0xff;
+        assert!(stage_verified(Cursor::new(corrupt), dir.path(), 1 << 20, |_| Ok(())).is_err());
+
+        let oversized = stage_verified(Cursor::new(package_bytes()), dir.path(), 16, |_| Ok(()));
+        assert!(oversized.unwrap_err().to_string().contains("size limit"));
+
+        assert!(entries(dir.path()).is_empty());
+    }
+
+    #[test]
+    fn rejects_plain_http() {
+        let dir = tempfile::tempdir().unwrap();
+        let options = RemoteOptions::from_config(&BundlesConfig::default());
+        let err = fetch_verified("http://example.com/a.msb", dir.path(), &options, |_| Ok(()))
+            .unwrap_err();
+        assert!(err.to_string().contains("https"));
+    }
+}
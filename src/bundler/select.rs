@@ -0,0 +1,156 @@
+//! Self-contained skill selections for `ms bundle create --from-query`.
+//!
+//! A skill that `extends` or `includes` another cannot be loaded without it,
+//! so a bundle must carry the whole dependency closure of its skills.
+
+use std::collections::{HashSet, VecDeque};
+
+use serde::Serialize;
+
+use crate::core::resolution::SkillRepository;
+use crate::core::skill::SkillSpec;
+use crate::error::Result;
+
+/// A skill pulled in (or needed) by another skill of the selection.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SkillDependency {
+    pub skill_id: String,
+    /// The skill that extends or includes it.
+    pub required_by: String,
+}
+
+/// Dependencies of a selection that are not part of it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct DependencyClosure {
+    /// Dependencies found in the repository, in discovery order.
+    pub added: Vec<SkillDependency>,
+    /// Dependencies the repository does not have.
+    pub missing: Vec<SkillDependency>,
+}
+
+impl DependencyClosure {
+    /// `skill (required by other)` for each dependency in `deps`.
+    #[must_use]
+    pub fn describe(deps: &[SkillDependency]) -> String {
+        deps.iter()
+            .map(|dep| format!("{} (required by {})", dep.skill_id, dep.required_by))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Parents and includes of `spec`.
+fn direct_dependencies(spec: &SkillSpec) -> impl Iterator<Item = &str> {
+    spec.extends
+        .as_deref()
+        .into_iter()
+        .chain(spec.includes.iter().map(|include| include.skill.as_str()))
+}
+
+/// Walk `extends` and `includes` from `selected`, transitively.
+pub fn dependency_closure<R: SkillRepository + ?Sized>(
+    selected: &[String],
+    repository: &R,
+) -> Result<DependencyClosure> {
+    let mut seen: HashSet<String> = selected.iter().cloned().collect();
+    let mut queue: VecDeque<String> = selected.iter().cloned().collect();
+    let mut closure = DependencyClosure::default();
+
+    while let Some(skill_id) = queue.pop_front() {
+        let Some(spec) = repository.get(&skill_id)? else {
+            continue;
+        };
+        for dependency in direct_dependencies(&spec) {
+            if !seen.insert(dependency.to_string()) {
+                continue;
+            }
+            let dep = SkillDependency {
+                skill_id: dependency.to_string(),
+                required_by: skill_id.clone(),
+            };
+            if repository.get(dependency)?.is_some() {
+                queue.push_back(dependency.to_string());
+                closure.added.push(dep);
+            } else {
+                closure.missing.push(dep);
+            }
+        }
+    }
+    Ok(closure)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::core::skill::{IncludePosition, IncludeTarget, SkillInclude};
+
+    struct MapRepository(HashMap<String, SkillSpec>);
+
+    impl SkillRepository for MapRepository {
+        fn get(&self, skill_id: &str) -> Result<Option<SkillSpec>> {
+            Ok(self.0.get(skill_id).cloned())
+        }
+    }
+
+    fn skill(id: &str, extends: Option<&str>, includes: &[&str]) -> (String, SkillSpec) {
+        let mut spec = SkillSpec::new(id, id);
+        spec.extends = extends.map(str::to_string);
+        spec.includes = includes
+            .iter()
+            .map(|skill| SkillInclude {
+                skill: (*skill).to_string(),
+                into: IncludeTarget::Rules,
+                prefix: None,
+                sections: None,
+                position: IncludePosition::default(),
+            })
+            .collect();
+        (id.to_string(), spec)
+    }
+
+    #[test]
+    fn closure_follows_parents_and_includes_transitively() {
+        let repo = MapRepository(HashMap::from([
+            skill("rust-async", Some("rust-base"), &["tokio-pitfalls"]),
+            skill("rust-base", Some("style-base"), &[]),
+            skill("style-base", None, &[]),
+            skill("tokio-pitfalls", None, &["ghost"]),
+            skill("rust-errors", Some("rust-base"), &[]),
+        ]));
+        let selected = vec!["rust-async".to_string(), "rust-errors".to_string()];
+        let closure = dependency_closure(&selected, &repo).unwrap();
+
+        let added: Vec<(&str, &str)> = closure
+            .added
+            .iter()
+            .map(|dep| (dep.skill_id.as_str(), dep.required_by.as_str()))
+            .collect();
+        assert_eq!(
+            added,
+            [
+                ("rust-base", "rust-async"),
+                ("tokio-pitfalls", "rust-async"),
+                ("style-base", "rust-base"),
+            ]
+        );
+        assert_eq!(
+            DependencyClosure::describe(&closure.missing),
+            "ghost (required by tokio-pitfalls)"
+        );
+    }
+
+    #[test]
+    fn closed_selection_adds_nothing() {
+        let repo = MapRepository(HashMap::from([
+            skill("child", Some("parent"), &[]),
+            skill("parent", None, &[]),
+        ]));
+        let selected = vec!["child".to_string(), "parent".to_string()];
+        assert_eq!(
+            dependency_closure(&selected, &repo).unwrap(),
+            DependencyClosure::default()
+        );
+    }
+}
@@ -0,0 +1,771 @@
+//! Session transcript adapters
+//!
+//! `ms build --from-file` mines transcripts written directly by coding agents,
+//! not only sessions exported through CASS. Each adapter normalizes one
+//! on-disk format into [`Session`], mapping the agent's tool invocations onto
+//! [`SessionMessage::tool_calls`] and their outputs onto
+//! [`SessionMessage::tool_results`], so mining, quality scoring and phase
+//! segmentation work unchanged.
+//!
+//! Tool names are mapped onto the Claude-style names the miner classifies
+//! (`Bash`, `Read`, `Edit`, ...); unknown tools keep their own name.
+
+use std::fmt;
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::{Value, json};
+
+use super::client::{
+    Session, SessionMessage, SessionMetadata, ToolCall, ToolResult, content_hash_of,
+    parse_inline_tool_markers, session_id_from_path,
+};
+use crate::error::{MsError, Result};
+
+/// Transcript format of a session file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SessionFormat {
+    /// `cass export --format json` output (array of messages)
+    Cass,
+    /// Claude Code project JSONL
+    ClaudeCode,
+    /// Codex CLI rollout JSONL
+    Codex,
+    /// Gemini CLI chat JSON (saved chats or session recordings)
+    Gemini,
+}
+
+impl SessionFormat {
+    pub const ALL: [Self; 4] = [Self::Cass, Self::ClaudeCode, Self::Codex, Self::Gemini];
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Cass => "cass",
+            Self::ClaudeCode => "claude-code",
+            Self::Codex => "codex",
+            Self::Gemini => "gemini",
+        }
+    }
+
+    #[must_use]
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::Cass => "cass export JSON",
+            Self::ClaudeCode => "Claude Code JSONL",
+            Self::Codex => "Codex CLI rollout JSONL",
+            Self::Gemini => "Gemini CLI chat JSON",
+        }
+    }
+
+    /// Sniff the transcript format from file contents.
+    #[must_use]
+    pub fn detect(text: &str) -> Option<Self> {
+        if let Ok(doc) = serde_json::from_str::<Value>(text) {
+            return detect_document(&doc);
+        }
+        jsonl_records(text)
+            .take(20)
+            .find_map(|record| detect_record(&record))
+    }
+}
+
+impl fmt::Display for SessionFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Read a transcript file, detect its format and normalize it.
+pub fn load_session_file(path: &Path) -> Result<(Session, SessionFormat)> {
+    let text = std::fs::read_to_string(path)?;
+    parse_session(&text, &path.display().to_string())
+}
+
+/// Normalize transcript text. `path` names the session in errors and
+/// becomes the session path.
+pub fn parse_session(text: &str, path: &str) -> Result<(Session, SessionFormat)> {
+    let format = SessionFormat::detect(text).ok_or_else(|| {
+        let supported = SessionFormat::ALL
+            .iter()
+            .map(|format| format!("{format} ({})", format.description()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        MsError::ValidationFailed(format!(
+            "{path}: unrecognized session transcript format (supported: {supported})"
+        ))
+    })?;
+
+    let mut transcript = match format {
+        SessionFormat::Cass => from_cass(text)?,
+        SessionFormat::ClaudeCode => from_claude_code(text),
+        SessionFormat::Codex => from_codex(text),
+        SessionFormat::Gemini => from_gemini(text),
+    };
+    if transcript.messages.is_empty() {
+        return Err(MsError::ValidationFailed(format!(
+            "{path}: {} transcript contains no messages",
+            format.description()
+        )));
+    }
+
+    for (idx, message) in transcript.messages.iter_mut().enumerate() {
+        message.index = idx;
+    }
+    transcript.metadata.message_count = transcript.messages.len();
+    if format != SessionFormat::Cass {
+        transcript.metadata.agent = Some(format.as_str().to_string());
+    }
+
+    let session = Session {
+        id: transcript.id.unwrap_or_else(|| session_id_from_path(path)),
+        path: path.to_string(),
+        messages: transcript.messages,
+        metadata: transcript.metadata,
+        content_hash: content_hash_of(text.as_bytes()),
+    };
+    Ok((session, format))
+}
+
+// =============================================================================
+// Detection
+// =============================================================================
+
+fn jsonl_records(text: &str) -> impl Iterator<Item = Value> + '_ {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter(Value::is_object)
+}
+
+fn detect_document(doc: &Value) -> Option<SessionFormat> {
+    match doc {
+        Value::Array(items) => {
+            let first = items.iter().find(|item| item.is_object())?;
+            if first.get("parts").is_some() {
+                Some(SessionFormat::Gemini)
+            } else if first.get("role").is_some() && first.get("content").is_some() {
+                Some(SessionFormat::Cass)
+            } else {
+                items.iter().find_map(detect_record)
+            }
+        }
+        Value::Object(map) => match map.get("messages").and_then(Value::as_array) {
+            Some(messages) => {
+                let first = messages.first()?;
+                if first.get("toolCalls").is_some()
+                    || matches!(
+                        first.get("type").and_then(Value::as_str),
+                        Some("user" | "gemini")
+                    )
+                {
+                    Some(SessionFormat::Gemini)
+                } else if first.get("role").is_some() {
+                    Some(SessionFormat::Cass)
+                } else {
+                    None
+                }
+            }
+            None => detect_record(doc),
+        },
+        _ => None,
+    }
+}
+
+fn detect_record(record: &Value) -> Option<SessionFormat> {
+    let kind = record.get("type").and_then(Value::as_str);
+    match kind {
+        Some(
+            "session_meta"
+            | "response_item"
+            | "turn_context"
+            | "event_msg"
+            | "function_call"
+            | "function_call_output"
+            | "reasoning",
+        ) => return Some(SessionFormat::Codex),
+        Some("message") if record.get("content").is_some_and(Value::is_array) => {
+            return Some(SessionFormat::Codex);
+        }
+        Some("user" | "assistant") if record.get("message").is_some_and(Value::is_object) => {
+            return Some(SessionFormat::ClaudeCode);
+        }
+        _ => {}
+    }
+    if record.get("parts").is_some_and(Value::is_array) && record.get("role").is_some() {
+        return Some(SessionFormat::Gemini);
+    }
+    None
+}
+
+// =============================================================================
+// Normalization
+// =============================================================================
+
+#[derive(Default)]
+struct Transcript {
+    id: Option<String>,
+    messages: Vec<SessionMessage>,
+    metadata: SessionMetadata,
+}
+
+impl Transcript {
+    fn push(&mut self, role: &str, content: String) -> &mut SessionMessage {
+        self.messages.push(SessionMessage {
+            index: self.messages.len(),
+            role: role.to_string(),
+            content,
+            tool_calls: Vec::new(),
+            tool_results: Vec::new(),
+        });
+        self.messages.last_mut().expect("message just pushed")
+    }
+
+    /// Attach a tool call to the assistant text that introduced it. Further
+    /// calls become turns of their own so each keeps its own phase.
+    fn add_call(&mut self, call: ToolCall) {
+        match self.messages.last_mut() {
+            Some(last) if last.role == "assistant" && last.tool_calls.is_empty() => {
+                last.tool_calls.push(call);
+            }
+            _ => self.push("assistant", String::new()).tool_calls.push(call),
+        }
+    }
+
+    /// Attach a result to the message that issued the call, or to a
+    /// standalone tool message when the call is unknown.
+    fn add_result(&mut self, result: ToolResult) {
+        let owner = self.messages.iter_mut().rev().find(|message| {
+            message
+                .tool_calls
+                .iter()
+                .any(|call| call.id == result.tool_call_id)
+        });
+        match owner {
+            Some(message) => message.tool_results.push(result),
+            None => self.push("tool", String::new()).tool_results.push(result),
+        }
+    }
+
+    fn note_time(&mut self, timestamp: Option<&str>) {
+        if let Some(ts) = timestamp {
+            if self.metadata.started_at.is_none() {
+                self.metadata.started_at = Some(ts.to_string());
+            }
+            self.metadata.ended_at = Some(ts.to_string());
+        }
+    }
+}
+
+fn str_field<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+    value.get(key).and_then(Value::as_str)
+}
+
+/// Text from a string or an array of `{text}` blocks.
+fn text_of(value: Option<&Value>) -> String {
+    match value {
+        Some(Value::String(text)) => text.clone(),
+        Some(Value::Array(blocks)) => blocks
+            .iter()
+            .filter_map(|block| block.as_str().or_else(|| str_field(block, "text")))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+/// Map an agent's tool name and arguments onto what the miner classifies.
+fn canonical_tool(name: &str, arguments: Value) -> (String, Value) {
+    let canonical = match name {
+        "shell" | "local_shell" | "container.exec" | "run_shell_command" => "Bash",
+        "read_file" | "read_many_files" => "Read",
+        "write_file" => "Write",
+        "apply_patch" | "replace" | "edit" | "edit_file" => "Edit",
+        "glob" => "Glob",
+        "search_file_content" | "grep" => "Grep",
+        "list_directory" | "ls" => "ListDirectory",
+        other => return (other.to_string(), arguments),
+    };
+    let mut arguments = match arguments {
+        Value::Object(map) => Value::Object(map),
+        Value::Null => json!({}),
+        other => json!({ "input": other }),
+    };
+    if canonical == "Bash" {
+        if let Some(command) = arguments.get("command").and_then(shell_command) {
+            arguments["command"] = Value::String(command);
+        }
+    } else if arguments.get("file_path").is_none() {
+        let path = ["absolute_path", "path", "file"]
+            .iter()
+            .find_map(|key| arguments.get(*key).cloned());
+        if let Some(path) = path {
+            arguments["file_path"] = path;
+        }
+    }
+    (canonical.to_string(), arguments)
+}
+
+/// Codex passes argv arrays such as `["bash", "-lc", "cargo test"]`.
+fn shell_command(command: &Value) -> Option<String> {
+    match command {
+        Value::String(command) => Some(command.clone()),
+        Value::Array(argv) => {
+            let argv: Vec<&str> = argv.iter().filter_map(Value::as_str).collect();
+            match argv.as_slice() {
+                ["bash" | "sh" | "zsh", "-lc" | "-c", script] => Some((*script).to_string()),
+                _ => Some(argv.join(" ")),
+            }
+        }
+        _ => None,
+    }
+}
+
+fn from_cass(text: &str) -> Result<Transcript> {
+    let doc: Value = serde_json::from_str(text)?;
+    let messages = match doc {
+        Value::Object(mut map) => map.remove("messages").unwrap_or_default(),
+        other => other,
+    };
+    let mut messages: Vec<SessionMessage> = serde_json::from_value(messages)?;
+    for (idx, message) in messages.iter_mut().enumerate() {
+        if message.tool_calls.is_empty() {
+            message.tool_calls = parse_inline_tool_markers(&message.content, idx);
+        }
+    }
+    Ok(Transcript {
+        messages,
+        ..Transcript::default()
+    })
+}
+
+fn from_claude_code(text: &str) -> Transcript {
+    let mut out = Transcript::default();
+    for record in jsonl_records(text) {
+        let Some(role @ ("user" | "assistant")) = str_field(&record, "type") else {
+            continue;
+        };
+        let Some(message) = record.get("message") else {
+            continue;
+        };
+        if out.id.is_none() {
+            out.id = str_field(&record, "sessionId").map(str::to_string);
+        }
+        if out.metadata.project.is_none() {
+            out.metadata.project = str_field(&record, "cwd").map(str::to_string);
+        }
+        if let Some(model) = str_field(message, "model") {
+            out.metadata.model = Some(model.to_string());
+        }
+        out.note_time(str_field(&record, "timestamp"));
+
+        let blocks = match message.get("content") {
+            Some(Value::Array(blocks)) => blocks.clone(),
+            other => {
+                out.push(role, text_of(other));
+                continue;
+            }
+        };
+        let mut text = Vec::new();
+        let mut calls = Vec::new();
+        let mut results = Vec::new();
+        for block in &blocks {
+            match str_field(block, "type") {
+                Some("text") => text.extend(str_field(block, "text").map(str::to_string)),
+                Some("tool_use") => calls.push(ToolCall {
+                    id: str_field(block, "id").unwrap_or_default().to_string(),
+                    name: str_field(block, "name").unwrap_or_default().to_string(),
+                    arguments: block.get("input").cloned().unwrap_or_else(|| json!({})),
+                }),
+                Some("tool_result") => results.push(ToolResult {
+                    tool_call_id: str_field(block, "tool_use_id")
+                        .unwrap_or_default()
+                        .to_string(),
+                    content: text_of(block.get("content")),
+                    is_error: block
+                        .get("is_error")
+                        .and_then(Value::as_bool)
+                        .unwrap_or(false),
+                }),
+                _ => {}
+            }
+        }
+        if !text.is_empty() || !calls.is_empty() {
+            out.push(role, text.join("\n")).tool_calls = calls;
+        }
+        for result in results {
+            out.add_result(result);
+        }
+    }
+    out
+}
+
+fn from_codex(text: &str) -> Transcript {
+    let mut out = Transcript::default();
+    for record in jsonl_records(text) {
+        out.note_time(str_field(&record, "timestamp"));
+        let item = match str_field(&record, "type") {
+            Some("session_meta") => {
+                let payload = record.get("payload").unwrap_or(&Value::Null);
+                out.id = str_field(payload, "id").map(str::to_string);
+                out.metadata.project = str_field(payload, "cwd").map(str::to_string);
+                continue;
+            }
+            Some("turn_context") => {
+                let payload = record.get("payload").unwrap_or(&Value::Null);
+                out.metadata.model = str_field(payload, "model").map(str::to_string);
+                continue;
+            }
+            Some("response_item") => record.get("payload").cloned().unwrap_or_default(),
+            // Older rollouts write items without the envelope; the first line
+            // is bare session metadata.
+            Some(_) => record,
+            None => {
+                out.id = str_field(&record, "id").map(str::to_string);
+                continue;
+            }
+        };
+
+        match str_field(&item, "type") {
+            Some("message") => {
+                let role = str_field(&item, "role").unwrap_or("user");
+                let content = text_of(item.get("content"));
+                // Codex injects its instructions and environment as turns.
+                let injected = content.trim_start().starts_with("<environment_context>")
+                    || content.trim_start().starts_with("<user_instructions>");
+                if matches!(role, "user" | "assistant") && !injected && !content.is_empty() {
+                    out.push(role, content);
+                }
+            }
+            Some(kind @ ("function_call" | "custom_tool_call" | "local_shell_call")) => {
+                let name = match kind {
+                    "local_shell_call" => "local_shell",
+                    _ => str_field(&item, "name").unwrap_or("unknown"),
+                };
+                let arguments = match kind {
+                    "function_call" => str_field(&item, "arguments")
+                        .and_then(|raw| serde_json::from_str(raw).ok())
+                        .unwrap_or_default(),
+                    "custom_tool_call" => json!({ "input": item.get("input") }),
+                    _ => item.get("action").cloned().unwrap_or_default(),
+                };
+                let (name, arguments) = canonical_tool(name, arguments);
+                out.add_call(ToolCall {
+                    id: str_field(&item, "call_id").unwrap_or_default().to_string(),
+                    name,
+                    arguments,
+                });
+            }
+            Some("function_call_output" | "custom_tool_call_output") => {
+                let (content, is_error) = codex_output(item.get("output"));
+                out.add_result(ToolResult {
+                    tool_call_id: str_field(&item, "call_id").unwrap_or_default().to_string(),
+                    content,
+                    is_error,
+                });
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Codex tool output is either plain text or a JSON string carrying
+/// `{"output": ..., "metadata": {"exit_code": N}}`.
+fn codex_output(output: Option<&Value>) -> (String, bool) {
+    let raw = match output {
+        Some(Value::String(raw)) => raw.clone(),
+        Some(other) => other.to_string(),
+        None => return (String::new(), false),
+    };
+    match serde_json::from_str::<Value>(&raw) {
+        Ok(parsed) if parsed.get("output").is_some() => {
+            let exit_code = parsed
+                .pointer("/metadata/exit_code")
+                .and_then(Value::as_i64)
+                .unwrap_or(0);
+            (text_of(parsed.get("output")), exit_code != 0)
+        }
+        _ => (raw, false),
+    }
+}
+
+fn from_gemini(text: &str) -> Transcript {
+    let doc = serde_json::from_str::<Value>(text)
+        .unwrap_or_else(|_| Value::Array(jsonl_records(text).collect()));
+    match doc {
+        Value::Object(ref map) if map.contains_key("messages") => from_gemini_recording(&doc),
+        Value::Array(contents) => from_gemini_contents(&contents),
+        other => from_gemini_contents(&[other]),
+    }
+}
+
+/// Saved chats: a list of API `Content` turns with `parts`.
+fn from_gemini_contents(contents: &[Value]) -> Transcript {
+    let mut out = Transcript::default();
+    let mut seq = 0usize;
+    for content in contents {
+        let role = match str_field(content, "role") {
+            Some("model") => "assistant",
+            _ => "user",
+        };
+        let parts = content.get("parts").and_then(Value::as_array);
+        let mut text = Vec::new();
+        let mut calls = Vec::new();
+        let mut results = Vec::new();
+        for part in parts.into_iter().flatten() {
+            if let Some(chunk) = str_field(part, "text") {
+                text.push(chunk.to_string());
+            } else if let Some(call) = part.get("functionCall") {
+                let id = str_field(call, "id").map_or_else(
+                    || {
+                        seq += 1;
+                        format!("gemini_{seq}")
+                    },
+                    str::to_string,
+                );
+                let (name, arguments) = canonical_tool(
+                    str_field(call, "name").unwrap_or("unknown"),
+                    call.get("args").cloned().unwrap_or_default(),
+                );
+                calls.push(ToolCall {
+                    id,
+                    name,
+                    arguments,
+                });
+            } else if let Some(response) = part.get("functionResponse") {
+                results.push(response.clone());
+            }
+        }
+        if !text.is_empty() || !calls.is_empty() {
+            out.push(role, text.join("\n")).tool_calls = calls;
+        }
+        for response in results {
+            let tool_call_id = match str_field(&response, "id") {
+                Some(id) => id.to_string(),
+                // Older saves omit ids: pair with the latest call without a result.
+                None => pending_call(&out).unwrap_or_default(),
+            };
+            let (content, is_error) = gemini_response(response.get("response"));
+            out.add_result(ToolResult {
+                tool_call_id,
+                content,
+                is_error,
+            });
+        }
+    }
+    out
+}
+
+fn pending_call(out: &Transcript) -> Option<String> {
+    out.messages.iter().rev().find_map(|message| {
+        message
+            .tool_calls
+            .iter()
+            .find(|call| {
+                !message
+                    .tool_results
+                    .iter()
+                    .any(|result| result.tool_call_id == call.id)
+            })
+            .map(|call| call.id.clone())
+    })
+}
+
+fn gemini_response(response: Option<&Value>) -> (String, bool) {
+    let Some(response) = response else {
+        return (String::new(), false);
+    };
+    if let Some(error) = response.get("error") {
+        return (text_of(Some(error)), true);
+    }
+    match response.get("output") {
+        Some(output) => (text_of(Some(output)), false),
+        None => (response.to_string(), false),
+    }
+}
+
+/// Session recordings: `{sessionId, startTime, messages: [{type, content, toolCalls}]}`.
+fn from_gemini_recording(doc: &Value) -> Transcript {
+    let mut out = Transcript {
+        id: str_field(doc, "sessionId").map(str::to_string),
+        ..Transcript::default()
+    };
+    out.note_time(str_field(doc, "startTime"));
+    out.note_time(str_field(doc, "lastUpdated"));
+
+    let messages = doc.get("messages").and_then(Value::as_array);
+    for message in messages.into_iter().flatten() {
+        let role = match str_field(message, "type") {
+            Some("user") => "user",
+            Some("gemini" | "model") => "assistant",
+            _ => continue,
+        };
+        if let Some(model) = str_field(message, "model") {
+            out.metadata.model = Some(model.to_string());
+        }
+        let turn = out.push(role, text_of(message.get("content")));
+        let calls = message.get("toolCalls").and_then(Value::as_array);
+        for call in calls.into_iter().flatten() {
+            let id = str_field(call, "id").unwrap_or_default().to_string();
+            let (name, arguments) = canonical_tool(
+                str_field(call, "name").unwrap_or("unknown"),
+                call.get("args").cloned().unwrap_or_default(),
+            );
+            let content = match call.get("resultDisplay") {
+                Some(Value::String(display)) => display.clone(),
+                _ => call
+                    .get("result")
+                    .and_then(Value::as_array)
+                    .and_then(|parts| parts.iter().find_map(|p| p.get("functionResponse")))
+                    .map(|response| gemini_response(response.get("response")).0)
+                    .unwrap_or_default(),
+            };
+            turn.tool_results.push(ToolResult {
+                tool_call_id: id.clone(),
+                content,
+                is_error: str_field(call, "status") == Some("error"),
+            });
+            turn.tool_calls.push(ToolCall {
+                id,
+                name,
+                arguments,
+            });
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cass::mining::{SessionPhase, segment_session};
+
+    fn fixture(name: &str) -> std::path::PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/sessions")
+            .join(name)
+    }
+
+    fn phases(session: &Session) -> Vec<SessionPhase> {
+        segment_session(session)
+            .segments
+            .iter()
+            .map(|segment| segment.phase)
+            .collect()
+    }
+
+    #[test]
+    fn codex_rollout_is_normalized() {
+        let (session, format) = load_session_file(&fixture("codex_rollout.jsonl")).unwrap();
+        assert_eq!(format, SessionFormat::Codex);
+        assert_eq!(session.id, "0199a1b2-codex-fixture");
+        assert_eq!(session.metadata.agent.as_deref(), Some("codex"));
+        assert_eq!(session.metadata.model.as_deref(), Some("gpt-5-codex"));
+        assert_eq!(session.messages[0].role, "user");
+        assert!(!session.messages[0].content.contains("environment_context"));
+
+        let calls: Vec<&ToolCall> = session
+            .messages
+            .iter()
+            .flat_map(|m| &m.tool_calls)
+            .collect();
+        assert_eq!(calls[0].name, "Bash");
+        assert_eq!(calls[0].arguments["command"], "rg -n parse_port src");
+        assert!(calls.iter().any(|call| call.name == "Edit"));
+
+        let results: Vec<&ToolResult> = session
+            .messages
+            .iter()
+            .flat_map(|m| &m.tool_results)
+            .collect();
+        assert_eq!(results.len(), calls.len());
+        assert!(results.iter().any(|result| result.is_error));
+        assert!(
+            results
+                .iter()
+                .any(|result| result.content.contains("test result: ok"))
+        );
+    }
+
+    #[test]
+    fn codex_segments_follow_the_work() {
+        let (session, _) = load_session_file(&fixture("codex_rollout.jsonl")).unwrap();
+        let phases = phases(&session);
+        assert_eq!(phases.first(), Some(&SessionPhase::Reconnaissance));
+        assert!(phases.contains(&SessionPhase::Change));
+        assert!(phases.contains(&SessionPhase::Validation));
+    }
+
+    #[test]
+    fn gemini_chat_is_normalized() {
+        let (session, format) = load_session_file(&fixture("gemini_chat.json")).unwrap();
+        assert_eq!(format, SessionFormat::Gemini);
+        assert_eq!(session.metadata.agent.as_deref(), Some("gemini"));
+        assert_eq!(session.messages[1].role, "assistant");
+
+        let names: Vec<&str> = session
+            .messages
+            .iter()
+            .flat_map(|m| &m.tool_calls)
+            .map(|call| call.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Read", "Edit", "Bash"]);
+        for message in &session.messages {
+            assert_eq!(message.tool_calls.len(), message.tool_results.len());
+        }
+        let phases = phases(&session);
+        assert_eq!(phases.first(), Some(&SessionPhase::Reconnaissance));
+        assert!(phases.contains(&SessionPhase::Change));
+        assert!(phases.contains(&SessionPhase::Validation));
+    }
+
+    #[test]
+    fn gemini_recording_is_normalized() {
+        let (session, format) = load_session_file(&fixture("gemini_session.json")).unwrap();
+        assert_eq!(format, SessionFormat::Gemini);
+        assert_eq!(session.id, "gemini-fixture-session");
+        assert_eq!(session.metadata.model.as_deref(), Some("gemini-2.5-pro"));
+        let bash = session
+            .messages
+            .iter()
+            .flat_map(|m| &m.tool_calls)
+            .find(|call| call.name == "Bash")
+            .unwrap();
+        assert_eq!(bash.arguments["command"], "npm test");
+        assert!(phases(&session).contains(&SessionPhase::Validation));
+    }
+
+    #[test]
+    fn detects_cass_and_claude_code() {
+        let cass = r#"[{"role":"user","content":"hi"},{"role":"assistant","content":"[Tool: Read - src/lib.rs]"}]"#;
+        let (session, format) = parse_session(cass, "/tmp/cass.json").unwrap();
+        assert_eq!(format, SessionFormat::Cass);
+        assert_eq!(session.messages[1].tool_calls[0].name, "Read");
+
+        let claude = concat!(
+            r#"{"type":"user","sessionId":"s1","message":{"role":"user","content":"fix it"}}"#,
+            "\n",
+            r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","id":"t1","name":"Bash","input":{"command":"cargo test"}}]}}"#,
+            "\n",
+            r#"{"type":"user","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"t1","content":"ok"}]}}"#,
+        );
+        let (session, format) = parse_session(claude, "/tmp/claude.jsonl").unwrap();
+        assert_eq!(format, SessionFormat::ClaudeCode);
+        assert_eq!(session.id, "s1");
+        assert_eq!(session.messages.len(), 2);
+        assert_eq!(session.messages[1].tool_results[0].content, "ok");
+    }
+
+    #[test]
+    fn unknown_format_lists_supported_formats() {
+        let err = parse_session("{\"hello\": \"world\"}", "notes.json").unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("notes.json"));
+        for format in SessionFormat::ALL {
+            assert!(message.contains(format.as_str()), "{message}");
+        }
+    }
+}
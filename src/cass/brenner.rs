@@ -8,12 +8,13 @@
 //! 1. Effective coding has a generative grammar - cognitive moves can be identified
 //! 2. Understanding = ability to reproduce - a skill is valid only if executable
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::io::{self, BufRead, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
 use crate::error::{MsError, Result};
@@ -251,6 +252,26 @@ pub struct WizardCheckpoint {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub query: String,
+    /// Sessions confirmed for mining, with their content at that time
+    #[serde(default)]
+    pub sessions: Vec<SessionFingerprint>,
+    /// Ids of moves already decided in the guard phase
+    #[serde(default)]
+    pub decided_moves: Vec<String>,
+}
+
+/// Identity and content hash of a mined session
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionFingerprint {
+    pub session_id: String,
+    pub path: String,
+    pub content_hash: String,
+}
+
+/// Directory holding wizard checkpoints under the ms data dir
+#[must_use]
+pub fn wizard_checkpoint_dir(ms_root: &Path) -> PathBuf {
+    ms_root.join("wizard-checkpoints")
 }
 
 impl WizardCheckpoint {
@@ -263,6 +284,8 @@ impl WizardCheckpoint {
             created_at: now,
             updated_at: now,
             query: query.to_string(),
+            sessions: Vec::new(),
+            decided_moves: Vec::new(),
         }
     }
 
@@ -270,6 +293,121 @@ impl WizardCheckpoint {
         self.state = state;
         self.updated_at = Utc::now();
     }
+
+    /// Key for the checkpoint file: the query plus the selected session ids.
+    ///
+    /// `None` until sessions are confirmed; there is nothing worth resuming
+    /// before that.
+    #[must_use]
+    pub fn selection_hash(&self) -> Option<String> {
+        if self.sessions.is_empty() {
+            return None;
+        }
+        let mut ids: Vec<&str> = self
+            .sessions
+            .iter()
+            .map(|s| s.session_id.as_str())
+            .collect();
+        ids.sort_unstable();
+        let mut hasher = Sha256::new();
+        hasher.update(self.query.as_bytes());
+        for id in ids {
+            hasher.update(b"\n");
+            hasher.update(id.as_bytes());
+        }
+        Some(hex::encode(hasher.finalize()))
+    }
+
+    /// Whether the wizard reached a terminal state
+    #[must_use]
+    pub const fn is_finished(&self) -> bool {
+        matches!(
+            self.state,
+            WizardState::Complete { .. } | WizardState::Cancelled { .. }
+        )
+    }
+
+    /// Short phase label for listings
+    #[must_use]
+    pub const fn phase(&self) -> &'static str {
+        match self.state {
+            WizardState::SessionSelection { .. } => "session_selection",
+            WizardState::MoveExtraction { .. } => "move_extraction",
+            WizardState::ThirdAlternativeGuard { .. } => "guard",
+            WizardState::SkillFormalization { .. } => "formalization",
+            WizardState::MaterializationTest { .. } => "materialization_test",
+            WizardState::Complete { .. } => "complete",
+            WizardState::Cancelled { .. } => "cancelled",
+        }
+    }
+
+    /// Sessions whose content no longer matches the checkpoint.
+    ///
+    /// `current` returns a session's present content hash, or `None` when it
+    /// can no longer be loaded (which also counts as changed).
+    pub fn changed_sessions<F>(&self, mut current: F) -> Vec<String>
+    where
+        F: FnMut(&SessionFingerprint) -> Option<String>,
+    {
+        self.sessions
+            .iter()
+            .filter(|fp| current(fp).as_deref() != Some(fp.content_hash.as_str()))
+            .map(|fp| fp.session_id.clone())
+            .collect()
+    }
+
+    /// Write the checkpoint to `<dir>/<selection hash>.json`.
+    ///
+    /// Returns `None` without writing when no sessions are selected yet.
+    pub fn save(&self, dir: &Path) -> Result<Option<PathBuf>> {
+        let Some(key) = self.selection_hash() else {
+            return Ok(None);
+        };
+        std::fs::create_dir_all(dir)?;
+        let path = dir.join(format!("{key}.json"));
+        let tmp = dir.join(format!("{key}.json.tmp"));
+        std::fs::write(&tmp, serde_json::to_vec_pretty(self)?)?;
+        std::fs::rename(&tmp, &path)?;
+        Ok(Some(path))
+    }
+
+    /// Load every checkpoint in `dir`, most recently updated first.
+    ///
+    /// Unreadable files are skipped so one corrupt checkpoint does not hide
+    /// the rest.
+    pub fn load_all(dir: &Path) -> Result<Vec<(PathBuf, Self)>> {
+        let entries = match std::fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        };
+        let mut checkpoints = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let parsed = std::fs::read(&path)
+                .map_err(MsError::from)
+                .and_then(|bytes| serde_json::from_slice::<Self>(&bytes).map_err(MsError::from));
+            match parsed {
+                Ok(checkpoint) => checkpoints.push((path, checkpoint)),
+                Err(err) => tracing::warn!("skipping wizard checkpoint {}: {err}", path.display()),
+            }
+        }
+        checkpoints.sort_by(|a, b| b.1.updated_at.cmp(&a.1.updated_at));
+        Ok(checkpoints)
+    }
+
+    /// Find a checkpoint by id or by (a prefix of) its selection hash.
+    pub fn find(dir: &Path, key: &str) -> Result<Option<(PathBuf, Self)>> {
+        Ok(Self::load_all(dir)?.into_iter().find(|(_, cp)| {
+            cp.id == key
+                || cp
+                    .selection_hash()
+                    .is_some_and(|hash| !key.is_empty() && hash.starts_with(key))
+        }))
+    }
 }
 
 // =============================================================================
@@ -366,6 +504,10 @@ pub struct BrennerWizard {
     /// Uncertainty queue for low-confidence items
     #[allow(dead_code)]
     uncertainty_queue: Option<UncertaintyQueue>,
+    /// Where checkpoints are written; `None` keeps them in memory only
+    checkpoint_dir: Option<PathBuf>,
+    /// Quality of listed sessions by id; `None` when the session failed to load
+    session_quality: HashMap<String, Option<SessionQuality>>,
 }
 
 /// Configuration for the Brenner wizard
@@ -409,19 +551,40 @@ impl BrennerWizard {
             config,
             transformer: None,
             uncertainty_queue: None,
+            checkpoint_dir: None,
+            session_quality: HashMap::new(),
         }
     }
 
     /// Resume from a checkpoint
+    ///
+    /// In the guard phase this lands on the first flagged move that has not
+    /// been decided yet.
     #[must_use]
     pub fn resume(checkpoint: WizardCheckpoint, config: BrennerConfig) -> Self {
-        Self {
+        let mut wizard = Self {
             state: checkpoint.state.clone(),
             checkpoint,
             config,
             transformer: None,
             uncertainty_queue: None,
-        }
+            checkpoint_dir: None,
+            session_quality: HashMap::new(),
+        };
+        wizard.seek_undecided_move();
+        wizard
+    }
+
+    /// Persist a checkpoint to `dir` after every decision and phase change
+    #[must_use]
+    pub fn with_checkpoint_dir(mut self, dir: PathBuf) -> Self {
+        self.checkpoint_dir = Some(dir);
+        self
+    }
+
+    /// Get the wizard configuration
+    pub const fn config(&self) -> &BrennerConfig {
+        &self.config
     }
 
     /// Get current checkpoint for saving
@@ -429,6 +592,49 @@ impl BrennerWizard {
         &self.checkpoint
     }
 
+    /// Write the current checkpoint, if a checkpoint dir is configured.
+    ///
+    /// Returns the file written, or `None` when persistence is off or no
+    /// sessions have been selected yet.
+    pub fn save_checkpoint(&self) -> Result<Option<PathBuf>> {
+        match &self.checkpoint_dir {
+            Some(dir) => self.checkpoint.save(dir),
+            None => Ok(None),
+        }
+    }
+
+    /// Record the current state in the checkpoint and persist it
+    fn record(&mut self) -> Result<()> {
+        self.checkpoint.update(self.state.clone());
+        self.save_checkpoint()?;
+        Ok(())
+    }
+
+    fn seek_undecided_move(&mut self) {
+        let WizardState::ThirdAlternativeGuard {
+            ref moves,
+            ref flagged_indices,
+            ref mut current_idx,
+        } = self.state
+        else {
+            return;
+        };
+        let decided = &self.checkpoint.decided_moves;
+        let next = flagged_indices.iter().position(|&idx| {
+            moves
+                .get(idx)
+                .is_some_and(|m| !decided.iter().any(|id| *id == m.id))
+        });
+        match next {
+            Some(pos) => *current_idx = pos,
+            None if !flagged_indices.is_empty() => {
+                // Every flagged move was decided before the interruption
+                let _ = self.finish_guard();
+            }
+            None => {}
+        }
+    }
+
     /// Get current state
     pub const fn state(&self) -> &WizardState {
         &self.state
@@ -457,6 +663,46 @@ impl BrennerWizard {
         }
     }
 
+    /// Score listed sessions that have not been scored yet
+    ///
+    /// Sessions that fail to load are remembered so they are not fetched again.
+    pub fn score_session_results(&mut self, client: &CassClient, scorer: &QualityScorer) {
+        let WizardState::SessionSelection { ref results, .. } = self.state else {
+            return;
+        };
+        for result in results {
+            if self.session_quality.contains_key(&result.session_id) {
+                continue;
+            }
+            let quality = client
+                .get_session(&result.path)
+                .ok()
+                .map(|session| scorer.score(&session));
+            self.session_quality
+                .insert(result.session_id.clone(), quality);
+        }
+    }
+
+    /// Quality of a listed session, once scored
+    #[must_use]
+    pub fn session_quality(&self, session_id: &str) -> Option<&SessionQuality> {
+        self.session_quality.get(session_id)?.as_ref()
+    }
+
+    /// Badge for a listed session, e.g. `good 72%` or `fair 45%, below 60%`
+    #[must_use]
+    pub fn quality_badge(&self, session_id: &str) -> String {
+        match self.session_quality(session_id) {
+            Some(quality) if quality.score < self.config.min_quality => format!(
+                "{}, below {:.0}%",
+                quality.badge(),
+                self.config.min_quality * 100.0
+            ),
+            Some(quality) => quality.badge(),
+            None => "unscored".to_string(),
+        }
+    }
+
     /// Toggle session selection
     pub fn toggle_session(&mut self, index: usize) {
         if let WizardState::SessionSelection {
@@ -480,13 +726,20 @@ impl BrennerWizard {
             return Err(MsError::Config("No sessions selected".into()));
         }
 
+        self.checkpoint.sessions = sessions
+            .iter()
+            .map(|s| SessionFingerprint {
+                session_id: s.session.id.clone(),
+                path: s.session.path.clone(),
+                content_hash: s.session.content_hash.clone(),
+            })
+            .collect();
         self.state = WizardState::MoveExtraction {
             sessions,
             moves: Vec::new(),
             current_session_idx: 0,
         };
-        self.checkpoint.update(self.state.clone());
-        Ok(())
+        self.record()
     }
 
     /// Add an extracted cognitive move
@@ -538,8 +791,8 @@ impl BrennerWizard {
             flagged_indices,
             current_idx: 0,
         };
-        self.checkpoint.update(self.state.clone());
-        Ok(())
+        self.checkpoint.decided_moves.clear();
+        self.record()
     }
 
     /// Review a move in the guard phase
@@ -554,10 +807,12 @@ impl BrennerWizard {
                 if let Some(mov) = moves.get_mut(move_idx) {
                     mov.decision = Some(decision);
                     mov.reviewed = true;
+                    if !self.checkpoint.decided_moves.contains(&mov.id) {
+                        self.checkpoint.decided_moves.push(mov.id.clone());
+                    }
                 }
             }
-            self.checkpoint.update(self.state.clone());
-            Ok(())
+            self.record()
         } else {
             Err(MsError::Config("Not in guard state".into()))
         }
@@ -605,8 +860,7 @@ impl BrennerWizard {
             moves: accepted_moves,
             draft,
         };
-        self.checkpoint.update(self.state.clone());
-        Ok(())
+        self.record()
     }
 
     /// Build a skill draft from moves
@@ -677,8 +931,7 @@ impl BrennerWizard {
             draft,
             test_results: None,
         };
-        self.checkpoint.update(self.state.clone());
-        Ok(())
+        self.record()
     }
 
     /// Set test results
@@ -711,7 +964,7 @@ impl BrennerWizard {
             manifest_path: manifest_path.clone(),
             draft: draft.clone(),
         };
-        self.checkpoint.update(self.state.clone());
+        self.record()?;
 
         // Generate manifest JSON
         let manifest_json = self.generate_manifest()?;
@@ -779,6 +1032,7 @@ pub fn run_interactive(
     let mut stdout = io::stdout();
 
     loop {
+        wizard.score_session_results(client, quality_scorer);
         match wizard.state() {
             WizardState::SessionSelection {
                 query,
@@ -795,6 +1049,14 @@ pub fn run_interactive(
                     // In real impl, search via client
                     println!("(Simulated: no sessions found - enter 'd' to use demo data)");
                 }
+                for (i, result) in results.iter().enumerate() {
+                    let mark = if selected.contains(&i) { "[x]" } else { "[ ]" };
+                    println!(
+                        "  {mark} {i}. {} [{}]",
+                        result.snippet.as_deref().unwrap_or(&result.session_id),
+                        wizard.quality_badge(&result.session_id)
+                    );
+                }
 
                 println!("\nSelected: {} sessions", selected.len());
                 println!("\nCommands:");
@@ -1230,6 +1492,144 @@ mod tests {
         assert!(!checkpoint.id.is_empty());
     }
 
+    #[test]
+    fn test_session_results_get_quality_badges() {
+        let mut wizard = BrennerWizard::new("query", BrennerConfig::default());
+        let mut low = selected_session("low");
+        wizard.set_session_results(vec![low.match_data.clone()]);
+        let dir = tempfile::tempdir().unwrap();
+        let client = CassClient::with_binary(dir.path().join("cass"));
+        wizard.score_session_results(&client, &QualityScorer::with_defaults());
+        assert_eq!(wizard.quality_badge("low"), "unscored");
+
+        low.quality.score = 0.45;
+        wizard
+            .session_quality
+            .insert("low".to_string(), Some(low.quality.clone()));
+        assert_eq!(wizard.quality_badge("low"), "fair 45%, below 60%");
+        low.quality.score = 0.72;
+        wizard
+            .session_quality
+            .insert("low".to_string(), Some(low.quality));
+        assert_eq!(wizard.quality_badge("low"), "good 72%");
+    }
+
+    fn selected_session(id: &str) -> SelectedSession {
+        let session = Session {
+            id: id.to_string(),
+            path: format!("/sessions/{id}.jsonl"),
+            messages: Vec::new(),
+            metadata: crate::cass::client::SessionMetadata::default(),
+            content_hash: format!("hash-{id}"),
+        };
+        let quality = QualityScorer::with_defaults().score(&session);
+        SelectedSession {
+            match_data: SessionMatch {
+                session_id: id.to_string(),
+                path: session.path.clone(),
+                score: 0.9,
+                snippet: None,
+                content_hash: None,
+                project: None,
+                timestamp: None,
+            },
+            session,
+            quality,
+            confirmed: true,
+        }
+    }
+
+    fn low_confidence_move(id: &str) -> CognitiveMove {
+        CognitiveMove {
+            id: id.to_string(),
+            tag: CognitiveMoveTag::InnerTruth,
+            description: format!("move {id}"),
+            evidence: MoveEvidence {
+                session_id: "s1".to_string(),
+                message_indices: vec![],
+                excerpt: "...".to_string(),
+                notes: None,
+            },
+            confidence: 0.2,
+            reviewed: false,
+            decision: None,
+        }
+    }
+
+    #[test]
+    fn test_checkpoint_resumes_at_next_undecided_move() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut wizard = BrennerWizard::new("auth errors", BrennerConfig::default())
+            .with_checkpoint_dir(dir.path().to_path_buf());
+        wizard
+            .confirm_sessions(vec![selected_session("s1"), selected_session("s2")])
+            .unwrap();
+        for id in ["m1", "m2", "m3", "m4"] {
+            wizard.add_move(low_confidence_move(id));
+        }
+        wizard.finish_extraction().unwrap();
+
+        wizard.review_move(MoveDecision::Accept).unwrap();
+        assert!(wizard.next_flagged_move());
+        wizard
+            .review_move(MoveDecision::Flagged {
+                reason: "too specific".to_string(),
+            })
+            .unwrap();
+        // Interrupted before advancing past m2
+
+        let checkpoints = WizardCheckpoint::load_all(dir.path()).unwrap();
+        assert_eq!(checkpoints.len(), 1);
+        let (path, checkpoint) = checkpoints.into_iter().next().unwrap();
+        let key = checkpoint.selection_hash().unwrap();
+        assert_eq!(path, dir.path().join(format!("{key}.json")));
+        assert_eq!(checkpoint.sessions.len(), 2);
+        assert_eq!(checkpoint.decided_moves, vec!["m1", "m2"]);
+        assert!(
+            WizardCheckpoint::find(dir.path(), &key[..8])
+                .unwrap()
+                .is_some()
+        );
+        assert!(
+            WizardCheckpoint::find(dir.path(), &checkpoint.id)
+                .unwrap()
+                .is_some()
+        );
+
+        let resumed = BrennerWizard::resume(checkpoint, BrennerConfig::default());
+        match resumed.state() {
+            WizardState::ThirdAlternativeGuard {
+                moves, current_idx, ..
+            } => {
+                assert_eq!(*current_idx, 2);
+                assert!(matches!(moves[0].decision, Some(MoveDecision::Accept)));
+                assert!(matches!(
+                    moves[1].decision,
+                    Some(MoveDecision::Flagged { ref reason }) if reason == "too specific"
+                ));
+                assert!(moves[2].decision.is_none());
+            }
+            other => panic!("unexpected state: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_checkpoint_detects_changed_sessions() {
+        let mut wizard = BrennerWizard::new("q", BrennerConfig::default());
+        assert!(wizard.checkpoint().selection_hash().is_none());
+        wizard
+            .confirm_sessions(vec![selected_session("s1"), selected_session("s2")])
+            .unwrap();
+        let changed = wizard
+            .checkpoint()
+            .changed_sessions(|fp| match fp.session_id.as_str() {
+                "s1" => Some(fp.content_hash.clone()),
+                _ => Some("edited".to_string()),
+            });
+        assert_eq!(changed, vec!["s2"]);
+        assert_eq!(wizard.checkpoint().changed_sessions(|_| None).len(), 2);
+    }
+
     #[test]
     fn test_skill_draft_generation() {
         let wizard = BrennerWizard::new("test", BrennerConfig::default());
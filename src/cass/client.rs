@@ -2,12 +2,25 @@
 //!
 //! Wraps the CASS CLI for programmatic access using robot mode.
 //! Never runs bare cass - always uses --robot/--json for automation.
-
-use std::path::PathBuf;
-use std::process::Command;
+//!
+//! Availability is a capability probed once per process: `cass health` with
+//! a short timeout, which also checks the version against
+//! [`MIN_CASS_VERSION`]. Every command refuses to run against an unusable
+//! cass with the probe's reason, so callers can degrade instead of hitting
+//! timeouts. Health and capability probes cost a process round trip each.
+//! With a [`ProbeCache`] attached their results are persisted (next to the
+//! [`FingerprintCache`], in [`CASS_CACHE_FILE`]) and reused until the TTL
+//! passes or the cass binary changes.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::sync::{LazyLock, Mutex, OnceLock};
+use std::time::{Duration, Instant, UNIX_EPOCH};
 
 use fsqlite::Connection;
 use fsqlite::compat::{ConnectionExt, OptionalExtension, RowExt};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
 use crate::ms_params as params;
@@ -15,6 +28,25 @@ use crate::ms_params as params;
 use crate::error::{MsError, Result};
 use crate::security::SafetyGate;
 
+/// File in the ms root holding cached CASS probes and session fingerprints.
+pub const CASS_CACHE_FILE: &str = "cass_cache.db";
+
+/// How long probe results are reused by default.
+pub const DEFAULT_PROBE_TTL: Duration = Duration::from_secs(300);
+
+/// How long the availability probe waits for `cass health`.
+pub const DEFAULT_PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Oldest cass whose robot output ms understands.
+pub const MIN_CASS_VERSION: &str = "0.6.0";
+
+const PROBE_HEALTH: &str = "health";
+const PROBE_CAPABILITIES: &str = "capabilities";
+
+/// Availability probes already run in this process, by install identity.
+static PROCESS_PROBES: LazyLock<Mutex<HashMap<String, CassProbe>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
 /// Client for interacting with CASS (Coding Agent Session Search)
 pub struct CassClient {
     /// Path to cass binary (default: "cass")
@@ -28,18 +60,23 @@ pub struct CassClient {
 
     /// Optional safety gate for command execution
     safety: Option<SafetyGate>,
+
+    /// Persisted probe results shared across invocations
+    probe_cache: Option<ProbeCache>,
+
+    /// How long the availability probe may take
+    probe_timeout: Duration,
+
+    /// Availability, probed at most once per client (and reused across
+    /// clients of the same install)
+    probe: OnceLock<CassProbe>,
 }
 
 impl CassClient {
     /// Create a new CASS client with default settings
     #[must_use]
     pub fn new() -> Self {
-        Self {
-            cass_bin: PathBuf::from("cass"),
-            data_dir: None,
-            fingerprint_cache: None,
-            safety: None,
-        }
+        Self::with_binary("cass")
     }
 
     /// Create a CASS client with custom binary path
@@ -49,6 +86,9 @@ impl CassClient {
             data_dir: None,
             fingerprint_cache: None,
             safety: None,
+            probe_cache: None,
+            probe_timeout: DEFAULT_PROBE_TIMEOUT,
+            probe: OnceLock::new(),
         }
     }
 
@@ -69,24 +109,173 @@ impl CassClient {
         self
     }
 
-    /// Check if CASS is available and responsive
+    /// Reuse availability, health and capability probes from `cache`
+    pub fn with_probe_cache(mut self, cache: ProbeCache) -> Self {
+        self.probe_cache = Some(cache);
+        self
+    }
+
+    /// Give up on the availability probe after `timeout`
+    #[must_use]
+    pub const fn with_probe_timeout(mut self, timeout: Duration) -> Self {
+        self.probe_timeout = timeout;
+        self
+    }
+
+    /// Drop every cached probe result so the next calls probe cass again
+    pub fn clear_probe_cache(&self) -> Result<()> {
+        if let Some(cache) = &self.probe_cache {
+            cache.clear()?;
+        }
+        if let Ok(mut probes) = PROCESS_PROBES.lock() {
+            probes.remove(&self.probe_key());
+        }
+        Ok(())
+    }
+
+    /// Check if CASS is installed, answers `cass health` in time and is
+    /// recent enough
     pub fn is_available(&self) -> bool {
-        let mut cmd = Command::new(&self.cass_bin);
-        cmd.arg("--version");
-        if let Some(gate) = self.safety.as_ref() {
-            let command_str = command_string(&cmd);
-            if gate.enforce(&command_str, None).is_err() {
-                return false;
+        self.probe().is_available()
+    }
+
+    /// The availability probe, run at most once per process for this
+    /// install (a zero probe-cache TTL, i.e. `--no-cache`, probes again)
+    pub fn probe(&self) -> &CassProbe {
+        self.probe.get_or_init(|| {
+            let key = self.probe_key();
+            let reuse = self
+                .probe_cache
+                .as_ref()
+                .is_none_or(|cache| !cache.ttl.is_zero());
+            if reuse
+                && let Some(probe) = PROCESS_PROBES
+                    .lock()
+                    .ok()
+                    .and_then(|probes| probes.get(&key).cloned())
+            {
+                return probe;
+            }
+            let probe = self.run_probe();
+            tracing::debug!(
+                binary = %probe.binary.display(),
+                status = ?probe.status,
+                latency_ms = probe.latency_ms,
+                "cass probed"
+            );
+            if let Ok(mut probes) = PROCESS_PROBES.lock() {
+                probes.insert(key, probe.clone());
+            }
+            probe
+        })
+    }
+
+    /// The error commands fail with while cass is unusable
+    #[must_use]
+    pub fn unavailable_error(&self) -> MsError {
+        MsError::CassUnavailable(
+            self.probe()
+                .unavailable_reason()
+                .unwrap_or_else(|| "CASS is unavailable".to_string()),
+        )
+    }
+
+    fn probe_key(&self) -> String {
+        self.install_identity()
+            .unwrap_or_else(|| self.cass_bin.display().to_string())
+    }
+
+    /// Run `cass health` within the probe timeout and classify the result
+    fn run_probe(&self) -> CassProbe {
+        let mut probe = CassProbe {
+            binary: self.cass_bin.clone(),
+            resolved: which::which(&self.cass_bin).ok(),
+            data_dir: self.data_dir.clone(),
+            status: CassProbeStatus::NotFound,
+            health: None,
+            latency_ms: 0,
+            cached: false,
+        };
+        if probe.resolved.is_none() {
+            return probe;
+        }
+
+        let health = if let Some(health) = self.cached_probe::<CassHealth>(PROBE_HEALTH) {
+            probe.cached = true;
+            health
+        } else {
+            let mut cmd = self.command(&["health", "--robot"]);
+            if let Some(gate) = self.safety.as_ref()
+                && let Err(err) = gate.enforce(&command_string(&cmd), None)
+            {
+                probe.status = CassProbeStatus::Unreachable {
+                    error: err.to_string(),
+                };
+                return probe;
             }
+            let started = Instant::now();
+            let output = output_within(&mut cmd, self.probe_timeout);
+            probe.latency_ms = started.elapsed().as_millis() as u64;
+            let output = match output {
+                Ok(Some(output)) if output.status.success() => output,
+                Ok(Some(output)) => {
+                    probe.status = CassProbeStatus::Unreachable {
+                        error: format!(
+                            "exit {}: {}",
+                            output.status.code().unwrap_or(-1),
+                            String::from_utf8_lossy(&output.stderr).trim()
+                        ),
+                    };
+                    return probe;
+                }
+                Ok(None) => {
+                    probe.status = CassProbeStatus::TimedOut {
+                        timeout_ms: self.probe_timeout.as_millis() as u64,
+                    };
+                    return probe;
+                }
+                Err(err) => {
+                    probe.status = CassProbeStatus::Unreachable {
+                        error: err.to_string(),
+                    };
+                    return probe;
+                }
+            };
+            match serde_json::from_slice::<CassHealth>(&output.stdout) {
+                Ok(health) => health,
+                Err(err) => {
+                    probe.status = CassProbeStatus::Unreachable {
+                        error: format!("unreadable health output: {err}"),
+                    };
+                    return probe;
+                }
+            }
+        };
+
+        let minimum = semver::Version::parse(MIN_CASS_VERSION).ok();
+        let version = crate::core::requirements::extract_version(&health.version);
+        probe.status = match (version, minimum) {
+            (Some(version), Some(minimum)) if version < minimum => CassProbeStatus::TooOld {
+                version: health.version.clone(),
+            },
+            _ => CassProbeStatus::Available,
+        };
+        // Only a healthy, supported result is worth reusing; a broken
+        // install should be re-checked by the next invocation.
+        if !probe.cached && health.healthy && probe.is_available() {
+            self.store_probe(PROBE_HEALTH, &health);
         }
-        cmd.output().map(|o| o.status.success()).unwrap_or(false)
+        probe.health = Some(health);
+        probe
     }
 
     /// Get CASS health status
     pub fn health(&self) -> Result<CassHealth> {
-        let output = self.run_command(&["health", "--robot"])?;
-        serde_json::from_slice(&output)
-            .map_err(|e| MsError::CassUnavailable(format!("Failed to parse health output: {e}")))
+        let probe = self.probe();
+        match &probe.health {
+            Some(health) if probe.is_available() => Ok(health.clone()),
+            _ => Err(self.unavailable_error()),
+        }
     }
 
     /// Search sessions with the given query
@@ -224,9 +413,52 @@ impl CassClient {
 
     /// Get CASS capabilities and schema information
     pub fn capabilities(&self) -> Result<CassCapabilities> {
+        if let Some(capabilities) = self.cached_probe(PROBE_CAPABILITIES) {
+            return Ok(capabilities);
+        }
         let output = self.run_command(&["capabilities", "--robot"])?;
-        serde_json::from_slice(&output)
-            .map_err(|e| MsError::CassUnavailable(format!("Failed to parse capabilities: {e}")))
+        let capabilities: CassCapabilities = serde_json::from_slice(&output)
+            .map_err(|e| MsError::CassUnavailable(format!("Failed to parse capabilities: {e}")))?;
+        self.store_probe(PROBE_CAPABILITIES, &capabilities);
+        Ok(capabilities)
+    }
+
+    /// Identity of the cass install the probes describe: the resolved binary
+    /// (path, size, mtime) and data dir. Upgrading cass changes it, which
+    /// invalidates every cached probe.
+    fn install_identity(&self) -> Option<String> {
+        let path = which::which(&self.cass_bin).ok()?;
+        let meta = std::fs::metadata(&path).ok()?;
+        let modified = meta.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+        Some(format!(
+            "{}|{}|{}|{}",
+            path.display(),
+            meta.len(),
+            modified.as_nanos(),
+            self.data_dir
+                .as_ref()
+                .map(|dir| dir.display().to_string())
+                .unwrap_or_default()
+        ))
+    }
+
+    fn cached_probe<T: DeserializeOwned>(&self, kind: &str) -> Option<T> {
+        let cache = self.probe_cache.as_ref()?;
+        let identity = self.install_identity()?;
+        cache.get(kind, &identity).unwrap_or_else(|err| {
+            tracing::debug!(error = %err, kind, "cass probe cache read failed");
+            None
+        })
+    }
+
+    fn store_probe<T: Serialize>(&self, kind: &str, value: &T) {
+        let (Some(cache), Some(identity)) = (self.probe_cache.as_ref(), self.install_identity())
+        else {
+            return;
+        };
+        if let Err(err) = cache.put(kind, &identity, value) {
+            tracing::debug!(error = %err, kind, "cass probe cache write failed");
+        }
     }
 
     /// Get lightweight session metadata.
@@ -241,14 +473,8 @@ impl CassClient {
         })
     }
 
-    /// Run a CASS command and return stdout
-    fn run_command(&self, args: &[&str]) -> Result<Vec<u8>> {
-        if !self.is_available() {
-            return Err(MsError::CassUnavailable(
-                "CASS binary not found or not executable".into(),
-            ));
-        }
-
+    /// A cass invocation with the configured data directory
+    fn command(&self, args: &[&str]) -> Command {
         let mut cmd = Command::new(&self.cass_bin);
         cmd.args(args);
 
@@ -256,6 +482,16 @@ impl CassClient {
         if let Some(ref data_dir) = self.data_dir {
             cmd.args(["--data-dir", &data_dir.to_string_lossy()]);
         }
+        cmd
+    }
+
+    /// Run a CASS command and return stdout
+    fn run_command(&self, args: &[&str]) -> Result<Vec<u8>> {
+        if !self.is_available() {
+            return Err(self.unavailable_error());
+        }
+
+        let mut cmd = self.command(args);
 
         if let Some(gate) = self.safety.as_ref() {
             let command_str = command_string(&cmd);
@@ -276,6 +512,27 @@ impl CassClient {
     }
 }
 
+/// Run `cmd` to completion, or kill it and return `None` after `timeout`.
+fn output_within(cmd: &mut Command, timeout: Duration) -> std::io::Result<Option<Output>> {
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let started = Instant::now();
+    loop {
+        if child.try_wait()?.is_some() {
+            return child.wait_with_output().map(Some);
+        }
+        if started.elapsed() >= timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Ok(None);
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
 fn command_string(cmd: &Command) -> String {
     let program = cmd.get_program().to_string_lossy().to_string();
     let args = cmd
@@ -329,7 +586,7 @@ fn classify_cass_error(exit_code: i32, stderr: &str) -> MsError {
 
 /// Derive a stable session id from a session file path: the file stem
 /// (e.g. `/…/<uuid>.jsonl` → `<uuid>`).
-fn session_id_from_path(path: &str) -> String {
+pub(super) fn session_id_from_path(path: &str) -> String {
     std::path::Path::new(path)
         .file_stem()
         .and_then(|s| s.to_str())
@@ -346,7 +603,7 @@ fn normalize_match(mut m: SessionMatch) -> SessionMatch {
 
 /// SHA-256 of the raw export bytes, used as a change-detection fingerprint
 /// (cass 0.6.x no longer emits a `content_hash`).
-fn content_hash_of(bytes: &[u8]) -> String {
+pub(super) fn content_hash_of(bytes: &[u8]) -> String {
     use sha2::{Digest, Sha256};
     let mut hasher = Sha256::new();
     hasher.update(bytes);
@@ -391,7 +648,7 @@ where
 /// raw shell command is unrecoverable (cass emits only the description), so the
 /// reconstruction is necessarily lossy but restores enough structure for the
 /// existing extractors to produce patterns again (issue #114).
-fn parse_inline_tool_markers(content: &str, msg_index: usize) -> Vec<ToolCall> {
+pub(super) fn parse_inline_tool_markers(content: &str, msg_index: usize) -> Vec<ToolCall> {
     let mut calls = Vec::new();
     let mut rest = content;
     let mut seq = 0usize;
@@ -583,6 +840,74 @@ pub struct CassHealth {
     pub last_indexed: Option<String>,
 }
 
+/// Result of the availability probe.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum CassProbeStatus {
+    Available,
+    /// The binary is not on PATH (or not at the configured path)
+    NotFound,
+    /// `cass health` failed or printed something unreadable
+    Unreachable {
+        error: String,
+    },
+    /// `cass health` did not answer within the probe timeout
+    TimedOut {
+        timeout_ms: u64,
+    },
+    /// Older than [`MIN_CASS_VERSION`]
+    TooOld {
+        version: String,
+    },
+}
+
+/// What the availability probe found, for `ms doctor` and error messages.
+#[derive(Debug, Clone, Serialize)]
+pub struct CassProbe {
+    /// Configured binary
+    pub binary: PathBuf,
+    /// The binary as found on PATH
+    pub resolved: Option<PathBuf>,
+    pub data_dir: Option<PathBuf>,
+    #[serde(flatten)]
+    pub status: CassProbeStatus,
+    pub health: Option<CassHealth>,
+    /// Round trip of `cass health` (0 when `cached`)
+    pub latency_ms: u64,
+    /// Whether the health result came from the probe cache
+    pub cached: bool,
+}
+
+impl CassProbe {
+    #[must_use]
+    pub fn is_available(&self) -> bool {
+        self.status == CassProbeStatus::Available
+    }
+
+    /// Why cass cannot be used and what to do about it; `None` when it can.
+    #[must_use]
+    pub fn unavailable_reason(&self) -> Option<String> {
+        let at = self.resolved.as_ref().unwrap_or(&self.binary).display();
+        match &self.status {
+            CassProbeStatus::Available => None,
+            CassProbeStatus::NotFound => Some(format!(
+                "CASS not found at {at}; install via `cargo install cass` or set [cass].cass_path"
+            )),
+            CassProbeStatus::Unreachable { error } => Some(format!(
+                "CASS at {at} failed its health check ({error}); run `cass health` to diagnose"
+            )),
+            CassProbeStatus::TimedOut { timeout_ms } => Some(format!(
+                "CASS at {at} did not answer `cass health` within {timeout_ms}ms; \
+                 check that it is not busy reindexing"
+            )),
+            CassProbeStatus::TooOld { version } => Some(format!(
+                "CASS {version} at {at} is older than the supported minimum \
+                 {MIN_CASS_VERSION}; upgrade via `cargo install cass`"
+            )),
+        }
+    }
+}
+
 /// CASS capabilities and schema
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CassCapabilities {
@@ -594,6 +919,83 @@ pub struct CassCapabilities {
     pub supports_robot_mode: bool,
 }
 
+// =============================================================================
+// Probe Cache
+// =============================================================================
+
+/// Persisted CASS probe results (availability, health, capabilities), each
+/// stored with the install identity it was probed from.
+pub struct ProbeCache {
+    conn: Connection,
+    ttl: Duration,
+}
+
+impl ProbeCache {
+    /// Open or create a probe cache at the given path. Results older than
+    /// `ttl` are ignored; a zero TTL always probes but still records results.
+    pub fn open(path: impl AsRef<Path>, ttl: Duration) -> Result<Self> {
+        let conn = Connection::open(path.as_ref().to_string_lossy().into_owned())?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS cass_probes (
+                kind TEXT PRIMARY KEY,
+                install TEXT NOT NULL,
+                payload TEXT NOT NULL,
+                probed_at INTEGER NOT NULL
+            )",
+        )?;
+        Ok(Self { conn, ttl })
+    }
+
+    /// The cached result of `kind` if it was probed from `install` within
+    /// the TTL.
+    pub fn get<T: DeserializeOwned>(&self, kind: &str, install: &str) -> Result<Option<T>> {
+        let row = self
+            .conn
+            .query_row_map(
+                "SELECT install, payload, probed_at FROM cass_probes WHERE kind = ?",
+                params![kind],
+                |row| {
+                    Ok((
+                        row.get_typed::<String>(0)?,
+                        row.get_typed::<String>(1)?,
+                        row.get_typed::<i64>(2)?,
+                    ))
+                },
+            )
+            .optional()?;
+        let Some((cached_install, payload, probed_at)) = row else {
+            return Ok(None);
+        };
+        let age = chrono::Utc::now().timestamp() - probed_at;
+        let fresh = u64::try_from(age).is_ok_and(|age| age < self.ttl.as_secs());
+        if cached_install != install || !fresh {
+            return Ok(None);
+        }
+        Ok(serde_json::from_str(&payload).ok())
+    }
+
+    /// Record the result of `kind` probed from `install`
+    pub fn put<T: Serialize>(&self, kind: &str, install: &str, value: &T) -> Result<()> {
+        let payload = serde_json::to_string(value)?;
+        self.conn.execute_compat(
+            "INSERT INTO cass_probes (kind, install, payload, probed_at)
+             VALUES (?, ?, ?, ?)
+             ON CONFLICT(kind) DO UPDATE SET
+                install = excluded.install,
+                payload = excluded.payload,
+                probed_at = excluded.probed_at",
+            params![kind, install, payload, chrono::Utc::now().timestamp()],
+        )?;
+        Ok(())
+    }
+
+    /// Drop every cached result
+    pub fn clear(&self) -> Result<()> {
+        self.conn.execute("DELETE FROM cass_probes")?;
+        Ok(())
+    }
+}
+
 // =============================================================================
 // Fingerprint Cache
 // =============================================================================
@@ -749,6 +1151,212 @@ mod tests {
         assert_eq!(cache.count().unwrap(), 0);
     }
 
+    #[test]
+    fn test_probe_cache_expires_and_tracks_install() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(CASS_CACHE_FILE);
+        let cache = ProbeCache::open(&path, DEFAULT_PROBE_TTL).unwrap();
+        cache.put("version", "cass|1", &"cass 0.6.2").unwrap();
+
+        assert_eq!(
+            cache.get::<String>("version", "cass|1").unwrap().as_deref(),
+            Some("cass 0.6.2")
+        );
+        assert!(cache.get::<String>("version", "cass|2").unwrap().is_none());
+
+        // Another handle on the same file sees the entry; a zero TTL does not.
+        let reopened = ProbeCache::open(&path, DEFAULT_PROBE_TTL).unwrap();
+        assert!(
+            reopened
+                .get::<String>("version", "cass|1")
+                .unwrap()
+                .is_some()
+        );
+        let expired = ProbeCache::open(&path, Duration::ZERO).unwrap();
+        assert!(
+            expired
+                .get::<String>("version", "cass|1")
+                .unwrap()
+                .is_none()
+        );
+
+        cache.clear().unwrap();
+        assert!(
+            reopened
+                .get::<String>("version", "cass|1")
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    /// Write a fake cass that logs each invocation and takes 300ms to answer.
+    #[cfg(unix)]
+    fn mock_cass(dir: &Path, version: &str) -> (PathBuf, PathBuf) {
+        use std::os::unix::fs::PermissionsExt;
+
+        let bin = dir.join("cass");
+        let log = dir.join("calls.log");
+        let script = format!(
+            "#!/bin/sh\necho \"$1\" >> '{log}'\nsleep 0.3\ncase \"$1\" in\n\
+             --version) echo 'cass {version}' ;;\n\
+             health) echo '{{\"healthy\":true,\"version\":\"{version}\",\"database_ok\":true,\"index_ok\":true,\"session_count\":3,\"last_indexed\":null}}' ;;\n\
+             capabilities) echo '{{\"version\":\"{version}\",\"search_modes\":[\"lexical\"],\"output_formats\":[\"json\"],\"max_results\":100,\"supports_incremental\":true,\"supports_robot_mode\":true}}' ;;\n\
+             esac\n",
+            log = log.display()
+        );
+        std::fs::write(&bin, script).unwrap();
+        std::fs::set_permissions(&bin, std::fs::Permissions::from_mode(0o755)).unwrap();
+        (bin, log)
+    }
+
+    #[cfg(unix)]
+    fn calls(log: &Path) -> usize {
+        std::fs::read_to_string(log).map_or(0, |log| log.lines().count())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_second_client_reuses_cached_probes() {
+        let dir = tempdir().unwrap();
+        let (bin, log) = mock_cass(dir.path(), "0.6.2");
+        let cache_path = dir.path().join(CASS_CACHE_FILE);
+        let client = |ttl| {
+            CassClient::with_binary(&bin)
+                .with_probe_cache(ProbeCache::open(&cache_path, ttl).unwrap())
+        };
+
+        let started = Instant::now();
+        let first = client(DEFAULT_PROBE_TTL);
+        assert!(first.health().unwrap().healthy);
+        assert!(first.capabilities().unwrap().supports_robot_mode);
+        assert!(started.elapsed() >= Duration::from_millis(600));
+        assert_eq!(calls(&log), 2, "health (the probe), capabilities");
+        assert!(first.probe().latency_ms >= 300);
+
+        // A fresh client (a new ms invocation) skips every round trip.
+        let started = Instant::now();
+        let second = client(DEFAULT_PROBE_TTL);
+        assert!(second.is_available());
+        assert_eq!(second.health().unwrap().version, "0.6.2");
+        assert!(second.capabilities().is_ok());
+        assert!(
+            started.elapsed() < Duration::from_millis(300),
+            "cached probes took {:?}",
+            started.elapsed()
+        );
+        assert_eq!(calls(&log), 2);
+
+        // --no-cache probes again.
+        assert!(client(Duration::ZERO).health().is_ok());
+        assert_eq!(calls(&log), 3);
+
+        // Upgrading cass invalidates the cache.
+        mock_cass(dir.path(), "0.7.0-beta");
+        assert_eq!(
+            client(DEFAULT_PROBE_TTL).health().unwrap().version,
+            "0.7.0-beta"
+        );
+        assert_eq!(calls(&log), 4);
+
+        // So does an explicit refresh.
+        let refreshed = client(DEFAULT_PROBE_TTL);
+        refreshed.clear_probe_cache().unwrap();
+        assert!(refreshed.capabilities().is_ok());
+        assert_eq!(calls(&log), 6);
+    }
+
+    /// Write a fake cass whose `health` runs `body`.
+    #[cfg(unix)]
+    fn fake_cass(dir: &Path, body: &str) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+
+        let bin = dir.join("cass");
+        std::fs::write(&bin, format!("#!/bin/sh\n{body}\n")).unwrap();
+        std::fs::set_permissions(&bin, std::fs::Permissions::from_mode(0o755)).unwrap();
+        bin
+    }
+
+    #[cfg(unix)]
+    fn health_json(version: &str) -> String {
+        format!(
+            "echo '{{\"healthy\":true,\"version\":\"{version}\",\"database_ok\":true,\"index_ok\":true,\"session_count\":0,\"last_indexed\":null}}'"
+        )
+    }
+
+    #[test]
+    fn test_probe_reports_missing_binary() {
+        let dir = tempdir().unwrap();
+        let client = CassClient::with_binary(dir.path().join("no-such-cass"));
+        assert!(!client.is_available());
+        assert_eq!(client.probe().status, CassProbeStatus::NotFound);
+        let err = client.search("anything", 5).unwrap_err();
+        assert!(matches!(err, MsError::CassUnavailable(_)));
+        assert!(err.to_string().contains("CASS not found at"), "{err}");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_probe_reports_unreachable_cass() {
+        let dir = tempdir().unwrap();
+        let bin = fake_cass(dir.path(), "echo 'database is corrupt' >&2\nexit 3");
+        let client = CassClient::with_binary(&bin);
+        assert!(matches!(
+            client.probe().status,
+            CassProbeStatus::Unreachable { ref error } if error.contains("database is corrupt")
+        ));
+        assert!(matches!(client.health(), Err(MsError::CassUnavailable(_))));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_probe_times_out_on_slow_cass() {
+        let dir = tempdir().unwrap();
+        let bin = fake_cass(dir.path(), &format!("sleep 5\n{}", health_json("0.6.2")));
+        let started = Instant::now();
+        let client = CassClient::with_binary(&bin).with_probe_timeout(Duration::from_millis(200));
+        assert!(!client.is_available());
+        assert!(started.elapsed() < Duration::from_secs(2));
+        assert_eq!(
+            client.probe().status,
+            CassProbeStatus::TimedOut { timeout_ms: 200 }
+        );
+        assert!(
+            client
+                .probe()
+                .unavailable_reason()
+                .unwrap()
+                .contains("within 200ms")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_probe_rejects_old_cass_once_per_process() {
+        let dir = tempdir().unwrap();
+        let log = dir.path().join("calls.log");
+        let bin = fake_cass(
+            dir.path(),
+            &format!(
+                "echo probe >> '{}'\n{}",
+                log.display(),
+                health_json("0.5.9")
+            ),
+        );
+        let client = CassClient::with_binary(&bin);
+        assert_eq!(
+            client.probe().status,
+            CassProbeStatus::TooOld {
+                version: "0.5.9".to_string()
+            }
+        );
+        let reason = client.probe().unavailable_reason().unwrap();
+        assert!(reason.contains(MIN_CASS_VERSION), "{reason}");
+
+        // Another client for the same install reuses the probe
+        assert!(!CassClient::with_binary(&bin).is_available());
+        assert_eq!(calls(&log), 1);
+    }
+
     #[test]
     fn test_error_classification_not_found() {
         let err = classify_cass_error(2, "Session not found: xyz");
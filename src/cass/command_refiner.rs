@@ -0,0 +1,347 @@
+//! Generalization critique by an external command.
+//!
+//! [`CommandRefiner`] implements [`GeneralizationRefiner`] by running the
+//! command from `[build.refiner]` (`claude -p`, `llm`, or any script that
+//! reads stdin and writes stdout). It sends the candidate's common elements
+//! and a sample of the cluster as one JSON payload, with instructions, and
+//! expects a JSON critique back.
+//!
+//! The command is bounded by a timeout and an output size limit; running
+//! past either is an error, which the transformer logs before carrying on
+//! uncritiqued. Output that is not a valid critique is not an error: it
+//! becomes a low-confidence critique that flags nothing.
+
+use std::io::{Read, Write};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+use serde_json::json;
+
+use super::transformation::{
+    CommonElements, GeneralizationRefiner, InstanceCluster, RefinementCritique,
+};
+use crate::config::RefinerConfig;
+use crate::error::{MsError, Result};
+
+/// Characters of each sampled instance sent to the command.
+const MAX_INSTANCE_CHARS: usize = 1000;
+
+const INSTRUCTIONS: &str = "You review generalizations mined from coding sessions. \
+Given the common elements extracted from a cluster and a sample of its instances, decide \
+whether the generalization claims more than the instances support (overgeneralization). \
+Reply with only a JSON object: {\"summary\": string, \"flags_overgeneralization\": boolean, \
+\"suggested_refinements\": [string], \"critique_confidence\": number between 0 and 1}.";
+
+/// Runs `[build.refiner] command` to critique generalizations.
+#[derive(Debug, Clone)]
+pub struct CommandRefiner {
+    program: String,
+    args: Vec<String>,
+    timeout: Duration,
+    max_output_bytes: usize,
+    sample_size: usize,
+}
+
+impl CommandRefiner {
+    /// The configured refiner, or `None` when `command` is empty.
+    #[must_use]
+    pub fn from_config(config: &RefinerConfig) -> Option<Self> {
+        let (program, args) = config.command.split_first()?;
+        Some(Self {
+            program: program.clone(),
+            args: args.to_vec(),
+            timeout: Duration::from_secs(config.timeout_secs),
+            max_output_bytes: config.max_output_bytes,
+            sample_size: config.sample_size,
+        })
+    }
+
+    /// What the command receives on stdin.
+    #[must_use]
+    pub fn payload(&self, common: &CommonElements, cluster: &InstanceCluster) -> serde_json::Value {
+        let mut sample: Vec<_> = cluster.instances.iter().collect();
+        sample.sort_by(|a, b| a.distance_to_centroid.total_cmp(&b.distance_to_centroid));
+        let sample: Vec<_> = sample
+            .into_iter()
+            .take(self.sample_size)
+            .map(|clustered| {
+                json!({
+                    "id": clustered.instance.id,
+                    "content": clustered
+                        .instance
+                        .content
+                        .chars()
+                        .take(MAX_INSTANCE_CHARS)
+                        .collect::<String>(),
+                    "tags": clustered.instance.context.tags,
+                    "is_counter_example": clustered.instance.is_counter_example,
+                    "distance_to_centroid": clustered.distance_to_centroid,
+                })
+            })
+            .collect();
+        json!({
+            "instructions": INSTRUCTIONS,
+            "common_elements": common,
+            "cluster": {
+                "id": cluster.id,
+                "instance_count": cluster.instances.len(),
+                "coherence": cluster.coherence,
+                "context_conditions": cluster.context_conditions,
+                "sample": sample,
+            },
+        })
+    }
+
+    /// Run the command with `input` on stdin; its stdout.
+    fn run(&self, input: Vec<u8>) -> Result<Vec<u8>> {
+        let mut child = Command::new(&self.program)
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|err| MsError::Config(format!("refiner '{}': {err}", self.program)))?;
+
+        let mut stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| MsError::Config(format!("refiner '{}': no stdin", self.program)))?;
+        // The command may exit without reading everything; that is its call.
+        let writer = std::thread::spawn(move || {
+            let _ = stdin.write_all(&input);
+        });
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| MsError::Config(format!("refiner '{}': no stdout", self.program)))?;
+        // Stop reading (closing the pipe) one byte past the limit
+        let limit = self.max_output_bytes as u64 + 1;
+        let reader = std::thread::spawn(move || {
+            let mut buf = Vec::new();
+            stdout.take(limit).read_to_end(&mut buf).map(|_| buf)
+        });
+
+        let start = Instant::now();
+        let status = loop {
+            match child.try_wait()? {
+                Some(status) => break status,
+                None if start.elapsed() < self.timeout => {
+                    std::thread::sleep(Duration::from_millis(25));
+                }
+                None => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(MsError::Config(format!(
+                        "refiner '{}' timed out after {}s",
+                        self.program,
+                        self.timeout.as_secs()
+                    )));
+                }
+            }
+        };
+        let _ = writer.join();
+        let output = reader
+            .join()
+            .map_err(|_| MsError::Config("refiner output capture panicked".to_string()))??;
+
+        if output.len() > self.max_output_bytes {
+            return Err(MsError::Config(format!(
+                "refiner '{}' wrote more than {} bytes",
+                self.program, self.max_output_bytes
+            )));
+        }
+        if !status.success() {
+            return Err(MsError::Config(format!(
+                "refiner '{}' exited with {status}",
+                self.program
+            )));
+        }
+        Ok(output)
+    }
+}
+
+impl GeneralizationRefiner for CommandRefiner {
+    fn critique(
+        &self,
+        common: &CommonElements,
+        cluster: &InstanceCluster,
+    ) -> Result<RefinementCritique> {
+        let input = serde_json::to_vec(&self.payload(common, cluster))?;
+        let output = self.run(input)?;
+        Ok(parse_critique(&String::from_utf8_lossy(&output)))
+    }
+}
+
+/// The critique a refiner must answer with.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CritiqueResponse {
+    summary: String,
+    flags_overgeneralization: bool,
+    #[serde(default)]
+    suggested_refinements: Vec<String>,
+    critique_confidence: f32,
+}
+
+/// Parse a refiner's answer. The JSON object may be surrounded by prose or a
+/// code fence; anything that does not match the schema becomes "no critique,
+/// low confidence".
+#[must_use]
+pub fn parse_critique(output: &str) -> RefinementCritique {
+    let object = match (output.find('{'), output.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &output[start..=end],
+        _ => output,
+    };
+    match serde_json::from_str::<CritiqueResponse>(object) {
+        Ok(response) if (0.0..=1.0).contains(&response.critique_confidence) => RefinementCritique {
+            summary: response.summary,
+            flags_overgeneralization: response.flags_overgeneralization,
+            suggested_refinements: response.suggested_refinements,
+            critique_confidence: response.critique_confidence,
+        },
+        Ok(response) => unparsed_critique(&format!(
+            "critique_confidence {} is outside 0-1",
+            response.critique_confidence
+        )),
+        Err(err) => unparsed_critique(&err.to_string()),
+    }
+}
+
+fn unparsed_critique(reason: &str) -> RefinementCritique {
+    RefinementCritique {
+        summary: format!("refiner output not understood ({reason}); no critique"),
+        flags_overgeneralization: false,
+        suggested_refinements: Vec::new(),
+        critique_confidence: 0.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cass::transformation::{
+        ClusteredInstance, InstanceContext, InstanceSource, SpecificInstance,
+    };
+
+    fn common() -> CommonElements {
+        CommonElements {
+            abstracted_description: "Always retry failed operations".to_string(),
+            invariants: vec!["retries".to_string()],
+            context_conditions: vec![],
+            placeholders: vec![],
+            extraction_confidence: 0.8,
+        }
+    }
+
+    fn cluster(count: usize) -> InstanceCluster {
+        InstanceCluster {
+            id: "cluster-1".to_string(),
+            instances: (0..count)
+                .map(|i| ClusteredInstance {
+                    instance: SpecificInstance {
+                        id: format!("inst-{i}"),
+                        content: format!("retried the flaky HTTP call {i}"),
+                        context: InstanceContext {
+                            file_type: Some("rust".to_string()),
+                            project_type: None,
+                            tags: vec!["http".to_string()],
+                            description: None,
+                        },
+                        source: InstanceSource {
+                            session_id: "s1".to_string(),
+                            message_indices: vec![i],
+                            observed_at: None,
+                        },
+                        is_counter_example: false,
+                    },
+                    distance_to_centroid: 1.0 / (i as f32 + 1.0),
+                    embedding: vec![],
+                })
+                .collect(),
+            context_conditions: vec!["network calls".to_string()],
+            centroid: None,
+            coherence: 0.7,
+        }
+    }
+
+    /// A fake refiner: a shell script given as `sh -c <script>`.
+    fn script_refiner(script: &str, timeout_secs: u64) -> CommandRefiner {
+        CommandRefiner::from_config(&RefinerConfig {
+            command: vec!["sh".to_string(), "-c".to_string(), script.to_string()],
+            timeout_secs,
+            max_output_bytes: 4096,
+            sample_size: 2,
+        })
+        .unwrap()
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn flags_overgeneralization_from_fake_refiner() {
+        let dir = tempfile::tempdir().unwrap();
+        let seen = dir.path().join("payload.json");
+        // Saves the payload, then answers inside a code fence
+        let refiner = script_refiner(
+            &format!(
+                "cat > '{}'\nprintf '%s\\n' 'Verdict:' '```json' \
+                 '{{\"summary\": \"only HTTP retries observed\", \"flags_overgeneralization\": true, \
+                 \"suggested_refinements\": [\"limit to idempotent HTTP calls\"], \
+                 \"critique_confidence\": 0.9}}' '```'",
+                seen.display()
+            ),
+            10,
+        );
+
+        let critique = refiner.critique(&common(), &cluster(4)).unwrap();
+        assert!(critique.flags_overgeneralization);
+        assert_eq!(critique.summary, "only HTTP retries observed");
+        assert_eq!(
+            critique.suggested_refinements,
+            ["limit to idempotent HTTP calls"]
+        );
+
+        let payload: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(seen).unwrap()).unwrap();
+        assert_eq!(
+            payload["common_elements"]["abstracted_description"],
+            "Always retry failed operations"
+        );
+        assert_eq!(payload["cluster"]["instance_count"], 4);
+        // Closest to the centroid first, capped at sample_size
+        let sample = payload["cluster"]["sample"].as_array().unwrap();
+        assert_eq!(sample.len(), 2);
+        assert_eq!(sample[0]["id"], "inst-3");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn command_failures_are_errors() {
+        let slow = script_refiner("sleep 5", 1);
+        let start = Instant::now();
+        assert!(slow.critique(&common(), &cluster(1)).is_err());
+        assert!(start.elapsed() < Duration::from_secs(4));
+
+        let noisy = script_refiner("cat >/dev/null; head -c 10000 /dev/zero", 10);
+        let err = noisy.critique(&common(), &cluster(1)).unwrap_err();
+        assert!(err.to_string().contains("more than 4096 bytes"));
+
+        let failing = script_refiner("exit 3", 10);
+        assert!(failing.critique(&common(), &cluster(1)).is_err());
+    }
+
+    #[test]
+    fn unparseable_output_is_no_critique() {
+        for output in [
+            "I think it is fine.",
+            r#"{"summary": "x", "flags_overgeneralization": "yes", "critique_confidence": 0.5}"#,
+            r#"{"summary": "x", "flags_overgeneralization": true, "critique_confidence": 7}"#,
+            r#"{"summary": "x", "flags_overgeneralization": true, "critique_confidence": 0.5, "extra": 1}"#,
+        ] {
+            let critique = parse_critique(output);
+            assert!(!critique.flags_overgeneralization, "{output}");
+            assert!(critique.critique_confidence.abs() < f32::EPSILON);
+        }
+        assert!(CommandRefiner::from_config(&RefinerConfig::default()).is_none());
+    }
+}
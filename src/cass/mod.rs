@@ -2,29 +2,36 @@
 //!
 //! Mines CASS sessions to extract patterns and generate skills.
 
+pub mod adapters;
 pub mod brenner;
 pub mod client;
+pub mod command_refiner;
 pub mod mining;
 pub mod quality;
 pub mod refinement;
+pub mod skill_id;
 pub mod synthesis;
 pub mod transformation;
 pub mod uncertainty;
 
 // Re-export main types
+pub use adapters::{SessionFormat, load_session_file};
 pub use brenner::{
     BrennerConfig, BrennerSkillDraft, BrennerWizard, CognitiveMove, CognitiveMoveTag, MoveDecision,
-    MoveEvidence, SelectedSession, SkillExample, SkillRule, TestResults, WizardCheckpoint,
-    WizardOutput, WizardState, generate_skill_md,
+    MoveEvidence, SelectedSession, SessionFingerprint, SkillExample, SkillRule, TestResults,
+    WizardCheckpoint, WizardOutput, WizardState, generate_skill_md, wizard_checkpoint_dir,
 };
 pub use client::{
-    CassCapabilities, CassClient, CassHealth, FingerprintCache, Session, SessionExpanded,
-    SessionMatch, SessionMessage, SessionMetadata, ToolCall, ToolResult,
+    CASS_CACHE_FILE, CassCapabilities, CassClient, CassHealth, CassProbe, CassProbeStatus,
+    DEFAULT_PROBE_TIMEOUT, DEFAULT_PROBE_TTL, FingerprintCache, MIN_CASS_VERSION, ProbeCache,
+    Session, SessionExpanded, SessionMatch, SessionMessage, SessionMetadata, ToolCall, ToolResult,
 };
+pub use command_refiner::CommandRefiner;
 pub use mining::{
     Pattern, PatternType, SegmentedSession, SessionPhase, SessionSegment, segment_session,
 };
 pub use quality::{MissingSignal, QualityConfig, QualityScorer, SessionQuality};
+pub use skill_id::{MinedIdRegistry, MinedSkillId};
 pub use synthesis::SkillDraft;
 pub use transformation::{
     GeneralPattern, GeneralizationRefiner, GeneralizationValidation, InstanceCluster,
@@ -32,8 +39,8 @@ pub use transformation::{
     UncertaintyQueueSink,
 };
 pub use uncertainty::{
-    DefaultQueryGenerator, DefaultResolver, QueryGenerator, QueryResults, QueryType, Resolution,
-    ResolutionAttempt, ResolutionResult, SuggestedQuery, UncertaintyConfig, UncertaintyCounts,
-    UncertaintyId, UncertaintyItem, UncertaintyQueue, UncertaintyReason, UncertaintyResolver,
-    UncertaintyStatus,
+    DefaultQueryGenerator, DefaultResolver, PersistentUncertaintyQueue, QueryGenerator,
+    QueryResults, QueryType, Resolution, ResolutionAttempt, ResolutionResult, SuggestedQuery,
+    UncertaintyConfig, UncertaintyCounts, UncertaintyId, UncertaintyItem, UncertaintyQueue,
+    UncertaintyReason, UncertaintyResolver, UncertaintyStatus, UncertaintyStore,
 };
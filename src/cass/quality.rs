@@ -29,10 +29,10 @@ impl SessionQuality {
         self.score >= config.min_score
     }
 
-    /// Get a human-readable summary of the quality assessment
+    /// Grade word for the score ("excellent" down to "very poor")
     #[must_use]
-    pub fn summary(&self) -> String {
-        let grade = if self.score >= 0.8 {
+    pub const fn grade(&self) -> &'static str {
+        if self.score >= 0.8 {
             "excellent"
         } else if self.score >= 0.6 {
             "good"
@@ -42,11 +42,27 @@ impl SessionQuality {
             "poor"
         } else {
             "very poor"
-        };
+        }
+    }
 
+    /// Short badge for session lists, e.g. `good 72%`
+    #[must_use]
+    pub fn badge(&self) -> String {
+        format!("{} {:.0}%", self.grade(), self.score * 100.0)
+    }
+
+    /// Why the session would be skipped, one short phrase per missing signal
+    #[must_use]
+    pub fn skip_reasons(&self, config: &QualityConfig) -> Vec<String> {
+        self.missing.iter().map(|m| m.reason(config)).collect()
+    }
+
+    /// Get a human-readable summary of the quality assessment
+    #[must_use]
+    pub fn summary(&self) -> String {
         format!(
             "Quality: {} ({:.0}%) - {} positive signals, {} missing",
-            grade,
+            self.grade(),
             self.score * 100.0,
             self.signals.len(),
             self.missing.len()
@@ -70,6 +86,8 @@ pub enum MissingSignal {
     TooShort,
     /// Session is excessively long (may indicate thrashing)
     TooLong,
+    /// Session was abandoned before the work finished
+    Abandoned,
 }
 
 impl MissingSignal {
@@ -83,6 +101,21 @@ impl MissingSignal {
             Self::NoCodeChanges => "No code changes were made",
             Self::TooShort => "Session is too short to contain meaningful patterns",
             Self::TooLong => "Session is excessively long, may indicate thrashing",
+            Self::Abandoned => "Session was abandoned before the work finished",
+        }
+    }
+
+    /// Terse phrase for skip reports, e.g. `under 3 messages`
+    #[must_use]
+    pub fn reason(&self, config: &QualityConfig) -> String {
+        match self {
+            Self::NoTestsPassed => "no validation phase".to_string(),
+            Self::NoUserConfirmation => "no user confirmation".to_string(),
+            Self::NoClearResolution => "no clear resolution".to_string(),
+            Self::NoCodeChanges => "no code changes".to_string(),
+            Self::TooShort => format!("under {} messages", config.min_turns),
+            Self::TooLong => format!("over {} messages", config.max_turns),
+            Self::Abandoned => "abandoned mid-change".to_string(),
         }
     }
 }
@@ -214,6 +247,7 @@ impl QualityScorer {
         if is_abandoned(&session.messages) {
             score -= self.config.penalty_abandoned;
             signals.push("abandoned".to_string());
+            missing.push(MissingSignal::Abandoned);
         }
 
         // Normalize score to 0.0-1.0 range
@@ -637,6 +671,9 @@ mod tests {
 
         assert!(quality.score < 0.3);
         assert!(quality.signals.contains(&"abandoned".to_string()));
+        let reasons = quality.skip_reasons(scorer.config());
+        assert!(reasons.contains(&"no validation phase".to_string()));
+        assert!(reasons.contains(&"abandoned mid-change".to_string()));
     }
 
     #[test]
@@ -677,6 +714,7 @@ mod tests {
         let summary = quality.summary();
         assert!(summary.contains("good"));
         assert!(summary.contains("65%"));
+        assert_eq!(quality.badge(), "good 65%");
     }
 
     #[test]
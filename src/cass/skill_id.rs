@@ -0,0 +1,489 @@
+//! Deterministic ids for mined skills
+//!
+//! A mined skill id is `<slug>-<fingerprint>`: the slugified skill name plus a
+//! prefix of a SHA-256 over the content of its pattern cluster. Re-mining the
+//! same workflow yields the same id, so rebuilds recognize "this is the same
+//! skill as before" instead of minting a new one.
+//!
+//! Assignments are recorded in a small registry file under the ms root, which
+//! also maps ids from the old session-prefixed scheme (`cmd_a1b2c3d4`) to their
+//! replacements so previously generated ids keep resolving.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::{MsError, Result};
+use crate::storage::{Database, GlobalLock, lock_timeout};
+
+use super::mining::ExtractedPattern;
+
+/// Number of fingerprint hex characters used in a fresh id.
+pub const FINGERPRINT_MIN_LEN: usize = 8;
+
+/// Characters added to the fingerprint each time an id collides.
+const FINGERPRINT_STEP: usize = 4;
+
+/// Registry file name, relative to the ms root.
+pub const REGISTRY_FILE: &str = "mined-ids.json";
+
+/// Lock file guarding registry updates, relative to the ms root.
+const REGISTRY_LOCK_FILE: &str = "mined-ids.lock";
+
+/// Alias type recorded for old-style ids migrated into the alias table.
+pub const LEGACY_ALIAS_TYPE: &str = "legacy_mined";
+
+/// Slugify a skill name into an id-safe form.
+#[must_use]
+pub fn slugify(name: &str) -> String {
+    let mut out = String::new();
+    let mut prev_dash = false;
+    for ch in name.chars() {
+        let lower = ch.to_ascii_lowercase();
+        if lower.is_ascii_alphanumeric() {
+            out.push(lower);
+            prev_dash = false;
+        } else if !prev_dash {
+            out.push('-');
+            prev_dash = true;
+        }
+    }
+    let trimmed = out.trim_matches('-');
+    if trimmed.is_empty() {
+        "mined-skill".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Fingerprint of a pattern cluster (hex SHA-256).
+///
+/// Pattern fingerprints are sorted and deduplicated first, so the result does
+/// not depend on session order or on how often a pattern was observed.
+#[must_use]
+pub fn cluster_fingerprint(patterns: &[ExtractedPattern]) -> String {
+    let mut fingerprints: Vec<String> = patterns
+        .iter()
+        .map(ExtractedPattern::content_fingerprint)
+        .collect();
+    fingerprints.sort();
+    fingerprints.dedup();
+
+    let mut hasher = Sha256::new();
+    for fingerprint in &fingerprints {
+        hasher.update(fingerprint.as_bytes());
+        hasher.update(b"\n");
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Merge patterns with identical content ids, combining their evidence.
+///
+/// Mined pattern ids are content fingerprints, so equal ids mean the same
+/// pattern was observed in more than one session.
+#[must_use]
+pub fn dedupe_by_fingerprint(patterns: Vec<ExtractedPattern>) -> Vec<ExtractedPattern> {
+    let mut index: HashMap<String, usize> = HashMap::new();
+    let mut unique: Vec<ExtractedPattern> = Vec::new();
+    for pattern in patterns {
+        if let Some(&idx) = index.get(&pattern.id) {
+            let existing = &mut unique[idx];
+            existing.frequency += pattern.frequency;
+            existing.evidence.extend(pattern.evidence);
+            existing.confidence = existing.confidence.max(pattern.confidence);
+        } else {
+            index.insert(pattern.id.clone(), unique.len());
+            unique.push(pattern);
+        }
+    }
+    unique
+}
+
+/// Id assigned to a mined skill.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MinedSkillId {
+    pub id: String,
+    /// Full cluster fingerprint the id was derived from.
+    pub fingerprint: String,
+    /// True when the fingerprint was already registered (a re-mine/update).
+    pub existing: bool,
+    /// Old-style ids now resolving to this id.
+    pub legacy_ids: Vec<String>,
+}
+
+/// A registered mined skill.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MinedSkillEntry {
+    pub id: String,
+    pub name: String,
+}
+
+/// Persistent fingerprint → id map plus the legacy id compatibility map.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MinedIdRegistry {
+    /// Keyed by full cluster fingerprint.
+    #[serde(default)]
+    pub skills: BTreeMap<String, MinedSkillEntry>,
+    /// Old-style id → current id.
+    #[serde(default)]
+    pub legacy: BTreeMap<String, String>,
+}
+
+impl MinedIdRegistry {
+    #[must_use]
+    pub fn path(ms_root: &Path) -> PathBuf {
+        ms_root.join(REGISTRY_FILE)
+    }
+
+    /// Load the registry, returning an empty one when the file does not exist.
+    pub fn load(ms_root: &Path) -> Result<Self> {
+        let path = Self::path(ms_root);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)?;
+        serde_json::from_str(&content).map_err(|e| {
+            MsError::Config(format!(
+                "Failed to parse mined id registry {}: {e}",
+                path.display()
+            ))
+        })
+    }
+
+    /// Write the registry through a temp file and rename, so readers never
+    /// see a partial file. Callers sharing the ms root should go through
+    /// [`update`](Self::update), which holds the registry lock.
+    pub fn save(&self, ms_root: &Path) -> Result<()> {
+        fs::create_dir_all(ms_root)?;
+        let content = serde_json::to_string_pretty(self)?;
+        let mut staged = tempfile::NamedTempFile::new_in(ms_root)?;
+        staged.write_all(content.as_bytes())?;
+        staged
+            .persist(Self::path(ms_root))
+            .map_err(|err| MsError::Io(err.error))?;
+        Ok(())
+    }
+
+    /// Load the registry, apply `change`, and save it while holding the
+    /// registry lock, so concurrent builds never drop each other's ids.
+    pub fn update<T>(ms_root: &Path, change: impl FnOnce(&mut Self) -> T) -> Result<T> {
+        fs::create_dir_all(ms_root)?;
+        let _lock = GlobalLock::acquire_at_or_busy(
+            &ms_root.join(REGISTRY_LOCK_FILE),
+            REGISTRY_FILE,
+            lock_timeout(),
+        )?;
+        let mut registry = Self::load(ms_root)?;
+        let out = change(&mut registry);
+        registry.save(ms_root)?;
+        Ok(out)
+    }
+
+    /// Look up the id registered for a cluster fingerprint.
+    #[must_use]
+    pub fn find_by_fingerprint(&self, fingerprint: &str) -> Option<&MinedSkillEntry> {
+        self.skills.get(fingerprint)
+    }
+
+    /// Assign an id for a mined skill.
+    ///
+    /// A known fingerprint keeps its id. Otherwise the id is the name slug plus
+    /// the first [`FINGERPRINT_MIN_LEN`] fingerprint characters; if another
+    /// fingerprint already owns that id, the fingerprint is extended until the
+    /// id is unique. `legacy_ids` are recorded so they resolve to the new id.
+    pub fn assign(&mut self, name: &str, fingerprint: &str, legacy_ids: &[String]) -> MinedSkillId {
+        let (id, existing) = if let Some(entry) = self.skills.get(fingerprint) {
+            (entry.id.clone(), true)
+        } else {
+            let id = self.fresh_id(name, fingerprint);
+            self.skills.insert(
+                fingerprint.to_string(),
+                MinedSkillEntry {
+                    id: id.clone(),
+                    name: name.to_string(),
+                },
+            );
+            (id, false)
+        };
+
+        let mut recorded = Vec::new();
+        for legacy in legacy_ids {
+            if *legacy == id {
+                continue;
+            }
+            // First mapping wins so a legacy id never silently changes target.
+            let target = self
+                .legacy
+                .entry(legacy.clone())
+                .or_insert_with(|| id.clone());
+            if *target == id {
+                recorded.push(legacy.clone());
+            }
+        }
+
+        MinedSkillId {
+            id,
+            fingerprint: fingerprint.to_string(),
+            existing,
+            legacy_ids: recorded,
+        }
+    }
+
+    fn fresh_id(&self, name: &str, fingerprint: &str) -> String {
+        let slug = slugify(name);
+        let mut len = FINGERPRINT_MIN_LEN.min(fingerprint.len());
+        loop {
+            let candidate = format!("{slug}-{}", &fingerprint[..len]);
+            let taken = self.skills.values().any(|entry| entry.id == candidate)
+                || self.legacy.contains_key(&candidate);
+            if !taken || len >= fingerprint.len() {
+                return candidate;
+            }
+            len = (len + FINGERPRINT_STEP).min(fingerprint.len());
+        }
+    }
+
+    /// Create aliases for legacy ids whose target skill is indexed.
+    ///
+    /// Returns the number of aliases written. Targets that are not in the
+    /// database yet are skipped and picked up on a later run.
+    pub fn migrate_aliases(&self, db: &Database) -> Result<usize> {
+        let now = chrono::Utc::now().to_rfc3339();
+        let mut migrated = 0;
+        for (legacy, id) in &self.legacy {
+            if db.get_skill(id)?.is_none() || db.resolve_alias(legacy)?.is_some() {
+                continue;
+            }
+            db.upsert_alias(legacy, id, LEGACY_ALIAS_TYPE, &now)?;
+            migrated += 1;
+        }
+        Ok(migrated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cass::client::{Session, SessionMessage, ToolCall};
+    use crate::cass::mining::{extract_from_session, extract_from_sessions};
+
+    fn command_session(id: &str, commands: &[&str]) -> Session {
+        Session {
+            id: id.to_string(),
+            path: format!("/sessions/{id}.json"),
+            content_hash: "hash".to_string(),
+            messages: commands
+                .iter()
+                .enumerate()
+                .map(|(idx, cmd)| SessionMessage {
+                    index: idx,
+                    role: "assistant".to_string(),
+                    content: "running command".to_string(),
+                    tool_calls: vec![ToolCall {
+                        id: format!("call-{idx}"),
+                        name: "bash".to_string(),
+                        arguments: serde_json::json!({ "command": cmd }),
+                    }],
+                    tool_results: vec![],
+                })
+                .collect(),
+            metadata: Default::default(),
+        }
+    }
+
+    fn mine(session: &Session, registry: &mut MinedIdRegistry, name: &str) -> MinedSkillId {
+        let patterns = extract_from_session(session).unwrap();
+        let legacy: Vec<String> = patterns
+            .iter()
+            .flat_map(ExtractedPattern::legacy_ids)
+            .collect();
+        registry.assign(name, &cluster_fingerprint(&patterns), &legacy)
+    }
+
+    #[test]
+    fn slugify_normalizes_names() {
+        assert_eq!(slugify("Rust Error Handling!"), "rust-error-handling");
+        assert_eq!(slugify("  --  "), "mined-skill");
+    }
+
+    #[test]
+    fn mining_same_session_twice_yields_identical_ids() {
+        let session = command_session("a1b2c3d4-session", &["cargo build", "cargo test"]);
+
+        let first_patterns = extract_from_session(&session).unwrap();
+        let second_patterns = extract_from_session(&session).unwrap();
+        let first_ids: Vec<_> = first_patterns.iter().map(|p| p.id.clone()).collect();
+        let second_ids: Vec<_> = second_patterns.iter().map(|p| p.id.clone()).collect();
+        assert_eq!(first_ids, second_ids);
+
+        let mut registry = MinedIdRegistry::default();
+        let first = mine(&session, &mut registry, "Cargo Workflow");
+        let second = mine(&session, &mut registry, "Cargo Workflow");
+        assert_eq!(first.id, second.id);
+        assert!(!first.existing);
+        assert!(second.existing);
+        assert!(first.id.starts_with("cargo-workflow-"));
+    }
+
+    #[test]
+    fn same_workflow_from_different_sessions_shares_an_id() {
+        let mut registry = MinedIdRegistry::default();
+        let a = mine(
+            &command_session("session-one", &["make", "make check"]),
+            &mut registry,
+            "Make",
+        );
+        let b = mine(
+            &command_session("session-two", &["make", "make check"]),
+            &mut registry,
+            "Make",
+        );
+        assert_eq!(a.id, b.id);
+    }
+
+    #[test]
+    fn different_workflows_with_same_name_get_distinct_stable_ids() {
+        let cargo = command_session("s1", &["cargo build"]);
+        let npm = command_session("s2", &["npm install", "npm test"]);
+
+        let mut registry = MinedIdRegistry::default();
+        let cargo_id = mine(&cargo, &mut registry, "Build").id;
+        let npm_id = mine(&npm, &mut registry, "Build").id;
+        assert_ne!(cargo_id, npm_id);
+
+        // Stable across a fresh registry as well.
+        let mut fresh = MinedIdRegistry::default();
+        assert_eq!(mine(&cargo, &mut fresh, "Build").id, cargo_id);
+        assert_eq!(mine(&npm, &mut fresh, "Build").id, npm_id);
+    }
+
+    #[test]
+    fn collisions_extend_the_fingerprint() {
+        let mut registry = MinedIdRegistry::default();
+        let first = registry.assign("Deploy", "abcdef0123456789", &[]);
+        let second = registry.assign("Deploy", "abcdef01ffffffff", &[]);
+        assert_eq!(first.id, "deploy-abcdef01");
+        assert_eq!(second.id, "deploy-abcdef01ffff");
+    }
+
+    #[test]
+    fn legacy_ids_resolve_to_new_ids() {
+        let session = command_session("a1b2c3d4e5", &["git status", "git commit -m x"]);
+        let mut registry = MinedIdRegistry::default();
+        let assigned = mine(&session, &mut registry, "Git");
+        assert!(assigned.legacy_ids.contains(&"cmd_a1b2c3d4".to_string()));
+        assert_eq!(registry.legacy.get("cmd_a1b2c3d4"), Some(&assigned.id));
+        assert_eq!(registry.legacy.get("unknown"), None);
+    }
+
+    #[test]
+    fn registry_round_trips_through_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let assigned = MinedIdRegistry::update(dir.path(), |registry| {
+            registry.assign("Lint", "0123456789abcdef", &["cmd_old".to_string()])
+        })
+        .unwrap();
+
+        let loaded = MinedIdRegistry::load(dir.path()).unwrap();
+        assert_eq!(
+            loaded
+                .find_by_fingerprint("0123456789abcdef")
+                .map(|e| e.id.as_str()),
+            Some(assigned.id.as_str())
+        );
+        assert_eq!(loaded.legacy.get("cmd_old"), Some(&assigned.id));
+
+        // A second update sees the first one's assignment
+        let again = MinedIdRegistry::update(dir.path(), |registry| {
+            registry.assign("Lint", "0123456789abcdef", &[])
+        })
+        .unwrap();
+        assert!(again.existing);
+        assert_eq!(again.id, assigned.id);
+    }
+
+    #[test]
+    fn fingerprint_ignores_frequency_and_project_context() {
+        let pattern = |frequency: usize, context: &str, command: &str| ExtractedPattern {
+            id: String::new(),
+            pattern_type: crate::cass::mining::PatternType::CommandPattern {
+                commands: vec![command.to_string()],
+                frequency,
+                contexts: vec![context.to_string()],
+            },
+            evidence: Vec::new(),
+            confidence: 0.5,
+            frequency,
+            tags: Vec::new(),
+            description: None,
+            taint_label: None,
+        };
+        let base = pattern(1, "project-a", "cargo test");
+        assert_eq!(
+            base.content_fingerprint(),
+            pattern(7, "project-b", "  cargo   test ").content_fingerprint()
+        );
+        assert_ne!(
+            base.content_fingerprint(),
+            pattern(1, "project-a", "cargo build").content_fingerprint()
+        );
+    }
+
+    #[test]
+    fn dedupe_merges_patterns_with_equal_ids() {
+        let a = extract_from_session(&command_session("one", &["ls -la"])).unwrap();
+        let b = extract_from_session(&command_session("two", &["ls -la"])).unwrap();
+        let merged = dedupe_by_fingerprint(a.into_iter().chain(b).collect());
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].evidence.len(), 2);
+    }
+
+    #[test]
+    fn parallel_extraction_matches_serial() {
+        let sessions = vec![
+            command_session("s3", &["cargo build", "cargo test"]),
+            command_session("s1", &["npm install", "npm test"]),
+            command_session("s4", &["cargo build", "cargo test"]),
+            command_session("s2", &["make", "make check", "make install"]),
+            command_session("s5", &["git status"]),
+        ];
+        let refs: Vec<&Session> = sessions.iter().collect();
+
+        let mut by_id: Vec<&Session> = refs.clone();
+        by_id.sort_by(|a, b| a.id.cmp(&b.id));
+        let serial: Vec<ExtractedPattern> = by_id
+            .iter()
+            .flat_map(|s| extract_from_session(s).unwrap())
+            .collect();
+        let serial = dedupe_by_fingerprint(serial);
+
+        let completed = std::sync::atomic::AtomicUsize::new(0);
+        let extractions = extract_from_sessions(&refs, 4, |_, total| {
+            assert_eq!(total, 5);
+            completed.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        })
+        .unwrap();
+        assert_eq!(completed.into_inner(), 5);
+        let ids: Vec<&str> = extractions.iter().map(|e| e.session_id.as_str()).collect();
+        assert_eq!(ids, vec!["s1", "s2", "s3", "s4", "s5"]);
+
+        let parallel: Vec<ExtractedPattern> = extractions
+            .into_iter()
+            .flat_map(|e| e.result.unwrap())
+            .collect();
+        let parallel = dedupe_by_fingerprint(parallel);
+
+        let summarize = |patterns: &[ExtractedPattern]| -> Vec<(String, usize, usize)> {
+            patterns
+                .iter()
+                .map(|p| (p.id.clone(), p.frequency, p.evidence.len()))
+                .collect()
+        };
+        assert_eq!(summarize(&serial), summarize(&parallel));
+        assert!(!serial.is_empty());
+    }
+}
@@ -33,6 +33,37 @@ pub struct SpecificInstance {
     pub is_counter_example: bool,
 }
 
+impl SpecificInstance {
+    /// An instance for a mined pattern. Its id is the pattern's content
+    /// fingerprint, so mining the same pattern again yields the same instance.
+    #[must_use]
+    pub fn from_pattern(pattern: &ExtractedPattern) -> Self {
+        let content = pattern
+            .description
+            .clone()
+            .unwrap_or_else(|| serde_json::to_string(&pattern.pattern_type).unwrap_or_default());
+        let evidence = pattern.evidence.first();
+        Self {
+            id: pattern.content_fingerprint(),
+            content,
+            context: InstanceContext {
+                file_type: None,
+                project_type: None,
+                tags: pattern.tags.clone(),
+                description: pattern.description.clone(),
+            },
+            source: InstanceSource {
+                session_id: evidence.map(|e| e.session_id.clone()).unwrap_or_default(),
+                message_indices: evidence
+                    .map(|e| e.message_indices.clone())
+                    .unwrap_or_default(),
+                observed_at: None,
+            },
+            is_counter_example: false,
+        }
+    }
+}
+
 /// Context surrounding a specific instance
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InstanceContext {
@@ -512,6 +543,12 @@ impl SpecificToGeneralTransformer {
 
     /// Transform a specific instance into a general pattern
     pub fn transform(&self, instance: &SpecificInstance) -> Result<GeneralPattern> {
+        // Generalizing needs similar instances from CASS; without it this is
+        // not a mining failure, and nothing should be queued for review
+        if !self.cass.is_available() {
+            return Err(self.cass.unavailable_error());
+        }
+
         // Step 1: Extract structural features
         let structure = self.extract_structure(instance)?;
 
@@ -564,19 +601,16 @@ impl SpecificToGeneralTransformer {
         }
 
         // Step 6: Optional refinement/critique (LLM-assisted if configured)
-        let critique = if let Some(ref refiner) = self.refiner {
-            let critique = refiner.critique(&common, &primary_cluster)?;
-            if critique.flags_overgeneralization {
-                self.queue_uncertainty(instance, &validation, &primary_cluster, Some(&critique))?;
-                return Err(MsError::MiningFailed(format!(
-                    "Generalization critique failed: {}",
-                    critique.summary
-                )));
-            }
-            Some(critique)
-        } else {
-            None
-        };
+        let critique = self.critique(&common, &primary_cluster);
+        if let Some(critique) = &critique
+            && critique.flags_overgeneralization
+        {
+            self.queue_uncertainty(instance, &validation, &primary_cluster, Some(critique))?;
+            return Err(MsError::MiningFailed(format!(
+                "Generalization critique failed: {}",
+                critique.summary
+            )));
+        }
 
         // Step 7: Generate general pattern
         Ok(GeneralPattern {
@@ -1107,6 +1141,23 @@ impl SpecificToGeneralTransformer {
         placeholders
     }
 
+    /// The refiner's critique, if one is configured. A failing refiner is
+    /// logged and skipped: generalization proceeds uncritiqued.
+    fn critique(
+        &self,
+        common: &CommonElements,
+        cluster: &InstanceCluster,
+    ) -> Option<RefinementCritique> {
+        let refiner = self.refiner.as_ref()?;
+        match refiner.critique(common, cluster) {
+            Ok(critique) => Some(critique),
+            Err(err) => {
+                tracing::warn!(cluster = %cluster.id, "generalization refiner failed, continuing without critique: {err}");
+                None
+            }
+        }
+    }
+
     /// Queue an uncertain generalization for human review
     fn queue_uncertainty(
         &self,
@@ -1128,7 +1179,7 @@ impl SpecificToGeneralTransformer {
 impl From<GeneralPattern> for ExtractedPattern {
     fn from(gp: GeneralPattern) -> Self {
         Self {
-            id: format!("gen_{}", &uuid::Uuid::new_v4().to_string()[..8]),
+            id: String::new(),
             pattern_type: PatternType::WorkflowPattern {
                 steps: vec![],
                 triggers: gp.applicability.clone(),
@@ -1151,6 +1202,7 @@ impl From<GeneralPattern> for ExtractedPattern {
             description: Some(gp.principle),
             taint_label: None,
         }
+        .with_content_id("gen")
     }
 }
 
@@ -1444,8 +1496,10 @@ mod tests {
             },
         };
 
-        let extracted: ExtractedPattern = gp.into();
+        let extracted: ExtractedPattern = gp.clone().into();
         assert!(extracted.id.starts_with("gen_"));
+        let again: ExtractedPattern = gp.into();
+        assert_eq!(extracted.id, again.id, "generalized ids must be stable");
         assert_eq!(extracted.confidence, 0.85);
         assert_eq!(extracted.frequency, 5);
         assert!(extracted.tags.contains(&"generalized".to_string()));
@@ -1461,4 +1515,49 @@ mod tests {
         let json = serde_json::to_string(&reason).unwrap();
         assert_eq!(json, "\"outcome_mismatch\"");
     }
+
+    struct FailingRefiner;
+
+    impl GeneralizationRefiner for FailingRefiner {
+        fn critique(
+            &self,
+            _common: &CommonElements,
+            _cluster: &InstanceCluster,
+        ) -> Result<RefinementCritique> {
+            Err(MsError::Config(
+                "refiner 'llm' timed out after 60s".to_string(),
+            ))
+        }
+    }
+
+    #[test]
+    fn failing_refiner_leaves_generalization_uncritiqued() {
+        let transformer = SpecificToGeneralTransformer::new(CassClient::new())
+            .with_refiner(Box::new(FailingRefiner));
+        let embedder = HashEmbedder::new(64);
+        let cluster = InstanceCluster {
+            id: "cluster-1".to_string(),
+            instances: vec![make_clustered_instance(
+                make_test_instance("1", "error handling with Result type"),
+                &embedder,
+            )],
+            context_conditions: vec![],
+            centroid: None,
+            coherence: 1.0,
+        };
+        let common = transformer.extract_common_elements(&cluster).unwrap();
+
+        assert!(transformer.critique(&common, &cluster).is_none());
+    }
+
+    #[test]
+    fn transform_without_cass_is_typed_unavailable() {
+        let dir = tempfile::tempdir().unwrap();
+        let transformer =
+            SpecificToGeneralTransformer::new(CassClient::with_binary(dir.path().join("cass")));
+        let err = transformer
+            .transform(&make_test_instance("1", "error handling with Result"))
+            .unwrap_err();
+        assert!(matches!(err, MsError::CassUnavailable(_)), "{err}");
+    }
 }
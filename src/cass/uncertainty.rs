@@ -7,6 +7,7 @@
 
 use std::collections::VecDeque;
 use std::ops::RangeInclusive;
+use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
@@ -14,12 +15,13 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::error::Result;
+use crate::error::{MsError, Result};
+use crate::storage::{Database, UncertaintyRecord};
 
 use super::mining::ExtractedPattern;
 use super::transformation::{
-    GeneralizationValidation, InstanceCluster, RefinementCritique, SpecificInstance,
-    UncertaintyQueueSink,
+    ClusteredInstance, GeneralizationValidation, InstanceCluster, RefinementCritique,
+    SpecificInstance, UncertaintyQueueSink,
 };
 
 // =============================================================================
@@ -76,11 +78,18 @@ pub struct UncertaintyItem {
     /// Cluster info if available
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cluster_info: Option<ClusterSummary>,
+
+    /// Validation scores of the rejected generalization
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validation: Option<GeneralizationValidation>,
 }
 
 /// Minimal info about source instance (to avoid storing full instance)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SourceInstanceInfo {
+    /// `SpecificInstance::id`; identifies the instance across builds
+    #[serde(default)]
+    pub instance_id: String,
     pub session_id: String,
     pub description: String,
     pub tool_signatures: Vec<String>,
@@ -93,6 +102,9 @@ pub struct ClusterSummary {
     pub instance_count: usize,
     pub common_tools: Vec<String>,
     pub common_file_types: Vec<String>,
+    /// Excerpts of the instances closest to the cluster centroid
+    #[serde(default)]
+    pub examples: Vec<String>,
 }
 
 /// Why confidence is insufficient - reasons for uncertainty
@@ -138,6 +150,23 @@ pub enum UncertaintyReason {
     },
 }
 
+impl UncertaintyReason {
+    /// The serialized type, e.g. `high_variance`; `ms review list` groups by it.
+    #[must_use]
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::InsufficientInstances { .. } => "insufficient_instances",
+            Self::HighVariance { .. } => "high_variance",
+            Self::CounterExampleFound { .. } => "counter_example_found",
+            Self::AmbiguousScope { .. } => "ambiguous_scope",
+            Self::UnclearPreconditions { .. } => "unclear_preconditions",
+            Self::UnknownBoundaries { .. } => "unknown_boundaries",
+            Self::OvergeneralizationFlagged { .. } => "overgeneralization_flagged",
+            Self::ConflictingPatterns { .. } => "conflicting_patterns",
+        }
+    }
+}
+
 /// A possible scope for a pattern
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScopeCandidate {
@@ -182,6 +211,30 @@ pub enum UncertaintyStatus {
     Expired { expired_at: DateTime<Utc> },
 }
 
+impl UncertaintyStatus {
+    /// The serialized status, e.g. `pending` or `needs_human`.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::InProgress { .. } => "in_progress",
+            Self::Resolved { .. } => "resolved",
+            Self::Rejected { .. } => "rejected",
+            Self::NeedsHuman { .. } => "needs_human",
+            Self::Expired { .. } => "expired",
+        }
+    }
+
+    /// Whether the item still awaits a decision.
+    #[must_use]
+    pub const fn is_open(&self) -> bool {
+        matches!(
+            self,
+            Self::Pending | Self::InProgress { .. } | Self::NeedsHuman { .. }
+        )
+    }
+}
+
 /// How an uncertainty was resolved
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -1065,186 +1118,410 @@ impl UncertaintyQueueSink for UncertaintyQueue {
         cluster: &InstanceCluster,
         critique: Option<&RefinementCritique>,
     ) -> Result<String> {
-        // Determine reason for uncertainty
-        let reason = self.determine_uncertainty_reason(validation, critique);
-
-        // Create extracted pattern from cluster
-        let pattern = self.create_pattern_from_cluster(cluster);
-
-        // Create source instance info
-        let source_info = SourceInstanceInfo {
-            session_id: instance.source.session_id.clone(),
-            description: instance.context.description.clone().unwrap_or_default(),
-            // Use tags from context as a proxy for tool signatures
-            tool_signatures: instance.context.tags.clone(),
+        let item = uncertainty_item(
+            instance,
+            validation,
+            cluster,
+            critique,
+            self.config.min_confidence,
+        );
+        Ok(self.enqueue(item))
+    }
+}
+
+/// Examples kept in a [`ClusterSummary`].
+const CLUSTER_EXAMPLES: usize = 3;
+
+/// A pending item for a generalization that failed validation or critique.
+/// Suggested queries are left for the queue to generate.
+#[must_use]
+pub fn uncertainty_item(
+    instance: &SpecificInstance,
+    validation: &GeneralizationValidation,
+    cluster: &InstanceCluster,
+    critique: Option<&RefinementCritique>,
+    threshold: f32,
+) -> UncertaintyItem {
+    // Determine reason for uncertainty
+    let reason = determine_uncertainty_reason(validation, critique);
+
+    // Create extracted pattern from cluster
+    let pattern = create_pattern_from_cluster(cluster);
+
+    // Create source instance info
+    let source_info = SourceInstanceInfo {
+        instance_id: instance.id.clone(),
+        session_id: instance.source.session_id.clone(),
+        description: instance.context.description.clone().unwrap_or_default(),
+        // Use tags from context as a proxy for tool signatures
+        tool_signatures: instance.context.tags.clone(),
+    };
+
+    // Derive common file types from cluster instances
+    let common_file_types: Vec<String> = cluster
+        .instances
+        .iter()
+        .filter_map(|ci| ci.instance.context.file_type.clone())
+        .take(5)
+        .collect();
+
+    // The best examples are the instances nearest the centroid
+    let mut nearest: Vec<_> = cluster.instances.iter().collect();
+    nearest.sort_by(|a, b| a.distance_to_centroid.total_cmp(&b.distance_to_centroid));
+    let examples = nearest
+        .into_iter()
+        .take(CLUSTER_EXAMPLES)
+        .map(ClusteredInstance::to_example)
+        .collect();
+
+    // Create cluster summary
+    let cluster_summary = ClusterSummary {
+        cluster_id: cluster.id.clone(),
+        instance_count: cluster.instances.len(),
+        // Use context_conditions as a proxy for common tools
+        common_tools: cluster.context_conditions.iter().take(5).cloned().collect(),
+        common_file_types,
+        examples,
+    };
+
+    UncertaintyItem {
+        id: Uuid::new_v4().to_string(),
+        pattern_candidate: pattern,
+        reason,
+        confidence: validation.confidence,
+        threshold,
+        suggested_queries: Vec::new(), // Will be generated on enqueue
+        status: UncertaintyStatus::Pending,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        attempts: Vec::new(),
+        critique: critique.cloned(),
+        source_instance: Some(source_info),
+        cluster_info: Some(cluster_summary),
+        validation: Some(validation.clone()),
+    }
+}
+
+fn determine_uncertainty_reason(
+    validation: &GeneralizationValidation,
+    critique: Option<&RefinementCritique>,
+) -> UncertaintyReason {
+    // Check for LLM critique first
+    if let Some(c) = critique {
+        if c.flags_overgeneralization {
+            return UncertaintyReason::OvergeneralizationFlagged {
+                critique_summary: c.summary.clone(),
+            };
+        }
+    }
+
+    // Check validation metrics
+    if validation.coverage < 0.3 {
+        return UncertaintyReason::InsufficientInstances {
+            have: (validation.coverage * 10.0) as u32,
+            need: 5,
+            variance: 1.0 - validation.coherence,
         };
+    }
 
-        // Derive common file types from cluster instances
-        let common_file_types: Vec<String> = cluster
-            .instances
-            .iter()
-            .filter_map(|ci| ci.instance.context.file_type.clone())
-            .take(5)
-            .collect();
+    if validation.coherence < 0.4 {
+        return UncertaintyReason::HighVariance {
+            variance_score: 1.0 - validation.coherence,
+            conflicting_aspects: vec![],
+        };
+    }
 
-        // Create cluster summary
-        let cluster_summary = ClusterSummary {
-            cluster_id: cluster.id.clone(),
-            instance_count: cluster.instances.len(),
-            // Use context_conditions as a proxy for common tools
-            common_tools: cluster.context_conditions.iter().take(5).cloned().collect(),
-            common_file_types,
+    if !validation.counterexamples.is_empty() {
+        let ce = &validation.counterexamples[0];
+        return UncertaintyReason::CounterExampleFound {
+            counter_example: ce.instance_id.clone(),
+            contradiction: ce
+                .suggests_refinement
+                .clone()
+                .unwrap_or_else(|| "Pattern contradiction found".into()),
         };
+    }
 
-        // Build the uncertainty item
-        let item_id = Uuid::new_v4().to_string();
-        let item = UncertaintyItem {
-            id: item_id.clone(),
-            pattern_candidate: pattern,
-            reason,
-            confidence: validation.confidence,
-            threshold: self.config.min_confidence,
-            suggested_queries: Vec::new(), // Will be generated on enqueue
-            status: UncertaintyStatus::Pending,
-            created_at: Utc::now(),
-            updated_at: Utc::now(),
-            attempts: Vec::new(),
-            critique: critique.cloned(),
-            source_instance: Some(source_info),
-            cluster_info: Some(cluster_summary),
+    if validation.specificity < 0.3 {
+        return UncertaintyReason::AmbiguousScope {
+            possible_scopes: vec![],
         };
+    }
 
-        let _ = self.enqueue(item);
-        Ok(item_id)
+    // Default to insufficient instances
+    UncertaintyReason::InsufficientInstances {
+        have: 2,
+        need: 5,
+        variance: 0.5,
     }
 }
 
-impl UncertaintyQueue {
-    fn determine_uncertainty_reason(
-        &self,
-        validation: &GeneralizationValidation,
-        critique: Option<&RefinementCritique>,
-    ) -> UncertaintyReason {
-        // Check for LLM critique first
-        if let Some(c) = critique {
-            if c.flags_overgeneralization {
-                return UncertaintyReason::OvergeneralizationFlagged {
-                    critique_summary: c.summary.clone(),
+fn create_pattern_from_cluster(cluster: &InstanceCluster) -> ExtractedPattern {
+    use super::mining::{EvidenceRef, PatternType, WorkflowStep};
+
+    // Extract workflow steps from context_conditions
+    let steps: Vec<WorkflowStep> = cluster
+        .context_conditions
+        .iter()
+        .enumerate()
+        .map(|(i, condition)| WorkflowStep {
+            order: i,
+            action: condition.clone(),
+            description: format!("Condition {} from cluster", i + 1),
+            optional: false,
+            conditions: vec![],
+        })
+        .take(10)
+        .collect();
+
+    // Create evidence refs from cluster instances
+    let evidence: Vec<EvidenceRef> = cluster
+        .instances
+        .iter()
+        .map(|inst| {
+            // Convert distance_to_centroid to similarity (closer = more similar)
+            let similarity = 1.0 / (1.0 + inst.distance_to_centroid);
+            // Use content truncated as snippet
+            let snippet = inst.instance.content.chars().take(100).collect::<String>();
+            EvidenceRef {
+                session_id: inst.instance.source.session_id.clone(),
+                message_indices: vec![],
+                relevance: similarity,
+                snippet: Some(snippet),
+            }
+        })
+        .collect();
+
+    // Derive file types from instances
+    let file_types: Vec<String> = cluster
+        .instances
+        .iter()
+        .filter_map(|i| i.instance.context.file_type.clone())
+        .collect();
+
+    // Create description from first instance content
+    let description = cluster.instances.first().map(|i| {
+        let truncated: String = i.instance.content.chars().take(200).collect();
+        format!(
+            "Pattern from cluster {} instances: {}",
+            cluster.instances.len(),
+            truncated
+        )
+    });
+
+    ExtractedPattern {
+        id: format!("uncertain-{}", Uuid::new_v4()),
+        pattern_type: PatternType::WorkflowPattern {
+            steps,
+            triggers: cluster.context_conditions.clone(),
+            outcomes: vec![], // Derived outcomes not available
+        },
+        evidence,
+        confidence: 0.0, // Will be set from validation
+        frequency: cluster.instances.len(),
+        tags: file_types,
+        description,
+        taint_label: None,
+    }
+}
+
+// =============================================================================
+// PERSISTENT QUEUE
+// =============================================================================
+
+/// Uncertainty items in the `uncertainty_queue` table, where `ms build`
+/// leaves them for `ms review`.
+pub struct UncertaintyStore<'a> {
+    db: &'a Database,
+}
+
+impl<'a> UncertaintyStore<'a> {
+    #[must_use]
+    pub const fn new(db: &'a Database) -> Self {
+        Self { db }
+    }
+
+    /// Queue `item` unless its instance was rejected before or is already
+    /// awaiting review. Returns the id of the item standing for the instance.
+    pub fn enqueue(&self, mut item: UncertaintyItem) -> Result<UncertaintyId> {
+        if let Some(key) = instance_key(&item) {
+            for record in self.db.uncertainties_for_instance(key)? {
+                let Some(existing) = item_from_record(&record) else {
+                    continue;
                 };
+                if matches!(existing.status, UncertaintyStatus::Rejected { .. }) {
+                    return Ok(existing.id);
+                }
+                if existing.status.is_open() {
+                    // Fresh scores, same identity and age.
+                    item.id = existing.id;
+                    item.created_at = existing.created_at;
+                    item.attempts = existing.attempts;
+                }
             }
         }
+        self.save(&item)?;
+        Ok(item.id)
+    }
 
-        // Check validation metrics
-        if validation.coverage < 0.3 {
-            return UncertaintyReason::InsufficientInstances {
-                have: (validation.coverage * 10.0) as u32,
-                need: 5,
-                variance: 1.0 - validation.coherence,
-            };
-        }
+    pub fn save(&self, item: &UncertaintyItem) -> Result<()> {
+        self.db.upsert_uncertainty(&UncertaintyRecord {
+            id: item.id.clone(),
+            instance_key: instance_key(item).map(str::to_string),
+            reason: item.reason.kind().to_string(),
+            confidence: f64::from(item.confidence),
+            status: item.status.name().to_string(),
+            pattern_json: serde_json::to_string(&item.pattern_candidate)?,
+            suggested_queries: serde_json::to_string(&item.suggested_queries)?,
+            item_json: serde_json::to_string(item)?,
+            created_at: item.created_at.to_rfc3339(),
+            updated_at: item.updated_at.to_rfc3339(),
+        })
+    }
 
-        if validation.coherence < 0.4 {
-            return UncertaintyReason::HighVariance {
-                variance_score: 1.0 - validation.coherence,
-                conflicting_aspects: vec![],
-            };
+    /// The item with id `id`, or the only one whose id starts with it.
+    pub fn get(&self, id: &str) -> Result<UncertaintyItem> {
+        if let Some(item) = self
+            .db
+            .get_uncertainty(id)?
+            .as_ref()
+            .and_then(item_from_record)
+        {
+            return Ok(item);
         }
-
-        if !validation.counterexamples.is_empty() {
-            let ce = &validation.counterexamples[0];
-            return UncertaintyReason::CounterExampleFound {
-                counter_example: ce.instance_id.clone(),
-                contradiction: ce
-                    .suggests_refinement
-                    .clone()
-                    .unwrap_or_else(|| "Pattern contradiction found".into()),
-            };
+        let mut matches: Vec<UncertaintyItem> = self
+            .list(None)?
+            .into_iter()
+            .filter(|item| item.id.starts_with(id))
+            .collect();
+        match matches.len() {
+            0 => Err(MsError::NotFound(format!("no review item {id}"))),
+            1 => Ok(matches.remove(0)),
+            n => Err(MsError::ValidationFailed(format!(
+                "review item id {id} is ambiguous ({n} matches)"
+            ))),
         }
+    }
 
-        if validation.specificity < 0.3 {
-            return UncertaintyReason::AmbiguousScope {
-                possible_scopes: vec![],
-            };
-        }
+    /// Items in `status` (all when `None`), oldest first. Rows queued before
+    /// items were stored whole are skipped.
+    pub fn list(&self, status: Option<&str>) -> Result<Vec<UncertaintyItem>> {
+        Ok(self
+            .db
+            .list_uncertainties(status)?
+            .iter()
+            .filter_map(item_from_record)
+            .collect())
+    }
 
-        // Default to insufficient instances
-        UncertaintyReason::InsufficientInstances {
-            have: 2,
-            need: 5,
-            variance: 0.5,
+    /// Items still awaiting a decision, oldest first.
+    pub fn open(&self) -> Result<Vec<UncertaintyItem>> {
+        Ok(self
+            .list(None)?
+            .into_iter()
+            .filter(|item| item.status.is_open())
+            .collect())
+    }
+
+    /// Expire open items older than `expiry`; returns the expired items.
+    pub fn age_out(&self, expiry: Duration) -> Result<Vec<UncertaintyItem>> {
+        let now = Utc::now();
+        let mut expired = Vec::new();
+        for mut item in self.open()? {
+            let age = now.signed_duration_since(item.created_at);
+            if age.to_std().unwrap_or(Duration::ZERO) > expiry {
+                item.status = UncertaintyStatus::Expired { expired_at: now };
+                item.updated_at = now;
+                self.save(&item)?;
+                expired.push(item);
+            }
         }
+        Ok(expired)
     }
 
-    fn create_pattern_from_cluster(&self, cluster: &InstanceCluster) -> ExtractedPattern {
-        use super::mining::{EvidenceRef, PatternType, WorkflowStep};
+    /// Mark `item` accepted by a reviewer.
+    pub fn resolve(&self, item: &mut UncertaintyItem, resolution: Resolution) -> Result<()> {
+        let now = Utc::now();
+        item.status = UncertaintyStatus::Resolved {
+            new_confidence: item.confidence,
+            resolution,
+            resolved_at: now,
+        };
+        item.updated_at = now;
+        self.save(item)
+    }
 
-        // Extract workflow steps from context_conditions
-        let steps: Vec<WorkflowStep> = cluster
-            .context_conditions
-            .iter()
-            .enumerate()
-            .map(|(i, condition)| WorkflowStep {
-                order: i,
-                action: condition.clone(),
-                description: format!("Condition {} from cluster", i + 1),
-                optional: false,
-                conditions: vec![],
-            })
-            .take(10)
-            .collect();
+    /// Mark `item` rejected; its instance will not be queued again.
+    pub fn reject(&self, item: &mut UncertaintyItem, reason: &str) -> Result<()> {
+        let now = Utc::now();
+        item.status = UncertaintyStatus::Rejected {
+            reason: reason.to_string(),
+            rejected_at: now,
+        };
+        item.updated_at = now;
+        self.save(item)
+    }
+}
 
-        // Create evidence refs from cluster instances
-        let evidence: Vec<EvidenceRef> = cluster
-            .instances
-            .iter()
-            .map(|inst| {
-                // Convert distance_to_centroid to similarity (closer = more similar)
-                let similarity = 1.0 / (1.0 + inst.distance_to_centroid);
-                // Use content truncated as snippet
-                let snippet = inst.instance.content.chars().take(100).collect::<String>();
-                EvidenceRef {
-                    session_id: inst.instance.source.session_id.clone(),
-                    message_indices: vec![],
-                    relevance: similarity,
-                    snippet: Some(snippet),
-                }
-            })
-            .collect();
+fn instance_key(item: &UncertaintyItem) -> Option<&str> {
+    item.source_instance
+        .as_ref()
+        .map(|source| source.instance_id.as_str())
+        .filter(|key| !key.is_empty())
+}
 
-        // Derive file types from instances
-        let file_types: Vec<String> = cluster
-            .instances
-            .iter()
-            .filter_map(|i| i.instance.context.file_type.clone())
-            .collect();
+fn item_from_record(record: &UncertaintyRecord) -> Option<UncertaintyItem> {
+    serde_json::from_str(&record.item_json)
+        .inspect_err(|err| {
+            tracing::debug!(target: "uncertainty", id = %record.id, error = %err, "unreadable queue row");
+        })
+        .ok()
+}
 
-        // Create description from first instance content
-        let description = cluster.instances.first().map(|i| {
-            let truncated: String = i.instance.content.chars().take(200).collect();
-            format!(
-                "Pattern from cluster {} instances: {}",
-                cluster.instances.len(),
-                truncated
-            )
-        });
+/// [`UncertaintyQueueSink`] backed by [`UncertaintyStore`].
+///
+/// Sinks must be `Send + Sync` and a [`Database`] is not, so each queued item
+/// opens its own connection to `db_path`.
+pub struct PersistentUncertaintyQueue {
+    db_path: PathBuf,
+    config: UncertaintyConfig,
+    query_generator: Box<dyn QueryGenerator>,
+}
 
-        ExtractedPattern {
-            id: format!("uncertain-{}", Uuid::new_v4()),
-            pattern_type: PatternType::WorkflowPattern {
-                steps,
-                triggers: cluster.context_conditions.clone(),
-                outcomes: vec![], // Derived outcomes not available
-            },
-            evidence,
-            confidence: 0.0, // Will be set from validation
-            frequency: cluster.instances.len(),
-            tags: file_types,
-            description,
-            taint_label: None,
+impl PersistentUncertaintyQueue {
+    #[must_use]
+    pub fn new(db_path: impl Into<PathBuf>, config: UncertaintyConfig) -> Self {
+        Self {
+            db_path: db_path.into(),
+            config,
+            query_generator: Box::new(DefaultQueryGenerator::default()),
         }
     }
 }
 
+impl UncertaintyQueueSink for PersistentUncertaintyQueue {
+    fn queue_uncertain(
+        &self,
+        instance: &SpecificInstance,
+        validation: &GeneralizationValidation,
+        cluster: &InstanceCluster,
+        critique: Option<&RefinementCritique>,
+    ) -> Result<String> {
+        let mut item = uncertainty_item(
+            instance,
+            validation,
+            cluster,
+            critique,
+            self.config.min_confidence,
+        );
+        let max_queries = *self.config.queries_range().end() as usize;
+        item.suggested_queries = self.query_generator.generate_queries(&item, max_queries);
+
+        let db = Database::open(&self.db_path)?;
+        UncertaintyStore::new(&db).enqueue(item)
+    }
+}
+
 // =============================================================================
 // TESTS
 // =============================================================================
@@ -1291,6 +1568,7 @@ mod tests {
             critique: None,
             source_instance: None,
             cluster_info: None,
+            validation: None,
         }
     }
 
@@ -1618,4 +1896,94 @@ mod tests {
         let parsed: ScopeCandidate = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed.scope, "rust files");
     }
+
+    fn queue_fixture(
+        queue: &PersistentUncertaintyQueue,
+        pattern: &ExtractedPattern,
+        confidence: f32,
+    ) -> String {
+        use super::super::transformation::ClusteredInstance;
+
+        let instance = SpecificInstance::from_pattern(pattern);
+        let cluster = InstanceCluster {
+            id: "cluster-1".to_string(),
+            instances: ["far example", "near example"]
+                .iter()
+                .zip([0.9, 0.1])
+                .map(|(content, distance)| ClusteredInstance {
+                    instance: SpecificInstance {
+                        content: (*content).to_string(),
+                        ..instance.clone()
+                    },
+                    distance_to_centroid: distance,
+                    embedding: vec![],
+                })
+                .collect(),
+            context_conditions: vec!["file_type=rust".to_string()],
+            centroid: None,
+            coherence: 0.3,
+        };
+        let validation = GeneralizationValidation {
+            coverage: 0.6,
+            predictive_power: 0.5,
+            coherence: 0.3,
+            specificity: 0.7,
+            confidence,
+            counterexamples: vec![],
+        };
+        queue
+            .queue_uncertain(&instance, &validation, &cluster, None)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_persistent_queue_dedupes_and_remembers_rejections() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("ms.db");
+        let queue = PersistentUncertaintyQueue::new(&db_path, UncertaintyConfig::default());
+        let pattern = make_test_pattern();
+
+        let first = queue_fixture(&queue, &pattern, 0.4);
+        let again = queue_fixture(&queue, &pattern, 0.5);
+        assert_eq!(first, again);
+
+        let db = Database::open(&db_path).unwrap();
+        let store = UncertaintyStore::new(&db);
+        let mut item = store.get(&first[..8]).unwrap();
+        assert!((item.confidence - 0.5).abs() < f32::EPSILON);
+        assert_eq!(item.reason.kind(), "high_variance");
+        assert!(!item.suggested_queries.is_empty());
+        assert_eq!(
+            item.cluster_info.as_ref().unwrap().examples,
+            ["near example", "far example"]
+        );
+        assert!(item.validation.is_some());
+
+        store.reject(&mut item, "too broad").unwrap();
+        assert!(store.open().unwrap().is_empty());
+        assert_eq!(queue_fixture(&queue, &pattern, 0.45), first);
+        assert!(store.open().unwrap().is_empty());
+        assert_eq!(store.list(Some("rejected")).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_persistent_queue_ages_out_open_items() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(dir.path().join("ms.db")).unwrap();
+        let store = UncertaintyStore::new(&db);
+
+        let mut old = make_test_item();
+        old.created_at = Utc::now() - chrono::Duration::days(40);
+        let fresh = make_test_item();
+        store.enqueue(old.clone()).unwrap();
+        store.enqueue(fresh).unwrap();
+
+        let expired = store
+            .age_out(Duration::from_secs(30 * 24 * 60 * 60))
+            .unwrap();
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].id, old.id);
+        assert_eq!(store.open().unwrap().len(), 1);
+        assert!(store.get("no-such-item").is_err());
+    }
 }
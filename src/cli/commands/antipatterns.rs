@@ -10,7 +10,7 @@ use crate::antipatterns::{
     AntiPattern, AntiPatternSeverity, DefaultDetector, format_anti_patterns, mine_anti_patterns,
 };
 use crate::app::AppContext;
-use crate::cass::CassClient;
+use crate::cli::commands::cass_client;
 use crate::cli::output::OutputFormat;
 use crate::error::{MsError, Result};
 
@@ -51,6 +51,10 @@ pub struct MineArgs {
     /// Save results to database
     #[arg(long)]
     pub save: bool,
+
+    /// Probe cass again instead of reusing cached health and capabilities
+    #[arg(long)]
+    pub no_cache: bool,
 }
 
 #[derive(Args, Debug)]
@@ -104,11 +108,7 @@ fn run_mine(ctx: &AppContext, args: &MineArgs) -> Result<()> {
     }
 
     // Load sessions from CASS
-    let cass = if let Some(ref cass_path) = ctx.config.cass.cass_path {
-        CassClient::with_binary(cass_path)
-    } else {
-        CassClient::new()
-    };
+    let cass = cass_client(ctx, None, args.no_cache);
     let mut sessions = Vec::new();
 
     for session_id in &args.sessions {
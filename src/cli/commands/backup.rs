@@ -1,15 +1,37 @@
 //! ms backup - snapshot and restore ms state.
-
-use std::path::{Path, PathBuf};
-
-use chrono::Utc;
+//!
+//! A backup is a single zstd-compressed tarball, `backups/<id>.tar.zst`:
+//! `manifest.json` first, then a snapshot of the database, the Git archive
+//! and the config. The search index is left out and rebuilt from the
+//! restored database. Backups taken before the tarball format are
+//! directories under `backups/`; they can still be listed and restored.
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read};
+use std::path::{Component, Path, PathBuf};
+
+use chrono::{DateTime, Utc};
 use clap::{Args, Subcommand};
 use serde::{Deserialize, Serialize};
 
 use crate::app::AppContext;
+use crate::cli::commands::doctor::rebuild_search_index;
 use crate::cli::output::OutputFormat;
+use crate::config::BackupConfig;
 use crate::error::{MsError, Result};
-use crate::storage::tx::GlobalLock;
+use crate::search::SearchIndex;
+use crate::search::health::{self, IndexProblem};
+use crate::storage::migrations::{self, SCHEMA_VERSION};
+use crate::storage::tx::{GlobalLock, lock_timeout};
+use crate::storage::{Database, TombstoneManager};
+use crate::utils::format::{format_duration, format_size};
+
+/// Layout of tarball backups. Directory backups are format 1.
+const BACKUP_FORMAT: u32 = 2;
+
+const ARCHIVE_SUFFIX: &str = ".tar.zst";
+
+const MANIFEST_NAME: &str = "manifest.json";
 
 #[derive(Args, Debug)]
 pub struct BackupArgs {
@@ -32,6 +54,10 @@ pub struct BackupCreateArgs {
     /// Backup ID (default: timestamp)
     #[arg(long)]
     pub id: Option<String>,
+
+    /// Write the archive here instead of the backups directory
+    #[arg(long, value_name = "FILE")]
+    pub out: Option<PathBuf>,
 }
 
 #[derive(Args, Debug)]
@@ -53,19 +79,51 @@ pub struct BackupRestoreArgs {
     /// Apply restore (required)
     #[arg(long)]
     pub approve: bool,
+
+    /// Restore even if the backup's schema is newer than this ms supports
+    #[arg(long)]
+    pub force_migrate: bool,
+}
+
+/// `ms restore <file>`: restore a backup archive from any path.
+#[derive(Args, Debug)]
+pub struct RestoreArgs {
+    /// Backup archive (.tar.zst), or the ID of one in the backups directory
+    pub file: PathBuf,
+
+    /// Apply restore (required)
+    #[arg(long)]
+    pub approve: bool,
+
+    /// Restore even if the backup's schema is newer than this ms supports
+    #[arg(long)]
+    pub force_migrate: bool,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct BackupManifest {
+    /// Absent from directory backups.
+    #[serde(default = "directory_format")]
+    format: u32,
     id: String,
     created_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ms_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    schema_version: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    skill_count: Option<u64>,
     ms_root: String,
     config_path: Option<String>,
     entries: Vec<BackupEntry>,
     total_bytes: u64,
 }
 
-#[derive(Serialize, Deserialize)]
+const fn directory_format() -> u32 {
+    1
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct BackupEntry {
     name: String,
     source_path: String,
@@ -74,6 +132,70 @@ struct BackupEntry {
     is_dir: bool,
 }
 
+#[derive(Serialize)]
+struct BackupCreateReport<'a> {
+    #[serde(flatten)]
+    manifest: &'a BackupManifest,
+    path: String,
+    /// Compressed size of the archive.
+    archive_bytes: u64,
+}
+
+/// A backup in the backups directory.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct BackupInfo {
+    pub id: String,
+    pub path: PathBuf,
+    /// 1 for a directory backup, 2 for a tarball.
+    pub format: u32,
+    pub created_at: Option<DateTime<Utc>>,
+    /// Size on disk (uncompressed for directory backups).
+    pub bytes: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ms_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema_version: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skill_count: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+struct RestoreReport {
+    status: &'static str,
+    restored: String,
+    source: String,
+    format: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    schema_version: Option<u32>,
+    /// Schema version of an older snapshot that was migrated on restore.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    migrated_from: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    skill_count: Option<u64>,
+    entries: usize,
+    restored_count: usize,
+    skipped: Vec<String>,
+    /// Tombstone holding the state the restore replaced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    previous_tombstone: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    index: Option<IndexVerification>,
+}
+
+/// Search index state after a restore.
+#[derive(Debug, Serialize)]
+struct IndexVerification {
+    skills: u64,
+    indexed: u64,
+    problems: Vec<String>,
+}
+
+impl IndexVerification {
+    fn is_ok(&self) -> bool {
+        self.problems.is_empty() && self.indexed == self.skills
+    }
+}
+
 pub fn run(ctx: &AppContext, args: &BackupArgs) -> Result<()> {
     match &args.command {
         BackupCommand::Create(create) => run_create(ctx, create),
@@ -82,159 +204,129 @@ pub fn run(ctx: &AppContext, args: &BackupArgs) -> Result<()> {
     }
 }
 
+/// `ms restore <file>`.
+pub fn run_restore_file(ctx: &AppContext, args: &RestoreArgs) -> Result<()> {
+    if !args.approve {
+        return Err(MsError::ApprovalRequired(
+            "restore requires --approve".to_string(),
+        ));
+    }
+    let _lock = GlobalLock::acquire_or_busy(&ctx.ms_root, lock_timeout())?;
+
+    let path = if args.file.is_file() {
+        args.file.clone()
+    } else {
+        // Not a file: try it as the ID of a backup in the backups directory
+        let id = args.file.to_string_lossy();
+        let archive = archive_path(&backup_root(&ctx.ms_root), &id);
+        if validate_backup_id(&id).is_err() || !archive.is_file() {
+            return Err(MsError::NotFound(format!(
+                "backup archive {} not found",
+                args.file.display()
+            )));
+        }
+        archive
+    };
+    let report = restore_archive(ctx, &path, args.force_migrate)?;
+    emit_restore(ctx, &report)
+}
+
 fn run_create(ctx: &AppContext, args: &BackupCreateArgs) -> Result<()> {
-    let _lock = GlobalLock::acquire(&ctx.ms_root)?;
-    let backup_root = backup_root(ctx);
+    let _lock = GlobalLock::acquire_or_busy(&ctx.ms_root, lock_timeout())?;
+    let backup_root = backup_root(&ctx.ms_root);
     let backup_id = args.id.clone().unwrap_or_else(timestamp_id);
     validate_backup_id(&backup_id)?;
 
-    let backup_dir = backup_root.join(&backup_id);
-    if backup_dir.exists() {
+    let out = args
+        .out
+        .clone()
+        .unwrap_or_else(|| archive_path(&backup_root, &backup_id));
+    let taken = out.exists() || (args.out.is_none() && backup_root.join(&backup_id).exists());
+    if taken {
         return Err(MsError::ValidationFailed(format!(
             "backup {backup_id} already exists"
         )));
     }
-    std::fs::create_dir_all(&backup_dir)
-        .map_err(|err| MsError::Config(format!("create {}: {err}", backup_dir.display())))?;
 
-    let mut entries = Vec::new();
-    let mut total_bytes = 0u64;
+    let staging = staging_dir(&ctx.ms_root, ".backup-")?;
+    let db_snapshot = staging.path().join("ms.db");
+    ctx.db.snapshot_to(&db_snapshot)?;
 
-    let ms_db = ctx.ms_root.join("ms.db");
-    push_file_entry(
-        &mut entries,
-        &mut total_bytes,
-        "ms.db",
-        &ms_db,
-        &backup_dir.join("ms.db"),
-    )?;
-    let wal = ctx.ms_root.join("ms.db-wal");
-    push_file_entry(
-        &mut entries,
-        &mut total_bytes,
-        "ms.db-wal",
-        &wal,
-        &backup_dir.join("ms.db-wal"),
-    )?;
-    let shm = ctx.ms_root.join("ms.db-shm");
-    push_file_entry(
-        &mut entries,
-        &mut total_bytes,
-        "ms.db-shm",
-        &shm,
-        &backup_dir.join("ms.db-shm"),
-    )?;
+    let mut entries = vec![BackupEntry {
+        name: "ms.db".to_string(),
+        source_path: ctx.db_path().display().to_string(),
+        backup_path: "ms.db".to_string(),
+        bytes: std::fs::metadata(&db_snapshot)?.len(),
+        is_dir: false,
+    }];
+    let mut sources = vec![("ms.db", db_snapshot)];
 
     let archive = ctx.ms_root.join("archive");
     if archive.exists() {
-        let backup_path = backup_dir.join("archive");
-        let bytes = copy_dir_recursive(&archive, &backup_path)?;
-        total_bytes += bytes;
         entries.push(BackupEntry {
             name: "archive".to_string(),
             source_path: archive.display().to_string(),
-            backup_path: backup_path.display().to_string(),
-            bytes,
-            is_dir: true,
-        });
-    }
-
-    let index = ctx.ms_root.join("index");
-    if index.exists() {
-        let backup_path = backup_dir.join("index");
-        let bytes = copy_dir_recursive(&index, &backup_path)?;
-        total_bytes += bytes;
-        entries.push(BackupEntry {
-            name: "index".to_string(),
-            source_path: index.display().to_string(),
-            backup_path: backup_path.display().to_string(),
-            bytes,
+            backup_path: "archive".to_string(),
+            bytes: dir_size(&archive)?,
             is_dir: true,
         });
+        sources.push(("archive", archive));
     }
 
     let config_path = if ctx.config_path.exists() {
-        let backup_path = backup_dir.join("config.toml");
-        let bytes = copy_file(&ctx.config_path, &backup_path)?;
-        total_bytes += bytes;
         entries.push(BackupEntry {
             name: "config".to_string(),
             source_path: ctx.config_path.display().to_string(),
-            backup_path: backup_path.display().to_string(),
-            bytes,
+            backup_path: "config.toml".to_string(),
+            bytes: std::fs::metadata(&ctx.config_path)?.len(),
             is_dir: false,
         });
+        sources.push(("config.toml", ctx.config_path.clone()));
         Some(ctx.config_path.display().to_string())
     } else {
         None
     };
 
     let manifest = BackupManifest {
+        format: BACKUP_FORMAT,
         id: backup_id.clone(),
         created_at: Utc::now().to_rfc3339(),
+        ms_version: Some(env!("CARGO_PKG_VERSION").to_string()),
+        schema_version: Some(ctx.db.schema_version()),
+        skill_count: Some(ctx.db.count_skills()?),
         ms_root: ctx.ms_root.display().to_string(),
         config_path,
+        total_bytes: entries.iter().map(|entry| entry.bytes).sum(),
         entries,
-        total_bytes,
     };
-    let manifest_path = backup_dir.join("manifest.json");
-    let manifest_json = serde_json::to_string_pretty(&manifest)
-        .map_err(|err| MsError::Config(format!("serialize manifest: {err}")))?;
-    std::fs::write(&manifest_path, manifest_json)
-        .map_err(|err| MsError::Config(format!("write {}: {err}", manifest_path.display())))?;
+    let archive_bytes = write_archive(&out, &manifest, &sources)?;
 
     if ctx.output_format != OutputFormat::Human {
-        return crate::cli::output::emit_json(&manifest);
+        return crate::cli::output::emit_json(&BackupCreateReport {
+            manifest: &manifest,
+            path: out.display().to_string(),
+            archive_bytes,
+        });
     }
 
     println!("Backup created: {backup_id}");
-    println!("Path: {}", backup_dir.display());
-    println!("Entries: {}", manifest.entries.len());
-    println!("Size: {} bytes", manifest.total_bytes);
+    println!("Path: {}", out.display());
+    println!(
+        "Skills: {} (schema v{})",
+        manifest.skill_count.unwrap_or(0),
+        manifest.schema_version.unwrap_or(SCHEMA_VERSION)
+    );
+    println!(
+        "Size: {} ({} uncompressed)",
+        format_size(archive_bytes),
+        format_size(manifest.total_bytes)
+    );
     Ok(())
 }
 
 fn run_list(ctx: &AppContext, args: &BackupListArgs) -> Result<()> {
-    let backup_root = backup_root(ctx);
-    if !backup_root.exists() {
-        if ctx.output_format != OutputFormat::Human {
-            return crate::cli::output::emit_json(&serde_json::json!({
-                "status": "ok",
-                "count": 0,
-                "backups": []
-            }));
-        }
-        println!("No backups found.");
-        return Ok(());
-    }
-
-    let mut backups = Vec::new();
-    let mut dirs = std::fs::read_dir(&backup_root)
-        .map_err(|err| MsError::Config(format!("read {}: {err}", backup_root.display())))?
-        .filter_map(std::result::Result::ok)
-        .filter(|entry| entry.path().is_dir())
-        .collect::<Vec<_>>();
-    dirs.sort_by_key(std::fs::DirEntry::file_name);
-    dirs.reverse();
-
-    for entry in dirs.into_iter().take(args.limit) {
-        let path = entry.path();
-        let manifest_path = path.join("manifest.json");
-        if let Ok(content) = std::fs::read_to_string(&manifest_path) {
-            if let Ok(manifest) = serde_json::from_str::<BackupManifest>(&content) {
-                backups.push(manifest);
-                continue;
-            }
-        }
-        backups.push(BackupManifest {
-            id: entry.file_name().to_string_lossy().to_string(),
-            created_at: String::new(),
-            ms_root: ctx.ms_root.display().to_string(),
-            config_path: None,
-            entries: Vec::new(),
-            total_bytes: 0,
-        });
-    }
+    let mut backups = list_backups(&ctx.ms_root)?;
+    backups.truncate(args.limit);
 
     if ctx.output_format != OutputFormat::Human {
         return crate::cli::output::emit_json(&serde_json::json!({
@@ -250,13 +342,26 @@ fn run_list(ctx: &AppContext, args: &BackupListArgs) -> Result<()> {
     }
 
     println!("Backups:");
-    for backup in backups {
-        let created = if backup.created_at.is_empty() {
-            "unknown".to_string()
+    println!(
+        "  {:<24} {:>10} {:>10} {:>7}",
+        "ID", "Age", "Size", "Skills"
+    );
+    for backup in &backups {
+        let skills = backup
+            .skill_count
+            .map_or_else(|| "-".to_string(), |count| count.to_string());
+        let kind = if backup.format < BACKUP_FORMAT {
+            "  (directory)"
         } else {
-            backup.created_at.clone()
+            ""
         };
-        println!("  {}  {}  {} bytes", backup.id, created, backup.total_bytes);
+        println!(
+            "  {:<24} {:>10} {:>10} {:>7}{kind}",
+            backup.id,
+            format_age(backup.created_at),
+            format_size(backup.bytes),
+            skills
+        );
     }
     Ok(())
 }
@@ -267,9 +372,9 @@ fn run_restore(ctx: &AppContext, args: &BackupRestoreArgs) -> Result<()> {
             "backup restore requires --approve".to_string(),
         ));
     }
-    let _lock = GlobalLock::acquire(&ctx.ms_root)?;
+    let _lock = GlobalLock::acquire_or_busy(&ctx.ms_root, lock_timeout())?;
 
-    let backup_root = backup_root(ctx);
+    let backup_root = backup_root(&ctx.ms_root);
     let backup_id = match (&args.id, args.latest) {
         (Some(id), false) => {
             validate_backup_id(id)?;
@@ -289,12 +394,189 @@ fn run_restore(ctx: &AppContext, args: &BackupRestoreArgs) -> Result<()> {
     };
     validate_backup_id(&backup_id)?;
 
-    let backup_dir = backup_root.join(&backup_id);
-    let manifest_path = backup_dir.join("manifest.json");
+    let archive = archive_path(&backup_root, &backup_id);
+    let report = if archive.is_file() {
+        restore_archive(ctx, &archive, args.force_migrate)?
+    } else {
+        restore_directory(ctx, &backup_root.join(&backup_id))?
+    };
+    emit_restore(ctx, &report)
+}
+
+fn emit_restore(ctx: &AppContext, report: &RestoreReport) -> Result<()> {
+    if ctx.output_format != OutputFormat::Human {
+        return crate::cli::output::emit_json(report);
+    }
+
+    println!("Restored backup: {}", report.restored);
+    println!("Entries restored: {}", report.restored_count);
+    if !report.skipped.is_empty() {
+        println!("Entries skipped: {}", report.skipped.join(", "));
+    }
+    if let Some(from) = report.migrated_from {
+        println!("Database migrated from schema v{from} to v{SCHEMA_VERSION}");
+    }
+    if let Some(tombstone) = &report.previous_tombstone {
+        println!("Previous state tombstoned as {tombstone}");
+    }
+    if let Some(index) = &report.index {
+        if index.is_ok() {
+            println!("Search index rebuilt: {} skill(s)", index.indexed);
+        } else {
+            println!(
+                "Search index needs attention: {} of {} skill(s) indexed; run 'ms doctor --fix'",
+                index.indexed, index.skills
+            );
+            for problem in &index.problems {
+                println!("  - {problem}");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Restore a tarball backup.
+///
+/// The archive is extracted and checked in a staging directory first; only
+/// then is the live state swapped out for it, by renames. If a rename fails,
+/// everything already moved is put back. The replaced state is tombstoned.
+fn restore_archive(ctx: &AppContext, path: &Path, force_migrate: bool) -> Result<RestoreReport> {
+    let staging = staging_dir(&ctx.ms_root, ".restore-")?;
+    let staged = staging.path().join("restored");
+    let manifest = extract_archive(path, &staged, force_migrate)?;
+
+    // The snapshot must be intact before anything is replaced
+    let snapshot = Database::open_existing(staged.join("ms.db"))?;
+    let snapshot_version = migrations::current_version(snapshot.conn())?;
+    if let Some(expected) = manifest.skill_count {
+        let found = snapshot.count_skills()?;
+        if found != expected {
+            return Err(MsError::ValidationFailed(format!(
+                "backup {} is damaged: manifest lists {expected} skill(s), database has {found}",
+                manifest.id
+            )));
+        }
+    }
+    drop(snapshot);
+
+    let mut items = Vec::new();
+    let mut skipped = Vec::new();
+    for entry in &manifest.entries {
+        let Some((source, dest, _)) =
+            restore_paths_for_entry(&ctx.ms_root, &ctx.config_path, &staged, &entry.name)
+        else {
+            skipped.push(entry.name.clone());
+            continue;
+        };
+        validate_restore_paths(
+            source.to_string_lossy().as_ref(),
+            dest.to_string_lossy().as_ref(),
+            &staged,
+            &ctx.ms_root,
+            ctx.config_path.as_path(),
+        )?;
+        if !source.exists() {
+            skipped.push(entry.name.clone());
+            continue;
+        }
+        if entry.name == "ms.db" {
+            // A WAL left next to the restored database would be replayed
+            // into it, so the old one is set aside too.
+            for companion in ["ms.db-wal", "ms.db-shm"] {
+                items.push(SwapItem {
+                    name: companion.to_string(),
+                    source: None,
+                    dest: ctx.ms_root.join(companion),
+                });
+            }
+        }
+        items.push(SwapItem {
+            name: entry.name.clone(),
+            source: Some(source),
+            dest,
+        });
+    }
+
+    if let Err(err) = ctx.db.checkpoint_wal() {
+        tracing::warn!(error = %err, "WAL checkpoint before restore failed");
+    }
+    let previous = staging.path().join("previous");
+    swap_into_place(&items, &previous)?;
+
+    let previous_tombstone = match TombstoneManager::new(&ctx.ms_root).tombstone(
+        &previous,
+        Some(&format!("replaced by restore of backup {}", manifest.id)),
+        Some("ms restore"),
+    ) {
+        Ok(record) => Some(record.id),
+        Err(err) => {
+            let kept = staging.keep();
+            tracing::warn!(
+                error = %err,
+                "could not tombstone the replaced state; it is kept in {}",
+                kept.display()
+            );
+            None
+        }
+    };
+
+    // Reopening runs the migrations an older snapshot still needs
+    let mut restored = ctx.clone();
+    restored.reopen_stores()?;
+    let index = verify_index(&restored)?;
+    if let Err(err) = restored.db.checkpoint_wal() {
+        tracing::warn!(error = %err, "WAL checkpoint after restore failed");
+    }
+
+    Ok(RestoreReport {
+        status: "ok",
+        restored: manifest.id.clone(),
+        source: path.display().to_string(),
+        format: manifest.format,
+        schema_version: manifest.schema_version,
+        migrated_from: (snapshot_version < SCHEMA_VERSION).then_some(snapshot_version),
+        skill_count: manifest.skill_count,
+        entries: manifest.entries.len(),
+        restored_count: items.iter().filter(|item| item.source.is_some()).count(),
+        skipped,
+        previous_tombstone,
+        index: Some(index),
+    })
+}
+
+/// The backup carries no search index, so the current one describes the
+/// database that was just replaced: rebuild it from the restored database,
+/// then check that it is healthy and covers every skill.
+fn verify_index(ctx: &AppContext) -> Result<IndexVerification> {
+    let index_path = ctx.ms_root.join("index");
+    rebuild_search_index(
+        ctx,
+        &index_path,
+        "replaced by backup restore",
+        "ms restore",
+        false,
+    )?;
+    let problems = health::diagnose(&index_path)
+        .iter()
+        .filter(|problem| problem.is_issue())
+        .map(IndexProblem::to_string)
+        .collect();
+    Ok(IndexVerification {
+        skills: ctx.db.count_skills()?,
+        indexed: SearchIndex::open_readonly(&index_path)?.num_docs(),
+        problems,
+    })
+}
+
+/// Restore a directory backup, copying its files over the live ones.
+fn restore_directory(ctx: &AppContext, backup_dir: &Path) -> Result<RestoreReport> {
+    let manifest_path = backup_dir.join(MANIFEST_NAME);
     if !manifest_path.exists() {
-        return Err(MsError::NotFound(format!(
-            "backup {backup_id} missing manifest"
-        )));
+        let id = backup_dir
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        return Err(MsError::NotFound(format!("backup {id} missing manifest")));
     }
     let manifest_content = std::fs::read_to_string(&manifest_path)
         .map_err(|err| MsError::Config(format!("read {}: {err}", manifest_path.display())))?;
@@ -304,7 +586,7 @@ fn run_restore(ctx: &AppContext, args: &BackupRestoreArgs) -> Result<()> {
     let mut skipped = Vec::new();
     for entry in &manifest.entries {
         let Some((source, dest, is_dir)) =
-            restore_paths_for_entry(&ctx.ms_root, &ctx.config_path, &backup_dir, &entry.name)
+            restore_paths_for_entry(&ctx.ms_root, &ctx.config_path, backup_dir, &entry.name)
         else {
             skipped.push(entry.name.clone());
             continue;
@@ -312,7 +594,7 @@ fn run_restore(ctx: &AppContext, args: &BackupRestoreArgs) -> Result<()> {
         validate_restore_paths(
             source.to_string_lossy().as_ref(),
             dest.to_string_lossy().as_ref(),
-            &backup_dir,
+            backup_dir,
             &ctx.ms_root,
             ctx.config_path.as_path(),
         )?;
@@ -328,26 +610,404 @@ fn run_restore(ctx: &AppContext, args: &BackupRestoreArgs) -> Result<()> {
         restored += 1;
     }
 
-    if ctx.output_format != OutputFormat::Human {
-        return crate::cli::output::emit_json(&serde_json::json!({
-            "status": "ok",
-            "restored": manifest.id,
-            "entries": manifest.entries.len(),
-            "restored_count": restored,
-            "skipped": skipped,
-        }));
+    Ok(RestoreReport {
+        status: "ok",
+        restored: manifest.id.clone(),
+        source: backup_dir.display().to_string(),
+        format: manifest.format,
+        schema_version: manifest.schema_version,
+        migrated_from: None,
+        skill_count: manifest.skill_count,
+        entries: manifest.entries.len(),
+        restored_count: restored,
+        skipped,
+        previous_tombstone: None,
+        index: None,
+    })
+}
+
+/// Backups in `ms_root`'s backups directory, newest first.
+pub(crate) fn list_backups(ms_root: &Path) -> Result<Vec<BackupInfo>> {
+    let root = backup_root(ms_root);
+    if !root.exists() {
+        return Ok(Vec::new());
     }
 
-    println!("Restored backup: {}", manifest.id);
-    println!("Entries restored: {restored}");
-    if !skipped.is_empty() {
-        println!("Entries skipped: {}", skipped.join(", "));
+    let mut backups = Vec::new();
+    for entry in std::fs::read_dir(&root)
+        .map_err(|err| MsError::Config(format!("read {}: {err}", root.display())))?
+    {
+        let path = entry?.path();
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if path.is_dir() {
+            let manifest = std::fs::read_to_string(path.join(MANIFEST_NAME))
+                .ok()
+                .and_then(|content| serde_json::from_str::<BackupManifest>(&content).ok());
+            backups.push(backup_info(name, path, directory_format(), manifest, None));
+        } else if let Some(id) = name.strip_suffix(ARCHIVE_SUFFIX) {
+            let manifest = match read_archive_manifest(&path) {
+                Ok(manifest) => Some(manifest),
+                Err(err) => {
+                    tracing::warn!(error = %err, "unreadable backup {}", path.display());
+                    None
+                }
+            };
+            let bytes = std::fs::metadata(&path)?.len();
+            backups.push(backup_info(
+                id.to_string(),
+                path,
+                BACKUP_FORMAT,
+                manifest,
+                Some(bytes),
+            ));
+        }
+    }
+    backups.sort_by(|a, b| (b.created_at, &b.id).cmp(&(a.created_at, &a.id)));
+    Ok(backups)
+}
+
+fn backup_info(
+    id: String,
+    path: PathBuf,
+    format: u32,
+    manifest: Option<BackupManifest>,
+    bytes: Option<u64>,
+) -> BackupInfo {
+    let created_at = manifest
+        .as_ref()
+        .and_then(|m| DateTime::parse_from_rfc3339(&m.created_at).ok())
+        .map(|created| created.with_timezone(&Utc));
+    BackupInfo {
+        id,
+        path,
+        format,
+        created_at,
+        bytes: bytes.unwrap_or_else(|| manifest.as_ref().map_or(0, |m| m.total_bytes)),
+        ms_version: manifest.as_ref().and_then(|m| m.ms_version.clone()),
+        schema_version: manifest.as_ref().and_then(|m| m.schema_version),
+        skill_count: manifest.as_ref().and_then(|m| m.skill_count),
+    }
+}
+
+/// Backups (newest first) that `retention` expires at `now`. The newest
+/// backup is always kept.
+pub(crate) fn expired_backups<'a>(
+    backups: &'a [BackupInfo],
+    retention: &BackupConfig,
+    now: DateTime<Utc>,
+) -> Vec<&'a BackupInfo> {
+    let max_age = (retention.max_age_days > 0)
+        .then(|| chrono::Duration::days(i64::from(retention.max_age_days)));
+    backups
+        .iter()
+        .enumerate()
+        .skip(1)
+        .filter(|(rank, backup)| {
+            *rank >= retention.keep_last
+                || max_age
+                    .is_some_and(|max| backup.created_at.is_some_and(|created| now - created > max))
+        })
+        .map(|(_, backup)| backup)
+        .collect()
+}
+
+/// Delete a backup, returning the bytes freed.
+pub(crate) fn remove_backup(backup: &BackupInfo) -> Result<u64> {
+    if backup.path.is_dir() {
+        std::fs::remove_dir_all(&backup.path)?;
+    } else {
+        std::fs::remove_file(&backup.path)?;
+    }
+    Ok(backup.bytes)
+}
+
+/// Write `manifest` and then `sources` (name in the archive, path on disk)
+/// to a compressed tarball at `out`. The tarball is built in a temporary
+/// file next to `out` and renamed into place, so a failed backup leaves no
+/// partial archive behind. Returns the archive size.
+fn write_archive(
+    out: &Path,
+    manifest: &BackupManifest,
+    sources: &[(&str, PathBuf)],
+) -> Result<u64> {
+    let dir = out
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    std::fs::create_dir_all(dir)
+        .map_err(|err| MsError::Config(format!("create {}: {err}", dir.display())))?;
+    let partial = tempfile::NamedTempFile::new_in(dir)
+        .map_err(|err| MsError::Config(format!("create archive in {}: {err}", dir.display())))?;
+
+    let encoder = zstd::Encoder::new(BufWriter::new(partial.as_file()), 0)?;
+    let mut tar = tar::Builder::new(encoder);
+    let manifest_json = serde_json::to_vec_pretty(manifest)?;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(manifest_json.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(Utc::now().timestamp().max(0).unsigned_abs());
+    tar.append_data(&mut header, MANIFEST_NAME, manifest_json.as_slice())?;
+    for (name, path) in sources {
+        if path.is_dir() {
+            tar.append_dir_all(name, path)?;
+        } else {
+            tar.append_path_with_name(path, name)?;
+        }
+    }
+    tar.into_inner()?
+        .finish()?
+        .into_inner()
+        .map_err(std::io::IntoInnerError::into_error)?;
+    partial.as_file().sync_all()?;
+
+    partial
+        .persist_noclobber(out)
+        .map_err(|err| MsError::Config(format!("write {}: {}", out.display(), err.error)))?;
+    Ok(std::fs::metadata(out)?.len())
+}
+
+type BackupArchive = tar::Archive<zstd::Decoder<'static, BufReader<File>>>;
+
+fn open_archive(path: &Path) -> Result<BackupArchive> {
+    let file = File::open(path)
+        .map_err(|err| MsError::NotFound(format!("backup {}: {err}", path.display())))?;
+    Ok(tar::Archive::new(zstd::Decoder::new(file)?))
+}
+
+/// Parse the manifest, which must be the first entry of a backup tarball.
+fn read_manifest<R: Read>(
+    first: Option<std::io::Result<tar::Entry<'_, R>>>,
+    path: &Path,
+) -> Result<BackupManifest> {
+    let not_a_backup = || {
+        MsError::ValidationFailed(format!(
+            "{} is not an ms backup (no {MANIFEST_NAME})",
+            path.display()
+        ))
+    };
+    let mut entry = first.ok_or_else(not_a_backup)??;
+    if *entry.path()? != *Path::new(MANIFEST_NAME) {
+        return Err(not_a_backup());
+    }
+    let mut json = String::new();
+    entry.read_to_string(&mut json)?;
+    Ok(serde_json::from_str(&json)?)
+}
+
+fn read_archive_manifest(path: &Path) -> Result<BackupManifest> {
+    let mut archive = open_archive(path)?;
+    let mut entries = archive.entries()?;
+    read_manifest(entries.next(), path)
+}
+
+/// Extract a backup tarball into `dest` and return its manifest. Nothing is
+/// extracted unless the manifest passes [`check_manifest`].
+fn extract_archive(path: &Path, dest: &Path, force_migrate: bool) -> Result<BackupManifest> {
+    let mut archive = open_archive(path)?;
+    let mut entries = archive.entries()?;
+    let manifest = read_manifest(entries.next(), path)?;
+    check_manifest(&manifest, force_migrate)?;
+
+    std::fs::create_dir_all(dest)
+        .map_err(|err| MsError::Config(format!("create {}: {err}", dest.display())))?;
+    for entry in entries {
+        let mut entry = entry?;
+        let entry_path = entry.path()?.into_owned();
+        if !archive_entry_allowed(&entry_path, entry.header().entry_type()) {
+            return Err(MsError::ValidationFailed(format!(
+                "backup {} contains unexpected entry {}",
+                manifest.id,
+                entry_path.display()
+            )));
+        }
+        entry.unpack_in(dest)?;
+    }
+    if !dest.join("ms.db").is_file() {
+        return Err(MsError::ValidationFailed(format!(
+            "backup {} has no database",
+            manifest.id
+        )));
+    }
+    Ok(manifest)
+}
+
+/// Check that this ms can restore the backup `manifest` describes.
+fn check_manifest(manifest: &BackupManifest, force_migrate: bool) -> Result<()> {
+    validate_backup_id(&manifest.id)?;
+    if manifest.format > BACKUP_FORMAT {
+        return Err(MsError::ValidationFailed(format!(
+            "backup {} uses format {}, newer than this ms supports ({BACKUP_FORMAT}); upgrade ms to restore it",
+            manifest.id, manifest.format
+        )));
+    }
+    if let Some(schema) = manifest.schema_version
+        && schema > SCHEMA_VERSION
+        && !force_migrate
+    {
+        return Err(MsError::ValidationFailed(format!(
+            "backup {} has schema v{schema}, newer than this ms supports (v{SCHEMA_VERSION}); \
+             upgrade ms or pass --force-migrate",
+            manifest.id
+        )));
+    }
+    Ok(())
+}
+
+/// Whether a tarball entry may be extracted: a plain file or directory
+/// under one of the names a backup writes, with no `..` or absolute parts.
+fn archive_entry_allowed(path: &Path, kind: tar::EntryType) -> bool {
+    if !(kind.is_file() || kind.is_dir()) {
+        return false;
+    }
+    let mut components = path.components();
+    let top_level = match components.next() {
+        Some(Component::Normal(first)) => first.to_str(),
+        _ => None,
+    };
+    matches!(top_level, Some("ms.db" | "archive" | "config.toml"))
+        && components.all(|component| matches!(component, Component::Normal(_)))
+}
+
+/// A live path a restore replaces.
+struct SwapItem {
+    /// Name of the old copy under the set-aside directory.
+    name: String,
+    /// Restored copy to move in; `None` only sets `dest` aside.
+    source: Option<PathBuf>,
+    dest: PathBuf,
+}
+
+/// A step of [`swap_into_place`], for rolling it back.
+struct Swapped<'a> {
+    item: &'a SwapItem,
+    set_aside: bool,
+    placed: bool,
+}
+
+/// Move each item's current `dest` into `previous`, then its `source` into
+/// place. On failure, everything moved so far is put back.
+fn swap_into_place(items: &[SwapItem], previous: &Path) -> Result<()> {
+    std::fs::create_dir_all(previous)
+        .map_err(|err| MsError::Config(format!("create {}: {err}", previous.display())))?;
+    let mut done: Vec<Swapped<'_>> = Vec::new();
+    for item in items {
+        let set_aside = item.dest.exists();
+        if set_aside && let Err(err) = move_path(&item.dest, &previous.join(&item.name)) {
+            roll_back(&done, previous);
+            return Err(err);
+        }
+        done.push(Swapped {
+            item,
+            set_aside,
+            placed: false,
+        });
+        if let Some(source) = &item.source {
+            if let Err(err) = move_path(source, &item.dest) {
+                roll_back(&done, previous);
+                return Err(err);
+            }
+            if let Some(step) = done.last_mut() {
+                step.placed = true;
+            }
+        }
     }
     Ok(())
 }
 
-fn backup_root(ctx: &AppContext) -> PathBuf {
-    ctx.ms_root.join("backups")
+fn roll_back(done: &[Swapped<'_>], previous: &Path) {
+    for step in done.iter().rev() {
+        let dest = &step.item.dest;
+        if step.placed {
+            let removed = if dest.is_dir() {
+                std::fs::remove_dir_all(dest)
+            } else {
+                std::fs::remove_file(dest)
+            };
+            if let Err(err) = removed {
+                tracing::warn!(error = %err, "rollback: remove {}", dest.display());
+            }
+        }
+        if step.set_aside
+            && let Err(err) = move_path(&previous.join(&step.item.name), dest)
+        {
+            tracing::warn!(error = %err, "rollback: put back {}", dest.display());
+        }
+    }
+}
+
+/// Rename `from` to `to`. A file on another filesystem (the config may live
+/// outside `ms_root`) is copied next to `to` and renamed over it instead, so
+/// `to` still changes in one step.
+fn move_path(from: &Path, to: &Path) -> Result<()> {
+    match std::fs::rename(from, to) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == std::io::ErrorKind::CrossesDevices && from.is_file() => {
+            let tmp = to.with_extension("restore-tmp");
+            copy_file(from, &tmp)?;
+            std::fs::rename(&tmp, to).map_err(|err| {
+                MsError::Config(format!("move {} to {}: {err}", tmp.display(), to.display()))
+            })?;
+            std::fs::remove_file(from)
+                .map_err(|err| MsError::Config(format!("remove {}: {err}", from.display())))
+        }
+        Err(err) => Err(MsError::Config(format!(
+            "move {} to {}: {err}",
+            from.display(),
+            to.display()
+        ))),
+    }
+}
+
+/// A temporary directory in `ms_root`, on the same filesystem as the state
+/// it is swapped with.
+fn staging_dir(ms_root: &Path, prefix: &str) -> Result<tempfile::TempDir> {
+    tempfile::Builder::new()
+        .prefix(prefix)
+        .tempdir_in(ms_root)
+        .map_err(|err| {
+            MsError::Config(format!(
+                "create staging dir in {}: {err}",
+                ms_root.display()
+            ))
+        })
+}
+
+fn dir_size(path: &Path) -> Result<u64> {
+    let mut total = 0;
+    for entry in walkdir::WalkDir::new(path) {
+        let entry =
+            entry.map_err(|err| MsError::Config(format!("walk {}: {err}", path.display())))?;
+        if entry.file_type().is_file() {
+            total += entry.metadata().map_or(0, |meta| meta.len());
+        }
+    }
+    Ok(total)
+}
+
+fn format_age(created_at: Option<DateTime<Utc>>) -> String {
+    let Some(created_at) = created_at else {
+        return "unknown".to_string();
+    };
+    let secs = (Utc::now() - created_at)
+        .num_seconds()
+        .max(0)
+        .unsigned_abs();
+    if secs >= 86_400 {
+        format!("{}d {}h", secs / 86_400, (secs % 86_400) / 3600)
+    } else {
+        format_duration(secs)
+    }
+}
+
+fn backup_root(ms_root: &Path) -> PathBuf {
+    ms_root.join("backups")
+}
+
+fn archive_path(backup_root: &Path, id: &str) -> PathBuf {
+    backup_root.join(format!("{id}{ARCHIVE_SUFFIX}"))
 }
 
 fn timestamp_id() -> String {
@@ -468,38 +1128,21 @@ fn latest_backup_id(root: &Path) -> Result<String> {
     if !root.exists() {
         return Err(MsError::NotFound("no backups found".to_string()));
     }
-    let mut dirs = std::fs::read_dir(root)
+    let mut ids = std::fs::read_dir(root)
         .map_err(|err| MsError::Config(format!("read {}: {err}", root.display())))?
         .filter_map(std::result::Result::ok)
-        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if entry.path().is_dir() {
+                Some(name)
+            } else {
+                name.strip_suffix(ARCHIVE_SUFFIX).map(str::to_string)
+            }
+        })
         .collect::<Vec<_>>();
-    dirs.sort_by_key(std::fs::DirEntry::file_name);
-    let latest = dirs
-        .pop()
-        .ok_or_else(|| MsError::NotFound("no backups found".to_string()))?;
-    Ok(latest.file_name().to_string_lossy().to_string())
-}
-
-fn push_file_entry(
-    entries: &mut Vec<BackupEntry>,
-    total_bytes: &mut u64,
-    name: &str,
-    src: &Path,
-    dst: &Path,
-) -> Result<()> {
-    if !src.exists() {
-        return Ok(());
-    }
-    let bytes = copy_file(src, dst)?;
-    *total_bytes += bytes;
-    entries.push(BackupEntry {
-        name: name.to_string(),
-        source_path: src.display().to_string(),
-        backup_path: dst.display().to_string(),
-        bytes,
-        is_dir: false,
-    });
-    Ok(())
+    ids.sort();
+    ids.pop()
+        .ok_or_else(|| MsError::NotFound("no backups found".to_string()))
 }
 
 fn copy_file(src: &Path, dst: &Path) -> Result<u64> {
@@ -615,6 +1258,185 @@ mod tests {
 
         let latest = latest_backup_id(dir.path()).unwrap();
         assert_eq!(latest, "20240202020202");
+
+        std::fs::write(dir.path().join("20240303030303.tar.zst"), b"").unwrap();
+        std::fs::write(dir.path().join("20250101010101.partial"), b"").unwrap();
+        assert_eq!(latest_backup_id(dir.path()).unwrap(), "20240303030303");
+    }
+
+    fn manifest(id: &str, schema_version: u32) -> BackupManifest {
+        BackupManifest {
+            format: BACKUP_FORMAT,
+            id: id.to_string(),
+            created_at: Utc::now().to_rfc3339(),
+            ms_version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            schema_version: Some(schema_version),
+            skill_count: Some(1),
+            ms_root: "/tmp/ms-root".to_string(),
+            config_path: None,
+            entries: Vec::new(),
+            total_bytes: 0,
+        }
+    }
+
+    #[test]
+    fn archive_roundtrip_keeps_manifest_first_and_tree_intact() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = dir.path().join("snapshot.db");
+        std::fs::write(&db, "db").unwrap();
+        let git = dir.path().join("git");
+        std::fs::create_dir_all(git.join("objects/ab")).unwrap();
+        std::fs::write(git.join("objects/ab/cdef"), "object").unwrap();
+
+        let out = dir.path().join("out").join("snap.tar.zst");
+        let sources = [("ms.db", db), ("archive", git)];
+        let bytes = write_archive(&out, &manifest("snap", SCHEMA_VERSION), &sources).unwrap();
+        assert_eq!(bytes, std::fs::metadata(&out).unwrap().len());
+        assert!(write_archive(&out, &manifest("snap", SCHEMA_VERSION), &sources).is_err());
+
+        assert_eq!(read_archive_manifest(&out).unwrap().id, "snap");
+        let dest = dir.path().join("restored");
+        extract_archive(&out, &dest, false).unwrap();
+        assert_eq!(std::fs::read_to_string(dest.join("ms.db")).unwrap(), "db");
+        assert_eq!(
+            std::fs::read_to_string(dest.join("archive/objects/ab/cdef")).unwrap(),
+            "object"
+        );
+    }
+
+    #[test]
+    fn newer_schema_needs_force_migrate() {
+        let newer = manifest("snap", SCHEMA_VERSION + 1);
+        let err = check_manifest(&newer, false).unwrap_err().to_string();
+        assert!(err.contains("--force-migrate"), "{err}");
+        assert!(check_manifest(&newer, true).is_ok());
+        assert!(check_manifest(&manifest("snap", 1), false).is_ok());
+
+        let mut future = manifest("snap", SCHEMA_VERSION);
+        future.format = BACKUP_FORMAT + 1;
+        assert!(check_manifest(&future, true).is_err());
+    }
+
+    #[test]
+    fn archive_entries_stay_inside_known_names() {
+        let file = tar::EntryType::Regular;
+        assert!(archive_entry_allowed(Path::new("ms.db"), file));
+        assert!(archive_entry_allowed(
+            Path::new("archive/skills/a/SKILL.md"),
+            file
+        ));
+        assert!(archive_entry_allowed(
+            Path::new("archive"),
+            tar::EntryType::Directory
+        ));
+        assert!(!archive_entry_allowed(Path::new("archive/../../x"), file));
+        assert!(!archive_entry_allowed(Path::new("/etc/passwd"), file));
+        assert!(!archive_entry_allowed(Path::new("index/meta.json"), file));
+        assert!(!archive_entry_allowed(
+            Path::new("archive/link"),
+            tar::EntryType::Symlink
+        ));
+    }
+
+    #[test]
+    fn failed_swap_puts_everything_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let live = dir.path().join("live");
+        let staged = dir.path().join("staged");
+        std::fs::create_dir_all(&live).unwrap();
+        std::fs::create_dir_all(&staged).unwrap();
+        std::fs::write(live.join("ms.db"), "old db").unwrap();
+        std::fs::write(live.join("ms.db-wal"), "old wal").unwrap();
+        std::fs::write(staged.join("ms.db"), "new db").unwrap();
+
+        let items = [
+            SwapItem {
+                name: "ms.db-wal".to_string(),
+                source: None,
+                dest: live.join("ms.db-wal"),
+            },
+            SwapItem {
+                name: "ms.db".to_string(),
+                source: Some(staged.join("ms.db")),
+                dest: live.join("ms.db"),
+            },
+            SwapItem {
+                name: "archive".to_string(),
+                source: Some(staged.join("missing")),
+                dest: live.join("archive"),
+            },
+        ];
+        let previous = dir.path().join("previous");
+        assert!(swap_into_place(&items, &previous).is_err());
+        assert_eq!(
+            std::fs::read_to_string(live.join("ms.db")).unwrap(),
+            "old db"
+        );
+        assert_eq!(
+            std::fs::read_to_string(live.join("ms.db-wal")).unwrap(),
+            "old wal"
+        );
+
+        swap_into_place(&items[..2], &previous).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(live.join("ms.db")).unwrap(),
+            "new db"
+        );
+        assert!(!live.join("ms.db-wal").exists());
+        assert_eq!(
+            std::fs::read_to_string(previous.join("ms.db")).unwrap(),
+            "old db"
+        );
+    }
+
+    fn backup_at(id: &str, days_ago: i64) -> BackupInfo {
+        BackupInfo {
+            id: id.to_string(),
+            path: PathBuf::from(id),
+            format: BACKUP_FORMAT,
+            created_at: Some(Utc::now() - chrono::Duration::days(days_ago)),
+            bytes: 1,
+            ms_version: None,
+            schema_version: None,
+            skill_count: None,
+        }
+    }
+
+    #[test]
+    fn retention_keeps_newest_and_expires_by_count_or_age() {
+        let mut backups = vec![
+            backup_at("d", 200),
+            backup_at("c", 100),
+            backup_at("b", 2),
+            backup_at("a", 1),
+        ];
+        backups.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        let ids = |retention: &BackupConfig| -> Vec<String> {
+            expired_backups(&backups, retention, Utc::now())
+                .into_iter()
+                .map(|backup| backup.id.clone())
+                .collect()
+        };
+
+        let by_age = BackupConfig {
+            keep_last: 10,
+            max_age_days: 90,
+        };
+        assert_eq!(ids(&by_age), ["c", "d"]);
+        let by_count = BackupConfig {
+            keep_last: 3,
+            max_age_days: 0,
+        };
+        assert_eq!(ids(&by_count), ["d"]);
+        let nothing_kept = BackupConfig {
+            keep_last: 0,
+            max_age_days: 1,
+        };
+        assert_eq!(ids(&nothing_kept), ["b", "c", "d"]);
+
+        // The newest backup survives even when it is too old
+        let old = [backup_at("old", 400)];
+        assert!(expired_backups(&old, &by_age, Utc::now()).is_empty());
     }
 
     #[test]
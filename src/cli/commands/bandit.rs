@@ -156,7 +156,7 @@ fn reset(ctx: &AppContext, args: &ResetArgs) -> Result<()> {
     }
 }
 
-fn default_bandit_path() -> PathBuf {
+pub(crate) fn default_bandit_path() -> PathBuf {
     let base = dirs::data_dir().unwrap_or_else(|| PathBuf::from("."));
     base.join("ms").join("bandit.json")
 }
@@ -0,0 +1,210 @@
+//! ms budget - Report context budget consumption per layer and pack
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::PathBuf;
+
+use clap::Args;
+
+use crate::app::AppContext;
+use crate::cli::commands::meta::{detect_tech_stacks, get_meta_skill_paths};
+use crate::cli::output::{HumanLayout, OutputFormat, emit_json};
+use crate::core::budget::{
+    BudgetContributor, BudgetReport, BudgetScope, BudgetUsage, DEFAULT_TOP_CONTRIBUTORS,
+    layer_usage,
+};
+use crate::error::Result;
+use crate::meta_skills::{ConditionContext, MetaSkillManager, MetaSkillRegistry};
+
+#[derive(Args, Debug)]
+pub struct BudgetArgs {
+    /// Number of largest contributors to list per budget
+    #[arg(long, default_value_t = DEFAULT_TOP_CONTRIBUTORS)]
+    pub top: usize,
+
+    /// Exit with an error if any budget is exceeded
+    #[arg(long)]
+    pub strict: bool,
+}
+
+pub fn run(ctx: &AppContext, args: &BudgetArgs) -> Result<()> {
+    let report = build_report(ctx, args.top)?;
+    let exceeded = report.over_budget().len();
+
+    if ctx.output_format != OutputFormat::Human {
+        let payload = serde_json::json!({
+            "status": if exceeded == 0 { "ok" } else { "over_budget" },
+            "exceeded": exceeded,
+            "report": report,
+        });
+        emit_json(&payload)?;
+    } else {
+        crate::cli::output::emit_human(render_human(&report));
+    }
+
+    if args.strict && exceeded > 0 {
+        return Err(crate::error::MsError::ValidationFailed(format!(
+            "{exceeded} context budget(s) exceeded"
+        )));
+    }
+    Ok(())
+}
+
+/// Build the budget report for every layer and pack budget in the config.
+pub(crate) fn build_report(ctx: &AppContext, top_n: usize) -> Result<BudgetReport> {
+    let (mut budgets, recomputed) = layer_usage(&ctx.db, &ctx.config.budget, top_n)?;
+    budgets.extend(pack_usage(ctx, top_n)?);
+    Ok(BudgetReport::new(budgets, recomputed))
+}
+
+/// Measure the full (unbudgeted) size of every pack that has a budget.
+fn pack_usage(ctx: &AppContext, top_n: usize) -> Result<Vec<BudgetUsage>> {
+    if ctx.config.budget.pack.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut registry = MetaSkillRegistry::new();
+    registry.load_from_paths(&get_meta_skill_paths(ctx))?;
+
+    let manager = MetaSkillManager::new(ctx);
+    let working_dir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let tech_stacks = detect_tech_stacks(&working_dir);
+    let loaded = HashSet::new();
+    let condition_ctx = ConditionContext {
+        working_dir: &working_dir,
+        tech_stacks: &tech_stacks,
+        loaded_slices: &loaded,
+    };
+
+    let mut usages = Vec::new();
+    for (pack_id, budget) in &ctx.config.budget.pack {
+        let contributors = match registry.get(pack_id) {
+            Some(meta_skill) => {
+                let result = manager.load(meta_skill, usize::MAX, &condition_ctx)?;
+                let mut by_skill: BTreeMap<String, usize> = BTreeMap::new();
+                for slice in &result.slices {
+                    *by_skill.entry(slice.skill_id.clone()).or_insert(0) += slice.token_estimate;
+                }
+                by_skill
+                    .into_iter()
+                    .map(|(id, tokens)| BudgetContributor { id, tokens })
+                    .collect()
+            }
+            None => {
+                tracing::debug!(pack = %pack_id, "budgeted pack not found; reporting as empty");
+                Vec::new()
+            }
+        };
+        usages.push(BudgetUsage::new(
+            BudgetScope::Pack,
+            pack_id.clone(),
+            *budget as usize,
+            contributors,
+            top_n,
+        ));
+    }
+    Ok(usages)
+}
+
+fn render_human(report: &BudgetReport) -> HumanLayout {
+    let mut layout = HumanLayout::new();
+    layout.title("Context Budgets");
+
+    if report.budgets.is_empty() {
+        layout.push_line("No budgets configured.");
+        layout.push_line("Add a [budget] section to config.toml, e.g.:");
+        layout.push_line("  [budget]");
+        layout.push_line("  project_layer = 8000");
+        layout.push_line("  pack.onboarding = 4000");
+        return layout;
+    }
+
+    for usage in &report.budgets {
+        let marker = if usage.over_budget { "[!]" } else { "[ok]" };
+        layout
+            .section(&format!("{marker} {} {}", usage.scope, usage.name))
+            .kv(
+                "Used",
+                &format!(
+                    "{} / {} tokens ({:.1}%)",
+                    usage.used, usage.budget, usage.percent_used
+                ),
+            )
+            .kv("Contributors", &usage.contributor_count.to_string());
+        if usage.over_budget {
+            layout.kv("Over by", &format!("{} tokens", usage.overage()));
+        }
+        for contributor in &usage.top_contributors {
+            layout.bullet(&format!(
+                "{} ({} tokens)",
+                contributor.id, contributor.tokens
+            ));
+        }
+        layout.blank();
+    }
+
+    if report.recomputed_token_counts > 0 {
+        layout.push_line(format!(
+            "Recomputed {} stale token count(s).",
+            report.recomputed_token_counts
+        ));
+    }
+    for advisory in &report.advisories {
+        layout.push_line(advisory.to_string());
+    }
+    layout
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser, Debug)]
+    #[command(name = "test")]
+    struct TestCli {
+        #[command(flatten)]
+        budget: BudgetArgs,
+    }
+
+    #[test]
+    fn parse_budget_defaults() {
+        let cli = TestCli::try_parse_from(["test"]).unwrap();
+        assert_eq!(cli.budget.top, DEFAULT_TOP_CONTRIBUTORS);
+        assert!(!cli.budget.strict);
+    }
+
+    #[test]
+    fn parse_budget_options() {
+        let cli = TestCli::try_parse_from(["test", "--top", "3", "--strict"]).unwrap();
+        assert_eq!(cli.budget.top, 3);
+        assert!(cli.budget.strict);
+    }
+
+    #[test]
+    fn render_human_flags_over_budget() {
+        let report = BudgetReport::new(
+            vec![BudgetUsage::new(
+                BudgetScope::Pack,
+                "onboarding",
+                10,
+                vec![BudgetContributor {
+                    id: "big-skill".to_string(),
+                    tokens: 25,
+                }],
+                5,
+            )],
+            0,
+        );
+        let text = render_human(&report).build();
+        assert!(text.contains("[!] pack onboarding"));
+        assert!(text.contains("big-skill (25 tokens)"));
+        assert!(text.contains("pack-budget-exceeded"));
+    }
+
+    #[test]
+    fn render_human_explains_empty_config() {
+        let report = BudgetReport::new(Vec::new(), 0);
+        let text = render_human(&report).build();
+        assert!(text.contains("No budgets configured"));
+    }
+}
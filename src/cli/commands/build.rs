@@ -24,13 +24,20 @@ use serde::{Deserialize, Serialize};
 use crate::app::AppContext;
 use crate::beads::{BeadsClient, IssueStatus, UpdateIssueRequest};
 use crate::cass::{
-    CassClient, QualityScorer,
-    brenner::{BrennerConfig, BrennerWizard, WizardOutput, generate_skill_md, run_interactive},
+    CassClient, MissingSignal, QualityConfig, QualityScorer, SessionQuality,
+    adapters::load_session_file,
+    brenner::{
+        BrennerConfig, BrennerWizard, WizardCheckpoint, WizardOutput, generate_skill_md,
+        run_interactive, wizard_checkpoint_dir,
+    },
 };
+use crate::cli::commands::cass_client;
 use crate::cli::output::OutputFormat;
 use crate::cm::CmClient;
+use crate::config::Config;
 use crate::core::recovery::Checkpoint;
 use crate::error::{MsError, Result};
+use crate::output::PhaseProgress;
 use crate::tui::build_tui::run_build_tui;
 
 // =============================================================================
@@ -135,6 +142,51 @@ impl Default for QualityGates {
     }
 }
 
+/// A session that scored below the session quality threshold.
+#[derive(Debug, Clone, Serialize)]
+pub struct LowQualitySession {
+    pub session_id: String,
+    pub score: f32,
+    /// Signals the scorer looked for and did not find
+    pub missing: Vec<MissingSignal>,
+    /// The missing signals as short phrases, e.g. "no validation phase"
+    pub reasons: Vec<String>,
+}
+
+/// Outcome of the session quality gate for one candidate session.
+#[derive(Debug)]
+enum SessionGate {
+    /// Meets the threshold
+    Pass,
+    /// Below the threshold but kept by `--include-low-quality`
+    Included(LowQualitySession),
+    /// Below the threshold; left out of mining
+    Skipped(LowQualitySession),
+}
+
+/// Apply the session quality threshold to a scored candidate session.
+fn gate_session(
+    session_id: &str,
+    quality: &SessionQuality,
+    config: &QualityConfig,
+    include_low_quality: bool,
+) -> SessionGate {
+    if quality.passes_threshold(config) {
+        return SessionGate::Pass;
+    }
+    let low = LowQualitySession {
+        session_id: session_id.to_string(),
+        score: quality.score,
+        missing: quality.missing.clone(),
+        reasons: quality.skip_reasons(config),
+    };
+    if include_low_quality {
+        SessionGate::Included(low)
+    } else {
+        SessionGate::Skipped(low)
+    }
+}
+
 /// Persistent state for resumable builds.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct BuildState {
@@ -373,6 +425,16 @@ pub struct BuildArgs {
     #[arg(long)]
     pub from_cass: Option<String>,
 
+    /// Build from session transcript files instead of CASS (cass export,
+    /// Claude Code, Codex CLI or Gemini CLI; the format is detected)
+    #[arg(
+        long,
+        value_name = "PATH",
+        num_args = 1..,
+        conflicts_with_all = ["from_cass", "guided", "resume"]
+    )]
+    pub from_file: Vec<PathBuf>,
+
     /// Track build progress against a beads issue
     #[arg(long)]
     pub bead_id: Option<String>,
@@ -397,6 +459,10 @@ pub struct BuildArgs {
     #[arg(long, default_value = "5")]
     pub sessions: usize,
 
+    /// Worker threads for pattern extraction (default: number of cores)
+    #[arg(long, short = 'j', value_parser = clap::value_parser!(usize).range(1..))]
+    pub jobs: Option<usize>,
+
     /// Autonomous build duration (e.g., "4h")
     #[arg(long)]
     pub duration: Option<String>,
@@ -405,17 +471,21 @@ pub struct BuildArgs {
     #[arg(long)]
     pub checkpoint_interval: Option<String>,
 
-    /// Resume a previous build session
+    /// Resume a previous build session (without an id: list wizard checkpoints)
     #[arg(long)]
-    pub resume: Option<String>,
+    pub resume: Option<Option<String>>,
 
     /// Seed build with CM (cass-memory) context and rules
     #[arg(long)]
     pub with_cm: bool,
 
-    /// Minimum session quality score (0.0-1.0)
-    #[arg(long, default_value = "0.6")]
-    pub min_session_quality: f32,
+    /// Minimum session quality score (0.0-1.0); overrides `[build].min_session_quality`
+    #[arg(long, visible_alias = "min-session-quality", value_name = "SCORE")]
+    pub min_quality: Option<f32>,
+
+    /// Mine sessions below the quality threshold too (they are still reported)
+    #[arg(long)]
+    pub include_low_quality: bool,
 
     /// Emit redaction report without building
     #[arg(long)]
@@ -433,6 +503,10 @@ pub struct BuildArgs {
     #[arg(long)]
     pub no_injection_filter: bool,
 
+    /// Probe cass again instead of reusing cached health and capabilities
+    #[arg(long)]
+    pub no_cache: bool,
+
     /// Generalization method: "heuristic" or "llm"
     #[arg(long, default_value = "heuristic")]
     pub generalize: String,
@@ -470,6 +544,13 @@ pub struct BuildArgs {
     pub resolve_uncertainties: bool,
 }
 
+impl BuildArgs {
+    /// Session quality threshold: `--min-quality`, else the configured one.
+    fn min_session_quality(&self, config: &Config) -> f32 {
+        self.min_quality.unwrap_or(config.build.min_session_quality)
+    }
+}
+
 /// CM integration context for build process.
 pub struct CmBuildContext {
     /// Rules to seed pattern extraction
@@ -721,10 +802,17 @@ pub fn run(ctx: &AppContext, args: &BuildArgs) -> Result<()> {
         ));
     }
 
+    // Mining sessions needs cass; say so now rather than after CM, beads
+    // and the first search
+    if args.from_cass.is_some() && args.resume.is_none() && !args.resolve_uncertainties {
+        require_cass(&cass_client(ctx, None, args.no_cache))?;
+    }
+
     // Warn about risky flags
     if (args.no_redact || args.no_injection_filter)
         && !args.auto
         && !args.guided
+        && args.from_file.is_empty()
         && ctx.output_format == OutputFormat::Human
     {
         eprintln!(
@@ -802,8 +890,10 @@ pub fn run(ctx: &AppContext, args: &BuildArgs) -> Result<()> {
     }
 
     // Handle resume
-    if let Some(ref session_id) = args.resume {
-        return run_resume(ctx, args, session_id, bead_tracker);
+    match args.resume {
+        Some(Some(ref session_id)) => return run_resume(ctx, args, session_id, bead_tracker),
+        Some(None) => return run_resume_picker(ctx, args, bead_tracker),
+        None => {}
     }
 
     // Handle resolve uncertainties
@@ -816,8 +906,8 @@ pub fn run(ctx: &AppContext, args: &BuildArgs) -> Result<()> {
         return run_guided(ctx, args, cm_context.as_ref(), bead_tracker);
     }
 
-    // Auto mode
-    if args.auto {
+    // Auto mode (transcript files have no interactive flow)
+    if args.auto || !args.from_file.is_empty() {
         return run_auto(ctx, args, cm_context.as_ref(), bead_tracker, None);
     }
 
@@ -825,6 +915,16 @@ pub fn run(ctx: &AppContext, args: &BuildArgs) -> Result<()> {
     run_interactive_build(ctx, args, cm_context.as_ref(), bead_tracker)
 }
 
+/// Fail with the probe's reason when cass cannot be used.
+fn require_cass(client: &CassClient) -> Result<()> {
+    match client.probe().unavailable_reason() {
+        None => Ok(()),
+        Some(reason) => Err(MsError::CassUnavailable(format!(
+            "{reason} or pass --from-file"
+        ))),
+    }
+}
+
 /// Run guided build using Brenner Method wizard
 fn run_guided(
     ctx: &AppContext,
@@ -836,27 +936,11 @@ fn run_guided(
         .from_cass
         .clone()
         .unwrap_or_else(|| "skill patterns".to_string());
+    let config = guided_config(ctx, args, &query)?;
+    let output_dir = config.output_dir.clone();
 
-    let output_dir = args.output.clone().unwrap_or_else(|| {
-        ctx.ms_root.join("builds").join(
-            query
-                .chars()
-                .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
-                .collect::<String>(),
-        )
-    });
-
-    // Ensure output directory exists
-    fs::create_dir_all(&output_dir)?;
-
-    let config = BrennerConfig {
-        min_quality: args.min_session_quality,
-        min_confidence: args.min_confidence,
-        max_sessions: args.sessions,
-        output_dir: output_dir.clone(),
-    };
-
-    let mut wizard = BrennerWizard::new(&query, config.clone());
+    let wizard =
+        BrennerWizard::new(&query, config).with_checkpoint_dir(wizard_checkpoint_dir(&ctx.ms_root));
 
     // Show CM suggestions if available
     if let Some(cm_ctx) = cm_context {
@@ -869,14 +953,6 @@ fn run_guided(
         }
     }
 
-    // Create CASS client and quality scorer
-    let client = if let Some(ref cass_path) = ctx.config.cass.cass_path {
-        CassClient::with_binary(cass_path)
-    } else {
-        CassClient::new()
-    };
-    let quality_scorer = QualityScorer::with_defaults();
-
     if ctx.output_format != OutputFormat::Human {
         // Robot mode: output checkpoint ID and wait for commands
         let output = json!({
@@ -889,9 +965,48 @@ fn run_guided(
         return Ok(());
     }
 
+    drive_wizard(ctx, args, wizard, tracker)
+}
+
+/// Wizard configuration for `query`, creating its output directory.
+fn guided_config(ctx: &AppContext, args: &BuildArgs, query: &str) -> Result<BrennerConfig> {
+    let output_dir = args.output.clone().unwrap_or_else(|| {
+        ctx.ms_root.join("builds").join(
+            query
+                .chars()
+                .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+                .collect::<String>(),
+        )
+    });
+
+    // Ensure output directory exists
+    fs::create_dir_all(&output_dir)?;
+
+    Ok(BrennerConfig {
+        min_quality: args.min_session_quality(&ctx.config),
+        min_confidence: args.min_confidence,
+        max_sessions: args.sessions,
+        output_dir,
+    })
+}
+
+/// Run a new or resumed wizard to completion and write its outputs.
+fn drive_wizard(
+    ctx: &AppContext,
+    args: &BuildArgs,
+    mut wizard: BrennerWizard,
+    tracker: Option<BeadsTracker>,
+) -> Result<()> {
+    // Create CASS client and quality scorer
+    let client = cass_client(ctx, None, args.no_cache);
+    let quality_scorer = QualityScorer::new(QualityConfig {
+        min_score: wizard.config().min_quality,
+        ..Default::default()
+    });
+
     // Run interactive wizard - TUI or text mode
     let result = if args.tui {
-        run_build_tui(&query, config, &client, &quality_scorer)?
+        run_build_tui(wizard, &client, &quality_scorer)?
     } else {
         run_interactive(&mut wizard, &client, &quality_scorer)?
     };
@@ -937,8 +1052,11 @@ fn run_guided(
             checkpoint_id,
         } => {
             println!("\n{} Build cancelled: {}", "Info:", reason);
+            // Nothing is saved until sessions are confirmed
             if let Some(id) = checkpoint_id {
-                println!("  Resume with: ms build --resume {id}");
+                if WizardCheckpoint::find(&wizard_checkpoint_dir(&ctx.ms_root), &id)?.is_some() {
+                    println!("  Resume with: ms build --resume {id}");
+                }
             }
             if let Some(t) = tracker {
                 t.on_failure(&format!("Cancelled: {reason}"))?;
@@ -949,7 +1067,170 @@ fn run_guided(
     Ok(())
 }
 
+/// `ms build --resume` without an id: list unfinished wizard checkpoints and
+/// let the user pick one.
+fn run_resume_picker(
+    ctx: &AppContext,
+    args: &BuildArgs,
+    tracker: Option<BeadsTracker>,
+) -> Result<()> {
+    let dir = wizard_checkpoint_dir(&ctx.ms_root);
+    let checkpoints: Vec<(PathBuf, WizardCheckpoint)> = WizardCheckpoint::load_all(&dir)?
+        .into_iter()
+        .filter(|(_, cp)| !cp.is_finished())
+        .collect();
+
+    if ctx.output_format != OutputFormat::Human {
+        let items: Vec<serde_json::Value> = checkpoints
+            .iter()
+            .map(|(_, cp)| {
+                json!({
+                    "key": cp.selection_hash(),
+                    "checkpoint_id": cp.id,
+                    "query": cp.query,
+                    "phase": cp.phase(),
+                    "sessions": cp.sessions.len(),
+                    "decided_moves": cp.decided_moves.len(),
+                    "created_at": cp.created_at.to_rfc3339(),
+                    "updated_at": cp.updated_at.to_rfc3339(),
+                })
+            })
+            .collect();
+        let output = json!({
+            "status": "ok",
+            "checkpoints": items,
+            "count": items.len(),
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    if checkpoints.is_empty() {
+        println!("No resumable wizard checkpoints.");
+        println!("  Start one with: ms build --guided --from-cass <query>");
+        return Ok(());
+    }
+
+    println!("Resumable wizard checkpoints:");
+    for (idx, (_, cp)) in checkpoints.iter().enumerate() {
+        let key = cp.selection_hash().unwrap_or_default();
+        println!(
+            "  [{}] {}  {}  {} sessions, {} decided, phase {}  \"{}\"",
+            idx + 1,
+            &key[..key.len().min(12)],
+            cp.updated_at.format("%Y-%m-%d %H:%M"),
+            cp.sessions.len(),
+            cp.decided_moves.len(),
+            cp.phase(),
+            cp.query
+        );
+    }
+    print!(
+        "\nResume which? [1-{}, enter to cancel] ",
+        checkpoints.len()
+    );
+    io::stdout().flush()?;
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    let choice = input
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .and_then(|n| n.checked_sub(1))
+        .and_then(|idx| checkpoints.into_iter().nth(idx));
+
+    match choice {
+        Some((path, checkpoint)) => resume_wizard(ctx, args, path, checkpoint, tracker),
+        None => Ok(()),
+    }
+}
+
+/// Restore a wizard checkpoint, refusing silently-stale session content.
+fn resume_wizard(
+    ctx: &AppContext,
+    args: &BuildArgs,
+    path: PathBuf,
+    checkpoint: WizardCheckpoint,
+    tracker: Option<BeadsTracker>,
+) -> Result<()> {
+    let client = cass_client(ctx, None, args.no_cache);
+    let changed = checkpoint.changed_sessions(|fp| {
+        client
+            .get_session(&fp.path)
+            .ok()
+            .map(|session| session.content_hash)
+    });
+    let query = checkpoint.query.clone();
+
+    if ctx.output_format != OutputFormat::Human {
+        let output = json!({
+            "status": if changed.is_empty() { "resumable" } else { "stale" },
+            "key": checkpoint.selection_hash(),
+            "checkpoint_id": checkpoint.id,
+            "query": query,
+            "phase": checkpoint.phase(),
+            "decided_moves": checkpoint.decided_moves.len(),
+            "changed_sessions": changed,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    if checkpoint.is_finished() {
+        println!(
+            "{} Checkpoint {} already finished ({}).",
+            "Info:",
+            checkpoint.id,
+            checkpoint.phase()
+        );
+        return Ok(());
+    }
+
+    let config = guided_config(ctx, args, &query)?;
+    let dir = wizard_checkpoint_dir(&ctx.ms_root);
+
+    if !changed.is_empty() {
+        println!(
+            "{} {} session(s) changed since this checkpoint: {}",
+            "Warning:",
+            changed.len(),
+            changed.join(", ")
+        );
+        print!("Restart the wizard from scratch? [y/N] ");
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        if !input.trim().eq_ignore_ascii_case("y") {
+            println!("Checkpoint kept; nothing resumed.");
+            return Ok(());
+        }
+        fs::remove_file(&path)?;
+        let wizard = BrennerWizard::new(&query, config).with_checkpoint_dir(dir);
+        return drive_wizard(ctx, args, wizard, tracker);
+    }
+
+    println!("{}", "Resuming wizard from checkpoint...");
+    println!("  Query: {query}");
+    println!("  Phase: {}", checkpoint.phase());
+    println!("  Sessions: {}", checkpoint.sessions.len());
+    println!("  Decided moves: {}", checkpoint.decided_moves.len());
+    let wizard = BrennerWizard::resume(checkpoint, config).with_checkpoint_dir(dir);
+    drive_wizard(ctx, args, wizard, tracker)
+}
+
+/// Extraction workers when `--jobs` is not given: one per core.
+fn default_jobs() -> usize {
+    std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get)
+}
+
 /// Run automatic build (no user interaction)
+/// A session to consider for mining: a CASS search hit fetched lazily, or a
+/// transcript already loaded from `--from-file`.
+enum SessionCandidate {
+    Cass(crate::cass::SessionMatch),
+    File(Box<crate::cass::Session>),
+}
+
 fn run_auto(
     ctx: &AppContext,
     args: &BuildArgs,
@@ -957,13 +1238,40 @@ fn run_auto(
     tracker: Option<BeadsTracker>,
     query_override: Option<&str>,
 ) -> Result<()> {
-    use crate::cass::QualityConfig;
-    use crate::cass::mining::{ExtractedPattern, extract_from_session};
+    let progress = ctx.progress();
+    let result = run_auto_phases(ctx, args, cm_context, tracker, query_override, &progress);
+    progress.settle(&result);
+    result
+}
+
+fn run_auto_phases(
+    ctx: &AppContext,
+    args: &BuildArgs,
+    cm_context: Option<&CmBuildContext>,
+    tracker: Option<BeadsTracker>,
+    query_override: Option<&str>,
+    progress: &PhaseProgress,
+) -> Result<()> {
+    use crate::cass::mining::{ExtractedPattern, extract_from_sessions};
+    use crate::cass::skill_id::{MinedIdRegistry, cluster_fingerprint, dedupe_by_fingerprint};
+
+    // Transcript files replace the CASS search; load them up front so a bad
+    // file fails before any work starts.
+    let mut file_sessions = Vec::new();
+    for path in &args.from_file {
+        let (cass_session, format) = load_session_file(path)?;
+        file_sessions.push((path.display().to_string(), format, cass_session));
+    }
 
     // Use query_override (from checkpoint resume) or fall back to args.from_cass
     let query = query_override
         .map(std::string::ToString::to_string)
         .or_else(|| args.from_cass.clone())
+        .or_else(|| {
+            file_sessions
+                .first()
+                .map(|_| args.name.clone().unwrap_or_else(|| "from-file".to_string()))
+        })
         .ok_or_else(|| MsError::Config("--from-cass is required for --auto builds".into()))?;
 
     let output_dir = args.output.clone().unwrap_or_else(|| {
@@ -979,10 +1287,15 @@ fn run_auto(
     fs::create_dir_all(&output_dir)?;
 
     // Initialize BuildSession with quality gates
+    let min_session_quality = args.min_session_quality(&ctx.config);
     let gates = QualityGates {
-        min_session_quality: args.min_session_quality,
+        min_session_quality,
         min_pattern_confidence: args.min_confidence,
-        min_sessions: args.min_sessions.unwrap_or(3),
+        min_sessions: args.min_sessions.unwrap_or(if file_sessions.is_empty() {
+            3
+        } else {
+            file_sessions.len().min(3)
+        }),
         min_patterns: args.min_patterns.unwrap_or(5),
     };
 
@@ -1012,6 +1325,14 @@ fn run_auto(
             "duration": args.duration,
             "output_dir": output_dir.display().to_string(),
             "cm_available": cm_context.is_some(),
+            "session_files": file_sessions
+                .iter()
+                .map(|(path, format, s)| json!({
+                    "path": path,
+                    "format": format,
+                    "messages": s.messages.len(),
+                }))
+                .collect::<Vec<_>>(),
         });
         println!("{}", serde_json::to_string_pretty(&output)?);
     } else {
@@ -1029,17 +1350,20 @@ fn run_auto(
         if let Some(cm_ctx) = cm_context {
             println!("  CM rules: {}", cm_ctx.seed_rules.len());
         }
+        for (path, format, s) in &file_sessions {
+            println!(
+                "  Transcript: {path} ({}, {} messages)",
+                format.description(),
+                s.messages.len()
+            );
+        }
     }
 
     // Create CASS client and quality scorer
-    let cass_client = if let Some(ref cass_path) = ctx.config.cass.cass_path {
-        CassClient::with_binary(cass_path)
-    } else {
-        CassClient::new()
-    };
+    let cass_client = cass_client(ctx, None, args.no_cache);
 
     let quality_config = QualityConfig {
-        min_score: args.min_session_quality,
+        min_score: min_session_quality,
         ..Default::default()
     };
     let quality_scorer = QualityScorer::new(quality_config.clone());
@@ -1048,8 +1372,13 @@ fn run_auto(
     // Phase 1: Search CASS for sessions
     // =========================================================================
     debug!(target: "build", stage = "search_sessions", "stage start");
+    progress.phase("search_sessions", None);
     if ctx.output_format == OutputFormat::Human {
-        println!("\n{} Searching CASS...", "Phase 1:");
+        if file_sessions.is_empty() {
+            println!("\n{} Searching CASS...", "Phase 1:");
+        } else {
+            println!("\n{} Using transcript files...", "Phase 1:");
+        }
     }
 
     // Check for timeout before starting phase
@@ -1060,8 +1389,21 @@ fn run_auto(
         return output_timeout(ctx, &mut session, &output_dir);
     }
 
-    let search_limit = args.sessions * 3;
-    let session_matches = cass_client.search(&query, search_limit)?;
+    let (search_limit, session_matches): (usize, Vec<SessionCandidate>) =
+        if file_sessions.is_empty() {
+            let search_limit = args.sessions * 3;
+            let matches = cass_client.search(&query, search_limit)?;
+            (
+                search_limit,
+                matches.into_iter().map(SessionCandidate::Cass).collect(),
+            )
+        } else {
+            let candidates: Vec<_> = file_sessions
+                .into_iter()
+                .map(|(_, _, s)| SessionCandidate::File(Box::new(s)))
+                .collect();
+            (candidates.len(), candidates)
+        };
 
     session.phase_progress = 1.0;
     session.advance_phase(); // -> QualityFilter
@@ -1102,34 +1444,52 @@ fn run_auto(
 
     let mut quality_sessions = Vec::new();
     let mut skipped_sessions = Vec::new();
+    let mut included_low_quality = Vec::new();
     let total_to_process = session_matches.len().min(search_limit);
+    progress.phase("quality_filter", Some(total_to_process as u64));
 
-    for (i, session_match) in session_matches.into_iter().take(search_limit).enumerate() {
+    let session_limit = if args.from_file.is_empty() {
+        args.sessions
+    } else {
+        total_to_process
+    };
+    for (i, candidate) in session_matches.into_iter().take(search_limit).enumerate() {
         // Update phase progress
         session.phase_progress = (i + 1) as f64 / total_to_process as f64;
+        progress.update((i + 1) as u64, total_to_process as u64, None);
 
-        match cass_client.get_session(&session_match.path) {
+        let (session_id, fetched) = match candidate {
+            SessionCandidate::Cass(session_match) => {
+                let fetched = cass_client.get_session(&session_match.path);
+                (session_match.session_id, fetched)
+            }
+            SessionCandidate::File(loaded) => (loaded.id.clone(), Ok(*loaded)),
+        };
+        match fetched {
             Ok(cass_session) => {
                 let quality = quality_scorer.score(&cass_session);
-                if quality.passes_threshold(&quality_config) {
-                    quality_sessions.push((cass_session, quality));
-                    session
-                        .state
-                        .qualified_session_ids
-                        .push(session_match.session_id.clone());
-                    if quality_sessions.len() >= args.sessions {
-                        break;
+                match gate_session(
+                    &session_id,
+                    &quality,
+                    &quality_config,
+                    args.include_low_quality,
+                ) {
+                    SessionGate::Skipped(low) => skipped_sessions.push(low),
+                    gate => {
+                        if let SessionGate::Included(low) = gate {
+                            included_low_quality.push(low);
+                        }
+                        quality_sessions.push((cass_session, quality));
+                        session.state.qualified_session_ids.push(session_id);
+                        if quality_sessions.len() >= session_limit {
+                            break;
+                        }
                     }
-                } else {
-                    skipped_sessions.push((session_match.session_id, quality.score));
                 }
             }
             Err(e) => {
                 if ctx.output_format == OutputFormat::Human {
-                    eprintln!(
-                        "  Warning: Failed to fetch session {}: {}",
-                        session_match.session_id, e
-                    );
+                    eprintln!("  Warning: Failed to fetch session {session_id}: {e}");
                 }
             }
         }
@@ -1145,6 +1505,7 @@ fn run_auto(
 
     session.phase_progress = 1.0;
     session.advance_phase(); // -> ExtractPatterns
+    progress.finish();
 
     if quality_sessions.is_empty() {
         if let Some(t) = &tracker {
@@ -1155,7 +1516,7 @@ fn run_auto(
             &session,
             &query,
             &skipped_sessions,
-            args.min_session_quality,
+            min_session_quality,
         );
     }
 
@@ -1163,11 +1524,23 @@ fn run_auto(
         println!(
             "  {} sessions passed quality threshold (min: {:.0}%)",
             quality_sessions.len(),
-            args.min_session_quality * 100.0
+            min_session_quality * 100.0
         );
         for (s, q) in &quality_sessions {
-            println!("    - {} ({:.0}%)", s.id, q.score * 100.0);
+            if included_low_quality
+                .iter()
+                .any(|low| low.session_id == s.id)
+            {
+                println!(
+                    "    - {} ({:.0}%, below threshold, --include-low-quality)",
+                    s.id,
+                    q.score * 100.0
+                );
+            } else {
+                println!("    - {} ({:.0}%)", s.id, q.score * 100.0);
+            }
         }
+        print_low_quality_sessions(&skipped_sessions);
     }
 
     // Save checkpoint if interval elapsed
@@ -1193,16 +1566,24 @@ fn run_auto(
         return output_timeout(ctx, &mut session, &output_dir);
     }
 
-    let mut all_patterns: Vec<ExtractedPattern> = Vec::new();
-    let total_sessions = quality_sessions.len();
-
-    for (i, (cass_session, _quality)) in quality_sessions.iter().enumerate() {
-        session.phase_progress = (i + 1) as f64 / total_sessions as f64;
+    let jobs = args.jobs.unwrap_or_else(default_jobs);
+    let sessions_to_mine: Vec<_> = quality_sessions.iter().map(|(s, _)| s).collect();
+    progress.phase("extract_patterns", Some(sessions_to_mine.len() as u64));
+    let extractions = extract_from_sessions(&sessions_to_mine, jobs, |done, total| {
+        progress.update(done as u64, total as u64, None);
+    })?;
+    progress.finish();
 
-        match extract_from_session(cass_session) {
+    let mut all_patterns: Vec<ExtractedPattern> = Vec::new();
+    for extraction in extractions {
+        match extraction.result {
             Ok(patterns) => {
                 if ctx.output_format == OutputFormat::Human && !patterns.is_empty() {
-                    println!("  {} patterns from {}", patterns.len(), cass_session.id);
+                    println!(
+                        "  {} patterns from {}",
+                        patterns.len(),
+                        extraction.session_id
+                    );
                 }
                 session.state.patterns_extracted += patterns.len();
                 all_patterns.extend(patterns);
@@ -1211,20 +1592,25 @@ fn run_auto(
                 if ctx.output_format == OutputFormat::Human {
                     eprintln!(
                         "  Warning: Failed to extract from {}: {}",
-                        cass_session.id, e
+                        extraction.session_id, e
                     );
                 }
             }
         }
+    }
+    session.phase_progress = 1.0;
 
-        if session.is_timed_out() {
-            if let Some(t) = &tracker {
-                t.on_failure("Build timed out during pattern extraction loop")?;
-            }
-            return output_timeout(ctx, &mut session, &output_dir);
+    if session.is_timed_out() {
+        if let Some(t) = &tracker {
+            t.on_failure("Build timed out during pattern extraction")?;
         }
+        return output_timeout(ctx, &mut session, &output_dir);
     }
 
+    // Pattern ids are content fingerprints, so the same pattern seen in
+    // several sessions collapses into one entry with combined evidence.
+    let all_patterns = dedupe_by_fingerprint(all_patterns);
+
     session.phase_progress = 1.0;
     session.advance_phase(); // -> FilterPatterns
 
@@ -1243,6 +1629,7 @@ fn run_auto(
     // Phase 4: Filter patterns
     // =========================================================================
     debug!(target: "build", stage = "filter_patterns", "stage start");
+    progress.phase("filter_patterns", None);
     if ctx.output_format == OutputFormat::Human {
         println!("\n{} Filtering by confidence...", "Phase 4:");
     }
@@ -1254,10 +1641,10 @@ fn run_auto(
         return output_timeout(ctx, &mut session, &output_dir);
     }
 
-    let high_confidence_patterns: Vec<_> = all_patterns
+    let (high_confidence_patterns, near_misses): (Vec<_>, Vec<_>) = all_patterns
         .into_iter()
-        .filter(|p| p.confidence >= args.min_confidence)
-        .collect();
+        .partition(|p| p.confidence >= args.min_confidence);
+    let queued_for_review = queue_near_misses(ctx, args, &near_misses)?;
 
     session.phase_progress = 0.5;
 
@@ -1267,6 +1654,9 @@ fn run_auto(
             high_confidence_patterns.len(),
             args.min_confidence * 100.0
         );
+        if queued_for_review > 0 {
+            println!("  {queued_for_review} near misses queued for review (ms review list)");
+        }
     }
 
     // Filter out tainted patterns (unless --no-injection-filter)
@@ -1303,6 +1693,7 @@ fn run_auto(
     // Phase 5: Synthesize (write outputs)
     // =========================================================================
     debug!(target: "build", stage = "synthesize", "stage start");
+    progress.phase("synthesize", None);
     if ctx.output_format == OutputFormat::Human {
         println!("\n{} Writing outputs...", "Phase 5:");
     }
@@ -1314,6 +1705,27 @@ fn run_auto(
         return output_timeout(ctx, &mut session, &output_dir);
     }
 
+    // Assign a deterministic skill id from the pattern cluster fingerprint so
+    // re-mining the same workflow maps onto the previously built skill.
+    let skill_name = args.name.clone().unwrap_or_else(|| query.clone());
+    let fingerprint = cluster_fingerprint(&filtered_patterns);
+    let legacy_ids: Vec<String> = filtered_patterns
+        .iter()
+        .flat_map(ExtractedPattern::legacy_ids)
+        .collect();
+    let (skill_id, id_registry) = MinedIdRegistry::update(&ctx.ms_root, |registry| {
+        let skill_id = registry.assign(&skill_name, &fingerprint, &legacy_ids);
+        (skill_id, registry.clone())
+    })?;
+    let aliases_migrated = id_registry.migrate_aliases(&ctx.db)?;
+    debug!(
+        target: "build",
+        skill_id = %skill_id.id,
+        existing = skill_id.existing,
+        aliases_migrated,
+        "assigned mined skill id"
+    );
+
     // Write patterns JSON
     let patterns_path = output_dir.join("patterns.json");
     let patterns_json = serde_json::to_string_pretty(&filtered_patterns)?;
@@ -1331,14 +1743,22 @@ fn run_auto(
         "session_id": session.session_id,
         "query": query,
         "build_type": "auto",
+        "skill_id": skill_id.id,
+        "skill_name": skill_name,
+        "fingerprint": skill_id.fingerprint,
+        "updated_existing": skill_id.existing,
+        "legacy_ids": skill_id.legacy_ids,
         "sessions_used": quality_sessions.iter().map(|(s, q)| json!({
             "id": s.id,
             "quality_score": q.score,
         })).collect::<Vec<_>>(),
         "patterns_extracted": filtered_patterns.len(),
+        "sessions_skipped": skipped_sessions,
+        "sessions_below_threshold": included_low_quality,
         "quality_gates": {
             "min_confidence": args.min_confidence,
-            "min_session_quality": args.min_session_quality,
+            "min_session_quality": min_session_quality,
+            "include_low_quality": args.include_low_quality,
             "min_sessions": session.gates.min_sessions,
             "min_patterns": session.gates.min_patterns,
         },
@@ -1380,8 +1800,13 @@ fn run_auto(
             "status": "complete",
             "session_id": session.session_id,
             "query": query,
+            "skill_id": skill_id.id,
+            "updated_existing": skill_id.existing,
             "sessions_used": quality_sessions.len(),
+            "sessions_skipped": skipped_sessions,
+            "sessions_below_threshold": included_low_quality,
             "patterns_extracted": filtered_patterns.len(),
+            "queued_for_review": queued_for_review,
             "progress": session.overall_progress(),
             "elapsed_ms": session.started_at.elapsed().as_millis(),
             "output_dir": output_dir.display().to_string(),
@@ -1392,7 +1817,19 @@ fn run_auto(
     } else {
         println!("\n{} Auto build complete!", "Success:");
         println!("  Session: {}", session.session_id);
+        if skill_id.existing {
+            println!("  Skill id: {} (matches previous build)", skill_id.id);
+        } else {
+            println!("  Skill id: {}", skill_id.id);
+        }
         println!("  Sessions processed: {}", quality_sessions.len());
+        if !skipped_sessions.is_empty() {
+            println!(
+                "  Sessions skipped: {} (below {:.0}%; --min-quality or --include-low-quality to keep)",
+                skipped_sessions.len(),
+                min_session_quality * 100.0
+            );
+        }
         println!("  Patterns extracted: {}", filtered_patterns.len());
         println!("  Output directory: {}", output_dir.display());
     }
@@ -1404,6 +1841,65 @@ fn run_auto(
     Ok(())
 }
 
+/// Near misses the generalizer takes a second look at per build.
+const MAX_NEAR_MISSES: usize = 10;
+
+/// Run the best patterns that missed the confidence threshold through the
+/// specific-to-general transformer. Generalizations it cannot confirm land
+/// in the review queue (`ms review`); returns how many items that added.
+fn queue_near_misses(
+    ctx: &AppContext,
+    args: &BuildArgs,
+    near_misses: &[crate::cass::mining::ExtractedPattern],
+) -> Result<usize> {
+    use crate::cass::{
+        CommandRefiner, PersistentUncertaintyQueue, SpecificInstance, SpecificToGeneralTransformer,
+        TransformerConfig, UncertaintyConfig, UncertaintyStore,
+    };
+
+    let client = cass_client(ctx, None, args.no_cache);
+    if near_misses.is_empty() || !client.is_available() {
+        return Ok(0);
+    }
+
+    let store = UncertaintyStore::new(&ctx.db);
+    let before = store.open()?.len();
+    let queue = PersistentUncertaintyQueue::new(
+        ctx.db_path(),
+        UncertaintyConfig {
+            min_confidence: args.min_confidence,
+            expiry_seconds: ctx.config.review.expiry().as_secs(),
+            ..UncertaintyConfig::default()
+        },
+    );
+    let mut transformer = SpecificToGeneralTransformer::with_config(
+        client,
+        TransformerConfig {
+            confidence_threshold: args.min_confidence,
+            ..TransformerConfig::default()
+        },
+    )
+    .with_uncertainty_queue(Box::new(queue));
+    if let Some(refiner) = CommandRefiner::from_config(&ctx.config.build.refiner) {
+        transformer = transformer.with_refiner(Box::new(refiner));
+    }
+
+    let mut candidates: Vec<_> = near_misses.iter().collect();
+    candidates.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+    for pattern in candidates.into_iter().take(MAX_NEAR_MISSES) {
+        match transformer.transform(&SpecificInstance::from_pattern(pattern)) {
+            Ok(general) => {
+                debug!(target: "build", pattern = %pattern.id, confidence = general.confidence, "near miss generalized");
+            }
+            Err(err) => {
+                debug!(target: "build", pattern = %pattern.id, error = %err, "near miss not generalized");
+            }
+        }
+    }
+
+    Ok(store.open()?.len().saturating_sub(before))
+}
+
 /// Output helper for timeout condition.
 fn output_timeout(
     ctx: &AppContext,
@@ -1457,7 +1953,7 @@ fn output_no_quality(
     ctx: &AppContext,
     session: &BuildSession,
     query: &str,
-    skipped: &[(String, f32)],
+    skipped: &[LowQualitySession],
     min_quality: f32,
 ) -> Result<()> {
     if ctx.output_format != OutputFormat::Human {
@@ -1466,6 +1962,7 @@ fn output_no_quality(
             "session_id": session.session_id,
             "query": query,
             "skipped": skipped.len(),
+            "sessions_skipped": skipped,
             "min_quality": min_quality,
             "message": "No sessions passed quality threshold"
         });
@@ -1476,16 +1973,33 @@ fn output_no_quality(
             "Error:",
             min_quality * 100.0
         );
+        print_low_quality_sessions(skipped);
         if !skipped.is_empty() {
-            println!("  {} sessions were below threshold:", skipped.len());
-            for (id, score) in skipped.iter().take(5) {
-                println!("    - {} ({:.0}%)", id, score * 100.0);
-            }
+            println!("  Lower the bar with --min-quality or keep them with --include-low-quality");
         }
     }
     Ok(())
 }
 
+/// Print skipped sessions with the reasons they fell short.
+fn print_low_quality_sessions(skipped: &[LowQualitySession]) {
+    if skipped.is_empty() {
+        return;
+    }
+    println!("  {} sessions were below threshold:", skipped.len());
+    for low in skipped.iter().take(5) {
+        println!(
+            "    - {} ({:.0}%) skipped: {}",
+            low.session_id,
+            low.score * 100.0,
+            low.reasons.join(", ")
+        );
+    }
+    if skipped.len() > 5 {
+        println!("    ... and {} more", skipped.len() - 5);
+    }
+}
+
 /// Output helper for no patterns extracted.
 fn output_no_patterns(
     ctx: &AppContext,
@@ -1596,6 +2110,13 @@ fn run_resume(
 ) -> Result<()> {
     use crate::core::recovery::Checkpoint;
 
+    // Brenner wizard checkpoints are keyed by id or selection hash
+    if let Some((path, checkpoint)) =
+        WizardCheckpoint::find(&wizard_checkpoint_dir(&ctx.ms_root), session_id)?
+    {
+        return resume_wizard(ctx, args, path, checkpoint, tracker);
+    }
+
     // Try to load checkpoint
     let checkpoint = if let Some(cp) = Checkpoint::load(&ctx.ms_root, session_id)? {
         cp
@@ -1736,11 +2257,13 @@ fn run_resume(
 
 /// Resolve pending uncertainties
 fn run_resolve_uncertainties(ctx: &AppContext, args: &BuildArgs) -> Result<()> {
-    use crate::cass::{DefaultResolver, UncertaintyResolver, UncertaintyStatus};
+    use crate::cass::{DefaultResolver, UncertaintyResolver, UncertaintyStatus, UncertaintyStore};
 
-    // Load uncertainty queue from file or create new
-    let uncertainties_path = ctx.ms_root.join(".ms").join("uncertainties.json");
-    let (_queue, items) = load_uncertainties(&uncertainties_path)?;
+    // Items queued by earlier builds; expire the stale ones first
+    let uncertainties_path = ctx.db_path();
+    let store = UncertaintyStore::new(&ctx.db);
+    store.age_out(ctx.config.review.expiry())?;
+    let items = store.list(None)?;
 
     // Get counts
     let counts = count_uncertainties(&items);
@@ -1857,11 +2380,7 @@ fn run_resolve_uncertainties(ctx: &AppContext, args: &BuildArgs) -> Result<()> {
         }
 
         let resolver = DefaultResolver::new(args.min_confidence, 5);
-        let cass_client = if let Some(ref cass_path) = ctx.config.cass.cass_path {
-            CassClient::with_binary(cass_path)
-        } else {
-            CassClient::new()
-        };
+        let cass_client = cass_client(ctx, None, args.no_cache);
 
         let mut resolved_count = 0;
         let mut escalated_count = 0;
@@ -1993,7 +2512,9 @@ fn run_resolve_uncertainties(ctx: &AppContext, args: &BuildArgs) -> Result<()> {
         }
 
         // Save updated uncertainties
-        save_uncertainties(&uncertainties_path, &updated_items)?;
+        for item in &updated_items {
+            store.save(item)?;
+        }
 
         // Summary
         if ctx.output_format != OutputFormat::Human {
@@ -2058,45 +2579,9 @@ fn run_resolve_uncertainties(ctx: &AppContext, args: &BuildArgs) -> Result<()> {
         // Interactive mode hint (non-auto, non-robot)
         println!("{}", "Options:");
         println!("  Run with --auto to attempt automatic resolution");
-        println!("  Use: ms uncertainties resolve <id> for manual resolution");
-    }
-
-    Ok(())
-}
-
-/// Load uncertainties from JSON file
-fn load_uncertainties(
-    path: &std::path::Path,
-) -> Result<(
-    crate::cass::UncertaintyQueue,
-    Vec<crate::cass::UncertaintyItem>,
-)> {
-    use crate::cass::{UncertaintyConfig, UncertaintyItem, UncertaintyQueue};
-
-    let queue = UncertaintyQueue::new(UncertaintyConfig::default());
-
-    if path.exists() {
-        let content = fs::read_to_string(path)?;
-        let items: Vec<UncertaintyItem> = serde_json::from_str(&content)
-            .map_err(|e| MsError::Config(format!("Failed to parse uncertainties file: {e}")))?;
-        Ok((queue, items))
-    } else {
-        Ok((queue, Vec::new()))
-    }
-}
-
-/// Save uncertainties to JSON file
-fn save_uncertainties(
-    path: &std::path::Path,
-    items: &[crate::cass::UncertaintyItem],
-) -> Result<()> {
-    // Ensure parent directory exists
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent)?;
+        println!("  Use: ms review resolve <id> for manual resolution");
     }
 
-    let content = serde_json::to_string_pretty(items)?;
-    fs::write(path, content)?;
     Ok(())
 }
 
@@ -2121,7 +2606,7 @@ fn count_uncertainties(items: &[crate::cass::UncertaintyItem]) -> crate::cass::U
 }
 
 /// Format uncertainty reason for display
-fn format_uncertainty_reason(reason: &crate::cass::UncertaintyReason) -> String {
+pub(crate) fn format_uncertainty_reason(reason: &crate::cass::UncertaintyReason) -> String {
     use crate::cass::UncertaintyReason;
 
     match reason {
@@ -2194,6 +2679,52 @@ fn terminal_width() -> usize {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_require_cass_names_binary_and_alternative() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("cass");
+        let err = require_cass(&CassClient::with_binary(&missing)).unwrap_err();
+        assert!(matches!(err, MsError::CassUnavailable(_)));
+        let message = err.to_string();
+        assert!(
+            message.contains(&format!("CASS not found at {}", missing.display())),
+            "{message}"
+        );
+        assert!(message.ends_with("or pass --from-file"), "{message}");
+    }
+
+    #[test]
+    fn test_session_without_validation_is_gated() {
+        use crate::cass::mining::{SessionPhase, segment_session};
+
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/sessions/no_validation.json");
+        let (session, _) = load_session_file(&path).unwrap();
+        assert!(
+            !segment_session(&session)
+                .segments
+                .iter()
+                .any(|segment| segment.phase == SessionPhase::Validation)
+        );
+
+        let config = QualityConfig {
+            min_score: Config::default().build.min_session_quality,
+            ..Default::default()
+        };
+        let quality = QualityScorer::new(config.clone()).score(&session);
+        match gate_session(&session.id, &quality, &config, false) {
+            SessionGate::Skipped(low) => {
+                assert!(low.missing.contains(&MissingSignal::NoTestsPassed));
+                assert!(low.reasons.contains(&"no validation phase".to_string()));
+            }
+            other => panic!("expected skip, got {other:?}"),
+        }
+        assert!(matches!(
+            gate_session(&session.id, &quality, &config, true),
+            SessionGate::Included(_)
+        ));
+    }
+
     // =========================================================================
     // BuildPhase Tests
     // =========================================================================
@@ -4,21 +4,45 @@ use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 use clap::{Args, Subcommand};
+use ms_search_core::ParsedQuery;
 use semver::Version;
 use serde::Serialize;
 
 use crate::app::AppContext;
 use crate::bundler::github::{GitHubConfig, download_bundle, download_url, publish_bundle};
-use crate::bundler::install::InstallReport;
+use crate::bundler::install::{
+    DependencyCheck, DependencyStatus, InstallReport, check_dependencies, resolve_dependencies,
+};
 use crate::bundler::local_safety::{
-    ModificationStatus, SkillModificationReport, backup_file, detect_modifications, hash_bytes,
+    ConflictDetail, ConflictStrategy, ModificationStatus, SkillModificationReport, backup_file,
+    detect_conflicts, detect_modifications, hash_bytes, hash_directory,
 };
+use crate::bundler::pinning::{KeyCheck, PinnedKey, accept_new_key, check_signing_key};
 use crate::bundler::registry::{BundleRegistry, InstallSource, InstalledBundle, ParsedSource};
-use crate::bundler::{Bundle, BundleInfo, BundleManifest, BundlePackage, BundledSkill};
+use crate::bundler::remote::{self, RemoteOptions};
+use crate::bundler::{
+    BlobStore, Bundle, BundleDependency, BundleInfo, BundleManifest, BundlePackage,
+    BundledResource, BundledSkill, DependencyClosure, Ed25519Verifier, SkillDependency,
+    SkillReleaseNotes, dependency_closure,
+};
+use crate::cli::commands::expand_path;
+use crate::cli::commands::search::bm25_ranked;
 use crate::cli::output::OutputFormat;
 use crate::cli::output::emit_json;
+use crate::config::BundlesConfig;
+use crate::core::changelog::{ChangelogSince, build_changelog, group_by_version};
+use crate::core::resolution::{GitSkillRepository, SkillRepository};
+use crate::core::resources::collect_resources;
 use crate::error::{MsError, Result};
-use crate::storage::GlobalLock;
+use crate::lint::{ValidationEngine, structural_rules};
+use crate::search::{SearchFilters, matches_skill_record};
+use crate::storage::sqlite::SkillResourceRecord;
+use crate::storage::{GlobalLock, Provenance, lock_timeout};
+use crate::utils::format::format_size;
+use crate::utils::path::looks_like_path;
+
+/// Skills `--from-query` considers before applying its filters.
+const MAX_QUERY_CANDIDATES: usize = 10_000;
 
 #[derive(Args, Debug)]
 pub struct BundleArgs {
@@ -47,9 +71,15 @@ pub enum BundleCommand {
 }
 
 #[derive(Args, Debug)]
+#[command(disable_version_flag = true)]
 pub struct BundleCreateArgs {
     /// Bundle name
-    pub name: String,
+    #[arg(required_unless_present = "name_flag")]
+    pub name: Option<String>,
+
+    /// Bundle name (same as the positional NAME)
+    #[arg(long = "name", value_name = "NAME", conflicts_with = "name")]
+    pub name_flag: Option<String>,
 
     /// Skills to include (by ID)
     #[arg(long)]
@@ -59,12 +89,29 @@ pub struct BundleCreateArgs {
     #[arg(long, conflicts_with = "skills")]
     pub from_dir: Option<PathBuf>,
 
+    /// Select indexed skills by query, e.g. "tag:rust layer:project"
+    #[arg(long, conflicts_with_all = ["skills", "from_dir"])]
+    pub from_query: Option<String>,
+
+    /// Fail if selected skills extend or include skills outside the
+    /// selection instead of adding them
+    #[arg(long, requires = "from_query")]
+    pub no_deps: bool,
+
+    /// Include skills marked sensitive
+    #[arg(long, requires = "from_query")]
+    pub allow_sensitive: bool,
+
     /// Bundle id (defaults to slug of name)
     #[arg(long)]
     pub id: Option<String>,
 
     /// Bundle version
-    #[arg(long = "bundle-version", default_value = "0.1.0")]
+    #[arg(
+        long = "bundle-version",
+        visible_alias = "version",
+        default_value = "0.1.0"
+    )]
     pub bundle_version: String,
 
     /// Output path for bundle file (.msb or .tar.gz)
@@ -82,6 +129,12 @@ pub struct BundleCreateArgs {
     /// Path to SSH private key for signing
     #[arg(long, requires = "sign")]
     pub sign_key: Option<PathBuf>,
+
+    /// Embed each skill's changelog since an earlier bundle file (using the
+    /// skill versions it shipped), a version (v1.2.0), an age (30d), or a
+    /// date (2026-03-01)
+    #[arg(long, value_name = "SINCE")]
+    pub changelog_since: Option<String>,
 }
 
 #[derive(Args, Debug)]
@@ -116,7 +169,7 @@ pub struct BundlePublishArgs {
 
 #[derive(Args, Debug)]
 pub struct BundleInstallArgs {
-    /// Bundle source (path or URL)
+    /// Bundle source (path, GitHub repo, or http(s) URL)
     pub source: String,
 
     /// Skills to install (defaults to all)
@@ -142,6 +195,10 @@ pub struct BundleInstallArgs {
     /// Force reinstallation if bundle is already installed
     #[arg(long, short = 'f')]
     pub force: bool,
+
+    /// Fetch and install missing dependencies that declare a source
+    #[arg(long)]
+    pub with_deps: bool,
 }
 
 #[derive(Args, Debug)]
@@ -238,20 +295,30 @@ pub fn run(_ctx: &AppContext, _args: &BundleArgs) -> Result<()> {
 }
 
 fn run_create(ctx: &AppContext, args: &BundleCreateArgs) -> Result<()> {
-    // Discover skills from --skills list or --from-dir directory
+    let name = args
+        .name
+        .clone()
+        .or_else(|| args.name_flag.clone())
+        .unwrap_or_default();
+
+    // Discover skills from --skills list, --from-dir directory or --from-query
+    let mut selection = QuerySelection::default();
     let skills = if let Some(ref from_dir) = args.from_dir {
         discover_skills_in_dir(from_dir)?
+    } else if let Some(ref query) = args.from_query {
+        selection = select_by_query(ctx, query, args)?;
+        selection.skills.clone()
     } else {
         normalize_skill_list(&args.skills)
     };
 
     if skills.is_empty() {
         return Err(MsError::ValidationFailed(
-            "bundle create requires --skills or --from-dir".to_string(),
+            "bundle create requires --skills, --from-dir or --from-query".to_string(),
         ));
     }
 
-    let bundle_id = args.id.clone().unwrap_or_else(|| slugify(&args.name));
+    let bundle_id = args.id.clone().unwrap_or_else(|| slugify(&name));
     let root = if let Some(ref dir) = args.from_dir {
         dir.canonicalize().unwrap_or(dir.clone())
     } else {
@@ -261,6 +328,12 @@ fn run_create(ctx: &AppContext, args: &BundleCreateArgs) -> Result<()> {
             .unwrap_or(ctx.git.root().to_path_buf())
     };
 
+    let changelog_since = args
+        .changelog_since
+        .as_deref()
+        .map(ChangelogBaseline::parse)
+        .transpose()?;
+
     let mut entries = Vec::new();
     for skill_id in skills {
         // Resolve skill directory: check root first (handle --from-dir), then archive
@@ -305,19 +378,26 @@ fn run_create(ctx: &AppContext, args: &BundleCreateArgs) -> Result<()> {
             }
         });
 
+        let changelog = match &changelog_since {
+            Some(baseline) => skill_changelog(ctx, &skill_id, baseline)?,
+            None => Vec::new(),
+        };
+
         entries.push(BundledSkill {
             name: skill_id,
             path: rel,
             version,
             hash: None,
             optional: false,
+            changelog,
+            resources: bundled_resources(&skill_dir)?,
         });
     }
 
     let manifest = BundleManifest {
         bundle: BundleInfo {
             id: bundle_id.clone(),
-            name: args.name.clone(),
+            name,
             version: args.bundle_version.clone(),
             description: None,
             authors: Vec::new(),
@@ -339,7 +419,13 @@ fn run_create(ctx: &AppContext, args: &BundleCreateArgs) -> Result<()> {
 
     // Sign the bundle if requested
     if args.sign {
-        let key_path = if let Some(path) = args.sign_key.clone() {
+        let key_path = if let Some(path) = args.sign_key.clone().or_else(|| {
+            ctx.config
+                .bundles
+                .signing_key
+                .as_deref()
+                .map(|path| expand_path(&path.to_string_lossy()))
+        }) {
             path
         } else {
             let home = dirs::home_dir().ok_or_else(|| {
@@ -381,6 +467,8 @@ fn run_create(ctx: &AppContext, args: &BundleCreateArgs) -> Result<()> {
             output.display()
         )));
     }
+    let blobs_written =
+        package.write_missing_blobs(&BlobStore::open(ctx.git.root().join("bundles"))?)?;
     let bytes = package.to_bytes()?;
     std::fs::write(&output, bytes)
         .map_err(|err| MsError::Config(format!("write {}: {err}", output.display())))?;
@@ -403,17 +491,72 @@ fn run_create(ctx: &AppContext, args: &BundleCreateArgs) -> Result<()> {
         manifest_path = Some(path);
     }
 
+    let sizes: HashMap<&str, u64> = package
+        .blobs
+        .iter()
+        .map(|blob| (blob.hash.as_str(), blob.bytes.len() as u64))
+        .collect();
+    let included: Vec<BundleCreateSkill> = package
+        .manifest
+        .skills
+        .iter()
+        .map(|skill| BundleCreateSkill {
+            id: skill.name.clone(),
+            version: skill.version.clone(),
+            size: skill
+                .hash
+                .as_deref()
+                .and_then(|hash| sizes.get(hash).copied())
+                .unwrap_or(0),
+            required_by: selection
+                .dependencies
+                .iter()
+                .find(|dep| dep.skill_id == skill.name)
+                .map(|dep| dep.required_by.clone()),
+        })
+        .collect();
+    let total_size = included.iter().map(|skill| skill.size).sum();
+
     if ctx.output_format != OutputFormat::Human {
         let report = BundleCreateReport {
             id: bundle_id,
             output: output.display().to_string(),
             manifest_path: manifest_path.map(|p| p.display().to_string()),
             checksum: package.manifest.checksum.clone(),
+            skills: included,
+            total_size,
+            blobs_written,
+            warnings: selection.warnings,
         };
         return emit_json(&report);
     }
 
+    for warning in &selection.warnings {
+        eprintln!("warning: {warning}");
+    }
     println!("Bundle created: {}", output.display());
+    for skill in &included {
+        println!(
+            "  - {}{} ({}){}",
+            skill.id,
+            skill
+                .version
+                .as_deref()
+                .map(|version| format!(" v{version}"))
+                .unwrap_or_default(),
+            format_size(skill.size),
+            skill
+                .required_by
+                .as_deref()
+                .map(|parent| format!("  [dependency of {parent}]"))
+                .unwrap_or_default()
+        );
+    }
+    println!(
+        "{} skill(s), {} total",
+        included.len(),
+        format_size(total_size)
+    );
     if let Some(path) = manifest_path {
         println!("Manifest written: {}", path.display());
     }
@@ -421,9 +564,115 @@ fn run_create(ctx: &AppContext, args: &BundleCreateArgs) -> Result<()> {
     Ok(())
 }
 
+/// Skills picked by `--from-query`, plus what the CLI should report.
+#[derive(Debug, Default)]
+struct QuerySelection {
+    /// Matching skills, then the dependencies they pulled in.
+    skills: Vec<String>,
+    dependencies: Vec<SkillDependency>,
+    warnings: Vec<String>,
+}
+
+/// Select indexed skills with the search filter syntax, close the selection
+/// over `extends`/`includes`, and vet it: sensitive skills need
+/// `--allow-sensitive`, structural lint errors are warnings.
+fn select_by_query(
+    ctx: &AppContext,
+    query: &str,
+    args: &BundleCreateArgs,
+) -> Result<QuerySelection> {
+    let parsed = ParsedQuery::parse(query);
+    let filters = SearchFilters::from_query(&parsed)?;
+    let text = parsed.text();
+    let candidates = if text.is_empty() {
+        ctx.db.list_skills(MAX_QUERY_CANDIDATES, 0)?
+    } else {
+        let mut records = Vec::new();
+        for hit in bm25_ranked(ctx, &text, None, MAX_QUERY_CANDIDATES)? {
+            if let Some(record) = ctx.db.get_skill(&hit.skill_id)? {
+                records.push(record);
+            }
+        }
+        records
+    };
+
+    let mut seen = HashSet::new();
+    let mut skills: Vec<String> = candidates
+        .iter()
+        .filter(|record| matches_skill_record(&filters, record))
+        .filter(|record| seen.insert(record.id.clone()))
+        .map(|record| record.id.clone())
+        .collect();
+    if skills.is_empty() {
+        return Err(MsError::ValidationFailed(format!(
+            "query '{query}' matched no skills"
+        )));
+    }
+
+    let repository = GitSkillRepository::new(&ctx.git);
+    let closure = dependency_closure(&skills, &repository)?;
+    if !closure.missing.is_empty() {
+        return Err(MsError::ValidationFailed(format!(
+            "bundle would not be self-contained; missing dependencies: {}",
+            DependencyClosure::describe(&closure.missing)
+        )));
+    }
+    if args.no_deps && !closure.added.is_empty() {
+        return Err(MsError::ValidationFailed(format!(
+            "--no-deps: selected skills depend on skills outside the selection: {}",
+            DependencyClosure::describe(&closure.added)
+        )));
+    }
+    skills.extend(closure.added.iter().map(|dep| dep.skill_id.clone()));
+
+    let mut structural = ValidationEngine::with_defaults();
+    for rule in structural_rules() {
+        structural.register(rule);
+    }
+    let mut sensitive = Vec::new();
+    let mut warnings = Vec::new();
+    for skill_id in &skills {
+        let spec = repository
+            .get(skill_id)?
+            .ok_or_else(|| MsError::SkillNotFound(skill_id.clone()))?;
+        if spec.metadata.sensitive {
+            sensitive.push(skill_id.clone());
+        }
+        let errors: Vec<String> = structural
+            .validate(&spec)
+            .errors()
+            .map(|d| format!("{}: {}", d.rule_id, d.message))
+            .collect();
+        if !errors.is_empty() {
+            warnings.push(format!(
+                "{skill_id} fails structural lint: {}",
+                errors.join("; ")
+            ));
+        }
+    }
+    if !sensitive.is_empty() {
+        if !args.allow_sensitive {
+            return Err(MsError::ValidationFailed(format!(
+                "refusing to bundle sensitive skills: {} (pass --allow-sensitive to include them)",
+                sensitive.join(", ")
+            )));
+        }
+        warnings.push(format!(
+            "including sensitive skills: {}",
+            sensitive.join(", ")
+        ));
+    }
+
+    Ok(QuerySelection {
+        skills,
+        dependencies: closure.added,
+        warnings,
+    })
+}
+
 fn run_install(ctx: &AppContext, args: &BundleInstallArgs) -> Result<()> {
     // Acquire lock to prevent concurrent modifications
-    let _lock = GlobalLock::acquire(&ctx.ms_root)?;
+    let _lock = GlobalLock::acquire_or_busy(&ctx.ms_root, lock_timeout())?;
 
     // Parse the source using the new ParsedSource
     let parsed = ParsedSource::parse(&args.source)?;
@@ -438,8 +687,324 @@ fn run_install(ctx: &AppContext, args: &BundleInstallArgs) -> Result<()> {
         other => other,
     };
 
-    // Download or read the bundle bytes
-    let bytes = match &source {
+    let package = fetch_package(ctx, &source, args.token.clone(), args.no_verify)?;
+    let (report, backups) = match install_bundle(ctx, &package, source, args)? {
+        BundleInstall::Installed {
+            report, backups, ..
+        } => (report, backups),
+        BundleInstall::Conflicts(conflicts) => return Err(conflicts.into_error()),
+    };
+
+    if ctx.output_format != OutputFormat::Human {
+        return emit_json(&report);
+    }
+
+    print_install_report(&report);
+    if !backups.is_empty() {
+        println!("Backed up conflicting local files:");
+        for backup in &backups {
+            println!("  - {}", backup.display());
+        }
+    }
+    Ok(())
+}
+
+/// Result of [`install_bundle`].
+#[derive(Debug)]
+pub(crate) enum BundleInstall {
+    Installed {
+        report: InstallReport,
+        /// Local files that differed from the bundle and were replaced
+        conflicts_overwritten: Vec<ConflictDetail>,
+        /// Where those files were backed up
+        backups: Vec<PathBuf>,
+    },
+    /// Nothing was changed: the bundle or some of its skills are already
+    /// present and `force` was not given
+    Conflicts(InstallConflicts),
+}
+
+/// What a non-forced install would overwrite.
+#[derive(Debug, Serialize)]
+pub(crate) struct InstallConflicts {
+    pub bundle_id: String,
+    pub already_installed: bool,
+    pub existing_skills: Vec<String>,
+    /// Files in those skills whose content differs from the bundle
+    pub conflicts: Vec<ConflictDetail>,
+}
+
+impl InstallConflicts {
+    fn into_error(self) -> MsError {
+        if self.already_installed {
+            MsError::ValidationFailed(format!(
+                "bundle {} is already installed; use --force to reinstall or ms bundle remove first",
+                self.bundle_id
+            ))
+        } else {
+            MsError::ValidationFailed(format!(
+                "skills already exist: {}; use --force to overwrite (conflicting files are backed up first)",
+                self.existing_skills.join(", ")
+            ))
+        }
+    }
+}
+
+/// Install a fetched bundle package: the one install path behind
+/// `ms bundle install` and the MCP `bundle` tool.
+///
+/// An installed bundle or an existing skill directory is only replaced with
+/// `args.force`, and local files that differ from the bundle are backed up
+/// first. Dependencies are resolved against the registry (and fetched with
+/// `args.with_deps`), signed bundles must verify against
+/// `[bundles.trusted_keys]` unless `args.no_verify`, and the signing key is
+/// pinned for later updates. Dependencies are installed before anything is
+/// replaced, and replaced skill directories are restored if the install fails.
+pub(crate) fn install_bundle(
+    ctx: &AppContext,
+    package: &BundlePackage,
+    source: InstallSource,
+    args: &BundleInstallArgs,
+) -> Result<BundleInstall> {
+    let root = ctx.git.root();
+    let bundle_id = package.manifest.bundle.id.clone();
+    let only = normalize_skill_list(&args.skills);
+
+    let mut registry = BundleRegistry::open(root)?;
+    let already_installed = registry.is_installed(&bundle_id);
+
+    // Skill directories the install would replace, and the files in them that
+    // differ from the bundle
+    let mut existing = Vec::new();
+    let mut conflicts = Vec::new();
+    for skill in &package.manifest.skills {
+        if !only.is_empty() && !only.contains(&skill.name) {
+            continue;
+        }
+        let target = resolve_bundle_target(root, &skill.path, &skill.name)?;
+        if !target.exists() {
+            continue;
+        }
+        let (_, bundle_hashes) = bundle_skill_entries(package, skill)?;
+        conflicts.extend(detect_conflicts(
+            &target,
+            &skill.name,
+            &bundle_hashes,
+            &hash_directory(&target)?,
+        ));
+        existing.push((skill.name.clone(), target));
+    }
+    if !args.force && (already_installed || !existing.is_empty()) {
+        return Ok(BundleInstall::Conflicts(InstallConflicts {
+            bundle_id,
+            already_installed,
+            existing_skills: existing.into_iter().map(|(name, _)| name).collect(),
+            conflicts,
+        }));
+    }
+
+    // Resolve dependencies before touching anything on disk
+    let mut dependency_sources = HashMap::new();
+    let dependencies = if args.with_deps {
+        let mut fetch = |dep: &BundleDependency, source: &str| -> Result<BundlePackage> {
+            let parsed = ParsedSource::parse(source)?;
+            let package = fetch_package(ctx, &parsed.source, args.token.clone(), args.no_verify)?;
+            dependency_sources.insert(dep.id.clone(), parsed.source);
+            Ok(package)
+        };
+        resolve_dependencies(&package.manifest, &registry, Some(&mut fetch))?
+    } else {
+        resolve_dependencies(&package.manifest, &registry, None)?
+    };
+
+    // Dependencies come back in install order, so each one's own
+    // dependencies are registered before it installs. They go in before the
+    // bundle's own skills are touched, so a failing dependency leaves the
+    // existing install as it was.
+    let mut dependencies_installed = Vec::new();
+    for dependency in &dependencies {
+        let dep_id = dependency.manifest.bundle.id.clone();
+        let dep_source = dependency_sources.remove(&dep_id).ok_or_else(|| {
+            MsError::Config(format!("no source recorded for dependency {dep_id}"))
+        })?;
+        let report = install_package(ctx, dependency, &[], args.no_verify)?;
+        registry.register(InstalledBundle {
+            id: dep_id,
+            version: dependency.manifest.bundle.version.clone(),
+            source: dep_source,
+            installed_at: chrono::Utc::now(),
+            skills: report.installed.clone(),
+            checksum: dependency.manifest.checksum.clone(),
+            blobs: dependency.blob_hashes(),
+            pinned_key: install_pin(ctx, dependency, args.no_verify)?,
+        })?;
+        dependencies_installed.push(report);
+    }
+
+    let backups = backup_conflicts(root, &bundle_id, &existing, &conflicts)?;
+    // --force: move the previous install and any existing skill directories
+    // aside, and put them back if the new install fails
+    let mut replaced: Vec<PathBuf> = existing.into_iter().map(|(_, target)| target).collect();
+    if let Some(previous) = registry.get(&bundle_id) {
+        replaced.extend(
+            previous
+                .skills
+                .iter()
+                .filter_map(|skill_id| ctx.git.skill_path(skill_id)),
+        );
+    }
+    let staged = StagedDirs::stage(root, &bundle_id, &replaced)?;
+    let mut report = match install_package(ctx, package, &only, args.no_verify) {
+        Ok(report) => report,
+        Err(err) => {
+            if let Err(restore_err) = staged.restore() {
+                tracing::warn!(
+                    bundle = %bundle_id,
+                    error = %restore_err,
+                    "failed to restore replaced skills"
+                );
+            }
+            return Err(err);
+        }
+    };
+    staged.discard()?;
+    report.dependencies_installed = dependencies_installed;
+
+    // Register the installation
+    registry.register(InstalledBundle {
+        id: bundle_id,
+        version: package.manifest.bundle.version.clone(),
+        source,
+        installed_at: chrono::Utc::now(),
+        skills: report.installed.clone(),
+        checksum: package.manifest.checksum.clone(),
+        blobs: package.blob_hashes(),
+        pinned_key: install_pin(ctx, package, args.no_verify)?,
+    })?;
+
+    Ok(BundleInstall::Installed {
+        report,
+        conflicts_overwritten: conflicts,
+        backups,
+    })
+}
+
+/// Skill directories a forced install replaces, moved under
+/// `bundles/staging/<bundle>` until the new install succeeds.
+struct StagedDirs {
+    staging: PathBuf,
+    moved: Vec<(PathBuf, PathBuf)>,
+}
+
+impl StagedDirs {
+    fn stage(root: &Path, bundle_id: &str, targets: &[PathBuf]) -> Result<Self> {
+        let staging = root.join("bundles/staging").join(bundle_id);
+        let mut staged = Self {
+            staging,
+            moved: Vec::new(),
+        };
+        for target in targets {
+            if !target.exists() || staged.moved.iter().any(|(from, _)| from == target) {
+                continue;
+            }
+            let dest = staged.staging.join(staged.moved.len().to_string());
+            let moved = std::fs::create_dir_all(&staged.staging)
+                .and_then(|()| std::fs::rename(target, &dest));
+            if let Err(err) = moved {
+                let message = format!(
+                    "failed to move existing skill {} aside: {err}",
+                    target.display()
+                );
+                staged.restore()?;
+                return Err(MsError::Config(message));
+            }
+            staged.moved.push((target.clone(), dest));
+        }
+        Ok(staged)
+    }
+
+    /// Put every staged directory back, replacing whatever was written in
+    /// its place.
+    fn restore(self) -> Result<()> {
+        for (target, dest) in self.moved.iter().rev() {
+            if target.exists() {
+                std::fs::remove_dir_all(target)?;
+            }
+            std::fs::rename(dest, target)?;
+        }
+        remove_staging(&self.staging)
+    }
+
+    /// Drop the staged directories once the replacement is in place.
+    fn discard(self) -> Result<()> {
+        remove_staging(&self.staging)
+    }
+}
+
+fn remove_staging(staging: &Path) -> Result<()> {
+    if staging.exists() {
+        std::fs::remove_dir_all(staging).map_err(|err| {
+            MsError::Config(format!("failed to remove {}: {err}", staging.display()))
+        })?;
+    }
+    Ok(())
+}
+
+/// Copy each conflicting local file under `bundles/backups/<bundle>/<time>`.
+fn backup_conflicts(
+    root: &Path,
+    bundle_id: &str,
+    existing: &[(String, PathBuf)],
+    conflicts: &[ConflictDetail],
+) -> Result<Vec<PathBuf>> {
+    let backup_root = root
+        .join("bundles/backups")
+        .join(bundle_id)
+        .join(chrono::Utc::now().format("%Y%m%d_%H%M%S").to_string());
+    let mut backups = Vec::new();
+    for conflict in conflicts {
+        let Some((_, target)) = existing.iter().find(|(name, _)| *name == conflict.skill_id) else {
+            continue;
+        };
+        let dest = backup_root
+            .join(&conflict.skill_id)
+            .join(conflict.file_path.parent().unwrap_or(Path::new("")));
+        let info = backup_file(&target.join(&conflict.file_path), &dest)?;
+        backups.push(info.backup_path);
+    }
+    Ok(backups)
+}
+
+/// Key to pin for a freshly installed bundle: the trusted key that signed
+/// it, unless verification was skipped or the bundle is unsigned.
+fn install_pin(
+    ctx: &AppContext,
+    package: &BundlePackage,
+    no_verify: bool,
+) -> Result<Option<PinnedKey>> {
+    if no_verify {
+        return Ok(None);
+    }
+    let check = check_signing_key(
+        &package.manifest,
+        &package.to_bytes()?,
+        None,
+        &ctx.config.bundles.trusted_keys,
+    )?;
+    Ok(match check {
+        KeyCheck::Pinned(key) => Some(key),
+        KeyCheck::Unsigned | KeyCheck::Changed { .. } => None,
+    })
+}
+
+/// Download or read the bundle at `source`.
+fn fetch_package(
+    ctx: &AppContext,
+    source: &InstallSource,
+    token: Option<String>,
+    no_verify: bool,
+) -> Result<BundlePackage> {
+    match source {
         InstallSource::File { path } => {
             let local_path = PathBuf::from(path);
             if !local_path.exists() {
@@ -448,66 +1013,38 @@ fn run_install(ctx: &AppContext, args: &BundleInstallArgs) -> Result<()> {
                     local_path.display()
                 )));
             }
-            std::fs::read(&local_path)
-                .map_err(|err| MsError::Config(format!("read {}: {err}", local_path.display())))?
+            let bytes = std::fs::read(&local_path)
+                .map_err(|err| MsError::Config(format!("read {}: {err}", local_path.display())))?;
+            BundlePackage::from_bytes(&bytes)
         }
-        InstallSource::Url { url } => download_url(url, args.token.clone())?,
+        InstallSource::Url { url } => BundlePackage::from_bytes(&download_url(url, token)?),
         InstallSource::GitHub { repo, tag, asset } => {
-            let download =
-                download_bundle(repo, tag.as_deref(), asset.as_deref(), args.token.clone())?;
-            download.bytes
-        }
-    };
-
-    let package = crate::bundler::package::BundlePackage::from_bytes(&bytes)?;
-    let bundle_id = package.manifest.bundle.id.clone();
-    let bundle_version = package.manifest.bundle.version.clone();
-    let checksum = package.manifest.checksum.clone();
-
-    // Check if already installed
-    let mut registry = BundleRegistry::open(ctx.git.root())?;
-    if registry.is_installed(&bundle_id) {
-        if !args.force {
-            return Err(MsError::ValidationFailed(format!(
-                "bundle {bundle_id} is already installed; use --force to reinstall or ms bundle remove first"
-            )));
-        }
-        // --force: remove existing skill directories before reinstalling
-        if let Some(existing) = registry.get(&bundle_id) {
-            for skill_id in &existing.skills {
-                if let Some(skill_path) = ctx.git.skill_path(skill_id) {
-                    if skill_path.exists() {
-                        std::fs::remove_dir_all(&skill_path).map_err(|err| {
-                            MsError::Config(format!(
-                                "failed to remove existing skill {skill_id}: {err}"
-                            ))
-                        })?;
-                    }
-                }
-            }
+            let download = download_bundle(repo, tag.as_deref(), asset.as_deref(), token)?;
+            BundlePackage::from_bytes(&download.bytes)
         }
-        // Unregister old entry before re-registering
-        registry.unregister(&bundle_id)?;
+        // Verified before anything is kept on disk
+        InstallSource::Https { url } => fetch_https_bundle(ctx, url, no_verify),
     }
+}
 
-    let only = normalize_skill_list(&args.skills);
-
+fn install_package(
+    ctx: &AppContext,
+    package: &BundlePackage,
+    only: &[String],
+    no_verify: bool,
+) -> Result<InstallReport> {
     // Install with verification
     //
     // Current behavior:
     // - --no-verify: Skip all verification, allow unsigned bundles
-    // - Default (no flag): Allow unsigned bundles (with warning), but require valid
-    //   signatures for signed bundles when a verifier is configured
-    //
-    // Note: Trusted key configuration is not yet implemented. For now, signed bundles
-    // will fail verification unless --no-verify is used. This is intentional - we want
-    // to establish the signature verification pattern early, even if the full key
-    // management workflow isn't complete yet.
-    let report = if args.no_verify {
+    // - Default (no flag): Allow unsigned bundles (with warning), but require a valid
+    //   signature from a key in [bundles.trusted_keys] for signed bundles
+    // - HTTPS sources were already required to be signed by fetch_https_bundle
+    let report = if no_verify {
         let options = crate::bundler::InstallOptions::<
             crate::bundler::manifest::NoopSignatureVerifier,
         >::allow_unsigned();
-        crate::bundler::install_with_options(&package, ctx.git.root(), &only, &options)?
+        crate::bundler::install_with_options(package, ctx.git.root(), only, &options)
     } else if package.manifest.signatures.is_empty() {
         // Unsigned bundle: allow but warn (development/testing scenario)
         if ctx.output_format == OutputFormat::Human {
@@ -520,42 +1057,129 @@ fn run_install(ctx: &AppContext, args: &BundleInstallArgs) -> Result<()> {
         let options = crate::bundler::InstallOptions::<
             crate::bundler::manifest::NoopSignatureVerifier,
         >::allow_unsigned();
-        crate::bundler::install_with_options(&package, ctx.git.root(), &only, &options)?
+        crate::bundler::install_with_options(package, ctx.git.root(), only, &options)
     } else {
-        // Signed bundle: require verification
-        // TODO: Load trusted keys from config when implemented
-        return Err(MsError::ValidationFailed(format!(
-            "Bundle '{}' is signed but trusted key configuration is not yet implemented. \
-             Use --no-verify to install (not recommended for production), \
-             or wait for trusted key support in a future release.",
-            package.manifest.bundle.id
-        )));
-    };
+        // Signed bundle: require a signature from a key in [bundles.trusted_keys]
+        let verifier = trusted_verifier(&ctx.config.bundles)?;
+        let options = crate::bundler::InstallOptions::with_verifier(&verifier);
+        crate::bundler::install_with_options(package, ctx.git.root(), only, &options)
+    }?;
+    commit_bundle_skills(ctx, package, &report.installed, "Install")?;
+    Ok(report)
+}
 
-    // Register the installation
-    let installed = InstalledBundle {
-        id: bundle_id,
-        version: bundle_version,
-        source,
-        installed_at: chrono::Utc::now(),
-        skills: report.installed.clone(),
-        checksum,
+/// Commit the archive directories of `skills` from `package`, recording the
+/// bundle as their provenance.
+fn commit_bundle_skills(
+    ctx: &AppContext,
+    package: &BundlePackage,
+    skills: &[String],
+    action: &str,
+) -> Result<()> {
+    if skills.is_empty() {
+        return Ok(());
+    }
+    let mut dirs = Vec::new();
+    for skill in &package.manifest.skills {
+        if skills.contains(&skill.name) {
+            dirs.push(resolve_bundle_target(
+                ctx.git.root(),
+                &skill.path,
+                &skill.name,
+            )?);
+        }
+    }
+    for skill in &package.manifest.skills {
+        if skills.contains(&skill.name) && !skill.resources.is_empty() {
+            ctx.db
+                .replace_skill_resources(&skill.name, &resource_records(&skill.resources))?;
+        }
+    }
+    let bundle = &package.manifest.bundle;
+    let provenance = Provenance::new(format!("bundle {}", action.to_lowercase()))
+        .with_bundle(format!("{}@{}", bundle.id, bundle.version));
+    ctx.git.commit_dirs(
+        &dirs,
+        &format!("{action} bundle {} {}", bundle.id, bundle.version),
+        &provenance,
+    )?;
+    Ok(())
+}
+
+/// Resources of the skill in `skill_dir`, as its blob will hold them.
+fn bundled_resources(skill_dir: &Path) -> Result<Vec<BundledResource>> {
+    let skill_md = skill_dir.join("SKILL.md");
+    let Ok(markdown) = std::fs::read_to_string(&skill_md) else {
+        return Ok(Vec::new());
     };
-    registry.register(installed)?;
+    Ok(collect_resources(&skill_md, &markdown)?
+        .into_iter()
+        .map(|file| BundledResource {
+            path: file.path,
+            size: file.size,
+            hash: format!("sha256:{}", file.content_hash),
+        })
+        .collect())
+}
 
-    if ctx.output_format != OutputFormat::Human {
-        return emit_json(&report);
-    }
+/// Database rows for resources restored from a bundle.
+fn resource_records(resources: &[BundledResource]) -> Vec<SkillResourceRecord> {
+    resources
+        .iter()
+        .map(|resource| SkillResourceRecord {
+            path: resource.path.clone(),
+            size: i64::try_from(resource.size).unwrap_or(i64::MAX),
+            content_hash: resource
+                .hash
+                .strip_prefix("sha256:")
+                .unwrap_or(&resource.hash)
+                .to_string(),
+        })
+        .collect()
+}
 
-    print_install_report(&report);
-    Ok(())
+/// Download an HTTPS bundle into `bundles/downloads/`, verifying its checksum
+/// and signatures before anything is kept on disk.
+///
+/// Remote bundles must be signed by a trusted key unless `no_verify` is set.
+fn fetch_https_bundle(ctx: &AppContext, url: &str, no_verify: bool) -> Result<BundlePackage> {
+    let verifier = trusted_verifier(&ctx.config.bundles)?;
+    let staging = ctx.git.root().join("bundles").join("downloads");
+    let options = RemoteOptions::from_config(&ctx.config.bundles);
+    let fetched = remote::fetch_verified(url, &staging, &options, |package| {
+        if no_verify {
+            return Ok(());
+        }
+        if package.manifest.signatures.is_empty() {
+            return Err(MsError::ValidationFailed(format!(
+                "remote bundle '{}' is unsigned; use --no-verify to install it anyway",
+                package.manifest.bundle.id
+            )));
+        }
+        package.verify_signatures(&verifier)
+    })?;
+    Ok(fetched.package)
+}
+
+/// Verifier trusting the keys in `[bundles.trusted_keys]`.
+pub(crate) fn trusted_verifier(config: &BundlesConfig) -> Result<Ed25519Verifier> {
+    let mut verifier = Ed25519Verifier::new();
+    for (key_id, key) in &config.trusted_keys {
+        let bytes = hex::decode(key.trim()).map_err(|err| {
+            MsError::Config(format!(
+                "bundles.trusted_keys.{key_id} is not valid hex: {err}"
+            ))
+        })?;
+        verifier.add_key(key_id.clone(), bytes);
+    }
+    Ok(verifier)
 }
 
 fn run_remove(ctx: &AppContext, args: &BundleRemoveArgs) -> Result<()> {
     use std::io::Write;
 
     // Acquire lock to prevent concurrent modifications
-    let _lock = GlobalLock::acquire(&ctx.ms_root)?;
+    let _lock = GlobalLock::acquire_or_busy(&ctx.ms_root, lock_timeout())?;
 
     let mut registry = BundleRegistry::open(ctx.git.root())?;
 
@@ -661,7 +1285,7 @@ struct BundleConflictSummary {
 
 fn run_update(ctx: &AppContext, args: &BundleUpdateArgs) -> Result<()> {
     // Acquire lock to prevent concurrent modifications
-    let _lock = GlobalLock::acquire(&ctx.ms_root)?;
+    let _lock = GlobalLock::acquire_or_busy(&ctx.ms_root, lock_timeout())?;
 
     if args.all && args.bundle_id.is_some() {
         return Err(MsError::ValidationFailed(
@@ -698,7 +1322,12 @@ fn run_update(ctx: &AppContext, args: &BundleUpdateArgs) -> Result<()> {
     let default_check = !args.check && !args.dry_run && !args.all && args.bundle_id.is_none();
 
     for installed in targets {
-        let item = match fetch_update_candidate(ctx, args, &installed) {
+        let candidate =
+            download_update(ctx, &installed, args.token.clone()).and_then(|candidate| {
+                verify_bundle(ctx, args, &candidate.package)?;
+                Ok(candidate)
+            });
+        let item = match candidate {
             Ok(candidate) => build_update_item(ctx, args, &installed, candidate, default_check)?,
             Err(err) => BundleUpdateItem {
                 bundle_id: installed.id.clone(),
@@ -732,15 +1361,16 @@ struct UpdateCandidate {
     source: InstallSource,
 }
 
-fn fetch_update_candidate(
+/// Fetch the current release of an installed bundle from its source.
+/// Signatures are left to the caller.
+fn download_update(
     ctx: &AppContext,
-    args: &BundleUpdateArgs,
     installed: &InstalledBundle,
+    token: Option<String>,
 ) -> Result<UpdateCandidate> {
     let (bytes, source_override) = match &installed.source {
         InstallSource::GitHub { repo, tag, asset } => {
-            let result =
-                download_bundle(repo, tag.as_deref(), asset.as_deref(), args.token.clone())?;
+            let result = download_bundle(repo, tag.as_deref(), asset.as_deref(), token)?;
             (
                 result.bytes,
                 Some(InstallSource::GitHub {
@@ -750,7 +1380,14 @@ fn fetch_update_candidate(
                 }),
             )
         }
-        InstallSource::Url { url } => (download_url(url, args.token.clone())?, None),
+        InstallSource::Url { url } => (download_url(url, token)?, None),
+        InstallSource::Https { url } => {
+            let options = RemoteOptions::from_config(&ctx.config.bundles);
+            let staging = ctx.git.root().join("bundles").join("downloads");
+            // Signatures are checked by the caller
+            let fetched = remote::fetch_verified(url, &staging, &options, |_| Ok(()))?;
+            (fetched.package.to_bytes()?, None)
+        }
         InstallSource::File { path } => (
             std::fs::read(path)
                 .map_err(|err| MsError::Config(format!("read bundle {path}: {err}")))?,
@@ -766,7 +1403,6 @@ fn fetch_update_candidate(
             installed.id, package.manifest.bundle.id
         )));
     }
-    verify_bundle(ctx, args, &package)?;
     Ok(UpdateCandidate { package, source })
 }
 
@@ -787,11 +1423,7 @@ fn verify_bundle(ctx: &AppContext, args: &BundleUpdateArgs, package: &BundlePack
         return Ok(());
     }
 
-    Err(MsError::ValidationFailed(format!(
-        "Bundle '{}' is signed but trusted key configuration is not yet implemented. \
-         Use --no-verify to update (not recommended for production).",
-        package.manifest.bundle.id
-    )))
+    package.verify_signatures(&trusted_verifier(&ctx.config.bundles)?)
 }
 
 fn build_update_item(
@@ -827,7 +1459,18 @@ fn build_update_item(
         return Ok(item);
     }
 
-    let apply_result = apply_bundle_update(ctx, args, installed, &candidate)?;
+    let strategy = if args.force {
+        ConflictStrategy::BackupAndReplace
+    } else {
+        ConflictStrategy::Abort
+    };
+    let apply_result = apply_bundle_update(
+        ctx,
+        strategy,
+        installed,
+        &candidate,
+        installed.pinned_key.clone(),
+    )?;
     item.applied = apply_result.applied;
     item.conflicts = apply_result.conflicts;
     item.skipped_reason = apply_result.skipped_reason;
@@ -840,11 +1483,15 @@ struct ApplyResult {
     skipped_reason: Option<String>,
 }
 
+/// Write an update's skills over the installed ones. Locally modified files
+/// block the update under [`ConflictStrategy::Abort`]; otherwise they are
+/// backed up and replaced, replaced, or kept as they are.
 fn apply_bundle_update(
     ctx: &AppContext,
-    args: &BundleUpdateArgs,
+    strategy: ConflictStrategy,
     installed: &InstalledBundle,
     candidate: &UpdateCandidate,
+    pinned_key: Option<PinnedKey>,
 ) -> Result<ApplyResult> {
     let mut conflicts = Vec::new();
     let backup_root = backup_root(ctx, &installed.id);
@@ -882,7 +1529,12 @@ fn apply_bundle_update(
         pending.push((skill.name.clone(), target, entries, new_hashes, report));
     }
 
-    if !conflicts.is_empty() && !args.force {
+    if !conflicts.is_empty()
+        && matches!(
+            strategy,
+            ConflictStrategy::Abort | ConflictStrategy::Interactive
+        )
+    {
         return Ok(ApplyResult {
             applied: false,
             conflicts,
@@ -890,27 +1542,51 @@ fn apply_bundle_update(
         });
     }
 
-    for (skill_name, target, entries, expected_hashes, report) in pending {
+    for (skill_name, target, mut entries, expected_hashes, report) in pending {
         let skill_backup_root = backup_root.join(&skill_name);
-        if let Some(report) = report {
-            if report.needs_attention() && args.force {
-                for file in &report.files {
-                    if matches!(
-                        file.status,
-                        ModificationStatus::Modified | ModificationStatus::Conflict
-                    ) {
-                        let path = target.join(&file.path);
-                        if path.exists() {
-                            let _ = backup_file(&path, &skill_backup_root);
+        if let Some(report) = report
+            && report.needs_attention()
+        {
+            match strategy {
+                ConflictStrategy::BackupAndReplace => {
+                    for file in &report.files {
+                        if matches!(
+                            file.status,
+                            ModificationStatus::Modified | ModificationStatus::Conflict
+                        ) {
+                            let path = target.join(&file.path);
+                            if path.exists() {
+                                let _ = backup_file(&path, &skill_backup_root);
+                            }
                         }
                     }
                 }
+                ConflictStrategy::PreferLocal => {
+                    // Leave modified and deleted files as the user left them
+                    entries.retain(|(path, _)| {
+                        !report
+                            .files
+                            .iter()
+                            .any(|file| &file.path == path && file.needs_attention())
+                    });
+                }
+                ConflictStrategy::PreferBundle
+                | ConflictStrategy::Abort
+                | ConflictStrategy::Interactive => {}
             }
         }
 
         write_bundle_files(&target, &entries)?;
         write_bundle_meta(&target, &expected_hashes)?;
     }
+    let updated: Vec<String> = candidate
+        .package
+        .manifest
+        .skills
+        .iter()
+        .map(|s| s.name.clone())
+        .collect();
+    commit_bundle_skills(ctx, &candidate.package, &updated, "Update")?;
 
     let installed = InstalledBundle {
         id: candidate.package.manifest.bundle.id.clone(),
@@ -925,6 +1601,8 @@ fn apply_bundle_update(
             .map(|s| s.name.clone())
             .collect(),
         checksum: candidate.package.manifest.checksum.clone(),
+        blobs: candidate.package.blob_hashes(),
+        pinned_key,
     };
     BundleRegistry::open(ctx.git.root())?.register(installed)?;
 
@@ -935,6 +1613,216 @@ fn apply_bundle_update(
     })
 }
 
+/// One bundle's entry in the `ms update --bundles` plan.
+#[derive(Debug, Serialize)]
+pub(crate) struct BundleUpdatePlan {
+    pub bundle_id: String,
+    pub source: String,
+    pub current_version: String,
+    pub available_version: Option<String>,
+    pub update_available: bool,
+    /// Skills the update adds, changes or removes
+    pub changed_skills: usize,
+    /// Skills with local modifications (see `ms bundle conflicts`)
+    pub locally_modified: Vec<String>,
+    /// Key id updates must be signed with, if one is pinned
+    pub pinned_key: Option<String>,
+    /// The update is not signed by the pinned key
+    pub key_changed: bool,
+    pub applied: bool,
+    pub skipped_reason: Option<String>,
+    pub error: Option<String>,
+}
+
+/// What `ms update --bundles` should do.
+pub(crate) struct BundleUpdateRequest<'a> {
+    /// Bundles to update; empty for all installed bundles
+    pub names: &'a [String],
+    pub strategy: ConflictStrategy,
+    pub accept_new_key: bool,
+    /// Plan only
+    pub dry_run: bool,
+}
+
+/// A fetched update that passed its checks, with the key to pin after it.
+struct PlannedUpdate {
+    installed: InstalledBundle,
+    candidate: UpdateCandidate,
+    key: Option<PinnedKey>,
+}
+
+/// Plan updates for installed bundles from versioned sources and, unless
+/// `dry_run`, apply them.
+///
+/// A bundle whose update is not signed by its pinned key fails the whole run
+/// before anything is applied, unless `accept_new_key` is set.
+pub(crate) fn update_bundles(
+    ctx: &AppContext,
+    request: &BundleUpdateRequest<'_>,
+) -> Result<Vec<BundleUpdatePlan>> {
+    let _lock = GlobalLock::acquire_or_busy(&ctx.ms_root, lock_timeout())?;
+
+    let registry = BundleRegistry::open(ctx.git.root())?;
+    let mut targets: Vec<InstalledBundle> = if request.names.is_empty() {
+        registry.list().cloned().collect()
+    } else {
+        request
+            .names
+            .iter()
+            .map(|name| {
+                registry
+                    .get(name)
+                    .cloned()
+                    .ok_or_else(|| MsError::NotFound(format!("bundle '{name}' is not installed")))
+            })
+            .collect::<Result<_>>()?
+    };
+    targets.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let mut planned = Vec::new();
+    for installed in targets {
+        let mut plan = BundleUpdatePlan {
+            bundle_id: installed.id.clone(),
+            source: installed.source.to_string(),
+            current_version: installed.version.clone(),
+            available_version: None,
+            update_available: false,
+            changed_skills: 0,
+            locally_modified: Vec::new(),
+            pinned_key: installed.pinned_key.as_ref().map(|key| key.key_id.clone()),
+            key_changed: false,
+            applied: false,
+            skipped_reason: None,
+            error: None,
+        };
+        if !installed.source.supports_versioning() {
+            plan.skipped_reason = Some("source_not_versioned".to_string());
+            planned.push((plan, None));
+            continue;
+        }
+        match plan_update(ctx, &installed, &mut plan) {
+            Ok(update) => planned.push((plan, update)),
+            Err(err) => {
+                plan.error = Some(err.to_string());
+                planned.push((plan, None));
+            }
+        }
+    }
+
+    if request.dry_run {
+        return Ok(planned.into_iter().map(|(plan, _)| plan).collect());
+    }
+    if !request.accept_new_key
+        && let Some((plan, _)) = planned
+            .iter()
+            .find(|(plan, update)| plan.key_changed && update.is_some())
+    {
+        return Err(MsError::ValidationFailed(format!(
+            "the signing key of bundle {} changed (pinned: {}); re-run with --accept-new-key to trust the new key",
+            plan.bundle_id,
+            plan.pinned_key.as_deref().unwrap_or("none")
+        )));
+    }
+
+    let mut plans = Vec::new();
+    for (mut plan, update) in planned {
+        if let Some(update) = update
+            && let Err(err) = apply_planned(ctx, request.strategy, update, &mut plan)
+        {
+            plan.error = Some(err.to_string());
+        }
+        plans.push(plan);
+    }
+    Ok(plans)
+}
+
+/// Fetch and check one bundle's update, filling in its plan. Returns the
+/// update when there is one to apply.
+fn plan_update(
+    ctx: &AppContext,
+    installed: &InstalledBundle,
+    plan: &mut BundleUpdatePlan,
+) -> Result<Option<PlannedUpdate>> {
+    let candidate = download_update(ctx, installed, None)?;
+    let package = &candidate.package;
+    package.verify()?;
+    let key = match check_signing_key(
+        &package.manifest,
+        &package.to_bytes()?,
+        installed.pinned_key.as_ref(),
+        &ctx.config.bundles.trusted_keys,
+    )? {
+        KeyCheck::Pinned(key) => Some(key),
+        KeyCheck::Unsigned => None,
+        KeyCheck::Changed { pinned, .. } => {
+            plan.key_changed = true;
+            Some(pinned)
+        }
+    };
+
+    let available = package.manifest.bundle.version.clone();
+    plan.update_available = is_newer_version(&installed.version, &available)?;
+    plan.available_version = Some(available);
+    if !plan.update_available {
+        return Ok(None);
+    }
+
+    for skill in &package.manifest.skills {
+        let (_, new_hashes) = bundle_skill_entries(package, skill)?;
+        let target = resolve_bundle_target(ctx.git.root(), &skill.path, &skill.name)?;
+        let Some(existing) = load_bundle_meta(&target)? else {
+            plan.changed_skills += 1;
+            continue;
+        };
+        if existing != new_hashes {
+            plan.changed_skills += 1;
+        }
+        if detect_modifications(&target, &skill.name, &existing)?.needs_attention() {
+            plan.locally_modified.push(skill.name.clone());
+        }
+    }
+    plan.changed_skills += installed
+        .skills
+        .iter()
+        .filter(|id| !package.manifest.skills.iter().any(|s| &s.name == *id))
+        .count();
+
+    Ok(Some(PlannedUpdate {
+        installed: installed.clone(),
+        candidate,
+        key,
+    }))
+}
+
+fn apply_planned(
+    ctx: &AppContext,
+    strategy: ConflictStrategy,
+    update: PlannedUpdate,
+    plan: &mut BundleUpdatePlan,
+) -> Result<()> {
+    let PlannedUpdate {
+        installed,
+        candidate,
+        mut key,
+    } = update;
+    if plan.key_changed {
+        let package = &candidate.package;
+        key = Some(accept_new_key(
+            &package.manifest,
+            &package.to_bytes()?,
+            &ctx.config.bundles.trusted_keys,
+        )?);
+        plan.pinned_key = key.as_ref().map(|key| key.key_id.clone());
+    }
+    let result = apply_bundle_update(ctx, strategy, &installed, &candidate, key)?;
+    plan.applied = result.applied;
+    plan.skipped_reason = result.skipped_reason.map(|reason| match reason.as_str() {
+        "conflicts_detected" => "local_modifications".to_string(),
+        _ => reason,
+    });
+    Ok(())
+}
+
 fn is_newer_version(current: &str, candidate: &str) -> Result<bool> {
     let current_trim = current.trim_start_matches('v');
     let candidate_trim = candidate.trim_start_matches('v');
@@ -944,7 +1832,7 @@ fn is_newer_version(current: &str, candidate: &str) -> Result<bool> {
     }
 }
 
-fn bundle_skill_entries(
+pub(crate) fn bundle_skill_entries(
     package: &BundlePackage,
     skill: &BundledSkill,
 ) -> Result<(Vec<(PathBuf, Vec<u8>)>, HashMap<PathBuf, String>)> {
@@ -1022,7 +1910,11 @@ fn write_bundle_meta(target: &Path, expected_hashes: &HashMap<PathBuf, String>)
         .map_err(|err| MsError::Config(format!("write {}: {err}", meta_path.display())))
 }
 
-fn resolve_bundle_target(root: &Path, path: &Path, fallback_id: &str) -> Result<PathBuf> {
+pub(crate) fn resolve_bundle_target(
+    root: &Path,
+    path: &Path,
+    fallback_id: &str,
+) -> Result<PathBuf> {
     if !path.as_os_str().is_empty() {
         ensure_relative(path)?;
         return Ok(root.join(path));
@@ -1321,7 +2213,7 @@ fn run_list(ctx: &AppContext) -> Result<()> {
 }
 
 fn run_show(ctx: &AppContext, args: &BundleShowArgs) -> Result<()> {
-    let local_path = expand_local_path(&args.source);
+    let local_path = expand_path(&args.source);
     let bytes = if local_path.exists() {
         std::fs::read(&local_path)
             .map_err(|err| MsError::Config(format!("read {}: {err}", local_path.display())))?
@@ -1344,23 +2236,11 @@ fn run_show(ctx: &AppContext, args: &BundleShowArgs) -> Result<()> {
     };
     let package = crate::bundler::package::BundlePackage::from_bytes(&bytes)?;
     let manifest = &package.manifest;
+    let registry = BundleRegistry::open(ctx.git.root())?;
+    let dependencies = check_dependencies(manifest, &registry)?;
 
     if ctx.output_format != OutputFormat::Human {
-        return emit_json(&BundleShowReport {
-            id: manifest.bundle.id.clone(),
-            name: manifest.bundle.name.clone(),
-            version: manifest.bundle.version.clone(),
-            description: manifest.bundle.description.clone(),
-            authors: manifest.bundle.authors.clone(),
-            license: manifest.bundle.license.clone(),
-            repository: manifest.bundle.repository.clone(),
-            keywords: manifest.bundle.keywords.clone(),
-            ms_version: manifest.bundle.ms_version.clone(),
-            skills: manifest.skills.iter().map(|s| s.name.clone()).collect(),
-            skill_count: manifest.skills.len(),
-            checksum: manifest.checksum.clone(),
-            signed: !manifest.signatures.is_empty(),
-        });
+        return emit_json(&BundleShowReport::from_manifest(manifest, dependencies));
     }
 
     println!("Bundle: {} ({})", manifest.bundle.name, manifest.bundle.id);
@@ -1391,6 +2271,47 @@ fn run_show(ctx: &AppContext, args: &BundleShowArgs) -> Result<()> {
         println!("  - {} v{}{}", skill.name, version_str, optional_str);
     }
 
+    let changed: Vec<&BundledSkill> = manifest
+        .skills
+        .iter()
+        .filter(|skill| !skill.changelog.is_empty())
+        .collect();
+    if !changed.is_empty() {
+        println!("\nWhat's new:");
+        for skill in changed {
+            println!("  {}", skill.name);
+            for release in &skill.changelog {
+                println!(
+                    "    {} ({})",
+                    release.version.as_deref().unwrap_or("unversioned"),
+                    release.date
+                );
+                for note in &release.notes {
+                    println!("      {note}");
+                }
+            }
+        }
+    }
+
+    if !dependencies.is_empty() {
+        let satisfied = dependencies
+            .iter()
+            .filter(|dep| matches!(dep.status, DependencyStatus::Satisfied { .. }))
+            .count();
+        println!(
+            "\nDependencies ({} declared, {satisfied} satisfied):",
+            dependencies.len()
+        );
+        for dep in &dependencies {
+            let optional_str = if dep.optional { " (optional)" } else { "" };
+            println!("  - {dep}{optional_str}");
+            let satisfied = matches!(dep.status, DependencyStatus::Satisfied { .. });
+            if let Some(source) = dep.source.as_deref().filter(|_| !satisfied) {
+                println!("      source: {source}");
+            }
+        }
+    }
+
     if let Some(checksum) = &manifest.checksum {
         println!("\nChecksum: {checksum}");
     }
@@ -1403,6 +2324,59 @@ fn run_show(ctx: &AppContext, args: &BundleShowArgs) -> Result<()> {
     Ok(())
 }
 
+/// Where `bundle create --changelog-since` starts each skill's changelog.
+enum ChangelogBaseline {
+    /// Skill versions shipped in an earlier bundle; skills it did not ship
+    /// get no changelog
+    Bundle(HashMap<String, String>),
+    /// The same starting point for every skill
+    All(ChangelogSince),
+}
+
+impl ChangelogBaseline {
+    fn parse(value: &str) -> Result<Self> {
+        let path = expand_path(value);
+        if !path.is_file() {
+            return Ok(Self::All(ChangelogSince::parse(value, chrono::Utc::now())?));
+        }
+        let bytes = std::fs::read(&path)
+            .map_err(|err| MsError::Config(format!("read {}: {err}", path.display())))?;
+        let package = BundlePackage::from_bytes(&bytes)?;
+        Ok(Self::Bundle(
+            package
+                .manifest
+                .skills
+                .into_iter()
+                .filter_map(|skill| Some((skill.name, skill.version?)))
+                .collect(),
+        ))
+    }
+}
+
+/// Release notes for `skill_id` since `baseline`, newest release first.
+fn skill_changelog(
+    ctx: &AppContext,
+    skill_id: &str,
+    baseline: &ChangelogBaseline,
+) -> Result<Vec<SkillReleaseNotes>> {
+    let since = match baseline {
+        ChangelogBaseline::All(since) => since.clone(),
+        ChangelogBaseline::Bundle(versions) => match versions.get(skill_id) {
+            Some(version) => ChangelogSince::Version(version.clone()),
+            None => return Ok(Vec::new()),
+        },
+    };
+    let entries = since.filter(build_changelog(&ctx.git.skill_versions(skill_id)?))?;
+    Ok(group_by_version(entries)
+        .iter()
+        .map(|release| SkillReleaseNotes {
+            version: release.version.clone(),
+            date: release.date.format("%Y-%m-%d").to_string(),
+            notes: release.notes(),
+        })
+        .collect())
+}
+
 fn normalize_skill_list(values: &[String]) -> Vec<String> {
     let mut out = Vec::new();
     let mut seen = HashSet::new();
@@ -1495,6 +2469,16 @@ fn print_install_report(report: &InstallReport) {
         }
     }
     println!("Blobs written: {}", report.blobs_written);
+    if !report.dependencies_installed.is_empty() {
+        println!("Dependencies installed:");
+        for dependency in &report.dependencies_installed {
+            println!(
+                "  - {} ({} skill(s))",
+                dependency.bundle_id,
+                dependency.installed.len()
+            );
+        }
+    }
 }
 
 fn split_repo_tag(input: &str) -> Option<(&str, Option<&str>)> {
@@ -1507,34 +2491,28 @@ fn split_repo_tag(input: &str) -> Option<(&str, Option<&str>)> {
     None
 }
 
-fn looks_like_path(input: &str) -> bool {
-    input == "~"
-        || input.starts_with("~/")
-        || input.starts_with("./")
-        || input.starts_with("../")
-        || input.starts_with('/')
-}
-
-fn expand_local_path(input: &str) -> PathBuf {
-    if input == "~" {
-        if let Some(home) = dirs::home_dir() {
-            return home;
-        }
-    }
-    if let Some(stripped) = input.strip_prefix("~/") {
-        if let Some(home) = dirs::home_dir() {
-            return home.join(stripped);
-        }
-    }
-    PathBuf::from(input)
-}
-
 #[derive(serde::Serialize)]
 struct BundleCreateReport {
     id: String,
     output: String,
     manifest_path: Option<String>,
     checksum: Option<String>,
+    skills: Vec<BundleCreateSkill>,
+    /// Sum of the skill payload sizes, in bytes
+    total_size: u64,
+    /// Blobs newly added to the local blob store
+    blobs_written: usize,
+    warnings: Vec<String>,
+}
+
+#[derive(serde::Serialize)]
+struct BundleCreateSkill {
+    id: String,
+    version: Option<String>,
+    size: u64,
+    /// Set for skills added because another skill extends or includes them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    required_by: Option<String>,
 }
 
 #[allow(dead_code)]
@@ -1568,7 +2546,7 @@ struct BundleRemoveReport {
 }
 
 #[derive(serde::Serialize)]
-struct BundleShowReport {
+pub(crate) struct BundleShowReport {
     id: String,
     name: String,
     version: String,
@@ -1580,10 +2558,44 @@ struct BundleShowReport {
     ms_version: Option<String>,
     skills: Vec<String>,
     skill_count: usize,
+    /// Release notes per skill, for skills that embed them
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    changelog: HashMap<String, Vec<SkillReleaseNotes>>,
+    dependencies: Vec<DependencyCheck>,
     checksum: Option<String>,
     signed: bool,
 }
 
+impl BundleShowReport {
+    pub(crate) fn from_manifest(
+        manifest: &BundleManifest,
+        dependencies: Vec<DependencyCheck>,
+    ) -> Self {
+        Self {
+            id: manifest.bundle.id.clone(),
+            name: manifest.bundle.name.clone(),
+            version: manifest.bundle.version.clone(),
+            description: manifest.bundle.description.clone(),
+            authors: manifest.bundle.authors.clone(),
+            license: manifest.bundle.license.clone(),
+            repository: manifest.bundle.repository.clone(),
+            keywords: manifest.bundle.keywords.clone(),
+            ms_version: manifest.bundle.ms_version.clone(),
+            skills: manifest.skills.iter().map(|s| s.name.clone()).collect(),
+            skill_count: manifest.skills.len(),
+            changelog: manifest
+                .skills
+                .iter()
+                .filter(|s| !s.changelog.is_empty())
+                .map(|s| (s.name.clone(), s.changelog.clone()))
+                .collect(),
+            dependencies,
+            checksum: manifest.checksum.clone(),
+            signed: !manifest.signatures.is_empty(),
+        }
+    }
+}
+
 #[derive(serde::Serialize)]
 struct ConflictsReport {
     skills: Vec<SkillModificationReport>,
@@ -1599,6 +2611,27 @@ mod tests {
 
     // ==================== Helper Function Tests ====================
 
+    #[test]
+    fn test_staged_dirs_restore_replaced_skills() {
+        let temp = TempDir::new().unwrap();
+        let target = temp.path().join("skills/by-id/demo");
+        fs::create_dir_all(&target).unwrap();
+        fs::write(target.join("SKILL.md"), "# Local\n").unwrap();
+
+        let staged = StagedDirs::stage(temp.path(), "demo-bundle", &[target.clone()]).unwrap();
+        assert!(!target.exists());
+        // A half-written replacement is discarded on restore
+        fs::create_dir_all(&target).unwrap();
+        fs::write(target.join("SKILL.md"), "# Partial\n").unwrap();
+        staged.restore().unwrap();
+
+        assert_eq!(
+            fs::read_to_string(target.join("SKILL.md")).unwrap(),
+            "# Local\n"
+        );
+        assert!(!temp.path().join("bundles/staging/demo-bundle").exists());
+    }
+
     #[test]
     fn test_normalize_skill_list_simple() {
         let input = vec!["skill1".to_string(), "skill2".to_string()];
@@ -1751,20 +2784,20 @@ mod tests {
     // ==================== Expand Local Path Tests ====================
 
     #[test]
-    fn test_expand_local_path_relative() {
-        let result = expand_local_path("./relative");
+    fn test_expand_path_relative() {
+        let result = expand_path("./relative");
         assert_eq!(result, PathBuf::from("./relative"));
     }
 
     #[test]
-    fn test_expand_local_path_absolute() {
-        let result = expand_local_path("/absolute/path");
+    fn test_expand_path_absolute() {
+        let result = expand_path("/absolute/path");
         assert_eq!(result, PathBuf::from("/absolute/path"));
     }
 
     #[test]
-    fn test_expand_local_path_home_tilde() {
-        let result = expand_local_path("~");
+    fn test_expand_path_home_tilde() {
+        let result = expand_path("~");
         // Should either be home dir or "~" if no home dir
         if let Some(home) = dirs::home_dir() {
             assert_eq!(result, home);
@@ -1774,8 +2807,8 @@ mod tests {
     }
 
     #[test]
-    fn test_expand_local_path_home_subpath() {
-        let result = expand_local_path("~/subpath");
+    fn test_expand_path_home_subpath() {
+        let result = expand_path("~/subpath");
         if let Some(home) = dirs::home_dir() {
             assert_eq!(result, home.join("subpath"));
         } else {
@@ -1877,7 +2910,7 @@ mod tests {
 
         let args = TestCli::parse_from(["test", "create", "my-bundle", "--skills", "skill1"]);
         if let BundleCommand::Create(create) = args.cmd {
-            assert_eq!(create.name, "my-bundle");
+            assert_eq!(create.name.as_deref(), Some("my-bundle"));
             assert_eq!(create.bundle_version, "0.1.0"); // default
             assert_eq!(create.skills, vec!["skill1"]);
             assert!(!create.sign);
@@ -1911,7 +2944,7 @@ mod tests {
         ]);
 
         if let BundleCommand::Create(create) = args.cmd {
-            assert_eq!(create.name, "my-bundle");
+            assert_eq!(create.name.as_deref(), Some("my-bundle"));
             assert_eq!(create.bundle_version, "1.0.0");
             assert_eq!(create.id, Some("custom-id".to_string()));
             assert!(create.write_manifest);
@@ -1935,6 +2968,7 @@ mod tests {
             assert_eq!(install.source, "./bundle.msb");
             assert!(!install.no_verify);
             assert!(!install.force);
+            assert!(!install.with_deps);
             assert!(install.skills.is_empty());
         } else {
             panic!("Expected Install command");
@@ -1959,12 +2993,14 @@ mod tests {
             "skill1",
             "--force",
             "--no-verify",
+            "--with-deps",
         ]);
 
         if let BundleCommand::Install(install) = args.cmd {
             assert_eq!(install.source, "owner/repo@v1.0.0");
             assert!(install.force);
             assert!(install.no_verify);
+            assert!(install.with_deps);
             assert_eq!(install.skills, vec!["skill1"]);
         } else {
             panic!("Expected Install command");
@@ -2170,6 +3206,53 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_bundle_create_from_query() {
+        use clap::Parser;
+
+        #[derive(Parser)]
+        struct TestCli {
+            #[command(subcommand)]
+            cmd: BundleCommand,
+        }
+
+        let args = TestCli::parse_from([
+            "test",
+            "create",
+            "--from-query",
+            "tag:rust layer:project",
+            "--name",
+            "team-rust",
+            "--version",
+            "0.3.0",
+            "--no-deps",
+        ]);
+        let BundleCommand::Create(create) = args.cmd else {
+            panic!("expected create");
+        };
+        assert_eq!(create.name, None);
+        assert_eq!(create.name_flag.as_deref(), Some("team-rust"));
+        assert_eq!(create.from_query.as_deref(), Some("tag:rust layer:project"));
+        assert_eq!(create.bundle_version, "0.3.0");
+        assert!(create.no_deps);
+
+        // A name is still required, and a query replaces explicit skill lists.
+        assert!(TestCli::try_parse_from(["test", "create", "--from-query", "tag:rust"]).is_err());
+        assert!(
+            TestCli::try_parse_from([
+                "test",
+                "create",
+                "b",
+                "--from-query",
+                "tag:rust",
+                "--skills",
+                "x",
+            ])
+            .is_err()
+        );
+        assert!(TestCli::try_parse_from(["test", "create", "b", "--no-deps"]).is_err());
+    }
+
     #[test]
     fn test_bundle_create_sign_key_requires_sign() {
         use clap::Parser;
@@ -2332,13 +3415,14 @@ mod tests {
 
     #[test]
     fn test_looks_like_path_windows_style() {
-        // Windows-style paths shouldn't be recognized (we're on Unix)
-        assert!(!looks_like_path("C:\\Users\\name"));
+        assert!(looks_like_path("C:\\Users\\name"));
+        assert!(looks_like_path("C:/Users/name"));
+        assert!(looks_like_path("%USERPROFILE%\\bundles\\kit.msb"));
     }
 
     #[test]
-    fn test_expand_local_path_empty() {
-        let result = expand_local_path("");
+    fn test_expand_path_empty() {
+        let result = expand_path("");
         assert_eq!(result, PathBuf::from(""));
     }
 
@@ -2351,10 +3435,21 @@ mod tests {
             output: "/path/to/output.msb".to_string(),
             manifest_path: Some("/path/to/manifest.toml".to_string()),
             checksum: Some("sha256:abc123".to_string()),
+            skills: vec![BundleCreateSkill {
+                id: "rust-base".to_string(),
+                version: Some("1.0.0".to_string()),
+                size: 2048,
+                required_by: Some("rust-async".to_string()),
+            }],
+            total_size: 2048,
+            blobs_written: 1,
+            warnings: Vec::new(),
         };
         let json = serde_json::to_string(&report).unwrap();
         assert!(json.contains("\"id\":\"test-bundle\""));
         assert!(json.contains("\"checksum\":\"sha256:abc123\""));
+        assert!(json.contains("\"required_by\":\"rust-async\""));
+        assert!(json.contains("\"total_size\":2048"));
     }
 
     #[test]
@@ -0,0 +1,324 @@
+//! ms completions - Shell completion scripts
+//!
+//! `ms completions <shell>` prints clap's static completions. The zsh and
+//! fish scripts additionally complete skill ids, aliases and bundle ids by
+//! calling the hidden `ms __complete <kind> --prefix <word>`, which reads the
+//! database directly (no search index, no full [`AppContext`]) and prints
+//! nothing at all when there is nothing to offer, including when ms has not
+//! been initialized yet.
+//!
+//! [`AppContext`]: crate::app::AppContext
+
+use std::io::{self, Write};
+use std::path::Path;
+
+use clap::{Args, Command, CommandFactory, ValueEnum};
+use clap_complete::{Shell, generate};
+
+use crate::app::AppContext;
+use crate::bundler::registry::BundleRegistry;
+use crate::cli::Cli;
+use crate::error::Result;
+use crate::storage::Database;
+
+/// Most candidates `ms __complete` prints.
+const MAX_CANDIDATES: usize = 200;
+
+#[derive(Args, Debug)]
+pub struct CompletionsArgs {
+    /// Shell to generate completions for
+    #[arg(value_enum)]
+    pub shell: Shell,
+}
+
+#[derive(Args, Debug)]
+pub struct CompleteArgs {
+    /// What to complete
+    #[arg(value_enum)]
+    pub kind: CompleteKind,
+
+    /// Only candidates starting with this
+    #[arg(long, default_value = "")]
+    pub prefix: String,
+}
+
+/// Values `ms __complete` can list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CompleteKind {
+    SkillIds,
+    Aliases,
+    Bundles,
+}
+
+impl CompleteKind {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::SkillIds => "skill-ids",
+            Self::Aliases => "aliases",
+            Self::Bundles => "bundles",
+        }
+    }
+
+    const fn label(self) -> &'static str {
+        match self {
+            Self::SkillIds => "skill",
+            Self::Aliases => "alias",
+            Self::Bundles => "bundle",
+        }
+    }
+}
+
+/// Positional arguments completed dynamically: subcommand path, argument id.
+const DYNAMIC_ARGS: &[(&[&str], &str, CompleteKind)] = &[
+    (&["load"], "skill", CompleteKind::SkillIds),
+    (&["show"], "skill", CompleteKind::SkillIds),
+    (&["edit"], "skill", CompleteKind::SkillIds),
+    (&["diff"], "skill_a", CompleteKind::SkillIds),
+    (&["diff"], "skill_b", CompleteKind::SkillIds),
+    (&["simulate"], "skill", CompleteKind::SkillIds),
+    (&["quality"], "skill", CompleteKind::SkillIds),
+    (&["outcome"], "skill", CompleteKind::SkillIds),
+    (&["favorite"], "skill", CompleteKind::SkillIds),
+    (&["hide"], "skill", CompleteKind::SkillIds),
+    (&["unhide"], "skill", CompleteKind::SkillIds),
+    (&["stats"], "skill", CompleteKind::SkillIds),
+    (&["test"], "skill", CompleteKind::SkillIds),
+    (&["validate"], "skill", CompleteKind::SkillIds),
+    (&["why-suggested"], "skill", CompleteKind::SkillIds),
+    (&["feedback", "add"], "skill", CompleteKind::SkillIds),
+    (&["evidence", "show"], "skill_id", CompleteKind::SkillIds),
+    (&["dedup", "review"], "skill_a", CompleteKind::SkillIds),
+    (&["dedup", "review"], "skill_b", CompleteKind::SkillIds),
+    (&["experiment", "create"], "skill", CompleteKind::SkillIds),
+    (&["alias", "remove"], "alias", CompleteKind::Aliases),
+    (&["alias", "resolve"], "alias", CompleteKind::Aliases),
+    (&["bundle", "remove"], "bundle_id", CompleteKind::Bundles),
+    (&["bundle", "update"], "bundle_id", CompleteKind::Bundles),
+];
+
+/// `ms completions <shell>`: print the completion script.
+pub fn run(args: &CompletionsArgs) -> Result<()> {
+    let script = completion_script(args.shell);
+    io::stdout().lock().write_all(script.as_bytes())?;
+    Ok(())
+}
+
+/// `ms __complete <kind>`: print matching candidates, one per line. Never
+/// fails: any error means no candidates, so nothing leaks into the user's
+/// command line.
+pub fn run_complete(args: &CompleteArgs) -> Result<()> {
+    let Ok(ms_root) = AppContext::find_ms_root() else {
+        return Ok(());
+    };
+    let candidates = candidates(&ms_root, args.kind, &args.prefix).unwrap_or_default();
+    let mut out = io::stdout().lock();
+    for candidate in candidates {
+        if writeln!(out, "{candidate}").is_err() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Values of `kind` under `ms_root` starting with `prefix`.
+pub fn candidates(ms_root: &Path, kind: CompleteKind, prefix: &str) -> Result<Vec<String>> {
+    match kind {
+        CompleteKind::SkillIds => Database::open_existing(ms_root.join("ms.db"))?
+            .skill_ids_with_prefix(prefix, MAX_CANDIDATES),
+        CompleteKind::Aliases => Database::open_existing(ms_root.join("ms.db"))?
+            .aliases_with_prefix(prefix, MAX_CANDIDATES),
+        CompleteKind::Bundles => Ok(BundleRegistry::installed_ids(&ms_root.join("archive"))?
+            .into_iter()
+            .filter(|id| id.starts_with(prefix))
+            .take(MAX_CANDIDATES)
+            .collect()),
+    }
+}
+
+/// The completion script for `shell`.
+#[must_use]
+pub fn completion_script(shell: Shell) -> String {
+    match shell {
+        Shell::Zsh => zsh_script(),
+        Shell::Fish => {
+            let mut script = generated(shell, Cli::command());
+            script.push_str(&fish_dynamic());
+            script
+        }
+        _ => generated(shell, Cli::command()),
+    }
+}
+
+fn generated(shell: Shell, mut cmd: Command) -> String {
+    let mut buf = Vec::new();
+    generate(shell, &mut cmd, "ms", &mut buf);
+    String::from_utf8_lossy(&buf).into_owned()
+}
+
+/// Marker put in the help of dynamic arguments so their zsh specs can be
+/// found in the generated script.
+fn marker(kind: CompleteKind) -> String {
+    format!("ms-dynamic-{}", kind.name())
+}
+
+/// clap's zsh script with every dynamic argument's action replaced by
+/// `_ms_dynamic <kind>`.
+fn zsh_script() -> String {
+    let mut cmd = Cli::command();
+    for (path, arg, kind) in DYNAMIC_ARGS {
+        cmd = mut_subcommand(cmd, path, |sub| {
+            sub.mut_arg(*arg, |a| a.help(marker(*kind)))
+        });
+    }
+    let mut script = generated(Shell::Zsh, cmd);
+
+    for kind in [
+        CompleteKind::SkillIds,
+        CompleteKind::Aliases,
+        CompleteKind::Bundles,
+    ] {
+        // Specs look like `':skill -- <marker>:_default'`.
+        let needle = format!("-- {}:", marker(kind));
+        while let Some(start) = script.find(&needle) {
+            let Some(len) = script[start..].find('\'') else {
+                break;
+            };
+            script.replace_range(
+                start..start + len,
+                &format!("-- {}:_ms_dynamic {}", kind.label(), kind.name()),
+            );
+        }
+    }
+
+    // The helper must be defined before the trailer that runs `_ms`.
+    let helper = "\n_ms_dynamic() {\n    local -a candidates\n    \
+                  candidates=(${(f)\"$(ms __complete $1 --prefix \"$PREFIX\" 2>/dev/null)\"})\n    \
+                  compadd -a candidates\n}\n";
+    let split = script.find('\n').map_or(0, |i| i + 1);
+    script.insert_str(split, helper);
+    script
+}
+
+/// `complete` lines that feed dynamic arguments from `ms __complete`.
+fn fish_dynamic() -> String {
+    let mut lines: Vec<String> = Vec::new();
+    for (path, _, kind) in DYNAMIC_ARGS {
+        let condition = match path {
+            [command] => format!("__fish_ms_using_subcommand {command}"),
+            [command, sub, ..] => format!(
+                "__fish_ms_using_subcommand {command}; and __fish_seen_subcommand_from {sub}"
+            ),
+            [] => continue,
+        };
+        let line = format!(
+            "complete -c ms -n \"{condition}\" -f -a \"(ms __complete {} --prefix (commandline -ct) 2>/dev/null)\"\n",
+            kind.name()
+        );
+        if !lines.contains(&line) {
+            lines.push(line);
+        }
+    }
+    lines.concat()
+}
+
+fn mut_subcommand(cmd: Command, path: &[&str], f: impl FnOnce(Command) -> Command) -> Command {
+    match path.split_first() {
+        None => f(cmd),
+        Some((name, rest)) => cmd.mut_subcommand(*name, |sub| mut_subcommand(sub, rest, f)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::*;
+    use crate::storage::sqlite::SkillRecord;
+
+    fn record(id: &str) -> SkillRecord {
+        SkillRecord {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: "completion fixture".to_string(),
+            version: None,
+            author: None,
+            source_path: format!("/test/{id}"),
+            source_layer: "project".to_string(),
+            git_remote: None,
+            git_commit: None,
+            content_hash: "hash".to_string(),
+            body: String::new(),
+            metadata_json: "{}".to_string(),
+            assets_json: "{}".to_string(),
+            token_count: 0,
+            quality_score: 0.5,
+            indexed_at: "2025-01-01T00:00:00Z".to_string(),
+            modified_at: "2025-01-01T00:00:00Z".to_string(),
+            is_deprecated: false,
+            deprecation_reason: None,
+        }
+    }
+
+    #[test]
+    fn zsh_script_completes_dynamic_args() {
+        let script = completion_script(Shell::Zsh);
+        assert!(script.starts_with("#compdef ms\n\n_ms_dynamic() {"));
+        assert!(script.contains(":skill_a -- skill:_ms_dynamic skill-ids'"));
+        assert!(script.contains(":alias -- alias:_ms_dynamic aliases'"));
+        assert!(script.contains("_ms_dynamic bundles'"));
+        assert!(!script.contains("ms-dynamic-"));
+    }
+
+    #[test]
+    fn fish_script_completes_dynamic_args() {
+        let script = completion_script(Shell::Fish);
+        assert!(script.contains(
+            "complete -c ms -n \"__fish_ms_using_subcommand load\" -f -a \"(ms __complete skill-ids"
+        ));
+        assert!(script.contains(
+            "__fish_ms_using_subcommand bundle; and __fish_seen_subcommand_from remove\" -f -a \"(ms __complete bundles"
+        ));
+        assert_eq!(
+            script
+                .matches("__fish_ms_using_subcommand diff\" -f")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn candidates_come_from_the_database() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(candidates(dir.path(), CompleteKind::SkillIds, "").is_err());
+        assert!(
+            candidates(dir.path(), CompleteKind::Bundles, "")
+                .unwrap()
+                .is_empty()
+        );
+
+        let db = Database::open(dir.path().join("ms.db")).unwrap();
+        for id in ["rust-errors", "rust_style", "go-errors"] {
+            db.upsert_skill(&record(id)).unwrap();
+        }
+        db.upsert_alias("re", "rust-errors", "short", "").unwrap();
+        drop(db);
+
+        assert_eq!(
+            candidates(dir.path(), CompleteKind::SkillIds, "rust").unwrap(),
+            ["rust-errors", "rust_style"]
+        );
+        assert_eq!(
+            candidates(dir.path(), CompleteKind::SkillIds, "rust_").unwrap(),
+            ["rust_style"]
+        );
+        assert_eq!(
+            candidates(dir.path(), CompleteKind::Aliases, "r").unwrap(),
+            ["re"]
+        );
+
+        // Warm: the fast path stays well inside a keystroke.
+        let started = Instant::now();
+        candidates(dir.path(), CompleteKind::SkillIds, "go").unwrap();
+        assert!(started.elapsed() < Duration::from_millis(50));
+    }
+}
@@ -1,14 +1,18 @@
 //! ms config - Manage configuration
 
-use clap::Args;
+use clap::{Args, ValueEnum};
+use toml_edit::{DocumentMut, Item, TableLike};
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::app::AppContext;
 use crate::cli::output;
 use crate::cli::output::OutputFormat;
-use crate::config::Config;
-use crate::error::Result;
+use crate::config::{
+    Config, ConfigIssueLevel, check_config_file, check_config_str, project_config_path,
+    suggest_config_key, user_config_path,
+};
+use crate::error::{MsError, Result};
 
 /// `ms config` accepts both the bare git/gh-style forms and explicit verbs:
 ///
@@ -21,6 +25,11 @@ use crate::error::Result;
 /// - `ms config set <key> <value>`     -> write a key (verb form)
 /// - `ms config unset <key>`           -> remove a key
 /// - `ms config --unset <key>`         -> remove a key
+/// - `ms config doctor`                -> strict check of every config file
+///
+/// `set`/`unset` write `--scope user` (the per-user file) or `--scope project`
+/// (`.ms/config.toml`); without a scope they write the active config file.
+/// Values are coerced to the key's type and edits keep the file's comments.
 ///
 /// The verb forms exist so the muscle-memory `ms config get <key>` no longer
 /// silently writes a junk `get = "<key>"` top-level entry (issue #142). Writes
@@ -43,8 +52,45 @@ pub struct ConfigArgs {
     /// Unset a configuration key
     #[arg(long)]
     pub unset: bool,
+
+    /// Config file to write for set/unset
+    #[arg(long, value_enum)]
+    pub scope: Option<ConfigScope>,
+
+    /// With get: also print which file the effective value comes from
+    #[arg(long)]
+    pub show_origin: bool,
 }
 
+/// Which config file `set`/`unset` write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ConfigScope {
+    /// The per-user config (`~/.config/ms/config.toml`)
+    User,
+    /// The project config (`.ms/config.toml`), merged over the user's
+    Project,
+}
+
+/// Keys the MCP `config` tool may write: ranking weights and suggestion
+/// limits. Paths and every other key stay read-only over MCP.
+pub(crate) const MCP_WRITABLE_KEYS: &[&str] = &[
+    "search.bm25_weight",
+    "search.semantic_weight",
+    "search.rrf.k",
+    "search.rrf.bm25_weight",
+    "search.rrf.semantic_weight",
+    "search.rrf.min_score",
+    "search.context.project_boost",
+    "search.context.type_boost",
+    "search.batch.max_queries",
+    "search.batch.max_results",
+    "suggestions.cooldown_secs",
+    "suggestions.dismiss_cooldown_secs",
+    "suggestions.dismiss_multiplier",
+    "suggestions.max_dismiss_cooldown_secs",
+    "suggestions.cross_agent_cooldown_secs",
+];
+
 /// A resolved `ms config` invocation after verb/positional disambiguation.
 #[derive(Debug, PartialEq, Eq)]
 enum ConfigAction {
@@ -52,6 +98,7 @@ enum ConfigAction {
     Get { key: String },
     Set { key: String, value: String },
     Unset { key: String },
+    Doctor,
 }
 
 fn resolve_action(args: &ConfigArgs) -> Result<ConfigAction> {
@@ -79,6 +126,12 @@ fn resolve_action(args: &ConfigArgs) -> Result<ConfigAction> {
             }
             Ok(ConfigAction::List)
         }
+        Some("doctor") => {
+            if args.value.is_some() || args.extra.is_some() {
+                return Err(cfg_err("`config doctor` takes no arguments"));
+            }
+            Ok(ConfigAction::Doctor)
+        }
         // Verb forms: the first token is a verb, operands shift right by one.
         Some("get") => {
             let key = args
@@ -132,30 +185,105 @@ fn resolve_action(args: &ConfigArgs) -> Result<ConfigAction> {
 }
 
 pub fn run(ctx: &AppContext, args: &ConfigArgs) -> Result<()> {
+    let config_path = match args.scope {
+        None => ctx.config_path.clone(),
+        Some(ConfigScope::User) => user_config_path()
+            .ok_or_else(|| MsError::MissingConfig("config directory not found".to_string()))?,
+        Some(ConfigScope::Project) => project_config_path(&ctx.ms_root),
+    };
     let ctx = ConfigContext {
         config: ctx.config.clone(),
-        config_path: ctx.config_path.clone(),
+        config_path,
+        sources: ctx.config_sources.clone(),
         robot_mode: ctx.output_format != OutputFormat::Human,
     };
 
-    if args.list {
-        return emit_config(&ctx);
+    let action = if args.list {
+        ConfigAction::List
+    } else {
+        resolve_action(args)?
+    };
+    if args.scope.is_some()
+        && !matches!(
+            action,
+            ConfigAction::Set { .. } | ConfigAction::Unset { .. }
+        )
+    {
+        return Err(MsError::Config(
+            "--scope applies to `config set` and `config unset`".to_string(),
+        ));
+    }
+    if args.show_origin && !matches!(action, ConfigAction::Get { .. }) {
+        return Err(MsError::Config(
+            "--show-origin applies to `config get`".to_string(),
+        ));
     }
 
-    match resolve_action(args)? {
+    match action {
         ConfigAction::List => emit_config(&ctx),
-        ConfigAction::Get { key } => get_key(&ctx, &key),
+        ConfigAction::Get { key } => get_key(&ctx, &key, args.show_origin),
         ConfigAction::Set { key, value } => set_key(&ctx, &key, &value),
         ConfigAction::Unset { key } => unset_key(&ctx, &key),
+        ConfigAction::Doctor => doctor(&ctx),
     }
 }
 
 struct ConfigContext {
     config: Config,
     config_path: PathBuf,
+    sources: Vec<PathBuf>,
     robot_mode: bool,
 }
 
+/// Run the strict checks over every config file that exists and report the
+/// findings per file. Exits nonzero when any error-level issue is found.
+fn doctor(ctx: &ConfigContext) -> Result<()> {
+    let mut files = Vec::new();
+    for path in ctx.sources.iter().filter(|p| p.exists()) {
+        files.push((path.clone(), check_config_file(path)?));
+    }
+    let errors = files
+        .iter()
+        .flat_map(|(_, issues)| issues)
+        .filter(|issue| issue.level == ConfigIssueLevel::Error)
+        .count();
+
+    if ctx.robot_mode {
+        let report = serde_json::json!({
+            "status": if errors == 0 { "ok" } else { "error" },
+            "files": files
+                .iter()
+                .map(|(path, issues)| serde_json::json!({ "path": path, "issues": issues }))
+                .collect::<Vec<_>>(),
+        });
+        output::emit_json(&report)?;
+    } else if files.is_empty() {
+        println!("No config files found (using defaults)");
+    } else {
+        for (path, issues) in &files {
+            if issues.is_empty() {
+                println!("[ok] {}", path.display());
+                continue;
+            }
+            println!("{}", path.display());
+            for issue in issues {
+                let tag = match issue.level {
+                    ConfigIssueLevel::Error => "error",
+                    ConfigIssueLevel::Warning => "warning",
+                };
+                println!("  {tag}: {} {issue}", issue.key);
+            }
+        }
+    }
+
+    if errors > 0 {
+        return Err(crate::error::MsError::ValidationFailed(format!(
+            "{errors} config error(s) found"
+        )));
+    }
+    Ok(())
+}
+
 fn emit_config(ctx: &ConfigContext) -> Result<()> {
     if ctx.robot_mode {
         return output::emit_json(&ctx.config);
@@ -167,24 +295,49 @@ fn emit_config(ctx: &ConfigContext) -> Result<()> {
     Ok(())
 }
 
-fn get_key(ctx: &ConfigContext, key: &str) -> Result<()> {
+fn get_key(ctx: &ConfigContext, key: &str, show_origin: bool) -> Result<()> {
     let value = config_value_at(&ctx.config, key)?;
+    if !show_origin {
+        if ctx.robot_mode {
+            return output::emit_json(&value);
+        }
+        println!("{}", format_value(&value));
+        return Ok(());
+    }
+
+    let origin = value_origin(&ctx.sources, key)?;
     if ctx.robot_mode {
-        return output::emit_json(&value);
+        return output::emit_json(&serde_json::json!({
+            "key": key,
+            "value": value,
+            "origin": origin,
+        }));
     }
-    println!("{}", format_value(&value));
+    println!("{origin}\t{}", format_value(&value));
     Ok(())
 }
 
+/// Where the effective value of `key` comes from: a `MS_*` environment
+/// override, the last config file in merge order that sets it, or `default`.
+fn value_origin(sources: &[PathBuf], key: &str) -> Result<String> {
+    let env = format!("MS_{}", key.replace('.', "_").to_uppercase());
+    if std::env::var_os(&env).is_some() {
+        return Ok(format!("env:{env}"));
+    }
+    for path in sources.iter().rev().filter(|p| p.exists()) {
+        let raw = std::fs::read_to_string(path)
+            .map_err(|err| MsError::Config(format!("read config {}: {err}", path.display())))?;
+        let doc: toml::Value = toml::from_str(&raw)
+            .map_err(|err| MsError::Config(format!("parse config {}: {err}", path.display())))?;
+        if get_path(&doc, key).is_ok() {
+            return Ok(format!("file:{}", path.display()));
+        }
+    }
+    Ok("default".to_string())
+}
+
 fn set_key(ctx: &ConfigContext, key: &str, raw_value: &str) -> Result<()> {
-    let mut doc = load_config_doc(&ctx.config_path)?;
-    let value = parse_value(raw_value)?;
-    set_path(&mut doc, key, value.clone())?;
-    // Reject unknown/typo keys BEFORE persisting so a mistyped key (or a stray
-    // `get`/`set` verb that slipped through) can never silently land as a junk
-    // top-level entry (issue #142).
-    validate_key_known(&doc, key)?;
-    write_config_doc(&ctx.config_path, &doc)?;
+    let value = write_key(&ctx.config_path, key, raw_value)?;
 
     if ctx.robot_mode {
         let confirmation = serde_json::json!({
@@ -192,10 +345,49 @@ fn set_key(ctx: &ConfigContext, key: &str, raw_value: &str) -> Result<()> {
             "action": "set",
             "key": key,
             "value": value,
+            "path": ctx.config_path,
         });
         return output::emit_json(&confirmation);
     }
-    println!("Set {key} = {}", format_value(&value));
+    println!(
+        "Set {key} = {} in {}",
+        format_value(&value),
+        ctx.config_path.display()
+    );
+    Ok(())
+}
+
+/// Coerce `raw_value` to the type `key` has in [`Config`], validate it, and
+/// write it to the config file at `path`. Comments and layout of the file
+/// are kept. Returns the value written.
+pub(crate) fn write_key(path: &Path, key: &str, raw_value: &str) -> Result<toml::Value> {
+    let mut doc = load_config_doc(path)?;
+    let value = coerce_value(key, raw_value)?;
+    set_path(&mut doc, key, &value)?;
+    // Reject unknown/typo keys BEFORE persisting so a mistyped key (or a stray
+    // `get`/`set` verb that slipped through) can never silently land as a junk
+    // top-level entry (issue #142).
+    validate_doc(&doc, key)?;
+    write_config_doc(path, &doc)?;
+    Ok(value)
+}
+
+/// Check the edited document: `key` must be known, the value must
+/// deserialize into [`Config`], and range checks (weights, timeouts) must pass.
+fn validate_doc(doc: &DocumentMut, key: &str) -> Result<()> {
+    let rendered = doc.to_string();
+    let value: toml::Value =
+        toml::from_str(&rendered).map_err(|err| MsError::Config(format!("parse config: {err}")))?;
+    validate_key_known(&value, key)?;
+    if let Some(issue) = check_config_str(&rendered)?
+        .into_iter()
+        .find(|issue| issue.level == ConfigIssueLevel::Error && issue.key == key)
+    {
+        return Err(MsError::Config(format!(
+            "invalid value for `{key}`: {}",
+            issue.message
+        )));
+    }
     Ok(())
 }
 
@@ -210,19 +402,112 @@ fn set_key(ctx: &ConfigContext, key: &str, raw_value: &str) -> Result<()> {
 /// and are accepted. A type mismatch surfaces here as a parse error instead of
 /// corrupting the config file.
 fn validate_key_known(doc: &toml::Value, key: &str) -> Result<()> {
-    let typed: Config = doc.clone().try_into().map_err(|err| {
-        crate::error::MsError::Config(format!("invalid value for `{key}`: {err}"))
-    })?;
+    let typed: Config = doc
+        .clone()
+        .try_into()
+        .map_err(|err| MsError::Config(format!("invalid value for `{key}`: {err}")))?;
     let normalized = toml::Value::try_from(&typed)
-        .map_err(|err| crate::error::MsError::Config(format!("serialize config: {err}")))?;
+        .map_err(|err| MsError::Config(format!("serialize config: {err}")))?;
     if get_path(&normalized, key).is_err() {
-        return Err(crate::error::MsError::Config(format!(
-            "unknown config key: {key} (run `ms config --list` to see available keys)"
+        let hint = suggest_config_key(key).map_or_else(
+            || "run `ms config --list` to see available keys".to_string(),
+            |best| format!("did you mean `{best}`?"),
+        );
+        return Err(MsError::Config(format!(
+            "unknown config key: {key} ({hint})"
         )));
     }
     Ok(())
 }
 
+/// Parse `raw` as the type `key` has in the default [`Config`]. Keys without
+/// a default (optional values) fall back to TOML value syntax.
+fn coerce_value(key: &str, raw: &str) -> Result<toml::Value> {
+    let defaults = toml::Value::try_from(Config::default())
+        .map_err(|err| MsError::Config(format!("serialize config: {err}")))?;
+    let Ok(expected) = get_path(&defaults, key) else {
+        return parse_value(raw);
+    };
+    let invalid = |what: &str| {
+        MsError::Config(format!(
+            "invalid value for `{key}`: expected {what}, got `{raw}`"
+        ))
+    };
+    match expected {
+        toml::Value::Table(_) => Err(MsError::Config(format!(
+            "`{key}` is a table; set one of its keys instead"
+        ))),
+        toml::Value::Array(items) => parse_array(raw, items.first())
+            .map_err(|_| invalid("an array (JSON like `[\"a\", \"b\"]` or comma-separated `a,b`)")),
+        scalar => coerce_scalar(raw, &scalar).ok_or_else(|| invalid(type_name(&scalar))),
+    }
+}
+
+fn coerce_scalar(raw: &str, expected: &toml::Value) -> Option<toml::Value> {
+    let trimmed = raw.trim();
+    match expected {
+        toml::Value::Boolean(_) => match trimmed.to_ascii_lowercase().as_str() {
+            "true" | "yes" | "on" | "1" => Some(toml::Value::Boolean(true)),
+            "false" | "no" | "off" | "0" => Some(toml::Value::Boolean(false)),
+            _ => None,
+        },
+        toml::Value::Integer(_) => trimmed.parse().ok().map(toml::Value::Integer),
+        toml::Value::Float(_) => trimmed
+            .parse::<f64>()
+            .ok()
+            .filter(|v| v.is_finite())
+            .map(toml::Value::Float),
+        toml::Value::String(_) => match parse_value(raw) {
+            Ok(toml::Value::String(s)) => Some(toml::Value::String(s)),
+            _ => Some(toml::Value::String(raw.to_string())),
+        },
+        _ => parse_value(raw).ok(),
+    }
+}
+
+/// Arrays are given as JSON (`["a","b"]`), TOML, or comma syntax (`a,b`);
+/// comma items take the type of `sample`, the first default element.
+fn parse_array(raw: &str, sample: Option<&toml::Value>) -> Result<toml::Value> {
+    let trimmed = raw.trim();
+    if trimmed.starts_with('[') {
+        if let Ok(json) = serde_json::from_str::<serde_json::Value>(trimmed)
+            && json.is_array()
+        {
+            return toml::Value::try_from(json)
+                .map_err(|err| MsError::Config(format!("parse value: {err}")));
+        }
+        return match parse_value(trimmed)? {
+            array @ toml::Value::Array(_) => Ok(array),
+            _ => Err(MsError::Config("parse value: not an array".to_string())),
+        };
+    }
+
+    let string = toml::Value::String(String::new());
+    let sample = sample.unwrap_or(&string);
+    trimmed
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| {
+            coerce_scalar(item, sample)
+                .ok_or_else(|| MsError::Config(format!("parse value: bad item `{item}`")))
+        })
+        .collect::<Result<Vec<_>>>()
+        .map(toml::Value::Array)
+}
+
+const fn type_name(value: &toml::Value) -> &'static str {
+    match value {
+        toml::Value::Boolean(_) => "a boolean (true/false)",
+        toml::Value::Integer(_) => "an integer",
+        toml::Value::Float(_) => "a number",
+        toml::Value::String(_) => "a string",
+        toml::Value::Datetime(_) => "a datetime",
+        toml::Value::Array(_) => "an array",
+        toml::Value::Table(_) => "a table",
+    }
+}
+
 fn unset_key(ctx: &ConfigContext, key: &str) -> Result<()> {
     let mut doc = load_config_doc(&ctx.config_path)?;
     unset_path(&mut doc, key)?;
@@ -230,27 +515,24 @@ fn unset_key(ctx: &ConfigContext, key: &str) -> Result<()> {
     Ok(())
 }
 
-fn load_config_doc(path: &std::path::Path) -> Result<toml::Value> {
+fn load_config_doc(path: &Path) -> Result<DocumentMut> {
     if path.exists() {
         let raw = std::fs::read_to_string(path)
-            .map_err(|err| crate::error::MsError::Config(format!("read config: {err}")))?;
-        let doc = toml::from_str(&raw)
-            .map_err(|err| crate::error::MsError::Config(format!("parse config: {err}")))?;
-        Ok(doc)
+            .map_err(|err| MsError::Config(format!("read config: {err}")))?;
+        raw.parse()
+            .map_err(|err| MsError::Config(format!("parse config: {err}")))
     } else {
-        Ok(toml::Value::Table(toml::map::Map::new()))
+        Ok(DocumentMut::new())
     }
 }
 
-fn write_config_doc(path: &std::path::Path, doc: &toml::Value) -> Result<()> {
+fn write_config_doc(path: &Path, doc: &DocumentMut) -> Result<()> {
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent)
-            .map_err(|err| crate::error::MsError::Config(format!("create config dir: {err}")))?;
+            .map_err(|err| MsError::Config(format!("create config dir: {err}")))?;
     }
-    let rendered = toml::to_string_pretty(doc)
-        .map_err(|err| crate::error::MsError::Config(format!("render config: {err}")))?;
-    std::fs::write(path, rendered)
-        .map_err(|err| crate::error::MsError::Config(format!("write config: {err}")))?;
+    std::fs::write(path, doc.to_string())
+        .map_err(|err| MsError::Config(format!("write config: {err}")))?;
     Ok(())
 }
 
@@ -264,16 +546,16 @@ fn parse_value(raw: &str) -> Result<toml::Value> {
 
     let quoted = format!("value = {}", toml::Value::String(raw.to_string()));
     let parsed = toml::from_str::<toml::Value>(&quoted)
-        .map_err(|err| crate::error::MsError::Config(format!("parse value: {err}")))?;
+        .map_err(|err| MsError::Config(format!("parse value: {err}")))?;
     parsed
         .get("value")
         .cloned()
-        .ok_or_else(|| crate::error::MsError::Config("parse value: missing".to_string()))
+        .ok_or_else(|| MsError::Config("parse value: missing".to_string()))
 }
 
 fn config_value_at(config: &Config, key: &str) -> Result<toml::Value> {
     let doc = toml::Value::try_from(config)
-        .map_err(|err| crate::error::MsError::Config(format!("serialize config: {err}")))?;
+        .map_err(|err| MsError::Config(format!("serialize config: {err}")))?;
     get_path(&doc, key)
 }
 
@@ -282,69 +564,61 @@ fn get_path(doc: &toml::Value, key: &str) -> Result<toml::Value> {
     for part in key.split('.') {
         current = current
             .get(part)
-            .ok_or_else(|| crate::error::MsError::Config(format!("unknown key: {key}")))?;
+            .ok_or_else(|| MsError::Config(format!("unknown key: {key}")))?;
     }
     Ok(current.clone())
 }
 
-fn set_path(doc: &mut toml::Value, key: &str, value: toml::Value) -> Result<()> {
-    let parts: Vec<&str> = key.split('.').collect();
-    if parts.is_empty() {
-        return Err(crate::error::MsError::Config("empty key".to_string()));
+fn split_key(key: &str) -> Result<(&str, Vec<&str>)> {
+    let mut parts: Vec<&str> = key.split('.').collect();
+    match parts.pop() {
+        Some(last) if !last.is_empty() => Ok((last, parts)),
+        _ => Err(MsError::Config("empty key".to_string())),
     }
-
-    ensure_table(doc)?;
-    let mut current = doc;
-    for part in &parts[..parts.len() - 1] {
-        let table = current
-            .as_table_mut()
-            .ok_or_else(|| crate::error::MsError::Config("invalid config table".to_string()))?;
-        current = table
-            .entry((*part).to_string())
-            .or_insert_with(|| toml::Value::Table(toml::map::Map::new()));
-        ensure_table(current)?;
-    }
-
-    let table = current
-        .as_table_mut()
-        .ok_or_else(|| crate::error::MsError::Config("invalid config table".to_string()))?;
-    table.insert(parts[parts.len() - 1].to_string(), value);
-    Ok(())
 }
 
-fn unset_path(doc: &mut toml::Value, key: &str) -> Result<()> {
-    let parts: Vec<&str> = key.split('.').collect();
-    if parts.is_empty() {
-        return Err(crate::error::MsError::Config("empty key".to_string()));
+fn set_path(doc: &mut DocumentMut, key: &str, value: &toml::Value) -> Result<()> {
+    let (last, parents) = split_key(key)?;
+
+    let mut current: &mut dyn TableLike = doc.as_table_mut();
+    for part in parents {
+        let item = current.entry(part).or_insert_with(|| {
+            let mut table = toml_edit::Table::new();
+            table.set_implicit(true);
+            Item::Table(table)
+        });
+        current = item.as_table_like_mut().ok_or_else(not_a_table)?;
     }
 
-    ensure_table(doc)?;
-    let mut current = doc;
-    for part in &parts[..parts.len() - 1] {
-        let table = current
-            .as_table_mut()
-            .ok_or_else(|| crate::error::MsError::Config("invalid config table".to_string()))?;
-        current = table
-            .get_mut(*part)
-            .ok_or_else(|| crate::error::MsError::Config(format!("unknown key: {key}")))?;
-        ensure_table(current)?;
-    }
-
-    let table = current
-        .as_table_mut()
-        .ok_or_else(|| crate::error::MsError::Config("invalid config table".to_string()))?;
-    table.remove(parts[parts.len() - 1]);
+    let mut new_value: toml_edit::Value = value
+        .to_string()
+        .parse()
+        .map_err(|err| MsError::Config(format!("render value: {err}")))?;
+    // Keep an inline comment that followed the old value
+    if let Some(old) = current.get(last).and_then(Item::as_value) {
+        *new_value.decor_mut() = old.decor().clone();
+    }
+    current.insert(last, Item::Value(new_value));
     Ok(())
 }
 
-fn ensure_table(value: &mut toml::Value) -> Result<()> {
-    if value.is_table() {
-        Ok(())
-    } else {
-        Err(crate::error::MsError::Config(
-            "config path is not a table".to_string(),
-        ))
+fn unset_path(doc: &mut DocumentMut, key: &str) -> Result<()> {
+    let (last, parents) = split_key(key)?;
+
+    let mut current: &mut dyn TableLike = doc.as_table_mut();
+    for part in parents {
+        current = current
+            .get_mut(part)
+            .ok_or_else(|| MsError::Config(format!("unknown key: {key}")))?
+            .as_table_like_mut()
+            .ok_or_else(not_a_table)?;
     }
+    current.remove(last);
+    Ok(())
+}
+
+fn not_a_table() -> MsError {
+    MsError::Config("config path is not a table".to_string())
 }
 
 fn format_value(value: &toml::Value) -> String {
@@ -356,7 +630,9 @@ fn format_value(value: &toml::Value) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::{ConfigAction, ConfigArgs, ConfigContext, get_key, set_key, unset_key};
+    use super::{
+        ConfigAction, ConfigArgs, ConfigContext, get_key, set_key, unset_key, value_origin,
+    };
     use crate::config::Config;
 
     fn args(key: Option<&str>, value: Option<&str>, extra: Option<&str>) -> ConfigArgs {
@@ -366,6 +642,8 @@ mod tests {
             extra: extra.map(str::to_string),
             list: false,
             unset: false,
+            scope: None,
+            show_origin: false,
         }
     }
 
@@ -373,6 +651,7 @@ mod tests {
         ConfigContext {
             config: Config::default(),
             config_path: dir.join("config.toml"),
+            sources: vec![dir.join("config.toml")],
             robot_mode: false,
         }
     }
@@ -460,6 +739,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn doctor_verb_resolves_and_rejects_arguments() {
+        assert_eq!(
+            super::resolve_action(&args(Some("doctor"), None, None)).unwrap(),
+            ConfigAction::Doctor
+        );
+        assert!(super::resolve_action(&args(Some("doctor"), Some("x"), None)).is_err());
+    }
+
+    #[test]
+    fn doctor_reports_unknown_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let c = ctx(dir.path());
+        std::fs::write(&c.config_path, "[search]\nbm25_wieght = 0.5\n").unwrap();
+        let err = super::doctor(&c).unwrap_err();
+        assert!(matches!(err, crate::error::MsError::ValidationFailed(_)));
+
+        std::fs::write(&c.config_path, "[search]\nbm25_weight = 0.5\n").unwrap();
+        super::doctor(&c).expect("valid config passes");
+    }
+
     #[test]
     fn unset_verb_resolves() {
         let action =
@@ -478,7 +778,7 @@ mod tests {
     fn read_form_returns_known_value() {
         let dir = tempfile::tempdir().unwrap();
         // get_key reads from the in-memory config; a known key must succeed.
-        get_key(&ctx(dir.path()), "search.use_embeddings").expect("read known key");
+        get_key(&ctx(dir.path()), "search.use_embeddings", false).expect("read known key");
     }
 
     #[test]
@@ -550,4 +850,78 @@ mod tests {
         let written = std::fs::read_to_string(&c.config_path).unwrap();
         assert!(!written.contains("theme = \"dark\""), "got:\n{written}");
     }
+
+    #[test]
+    fn set_preserves_comments_and_layout() {
+        let dir = tempfile::tempdir().unwrap();
+        let c = ctx(dir.path());
+        std::fs::write(
+            &c.config_path,
+            "# team defaults\n[search]\nbm25_weight = 0.5 # tuned for docs\nuse_embeddings = true\n",
+        )
+        .unwrap();
+        set_key(&c, "search.bm25_weight", "0.3").expect("set weight");
+        let written = std::fs::read_to_string(&c.config_path).unwrap();
+        assert_eq!(
+            written,
+            "# team defaults\n[search]\nbm25_weight = 0.3 # tuned for docs\nuse_embeddings = true\n"
+        );
+    }
+
+    #[test]
+    fn set_coerces_values_to_the_key_type() {
+        let dir = tempfile::tempdir().unwrap();
+        let c = ctx(dir.path());
+        set_key(&c, "search.use_embeddings", "no").expect("bool alias");
+        set_key(&c, "search.bm25_weight", "1").expect("integer literal for a float");
+        set_key(&c, "skill_paths.global", "a, b").expect("comma array");
+        set_key(&c, "skill_paths.local", r#"["x", "y"]"#).expect("JSON array");
+        let written: toml::Value =
+            toml::from_str(&std::fs::read_to_string(&c.config_path).unwrap()).unwrap();
+        assert_eq!(written["search"]["use_embeddings"].as_bool(), Some(false));
+        assert_eq!(written["search"]["bm25_weight"].as_float(), Some(1.0));
+        assert_eq!(
+            written["skill_paths"]["global"].as_array().unwrap().len(),
+            2
+        );
+        assert_eq!(written["skill_paths"]["local"][1].as_str(), Some("y"));
+
+        let err = set_key(&c, "search.use_embeddings", "maybe").unwrap_err();
+        assert!(err.to_string().contains("expected a boolean"), "{err}");
+        let err = set_key(&c, "search.bm25_weight", "1.5").unwrap_err();
+        assert!(err.to_string().contains("between 0.0 and 1.0"), "{err}");
+        let err = set_key(&c, "search", "1").unwrap_err();
+        assert!(err.to_string().contains("is a table"), "{err}");
+    }
+
+    #[test]
+    fn set_unknown_key_suggests_the_closest_known_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let err = set_key(&ctx(dir.path()), "search.bm25_wieght", "0.5").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("did you mean `search.bm25_weight`"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn show_origin_reports_the_last_file_setting_the_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let user = dir.path().join("user.toml");
+        let project = dir.path().join("project.toml");
+        std::fs::write(
+            &user,
+            "[search]\nbm25_weight = 0.4\nsemantic_weight = 0.6\n",
+        )
+        .unwrap();
+        std::fs::write(&project, "[search]\nbm25_weight = 0.2\n").unwrap();
+        let sources = vec![user.clone(), project.clone()];
+
+        let origin = value_origin(&sources, "search.bm25_weight").unwrap();
+        assert_eq!(origin, format!("file:{}", project.display()));
+        let origin = value_origin(&sources, "search.semantic_weight").unwrap();
+        assert_eq!(origin, format!("file:{}", user.display()));
+        assert_eq!(value_origin(&sources, "cache.enabled").unwrap(), "default");
+    }
 }
@@ -13,6 +13,7 @@ use serde::Serialize;
 use crate::app::AppContext;
 use crate::cass::CassClient;
 use crate::cass::mining::{ExtractedPattern, PatternType, extract_from_session};
+use crate::cli::commands::cass_client;
 use crate::cli::output::OutputFormat;
 use crate::cli::output::{HumanLayout, emit_json};
 use crate::error::{MsError, Result};
@@ -59,6 +60,10 @@ pub struct CrossProjectSummaryArgs {
     /// Override path to cass binary
     #[arg(long)]
     pub cass_path: Option<PathBuf>,
+
+    /// Probe cass again instead of reusing cached health and capabilities
+    #[arg(long)]
+    pub no_cache: bool,
 }
 
 #[derive(Args, Debug)]
@@ -90,6 +95,10 @@ pub struct CrossProjectPatternsArgs {
     /// Override path to cass binary
     #[arg(long)]
     pub cass_path: Option<PathBuf>,
+
+    /// Probe cass again instead of reusing cached health and capabilities
+    #[arg(long)]
+    pub no_cache: bool,
 }
 
 #[derive(Args, Debug)]
@@ -129,6 +138,10 @@ pub struct CrossProjectGapsArgs {
     /// Override path to cass binary
     #[arg(long)]
     pub cass_path: Option<PathBuf>,
+
+    /// Probe cass again instead of reusing cached health and capabilities
+    #[arg(long)]
+    pub no_cache: bool,
 }
 
 pub fn run(ctx: &AppContext, args: &CrossProjectArgs) -> Result<()> {
@@ -146,12 +159,10 @@ fn run_summary(ctx: &AppContext, args: &CrossProjectSummaryArgs) -> Result<()> {
         ));
     }
 
-    let cass = cass_client(ctx, &args.cass_path);
+    let cass = cass_client(ctx, args.cass_path.as_deref(), args.no_cache);
 
     if !cass.is_available() {
-        return Err(MsError::CassUnavailable(
-            "cass binary not found or not executable".to_string(),
-        ));
+        return Err(cass.unavailable_error());
     }
 
     let matches = cass.search(&args.query, args.limit)?;
@@ -238,12 +249,10 @@ fn run_patterns(ctx: &AppContext, args: &CrossProjectPatternsArgs) -> Result<()>
         ));
     }
 
-    let cass = cass_client(ctx, &args.cass_path);
+    let cass = cass_client(ctx, args.cass_path.as_deref(), args.no_cache);
 
     if !cass.is_available() {
-        return Err(MsError::CassUnavailable(
-            "cass binary not found or not executable".to_string(),
-        ));
+        return Err(cass.unavailable_error());
     }
 
     let (aggregates, scanned) =
@@ -291,12 +300,10 @@ fn run_gaps(ctx: &AppContext, args: &CrossProjectGapsArgs) -> Result<()> {
         ));
     }
 
-    let cass = cass_client(ctx, &args.cass_path);
+    let cass = cass_client(ctx, args.cass_path.as_deref(), args.no_cache);
 
     if !cass.is_available() {
-        return Err(MsError::CassUnavailable(
-            "cass binary not found or not executable".to_string(),
-        ));
+        return Err(cass.unavailable_error());
     }
 
     let (aggregates, scanned) =
@@ -425,16 +432,6 @@ fn format_date(raw: Option<&str>) -> String {
     parsed.map_or_else(|| raw.to_string(), |dt| dt.format("%Y-%m-%d").to_string())
 }
 
-fn cass_client(ctx: &AppContext, override_path: &Option<PathBuf>) -> CassClient {
-    if let Some(cass_path) = override_path {
-        CassClient::with_binary(cass_path)
-    } else if let Some(ref cass_path) = ctx.config.cass.cass_path {
-        CassClient::with_binary(cass_path)
-    } else {
-        CassClient::new()
-    }
-}
-
 fn collect_pattern_aggregates(
     ctx: &AppContext,
     cass: &CassClient,
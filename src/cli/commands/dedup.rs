@@ -1,16 +1,30 @@
 //! ms dedup - Find and manage duplicate skills
 //!
 //! Scans skills for near-duplicates using semantic and structural similarity.
+//! `ms dedup clusters` groups them, diffs their rules and examples, and can
+//! draft a merged skill; pairs dismissed as "not duplicates" stay hidden.
+
+use std::io::{self, Write};
+use std::path::PathBuf;
 
 use clap::{Args, Subcommand};
 use colored::Colorize;
 
 use crate::app::AppContext;
+use crate::cli::commands::expand_path;
+use crate::cli::commands::index::index_one;
 use crate::cli::output::OutputFormat;
+use crate::core::SkillLayer;
+use crate::core::spec_lens::compile_markdown;
+use crate::dedup::clusters::{ClusterItem, DuplicateCluster, build_clusters, draft_merge};
 use crate::dedup::{DedupConfig, DeduplicationAction, DeduplicationEngine, DuplicatePair};
-use crate::error::Result;
+use crate::error::{MsError, Result};
+use crate::search::Embedder;
 use crate::search::embeddings::build_embedder;
-use crate::storage::Database;
+use crate::storage::{Database, SkillRecord};
+
+/// Differing rules/examples shown per cluster in human output.
+const MAX_DIFF_ITEMS: usize = 8;
 
 #[derive(Args, Debug)]
 pub struct DedupArgs {
@@ -28,6 +42,54 @@ pub enum DedupCommand {
     Merge(MergeArgs),
     /// Create an alias for a skill
     Alias(AliasArgs),
+    /// Cluster near-duplicates and diff their rules and examples
+    Clusters(ClustersArgs),
+    /// Mark two skills as not duplicates so later scans skip them
+    Dismiss(DismissArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct ClustersArgs {
+    /// Similarity threshold (0.0-1.0, default: 0.85)
+    #[arg(long, short)]
+    pub threshold: Option<f32>,
+
+    /// Offer to merge each cluster into a new skill (interactive; ignored
+    /// in robot mode)
+    #[arg(long)]
+    pub merge: bool,
+
+    /// Deprecate merged originals without asking, pointing them at the
+    /// merged skill
+    #[arg(long, requires = "merge")]
+    pub deprecate_originals: bool,
+
+    /// Directory for merged skills (default: first project skill path)
+    #[arg(long, requires = "merge")]
+    pub dir: Option<PathBuf>,
+
+    /// Maximum number of clusters
+    #[arg(long, default_value = "20")]
+    pub limit: usize,
+}
+
+#[derive(Args, Debug)]
+pub struct DismissArgs {
+    /// First skill ID
+    #[arg(required_unless_present = "list")]
+    pub skill_a: Option<String>,
+    /// Second skill ID
+    #[arg(required_unless_present = "list")]
+    pub skill_b: Option<String>,
+    /// Why the skills are not duplicates
+    #[arg(long)]
+    pub reason: Option<String>,
+    /// Report the pair again in later scans
+    #[arg(long, conflicts_with = "list")]
+    pub undo: bool,
+    /// List dismissed pairs
+    #[arg(long)]
+    pub list: bool,
 }
 
 #[derive(Args, Debug)]
@@ -78,19 +140,34 @@ pub fn run(ctx: &AppContext, args: &DedupArgs) -> Result<()> {
         DedupCommand::Review(review_args) => run_review(ctx, review_args),
         DedupCommand::Merge(merge_args) => run_merge(ctx, merge_args),
         DedupCommand::Alias(alias_args) => run_alias(ctx, alias_args),
+        DedupCommand::Clusters(clusters_args) => run_clusters(ctx, clusters_args),
+        DedupCommand::Dismiss(dismiss_args) => run_dismiss(ctx, dismiss_args),
     }
 }
 
-fn run_scan(ctx: &AppContext, args: &ScanArgs) -> Result<()> {
-    let db = ctx.db.as_ref();
-    let embedder = build_embedder(&ctx.config.search)?;
-
+/// Engine over stored embeddings, skipping dismissed pairs.
+fn build_engine<'a>(
+    db: &Database,
+    embedder: &'a dyn Embedder,
+    threshold: Option<f32>,
+) -> Result<DeduplicationEngine<'a>> {
     let mut config = DedupConfig::default();
-    if let Some(threshold) = args.threshold {
+    if let Some(threshold) = threshold {
         config.similarity_threshold = threshold;
     }
+    let dismissed = db
+        .list_dedup_dismissals()?
+        .into_iter()
+        .map(|d| (d.skill_a, d.skill_b));
+    Ok(DeduplicationEngine::new(config, embedder)
+        .with_stored_embeddings(db.get_all_embedding_records()?)
+        .with_dismissed(dismissed))
+}
 
-    let engine = DeduplicationEngine::new(config, embedder.as_ref());
+fn run_scan(ctx: &AppContext, args: &ScanArgs) -> Result<()> {
+    let db = ctx.db.as_ref();
+    let embedder = build_embedder(&ctx.config.search)?;
+    let engine = build_engine(db, embedder.as_ref(), args.threshold)?;
 
     if ctx.output_format != OutputFormat::Human {
         run_scan_robot(ctx, args, db, &engine)
@@ -406,6 +483,300 @@ fn run_alias(ctx: &AppContext, args: &AliasArgs) -> Result<()> {
     Ok(())
 }
 
+fn run_clusters(ctx: &AppContext, args: &ClustersArgs) -> Result<()> {
+    let db = ctx.db.as_ref();
+    let embedder = build_embedder(&ctx.config.search)?;
+    let engine = build_engine(db, embedder.as_ref(), args.threshold)?;
+    let threshold = args
+        .threshold
+        .unwrap_or_else(|| DedupConfig::default().similarity_threshold);
+
+    let skills = db.list_skills(10000, 0)?;
+    let pairs = engine.scan_all(db)?;
+    let mut clusters = build_clusters(&pairs, &skills);
+    let total_clusters = clusters.len();
+    clusters.truncate(args.limit);
+
+    if ctx.output_format != OutputFormat::Human {
+        let output = serde_json::json!({
+            "status": "ok",
+            "threshold": threshold,
+            "total_clusters": total_clusters,
+            "displayed_clusters": clusters.len(),
+            "reused_embeddings": engine.stored_embeddings(),
+            "clusters": clusters,
+            "merged": false,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    if clusters.is_empty() {
+        println!(
+            "{}",
+            format!("No duplicate clusters at threshold {threshold:.2}.").green()
+        );
+        return Ok(());
+    }
+    println!(
+        "Found {} duplicate cluster(s) at threshold {threshold:.2} (showing {}):",
+        total_clusters,
+        clusters.len()
+    );
+    println!();
+
+    for (i, cluster) in clusters.iter().enumerate() {
+        print_cluster(i + 1, cluster);
+        if args.merge {
+            review_cluster(ctx, args, cluster, &skills)?;
+        }
+        println!();
+    }
+
+    if !args.merge {
+        println!("{}", "Commands:".bold());
+        println!("  ms dedup clusters --merge            - Merge clusters interactively");
+        println!("  ms dedup dismiss <skill_a> <skill_b> - Mark a pair as not duplicates");
+    }
+    Ok(())
+}
+
+fn print_cluster(number: usize, cluster: &DuplicateCluster) {
+    println!(
+        "{} {} skills, max similarity {}",
+        format!("Cluster {number}:").bold(),
+        cluster.members.len(),
+        format!("{:.1}%", cluster.max_similarity * 100.0).yellow()
+    );
+    for (rank, member) in cluster.members.iter().enumerate() {
+        println!(
+            "  {}. {} ({}) [{}] quality {:.2}",
+            rank + 1,
+            member.name.bold(),
+            member.skill_id,
+            member.layer.dimmed(),
+            member.quality_score
+        );
+    }
+    let diff = &cluster.diff;
+    println!(
+        "  Shared: {} rule(s), {} example(s)",
+        diff.shared_rules, diff.shared_examples
+    );
+    print_diff_items("Differing rules", &diff.differing_rules);
+    print_diff_items("Differing examples", &diff.differing_examples);
+}
+
+fn print_diff_items(title: &str, items: &[ClusterItem]) {
+    if items.is_empty() {
+        return;
+    }
+    println!("  {title}:");
+    for item in items.iter().take(MAX_DIFF_ITEMS) {
+        println!(
+            "    - {} {}",
+            item.text,
+            format!("({})", item.skills.join(", ")).dimmed()
+        );
+    }
+    if items.len() > MAX_DIFF_ITEMS {
+        println!("    ... and {} more", items.len() - MAX_DIFF_ITEMS);
+    }
+}
+
+/// Ask what to do with one cluster: merge it, dismiss it, or skip.
+fn review_cluster(
+    ctx: &AppContext,
+    args: &ClustersArgs,
+    cluster: &DuplicateCluster,
+    skills: &[SkillRecord],
+) -> Result<()> {
+    let choice = prompt("  [m]erge, [n]ot duplicates, [s]kip (default: skip): ")?;
+    match choice.as_str() {
+        "m" | "merge" => {
+            let default_id = format!("{}-merged", cluster.members[0].skill_id);
+            let answer = prompt(&format!("  Merged skill id [{default_id}]: "))?;
+            let merged_id = if answer.is_empty() {
+                default_id
+            } else {
+                answer
+            };
+            merge_cluster(ctx, args, cluster, skills, &merged_id)
+        }
+        "n" | "not" => {
+            let now = chrono::Utc::now().to_rfc3339();
+            for pair in &cluster.pairs {
+                ctx.db.dismiss_dedup_pair(
+                    &pair.skill_a_id,
+                    &pair.skill_b_id,
+                    Some("reviewed in ms dedup clusters"),
+                    &now,
+                )?;
+            }
+            println!(
+                "  {} {} pair(s) marked as not duplicates",
+                "✓".green(),
+                cluster.pairs.len()
+            );
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+fn merge_cluster(
+    ctx: &AppContext,
+    args: &ClustersArgs,
+    cluster: &DuplicateCluster,
+    skills: &[SkillRecord],
+    merged_id: &str,
+) -> Result<()> {
+    let root = args.dir.clone().unwrap_or_else(|| {
+        ctx.config
+            .skill_paths
+            .project
+            .first()
+            .map_or_else(|| PathBuf::from(".ms/skills"), |path| expand_path(path))
+    });
+    let skill_path = root.join(merged_id).join("SKILL.md");
+    if skill_path.exists() {
+        return Err(MsError::ValidationFailed(format!(
+            "{} already exists; refusing to overwrite",
+            skill_path.display()
+        )));
+    }
+
+    let draft = draft_merge(cluster, skills, merged_id)?;
+    if let Some(parent) = skill_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&skill_path, compile_markdown(&draft.spec))?;
+    index_one(ctx, &skill_path, SkillLayer::Project)?;
+    println!(
+        "  {} Wrote {} ({} duplicate item(s) collapsed, {} conflict(s) flagged)",
+        "✓".green(),
+        skill_path.display(),
+        draft.collapsed,
+        draft.conflicts
+    );
+
+    let deprecate = args.deprecate_originals
+        || matches!(
+            prompt(&format!(
+                "  Deprecate the originals in favor of {merged_id}? [y/N]: "
+            ))?
+            .as_str(),
+            "y" | "yes"
+        );
+    if deprecate {
+        let reason = format!("Merged into {merged_id}");
+        let created_at = chrono::Utc::now().to_rfc3339();
+        for source in &draft.sources {
+            ctx.db
+                .update_skill_deprecation(source, true, Some(&reason))?;
+            ctx.db
+                .upsert_alias(source, merged_id, "deprecated", &created_at)?;
+            if let Some(record) = ctx.db.get_skill(source)? {
+                ctx.search.index_skill(&record)?;
+            }
+        }
+        ctx.search.commit()?;
+        println!(
+            "  {} Deprecated {} (aliased to {merged_id})",
+            "✓".green(),
+            draft.sources.join(", ")
+        );
+    }
+    Ok(())
+}
+
+fn prompt(message: &str) -> Result<String> {
+    print!("{message}");
+    io::stdout()
+        .flush()
+        .map_err(|err| MsError::Config(format!("prompt flush: {err}")))?;
+    let mut input = String::new();
+    io::stdin()
+        .read_line(&mut input)
+        .map_err(|err| MsError::Config(format!("prompt read: {err}")))?;
+    Ok(input.trim().to_lowercase())
+}
+
+fn run_dismiss(ctx: &AppContext, args: &DismissArgs) -> Result<()> {
+    let db = ctx.db.as_ref();
+    if args.list {
+        let dismissals = db.list_dedup_dismissals()?;
+        if ctx.output_format != OutputFormat::Human {
+            let output = serde_json::json!({
+                "status": "ok",
+                "count": dismissals.len(),
+                "dismissals": dismissals,
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        } else if dismissals.is_empty() {
+            println!("No dismissed pairs.");
+        } else {
+            for d in &dismissals {
+                println!(
+                    "  {} <-> {}  {}",
+                    d.skill_a,
+                    d.skill_b,
+                    d.reason.as_deref().unwrap_or_default().dimmed()
+                );
+            }
+        }
+        return Ok(());
+    }
+
+    let (Some(skill_a), Some(skill_b)) = (args.skill_a.as_deref(), args.skill_b.as_deref()) else {
+        return Err(MsError::ValidationFailed(
+            "dismiss needs two skill ids".to_string(),
+        ));
+    };
+    if skill_a == skill_b {
+        return Err(MsError::ValidationFailed(
+            "cannot dismiss a skill against itself".to_string(),
+        ));
+    }
+
+    let changed = if args.undo {
+        db.delete_dedup_dismissal(skill_a, skill_b)?
+    } else {
+        for id in [skill_a, skill_b] {
+            if db.get_skill(id)?.is_none() {
+                return Err(MsError::SkillNotFound(format!("skill not found: {id}")));
+            }
+        }
+        let now = chrono::Utc::now().to_rfc3339();
+        db.dismiss_dedup_pair(skill_a, skill_b, args.reason.as_deref(), &now)?;
+        true
+    };
+
+    if ctx.output_format != OutputFormat::Human {
+        let output = serde_json::json!({
+            "status": "ok",
+            "action": if args.undo { "undismiss" } else { "dismiss" },
+            "skill_a": skill_a,
+            "skill_b": skill_b,
+            "changed": changed,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else if args.undo && !changed {
+        println!("{skill_a} <-> {skill_b} was not dismissed.");
+    } else if args.undo {
+        println!(
+            "{} {skill_a} <-> {skill_b} will be reported again",
+            "✓".green()
+        );
+    } else {
+        println!(
+            "{} {skill_a} <-> {skill_b} marked as not duplicates",
+            "✓".green()
+        );
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -444,6 +815,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_clusters_args_parse() {
+        let cli = TestCli::parse_from(["test", "clusters", "--threshold", "0.8", "--merge"]);
+        if let DedupCommand::Clusters(args) = cli.command {
+            assert_eq!(args.threshold, Some(0.8));
+            assert!(args.merge);
+            assert!(!args.deprecate_originals);
+            assert_eq!(args.limit, 20);
+        } else {
+            panic!("Expected Clusters command");
+        }
+
+        assert!(TestCli::try_parse_from(["test", "clusters", "--deprecate-originals"]).is_err());
+    }
+
+    #[test]
+    fn test_dismiss_args_parse() {
+        let cli = TestCli::parse_from(["test", "dismiss", "a", "b", "--reason", "different"]);
+        if let DedupCommand::Dismiss(args) = cli.command {
+            assert_eq!(args.skill_a.as_deref(), Some("a"));
+            assert_eq!(args.skill_b.as_deref(), Some("b"));
+            assert_eq!(args.reason.as_deref(), Some("different"));
+        } else {
+            panic!("Expected Dismiss command");
+        }
+
+        assert!(TestCli::try_parse_from(["test", "dismiss", "--list"]).is_ok());
+        assert!(TestCli::try_parse_from(["test", "dismiss", "a"]).is_err());
+    }
+
     #[test]
     fn test_merge_args_parse() {
         let cli = TestCli::parse_from(["test", "merge", "primary", "secondary"]);
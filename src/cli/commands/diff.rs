@@ -1,4 +1,12 @@
 //! ms diff - Semantic diff between skills
+//!
+//! Either side may be `skill@revision` to compare against the SKILL.md that
+//! the git archive held at that revision (`HEAD~3`, a tag, a commit id).
+//! `--overrides` compares a skill with itself under the project's
+//! `.ms/overrides.toml`. `--upstream` compares a fork with the read-only
+//! skill it was forked from and reports whether that skill has changed since.
+
+use std::collections::HashMap;
 
 use clap::Args;
 
@@ -7,16 +15,28 @@ use crate::cli::commands::resolve_skill_markdown;
 use crate::cli::output;
 use crate::cli::output::OutputFormat;
 use crate::core::SkillSpec;
+use crate::core::fork::{Upstream, upstream_status};
+use crate::core::layering::{SectionDiff, compute_section_diff};
 use crate::core::spec_lens::parse_markdown;
-use crate::error::Result;
+use crate::error::{MsError, Result};
 
 #[derive(Args, Debug)]
 pub struct DiffArgs {
-    /// First skill
+    /// First skill (`skill@revision` reads it from the git archive)
     pub skill_a: String,
 
-    /// Second skill
-    pub skill_b: String,
+    /// Second skill (`skill@revision` reads it from the git archive)
+    #[arg(required_unless_present_any = ["overrides", "upstream"])]
+    pub skill_b: Option<String>,
+
+    /// Compare the upstream skill with its effective version under the
+    /// project's overrides
+    #[arg(long, conflicts_with = "skill_b")]
+    pub overrides: bool,
+
+    /// Compare a fork with the skill it was forked from and flag upstream drift
+    #[arg(long, conflicts_with_all = ["skill_b", "overrides"])]
+    pub upstream: bool,
 
     /// Show only structural differences
     #[arg(long)]
@@ -31,45 +51,222 @@ pub fn run(_ctx: &AppContext, _args: &DiffArgs) -> Result<()> {
     let ctx = _ctx;
     let args = _args;
 
-    let path_a = resolve_skill_markdown(ctx, &args.skill_a)?;
-    let path_b = resolve_skill_markdown(ctx, &args.skill_b)?;
-
-    let spec_a = parse_markdown(&std::fs::read_to_string(&path_a).map_err(|err| {
-        crate::error::MsError::Config(format!("read {}: {err}", path_a.display()))
-    })?)?;
-    let spec_b = parse_markdown(&std::fs::read_to_string(&path_b).map_err(|err| {
-        crate::error::MsError::Config(format!("read {}: {err}", path_b.display()))
-    })?)?;
+    let mut upstream = None;
+    let (label_a, spec_a, label_b, spec_b) = if args.upstream {
+        let (label_b, spec_b) = load_side(ctx, &args.skill_a)?;
+        let (label_a, spec_a, status) = origin_side(&spec_b)?;
+        upstream = Some(status);
+        (label_a, spec_a, label_b, spec_b)
+    } else {
+        let (label_a, spec_a) = load_side(ctx, &args.skill_a)?;
+        let (label_b, spec_b) = match args.skill_b.as_deref() {
+            Some(skill_b) => load_side(ctx, skill_b)?,
+            None => effective_side(ctx, &args.skill_a, &spec_a)?,
+        };
+        (label_a, spec_a, label_b, spec_b)
+    };
 
     let diffs = diff_specs(&spec_a, &spec_b, args.structure_only);
-    let same = diffs.is_empty();
+    let sections = section_changes(&spec_a, &spec_b, args.structure_only);
+    let same = diffs.is_empty() && sections.is_empty();
 
     if ctx.output_format != OutputFormat::Human || args.format == "json" {
         let payload = DiffReport {
-            skill_a: path_a.display().to_string(),
-            skill_b: path_b.display().to_string(),
+            skill_a: label_a,
+            skill_b: label_b,
             same,
             differences: diffs,
+            sections,
+            upstream,
         };
         return output::emit_json(&payload);
     }
 
+    match &upstream {
+        Some(Upstream::Drifted { .. }) => {
+            println!("Upstream changed since the fork: {label_a}");
+            println!();
+        }
+        Some(Upstream::Missing) => {
+            println!("Upstream no longer exists: {label_a}");
+            return Ok(());
+        }
+        Some(Upstream::Current) | None => {}
+    }
     if same {
         println!("No differences.");
     } else {
         for diff in diffs {
             println!("- {diff}");
         }
+        if !sections.is_empty() {
+            println!();
+            println!("Sections ({label_a} -> {label_b}):");
+            for change in &sections {
+                println!("  {}", change.summary());
+            }
+        }
     }
     Ok(())
 }
 
+/// Split `skill@revision`; a path that exists as written is never split.
+fn split_revision(input: &str) -> (&str, Option<&str>) {
+    if crate::cli::commands::expand_path(input).exists() {
+        return (input, None);
+    }
+    match input.rsplit_once('@') {
+        Some((skill, revision)) if !skill.is_empty() && !revision.is_empty() => {
+            (skill, Some(revision))
+        }
+        _ => (input, None),
+    }
+}
+
+/// Load one side of the diff, returning a display label and the parsed spec.
+fn load_side(ctx: &AppContext, input: &str) -> Result<(String, SkillSpec)> {
+    let (skill, revision) = split_revision(input);
+    let Some(revision) = revision else {
+        let path = resolve_skill_markdown(ctx, input)?;
+        let spec = parse_markdown(
+            &std::fs::read_to_string(&path)
+                .map_err(|err| MsError::Config(format!("read {}: {err}", path.display())))?,
+        )?;
+        return Ok((path.display().to_string(), spec));
+    };
+
+    // The skill may no longer exist in the index; fall back to the raw id.
+    let skill_id = ctx.canonical_skill_id(skill)?;
+    let markdown = ctx.git.read_markdown_at(&skill_id, revision)?;
+    Ok((format!("{skill_id}@{revision}"), parse_markdown(&markdown)?))
+}
+
+/// The effective side of `--overrides`: `upstream` with the project's
+/// overrides for the skill applied.
+fn effective_side(
+    ctx: &AppContext,
+    input: &str,
+    upstream: &SkillSpec,
+) -> Result<(String, SkillSpec)> {
+    let skill_id = ctx.canonical_skill_id(split_revision(input).0)?;
+    let overrides = ctx.project_overrides()?;
+    let mut effective = upstream.clone();
+    effective.metadata.id.clone_from(&skill_id);
+    if overrides.apply(&mut effective).is_none() {
+        return Err(MsError::NotFound(format!(
+            "no overrides for {skill_id} in {}",
+            overrides.path.display()
+        )));
+    }
+    effective.metadata.id.clone_from(&upstream.metadata.id);
+    Ok((format!("{skill_id} (overridden)"), effective))
+}
+
+/// The origin side of `--upstream`: the skill `fork` was forked from, as it
+/// is now, and whether it changed since the fork.
+fn origin_side(fork: &SkillSpec) -> Result<(String, SkillSpec, Upstream)> {
+    let Some(provenance) = &fork.metadata.forked_from else {
+        return Err(MsError::ValidationFailed(format!(
+            "{} is not a fork; create one with `ms edit {} --fork-to project`",
+            fork.metadata.id, fork.metadata.id
+        )));
+    };
+    let status = upstream_status(provenance)?;
+    let label = format!("{} ({})", provenance.path, provenance.layer);
+    if status == Upstream::Missing {
+        return Ok((label, fork.clone(), status));
+    }
+    let raw = std::fs::read_to_string(&provenance.path)
+        .map_err(|err| MsError::Config(format!("read {}: {err}", provenance.path)))?;
+    Ok((label, parse_markdown(&raw)?, status))
+}
+
 #[derive(serde::Serialize)]
 struct DiffReport {
     skill_a: String,
     skill_b: String,
     same: bool,
     differences: Vec<String>,
+    sections: Vec<SectionChange>,
+    /// Fork point comparison for `--upstream`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    upstream: Option<Upstream>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+enum SectionStatus {
+    Added,
+    Removed,
+    Modified,
+}
+
+/// A section that differs between the two sides, matched by section id.
+///
+/// In `diff`, "higher" is the second skill and "lower" the first, so
+/// `higher_only` blocks were added and `lower_only` blocks were removed.
+#[derive(Debug, Clone, serde::Serialize)]
+struct SectionChange {
+    section_id: String,
+    status: SectionStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    diff: Option<SectionDiff>,
+}
+
+impl SectionChange {
+    fn summary(&self) -> String {
+        match (&self.status, &self.diff) {
+            (SectionStatus::Added, _) => format!("+ {}", self.section_id),
+            (SectionStatus::Removed, _) => format!("- {}", self.section_id),
+            (SectionStatus::Modified, Some(diff)) => format!(
+                "~ {} (blocks: +{} -{} ~{})",
+                self.section_id,
+                diff.higher_only.len(),
+                diff.lower_only.len(),
+                diff.modified.len()
+            ),
+            (SectionStatus::Modified, None) => format!("~ {}", self.section_id),
+        }
+    }
+}
+
+fn section_changes(a: &SkillSpec, b: &SkillSpec, structure_only: bool) -> Vec<SectionChange> {
+    let a_sections: HashMap<&str, _> = a.sections.iter().map(|s| (s.id.as_str(), s)).collect();
+    let b_ids: HashMap<&str, ()> = b.sections.iter().map(|s| (s.id.as_str(), ())).collect();
+
+    let mut changes = Vec::new();
+    for section_b in &b.sections {
+        let Some(section_a) = a_sections.get(section_b.id.as_str()) else {
+            changes.push(SectionChange {
+                section_id: section_b.id.clone(),
+                status: SectionStatus::Added,
+                diff: None,
+            });
+            continue;
+        };
+        let mut diff = compute_section_diff(section_b, section_a);
+        if structure_only {
+            diff.modified.clear();
+        }
+        if !diff.higher_only.is_empty() || !diff.lower_only.is_empty() || !diff.modified.is_empty()
+        {
+            changes.push(SectionChange {
+                section_id: section_b.id.clone(),
+                status: SectionStatus::Modified,
+                diff: Some(diff),
+            });
+        }
+    }
+    for section_a in &a.sections {
+        if !b_ids.contains_key(section_a.id.as_str()) {
+            changes.push(SectionChange {
+                section_id: section_a.id.clone(),
+                status: SectionStatus::Removed,
+                diff: None,
+            });
+        }
+    }
+    changes
 }
 
 fn diff_specs(a: &SkillSpec, b: &SkillSpec, structure_only: bool) -> Vec<String> {
@@ -134,3 +331,56 @@ fn diff_specs(a: &SkillSpec, b: &SkillSpec, structure_only: bool) -> Vec<String>
 
     diffs
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(markdown: &str) -> SkillSpec {
+        parse_markdown(markdown).unwrap()
+    }
+
+    #[test]
+    fn splits_revision_suffix() {
+        assert_eq!(
+            split_revision("rust-errors@HEAD~3"),
+            ("rust-errors", Some("HEAD~3"))
+        );
+        assert_eq!(
+            split_revision("rust-errors@v1.2"),
+            ("rust-errors", Some("v1.2"))
+        );
+        assert_eq!(split_revision("rust-errors"), ("rust-errors", None));
+        assert_eq!(split_revision("rust-errors@"), ("rust-errors@", None));
+        assert_eq!(split_revision("@HEAD"), ("@HEAD", None));
+    }
+
+    #[test]
+    fn reports_added_removed_and_modified_sections() {
+        let old = spec("# Skill\n\n## Intro\n\nHello\n\n## Legacy\n\nOld notes\n");
+        let new = spec("# Skill\n\n## Intro\n\nHello again\n\n## Usage\n\nRun it\n");
+
+        let changes = section_changes(&old, &new, false);
+        let summary: Vec<(String, SectionStatus)> = changes
+            .iter()
+            .map(|c| (c.section_id.clone(), c.status))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("intro".to_string(), SectionStatus::Modified),
+                ("usage".to_string(), SectionStatus::Added),
+                ("legacy".to_string(), SectionStatus::Removed),
+            ]
+        );
+        let intro = changes[0].diff.as_ref().unwrap();
+        assert_eq!(intro.modified.len(), 1);
+        assert!(intro.modified[0].higher_content.contains("Hello again"));
+        assert!(intro.modified[0].lower_content.contains("Hello"));
+
+        // Content-only edits vanish under --structure-only.
+        let structural = section_changes(&old, &new, true);
+        assert!(structural.iter().all(|c| c.section_id != "intro"));
+        assert!(section_changes(&old, &old, false).is_empty());
+    }
+}
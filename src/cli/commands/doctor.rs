@@ -1,20 +1,39 @@
 //! ms doctor - Health checks and repairs
 
-#[cfg(target_os = "linux")]
 use std::path::Path;
 use std::sync::Arc;
 
-use clap::Args;
+use clap::{Args, Subcommand};
+use colored::Colorize;
+use serde::Serialize;
 use tracing::debug;
 
+use crate::agent_detection::{
+    AgentDetectionService, ClaudeHook, HookStatus, IntegrationAction, IntegrationWriter,
+    configure_agents,
+};
 use crate::app::AppContext;
+use crate::cli::commands::cass_client;
+use crate::cli::commands::index::{
+    LayerConflict, apply_layer_resolution, compact_index, load_layer_conflicts, store_embedding,
+};
+use crate::cli::commands::prune::format_size;
+use crate::cli::output::emit_json;
+use crate::config::{ConfigIssueLevel, check_config_file};
+use crate::core::lookup::find_ambiguities;
 use crate::core::recovery::{RecoveryManager, RecoveryReport};
-use crate::error::Result;
+use crate::core::{ConflictStrategy, LayeredRegistry, SkillLayer};
+use crate::error::{MsError, Result};
 use crate::output::{
     OutputModeReport, is_agent_environment, is_ci_environment, is_ide_environment,
 };
-use crate::security::{SafetyGate, scan_secrets_summary};
+use crate::search::health::{self, IndexProblem};
+use crate::search::{IndexStats, SearchIndex, VectorIndex, build_embedder};
+use crate::security::SafetyGate;
+use crate::storage::sqlite::LayerResolutionRecord;
+use crate::storage::tombstone::TombstoneManager;
 use crate::storage::tx::GlobalLock;
+use crate::storage::{Provenance, TxManager};
 
 /// Conditional `println!` for the doctor command. Emits human-readable
 /// output only when the active OutputFormat is *not* machine-readable
@@ -67,14 +86,52 @@ pub struct DoctorArgs {
     /// Run comprehensive recovery diagnostics
     #[arg(long)]
     pub comprehensive: bool,
+
+    /// Inspect schema migrations without applying them (works on databases
+    /// that refuse to open)
+    #[arg(long)]
+    pub migrations: bool,
+
+    /// Drop cached cass health and capabilities and probe cass again
+    #[arg(long)]
+    pub refresh_cass: bool,
+
+    #[command(subcommand)]
+    pub command: Option<DoctorCommand>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum DoctorCommand {
+    /// List and resolve skills defined differently in several layers
+    Conflicts(DoctorConflictsArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct DoctorConflictsArgs {
+    /// Only this skill id
+    pub skill: Option<String>,
+
+    /// Resolve without prompting: prefer-project | prefer-global | newest | merge
+    #[arg(long)]
+    pub strategy: Option<String>,
+
+    /// Clear remembered resolutions (for SKILL, or all)
+    #[arg(long)]
+    pub forget: bool,
 }
 
 pub fn run(ctx: &AppContext, args: &DoctorArgs) -> Result<()> {
+    if let Some(DoctorCommand::Conflicts(conflict_args)) = &args.command {
+        return run_conflicts(ctx, conflict_args);
+    }
+
     debug!(target: "doctor", mode = ?ctx.output_format, "output mode selected");
     debug!(target: "doctor", stage = "checks_start");
 
     let mut issues_found = 0;
     let mut issues_fixed = 0;
+    let mut embeddings = None;
+    let mut index_size = None;
     let verbose = ctx.verbosity > 0;
 
     say!(ctx, "{}", "ms doctor - Health Checks");
@@ -99,6 +156,11 @@ pub fn run(ctx: &AppContext, args: &DoctorArgs) -> Result<()> {
         }
     }
 
+    // Strict-check the config files (unknown keys, out-of-range values)
+    if run_only.is_none() {
+        issues_found += check_config(ctx, verbose)?;
+    }
+
     // Check database integrity
     if run_only.is_none() {
         issues_found += check_database(ctx, verbose)?;
@@ -109,11 +171,72 @@ pub fn run(ctx: &AppContext, args: &DoctorArgs) -> Result<()> {
         issues_found += check_git_archive(ctx, verbose)?;
     }
 
+    // Check the search index (stale locks, missing meta, corrupt segments)
+    if run_only.is_none() {
+        issues_found += check_search_index(ctx, args.fix, verbose, &mut issues_fixed)?;
+    }
+
+    // Report index size and deleted documents awaiting compaction
+    if run_only.is_none() {
+        issues_found += check_index_size(ctx, args.fix, &mut issues_fixed, &mut index_size)?;
+    }
+
+    // Report the embedding backend and check stored vectors match it
+    if run_only.is_none() {
+        issues_found +=
+            check_embeddings(ctx, args.fix, verbose, &mut issues_fixed, &mut embeddings)?;
+    }
+
     // Check for incomplete transactions
     if run_only.is_none() {
         issues_found += check_transactions(ctx, args.fix, verbose, &mut issues_fixed)?;
     }
 
+    // Report sensitive skills and whether their key is reachable
+    if run_only.is_none() {
+        issues_found += check_encryption(ctx, verbose)?;
+    }
+
+    // Check for aliases and names that resolve to an unexpected skill
+    if run_only.is_none() {
+        issues_found += check_ambiguity(ctx, verbose)?;
+    }
+
+    // Check that project overrides target existing skills and blocks
+    if run_only.is_none() {
+        issues_found += check_overrides(ctx, verbose)?;
+    }
+
+    // Warn when low-confidence generalizations pile up unreviewed
+    if run_only.is_none() {
+        issues_found += check_review_queue(ctx, verbose)?;
+    }
+
+    // Warn when stored quality scores used other `[quality]` weights
+    if run_only.is_none() {
+        issues_found += check_quality_weights(ctx, verbose)?;
+    }
+
+    // Re-probe cass, replacing its cached health and capabilities
+    if run_only.is_none() && args.refresh_cass {
+        issues_found += check_cass(ctx, true, verbose)?;
+    }
+
+    // Check context budgets (only when any are configured)
+    if run_only.is_none() && !ctx.config.budget.is_empty() {
+        issues_found += check_budgets(ctx, verbose)?;
+    }
+
+    // Register the ms MCP server with detected agents
+    if run_only.is_none() && args.fix {
+        issues_found += check_agent_integrations(ctx, args.fix, verbose, &mut issues_fixed)?;
+    }
+
+    // Check installed agent hooks still run this ms binary
+    if run_only.is_none() {
+        issues_found += check_agent_hooks(ctx, args.fix, verbose, &mut issues_fixed)?;
+    }
+
     // Run comprehensive recovery diagnostics if requested
     if run_only.is_none() && args.comprehensive {
         issues_found += run_comprehensive_check(ctx, args.fix, verbose, &mut issues_fixed)?;
@@ -126,12 +249,29 @@ pub fn run(ctx: &AppContext, args: &DoctorArgs) -> Result<()> {
             "security" => check_security(ctx, verbose)?,
             "recovery" => run_comprehensive_check(ctx, args.fix, verbose, &mut issues_fixed)?,
             "perf" => check_perf(ctx, verbose)?,
+            "config" => check_config(ctx, verbose)?,
             "output" | "output-mode" => check_output_mode(ctx, verbose)?,
+            "budget" => check_budgets(ctx, verbose)?,
+            "ambiguity" => check_ambiguity(ctx, verbose)?,
+            "encryption" => check_encryption(ctx, verbose)?,
+            "overrides" => check_overrides(ctx, verbose)?,
+            "review" => check_review_queue(ctx, verbose)?,
+            "quality" => check_quality_weights(ctx, verbose)?,
+            "cass" => check_cass(ctx, args.refresh_cass, verbose)?,
+            "agents" => check_agent_integrations(ctx, args.fix, verbose, &mut issues_fixed)?,
+            "hooks" => check_agent_hooks(ctx, args.fix, verbose, &mut issues_fixed)?,
+            "index" => {
+                check_search_index(ctx, args.fix, verbose, &mut issues_fixed)?
+                    + check_index_size(ctx, args.fix, &mut issues_fixed, &mut index_size)?
+            }
+            "embeddings" => {
+                check_embeddings(ctx, args.fix, verbose, &mut issues_fixed, &mut embeddings)?
+            }
             other => {
                 say!(ctx, "{} Unknown check: {}", "[!]", other);
                 say!(
                     ctx,
-                    "  Available checks: safety, security, recovery, perf, output"
+                    "  Available checks: safety, security, recovery, perf, output, budget, ambiguity, encryption, overrides, review, quality, cass, agents, hooks, index, embeddings, config"
                 );
                 1
             }
@@ -167,6 +307,8 @@ pub fn run(ctx: &AppContext, args: &DoctorArgs) -> Result<()> {
             "fix_requested": args.fix,
             "comprehensive": args.comprehensive,
             "check": args.check,
+            "embeddings": embeddings,
+            "index_size": index_size,
         });
         // Use `to_string` rather than `to_string_pretty` so consumers
         // doing line-buffered ndjson capture get a single line.
@@ -203,6 +345,55 @@ pub fn run(ctx: &AppContext, args: &DoctorArgs) -> Result<()> {
     Ok(())
 }
 
+/// `ms doctor --migrations`: report the schema version, pending migrations,
+/// and partially applied migrations. Runs without an [`AppContext`] because
+/// opening the database would try to migrate it.
+pub fn run_migrations_check(cli: &crate::cli::Cli) -> Result<()> {
+    let db_path = AppContext::find_ms_root()?.join("ms.db");
+    if !db_path.exists() {
+        return Err(crate::error::MsError::NotFound(format!(
+            "database not found at {} (run 'ms init')",
+            db_path.display()
+        )));
+    }
+    let report = crate::storage::migrations::inspect(&db_path)?;
+
+    if cli.output_format().is_machine_readable() {
+        let payload = serde_json::json!({
+            "status": if report.partial.is_empty() { "ok" } else { "partial" },
+            "report": report,
+        });
+        return crate::cli::output::emit_json(&payload);
+    }
+
+    println!("ms doctor - Migrations");
+    println!();
+    println!("Database:       {}", report.db_path.display());
+    println!(
+        "Schema version: {} (latest {})",
+        report.version, report.latest
+    );
+    if report.pending.is_empty() {
+        println!("Pending:        none");
+    } else {
+        let pending: Vec<String> = report.pending.iter().map(u32::to_string).collect();
+        println!("Pending:        {}", pending.join(", "));
+    }
+    println!();
+    if report.partial.is_empty() {
+        println!("[ok] No partially applied migrations");
+    } else {
+        for partial in &report.partial {
+            println!("[FAIL] {partial}");
+        }
+        println!();
+        println!("  The schema was left half-migrated (e.g. by two processes migrating at once).");
+        println!("  Restore a backup with 'ms backup restore', or repair the listed tables and");
+        println!("  set PRAGMA user_version to match, then re-run 'ms doctor --migrations'.");
+    }
+    Ok(())
+}
+
 /// Check the global lock status
 fn check_lock_status(ctx: &AppContext, verbose: bool) -> Result<usize> {
     say_inline!(ctx, "Checking lock status... ");
@@ -344,6 +535,382 @@ fn check_git_archive(ctx: &AppContext, verbose: bool) -> Result<usize> {
     }
 }
 
+/// Check the search index and, with `--fix`, repair it: stale locks are
+/// removed, and an index that cannot be read or predates the current schema
+/// is tombstoned and rebuilt from the database (together with the stored
+/// embeddings).
+fn check_search_index(
+    ctx: &AppContext,
+    fix: bool,
+    verbose: bool,
+    issues_fixed: &mut usize,
+) -> Result<usize> {
+    say_inline!(ctx, "Checking search index... ");
+
+    let index_path = ctx.ms_root.join("index");
+    let problems = health::diagnose(&index_path);
+    let issues: Vec<&IndexProblem> = problems.iter().filter(|p| p.is_issue()).collect();
+    if issues.is_empty() {
+        say!(ctx, "{} OK", "[ok]");
+        if verbose {
+            say!(ctx, "  Index path: {}", index_path.display());
+            for problem in &problems {
+                say!(ctx, "  - {}", problem);
+            }
+        }
+        return Ok(0);
+    }
+
+    say!(ctx, "{} {} problem(s)", "[FAIL]", issues.len());
+    for problem in &problems {
+        say!(ctx, "  - {}", problem);
+    }
+    if !fix {
+        say!(ctx, "  Run with --fix to repair or rebuild the index");
+        return Ok(issues.len());
+    }
+
+    let removed = health::remove_stale_locks(&index_path)?;
+    if removed > 0 {
+        say!(ctx, "  {} Removed {} stale lock file(s)", "[ok]", removed);
+    }
+    if issues.iter().any(|p| p.needs_rebuild()) {
+        if problems
+            .iter()
+            .any(|p| matches!(p, IndexProblem::LockHeld { .. }))
+        {
+            say!(
+                ctx,
+                "  {} Index is locked by a running process; stop it and re-run 'ms doctor --fix'",
+                "[!]"
+            );
+            return Ok(issues.len());
+        }
+        let reason = if issues
+            .iter()
+            .any(|p| matches!(p, IndexProblem::OutdatedSchema))
+        {
+            "outdated search index schema"
+        } else {
+            "unreadable search index"
+        };
+        rebuild_search_index(ctx, &index_path, reason, "ms doctor --fix", verbose)?;
+    }
+
+    let remaining = health::diagnose(&index_path)
+        .iter()
+        .filter(|p| p.is_issue())
+        .count();
+    if remaining == 0 {
+        *issues_fixed += issues.len();
+        say!(ctx, "  {} Search index repaired", "[ok]");
+    } else {
+        say!(ctx, "  {} {} problem(s) remain", "[!]", remaining);
+    }
+    Ok(issues.len())
+}
+
+/// On-disk size and document counts of the search index, and the memory the
+/// stored vectors take once loaded.
+#[derive(Debug, Serialize)]
+struct IndexSizeReport {
+    size_bytes: u64,
+    #[serde(flatten)]
+    stats: IndexStats,
+    vector_count: usize,
+    vector_bytes: usize,
+}
+
+/// Report the search index size and flag it when the share of deleted
+/// documents exceeds `search.compaction.deleted_ratio`. With `--fix` the
+/// index is compacted.
+fn check_index_size(
+    ctx: &AppContext,
+    fix: bool,
+    issues_fixed: &mut usize,
+    report_out: &mut Option<IndexSizeReport>,
+) -> Result<usize> {
+    say_inline!(ctx, "Checking index size... ");
+
+    let mut vectors = match build_embedder(&ctx.config.search) {
+        Ok(embedder) => VectorIndex::for_embedder(embedder.as_ref()),
+        Err(_) => VectorIndex::new(ctx.config.search.embedding_dims as usize),
+    };
+    for record in ctx.db.get_all_embedding_records()? {
+        // Vectors from another backend are never loaded (see check_embeddings)
+        let _ = vectors.insert_stored(record.skill_id, record.embedding, &record.embedder_type);
+    }
+    let mut report = IndexSizeReport {
+        size_bytes: health::index_size(&ctx.ms_root.join("index")),
+        stats: ctx.search.stats(),
+        vector_count: vectors.len(),
+        vector_bytes: vectors.memory_bytes(),
+    };
+    let summary = format!(
+        "{} on disk, {} live / {} deleted doc(s) in {} segment(s); {} vector(s) use {}",
+        format_size(report.size_bytes),
+        report.stats.live_docs,
+        report.stats.deleted_docs,
+        report.stats.segments,
+        report.vector_count,
+        format_size(report.vector_bytes as u64)
+    );
+
+    let threshold = f64::from(ctx.config.search.compaction.deleted_ratio);
+    let ratio = report.stats.deleted_ratio();
+    if report.stats.deleted_docs == 0 || ratio <= threshold {
+        say!(ctx, "{} {}", "[ok]", summary);
+        *report_out = Some(report);
+        return Ok(0);
+    }
+
+    say!(ctx, "{} {}", "[!]", summary);
+    say!(
+        ctx,
+        "  {:.0}% of documents are deleted (threshold {:.0}%)",
+        ratio * 100.0,
+        threshold * 100.0
+    );
+    if !fix || ctx.search.is_readonly() {
+        say!(
+            ctx,
+            "  Run `ms index --compact` (or `ms doctor --fix`) to reclaim space"
+        );
+        *report_out = Some(report);
+        return Ok(1);
+    }
+
+    let compaction = compact_index(ctx)?;
+    say!(
+        ctx,
+        "  {} Compacted: {} -> {}",
+        "[ok]",
+        format_size(compaction.before.size_bytes),
+        format_size(compaction.after.size_bytes)
+    );
+    report.size_bytes = compaction.after.size_bytes;
+    report.stats = compaction.after.stats;
+    *report_out = Some(report);
+    *issues_fixed += 1;
+    Ok(1)
+}
+
+/// Set the index aside (tombstoned with `reason`, on behalf of
+/// `requested_by`) and rebuild it and the stored embeddings from the skill
+/// records in the database. Returns the number of skills indexed.
+pub(crate) fn rebuild_search_index(
+    ctx: &AppContext,
+    index_path: &Path,
+    reason: &str,
+    requested_by: &str,
+    verbose: bool,
+) -> Result<usize> {
+    const PAGE: usize = 500;
+
+    if index_path.exists() {
+        let tombstone = TombstoneManager::new(&ctx.ms_root).tombstone(
+            index_path,
+            Some(reason),
+            Some(requested_by),
+        )?;
+        if verbose {
+            say!(ctx, "  Old index tombstoned as {}", tombstone.id);
+        }
+    }
+
+    let index = SearchIndex::open(index_path)?;
+    let embedder = build_embedder(&ctx.config.search)?;
+    let mut vectors = VectorIndex::for_embedder(embedder.as_ref());
+    let mut offset = 0;
+    loop {
+        let page = ctx.db.list_skills(PAGE, offset)?;
+        if page.is_empty() {
+            break;
+        }
+        for skill in &page {
+            let derived: Vec<String> = ctx
+                .db
+                .get_derived_tags(&skill.id)?
+                .into_iter()
+                .map(|derived| derived.tag)
+                .collect();
+            index.index_skill_tagged(skill, &derived)?;
+            let embedding = store_embedding(ctx, embedder.as_ref(), skill)?;
+            vectors.insert_stored(skill.id.clone(), embedding, embedder.name())?;
+        }
+        offset += page.len();
+        say_inline!(ctx, "\r  Rebuilding index... {} skill(s)", offset);
+    }
+    index.commit()?;
+    say!(
+        ctx,
+        "\r  {} Rebuilt index from {} skill(s) ({} embedding(s))",
+        "[ok]",
+        offset,
+        vectors.len()
+    );
+    Ok(offset)
+}
+
+/// Active embedding backend and how many stored vectors it can use.
+#[derive(Debug, Serialize)]
+/// Run the `ms config doctor` checks over every existing config file.
+/// Warnings (e.g. missing paths) are shown with `--verbose` but not counted.
+fn check_config(ctx: &AppContext, verbose: bool) -> Result<usize> {
+    say_inline!(ctx, "Checking config... ");
+
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+    for path in ctx.config_sources.iter().filter(|p| p.exists()) {
+        let issues = match check_config_file(path) {
+            Ok(issues) => issues,
+            Err(err) => {
+                errors.push(format!("{}: {err}", path.display()));
+                continue;
+            }
+        };
+        for issue in issues {
+            let line = format!("{}: {issue}", path.display());
+            match issue.level {
+                ConfigIssueLevel::Error => errors.push(line),
+                ConfigIssueLevel::Warning => warnings.push(line),
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        say!(ctx, "{} OK", "[ok]");
+    } else {
+        say!(ctx, "{} {} problem(s)", "[!]", errors.len());
+        for line in &errors {
+            say!(ctx, "  - {}", line);
+        }
+        say!(ctx, "  Run `ms config doctor` for details");
+    }
+    if verbose {
+        for line in &warnings {
+            say!(ctx, "  warning: {}", line);
+        }
+    }
+    Ok(errors.len())
+}
+
+struct EmbeddingStatus {
+    backend: String,
+    dims: usize,
+    stored: usize,
+    incompatible: usize,
+}
+
+/// Report the active embedding backend and dimension, and flag stored
+/// vectors built by another backend. With `--fix` every skill is re-embedded.
+fn check_embeddings(
+    ctx: &AppContext,
+    fix: bool,
+    verbose: bool,
+    issues_fixed: &mut usize,
+    status_out: &mut Option<EmbeddingStatus>,
+) -> Result<usize> {
+    const PAGE: usize = 500;
+
+    say_inline!(ctx, "Checking embeddings... ");
+
+    let embedder = match build_embedder(&ctx.config.search) {
+        Ok(embedder) => embedder,
+        Err(err) => {
+            say!(ctx, "{} {}", "[FAIL]", err);
+            return Ok(1);
+        }
+    };
+    let records = ctx.db.get_all_embedding_records()?;
+    let expected = VectorIndex::for_embedder(embedder.as_ref());
+    let incompatible = records
+        .iter()
+        .filter(|r| {
+            expected
+                .check_compatible(&r.embedder_type, r.embedding.len())
+                .is_err()
+        })
+        .count();
+    let mut status = EmbeddingStatus {
+        backend: embedder.name().to_string(),
+        dims: embedder.dims(),
+        stored: records.len(),
+        incompatible,
+    };
+
+    if incompatible == 0 {
+        say!(
+            ctx,
+            "{} {} ({} dims), {} stored",
+            "[ok]",
+            status.backend,
+            status.dims,
+            status.stored
+        );
+        *status_out = Some(status);
+        return Ok(0);
+    }
+
+    say!(
+        ctx,
+        "{} {} of {} stored embedding(s) do not match {} ({} dims); re-index required",
+        "[!]",
+        incompatible,
+        status.stored,
+        status.backend,
+        status.dims
+    );
+    if verbose {
+        for record in records.iter().filter(|r| {
+            expected
+                .check_compatible(&r.embedder_type, r.embedding.len())
+                .is_err()
+        }) {
+            say!(
+                ctx,
+                "  - {}: {} ({} dims)",
+                record.skill_id,
+                record.embedder_type,
+                record.dims
+            );
+        }
+    }
+    if !fix {
+        say!(
+            ctx,
+            "  Run with --fix to re-embed skills with the active backend"
+        );
+        *status_out = Some(status);
+        return Ok(1);
+    }
+
+    let mut offset = 0;
+    loop {
+        let page = ctx.db.list_skills(PAGE, offset)?;
+        if page.is_empty() {
+            break;
+        }
+        for skill in &page {
+            store_embedding(ctx, embedder.as_ref(), skill)?;
+        }
+        offset += page.len();
+        say_inline!(ctx, "\r  Re-embedding... {} skill(s)", offset);
+    }
+    say!(
+        ctx,
+        "\r  {} Re-embedded {} skill(s) with {}",
+        "[ok]",
+        offset,
+        status.backend
+    );
+    status.stored = offset;
+    status.incompatible = 0;
+    *status_out = Some(status);
+    *issues_fixed += 1;
+    Ok(1)
+}
+
 /// Check command safety (DCG) availability
 fn check_safety(ctx: &AppContext, verbose: bool) -> Result<usize> {
     say_inline!(ctx, "Checking command safety... ");
@@ -443,7 +1010,7 @@ fn check_security(ctx: &AppContext, verbose: bool) -> Result<usize> {
                 if path.is_file() && path.extension().is_some_and(|e| e == "json" || e == "md") {
                     if let Ok(content) = std::fs::read_to_string(&path) {
                         files_scanned += 1;
-                        let summary = scan_secrets_summary(&content);
+                        let summary = ctx.secrets.summary(&content);
                         if summary.total_count > 0 {
                             secrets_found += summary.total_count;
                             if verbose {
@@ -909,6 +1476,499 @@ fn check_output_mode(ctx: &AppContext, verbose: bool) -> Result<usize> {
     Ok(0)
 }
 
+/// Check context budget consumption against configured budgets
+fn check_budgets(ctx: &AppContext, verbose: bool) -> Result<usize> {
+    say_inline!(ctx, "Checking context budgets... ");
+
+    let report = crate::cli::commands::budget::build_report(ctx, 3)?;
+    if report.budgets.is_empty() {
+        say!(ctx, "{} No budgets configured", "-");
+        return Ok(0);
+    }
+
+    let over = report.over_budget();
+    if over.is_empty() {
+        say!(
+            ctx,
+            "{} {} budget(s) within limits",
+            "[ok]",
+            report.budgets.len()
+        );
+    } else {
+        say!(ctx, "{} {} budget(s) exceeded", "[!]", over.len());
+        for usage in &over {
+            let largest = usage
+                .top_contributors
+                .iter()
+                .map(|c| format!("{} ({})", c.id, c.tokens))
+                .collect::<Vec<_>>()
+                .join(", ");
+            say!(
+                ctx,
+                "  {} {}: {} / {} tokens; largest: {}",
+                usage.scope,
+                usage.name,
+                usage.used,
+                usage.budget,
+                largest
+            );
+        }
+        say!(ctx, "  Run 'ms budget' for the full report");
+    }
+    if verbose {
+        for usage in report.budgets.iter().filter(|u| !u.over_budget) {
+            say!(
+                ctx,
+                "  {} {}: {} / {} tokens",
+                usage.scope,
+                usage.name,
+                usage.used,
+                usage.budget
+            );
+        }
+    }
+
+    Ok(over.len())
+}
+
+/// Check that detected agents have the ms MCP server registered
+fn check_agent_integrations(
+    ctx: &AppContext,
+    fix: bool,
+    verbose: bool,
+    issues_fixed: &mut usize,
+) -> Result<usize> {
+    say_inline!(ctx, "Checking agent integrations... ");
+
+    let writer = IntegrationWriter::new();
+    let mut agents: Vec<_> = AgentDetectionService::new()
+        .detect_all()
+        .into_iter()
+        .filter(|agent| writer.target(agent).is_some())
+        .collect();
+    if agents.is_empty() {
+        say!(ctx, "{} No configurable agents detected", "-");
+        return Ok(0);
+    }
+    for agent in &mut agents {
+        agent.integration_status = writer.status(agent);
+    }
+    let missing = agents
+        .iter()
+        .filter(|agent| agent.integration_status.needs_configuration())
+        .count();
+    if missing == 0 {
+        say!(ctx, "{} {} agent(s) configured", "[ok]", agents.len());
+        return Ok(0);
+    }
+
+    if !fix {
+        say!(
+            ctx,
+            "{} {} agent(s) without the ms MCP server",
+            "[!]",
+            missing
+        );
+        if verbose {
+            for agent in agents
+                .iter()
+                .filter(|agent| agent.integration_status.needs_configuration())
+            {
+                say!(
+                    ctx,
+                    "  - {} ({:?})",
+                    agent.agent_type,
+                    agent.integration_status
+                );
+            }
+        }
+        say!(ctx, "  Run with --fix or 'ms init --configure-agents'");
+        return Ok(missing);
+    }
+
+    let outcomes = configure_agents(&writer, &mut agents, false, false);
+    let configured = outcomes
+        .iter()
+        .filter(|outcome| outcome.action == IntegrationAction::Configured)
+        .count();
+    *issues_fixed += configured;
+    say!(ctx, "{} Registered ms with {} agent(s)", "[ok]", configured);
+    for outcome in &outcomes {
+        match &outcome.action {
+            IntegrationAction::Failed(err) => say!(ctx, "  {} {}: {}", "[!]", outcome.agent, err),
+            IntegrationAction::Configured if verbose => {
+                if let Some(backup) = &outcome.backup {
+                    say!(ctx, "  {}: backup at {}", outcome.agent, backup.display());
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(missing)
+}
+
+/// Check that an installed suggestion hook is registered, executable and
+/// runs this ms binary
+fn check_agent_hooks(
+    ctx: &AppContext,
+    fix: bool,
+    verbose: bool,
+    issues_fixed: &mut usize,
+) -> Result<usize> {
+    say_inline!(ctx, "Checking agent hooks... ");
+
+    let Ok(hook) = ClaudeHook::for_current_user() else {
+        say!(ctx, "{} Cannot locate the Claude Code settings", "-");
+        return Ok(0);
+    };
+    let problem = match hook.status() {
+        HookStatus::NotInstalled => {
+            say!(ctx, "{} No agent hooks installed", "-");
+            return Ok(0);
+        }
+        HookStatus::Installed => {
+            say!(ctx, "{} Claude Code suggestion hook installed", "[ok]");
+            if verbose {
+                say!(ctx, "  script: {}", hook.script_path().display());
+            }
+            return Ok(0);
+        }
+        HookStatus::Unregistered => format!(
+            "hook script is not registered in {}",
+            hook.settings_path().display()
+        ),
+        HookStatus::MissingScript => {
+            format!("hook script {} is missing", hook.script_path().display())
+        }
+        HookStatus::NotExecutable => format!(
+            "hook script {} is not executable",
+            hook.script_path().display()
+        ),
+        HookStatus::WrongBinary(bin) => format!("hook runs {bin}, not this ms binary"),
+    };
+
+    if !fix {
+        say!(ctx, "{} Claude Code {}", "[!]", problem);
+        say!(
+            ctx,
+            "  Run with --fix or 'ms init --install-hooks claude-code'"
+        );
+        return Ok(1);
+    }
+
+    hook.install()?;
+    *issues_fixed += 1;
+    say!(
+        ctx,
+        "{} Reinstalled the Claude Code suggestion hook",
+        "[ok]"
+    );
+    if verbose {
+        say!(ctx, "  was: {}", problem);
+    }
+    Ok(1)
+}
+
+/// Check for skill references that resolve somewhere surprising
+fn check_ambiguity(ctx: &AppContext, verbose: bool) -> Result<usize> {
+    say_inline!(ctx, "Checking skill name resolution... ");
+
+    let found = find_ambiguities(&ctx.db)?;
+    if found.is_empty() {
+        say!(ctx, "{} No shadowed aliases or ambiguous names", "[ok]");
+        return Ok(0);
+    }
+
+    say!(ctx, "{} {} ambiguous reference(s)", "[!]", found.len());
+    for ambiguity in &found {
+        say!(ctx, "  {}", ambiguity.detail);
+        say!(ctx, "    fix: {}", ambiguity.fix);
+    }
+    if verbose {
+        say!(ctx, "  Run 'ms resolve <name>' to see each resolution step");
+    }
+
+    Ok(found.len())
+}
+
+/// Warn when more items await `ms review` than `[review].max_pending`.
+fn check_review_queue(ctx: &AppContext, verbose: bool) -> Result<usize> {
+    use crate::cass::UncertaintyStore;
+
+    say_inline!(ctx, "Checking review queue... ");
+
+    let open = UncertaintyStore::new(&ctx.db).open()?;
+    let limit = ctx.config.review.max_pending;
+    if open.len() <= limit {
+        say!(ctx, "{} {} item(s) awaiting review", "[ok]", open.len());
+        return Ok(0);
+    }
+
+    say!(
+        ctx,
+        "{} {} items awaiting review (limit {})",
+        "[!]",
+        open.len(),
+        limit
+    );
+    if verbose && let Some(oldest) = open.first() {
+        let age = chrono::Utc::now().signed_duration_since(oldest.created_at);
+        say!(ctx, "  Oldest: {} ({} days)", oldest.id, age.num_days());
+    }
+    say!(
+        ctx,
+        "  fix: work through `ms review list`, or raise [review].max_pending"
+    );
+    Ok(1)
+}
+
+/// Warn when the stored quality scores were computed with other weights than
+/// the configured `[quality]` ones.
+fn check_quality_weights(ctx: &AppContext, verbose: bool) -> Result<usize> {
+    use crate::cli::commands::quality::WEIGHTS_HASH_KEY;
+
+    say_inline!(ctx, "Checking quality weights... ");
+
+    let current = ctx.config.quality.weights().fingerprint();
+    let Some(stored) = ctx.db.get_config_value(WEIGHTS_HASH_KEY)? else {
+        say!(ctx, "{} No stored scores recorded yet", "[ok]");
+        return Ok(0);
+    };
+    if stored == current {
+        say!(ctx, "{} Stored scores match the configured weights", "[ok]");
+        return Ok(0);
+    }
+
+    say!(
+        ctx,
+        "{} Stored quality scores were computed with other weights",
+        "[!]"
+    );
+    if verbose {
+        say!(ctx, "  Stored:     {}", stored);
+        say!(ctx, "  Configured: {}", current);
+    }
+    say!(
+        ctx,
+        "  fix: run `ms index --rescore` (add --dry-run to preview the changes)"
+    );
+    Ok(1)
+}
+
+/// Report the cass availability probe: where cass was looked for, the
+/// outcome and its latency. `refresh` drops the cached probes first so the
+/// result (and the cache) reflect the installed cass.
+fn check_cass(ctx: &AppContext, refresh: bool, verbose: bool) -> Result<usize> {
+    use crate::cass::CassProbeStatus;
+
+    say_inline!(ctx, "Checking cass... ");
+
+    let client = cass_client(ctx, None, false);
+    if refresh {
+        client.clear_probe_cache()?;
+    }
+    let probe = client.probe();
+    let location = probe.resolved.as_ref().unwrap_or(&probe.binary).display();
+    match (&probe.status, &probe.health) {
+        (CassProbeStatus::NotFound, _) => {
+            say!(
+                ctx,
+                "{} cass not installed (optional; looked for {})",
+                "[ok]",
+                probe.binary.display()
+            );
+            return Ok(0);
+        }
+        (CassProbeStatus::Available, Some(health)) if !health.healthy => {
+            say!(
+                ctx,
+                "{} cass {} at {} unhealthy (database_ok: {})",
+                "[!]",
+                health.version,
+                location,
+                health.database_ok
+            );
+            return Ok(1);
+        }
+        (CassProbeStatus::Available, Some(health)) => {
+            let latency = if probe.cached {
+                "cached".to_string()
+            } else {
+                format!("{}ms", probe.latency_ms)
+            };
+            say!(
+                ctx,
+                "{} cass {} at {} ({latency}){}",
+                "[ok]",
+                health.version,
+                location,
+                if refresh { ", re-probed" } else { "" }
+            );
+        }
+        _ => {
+            say!(
+                ctx,
+                "{} {}",
+                "[!]",
+                probe.unavailable_reason().unwrap_or_default()
+            );
+            return Ok(1);
+        }
+    }
+    if verbose && let Some(data_dir) = &probe.data_dir {
+        say!(ctx, "  Data dir: {}", data_dir.display());
+    }
+    if verbose {
+        match client.capabilities() {
+            Ok(capabilities) => {
+                say!(
+                    ctx,
+                    "  Search modes: {}",
+                    capabilities.search_modes.join(", ")
+                );
+                say!(
+                    ctx,
+                    "  Output formats: {}",
+                    capabilities.output_formats.join(", ")
+                );
+            }
+            Err(err) => say!(ctx, "  Capabilities unavailable: {}", err),
+        }
+    }
+    Ok(0)
+}
+
+/// Check that every `.ms/overrides.toml` entry targets an indexed skill and
+/// blocks or sections it has. Overrides that match nothing would otherwise
+/// be silent no-ops.
+fn check_overrides(ctx: &AppContext, verbose: bool) -> Result<usize> {
+    use crate::core::spec_lens::parse_markdown;
+    use crate::security::encryption;
+
+    say_inline!(ctx, "Checking project overrides... ");
+
+    let overrides = match ctx.project_overrides() {
+        Ok(overrides) => overrides,
+        Err(err) => {
+            say!(ctx, "{} {}", "[FAIL]", err);
+            return Ok(1);
+        }
+    };
+    if overrides.is_empty() {
+        say!(ctx, "{} No project overrides", "[ok]");
+        return Ok(0);
+    }
+
+    let mut problems = Vec::new();
+    for skill_id in overrides.skills.keys() {
+        let Some(skill) = ctx.db.get_skill(skill_id)? else {
+            problems.push(format!("{skill_id}: skill not found"));
+            continue;
+        };
+        let body = match encryption::reveal_body(&skill.id, &skill.body) {
+            Ok(body) => body,
+            Err(err) => {
+                problems.push(format!("{skill_id}: cannot check blocks: {err}"));
+                continue;
+            }
+        };
+        let mut spec = parse_markdown(&body)?;
+        spec.metadata.id.clone_from(&skill.id);
+        if let Some(result) = overrides.apply(&mut spec) {
+            problems.extend(
+                result
+                    .unmatched
+                    .iter()
+                    .map(|unmatched| format!("{skill_id}: {unmatched}")),
+            );
+        }
+    }
+
+    if problems.is_empty() {
+        say!(
+            ctx,
+            "{} {} overridden skill(s) in {}",
+            "[ok]",
+            overrides.skills.len(),
+            overrides.path.display()
+        );
+        if verbose {
+            for skill_id in overrides.skills.keys() {
+                say!(ctx, "  {}", skill_id);
+            }
+        }
+        return Ok(0);
+    }
+
+    say!(
+        ctx,
+        "{} {} override target(s) in {} match nothing",
+        "[!]",
+        problems.len(),
+        overrides.path.display()
+    );
+    for problem in &problems {
+        say!(ctx, "  {}", problem);
+    }
+    Ok(problems.len())
+}
+
+/// Report sensitive skills and whether the key that decrypts them is
+/// reachable. A missing key is only an issue when sensitive skills exist.
+fn check_encryption(ctx: &AppContext, verbose: bool) -> Result<usize> {
+    use crate::security::encryption::{KEY_ENV, SkillKey};
+
+    say_inline!(ctx, "Checking skill encryption... ");
+
+    let sensitive = ctx.db.sensitive_skill_ids()?;
+    let issues = match SkillKey::locate() {
+        Ok(Some((_, source))) => {
+            say!(
+                ctx,
+                "{} {} sensitive skill(s), key from {}",
+                "[ok]",
+                sensitive.len(),
+                source
+            );
+            0
+        }
+        Ok(None) if sensitive.is_empty() => {
+            say!(ctx, "{} No sensitive skills", "[ok]");
+            0
+        }
+        Ok(None) => {
+            say!(
+                ctx,
+                "{} {} sensitive skill(s), no key found",
+                "[!]",
+                sensitive.len()
+            );
+            say!(
+                ctx,
+                "  Set {} or store the key in the OS keychain to read their bodies",
+                KEY_ENV
+            );
+            1
+        }
+        Err(err) => {
+            say!(
+                ctx,
+                "{} {} sensitive skill(s), key unusable: {}",
+                "[FAIL]",
+                sensitive.len(),
+                err
+            );
+            1
+        }
+    };
+    if verbose {
+        for id in &sensitive {
+            say!(ctx, "  {}", id);
+        }
+    }
+    Ok(issues)
+}
+
 /// Check performance metrics
 fn check_perf(ctx: &AppContext, verbose: bool) -> Result<usize> {
     say_inline!(ctx, "Checking performance... ");
@@ -977,8 +2037,233 @@ fn check_perf(ctx: &AppContext, verbose: bool) -> Result<usize> {
     Ok(issues)
 }
 
+// =============================================================================
+// Layer conflicts
+// =============================================================================
+
+/// `ms doctor conflicts`: list skills whose layers disagree and record how to
+/// resolve them. Resolutions are remembered per set of candidate contents,
+/// so `ms index` keeps applying them until one of the files changes.
+fn run_conflicts(ctx: &AppContext, args: &DoctorConflictsArgs) -> Result<()> {
+    let strategy = args
+        .strategy
+        .as_deref()
+        .map(|raw| {
+            ConflictStrategy::parse(raw)
+                .filter(|s| *s != ConflictStrategy::Interactive)
+                .ok_or_else(|| {
+                    MsError::ValidationFailed(format!(
+                        "unknown conflict strategy '{raw}' (expected prefer-project, \
+                         prefer-global, newest, or merge)"
+                    ))
+                })
+        })
+        .transpose()?;
+    let interactive =
+        strategy.is_none() && !ctx.output_format.is_machine_readable() && should_prompt();
+
+    let mut forgotten = 0;
+    if args.forget {
+        forgotten = ctx.db.delete_layer_resolutions(args.skill.as_deref())?;
+    }
+
+    let (registry, mut conflicts) = load_layer_conflicts(ctx)?;
+    if let Some(ref skill) = args.skill {
+        conflicts.retain(|c| c.skill_id == *skill);
+    }
+
+    let mut changed = args.forget;
+    for conflict in &mut conflicts {
+        let choice = match strategy {
+            Some(strategy) if !conflict.sections.is_empty() => Some(strategy),
+            None if interactive && conflict.is_unresolved() => prompt_conflict_choice(conflict)?,
+            _ => None,
+        };
+        if let Some(choice) = choice {
+            ctx.db.upsert_layer_resolution(&LayerResolutionRecord {
+                skill_id: conflict.skill_id.clone(),
+                candidates_hash: conflict.candidates_hash.clone(),
+                strategy: choice.name(),
+                resolved_at: chrono::Utc::now().to_rfc3339(),
+            })?;
+            conflict.resolution = Some(choice);
+            changed = true;
+        }
+    }
+
+    if changed {
+        apply_layer_resolutions(ctx, &registry, &conflicts)?;
+    }
+
+    let unresolved: Vec<&str> = conflicts
+        .iter()
+        .filter(|c| c.is_unresolved())
+        .map(|c| c.skill_id.as_str())
+        .collect();
+
+    if ctx.output_format.is_machine_readable() {
+        let items: Vec<serde_json::Value> = conflicts
+            .iter()
+            .filter(|c| !c.sections.is_empty())
+            .map(|c| {
+                serde_json::json!({
+                    "skill_id": c.skill_id,
+                    "layers": c.layers,
+                    "candidates_hash": c.candidates_hash,
+                    "resolution": c.resolution.map(|s| s.name()),
+                    "sections": c.sections,
+                })
+            })
+            .collect();
+        emit_json(&serde_json::json!({
+            "status": if unresolved.is_empty() { "ok" } else { "unresolved" },
+            "conflicts": items,
+            "unresolved": unresolved,
+            "forgotten": forgotten,
+        }))?;
+        if !unresolved.is_empty() {
+            return Err(MsError::ValidationFailed(format!(
+                "{} unresolved layer conflict(s)",
+                unresolved.len()
+            )));
+        }
+        return Ok(());
+    }
+
+    println!("ms doctor - Layer Conflicts");
+    println!();
+    if args.forget {
+        println!("Forgot {forgotten} stored resolution(s)");
+        println!();
+    }
+    let shown: Vec<&LayerConflict> = conflicts
+        .iter()
+        .filter(|c| !c.sections.is_empty())
+        .collect();
+    if shown.is_empty() {
+        println!("[ok] No conflicting sections between layers");
+        return Ok(());
+    }
+    for conflict in &shown {
+        print_conflict(conflict);
+    }
+    if unresolved.is_empty() {
+        println!("[ok] All {} conflict(s) resolved", shown.len());
+    } else {
+        println!(
+            "[!] {} unresolved; the highest layer is used until you choose",
+            unresolved.len()
+        );
+        println!(
+            "  Resolve with: ms doctor conflicts --strategy prefer-project|prefer-global|newest|merge"
+        );
+    }
+    Ok(())
+}
+
+/// Write each conflict's winner to the index under the global lock.
+fn apply_layer_resolutions(
+    ctx: &AppContext,
+    registry: &LayeredRegistry,
+    conflicts: &[LayerConflict],
+) -> Result<()> {
+    ctx.require_writable_search()?;
+    let _lock = GlobalLock::acquire_or_busy(&ctx.ms_root, crate::storage::tx::lock_timeout())?;
+    ctx.search.reserve_writer()?;
+    let tx_mgr = TxManager::new(
+        Arc::clone(&ctx.db),
+        Arc::clone(&ctx.git),
+        ctx.ms_root.clone(),
+    )?;
+    let provenance = Provenance::new("doctor");
+    for conflict in conflicts {
+        apply_layer_resolution(ctx, &tx_mgr, registry, conflict, &provenance)?;
+    }
+    ctx.search.commit()?;
+    Ok(())
+}
+
+fn print_conflict(conflict: &LayerConflict) {
+    let layers: Vec<&str> = conflict.layers.iter().map(SkillLayer::as_str).collect();
+    let resolution = conflict
+        .resolution
+        .map_or_else(|| "unresolved".yellow().to_string(), |s| s.name());
+    println!("{} ({})", conflict.skill_id.bold(), layers.join(", "));
+    println!("  resolution: {resolution}");
+    for detail in &conflict.sections {
+        println!(
+            "  section {} ({} vs {})",
+            detail.section_name, detail.higher_layer, detail.lower_layer
+        );
+        let Some(ref diff) = detail.diff else {
+            continue;
+        };
+        for block in &diff.modified {
+            println!("    block {}:", block.block_id);
+            for line in block.lower_content.lines() {
+                println!("      {}", format!("- {line}").red());
+            }
+            for line in block.higher_content.lines() {
+                println!("      {}", format!("+ {line}").green());
+            }
+        }
+        for block in &diff.higher_only {
+            println!("    block {block}: only in {}", detail.higher_layer);
+        }
+        for block in &diff.lower_only {
+            println!("    block {block}: only in {}", detail.lower_layer);
+        }
+    }
+    println!();
+}
+
+/// Show one conflict and ask which candidate wins. `None` skips it.
+fn prompt_conflict_choice(conflict: &LayerConflict) -> Result<Option<ConflictStrategy>> {
+    use std::io::Write;
+
+    print_conflict(conflict);
+    let mut options: Vec<(String, ConflictStrategy)> = conflict
+        .layers
+        .iter()
+        .rev()
+        .map(|layer| {
+            (
+                format!("keep {layer}"),
+                ConflictStrategy::PreferLayer(*layer),
+            )
+        })
+        .collect();
+    options.push(("newest".to_string(), ConflictStrategy::Newest));
+    options.push(("merge".to_string(), ConflictStrategy::Merge));
+    for (idx, (label, _)) in options.iter().enumerate() {
+        println!("  [{}] {label}", idx + 1);
+    }
+    print!("  Choose 1-{} (enter to skip): ", options.len());
+    std::io::stdout()
+        .flush()
+        .map_err(|err| MsError::Config(format!("prompt flush: {err}")))?;
+    let mut input = String::new();
+    std::io::stdin()
+        .read_line(&mut input)
+        .map_err(|err| MsError::Config(format!("prompt read: {err}")))?;
+    println!();
+    Ok(input
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .and_then(|n| n.checked_sub(1))
+        .and_then(|idx| options.get(idx))
+        .map(|(_, strategy)| *strategy))
+}
+
+/// Prompt only on an interactive terminal in rich mode.
+fn should_prompt() -> bool {
+    use std::io::IsTerminal;
+
+    should_use_rich_for_doctor() && std::io::stdin().is_terminal()
+}
+
 /// Check whether the terminal supports rich output for the doctor command.
-#[allow(dead_code)]
 fn should_use_rich_for_doctor() -> bool {
     use std::io::IsTerminal;
 
@@ -1028,6 +2313,7 @@ mod tests {
         assert!(!cli.doctor.check_lock);
         assert!(!cli.doctor.break_lock);
         assert!(!cli.doctor.comprehensive);
+        assert!(!cli.doctor.migrations);
     }
 
     #[test]
@@ -1072,6 +2358,12 @@ mod tests {
         assert!(cli.doctor.comprehensive);
     }
 
+    #[test]
+    fn parse_doctor_migrations() {
+        let cli = TestCli::try_parse_from(["test", "--migrations"]).unwrap();
+        assert!(cli.doctor.migrations);
+    }
+
     #[test]
     fn parse_doctor_all_options() {
         let cli = TestCli::try_parse_from([
@@ -1170,6 +2462,10 @@ mod tests {
             "perf",
             "output",
             "output-mode",
+            "budget",
+            "ambiguity",
+            "quality",
+            "config",
         ];
 
         for check in &available_checks {
@@ -1,21 +1,37 @@
 //! ms edit - Edit a skill (structured round-trip)
 
-use clap::Args;
+use clap::{Args, ValueEnum};
 
-use std::path::PathBuf;
+use std::collections::{BTreeMap, VecDeque};
+use std::io::Read;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::Arc;
 
 use crate::app::AppContext;
-use crate::cli::commands::resolve_skill_markdown;
-use crate::core::SkillMetadata;
+use crate::bundler::BundleRegistry;
+use crate::cli::commands::index::index_one;
+use crate::cli::commands::{expand_path, resolve_skill_markdown};
+use crate::cli::output::{HumanLayout, OutputFormat, emit_human, emit_json};
+use crate::core::bulk_edit::{
+    BulkEditEntry, BulkEditPlan, BulkEditStatus, BulkOperation, BulkOpsDocument, BulkSelector,
+};
+use crate::core::fork::fork_spec;
 use crate::core::spec_lens::{compile_markdown, parse_markdown};
-use crate::error::Result;
+use crate::core::structured_edit::{DEFAULT_RULE_SECTION, EditChange, EditOperation, apply_edits};
+use crate::core::{SkillLayer, SkillMetadata, SkillSpec};
+use crate::error::{MsError, Result};
+use crate::lint::ValidationEngine;
+use crate::lint::rules::structural_rules;
+use crate::search::context::SearchLayer;
 use crate::security::SafetyGate;
+use crate::storage::{GlobalLock, Provenance, SkillRecord, TxManager, lock_timeout};
 
 #[derive(Args, Debug)]
 pub struct EditArgs {
     /// Skill ID or name to edit
-    pub skill: String,
+    #[arg(required_unless_present = "bulk", conflicts_with = "bulk")]
+    pub skill: Option<String>,
 
     /// Editor to use (default: $EDITOR)
     #[arg(long)]
@@ -24,10 +40,117 @@ pub struct EditArgs {
     /// Edit metadata only
     #[arg(long)]
     pub meta: bool,
+
+    /// Edit metadata and tags across every skill matching --filter
+    #[arg(long, conflicts_with_all = ["editor", "meta"])]
+    pub bulk: bool,
+
+    /// Bulk selection, same syntax as search (e.g. 'tag:database layer:project')
+    #[arg(long, requires = "bulk")]
+    pub filter: Option<String>,
+
+    /// Set a metadata field on every selected skill (repeatable)
+    #[arg(long = "set", value_name = "FIELD=VALUE", requires = "bulk")]
+    pub set: Vec<String>,
+
+    /// Add a tag to every selected skill (repeatable)
+    #[arg(long, value_name = "TAG", requires = "bulk")]
+    pub add_tag: Vec<String>,
+
+    /// Remove a tag from every selected skill (repeatable)
+    #[arg(long, value_name = "TAG", requires = "bulk")]
+    pub remove_tag: Vec<String>,
+
+    /// JSON operations document ("-" for stdin)
+    #[arg(long, value_name = "FILE", requires = "bulk")]
+    pub ops: Option<String>,
+
+    /// Show the change plan without writing anything
+    #[arg(long, requires = "bulk")]
+    pub dry_run: bool,
+
+    /// Append a rule block (repeatable)
+    #[arg(long, value_name = "TEXT", conflicts_with_all = ["bulk", "editor", "meta"])]
+    pub add_rule: Vec<String>,
+
+    /// Section for --add-rule, by id or title; created if missing (default: rules)
+    #[arg(long, value_name = "SECTION", requires = "add_rule")]
+    pub section: Option<String>,
+
+    /// Remove a block by id (repeatable)
+    #[arg(long, value_name = "BLOCK_ID", conflicts_with_all = ["bulk", "editor", "meta"])]
+    pub remove_block: Vec<String>,
+
+    /// Set a metadata field (repeatable)
+    #[arg(long, value_name = "KEY=VALUE", conflicts_with_all = ["bulk", "editor", "meta"])]
+    pub set_meta: Vec<String>,
+
+    /// Retitle a section, keeping its id (repeatable)
+    #[arg(long, value_name = "OLD=NEW", conflicts_with_all = ["bulk", "editor", "meta"])]
+    pub rename_section: Vec<String>,
+
+    /// Append a code example from --file to the examples section
+    #[arg(long, requires_all = ["lang", "file"], conflicts_with_all = ["bulk", "editor", "meta"])]
+    pub append_example: bool,
+
+    /// Language of the --append-example code fence
+    #[arg(long, requires = "append_example")]
+    pub lang: Option<String>,
+
+    /// File holding the --append-example code
+    #[arg(long, requires = "append_example")]
+    pub file: Option<PathBuf>,
+
+    /// Edit a copy in this layer when the skill's own layer is read-only
+    #[arg(long, value_enum, value_name = "LAYER", conflicts_with = "bulk")]
+    pub fork_to: Option<ForkTarget>,
 }
 
+/// Where a read-only skill is copied before it is edited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ForkTarget {
+    /// The first `skill_paths.project` directory
+    Project,
+}
+
+/// A layer `ms edit` and `ms fmt` do not rewrite in place (`[layers.<name>]`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ReadOnlyLayer {
+    Community,
+    Bundle,
+}
+
+impl ReadOnlyLayer {
+    pub(crate) const fn name(self) -> &'static str {
+        match self {
+            Self::Community => "community",
+            Self::Bundle => "bundle",
+        }
+    }
+}
+
+/// Flags that become structured operations, as written on the command line.
+const OPERATION_FLAGS: [&str; 5] = [
+    "--add-rule",
+    "--remove-block",
+    "--set-meta",
+    "--rename-section",
+    "--append-example",
+];
+
 pub fn run(ctx: &AppContext, args: &EditArgs) -> Result<()> {
-    let skill_md = resolve_skill_markdown(ctx, &args.skill)?;
+    if args.bulk {
+        return run_bulk(ctx, args);
+    }
+    let skill = args
+        .skill
+        .as_deref()
+        .ok_or_else(|| MsError::Config("skill is required".to_string()))?;
+    let operations = structured_operations(args, std::env::args())?;
+    if !operations.is_empty() {
+        return run_structured(ctx, skill, &operations, args.fork_to);
+    }
+    let skill_md = writable_skill_markdown(ctx, resolve_skill_markdown(ctx, skill)?, args.fork_to)?;
     let skill_dir = skill_md
         .parent()
         .ok_or_else(|| crate::error::MsError::Config("invalid skill path".to_string()))?;
@@ -83,11 +206,651 @@ pub fn run(ctx: &AppContext, args: &EditArgs) -> Result<()> {
     Ok(())
 }
 
+/// The structured operations requested by flags, in command-line order.
+///
+/// clap keeps each flag's values separately, so the interleaving is recovered
+/// from `argv`; values not accounted for there (e.g. when `args` was built
+/// programmatically) follow in flag order.
+fn structured_operations(
+    args: &EditArgs,
+    argv: impl IntoIterator<Item = String>,
+) -> Result<Vec<EditOperation>> {
+    let section = args.section.as_deref().unwrap_or(DEFAULT_RULE_SECTION);
+    let mut queues: [VecDeque<EditOperation>; 5] = Default::default();
+    queues[0].extend(args.add_rule.iter().map(|text| EditOperation::AddRule {
+        section: section.to_string(),
+        text: text.clone(),
+    }));
+    queues[1].extend(
+        args.remove_block
+            .iter()
+            .map(|block_id| EditOperation::RemoveBlock {
+                block_id: block_id.clone(),
+            }),
+    );
+    for expr in &args.set_meta {
+        queues[2].push_back(EditOperation::parse_set_meta(expr)?);
+    }
+    for expr in &args.rename_section {
+        queues[3].push_back(EditOperation::parse_rename(expr)?);
+    }
+    if args.append_example
+        && let (Some(lang), Some(file)) = (&args.lang, &args.file)
+    {
+        let code = std::fs::read_to_string(file)
+            .map_err(|err| MsError::Config(format!("read {}: {err}", file.display())))?;
+        queues[4].push_back(EditOperation::AppendExample {
+            lang: lang.clone(),
+            code,
+        });
+    }
+
+    let mut operations = Vec::new();
+    for arg in argv {
+        if arg == "--" {
+            break;
+        }
+        let flag = arg.split_once('=').map_or(arg.as_str(), |(flag, _)| flag);
+        if let Some(kind) = OPERATION_FLAGS.iter().position(|f| *f == flag)
+            && let Some(op) = queues[kind].pop_front()
+        {
+            operations.push(op);
+        }
+    }
+    operations.extend(queues.into_iter().flatten());
+    Ok(operations)
+}
+
+fn run_structured(
+    ctx: &AppContext,
+    skill: &str,
+    operations: &[EditOperation],
+    fork_to: Option<ForkTarget>,
+) -> Result<()> {
+    let edit = apply_structured_edit(ctx, skill, operations, fork_to)?;
+    emit_structured(
+        ctx,
+        &edit.skill_id,
+        &edit.message,
+        &edit.changes,
+        &edit.spec,
+    )
+}
+
+/// A structured edit that has been written.
+pub(crate) struct StructuredEdit {
+    pub skill_id: String,
+    /// The rewritten SKILL.md
+    pub path: PathBuf,
+    /// Archive commit message
+    pub message: String,
+    pub changes: Vec<EditChange>,
+    /// The skill as re-parsed from the written file
+    pub spec: SkillSpec,
+}
+
+/// Apply structured operations to one skill: parse, mutate, validate with the
+/// structural rules, then write SKILL.md (atomically) followed by archive,
+/// database and index. Nothing is written unless every operation applies and
+/// the result validates; if the archive/database write fails, the original
+/// SKILL.md is put back so the file never runs ahead of the stores.
+/// Skills in a read-only layer are refused unless `fork_to` names a layer to
+/// fork them into first.
+pub(crate) fn apply_structured_edit(
+    ctx: &AppContext,
+    skill: &str,
+    operations: &[EditOperation],
+    fork_to: Option<ForkTarget>,
+) -> Result<StructuredEdit> {
+    let skill_md = writable_skill_markdown(ctx, resolve_skill_markdown(ctx, skill)?, fork_to)?;
+    let raw = std::fs::read_to_string(&skill_md)
+        .map_err(|err| MsError::Config(format!("read {}: {err}", skill_md.display())))?;
+    let spec = parse_markdown(&raw)?;
+    let (edited, changes) = apply_edits(&spec, operations)?;
+
+    let compiled = compile_markdown(&edited);
+    let reparsed = parse_markdown(&compiled)
+        .map_err(|err| MsError::ValidationFailed(format!("edited skill does not parse: {err}")))?;
+    let mut structural = ValidationEngine::with_defaults();
+    for rule in structural_rules() {
+        structural.register(rule);
+    }
+    let errors: Vec<String> = structural
+        .validate(&reparsed)
+        .errors()
+        .map(|d| format!("{}: {}", d.rule_id, d.message))
+        .collect();
+    if !errors.is_empty() {
+        return Err(MsError::ValidationFailed(format!(
+            "edit rejected, structural errors: {}",
+            errors.join("; ")
+        )));
+    }
+
+    let skill_id = edited.metadata.id.clone();
+    let layer = ctx
+        .db
+        .get_skill(&skill_id)?
+        .map_or(SkillLayer::Project, |record| {
+            skill_layer(&record.source_layer)
+        });
+    let message = format!(
+        "Edit skill {skill_id}: {}",
+        operations
+            .iter()
+            .map(EditOperation::describe)
+            .collect::<Vec<_>>()
+            .join("; ")
+    );
+    let tx_mgr = TxManager::new(
+        Arc::clone(&ctx.db),
+        Arc::clone(&ctx.git),
+        ctx.ms_root.clone(),
+    )?;
+    {
+        let _lock = GlobalLock::acquire_or_busy(&ctx.ms_root, lock_timeout())?;
+        replace_skill_markdown(&skill_md, &compiled)?;
+        if let Err(err) = tx_mgr.write_skill_with_message(
+            &edited,
+            layer,
+            Some(&message),
+            &Provenance::new("edit"),
+        ) {
+            replace_skill_markdown(&skill_md, &raw)?;
+            return Err(err);
+        }
+    }
+    if let Some(record) = ctx.db.get_skill(&skill_id)? {
+        ctx.search.index_skill(&record)?;
+        ctx.search.commit()?;
+    }
+    if let Some(skill_dir) = skill_md.parent() {
+        record_field_history(skill_dir, &spec, &edited)?;
+    }
+
+    Ok(StructuredEdit {
+        skill_id,
+        path: skill_md,
+        message,
+        changes,
+        spec: reparsed,
+    })
+}
+
+/// Replace SKILL.md via a temp file in the same directory and rename, so a
+/// failed write never leaves a truncated skill behind.
+fn replace_skill_markdown(path: &Path, contents: &str) -> Result<()> {
+    let write_err =
+        |err: std::io::Error| MsError::Config(format!("write {}: {err}", path.display()));
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let mut staged = tempfile::NamedTempFile::new_in(dir).map_err(write_err)?;
+    std::io::Write::write_all(&mut staged, contents.as_bytes()).map_err(write_err)?;
+    staged.persist(path).map_err(|err| write_err(err.error))?;
+    Ok(())
+}
+
+fn emit_structured(
+    ctx: &AppContext,
+    skill_id: &str,
+    message: &str,
+    changes: &[EditChange],
+    spec: &SkillSpec,
+) -> Result<()> {
+    if ctx.output_format != OutputFormat::Human {
+        let sections: Vec<serde_json::Value> = spec
+            .sections
+            .iter()
+            .map(|section| {
+                serde_json::json!({
+                    "id": section.id,
+                    "title": section.title,
+                    "blocks": section.blocks.iter().map(|b| b.id.as_str()).collect::<Vec<_>>(),
+                })
+            })
+            .collect();
+        return emit_json(&serde_json::json!({
+            "status": "ok",
+            "skill_id": skill_id,
+            "commit_message": message,
+            "changes": changes,
+            "sections": sections,
+        }));
+    }
+
+    let mut layout = HumanLayout::new();
+    layout.title(&format!("Edited {skill_id}"));
+    for change in changes {
+        let target = match (&change.section_id, &change.block_id, &change.field) {
+            (_, Some(block), _) => format!(" [{block}]"),
+            (Some(section), None, _) => format!(" [{section}]"),
+            (None, None, Some(field)) => format!(" [{field}]"),
+            (None, None, None) => String::new(),
+        };
+        layout.bullet(&format!("{}{target}", change.operation));
+    }
+    emit_human(layout);
+    Ok(())
+}
+
+fn run_bulk(ctx: &AppContext, args: &EditArgs) -> Result<()> {
+    let (filter, operations) = bulk_request(args)?;
+    let selector = BulkSelector::parse(&filter)?;
+
+    let mut selected = Vec::new();
+    let mut offset = 0usize;
+    loop {
+        let batch = ctx.db.list_skills(BULK_PAGE_SIZE, offset)?;
+        let count = batch.len();
+        offset += count;
+        for record in batch {
+            let spec = match ctx.git.read_skill(&record.id) {
+                Ok(spec) => spec,
+                Err(err) => {
+                    tracing::debug!(skill = %record.id, error = %err, "skipping unreadable skill");
+                    continue;
+                }
+            };
+            if selector.matches(
+                &spec,
+                &record.source_layer,
+                record.quality_score as f32,
+                record.is_deprecated,
+            ) {
+                selected.push((spec, record.source_layer));
+            }
+        }
+        if count < BULK_PAGE_SIZE {
+            break;
+        }
+    }
+
+    let mut plan = BulkEditPlan::build(&operations, selected);
+    for entry in &mut plan.entries {
+        if entry.status == BulkEditStatus::Planned
+            && let Ok(path) = resolve_skill_markdown(ctx, &entry.skill_id)
+            && let Some(layer) = read_only_layer(ctx, &path, &entry.skill_id)?
+        {
+            entry.status = BulkEditStatus::Invalid;
+            entry.error = Some(format!(
+                "read-only {} layer; fork it with `ms edit {} --fork-to project`",
+                layer.name(),
+                entry.skill_id
+            ));
+        }
+    }
+    if args.dry_run {
+        return emit_bulk(ctx, &filter, &plan, "dry_run");
+    }
+
+    let mut snapshots: BTreeMap<String, BulkSnapshot> = BTreeMap::new();
+    for entry in &plan.entries {
+        if entry.status == BulkEditStatus::Planned {
+            snapshots.insert(
+                entry.skill_id.clone(),
+                BulkSnapshot::capture(ctx, &entry.skill_id)?,
+            );
+        }
+    }
+
+    let tx_mgr = TxManager::new(
+        Arc::clone(&ctx.db),
+        Arc::clone(&ctx.git),
+        ctx.ms_root.clone(),
+    )?;
+    let outcome = {
+        let _lock = GlobalLock::acquire_or_busy(&ctx.ms_root, lock_timeout())?;
+        plan.apply(
+            |entry| write_bulk_entry(ctx, &tx_mgr, entry),
+            |entry| match snapshots.get(&entry.skill_id) {
+                Some(snapshot) => snapshot.restore(ctx, &entry.skill_id),
+                None => Ok(()),
+            },
+        )
+    };
+    ctx.search.commit()?;
+
+    for entry in &plan.entries {
+        if entry.status != BulkEditStatus::Applied {
+            continue;
+        }
+        if let Some(skill_dir) = snapshots
+            .get(&entry.skill_id)
+            .and_then(|snapshot| snapshot.source.as_ref())
+            .and_then(|(path, _)| path.parent())
+        {
+            record_field_history(skill_dir, &entry.before, &entry.after)?;
+        }
+    }
+
+    let status = if outcome.is_ok() { "ok" } else { "rolled_back" };
+    emit_bulk(ctx, &filter, &plan, status)?;
+    outcome
+}
+
+/// Collect the filter and operations from flags and the optional ops document.
+fn bulk_request(args: &EditArgs) -> Result<(String, Vec<BulkOperation>)> {
+    let mut document = match args.ops.as_deref() {
+        Some(source) => {
+            let raw = if source == "-" {
+                let mut buf = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut buf)
+                    .map_err(|err| MsError::Config(format!("read ops from stdin: {err}")))?;
+                buf
+            } else {
+                std::fs::read_to_string(source)
+                    .map_err(|err| MsError::Config(format!("read {source}: {err}")))?
+            };
+            serde_json::from_str::<BulkOpsDocument>(&raw)
+                .map_err(|err| MsError::ValidationFailed(format!("ops document: {err}")))?
+        }
+        None => BulkOpsDocument::default(),
+    };
+
+    for expr in &args.set {
+        document.operations.push(BulkOperation::parse_set(expr)?);
+    }
+    document.operations.extend(
+        args.add_tag
+            .iter()
+            .map(|tag| BulkOperation::AddTag { tag: tag.clone() }),
+    );
+    document.operations.extend(
+        args.remove_tag
+            .iter()
+            .map(|tag| BulkOperation::RemoveTag { tag: tag.clone() }),
+    );
+    for op in &document.operations {
+        op.check()?;
+    }
+    if document.operations.is_empty() {
+        return Err(MsError::ValidationFailed(
+            "bulk edit needs at least one of --set, --add-tag, --remove-tag, or --ops".to_string(),
+        ));
+    }
+
+    let filter = args
+        .filter
+        .clone()
+        .or(document.filter)
+        .filter(|f| !f.trim().is_empty())
+        .ok_or_else(|| {
+            MsError::ValidationFailed("bulk edit needs a --filter selection".to_string())
+        })?;
+    Ok((filter, document.operations))
+}
+
+/// Page size used to walk every skill when selecting bulk edit targets.
+const BULK_PAGE_SIZE: usize = 500;
+
+/// Persist one edited skill through the archive, database, search index, and working tree.
+fn write_bulk_entry(ctx: &AppContext, tx_mgr: &TxManager, entry: &BulkEditEntry) -> Result<()> {
+    tx_mgr.write_skill_with_layer(
+        &entry.after,
+        skill_layer(&entry.layer),
+        &Provenance::new("edit"),
+    )?;
+    if let Some(record) = ctx.db.get_skill(&entry.skill_id)? {
+        ctx.search.index_skill(&record)?;
+    }
+    if let Ok(path) = resolve_skill_markdown(ctx, &entry.skill_id) {
+        std::fs::write(&path, compile_markdown(&entry.after))
+            .map_err(|err| MsError::Config(format!("write {}: {err}", path.display())))?;
+    }
+    Ok(())
+}
+
+/// A skill's archive files, database row, and working-tree SKILL.md as they
+/// were before a bulk edit, so a rollback puts back the original bytes.
+struct BulkSnapshot {
+    record: Option<SkillRecord>,
+    archive_dir: Option<PathBuf>,
+    archive: Vec<(PathBuf, Vec<u8>)>,
+    source: Option<(PathBuf, Vec<u8>)>,
+}
+
+impl BulkSnapshot {
+    fn capture(ctx: &AppContext, skill_id: &str) -> Result<Self> {
+        let read = |path: &Path| {
+            std::fs::read(path)
+                .map_err(|err| MsError::Config(format!("read {}: {err}", path.display())))
+        };
+        let archive_dir = ctx.git.skill_path(skill_id).filter(|dir| dir.is_dir());
+        let mut archive = Vec::new();
+        if let Some(dir) = &archive_dir {
+            for entry in walkdir::WalkDir::new(dir) {
+                let entry = entry
+                    .map_err(|err| MsError::Config(format!("walk {}: {err}", dir.display())))?;
+                if entry.file_type().is_file() {
+                    archive.push((entry.path().to_path_buf(), read(entry.path())?));
+                }
+            }
+        }
+        let source = match resolve_skill_markdown(ctx, skill_id) {
+            Ok(path) => {
+                let bytes = read(&path)?;
+                Some((path, bytes))
+            }
+            Err(_) => None,
+        };
+        Ok(Self {
+            record: ctx.db.get_skill(skill_id)?,
+            archive_dir,
+            archive,
+            source,
+        })
+    }
+
+    fn restore(&self, ctx: &AppContext, skill_id: &str) -> Result<()> {
+        let write = |path: &Path, bytes: &[u8]| {
+            std::fs::write(path, bytes)
+                .map_err(|err| MsError::Config(format!("write {}: {err}", path.display())))
+        };
+        if let Some(dir) = &self.archive_dir {
+            for (path, bytes) in &self.archive {
+                write(path, bytes)?;
+            }
+            ctx.git.commit_dirs(
+                std::slice::from_ref(dir),
+                &format!("Restore skill {skill_id} after failed bulk edit"),
+                &Provenance::new("edit"),
+            )?;
+        }
+        match &self.record {
+            Some(record) => {
+                ctx.db.upsert_skill(record)?;
+                ctx.search.index_skill(record)?;
+            }
+            None => {
+                ctx.db.delete_skill(skill_id)?;
+                ctx.search.delete_skill(skill_id)?;
+            }
+        }
+        if let Some((path, bytes)) = &self.source {
+            write(path, bytes)?;
+        }
+        Ok(())
+    }
+}
+
+/// The read-only layer `path` belongs to, if any.
+pub(crate) fn read_only_layer(
+    ctx: &AppContext,
+    path: &Path,
+    skill_id: &str,
+) -> Result<Option<ReadOnlyLayer>> {
+    let policy = &ctx.config.layers;
+    if policy.community.read_only
+        && ctx
+            .config
+            .skill_paths
+            .community
+            .iter()
+            .any(|root| is_within(path, &expand_path(root)))
+    {
+        return Ok(Some(ReadOnlyLayer::Community));
+    }
+    if policy.bundle.read_only
+        && is_within(path, ctx.git.root())
+        && BundleRegistry::provides_skill(ctx.git.root(), skill_id)?
+    {
+        return Ok(Some(ReadOnlyLayer::Bundle));
+    }
+    Ok(None)
+}
+
+fn is_within(path: &Path, root: &Path) -> bool {
+    let canonical = |p: &Path| std::fs::canonicalize(p).unwrap_or_else(|_| p.to_path_buf());
+    canonical(path).starts_with(canonical(root))
+}
+
+/// The SKILL.md an edit may rewrite: `path` itself, or its fork when `path`
+/// is in a read-only layer and `fork_to` asks for one.
+pub(crate) fn writable_skill_markdown(
+    ctx: &AppContext,
+    path: PathBuf,
+    fork_to: Option<ForkTarget>,
+) -> Result<PathBuf> {
+    let raw = std::fs::read_to_string(&path)
+        .map_err(|err| MsError::Config(format!("read {}: {err}", path.display())))?;
+    let spec = parse_markdown(&raw)?;
+    let skill_id = spec.metadata.id.as_str();
+    let Some(layer) = read_only_layer(ctx, &path, skill_id)? else {
+        return Ok(path);
+    };
+    match fork_to {
+        Some(ForkTarget::Project) => fork_to_project(ctx, &path, &raw, &spec, layer),
+        None => Err(MsError::ValidationFailed(format!(
+            "{skill_id} is in the read-only {name} layer; rerun with --fork-to project to edit \
+             a project copy, or set `[layers.{name}] read_only = false`",
+            name = layer.name()
+        ))),
+    }
+}
+
+/// Copy a read-only skill's SKILL.md into the first project skill path,
+/// recording its origin, and index the copy. An existing fork is reused.
+fn fork_to_project(
+    ctx: &AppContext,
+    origin_path: &Path,
+    raw: &str,
+    origin: &SkillSpec,
+    layer: ReadOnlyLayer,
+) -> Result<PathBuf> {
+    let skill_id = &origin.metadata.id;
+    let root = ctx.config.skill_paths.project.first().map_or_else(
+        || PathBuf::from(".ms/skills"),
+        |path| ctx.project_skill_path(path),
+    );
+    let fork_dir = root.join(skill_id);
+    let fork_path = fork_dir.join("SKILL.md");
+    if fork_path.exists() {
+        let existing = std::fs::read_to_string(&fork_path)
+            .map_err(|err| MsError::Config(format!("read {}: {err}", fork_path.display())))?;
+        if parse_markdown(&existing)?.metadata.forked_from.is_some() {
+            return Ok(fork_path);
+        }
+        return Err(MsError::ValidationFailed(format!(
+            "{} already exists and is not a fork of {skill_id}",
+            fork_path.display()
+        )));
+    }
+
+    let origin_layer = match layer {
+        ReadOnlyLayer::Community => SkillLayer::Base,
+        ReadOnlyLayer::Bundle => ctx
+            .db
+            .get_skill(skill_id)?
+            .map_or(SkillLayer::Base, |record| skill_layer(&record.source_layer)),
+    };
+    let fork = fork_spec(origin, origin_layer, origin_path, raw);
+    std::fs::create_dir_all(&fork_dir)
+        .map_err(|err| MsError::Config(format!("create {}: {err}", fork_dir.display())))?;
+    std::fs::write(&fork_path, compile_markdown(&fork))
+        .map_err(|err| MsError::Config(format!("write {}: {err}", fork_path.display())))?;
+    index_one(ctx, &fork_path, SkillLayer::Project)?;
+    if ctx.output_format == OutputFormat::Human {
+        eprintln!("Forked {skill_id} to {}", fork_path.display());
+    }
+    Ok(fork_path)
+}
+
+fn skill_layer(layer: &str) -> SkillLayer {
+    match SearchLayer::from_str(layer) {
+        Some(SearchLayer::Base) => SkillLayer::Base,
+        Some(SearchLayer::Org) => SkillLayer::Org,
+        Some(SearchLayer::User) => SkillLayer::User,
+        Some(SearchLayer::Project) | None => SkillLayer::Project,
+    }
+}
+
+fn emit_bulk(ctx: &AppContext, filter: &str, plan: &BulkEditPlan, status: &str) -> Result<()> {
+    if ctx.output_format != OutputFormat::Human {
+        let payload = serde_json::json!({
+            "status": status,
+            "dry_run": status == "dry_run",
+            "filter": filter,
+            "selected": plan.entries.len(),
+            "changed": plan.changed_count(),
+            "operations": plan.operations,
+            "results": plan.entries,
+        });
+        return emit_json(&payload);
+    }
+    emit_human(render_bulk_human(filter, plan, status));
+    Ok(())
+}
+
+fn render_bulk_human(filter: &str, plan: &BulkEditPlan, status: &str) -> HumanLayout {
+    let mut layout = HumanLayout::new();
+    layout
+        .title(if status == "dry_run" {
+            "Bulk Edit Plan (dry run)"
+        } else {
+            "Bulk Edit"
+        })
+        .kv("Filter", filter)
+        .kv("Selected", &plan.entries.len().to_string())
+        .kv("Changed", &plan.changed_count().to_string())
+        .blank();
+
+    if plan.entries.is_empty() {
+        layout.push_line("No skills matched the filter.");
+        return layout;
+    }
+    for entry in &plan.entries {
+        let marker = match entry.status {
+            BulkEditStatus::Planned => "[plan]",
+            BulkEditStatus::Unchanged => "[same]",
+            BulkEditStatus::Invalid => "[fail]",
+            BulkEditStatus::Applied => "[ok]",
+            BulkEditStatus::RolledBack => "[undo]",
+            BulkEditStatus::Skipped => "[skip]",
+        };
+        layout.section(&format!("{marker} {} ({})", entry.skill_id, entry.layer));
+        for change in &entry.changes {
+            layout.bullet(&format!(
+                "{}: {} -> {}",
+                change.field, change.old, change.new
+            ));
+        }
+        if let Some(error) = &entry.error {
+            layout.kv("Error", error);
+        }
+    }
+    if status == "rolled_back" {
+        layout
+            .blank()
+            .push_line("No changes were kept; every written skill was restored.");
+    }
+    layout
+}
+
 fn edit_spec_path(skill_dir: &std::path::Path) -> PathBuf {
     skill_dir.join(".ms").join("spec_edit.yaml")
 }
 
-fn run_editor(gate: &SafetyGate, editor: &str, path: &PathBuf) -> Result<()> {
+pub(crate) fn run_editor(gate: &SafetyGate, editor: &str, path: &PathBuf) -> Result<()> {
     // Parse editor string into command and arguments
     // Handle quoted paths for editors with spaces in path
     let (cmd, args) = parse_editor_string(editor)?;
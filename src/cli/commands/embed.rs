@@ -15,7 +15,7 @@ pub struct EmbedArgs {
     /// Text to embed
     pub text: String,
 
-    /// Override embedding backend (hash, api, local)
+    /// Override embedding backend (hash, api, onnx)
     #[arg(long, short)]
     pub backend: Option<String>,
 
@@ -32,7 +32,7 @@ pub fn run(ctx: &AppContext, args: &EmbedArgs) -> Result<()> {
     // Override backend if specified
     let mut config = ctx.config.search.clone();
     if let Some(ref backend) = args.backend {
-        config.embedding_backend = backend.clone();
+        config.embeddings.backend = Some(backend.clone());
     }
 
     if ctx.output_format != OutputFormat::Human {
@@ -58,7 +58,7 @@ fn run_human(
     println!("  \"{}\"", args.text.green());
     println!();
 
-    let embedding = embedder.embed(&args.text);
+    let embedding = embedder.embed(&args.text)?;
 
     println!("{}", "Embedding Result".bold());
     println!("  Length: {} floats", embedding.len());
@@ -104,7 +104,7 @@ fn run_human(
         println!("  Text A: \"{}\"", args.text.green());
         println!("  Text B: \"{}\"", compare_text.green());
 
-        let embedding_b = embedder.embed(compare_text);
+        let embedding_b = embedder.embed(compare_text)?;
 
         // Calculate cosine similarity
         let dot: f32 = embedding
@@ -143,7 +143,7 @@ fn run_robot(
     config: &crate::config::SearchConfig,
 ) -> Result<()> {
     let embedder = build_embedder(config)?;
-    let embedding = embedder.embed(&args.text);
+    let embedding = embedder.embed(&args.text)?;
 
     let mut output = serde_json::json!({
         "status": "ok",
@@ -177,7 +177,7 @@ fn run_robot(
     });
 
     if let Some(ref compare_text) = args.compare {
-        let embedding_b = embedder.embed(compare_text);
+        let embedding_b = embedder.embed(compare_text)?;
         let dot: f32 = embedding
             .iter()
             .zip(embedding_b.iter())
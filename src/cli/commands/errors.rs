@@ -0,0 +1,121 @@
+//! ms errors - Catalog of machine-readable error codes
+//!
+//! Robot-mode errors (`--robot`, and `isError` results of MCP tools) carry a
+//! stable `code` id from this catalog. The catalog is generated from
+//! [`ErrorCode`], so it lists exactly the codes ms can emit. Works before
+//! `ms init`.
+
+use clap::{Args, Subcommand};
+use colored::Colorize;
+
+use crate::cli::output::{OutputFormat, emit_json};
+use crate::error::{ErrorCode, ExitGroup, Result};
+
+#[derive(Args, Debug)]
+pub struct ErrorsArgs {
+    #[command(subcommand)]
+    pub command: ErrorsCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ErrorsCommand {
+    /// List every error code with its exit code and description
+    List(ErrorsListArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct ErrorsListArgs {
+    /// Only codes in this category (skill, index, config, search, network,
+    /// storage, git, validation, internal)
+    #[arg(long)]
+    pub category: Option<String>,
+}
+
+pub fn run(format: OutputFormat, args: &ErrorsArgs) -> Result<()> {
+    match &args.command {
+        ErrorsCommand::List(list) => run_list(format, list),
+    }
+}
+
+fn run_list(format: OutputFormat, args: &ErrorsListArgs) -> Result<()> {
+    let codes: Vec<ErrorCode> = ErrorCode::all()
+        .filter(|code| {
+            args.category
+                .as_deref()
+                .is_none_or(|category| code.category() == category)
+        })
+        .collect();
+
+    if format != OutputFormat::Human {
+        let errors: Vec<_> = codes
+            .iter()
+            .map(|code| {
+                serde_json::json!({
+                    "code": code.as_str(),
+                    "numeric_code": code.numeric(),
+                    "category": code.category(),
+                    "exit_code": code.exit_group().code(),
+                    "recoverable": code.is_recoverable(),
+                    "description": code.description(),
+                    "suggestion": code.suggestion(),
+                })
+            })
+            .collect();
+        let exit_codes: Vec<_> = ExitGroup::ALL
+            .iter()
+            .map(|group| {
+                serde_json::json!({
+                    "exit_code": group.code(),
+                    "group": group.as_str(),
+                    "description": group.description(),
+                })
+            })
+            .collect();
+        let payload = serde_json::json!({
+            "status": "ok",
+            "exit_codes": exit_codes,
+            "errors": errors,
+        });
+        return emit_json(&payload);
+    }
+
+    println!("{}", "Error Codes".bold());
+    println!("{}", "─".repeat(72));
+    println!("{:<5} {:<26} {:>4}  Description", "Code", "Id", "Exit");
+    for code in &codes {
+        println!(
+            "{:<5} {:<26} {:>4}  {}",
+            code.code_string(),
+            code.as_str(),
+            code.exit_group().code(),
+            code.description()
+        );
+    }
+    println!();
+    println!("{}", "Exit Codes".bold());
+    println!("{}", "─".repeat(72));
+    println!("   0  success");
+    for group in ExitGroup::ALL {
+        println!("{:>4}  {}", group.code(), group.description());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct TestCli {
+        #[command(subcommand)]
+        cmd: ErrorsCommand,
+    }
+
+    #[test]
+    fn parse_errors_list() {
+        let parsed = TestCli::parse_from(["test", "list", "--category", "skill"]);
+        let ErrorsCommand::List(args) = parsed.cmd;
+        assert_eq!(args.category.as_deref(), Some("skill"));
+    }
+}
@@ -16,6 +16,8 @@ use crate::cli::commands::load::{
 };
 use crate::cli::output::OutputFormat;
 use crate::cli::output::{HumanLayout, emit_json};
+use crate::core::resources::DEFAULT_INLINE_RESOURCE_BYTES;
+use crate::core::slice_query::DEFAULT_TOP_K;
 use crate::error::{MsError, Result};
 use crate::storage::sqlite::ExperimentEventRecord;
 
@@ -244,7 +246,7 @@ fn run_create(ctx: &AppContext, args: &ExperimentCreateArgs) -> Result<()> {
         ));
     }
 
-    let skill_id = resolve_skill_id(ctx, &args.skill)?;
+    let skill_id = ctx.resolve_skill_id(&args.skill)?;
 
     let (variants_json, allocation_json) =
         build_variants_payload(&args.variant, &strategy, &args.weight)?;
@@ -282,7 +284,7 @@ fn run_create(ctx: &AppContext, args: &ExperimentCreateArgs) -> Result<()> {
 
 fn run_list(ctx: &AppContext, args: &ExperimentListArgs) -> Result<()> {
     let skill_id = match &args.skill {
-        Some(skill) => Some(resolve_skill_id(ctx, skill)?),
+        Some(skill) => Some(ctx.resolve_skill_id(skill)?),
         None => None,
     };
 
@@ -458,6 +460,10 @@ fn run_load(ctx: &AppContext, args: &ExperimentLoadArgs) -> Result<()> {
         dry_run: false,
         level: args.level.clone(),
         pack: args.pack,
+        max_tokens: None,
+        slices: None,
+        select: Vec::new(),
+        top_k: DEFAULT_TOP_K,
         mode: args.mode,
         contract: args.contract,
         contract_id: args.contract_id.clone(),
@@ -467,6 +473,12 @@ fn run_load(ctx: &AppContext, args: &ExperimentLoadArgs) -> Result<()> {
         deps: args.deps,
         experiment_id: Some(record.id.clone()),
         variant_id: Some(selection.variant.id.clone()),
+        model: None,
+        session_id: None,
+        trace_resolution: false,
+        trial: false,
+        with_resources: false,
+        resource_cap: DEFAULT_INLINE_RESOURCE_BYTES,
     };
 
     let load_result = load_skill(ctx, &load_args, &record.skill_id)?;
@@ -1153,18 +1165,6 @@ fn get_experiment(ctx: &AppContext, id: &str) -> Result<crate::storage::sqlite::
         .ok_or_else(|| MsError::NotFound(format!("experiment not found: {id}")))
 }
 
-fn resolve_skill_id(ctx: &AppContext, input: &str) -> Result<String> {
-    if let Some(skill) = ctx.db.get_skill(input)? {
-        return Ok(skill.id);
-    }
-    if let Ok(Some(alias)) = ctx.db.resolve_alias(input) {
-        if let Some(skill) = ctx.db.get_skill(&alias.canonical_id)? {
-            return Ok(skill.id);
-        }
-    }
-    Err(MsError::SkillNotFound(format!("skill not found: {input}")))
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,253 @@
+//! ms export - Write skills into an agent's native skill directory
+//!
+//! `ms export --target claude-code --all` compiles each skill to SKILL.md
+//! under `~/.claude/skills/<id>/`; `--target cursor` writes
+//! `.cursor/rules/<id>.mdc` in the current project. A manifest next to the
+//! exported files records what was written, so edits made in place are
+//! detected and kept unless `--force` is given.
+
+use std::path::{Path, PathBuf};
+
+use clap::{Args, ValueEnum};
+use serde::Serialize;
+
+use crate::agent_detection::{
+    ExportManifest, ExportOutcome, ExportSkill, export_skill, export_target,
+};
+use crate::app::AppContext;
+use crate::cli::output::{HumanLayout, OutputFormat, emit_human, emit_json};
+use crate::core::SkillSpec;
+use crate::core::spec_lens::parse_markdown;
+use crate::error::{MsError, Result};
+use crate::storage::sqlite::SkillRecord;
+
+#[derive(Args, Debug)]
+pub struct ExportArgs {
+    /// Agent to export for
+    #[arg(long, value_enum)]
+    pub target: ExportTargetArg,
+
+    /// Skill to export (repeatable; ids or aliases)
+    #[arg(long = "skill", value_name = "ID", required_unless_present = "all")]
+    pub skills: Vec<String>,
+
+    /// Export every indexed skill
+    #[arg(long, conflicts_with = "skills")]
+    pub all: bool,
+
+    /// Include deprecated skills
+    #[arg(long)]
+    pub include_deprecated: bool,
+
+    /// Overwrite files edited since the last export
+    #[arg(long)]
+    pub force: bool,
+
+    /// Export into this directory instead of the agent's default
+    #[arg(long)]
+    pub dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportTargetArg {
+    /// ~/.claude/skills/<id>/SKILL.md
+    #[value(name = "claude-code")]
+    ClaudeCode,
+    /// .cursor/rules/<id>.mdc
+    #[value(name = "cursor")]
+    Cursor,
+}
+
+impl ExportTargetArg {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::ClaudeCode => "claude-code",
+            Self::Cursor => "cursor",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct SkillResult {
+    skill_id: String,
+    #[serde(flatten)]
+    outcome: Outcome,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum Outcome {
+    Exported(ExportOutcome),
+    Skipped {
+        status: &'static str,
+        reason: String,
+    },
+}
+
+pub fn run(ctx: &AppContext, args: &ExportArgs) -> Result<()> {
+    let target = export_target(args.target.as_str())?;
+    let root = match &args.dir {
+        Some(dir) => dir.clone(),
+        None => target.default_root()?,
+    };
+    let mut manifest = ExportManifest::load(&root)?;
+
+    let mut results = Vec::new();
+    for record in selected_skills(ctx, args)? {
+        if record.is_deprecated && !args.include_deprecated {
+            results.push(SkillResult {
+                skill_id: record.id,
+                outcome: Outcome::Skipped {
+                    status: "skipped",
+                    reason: "deprecated (use --include-deprecated)".to_string(),
+                },
+            });
+            continue;
+        }
+        if record.is_sealed() {
+            results.push(SkillResult {
+                skill_id: record.id,
+                outcome: Outcome::Skipped {
+                    status: "skipped",
+                    reason: "sensitive (would be written unencrypted)".to_string(),
+                },
+            });
+            continue;
+        }
+        let skill = ExportSkill {
+            id: record.id.clone(),
+            spec: skill_spec(ctx, &record)?,
+            source_dir: Path::new(&record.source_path)
+                .parent()
+                .map(Path::to_path_buf),
+            resources: archived_resources(ctx, &record.id)?,
+        };
+        let outcome = export_skill(target.as_ref(), &root, &mut manifest, &skill, args.force)?;
+        results.push(SkillResult {
+            skill_id: record.id,
+            outcome: Outcome::Exported(outcome),
+        });
+    }
+    manifest.save(&root)?;
+
+    let conflicts = results
+        .iter()
+        .filter(|result| {
+            matches!(
+                result.outcome,
+                Outcome::Exported(ExportOutcome::Conflict { .. })
+            )
+        })
+        .count();
+
+    if ctx.output_format == OutputFormat::Human {
+        emit_human(human_report(target.name(), &root, &results));
+    } else {
+        emit_json(&serde_json::json!({
+            "status": if conflicts == 0 { "ok" } else { "conflict" },
+            "target": target.name(),
+            "root": root.display().to_string(),
+            "skills": results,
+        }))?;
+    }
+
+    if conflicts > 0 {
+        return Err(MsError::ValidationFailed(format!(
+            "{conflicts} skill(s) have local edits in {}; re-run with --force to overwrite",
+            root.display()
+        )));
+    }
+    Ok(())
+}
+
+fn selected_skills(ctx: &AppContext, args: &ExportArgs) -> Result<Vec<SkillRecord>> {
+    if !args.all {
+        return args
+            .skills
+            .iter()
+            .map(|skill| ctx.resolve_skill(skill))
+            .collect();
+    }
+    let mut out = Vec::new();
+    let mut offset = 0usize;
+    let limit = 500usize;
+    loop {
+        let batch = ctx.db.list_skills(limit, offset)?;
+        let count = batch.len();
+        offset += count;
+        out.extend(batch);
+        if count < limit {
+            break;
+        }
+    }
+    Ok(out)
+}
+
+/// The archived spec, or the indexed SKILL.md for skills never archived.
+fn skill_spec(ctx: &AppContext, record: &SkillRecord) -> Result<SkillSpec> {
+    if let Ok(spec) = ctx.git.read_skill(&record.id) {
+        return Ok(spec);
+    }
+    let content = std::fs::read_to_string(&record.source_path).map_err(|err| {
+        MsError::NotFound(format!(
+            "no archived spec for {} and cannot read {}: {err}",
+            record.id, record.source_path
+        ))
+    })?;
+    let mut spec = parse_markdown(&content)?;
+    if spec.metadata.id.is_empty() {
+        spec.metadata.id.clone_from(&record.id);
+    }
+    Ok(spec)
+}
+
+/// The skill's recorded resources as archived, keyed by their path relative
+/// to the skill directory.
+fn archived_resources(ctx: &AppContext, skill_id: &str) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+    let mut resources = Vec::new();
+    for record in ctx.db.get_skill_resources(skill_id)? {
+        if let Some(path) = ctx.git.resource_path(skill_id, &record.path)
+            && path.is_file()
+        {
+            resources.push((PathBuf::from(&record.path), std::fs::read(&path)?));
+        }
+    }
+    Ok(resources)
+}
+
+fn human_report(target: &str, root: &Path, results: &[SkillResult]) -> HumanLayout {
+    let mut layout = HumanLayout::new();
+    layout
+        .title("Skill Export")
+        .kv("Target", target)
+        .kv("Directory", &root.display().to_string());
+    layout.blank();
+    for result in results {
+        let line = match &result.outcome {
+            Outcome::Exported(ExportOutcome::Written { files }) => {
+                format!("{}: wrote {} file(s)", result.skill_id, files.len())
+            }
+            Outcome::Exported(ExportOutcome::Unchanged) => {
+                format!("{}: up to date", result.skill_id)
+            }
+            Outcome::Exported(ExportOutcome::Conflict { files }) => format!(
+                "{}: edited locally, not overwritten ({})",
+                result.skill_id,
+                files
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Outcome::Skipped { reason, .. } => {
+                format!("{}: skipped, {reason}", result.skill_id)
+            }
+        };
+        layout.bullet(&line);
+    }
+    if results.is_empty() {
+        layout.push_line("No skills to export.");
+    }
+    layout
+}
@@ -0,0 +1,275 @@
+//! ms export-site - Export the skill catalog for client-side search
+//!
+//! Writes `skills.cards.json`: one card per skill (the `ms show` JSON fields
+//! plus tags) with its precomputed embedding, in the format read by the
+//! `ms-search-core` WASM module. The docs site only embeds queries, never
+//! skill content.
+
+use std::path::PathBuf;
+
+use clap::Args;
+use ms_search_core::{CATALOG_FORMAT_VERSION, Card, CardCatalog, EmbeddingInfo, RrfWeights};
+
+use crate::app::AppContext;
+use crate::cli::output::{HumanLayout, OutputFormat, emit_json};
+use crate::error::{MsError, Result};
+use crate::search::embeddings::{Embedder, build_embedder};
+use crate::storage::sqlite::SkillRecord;
+
+/// File name of the exported catalog inside the output directory.
+pub const CATALOG_FILE: &str = "skills.cards.json";
+
+#[derive(Args, Debug)]
+pub struct ExportSiteArgs {
+    /// Output directory for the catalog
+    #[arg(long, default_value = "site")]
+    pub out: PathBuf,
+
+    /// Include full skill bodies (larger catalog; bodies become searchable)
+    #[arg(long)]
+    pub include_body: bool,
+
+    /// Include deprecated skills
+    #[arg(long)]
+    pub include_deprecated: bool,
+}
+
+pub fn run(ctx: &AppContext, args: &ExportSiteArgs) -> Result<()> {
+    let embedder = build_embedder(&ctx.config.search)?;
+    let skills = load_all_skills(ctx)?;
+    let mut reused = 0usize;
+    let mut cards = Vec::new();
+    for skill in skills
+        .iter()
+        .filter(|skill| args.include_deprecated || !skill.is_deprecated)
+    {
+        let stored = ctx
+            .db
+            .get_embedding(&skill.id)?
+            .filter(|record| {
+                record.dims == embedder.dims()
+                    && record.embedder_type == embedder.name()
+                    && record.content_hash.as_deref() == Some(skill.content_hash.as_str())
+            })
+            .map(|record| record.embedding);
+        if stored.is_some() {
+            reused += 1;
+        }
+        let embedding = match stored {
+            Some(embedding) => embedding,
+            None => embedder.embed(&embedding_text(skill))?,
+        };
+        cards.push(skill_card(skill, embedding, args.include_body));
+    }
+
+    let catalog = CardCatalog {
+        format_version: CATALOG_FORMAT_VERSION,
+        embedding: Some(EmbeddingInfo {
+            backend: embedder.name().to_string(),
+            dims: embedder.dims(),
+        }),
+        rrf: RrfWeights {
+            lexical_weight: ctx.config.search.bm25_weight,
+            semantic_weight: ctx.config.search.semantic_weight,
+            ..RrfWeights::default()
+        },
+        cards,
+    };
+    let json = catalog
+        .to_json()
+        .map_err(|err| MsError::Serialization(err.to_string()))?;
+    std::fs::create_dir_all(&args.out)?;
+    let path = args.out.join(CATALOG_FILE);
+    std::fs::write(&path, &json)?;
+
+    let client_side_queries = embedder.name() == "hash";
+    if ctx.output_format != OutputFormat::Human {
+        let payload = serde_json::json!({
+            "status": "ok",
+            "path": path.display().to_string(),
+            "cards": catalog.cards.len(),
+            "embeddings_reused": reused,
+            "embedding_backend": embedder.name(),
+            "dims": embedder.dims(),
+            "bytes": json.len(),
+            "client_side_semantic": client_side_queries,
+        });
+        return emit_json(&payload);
+    }
+
+    let mut layout = HumanLayout::new();
+    layout
+        .title("Site Export")
+        .kv("Path", &path.display().to_string())
+        .kv("Cards", &catalog.cards.len().to_string())
+        .kv(
+            "Embeddings",
+            &format!(
+                "{} x {} dims ({reused} reused from the index)",
+                embedder.name(),
+                embedder.dims()
+            ),
+        )
+        .kv("Size", &format!("{} bytes", json.len()));
+    if !client_side_queries {
+        layout.push_line(
+            "Note: queries can only be embedded client-side with the hash backend; \
+             the docs site will fall back to lexical search.",
+        );
+    }
+    crate::cli::output::emit_human(layout);
+    Ok(())
+}
+
+fn load_all_skills(ctx: &AppContext) -> Result<Vec<SkillRecord>> {
+    let mut out = Vec::new();
+    let mut offset = 0usize;
+    let limit = 1000usize;
+    loop {
+        let batch = ctx.db.list_skills(limit, offset)?;
+        let count = batch.len();
+        offset += count;
+        out.extend(batch);
+        if count < limit {
+            break;
+        }
+    }
+    Ok(out)
+}
+
+/// Text embedded for a skill: name, description, tags, and body. A sealed
+/// body is left out, so only a sensitive skill's readable metadata is
+/// published.
+pub(crate) fn embedding_text(skill: &SkillRecord) -> String {
+    let body = if skill.is_sealed() { "" } else { &skill.body };
+    format!(
+        "{} {} {} {}",
+        skill.name,
+        skill.description,
+        skill_tags(&skill.metadata_json).join(" "),
+        body
+    )
+}
+
+fn skill_card(skill: &SkillRecord, embedding: Vec<f32>, include_body: bool) -> Card {
+    Card {
+        id: skill.id.clone(),
+        name: skill.name.clone(),
+        description: skill.description.clone(),
+        layer: skill.source_layer.clone(),
+        version: skill.version.clone(),
+        author: skill.author.clone(),
+        quality_score: skill.quality_score,
+        is_deprecated: skill.is_deprecated,
+        deprecation_reason: skill.deprecation_reason.clone(),
+        body: (include_body && !skill.is_sealed()).then(|| skill.body.clone()),
+        tags: skill_tags(&skill.metadata_json),
+        embedding: Some(embedding),
+    }
+}
+
+/// Tags from metadata, stored either as an array or a comma-separated string.
+fn skill_tags(metadata_json: &str) -> Vec<String> {
+    let Ok(meta) = serde_json::from_str::<serde_json::Value>(metadata_json) else {
+        return Vec::new();
+    };
+    match meta.get("tags") {
+        Some(serde_json::Value::Array(tags)) => tags
+            .iter()
+            .filter_map(|tag| tag.as_str().map(str::to_lowercase))
+            .collect(),
+        Some(serde_json::Value::String(tags)) => crate::search::SearchFilters::parse_tags(tags),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::embeddings::HashEmbedder;
+    use ms_search_core::SearchCatalog;
+
+    fn skill(id: &str, name: &str, tags: &str) -> SkillRecord {
+        SkillRecord {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: format!("{name} guidance"),
+            version: None,
+            author: None,
+            source_path: format!("/skills/{id}/SKILL.md"),
+            source_layer: "project".to_string(),
+            git_remote: None,
+            git_commit: None,
+            content_hash: "hash".to_string(),
+            body: "body text".to_string(),
+            metadata_json: tags.to_string(),
+            assets_json: "[]".to_string(),
+            token_count: 10,
+            quality_score: 0.8,
+            indexed_at: "2026-01-01T00:00:00Z".to_string(),
+            modified_at: "2026-01-01T00:00:00Z".to_string(),
+            is_deprecated: false,
+            deprecation_reason: None,
+        }
+    }
+
+    #[test]
+    fn tags_accept_array_or_comma_string() {
+        assert_eq!(
+            skill_tags(r#"{"tags":["Rust","cli"]}"#),
+            vec!["rust", "cli"]
+        );
+        assert_eq!(
+            skill_tags(r#"{"tags":"git, workflow"}"#),
+            vec!["git", "workflow"]
+        );
+        assert!(skill_tags("not json").is_empty());
+    }
+
+    #[test]
+    fn exported_catalog_matches_native_hash_search() {
+        let embedder = HashEmbedder::new(64);
+        let skills = [
+            skill("git-workflow", "Git Workflow", r#"{"tags":["git"]}"#),
+            skill("rust-errors", "Rust Errors", r#"{"tags":"rust"}"#),
+        ];
+        let catalog = CardCatalog {
+            format_version: CATALOG_FORMAT_VERSION,
+            embedding: Some(EmbeddingInfo {
+                backend: "hash".to_string(),
+                dims: 64,
+            }),
+            rrf: RrfWeights::default(),
+            cards: skills
+                .iter()
+                .map(|skill| skill_card(skill, embedder.embed(&embedding_text(skill)), false))
+                .collect(),
+        };
+        assert!(catalog.cards.iter().all(|card| card.body.is_none()));
+
+        let loaded = SearchCatalog::from_json(&catalog.to_json().unwrap()).unwrap();
+        let hits = loaded.search("rust errors", 2);
+        assert_eq!(hits[0].id, "rust-errors");
+
+        let query = embedder.embed("rust errors");
+        let native = embedder.similarity(&query, &embedder.embed(&embedding_text(&skills[1])));
+        assert!(native > 0.0);
+        assert_eq!(hits[0].semantic_rank, Some(1));
+    }
+
+    #[test]
+    fn sealed_body_is_not_published() {
+        let mut sealed = skill("internal-deploy", "Internal Deploy", "{}");
+        sealed.body = format!(
+            "# Internal Deploy\n\n{}c2VjcmV0\n",
+            crate::security::encryption::ENVELOPE_PREFIX
+        );
+        let card = skill_card(&sealed, Vec::new(), true);
+        assert!(card.body.is_none());
+        assert!(!embedding_text(&sealed).contains("c2VjcmV0"));
+        assert!(embedding_text(&sealed).contains("Internal Deploy guidance"));
+
+        let card = skill_card(&skill("git", "Git", "{}"), Vec::new(), true);
+        assert_eq!(card.body.as_deref(), Some("body text"));
+    }
+}
@@ -1,14 +1,25 @@
 //! ms feedback - Record and inspect skill feedback.
 
-use std::path::PathBuf;
+use std::io::Read;
 
 use clap::{Args, Subcommand};
 
 use crate::app::AppContext;
+use crate::cass::{Session, load_session_file, segment_session};
+use crate::cli::commands::bandit::default_bandit_path;
+use crate::cli::commands::{cass_client, expand_path};
 use crate::cli::output::OutputFormat;
 use crate::cli::output::{HumanLayout, emit_json};
 use crate::error::{MsError, Result};
-use crate::suggestions::bandit::{ContextFeatures, ContextualBandit, SkillFeedback};
+use crate::storage::MinedFeedbackRecord;
+use crate::suggestions::bandit::{
+    ContextFeatures, Reward, SignalBandit, SignalType, SkillFeedback, SuggestionContext,
+};
+use crate::suggestions::cooldown;
+use crate::suggestions::session_outcome::{
+    OutcomeSignals, Verdict, infer_outcome, loaded_skill_refs,
+};
+use crate::suggestions::{SuggestionOutcome, SuggestionRecord};
 
 #[derive(Args, Debug)]
 pub struct FeedbackArgs {
@@ -22,6 +33,8 @@ pub enum FeedbackCommand {
     Add(FeedbackAddArgs),
     /// List feedback records
     List(FeedbackListArgs),
+    /// Score the skills loaded in a session from how the session ended
+    Mine(FeedbackMineArgs),
 }
 
 #[derive(Args, Debug)]
@@ -44,6 +57,10 @@ pub struct FeedbackAddArgs {
     /// Optional comment
     #[arg(long)]
     pub comment: Option<String>,
+
+    /// Agent giving the feedback; only its bandit learns from it
+    #[arg(long, env = "MS_AGENT_ID")]
+    pub agent_id: Option<String>,
 }
 
 #[derive(Args, Debug)]
@@ -61,15 +78,28 @@ pub struct FeedbackListArgs {
     pub offset: usize,
 }
 
+#[derive(Args, Debug)]
+pub struct FeedbackMineArgs {
+    /// CASS session ID or path, or a transcript file
+    #[arg(required_unless_present = "hook")]
+    pub session: Option<String>,
+
+    /// Read a Claude Code `SessionEnd` hook payload from stdin and mine its
+    /// transcript; prints nothing and never fails
+    #[arg(long, conflicts_with = "session")]
+    pub hook: bool,
+}
+
 pub fn run(ctx: &AppContext, args: &FeedbackArgs) -> Result<()> {
     match &args.command {
         FeedbackCommand::Add(add) => run_add(ctx, add),
         FeedbackCommand::List(list) => run_list(ctx, list),
+        FeedbackCommand::Mine(mine) => run_mine(ctx, mine),
     }
 }
 
 fn run_add(ctx: &AppContext, args: &FeedbackAddArgs) -> Result<()> {
-    let skill_id = resolve_skill_id(ctx, &args.skill)?;
+    let skill_id = ctx.resolve_skill_id(&args.skill)?;
     let feedback_type = select_feedback_type(args)?;
 
     if let Some(rating) = args.rating {
@@ -88,15 +118,35 @@ fn run_add(ctx: &AppContext, args: &FeedbackAddArgs) -> Result<()> {
     )?;
 
     // Update the contextual bandit with this feedback
-    if let Err(e) = update_contextual_bandit(&skill_id, &feedback_type, args.rating) {
+    let agent_id = args.agent_id.as_deref();
+    if let Err(e) = update_contextual_bandit(ctx, &skill_id, &feedback_type, args.rating, agent_id)
+    {
         // Log but don't fail - bandit update is best-effort
         eprintln!("Warning: Failed to update bandit: {e}");
     }
 
+    // Negative feedback dismisses the skill from `ms suggest` for a while;
+    // positive feedback holds it back briefly, since the agent has it
+    let (db, config) = (&ctx.db, &ctx.config.suggestions);
+    let started = match feedback_type.as_str() {
+        "negative" => Some(cooldown::start_dismissal(db, config, &skill_id, agent_id)),
+        "positive" => Some(cooldown::start_acceptance(db, config, &skill_id, agent_id)),
+        _ => None,
+    };
+    let cooldown = match started {
+        Some(Ok(entry)) => Some(entry),
+        Some(Err(e)) => {
+            eprintln!("Warning: Failed to start suggestion cooldown: {e}");
+            None
+        }
+        None => None,
+    };
+
     if ctx.output_format != OutputFormat::Human {
         let payload = serde_json::json!({
             "status": "ok",
             "record": record,
+            "cooldown": cooldown,
         });
         return emit_json(&payload);
     }
@@ -116,13 +166,19 @@ fn run_add(ctx: &AppContext, args: &FeedbackAddArgs) -> Result<()> {
             "Comment",
             &record.comment.clone().unwrap_or_else(|| "-".to_string()),
         );
+    if let Some(entry) = &cooldown {
+        layout.kv(
+            "Suggestions paused",
+            &crate::utils::format::format_duration(entry.cooldown_seconds),
+        );
+    }
     crate::cli::output::emit_human(layout);
     Ok(())
 }
 
 fn run_list(ctx: &AppContext, args: &FeedbackListArgs) -> Result<()> {
     let skill_id = match &args.skill {
-        Some(skill) => Some(resolve_skill_id(ctx, skill)?),
+        Some(skill) => Some(ctx.resolve_skill_id(skill)?),
         None => None,
     };
 
@@ -157,6 +213,250 @@ fn run_list(ctx: &AppContext, args: &FeedbackListArgs) -> Result<()> {
     Ok(())
 }
 
+/// A skill scored by `ms feedback mine`.
+#[derive(Debug, serde::Serialize)]
+struct MinedSkill {
+    skill_id: String,
+    verdict: Verdict,
+    reward: f32,
+    signals: OutcomeSignals,
+    /// Already mined by an earlier run; nothing was recorded this time
+    already_mined: bool,
+}
+
+fn run_mine(ctx: &AppContext, args: &FeedbackMineArgs) -> Result<()> {
+    if args.hook {
+        // Like the suggestion hook, this must never get in the agent's way
+        if let Err(err) = mine_hook_payload(ctx) {
+            tracing::debug!(error = %err, "feedback hook failed");
+        }
+        return Ok(());
+    }
+    let reference = args.session.as_deref().unwrap_or_default();
+    let session = load_session(ctx, reference)?;
+    let mined = mine_session(ctx, &session)?;
+
+    if ctx.output_format != OutputFormat::Human {
+        let payload = serde_json::json!({
+            "status": "ok",
+            "session_id": session.id,
+            "skills": mined,
+        });
+        return emit_json(&payload);
+    }
+
+    if mined.is_empty() {
+        println!("No skills were loaded in session {}.", session.id);
+        return Ok(());
+    }
+    let mut layout = HumanLayout::new();
+    layout.title("Mined Feedback").kv("Session", &session.id);
+    for skill in &mined {
+        let mut summary = format!(
+            "{} · reward {:.2} · {}",
+            skill.verdict.as_str(),
+            skill.reward,
+            describe_signals(&skill.signals)
+        );
+        if skill.already_mined {
+            summary.push_str(" (already mined)");
+        }
+        layout.kv(&skill.skill_id, &summary);
+    }
+    crate::cli::output::emit_human(layout);
+    Ok(())
+}
+
+/// Mine the transcript named by a Claude Code hook payload on stdin.
+fn mine_hook_payload(ctx: &AppContext) -> Result<()> {
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input)?;
+    let payload: serde_json::Value = serde_json::from_str(&input)?;
+    let transcript = payload
+        .get("transcript_path")
+        .and_then(serde_json::Value::as_str)
+        .ok_or_else(|| MsError::ValidationFailed("hook payload has no transcript_path".into()))?;
+    let (session, _) = load_session_file(&expand_path(transcript))?;
+    mine_session(ctx, &session)?;
+    Ok(())
+}
+
+/// A transcript file when `reference` names one, otherwise a CASS session.
+fn load_session(ctx: &AppContext, reference: &str) -> Result<Session> {
+    let path = expand_path(reference);
+    if path.is_file() {
+        let (session, _) = load_session_file(&path)?;
+        return Ok(session);
+    }
+    cass_client(ctx, None, false).get_session(reference)
+}
+
+/// Score every skill loaded in `session`, feeding the bandits once per
+/// (session, skill) pair.
+fn mine_session(ctx: &AppContext, session: &Session) -> Result<Vec<MinedSkill>> {
+    let now = chrono::Utc::now();
+    let segmented = segment_session(session);
+
+    // Skills are found from the loads `ms load` recorded for this session
+    // and from the loads the transcript shows
+    let transcript_loads: Vec<(String, usize)> = loaded_skill_refs(session)
+        .into_iter()
+        .filter_map(|(reference, idx)| Some((ctx.resolve_skill_id(&reference).ok()?, idx)))
+        .collect();
+    let mut loads: Vec<(String, usize, Option<chrono::DateTime<chrono::Utc>>)> = Vec::new();
+    for (skill_id, used_at) in ctx.db.list_session_skill_loads(&session.id)? {
+        if loads.iter().any(|(seen, _, _)| *seen == skill_id) {
+            continue;
+        }
+        let idx = transcript_loads
+            .iter()
+            .find(|(id, _)| *id == skill_id)
+            .map_or(0, |(_, idx)| *idx);
+        let loaded_at = chrono::DateTime::parse_from_rfc3339(&used_at)
+            .ok()
+            .map(|at| at.with_timezone(&chrono::Utc));
+        loads.push((skill_id, idx, loaded_at));
+    }
+    for (skill_id, idx) in transcript_loads {
+        if !loads.iter().any(|(seen, _, _)| *seen == skill_id) {
+            loads.push((skill_id, idx, None));
+        }
+    }
+
+    let mut signal_bandit: Option<SignalBandit> = None;
+    let mut mined = Vec::new();
+    for (skill_id, idx, loaded_at) in loads {
+        let signals = infer_outcome(session, &segmented, idx);
+        let verdict = signals.verdict();
+        let reward = signals.reward(loaded_at.and_then(|at| (now - at).to_std().ok()));
+        let record = MinedFeedbackRecord {
+            session_id: session.id.clone(),
+            skill_id: skill_id.clone(),
+            verdict: verdict.as_str().to_string(),
+            reward: f64::from(reward),
+            signals_json: serde_json::to_string(&signals)?,
+            mined_at: now.to_rfc3339(),
+        };
+        let already_mined = !ctx.db.insert_mined_feedback(&record)?;
+        if !already_mined {
+            apply_mined_feedback(ctx, session, &skill_id, verdict, reward, &mut signal_bandit)?;
+        }
+        mined.push(MinedSkill {
+            skill_id,
+            verdict,
+            reward,
+            signals,
+            already_mined,
+        });
+    }
+
+    if let Some(bandit) = signal_bandit
+        && let Err(e) = bandit.save(&default_bandit_path())
+    {
+        eprintln!("Warning: Failed to update signal bandit: {e}");
+    }
+    Ok(mined)
+}
+
+/// Record a freshly mined verdict: settle the skill's suggestions in the
+/// session, mark its loads, and update the bandits.
+fn apply_mined_feedback(
+    ctx: &AppContext,
+    session: &Session,
+    skill_id: &str,
+    verdict: Verdict,
+    reward: f32,
+    signal_bandit: &mut Option<SignalBandit>,
+) -> Result<()> {
+    // Loading the skill is what a selected suggestion means
+    let suggestions = ctx
+        .db
+        .list_suggestion_records(skill_id, Some(&session.id))?;
+    ctx.db
+        .settle_session_suggestions(skill_id, &session.id, &SuggestionOutcome::Selected)?;
+    if verdict != Verdict::Inconclusive {
+        ctx.db
+            .record_session_outcome(skill_id, &session.id, verdict == Verdict::Success)?;
+    }
+
+    // The verdict trains the bandit of the agent the skill was suggested to
+    let agent_id = suggestions
+        .iter()
+        .find_map(|record| record.agent_id.as_deref());
+    let features = ContextFeatures::default();
+    if let Err(e) = ctx.update_bandit(agent_id, |bandit| {
+        bandit.update_with_reward(skill_id, &features, reward);
+    }) {
+        eprintln!("Warning: Failed to update bandit: {e}");
+    }
+
+    // Credit the signals that got the skill suggested in this session
+    let signals = credited_signals(&suggestions);
+    if verdict == Verdict::Inconclusive || signals.is_empty() {
+        return Ok(());
+    }
+    if signal_bandit.is_none() {
+        match SignalBandit::load(&default_bandit_path()) {
+            Ok(bandit) => *signal_bandit = Some(bandit),
+            Err(e) => {
+                eprintln!("Warning: Failed to load signal bandit: {e}");
+                return Ok(());
+            }
+        }
+    }
+    if let Some(bandit) = signal_bandit {
+        let outcome = if verdict == Verdict::Success {
+            Reward::Success
+        } else {
+            Reward::Failure
+        };
+        let context = SuggestionContext::default();
+        for signal in signals {
+            bandit.update(signal, outcome, &context);
+        }
+    }
+    Ok(())
+}
+
+/// Signals that contributed to the latest explained suggestion.
+fn credited_signals(suggestions: &[SuggestionRecord]) -> Vec<SignalType> {
+    let Some(scores) = suggestions
+        .iter()
+        .find_map(|record| record.explanation.as_ref())
+        .map(|explanation| &explanation.signals)
+    else {
+        return Vec::new();
+    };
+    let mut signals = Vec::new();
+    if scores.search_relevance > 0.0 {
+        signals.extend([SignalType::Bm25, SignalType::Embedding]);
+    }
+    if scores.contextual > 0.0 {
+        signals.push(SignalType::ProjectMatch);
+    }
+    if scores.personal_boost > 0.0 {
+        signals.push(SignalType::UserHistory);
+    }
+    signals
+}
+
+fn describe_signals(signals: &OutcomeSignals) -> String {
+    let tests = match signals.tests_passed {
+        Some(true) => "tests passed",
+        Some(false) => "tests failed",
+        None => "no tests",
+    };
+    let wrap_up = if signals.ended_in_wrap_up {
+        "wrapped up"
+    } else {
+        "no wrap-up"
+    };
+    format!(
+        "{tests}, {wrap_up}, {} error(s) after load",
+        signals.errors_after_load
+    )
+}
+
 fn select_feedback_type(args: &FeedbackAddArgs) -> Result<String> {
     if args.positive && args.negative {
         return Err(MsError::ValidationFailed(
@@ -177,29 +477,14 @@ fn select_feedback_type(args: &FeedbackAddArgs) -> Result<String> {
     ))
 }
 
-fn resolve_skill_id(ctx: &AppContext, input: &str) -> Result<String> {
-    if let Some(skill) = ctx.db.get_skill(input)? {
-        return Ok(skill.id);
-    }
-    if let Ok(Some(alias)) = ctx.db.resolve_alias(input) {
-        if let Some(skill) = ctx.db.get_skill(&alias.canonical_id)? {
-            return Ok(skill.id);
-        }
-    }
-    Err(MsError::SkillNotFound(format!("skill not found: {input}")))
-}
-
-/// Update the contextual bandit with skill feedback.
-fn update_contextual_bandit(
+/// Update `agent_id`'s persisted contextual bandit with skill feedback.
+pub(crate) fn update_contextual_bandit(
+    ctx: &AppContext,
     skill_id: &str,
     feedback_type: &str,
     rating: Option<i64>,
+    agent_id: Option<&str>,
 ) -> Result<()> {
-    let path = default_contextual_bandit_path();
-
-    // Load existing bandit or create new
-    let mut bandit = ContextualBandit::load(&path)?;
-
     // Convert feedback type to SkillFeedback enum
     let feedback = match feedback_type {
         "positive" => SkillFeedback::ExplicitHelpful,
@@ -216,21 +501,15 @@ fn update_contextual_bandit(
     // Use default context features (user could be in any context)
     let features = ContextFeatures::default();
 
-    // Update the bandit
-    bandit.update(skill_id, &features, &feedback);
-
-    // Save the updated bandit
-    bandit.save(&path)?;
+    // Read-modify-write against the database so concurrent runs don't clobber
+    // each other's updates
+    ctx.update_bandit(agent_id, |bandit| {
+        bandit.update(skill_id, &features, &feedback);
+    })?;
 
     Ok(())
 }
 
-/// Default path for the contextual bandit state file.
-fn default_contextual_bandit_path() -> PathBuf {
-    let base = dirs::data_dir().unwrap_or_else(|| PathBuf::from("."));
-    base.join("ms").join("contextual_bandit.json")
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -259,6 +538,8 @@ mod tests {
             "5",
             "--comment",
             "nice",
+            "--agent-id",
+            "cursor",
         ]);
         let TestCommand::Feedback(args) = parsed.cmd;
         match args.command {
@@ -268,6 +549,7 @@ mod tests {
                 assert!(!add.negative);
                 assert_eq!(add.rating, Some(5));
                 assert_eq!(add.comment.as_deref(), Some("nice"));
+                assert_eq!(add.agent_id.as_deref(), Some("cursor"));
             }
             _ => panic!("expected add"),
         }
@@ -287,6 +569,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_feedback_mine_session_or_hook() {
+        let parsed = TestCli::parse_from(["test", "feedback", "mine", "sess-1"]);
+        let TestCommand::Feedback(args) = parsed.cmd;
+        match args.command {
+            FeedbackCommand::Mine(mine) => {
+                assert_eq!(mine.session.as_deref(), Some("sess-1"));
+                assert!(!mine.hook);
+            }
+            _ => panic!("expected mine"),
+        }
+
+        let parsed = TestCli::parse_from(["test", "feedback", "mine", "--hook"]);
+        let TestCommand::Feedback(args) = parsed.cmd;
+        match args.command {
+            FeedbackCommand::Mine(mine) => {
+                assert!(mine.session.is_none());
+                assert!(mine.hook);
+            }
+            _ => panic!("expected mine"),
+        }
+
+        assert!(TestCli::try_parse_from(["test", "feedback", "mine"]).is_err());
+        assert!(TestCli::try_parse_from(["test", "feedback", "mine", "sess-1", "--hook"]).is_err());
+    }
+
     #[test]
     fn select_feedback_type_validation() {
         let base = FeedbackAddArgs {
@@ -295,6 +603,7 @@ mod tests {
             negative: false,
             rating: None,
             comment: None,
+            agent_id: None,
         };
         assert!(select_feedback_type(&base).is_err());
 
@@ -304,6 +613,7 @@ mod tests {
             negative: false,
             rating: None,
             comment: None,
+            agent_id: None,
         };
         assert_eq!(select_feedback_type(&positive).unwrap(), "positive");
 
@@ -313,6 +623,7 @@ mod tests {
             negative: true,
             rating: None,
             comment: None,
+            agent_id: None,
         };
         assert_eq!(select_feedback_type(&negative).unwrap(), "negative");
 
@@ -322,6 +633,7 @@ mod tests {
             negative: false,
             rating: Some(3),
             comment: None,
+            agent_id: None,
         };
         assert_eq!(select_feedback_type(&rating).unwrap(), "rating");
 
@@ -331,6 +643,7 @@ mod tests {
             negative: true,
             rating: None,
             comment: None,
+            agent_id: None,
         };
         assert!(select_feedback_type(&both).is_err());
     }
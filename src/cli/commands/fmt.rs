@@ -1,31 +1,89 @@
 //! ms fmt - Format skill files
+//!
+//! `ms fmt -` formats stdin to stdout (for editor format-on-save hooks), and
+//! `--check` prints a unified diff per file that would change and exits 1
+//! (for CI). Fenced code blocks are kept byte-for-byte.
+//!
+//! With `--fix-lint`, lint auto-fixes are applied before formatting. The fixed
+//! skill is compiled, re-parsed, and re-validated against the structural rules;
+//! a file is only rewritten when that round trip is clean.
+//!
+//! Skills in a read-only layer (community and bundle-installed by default)
+//! are skipped when formatting everything and refused when named, unless
+//! `--fork-to project` formats a project fork instead.
+
+use std::io::Read;
+use std::path::PathBuf;
 
 use clap::Args;
 use itertools::Itertools;
 
 use crate::app::AppContext;
+use crate::cli::commands::edit::{ForkTarget, read_only_layer, writable_skill_markdown};
 use crate::cli::commands::{discover_skill_markdowns, resolve_skill_markdown};
-use crate::core::spec_lens::{compile_markdown, parse_markdown};
-use crate::error::Result;
+use crate::cli::output::{OutputFormat, emit_json};
+use crate::core::spec_lens::{compile_markdown, format_markdown, parse_markdown};
+use crate::error::{MsError, Result};
+use crate::lint::ValidationEngine;
+use crate::lint::rules::{all_rules, structural_rules};
 
 #[derive(Args, Debug)]
 pub struct FmtArgs {
-    /// Skills to format (default: all)
+    /// Skills or SKILL.md paths to format (default: all); `-` formats stdin to stdout
     pub skills: Vec<String>,
 
-    /// Check formatting without modifying
+    /// Print a diff of files that would change and exit 1; nothing is written
     #[arg(long)]
     pub check: bool,
 
+    /// File name to use in diagnostics when formatting stdin
+    #[arg(long, value_name = "PATH")]
+    pub stdin_filepath: Option<PathBuf>,
+
     /// Show diff instead of modifying
     #[arg(long)]
     pub diff: bool,
+
+    /// Apply lint auto-fixes before formatting
+    #[arg(long)]
+    pub fix_lint: bool,
+
+    /// Format a copy in this layer when a named skill's layer is read-only
+    #[arg(long, value_enum, value_name = "LAYER")]
+    pub fork_to: Option<ForkTarget>,
+}
+
+/// Outcome of `--fix-lint` for one file.
+#[derive(Debug, Default, serde::Serialize)]
+struct LintFixReport {
+    path: PathBuf,
+    /// Rule ids whose fixes were applied (one entry per rule)
+    fixed: Vec<String>,
+    /// Fixes that could not be applied, as `(rule_id, error)`
+    failed: Vec<(String, String)>,
+    /// Why the fixed file was not written, if it was rejected
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rejected: Option<String>,
 }
 
 pub fn run(_ctx: &AppContext, _args: &FmtArgs) -> Result<()> {
     let ctx = _ctx;
     let args = _args;
 
+    if args.skills.iter().any(|skill| skill == "-") {
+        if args.skills.len() > 1 {
+            return Err(MsError::ValidationFailed(
+                "`-` (stdin) cannot be combined with other skills".to_string(),
+            ));
+        }
+        if args.fix_lint {
+            return Err(MsError::ValidationFailed(
+                "--fix-lint is not supported with stdin".to_string(),
+            ));
+        }
+        return run_stdin(args);
+    }
+
     let targets = if args.skills.is_empty() {
         discover_skill_markdowns(ctx)?
     } else {
@@ -36,27 +94,48 @@ pub fn run(_ctx: &AppContext, _args: &FmtArgs) -> Result<()> {
     };
 
     let mut dirty = Vec::new();
+    let mut reports = Vec::new();
 
     for path in targets {
+        let path = if args.skills.is_empty() {
+            if is_read_only(ctx, &path)? {
+                continue;
+            }
+            path
+        } else if args.diff || args.check {
+            path
+        } else {
+            writable_skill_markdown(ctx, path, args.fork_to)?
+        };
         let raw = std::fs::read_to_string(&path).map_err(|err| {
             crate::error::MsError::Config(format!("read {}: {err}", path.display()))
         })?;
-        let spec = parse_markdown(&raw)?;
-        let formatted = compile_markdown(&spec);
+        let formatted = if args.fix_lint {
+            let mut report = LintFixReport {
+                path: path.clone(),
+                ..LintFixReport::default()
+            };
+            let fixed = fix_lint_markdown(&raw, &mut report);
+            reports.push(report);
+            match fixed {
+                Ok(formatted) => formatted,
+                // Leave the file untouched; the report says why
+                Err(_) => continue,
+            }
+        } else {
+            format_markdown(&raw)
+                .map_err(|err| MsError::ValidationFailed(format!("{}: {err}", path.display())))?
+        };
 
         if raw != formatted {
             dirty.push(path.clone());
         }
 
-        if args.diff {
-            let diff = simple_diff(&raw, &formatted);
-            println!("--- {}", path.display());
-            println!("+++ formatted");
-            print!("{diff}");
-            continue;
-        }
-
-        if args.check {
+        if args.diff || args.check {
+            print!(
+                "{}",
+                unified_diff(&raw, &formatted, &path.display().to_string())
+            );
             continue;
         }
 
@@ -67,6 +146,10 @@ pub fn run(_ctx: &AppContext, _args: &FmtArgs) -> Result<()> {
         }
     }
 
+    if args.fix_lint {
+        report_lint_fixes(ctx, &reports, args.check)?;
+    }
+
     if args.check && !dirty.is_empty() {
         return Err(crate::error::MsError::ValidationFailed(format!(
             "{} files need formatting",
@@ -77,30 +160,265 @@ pub fn run(_ctx: &AppContext, _args: &FmtArgs) -> Result<()> {
     Ok(())
 }
 
-fn simple_diff(old: &str, new: &str) -> String {
-    let mut out = String::new();
-    for pair in old.lines().zip_longest(new.lines()) {
-        match pair {
-            itertools::EitherOrBoth::Both(left, right) if left == right => {}
-            itertools::EitherOrBoth::Both(left, right) => {
-                out.push('-');
-                out.push_str(left);
-                out.push('\n');
-                out.push('+');
-                out.push_str(right);
-                out.push('\n');
-            }
-            itertools::EitherOrBoth::Left(left) => {
-                out.push('-');
-                out.push_str(left);
-                out.push('\n');
-            }
-            itertools::EitherOrBoth::Right(right) => {
-                out.push('+');
-                out.push_str(right);
-                out.push('\n');
-            }
+/// Whether a discovered SKILL.md belongs to a read-only layer.
+fn is_read_only(ctx: &AppContext, path: &std::path::Path) -> Result<bool> {
+    let skill_id = std::fs::read_to_string(path)
+        .ok()
+        .and_then(|raw| parse_markdown(&raw).ok())
+        .map(|spec| spec.metadata.id)
+        .unwrap_or_default();
+    Ok(read_only_layer(ctx, path, &skill_id)?.is_some())
+}
+
+/// `ms fmt -`: format stdin to stdout. With `--check`, print a diff instead
+/// and fail when the input is not formatted.
+fn run_stdin(args: &FmtArgs) -> Result<()> {
+    let name = args
+        .stdin_filepath
+        .as_ref()
+        .map_or_else(|| "<stdin>".to_string(), |path| path.display().to_string());
+    let mut raw = String::new();
+    std::io::stdin()
+        .read_to_string(&mut raw)
+        .map_err(|err| MsError::Config(format!("read {name}: {err}")))?;
+    let formatted =
+        format_markdown(&raw).map_err(|err| MsError::ValidationFailed(format!("{name}: {err}")))?;
+
+    if !(args.check || args.diff) {
+        print!("{formatted}");
+        return Ok(());
+    }
+    print!("{}", unified_diff(&raw, &formatted, &name));
+    if args.check && raw != formatted {
+        return Err(MsError::ValidationFailed(format!(
+            "{name} needs formatting"
+        )));
+    }
+    Ok(())
+}
+
+/// Apply lint fixes to `raw` and return the compiled result.
+///
+/// Errors when the fixed skill no longer parses or still has structural
+/// errors; `report.rejected` carries the reason.
+fn fix_lint_markdown(raw: &str, report: &mut LintFixReport) -> Result<String> {
+    let outcome = apply_lint_fixes(raw, report);
+    if let Err(err) = &outcome {
+        report.rejected = Some(err.to_string());
+    }
+    outcome
+}
+
+fn apply_lint_fixes(raw: &str, report: &mut LintFixReport) -> Result<String> {
+    let mut spec = parse_markdown(raw)?;
+    let mut fixer = ValidationEngine::with_defaults();
+    for rule in all_rules() {
+        fixer.register(rule);
+    }
+    let fix_result = fixer.auto_fix(&mut spec)?;
+    report.fixed = fix_result.fixed.into_iter().unique().collect();
+    report.failed = fix_result.failed;
+
+    let compiled = compile_markdown(&spec);
+    let reparsed = parse_markdown(&compiled)
+        .map_err(|err| MsError::ValidationFailed(format!("fixed skill does not parse: {err}")))?;
+    let mut structural = ValidationEngine::with_defaults();
+    for rule in structural_rules() {
+        structural.register(rule);
+    }
+    let result = structural.validate(&reparsed);
+    let remaining: Vec<String> = result
+        .errors()
+        .map(|d| format!("{}: {}", d.rule_id, d.message))
+        .collect();
+    if !remaining.is_empty() {
+        return Err(MsError::ValidationFailed(format!(
+            "structural errors remain after fixing: {}",
+            remaining.join("; ")
+        )));
+    }
+    Ok(compiled)
+}
+
+fn report_lint_fixes(ctx: &AppContext, reports: &[LintFixReport], check: bool) -> Result<()> {
+    let fixed: usize = reports.iter().map(|r| r.fixed.len()).sum();
+    let rejected = reports.iter().filter(|r| r.rejected.is_some()).count();
+
+    if ctx.output_format != OutputFormat::Human {
+        return emit_json(&serde_json::json!({
+            "status": if rejected == 0 { "ok" } else { "partial" },
+            "check": check,
+            "fixed": fixed,
+            "rejected": rejected,
+            "files": reports,
+        }));
+    }
+
+    for report in reports {
+        if report.fixed.is_empty() && report.failed.is_empty() && report.rejected.is_none() {
+            continue;
+        }
+        println!("{}", report.path.display());
+        for rule_id in &report.fixed {
+            println!("  fixed   {rule_id}");
+        }
+        for (rule_id, err) in &report.failed {
+            println!("  failed  {rule_id}: {err}");
+        }
+        if let Some(reason) = &report.rejected {
+            println!("  skipped (not written): {reason}");
+        }
+    }
+    let verb = if check { "would apply" } else { "applied" };
+    println!(
+        "Lint fixes: {verb} {fixed} across {} file(s), {rejected} file(s) rejected",
+        reports.len()
+    );
+    Ok(())
+}
+
+/// Unified diff (three lines of context) from `old` to `new`; empty when
+/// they have the same lines.
+fn unified_diff(old: &str, new: &str, path: &str) -> String {
+    const CONTEXT: usize = 3;
+
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    // (tag, line, old index, new index) with indices taken before the op
+    let mut ops: Vec<(char, &str, usize, usize)> = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            ops.push((' ', old[i], i, j));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            ops.push(('-', old[i], i, j));
+            i += 1;
+        } else {
+            ops.push(('+', new[j], i, j));
+            j += 1;
+        }
+    }
+
+    let changes: Vec<usize> = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, op)| op.0 != ' ')
+        .map(|(idx, _)| idx)
+        .collect();
+    if changes.is_empty() {
+        return String::new();
+    }
+
+    let mut out = format!("--- {path}\n+++ {path}\n");
+    let mut k = 0;
+    while k < changes.len() {
+        let start = changes[k].saturating_sub(CONTEXT);
+        let mut end = changes[k] + 1;
+        // Merge changes whose context would overlap into one hunk
+        while k + 1 < changes.len() && changes[k + 1] <= end + 2 * CONTEXT {
+            k += 1;
+            end = changes[k] + 1;
+        }
+        k += 1;
+
+        let hunk = &ops[start..(end + CONTEXT).min(ops.len())];
+        let old_len = hunk.iter().filter(|op| op.0 != '+').count();
+        let new_len = hunk.iter().filter(|op| op.0 != '-').count();
+        out.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            hunk_range(hunk[0].2, old_len),
+            hunk_range(hunk[0].3, new_len)
+        ));
+        for (tag, line, _, _) in hunk {
+            out.push(*tag);
+            out.push_str(line);
+            out.push('\n');
         }
     }
     out
 }
+
+fn hunk_range(start: usize, len: usize) -> String {
+    match len {
+        0 => format!("{start},0"),
+        1 => format!("{}", start + 1),
+        _ => format!("{},{len}", start + 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BROKEN: &str = "---\nid: demo\nname: Demo\nversion: v2.1\n---\n\n# Demo\n\n\
+                          Demo skill.\n\n## Rules\n\n<!-- ms:anchor step -->\nFirst.\n\n\
+                          <!-- ms:anchor step -->\nSecond.\n";
+
+    #[test]
+    fn fix_lint_repairs_version_and_block_ids() {
+        let mut report = LintFixReport::default();
+        let fixed = fix_lint_markdown(BROKEN, &mut report).unwrap();
+        let spec = parse_markdown(&fixed).unwrap();
+        assert_eq!(spec.metadata.version, "2.1.0");
+        let ids: Vec<&str> = spec.sections[0]
+            .blocks
+            .iter()
+            .map(|b| b.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["step", "step-2"]);
+        assert!(report.fixed.contains(&"valid-version".to_string()));
+        assert!(report.fixed.contains(&"unique-block-ids".to_string()));
+        assert!(report.rejected.is_none());
+    }
+
+    #[test]
+    fn fix_lint_is_idempotent() {
+        let mut report = LintFixReport::default();
+        let once = fix_lint_markdown(BROKEN, &mut report).unwrap();
+        let mut again = LintFixReport::default();
+        let twice = fix_lint_markdown(&once, &mut again).unwrap();
+        assert_eq!(once, twice);
+        assert!(again.fixed.is_empty());
+    }
+
+    #[test]
+    fn unified_diff_groups_changes_into_hunks() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\n";
+        let new = "a\nB\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\nm\n";
+        let diff = unified_diff(old, new, "SKILL.md");
+        assert_eq!(
+            diff,
+            "--- SKILL.md\n+++ SKILL.md\n\
+             @@ -1,5 +1,5 @@\n a\n-b\n+B\n c\n d\n e\n\
+             @@ -10,3 +10,4 @@\n j\n k\n l\n+m\n"
+        );
+        assert!(unified_diff(old, old, "SKILL.md").is_empty());
+    }
+
+    #[test]
+    fn fix_lint_rejects_unfixable_structure() {
+        let raw = "---\nid: demo\nname: Demo\nversion: 1.0.0\n---\n\n# Demo\n\nDemo.\n\n\
+                   ## Rules\n\nOne.\n\n## Rules\n\nTwo.\n";
+        let mut report = LintFixReport::default();
+        assert!(fix_lint_markdown(raw, &mut report).is_err());
+        assert!(
+            report
+                .rejected
+                .unwrap()
+                .contains("structural errors remain")
+        );
+    }
+}
@@ -1,24 +1,68 @@
-//! ms graph - skill graph analysis via bv.
+//! ms graph - skill dependency graphs.
+//!
+//! Without a subcommand, renders the extends/includes/requires graph of
+//! indexed skills directly. Subcommands delegate analysis to bv.
 
 use std::path::PathBuf;
 
-use clap::{Args, Subcommand};
+use clap::{Args, Subcommand, ValueEnum};
 use tracing::debug;
 
 use crate::app::AppContext;
 use crate::cli::output::OutputFormat;
 use crate::error::{MsError, Result};
 use crate::graph::bv::{BvClient, run_bv_on_issues, run_bv_on_issues_raw};
+use crate::graph::deps::{EdgeKind, SkillGraph, SkillNode};
 use crate::graph::skills::skills_to_issues;
+use crate::output::{Theme, TreeGuides};
+
+/// Graphs with more skills than this render as a summary instead of a tree.
+const TREE_MAX_NODES: usize = 50;
 
 #[derive(Args, Debug)]
 pub struct GraphArgs {
     #[command(subcommand)]
-    pub command: GraphCommand,
+    pub command: Option<GraphCommand>,
 
     /// Path to bv binary (default: bv)
     #[arg(long)]
     pub bv_path: Option<PathBuf>,
+
+    /// Output format for the dependency graph (default: tree, or json in robot mode)
+    #[arg(long, value_enum)]
+    pub format: Option<GraphFormat>,
+
+    /// Only show this skill and what it transitively depends on
+    #[arg(long)]
+    pub root: Option<String>,
+
+    /// Maximum number of dependency hops from --root (or from each tree root)
+    #[arg(long)]
+    pub depth: Option<usize>,
+
+    /// Exit nonzero when the graph has cycles or missing references
+    #[arg(long)]
+    pub strict: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum GraphFormat {
+    Tree,
+    Dot,
+    Mermaid,
+    Json,
+}
+
+impl GraphFormat {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Tree => "tree",
+            Self::Dot => "dot",
+            Self::Mermaid => "mermaid",
+            Self::Json => "json",
+        }
+    }
 }
 
 #[derive(Subcommand, Debug)]
@@ -77,6 +121,10 @@ pub struct GraphHealthArgs {}
 pub fn run(ctx: &AppContext, args: &GraphArgs) -> Result<()> {
     debug!(target: "graph", mode = ?ctx.output_format, "output mode selected");
 
+    let Some(command) = &args.command else {
+        return run_dependencies(ctx, args);
+    };
+
     let client = if let Some(ref path) = args.bv_path {
         BvClient::with_binary(path)
     } else {
@@ -98,7 +146,7 @@ pub fn run(ctx: &AppContext, args: &GraphArgs) -> Result<()> {
 
     debug!(target: "graph", nodes = skills.len(), edges = issues.len(), "graph loaded");
 
-    let result = match &args.command {
+    let result = match command {
         GraphCommand::Insights(_) => run_insights(ctx, &client, &issues, &name_map),
         GraphCommand::Plan(_) => run_plan(ctx, &client, &issues),
         GraphCommand::Triage(_) => run_triage(ctx, &client, &issues),
@@ -133,6 +181,126 @@ fn load_all_skills(ctx: &AppContext) -> Result<Vec<crate::storage::sqlite::Skill
     Ok(out)
 }
 
+fn run_dependencies(ctx: &AppContext, args: &GraphArgs) -> Result<()> {
+    let skills = load_all_skills(ctx)?;
+    let nodes: Vec<SkillNode> = skills
+        .iter()
+        .map(|skill| SkillNode::from_record(skill, ctx.git.read_skill(&skill.id).ok().as_ref()))
+        .collect();
+    let mut graph = SkillGraph::build(&nodes);
+    if let Some(root) = &args.root {
+        if !graph.contains(root) {
+            return Err(MsError::SkillNotFound(root.clone()));
+        }
+        graph = graph.restrict(root, args.depth);
+    }
+
+    let cycles = graph.cycles();
+    let missing = graph.missing().count();
+    debug!(
+        target: "graph",
+        nodes = graph.node_count(),
+        edges = graph.edges().len(),
+        cycles = cycles.len(),
+        missing,
+        "dependency graph built"
+    );
+
+    let format = args
+        .format
+        .unwrap_or(if ctx.output_format.is_machine_readable() {
+            GraphFormat::Json
+        } else {
+            GraphFormat::Tree
+        });
+    let rendered = match format {
+        GraphFormat::Dot => Some(graph.to_dot()),
+        GraphFormat::Mermaid => Some(graph.to_mermaid()),
+        GraphFormat::Tree | GraphFormat::Json => None,
+    };
+
+    if ctx.output_format.is_machine_readable() {
+        let mut output = graph.to_json();
+        output["status"] = serde_json::json!(if cycles.is_empty() && missing == 0 {
+            "ok"
+        } else {
+            "issues"
+        });
+        if let Some(rendered) = &rendered {
+            output["format"] = serde_json::json!(format.as_str());
+            output["graph"] = serde_json::json!(rendered);
+        }
+        crate::cli::output::emit_json(&output)?;
+    } else {
+        match (format, rendered) {
+            (_, Some(rendered)) => print!("{rendered}"),
+            (GraphFormat::Json, _) => {
+                println!("{}", serde_json::to_string_pretty(&graph.to_json())?);
+            }
+            _ if args.root.is_none() && graph.node_count() > TREE_MAX_NODES => {
+                print_graph_summary(&graph, cycles.len(), missing);
+            }
+            _ => {
+                let chars = if should_use_rich_for_graph() {
+                    Theme::from_config(&ctx.config)
+                        .map_or(TreeGuides::Unicode, |theme| theme.tree_guides)
+                        .chars()
+                } else {
+                    TreeGuides::Ascii.chars()
+                };
+                let roots: Vec<String> = args.root.iter().cloned().collect();
+                print!("{}", graph.render_tree(&roots, chars, args.depth));
+                print_graph_problems(&graph, &cycles);
+            }
+        }
+    }
+
+    if args.strict && (!cycles.is_empty() || missing > 0) {
+        return Err(MsError::ValidationFailed(format!(
+            "skill graph has {} cycle(s) and {missing} missing reference(s)",
+            cycles.len()
+        )));
+    }
+    Ok(())
+}
+
+fn print_graph_summary(graph: &SkillGraph, cycles: usize, missing: usize) {
+    let count = |kind| graph.edges().iter().filter(|e| e.kind == kind).count();
+    println!(
+        "Skill graph: {} skills, {} edges",
+        graph.node_count(),
+        graph.edges().len()
+    );
+    println!("  extends: {}", count(EdgeKind::Extends));
+    println!("  includes: {}", count(EdgeKind::Includes));
+    println!("  requires: {}", count(EdgeKind::Requires));
+    println!("  cycles: {cycles}");
+    println!("  missing references: {missing}");
+    println!();
+    println!("Too large to draw as a tree; use --root <skill> or --format dot|mermaid.");
+}
+
+fn print_graph_problems(graph: &SkillGraph, cycles: &[Vec<String>]) {
+    if !cycles.is_empty() {
+        println!();
+        println!("Cycles:");
+        for cycle in cycles {
+            println!("  {}", cycle.join(" <-> "));
+        }
+    }
+    let missing: Vec<String> = graph
+        .missing()
+        .map(|edge| format!("  {} {} {}", edge.from, edge.kind.as_str(), edge.to))
+        .collect();
+    if !missing.is_empty() {
+        println!();
+        println!("Missing references:");
+        for line in missing {
+            println!("{line}");
+        }
+    }
+}
+
 fn run_insights(
     ctx: &AppContext,
     client: &BvClient,
@@ -423,7 +591,6 @@ fn run_health(ctx: &AppContext, client: &BvClient, issues: &[crate::beads::Issue
 }
 
 /// Check whether the terminal supports rich output for graph commands.
-#[allow(dead_code)]
 fn should_use_rich_for_graph() -> bool {
     use std::io::IsTerminal;
 
@@ -8,7 +8,7 @@ use colored::Colorize;
 
 use crate::app::AppContext;
 use crate::cli::output::OutputFormat;
-use crate::error::{MsError, Result};
+use crate::error::Result;
 
 #[derive(Args, Debug)]
 pub struct HideArgs {
@@ -80,20 +80,8 @@ pub fn run(ctx: &AppContext, args: &HideArgs) -> Result<()> {
     }
 }
 
-fn resolve_skill_id(ctx: &AppContext, input: &str) -> Result<String> {
-    if let Some(skill) = ctx.db.get_skill(input)? {
-        return Ok(skill.id);
-    }
-    if let Ok(Some(alias)) = ctx.db.resolve_alias(input) {
-        if let Some(skill) = ctx.db.get_skill(&alias.canonical_id)? {
-            return Ok(skill.id);
-        }
-    }
-    Err(MsError::SkillNotFound(format!("skill not found: {input}")))
-}
-
 fn add_hidden(ctx: &AppContext, skill: &str) -> Result<()> {
-    let skill_id = resolve_skill_id(ctx, skill)?;
+    let skill_id = ctx.resolve_skill_id(skill)?;
 
     // Check if already hidden
     if ctx.db.has_user_preference(&skill_id, "hidden")? {
@@ -0,0 +1,44 @@
+//! ms __hook - Format `ms suggest --robot` output for agent hooks
+//!
+//! The scripts written by `ms init --install-hooks` pipe `ms suggest --robot`
+//! into the hidden `ms __hook <agent>`, which prints the context block the
+//! agent expects. Like the script around it, it fails open: unreadable or
+//! empty input prints nothing.
+
+use std::io::{self, Read};
+
+use clap::{Args, ValueEnum};
+
+use crate::agent_detection::{claude_context, claude_hook_output};
+use crate::error::Result;
+
+/// Agents `ms init --install-hooks` can install a suggestion hook for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum HookAgent {
+    ClaudeCode,
+}
+
+#[derive(Args, Debug)]
+pub struct HookArgs {
+    /// Agent to format the context block for
+    #[arg(value_enum)]
+    pub agent: HookAgent,
+}
+
+pub fn run(args: &HookArgs) -> Result<()> {
+    let mut input = String::new();
+    if io::stdin().read_to_string(&mut input).is_err() {
+        return Ok(());
+    }
+    let Ok(suggestions) = serde_json::from_str(&input) else {
+        return Ok(());
+    };
+    match args.agent {
+        HookAgent::ClaudeCode => {
+            if let Some(context) = claude_context(&suggestions) {
+                println!("{}", claude_hook_output(&context));
+            }
+        }
+    }
+    Ok(())
+}
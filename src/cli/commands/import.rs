@@ -16,12 +16,12 @@ use crate::cli::output::OutputFormat;
 use crate::cli::output::{HumanLayout, emit_human, emit_json};
 use crate::core::spec_lens::compile_markdown;
 use crate::error::{MsError, Result};
+use crate::import::repo_rules::import_repo_rules;
 use crate::import::{
     ContentBlock, ContentBlockType, ContentParser, GeneratedSkill, GeneratorConfig, ImportHints,
     ImportStats, SkillGenerator, Suggestion, UnknownHandling, Warning,
 };
 use crate::lint::ValidationEngine;
-use crate::lint::rules::all_rules;
 
 // =============================================================================
 // ARGUMENT TYPES
@@ -65,6 +65,13 @@ pub struct ImportArgs {
     #[arg(long)]
     pub batch: bool,
 
+    /// Treat PATH as a repository and import its Cursor rules
+    /// (`.cursor/rules/*.mdc`, `.cursorrules`) and Copilot instructions
+    /// (`.github/copilot-instructions.md`, `.github/instructions/*.instructions.md`),
+    /// one skill per file. Output defaults to the current directory.
+    #[arg(long, conflicts_with = "batch")]
+    pub from_repo: bool,
+
     /// Output path (file or directory)
     #[arg(long, short)]
     pub output: Option<PathBuf>,
@@ -127,7 +134,9 @@ pub struct ImportArgs {
 // =============================================================================
 
 pub fn run(ctx: &AppContext, args: &ImportArgs) -> Result<()> {
-    if args.batch {
+    if args.from_repo {
+        run_from_repo(ctx, args)
+    } else if args.batch {
         run_batch(ctx, args)
     } else {
         run_single(ctx, args)
@@ -196,7 +205,7 @@ fn run_single(ctx: &AppContext, args: &ImportArgs) -> Result<()> {
     // Run linting if requested
     let lint_result = if args.lint {
         let mut engine = ValidationEngine::with_defaults();
-        for rule in all_rules() {
+        for rule in ctx.lint_rules()? {
             engine.register(rule);
         }
         let result = engine.validate(&generated.skill);
@@ -460,6 +469,113 @@ fn import_single_for_batch(
     })
 }
 
+// =============================================================================
+// REPOSITORY RULE FILE IMPORT
+// =============================================================================
+
+fn run_from_repo(ctx: &AppContext, args: &ImportArgs) -> Result<()> {
+    let gen_config = GeneratorConfig {
+        min_confidence: args.min_confidence,
+        unknown_handling: UnknownHandling::AddToContext,
+        infer_metadata: true,
+        deduplicate: true,
+    };
+    let mut imports = import_repo_rules(&args.path, &gen_config, &build_hints(args))?;
+
+    if imports.is_empty() {
+        return Err(MsError::Import(format!(
+            "No Cursor rules or Copilot instructions found under {}",
+            args.path.display()
+        )));
+    }
+
+    let output_dir = args.output.clone().unwrap_or_else(|| PathBuf::from("."));
+    if !args.dry_run && !output_dir.exists() {
+        std::fs::create_dir_all(&output_dir).map_err(|e| {
+            MsError::Config(format!(
+                "Failed to create output directory {}: {e}",
+                output_dir.display()
+            ))
+        })?;
+    }
+
+    let engine = if args.lint {
+        let mut engine = ValidationEngine::with_defaults();
+        for rule in ctx.lint_rules()? {
+            engine.register(rule);
+        }
+        Some(engine)
+    } else {
+        None
+    };
+
+    let mut files = Vec::with_capacity(imports.len());
+    for import in &mut imports {
+        let generated = &mut import.generated;
+        let lint_result = match &engine {
+            Some(engine) => {
+                let result = engine.validate(&generated.skill);
+                if args.fix && !result.passed {
+                    let _fix_result = engine.auto_fix(&mut generated.skill)?;
+                }
+                Some(result)
+            }
+            None => None,
+        };
+
+        let output_path = output_dir.join(format!("{}.skill.md", generated.skill.metadata.id));
+        if !args.dry_run {
+            let output_content = format_skill(generated, args.skill_format);
+            std::fs::write(&output_path, &output_content).map_err(|e| {
+                MsError::Config(format!("Failed to write {}: {e}", output_path.display()))
+            })?;
+        }
+
+        if ctx.output_format == OutputFormat::Human {
+            println!(
+                "  {} {} → {} ({} rules, {} examples)",
+                style("✓").green(),
+                import.source.relative,
+                style(output_path.display()).dim(),
+                generated.stats.rules_count,
+                generated.stats.examples_count,
+            );
+        }
+
+        files.push(RepoFileReport {
+            source: import.source.relative.clone(),
+            format: import.source.format.as_str().to_string(),
+            id: generated.skill.metadata.id.clone(),
+            output: (!args.dry_run).then(|| output_path.display().to_string()),
+            file_patterns: generated.skill.metadata.context.file_patterns.clone(),
+            always_apply: import.frontmatter.always_apply,
+            stats: generated.stats.clone(),
+            lint_passed: lint_result.as_ref().map(|r| r.passed),
+        });
+    }
+
+    if ctx.output_format != OutputFormat::Human {
+        emit_json(&RepoImportReport {
+            root: args.path.display().to_string(),
+            total: files.len(),
+            dry_run: args.dry_run,
+            files,
+        })?;
+    } else {
+        let mut layout = HumanLayout::new();
+        layout.section("Repository Import Summary");
+        layout.kv("Rule files", &files.len().to_string());
+        if args.dry_run {
+            layout.kv("Written", "0 (dry run)");
+        } else {
+            layout.kv("Output directory", &output_dir.display().to_string());
+        }
+        emit_human(layout);
+    }
+
+    Ok(())
+}
+
 // =============================================================================
 // HELPERS
 // =============================================================================
@@ -684,6 +800,30 @@ struct BatchFileReport {
     error: Option<String>,
 }
 
+#[derive(Serialize)]
+struct RepoImportReport {
+    root: String,
+    total: usize,
+    dry_run: bool,
+    files: Vec<RepoFileReport>,
+}
+
+#[derive(Serialize)]
+struct RepoFileReport {
+    source: String,
+    format: String,
+    id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    file_patterns: Vec<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    always_apply: bool,
+    stats: ImportStats,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lint_passed: Option<bool>,
+}
+
 // =============================================================================
 // TESTS
 // =============================================================================
@@ -730,6 +870,7 @@ mod tests {
         let args = ImportArgs {
             path: PathBuf::from("test.md"),
             batch: false,
+            from_repo: false,
             output: None,
             format: InputFormat::Auto,
             skill_format: SkillFormat::Markdown,
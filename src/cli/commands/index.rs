@@ -1,21 +1,39 @@
 //! ms index - Index skills from configured paths
 
-use std::path::PathBuf;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::sync::mpsc::RecvTimeoutError;
 use std::time::{Duration, Instant};
 
 use clap::Args;
 use colored::Colorize;
-use indicatif::{ProgressBar, ProgressStyle};
+use notify_debouncer_mini::new_debouncer;
+use notify_debouncer_mini::notify::RecursiveMode;
+use serde::Serialize;
 use walkdir::WalkDir;
 
 use crate::app::AppContext;
+use crate::cli::commands::export_site::embedding_text;
+use crate::cli::commands::prune::format_size;
 use crate::cli::output::OutputFormat;
-use crate::core::{GitSkillRepository, ResolutionCache, SkillLayer, spec_lens::parse_markdown};
+use crate::core::resources::{self, ResourceFile};
+use crate::core::{
+    ConflictDetail, ConflictStrategy, GitSkillRepository, LayeredRegistry, MergeStrategy,
+    ResolutionCache, SkillCandidate, SkillLayer, SkillSpec, derived_tags::TagDeriver,
+    spec_lens::parse_markdown,
+};
 use crate::error::{MsError, Result};
-use crate::storage::tx::GlobalLock;
-use crate::storage::{SkillRecord, TxManager};
+use crate::output::PhaseProgress;
+use crate::search::{Embedder, IndexStats, SCHEMA_VERSION, build_embedder, health};
+use crate::signals::interrupt_flag;
+use crate::storage::sqlite::{EmbeddingRecord, SkillResourceRecord};
+use crate::storage::tx::{GlobalLock, lock_timeout};
+use crate::storage::{Provenance, SkillRecord, SourceFileRecord, TxManager};
 use crate::sync::ru::RuClient;
+use crate::utils::ignore_rules::IgnoreRules;
+use crate::utils::path::expand_path;
 
 #[derive(Args, Debug)]
 pub struct IndexArgs {
@@ -38,11 +56,92 @@ pub struct IndexArgs {
     /// Index skills from ru-managed repositories
     #[arg(long)]
     pub from_ru: bool,
+
+    /// Compact the search index (merge segments, drop deleted documents)
+    /// instead of indexing
+    #[arg(long, conflicts_with = "watch")]
+    pub compact: bool,
+
+    /// Skip deriving language and framework tags from skill content
+    #[arg(long)]
+    pub no_enrich: bool,
+
+    /// Index every SKILL.md, ignoring `.gitignore`, `.ignore`, `.msignore`,
+    /// `[skill_paths.ignore]`, and the built-in skip list
+    #[arg(long)]
+    pub no_ignore: bool,
+
+    /// Recompute stored quality scores with the current `[quality]` weights
+    /// instead of indexing
+    #[arg(long, conflicts_with_all = ["watch", "compact"])]
+    pub rescore: bool,
+
+    /// Report how scores would change without storing them (with --rescore)
+    #[arg(long, requires = "rescore")]
+    pub dry_run: bool,
+
+    /// Only rescore skills of this layer (base, org, project, user)
+    #[arg(long, value_name = "LAYER", requires = "rescore")]
+    pub layer: Option<String>,
+
+    /// Only rescore skills with this tag (repeatable; any of them matches)
+    #[arg(long = "tag", value_name = "TAG", requires = "rescore")]
+    pub tags: Vec<String>,
+}
+
+impl IndexArgs {
+    /// Whether this run derives tags (`--no-enrich`, `[enrichment].enabled`).
+    const fn enrich(&self, ctx: &AppContext) -> bool {
+        !self.no_enrich && ctx.config.enrichment.enabled
+    }
+}
+
+/// What `ms index` did with one `SKILL.md`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IndexOutcome {
+    Added,
+    Updated,
+    /// Unchanged since the last run (or `--force` not given and content equal)
+    Skipped,
+}
+
+/// Per-run counts reported by `ms index`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct IndexCounts {
+    added: usize,
+    updated: usize,
+    skipped: usize,
+    removed: usize,
+}
+
+impl IndexCounts {
+    fn record(&mut self, outcome: IndexOutcome) {
+        match outcome {
+            IndexOutcome::Added => self.added += 1,
+            IndexOutcome::Updated => self.updated += 1,
+            IndexOutcome::Skipped => self.skipped += 1,
+        }
+    }
+
+    const fn indexed(&self) -> usize {
+        self.added + self.updated + self.skipped
+    }
 }
 
 struct SkillRoot {
     path: PathBuf,
     layer: SkillLayer,
+    /// `[skill_paths.ignore]` globs for this root; `None` with `--no-ignore`
+    ignore: Option<Vec<String>>,
+}
+
+/// `SKILL.md` files found under the skill roots
+#[derive(Default)]
+struct Discovery {
+    skills: Vec<DiscoveredSkill>,
+    /// Files and directories skipped by ignore rules (a skipped directory
+    /// counts once)
+    ignored: usize,
 }
 
 struct DiscoveredSkill {
@@ -57,6 +156,18 @@ struct DiscoveredSkill {
     companion_count: usize,
 }
 
+impl DiscoveredSkill {
+    /// Package directory name, shown in progress output.
+    fn label(&self) -> String {
+        self.path
+            .parent()
+            .and_then(Path::file_name)
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
 /// Directory-name segments that are skipped when discovering skill packages.
 /// Conservative list — only entries that are unambiguously build artifacts,
 /// VCS internals, or our own data dirs. We do not skip `.github` or
@@ -88,28 +199,54 @@ fn is_skipped_skill_discovery_dir(name: &str) -> bool {
 pub fn run(ctx: &AppContext, args: &IndexArgs) -> Result<()> {
     // Fail fast with a clear, actionable diagnostic if the search index is
     // read-only, BEFORE doing any work. `AppContext::from_cli` transparently
-    // falls back to a read-only index when the writable open fails (e.g. a live
-    // `ms mcp serve` holds the Tantivy writer lock, or the index is on a
-    // read-only mount). Without this guard, indexing would write skills into the
-    // SQLite DB and Git archive and only then abort mid-run at the Tantivy
-    // commit with the opaque "Index opened in read-only mode" error — the
-    // failure reported in issue #133. Checking here (before acquiring the global
-    // lock) also avoids creating an `ms.lock` on this doomed path.
+    // falls back to a read-only index when the writable open fails (e.g. the
+    // index is on a read-only mount). Without this guard, indexing would write
+    // skills into the SQLite DB and Git archive and only then abort mid-run at
+    // the Tantivy commit with the opaque "Index opened in read-only mode"
+    // error — the failure reported in issue #133. Checking here (before
+    // acquiring the global lock) also avoids creating an `ms.lock` on this
+    // doomed path.
     ctx.require_writable_search()?;
 
-    // Acquire global lock for indexing (exclusive write operation)
-    let lock_result = GlobalLock::acquire_timeout(&ctx.ms_root, Duration::from_secs(30))?;
-    let _lock = lock_result.ok_or_else(|| {
-        MsError::TransactionFailed(
-            "Could not acquire lock for indexing. Another process may be indexing.".to_string(),
-        )
-    })?;
+    // Acquire global lock for indexing (exclusive write operation), then the
+    // search index writer, so that a busy index fails before any writes
+    let lock = GlobalLock::acquire_or_busy(&ctx.ms_root, lock_timeout())?;
+    ctx.search.reserve_writer()?;
+    let rebuilt = rebuild_outdated_index(ctx)?;
 
-    if args.watch {
-        return Err(MsError::Config(
-            "Watch mode not yet implemented. Use a file watcher with 'ms index' instead."
-                .to_string(),
-        ));
+    if args.compact {
+        let report = compact_index(ctx)?;
+        if ctx.output_format != OutputFormat::Human {
+            println!(
+                "{}",
+                serde_json::json!({
+                    "status": "ok",
+                    "compaction": report,
+                })
+            );
+        } else {
+            print_compaction(&report);
+        }
+        return Ok(());
+    }
+
+    if args.rescore {
+        let progress = ctx.progress();
+        let report = rescore(ctx, args, &progress);
+        progress.settle(&report);
+        let report = report?;
+        if ctx.output_format != OutputFormat::Human {
+            println!(
+                "{}",
+                serde_json::json!({
+                    "status": "ok",
+                    "rescore": report,
+                })
+            );
+        } else {
+            print_rescore(&report);
+        }
+        return Ok(());
     }
 
     // Collect paths to index
@@ -134,14 +271,397 @@ pub fn run(ctx: &AppContext, args: &IndexArgs) -> Result<()> {
         return Ok(());
     }
 
-    if ctx.output_format != OutputFormat::Human {
-        index_robot(ctx, &roots, args)
+    let progress = ctx.progress();
+    let result = if ctx.output_format != OutputFormat::Human {
+        index_robot(ctx, &roots, args, rebuilt, &progress)
+    } else {
+        index_human(ctx, &roots, args, &progress)
+    };
+    progress.settle(&result);
+    result?;
+    // Scores of unchanged skills are kept, so an existing fingerprint stays
+    // until `--rescore` recomputes them all
+    crate::cli::commands::quality::record_weights(ctx, false)?;
+
+    if args.watch {
+        // Hold the lock only while a batch is being indexed
+        drop(lock);
+        return watch(ctx, &roots, args.enrich(ctx));
+    }
+    Ok(())
+}
+
+/// Refill a search index that was recreated for a new schema from the skill
+/// records in the database: unchanged skill files are not re-indexed, so it
+/// would otherwise stay empty. Returns how many skills were indexed, or
+/// `None` when no rebuild was due.
+fn rebuild_outdated_index(ctx: &AppContext) -> Result<Option<usize>> {
+    const PAGE: usize = 500;
+
+    if !ctx.search.needs_rebuild() {
+        return Ok(None);
+    }
+    let mut offset = 0;
+    loop {
+        let page = ctx.db.list_skills(PAGE, offset)?;
+        if page.is_empty() {
+            break;
+        }
+        for record in &page {
+            ctx.search
+                .index_skill_tagged(record, &derived_tag_names(ctx, &record.id)?)?;
+        }
+        offset += page.len();
+    }
+    ctx.search.commit()?;
+    ctx.search.mark_rebuilt()?;
+
+    if ctx.output_format == OutputFormat::Human {
+        println!(
+            "{} Search index schema changed (now v{}); rebuilt it from {} skill(s)",
+            "!".yellow(),
+            SCHEMA_VERSION,
+            offset
+        );
+    }
+    Ok(Some(offset))
+}
+
+/// Search index size and document counts at one point in time
+#[derive(Debug, Clone, Copy, Serialize)]
+pub(crate) struct IndexSnapshot {
+    pub size_bytes: u64,
+    #[serde(flatten)]
+    pub stats: IndexStats,
+}
+
+/// Search index state before and after a compaction
+#[derive(Debug, Clone, Copy, Serialize)]
+pub(crate) struct CompactionReport {
+    pub before: IndexSnapshot,
+    pub after: IndexSnapshot,
+}
+
+/// Compact the search index, measuring its directory before and after.
+pub(crate) fn compact_index(ctx: &AppContext) -> Result<CompactionReport> {
+    let index_path = ctx.ms_root.join("index");
+    let before_bytes = health::index_size(&index_path);
+    let compaction = ctx.search.compact()?;
+    Ok(CompactionReport {
+        before: IndexSnapshot {
+            size_bytes: before_bytes,
+            stats: compaction.before,
+        },
+        after: IndexSnapshot {
+            size_bytes: health::index_size(&index_path),
+            stats: compaction.after,
+        },
+    })
+}
+
+/// Skills rescored per search index commit
+const RESCORE_BATCH: usize = 200;
+
+/// Movers listed in the rescore summary
+const RESCORE_MOVERS: usize = 10;
+
+/// Mean and median of a set of quality scores
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub(crate) struct ScoreStats {
+    pub mean: f64,
+    pub median: f64,
+}
+
+impl ScoreStats {
+    fn of(scores: &[f64]) -> Self {
+        if scores.is_empty() {
+            return Self::default();
+        }
+        let mut sorted = scores.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let mid = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            f64::midpoint(sorted[mid - 1], sorted[mid])
+        } else {
+            sorted[mid]
+        };
+        Self {
+            mean: sorted.iter().sum::<f64>() / sorted.len() as f64,
+            median,
+        }
+    }
+}
+
+/// One skill's stored score before and after a rescore
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct ScoreMove {
+    pub skill_id: String,
+    pub before: f64,
+    pub after: f64,
+    pub delta: f64,
+}
+
+/// Outcome of `ms index --rescore`
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct RescoreReport {
+    pub dry_run: bool,
+    /// Fingerprint of the `[quality]` weights used
+    pub weights_hash: String,
+    pub rescored: usize,
+    /// Skills whose score moved by more than rounding noise
+    pub changed: usize,
+    pub before: ScoreStats,
+    pub after: ScoreStats,
+    /// Largest moves first
+    pub movers: Vec<ScoreMove>,
+    /// Skills that could not be rescored, with the reason
+    pub failed: Vec<String>,
+}
+
+/// Recompute the quality score of every indexed skill matching `--layer`
+/// and `--tag` with the configured weights, storing it in the database and
+/// the search index one batch at a time. Only an unfiltered run records the
+/// weights as the ones all stored scores use.
+fn rescore(ctx: &AppContext, args: &IndexArgs, progress: &PhaseProgress) -> Result<RescoreReport> {
+    const PAGE: usize = 500;
+
+    if let Some(layer) = &args.layer
+        && parse_layer(layer).is_none()
+    {
+        return Err(MsError::Config(format!(
+            "unknown layer: {layer} (expected base, org, project or user)"
+        )));
+    }
+
+    progress.phase("collecting", None);
+    let mut selected = Vec::new();
+    let mut offset = 0;
+    loop {
+        let page = ctx.db.list_skills(PAGE, offset)?;
+        if page.is_empty() {
+            break;
+        }
+        offset += page.len();
+        for record in page {
+            if args
+                .layer
+                .as_ref()
+                .is_some_and(|layer| record.source_layer != *layer)
+            {
+                continue;
+            }
+            let derived = derived_tag_names(ctx, &record.id)?;
+            if !args.tags.is_empty()
+                && !args
+                    .tags
+                    .iter()
+                    .any(|tag| derived.contains(tag) || metadata_tags(&record).contains(tag))
+            {
+                continue;
+            }
+            selected.push((record, derived));
+        }
+    }
+
+    let total = selected.len() as u64;
+    let mut before = Vec::with_capacity(selected.len());
+    let mut after = Vec::with_capacity(selected.len());
+    let mut movers = Vec::new();
+    let mut failed = Vec::new();
+    progress.phase("rescoring", Some(total));
+    let mut done = 0;
+    for batch in selected.chunks_mut(RESCORE_BATCH) {
+        for (record, derived) in batch.iter_mut() {
+            done += 1;
+            progress.update(done, total, Some(&record.id));
+            let score = match crate::cli::commands::quality::recompute_quality(ctx, record) {
+                Ok(score) => f64::from(score),
+                Err(err) => {
+                    failed.push(format!("{}: {err}", record.id));
+                    continue;
+                }
+            };
+            before.push(record.quality_score);
+            after.push(score);
+            movers.push(ScoreMove {
+                skill_id: record.id.clone(),
+                before: record.quality_score,
+                after: score,
+                delta: score - record.quality_score,
+            });
+            if !args.dry_run {
+                ctx.db.update_skill_quality(&record.id, score)?;
+                record.quality_score = score;
+                ctx.search.index_skill_tagged(record, derived)?;
+            }
+        }
+        if !args.dry_run {
+            ctx.search.commit()?;
+        }
+    }
+    progress.finish();
+
+    let unfiltered = args.layer.is_none() && args.tags.is_empty();
+    if !args.dry_run && unfiltered && failed.is_empty() {
+        crate::cli::commands::quality::record_weights(ctx, true)?;
+    }
+
+    movers.retain(|m| m.delta.abs() > f64::from(f32::EPSILON));
+    let changed = movers.len();
+    movers.sort_by(|a, b| b.delta.abs().total_cmp(&a.delta.abs()));
+    movers.truncate(RESCORE_MOVERS);
+
+    Ok(RescoreReport {
+        dry_run: args.dry_run,
+        weights_hash: ctx.config.quality.weights().fingerprint(),
+        rescored: after.len(),
+        changed,
+        before: ScoreStats::of(&before),
+        after: ScoreStats::of(&after),
+        movers,
+        failed,
+    })
+}
+
+/// Tags listed in a record's frontmatter metadata
+fn metadata_tags(record: &SkillRecord) -> Vec<String> {
+    serde_json::from_str::<serde_json::Value>(&record.metadata_json)
+        .ok()
+        .and_then(|meta| {
+            meta.get("tags").and_then(|t| t.as_array()).map(|tags| {
+                tags.iter()
+                    .filter_map(|tag| tag.as_str().map(String::from))
+                    .collect()
+            })
+        })
+        .unwrap_or_default()
+}
+
+fn print_rescore(report: &RescoreReport) {
+    let verb = if report.dry_run {
+        "Would rescore"
     } else {
-        index_human(ctx, &roots, args)
+        "Rescored"
+    };
+    println!(
+        "{} {} {} skill(s) with weights {} ({} changed)",
+        "✓".green().bold(),
+        verb,
+        report.rescored,
+        report.weights_hash,
+        report.changed
+    );
+    println!(
+        "  mean   {:.3} -> {:.3}",
+        report.before.mean, report.after.mean
+    );
+    println!(
+        "  median {:.3} -> {:.3}",
+        report.before.median, report.after.median
+    );
+    if !report.movers.is_empty() {
+        println!();
+        println!("Biggest movers:");
+        for mover in &report.movers {
+            let delta = format!("{:+.3}", mover.delta);
+            let delta = if mover.delta > 0.0 {
+                delta.green()
+            } else {
+                delta.red()
+            };
+            println!(
+                "  {:<32} {:.3} -> {:.3} ({})",
+                mover.skill_id, mover.before, mover.after, delta
+            );
+        }
     }
+    if !report.failed.is_empty() {
+        println!();
+        for failure in &report.failed {
+            eprintln!("{} {}", "✗".red(), failure);
+        }
+    }
+    if report.dry_run && report.changed > 0 {
+        println!();
+        println!("Run without --dry-run to store the new scores");
+    }
+}
+
+/// Compact after an index run when `[search.compaction]` allows it and the
+/// share of deleted documents exceeds its threshold.
+fn auto_compact(ctx: &AppContext) -> Result<Option<CompactionReport>> {
+    let settings = &ctx.config.search.compaction;
+    let stats = ctx.search.stats();
+    if !settings.auto
+        || stats.deleted_docs == 0
+        || stats.deleted_ratio() <= f64::from(settings.deleted_ratio)
+    {
+        return Ok(None);
+    }
+    compact_index(ctx).map(Some)
+}
+
+fn print_compaction(report: &CompactionReport) {
+    let (before, after) = (report.before, report.after);
+    println!(
+        "{} Compacted search index: {} -> {}",
+        "✓".green().bold(),
+        format_size(before.size_bytes),
+        format_size(after.size_bytes)
+    );
+    println!(
+        "  {} -> {} segment(s), {} deleted document(s) dropped, {} live",
+        before.stats.segments,
+        after.stats.segments,
+        before
+            .stats
+            .deleted_docs
+            .saturating_sub(after.stats.deleted_docs),
+        after.stats.live_docs
+    );
+}
+
+/// Index a single `SKILL.md` without rescanning (or pruning) the configured
+/// roots. Used by `ms new --index` right after the file is written.
+pub(crate) fn index_one(ctx: &AppContext, path: &Path, layer: SkillLayer) -> Result<()> {
+    ctx.require_writable_search()?;
+    let _lock = GlobalLock::acquire_or_busy(&ctx.ms_root, lock_timeout())?;
+    ctx.search.reserve_writer()?;
+
+    let tx_mgr = TxManager::new(
+        Arc::clone(&ctx.db),
+        Arc::clone(&ctx.git),
+        ctx.ms_root.clone(),
+    )?;
+    let skill = DiscoveredSkill {
+        path: path.to_path_buf(),
+        layer,
+        companion_count: count_companion_files(path),
+    };
+    index_skill_file(
+        ctx,
+        &IndexPass::new(ctx, &tx_mgr)?,
+        &skill,
+        true,
+        ctx.config.enrichment.enabled,
+    )?;
+    reconcile_layers(ctx, &tx_mgr)?;
+    ctx.search.commit()
 }
 
 fn collect_index_paths(ctx: &AppContext, args: &IndexArgs) -> Result<Vec<SkillRoot>> {
+    // Globs are keyed by the path as the user wrote it
+    let ignore = |configured: &str| {
+        (!args.no_ignore).then(|| {
+            ctx.config
+                .skill_paths
+                .ignore
+                .get(configured)
+                .cloned()
+                .unwrap_or_default()
+        })
+    };
+
     if !args.paths.is_empty() {
         // Use explicitly provided paths
         return Ok(args
@@ -150,13 +670,14 @@ fn collect_index_paths(ctx: &AppContext, args: &IndexArgs) -> Result<Vec<SkillRo
             .map(|p| SkillRoot {
                 path: expand_path(p),
                 layer: SkillLayer::Project,
+                ignore: ignore(p),
             })
             .collect());
     }
 
     // If --from-ru, use ru-managed repositories
     if args.from_ru {
-        return collect_ru_paths(ctx);
+        return collect_ru_paths(ctx, args.no_ignore);
     }
 
     // Use configured paths
@@ -167,24 +688,28 @@ fn collect_index_paths(ctx: &AppContext, args: &IndexArgs) -> Result<Vec<SkillRo
         roots.push(SkillRoot {
             path: expand_path(p),
             layer: SkillLayer::Org,
+            ignore: ignore(p),
         });
     }
     for p in &ctx.config.skill_paths.project {
         roots.push(SkillRoot {
-            path: expand_path(p),
+            path: ctx.project_skill_path(p),
             layer: SkillLayer::Project,
+            ignore: ignore(p),
         });
     }
     for p in &ctx.config.skill_paths.community {
         roots.push(SkillRoot {
             path: expand_path(p),
             layer: SkillLayer::Base,
+            ignore: ignore(p),
         });
     }
     for p in &ctx.config.skill_paths.local {
         roots.push(SkillRoot {
             path: expand_path(p),
             layer: SkillLayer::User,
+            ignore: ignore(p),
         });
     }
 
@@ -193,7 +718,7 @@ fn collect_index_paths(ctx: &AppContext, args: &IndexArgs) -> Result<Vec<SkillRo
 }
 
 /// Collect paths from ru-managed repositories
-fn collect_ru_paths(ctx: &AppContext) -> Result<Vec<SkillRoot>> {
+fn collect_ru_paths(ctx: &AppContext, no_ignore: bool) -> Result<Vec<SkillRoot>> {
     let mut ru_client = RuClient::new();
 
     if !ru_client.is_available() {
@@ -214,50 +739,32 @@ fn collect_ru_paths(ctx: &AppContext) -> Result<Vec<SkillRoot>> {
         .map(|path| SkillRoot {
             path,
             layer: SkillLayer::Base,
+            ignore: (!no_ignore).then(Vec::new),
         })
         .collect();
 
     Ok(roots)
 }
 
-fn expand_path(input: &str) -> PathBuf {
-    if let Some(stripped) = input.strip_prefix("~/") {
-        if let Some(home) = dirs::home_dir() {
-            return home.join(stripped);
-        }
-    }
-    if input == "~" {
-        if let Some(home) = dirs::home_dir() {
-            return home;
-        }
-    }
-    PathBuf::from(input)
-}
-
-fn index_human(ctx: &AppContext, roots: &[SkillRoot], args: &IndexArgs) -> Result<()> {
+fn index_human(
+    ctx: &AppContext,
+    roots: &[SkillRoot],
+    args: &IndexArgs,
+    progress: &PhaseProgress,
+) -> Result<()> {
     println!("{}", "Indexing skills...".bold());
     println!();
 
     let start = Instant::now();
-    let mut indexed = 0;
+    let mut counts = IndexCounts::default();
     let mut errors = 0;
 
     // First pass: discover all SKILL.md files
-    let skill_files = discover_skill_files(roots);
-
-    if skill_files.is_empty() {
-        println!("{}", "No SKILL.md files found".yellow());
-        return Ok(());
-    }
-
-    // Progress bar
-    let pb = ProgressBar::new(skill_files.len() as u64);
-    pb.set_style(
-        ProgressStyle::default_bar()
-            .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} {msg}")
-            .unwrap()
-            .progress_chars("#>-"),
-    );
+    let Discovery {
+        skills: skill_files,
+        ignored,
+    } = discover_skill_files(roots)?;
+    let tracked_before = ctx.db.list_source_files()?;
 
     // Create transaction manager
     let tx_mgr = TxManager::new(
@@ -266,38 +773,53 @@ fn index_human(ctx: &AppContext, roots: &[SkillRoot], args: &IndexArgs) -> Resul
         ctx.ms_root.clone(),
     )?;
 
-    // Create resolution cache and repository for resolving inherited/composed skills
-    let resolution_cache = ResolutionCache::new();
-    let repository = GitSkillRepository::new(&ctx.git);
+    if skill_files.is_empty() {
+        let removed = remove_stale_skills(ctx, &tx_mgr, roots, &skill_files, &tracked_before)?;
+        ctx.search.commit()?;
+        println!("{}", "No SKILL.md files found".yellow());
+        if ignored > 0 {
+            println!("{ignored} paths skipped by ignore rules (see --no-ignore)");
+        }
+        if !removed.is_empty() {
+            println!("Removed {} skills whose files were deleted", removed.len());
+        }
+        return Ok(());
+    }
 
-    for skill in &skill_files {
-        pb.set_message(format!(
-            "{}",
-            skill.path.file_name().unwrap_or_default().to_string_lossy()
-        ));
+    // Resolution cache, repository and embedder shared across the pass
+    let pass = IndexPass::new(ctx, &tx_mgr)?;
 
-        match index_skill_file(
-            ctx,
-            &tx_mgr,
-            &resolution_cache,
-            &repository,
-            skill,
-            args.force,
-        ) {
-            Ok(()) => indexed += 1,
+    let total = skill_files.len() as u64;
+    let mut failures = Vec::new();
+    progress.phase("indexing", Some(total));
+    for (done, skill) in skill_files.iter().enumerate() {
+        match index_skill_file(ctx, &pass, skill, args.force, args.enrich(ctx)) {
+            Ok(outcome) => counts.record(outcome),
             Err(e) => {
                 errors += 1;
-                pb.println(format!("{} {} - {}", "✗".red(), skill.path.display(), e));
+                failures.push(format!("{} {} - {}", "✗".red(), skill.path.display(), e));
             }
         }
-
-        pb.inc(1);
+        progress.update(done as u64 + 1, total, Some(&skill.label()));
+    }
+    progress.phase("finalizing", None);
+    for failure in &failures {
+        eprintln!("{failure}");
     }
 
-    pb.finish_and_clear();
+    counts.removed = remove_stale_skills(ctx, &tx_mgr, roots, &skill_files, &tracked_before)?.len();
+    let unresolved = reconcile_layers(ctx, &tx_mgr)?;
+    // Unchanged files are skipped, so tags of skills indexed before the
+    // `skill_tags` table existed are filled in here.
+    ctx.db.backfill_skill_tags()?;
+    if args.enrich(ctx) {
+        backfill_derived_tags(ctx)?;
+    }
 
     // Commit Tantivy index
     ctx.search.commit()?;
+    let compaction = auto_compact(ctx)?;
+    progress.finish();
 
     let elapsed = start.elapsed();
 
@@ -305,26 +827,57 @@ fn index_human(ctx: &AppContext, roots: &[SkillRoot], args: &IndexArgs) -> Resul
     println!(
         "{} Indexed {} skills in {:.2}s ({} errors)",
         "✓".green().bold(),
-        indexed,
+        counts.indexed(),
         elapsed.as_secs_f64(),
         errors
     );
+    println!(
+        "  {} added, {} updated, {} unchanged, {} removed",
+        counts.added, counts.updated, counts.skipped, counts.removed
+    );
+    if ignored > 0 {
+        println!("  {ignored} paths skipped by ignore rules");
+    }
+    if let Some(report) = &compaction {
+        print_compaction(report);
+    }
 
     if errors > 0 {
         println!();
         println!("{} {} skills failed to index", "!".yellow(), errors);
     }
 
+    if !unresolved.is_empty() {
+        println!();
+        println!(
+            "{} {} layer conflicts kept the highest layer: {}",
+            "!".yellow(),
+            unresolved.len(),
+            unresolved.join(", ")
+        );
+        println!("  Review and resolve with: ms doctor conflicts");
+    }
+
     Ok(())
 }
 
-fn index_robot(ctx: &AppContext, roots: &[SkillRoot], args: &IndexArgs) -> Result<()> {
+fn index_robot(
+    ctx: &AppContext,
+    roots: &[SkillRoot],
+    args: &IndexArgs,
+    rebuilt: Option<usize>,
+    progress: &PhaseProgress,
+) -> Result<()> {
     let start = Instant::now();
-    let mut indexed = 0;
+    let mut counts = IndexCounts::default();
     let mut errors: Vec<serde_json::Value> = Vec::new();
 
     // Discover skill files
-    let skill_files = discover_skill_files(roots);
+    let Discovery {
+        skills: skill_files,
+        ignored,
+    } = discover_skill_files(roots)?;
+    let tracked_before = ctx.db.list_source_files()?;
 
     // Create transaction manager
     let tx_mgr = TxManager::new(
@@ -333,20 +886,14 @@ fn index_robot(ctx: &AppContext, roots: &[SkillRoot], args: &IndexArgs) -> Resul
         ctx.ms_root.clone(),
     )?;
 
-    // Create resolution cache and repository for resolving inherited/composed skills
-    let resolution_cache = ResolutionCache::new();
-    let repository = GitSkillRepository::new(&ctx.git);
-
-    for skill in &skill_files {
-        match index_skill_file(
-            ctx,
-            &tx_mgr,
-            &resolution_cache,
-            &repository,
-            skill,
-            args.force,
-        ) {
-            Ok(()) => indexed += 1,
+    // Resolution cache, repository and embedder shared across the pass
+    let pass = IndexPass::new(ctx, &tx_mgr)?;
+
+    let total = skill_files.len() as u64;
+    progress.phase("indexing", Some(total));
+    for (done, skill) in skill_files.iter().enumerate() {
+        match index_skill_file(ctx, &pass, skill, args.force, args.enrich(ctx)) {
+            Ok(outcome) => counts.record(outcome),
             Err(e) => {
                 errors.push(serde_json::json!({
                     "path": skill.path.display().to_string(),
@@ -354,37 +901,303 @@ fn index_robot(ctx: &AppContext, roots: &[SkillRoot], args: &IndexArgs) -> Resul
                 }));
             }
         }
+        progress.update(done as u64 + 1, total, Some(&skill.label()));
+    }
+    progress.phase("finalizing", None);
+
+    let removed = remove_stale_skills(ctx, &tx_mgr, roots, &skill_files, &tracked_before)?;
+    counts.removed = removed.len();
+    let unresolved = reconcile_layers(ctx, &tx_mgr)?;
+    ctx.db.backfill_skill_tags()?;
+    if args.enrich(ctx) {
+        backfill_derived_tags(ctx)?;
     }
 
     // Commit Tantivy index
     ctx.search.commit()?;
+    let compaction = auto_compact(ctx)?;
+    progress.finish();
 
     let elapsed = start.elapsed();
 
-    let total_companions: usize = skill_files.iter().map(|s| s.companion_count).sum();
-    let skills_with_companions: usize =
-        skill_files.iter().filter(|s| s.companion_count > 0).count();
+    let total_companions: usize = skill_files.iter().map(|s| s.companion_count).sum();
+    let skills_with_companions: usize =
+        skill_files.iter().filter(|s| s.companion_count > 0).count();
+
+    println!(
+        "{}",
+        serde_json::json!({
+            "status": if errors.is_empty() { "ok" } else { "partial" },
+            "indexed": counts.indexed(),
+            "added": counts.added,
+            "updated": counts.updated,
+            "skipped": counts.skipped,
+            "removed": counts.removed,
+            "removed_skills": removed,
+            "ignored": ignored,
+            "unresolved_conflicts": unresolved,
+            "compaction": compaction,
+            "schema_rebuild": rebuilt.map(|skills| serde_json::json!({
+                "version": SCHEMA_VERSION,
+                "skills": skills,
+            })),
+            "errors": errors,
+            "elapsed_ms": elapsed.as_millis() as u64,
+            "package_summary": {
+                "skills_discovered": skill_files.len(),
+                "skills_with_companions": skills_with_companions,
+                "total_companion_files": total_companions,
+            },
+        })
+    );
+
+    Ok(())
+}
+
+// =============================================================================
+// Watch mode
+// =============================================================================
+
+/// Events for a path are coalesced until it has been quiet this long, so
+/// editors that write several times per save trigger one re-index
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// How often the watch loop checks for Ctrl-C while no events arrive
+const WATCH_STOP_CHECK: Duration = Duration::from_millis(250);
+
+/// Tracks `SKILL.md` stamps between file-system event batches.
+#[derive(Debug, Default)]
+struct WatchState {
+    /// (mtime_ms, size) per file as of the last scan
+    seen: HashMap<PathBuf, (i64, i64)>,
+}
+
+impl WatchState {
+    fn new(initial: HashMap<PathBuf, (i64, i64)>) -> Self {
+        Self { seen: initial }
+    }
+
+    /// Record a scan and return the new, changed, and deleted files.
+    fn changes(&mut self, scan: HashMap<PathBuf, (i64, i64)>) -> Vec<PathBuf> {
+        let mut changed: Vec<PathBuf> = scan
+            .iter()
+            .filter(|(path, stamp)| self.seen.get(*path) != Some(*stamp))
+            .map(|(path, _)| path.clone())
+            .chain(
+                self.seen
+                    .keys()
+                    .filter(|path| !scan.contains_key(*path))
+                    .cloned(),
+            )
+            .collect();
+        changed.sort();
+        self.seen = scan;
+        changed
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.seen.contains_key(path)
+    }
+}
+
+fn scan_stamps(skill_files: &[DiscoveredSkill]) -> HashMap<PathBuf, (i64, i64)> {
+    skill_files
+        .iter()
+        .filter_map(|skill| {
+            file_stamp(&skill.path)
+                .ok()
+                .map(|stamp| (skill.path.clone(), stamp))
+        })
+        .collect()
+}
+
+/// Watch the skill roots and re-index files as their events settle, until Ctrl-C.
+///
+/// File-system events are debounced per path; each settled batch triggers
+/// one rescan, and only files whose stamps changed are re-indexed. Human
+/// mode prints one line per change; robot mode prints one NDJSON event per
+/// change (`watching`, `indexed`, `removed`, `error`, `stopped`).
+fn watch(ctx: &AppContext, roots: &[SkillRoot], enrich: bool) -> Result<()> {
+    let robot = ctx.output_format != OutputFormat::Human;
+    let stop = interrupt_flag();
+    let mut state = WatchState::new(scan_stamps(&discover_skill_files(roots)?.skills));
+
+    let (events_tx, events) = std::sync::mpsc::channel();
+    let mut watcher = new_debouncer(WATCH_DEBOUNCE, events_tx).map_err(watch_error)?;
+    for root in roots.iter().filter(|root| root.path.exists()) {
+        watcher
+            .watcher()
+            .watch(&root.path, RecursiveMode::Recursive)
+            .map_err(watch_error)?;
+    }
+
+    if robot {
+        emit_watch_event(serde_json::json!({
+            "event": "watching",
+            "roots": roots.iter().map(|r| r.path.display().to_string()).collect::<Vec<_>>(),
+            "files": state.seen.len(),
+        }));
+    } else {
+        println!();
+        println!(
+            "Watching {} skill files for changes (Ctrl-C to stop)...",
+            state.seen.len()
+        );
+    }
+
+    while !stop.load(Ordering::SeqCst) {
+        match events.recv_timeout(WATCH_STOP_CHECK) {
+            Ok(Ok(_)) => {}
+            Ok(Err(e)) => {
+                report_watch_error(robot, None, &watch_error(e));
+                continue;
+            }
+            Err(RecvTimeoutError::Timeout) => continue,
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+        let skill_files = match discover_skill_files(roots) {
+            Ok(discovery) => discovery.skills,
+            Err(e) => {
+                report_watch_error(robot, None, &e);
+                continue;
+            }
+        };
+        let changed = state.changes(scan_stamps(&skill_files));
+        if changed.is_empty() {
+            continue;
+        }
+        if let Err(e) = reindex_batch(ctx, roots, &skill_files, &changed, &state, robot, enrich) {
+            report_watch_error(robot, None, &e);
+        }
+    }
+
+    drop(watcher);
+    // Nothing is left uncommitted between batches, but flush defensively
+    ctx.search.commit()?;
+    if robot {
+        emit_watch_event(serde_json::json!({ "event": "stopped" }));
+    } else {
+        println!("Stopped watching.");
+    }
+    Ok(())
+}
+
+fn watch_error(error: notify_debouncer_mini::notify::Error) -> MsError {
+    MsError::Io(std::io::Error::other(format!("file watcher: {error}")))
+}
+
+/// Re-index the settled files under the global lock and commit once.
+fn reindex_batch(
+    ctx: &AppContext,
+    roots: &[SkillRoot],
+    skill_files: &[DiscoveredSkill],
+    changed: &[PathBuf],
+    state: &WatchState,
+    robot: bool,
+    enrich: bool,
+) -> Result<()> {
+    let _lock = GlobalLock::acquire_or_busy(&ctx.ms_root, lock_timeout())?;
+    ctx.search.reserve_writer()?;
+    let tx_mgr = TxManager::new(
+        Arc::clone(&ctx.db),
+        Arc::clone(&ctx.git),
+        ctx.ms_root.clone(),
+    )?;
+    let pass = IndexPass::new(ctx, &tx_mgr)?;
+
+    let mut deleted = Vec::new();
+    for path in changed {
+        if !state.exists(path) {
+            if let Some(tracked) = ctx.db.get_source_file(&path.display().to_string())? {
+                deleted.push(tracked);
+            }
+            continue;
+        }
+        let Some(skill) = skill_files.iter().find(|s| &s.path == path) else {
+            continue;
+        };
+        match index_skill_file(ctx, &pass, skill, false, enrich) {
+            Ok(IndexOutcome::Skipped) => {}
+            Ok(outcome) => {
+                let skill_id = ctx
+                    .db
+                    .get_source_file(&path.display().to_string())?
+                    .map(|f| f.skill_id)
+                    .unwrap_or_default();
+                let action = if outcome == IndexOutcome::Added {
+                    "added"
+                } else {
+                    "updated"
+                };
+                if robot {
+                    emit_watch_event(serde_json::json!({
+                        "event": "indexed",
+                        "action": action,
+                        "skill_id": skill_id,
+                        "path": path.display().to_string(),
+                        "at": chrono::Utc::now().to_rfc3339(),
+                    }));
+                } else {
+                    println!(
+                        "{} {} {} ({})",
+                        chrono::Local::now().format("%H:%M:%S"),
+                        action.green(),
+                        skill_id,
+                        path.display()
+                    );
+                }
+            }
+            Err(e) => report_watch_error(robot, Some(path), &e),
+        }
+    }
+
+    for skill_id in remove_stale_skills(ctx, &tx_mgr, roots, skill_files, &deleted)? {
+        if robot {
+            emit_watch_event(serde_json::json!({
+                "event": "removed",
+                "skill_id": skill_id,
+                "at": chrono::Utc::now().to_rfc3339(),
+            }));
+        } else {
+            println!(
+                "{} {} {}",
+                chrono::Local::now().format("%H:%M:%S"),
+                "removed".red(),
+                skill_id
+            );
+        }
+    }
+
+    reconcile_layers(ctx, &tx_mgr)?;
+    ctx.search.commit()?;
+    Ok(())
+}
+
+fn report_watch_error(robot: bool, path: Option<&Path>, error: &MsError) {
+    if robot {
+        emit_watch_event(serde_json::json!({
+            "event": "error",
+            "path": path.map(|p| p.display().to_string()),
+            "error": error.to_string(),
+        }));
+    } else {
+        match path {
+            Some(path) => eprintln!("{} {} - {}", "✗".red(), path.display(), error),
+            None => eprintln!("{} {}", "✗".red(), error),
+        }
+    }
+}
 
-    println!(
-        "{}",
-        serde_json::json!({
-            "status": if errors.is_empty() { "ok" } else { "partial" },
-            "indexed": indexed,
-            "errors": errors,
-            "elapsed_ms": elapsed.as_millis() as u64,
-            "package_summary": {
-                "skills_discovered": skill_files.len(),
-                "skills_with_companions": skills_with_companions,
-                "total_companion_files": total_companions,
-            },
-        })
-    );
+fn emit_watch_event(event: serde_json::Value) {
+    use std::io::Write;
 
-    Ok(())
+    let mut stdout = std::io::stdout().lock();
+    let _ = writeln!(stdout, "{event}");
+    let _ = stdout.flush();
 }
 
-fn discover_skill_files(roots: &[SkillRoot]) -> Vec<DiscoveredSkill> {
-    let mut skill_files = Vec::new();
+fn discover_skill_files(roots: &[SkillRoot]) -> Result<Discovery> {
+    let mut discovery = Discovery::default();
 
     for root in roots {
         if !root.path.exists() {
@@ -392,39 +1205,68 @@ fn discover_skill_files(roots: &[SkillRoot]) -> Vec<DiscoveredSkill> {
         }
 
         // Filter out junk directories (`target/`, `node_modules/`, `.git/`,
-        // etc.) before they ever get walked. Cuts discovery time on large
-        // workspaces and avoids accidentally treating a build artifact tree
-        // as a skill package.
+        // etc.) and paths matched by ignore rules before they ever get
+        // walked. Cuts discovery time on large workspaces and avoids
+        // accidentally treating a build artifact tree as a skill package.
         //
         // Depth-0 (the root the user explicitly named) is exempt: a user who
         // says `ms index ~/work/build/` is asserting that path IS the
         // workspace, even if its final component happens to match the
         // skip-list. We only prune *descendants* whose names match.
+        let mut rules = root
+            .ignore
+            .as_deref()
+            .map(|globs| IgnoreRules::new(&root.path, globs))
+            .transpose()?;
+        let mut ignored = 0;
         let walker = WalkDir::new(&root.path)
             .follow_links(false)
             .into_iter()
             .filter_entry(|entry| {
-                if !entry.file_type().is_dir() || entry.depth() == 0 {
+                let Some(rules) = rules.as_mut() else {
+                    return true;
+                };
+                if entry.depth() == 0 {
                     return true;
                 }
+                let is_dir = entry.file_type().is_dir();
                 let name = entry.file_name().to_string_lossy();
-                !is_skipped_skill_discovery_dir(name.as_ref())
+                let skip = (is_dir && is_skipped_skill_discovery_dir(name.as_ref()))
+                    || rules.is_ignored(entry.path(), is_dir);
+                if skip {
+                    ignored += 1;
+                }
+                !skip
             })
             .filter_map(std::result::Result::ok);
 
         for entry in walker {
             if entry.file_type().is_file() && entry.file_name() == "SKILL.md" {
                 let companion_count = count_companion_files(entry.path());
-                skill_files.push(DiscoveredSkill {
+                discovery.skills.push(DiscoveredSkill {
                     path: entry.path().to_path_buf(),
                     layer: root.layer,
                     companion_count,
                 });
             }
         }
+        discovery.ignored += ignored;
     }
 
-    skill_files
+    Ok(discovery)
+}
+
+/// Whether `path`, a file under `root`, is now skipped by discovery: inside
+/// a junk directory or matched by the root's ignore rules.
+fn is_ignored_under(root: &SkillRoot, rules: &mut IgnoreRules, path: &Path) -> bool {
+    let Ok(relative) = path.strip_prefix(&root.path) else {
+        return false;
+    };
+    let in_junk_dir = relative.parent().is_some_and(|dirs| {
+        dirs.components()
+            .any(|dir| is_skipped_skill_discovery_dir(&dir.as_os_str().to_string_lossy()))
+    });
+    in_junk_dir || rules.is_ignored_path(path)
 }
 
 /// Count files in the same directory tree as a `SKILL.md`, excluding the
@@ -436,10 +1278,9 @@ fn discover_skill_files(roots: &[SkillRoot]) -> Vec<DiscoveredSkill> {
 /// its own `SKILL.md`. This prevents `parent/SKILL.md` from claiming files
 /// that semantically belong to `parent/child/SKILL.md`.
 ///
-/// This is read-only and side-effect-free — it does not store, hash, or
-/// index the companion files. It exists to surface package shape in the
-/// indexing summary while a deeper schema/storage design for indexed
-/// resources is worked out (PR #80, follow-up bead).
+/// This is read-only and side-effect-free: it surfaces package shape in the
+/// indexing summary. The files that travel with the skill are the narrower
+/// set [`sync_resources`] records and archives.
 fn count_companion_files(skill_md: &std::path::Path) -> usize {
     let pkg_root = match skill_md.parent() {
         Some(p) => p,
@@ -485,16 +1326,116 @@ fn count_companion_files(skill_md: &std::path::Path) -> usize {
     count
 }
 
+/// State shared by every file of one indexing pass.
+struct IndexPass<'a> {
+    tx_mgr: &'a TxManager,
+    resolution_cache: ResolutionCache,
+    repository: GitSkillRepository<'a>,
+    /// The active `search.embeddings` backend, built once per pass
+    embedder: Box<dyn Embedder>,
+}
+
+impl<'a> IndexPass<'a> {
+    fn new(ctx: &'a AppContext, tx_mgr: &'a TxManager) -> Result<Self> {
+        Ok(Self {
+            tx_mgr,
+            resolution_cache: ResolutionCache::new(),
+            repository: GitSkillRepository::new(&ctx.git),
+            embedder: build_embedder(&ctx.config.search)?,
+        })
+    }
+}
+
+/// Embed `skill` with `embedder` and store the vector, replacing one left by
+/// another backend. Returns the embedding.
+pub(crate) fn store_embedding(
+    ctx: &AppContext,
+    embedder: &dyn Embedder,
+    skill: &SkillRecord,
+) -> Result<Vec<f32>> {
+    let embedding = embedder.embed(&embedding_text(skill))?;
+    ctx.db.upsert_embedding(&EmbeddingRecord {
+        skill_id: skill.id.clone(),
+        dims: embedding.len(),
+        embedding: embedding.clone(),
+        embedder_type: embedder.name().to_string(),
+        content_hash: Some(skill.content_hash.clone()),
+        computed_at: chrono::Utc::now().to_rfc3339(),
+    })?;
+    Ok(embedding)
+}
+
+/// Index one `SKILL.md`, skipping it when unchanged since the last run.
+///
+/// Change detection uses the per-file state in `skill_source_files`: an equal
+/// mtime and size skips without reading the file, an equal content hash skips
+/// without parsing it. `force` bypasses both.
 fn index_skill_file(
     ctx: &AppContext,
-    tx_mgr: &TxManager,
-    resolution_cache: &ResolutionCache,
-    repository: &GitSkillRepository<'_>,
+    pass: &IndexPass<'_>,
     skill: &DiscoveredSkill,
     force: bool,
-) -> Result<()> {
+    enrich: bool,
+) -> Result<IndexOutcome> {
+    let IndexPass {
+        tx_mgr,
+        resolution_cache,
+        repository,
+        embedder,
+    } = pass;
+    let path_key = skill.path.display().to_string();
+    let layer = skill.layer.as_str();
+    let (mtime_ms, size) = file_stamp(&skill.path)?;
+    let tracked = ctx.db.get_source_file(&path_key)?.filter(|t| {
+        t.layer == layer
+            && ctx
+                .db
+                .get_skill_candidate(&t.skill_id)
+                .ok()
+                .flatten()
+                .is_some()
+    });
+
+    if !force
+        && let Some(tracked) = &tracked
+        && tracked.mtime_ms == mtime_ms
+        && tracked.size == size
+    {
+        // Resources can change without the SKILL.md changing
+        let has_resources = skill.path.with_file_name(resources::RESOURCES_DIR).is_dir()
+            || !ctx.db.get_skill_resources(&tracked.skill_id)?.is_empty();
+        if has_resources {
+            let content = std::fs::read_to_string(&skill.path)?;
+            return unchanged_unless_resources(ctx, &tracked.skill_id, &skill.path, &content);
+        }
+        return Ok(IndexOutcome::Skipped);
+    }
+
     // Read the file
-    let content = std::fs::read_to_string(&skill.path)?;
+    let bytes = std::fs::read(&skill.path)?;
+    let mut file_state = SourceFileRecord {
+        path: path_key,
+        skill_id: String::new(),
+        layer: layer.to_string(),
+        content_hash: hash_file_bytes(&bytes),
+        mtime_ms,
+        size,
+        indexed_at: chrono::Utc::now().to_rfc3339(),
+    };
+
+    // Touched but not edited: refresh the stamp only
+    if !force
+        && let Some(tracked) = &tracked
+        && tracked.content_hash == file_state.content_hash
+    {
+        file_state.skill_id.clone_from(&tracked.skill_id);
+        ctx.db.upsert_source_file(&file_state)?;
+        let content = String::from_utf8_lossy(&bytes);
+        return unchanged_unless_resources(ctx, &tracked.skill_id, &skill.path, &content);
+    }
+
+    let content = String::from_utf8(bytes)
+        .map_err(|e| MsError::InvalidSkill(format!("{}: {}", skill.path.display(), e)))?;
 
     // Parse the skill spec
     let spec = parse_markdown(&content)
@@ -506,30 +1447,48 @@ fn index_skill_file(
             skill.path.display()
         )));
     }
+    file_state.skill_id.clone_from(&spec.metadata.id);
 
     // Check if already indexed (unless force)
     let new_hash = compute_spec_hash(&spec)?;
-    if !force {
-        if let Ok(Some(existing)) = ctx.db.get_skill(&spec.metadata.id) {
-            // Check content hash to skip unchanged skills
-            let same_layer = existing.source_layer == skill.layer.as_str();
-            if existing.content_hash == new_hash && same_layer {
-                return Ok(()); // Skip unchanged
-            }
-        }
+    let existing = ctx.db.get_skill(&spec.metadata.id).ok().flatten();
+    if !force
+        && let Some(existing) = &existing
+        && existing.content_hash == new_hash
+        && existing.source_layer == layer
+    {
+        // Skip unchanged (e.g. only whitespace outside the spec changed)
+        ctx.db.upsert_source_file(&file_state)?;
+        return unchanged_unless_resources(ctx, &spec.metadata.id, &skill.path, &content);
     }
+    let outcome = if existing.is_some() {
+        IndexOutcome::Updated
+    } else {
+        IndexOutcome::Added
+    };
 
     // Write using 2PC transaction manager (stores raw spec)
-    tx_mgr.write_skill_with_layer(&spec, skill.layer)?;
+    tx_mgr.write_skill_with_layer(&spec, skill.layer, &Provenance::new("index"))?;
+    sync_resources(ctx, &spec.metadata.id, &skill.path, &content)?;
 
     // Compute and persist quality score
-    let scorer = crate::quality::QualityScorer::with_defaults();
-    let quality = scorer.score_spec(&spec, &crate::quality::QualityContext::default());
+    let quality = crate::cli::commands::quality::scorer(ctx)
+        .score_spec(&spec, &crate::quality::QualityContext::default());
     ctx.db
         .update_skill_quality(&spec.metadata.id, f64::from(quality.overall))?;
+    if let Some(record) = ctx.db.get_skill(&spec.metadata.id)? {
+        store_embedding(ctx, embedder.as_ref(), &record)?;
+    }
+
+    if enrich {
+        enrich_skill(ctx, &spec)?;
+    }
+    let derived_tags = derived_tag_names(ctx, &spec.metadata.id)?;
 
-    // Resolve the skill if it has inheritance or composition
-    let needs_resolution = spec.extends.is_some() || !spec.includes.is_empty();
+    // Resolve the skill if it has inheritance or composition. Sensitive skills
+    // are not resolved: the resolution cache would hold their plaintext.
+    let needs_resolution = !crate::security::encryption::is_sensitive(&spec)
+        && (spec.extends.is_some() || !spec.includes.is_empty());
 
     if needs_resolution {
         // Create a hash lookup function that reads skills from git archive and hashes them
@@ -547,25 +1506,433 @@ fn index_skill_file(
 
         // Get or compute the resolved skill
         let db_conn = ctx.db.conn();
-        let resolved = resolution_cache.get_or_resolve(
+        match resolution_cache.get_or_resolve(
             db_conn,
             &spec.metadata.id,
             &spec,
             repository,
             compute_hash,
-        )?;
-
-        // Build a SkillRecord from the resolved spec for search indexing
-        let resolved_record = build_skill_record_from_resolved(&resolved.spec, skill, &new_hash);
-        ctx.search.index_skill(&resolved_record)?;
+        ) {
+            Ok(resolved) => {
+                // Build a SkillRecord from the resolved spec for search indexing
+                let resolved_record =
+                    build_skill_record_from_resolved(&resolved.spec, skill, &new_hash);
+                ctx.search
+                    .index_skill_tagged(&resolved_record, &derived_tags)?;
+            }
+            // The parent may simply not be indexed yet; search gets the
+            // skill's own content and `ms show --effective` names the chain
+            Err(
+                err @ (MsError::ParentSkillNotFound { .. } | MsError::CyclicInheritance { .. }),
+            ) => {
+                tracing::warn!("indexing {} unresolved: {err}", spec.metadata.id);
+                if let Some(skill_record) = ctx.db.get_skill(&spec.metadata.id)? {
+                    ctx.search
+                        .index_skill_tagged(&skill_record, &derived_tags)?;
+                }
+            }
+            Err(err) => return Err(err),
+        }
     } else {
         // No resolution needed - index the raw spec directly
         if let Ok(Some(skill_record)) = ctx.db.get_skill(&spec.metadata.id) {
-            ctx.search.index_skill(&skill_record)?;
+            ctx.search
+                .index_skill_tagged(&skill_record, &derived_tags)?;
         }
     }
 
-    Ok(())
+    ctx.db.upsert_source_file(&file_state)?;
+    Ok(outcome)
+}
+
+/// `Skipped` for a skill whose `SKILL.md` is unchanged, or `Updated` when
+/// its resources changed anyway.
+fn unchanged_unless_resources(
+    ctx: &AppContext,
+    skill_id: &str,
+    skill_md: &Path,
+    markdown: &str,
+) -> Result<IndexOutcome> {
+    if sync_resources(ctx, skill_id, skill_md, markdown)? {
+        Ok(IndexOutcome::Updated)
+    } else {
+        Ok(IndexOutcome::Skipped)
+    }
+}
+
+/// Record the resources of the skill at `skill_md` and archive the changed
+/// ones next to its archived `SKILL.md`. Returns whether anything changed.
+///
+/// Resources of sensitive skills are recorded but never archived: the archive
+/// would hold them in plaintext next to the sealed `SKILL.md`.
+fn sync_resources(
+    ctx: &AppContext,
+    skill_id: &str,
+    skill_md: &Path,
+    markdown: &str,
+) -> Result<bool> {
+    let found = resources::collect_resources(skill_md, markdown)?;
+    let records: Vec<_> = found.iter().map(ResourceFile::record).collect();
+    let stored = ctx.db.get_skill_resources(skill_id)?;
+    if parse_markdown(markdown).is_ok_and(|spec| crate::security::encryption::is_sensitive(&spec)) {
+        return record_unarchived_resources(ctx, skill_id, &records, &stored);
+    }
+    if records == stored {
+        return Ok(false);
+    }
+
+    let files = found
+        .iter()
+        .zip(&records)
+        .filter(|(_, record)| !stored.contains(record))
+        .map(|(file, _)| Ok((file.path.clone(), std::fs::read(&file.source)?)))
+        .collect::<Result<Vec<_>>>()?;
+    let removed: Vec<String> = stored
+        .iter()
+        .filter(|old| !records.iter().any(|record| record.path == old.path))
+        .map(|old| old.path.clone())
+        .collect();
+    ctx.git
+        .commit_resources(skill_id, &files, &removed, &Provenance::new("index"))?;
+    ctx.db.replace_skill_resources(skill_id, &records)?;
+    Ok(true)
+}
+
+/// Record a sensitive skill's resources without archiving them, removing
+/// copies archived before the skill was marked sensitive.
+fn record_unarchived_resources(
+    ctx: &AppContext,
+    skill_id: &str,
+    records: &[SkillResourceRecord],
+    stored: &[SkillResourceRecord],
+) -> Result<bool> {
+    let archived: Vec<String> = stored
+        .iter()
+        .chain(records)
+        .map(|record| record.path.clone())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter(|path| {
+            ctx.git
+                .resource_path(skill_id, path)
+                .is_some_and(|archived| archived.is_file())
+        })
+        .collect();
+    if !archived.is_empty() {
+        ctx.git
+            .commit_resources(skill_id, &[], &archived, &Provenance::new("index"))?;
+    }
+    if records == stored {
+        return Ok(false);
+    }
+    ctx.db.replace_skill_resources(skill_id, records)?;
+    Ok(true)
+}
+
+/// Replace the language and framework tags derived for `spec`. Sensitive
+/// skills get none: the tags would describe content kept out of the index.
+fn enrich_skill(ctx: &AppContext, spec: &SkillSpec) -> Result<()> {
+    let tags = if crate::security::encryption::is_sensitive(spec) {
+        Vec::new()
+    } else {
+        TagDeriver::new(&ctx.config.enrichment).derive(spec)
+    };
+    ctx.db.replace_derived_tags(&spec.metadata.id, &tags)
+}
+
+fn derived_tag_names(ctx: &AppContext, skill_id: &str) -> Result<Vec<String>> {
+    Ok(ctx
+        .db
+        .get_derived_tags(skill_id)?
+        .into_iter()
+        .map(|derived| derived.tag)
+        .collect())
+}
+
+/// Derive tags for skills that have none, e.g. indexed before enrichment
+/// existed and unchanged since. Returns how many skills gained tags.
+fn backfill_derived_tags(ctx: &AppContext) -> Result<usize> {
+    let deriver = TagDeriver::new(&ctx.config.enrichment);
+    let mut filled = 0;
+    for skill_id in ctx.db.skills_without_derived_tags()? {
+        let Some(record) = ctx.db.get_skill(&skill_id)? else {
+            continue;
+        };
+        if crate::security::encryption::is_sealed_body(&record.body) {
+            continue;
+        }
+        let Ok(spec) = parse_markdown(&record.body) else {
+            continue;
+        };
+        if crate::security::encryption::is_sensitive(&spec) {
+            continue;
+        }
+        let tags = deriver.derive(&spec);
+        if tags.is_empty() {
+            continue;
+        }
+        ctx.db.replace_derived_tags(&skill_id, &tags)?;
+        let names: Vec<String> = tags.into_iter().map(|derived| derived.tag).collect();
+        ctx.search.index_skill_tagged(&record, &names)?;
+        filled += 1;
+    }
+    Ok(filled)
+}
+
+/// Remove skills whose `SKILL.md` disappeared from the scanned roots or is
+/// now skipped by ignore rules.
+///
+/// Only files tracked by earlier runs under a root that still exists are
+/// considered, and a skill is kept while any tracked file still defines it
+/// (e.g. it moved, or another layer has it). Returns the removed skill ids.
+fn remove_stale_skills(
+    ctx: &AppContext,
+    tx_mgr: &TxManager,
+    roots: &[SkillRoot],
+    skill_files: &[DiscoveredSkill],
+    tracked_before: &[SourceFileRecord],
+) -> Result<Vec<String>> {
+    // Files now skipped by ignore rules are dropped like deleted ones
+    let mut scanned: Vec<(&SkillRoot, Option<IgnoreRules>)> = Vec::new();
+    for root in roots.iter().filter(|root| root.path.exists()) {
+        let rules = root
+            .ignore
+            .as_deref()
+            .map(|globs| IgnoreRules::new(&root.path, globs))
+            .transpose()?;
+        scanned.push((root, rules));
+    }
+    let discovered: HashSet<String> = skill_files
+        .iter()
+        .map(|s| s.path.display().to_string())
+        .collect();
+
+    for file in tracked_before {
+        let path = Path::new(&file.path);
+        if discovered.contains(&file.path) {
+            continue;
+        }
+        let Some((root, rules)) = scanned
+            .iter_mut()
+            .find(|(root, _)| path.starts_with(&root.path))
+        else {
+            continue;
+        };
+        let ignored = rules
+            .as_mut()
+            .is_some_and(|rules| is_ignored_under(root, rules, path));
+        if ignored || !path.exists() {
+            ctx.db.delete_source_file(&file.path)?;
+        }
+    }
+
+    let still_defined: HashSet<String> = ctx
+        .db
+        .list_source_files()?
+        .into_iter()
+        .map(|f| f.skill_id)
+        .collect();
+    let orphaned: BTreeSet<&str> = tracked_before
+        .iter()
+        .map(|f| f.skill_id.as_str())
+        .filter(|id| !still_defined.contains(*id))
+        .collect();
+
+    let mut removed = Vec::new();
+    for skill_id in orphaned {
+        if ctx.db.get_skill_candidate(skill_id)?.is_none() {
+            continue;
+        }
+        ctx.db.delete_embedding(skill_id)?;
+        tx_mgr.delete_skill(skill_id, &Provenance::new("index"))?;
+        ctx.search.delete_skill(skill_id)?;
+        removed.push(skill_id.to_string());
+    }
+    Ok(removed)
+}
+
+// =============================================================================
+// Layer conflicts
+// =============================================================================
+
+/// A skill id defined by `SKILL.md` files in more than one layer
+#[derive(Debug, Clone)]
+pub(crate) struct LayerConflict {
+    pub skill_id: String,
+    /// Layers with a candidate, lowest first
+    pub layers: Vec<SkillLayer>,
+    /// Key for stored resolutions; changes whenever any candidate's file does
+    pub candidates_hash: String,
+    /// Sections whose blocks differ between layers
+    pub sections: Vec<ConflictDetail>,
+    /// Remembered strategy for exactly these candidates
+    pub resolution: Option<ConflictStrategy>,
+}
+
+impl LayerConflict {
+    /// Conflicting sections with no remembered resolution
+    pub(crate) const fn is_unresolved(&self) -> bool {
+        !self.sections.is_empty() && self.resolution.is_none()
+    }
+}
+
+/// Registry of every skill defined in more than one layer, built from the
+/// files `ms index` tracks, plus the conflicts between its candidates.
+pub(crate) fn load_layer_conflicts(
+    ctx: &AppContext,
+) -> Result<(LayeredRegistry, Vec<LayerConflict>)> {
+    let mut by_skill: HashMap<String, Vec<SourceFileRecord>> = HashMap::new();
+    for file in ctx.db.list_source_files()? {
+        by_skill
+            .entry(file.skill_id.clone())
+            .or_default()
+            .push(file);
+    }
+
+    let mut registry = LayeredRegistry::new();
+    let mut hashes = HashMap::new();
+    for (skill_id, files) in by_skill {
+        let layers: HashSet<&str> = files.iter().map(|f| f.layer.as_str()).collect();
+        if layers.len() < 2 {
+            continue;
+        }
+        let mut parts: Vec<String> = files
+            .iter()
+            .map(|f| format!("{}:{}", f.layer, f.content_hash))
+            .collect();
+        parts.sort();
+        hashes.insert(skill_id, hash_file_bytes(parts.join("\n").as_bytes()));
+
+        for file in files {
+            let Some(layer) = parse_layer(&file.layer) else {
+                continue;
+            };
+            let spec = match std::fs::read_to_string(&file.path)
+                .map_err(MsError::from)
+                .and_then(|content| parse_markdown(&content))
+            {
+                Ok(spec) => spec,
+                Err(err) => {
+                    tracing::warn!("skipping layer candidate {}: {err}", file.path);
+                    continue;
+                }
+            };
+            registry.register(SkillCandidate {
+                spec,
+                layer,
+                source_path: file.path,
+                modified_ms: Some(file.mtime_ms),
+            });
+        }
+    }
+
+    let mut conflicts = Vec::new();
+    for skill_id in registry.multi_layer_ids() {
+        let candidates_hash = hashes.remove(&skill_id).unwrap_or_default();
+        // A fork settles the conflict with its origin unless told otherwise
+        let resolution = ctx
+            .db
+            .get_layer_resolution(&skill_id, &candidates_hash)?
+            .and_then(|record| ConflictStrategy::parse(&record.strategy))
+            .or_else(|| {
+                registry
+                    .fork_layer(&skill_id)
+                    .map(ConflictStrategy::PreferLayer)
+            });
+        conflicts.push(LayerConflict {
+            layers: registry.candidate_layers(&skill_id),
+            sections: registry
+                .get_resolution_options(&skill_id)
+                .map(|options| options.conflicts)
+                .unwrap_or_default(),
+            skill_id,
+            candidates_hash,
+            resolution,
+        });
+    }
+    Ok((registry, conflicts))
+}
+
+/// Store the winner of a layer conflict as the indexed skill.
+///
+/// Uses the remembered strategy, or the highest layer when there is none.
+/// Returns whether the stored skill changed. `provenance` records which
+/// command applied it.
+pub(crate) fn apply_layer_resolution(
+    ctx: &AppContext,
+    tx_mgr: &TxManager,
+    registry: &LayeredRegistry,
+    conflict: &LayerConflict,
+    provenance: &Provenance,
+) -> Result<bool> {
+    let strategy = conflict.resolution.unwrap_or_default();
+    let Some(resolved) =
+        registry.effective_with_strategies(&conflict.skill_id, strategy, MergeStrategy::Replace)?
+    else {
+        return Ok(false);
+    };
+
+    let new_hash = compute_spec_hash(&resolved.spec)?;
+    let unchanged = ctx
+        .db
+        .get_skill(&conflict.skill_id)?
+        .is_some_and(|existing| {
+            existing.content_hash == new_hash
+                && existing.source_layer == resolved.source_layer.as_str()
+        });
+    if unchanged {
+        return Ok(false);
+    }
+
+    tx_mgr.write_skill_with_layer(&resolved.spec, resolved.source_layer, provenance)?;
+    if let Some(record) = ctx.db.get_skill(&conflict.skill_id)? {
+        ctx.search
+            .index_skill_tagged(&record, &derived_tag_names(ctx, &record.id)?)?;
+    }
+    Ok(true)
+}
+
+/// Re-apply layer resolutions after indexing, so which candidate is stored
+/// does not depend on which file happened to be written last. Returns the
+/// ids of conflicts that still need a decision.
+fn reconcile_layers(ctx: &AppContext, tx_mgr: &TxManager) -> Result<Vec<String>> {
+    let (registry, conflicts) = load_layer_conflicts(ctx)?;
+    let provenance = Provenance::new("index");
+    let mut unresolved = Vec::new();
+    for conflict in &conflicts {
+        apply_layer_resolution(ctx, tx_mgr, &registry, conflict, &provenance)?;
+        if conflict.is_unresolved() {
+            unresolved.push(conflict.skill_id.clone());
+        }
+    }
+    Ok(unresolved)
+}
+
+pub(crate) fn parse_layer(raw: &str) -> Option<SkillLayer> {
+    match raw {
+        "base" => Some(SkillLayer::Base),
+        "org" => Some(SkillLayer::Org),
+        "project" => Some(SkillLayer::Project),
+        "user" => Some(SkillLayer::User),
+        _ => None,
+    }
+}
+
+/// Modification time (ms since the epoch) and size of a file
+fn file_stamp(path: &Path) -> Result<(i64, i64)> {
+    let meta = std::fs::metadata(path)?;
+    let mtime_ms = meta
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_millis() as i64);
+    Ok((mtime_ms, meta.len() as i64))
+}
+
+fn hash_file_bytes(bytes: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+
+    hex::encode(Sha256::digest(bytes))
 }
 
 /// Build a SkillRecord from a resolved SkillSpec for search indexing
@@ -633,6 +2000,251 @@ mod tests {
     use std::fs;
     use tempfile::TempDir;
 
+    // ==================== Change Detection Tests ====================
+
+    #[test]
+    fn test_index_counts_record() {
+        let mut counts = IndexCounts::default();
+        counts.record(IndexOutcome::Added);
+        counts.record(IndexOutcome::Skipped);
+        counts.record(IndexOutcome::Skipped);
+        counts.record(IndexOutcome::Updated);
+        assert_eq!((counts.added, counts.updated, counts.skipped), (1, 1, 2));
+        assert_eq!(counts.indexed(), 4);
+    }
+
+    /// One non-forced indexing pass over `roots`, as `ms index` runs it.
+    fn index_pass(ctx: &AppContext, roots: &[SkillRoot]) -> IndexCounts {
+        let discovery = discover_skill_files(roots).unwrap();
+        let tracked_before = ctx.db.list_source_files().unwrap();
+        let tx_mgr = TxManager::new(
+            Arc::clone(&ctx.db),
+            Arc::clone(&ctx.git),
+            ctx.ms_root.clone(),
+        )
+        .unwrap();
+        let pass = IndexPass::new(ctx, &tx_mgr).unwrap();
+        let mut counts = IndexCounts::default();
+        for skill in &discovery.skills {
+            let outcome = index_skill_file(ctx, &pass, skill, false, false).unwrap();
+            counts.record(outcome);
+        }
+        counts.removed =
+            remove_stale_skills(ctx, &tx_mgr, roots, &discovery.skills, &tracked_before)
+                .unwrap()
+                .len();
+        ctx.search.commit().unwrap();
+        counts
+    }
+
+    #[test]
+    fn test_incremental_index_skips_updates_and_removes() {
+        let temp = TempDir::new().unwrap();
+        let ctx = AppContext::for_tests(&temp.path().join("ms"));
+        let skills = temp.path().join("skills");
+        let write_skill = |name: &str, body: &str| {
+            let dir = skills.join(name);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(
+                dir.join("SKILL.md"),
+                format!(
+                    "---\nname: {name}\ndescription: {name} skill\n---\n\n# {name}\n\n{body}\n"
+                ),
+            )
+            .unwrap();
+        };
+        for name in ["alpha", "beta", "gamma"] {
+            write_skill(name, "Original guidance.");
+        }
+        let roots = vec![SkillRoot {
+            path: skills.clone(),
+            layer: SkillLayer::Project,
+            ignore: None,
+        }];
+
+        let first = index_pass(&ctx, &roots);
+        assert_eq!((first.added, first.updated, first.skipped), (3, 0, 0));
+
+        let unchanged = index_pass(&ctx, &roots);
+        assert_eq!(
+            unchanged,
+            IndexCounts {
+                skipped: 3,
+                ..IndexCounts::default()
+            }
+        );
+
+        write_skill("beta", "Revised guidance with more detail.");
+        let edited = index_pass(&ctx, &roots);
+        assert_eq!(
+            edited,
+            IndexCounts {
+                updated: 1,
+                skipped: 2,
+                ..IndexCounts::default()
+            }
+        );
+
+        fs::remove_dir_all(skills.join("gamma")).unwrap();
+        let deleted = index_pass(&ctx, &roots);
+        assert_eq!(
+            deleted,
+            IndexCounts {
+                skipped: 2,
+                removed: 1,
+                ..IndexCounts::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_forced_index_re_embeds_with_active_backend() {
+        let temp = TempDir::new().unwrap();
+        let ctx = AppContext::for_tests(&temp.path().join("ms"));
+        let dir = temp.path().join("skills/alpha");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("SKILL.md"),
+            "---\nname: alpha\ndescription: alpha skill\n---\n\n# alpha\n\nGuidance.\n",
+        )
+        .unwrap();
+        let roots = vec![SkillRoot {
+            path: temp.path().join("skills"),
+            layer: SkillLayer::Project,
+            ignore: None,
+        }];
+        index_pass(&ctx, &roots);
+        let skill_id = ctx.db.list_skills(10, 0).unwrap()[0].id.clone();
+        assert_eq!(
+            ctx.db
+                .get_embedding(&skill_id)
+                .unwrap()
+                .unwrap()
+                .embedder_type,
+            "hash"
+        );
+
+        // Left behind by a previously configured backend
+        ctx.db
+            .upsert_embedding(&EmbeddingRecord {
+                skill_id: skill_id.clone(),
+                dims: 8,
+                embedding: vec![0.5; 8],
+                embedder_type: "onnx:mini".to_string(),
+                content_hash: None,
+                computed_at: chrono::Utc::now().to_rfc3339(),
+            })
+            .unwrap();
+        let tx_mgr = TxManager::new(
+            Arc::clone(&ctx.db),
+            Arc::clone(&ctx.git),
+            ctx.ms_root.clone(),
+        )
+        .unwrap();
+        let pass = IndexPass::new(&ctx, &tx_mgr).unwrap();
+        let skill = &discover_skill_files(&roots).unwrap().skills[0];
+        index_skill_file(&ctx, &pass, skill, true, false).unwrap();
+
+        let stored = ctx.db.get_embedding(&skill_id).unwrap().unwrap();
+        assert_eq!(stored.embedder_type, "hash");
+        assert_eq!(stored.dims, ctx.config.search.embedding_dims as usize);
+    }
+
+    #[test]
+    fn test_sensitive_skill_resources_stay_out_of_the_archive() {
+        let temp = TempDir::new().unwrap();
+        let ctx = AppContext::for_tests(&temp.path().join("ms"));
+        let dir = temp.path().join("skills/vault");
+        fs::create_dir_all(dir.join(resources::RESOURCES_DIR)).unwrap();
+        fs::write(
+            dir.join(resources::RESOURCES_DIR).join("creds.txt"),
+            "db-password-hunter2",
+        )
+        .unwrap();
+        let skill_md = dir.join("SKILL.md");
+        let markdown = |sensitive: bool| {
+            format!(
+                "---\nid: vault\nname: vault\ndescription: vault skill\nsensitive: {sensitive}\n---\n\n# vault\n\nGuidance.\n"
+            )
+        };
+        let archived_plaintext = || {
+            WalkDir::new(ctx.git.root())
+                .into_iter()
+                .filter_map(std::result::Result::ok)
+                .filter(|entry| entry.file_type().is_file())
+                .any(|entry| {
+                    fs::read(entry.path()).is_ok_and(|bytes| {
+                        bytes
+                            .windows(b"hunter2".len())
+                            .any(|window| window == b"hunter2")
+                    })
+                })
+        };
+
+        // Archived while the skill is not sensitive
+        assert!(sync_resources(&ctx, "vault", &skill_md, &markdown(false)).unwrap());
+        assert!(archived_plaintext());
+
+        // Marking it sensitive drops the archived copy but keeps the record
+        sync_resources(&ctx, "vault", &skill_md, &markdown(true)).unwrap();
+        assert!(!archived_plaintext());
+        assert_eq!(ctx.db.get_skill_resources("vault").unwrap().len(), 1);
+
+        // Changed resources of a sensitive skill are recorded, not archived
+        fs::write(
+            dir.join(resources::RESOURCES_DIR).join("creds.txt"),
+            "db-password-hunter2-rotated",
+        )
+        .unwrap();
+        assert!(sync_resources(&ctx, "vault", &skill_md, &markdown(true)).unwrap());
+        assert!(!archived_plaintext());
+    }
+
+    #[test]
+    fn test_score_stats_mean_and_median() {
+        assert_eq!(ScoreStats::of(&[]), ScoreStats::default());
+        let odd = ScoreStats::of(&[0.9, 0.1, 0.5]);
+        assert!((odd.mean - 0.5).abs() < 1e-9);
+        assert!((odd.median - 0.5).abs() < 1e-9);
+        let even = ScoreStats::of(&[0.2, 0.8, 0.4, 0.6]);
+        assert!((even.median - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_file_stamp_and_hash() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("SKILL.md");
+        fs::write(&path, "# Skill\n").unwrap();
+        let (mtime_ms, size) = file_stamp(&path).unwrap();
+        assert!(mtime_ms > 0);
+        assert_eq!(size, 8);
+        assert_eq!(hash_file_bytes(b"a"), hash_file_bytes(b"a"));
+        assert_ne!(hash_file_bytes(b"a"), hash_file_bytes(b"b"));
+        assert_eq!(hash_file_bytes(b"").len(), 64);
+    }
+
+    #[test]
+    fn test_watch_state_reports_changed_and_deleted_files() {
+        let a = PathBuf::from("/skills/a/SKILL.md");
+        let b = PathBuf::from("/skills/b/SKILL.md");
+        let c = PathBuf::from("/skills/c/SKILL.md");
+        let mut state =
+            WatchState::new(HashMap::from([(a.clone(), (1, 10)), (b.clone(), (1, 10))]));
+
+        // `a` edited, `b` deleted, `c` created
+        let changed = state.changes(HashMap::from([(a.clone(), (2, 11)), (c.clone(), (1, 5))]));
+        assert_eq!(changed, vec![a.clone(), b.clone(), c.clone()]);
+        assert!(state.exists(&a));
+        assert!(!state.exists(&b));
+
+        // An unrelated event batch rescans without reporting anything
+        assert!(
+            state
+                .changes(HashMap::from([(a, (2, 11)), (c, (1, 5))]))
+                .is_empty()
+        );
+    }
+
     // ==================== Expand Path Tests ====================
 
     #[test]
@@ -824,9 +2436,10 @@ mod tests {
         let roots = vec![SkillRoot {
             path: temp.path().to_path_buf(),
             layer: SkillLayer::Project,
+            ignore: Some(Vec::new()),
         }];
 
-        let result = discover_skill_files(&roots);
+        let result = discover_skill_files(&roots).unwrap().skills;
         assert!(result.is_empty());
     }
 
@@ -840,9 +2453,10 @@ mod tests {
         let roots = vec![SkillRoot {
             path: temp.path().to_path_buf(),
             layer: SkillLayer::Project,
+            ignore: Some(Vec::new()),
         }];
 
-        let result = discover_skill_files(&roots);
+        let result = discover_skill_files(&roots).unwrap().skills;
         assert_eq!(result.len(), 1);
         assert!(result[0].path.ends_with("SKILL.md"));
         assert_eq!(result[0].layer, SkillLayer::Project);
@@ -861,9 +2475,10 @@ mod tests {
         let roots = vec![SkillRoot {
             path: temp.path().to_path_buf(),
             layer: SkillLayer::User,
+            ignore: Some(Vec::new()),
         }];
 
-        let result = discover_skill_files(&roots);
+        let result = discover_skill_files(&roots).unwrap().skills;
         assert_eq!(result.len(), 3);
         assert!(result.iter().all(|s| s.layer == SkillLayer::User));
     }
@@ -879,9 +2494,10 @@ mod tests {
         let roots = vec![SkillRoot {
             path: temp.path().to_path_buf(),
             layer: SkillLayer::Base,
+            ignore: Some(Vec::new()),
         }];
 
-        let result = discover_skill_files(&roots);
+        let result = discover_skill_files(&roots).unwrap().skills;
         assert_eq!(result.len(), 1);
         assert!(result[0].path.to_string_lossy().contains("nested"));
     }
@@ -906,9 +2522,10 @@ mod tests {
         let roots = vec![SkillRoot {
             path: temp.path().to_path_buf(),
             layer: SkillLayer::Project,
+            ignore: Some(Vec::new()),
         }];
 
-        let result = discover_skill_files(&roots);
+        let result = discover_skill_files(&roots).unwrap().skills;
         // Should find both the nested skill and the root-level SKILL.md
         assert_eq!(result.len(), 2);
     }
@@ -918,9 +2535,10 @@ mod tests {
         let roots = vec![SkillRoot {
             path: PathBuf::from("/nonexistent/path/12345"),
             layer: SkillLayer::Project,
+            ignore: Some(Vec::new()),
         }];
 
-        let result = discover_skill_files(&roots);
+        let result = discover_skill_files(&roots).unwrap().skills;
         assert!(result.is_empty());
     }
 
@@ -942,14 +2560,16 @@ mod tests {
             SkillRoot {
                 path: temp1.path().to_path_buf(),
                 layer: SkillLayer::Project,
+                ignore: Some(Vec::new()),
             },
             SkillRoot {
                 path: temp2.path().to_path_buf(),
                 layer: SkillLayer::User,
+                ignore: Some(Vec::new()),
             },
         ];
 
-        let result = discover_skill_files(&roots);
+        let result = discover_skill_files(&roots).unwrap().skills;
         assert_eq!(result.len(), 2);
 
         let project_skills: Vec<_> = result
@@ -1013,6 +2633,7 @@ mod tests {
         let root = SkillRoot {
             path: PathBuf::from("/test/path"),
             layer: SkillLayer::Org,
+            ignore: Some(Vec::new()),
         };
 
         assert_eq!(root.path, PathBuf::from("/test/path"));
@@ -1155,8 +2776,9 @@ mod tests {
         let roots = vec![SkillRoot {
             path: target_root.clone(),
             layer: SkillLayer::Project,
+            ignore: Some(Vec::new()),
         }];
-        let discovered = discover_skill_files(&roots);
+        let discovered = discover_skill_files(&roots).unwrap().skills;
         assert_eq!(
             discovered.len(),
             1,
@@ -1236,9 +2858,10 @@ mod tests {
         let roots = vec![SkillRoot {
             path: temp.path().to_path_buf(),
             layer: SkillLayer::Project,
+            ignore: Some(Vec::new()),
         }];
 
-        let discovered = discover_skill_files(&roots);
+        let discovered = discover_skill_files(&roots).unwrap().skills;
         assert_eq!(
             discovered.len(),
             1,
@@ -1247,6 +2870,40 @@ mod tests {
         assert_eq!(discovered[0].path, pkg.join("SKILL.md"));
     }
 
+    #[test]
+    fn test_discover_skill_files_honors_ignore_rules() {
+        use std::fs;
+        let temp = tempfile::tempdir().unwrap();
+        for dir in [
+            "real-skill",
+            "vendor/lib/skill",
+            "drafts/wip",
+            "archive/old",
+        ] {
+            let pkg = temp.path().join(dir);
+            fs::create_dir_all(&pkg).unwrap();
+            fs::write(pkg.join("SKILL.md"), "# Skill").unwrap();
+        }
+        fs::write(temp.path().join(".gitignore"), "vendor/\n").unwrap();
+        fs::write(temp.path().join(".msignore"), "drafts/\n").unwrap();
+
+        let mut roots = vec![SkillRoot {
+            path: temp.path().to_path_buf(),
+            layer: SkillLayer::Project,
+            ignore: Some(vec!["archive/**".to_string()]),
+        }];
+        let discovery = discover_skill_files(&roots).unwrap();
+        assert_eq!(discovery.skills.len(), 1);
+        assert!(discovery.skills[0].path.ends_with("real-skill/SKILL.md"));
+        // vendor/, drafts/ and everything under archive/
+        assert_eq!(discovery.ignored, 3);
+
+        roots[0].ignore = None;
+        let discovery = discover_skill_files(&roots).unwrap();
+        assert_eq!(discovery.skills.len(), 4);
+        assert_eq!(discovery.ignored, 0);
+    }
+
     #[test]
     fn test_discover_skill_files_records_companion_count() {
         use std::fs;
@@ -1262,8 +2919,9 @@ mod tests {
         let roots = vec![SkillRoot {
             path: temp.path().to_path_buf(),
             layer: SkillLayer::Project,
+            ignore: Some(Vec::new()),
         }];
-        let discovered = discover_skill_files(&roots);
+        let discovered = discover_skill_files(&roots).unwrap().skills;
         assert_eq!(discovered.len(), 1);
         assert_eq!(discovered[0].companion_count, 2);
     }
@@ -1,25 +1,71 @@
-//! ms init - Initialize ms in current directory or globally
+//! ms init - Initialize ms in the current project or globally
+//!
+//! Project init places `.ms/` at the repository root, registers or imports
+//! skill-like assets already in the repository (`.claude/skills/`,
+//! `.cursor/rules/`, `docs/ai/`), writes `.ms/config.toml`, and ignores ms
+//! local state in `.gitignore`. Re-running reports what would change instead
+//! of duplicating entries; `--force` applies it.
 
 use std::fs;
+use std::io::{self, IsTerminal, Write};
 use std::path::{Path, PathBuf};
 
 use clap::Args;
 use colored::Colorize;
 
+use crate::agent_detection::{
+    AgentDetectionService, ClaudeHook, HookAction, HookChange, IntegrationAction,
+    IntegrationOutcome, IntegrationWriter, configure_agents,
+};
+use crate::cli::commands::hook::HookAgent;
 use crate::cli::output::OutputFormat;
+use crate::core::project_setup::{
+    AssetAction, AssetKind, FileChange, PROJECT_SKILLS_DIR, ProjectAsset, ProjectPlan,
+    detect_assets, find_project_root,
+};
+use crate::core::spec_lens::compile_markdown;
 use crate::error::{MsError, Result};
+use crate::import::repo_rules::import_repo_rules;
+use crate::import::{ContentParser, GeneratorConfig, ImportHints, SkillGenerator, UnknownHandling};
 use crate::search::SearchIndex;
 use crate::storage::{Database, GitArchive};
 
 #[derive(Args, Debug)]
 pub struct InitArgs {
-    /// Initialize globally (~/.local/share/ms) instead of locally (.ms/)
+    /// Initialize globally (the platform data dir, e.g. ~/.local/share/ms)
+    /// instead of for the current project (.ms/ at the repository root)
     #[arg(long)]
     pub global: bool,
 
     /// Force initialization even if already initialized
     #[arg(long, short)]
     pub force: bool,
+
+    /// Take the default for every detected skill asset without prompting
+    #[arg(long, short)]
+    pub yes: bool,
+
+    /// Register the ms MCP server with detected agents (Claude Code, Cursor, ...)
+    #[arg(long)]
+    pub configure_agents: bool,
+
+    /// Print the plan (config, .gitignore, imports, agent configs) without
+    /// writing anything
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Install a prompt hook that suggests skills during the agent's sessions
+    #[arg(
+        long,
+        value_enum,
+        value_name = "AGENT",
+        conflicts_with = "remove_hooks"
+    )]
+    pub install_hooks: Option<HookAgent>,
+
+    /// Remove the hooks installed with --install-hooks
+    #[arg(long)]
+    pub remove_hooks: bool,
 }
 
 pub fn run(ctx: &crate::app::AppContext, args: &InitArgs) -> Result<()> {
@@ -31,69 +77,45 @@ pub fn run_without_context(robot_mode: bool, args: &InitArgs) -> Result<()> {
 }
 
 fn run_with_robot(robot_mode: bool, args: &InitArgs) -> Result<()> {
-    let target = if args.global {
-        global_ms_root()?
-    } else {
-        local_ms_root()?
-    };
-    let config_path = config_path_for(&target, args.global)?;
+    if args.remove_hooks || (args.global && args.dry_run) {
+        return run_agent_setup(robot_mode, args);
+    }
+    if !args.global {
+        return init_project(robot_mode, args);
+    }
+
+    let config_path = global_config_path()?;
 
     // Check if already initialized
-    if args.global {
-        if config_path.exists() && !args.force {
-            if robot_mode {
-                println!(
-                    "{}",
-                    serde_json::json!({
-                        "status": "error",
-                        "message": "Already initialized",
-                        "config": config_path.display().to_string()
-                    })
-                );
-            } else {
-                println!(
-                    "{} Already initialized at {}",
-                    "!".yellow(),
-                    config_path.display()
-                );
-                println!("  Use --force to reinitialize");
-            }
-            return Ok(());
+    if config_path.exists() && !args.force {
+        if args.configure_agents || args.install_hooks.is_some() {
+            return run_agent_setup(robot_mode, args);
         }
-    } else if target.exists() && !args.force {
         if robot_mode {
             println!(
                 "{}",
                 serde_json::json!({
                     "status": "error",
                     "message": "Already initialized",
-                    "path": target.display().to_string()
+                    "config": config_path.display().to_string()
                 })
             );
         } else {
             println!(
                 "{} Already initialized at {}",
                 "!".yellow(),
-                target.display()
+                config_path.display()
             );
             println!("  Use --force to reinitialize");
         }
         return Ok(());
     }
 
-    if args.global {
-        return if robot_mode {
-            init_robot_global(&config_path, args)
-        } else {
-            init_human_global(&config_path, args)
-        };
-    }
-
     if robot_mode {
-        return init_robot(&target, args);
+        init_robot_global(&config_path, args)
+    } else {
+        init_human_global(&config_path, args)
     }
-
-    init_human(&target, args)
 }
 
 fn init_human_global(config_path: &Path, args: &InitArgs) -> Result<()> {
@@ -101,7 +123,7 @@ fn init_human_global(config_path: &Path, args: &InitArgs) -> Result<()> {
     println!();
 
     print!("Creating default configuration... ");
-    create_default_config(config_path, true, args.force)?;
+    create_default_config(config_path, args.force)?;
     println!("{}", "OK".green());
 
     println!();
@@ -114,102 +136,415 @@ fn init_human_global(config_path: &Path, args: &InitArgs) -> Result<()> {
     println!("Add skill paths with:");
     println!("  ms config add skill_paths.global ~/my-skills");
 
+    if let Some(outcomes) = agent_outcomes(args) {
+        print_agent_outcomes(&outcomes);
+    }
+    if let Some(change) = hook_change(args)? {
+        print_hook_change(&change);
+    }
+
     Ok(())
 }
 
 fn init_robot_global(config_path: &Path, args: &InitArgs) -> Result<()> {
-    create_default_config(config_path, true, args.force)?;
-    println!(
-        "{}",
-        serde_json::json!({
-            "status": "ok",
-            "config": config_path.display().to_string(),
-        })
-    );
+    create_default_config(config_path, args.force)?;
+    let mut payload = serde_json::json!({
+        "status": "ok",
+        "config": config_path.display().to_string(),
+    });
+    if let Some(outcomes) = agent_outcomes(args) {
+        payload["agents"] = serde_json::json!(outcomes);
+    }
+    if let Some(change) = hook_change(args)? {
+        payload["hooks"] = serde_json::json!(change);
+    }
+    println!("{payload}");
     Ok(())
 }
 
-fn init_human(target: &Path, args: &InitArgs) -> Result<()> {
-    println!("{}", "Initializing ms...".bold());
-    println!();
-
-    // Create directory structure
-    print!("Creating directory structure... ");
-    create_directories(target)?;
-    println!("{}", "OK".green());
+/// Plan the project setup, then apply it unless this is a dry run or the
+/// project is already initialized (without `--force`).
+fn init_project(robot_mode: bool, args: &InitArgs) -> Result<()> {
+    let root = find_project_root(&std::env::current_dir()?);
+    let mut assets = detect_assets(&root.path);
+    if !robot_mode && !args.yes && !args.dry_run && io::stdin().is_terminal() {
+        for asset in &mut assets {
+            if !confirm_asset(asset)? {
+                asset.action = AssetAction::Skip;
+            }
+        }
+    }
+    let mut plan = ProjectPlan::build(&root, assets)?;
+    let imports = import_changes(&plan)?;
+    plan.changes.extend(imports);
+
+    let status = if plan.initialized && !args.force {
+        "already_initialized"
+    } else if args.dry_run {
+        "dry_run"
+    } else {
+        plan.apply()?;
+        create_stores(&plan.ms_dir)?;
+        "ok"
+    };
+    let applied = status == "ok";
+    let outcomes = agent_outcomes(args);
+    let hooks = if args.dry_run {
+        None
+    } else {
+        hook_change(args)?
+    };
 
-    // Create SQLite database
-    print!("Initializing database... ");
-    let db_path = target.join("ms.db");
-    Database::open(&db_path)?;
-    println!("{}", "OK".green());
+    if robot_mode {
+        let ms_dir = &plan.ms_dir;
+        let mut payload = serde_json::json!({
+            "status": status,
+            "path": ms_dir.display().to_string(),
+            "root": plan.root.display().to_string(),
+            "vcs": plan.vcs,
+            "db": ms_dir.join("ms.db").display().to_string(),
+            "archive": ms_dir.join("archive").display().to_string(),
+            "index": ms_dir.join("index").display().to_string(),
+            "config": ms_dir.join("config.toml").display().to_string(),
+            "assets": plan.assets,
+            "changes": plan.pending().map(change_json).collect::<Vec<_>>(),
+            "applied": applied,
+        });
+        if let Some(outcomes) = outcomes {
+            payload["agents"] = serde_json::json!(outcomes);
+        }
+        if let Some(change) = hooks {
+            payload["hooks"] = serde_json::json!(change);
+        }
+        println!("{payload}");
+        return Ok(());
+    }
 
-    // Create Git archive
-    print!("Initializing Git archive... ");
-    let archive_path = target.join("archive");
-    GitArchive::open(&archive_path)?;
-    println!("{}", "OK".green());
+    print_project_plan(&plan, status);
+    if let Some(outcomes) = outcomes {
+        print_agent_outcomes(&outcomes);
+    }
+    if let Some(change) = hooks {
+        print_hook_change(&change);
+    }
+    Ok(())
+}
 
-    // Create search index
-    print!("Initializing search index... ");
-    let index_path = target.join("index");
-    SearchIndex::open(&index_path)?;
-    println!("{}", "OK".green());
+/// Ask whether to take the default action for a detected asset.
+fn confirm_asset(asset: &ProjectAsset) -> Result<bool> {
+    let question = match asset.action {
+        AssetAction::Register => format!(
+            "Register {} ({} skills) as a project skill path? [Y/n] ",
+            asset.path, asset.files
+        ),
+        AssetAction::Import => format!(
+            "Import {} ({} files) into {PROJECT_SKILLS_DIR}? [Y/n] ",
+            asset.path, asset.files
+        ),
+        AssetAction::Skip => return Ok(false),
+    };
+    print!("{question}");
+    io::stdout()
+        .flush()
+        .map_err(|err| MsError::Config(format!("prompt flush: {err}")))?;
+    let mut input = String::new();
+    io::stdin()
+        .read_line(&mut input)
+        .map_err(|err| MsError::Config(format!("prompt read: {err}")))?;
+    Ok(!matches!(input.trim().to_lowercase().as_str(), "n" | "no"))
+}
 
-    // Create default config
-    print!("Creating default configuration... ");
-    let config_path = config_path_for(target, args.global)?;
-    create_default_config(&config_path, args.global, args.force)?;
-    println!("{}", "OK".green());
+/// Skills converted from the assets marked for import, through the same
+/// pipeline as `ms import`. Skills already imported are left alone, so local
+/// edits survive re-running init.
+fn import_changes(plan: &ProjectPlan) -> Result<Vec<FileChange>> {
+    let config = GeneratorConfig {
+        min_confidence: 0.3,
+        unknown_handling: UnknownHandling::AddToContext,
+        infer_metadata: true,
+        deduplicate: true,
+    };
+    let mut skills = Vec::new();
+    for asset in &plan.assets {
+        if asset.action != AssetAction::Import {
+            continue;
+        }
+        match asset.kind {
+            AssetKind::CursorRules => {
+                let prefix = format!("{}/", asset.path);
+                for import in import_repo_rules(&plan.root, &config, &ImportHints::default())? {
+                    if import.source.relative.starts_with(&prefix) {
+                        skills.push(import.generated.skill);
+                    }
+                }
+            }
+            AssetKind::DocsAi => {
+                let generator = SkillGenerator::with_config(config.clone());
+                let parser = ContentParser::new();
+                for path in markdown_files(&plan.root.join(&asset.path)) {
+                    let content = fs::read_to_string(&path)?;
+                    let hints = ImportHints {
+                        source_filename: path
+                            .file_stem()
+                            .and_then(|stem| stem.to_str())
+                            .map(String::from),
+                        ..ImportHints::default()
+                    };
+                    skills.push(generator.generate(parser.parse(&content), &hints).skill);
+                }
+            }
+            // Already SKILL.md directories; registered, never imported
+            AssetKind::ClaudeSkills => {}
+        }
+    }
 
-    println!();
-    println!("{} Initialized at {}", "✓".green().bold(), target.display());
+    let target = plan.root.join(PROJECT_SKILLS_DIR);
+    let mut changes = Vec::with_capacity(skills.len());
+    for skill in skills {
+        let path = target.join(&skill.metadata.id).join("SKILL.md");
+        if !path.exists() {
+            changes.push(FileChange::new(path, compile_markdown(&skill))?);
+        }
+    }
+    Ok(changes)
+}
 
-    println!();
-    println!("Add skill paths with:");
-    println!("  ms config add skill_paths.project ./skills");
+/// Markdown files under `dir`, sorted.
+fn markdown_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = walkdir::WalkDir::new(dir)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(std::result::Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .map(walkdir::DirEntry::into_path)
+        .filter(|path| path.extension().is_some_and(|ext| ext == "md"))
+        .collect();
+    files.sort();
+    files
+}
 
-    Ok(())
+fn change_json(change: &FileChange) -> serde_json::Value {
+    serde_json::json!({
+        "path": change.path.display().to_string(),
+        "action": if change.before.is_some() { "update" } else { "create" },
+        "diff": change.diff(),
+    })
 }
 
-fn init_robot(target: &Path, args: &InitArgs) -> Result<()> {
-    // Create everything silently
-    create_directories(target)?;
+fn print_project_plan(plan: &ProjectPlan, status: &str) {
+    match status {
+        "already_initialized" => println!(
+            "{} Already initialized at {}",
+            "!".yellow(),
+            plan.ms_dir.display()
+        ),
+        "dry_run" => println!(
+            "{} {}",
+            "Dry run:".bold(),
+            "nothing will be written".dimmed()
+        ),
+        _ => println!("{}", "Initializing ms...".bold()),
+    }
+    let vcs = plan.vcs.map_or_else(
+        || "no VCS".to_string(),
+        |vcs| format!("{vcs:?}").to_lowercase(),
+    );
+    println!("  Project root: {} ({vcs})", plan.root.display());
+
+    if !plan.assets.is_empty() {
+        println!();
+        println!("{}", "Skill assets".bold());
+        for asset in &plan.assets {
+            let action = match asset.action {
+                AssetAction::Register => "register as a project skill path".green(),
+                AssetAction::Import => format!("import into {PROJECT_SKILLS_DIR}").green(),
+                AssetAction::Skip => "skip".dimmed(),
+            };
+            println!("  {} ({} files): {action}", asset.path, asset.files);
+        }
+    }
 
-    let db_path = target.join("ms.db");
-    Database::open(&db_path)?;
+    println!();
+    if plan.is_up_to_date() {
+        println!("  {} Nothing to change", "-".dimmed());
+    }
+    for change in plan.pending() {
+        let path = change.path.strip_prefix(&plan.root).unwrap_or(&change.path);
+        if change.before.is_none() {
+            println!("  {} create {}", "+".green(), path.display());
+            continue;
+        }
+        println!("  {} update {}", "~".yellow(), path.display());
+        for line in change.diff().lines().skip(2) {
+            println!("    {line}");
+        }
+    }
 
-    let archive_path = target.join("archive");
-    GitArchive::open(&archive_path)?;
+    match status {
+        "already_initialized" if !plan.is_up_to_date() => {
+            println!();
+            println!("  Use --force to apply these changes");
+        }
+        "ok" => {
+            println!();
+            println!(
+                "{} Initialized at {}",
+                "✓".green().bold(),
+                plan.ms_dir.display()
+            );
+            println!();
+            println!("Add skill paths with:");
+            println!("  ms config add skill_paths.project ./skills");
+        }
+        _ => {}
+    }
+}
 
-    let index_path = target.join("index");
-    SearchIndex::open(&index_path)?;
+/// Create the database, Git archive, and search index under `ms_dir`.
+fn create_stores(ms_dir: &Path) -> Result<()> {
+    create_directories(ms_dir)?;
+    Database::open(ms_dir.join("ms.db"))?;
+    GitArchive::open(ms_dir.join("archive"))?;
+    SearchIndex::open(&ms_dir.join("index"))?;
+    Ok(())
+}
 
-    let config_path = config_path_for(target, args.global)?;
-    create_default_config(&config_path, args.global, args.force)?;
+/// Register the ms MCP server with detected agents when `--configure-agents`
+/// is set.
+fn agent_outcomes(args: &InitArgs) -> Option<Vec<IntegrationOutcome>> {
+    if !args.configure_agents {
+        return None;
+    }
+    let mut agents = AgentDetectionService::new().detect_all();
+    Some(configure_agents(
+        &IntegrationWriter::new(),
+        &mut agents,
+        args.dry_run,
+        args.force,
+    ))
+}
 
-    println!(
-        "{}",
-        serde_json::json!({
-            "status": "ok",
-            "path": target.display().to_string(),
-            "db": db_path.display().to_string(),
-            "archive": archive_path.display().to_string(),
-            "index": index_path.display().to_string(),
-            "config": config_path.display().to_string(),
-        })
-    );
+/// Install or remove the suggestion hook for `--install-hooks` and
+/// `--remove-hooks`.
+fn hook_change(args: &InitArgs) -> Result<Option<HookChange>> {
+    let change = match args.install_hooks {
+        Some(HookAgent::ClaudeCode) => ClaudeHook::for_current_user()?.install()?,
+        None if args.remove_hooks => ClaudeHook::for_current_user()?.remove()?,
+        None => return Ok(None),
+    };
+    Ok(Some(change))
+}
 
+/// `--configure-agents` or `--install-hooks` on an existing global install
+/// (or `--global --dry-run`, `--remove-hooks`): skip the init steps and only
+/// touch agent configs.
+fn run_agent_setup(robot_mode: bool, args: &InitArgs) -> Result<()> {
+    let outcomes = agent_outcomes(args);
+    let hooks = hook_change(args)?;
+    if robot_mode {
+        let mut payload = serde_json::json!({
+            "status": "ok",
+            "dry_run": args.dry_run,
+        });
+        if let Some(outcomes) = outcomes {
+            payload["agents"] = serde_json::json!(outcomes);
+        }
+        if let Some(change) = hooks {
+            payload["hooks"] = serde_json::json!(change);
+        }
+        println!("{payload}");
+    } else {
+        if let Some(outcomes) = outcomes {
+            print_agent_outcomes(&outcomes);
+        }
+        if let Some(change) = hooks {
+            print_hook_change(&change);
+        }
+    }
     Ok(())
 }
 
+fn print_hook_change(change: &HookChange) {
+    println!();
+    println!("{}", "Agent hooks".bold());
+    let name = change.agent.display_name();
+    let settings = change.settings.display();
+    match change.action {
+        HookAction::Installed => {
+            println!(
+                "  {} {name}: suggestion hook registered in {settings}",
+                "✓".green()
+            );
+            println!("    script: {}", change.script.display());
+        }
+        HookAction::AlreadyInstalled => {
+            println!(
+                "  {} {name}: suggestion hook already installed",
+                "-".dimmed()
+            );
+        }
+        HookAction::Removed => {
+            println!(
+                "  {} {name}: suggestion hook removed from {settings}",
+                "✓".green()
+            );
+        }
+        HookAction::NotInstalled => {
+            println!("  {} {name}: no suggestion hook installed", "-".dimmed());
+        }
+    }
+    if let Some(backup) = &change.backup {
+        println!("    backup: {}", backup.display());
+    }
+}
+
+fn print_agent_outcomes(outcomes: &[IntegrationOutcome]) {
+    println!();
+    println!("{}", "Agent integrations".bold());
+    if outcomes.is_empty() {
+        println!("  No AI coding agents detected");
+        return;
+    }
+    for outcome in outcomes {
+        let name = outcome.agent.display_name();
+        let path = outcome
+            .path
+            .as_ref()
+            .map(|path| path.display().to_string())
+            .unwrap_or_default();
+        match &outcome.action {
+            IntegrationAction::Configured => {
+                println!("  {} {name}: registered in {path}", "✓".green());
+                if let Some(backup) = &outcome.backup {
+                    println!("    backup: {}", backup.display());
+                }
+            }
+            IntegrationAction::WouldConfigure => {
+                println!("  {} {name}: would update {path}", "~".yellow());
+                for line in outcome.diff.as_deref().unwrap_or_default().lines() {
+                    println!("    {line}");
+                }
+            }
+            IntegrationAction::AlreadyConfigured => {
+                println!("  {} {name}: already configured", "-".dimmed());
+            }
+            IntegrationAction::Unsupported => {
+                println!("  {} {name}: no MCP config support", "-".dimmed());
+            }
+            IntegrationAction::Failed(err) => {
+                println!("  {} {name}: {err}", "✗".red());
+            }
+        }
+    }
+}
+
 fn create_directories(target: &Path) -> Result<()> {
     fs::create_dir_all(target)?;
     fs::create_dir_all(target.join("tx"))?;
     Ok(())
 }
 
-fn create_default_config(config_path: &Path, global: bool, force: bool) -> Result<()> {
+fn create_default_config(config_path: &Path, force: bool) -> Result<()> {
     // Create parent directory if needed
     if let Some(parent) = config_path.parent() {
         fs::create_dir_all(parent)?;
@@ -220,8 +555,7 @@ fn create_default_config(config_path: &Path, global: bool, force: bool) -> Resul
         return Ok(());
     }
 
-    let default_config = if global {
-        r#"# ms configuration
+    let default_config = r#"# ms configuration
 
 [skill_paths]
 # Global skill repositories
@@ -255,60 +589,14 @@ dcg_bin = "dcg"
 dcg_packs = []
 dcg_explain_format = "json"
 require_verbatim_approval = true
-"#
-    } else {
-        r#"# ms configuration (project-local)
-
-[skill_paths]
-# Project-local skill paths
-project = ["./skills"]
-
-# Local overrides
-local = []
-
-[search]
-# Embedding backend configuration
-use_embeddings = true
-embedding_backend = "hash"
-embedding_dims = 384
-bm25_weight = 0.5
-semantic_weight = 0.5
-
-[cm]
-# cass-memory (cm) integration
-enabled = true
-# cm_path = "cm"
-# default_flags = []
-
-[safety]
-# Destructive Command Guard configuration
-dcg_bin = "dcg"
-dcg_packs = []
-dcg_explain_format = "json"
-require_verbatim_approval = true
-"#
-    };
+"#;
 
     fs::write(config_path, default_config)?;
     Ok(())
 }
 
-fn config_path_for(target: &Path, global: bool) -> Result<PathBuf> {
-    if global {
-        return dirs::config_dir()
-            .ok_or_else(|| MsError::MissingConfig("config directory not found".to_string()))
-            .map(|dir| dir.join("ms/config.toml"));
-    }
-    Ok(target.join("config.toml"))
-}
-
-fn global_ms_root() -> Result<PathBuf> {
-    let data_dir = dirs::data_dir()
-        .ok_or_else(|| MsError::MissingConfig("data directory not found".to_string()))?;
-    Ok(data_dir.join("ms"))
-}
-
-fn local_ms_root() -> Result<PathBuf> {
-    let cwd = std::env::current_dir()?;
-    Ok(cwd.join(".ms"))
+fn global_config_path() -> Result<PathBuf> {
+    dirs::config_dir()
+        .ok_or_else(|| MsError::MissingConfig("config directory not found".to_string()))
+        .map(|dir| dir.join("ms").join("config.toml"))
 }
@@ -34,6 +34,10 @@ pub struct InstallArgs {
     /// Force reinstallation if bundle is already installed
     #[arg(long, short = 'f')]
     pub force: bool,
+
+    /// Fetch and install missing dependencies that declare a source
+    #[arg(long)]
+    pub with_deps: bool,
 }
 
 pub fn run(ctx: &AppContext, args: &InstallArgs) -> Result<()> {
@@ -45,6 +49,7 @@ pub fn run(ctx: &AppContext, args: &InstallArgs) -> Result<()> {
         asset_name: args.asset_name.clone(),
         no_verify: args.no_verify,
         force: args.force,
+        with_deps: args.with_deps,
     };
 
     let bundle_args = BundleArgs {
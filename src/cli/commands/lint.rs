@@ -17,7 +17,7 @@ use crate::core::spec_lens::parse_markdown;
 use crate::error::{MsError, Result};
 use crate::lint::diagnostic::{RuleCategory, Severity};
 use crate::lint::rules::all_rules;
-use crate::lint::{ValidationConfig, ValidationEngine, ValidationResult};
+use crate::lint::{ValidationConfig, ValidationContext, ValidationEngine, ValidationResult};
 
 /// Output format for lint results
 #[derive(Debug, Clone, Copy, Default, ValueEnum)]
@@ -135,11 +135,13 @@ pub fn run(ctx: &AppContext, args: &LintArgs) -> Result<()> {
         .as_ref()
         .map(|r| r.iter().map(std::string::String::as_str).collect());
 
-    for rule in all_rules() {
+    for rule in ctx.lint_rules()? {
         if let Some(ref filter) = rules_filter {
             if !filter.contains(rule.id()) {
                 continue;
             }
+        } else if rule.opt_in() {
+            continue;
         }
         engine.register(rule);
     }
@@ -155,7 +157,9 @@ pub fn run(ctx: &AppContext, args: &LintArgs) -> Result<()> {
             .map_err(|e| MsError::Config(format!("read {}: {e}", path.display())))?;
 
         let mut spec = parse_markdown(&raw)?;
-        let result = engine.validate(&spec);
+        let result = engine.validate_with_context(
+            &ValidationContext::new(&spec, engine.config()).with_file_path(path),
+        );
 
         total_errors += result.error_count();
         total_warnings += result.warning_count();
@@ -284,8 +288,9 @@ fn list_rules(ctx: &AppContext) -> Result<()> {
             layout.section(cat_name);
             for rule in cat_rules {
                 let fix_badge = if rule.can_fix { " [fixable]" } else { "" };
+                let opt_in_badge = if rule.opt_in { " [opt-in]" } else { "" };
                 layout.bullet(&format!(
-                    "{} - {} ({}){fix_badge}",
+                    "{} - {} ({}){fix_badge}{opt_in_badge}",
                     rule.id, rule.name, rule.default_severity
                 ));
             }
@@ -1,102 +1,376 @@
 //! ms list - List all indexed skills
+//!
+//! Pages hold at most [`MAX_PAGE_SIZE`](crate::search::MAX_PAGE_SIZE)
+//! skills. Robot output carries a `next_cursor` while more remain; `--cursor`
+//! resumes after that page and fails with `cursor_stale` once the index has
+//! changed.
 
-use clap::Args;
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use clap::{Args, ValueEnum};
 use serde::Serialize;
 use tracing::debug;
 
 use crate::app::AppContext;
-use crate::cli::output::OutputFormat;
+use crate::cli::commands::search::parse_page_size;
+use crate::cli::output::{OutputFormat, TableFormat};
+use crate::core::applicability::{self, SkillWindow, SystemClock, WindowStatus};
 use crate::error::Result;
-use crate::storage::sqlite::SkillRecord;
+use crate::quality::usage::parse_since;
+use crate::search::Pager;
+use crate::search::cursor::query_hash;
+use crate::storage::sqlite::{SkillListQuery, SkillListSort, SkillRecord};
 
 #[derive(Args, Debug)]
 pub struct ListArgs {
-    /// Filter by tags
-    #[arg(long, short)]
+    /// Only skills carrying this tag (repeatable; all must match)
+    #[arg(long = "tag", short, visible_alias = "tags")]
     pub tags: Vec<String>,
 
-    /// Filter by layer: base, org, project, user
-    #[arg(long)]
-    pub layer: Option<String>,
+    /// Filter by layer
+    #[arg(long, value_enum)]
+    pub layer: Option<ListLayer>,
 
     /// Include deprecated skills
-    #[arg(long)]
+    #[arg(long, conflicts_with_all = ["deprecated", "no_deprecated"])]
     pub include_deprecated: bool,
 
-    /// Sort by: name, updated, relevance
-    #[arg(long, default_value = "name")]
-    pub sort: String,
+    /// Only deprecated skills
+    #[arg(long, conflicts_with = "no_deprecated")]
+    pub deprecated: bool,
+
+    /// Exclude deprecated skills (the default)
+    #[arg(long)]
+    pub no_deprecated: bool,
+
+    /// Minimum quality score (0.0-1.0)
+    #[arg(long, value_name = "SCORE", value_parser = parse_min_quality)]
+    pub min_quality: Option<f64>,
+
+    /// Only skills modified since a relative age (7d, 4w) or date (2026-03-01)
+    #[arg(long, value_name = "DURATION|DATE")]
+    pub updated_since: Option<String>,
+
+    /// Sort order
+    #[arg(long, value_enum, default_value_t = ListSort::Name)]
+    pub sort: ListSort,
 
-    /// Maximum number of skills to show
-    #[arg(long, short = 'n', default_value = "50")]
+    /// Maximum number of skills to show (page size, at most 100)
+    #[arg(long, short = 'n', default_value = "50", value_parser = parse_page_size)]
     pub limit: usize,
 
     /// Offset for pagination
     #[arg(long, default_value = "0")]
     pub offset: usize,
+
+    /// Resume after the page that returned this `next_cursor`
+    #[arg(long, conflicts_with = "offset")]
+    pub cursor: Option<String>,
+
+    /// Columns for `-O csv`/`-O tsv`, in order (comma-separated; default: the standard set)
+    #[arg(long, value_enum, value_delimiter = ',')]
+    pub columns: Vec<ListColumn>,
+
+    /// Omit the header row from `-O csv`/`-O tsv` output
+    #[arg(long)]
+    pub no_header: bool,
+
+    /// Only skills whose `expires` date falls within this many days
+    /// (e.g. 30d, 2w), soonest first; already-expired skills are included
+    #[arg(long, value_name = "DAYS", value_parser = parse_expiring_days)]
+    pub expiring: Option<i64>,
 }
 
-pub fn run(ctx: &AppContext, args: &ListArgs) -> Result<()> {
-    debug!(target: "list", mode = ?ctx.output_format, "output mode selected");
+/// Layers as named in `[skill_paths]`, mapped onto stored source layers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ListLayer {
+    Project,
+    /// `skill_paths.global` (stored as `org`)
+    #[value(alias = "org")]
+    Global,
+    /// `skill_paths.community` and ru repos (stored as `base`)
+    #[value(alias = "base", alias = "system")]
+    Community,
+    /// `skill_paths.local` (stored as `user`)
+    #[value(alias = "user")]
+    Local,
+}
 
-    // Fetch skills from database
-    let skills = ctx.db.list_skills(args.limit, args.offset)?;
+impl ListLayer {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Project => "project",
+            Self::Global => "global",
+            Self::Community => "community",
+            Self::Local => "local",
+        }
+    }
 
-    // Filter by layer if specified
-    let skills: Vec<_> = if let Some(ref layer) = args.layer {
-        let normalized = normalize_layer(layer);
-        skills
-            .into_iter()
-            .filter(|s| normalize_layer(&s.source_layer) == normalized)
-            .collect()
-    } else {
-        skills
-    };
+    /// `source_layer` values this layer matches, including legacy spellings.
+    #[must_use]
+    pub const fn source_layers(self) -> &'static [&'static str] {
+        match self {
+            Self::Project => &["project"],
+            Self::Global => &["org", "global"],
+            Self::Community => &["base", "system"],
+            Self::Local => &["user", "local"],
+        }
+    }
+}
 
-    // Filter by tags if specified
-    let skills: Vec<_> = if args.tags.is_empty() {
-        skills
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ListSort {
+    Name,
+    /// Most recently modified first
+    Updated,
+    /// Highest quality first
+    #[value(alias = "relevance")]
+    Quality,
+}
+
+impl ListSort {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::Updated => "updated",
+            Self::Quality => "quality",
+        }
+    }
+}
+
+fn parse_min_quality(raw: &str) -> std::result::Result<f64, String> {
+    raw.trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|q| (0.0..=1.0).contains(q))
+        .ok_or_else(|| format!("invalid quality '{raw}' (expected 0.0-1.0)"))
+}
+
+/// Parse `30d`, `2w`, or a bare day count.
+fn parse_expiring_days(raw: &str) -> std::result::Result<i64, String> {
+    let trimmed = raw.trim().to_ascii_lowercase();
+    let (value, multiplier) = if let Some(days) = trimmed.strip_suffix('d') {
+        (days, 1)
+    } else if let Some(weeks) = trimmed.strip_suffix('w') {
+        (weeks, 7)
     } else {
-        skills
-            .into_iter()
-            .filter(|s| {
-                // Parse metadata_json to check tags
-                if let Ok(meta) = serde_json::from_str::<serde_json::Value>(&s.metadata_json) {
-                    if let Some(tags) = meta.get("tags").and_then(|t| t.as_array()) {
-                        let skill_tags: Vec<String> = tags
-                            .iter()
-                            .filter_map(|v| v.as_str().map(String::from))
-                            .collect();
-                        return args.tags.iter().any(|t| skill_tags.contains(t));
-                    }
-                }
-                false
-            })
-            .collect()
+        (trimmed.as_str(), 1)
     };
+    value
+        .parse::<i64>()
+        .ok()
+        .filter(|n| *n >= 0)
+        .map(|n| n * multiplier)
+        .ok_or_else(|| format!("invalid duration '{raw}' (expected e.g. 30d or 2w)"))
+}
+
+/// Columns available to `ms -O csv|tsv list`.
+///
+/// The default set and its order are stable; new columns are only ever
+/// appended to the selectable list, never inserted into the default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ListColumn {
+    #[value(name = "id")]
+    Id,
+    #[value(name = "name")]
+    Name,
+    /// Source layer (base, org, project, user)
+    #[value(name = "layer")]
+    Layer,
+    /// Tags sorted and joined by `;`
+    #[value(name = "tags")]
+    Tags,
+    /// Quality score at full precision
+    #[value(name = "quality")]
+    Quality,
+    /// Stored token count
+    #[value(name = "tokens")]
+    Tokens,
+    /// Most recent load (RFC 3339), empty if never loaded
+    #[value(name = "last_used")]
+    LastUsed,
+    /// `true` or `false`
+    #[value(name = "deprecated")]
+    Deprecated,
+    /// Latest `reviewed-*` tag, empty if never reviewed
+    #[value(name = "review_state")]
+    ReviewState,
+    #[value(name = "version")]
+    Version,
+    #[value(name = "description")]
+    Description,
+    #[value(name = "modified_at")]
+    ModifiedAt,
+}
 
-    // Filter deprecated unless explicitly included
-    let skills: Vec<_> = if args.include_deprecated {
-        skills
+impl ListColumn {
+    /// Columns exported when `--columns` is not given.
+    pub const DEFAULT: &'static [Self] = &[
+        Self::Id,
+        Self::Name,
+        Self::Layer,
+        Self::Tags,
+        Self::Quality,
+        Self::Tokens,
+        Self::LastUsed,
+        Self::Deprecated,
+        Self::ReviewState,
+    ];
+
+    #[must_use]
+    pub const fn header(self) -> &'static str {
+        match self {
+            Self::Id => "id",
+            Self::Name => "name",
+            Self::Layer => "layer",
+            Self::Tags => "tags",
+            Self::Quality => "quality",
+            Self::Tokens => "tokens",
+            Self::LastUsed => "last_used",
+            Self::Deprecated => "deprecated",
+            Self::ReviewState => "review_state",
+            Self::Version => "version",
+            Self::Description => "description",
+            Self::ModifiedAt => "modified_at",
+        }
+    }
+}
+
+pub fn run(ctx: &AppContext, args: &ListArgs) -> Result<()> {
+    debug!(target: "list", mode = ?ctx.output_format, "output mode selected");
+
+    let updated_since = args
+        .updated_since
+        .as_deref()
+        .map(|raw| parse_since(raw, Utc::now()))
+        .transpose()?;
+    let mut query = skill_query(args, updated_since);
+    let pager = Pager::new(
+        list_query_hash(&query, args),
+        ctx.search.generation()?,
+        args.limit,
+        args.cursor.as_deref(),
+    )?;
+    // `--expiring` reorders, so it pages over every match
+    query.limit = if args.expiring.is_some() {
+        UNBOUNDED
     } else {
-        skills.into_iter().filter(|s| !s.is_deprecated).collect()
+        pager.window().min(UNBOUNDED)
     };
+    let mut skills = ctx.db.list_skills_filtered(&query)?;
 
-    // Sort
-    let mut skills = skills;
-    match args.sort.as_str() {
-        "name" => skills.sort_by(|a, b| a.name.cmp(&b.name)),
-        "updated" => skills.sort_by(|a, b| b.modified_at.cmp(&a.modified_at)),
-        _ => {}
+    let today = applicability::today(&SystemClock, &ctx.config.applicability);
+    if let Some(days) = args.expiring {
+        skills = expiring_within(skills, days, today);
     }
+    let (skills, next_cursor) = pager.page(skills, |skill| {
+        (sort_key(skill, args, today), skill.id.as_str())
+    })?;
 
-    debug!(target: "list", count = skills.len(), filters = ?args.tags, "listing skills");
+    debug!(target: "list", count = skills.len(), filters = ?query, "listing skills");
 
-    let result = display_list(ctx, &skills, args);
+    let filters = AppliedFilters::new(args, updated_since);
+    let result = display_list(ctx, &skills, args, &filters, today, next_cursor.as_deref());
     debug!(target: "list", stage = "render_complete");
     result
 }
 
+/// Row limit meaning "every match".
+const UNBOUNDED: usize = i64::MAX as usize;
+
+/// Filters and sort of `query` plus `--expiring` and the page size, so a
+/// cursor only resumes the listing it came from.
+fn list_query_hash(query: &SkillListQuery, args: &ListArgs) -> String {
+    let options = format!(
+        "{:?} {:?} {:?} {:?} {:?} {:?} {:?} {}",
+        query.tags,
+        query.layers,
+        query.min_quality,
+        query.deprecated,
+        query.updated_since,
+        query.sort,
+        args.expiring,
+        args.limit
+    );
+    query_hash(&["list", &options])
+}
+
+/// The database query for `args`; `--expiring` is applied afterwards since
+/// expiry lives in the metadata.
+fn skill_query(args: &ListArgs, updated_since: Option<DateTime<Utc>>) -> SkillListQuery {
+    let deprecated = if args.deprecated {
+        Some(true)
+    } else if args.include_deprecated {
+        None
+    } else {
+        Some(false)
+    };
+    SkillListQuery {
+        tags: args.tags.clone(),
+        layers: args
+            .layer
+            .map(|layer| {
+                layer
+                    .source_layers()
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect()
+            })
+            .unwrap_or_default(),
+        min_quality: args.min_quality,
+        deprecated,
+        updated_since,
+        sort: match args.sort {
+            ListSort::Name => SkillListSort::Name,
+            ListSort::Updated => SkillListSort::Updated,
+            ListSort::Quality => SkillListSort::Quality,
+        },
+        limit: args.limit,
+        offset: args.offset,
+    }
+}
+
+/// Filters echoed back in robot output.
+#[derive(Debug, Clone, Serialize)]
+struct AppliedFilters {
+    tags: Vec<String>,
+    layer: Option<&'static str>,
+    min_quality: Option<f64>,
+    /// `exclude`, `include`, or `only`
+    deprecated: &'static str,
+    updated_since: Option<String>,
+    expiring_days: Option<i64>,
+    sort: &'static str,
+    limit: usize,
+    offset: usize,
+}
+
+impl AppliedFilters {
+    fn new(args: &ListArgs, updated_since: Option<DateTime<Utc>>) -> Self {
+        let deprecated = if args.deprecated {
+            "only"
+        } else if args.include_deprecated {
+            "include"
+        } else {
+            "exclude"
+        };
+        Self {
+            tags: args.tags.iter().map(|t| t.trim().to_lowercase()).collect(),
+            layer: args.layer.map(ListLayer::as_str),
+            min_quality: args.min_quality,
+            deprecated,
+            updated_since: updated_since.map(|at| at.to_rfc3339()),
+            expiring_days: args.expiring,
+            sort: args.sort.as_str(),
+            limit: args.limit,
+            offset: args.offset,
+        }
+    }
+}
+
 /// Serializable skill entry for JSON/JSONL output
 #[derive(Debug, Clone, Serialize)]
 struct SkillEntry {
@@ -111,6 +385,24 @@ struct SkillEntry {
     is_deprecated: bool,
     deprecation_reason: Option<String>,
     quality_score: f64,
+    tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expires: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    days_until_expiry: Option<i64>,
+    is_expired: bool,
+}
+
+impl SkillEntry {
+    /// Entry with expiry fields evaluated as of `today`.
+    fn new(s: &SkillRecord, today: NaiveDate) -> Self {
+        let mut entry = Self::from(s);
+        let window = SkillWindow::from_metadata_json(&s.metadata_json);
+        entry.expires = window.expires_on().map(|d| d.to_string());
+        entry.days_until_expiry = window.days_until_expiry(today);
+        entry.is_expired = window.status_on(today) == WindowStatus::Expired;
+        entry
+    }
 }
 
 impl From<&SkillRecord> for SkillEntry {
@@ -127,20 +419,66 @@ impl From<&SkillRecord> for SkillEntry {
             is_deprecated: s.is_deprecated,
             deprecation_reason: s.deprecation_reason.clone(),
             quality_score: s.quality_score,
+            tags: sorted_tags(s),
+            expires: None,
+            days_until_expiry: None,
+            is_expired: false,
         }
     }
 }
 
-fn display_list(ctx: &AppContext, skills: &[SkillRecord], args: &ListArgs) -> Result<()> {
+/// The value a listing is ordered by ahead of the id, which the cursor pins:
+/// days to expiry and name under `--expiring`, else the `--sort` column.
+fn sort_key(skill: &SkillRecord, args: &ListArgs, today: NaiveDate) -> String {
+    if args.expiring.is_some() {
+        let left = SkillWindow::from_metadata_json(&skill.metadata_json)
+            .days_until_expiry(today)
+            .unwrap_or_default();
+        return format!("{left} {}", skill.name);
+    }
+    match args.sort {
+        ListSort::Name => skill.name.clone(),
+        ListSort::Updated => skill.modified_at.clone(),
+        ListSort::Quality => skill.quality_score.to_string(),
+    }
+}
+
+/// Skills expiring within `days` of `today` (or already expired), soonest first.
+fn expiring_within(skills: Vec<SkillRecord>, days: i64, today: NaiveDate) -> Vec<SkillRecord> {
+    let mut expiring: Vec<(i64, SkillRecord)> = skills
+        .into_iter()
+        .filter_map(|s| {
+            let left =
+                SkillWindow::from_metadata_json(&s.metadata_json).days_until_expiry(today)?;
+            (left <= days).then_some((left, s))
+        })
+        .collect();
+    expiring.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.name.cmp(&b.1.name)));
+    expiring.into_iter().map(|(_, s)| s).collect()
+}
+
+fn display_list(
+    ctx: &AppContext,
+    skills: &[SkillRecord],
+    args: &ListArgs,
+    filters: &AppliedFilters,
+    today: NaiveDate,
+    next_cursor: Option<&str>,
+) -> Result<()> {
     match ctx.output_format {
-        OutputFormat::Human => display_list_human(skills, args),
+        OutputFormat::Human => display_list_human(skills, args, today, next_cursor),
         OutputFormat::Json => {
-            let entries: Vec<SkillEntry> = skills.iter().map(SkillEntry::from).collect();
-            let output = serde_json::json!({
+            let entries: Vec<SkillEntry> =
+                skills.iter().map(|s| SkillEntry::new(s, today)).collect();
+            let mut output = serde_json::json!({
                 "status": "ok",
                 "count": entries.len(),
+                "filters": filters,
                 "skills": entries
             });
+            if let Some(cursor) = next_cursor {
+                output["next_cursor"] = cursor.into();
+            }
             println!(
                 "{}",
                 serde_json::to_string_pretty(&output).unwrap_or_default()
@@ -149,7 +487,7 @@ fn display_list(ctx: &AppContext, skills: &[SkillRecord], args: &ListArgs) -> Re
         }
         OutputFormat::Jsonl => {
             for skill in skills {
-                let entry = SkillEntry::from(skill);
+                let entry = SkillEntry::new(skill, today);
                 println!("{}", serde_json::to_string(&entry).unwrap_or_default());
             }
             Ok(())
@@ -188,33 +526,20 @@ fn display_list(ctx: &AppContext, skills: &[SkillRecord], args: &ListArgs) -> Re
             }
             Ok(())
         }
-        OutputFormat::Tsv => {
-            println!("id\tname\tversion\tlayer\tquality\tmodified_at\tis_deprecated");
-            for skill in skills {
-                println!(
-                    "{}\t{}\t{}\t{}\t{:.2}\t{}\t{}",
-                    skill.id,
-                    skill.name,
-                    skill.version.as_deref().unwrap_or("-"),
-                    skill.source_layer,
-                    skill.quality_score,
-                    skill
-                        .modified_at
-                        .split('T')
-                        .next()
-                        .unwrap_or(&skill.modified_at),
-                    skill.is_deprecated
-                );
-            }
-            Ok(())
-        }
+        OutputFormat::Tsv => display_list_table(ctx, skills, args, TableFormat::Tsv),
+        OutputFormat::Csv => display_list_table(ctx, skills, args, TableFormat::Csv),
         OutputFormat::Toon => {
-            let entries: Vec<SkillEntry> = skills.iter().map(SkillEntry::from).collect();
-            let output = serde_json::json!({
+            let entries: Vec<SkillEntry> =
+                skills.iter().map(|s| SkillEntry::new(s, today)).collect();
+            let mut output = serde_json::json!({
                 "status": "ok",
                 "count": entries.len(),
+                "filters": filters,
                 "skills": entries
             });
+            if let Some(cursor) = next_cursor {
+                output["next_cursor"] = cursor.into();
+            }
             let toon = toon_rust::encode(output, None);
             println!("{toon}");
             Ok(())
@@ -222,7 +547,108 @@ fn display_list(ctx: &AppContext, skills: &[SkillRecord], args: &ListArgs) -> Re
     }
 }
 
-fn display_list_human(skills: &[SkillRecord], args: &ListArgs) -> Result<()> {
+/// Tags from a record's metadata, sorted and deduplicated for a stable join order.
+fn sorted_tags(skill: &SkillRecord) -> Vec<String> {
+    let mut tags: Vec<String> = serde_json::from_str::<serde_json::Value>(&skill.metadata_json)
+        .ok()
+        .and_then(|meta| {
+            meta.get("tags").and_then(|t| t.as_array()).map(|arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_str().map(String::from))
+                    .collect()
+            })
+        })
+        .unwrap_or_default();
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+fn table_cell(
+    skill: &SkillRecord,
+    column: ListColumn,
+    tags: &[String],
+    last_used: &HashMap<String, String>,
+) -> String {
+    match column {
+        ListColumn::Id => skill.id.clone(),
+        ListColumn::Name => skill.name.clone(),
+        ListColumn::Layer => normalize_layer(&skill.source_layer),
+        ListColumn::Tags => tags.join(";"),
+        // Same shortest round-trip representation robot JSON uses.
+        ListColumn::Quality => serde_json::Value::from(skill.quality_score).to_string(),
+        ListColumn::Tokens => skill.token_count.to_string(),
+        ListColumn::LastUsed => last_used.get(&skill.id).cloned().unwrap_or_default(),
+        ListColumn::Deprecated => skill.is_deprecated.to_string(),
+        ListColumn::ReviewState => tags
+            .iter()
+            .filter(|t| t.starts_with("reviewed-"))
+            .max()
+            .cloned()
+            .unwrap_or_default(),
+        ListColumn::Version => skill.version.clone().unwrap_or_default(),
+        ListColumn::Description => skill.description.clone(),
+        ListColumn::ModifiedAt => skill.modified_at.clone(),
+    }
+}
+
+fn display_list_table(
+    ctx: &AppContext,
+    skills: &[SkillRecord],
+    args: &ListArgs,
+    format: TableFormat,
+) -> Result<()> {
+    let columns = if args.columns.is_empty() {
+        ListColumn::DEFAULT
+    } else {
+        args.columns.as_slice()
+    };
+    let last_used = if columns.contains(&ListColumn::LastUsed) {
+        ctx.db
+            .get_skill_usage_stats()?
+            .2
+            .into_iter()
+            .map(|(id, at)| (id, at.to_rfc3339()))
+            .collect()
+    } else {
+        HashMap::new()
+    };
+    for line in render_table(skills, format, columns, !args.no_header, &last_used) {
+        println!("{line}");
+    }
+    Ok(())
+}
+
+/// Render skills as CSV/TSV lines, header first unless suppressed.
+fn render_table(
+    skills: &[SkillRecord],
+    format: TableFormat,
+    columns: &[ListColumn],
+    header: bool,
+    last_used: &HashMap<String, String>,
+) -> Vec<String> {
+    let mut lines = Vec::with_capacity(skills.len() + 1);
+    if header {
+        let names: Vec<&str> = columns.iter().map(|c| c.header()).collect();
+        lines.push(format.row(&names));
+    }
+    for skill in skills {
+        let tags = sorted_tags(skill);
+        let cells: Vec<String> = columns
+            .iter()
+            .map(|&c| table_cell(skill, c, &tags, last_used))
+            .collect();
+        lines.push(format.row(&cells));
+    }
+    lines
+}
+
+fn display_list_human(
+    skills: &[SkillRecord],
+    args: &ListArgs,
+    today: NaiveDate,
+    next_cursor: Option<&str>,
+) -> Result<()> {
     if skills.is_empty() {
         println!("No skills found");
         println!();
@@ -232,10 +658,10 @@ fn display_list_human(skills: &[SkillRecord], args: &ListArgs) -> Result<()> {
 
     // Print header
     println!(
-        "{:40} {:12} {:8} {:20}",
-        "ID", "VERSION", "LAYER", "UPDATED"
+        "{:40} {:12} {:8} {:7} {:20} {}",
+        "ID", "VERSION", "LAYER", "QUALITY", "UPDATED", "TAGS"
     );
-    println!("{}", "─".repeat(84));
+    println!("{}", "─".repeat(96));
 
     for skill in skills {
         let layer = normalize_layer(&skill.source_layer);
@@ -245,6 +671,7 @@ fn display_list_human(skills: &[SkillRecord], args: &ListArgs) -> Result<()> {
         } else {
             String::new()
         };
+        let expiry_marker = expiry_marker(skill, today, args.expiring.is_some());
 
         // Truncate ID if too long (use char count for UTF-8 safety)
         let id_display = if skill.id.chars().count() > 38 {
@@ -261,12 +688,15 @@ fn display_list_human(skills: &[SkillRecord], args: &ListArgs) -> Result<()> {
             .unwrap_or(&skill.modified_at);
 
         println!(
-            "{:40} {:12} {:8} {:20}{}",
+            "{:40} {:12} {:8} {:<7.2} {:20} {}{}{}",
             id_display,
             skill.version.as_deref().unwrap_or("-"),
             layer,
+            skill.quality_score,
             updated,
-            deprecated_marker
+            sorted_tags(skill).join(","),
+            deprecated_marker,
+            expiry_marker
         );
     }
 
@@ -277,13 +707,32 @@ fn display_list_human(skills: &[SkillRecord], args: &ListArgs) -> Result<()> {
         args.limit,
         args.offset
     );
+    if let Some(cursor) = next_cursor {
+        println!("More skills: --cursor {cursor}");
+    }
 
     Ok(())
 }
 
+/// ` [expired]`, or with `verbose` the expiry date and days left.
+fn expiry_marker(skill: &SkillRecord, today: NaiveDate, verbose: bool) -> String {
+    let window = SkillWindow::from_metadata_json(&skill.metadata_json);
+    match (window.expires_on(), window.days_until_expiry(today)) {
+        (Some(expires), Some(left)) if left < 0 => {
+            if verbose {
+                format!(" [expired {expires}]")
+            } else {
+                " [expired]".to_string()
+            }
+        }
+        (Some(expires), Some(left)) if verbose => format!(" [expires {expires}, {left}d left]"),
+        _ => String::new(),
+    }
+}
+
 fn normalize_layer(input: &str) -> String {
     match input.to_lowercase().as_str() {
-        "system" => "base",
+        "system" | "community" => "base",
         "global" => "org",
         "local" => "user",
         other => other,
@@ -356,9 +805,17 @@ mod tests {
             tags: vec![],
             layer: None,
             include_deprecated: false,
-            sort: "name".to_string(),
+            deprecated: false,
+            no_deprecated: false,
+            min_quality: None,
+            updated_since: None,
+            sort: ListSort::Name,
             limit: 50,
             offset: 0,
+            cursor: None,
+            columns: vec![],
+            no_header: false,
+            expiring: None,
         }
     }
 
@@ -368,7 +825,7 @@ mod tests {
     fn test_list_render_empty_state() {
         let skills: Vec<SkillRecord> = vec![];
         let args = default_args();
-        let result = display_list_human(&skills, &args);
+        let result = display_list_human(&skills, &args, NaiveDate::default(), None);
         assert!(result.is_ok());
     }
 
@@ -378,7 +835,7 @@ mod tests {
     fn test_list_render_single_skill() {
         let skills = vec![make_skill("hello-world", "base", false)];
         let args = default_args();
-        let result = display_list_human(&skills, &args);
+        let result = display_list_human(&skills, &args, NaiveDate::default(), None);
         assert!(result.is_ok());
     }
 
@@ -390,7 +847,7 @@ mod tests {
             .map(|i| make_skill(&format!("skill-{i}"), "project", false))
             .collect();
         let args = default_args();
-        let result = display_list_human(&skills, &args);
+        let result = display_list_human(&skills, &args, NaiveDate::default(), None);
         assert!(result.is_ok());
     }
 
@@ -444,9 +901,17 @@ mod tests {
             tags: vec![],
             layer: None,
             include_deprecated: false,
-            sort: "name".to_string(),
+            deprecated: false,
+            no_deprecated: false,
+            min_quality: None,
+            updated_since: None,
+            sort: ListSort::Name,
             limit: 10,
             offset: 5,
+            cursor: None,
+            columns: vec![],
+            no_header: false,
+            expiring: None,
         };
         // Pagination values accessible
         assert_eq!(args.limit, 10);
@@ -594,4 +1059,155 @@ mod tests {
         assert_eq!(skills[1].name, "mike");
         assert_eq!(skills[2].name, "zulu");
     }
+
+    // ── 17. test_list_table_export ──────────────────────────────────
+
+    #[test]
+    fn test_list_table_csv_escapes_awkward_names() {
+        let mut skill = make_skill("plain", "project", false);
+        skill.name = "Commas, \"quotes\"\nand newlines".to_string();
+        skill.metadata_json = r#"{"tags":["rust","cli","reviewed-2025","cli"]}"#.to_string();
+        let mut last_used = HashMap::new();
+        last_used.insert(skill.id.clone(), "2025-07-01T00:00:00+00:00".to_string());
+
+        let lines = render_table(
+            &[skill],
+            TableFormat::Csv,
+            ListColumn::DEFAULT,
+            true,
+            &last_used,
+        );
+        assert_eq!(
+            lines[0],
+            "id,name,layer,tags,quality,tokens,last_used,deprecated,review_state"
+        );
+        assert_eq!(
+            lines[1],
+            "skill-plain,\"Commas, \"\"quotes\"\"\nand newlines\",project,cli;reviewed-2025;rust,0.85,100,2025-07-01T00:00:00+00:00,false,reviewed-2025"
+        );
+    }
+
+    #[test]
+    fn test_list_table_tsv_columns_and_no_header() {
+        let mut skill = make_skill("tabbed", "system", true);
+        skill.name = "tab\there".to_string();
+        skill.quality_score = 0.123_456_789;
+        let lines = render_table(
+            &[skill],
+            TableFormat::Tsv,
+            &[ListColumn::Quality, ListColumn::Name, ListColumn::Layer],
+            false,
+            &HashMap::new(),
+        );
+        assert_eq!(lines, vec!["0.123456789\ttab\\there\tbase".to_string()]);
+    }
+
+    #[test]
+    fn test_list_expiring_window() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        let with_expiry = |name: &str, expires: &str| {
+            let mut skill = make_skill(name, "project", false);
+            skill.metadata_json = format!(r#"{{"expires":"{expires}"}}"#);
+            skill
+        };
+        let skills = vec![
+            with_expiry("later", "2026-12-31"),
+            with_expiry("soon", "2026-11-15"),
+            with_expiry("gone", "2026-10-15"),
+            make_skill("forever", "project", false),
+        ];
+        let names: Vec<String> = expiring_within(skills.clone(), 30, today)
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+        assert_eq!(names, ["gone", "soon"]);
+
+        assert_eq!(expiry_marker(&skills[2], today, false), " [expired]");
+        assert_eq!(
+            expiry_marker(&skills[1], today, true),
+            " [expires 2026-11-15, 30d left]"
+        );
+        assert_eq!(expiry_marker(&skills[1], today, false), "");
+
+        let entry = SkillEntry::new(&skills[2], today);
+        assert!(entry.is_expired);
+        assert_eq!(entry.days_until_expiry, Some(-1));
+    }
+
+    #[test]
+    fn test_list_parse_expiring_days() {
+        assert_eq!(parse_expiring_days("30d"), Ok(30));
+        assert_eq!(parse_expiring_days("2w"), Ok(14));
+        assert_eq!(parse_expiring_days("7"), Ok(7));
+        assert!(parse_expiring_days("soon").is_err());
+        assert!(parse_expiring_days("-3d").is_err());
+    }
+
+    #[test]
+    fn test_list_layer_maps_to_stored_layers() {
+        assert_eq!(ListLayer::Global.source_layers(), ["org", "global"]);
+        assert_eq!(ListLayer::Community.source_layers(), ["base", "system"]);
+        assert_eq!(ListLayer::Local.source_layers(), ["user", "local"]);
+        assert_eq!(normalize_layer("community"), "base");
+    }
+
+    #[test]
+    fn test_list_query_from_args() {
+        let args = ListArgs {
+            tags: vec!["Rust".to_string(), "cli".to_string()],
+            layer: Some(ListLayer::Global),
+            min_quality: Some(0.7),
+            sort: ListSort::Quality,
+            ..default_args()
+        };
+        let query = skill_query(&args, None);
+        assert_eq!(query.tags, ["Rust", "cli"]);
+        assert_eq!(query.layers, ["org", "global"]);
+        assert_eq!(query.min_quality, Some(0.7));
+        assert_eq!(query.deprecated, Some(false));
+        assert_eq!(query.sort, SkillListSort::Quality);
+
+        let filters = serde_json::to_value(AppliedFilters::new(&args, None)).unwrap();
+        assert_eq!(filters["tags"], serde_json::json!(["rust", "cli"]));
+        assert_eq!(filters["layer"], "global");
+        assert_eq!(filters["deprecated"], "exclude");
+        assert_eq!(filters["sort"], "quality");
+
+        let only = ListArgs {
+            deprecated: true,
+            ..default_args()
+        };
+        assert_eq!(skill_query(&only, None).deprecated, Some(true));
+        let include = ListArgs {
+            include_deprecated: true,
+            ..default_args()
+        };
+        assert_eq!(skill_query(&include, None).deprecated, None);
+    }
+
+    #[test]
+    fn test_list_min_quality_range() {
+        assert_eq!(parse_min_quality("0.7"), Ok(0.7));
+        assert!(parse_min_quality("1.5").is_err());
+        assert!(parse_min_quality("high").is_err());
+    }
+
+    #[test]
+    fn test_list_cursor_key_follows_sort() {
+        let skill = make_skill("mike", "base", false);
+        let today = NaiveDate::default();
+        let key = |sort| {
+            sort_key(
+                &skill,
+                &ListArgs {
+                    sort,
+                    ..default_args()
+                },
+                today,
+            )
+        };
+        assert_eq!(key(ListSort::Name), "mike");
+        assert_eq!(key(ListSort::Updated), "2025-06-15T10:30:00Z");
+        assert_eq!(key(ListSort::Quality), "0.85");
+    }
 }
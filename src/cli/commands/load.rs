@@ -1,38 +1,58 @@
 //! ms load - Load a skill with progressive disclosure
 
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::io::{self, Write};
 use std::path::PathBuf;
 
 use clap::{Args, ValueEnum};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use tracing::debug;
 
 use crate::app::AppContext;
-use crate::cli::output::OutputFormat;
+use crate::cli::commands::trial::start_trial;
+use crate::cli::output::{OutputFormat, TableFormat};
 use crate::context::collector::{CollectedContext, ContextCollector, ContextCollectorConfig};
 use crate::context::scoring::{RankedSkill, RelevanceScorer, WorkingContext};
+use crate::core::budget::{BudgetScope, degrade_to_budget, dropped_warning};
+use crate::core::context_window::track_served;
 use crate::core::dependencies::{
-    DependencyGraph, DependencyLoadMode, DependencyResolver, DisclosureLevel as DepDisclosure,
+    DependencyGraph, DependencyKind, DependencyLoadMode, DependencyResolver,
+    DisclosureLevel as DepDisclosure, ResolvedDependencyPlan, SkillLoadPlan,
 };
 use crate::core::disclosure::{
     DisclosedContent, DisclosureLevel, DisclosurePlan, PackMode, TokenBudget, disclose,
+    render_packed_body,
 };
+use crate::core::follow_up::{self, FollowUpAction};
+use crate::core::lookup::{self, ResolveOptions};
 use crate::core::pack_contracts::{
     PackContractPreset, custom_contracts_path, find_custom_contract,
 };
+use crate::core::packing::{
+    MandatoryPredicate, MandatorySlice, MultiPackResult, PackConstraints, PackError, SkillSlices,
+    pack_skills,
+};
 use crate::core::resolution::{DbSkillRepository, resolve_full};
-use crate::core::skill::{PackContract, SkillAssets, SkillMetadata};
+use crate::core::resources::{DEFAULT_INLINE_RESOURCE_BYTES, is_text, normalize_link};
+use crate::core::skill::{
+    PackContract, SkillAssets, SkillMetadata, SkillSlice, SkillSpec, SliceType,
+};
+use crate::core::slice_query::{DEFAULT_TOP_K, SliceQuery, SliceSelection, select_slices};
+use crate::core::slicing::SkillSlicer;
 use crate::core::spec_lens::parse_markdown;
+use crate::core::tokenizer::TokenCounter;
 use crate::error::{MsError, Result};
 use crate::meta_skills::{ConditionContext, MetaSkillManager, MetaSkillRegistry};
+use crate::security::encryption;
 use crate::storage::sqlite::SkillRecord;
 use crate::suggestions::bandit::{
     ContextualBandit, DefaultFeatureExtractor, FeatureExtractor, SkillFeedback, UserHistory,
 };
+use crate::utils::format::format_size;
 
 /// Dependency loading strategy
-#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum DepsMode {
     /// Dependencies at overview level
     #[default]
@@ -41,6 +61,8 @@ pub enum DepsMode {
     Off,
     /// Dependencies at full disclosure
     Full,
+    /// Only `requires`; skip recommendations to save tokens
+    RequiredOnly,
 }
 
 impl From<DepsMode> for DependencyLoadMode {
@@ -49,6 +71,7 @@ impl From<DepsMode> for DependencyLoadMode {
             DepsMode::Auto => Self::Auto,
             DepsMode::Off => Self::Off,
             DepsMode::Full => Self::Full,
+            DepsMode::RequiredOnly => Self::RequiredOnly,
         }
     }
 }
@@ -132,6 +155,32 @@ pub struct LoadArgs {
     #[arg(long)]
     pub pack: Option<usize>,
 
+    /// Token budget shared by the skill and its dependencies (fails if the
+    /// mandatory slices alone do not fit)
+    #[arg(long, conflicts_with = "pack")]
+    pub max_tokens: Option<usize>,
+
+    /// Load only the slices matching this need, ranked by BM25 (e.g.
+    /// "retry on timeout"); --max-tokens caps them
+    #[arg(
+        long,
+        conflicts_with_all = ["auto", "pack", "level", "full", "complete", "trial"]
+    )]
+    pub slices: Option<String>,
+
+    /// Load these blocks or sections instead: block ids, section ids or
+    /// section titles (repeatable)
+    #[arg(
+        long,
+        value_delimiter = ',',
+        conflicts_with_all = ["slices", "auto", "pack", "level", "full", "complete", "trial"]
+    )]
+    pub select: Vec<String>,
+
+    /// Most slices to return for --slices
+    #[arg(long, default_value_t = DEFAULT_TOP_K)]
+    pub top_k: usize,
+
     /// Pack mode when using --pack
     #[arg(long, value_enum, default_value = "balanced")]
     pub mode: CliPackMode,
@@ -167,6 +216,30 @@ pub struct LoadArgs {
     /// Variant id for experiment attribution
     #[arg(long)]
     pub variant_id: Option<String>,
+
+    /// Model consuming the output (e.g. claude-sonnet-4), for context window warnings
+    #[arg(long, env = "MS_MODEL")]
+    pub model: Option<String>,
+
+    /// Agent session id; tokens served across loads in a session are summed
+    #[arg(long, env = "MS_SESSION_ID")]
+    pub session_id: Option<String>,
+
+    /// Print how the skill reference was resolved (to stderr)
+    #[arg(long)]
+    pub trace_resolution: bool,
+
+    /// Open a trial for this load and print its id for `ms trial report`
+    #[arg(long, conflicts_with = "auto")]
+    pub trial: bool,
+
+    /// Inline the skill's small text resources after its body
+    #[arg(long, conflicts_with = "auto")]
+    pub with_resources: bool,
+
+    /// Largest resource, in bytes, --with-resources inlines
+    #[arg(long, default_value_t = DEFAULT_INLINE_RESOURCE_BYTES, requires = "with_resources")]
+    pub resource_cap: u64,
 }
 
 /// Result of loading a skill
@@ -175,11 +248,47 @@ pub struct LoadResult {
     pub skill_id: String,
     pub name: String,
     pub disclosed: DisclosedContent,
+    /// Tokenizer that produced the token counts (`[packing].tokenizer`)
+    pub tokenizer: &'static str,
     pub dependencies_loaded: Vec<String>,
     pub slices_included: Option<usize>,
     pub inheritance_chain: Vec<String>,
     pub included_from: Vec<String>,
     pub warnings: Vec<String>,
+    /// Declared next steps; never executed by ms itself
+    pub follow_ups: Vec<FollowUpAction>,
+    /// Set when `--max-tokens` packed the skill and its dependencies
+    pub budget: Option<BudgetReport>,
+    /// Set when loaded with `--trial`
+    pub trial_id: Option<String>,
+    /// Set when loaded with `--with-resources`
+    pub resources: Vec<LoadedResource>,
+}
+
+/// A resource listed by `--with-resources`, inlined when it is text under
+/// the size cap.
+#[derive(Debug, Clone, Serialize)]
+pub struct LoadedResource {
+    pub path: String,
+    pub size: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// Why the content was not inlined
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipped: Option<&'static str>,
+}
+
+/// What `--max-tokens` kept and dropped.
+#[derive(Debug, Clone, Serialize)]
+pub struct BudgetReport {
+    pub max_tokens: usize,
+    pub tokens_used: usize,
+    pub slices_included: usize,
+    pub slices_dropped: usize,
+    /// Mandatory slices kept, as `skill_id/slice_id`
+    pub mandatory_kept: Vec<String>,
+    /// Skills that contributed content, in load order
+    pub skills: Vec<String>,
 }
 
 pub fn run(ctx: &AppContext, args: &LoadArgs) -> Result<()> {
@@ -195,8 +304,29 @@ pub fn run(ctx: &AppContext, args: &LoadArgs) -> Result<()> {
         MsError::ValidationFailed("skill argument required when not using --auto".to_string())
     })?;
 
+    if args.trace_resolution {
+        let options = ResolveOptions {
+            packs: meta_skill_ids(),
+            ..ResolveOptions::default()
+        };
+        for line in lookup::resolve(&ctx.db, skill_ref, &options)?
+            .trace
+            .render()
+        {
+            eprintln!("{line}");
+        }
+    }
+
+    if args.slices.is_some() || !args.select.is_empty() {
+        return run_slices(ctx, args, skill_ref);
+    }
+
     // First try to load as meta-skill
-    if let Some(meta_result) = try_load_meta_skill(ctx, args, skill_ref)? {
+    if let Some(mut meta_result) = try_load_meta_skill(ctx, args, skill_ref)? {
+        let served = [(meta_result.meta_skill_id.clone(), meta_result.tokens_used)];
+        meta_result
+            .warnings
+            .extend(context_window_warning(ctx, args, &served));
         return match ctx.output_format {
             OutputFormat::Json | OutputFormat::Jsonl | OutputFormat::Toon => {
                 output_robot_meta(ctx, &meta_result, args)
@@ -208,21 +338,120 @@ pub fn run(ctx: &AppContext, args: &LoadArgs) -> Result<()> {
     // Fall back to regular skill loading
     debug!(target: "load", skill_id = %skill_ref, "loading skill");
     debug!(target: "load", stage = "validation_start");
-    let result = load_skill(ctx, args, skill_ref)?;
+    let mut result = load_skill(ctx, args, skill_ref)?;
     debug!(target: "load", stage = "validation_complete", passed = true);
+    if args.trial {
+        result.trial_id = Some(start_trial(ctx, &result.skill_id, result.disclosed.level)?);
+    }
+
+    let served = [(result.skill_id.clone(), result.disclosed.token_estimate)];
+    let mut context_warning = context_window_warning(ctx, args, &served);
 
     let out = match ctx.output_format {
         OutputFormat::Json | OutputFormat::Jsonl | OutputFormat::Toon => {
+            result.warnings.extend(context_warning.take());
             output_robot(ctx, &result, args)
         }
         OutputFormat::Plain => output_plain(&result),
-        OutputFormat::Tsv => output_tsv(&result),
+        OutputFormat::Tsv => output_table(&result, TableFormat::Tsv),
+        OutputFormat::Csv => output_table(&result, TableFormat::Csv),
         OutputFormat::Human => output_human(ctx, &result, args),
     };
+    if let Some(warning) = context_warning {
+        eprintln!("warning: {warning}");
+    }
     debug!(target: "load", stage = "load_complete");
     out
 }
 
+/// Serve only the slices of a skill that match `--slices` or `--select`.
+fn run_slices(ctx: &AppContext, args: &LoadArgs, skill_ref: &str) -> Result<()> {
+    let query = SliceQuery {
+        need: args.slices.clone(),
+        selectors: args.select.clone(),
+        top_k: args.top_k,
+        max_tokens: args.max_tokens,
+    };
+    let (skill, selection) = load_slices(ctx, skill_ref, &query)?;
+    let served = [(skill.id.clone(), selection.total_tokens)];
+    let context_warning = context_window_warning(ctx, args, &served);
+
+    if ctx.output_format.is_machine_readable() {
+        let mut output = serde_json::json!({
+            "status": "ok",
+            "skill_id": skill.id,
+            "name": skill.name,
+            "selection": selection,
+        });
+        if let Some(warning) = context_warning {
+            output["warnings"] = serde_json::json!([warning]);
+        }
+        match ctx.output_format {
+            OutputFormat::Toon => println!("{}", toon_rust::encode(output, None)),
+            OutputFormat::Jsonl => println!("{}", serde_json::to_string(&output)?),
+            _ => println!("{}", serde_json::to_string_pretty(&output)?),
+        }
+        return Ok(());
+    }
+
+    if selection.slices.is_empty() {
+        println!("No slices of {} match.", skill.id);
+    }
+    for slice in &selection.slices {
+        println!(
+            "── {} ({}) · {} tokens · prev: {} · next: {}",
+            slice.id,
+            slice.ancestors.join(" › "),
+            slice.token_estimate,
+            slice.previous.as_deref().unwrap_or("-"),
+            slice.next.as_deref().unwrap_or("-"),
+        );
+        println!("{}\n", slice.content);
+    }
+    println!(
+        "{} of {} slices, {} tokens ({} matching left out)",
+        selection.slices.len(),
+        selection.available,
+        selection.total_tokens,
+        selection.dropped
+    );
+    if let Some(warning) = context_warning {
+        eprintln!("warning: {warning}");
+    }
+    Ok(())
+}
+
+/// Select slices of a skill as served by `ms load`; shared with the MCP
+/// `load_slices` tool.
+pub(crate) fn load_slices(
+    ctx: &AppContext,
+    skill_ref: &str,
+    query: &SliceQuery,
+) -> Result<(SkillRecord, SliceSelection)> {
+    let skill = ctx.resolve_skill(skill_ref)?;
+    let served = served_spec(ctx, &skill)?;
+    let counter = ctx.token_counter()?;
+    let selection = select_slices(&served.spec, query, counter.as_ref())?;
+    Ok((skill, selection))
+}
+
+/// Record what this load served against the agent session and warn when the
+/// consuming model's context window is filling up. Never blocks a load.
+fn context_window_warning(
+    ctx: &AppContext,
+    args: &LoadArgs,
+    served: &[(String, usize)],
+) -> Option<String> {
+    track_served(
+        &ctx.ms_root,
+        &ctx.config.context_window,
+        args.model.as_deref(),
+        args.session_id.as_deref(),
+        served,
+    )
+    .map(|warning| warning.to_string())
+}
+
 // =============================================================================
 // AUTO-LOAD IMPLEMENTATION
 // =============================================================================
@@ -235,6 +464,8 @@ pub struct AutoLoadResult {
     pub loaded: Vec<LoadResult>,
     pub skipped: Vec<String>,
     pub total_tokens: usize,
+    /// Budget warnings (what was dropped to stay within layer budgets).
+    pub warnings: Vec<String>,
 }
 
 /// Summary of detected context
@@ -296,7 +527,7 @@ fn run_auto_load(ctx: &AppContext, args: &LoadArgs) -> Result<()> {
 
     // Blend in bandit scores from historical learning
     let skill_ids: Vec<String> = candidates.iter().map(|c| c.skill_id.clone()).collect();
-    let bandit_scores = get_bandit_scores(&collected, &skill_ids);
+    let bandit_scores = get_bandit_scores(ctx, &collected, &skill_ids);
 
     // Apply bandit score boost (blend_factor from config, default 0.3)
     let blend_factor = ctx.config.auto_load.bandit_blend;
@@ -357,7 +588,6 @@ fn run_auto_load(ctx: &AppContext, args: &LoadArgs) -> Result<()> {
     // Load skills (with optional confirmation)
     let mut loaded_results = Vec::new();
     let mut skipped = Vec::new();
-    let mut total_tokens = 0usize;
 
     for candidate in &candidates {
         // Confirm mode: ask user before each load (only for human-readable output)
@@ -380,10 +610,7 @@ fn run_auto_load(ctx: &AppContext, args: &LoadArgs) -> Result<()> {
 
         // Load the skill
         match load_skill(ctx, args, &candidate.skill_id) {
-            Ok(result) => {
-                total_tokens += result.disclosed.token_estimate;
-                loaded_results.push(result);
-            }
+            Ok(result) => loaded_results.push(result),
             Err(e) => {
                 if ctx.verbosity > 0 {
                     eprintln!("warning: failed to load {}: {}", candidate.skill_id, e);
@@ -393,6 +620,13 @@ fn run_auto_load(ctx: &AppContext, args: &LoadArgs) -> Result<()> {
         }
     }
 
+    let (loaded_results, budget_dropped) = apply_layer_budgets(ctx, loaded_results);
+    skipped.extend(budget_dropped.values().flatten().cloned());
+    let total_tokens: usize = loaded_results
+        .iter()
+        .map(|r| r.disclosed.token_estimate)
+        .sum();
+
     // Record auto-load events to the contextual bandit for learning
     if ctx.config.auto_load.learning_enabled {
         let scores: Vec<(String, f32)> = candidates
@@ -400,6 +634,7 @@ fn run_auto_load(ctx: &AppContext, args: &LoadArgs) -> Result<()> {
             .map(|c| (c.skill_id.clone(), c.score))
             .collect();
         record_auto_load_events(
+            ctx,
             &collected,
             &loaded_results,
             &scores,
@@ -408,12 +643,26 @@ fn run_auto_load(ctx: &AppContext, args: &LoadArgs) -> Result<()> {
         );
     }
 
+    let mut warnings: Vec<String> = budget_dropped
+        .iter()
+        .map(|(layer, dropped)| {
+            let limit = ctx.config.budget.layer_budget(layer).unwrap_or(0) as usize;
+            dropped_warning(BudgetScope::Layer, layer, limit, dropped)
+        })
+        .collect();
+    let served: Vec<(String, usize)> = loaded_results
+        .iter()
+        .map(|r| (r.skill_id.clone(), r.disclosed.token_estimate))
+        .collect();
+    warnings.extend(context_window_warning(ctx, args, &served));
+
     let auto_result = AutoLoadResult {
         context_summary,
         candidates,
         loaded: loaded_results,
         skipped,
         total_tokens,
+        warnings,
     };
 
     match ctx.output_format {
@@ -424,6 +673,61 @@ fn run_auto_load(ctx: &AppContext, args: &LoadArgs) -> Result<()> {
     }
 }
 
+/// Drop loaded skills that do not fit their layer's budget.
+///
+/// `loaded` is in priority (score) order, so each budgeted layer is degraded
+/// with [`degrade_to_budget`]: a skill that would push its layer over budget
+/// is dropped and lower-priority skills that still fit are kept. Returns the
+/// kept results and the dropped skill ids per layer.
+fn apply_layer_budgets(
+    ctx: &AppContext,
+    loaded: Vec<LoadResult>,
+) -> (Vec<LoadResult>, BTreeMap<String, Vec<String>>) {
+    let mut by_layer: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for (idx, result) in loaded.iter().enumerate() {
+        if let Some(layer) = skill_layer(ctx, &result.skill_id) {
+            by_layer.entry(layer).or_default().push(idx);
+        }
+    }
+
+    let mut keep = vec![true; loaded.len()];
+    let mut dropped: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (layer, indices) in by_layer {
+        let Some(limit) = ctx.config.budget.layer_budget(&layer) else {
+            continue;
+        };
+        let costs: Vec<usize> = indices
+            .iter()
+            .map(|&idx| loaded[idx].disclosed.token_estimate)
+            .collect();
+        let (_, over) = degrade_to_budget(&costs, limit as usize);
+        for pos in over {
+            let idx = indices[pos];
+            keep[idx] = false;
+            dropped
+                .entry(layer.clone())
+                .or_default()
+                .push(loaded[idx].skill_id.clone());
+        }
+    }
+
+    let kept = loaded
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(result, keep)| keep.then_some(result))
+        .collect();
+    (kept, dropped)
+}
+
+/// Source layer of an indexed skill, lowercased.
+fn skill_layer(ctx: &AppContext, skill_id: &str) -> Option<String> {
+    ctx.db
+        .get_skill(skill_id)
+        .ok()
+        .flatten()
+        .map(|record| record.source_layer.to_lowercase())
+}
+
 /// Convert `CollectedContext` to `WorkingContext` for scoring
 fn convert_to_scoring_context(collected: &CollectedContext) -> WorkingContext {
     WorkingContext::new()
@@ -448,17 +752,12 @@ fn convert_to_scoring_context(collected: &CollectedContext) -> WorkingContext {
 // CONTEXTUAL BANDIT INTEGRATION FOR AUTO-LOAD
 // =============================================================================
 
-/// Default path for the contextual bandit state file.
-fn default_bandit_path() -> PathBuf {
-    let base = dirs::data_dir().unwrap_or_else(|| PathBuf::from("."));
-    base.join("ms").join("contextual_bandit.json")
-}
-
 /// Record auto-load events to the contextual bandit for learning.
 ///
 /// This function records that skills were auto-loaded based on context,
 /// enabling the bandit to learn which skills are relevant in different contexts.
 fn record_auto_load_events(
+    ctx: &AppContext,
     collected: &CollectedContext,
     loaded_skills: &[LoadResult],
     _scores: &[(String, f32)], // Reserved for future: weighted feedback based on relevance
@@ -490,36 +789,28 @@ fn record_auto_load_events(
         }
     }
 
-    // Load the bandit or create new with configured parameters
-    let bandit_path = default_bandit_path();
-    let mut bandit = match ContextualBandit::load(&bandit_path) {
-        Ok(b) => b,
-        Err(e) => {
-            if verbosity > 1 {
-                eprintln!("note: creating new bandit (load failed: {e})");
-            }
-            // Create new bandit with config from AutoLoadConfig
+    // Update the persisted bandit; a bandit that has not learned anything yet
+    // starts from the AutoLoadConfig parameters
+    let updated = ctx.update_bandit(None, |bandit| {
+        if bandit.total_updates() == 0 {
             let bandit_config = ContextualBanditConfig {
                 exploration_rate: config.exploration_rate,
                 learning_rate: config.learning_rate,
                 cold_start_threshold: config.cold_start_threshold,
                 ..Default::default()
             };
-            ContextualBandit::new(bandit_config, FEATURE_DIM)
+            *bandit = ContextualBandit::new(bandit_config, FEATURE_DIM);
         }
-    };
 
-    // Register and update each loaded skill
-    for result in loaded_skills {
-        // Register the skill if not already known
-        bandit.register_skill(&result.skill_id);
-
-        // Record as LoadedOnly initially - user feedback will upgrade this
-        bandit.update(&result.skill_id, &features, &SkillFeedback::LoadedOnly);
-    }
+        for result in loaded_skills {
+            // Register the skill if not already known
+            bandit.register_skill(&result.skill_id);
 
-    // Save the updated bandit
-    if let Err(e) = bandit.save(&bandit_path) {
+            // Record as LoadedOnly initially - user feedback will upgrade this
+            bandit.update(&result.skill_id, &features, &SkillFeedback::LoadedOnly);
+        }
+    });
+    if let Err(e) = updated {
         if verbosity > 0 {
             eprintln!("warning: failed to save bandit: {e}");
         }
@@ -530,15 +821,17 @@ fn record_auto_load_events(
 ///
 /// This function samples from the contextual bandit to boost scores for
 /// skills that have historically performed well in similar contexts.
-fn get_bandit_scores(collected: &CollectedContext, skill_ids: &[String]) -> HashMap<String, f32> {
+fn get_bandit_scores(
+    ctx: &AppContext,
+    collected: &CollectedContext,
+    skill_ids: &[String],
+) -> HashMap<String, f32> {
     let extractor = DefaultFeatureExtractor::new();
     let history = UserHistory::load(&UserHistory::default_path());
     let features = extractor.extract_from_collected(collected, &history);
 
-    let bandit_path = default_bandit_path();
-    let mut bandit = match ContextualBandit::load(&bandit_path) {
-        Ok(b) => b,
-        Err(_) => return HashMap::new(),
+    let Ok(mut bandit) = ctx.bandit(None) else {
+        return HashMap::new();
     };
 
     let mut scores = HashMap::new();
@@ -686,12 +979,17 @@ fn output_dry_run(
                 println!("{}", candidate.skill_id);
             }
         }
-        OutputFormat::Tsv => {
-            println!("skill_id\tname\tscore");
+        OutputFormat::Tsv | OutputFormat::Csv => {
+            let table = TableFormat::for_output(ctx.output_format).unwrap_or(TableFormat::Tsv);
+            println!("{}", table.row(&["skill_id", "name", "score"]));
             for candidate in candidates {
                 println!(
-                    "{}\t{}\t{:.2}",
-                    candidate.skill_id, candidate.skill_name, candidate.score
+                    "{}",
+                    table.row(&[
+                        candidate.skill_id.clone(),
+                        candidate.skill_name.clone(),
+                        format!("{:.2}", candidate.score),
+                    ])
                 );
             }
         }
@@ -791,6 +1089,10 @@ fn output_auto_human(ctx: &AppContext, result: &AutoLoadResult, _args: &LoadArgs
         println!("Skipped: {}", result.skipped.join(", "));
     }
 
+    for warning in &result.warnings {
+        eprintln!("warning: {warning}");
+    }
+
     Ok(())
 }
 
@@ -814,6 +1116,7 @@ fn output_auto_robot(ctx: &AppContext, result: &AutoLoadResult, args: &LoadArgs)
                     "skill_id": l.skill_id,
                     "name": l.name,
                     "token_count": l.disclosed.token_estimate,
+                    "tokenizer": l.tokenizer,
                     "content": l.disclosed.body
                 })
             }).collect::<Vec<_>>(),
@@ -821,7 +1124,7 @@ fn output_auto_robot(ctx: &AppContext, result: &AutoLoadResult, args: &LoadArgs)
             "total_tokens": result.total_tokens,
             "threshold": args.threshold
         },
-        "warnings": []
+        "warnings": result.warnings
     });
     match ctx.output_format {
         OutputFormat::Toon => {
@@ -838,16 +1141,20 @@ fn output_auto_robot(ctx: &AppContext, result: &AutoLoadResult, args: &LoadArgs)
 }
 
 pub(crate) fn load_skill(ctx: &AppContext, args: &LoadArgs, skill_ref: &str) -> Result<LoadResult> {
-    // Resolve skill by ID or alias
-    let skill = resolve_skill(ctx, skill_ref)?;
+    let skill = ctx.resolve_skill(skill_ref)?;
 
     if args.contract.is_some() && args.contract_id.is_some() {
         return Err(MsError::Config(
             "use either --contract or --contract-id".to_string(),
         ));
     }
-    if (args.contract.is_some() || args.contract_id.is_some()) && args.pack.is_none() {
-        return Err(MsError::Config("--contract requires --pack".to_string()));
+    if (args.contract.is_some() || args.contract_id.is_some())
+        && args.pack.is_none()
+        && args.max_tokens.is_none()
+    {
+        return Err(MsError::Config(
+            "--contract requires --pack or --max-tokens".to_string(),
+        ));
     }
 
     let contract = resolve_contract(ctx, args)?;
@@ -860,49 +1167,75 @@ pub(crate) fn load_skill(ctx: &AppContext, args: &LoadArgs, skill_ref: &str) ->
     )?;
 
     // Determine disclosure plan
-    let disclosure_plan = determine_disclosure_plan(args, contract);
+    let disclosure_plan = determine_disclosure_plan(args, contract.clone());
 
-    // Parse skill body into SkillSpec
-    let spec = parse_markdown(&skill.body)
-        .map_err(|e| MsError::ValidationFailed(format!("failed to parse skill body: {e}")))?;
-
-    // Merge metadata from database into spec metadata
-    let metadata = merge_metadata(&skill, &spec.metadata);
-    let mut spec = spec;
-    spec.metadata = metadata;
-
-    // Resolve inheritance and composition
-    let repo = DbSkillRepository::new(&ctx.db);
-    let resolved = resolve_full(&spec, &repo)?;
-    let spec = resolved.spec;
+    let ServedSpec {
+        spec,
+        inheritance_chain,
+        included_from,
+        mut warnings,
+    } = served_spec(ctx, &skill)?;
+    let follow_ups = follow_up::actions_for(&skill.id, &spec.metadata.follow_ups);
 
     // Load assets from database
     let assets: SkillAssets = serde_json::from_str(&skill.assets_json).unwrap_or_default();
 
     // Apply disclosure
-    let disclosed = disclose(&spec, &assets, &disclosure_plan);
-    let slices_included = disclosed.slices_included;
+    let counter = ctx.token_counter()?;
+    let mut disclosed = disclose(&spec, &assets, &disclosure_plan, counter.as_ref());
 
     // Handle dependencies if enabled
-    let dependencies_loaded = if matches!(args.deps, DepsMode::Off) {
+    let dependency_plan = if matches!(args.deps, DepsMode::Off) {
         vec![]
     } else {
-        load_dependencies(ctx, &skill, args)?
+        load_dependencies(ctx, &skill, args.deps, &mut warnings)?
+    };
+    let dependencies_loaded = dependency_plan
+        .iter()
+        .map(|plan| plan.skill_id.clone())
+        .collect();
+
+    let budget = match args.max_tokens {
+        Some(max_tokens) => {
+            let packed = pack_within_budget(
+                ctx,
+                args,
+                &skill.id,
+                &spec,
+                &dependency_plan,
+                max_tokens,
+                contract,
+                counter.as_ref(),
+            )?;
+            let body = render_budgeted(&skill.id, &packed);
+            disclosed.body = (!body.is_empty()).then_some(body);
+            disclosed.token_estimate = packed.total_tokens;
+            let report = budget_report(max_tokens, &packed);
+            disclosed.slices_included = Some(report.slices_included);
+            Some(report)
+        }
+        None => None,
     };
+    let slices_included = disclosed.slices_included;
 
     let result = LoadResult {
         skill_id: skill.id.clone(),
         name: skill.name.clone(),
         disclosed,
+        tokenizer: counter.name(),
         dependencies_loaded,
         slices_included,
-        inheritance_chain: resolved.inheritance_chain,
-        included_from: resolved.included_from,
-        warnings: resolved
-            .warnings
-            .iter()
-            .map(|w| format!("{:?}", w))
-            .collect(),
+        inheritance_chain,
+        included_from,
+        warnings,
+        follow_ups,
+        budget,
+        trial_id: None,
+        resources: if args.with_resources {
+            load_resources(ctx, &skill, args.resource_cap)?
+        } else {
+            Vec::new()
+        },
     };
 
     record_usage(
@@ -911,27 +1244,98 @@ pub(crate) fn load_skill(ctx: &AppContext, args: &LoadArgs, skill_ref: &str) ->
         &disclosure_plan,
         experiment_id.as_deref(),
         variant_id.as_deref(),
+        args.session_id.as_deref(),
     );
 
     Ok(result)
 }
 
-fn resolve_skill(ctx: &AppContext, skill_ref: &str) -> Result<SkillRecord> {
-    // Try direct ID lookup
-    if let Some(skill) = ctx.db.get_skill(skill_ref)? {
-        return Ok(skill);
-    }
-
-    // Try alias resolution
-    if let Some(alias_result) = ctx.db.resolve_alias(skill_ref)? {
-        if let Some(skill) = ctx.db.get_skill(&alias_result.canonical_id)? {
-            return Ok(skill);
+/// The skill's resources, with the text ones of at most `cap` bytes inlined.
+/// Content comes from the archive, or from the skill's source directory for
+/// files not archived.
+fn load_resources(ctx: &AppContext, skill: &SkillRecord, cap: u64) -> Result<Vec<LoadedResource>> {
+    let source_dir = std::path::Path::new(&skill.source_path).parent();
+    let mut loaded = Vec::new();
+    for record in ctx.db.get_skill_resources(&skill.id)? {
+        let size = u64::try_from(record.size).unwrap_or(0);
+        let mut resource = LoadedResource {
+            path: record.path.clone(),
+            size,
+            content: None,
+            skipped: None,
+        };
+        if size > cap {
+            resource.skipped = Some("over size cap");
+            loaded.push(resource);
+            continue;
+        }
+        let path = ctx
+            .git
+            .resource_path(&skill.id, &record.path)
+            .filter(|path| path.is_file())
+            .or_else(|| {
+                let rel = normalize_link(&record.path).ok()?;
+                source_dir.map(|dir| dir.join(rel))
+            });
+        match path.map(std::fs::read) {
+            Some(Ok(bytes)) if is_text(&bytes) => {
+                resource.content = Some(String::from_utf8_lossy(&bytes).into_owned());
+            }
+            Some(Ok(_)) => resource.skipped = Some("binary"),
+            _ => resource.skipped = Some("missing"),
         }
+        loaded.push(resource);
     }
+    Ok(loaded)
+}
 
-    Err(MsError::SkillNotFound(format!(
-        "skill not found: {skill_ref}"
-    )))
+/// A skill's spec as loads serve it.
+pub(crate) struct ServedSpec {
+    pub spec: SkillSpec,
+    pub inheritance_chain: Vec<String>,
+    pub included_from: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+/// Parse a skill body, resolve inheritance and composition, and apply
+/// project overrides.
+pub(crate) fn served_spec(ctx: &AppContext, skill: &SkillRecord) -> Result<ServedSpec> {
+    // Parse skill body into SkillSpec
+    let body = encryption::reveal_body(&skill.id, &skill.body)?;
+    let spec = parse_markdown(&body)
+        .map_err(|e| MsError::ValidationFailed(format!("failed to parse skill body: {e}")))?;
+
+    // Merge metadata from database into spec metadata
+    let metadata = merge_metadata(skill, &spec.metadata);
+    let mut spec = spec;
+    spec.metadata = metadata;
+
+    // Resolve inheritance and composition
+    let repo = DbSkillRepository::new(&ctx.db);
+    let resolved = resolve_full(&spec, &repo)?;
+    let mut spec = resolved.spec;
+    let mut warnings: Vec<String> = resolved
+        .warnings
+        .iter()
+        .map(|w| format!("{:?}", w))
+        .collect();
+
+    // Project overrides win over every layer
+    let overrides = ctx.project_overrides()?;
+    if let Some(applied) = overrides.apply(&mut spec) {
+        warnings.extend(
+            applied
+                .unmatched
+                .iter()
+                .map(|unmatched| format!("{}: {unmatched}", overrides.path.display())),
+        );
+    }
+    Ok(ServedSpec {
+        spec,
+        inheritance_chain: resolved.inheritance_chain,
+        included_from: resolved.included_from,
+        warnings,
+    })
 }
 
 // ==================== Meta-Skill Integration ====================
@@ -945,6 +1349,7 @@ pub struct MetaSkillLoadResultWrapper {
     pub slices_loaded: usize,
     pub slices_skipped: usize,
     pub packed_content: String,
+    pub warnings: Vec<String>,
 }
 
 /// Try to load as a meta-skill. Returns None if not found as a meta-skill.
@@ -978,11 +1383,32 @@ fn try_load_meta_skill(
         loaded_slices: &HashSet::new(),
     };
 
-    // Use pack budget if specified, otherwise use meta-skill's recommended tokens
-    let budget = args.pack.unwrap_or(meta_skill.recommended_context_tokens);
+    // Use pack budget if specified, otherwise use meta-skill's recommended tokens.
+    // A configured `budget.pack.<id>` caps either.
+    let requested = args
+        .pack
+        .or(args.max_tokens)
+        .unwrap_or(meta_skill.recommended_context_tokens);
+    let configured_budget = ctx
+        .config
+        .budget
+        .pack_budget(&meta_skill.id)
+        .map(|tokens| tokens as usize);
+    let budget = configured_budget.map_or(requested, |limit| requested.min(limit));
 
     let result = manager.load(&meta_skill, budget, &condition_ctx)?;
 
+    let mut warnings = Vec::new();
+    let dropped = result.budget_dropped();
+    if configured_budget.is_some() && !dropped.is_empty() {
+        warnings.push(dropped_warning(
+            BudgetScope::Pack,
+            &meta_skill.id,
+            budget,
+            &dropped,
+        ));
+    }
+
     Ok(Some(MetaSkillLoadResultWrapper {
         meta_skill_id: result.meta_skill_id,
         meta_skill_name: meta_skill.name,
@@ -990,9 +1416,23 @@ fn try_load_meta_skill(
         slices_loaded: result.slices.len(),
         slices_skipped: result.skipped.len(),
         packed_content: result.packed_content,
+        warnings,
     }))
 }
 
+/// Ids of the meta-skills `ms load` would try before skills
+pub(crate) fn meta_skill_ids() -> Vec<String> {
+    let mut registry = MetaSkillRegistry::new();
+    if registry
+        .load_from_paths(&get_meta_skill_paths())
+        .unwrap_or(0)
+        == 0
+    {
+        return Vec::new();
+    }
+    registry.all().into_iter().map(|ms| ms.id.clone()).collect()
+}
+
 /// Get meta-skill directories
 fn get_meta_skill_paths() -> Vec<PathBuf> {
     let mut paths = Vec::new();
@@ -1046,6 +1486,9 @@ fn output_human_meta(
     result: &MetaSkillLoadResultWrapper,
     _args: &LoadArgs,
 ) -> Result<()> {
+    for warning in &result.warnings {
+        eprintln!("warning: {warning}");
+    }
     println!(
         "# {} (meta-skill: {})",
         result.meta_skill_name, result.meta_skill_id
@@ -1082,12 +1525,12 @@ fn output_robot_meta(
             "meta_skill_id": result.meta_skill_id,
             "name": result.meta_skill_name,
             "tokens_used": result.tokens_used,
-            "budget": args.pack,
+            "budget": args.pack.or(args.max_tokens),
             "slices_loaded": result.slices_loaded,
             "slices_skipped": result.slices_skipped,
             "content": result.packed_content,
         },
-        "warnings": []
+        "warnings": result.warnings,
     });
     match ctx.output_format {
         OutputFormat::Toon => {
@@ -1105,7 +1548,7 @@ fn output_robot_meta(
 
 fn determine_disclosure_plan(args: &LoadArgs, contract: Option<PackContract>) -> DisclosurePlan {
     // Token budget takes precedence
-    if let Some(tokens) = args.pack {
+    if let Some(tokens) = args.pack.or(args.max_tokens) {
         return DisclosurePlan::Pack(TokenBudget {
             tokens,
             mode: args.mode.into(),
@@ -1222,6 +1665,7 @@ fn record_usage(
     plan: &DisclosurePlan,
     experiment_id: Option<&str>,
     variant_id: Option<&str>,
+    session_id: Option<&str>,
 ) {
     let disclosure_level = match plan {
         DisclosurePlan::Level(level) => level.level_num(),
@@ -1238,6 +1682,7 @@ fn record_usage(
         None,
         experiment_id,
         variant_id,
+        session_id,
     ) {
         if ctx.verbosity > 0 {
             eprintln!("warning: failed to record skill usage: {err}");
@@ -1281,25 +1726,101 @@ fn merge_metadata(skill: &SkillRecord, parsed_meta: &SkillMetadata) -> SkillMeta
                     .collect()
             })
             .unwrap_or_else(|| parsed_meta.provides.clone()),
+        recommends: parsed_meta.recommends.clone(),
+        conflicts_with: parsed_meta.conflicts_with.clone(),
         platforms: parsed_meta.platforms.clone(),
         author: skill.author.clone().or_else(|| parsed_meta.author.clone()),
         license: parsed_meta.license.clone(),
         context: parsed_meta.context.clone(),
+        active_during: parsed_meta.active_during.clone(),
+        expires: parsed_meta.expires.clone(),
+        follow_ups: parsed_meta.follow_ups.clone(),
+        imported_from: parsed_meta.imported_from.clone(),
+        forked_from: parsed_meta.forked_from.clone(),
+        sensitive: parsed_meta.sensitive,
+        share: parsed_meta.share,
+        requirements: parsed_meta.requirements.clone(),
     }
 }
 
 fn load_dependencies(
     ctx: &AppContext,
     skill: &SkillRecord,
-    args: &LoadArgs,
-) -> Result<Vec<String>> {
-    // Parse requires from metadata
+    deps: DepsMode,
+    warnings: &mut Vec<String>,
+) -> Result<Vec<SkillLoadPlan>> {
+    let Some(plan) = dependency_plan(ctx, skill, deps)? else {
+        return Ok(vec![]);
+    };
+
+    if !plan.conflicts.is_empty() {
+        let conflicts = plan
+            .conflicts
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("; ");
+        return Err(MsError::ValidationFailed(format!(
+            "cannot load {}: conflicting skills in dependency plan: {conflicts}",
+            skill.id
+        )));
+    }
+    warnings.extend(plan.unsatisfied_recommends.iter().map(|m| {
+        format!(
+            "recommended capability {} not available (recommended by {})",
+            m.capability, m.required_by
+        )
+    }));
+
+    if ctx.verbosity > 0 {
+        if !plan.missing.is_empty() {
+            let missing = plan
+                .missing
+                .iter()
+                .map(|m| format!("{} (required by {})", m.capability, m.required_by))
+                .collect::<Vec<_>>()
+                .join(", ");
+            eprintln!("warning: missing dependency capabilities: {missing}");
+        }
+        if !plan.cycles.is_empty() {
+            let cycles = plan
+                .cycles
+                .iter()
+                .map(|cycle| cycle.join(" -> "))
+                .collect::<Vec<_>>()
+                .join("; ");
+            eprintln!("warning: dependency cycles detected: {cycles}");
+        }
+    }
+
+    // Return just the dependencies (not the root)
+    Ok(plan
+        .ordered
+        .into_iter()
+        .filter(|p| p.skill_id != skill.id)
+        .collect())
+}
+
+/// Resolve the dependency plan of `skill` (the skill itself included) without
+/// judging conflicts; `None` when it declares nothing to load.
+pub(crate) fn dependency_plan(
+    ctx: &AppContext,
+    skill: &SkillRecord,
+    deps: DepsMode,
+) -> Result<Option<ResolvedDependencyPlan>> {
+    // Parse requires/recommends from metadata
     let meta: serde_json::Value = serde_json::from_str(&skill.metadata_json).unwrap_or_default();
 
+    let follow_recommends = !matches!(deps, DepsMode::RequiredOnly);
     let requires = meta_list(&meta, "requires");
+    let recommends = if follow_recommends {
+        meta_list(&meta, "recommends")
+    } else {
+        vec![]
+    };
 
-    if requires.is_empty() {
-        return Ok(vec![]);
+    if requires.is_empty() && recommends.is_empty() {
+        return Ok(None);
     }
 
     // Build dependency graph with available skills
@@ -1309,6 +1830,12 @@ fn load_dependencies(
     let provides = meta_list(&meta, "provides");
 
     graph.add_skill(skill.id.clone(), requires.clone(), provides);
+    graph.declare(&skill.id, DependencyKind::Recommends, recommends.clone());
+    graph.declare(
+        &skill.id,
+        DependencyKind::ConflictsWith,
+        meta_list(&meta, "conflicts_with"),
+    );
 
     let (skill_index, provider_index, meta_cache) = build_dependency_indexes(ctx)?;
 
@@ -1316,7 +1843,7 @@ fn load_dependencies(
     let mut loaded_deps = Vec::new();
     let mut loaded_set = HashSet::new();
     let mut seen_caps = HashSet::new();
-    let mut queue: VecDeque<String> = requires.into_iter().collect();
+    let mut queue: VecDeque<String> = requires.into_iter().chain(recommends).collect();
 
     while let Some(cap) = queue.pop_front() {
         if !seen_caps.insert(cap.clone()) {
@@ -1325,8 +1852,14 @@ fn load_dependencies(
 
         // Direct skill-id match (capability equals skill id).
         if let Some(dep_skill) = skill_index.get(&cap) {
-            if add_dependency_node(&mut graph, dep_skill, &meta_cache, &cap, &mut queue)
-                && loaded_set.insert(dep_skill.id.clone())
+            if add_dependency_node(
+                &mut graph,
+                dep_skill,
+                &meta_cache,
+                &cap,
+                follow_recommends,
+                &mut queue,
+            ) && loaded_set.insert(dep_skill.id.clone())
             {
                 loaded_deps.push(dep_skill.id.clone());
             }
@@ -1337,8 +1870,14 @@ fn load_dependencies(
         if let Some(provider_ids) = provider_index.get(&cap) {
             for provider_id in provider_ids {
                 if let Some(dep_skill) = skill_index.get(provider_id) {
-                    if add_dependency_node(&mut graph, dep_skill, &meta_cache, &cap, &mut queue)
-                        && loaded_set.insert(dep_skill.id.clone())
+                    if add_dependency_node(
+                        &mut graph,
+                        dep_skill,
+                        &meta_cache,
+                        &cap,
+                        follow_recommends,
+                        &mut queue,
+                    ) && loaded_set.insert(dep_skill.id.clone())
                     {
                         loaded_deps.push(dep_skill.id.clone());
                     }
@@ -1350,46 +1889,128 @@ fn load_dependencies(
     graph.build_edges();
 
     // Resolve and return dependency list
-    let dep_disclosure = match args.deps {
+    let dep_disclosure = match deps {
         DepsMode::Auto => DepDisclosure::Overview,
         DepsMode::Full => DepDisclosure::Full,
-        DepsMode::Off => return Ok(vec![]),
+        DepsMode::RequiredOnly => DepDisclosure::Overview,
+        DepsMode::Off => return Ok(None),
     };
 
     let resolver = DependencyResolver::new(&graph);
-    let plan = resolver.resolve(&skill.id, dep_disclosure, args.deps.into())?;
+    resolver
+        .resolve(&skill.id, dep_disclosure, deps.into())
+        .map(Some)
+}
 
-    if ctx.verbosity > 0 {
-        if !plan.missing.is_empty() {
-            let missing = plan
-                .missing
-                .iter()
-                .map(|m| format!("{} (required by {})", m.capability, m.required_by))
-                .collect::<Vec<_>>()
-                .join(", ");
-            eprintln!("warning: missing dependency capabilities: {missing}");
+/// Pack the root skill and its planned dependencies into one `--max-tokens`
+/// budget. Policy slices are mandatory; if they alone exceed the budget the
+/// load fails instead of truncating.
+fn pack_within_budget(
+    ctx: &AppContext,
+    args: &LoadArgs,
+    root_id: &str,
+    root_spec: &SkillSpec,
+    dependencies: &[SkillLoadPlan],
+    max_tokens: usize,
+    contract: Option<PackContract>,
+    counter: &dyn TokenCounter,
+) -> Result<MultiPackResult> {
+    let repo = DbSkillRepository::new(&ctx.db);
+    let mut skills = vec![SkillSlices {
+        skill_id: root_id.to_string(),
+        slices: budget_slices(root_spec, None, counter),
+    }];
+    for dependency in dependencies {
+        let Some(record) = ctx.db.get_skill(&dependency.skill_id)? else {
+            continue;
+        };
+        let body = encryption::reveal_body(&record.id, &record.body)?;
+        let spec = parse_markdown(&body).map_err(|e| {
+            MsError::ValidationFailed(format!(
+                "failed to parse dependency {}: {e}",
+                dependency.skill_id
+            ))
+        })?;
+        let spec = resolve_full(&spec, &repo)?.spec;
+        skills.push(SkillSlices {
+            skill_id: dependency.skill_id.clone(),
+            slices: budget_slices(&spec, Some(dependency.disclosure), counter),
+        });
+    }
+
+    let mut constraints = PackConstraints::new(max_tokens, args.max_per_group);
+    constraints.contract = contract;
+    constraints
+        .mandatory_slices
+        .push(MandatorySlice::ByPredicate(MandatoryPredicate::Always));
+    pack_skills(&skills, &constraints, args.mode.into()).map_err(|err| {
+        let required = match &err {
+            PackError::MandatorySliceOmitted {
+                required_tokens, ..
+            } => *required_tokens,
+            PackError::InsufficientBudget { required, .. } => *required,
+        };
+        MsError::PackBudgetExceeded {
+            budget: max_tokens,
+            required,
+            message: format!("--max-tokens {max_tokens}: {err}"),
         }
-        if !plan.cycles.is_empty() {
-            let cycles = plan
-                .cycles
-                .iter()
-                .map(|cycle| cycle.join(" -> "))
-                .collect::<Vec<_>>()
-                .join("; ");
-            eprintln!("warning: dependency cycles detected: {cycles}");
+    })
+}
+
+/// Slices a skill contributes to a `--max-tokens` pack, limited by the
+/// dependency's disclosure level and counted with the configured tokenizer
+/// so the numbers agree with `ms lint`.
+pub(crate) fn budget_slices(
+    spec: &SkillSpec,
+    disclosure: Option<DepDisclosure>,
+    counter: &dyn TokenCounter,
+) -> Vec<SkillSlice> {
+    SkillSlicer::slice_with(spec, counter)
+        .slices
+        .into_iter()
+        .filter(|slice| match disclosure {
+            Some(DepDisclosure::Minimal) => slice.slice_type == SliceType::Policy,
+            Some(DepDisclosure::Overview) => {
+                matches!(slice.slice_type, SliceType::Policy | SliceType::Overview)
+            }
+            _ => true,
+        })
+        .map(|mut slice| {
+            slice.token_estimate = counter.count(&slice.content);
+            slice
+        })
+        .collect()
+}
+
+fn render_budgeted(root_id: &str, packed: &MultiPackResult) -> String {
+    let mut out = String::new();
+    for skill in &packed.skills {
+        if !out.is_empty() {
+            out.push_str("\n\n");
+        }
+        if skill.skill_id != root_id {
+            out.push_str("# ");
+            out.push_str(&skill.skill_id);
+            out.push_str("\n\n");
         }
+        out.push_str(&render_packed_body(&skill.slices));
     }
+    out
+}
 
-    // Return just the dependency IDs (not the root)
-    Ok(plan
-        .ordered
-        .iter()
-        .filter(|p| p.skill_id != skill.id)
-        .map(|p| p.skill_id.clone())
-        .collect())
+fn budget_report(max_tokens: usize, packed: &MultiPackResult) -> BudgetReport {
+    BudgetReport {
+        max_tokens,
+        tokens_used: packed.total_tokens,
+        slices_included: packed.skills.iter().map(|s| s.slices.len()).sum(),
+        slices_dropped: packed.dropped,
+        mandatory_kept: packed.mandatory_kept.clone(),
+        skills: packed.skills.iter().map(|s| s.skill_id.clone()).collect(),
+    }
 }
 
-fn meta_list(meta: &serde_json::Value, key: &str) -> Vec<String> {
+pub(crate) fn meta_list(meta: &serde_json::Value, key: &str) -> Vec<String> {
     meta.get(key)
         .and_then(|v| v.as_array())
         .map(|arr| {
@@ -1403,6 +2024,8 @@ fn meta_list(meta: &serde_json::Value, key: &str) -> Vec<String> {
 #[derive(Clone)]
 struct CachedMeta {
     requires: Vec<String>,
+    recommends: Vec<String>,
+    conflicts_with: Vec<String>,
     provides: Vec<String>,
 }
 
@@ -1441,7 +2064,15 @@ fn build_dependency_indexes(
                 .or_default()
                 .push(skill.id.clone());
         }
-        meta_cache.insert(skill.id.clone(), CachedMeta { requires, provides });
+        meta_cache.insert(
+            skill.id.clone(),
+            CachedMeta {
+                requires,
+                recommends: meta_list(&meta_json, "recommends"),
+                conflicts_with: meta_list(&meta_json, "conflicts_with"),
+                provides,
+            },
+        );
         skill_index.insert(skill.id.clone(), skill);
     }
 
@@ -1453,6 +2084,7 @@ fn add_dependency_node(
     skill: &SkillRecord,
     meta_cache: &HashMap<String, CachedMeta>,
     fallback_capability: &str,
+    follow_recommends: bool,
     queue: &mut VecDeque<String>,
 ) -> bool {
     if graph.get_node(&skill.id).is_some() {
@@ -1461,17 +2093,31 @@ fn add_dependency_node(
 
     let meta = meta_cache.get(&skill.id);
     let mut provides = meta.map(|m| m.provides.clone()).unwrap_or_default();
-    if provides.is_empty() {
+    // A skill reached by its ID provides that ID as a capability
+    if !provides.iter().any(|cap| cap == fallback_capability) {
         provides.push(fallback_capability.to_string());
     }
 
     let requires = meta.map(|m| m.requires.clone()).unwrap_or_default();
+    let recommends = if follow_recommends {
+        meta.map(|m| m.recommends.clone()).unwrap_or_default()
+    } else {
+        vec![]
+    };
 
-    for required in &requires {
-        queue.push_back(required.clone());
+    for cap in requires.iter().chain(&recommends) {
+        queue.push_back(cap.clone());
     }
 
     graph.add_skill(skill.id.clone(), requires, provides);
+    graph.declare(&skill.id, DependencyKind::Recommends, recommends);
+    if let Some(meta) = meta {
+        graph.declare(
+            &skill.id,
+            DependencyKind::ConflictsWith,
+            meta.conflicts_with.clone(),
+        );
+    }
     true
 }
 
@@ -1539,14 +2185,62 @@ pub(crate) fn output_human(_ctx: &AppContext, result: &LoadResult, _args: &LoadA
         }
     }
 
+    if !result.resources.is_empty() {
+        println!();
+        println!("## Resources");
+        for resource in &result.resources {
+            match (&resource.content, resource.skipped) {
+                (Some(content), _) => {
+                    println!();
+                    println!("### {}", resource.path);
+                    println!();
+                    println!("````");
+                    println!("{}", content.trim_end());
+                    println!("````");
+                }
+                (None, reason) => println!(
+                    "- {} ({}, {})",
+                    resource.path,
+                    format_size(resource.size),
+                    reason.unwrap_or("not inlined")
+                ),
+            }
+        }
+    }
+
+    if !result.follow_ups.is_empty() {
+        println!();
+        println!("Next steps:");
+        for action in &result.follow_ups {
+            println!("  - {}", action.summary());
+        }
+    }
+
     // Footer with stats
     println!();
     println!(
-        "{} {} tokens | {} level",
+        "{} {} tokens ({}) | {} level",
         "─".repeat(40),
         disclosed.token_estimate,
+        result.tokenizer,
         disclosed.level.name()
     );
+    if let Some(budget) = &result.budget {
+        let mandatory = if budget.mandatory_kept.is_empty() {
+            "none".to_string()
+        } else {
+            budget.mandatory_kept.join(", ")
+        };
+        println!(
+            "budget: {}/{} tokens | {} slices kept, {} dropped | mandatory kept: {mandatory}",
+            budget.tokens_used, budget.max_tokens, budget.slices_included, budget.slices_dropped
+        );
+    }
+    if let Some(trial_id) = &result.trial_id {
+        println!(
+            "trial: {trial_id} (report with `ms trial report {trial_id} --outcome success|failure|neutral`)"
+        );
+    }
 
     Ok(())
 }
@@ -1572,13 +2266,15 @@ fn output_plain(result: &LoadResult) -> Result<()> {
     Ok(())
 }
 
-fn output_tsv(result: &LoadResult) -> Result<()> {
+fn output_table(result: &LoadResult, format: TableFormat) -> Result<()> {
     println!(
-        "{}\t{}\t{}\t{}",
-        result.skill_id,
-        result.name,
-        result.disclosed.level.name(),
-        result.disclosed.token_estimate
+        "{}",
+        format.row(&[
+            result.skill_id.clone(),
+            result.name.clone(),
+            result.disclosed.level.name().to_string(),
+            result.disclosed.token_estimate.to_string(),
+        ])
     );
     Ok(())
 }
@@ -1606,6 +2302,7 @@ pub(crate) fn build_robot_payload(result: &LoadResult, args: &LoadArgs) -> serde
             "name": result.name,
             "disclosure_level": disclosed.level.name(),
             "token_count": disclosed.token_estimate,
+            "tokenizer": result.tokenizer,
             "pack": pack_info,
             "content": disclosed.body,
             "frontmatter": {
@@ -1632,6 +2329,10 @@ pub(crate) fn build_robot_payload(result: &LoadResult, args: &LoadArgs) -> serde
                     "file_type": r.file_type,
                 })
             }).collect::<Vec<_>>(),
+            "follow_ups": result.follow_ups,
+            "budget": result.budget,
+            "trial_id": result.trial_id,
+            "resources": result.resources,
         },
         "warnings": result.warnings
     })
@@ -1668,6 +2369,7 @@ fn terminal_width() -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::tokenizer::HeuristicCounter;
 
     #[test]
     fn test_load_result_struct() {
@@ -1692,11 +2394,16 @@ mod tests {
                 token_estimate: 100,
                 slices_included: None,
             },
+            tokenizer: "heuristic",
             dependencies_loaded: vec!["dep1".to_string()],
             slices_included: None,
             inheritance_chain: vec!["test-skill".to_string()],
             included_from: vec![],
             warnings: vec![],
+            follow_ups: vec![],
+            budget: None,
+            trial_id: None,
+            resources: vec![],
         };
 
         assert_eq!(result.skill_id, "test-skill");
@@ -1746,11 +2453,16 @@ mod tests {
                 token_estimate: tokens,
                 slices_included: None,
             },
+            tokenizer: "heuristic",
             dependencies_loaded: vec![],
             slices_included: None,
             inheritance_chain: vec![name.to_string()],
             included_from: vec![],
             warnings: vec![],
+            follow_ups: vec![],
+            budget: None,
+            trial_id: None,
+            resources: vec![],
         }
     }
 
@@ -1904,6 +2616,45 @@ mod tests {
 
     // ── 13. test_load_deps_mode_conversion ──────────────────────────
 
+    #[test]
+    fn test_budgeted_render_and_report() {
+        let slice = |id: &str, title: &str, content: &str| SkillSlice {
+            id: id.to_string(),
+            slice_type: SliceType::Rule,
+            token_estimate: HeuristicCounter.count(content),
+            utility_score: 0.5,
+            coverage_group: None,
+            tags: vec![],
+            requires: vec![],
+            condition: None,
+            section_title: Some(title.to_string()),
+            content: content.to_string(),
+        };
+        let packed = MultiPackResult {
+            skills: vec![
+                SkillSlices {
+                    skill_id: "root".to_string(),
+                    slices: vec![slice("rule-1", "Rules", "Never unwrap.")],
+                },
+                SkillSlices {
+                    skill_id: "dep".to_string(),
+                    slices: vec![slice("rule-1", "Basics", "Use Result.")],
+                },
+            ],
+            total_tokens: 5,
+            dropped: 3,
+            mandatory_kept: vec!["root/policy-1".to_string()],
+        };
+        assert_eq!(
+            render_budgeted("root", &packed),
+            "## Rules\n\nNever unwrap.\n\n# dep\n\n## Basics\n\nUse Result."
+        );
+        let report = budget_report(4000, &packed);
+        assert_eq!(report.slices_included, 2);
+        assert_eq!(report.slices_dropped, 3);
+        assert_eq!(report.skills, vec!["root".to_string(), "dep".to_string()]);
+    }
+
     #[test]
     fn test_load_deps_mode_conversion() {
         let auto: DependencyLoadMode = DepsMode::Auto.into();
@@ -1912,6 +2663,8 @@ mod tests {
         assert!(matches!(off, DependencyLoadMode::Off));
         let full: DependencyLoadMode = DepsMode::Full.into();
         assert!(matches!(full, DependencyLoadMode::Full));
+        let required: DependencyLoadMode = DepsMode::RequiredOnly.into();
+        assert!(matches!(required, DependencyLoadMode::RequiredOnly));
     }
 
     // ── 14. test_load_should_use_rich_returns_bool ──────────────────
@@ -1,8 +1,8 @@
 //! ms mcp - MCP (Model Context Protocol) server mode
 //!
 //! Exposes ms functionality as an MCP server for tool-based integration
-//! with AI coding agents. Supports stdio transport (primary) and optional
-//! TCP transport.
+//! with AI coding agents. Supports stdio transport (primary) and an optional
+//! streamable-HTTP transport (`--tcp-port`, see [`http`]).
 //!
 //! # Output Safety
 //!
@@ -15,6 +15,7 @@
 //! See [`sanitize_mcp_output`] and [`validate_mcp_json`] for details.
 
 use std::io::{self, BufRead, Write};
+use std::net::IpAddr;
 
 use clap::{Args, Subcommand};
 use serde::{Deserialize, Serialize};
@@ -22,13 +23,33 @@ use serde_json::Value;
 use tracing::{debug, warn};
 
 use crate::app::{AppContext, StoreIdentity};
+use crate::bundler::{BundlePackage, BundleRegistry, InstallSource, check_dependencies};
+use crate::cli::commands::bundle::{
+    BundleInstall, BundleInstallArgs, BundleShowReport, install_bundle, trusted_verifier,
+};
+use crate::cli::commands::config::{MCP_WRITABLE_KEYS, write_key};
+use crate::cli::commands::load::load_slices;
+use crate::cli::commands::quality::quality_report;
+use crate::cli::commands::trial::{report_trial, start_trial};
 use crate::cli::output::OutputFormat;
 use crate::cli::output::emit_json;
-use crate::context::detector::ProjectDetector;
-use crate::core::spec_lens::parse_markdown;
+use crate::core::budget::effective_token_count;
+use crate::core::context_window::track_served;
+use crate::core::disclosure::DisclosureLevel;
+use crate::core::follow_up::{self, FollowUpAction, FollowUpKind};
+use crate::core::slice_query::{DEFAULT_TOP_K, SliceQuery};
+use crate::core::spec_lens::{compile_markdown, parse_markdown};
 use crate::error::{MsError, Result};
-use crate::lint::rules::all_rules;
 use crate::lint::{ValidationConfig, ValidationEngine};
+use crate::quality::trials::TrialOutcome;
+use crate::search::cursor::{query_hash, rank_order, score_key};
+use crate::search::{MAX_PAGE_SIZE, Pager, search_batch};
+use crate::security::encryption;
+use crate::storage::sqlite::{SkillListQuery, SkillListSort, SkillRecord};
+use crate::utils::path::expand_path;
+
+mod http;
+mod schema;
 
 /// MCP server protocol version
 const PROTOCOL_VERSION: &str = "2024-11-05";
@@ -36,6 +57,31 @@ const PROTOCOL_VERSION: &str = "2024-11-05";
 const SERVER_NAME: &str = "ms";
 /// Server version (from cargo)
 const SERVER_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// URI prefix of skill resources (`ms://skill/<id>`)
+const SKILL_URI_PREFIX: &str = "ms://skill/";
+/// Skills per `resources/list` page
+const RESOURCE_PAGE_SIZE: usize = 100;
+
+/// The client on the other end of a connection (stdio) or session (HTTP).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct ClientState {
+    /// `clientInfo.name` from `initialize`. Suggestions, cooldowns and
+    /// feedback are attributed to it, like `--agent-id` on the CLI.
+    agent_id: Option<String>,
+}
+
+impl ClientState {
+    fn from_initialize(params: &Value) -> Self {
+        Self {
+            agent_id: params
+                .pointer("/clientInfo/name")
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(str::to_string),
+        }
+    }
+}
 
 // ============================================================================
 // MCP Output Safety
@@ -210,7 +256,7 @@ pub struct McpArgs {
 
 #[derive(Subcommand, Debug)]
 pub enum McpCommand {
-    /// Start MCP server with stdio transport
+    /// Start MCP server (stdio, or HTTP with --tcp-port)
     Serve(ServeArgs),
     /// List available MCP tools
     Tools,
@@ -218,10 +264,23 @@ pub enum McpCommand {
 
 #[derive(Args, Debug)]
 pub struct ServeArgs {
-    /// Enable TCP transport on specified port (in addition to stdio)
-    #[arg(long)]
+    /// Serve over HTTP on this port instead of stdio (0 picks a free port)
+    #[arg(long, alias = "port")]
     pub tcp_port: Option<u16>,
 
+    /// Address the HTTP listener binds to
+    #[arg(long, default_value = "127.0.0.1", requires = "tcp_port")]
+    pub bind: IpAddr,
+
+    /// Bearer token HTTP clients must send (required for non-loopback binds)
+    #[arg(
+        long,
+        env = "MS_MCP_TOKEN",
+        hide_env_values = true,
+        requires = "tcp_port"
+    )]
+    pub auth_token: Option<String>,
+
     /// Enable debug logging to stderr
     #[arg(long)]
     pub debug: bool,
@@ -288,8 +347,9 @@ const PARSE_ERROR: i32 = -32700;
 const INVALID_REQUEST: i32 = -32600;
 const METHOD_NOT_FOUND: i32 = -32601;
 const INVALID_PARAMS: i32 = -32602;
-#[allow(dead_code)]
 const INTERNAL_ERROR: i32 = -32603;
+// MCP: the requested resource does not exist
+const RESOURCE_NOT_FOUND: i32 = -32002;
 
 // ============================================================================
 // MCP Protocol Types
@@ -298,6 +358,7 @@ const INTERNAL_ERROR: i32 = -32603;
 #[derive(Debug, Serialize)]
 struct ServerCapabilities {
     tools: ToolsCapability,
+    resources: ResourcesCapability,
 }
 
 #[derive(Debug, Serialize)]
@@ -306,6 +367,13 @@ struct ToolsCapability {
     list_changed: bool,
 }
 
+#[derive(Debug, Serialize)]
+struct ResourcesCapability {
+    subscribe: bool,
+    #[serde(rename = "listChanged")]
+    list_changed: bool,
+}
+
 #[derive(Debug, Serialize)]
 struct ServerInfo {
     name: String,
@@ -327,6 +395,44 @@ struct Tool {
     description: String,
     #[serde(rename = "inputSchema")]
     input_schema: Value,
+    annotations: ToolAnnotations,
+}
+
+/// Hints on what a tool call does to ms state, so clients can decide what
+/// needs confirmation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ToolAnnotations {
+    read_only_hint: bool,
+    destructive_hint: bool,
+    idempotent_hint: bool,
+}
+
+impl ToolAnnotations {
+    /// Only reads state.
+    const READ_ONLY: Self = Self {
+        read_only_hint: true,
+        destructive_hint: false,
+        idempotent_hint: true,
+    };
+
+    /// Records or updates state without removing or overwriting any.
+    const fn additive(idempotent: bool) -> Self {
+        Self {
+            read_only_hint: false,
+            destructive_hint: false,
+            idempotent_hint: idempotent,
+        }
+    }
+
+    /// May remove or overwrite existing state.
+    const fn destructive(idempotent: bool) -> Self {
+        Self {
+            read_only_hint: false,
+            destructive_hint: true,
+            idempotent_hint: idempotent,
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -339,6 +445,8 @@ struct ToolResult {
     content: Vec<ToolContent>,
     #[serde(rename = "isError", skip_serializing_if = "Option::is_none")]
     is_error: Option<bool>,
+    #[serde(rename = "_meta", skip_serializing_if = "Option::is_none")]
+    meta: Option<Value>,
 }
 
 #[derive(Debug, Serialize)]
@@ -363,6 +471,7 @@ impl ToolResult {
                 text: sanitized,
             }],
             is_error: None,
+            meta: None,
         }
     }
 
@@ -380,10 +489,94 @@ impl ToolResult {
                 text: sanitized,
             }],
             is_error: Some(true),
+            meta: None,
+        }
+    }
+
+    /// Attach result metadata (`_meta`), e.g. next-step hints.
+    fn with_meta(mut self, meta: Value) -> Self {
+        self.meta = Some(meta);
+        self
+    }
+
+    /// Add `warnings` to the result metadata, keeping what is there.
+    fn with_warnings(mut self, warnings: Vec<String>) -> Self {
+        if warnings.is_empty() {
+            return self;
+        }
+        let mut meta = match self.meta.take() {
+            Some(Value::Object(meta)) => meta,
+            _ => serde_json::Map::new(),
+        };
+        meta.insert("warnings".to_string(), Value::from(warnings));
+        self.meta = Some(Value::Object(meta));
+        self
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Resource {
+    uri: String,
+    name: String,
+    description: String,
+    #[serde(rename = "mimeType")]
+    mime_type: String,
+    annotations: ResourceAnnotations,
+}
+
+#[derive(Debug, Serialize)]
+struct ResourceAnnotations {
+    /// Layer the skill was indexed from (base, global, project, ...)
+    layer: String,
+    #[serde(rename = "lastModified")]
+    last_modified: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ResourcesListResult {
+    resources: Vec<Resource>,
+    #[serde(rename = "nextCursor", skip_serializing_if = "Option::is_none")]
+    next_cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ResourceTemplate {
+    #[serde(rename = "uriTemplate")]
+    uri_template: String,
+    name: String,
+    description: String,
+    #[serde(rename = "mimeType")]
+    mime_type: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ResourceContents {
+    uri: String,
+    #[serde(rename = "mimeType")]
+    mime_type: String,
+    text: String,
+}
+
+impl Resource {
+    /// Resource for an indexed skill, sanitizing any ANSI codes.
+    fn for_skill(skill: &SkillRecord) -> Self {
+        Self {
+            uri: skill_uri(&skill.id),
+            name: sanitize_mcp_output(&skill.name),
+            description: sanitize_mcp_output(&skill.description),
+            mime_type: "text/markdown".to_string(),
+            annotations: ResourceAnnotations {
+                layer: skill.source_layer.clone(),
+                last_modified: skill.modified_at.clone(),
+            },
         }
     }
 }
 
+fn skill_uri(skill_id: &str) -> String {
+    format!("{SKILL_URI_PREFIX}{skill_id}")
+}
+
 // ============================================================================
 // Tool Definitions
 // ============================================================================
@@ -392,7 +585,9 @@ fn define_tools() -> Vec<Tool> {
     vec![
         Tool {
             name: "search".to_string(),
-            description: "Search for skills using BM25 full-text search".to_string(),
+            description: "Search for skills using BM25 full-text search; pass next_cursor \
+                          back as cursor for the following page"
+                .to_string(),
             input_schema: serde_json::json!({
                 "type": "object",
                 "properties": {
@@ -402,12 +597,43 @@ fn define_tools() -> Vec<Tool> {
                     },
                     "limit": {
                         "type": "integer",
-                        "description": "Maximum number of results (default: 20)",
-                        "default": 20
+                        "description": "Page size (default: 20)",
+                        "default": 20,
+                        "minimum": 1,
+                        "maximum": MAX_PAGE_SIZE
+                    },
+                    "cursor": {
+                        "type": "string",
+                        "description": "next_cursor of the previous page (same query and limit)"
                     }
                 },
                 "required": ["query"]
             }),
+            annotations: ToolAnnotations::READ_ONLY,
+        },
+        Tool {
+            name: "search_batch".to_string(),
+            description: "Run several BM25 searches in one call; returns per-query results \
+                          and a merged, de-duplicated ranking"
+                .to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "queries": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Search queries (at most search.batch.max_queries)"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum results per query (default: 10)",
+                        "default": 10,
+                        "minimum": 1
+                    }
+                },
+                "required": ["queries"]
+            }),
+            annotations: ToolAnnotations::READ_ONLY,
         },
         Tool {
             name: "load".to_string(),
@@ -423,10 +649,68 @@ fn define_tools() -> Vec<Tool> {
                         "type": "boolean",
                         "description": "Include full skill content",
                         "default": false
+                    },
+                    "model": {
+                        "type": "string",
+                        "description": "Model consuming the content, for context window warnings"
+                    },
+                    "session_id": {
+                        "type": "string",
+                        "description": "Agent session id; served tokens are summed across loads"
+                    },
+                    "trial": {
+                        "type": "boolean",
+                        "description": "Open a trial for this load; report it with trial_report",
+                        "default": false
+                    }
+                },
+                "required": ["skill"]
+            }),
+            annotations: ToolAnnotations::additive(false),
+        },
+        Tool {
+            name: "load_slices".to_string(),
+            description: "Load only the slices of a skill that match a need or selectors"
+                .to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "skill": {
+                        "type": "string",
+                        "description": "Skill ID or name"
+                    },
+                    "need": {
+                        "type": "string",
+                        "description": "What the slices should cover; ranked by BM25 over block content, then section titles"
+                    },
+                    "selectors": {
+                        "type": "array",
+                        "items": {"type": "string"},
+                        "description": "Block ids, section ids or section titles to load instead of a need; use a slice's previous/next ids to fetch its neighbours"
+                    },
+                    "top_k": {
+                        "type": "integer",
+                        "description": "Most slices to return for a need",
+                        "default": DEFAULT_TOP_K,
+                        "minimum": 1
+                    },
+                    "max_tokens": {
+                        "type": "integer",
+                        "description": "Token cap on the returned slices",
+                        "minimum": 1
+                    },
+                    "model": {
+                        "type": "string",
+                        "description": "Model consuming the content, for context window warnings"
+                    },
+                    "session_id": {
+                        "type": "string",
+                        "description": "Agent session id; served tokens are summed across loads"
                     }
                 },
                 "required": ["skill"]
             }),
+            annotations: ToolAnnotations::additive(false),
         },
         Tool {
             name: "evidence".to_string(),
@@ -445,25 +729,36 @@ fn define_tools() -> Vec<Tool> {
                 },
                 "required": ["skill"]
             }),
+            annotations: ToolAnnotations::READ_ONLY,
         },
         Tool {
             name: "list".to_string(),
-            description: "List all indexed skills".to_string(),
+            description: "List all indexed skills, most recently modified first; pass \
+                          next_cursor back as cursor for the following page"
+                .to_string(),
             input_schema: serde_json::json!({
                 "type": "object",
                 "properties": {
                     "limit": {
                         "type": "integer",
-                        "description": "Maximum number of results",
-                        "default": 50
+                        "description": "Page size",
+                        "default": 50,
+                        "minimum": 1,
+                        "maximum": MAX_PAGE_SIZE
                     },
                     "offset": {
                         "type": "integer",
-                        "description": "Number of results to skip",
-                        "default": 0
+                        "description": "Number of results to skip (not with cursor)",
+                        "default": 0,
+                        "minimum": 0
+                    },
+                    "cursor": {
+                        "type": "string",
+                        "description": "next_cursor of the previous page (same limit)"
                     }
                 }
             }),
+            annotations: ToolAnnotations::READ_ONLY,
         },
         Tool {
             name: "show".to_string(),
@@ -479,10 +774,16 @@ fn define_tools() -> Vec<Tool> {
                         "type": "boolean",
                         "description": "Show full skill content",
                         "default": false
+                    },
+                    "quality": {
+                        "type": "boolean",
+                        "description": "Recompute the quality score and explain each component",
+                        "default": false
                     }
                 },
                 "required": ["skill"]
             }),
+            annotations: ToolAnnotations::READ_ONLY,
         },
         Tool {
             name: "doctor".to_string(),
@@ -497,6 +798,7 @@ fn define_tools() -> Vec<Tool> {
                     }
                 }
             }),
+            annotations: ToolAnnotations::destructive(true),
         },
         Tool {
             name: "lint".to_string(),
@@ -526,10 +828,11 @@ fn define_tools() -> Vec<Tool> {
                     }
                 }
             }),
+            annotations: ToolAnnotations::READ_ONLY,
         },
         Tool {
             name: "suggest".to_string(),
-            description: "Get context-aware skill suggestions based on working directory"
+            description: "Get context-aware skill suggestions based on working directory and, optionally, a task description"
                 .to_string(),
             input_schema: serde_json::json!({
                 "type": "object",
@@ -538,23 +841,31 @@ fn define_tools() -> Vec<Tool> {
                         "type": "string",
                         "description": "Working directory path for context detection"
                     },
+                    "task": {
+                        "type": "string",
+                        "description": "What the agent is about to do; relevant skills are ranked alongside the project context, ignoring cooldowns"
+                    },
                     "limit": {
                         "type": "integer",
                         "description": "Maximum suggestions (default: 5)",
-                        "default": 5
+                        "default": 5,
+                        "minimum": 1
                     },
                     "explain": {
                         "type": "boolean",
-                        "description": "Include explanation for each suggestion",
+                        "description": "Include each suggestion's score breakdown as a `signals` array",
                         "default": false
                     },
                     "threshold": {
                         "type": "number",
                         "description": "Minimum relevance score (0.0-1.0)",
-                        "default": 0.3
+                        "default": 0.3,
+                        "minimum": 0.0,
+                        "maximum": 1.0
                     }
                 }
             }),
+            annotations: ToolAnnotations::additive(false),
         },
         Tool {
             name: "feedback".to_string(),
@@ -581,6 +892,31 @@ fn define_tools() -> Vec<Tool> {
                 },
                 "required": ["skill_id", "helpful"]
             }),
+            annotations: ToolAnnotations::additive(false),
+        },
+        Tool {
+            name: "trial_report".to_string(),
+            description: "Report the outcome of a skill loaded with trial: true".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "trial_id": {
+                        "type": "string",
+                        "description": "Trial id returned by load"
+                    },
+                    "outcome": {
+                        "type": "string",
+                        "enum": ["success", "failure", "neutral"],
+                        "description": "How the trial went"
+                    },
+                    "notes": {
+                        "type": "string",
+                        "description": "Optional notes on what helped or got in the way"
+                    }
+                },
+                "required": ["trial_id", "outcome"]
+            }),
+            annotations: ToolAnnotations::additive(false),
         },
         Tool {
             name: "index".to_string(),
@@ -600,6 +936,7 @@ fn define_tools() -> Vec<Tool> {
                     }
                 }
             }),
+            annotations: ToolAnnotations::destructive(true),
         },
         Tool {
             name: "validate".to_string(),
@@ -617,10 +954,12 @@ fn define_tools() -> Vec<Tool> {
                     }
                 }
             }),
+            annotations: ToolAnnotations::READ_ONLY,
         },
         Tool {
             name: "config".to_string(),
-            description: "Get or set ms configuration values".to_string(),
+            description: "Get ms configuration values, or set search weights and suggestion limits"
+                .to_string(),
             input_schema: serde_json::json!({
                 "type": "object",
                 "properties": {
@@ -636,10 +975,42 @@ fn define_tools() -> Vec<Tool> {
                     },
                     "value": {
                         "type": "string",
-                        "description": "Configuration value (for set)"
+                        "description": "Configuration value (for set); coerced to the key's type"
                     }
                 }
             }),
+            annotations: ToolAnnotations::destructive(true),
+        },
+        Tool {
+            name: "bundle".to_string(),
+            description: "List installed bundles, or show, verify, and install a bundle file"
+                .to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "action": {
+                        "type": "string",
+                        "enum": ["list", "show", "install", "verify"],
+                        "description": "Action to perform"
+                    },
+                    "path": {
+                        "type": "string",
+                        "description": "Path to a .msb bundle file (for show/install/verify)"
+                    },
+                    "skills": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Only install these skills from the bundle"
+                    },
+                    "force": {
+                        "type": "boolean",
+                        "description": "Overwrite existing skills (conflicting files are backed up)",
+                        "default": false
+                    }
+                },
+                "required": ["action"]
+            }),
+            annotations: ToolAnnotations::destructive(false),
         },
     ]
 }
@@ -676,7 +1047,12 @@ fn run_serve(ctx: &AppContext, args: &ServeArgs) -> Result<()> {
     let debug = args.debug;
 
     if debug {
-        eprintln!("[ms-mcp] Starting MCP server (stdio mode)");
+        let mode = if args.tcp_port.is_some() {
+            "http"
+        } else {
+            "stdio"
+        };
+        eprintln!("[ms-mcp] Starting MCP server ({mode} mode)");
         eprintln!("[ms-mcp] Server: {SERVER_NAME} v{SERVER_VERSION}");
         eprintln!("[ms-mcp] Protocol: {PROTOCOL_VERSION}");
     }
@@ -696,7 +1072,10 @@ fn run_serve(ctx: &AppContext, args: &ServeArgs) -> Result<()> {
     // store if the on-disk state directory is rebuilt/replaced underneath it
     // while it runs (issue #135). The clone shares the same open `Arc` handles
     // until the first reopen swaps in fresh ones.
-    run_stdio_server(ctx.clone(), debug)
+    match args.tcp_port {
+        Some(port) => http::serve(ctx.clone(), args.bind, port, args.auth_token.clone(), debug),
+        None => run_stdio_server(ctx.clone(), debug),
+    }
 }
 
 fn run_stdio_server(mut ctx: AppContext, debug: bool) -> Result<()> {
@@ -707,6 +1086,7 @@ fn run_stdio_server(mut ctx: AppContext, debug: bool) -> Result<()> {
     // each request so a rebuild that renames the state dir out from under us is
     // detected and the DB + search index are reopened (issue #135).
     let mut opened = ctx.store_identity();
+    let mut client = ClientState::default();
 
     for line in stdin.lock().lines() {
         let line = match line {
@@ -733,7 +1113,7 @@ fn run_stdio_server(mut ctx: AppContext, debug: bool) -> Result<()> {
         maybe_reopen_stores(&mut ctx, &mut opened, debug);
 
         // Handle request - returns None for notifications (no response needed)
-        if let Some(response) = handle_request(&ctx, &line, debug) {
+        if let Some(response) = handle_request(&ctx, &mut client, &line, debug) {
             // CRITICAL: Use safe serialization to ensure no ANSI codes leak through
             let response_json = serialize_response_safe(&response);
 
@@ -804,7 +1184,12 @@ fn maybe_reopen_stores(ctx: &mut AppContext, opened: &mut StoreIdentity, debug:
     }
 }
 
-fn handle_request(ctx: &AppContext, line: &str, debug: bool) -> Option<JsonRpcResponse> {
+fn handle_request(
+    ctx: &AppContext,
+    client: &mut ClientState,
+    line: &str,
+    debug: bool,
+) -> Option<JsonRpcResponse> {
     // Parse JSON-RPC request
     let request: JsonRpcRequest = match serde_json::from_str(line) {
         Ok(r) => r,
@@ -830,21 +1215,21 @@ fn handle_request(ctx: &AppContext, line: &str, debug: bool) -> Option<JsonRpcRe
 
     // Dispatch method
     match request.method.as_str() {
-        "initialize" => Some(handle_initialize(request.id, &request.params)),
+        "initialize" => Some(handle_initialize(request.id, &request.params, client)),
         "initialized" | "notifications/initialized" => handle_initialized(request.id),
         "tools/list" => Some(handle_tools_list(request.id)),
-        "tools/call" => Some(handle_tools_call(ctx, request.id, &request.params, debug)),
-        "ping" => Some(handle_ping(request.id)),
-        "shutdown" => Some(handle_shutdown(request.id)),
-        // Return empty results for resource endpoints we don't support
-        "resources/list" => Some(JsonRpcResponse::success(
-            request.id,
-            serde_json::json!({"resources": []}),
-        )),
-        "resources/templates/list" => Some(JsonRpcResponse::success(
+        "tools/call" => Some(handle_tools_call(
+            ctx,
+            client,
             request.id,
-            serde_json::json!({"resourceTemplates": []}),
+            &request.params,
+            debug,
         )),
+        "ping" => Some(handle_ping(request.id)),
+        "shutdown" => Some(handle_shutdown(request.id)),
+        "resources/list" => Some(handle_resources_list(ctx, request.id, &request.params)),
+        "resources/read" => Some(handle_resources_read(ctx, request.id, &request.params)),
+        "resources/templates/list" => Some(handle_resource_templates_list(request.id)),
         _ => {
             // JSON-RPC 2.0: notifications (no id) MUST NOT receive a response
             if request.id.is_none() {
@@ -861,13 +1246,22 @@ fn handle_request(ctx: &AppContext, line: &str, debug: bool) -> Option<JsonRpcRe
     }
 }
 
-fn handle_initialize(id: Option<Value>, _params: &Value) -> JsonRpcResponse {
+fn handle_initialize(
+    id: Option<Value>,
+    params: &Value,
+    client: &mut ClientState,
+) -> JsonRpcResponse {
+    *client = ClientState::from_initialize(params);
     let result = InitializeResult {
         protocol_version: PROTOCOL_VERSION.to_string(),
         capabilities: ServerCapabilities {
             tools: ToolsCapability {
                 list_changed: false,
             },
+            resources: ResourcesCapability {
+                subscribe: false,
+                list_changed: false,
+            },
         },
         server_info: ServerInfo {
             name: SERVER_NAME.to_string(),
@@ -890,8 +1284,105 @@ fn handle_tools_list(id: Option<Value>) -> JsonRpcResponse {
     JsonRpcResponse::success(id, serde_json::to_value(result).unwrap())
 }
 
+/// One page of skill resources in id order. The cursor is the last id of
+/// the previous page; `include_deprecated` (an ms extension) lists
+/// deprecated skills too.
+fn handle_resources_list(ctx: &AppContext, id: Option<Value>, params: &Value) -> JsonRpcResponse {
+    let cursor = params.get("cursor").and_then(Value::as_str);
+    let include_deprecated = params
+        .get("include_deprecated")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+
+    // One extra row tells whether another page follows
+    let mut skills =
+        match ctx
+            .db
+            .list_skills_after(cursor, RESOURCE_PAGE_SIZE + 1, include_deprecated)
+        {
+            Ok(skills) => skills,
+            Err(e) => {
+                return JsonRpcResponse::error(id, INTERNAL_ERROR, e.to_string(), None);
+            }
+        };
+    let next_cursor = if skills.len() > RESOURCE_PAGE_SIZE {
+        skills.truncate(RESOURCE_PAGE_SIZE);
+        skills.last().map(|skill| skill.id.clone())
+    } else {
+        None
+    };
+    let result = ResourcesListResult {
+        resources: skills.iter().map(Resource::for_skill).collect(),
+        next_cursor,
+    };
+    JsonRpcResponse::success(id, serde_json::to_value(result).unwrap())
+}
+
+fn handle_resources_read(ctx: &AppContext, id: Option<Value>, params: &Value) -> JsonRpcResponse {
+    let Some(uri) = params.get("uri").and_then(Value::as_str) else {
+        return JsonRpcResponse::error(
+            id,
+            INVALID_PARAMS,
+            "Missing required parameter: uri".to_string(),
+            None,
+        );
+    };
+    let not_found = |id| {
+        JsonRpcResponse::error(
+            id,
+            RESOURCE_NOT_FOUND,
+            format!("Resource not found: {uri}"),
+            Some(serde_json::json!({ "uri": uri })),
+        )
+    };
+    let Some(skill_ref) = uri
+        .strip_prefix(SKILL_URI_PREFIX)
+        .filter(|skill_ref| !skill_ref.is_empty())
+    else {
+        return not_found(id);
+    };
+
+    let skill = match ctx.resolve_skill(skill_ref) {
+        Ok(skill) => skill,
+        Err(MsError::SkillNotFound(_)) => return not_found(id),
+        Err(e) => return JsonRpcResponse::error(id, INTERNAL_ERROR, e.to_string(), None),
+    };
+    match skill_markdown(&skill) {
+        Ok(text) => {
+            let contents = ResourceContents {
+                uri: uri.to_string(),
+                mime_type: "text/markdown".to_string(),
+                // CRITICAL: same sanitization as tool results
+                text: sanitize_mcp_output(&text),
+            };
+            JsonRpcResponse::success(id, serde_json::json!({ "contents": [contents] }))
+        }
+        Err(e) => JsonRpcResponse::error(id, INTERNAL_ERROR, e.to_string(), None),
+    }
+}
+
+fn handle_resource_templates_list(id: Option<Value>) -> JsonRpcResponse {
+    let template = ResourceTemplate {
+        uri_template: format!("{SKILL_URI_PREFIX}{{id}}"),
+        name: "Skill".to_string(),
+        description: "A skill by ID or alias, compiled to markdown".to_string(),
+        mime_type: "text/markdown".to_string(),
+    };
+    JsonRpcResponse::success(id, serde_json::json!({ "resourceTemplates": [template] }))
+}
+
+/// A skill's spec compiled to markdown, with sealed bodies revealed.
+fn skill_markdown(skill: &SkillRecord) -> Result<String> {
+    let mut spec = parse_markdown(&encryption::reveal_body(&skill.id, &skill.body)?)?;
+    spec.metadata.id.clone_from(&skill.id);
+    spec.metadata.name.clone_from(&skill.name);
+    spec.metadata.description.clone_from(&skill.description);
+    Ok(compile_markdown(&spec))
+}
+
 fn handle_tools_call(
     ctx: &AppContext,
+    client: &ClientState,
     id: Option<Value>,
     params: &Value,
     debug: bool,
@@ -918,30 +1409,79 @@ fn handle_tools_call(
         eprintln!("[ms-mcp] Calling tool: {name} with {arguments:?}");
     }
 
+    let warnings = match check_arguments(name, &arguments) {
+        Ok(warnings) => warnings,
+        Err((message, data)) => {
+            return JsonRpcResponse::error(id, INVALID_PARAMS, message, Some(data));
+        }
+    };
+
     // Dispatch to tool handler
     let result = match name {
         "search" => handle_tool_search(ctx, &arguments),
+        "search_batch" => handle_tool_search_batch(ctx, &arguments),
         "load" => handle_tool_load(ctx, &arguments),
+        "load_slices" => handle_tool_load_slices(ctx, &arguments),
         "evidence" => handle_tool_evidence(ctx, &arguments),
         "list" => handle_tool_list(ctx, &arguments),
         "show" => handle_tool_show(ctx, &arguments),
         "doctor" => handle_tool_doctor(ctx, &arguments),
         "lint" => handle_tool_lint(ctx, &arguments),
-        "suggest" => handle_tool_suggest(ctx, &arguments),
-        "feedback" => handle_tool_feedback(ctx, &arguments),
+        "suggest" => handle_tool_suggest(ctx, client, &arguments),
+        "feedback" => handle_tool_feedback(ctx, client, &arguments),
+        "trial_report" => handle_tool_trial_report(ctx, &arguments),
         "index" => handle_tool_index(ctx, &arguments),
         "validate" => handle_tool_validate(ctx, &arguments),
         "config" => handle_tool_config(ctx, &arguments),
+        "bundle" => handle_tool_bundle(ctx, &arguments),
         _ => Err(MsError::ValidationFailed(format!("Unknown tool: {name}"))),
     };
 
-    match result {
-        Ok(tool_result) => JsonRpcResponse::success(id, serde_json::to_value(tool_result).unwrap()),
+    let tool_result = match result {
+        Ok(tool_result) => tool_result,
         Err(e) => {
-            let tool_result = ToolResult::error(e.to_string());
-            JsonRpcResponse::success(id, serde_json::to_value(tool_result).unwrap())
+            // Same payload as robot-mode CLI errors, so agents can branch on `code`
+            let payload =
+                serde_json::to_string_pretty(&e.to_robot_json()).unwrap_or_else(|_| e.to_string());
+            ToolResult::error(payload)
         }
+    };
+    let tool_result = tool_result.with_warnings(warnings);
+    JsonRpcResponse::success(id, serde_json::to_value(tool_result).unwrap())
+}
+
+/// Check tool call arguments against the tool's input schema before
+/// dispatch; unknown tools are left to the dispatcher. Returns warnings for
+/// properties the schema does not declare (ignored, so older clients keep
+/// working), or the `INVALID_PARAMS` message and data listing every
+/// violation by JSON pointer.
+fn check_arguments(
+    name: &str,
+    arguments: &Value,
+) -> std::result::Result<Vec<String>, (String, Value)> {
+    let Some(tool) = define_tools().into_iter().find(|tool| tool.name == name) else {
+        return Ok(Vec::new());
+    };
+    let validation = schema::validate(&tool.input_schema, arguments);
+    if !validation.violations.is_empty() {
+        let summary: Vec<String> = validation
+            .violations
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        return Err((
+            format!("Invalid arguments for {name}: {}", summary.join("; ")),
+            serde_json::json!({
+                "tool": name,
+                "violations": validation.violations,
+            }),
+        ));
     }
+    Ok(validation
+        .unknown
+        .iter()
+        .map(|pointer| format!("{pointer}: unknown property, ignored"))
+        .collect())
 }
 
 fn handle_ping(id: Option<Value>) -> JsonRpcResponse {
@@ -956,21 +1496,6 @@ fn handle_shutdown(id: Option<Value>) -> JsonRpcResponse {
 // Helpers
 // ============================================================================
 
-/// Expand tilde in path strings to home directory
-fn expand_tilde(input: &str) -> std::path::PathBuf {
-    if let Some(stripped) = input.strip_prefix("~/") {
-        if let Some(home) = dirs::home_dir() {
-            return home.join(stripped);
-        }
-    }
-    if input == "~" {
-        if let Some(home) = dirs::home_dir() {
-            return home;
-        }
-    }
-    std::path::PathBuf::from(input)
-}
-
 // ============================================================================
 // Tool Handlers
 // ============================================================================
@@ -984,21 +1509,76 @@ fn handle_tool_search(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
         .get("limit")
         .and_then(serde_json::Value::as_u64)
         .unwrap_or(20) as usize;
+    let pager = Pager::new(
+        query_hash(&["mcp:search", query, &limit.to_string()]),
+        ctx.search.generation()?,
+        limit,
+        args.get("cursor").and_then(Value::as_str),
+    )?;
+
+    // Use BM25 search via Tantivy; resuming ranks everything up to the cursor
+    let window = if pager.is_resuming() {
+        ctx.search.num_docs().max(1) as usize
+    } else {
+        pager.window()
+    };
+    let mut results = ctx.search.search(query, window)?;
+    results.sort_by(|a, b| rank_order((a.score, &a.skill_id), (b.score, &b.skill_id)));
+    let (results, next_cursor) =
+        pager.page(results, |r| (score_key(r.score), r.skill_id.as_str()))?;
 
-    // Use BM25 search via Tantivy
-    let results = ctx.search.search(query, limit)?;
-
-    let output = serde_json::json!({
+    let mut output = serde_json::json!({
         "query": query,
         "count": results.len(),
         "results": results.iter().map(|r| {
             serde_json::json!({
                 "id": r.skill_id,
                 "score": r.score,
+                "snippets": r.snippets,
             })
         }).collect::<Vec<_>>()
     });
+    if let Some(cursor) = next_cursor {
+        output["next_cursor"] = cursor.into();
+    }
+
+    Ok(ToolResult::text(serde_json::to_string_pretty(&output)?))
+}
+
+fn handle_tool_search_batch(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
+    let mut queries: Vec<String> = args
+        .get("queries")
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| {
+            MsError::ValidationFailed("Missing required parameter: queries".to_string())
+        })?
+        .iter()
+        .filter_map(|v| v.as_str())
+        .map(str::trim)
+        .filter(|q| !q.is_empty())
+        .map(str::to_string)
+        .collect();
+    let mut seen = std::collections::HashSet::new();
+    queries.retain(|query| seen.insert(query.clone()));
 
+    let limit = args
+        .get("limit")
+        .and_then(serde_json::Value::as_u64)
+        .unwrap_or(10) as usize;
+
+    // One shared index reader for every query
+    let batch = search_batch(
+        ctx.search.as_ref(),
+        &queries,
+        limit,
+        &ctx.config.search.batch,
+    )?;
+
+    let output = serde_json::json!({
+        "count": batch.queries.len(),
+        "queries": batch.queries,
+        "merged": batch.merged,
+    });
     Ok(ToolResult::text(serde_json::to_string_pretty(&output)?))
 }
 
@@ -1012,18 +1592,14 @@ fn handle_tool_load(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
         .and_then(serde_json::Value::as_bool)
         .unwrap_or(false);
 
-    // Look up skill
-    let skill = ctx
-        .db
-        .get_skill(skill_id)?
-        .ok_or_else(|| MsError::SkillNotFound(skill_id.to_string()))?;
+    let skill = ctx.resolve_skill(skill_id)?;
 
-    let output = if full {
+    let mut output = if full {
         serde_json::json!({
             "skill_id": skill.id,
             "name": skill.name,
             "description": skill.description,
-            "content": skill.body,
+            "content": encryption::reveal_body(&skill.id, &skill.body)?,
             "layer": skill.source_layer,
             "quality_score": skill.quality_score,
         })
@@ -1036,13 +1612,134 @@ fn handle_tool_load(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
         })
     };
 
+    let follow_ups = follow_up::actions_for(
+        &skill.id,
+        &follow_up::from_metadata_json(&skill.metadata_json),
+    );
+    if !follow_ups.is_empty() {
+        output["follow_ups"] = serde_json::to_value(&follow_ups)?;
+    }
+
+    let served_tokens = if full {
+        effective_token_count(&skill).0
+    } else {
+        ctx.token_counter()?
+            .count(&format!("{}\n{}", skill.name, skill.description))
+    };
+    if let Some(warning) = track_served(
+        &ctx.ms_root,
+        &ctx.config.context_window,
+        args.get("model").and_then(|v| v.as_str()),
+        args.get("session_id").and_then(|v| v.as_str()),
+        &[(skill.id.clone(), served_tokens)],
+    ) {
+        output["warnings"] = serde_json::json!([warning.to_string()]);
+        output["context_window"] = serde_json::to_value(&warning)?;
+    }
+
+    if args
+        .get("trial")
+        .and_then(serde_json::Value::as_bool)
+        .unwrap_or(false)
+    {
+        let level = if full {
+            DisclosureLevel::Full
+        } else {
+            DisclosureLevel::Minimal
+        };
+        output["trial_id"] = start_trial(ctx, &skill.id, level)?.into();
+    }
+
+    let result = ToolResult::text(serde_json::to_string_pretty(&output)?);
+    if follow_ups.is_empty() {
+        return Ok(result);
+    }
+    Ok(result.with_meta(serde_json::json!({ "next_steps": next_step_hints(&follow_ups) })))
+}
+
+fn handle_tool_load_slices(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
+    let skill_ref = args.get("skill").and_then(|v| v.as_str()).ok_or_else(|| {
+        MsError::ValidationFailed("Missing required parameter: skill".to_string())
+    })?;
+    let query = SliceQuery {
+        need: args
+            .get("need")
+            .and_then(|v| v.as_str())
+            .map(str::to_string),
+        selectors: args
+            .get("selectors")
+            .and_then(|v| v.as_array())
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|v| v.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        top_k: args
+            .get("top_k")
+            .and_then(serde_json::Value::as_u64)
+            .map_or(DEFAULT_TOP_K, |top_k| top_k as usize),
+        max_tokens: args
+            .get("max_tokens")
+            .and_then(serde_json::Value::as_u64)
+            .map(|max_tokens| max_tokens as usize),
+    };
+
+    let (skill, selection) = load_slices(ctx, skill_ref, &query)?;
+    let mut output = serde_json::json!({
+        "skill_id": skill.id,
+        "name": skill.name,
+        "selection": selection,
+    });
+    if let Some(warning) = track_served(
+        &ctx.ms_root,
+        &ctx.config.context_window,
+        args.get("model").and_then(|v| v.as_str()),
+        args.get("session_id").and_then(|v| v.as_str()),
+        &[(skill.id.clone(), selection.total_tokens)],
+    ) {
+        output["warnings"] = serde_json::json!([warning.to_string()]);
+        output["context_window"] = serde_json::to_value(&warning)?;
+    }
     Ok(ToolResult::text(serde_json::to_string_pretty(&output)?))
 }
 
+/// Follow-ups as MCP next-step hints: the tool call to make, or the command
+/// to run for `verify`. Hints only; the server never acts on them.
+fn next_step_hints(follow_ups: &[FollowUpAction]) -> Vec<Value> {
+    follow_ups
+        .iter()
+        .map(|action| {
+            let mut hint = serde_json::json!({
+                "kind": action.kind,
+                "description": action.summary(),
+            });
+            match action.kind {
+                FollowUpKind::Load => {
+                    hint["tool"] = "load".into();
+                    hint["arguments"] = serde_json::json!({ "skill": action.target });
+                }
+                FollowUpKind::Feedback => {
+                    hint["tool"] = "feedback".into();
+                    hint["arguments"] = serde_json::json!({ "skill_id": action.target });
+                }
+                FollowUpKind::Verify => hint["command"] = action.target.clone().into(),
+            }
+            if let Some(condition) = &action.condition {
+                hint["condition"] = condition.clone().into();
+            }
+            hint
+        })
+        .collect()
+}
+
 fn handle_tool_evidence(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
-    let skill_id = args.get("skill").and_then(|v| v.as_str()).ok_or_else(|| {
+    let skill = args.get("skill").and_then(|v| v.as_str()).ok_or_else(|| {
         MsError::ValidationFailed("Missing required parameter: skill".to_string())
     })?;
+    let skill_id = ctx.canonical_skill_id(skill)?;
+    let skill_id = skill_id.as_str();
 
     let rule_id = args.get("rule_id").and_then(|v| v.as_str());
 
@@ -1103,12 +1800,31 @@ fn handle_tool_list(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
         .get("offset")
         .and_then(serde_json::Value::as_u64)
         .unwrap_or(0) as usize;
+    let cursor = args.get("cursor").and_then(Value::as_str);
+    if cursor.is_some() && offset > 0 {
+        return Err(MsError::ValidationFailed(
+            "offset cannot be combined with cursor".to_string(),
+        ));
+    }
+    let pager = Pager::new(
+        query_hash(&["mcp:list", &limit.to_string()]),
+        ctx.search.generation()?,
+        limit,
+        cursor,
+    )?;
+
+    // Deprecated skills included, newest first, ties by id
+    let skills = ctx.db.list_skills_filtered(&SkillListQuery {
+        sort: SkillListSort::Updated,
+        limit: pager.window().min(i64::MAX as usize),
+        offset,
+        ..SkillListQuery::default()
+    })?;
+    let (skills, next_cursor) = pager.page(skills, |s| (s.modified_at.clone(), s.id.as_str()))?;
 
-    let all_skills = ctx.db.list_skills(limit, offset)?;
-
-    let output = serde_json::json!({
-        "count": all_skills.len(),
-        "skills": all_skills.iter().map(|s| {
+    let mut output = serde_json::json!({
+        "count": skills.len(),
+        "skills": skills.iter().map(|s| {
             serde_json::json!({
                 "id": s.id,
                 "name": s.name,
@@ -1117,6 +1833,9 @@ fn handle_tool_list(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
             })
         }).collect::<Vec<_>>()
     });
+    if let Some(cursor) = next_cursor {
+        output["next_cursor"] = cursor.into();
+    }
 
     Ok(ToolResult::text(serde_json::to_string_pretty(&output)?))
 }
@@ -1131,12 +1850,14 @@ fn handle_tool_show(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
         .and_then(serde_json::Value::as_bool)
         .unwrap_or(false);
 
-    let skill = ctx
-        .db
-        .get_skill(skill_id)?
-        .ok_or_else(|| MsError::SkillNotFound(skill_id.to_string()))?;
+    let quality = args
+        .get("quality")
+        .and_then(serde_json::Value::as_bool)
+        .unwrap_or(false);
 
-    let output = if full {
+    let skill = ctx.resolve_skill(skill_id)?;
+
+    let mut output = if full {
         serde_json::json!({
             "id": skill.id,
             "name": skill.name,
@@ -1144,7 +1865,7 @@ fn handle_tool_show(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
             "layer": skill.source_layer,
             "quality_score": skill.quality_score,
             "is_deprecated": skill.is_deprecated,
-            "content": skill.body,
+            "content": encryption::reveal_body(&skill.id, &skill.body)?,
         })
     } else {
         serde_json::json!({
@@ -1154,6 +1875,9 @@ fn handle_tool_show(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
             "layer": skill.source_layer,
         })
     };
+    if quality {
+        output["quality"] = serde_json::to_value(quality_report(ctx, &skill)?)?;
+    }
 
     Ok(ToolResult::text(serde_json::to_string_pretty(&output)?))
 }
@@ -1244,7 +1968,7 @@ fn handle_tool_lint(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
         .and_then(|v| v.as_array())
         .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect());
 
-    for rule in all_rules() {
+    for rule in ctx.lint_rules()? {
         if let Some(ref filter) = rules_filter {
             if !filter.contains(rule.id()) {
                 continue;
@@ -1285,7 +2009,7 @@ fn handle_tool_lint(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
     Ok(ToolResult::text(serde_json::to_string_pretty(&output)?))
 }
 
-fn handle_tool_suggest(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
+fn handle_tool_suggest(ctx: &AppContext, client: &ClientState, args: &Value) -> Result<ToolResult> {
     let cwd = args
         .get("cwd")
         .and_then(|v| v.as_str())
@@ -1297,20 +2021,27 @@ fn handle_tool_suggest(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
         .and_then(serde_json::Value::as_u64)
         .unwrap_or(5) as usize;
 
-    let _explain = args
+    let explain = args
         .get("explain")
         .and_then(serde_json::Value::as_bool)
         .unwrap_or(false);
 
-    // Detect project context using available detector
-    let detector = crate::context::DefaultDetector::new();
-    let detected_projects = detector.detect(&cwd);
+    let mut suggest_args =
+        crate::cli::commands::suggest::SuggestArgs::for_cwd(&cwd, limit, explain);
+    suggest_args.task = args
+        .get("task")
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+    suggest_args.agent_id.clone_from(&client.agent_id);
 
-    // Get recent skills as suggestions (simple approach)
-    let skills = ctx.db.list_skills(limit, 0)?;
+    // Same pipeline as `ms suggest`, including its cooldowns and tracking
+    let outcome = crate::cli::commands::suggest::suggest(ctx, &suggest_args)?;
+    let with_task = suggest_args.task_text().is_some();
 
     // Format detected contexts
-    let contexts: Vec<_> = detected_projects
+    let contexts: Vec<_> = outcome
+        .context
+        .detected_projects
         .iter()
         .map(|p| {
             serde_json::json!({
@@ -1325,20 +2056,34 @@ fn handle_tool_suggest(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
     let output = serde_json::json!({
         "cwd": cwd.display().to_string(),
         "detected_contexts": contexts,
-        "count": skills.len(),
-        "suggestions": skills.iter().map(|s| {
-            serde_json::json!({
-                "skill_id": s.id,
+        "count": outcome.suggestions.len(),
+        "cooled_down": outcome.cooled_down,
+        "suggestions": outcome.suggestions.iter().map(|s| {
+            let mut entry = serde_json::json!({
+                "skill_id": s.skill_id,
                 "name": s.name,
                 "description": s.description,
-            })
+                "score": s.score,
+            });
+            if with_task {
+                entry["origin"] = serde_json::json!(s.origin());
+            }
+            if explain {
+                entry["signals"] = serde_json::to_value(&s.breakdown.signals)
+                    .unwrap_or_default();
+            }
+            entry
         }).collect::<Vec<_>>()
     });
 
     Ok(ToolResult::text(serde_json::to_string_pretty(&output)?))
 }
 
-fn handle_tool_feedback(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
+fn handle_tool_feedback(
+    ctx: &AppContext,
+    client: &ClientState,
+    args: &Value,
+) -> Result<ToolResult> {
     let skill_id = args
         .get("skill_id")
         .and_then(|v| v.as_str())
@@ -1360,6 +2105,27 @@ fn handle_tool_feedback(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
     let rating = if helpful { Some(1) } else { Some(-1) };
     ctx.db
         .record_skill_feedback(skill_id, feedback_type, rating, comment)?;
+    let agent_id = client.agent_id.as_deref();
+    if let Err(e) = crate::cli::commands::feedback::update_contextual_bandit(
+        ctx,
+        skill_id,
+        feedback_type,
+        None,
+        agent_id,
+    ) {
+        // Best-effort, as in `ms feedback add`
+        warn!("failed to update bandit: {e}");
+    }
+    let config = &ctx.config.suggestions;
+    let cooldown = if helpful {
+        crate::suggestions::cooldown::start_acceptance(&ctx.db, config, skill_id, agent_id)
+    } else {
+        crate::suggestions::cooldown::start_dismissal(&ctx.db, config, skill_id, agent_id)
+    };
+    if let Err(e) = cooldown {
+        // Cooldowns are best-effort too
+        warn!("failed to start suggestion cooldown: {e}");
+    }
 
     let output = serde_json::json!({
         "recorded": true,
@@ -1371,6 +2137,27 @@ fn handle_tool_feedback(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
     Ok(ToolResult::text(serde_json::to_string_pretty(&output)?))
 }
 
+fn handle_tool_trial_report(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
+    let trial_id = args
+        .get("trial_id")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| {
+            MsError::ValidationFailed("Missing required parameter: trial_id".to_string())
+        })?;
+    let outcome: TrialOutcome = args
+        .get("outcome")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| {
+            MsError::ValidationFailed("Missing required parameter: outcome".to_string())
+        })?
+        .parse()
+        .map_err(MsError::ValidationFailed)?;
+    let notes = args.get("notes").and_then(|v| v.as_str());
+
+    let report = report_trial(ctx, trial_id, outcome, notes)?;
+    Ok(ToolResult::text(serde_json::to_string_pretty(&report)?))
+}
+
 fn handle_tool_index(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
     let force = args
         .get("force")
@@ -1391,16 +2178,16 @@ fn handle_tool_index(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
         // Collect paths from all configured buckets
         let mut all_paths = Vec::new();
         for p in &ctx.config.skill_paths.global {
-            all_paths.push(expand_tilde(p));
+            all_paths.push(expand_path(p));
         }
         for p in &ctx.config.skill_paths.project {
-            all_paths.push(expand_tilde(p));
+            all_paths.push(ctx.project_skill_path(p));
         }
         for p in &ctx.config.skill_paths.community {
-            all_paths.push(expand_tilde(p));
+            all_paths.push(expand_path(p));
         }
         for p in &ctx.config.skill_paths.local {
-            all_paths.push(expand_tilde(p));
+            all_paths.push(expand_path(p));
         }
         all_paths
     };
@@ -1421,7 +2208,7 @@ fn handle_tool_index(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
     Ok(ToolResult::text(serde_json::to_string_pretty(&output)?))
 }
 
-fn handle_tool_validate(_ctx: &AppContext, args: &Value) -> Result<ToolResult> {
+fn handle_tool_validate(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
     // Get content from either content param or path
     let (content, source) = if let Some(content) = args.get("content").and_then(|v| v.as_str()) {
         (content.to_string(), "inline".to_string())
@@ -1443,7 +2230,7 @@ fn handle_tool_validate(_ctx: &AppContext, args: &Value) -> Result<ToolResult> {
     let config = ValidationConfig::new();
     let mut engine = ValidationEngine::new(config);
 
-    for rule in all_rules() {
+    for rule in ctx.lint_rules()? {
         engine.register(rule);
     }
 
@@ -1482,7 +2269,7 @@ fn handle_tool_config(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
         .unwrap_or("list");
 
     let key = args.get("key").and_then(|v| v.as_str());
-    let _value = args.get("value").and_then(|v| v.as_str());
+    let value = args.get("value").and_then(|v| v.as_str());
 
     match action {
         "list" => {
@@ -1544,11 +2331,22 @@ fn handle_tool_config(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
             Ok(ToolResult::text(serde_json::to_string_pretty(&output)?))
         }
         "set" => {
-            // For now, config is read-only through MCP
-            Err(MsError::ValidationFailed(
-                "Config modification via MCP not yet supported. Use 'ms config set' CLI."
-                    .to_string(),
-            ))
+            let key = key.ok_or_else(|| {
+                MsError::ValidationFailed("'key' parameter required for set action".to_string())
+            })?;
+            let value = value.ok_or_else(|| {
+                MsError::ValidationFailed("'value' parameter required for set action".to_string())
+            })?;
+            check_mcp_writable(key)?;
+
+            let written = write_key(&ctx.config_path, key, value)?;
+            let output = serde_json::json!({
+                "action": "set",
+                "key": key,
+                "value": written,
+                "path": ctx.config_path,
+            });
+            Ok(ToolResult::text(serde_json::to_string_pretty(&output)?))
         }
         _ => Err(MsError::ValidationFailed(format!(
             "Unknown action: {action}. Valid actions: list, get, set"
@@ -1556,6 +2354,200 @@ fn handle_tool_config(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
     }
 }
 
+/// Only weights and suggestion limits can be changed over MCP.
+fn check_mcp_writable(key: &str) -> Result<()> {
+    if MCP_WRITABLE_KEYS.contains(&key) {
+        return Ok(());
+    }
+    Err(MsError::ValidationFailed(format!(
+        "{key} is read-only over MCP (writable: {}); use `ms config set` for other keys",
+        MCP_WRITABLE_KEYS.join(", ")
+    )))
+}
+
+fn handle_tool_bundle(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
+    let action = args
+        .get("action")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| MsError::ValidationFailed("action is required".to_string()))?;
+
+    match action {
+        "list" => bundle_list(ctx.git.root()),
+        "show" => {
+            let package = read_bundle_arg(args)?;
+            let registry = BundleRegistry::open(ctx.git.root())?;
+            let dependencies = check_dependencies(&package.manifest, &registry)?;
+            let report = BundleShowReport::from_manifest(&package.manifest, dependencies);
+            Ok(ToolResult::text(serde_json::to_string_pretty(&report)?))
+        }
+        "verify" => bundle_verify(ctx, args),
+        "install" => {
+            // Same lock as `ms bundle install`
+            let _lock = crate::storage::GlobalLock::acquire_or_busy(
+                &ctx.ms_root,
+                crate::storage::lock_timeout(),
+            )?;
+            bundle_install(ctx, args)
+        }
+        _ => Err(MsError::ValidationFailed(format!(
+            "Unknown action: {action}. Valid actions: list, show, install, verify"
+        ))),
+    }
+}
+
+fn read_bundle_arg(args: &Value) -> Result<BundlePackage> {
+    let path = args
+        .get("path")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| MsError::ValidationFailed("path is required".to_string()))?;
+    let path = expand_path(path);
+    let bytes = std::fs::read(&path)
+        .map_err(|err| MsError::Config(format!("read {}: {err}", path.display())))?;
+    BundlePackage::from_bytes(&bytes)
+}
+
+/// Check the bundle checksum and its signatures against
+/// `[bundles.trusted_keys]`, as `ms bundle install` does.
+///
+/// Returns whether signatures were verified, or the failure message.
+fn check_bundle(
+    ctx: &AppContext,
+    package: &BundlePackage,
+) -> Result<std::result::Result<bool, String>> {
+    if let Err(err) = package.verify() {
+        return Ok(Err(err.to_string()));
+    }
+    if package.manifest.signatures.is_empty() {
+        return Ok(Ok(false));
+    }
+    let verifier = trusted_verifier(&ctx.config.bundles)?;
+    Ok(package
+        .verify_signatures(&verifier)
+        .map(|()| true)
+        .map_err(|err| err.to_string()))
+}
+
+fn bundle_list(root: &std::path::Path) -> Result<ToolResult> {
+    let registry = BundleRegistry::open(root)?;
+    let bundles: Vec<Value> = registry
+        .list()
+        .map(|b| {
+            serde_json::json!({
+                "id": b.id,
+                "version": b.version,
+                "source": b.source.to_string(),
+                "skills": b.skills,
+                "installed_at": b.installed_at.to_rfc3339(),
+            })
+        })
+        .collect();
+    let output = serde_json::json!({
+        "count": bundles.len(),
+        "bundles": bundles,
+    });
+    Ok(ToolResult::text(serde_json::to_string_pretty(&output)?))
+}
+
+fn bundle_verify(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
+    let package = read_bundle_arg(args)?;
+    let bundle_id = &package.manifest.bundle.id;
+    let signed = !package.manifest.signatures.is_empty();
+    match check_bundle(ctx, &package)? {
+        Ok(signature_verified) => {
+            let output = serde_json::json!({
+                "status": "ok",
+                "bundle_id": bundle_id,
+                "checksum": package.manifest.checksum,
+                "signed": signed,
+                "signature_verified": signature_verified,
+            });
+            Ok(ToolResult::text(serde_json::to_string_pretty(&output)?))
+        }
+        Err(error) => {
+            let output = serde_json::json!({
+                "status": "verification_failed",
+                "bundle_id": bundle_id,
+                "signed": signed,
+                "error": error,
+            });
+            Ok(ToolResult::error(serde_json::to_string_pretty(&output)?))
+        }
+    }
+}
+
+/// Install a bundle file through [`install_bundle`], the install path of
+/// `ms bundle install`: an installed bundle or existing skill directory is
+/// only replaced with `force`, and conflicting local files are backed up
+/// first. Signatures are always checked against `[bundles.trusted_keys]`.
+fn bundle_install(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
+    let package = read_bundle_arg(args)?;
+    let bundle_id = package.manifest.bundle.id.clone();
+    if let Err(error) = check_bundle(ctx, &package)? {
+        let output = serde_json::json!({
+            "status": "verification_failed",
+            "bundle_id": bundle_id,
+            "error": error,
+            "hint": "add the signer's key to [bundles.trusted_keys]",
+        });
+        return Ok(ToolResult::error(serde_json::to_string_pretty(&output)?));
+    }
+    let mut warnings = Vec::new();
+    if package.manifest.signatures.is_empty() {
+        warnings.push(format!(
+            "Installing unsigned bundle '{bundle_id}'. Use signed bundles for production deployments."
+        ));
+    }
+
+    // read_bundle_arg already required the path
+    let path = args["path"].as_str().unwrap_or_default();
+    let install_args = BundleInstallArgs {
+        source: path.to_string(),
+        skills: args
+            .get("skills")
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_str())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default(),
+        token: None,
+        tag: None,
+        asset_name: None,
+        no_verify: false,
+        force: args.get("force").and_then(|v| v.as_bool()).unwrap_or(false),
+        with_deps: false,
+    };
+    let source = InstallSource::File {
+        path: expand_path(path).display().to_string(),
+    };
+
+    match install_bundle(ctx, &package, source, &install_args)? {
+        BundleInstall::Installed {
+            report,
+            conflicts_overwritten,
+            backups,
+        } => {
+            let output = serde_json::json!({
+                "status": "installed",
+                "report": report,
+                "conflicts_overwritten": conflicts_overwritten,
+                "backups": backups,
+                "warnings": warnings,
+            });
+            Ok(ToolResult::text(serde_json::to_string_pretty(&output)?))
+        }
+        BundleInstall::Conflicts(conflicts) => {
+            let mut output = serde_json::to_value(&conflicts)?;
+            output["status"] = "conflicts".into();
+            output["hint"] =
+                "pass force: true to overwrite; conflicting files are backed up first".into();
+            Ok(ToolResult::error(serde_json::to_string_pretty(&output)?))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1565,9 +2557,238 @@ mod tests {
         let tools = define_tools();
         assert!(!tools.is_empty());
         assert!(tools.iter().any(|t| t.name == "search"));
+        assert!(tools.iter().any(|t| t.name == "search_batch"));
         assert!(tools.iter().any(|t| t.name == "load"));
     }
 
+    /// One valid and two invalid argument shapes per tool
+    fn argument_cases() -> Vec<(&'static str, Value, [Value; 2])> {
+        use serde_json::json;
+
+        vec![
+            (
+                "search",
+                json!({"query": "rust", "limit": 5}),
+                [json!({"query": "rust", "limit": "5"}), json!({"limit": 5})],
+            ),
+            (
+                "search_batch",
+                json!({"queries": ["errors", "async"]}),
+                [
+                    json!({"queries": "errors"}),
+                    json!({"queries": ["errors", 1]}),
+                ],
+            ),
+            (
+                "load",
+                json!({"skill": "rust-errors", "full": true}),
+                [json!({}), json!({"skill": "rust-errors", "trial": "yes"})],
+            ),
+            (
+                "load_slices",
+                json!({"skill": "rust-errors", "need": "retries", "top_k": 3}),
+                [
+                    json!({"skill": "rust-errors", "top_k": 0}),
+                    json!({"skill": "rust-errors", "selectors": "intro"}),
+                ],
+            ),
+            (
+                "evidence",
+                json!({"skill": "rust-errors", "rule_id": "rule-1"}),
+                [json!({"rule_id": "rule-1"}), json!({"skill": 42})],
+            ),
+            (
+                "list",
+                json!({"limit": 10, "offset": 0}),
+                [json!({"limit": -1}), json!({"offset": "0"})],
+            ),
+            (
+                "show",
+                json!({"skill": "rust-errors", "quality": true}),
+                [
+                    json!({"skill": "rust-errors", "full": 1}),
+                    json!({"skill": null}),
+                ],
+            ),
+            (
+                "doctor",
+                json!({"fix": false}),
+                [json!({"fix": "true"}), json!(["fix"])],
+            ),
+            (
+                "lint",
+                json!({"path": "SKILL.md", "rules": ["no-secrets"]}),
+                [json!({"rules": "no-secrets"}), json!({"strict": "no"})],
+            ),
+            (
+                "suggest",
+                json!({"cwd": "/tmp", "threshold": 0.5}),
+                [json!({"threshold": 1.5}), json!({"limit": 2.5})],
+            ),
+            (
+                "feedback",
+                json!({"skill_id": "rust-errors", "helpful": true}),
+                [
+                    json!({"skill_id": "rust-errors"}),
+                    json!({"skill_id": "rust-errors", "helpful": "yes"}),
+                ],
+            ),
+            (
+                "trial_report",
+                json!({"trial_id": "t-1", "outcome": "success"}),
+                [
+                    json!({"trial_id": "t-1", "outcome": "great"}),
+                    json!({"outcome": "success"}),
+                ],
+            ),
+            (
+                "index",
+                json!({"paths": ["./skills"], "force": true}),
+                [json!({"paths": "./skills"}), json!({"force": "yes"})],
+            ),
+            (
+                "validate",
+                json!({"content": "# Skill"}),
+                [json!({"content": 1}), json!({"path": ["SKILL.md"]})],
+            ),
+            (
+                "config",
+                json!({"action": "get", "key": "search.bm25_weight"}),
+                [json!({"action": "delete"}), json!({"value": 0.5})],
+            ),
+            (
+                "bundle",
+                json!({"action": "list"}),
+                [json!({}), json!({"action": "install", "force": "yes"})],
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_tool_arguments_validated_per_tool() {
+        let cases = argument_cases();
+        for tool in define_tools() {
+            assert!(
+                cases.iter().any(|(name, _, _)| *name == tool.name),
+                "no argument cases for tool {}",
+                tool.name
+            );
+        }
+        for (name, valid, invalid) in cases {
+            assert_eq!(
+                check_arguments(name, &valid),
+                Ok(Vec::new()),
+                "{name}: {valid}"
+            );
+            for arguments in invalid {
+                let (message, data) = check_arguments(name, &arguments)
+                    .expect_err(&format!("{name} accepted {arguments}"));
+                assert!(message.starts_with(&format!("Invalid arguments for {name}: ")));
+                assert_eq!(data["tool"], name);
+                assert!(!data["violations"].as_array().unwrap().is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_params_list_violations_by_pointer() {
+        let (message, data) =
+            check_arguments("search", &serde_json::json!({"query": "q", "limit": "10"}))
+                .unwrap_err();
+        assert_eq!(
+            message,
+            "Invalid arguments for search: /limit: expected integer, got string"
+        );
+        assert_eq!(data["violations"][0]["pointer"], "/limit");
+        assert_eq!(
+            data["violations"][0]["message"],
+            "expected integer, got string"
+        );
+
+        // Undeclared properties only warn; unknown tools are left to dispatch
+        let warnings = check_arguments(
+            "search",
+            &serde_json::json!({"query": "q", "verbose": true}),
+        )
+        .unwrap();
+        assert_eq!(warnings, ["/verbose: unknown property, ignored"]);
+        assert_eq!(
+            check_arguments("nope", &serde_json::json!(1)),
+            Ok(Vec::new())
+        );
+
+        let result = ToolResult::text("ok".to_string())
+            .with_meta(serde_json::json!({"next_steps": []}))
+            .with_warnings(warnings);
+        let json = serde_json::to_value(result).unwrap();
+        assert!(json["_meta"]["next_steps"].is_array());
+        assert_eq!(json["_meta"]["warnings"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_tool_annotations() {
+        let tools = serde_json::to_value(define_tools()).unwrap();
+        let annotations = |name: &str| {
+            tools
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|tool| tool["name"] == name)
+                .unwrap()["annotations"]
+                .clone()
+        };
+        for name in ["search", "list", "show"] {
+            assert_eq!(annotations(name)["readOnlyHint"], true, "{name}");
+            assert_eq!(annotations(name)["destructiveHint"], false, "{name}");
+        }
+        for name in ["index", "feedback"] {
+            assert_eq!(annotations(name)["readOnlyHint"], false, "{name}");
+        }
+        assert_eq!(annotations("index")["destructiveHint"], true);
+        assert_eq!(annotations("feedback")["idempotentHint"], false);
+    }
+
+    #[test]
+    fn test_initialize_advertises_resources() {
+        let mut client = ClientState::default();
+        let resp = handle_initialize(
+            Some(serde_json::json!(1)),
+            &serde_json::json!({}),
+            &mut client,
+        );
+        let result = resp.result.unwrap();
+        assert_eq!(result["capabilities"]["resources"]["subscribe"], false);
+        assert_eq!(result["capabilities"]["resources"]["listChanged"], false);
+        assert_eq!(client.agent_id, None);
+    }
+
+    #[test]
+    fn test_initialize_records_client_name() {
+        let mut client = ClientState::default();
+        let params = serde_json::json!({
+            "protocolVersion": PROTOCOL_VERSION,
+            "clientInfo": {"name": "cursor", "version": "1.2.0"},
+        });
+        handle_initialize(Some(serde_json::json!(1)), &params, &mut client);
+        assert_eq!(client.agent_id.as_deref(), Some("cursor"));
+
+        // A blank name is no name
+        let params = serde_json::json!({"clientInfo": {"name": "  "}});
+        handle_initialize(Some(serde_json::json!(2)), &params, &mut client);
+        assert_eq!(client, ClientState::default());
+    }
+
+    #[test]
+    fn test_resource_templates_list() {
+        let resp = handle_resource_templates_list(Some(serde_json::json!(1)));
+        let result = resp.result.unwrap();
+        assert_eq!(
+            result["resourceTemplates"][0]["uriTemplate"],
+            "ms://skill/{id}"
+        );
+        assert_eq!(result["resourceTemplates"][0]["mimeType"], "text/markdown");
+    }
+
     #[test]
     fn test_jsonrpc_response_success() {
         let resp =
@@ -1602,6 +2823,33 @@ mod tests {
         assert!(result.is_error == Some(true));
     }
 
+    #[test]
+    fn test_tool_result_meta_carries_next_steps() {
+        let follow_ups = follow_up::actions_for(
+            "rust-errors",
+            &follow_up::from_metadata_json(
+                r#"{"follow_ups":[
+                    {"kind":"load","target":"rust-async","when":"async code changed"},
+                    {"kind":"verify","target":"cargo test"},
+                    {"kind":"feedback"}
+                ]}"#,
+            ),
+        );
+        let hints = next_step_hints(&follow_ups);
+        assert_eq!(hints[0]["tool"], "load");
+        assert_eq!(hints[0]["arguments"]["skill"], "rust-async");
+        assert_eq!(hints[0]["condition"], "async code changed");
+        assert_eq!(hints[1]["command"], "cargo test");
+        assert_eq!(hints[2]["arguments"]["skill_id"], "rust-errors");
+
+        let plain = serde_json::to_value(ToolResult::text("ok".to_string())).unwrap();
+        assert!(plain.get("_meta").is_none());
+        let result = ToolResult::text("ok".to_string())
+            .with_meta(serde_json::json!({ "next_steps": hints }));
+        let json = serde_json::to_value(result).unwrap();
+        assert_eq!(json["_meta"]["next_steps"].as_array().unwrap().len(), 3);
+    }
+
     #[test]
     fn test_handle_initialized_notification() {
         // JSON-RPC 2.0: Notifications (no id) MUST NOT receive a response
@@ -1659,6 +2907,7 @@ mod tests {
         assert!(props.get("cwd").is_some());
         assert!(props.get("limit").is_some());
         assert!(props.get("explain").is_some());
+        assert_eq!(props["task"]["type"], "string");
     }
 
     #[test]
@@ -1688,6 +2937,37 @@ mod tests {
         assert!(required.iter().any(|r| r == "helpful"));
     }
 
+    #[test]
+    fn test_trial_report_tool_schema() {
+        let tools = define_tools();
+        let load = tools.iter().find(|t| t.name == "load").unwrap();
+        assert!(load.input_schema["properties"].get("trial").is_some());
+
+        let tool = tools.iter().find(|t| t.name == "trial_report").unwrap();
+        let props = tool.input_schema.get("properties").unwrap();
+        assert!(props.get("notes").is_some());
+        assert_eq!(
+            props["outcome"]["enum"],
+            serde_json::json!(["success", "failure", "neutral"])
+        );
+        assert_eq!(
+            tool.input_schema["required"],
+            serde_json::json!(["trial_id", "outcome"])
+        );
+    }
+
+    #[test]
+    fn test_load_slices_tool_schema() {
+        let tools = define_tools();
+        let tool = tools.iter().find(|t| t.name == "load_slices").unwrap();
+        let props = tool.input_schema.get("properties").unwrap();
+        for key in ["need", "selectors", "top_k", "max_tokens"] {
+            assert!(props.get(key).is_some(), "missing {key}");
+        }
+        assert_eq!(props["selectors"]["items"]["type"], "string");
+        assert_eq!(tool.input_schema["required"], serde_json::json!(["skill"]));
+    }
+
     #[test]
     fn test_define_tools_includes_index() {
         let tools = define_tools();
@@ -1738,14 +3018,146 @@ mod tests {
     }
 
     #[test]
-    fn test_expand_tilde_no_tilde() {
-        let result = expand_tilde("./foo/bar");
+    fn test_config_tool_writes_only_safe_keys() {
+        assert!(check_mcp_writable("search.bm25_weight").is_ok());
+        assert!(check_mcp_writable("suggestions.cooldown_secs").is_ok());
+        assert!(check_mcp_writable("skill_paths.global").is_err());
+        assert!(check_mcp_writable("search.embeddings.model_path").is_err());
+    }
+
+    #[test]
+    fn test_bundle_tool_schema() {
+        let tools = define_tools();
+        let tool = tools.iter().find(|t| t.name == "bundle").unwrap();
+
+        let props = tool.input_schema.get("properties").unwrap();
+        for prop in ["action", "path", "skills", "force"] {
+            assert!(props.get(prop).is_some(), "missing {prop}");
+        }
+        // Verification is governed by [bundles.trusted_keys], not the caller
+        assert!(props.get("no_verify").is_none());
+        assert!(props.get("trusted_keys").is_none());
+        let actions = props["action"]["enum"].as_array().unwrap();
+        assert_eq!(actions.len(), 4);
+        assert_eq!(tool.input_schema["required"], serde_json::json!(["action"]));
+    }
+
+    fn write_test_bundle(
+        dir: &std::path::Path,
+        signatures: Vec<crate::bundler::BundleSignature>,
+    ) -> std::path::PathBuf {
+        use crate::bundler::{Bundle, BundleInfo, BundleManifest, BundledSkill};
+
+        let skill_dir = dir.join("skills/by-id/demo");
+        std::fs::create_dir_all(&skill_dir).unwrap();
+        std::fs::write(skill_dir.join("SKILL.md"), "# Demo\n").unwrap();
+
+        let manifest = BundleManifest {
+            bundle: BundleInfo {
+                id: "demo-bundle".to_string(),
+                name: "Demo Bundle".to_string(),
+                version: "1.0.0".to_string(),
+                description: None,
+                authors: vec![],
+                license: None,
+                repository: None,
+                keywords: vec![],
+                ms_version: None,
+            },
+            skills: vec![BundledSkill {
+                name: "demo".to_string(),
+                path: std::path::PathBuf::from("skills/by-id/demo"),
+                version: Some("1.0.0".to_string()),
+                hash: None,
+                optional: false,
+                changelog: Vec::new(),
+                resources: Vec::new(),
+            }],
+            dependencies: vec![],
+            checksum: None,
+            signatures,
+        };
+        let package = Bundle::new(manifest, dir).package().unwrap();
+        let path = dir.join("demo.msb");
+        std::fs::write(&path, package.to_bytes().unwrap()).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_bundle_install_into_temp_root() {
+        let src = tempfile::tempdir().unwrap();
+        let root = tempfile::tempdir().unwrap();
+        let ctx = AppContext::for_tests(root.path());
+        let path = write_test_bundle(src.path(), vec![]);
+        let args = serde_json::json!({ "action": "install", "path": path });
+
+        let result = bundle_install(&ctx, &args).unwrap();
+        assert_eq!(result.is_error, None);
+        let output: Value = serde_json::from_str(&result.content[0].text).unwrap();
+        assert_eq!(output["status"], "installed");
+        assert_eq!(output["report"]["installed"], serde_json::json!(["demo"]));
+        let skill_md = ctx.git.root().join("skills/by-id/demo/SKILL.md");
+        assert!(skill_md.exists());
+
+        let listed = bundle_list(ctx.git.root()).unwrap();
+        let listed: Value = serde_json::from_str(&listed.content[0].text).unwrap();
+        assert_eq!(listed["count"], 1);
+
+        // A second install is refused without force, with the conflict list.
+        std::fs::write(&skill_md, "# Edited locally\n").unwrap();
+        let result = bundle_install(&ctx, &args).unwrap();
+        assert_eq!(result.is_error, Some(true));
+        let output: Value = serde_json::from_str(&result.content[0].text).unwrap();
+        assert_eq!(output["status"], "conflicts");
+        assert_eq!(output["already_installed"], true);
+        assert_eq!(output["conflicts"].as_array().unwrap().len(), 1);
+
+        // With force the local edit is backed up and the bundle reinstalled.
+        let forced = serde_json::json!({ "action": "install", "path": path, "force": true });
+        let result = bundle_install(&ctx, &forced).unwrap();
+        assert_eq!(result.is_error, None);
+        let output: Value = serde_json::from_str(&result.content[0].text).unwrap();
+        assert_eq!(output["backups"].as_array().unwrap().len(), 1);
+        assert_eq!(std::fs::read_to_string(&skill_md).unwrap(), "# Demo\n");
+    }
+
+    #[test]
+    fn test_bundle_install_signature_failure_is_error() {
+        let src = tempfile::tempdir().unwrap();
+        let root = tempfile::tempdir().unwrap();
+        let mut ctx = AppContext::for_tests(root.path());
+        ctx.config
+            .bundles
+            .trusted_keys
+            .insert("k1".to_string(), "11".repeat(32));
+        let signature = crate::bundler::BundleSignature {
+            signer: "someone".to_string(),
+            key_id: "k1".to_string(),
+            signature: "00".repeat(64),
+        };
+        let path = write_test_bundle(src.path(), vec![signature]);
+        // Callers cannot opt out of verification: no_verify is not a tool argument
+        let args = serde_json::json!({ "action": "install", "path": path, "no_verify": true });
+
+        let result = bundle_install(&ctx, &args).unwrap();
+        assert_eq!(result.is_error, Some(true));
+        let output: Value = serde_json::from_str(&result.content[0].text).unwrap();
+        assert_eq!(output["status"], "verification_failed");
+        assert!(!ctx.git.root().join("skills/by-id/demo").exists());
+
+        let verified = bundle_verify(&ctx, &args).unwrap();
+        assert_eq!(verified.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_expand_path_no_tilde() {
+        let result = expand_path("./foo/bar");
         assert_eq!(result, std::path::PathBuf::from("./foo/bar"));
     }
 
     #[test]
-    fn test_expand_tilde_with_tilde() {
-        let result = expand_tilde("~/test/path");
+    fn test_expand_path_with_tilde() {
+        let result = expand_path("~/test/path");
         // Should expand to home dir + path (if home exists)
         if let Some(home) = dirs::home_dir() {
             assert_eq!(result, home.join("test/path"));
@@ -1755,8 +3167,8 @@ mod tests {
     }
 
     #[test]
-    fn test_expand_tilde_only() {
-        let result = expand_tilde("~");
+    fn test_expand_path_only() {
+        let result = expand_path("~");
         if let Some(home) = dirs::home_dir() {
             assert_eq!(result, home);
         } else {
@@ -1768,7 +3180,7 @@ mod tests {
     fn test_tool_count() {
         let tools = define_tools();
         // We should have at least 12 tools: search, load, evidence, list, show, doctor, lint,
-        // suggest, feedback, index, validate, config
+        // suggest, feedback, index, validate, config, bundle
         assert!(
             tools.len() >= 12,
             "Expected at least 12 tools, got {}",
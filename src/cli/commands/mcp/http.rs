@@ -0,0 +1,888 @@
+//! Streamable-HTTP transport for `ms mcp serve --tcp-port`.
+//!
+//! Clients POST JSON-RPC messages (single or batched) to `/mcp`. Replies come
+//! back as `application/json`, or as `text/event-stream` events when the
+//! client accepts SSE. Connections are read on their own threads, but every
+//! message is dispatched on the serving thread through the same
+//! [`handle_request`] the stdio transport uses, so tool behavior is identical
+//! and the store never sees concurrent writers. Each POST carries its own
+//! reply channel, so clients that reuse the same JSON-RPC ids never receive
+//! each other's responses. The client name from each session's `initialize`
+//! goes along with its messages, so suggestions are attributed per session.
+//!
+//! Replies are streamed back as the dispatcher produces them (chunked, or
+//! delimited by connection close for HTTP/1.0). Listeners on non-loopback
+//! addresses require a bearer token, and the number of open connections is
+//! capped. Sessions expire after idling and the oldest are evicted past a
+//! cap, so clients that never DELETE theirs cannot grow the server.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{IpAddr, TcpListener, TcpStream};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender};
+use parking_lot::Mutex;
+use tracing::warn;
+
+use super::{
+    ClientState, handle_request, maybe_reopen_stores, serialize_response_safe, validate_mcp_json,
+};
+use crate::app::AppContext;
+use crate::error::{MsError, Result};
+use crate::signals::interrupt_flag;
+
+/// Endpoint path (`/` is accepted too).
+pub const MCP_PATH: &str = "/mcp";
+/// Header carrying the session id issued on `initialize`.
+const SESSION_HEADER: &str = "Mcp-Session-Id";
+/// How often the accept and dispatch loops check for Ctrl-C.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// Idle keep-alive connections are closed after this long.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+const MAX_BODY_BYTES: usize = 4 * 1024 * 1024;
+const MAX_HEADER_LINE: u64 = 8 * 1024;
+const MAX_HEADERS: usize = 100;
+/// Connections beyond this are answered with 503 and closed.
+const MAX_CONNECTIONS: usize = 64;
+/// Sessions unused for this long are forgotten.
+const SESSION_IDLE_TTL: Duration = Duration::from_secs(30 * 60);
+/// Sessions beyond this evict the least recently used one.
+const MAX_SESSIONS: usize = 1024;
+
+/// One POST's JSON-RPC messages, the client that sent them, and where to
+/// send the serialized replies. Each reply is sent as `Some` when ready;
+/// `None` marks the end of the job.
+struct Job {
+    messages: Vec<String>,
+    client: ClientState,
+    reply: Sender<Option<String>>,
+}
+
+/// State shared by connection threads.
+struct Shared {
+    jobs: Sender<Job>,
+    /// Live session ids and their clients
+    sessions: Mutex<Sessions>,
+    bind: IpAddr,
+    /// Bearer token every request must carry, if configured
+    token: Option<String>,
+    /// Connections currently being served
+    connections: AtomicUsize,
+    debug: bool,
+}
+
+/// Live sessions keyed by the id issued on `initialize`. Clients that never
+/// send DELETE would otherwise grow this forever, so sessions idle for `ttl`
+/// expire and the least recently used one is evicted beyond `max`.
+struct Sessions {
+    entries: HashMap<String, Session>,
+    ttl: Duration,
+    max: usize,
+}
+
+struct Session {
+    client: ClientState,
+    last_seen: Instant,
+}
+
+impl Sessions {
+    fn new(ttl: Duration, max: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            ttl,
+            max,
+        }
+    }
+
+    /// The client of live session `id`, marking it used at `now`.
+    fn touch(&mut self, id: &str, now: Instant) -> Option<ClientState> {
+        self.expire(now);
+        let session = self.entries.get_mut(id)?;
+        session.last_seen = now;
+        Some(session.client.clone())
+    }
+
+    fn insert(&mut self, id: String, client: ClientState, now: Instant) {
+        self.expire(now);
+        while self.entries.len() >= self.max {
+            let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, session)| session.last_seen)
+                .map(|(id, _)| id.clone())
+            else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+        self.entries.insert(
+            id,
+            Session {
+                client,
+                last_seen: now,
+            },
+        );
+    }
+
+    fn remove(&mut self, id: &str) {
+        self.entries.remove(id);
+    }
+
+    fn expire(&mut self, now: Instant) {
+        let ttl = self.ttl;
+        self.entries
+            .retain(|_, session| now.saturating_duration_since(session.last_seen) < ttl);
+    }
+}
+
+/// Serve MCP over HTTP on `bind:port` until Ctrl-C.
+///
+/// Refuses to listen on a non-loopback address unless `token` is set.
+pub(super) fn serve(
+    mut ctx: AppContext,
+    bind: IpAddr,
+    port: u16,
+    token: Option<String>,
+    debug: bool,
+) -> Result<()> {
+    check_bind(bind, token.as_deref())?;
+    let listener = TcpListener::bind((bind, port))?;
+    let addr = listener.local_addr()?;
+    // Always printed: with --tcp-port 0 this is how callers learn the port.
+    eprintln!("[ms-mcp] Listening on http://{addr}{MCP_PATH}");
+
+    let stop = interrupt_flag();
+    let (jobs, queue) = crossbeam_channel::unbounded();
+    let shared = Arc::new(Shared {
+        jobs,
+        sessions: Mutex::new(Sessions::new(SESSION_IDLE_TTL, MAX_SESSIONS)),
+        bind,
+        token,
+        connections: AtomicUsize::new(0),
+        debug,
+    });
+    let acceptor = {
+        let stop = Arc::clone(&stop);
+        thread::spawn(move || accept_loop(&listener, &shared, &stop))
+    };
+
+    dispatch_loop(&mut ctx, &queue, &stop, debug);
+
+    stop.store(true, Ordering::SeqCst);
+    let _ = acceptor.join();
+    if debug {
+        eprintln!("[ms-mcp] Server shutting down");
+    }
+    Ok(())
+}
+
+/// Anyone who can reach a non-loopback listener can call every tool, so
+/// those binds need a token.
+fn check_bind(bind: IpAddr, token: Option<&str>) -> Result<()> {
+    if bind.is_loopback() || token.is_some_and(|token| !token.is_empty()) {
+        return Ok(());
+    }
+    Err(MsError::Config(format!(
+        "refusing to serve MCP on non-loopback address {bind} without a token; \
+         pass --auth-token or set MS_MCP_TOKEN"
+    )))
+}
+
+/// Run queued messages through [`handle_request`] one POST at a time.
+fn dispatch_loop(ctx: &mut AppContext, queue: &Receiver<Job>, stop: &AtomicBool, debug: bool) {
+    let mut opened = ctx.store_identity();
+    while !stop.load(Ordering::SeqCst) {
+        let job = match queue.recv_timeout(POLL_INTERVAL) {
+            Ok(job) => job,
+            Err(RecvTimeoutError::Timeout) => continue,
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
+        maybe_reopen_stores(ctx, &mut opened, debug);
+        let mut client = job.client;
+        for message in &job.messages {
+            if debug {
+                eprintln!("[ms-mcp] <- {message}");
+            }
+            let Some(response) = handle_request(ctx, &mut client, message, debug) else {
+                continue;
+            };
+            // CRITICAL: same ANSI guarantees as the stdio transport
+            let json = serialize_response_safe(&response);
+            if let Err(e) = validate_mcp_json(&json) {
+                warn!("MCP response validation failed after sanitization: {}", e);
+            }
+            if debug {
+                eprintln!("[ms-mcp] -> {json}");
+            }
+            // The client may have hung up; nothing to do then.
+            let _ = job.reply.send(Some(json));
+        }
+        let _ = job.reply.send(None);
+    }
+}
+
+/// Accept connections until `stop` is set, then drop (close) the listener.
+fn accept_loop(listener: &TcpListener, shared: &Arc<Shared>, stop: &AtomicBool) {
+    if let Err(e) = listener.set_nonblocking(true) {
+        warn!("MCP HTTP: cannot poll listener: {e}");
+        stop.store(true, Ordering::SeqCst);
+        return;
+    }
+    while !stop.load(Ordering::SeqCst) {
+        match listener.accept() {
+            Ok((stream, peer)) => {
+                if shared.connections.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+                    shared.connections.fetch_sub(1, Ordering::SeqCst);
+                    reject_busy(stream);
+                    continue;
+                }
+                let shared = Arc::clone(shared);
+                thread::spawn(move || {
+                    let _slot = ConnectionSlot(&shared.connections);
+                    if let Err(e) = serve_connection(stream, &shared) {
+                        if shared.debug {
+                            eprintln!("[ms-mcp] connection {peer} closed: {e}");
+                        }
+                    }
+                });
+            }
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => thread::sleep(POLL_INTERVAL),
+            Err(e) => {
+                warn!("MCP HTTP accept failed: {e}");
+                thread::sleep(POLL_INTERVAL);
+            }
+        }
+    }
+}
+
+/// Releases a connection slot when its thread finishes.
+struct ConnectionSlot<'a>(&'a AtomicUsize);
+
+impl Drop for ConnectionSlot<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Answer a connection over the cap with 503 and close it.
+fn reject_busy(mut stream: TcpStream) {
+    let _ = stream.set_nonblocking(false);
+    let _ = stream.set_write_timeout(Some(IDLE_TIMEOUT));
+    let _ = HttpResponse::text(503, "Service Unavailable", "too many connections")
+        .with_header("Retry-After", "1")
+        .write_to(&mut stream, true);
+}
+
+fn serve_connection(stream: TcpStream, shared: &Shared) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(IDLE_TIMEOUT))?;
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+    loop {
+        let (response, close) = match read_request(&mut reader) {
+            Ok(None) => return Ok(()),
+            Ok(Some(request)) => {
+                let response = route(&request, shared);
+                // HTTP/1.0 has no chunked encoding: a streamed body ends at close
+                let close = request.wants_close()
+                    || (response.stream.is_some() && request.version == "HTTP/1.0");
+                (response, close)
+            }
+            Err(rejection) => (rejection, true),
+        };
+        response.write_to(&mut writer, close)?;
+        if close {
+            return Ok(());
+        }
+    }
+}
+
+fn route(request: &HttpRequest, shared: &Shared) -> HttpResponse {
+    let path = request.path.split('?').next().unwrap_or_default();
+    if path != MCP_PATH && path != "/" {
+        return HttpResponse::text(404, "Not Found", "unknown endpoint; use /mcp");
+    }
+    if let Some(token) = &shared.token
+        && !bearer_matches(request.header("authorization"), token)
+    {
+        return HttpResponse::text(401, "Unauthorized", "missing or invalid bearer token")
+            .with_header("WWW-Authenticate", "Bearer");
+    }
+    if let Some(origin) = request.header("origin") {
+        if !origin_allowed(origin, shared.bind) {
+            return HttpResponse::text(403, "Forbidden", "origin not allowed");
+        }
+    }
+    let session = request.header(SESSION_HEADER);
+    let session_client = match session {
+        Some(id) => match shared.sessions.lock().touch(id, Instant::now()) {
+            Some(client) => Some(client),
+            None => return HttpResponse::text(404, "Not Found", "unknown or expired session"),
+        },
+        None => None,
+    };
+    match request.method.as_str() {
+        "POST" => post(request, shared, session_client),
+        "DELETE" => match session {
+            Some(id) => {
+                shared.sessions.lock().remove(id);
+                HttpResponse::empty(200, "OK")
+            }
+            None => HttpResponse::text(400, "Bad Request", "missing Mcp-Session-Id"),
+        },
+        // No server-initiated stream: the spec has GET answer 405 then.
+        _ => HttpResponse::text(405, "Method Not Allowed", "use POST")
+            .with_header("Allow", "POST, DELETE"),
+    }
+}
+
+/// Handle a POST. `session_client` is the client of the request's session,
+/// if it named one.
+fn post(
+    request: &HttpRequest,
+    shared: &Shared,
+    session_client: Option<ClientState>,
+) -> HttpResponse {
+    let is_json = request
+        .header("content-type")
+        .is_some_and(|value| value.to_ascii_lowercase().starts_with("application/json"));
+    if !is_json {
+        return HttpResponse::text(
+            415,
+            "Unsupported Media Type",
+            "Content-Type must be application/json",
+        );
+    }
+    let body = String::from_utf8_lossy(&request.body);
+    let (messages, batch) = split_messages(&body);
+    let initialize = messages
+        .iter()
+        .find_map(|message| initialize_client(message));
+    let new_session = session_client.is_none();
+    let client = session_client.unwrap_or_else(|| initialize.clone().unwrap_or_default());
+
+    let (reply, replies) = crossbeam_channel::unbounded();
+    let job = Job {
+        messages,
+        client,
+        reply,
+    };
+    if shared.jobs.send(job).is_err() {
+        return HttpResponse::text(503, "Service Unavailable", "server is shutting down");
+    }
+
+    // Wait for the first reply only: it decides between 202 and a streamed body.
+    let mut response = match replies.recv() {
+        // Notifications and responses only: acknowledged with no body.
+        Ok(None) => HttpResponse::empty(202, "Accepted"),
+        Ok(Some(first)) if request.accepts_event_stream() => HttpResponse::stream(
+            "text/event-stream",
+            ReplyStream {
+                first,
+                rest: replies,
+                framing: Framing::EventStream,
+            },
+        )
+        .with_header("Cache-Control", "no-cache"),
+        Ok(Some(first)) => HttpResponse::stream(
+            "application/json",
+            ReplyStream {
+                first,
+                rest: replies,
+                framing: if batch {
+                    Framing::JsonArray
+                } else {
+                    Framing::Json
+                },
+            },
+        ),
+        Err(_) => {
+            return HttpResponse::text(503, "Service Unavailable", "server is shutting down");
+        }
+    };
+    if let Some(client) = initialize
+        && new_session
+    {
+        let id = uuid::Uuid::new_v4().to_string();
+        shared
+            .sessions
+            .lock()
+            .insert(id.clone(), client, Instant::now());
+        response = response.with_header(SESSION_HEADER, &id);
+    }
+    response
+}
+
+/// Split a POST body into individual JSON-RPC messages. Anything that is
+/// not a JSON array is passed through whole, so malformed bodies get the
+/// usual parse-error response from [`handle_request`].
+fn split_messages(body: &str) -> (Vec<String>, bool) {
+    match serde_json::from_str::<serde_json::Value>(body) {
+        Ok(serde_json::Value::Array(items)) if !items.is_empty() => (
+            items.iter().map(serde_json::Value::to_string).collect(),
+            true,
+        ),
+        _ => (vec![body.to_string()], false),
+    }
+}
+
+/// The client announced by an `initialize` message; `None` for other messages.
+fn initialize_client(message: &str) -> Option<ClientState> {
+    let value = serde_json::from_str::<serde_json::Value>(message).ok()?;
+    (value.get("method").and_then(|m| m.as_str()) == Some("initialize")).then(|| {
+        ClientState::from_initialize(value.get("params").unwrap_or(&serde_json::Value::Null))
+    })
+}
+
+/// Whether an `Authorization` header carries `Bearer <token>`. Compared in
+/// constant time so the token cannot be guessed byte by byte.
+fn bearer_matches(header: Option<&str>, token: &str) -> bool {
+    let Some(given) = header.and_then(|value| value.strip_prefix("Bearer ")) else {
+        return false;
+    };
+    let (given, token) = (given.trim().as_bytes(), token.as_bytes());
+    given.len() == token.len()
+        && given
+            .iter()
+            .zip(token)
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// Browsers send `Origin`; a loopback server only answers pages served from
+/// loopback, which blocks DNS-rebinding attacks from arbitrary websites.
+fn origin_allowed(origin: &str, bind: IpAddr) -> bool {
+    if !bind.is_loopback() {
+        return true;
+    }
+    let Some((_, rest)) = origin.split_once("://") else {
+        return false;
+    };
+    let authority = rest.split('/').next().unwrap_or_default();
+    let host = if let Some(bracketed) = authority.strip_prefix('[') {
+        bracketed
+            .split_once(']')
+            .map_or(bracketed, |(host, _)| host)
+    } else {
+        authority.split(':').next().unwrap_or_default()
+    };
+    host.eq_ignore_ascii_case("localhost")
+        || host.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+#[derive(Debug)]
+struct HttpRequest {
+    method: String,
+    path: String,
+    version: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl HttpRequest {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn wants_close(&self) -> bool {
+        match self.header("connection") {
+            Some(value) if value.eq_ignore_ascii_case("close") => true,
+            Some(value) if value.eq_ignore_ascii_case("keep-alive") => false,
+            _ => self.version == "HTTP/1.0",
+        }
+    }
+
+    fn accepts_event_stream(&self) -> bool {
+        self.header("accept")
+            .is_some_and(|value| value.to_ascii_lowercase().contains("text/event-stream"))
+    }
+}
+
+/// Read one request. `Ok(None)` means the client closed the connection (or
+/// went idle); `Err` is the response to send before closing.
+fn read_request<R: BufRead>(
+    reader: &mut R,
+) -> std::result::Result<Option<HttpRequest>, HttpResponse> {
+    let bad = |message: &str| HttpResponse::text(400, "Bad Request", message);
+    let Some(request_line) = read_line(reader)? else {
+        return Ok(None);
+    };
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(path), Some(version)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(bad("malformed request line"));
+    };
+    if !version.starts_with("HTTP/1.") {
+        return Err(HttpResponse::text(
+            505,
+            "HTTP Version Not Supported",
+            "HTTP/1.x only",
+        ));
+    }
+
+    let mut headers = Vec::new();
+    loop {
+        let line = read_line(reader)?.ok_or_else(|| bad("truncated headers"))?;
+        if line.is_empty() {
+            break;
+        }
+        if headers.len() == MAX_HEADERS {
+            return Err(bad("too many headers"));
+        }
+        let (name, value) = line
+            .split_once(':')
+            .ok_or_else(|| bad("malformed header"))?;
+        headers.push((name.trim().to_string(), value.trim().to_string()));
+    }
+    let mut request = HttpRequest {
+        method: method.to_ascii_uppercase(),
+        path: path.to_string(),
+        version: version.to_string(),
+        headers,
+        body: Vec::new(),
+    };
+
+    if request.header("transfer-encoding").is_some() {
+        return Err(HttpResponse::text(
+            411,
+            "Length Required",
+            "chunked bodies are not supported; send Content-Length",
+        ));
+    }
+    let length = match request.header("content-length") {
+        Some(value) => value
+            .parse::<usize>()
+            .map_err(|_| bad("invalid Content-Length"))?,
+        None => 0,
+    };
+    if length > MAX_BODY_BYTES {
+        return Err(HttpResponse::text(
+            413,
+            "Payload Too Large",
+            "request body too large",
+        ));
+    }
+    request.body = vec![0; length];
+    reader
+        .read_exact(&mut request.body)
+        .map_err(|_| bad("truncated body"))?;
+    Ok(Some(request))
+}
+
+/// One CRLF- (or LF-) terminated line, without the terminator.
+fn read_line<R: BufRead>(reader: &mut R) -> std::result::Result<Option<String>, HttpResponse> {
+    let mut line = String::new();
+    match reader.take(MAX_HEADER_LINE).read_line(&mut line) {
+        Ok(0) => Ok(None),
+        Ok(_) if !line.ends_with('\n') => Err(HttpResponse::text(
+            431,
+            "Request Header Fields Too Large",
+            "header line too long",
+        )),
+        Ok(_) => Ok(Some(line.trim_end_matches(['\r', '\n']).to_string())),
+        // Idle timeout or reset: just close.
+        Err(_) => Ok(None),
+    }
+}
+
+/// How streamed replies are framed in the response body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Framing {
+    /// One `message` SSE event per reply
+    EventStream,
+    /// A JSON array, for batched requests
+    JsonArray,
+    /// The single reply as-is
+    Json,
+}
+
+/// Replies written to the client as the dispatcher produces them.
+#[derive(Debug)]
+struct ReplyStream {
+    first: String,
+    rest: Receiver<Option<String>>,
+    framing: Framing,
+}
+
+impl ReplyStream {
+    /// Write every reply, chunk-encoded when `chunked`, flushing each so
+    /// the client sees it immediately.
+    fn write_body<W: Write>(&self, out: &mut W, chunked: bool) -> io::Result<()> {
+        let mut write = |bytes: &[u8]| -> io::Result<()> {
+            if chunked {
+                write!(out, "{:x}\r\n", bytes.len())?;
+                out.write_all(bytes)?;
+                out.write_all(b"\r\n")?;
+            } else {
+                out.write_all(bytes)?;
+            }
+            out.flush()
+        };
+        let replies = std::iter::once(self.first.clone())
+            .chain(std::iter::from_fn(|| self.rest.recv().ok().flatten()));
+        for (index, json) in replies.enumerate() {
+            let piece = match self.framing {
+                Framing::EventStream => format!("event: message\ndata: {json}\n\n"),
+                Framing::JsonArray if index == 0 => format!("[{json}"),
+                Framing::JsonArray => format!(",{json}"),
+                Framing::Json => json,
+            };
+            write(piece.as_bytes())?;
+        }
+        if self.framing == Framing::JsonArray {
+            write(b"]")?;
+        }
+        if chunked {
+            out.write_all(b"0\r\n\r\n")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct HttpResponse {
+    status: u16,
+    reason: &'static str,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+    /// Streamed body; replaces `body` when set
+    stream: Option<ReplyStream>,
+}
+
+impl HttpResponse {
+    fn body(status: u16, reason: &'static str, content_type: &str, body: Vec<u8>) -> Self {
+        Self {
+            status,
+            reason,
+            headers: vec![("Content-Type".to_string(), content_type.to_string())],
+            body,
+            stream: None,
+        }
+    }
+
+    fn stream(content_type: &str, stream: ReplyStream) -> Self {
+        Self {
+            stream: Some(stream),
+            ..Self::body(200, "OK", content_type, Vec::new())
+        }
+    }
+
+    fn text(status: u16, reason: &'static str, message: &str) -> Self {
+        Self::body(
+            status,
+            reason,
+            "text/plain; charset=utf-8",
+            format!("{message}\n").into_bytes(),
+        )
+    }
+
+    const fn empty(status: u16, reason: &'static str) -> Self {
+        Self {
+            status,
+            reason,
+            headers: Vec::new(),
+            body: Vec::new(),
+            stream: None,
+        }
+    }
+
+    fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Write the response. A streamed body is chunk-encoded on kept-alive
+    /// connections and delimited by the close otherwise.
+    fn write_to<W: Write>(&self, out: &mut W, close: bool) -> io::Result<()> {
+        let mut head = format!("HTTP/1.1 {} {}\r\n", self.status, self.reason);
+        for (name, value) in &self.headers {
+            head.push_str(&format!("{name}: {value}\r\n"));
+        }
+        match self.stream {
+            None => head.push_str(&format!("Content-Length: {}\r\n", self.body.len())),
+            Some(_) if !close => head.push_str("Transfer-Encoding: chunked\r\n"),
+            Some(_) => {}
+        }
+        if close {
+            head.push_str("Connection: close\r\n");
+        }
+        head.push_str("\r\n");
+        out.write_all(head.as_bytes())?;
+        match &self.stream {
+            None => out.write_all(&self.body)?,
+            Some(stream) => stream.write_body(out, !close)?,
+        }
+        out.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use std::net::Ipv4Addr;
+
+    fn parse(raw: &str) -> std::result::Result<Option<HttpRequest>, HttpResponse> {
+        read_request(&mut Cursor::new(raw.as_bytes().to_vec()))
+    }
+
+    #[test]
+    fn reads_requests_with_bodies() {
+        let body = r#"{"jsonrpc":"2.0","id":1,"method":"ping"}"#;
+        let raw = format!(
+            "POST /mcp HTTP/1.1\r\nHost: x\r\nContent-Type: application/json\r\n\
+             Accept: application/json, text/event-stream\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        );
+        let request = parse(&raw).unwrap().unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/mcp");
+        assert_eq!(request.body, body.as_bytes());
+        assert!(request.accepts_event_stream());
+        assert!(!request.wants_close());
+
+        assert!(parse("").unwrap().is_none());
+        assert_eq!(parse("GARBAGE\r\n\r\n").unwrap_err().status, 400);
+        let chunked = "POST /mcp HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n";
+        assert_eq!(parse(chunked).unwrap_err().status, 411);
+        let huge = format!(
+            "POST /mcp HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_BODY_BYTES + 1
+        );
+        assert_eq!(parse(&huge).unwrap_err().status, 413);
+    }
+
+    #[test]
+    fn splits_batches_and_passes_other_bodies_through() {
+        let (messages, batch) = split_messages(r#"[{"id":1},{"id":2}]"#);
+        assert!(batch);
+        assert_eq!(messages, vec![r#"{"id":1}"#, r#"{"id":2}"#]);
+
+        let (messages, batch) = split_messages("{ not json");
+        assert!(!batch);
+        assert_eq!(messages, vec!["{ not json"]);
+        assert_eq!(
+            initialize_client(r#"{"method":"initialize"}"#),
+            Some(ClientState::default())
+        );
+        let client = initialize_client(
+            r#"{"method":"initialize","params":{"clientInfo":{"name":"claude-code"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(client.agent_id.as_deref(), Some("claude-code"));
+        assert_eq!(initialize_client(r#"{"method":"ping"}"#), None);
+    }
+
+    #[test]
+    fn loopback_servers_only_accept_loopback_origins() {
+        let loopback = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        assert!(origin_allowed("http://localhost:6274", loopback));
+        assert!(origin_allowed("http://127.0.0.1", loopback));
+        assert!(origin_allowed("http://[::1]:8080", loopback));
+        assert!(!origin_allowed("https://evil.example", loopback));
+        assert!(!origin_allowed("null", loopback));
+        assert!(origin_allowed(
+            "https://evil.example",
+            IpAddr::V4(Ipv4Addr::UNSPECIFIED)
+        ));
+    }
+
+    #[test]
+    fn responses_carry_length_and_close() {
+        let mut out = Vec::new();
+        HttpResponse::body(200, "OK", "application/json", b"{}".to_vec())
+            .with_header(SESSION_HEADER, "abc")
+            .write_to(&mut out, true)
+            .unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(text.contains("Mcp-Session-Id: abc\r\n"));
+        assert!(text.contains("Content-Length: 2\r\nConnection: close\r\n\r\n{}"));
+    }
+
+    fn streamed(framing: Framing, replies: &[&str], close: bool) -> String {
+        let (tx, rest) = crossbeam_channel::unbounded();
+        for reply in &replies[1..] {
+            tx.send(Some((*reply).to_string())).unwrap();
+        }
+        tx.send(None).unwrap();
+        let stream = ReplyStream {
+            first: replies[0].to_string(),
+            rest,
+            framing,
+        };
+        let mut out = Vec::new();
+        HttpResponse::stream("application/json", stream)
+            .write_to(&mut out, close)
+            .unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn streams_replies_chunked_or_until_close() {
+        let text = streamed(Framing::JsonArray, &[r#"{"id":1}"#, r#"{"id":2}"#], false);
+        assert!(text.contains("Transfer-Encoding: chunked\r\n\r\n"));
+        assert!(!text.contains("Content-Length"));
+        assert!(text.ends_with("9\r\n[{\"id\":1}\r\n9\r\n,{\"id\":2}\r\n1\r\n]\r\n0\r\n\r\n"));
+
+        let text = streamed(Framing::EventStream, &[r#"{"id":1}"#], true);
+        assert!(!text.contains("Transfer-Encoding"));
+        assert!(text.ends_with("Connection: close\r\n\r\nevent: message\ndata: {\"id\":1}\n\n"));
+    }
+
+    #[test]
+    fn idle_sessions_expire() {
+        let start = Instant::now();
+        let mut sessions = Sessions::new(Duration::from_secs(60), 8);
+        sessions.insert("a".to_string(), ClientState::default(), start);
+        sessions.insert("b".to_string(), ClientState::default(), start);
+
+        let later = start + Duration::from_secs(45);
+        assert!(sessions.touch("a", later).is_some());
+        // "b" has been idle past the TTL, "a" was used 30s ago
+        let much_later = start + Duration::from_secs(75);
+        assert!(sessions.touch("b", much_later).is_none());
+        assert!(sessions.touch("a", much_later).is_some());
+        assert_eq!(sessions.entries.len(), 1);
+
+        sessions.remove("a");
+        assert!(sessions.touch("a", much_later).is_none());
+    }
+
+    #[test]
+    fn session_cap_evicts_least_recently_used() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut sessions = Sessions::new(Duration::from_secs(3600), 2);
+        sessions.insert("a".to_string(), ClientState::default(), at(0));
+        sessions.insert("b".to_string(), ClientState::default(), at(1));
+        assert!(sessions.touch("a", at(2)).is_some());
+
+        sessions.insert("c".to_string(), ClientState::default(), at(3));
+        assert_eq!(sessions.entries.len(), 2);
+        assert!(sessions.touch("b", at(4)).is_none());
+        assert!(sessions.touch("a", at(4)).is_some());
+        assert!(sessions.touch("c", at(4)).is_some());
+    }
+
+    #[test]
+    fn non_loopback_binds_require_a_token() {
+        let any = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+        assert!(check_bind(IpAddr::V4(Ipv4Addr::LOCALHOST), None).is_ok());
+        assert!(check_bind(any, None).is_err());
+        assert!(check_bind(any, Some("")).is_err());
+        assert!(check_bind(any, Some("s3cret")).is_ok());
+
+        assert!(bearer_matches(Some("Bearer s3cret"), "s3cret"));
+        assert!(!bearer_matches(Some("Bearer s3cre"), "s3cret"));
+        assert!(!bearer_matches(Some("Basic s3cret"), "s3cret"));
+        assert!(!bearer_matches(None, "s3cret"));
+    }
+}
@@ -0,0 +1,241 @@
+//! Validation of tool call arguments against a tool's `inputSchema`.
+//!
+//! Covers the JSON Schema subset the tool definitions use: `type` (a name
+//! or a list of names), `properties`, `required`, `items`, `enum`,
+//! `minimum`/`maximum` and `additionalProperties` given as a schema.
+//! Properties a schema does not declare are reported separately so callers
+//! can warn about them instead of rejecting older clients.
+
+use std::fmt;
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// One way the arguments break the schema, located by a JSON pointer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(super) struct Violation {
+    /// JSON pointer into the arguments (empty for the arguments themselves)
+    pub pointer: String,
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.pointer.is_empty() {
+            write!(f, "arguments: {}", self.message)
+        } else {
+            write!(f, "{}: {}", self.pointer, self.message)
+        }
+    }
+}
+
+/// Outcome of validating a tool call's arguments.
+#[derive(Debug, Default)]
+pub(super) struct Validation {
+    pub violations: Vec<Violation>,
+    /// Pointers of properties the schema does not declare
+    pub unknown: Vec<String>,
+}
+
+/// Check `value` against `schema`.
+pub(super) fn validate(schema: &Value, value: &Value) -> Validation {
+    let mut validation = Validation::default();
+    check(schema, value, "", &mut validation);
+    validation
+}
+
+fn check(schema: &Value, value: &Value, pointer: &str, out: &mut Validation) {
+    if let Some(expected) = schema.get("type") {
+        let names: Vec<&str> = match expected {
+            Value::String(name) => vec![name.as_str()],
+            Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !names.is_empty() && !names.iter().any(|name| has_type(value, name)) {
+            out.violations.push(Violation {
+                pointer: pointer.to_string(),
+                message: format!("expected {}, got {}", names.join(" or "), type_name(value)),
+            });
+            // Nested checks would only repeat the mismatch
+            return;
+        }
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array)
+        && !allowed.contains(value)
+    {
+        let allowed: Vec<String> = allowed.iter().map(Value::to_string).collect();
+        out.violations.push(Violation {
+            pointer: pointer.to_string(),
+            message: format!("expected one of {}, got {value}", allowed.join(", ")),
+        });
+    }
+
+    if let Some(number) = value.as_f64() {
+        if let Some(minimum) = schema.get("minimum").and_then(Value::as_f64)
+            && number < minimum
+        {
+            out.violations.push(Violation {
+                pointer: pointer.to_string(),
+                message: format!("must be at least {minimum}, got {value}"),
+            });
+        }
+        if let Some(maximum) = schema.get("maximum").and_then(Value::as_f64)
+            && number > maximum
+        {
+            out.violations.push(Violation {
+                pointer: pointer.to_string(),
+                message: format!("must be at most {maximum}, got {value}"),
+            });
+        }
+    }
+
+    match value {
+        Value::Object(object) => {
+            let properties = schema.get("properties").and_then(Value::as_object);
+            if let Some(required) = schema.get("required").and_then(Value::as_array) {
+                for name in required.iter().filter_map(Value::as_str) {
+                    if !object.contains_key(name) {
+                        out.violations.push(Violation {
+                            pointer: child(pointer, name),
+                            message: "required property is missing".to_string(),
+                        });
+                    }
+                }
+            }
+            let additional = schema.get("additionalProperties");
+            for (name, item) in object {
+                let pointer = child(pointer, name);
+                match properties.and_then(|properties| properties.get(name)) {
+                    Some(property) => check(property, item, &pointer, out),
+                    None => match additional {
+                        Some(Value::Bool(false)) => out.violations.push(Violation {
+                            pointer,
+                            message: "unknown property".to_string(),
+                        }),
+                        Some(additional @ Value::Object(_)) => {
+                            check(additional, item, &pointer, out);
+                        }
+                        _ if properties.is_some() => out.unknown.push(pointer),
+                        _ => {}
+                    },
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    check(item_schema, item, &child(pointer, &index.to_string()), out);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn has_type(value: &Value, name: &str) -> bool {
+    match name {
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "null" => value.is_null(),
+        "number" => value.is_number(),
+        // 5.0 is an integer to JSON Schema
+        "integer" => {
+            value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|n| n.fract() == 0.0)
+        }
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// `pointer` extended by one reference token, escaped per RFC 6901.
+fn child(pointer: &str, token: &str) -> String {
+    format!("{pointer}/{}", token.replace('~', "~0").replace('/', "~1"))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "query": { "type": "string" },
+                "limit": { "type": "integer", "minimum": 1, "maximum": 100 },
+                "mode": { "type": "string", "enum": ["fast", "full"] },
+                "tags": { "type": "array", "items": { "type": "string" } },
+                "keys": { "type": "object", "additionalProperties": { "type": "string" } }
+            },
+            "required": ["query"]
+        })
+    }
+
+    fn messages(validation: &Validation) -> Vec<String> {
+        let mut messages: Vec<String> = validation
+            .violations
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        messages.sort();
+        messages
+    }
+
+    #[test]
+    fn valid_arguments_pass() {
+        let validation = validate(
+            &schema(),
+            &json!({"query": "rust", "limit": 5.0, "mode": "fast", "tags": ["a"], "keys": {"k": "v"}}),
+        );
+        assert!(validation.violations.is_empty());
+        assert!(validation.unknown.is_empty());
+    }
+
+    #[test]
+    fn violations_carry_json_pointers() {
+        let validation = validate(
+            &schema(),
+            &json!({"limit": "10", "mode": "slow", "tags": ["a", 2], "keys": {"a/b": 1}}),
+        );
+        assert_eq!(
+            messages(&validation),
+            [
+                "/keys/a~1b: expected string, got integer",
+                "/limit: expected integer, got string",
+                "/mode: expected one of \"fast\", \"full\", got \"slow\"",
+                "/query: required property is missing",
+                "/tags/1: expected string, got integer",
+            ]
+        );
+
+        let validation = validate(&schema(), &json!({"query": "q", "limit": 0}));
+        assert_eq!(messages(&validation), ["/limit: must be at least 1, got 0"]);
+        let validation = validate(&schema(), &json!(["q"]));
+        assert_eq!(
+            messages(&validation),
+            ["arguments: expected object, got array"]
+        );
+    }
+
+    #[test]
+    fn unknown_properties_are_not_violations() {
+        let validation = validate(&schema(), &json!({"query": "q", "extra": true}));
+        assert!(validation.violations.is_empty());
+        assert_eq!(validation.unknown, ["/extra"]);
+    }
+}
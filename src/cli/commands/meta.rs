@@ -7,6 +7,8 @@ use clap::{Args, Subcommand};
 use colored::Colorize;
 
 use crate::app::AppContext;
+use crate::core::budget::{BudgetScope, dropped_warning};
+use crate::core::context_window::track_served;
 use crate::error::Result;
 use crate::meta_skills::{ConditionContext, MetaSkillManager, MetaSkillQuery, MetaSkillRegistry};
 use crate::utils::format::truncate_string;
@@ -72,6 +74,14 @@ pub struct LoadArgs {
     /// Output as JSON
     #[arg(long)]
     pub json: bool,
+
+    /// Model consuming the output, for context window warnings
+    #[arg(long, env = "MS_MODEL")]
+    pub model: Option<String>,
+
+    /// Agent session id; tokens served across loads in a session are summed
+    #[arg(long, env = "MS_SESSION_ID")]
+    pub session_id: Option<String>,
 }
 
 #[derive(Args, Debug)]
@@ -261,19 +271,52 @@ fn run_load(ctx: &AppContext, args: &LoadArgs) -> Result<()> {
         loaded_slices: &HashSet::new(),
     };
 
-    let result = manager.load(&meta_skill, args.budget, &condition_ctx)?;
+    // A configured pack budget caps whatever was requested on the command line.
+    let configured_budget = ctx
+        .config
+        .budget
+        .pack_budget(&meta_skill.id)
+        .map(|tokens| tokens as usize);
+    let budget = configured_budget.map_or(args.budget, |limit| args.budget.min(limit));
+
+    let result = manager.load(&meta_skill, budget, &condition_ctx)?;
+
+    let mut warnings = Vec::new();
+    let dropped = result.budget_dropped();
+    if configured_budget.is_some() && !dropped.is_empty() {
+        warnings.push(dropped_warning(
+            BudgetScope::Pack,
+            &meta_skill.id,
+            budget,
+            &dropped,
+        ));
+    }
+    warnings.extend(
+        track_served(
+            &ctx.ms_root,
+            &ctx.config.context_window,
+            args.model.as_deref(),
+            args.session_id.as_deref(),
+            &[(result.meta_skill_id.clone(), result.tokens_used)],
+        )
+        .map(|warning| warning.to_string()),
+    );
 
     if args.json {
         let json_output = serde_json::json!({
             "meta_skill_id": result.meta_skill_id,
             "tokens_used": result.tokens_used,
-            "budget": args.budget,
+            "budget": budget,
             "slices_loaded": result.slices.len(),
             "slices_skipped": result.skipped.len(),
             "content": result.packed_content,
+            "warnings": warnings,
         });
         println!("{}", serde_json::to_string_pretty(&json_output)?);
     } else {
+        for warning in &warnings {
+            eprintln!("{} {warning}", "warning:".yellow());
+        }
         println!(
             "{} Loaded meta-skill: {}\n",
             "SUCCESS".green().bold(),
@@ -282,8 +325,8 @@ fn run_load(ctx: &AppContext, args: &LoadArgs) -> Result<()> {
         println!(
             "Tokens: {} / {} ({:.1}% of budget)",
             result.tokens_used,
-            args.budget,
-            (result.tokens_used as f64 / args.budget as f64) * 100.0
+            budget,
+            (result.tokens_used as f64 / budget as f64) * 100.0
         );
         println!("Slices loaded: {}", result.slices.len());
 
@@ -511,13 +554,17 @@ ms meta bootstrap
     let tx_mgr =
         crate::storage::TxManager::new(ctx.db.clone(), ctx.git.clone(), ctx.ms_root.clone())?;
 
-    tx_mgr.write_skill_with_layer(&spec, crate::core::SkillLayer::Base)?;
+    tx_mgr.write_skill_with_layer(
+        &spec,
+        crate::core::SkillLayer::Base,
+        &crate::storage::Provenance::new("meta"),
+    )?;
 
     println!("Bootstrapped meta skill 'ms'.");
     Ok(())
 }
 
-fn get_meta_skill_paths(_ctx: &AppContext) -> Vec<PathBuf> {
+pub(crate) fn get_meta_skill_paths(_ctx: &AppContext) -> Vec<PathBuf> {
     let mut paths = Vec::new();
 
     // Project meta-skills directory
@@ -538,7 +585,7 @@ fn get_meta_skill_paths(_ctx: &AppContext) -> Vec<PathBuf> {
     paths
 }
 
-fn detect_tech_stacks(working_dir: &std::path::Path) -> Vec<String> {
+pub(crate) fn detect_tech_stacks(working_dir: &std::path::Path) -> Vec<String> {
     let mut stacks = Vec::new();
 
     // Check for common config files
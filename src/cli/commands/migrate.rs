@@ -7,6 +7,7 @@ use crate::cli::output::OutputFormat;
 use crate::cli::output::{HumanLayout, emit_json};
 use crate::core::{SkillLayer, migrate_spec};
 use crate::error::{MsError, Result};
+use crate::storage::Provenance;
 use crate::storage::tx::TxManager;
 
 #[derive(Args, Debug)]
@@ -47,7 +48,7 @@ pub fn run(ctx: &AppContext, args: &MigrateArgs) -> Result<()> {
             changed_count += 1;
             if !args.check {
                 let layer = resolve_layer(ctx, &skill_id);
-                tx_mgr.write_skill_with_layer(&migrated, layer)?;
+                tx_mgr.write_skill_with_layer(&migrated, layer, &Provenance::new("migrate"))?;
             }
         }
 
@@ -94,19 +95,10 @@ fn resolve_targets(ctx: &AppContext, inputs: &[String]) -> Result<Vec<String>> {
         return ctx.git.list_skill_ids();
     }
 
-    let mut out = Vec::new();
-    for input in inputs {
-        if let Some(skill) = ctx.db.get_skill(input)? {
-            out.push(skill.id);
-            continue;
-        }
-        if let Ok(Some(alias)) = ctx.db.resolve_alias(input) {
-            out.push(alias.canonical_id);
-            continue;
-        }
-        out.push(input.clone());
-    }
-    Ok(out)
+    inputs
+        .iter()
+        .map(|input| ctx.canonical_skill_id(input))
+        .collect()
 }
 
 fn resolve_layer(ctx: &AppContext, skill_id: &str) -> SkillLayer {
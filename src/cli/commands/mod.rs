@@ -5,13 +5,17 @@
 //! - `run()` function to execute the command
 
 use std::collections::HashSet;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use walkdir::WalkDir;
 
 use crate::app::AppContext;
+use crate::cass::{CASS_CACHE_FILE, CassClient, FingerprintCache, ProbeCache};
 use crate::cli::Commands;
+use crate::core::lookup::{self, ResolveOptions};
 use crate::error::Result;
+pub(crate) use crate::utils::path::expand_path;
 
 pub mod alias;
 pub mod antipatterns;
@@ -19,9 +23,11 @@ pub mod auth;
 pub mod backup;
 pub mod bandit;
 pub mod browse;
+pub mod budget;
 pub mod build;
 pub mod bundle;
 pub mod cm;
+pub mod completions;
 pub mod config;
 pub mod conflicts;
 pub mod contract;
@@ -31,13 +37,17 @@ pub mod diff;
 pub mod doctor;
 pub mod edit;
 pub mod embed;
+pub mod errors;
 pub mod evidence;
 pub mod experiment;
+pub mod export;
+pub mod export_site;
 pub mod favorite;
 pub mod feedback;
 pub mod fmt;
 pub mod graph;
 pub mod hide;
+pub mod hook;
 pub mod import;
 pub mod inbox;
 pub mod index;
@@ -50,7 +60,10 @@ pub mod machine;
 pub mod mcp;
 pub mod meta;
 pub mod migrate;
+pub mod new;
+pub mod open;
 pub mod outcome;
+pub mod pack;
 pub mod personalize;
 pub mod pre_commit;
 pub mod preferences;
@@ -59,6 +72,8 @@ pub mod quality;
 pub mod recommend;
 pub mod remote;
 pub mod requirements;
+pub mod resolve;
+pub mod review;
 pub mod safety;
 pub mod search;
 pub mod security;
@@ -66,13 +81,16 @@ pub mod setup;
 pub mod shell;
 pub mod show;
 pub mod simulate;
+pub mod stats;
 pub mod suggest;
 pub mod sync;
 pub mod template;
 pub mod test;
+pub mod trial;
 pub mod unhide;
 pub mod update;
 pub mod validate;
+pub mod why_suggested;
 
 /// Dispatch a command to its handler
 pub fn run(ctx: &AppContext, command: &Commands) -> Result<()> {
@@ -86,20 +104,26 @@ pub fn run(ctx: &AppContext, command: &Commands) -> Result<()> {
         Commands::Load(args) => load::run(ctx, args),
         Commands::Install(args) => install::run(ctx, args),
         Commands::Suggest(args) => suggest::run(ctx, args),
+        Commands::WhySuggested(args) => why_suggested::run(ctx, args),
         Commands::Show(args) => show::run(ctx, args),
+        Commands::Open(args) => open::run(ctx, args),
         Commands::List(args) => list::run(ctx, args),
         Commands::Inbox(args) => inbox::run(ctx, args),
         Commands::Lint(args) => lint::run(ctx, args),
         Commands::Edit(args) => edit::run(ctx, args),
+        Commands::New(args) => new::run(ctx, args),
         Commands::Fmt(args) => fmt::run(ctx, args),
         Commands::Diff(args) => diff::run(ctx, args),
         Commands::Dedup(args) => dedup::run(ctx, args),
         Commands::Alias(args) => alias::run(ctx, args),
+        Commands::Resolve(args) => resolve::run(ctx, args),
         Commands::Requirements(args) => requirements::run(ctx, args),
+        Commands::Review(args) => review::run(ctx, args),
         Commands::Favorite(args) => favorite::run(ctx, args),
         Commands::Feedback(args) => feedback::run(ctx, args),
         Commands::Hide(args) => hide::run(ctx, args),
         Commands::Outcome(args) => outcome::run(ctx, args),
+        Commands::Trial(args) => trial::run(ctx, args),
         Commands::Personalize(args) => personalize::run(ctx, args),
         Commands::Preferences(args) => preferences::run(ctx, args),
         Commands::Experiment(args) => experiment::run(ctx, args),
@@ -117,7 +141,10 @@ pub fn run(ctx: &AppContext, command: &Commands) -> Result<()> {
         Commands::Cm(args) => cm::run(ctx, args),
         Commands::Update(args) => update::run(ctx, args),
         Commands::Bandit(args) => bandit::run(ctx, args),
+        Commands::Budget(args) => budget::run(ctx, args),
+        Commands::Pack(args) => pack::run(ctx, args),
         Commands::Backup(args) => backup::run(ctx, args),
+        Commands::Restore(args) => backup::run_restore_file(ctx, args),
         Commands::Browse(args) => browse::run(ctx, args),
         Commands::Doctor(args) => doctor::run(ctx, args),
         Commands::PreCommit(args) => pre_commit::run(ctx, args),
@@ -126,17 +153,24 @@ pub fn run(ctx: &AppContext, command: &Commands) -> Result<()> {
         Commands::Security(args) => security::run(ctx, args),
         Commands::Setup(args) => setup::run(ctx, args),
         Commands::Shell(args) => shell::run(ctx, args),
+        Commands::Completions(args) => completions::run(args),
+        Commands::Complete(args) => completions::run_complete(args),
+        Commands::Hook(args) => hook::run(args),
+        Commands::Errors(args) => errors::run(ctx.output_format, args),
         Commands::Safety(args) => safety::run(ctx, args),
         Commands::Validate(args) => validate::run(ctx, args),
         Commands::Test(args) => test::run(ctx, args),
         Commands::Unhide(args) => unhide::run(ctx, args),
         Commands::Simulate(args) => simulate::run(ctx, args),
         Commands::Quality(args) => quality::run(ctx, args),
+        Commands::Stats(args) => stats::run(ctx, args),
         Commands::Recommend(args) => recommend::run(ctx, args),
         Commands::Evidence(args) => evidence::run(ctx, args),
         Commands::Mcp(args) => mcp::run(ctx, args),
         Commands::Template(args) => template::run(ctx, args),
         Commands::Embed(args) => embed::run(ctx, args),
+        Commands::Export(args) => export::run(ctx, args),
+        Commands::ExportSite(args) => export_site::run(ctx, args),
     }
 }
 
@@ -176,6 +210,14 @@ pub(crate) fn resolve_skill_markdown(ctx: &AppContext, input: &str) -> Result<Pa
         }
     }
 
+    let resolution = lookup::resolve(&ctx.db, input, &ResolveOptions::default())?;
+    if let Some(record) = &resolution.record {
+        let indexed = PathBuf::from(&record.source_path);
+        if indexed.is_file() {
+            return Ok(indexed);
+        }
+    }
+
     for root in skill_roots(ctx) {
         let candidate = root.join(input);
         if candidate.is_file() {
@@ -187,9 +229,43 @@ pub(crate) fn resolve_skill_markdown(ctx: &AppContext, input: &str) -> Result<Pa
         }
     }
 
-    Err(crate::error::MsError::SkillNotFound(format!(
-        "skill not found: {input}"
-    )))
+    Err(match resolution.into_record() {
+        Ok(record) => crate::error::MsError::SkillNotFound(format!(
+            "skill not found: {input} (indexed at {}, but the file is missing)",
+            record.source_path
+        )),
+        Err(err) => err,
+    })
+}
+
+/// CASS client for the configured binary (or `binary`), with probe results
+/// and session fingerprints persisted in the ms root. `no_cache` ignores
+/// cached probes but still records the fresh ones.
+pub(crate) fn cass_client(ctx: &AppContext, binary: Option<&Path>, no_cache: bool) -> CassClient {
+    let client = match binary {
+        Some(path) => CassClient::with_binary(path),
+        None => ctx
+            .config
+            .cass
+            .cass_path
+            .as_ref()
+            .map_or_else(CassClient::new, CassClient::with_binary),
+    };
+    let ttl = if no_cache {
+        Duration::ZERO
+    } else {
+        Duration::from_secs(ctx.config.cass.cache_ttl_secs)
+    };
+    let path = ctx.ms_root.join(CASS_CACHE_FILE);
+    match (ProbeCache::open(&path, ttl), FingerprintCache::open(&path)) {
+        (Ok(probes), Ok(fingerprints)) => client
+            .with_probe_cache(probes)
+            .with_fingerprint_cache(fingerprints),
+        (Err(err), _) | (_, Err(err)) => {
+            tracing::debug!(error = %err, path = %path.display(), "cass cache unavailable");
+            client
+        }
+    }
 }
 
 fn skill_roots(ctx: &AppContext) -> Vec<PathBuf> {
@@ -203,17 +279,3 @@ fn skill_roots(ctx: &AppContext) -> Vec<PathBuf> {
         .chain(ctx.config.skill_paths.local.iter());
     paths.map(|path| expand_path(path)).collect()
 }
-
-fn expand_path(input: &str) -> PathBuf {
-    if let Some(stripped) = input.strip_prefix("~/") {
-        if let Some(home) = dirs::home_dir() {
-            return home.join(stripped);
-        }
-    }
-    if input == "~" {
-        if let Some(home) = dirs::home_dir() {
-            return home;
-        }
-    }
-    PathBuf::from(input)
-}
@@ -0,0 +1,195 @@
+//! ms new - Scaffold a new skill from a template
+
+use std::path::PathBuf;
+
+use clap::Args;
+use colored::Colorize;
+
+use crate::app::AppContext;
+use crate::cli::commands::edit::run_editor;
+use crate::cli::commands::index::index_one;
+use crate::cli::output::{OutputFormat, emit_json};
+use crate::core::SkillLayer;
+use crate::core::scaffold::{
+    ScaffoldTemplate, TemplateSource, discover_templates, user_templates_dir,
+};
+use crate::core::spec_lens::{compile_markdown, slugify};
+use crate::error::{MsError, Result};
+use crate::security::SafetyGate;
+
+#[derive(Args, Debug)]
+pub struct NewArgs {
+    /// Id of the new skill (lowercase, e.g. `rust-error-handling`)
+    #[arg(required_unless_present = "list_templates")]
+    pub skill_id: Option<String>,
+
+    /// Template to start from (checklist, playbook, command-recipe, pitfalls,
+    /// or a user template)
+    #[arg(long, short, default_value = "checklist")]
+    pub template: String,
+
+    /// List available templates and exit
+    #[arg(long)]
+    pub list_templates: bool,
+
+    /// Directory to create the skill in (default: first project skill path)
+    #[arg(long)]
+    pub dir: Option<PathBuf>,
+
+    /// Open the new SKILL.md in $EDITOR
+    #[arg(long)]
+    pub edit: bool,
+
+    /// Index the new skill after writing it
+    #[arg(long)]
+    pub index: bool,
+}
+
+pub fn run(ctx: &AppContext, args: &NewArgs) -> Result<()> {
+    let templates = discover_templates(user_templates_dir().as_deref())?;
+    if args.list_templates {
+        return list(ctx, &templates);
+    }
+
+    let skill_id = args.skill_id.as_deref().unwrap_or_default();
+    validate_skill_id(skill_id)?;
+
+    let template = templates
+        .iter()
+        .find(|t| t.name == args.template)
+        .ok_or_else(|| {
+            let names: Vec<_> = templates.iter().map(|t| t.name.as_str()).collect();
+            MsError::NotFound(format!(
+                "template not found: {} (available: {})",
+                args.template,
+                names.join(", ")
+            ))
+        })?;
+
+    let root = args.dir.clone().unwrap_or_else(|| {
+        ctx.config.skill_paths.project.first().map_or_else(
+            || PathBuf::from(".ms/skills"),
+            |path| ctx.project_skill_path(path),
+        )
+    });
+    let skill_dir = root.join(skill_id);
+    let skill_path = skill_dir.join("SKILL.md");
+    if skill_path.exists() {
+        return Err(MsError::ValidationFailed(format!(
+            "{} already exists; refusing to overwrite",
+            skill_path.display()
+        )));
+    }
+
+    let spec = template.render(skill_id)?;
+    std::fs::create_dir_all(&skill_dir)?;
+    std::fs::write(&skill_path, compile_markdown(&spec))?;
+
+    if args.edit {
+        let editor =
+            std::env::var("EDITOR").map_err(|_| MsError::Config("EDITOR not set".to_string()))?;
+        run_editor(&SafetyGate::from_context(ctx), &editor, &skill_path)?;
+    }
+    if args.index {
+        index_one(ctx, &skill_path, SkillLayer::Project)?;
+    }
+
+    if ctx.output_format != OutputFormat::Human {
+        return emit_json(&serde_json::json!({
+            "status": "ok",
+            "skill_id": skill_id,
+            "template": template.name,
+            "path": skill_path.display().to_string(),
+            "indexed": args.index,
+        }));
+    }
+
+    println!(
+        "{} {} from template {}",
+        "Created".green().bold(),
+        skill_id.bold(),
+        template.name.cyan()
+    );
+    println!("  Path: {}", skill_path.display());
+    if !args.index {
+        println!();
+        println!("Fill in the TODO blocks, then run `ms lint {skill_id}` and `ms index`.");
+    }
+    Ok(())
+}
+
+fn list(ctx: &AppContext, templates: &[ScaffoldTemplate]) -> Result<()> {
+    if ctx.output_format != OutputFormat::Human {
+        let payload: Vec<_> = templates
+            .iter()
+            .map(|template| {
+                let (source, path) = match &template.source {
+                    TemplateSource::Builtin(_) => ("builtin", None),
+                    TemplateSource::User(path) => ("user", Some(path.display().to_string())),
+                };
+                serde_json::json!({
+                    "name": template.name,
+                    "summary": template.summary,
+                    "source": source,
+                    "path": path,
+                })
+            })
+            .collect();
+        return emit_json(&serde_json::json!({
+            "status": "ok",
+            "count": templates.len(),
+            "templates": payload,
+        }));
+    }
+
+    println!("Templates:");
+    for template in templates {
+        let source = match &template.source {
+            TemplateSource::Builtin(_) => String::new(),
+            TemplateSource::User(path) => format!(" ({})", path.display()),
+        };
+        println!(
+            "  {:<16} {}{}",
+            template.name,
+            template.summary,
+            source.dimmed()
+        );
+    }
+    if let Some(dir) = user_templates_dir() {
+        println!();
+        println!("Add your own as <name>.md in {}", dir.display());
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_skill_id(skill_id: &str) -> Result<()> {
+    let valid = !skill_id.is_empty()
+        && !skill_id.starts_with('.')
+        && skill_id
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "-_.".contains(c));
+    if valid {
+        return Ok(());
+    }
+    Err(MsError::ValidationFailed(format!(
+        "invalid skill id '{skill_id}': use lowercase letters, digits, '-', '_' or '.' (try '{}')",
+        slugify(skill_id)
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skill_ids_must_be_lowercase_slugs() {
+        assert!(validate_skill_id("rust-error-handling").is_ok());
+        assert!(validate_skill_id("py3.12_tips").is_ok());
+
+        let err = validate_skill_id("Rust Errors").unwrap_err().to_string();
+        assert!(err.contains("try 'rust-errors'"), "{err}");
+        assert!(validate_skill_id("").is_err());
+        assert!(validate_skill_id("../escape").is_err());
+        assert!(validate_skill_id("..").is_err());
+    }
+}
@@ -0,0 +1,89 @@
+//! ms open - Resolve an `ms://` deep link
+//!
+//! Prints just the linked section or block, or with `--web` opens the
+//! configured web URL for it. Links to anchors that no longer exist fall back
+//! to the whole skill and name the nearest current anchor.
+
+use clap::Args;
+
+use crate::app::AppContext;
+use crate::cli::output::{OutputFormat, emit_json};
+use crate::core::deep_link::{AnchorResolution, DeepLink, ResolvedLink, resolve_link};
+use crate::error::{MsError, Result};
+
+#[derive(Args, Debug)]
+pub struct OpenArgs {
+    /// Deep link, e.g. ms://project/rust-error-handling#rule-no-unwrap-in-handlers
+    pub link: String,
+
+    /// Open the web URL for the link (requires [links] web_url) instead of printing
+    #[arg(long)]
+    pub web: bool,
+}
+
+pub fn run(ctx: &AppContext, args: &OpenArgs) -> Result<()> {
+    let link = DeepLink::parse(&args.link)?;
+    let resolved = resolve_link(&ctx.db, &link)?;
+
+    let url = if args.web {
+        let template = ctx.config.links.web_url.as_deref().ok_or_else(|| {
+            MsError::MissingConfig(
+                "links.web_url (URL template under [links], or MS_LINKS_WEB_URL)".to_string(),
+            )
+        })?;
+        Some(resolved.web_url(template))
+    } else {
+        None
+    };
+
+    if ctx.output_format != OutputFormat::Human {
+        let payload = serde_json::json!({
+            "status": "ok",
+            "resolved": resolved,
+            "canonical_link": resolved.canonical_link().to_string(),
+            "url": url,
+            "content": if url.is_none() { Some(resolved.content()) } else { None },
+        });
+        return emit_json(&payload);
+    }
+
+    if let Some(note) = fallback_note(&resolved) {
+        eprintln!("{note}");
+    }
+    if let Some(url) = url {
+        if !crate::auth::device_code::open_browser(&url) {
+            eprintln!("Could not open a browser; visit the URL below.");
+        }
+        println!("{url}");
+    } else {
+        print!("{}", resolved.content());
+    }
+    Ok(())
+}
+
+/// Stderr note for links that only partially resolved.
+fn fallback_note(resolved: &ResolvedLink) -> Option<String> {
+    let mut notes = Vec::new();
+    if resolved.via_alias {
+        notes.push(format!(
+            "note: '{}' was renamed; canonical link is {}",
+            resolved.link.skill,
+            resolved.canonical_link()
+        ));
+    }
+    if let Some(AnchorResolution::Missing { requested, nearest }) = &resolved.anchor {
+        let mut line = format!("anchor not found: #{requested}; showing the whole skill");
+        if let Some(nearest) = nearest {
+            let suggestion = DeepLink {
+                anchor: Some(nearest.id.clone()),
+                ..resolved.canonical_link()
+            };
+            line.push_str(&format!(
+                "\nnearest match: {suggestion} ({})",
+                nearest.label
+            ));
+        }
+        notes.push(line);
+    }
+    (!notes.is_empty()).then(|| notes.join("\n"))
+}
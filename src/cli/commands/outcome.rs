@@ -28,7 +28,7 @@ pub fn run(ctx: &AppContext, args: &OutcomeArgs) -> Result<()> {
         ));
     }
 
-    let skill_id = resolve_skill_id(ctx, &args.skill)?;
+    let skill_id = ctx.resolve_skill_id(&args.skill)?;
     let success = args.success;
     ctx.db.record_skill_outcome(&skill_id, success)?;
 
@@ -50,18 +50,6 @@ pub fn run(ctx: &AppContext, args: &OutcomeArgs) -> Result<()> {
     Ok(())
 }
 
-fn resolve_skill_id(ctx: &AppContext, input: &str) -> Result<String> {
-    if let Some(skill) = ctx.db.get_skill(input)? {
-        return Ok(skill.id);
-    }
-    if let Ok(Some(alias)) = ctx.db.resolve_alias(input) {
-        if let Some(skill) = ctx.db.get_skill(&alias.canonical_id)? {
-            return Ok(skill.id);
-        }
-    }
-    Err(MsError::SkillNotFound(format!("skill not found: {input}")))
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
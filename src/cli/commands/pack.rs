@@ -0,0 +1,968 @@
+//! ms pack - Assemble skills into one context pack for an agent session
+//!
+//! `ms pack --skills a,b --query "terraform deploys" --budget 12000` collects
+//! the named skills, the best matches for the query and their dependencies,
+//! packs their slices under one budget and writes a single markdown file with
+//! a table of contents. A manifest next to the file records how the pack was
+//! built and the content hash of every skill considered, so
+//! `ms pack --refresh pack.md` rebuilds it only when one of them changed.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::{Args, ValueEnum};
+use serde::{Deserialize, Serialize};
+
+use crate::app::AppContext;
+use crate::cli::commands::load::{
+    DepsMode, budget_slices, dependency_plan, meta_list, served_spec,
+};
+use crate::cli::output::{HumanLayout, OutputFormat, emit_human, emit_json};
+use crate::core::context_window::context_window_for;
+use crate::core::dependencies::DisclosureLevel as DepDisclosure;
+use crate::core::disclosure::{PackMode, render_packed_body};
+use crate::core::packing::{
+    CoverageQuota, MandatoryPredicate, MandatorySlice, MultiPackResult, PackConstraints, PackError,
+    SkillSlices, pack_skills,
+};
+use crate::core::tokenizer::{self, CachedCounter, TokenCounter, TokenizerKind};
+use crate::error::{MsError, Result};
+use crate::storage::sqlite::SkillRecord;
+
+/// Suffix of the manifest written next to a pack (`pack.md` ->
+/// `pack.ms-pack.json`).
+pub const PACK_MANIFEST_EXTENSION: &str = "ms-pack.json";
+
+const MANIFEST_VERSION: u32 = 1;
+
+/// Without `--budget`, a pack takes this fraction (1/n) of the profile's
+/// context window.
+const DEFAULT_WINDOW_DIVISOR: usize = 10;
+
+/// Coverage groups tried, in order, for the quota that makes each requested
+/// skill contribute more than its policy slices.
+const LEAD_GROUPS: &[&str] = &[
+    "overview",
+    "rules",
+    "commands",
+    "pitfalls",
+    "examples",
+    "checklists",
+    "reference",
+];
+
+#[derive(Args, Debug)]
+pub struct PackArgs {
+    /// Skills to include, comma-separated (ids or aliases)
+    #[arg(
+        long,
+        value_delimiter = ',',
+        required_unless_present_any = ["query", "refresh"]
+    )]
+    pub skills: Vec<String>,
+
+    /// Also include the best matches for this search query
+    #[arg(long)]
+    pub query: Option<String>,
+
+    /// Maximum number of skills taken from --query
+    #[arg(long, default_value = "5", requires = "query")]
+    pub query_limit: usize,
+
+    /// Token budget (default: a tenth of the profile's context window)
+    #[arg(long)]
+    pub budget: Option<usize>,
+
+    /// Model family the pack is for; picks the tokenizer and context window
+    #[arg(long, value_enum, default_value = "claude")]
+    pub model_profile: ModelProfile,
+
+    /// Dependency loading strategy
+    #[arg(long, value_enum, default_value = "auto")]
+    pub deps: DepsMode,
+
+    /// Max slices per coverage group of each skill
+    #[arg(long, default_value = "4")]
+    pub max_per_group: usize,
+
+    /// Markdown file to write
+    #[arg(long, short, default_value = "pack.md")]
+    pub out: PathBuf,
+
+    /// Rebuild an existing pack from its manifest if any member skill changed
+    #[arg(
+        long,
+        value_name = "PACK",
+        conflicts_with_all = ["skills", "query", "budget", "model_profile", "deps", "out"]
+    )]
+    pub refresh: Option<PathBuf>,
+
+    /// With --refresh, rebuild even when no member skill changed
+    #[arg(long, requires = "refresh")]
+    pub force: bool,
+}
+
+/// Model family a pack is sized for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModelProfile {
+    /// Claude models; counts with the configured tokenizer
+    #[default]
+    Claude,
+    /// GPT-4o era models; counts with o200k when ms has the `bpe` feature
+    Gpt,
+}
+
+impl ModelProfile {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Claude => "claude",
+            Self::Gpt => "gpt",
+        }
+    }
+
+    /// Model name looked up in `[context_window]` and the built-in table.
+    const fn model(self) -> &'static str {
+        match self {
+            Self::Claude => "claude",
+            Self::Gpt => "gpt-4o",
+        }
+    }
+}
+
+/// How a pack is built; stored in the manifest for `--refresh`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackRequest {
+    pub skills: Vec<String>,
+    pub query: Option<String>,
+    pub query_limit: usize,
+    pub budget: Option<usize>,
+    pub model_profile: ModelProfile,
+    pub deps: DepsMode,
+    pub max_per_group: usize,
+}
+
+impl From<&PackArgs> for PackRequest {
+    fn from(args: &PackArgs) -> Self {
+        Self {
+            skills: args.skills.clone(),
+            query: args.query.clone(),
+            query_limit: args.query_limit,
+            budget: args.budget,
+            model_profile: args.model_profile,
+            deps: args.deps,
+            max_per_group: args.max_per_group,
+        }
+    }
+}
+
+/// Manifest written next to a pack.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackManifest {
+    pub version: u32,
+    pub generated_at: String,
+    pub request: PackRequest,
+    /// Content hash of every skill considered, including dropped ones
+    pub skills: BTreeMap<String, String>,
+}
+
+impl PackManifest {
+    #[must_use]
+    pub fn path_for(pack: &Path) -> PathBuf {
+        pack.with_extension(PACK_MANIFEST_EXTENSION)
+    }
+
+    pub fn load(pack: &Path) -> Result<Self> {
+        let path = Self::path_for(pack);
+        if !path.exists() {
+            return Err(MsError::NotFound(format!(
+                "no pack manifest at {}; build the pack with `ms pack --out {}` first",
+                path.display(),
+                pack.display()
+            )));
+        }
+        let manifest: Self = serde_json::from_str(&fs::read_to_string(&path)?).map_err(|err| {
+            MsError::Config(format!("invalid pack manifest {}: {err}", path.display()))
+        })?;
+        if manifest.version > MANIFEST_VERSION {
+            return Err(MsError::Config(format!(
+                "pack manifest {} has version {}; this ms reads up to {MANIFEST_VERSION}",
+                path.display(),
+                manifest.version
+            )));
+        }
+        Ok(manifest)
+    }
+
+    pub fn save(&self, pack: &Path) -> Result<()> {
+        fs::write(Self::path_for(pack), serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Skills whose content hash differs from the recorded one; `current`
+    /// returns `None` for skills that are no longer indexed.
+    pub fn changed_skills(
+        &self,
+        mut current: impl FnMut(&str) -> Result<Option<String>>,
+    ) -> Result<Vec<String>> {
+        let mut changed = Vec::new();
+        for (skill_id, hash) in &self.skills {
+            if current(skill_id)?.as_deref() != Some(hash.as_str()) {
+                changed.push(skill_id.clone());
+            }
+        }
+        Ok(changed)
+    }
+}
+
+/// Why a skill is part of the pack, in priority order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MemberRole {
+    /// Named in `--skills`
+    Requested,
+    /// Matched `--query` (1-based rank)
+    Query { rank: usize },
+    /// Needed by another member
+    Dependency { of: String },
+}
+
+impl MemberRole {
+    fn describe(&self) -> String {
+        match self {
+            Self::Requested => "requested".to_string(),
+            Self::Query { rank } => format!("query match #{rank}"),
+            Self::Dependency { of } => format!("dependency of {of}"),
+        }
+    }
+}
+
+/// Why a skill was left out of the pack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DropReason {
+    /// None of its slices fit the budget
+    Budget,
+    /// Conflicts with a higher-priority member
+    Conflict,
+    /// Marked deprecated
+    Deprecated,
+}
+
+impl DropReason {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Budget => "budget",
+            Self::Conflict => "conflict",
+            Self::Deprecated => "deprecated",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DroppedSkill {
+    pub skill_id: String,
+    pub role: MemberRole,
+    pub reason: DropReason,
+    pub detail: String,
+}
+
+/// A skill that made it into the pack.
+#[derive(Debug, Clone, Serialize)]
+pub struct PackMember {
+    pub skill_id: String,
+    pub name: String,
+    pub layer: String,
+    pub role: MemberRole,
+    pub content_hash: String,
+    pub tokens: usize,
+    pub slices: usize,
+    /// Candidate slices left out to stay within budget
+    pub slices_dropped: usize,
+}
+
+/// Composition of a written pack.
+#[derive(Debug, Clone, Serialize)]
+pub struct PackReport {
+    pub out: PathBuf,
+    pub manifest: PathBuf,
+    pub model_profile: ModelProfile,
+    pub tokenizer: String,
+    pub budget: usize,
+    pub tokens_used: usize,
+    /// Members in priority order, as they appear in the file
+    pub members: Vec<PackMember>,
+    pub dropped: Vec<DroppedSkill>,
+    pub warnings: Vec<String>,
+}
+
+pub fn run(ctx: &AppContext, args: &PackArgs) -> Result<()> {
+    if let Some(pack) = &args.refresh {
+        return refresh(ctx, pack, args.force);
+    }
+    let report = build_pack(ctx, &PackRequest::from(args), &args.out)?;
+    output(ctx, &report, None)
+}
+
+fn refresh(ctx: &AppContext, pack: &Path, force: bool) -> Result<()> {
+    let manifest = PackManifest::load(pack)?;
+    let changed = manifest.changed_skills(|skill_id| {
+        Ok(ctx
+            .db
+            .get_skill(skill_id)?
+            .map(|record| record.content_hash))
+    })?;
+
+    if changed.is_empty() && !force {
+        if ctx.output_format == OutputFormat::Human {
+            println!("{} is up to date", pack.display());
+        } else {
+            emit_json(&serde_json::json!({
+                "status": "ok",
+                "rebuilt": false,
+                "out": pack,
+                "changed": changed,
+            }))?;
+        }
+        return Ok(());
+    }
+
+    let report = build_pack(ctx, &manifest.request, pack)?;
+    output(ctx, &report, Some(&changed))
+}
+
+/// A skill being considered for the pack.
+struct Candidate {
+    record: SkillRecord,
+    role: MemberRole,
+    /// Disclosure limit for dependencies
+    disclosure: Option<DepDisclosure>,
+    conflicts_with: Vec<String>,
+    provides: Vec<String>,
+}
+
+impl Candidate {
+    fn new(record: SkillRecord, role: MemberRole, disclosure: Option<DepDisclosure>) -> Self {
+        let meta: serde_json::Value =
+            serde_json::from_str(&record.metadata_json).unwrap_or_default();
+        Self {
+            conflicts_with: meta_list(&meta, "conflicts_with"),
+            provides: meta_list(&meta, "provides"),
+            record,
+            role,
+            disclosure,
+        }
+    }
+
+    /// Whether this skill declares a conflict with `other`, by id or by a
+    /// capability `other` provides.
+    fn conflicts_with(&self, other: &Self) -> bool {
+        self.conflicts_with
+            .iter()
+            .any(|declared| *declared == other.record.id || other.provides.contains(declared))
+    }
+
+    fn dropped(self, reason: DropReason, detail: String) -> DroppedSkill {
+        DroppedSkill {
+            skill_id: self.record.id,
+            role: self.role,
+            reason,
+            detail,
+        }
+    }
+}
+
+/// Build the pack described by `request` and write it (and its manifest) to
+/// `out`.
+fn build_pack(ctx: &AppContext, request: &PackRequest, out: &Path) -> Result<PackReport> {
+    let budget = pack_budget(ctx, request)?;
+    let counter = profile_counter(ctx, request.model_profile)?;
+    let mut warnings = Vec::new();
+    let mut dropped = Vec::new();
+
+    // Dropped skills are recorded too: editing one can change the pack
+    let mut hashes = BTreeMap::new();
+    let candidates = collect_candidates(ctx, request, &mut hashes, &mut dropped, &mut warnings)?;
+    let candidates = drop_conflicts(candidates, &mut dropped);
+
+    let mut skills = Vec::with_capacity(candidates.len());
+    for candidate in &candidates {
+        let served = served_spec(ctx, &candidate.record)?;
+        warnings.extend(
+            served
+                .warnings
+                .iter()
+                .map(|warning| format!("{}: {warning}", candidate.record.id)),
+        );
+        let slices = budget_slices(&served.spec, candidate.disclosure, counter.as_ref())
+            .into_iter()
+            .map(|mut slice| {
+                slice.coverage_group = slice
+                    .coverage_group
+                    .map(|group| member_group(&candidate.record.id, &group));
+                slice
+            })
+            .collect();
+        skills.push(SkillSlices {
+            skill_id: candidate.record.id.clone(),
+            slices,
+        });
+    }
+
+    let requested: HashSet<&str> = candidates
+        .iter()
+        .filter(|c| !matches!(c.role, MemberRole::Dependency { .. }))
+        .map(|c| c.record.id.as_str())
+        .collect();
+    let constraints = pack_constraints(budget, request.max_per_group, &skills, &requested);
+    let packed = pack_skills(&skills, &constraints, PackMode::Balanced).map_err(|err| {
+        let required = match &err {
+            PackError::MandatorySliceOmitted {
+                required_tokens, ..
+            } => *required_tokens,
+            PackError::InsufficientBudget { required, .. } => *required,
+        };
+        MsError::PackBudgetExceeded {
+            budget,
+            required,
+            message: format!("--budget {budget}: {err}"),
+        }
+    })?;
+
+    let mut members = Vec::new();
+    for (candidate, offered) in candidates.into_iter().zip(&skills) {
+        let selected = packed
+            .skills
+            .iter()
+            .find(|s| s.skill_id == candidate.record.id);
+        let Some(selected) = selected else {
+            let total = offered.slices.len();
+            let detail = if total == 0 {
+                "has no content to pack".to_string()
+            } else {
+                format!("none of its {total} slices fit")
+            };
+            dropped.push(candidate.dropped(DropReason::Budget, detail));
+            continue;
+        };
+        let record = &candidate.record;
+        members.push(PackMember {
+            skill_id: record.id.clone(),
+            name: record.name.clone(),
+            layer: record.source_layer.clone(),
+            role: candidate.role,
+            content_hash: record.content_hash.clone(),
+            tokens: selected.slices.iter().map(|s| s.token_estimate).sum(),
+            slices: selected.slices.len(),
+            slices_dropped: offered.slices.len() - selected.slices.len(),
+        });
+    }
+
+    let report = PackReport {
+        out: out.to_path_buf(),
+        manifest: PackManifest::path_for(out),
+        model_profile: request.model_profile,
+        tokenizer: counter.name().to_string(),
+        budget,
+        tokens_used: packed.total_tokens,
+        members,
+        dropped,
+        warnings,
+    };
+    fs::write(out, render_pack(&report, &packed))?;
+    PackManifest {
+        version: MANIFEST_VERSION,
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        request: request.clone(),
+        skills: hashes,
+    }
+    .save(out)?;
+    Ok(report)
+}
+
+/// `--budget`, or a share of the profile's context window; never more than
+/// the window itself.
+fn pack_budget(ctx: &AppContext, request: &PackRequest) -> Result<usize> {
+    let profile = request.model_profile;
+    let window = context_window_for(profile.model(), &ctx.config.context_window.windows);
+    let budget = match (request.budget, window) {
+        (Some(budget), Some(window)) if budget > window => {
+            return Err(MsError::Config(format!(
+                "--budget {budget} exceeds the {window}-token context window of the {} profile",
+                profile.name()
+            )));
+        }
+        (Some(budget), _) => budget,
+        (None, Some(window)) => window / DEFAULT_WINDOW_DIVISOR,
+        (None, None) => {
+            return Err(MsError::Config(format!(
+                "no context window known for the {} profile; pass --budget",
+                profile.name()
+            )));
+        }
+    };
+    if budget == 0 {
+        return Err(MsError::Config("--budget must be positive".to_string()));
+    }
+    Ok(budget)
+}
+
+/// The GPT profile counts with o200k when ms was built with `bpe`; otherwise
+/// both profiles use `[packing].tokenizer`.
+fn profile_counter(ctx: &AppContext, profile: ModelProfile) -> Result<Box<dyn TokenCounter + '_>> {
+    if profile == ModelProfile::Gpt
+        && let Ok(counter) = tokenizer::token_counter(TokenizerKind::O200k)
+    {
+        return Ok(Box::new(CachedCounter::new(counter, &ctx.db)));
+    }
+    ctx.token_counter()
+}
+
+/// Requested skills, then query matches, then their dependencies, each
+/// skill once. Deprecated skills are dropped before their dependencies are
+/// resolved. The content hash of every skill seen goes into `hashes`.
+fn collect_candidates(
+    ctx: &AppContext,
+    request: &PackRequest,
+    hashes: &mut BTreeMap<String, String>,
+    dropped: &mut Vec<DroppedSkill>,
+    warnings: &mut Vec<String>,
+) -> Result<Vec<Candidate>> {
+    let mut primary = Vec::new();
+    for reference in &request.skills {
+        let record = ctx.resolve_skill(reference)?;
+        if !hashes.contains_key(&record.id) {
+            hashes.insert(record.id.clone(), record.content_hash.clone());
+            primary.push(Candidate::new(record, MemberRole::Requested, None));
+        }
+    }
+    if let Some(query) = &request.query {
+        let hits = ctx.search.search(query, request.query_limit)?;
+        if hits.is_empty() {
+            warnings.push(format!("no skills matched query {query:?}"));
+        }
+        for (rank, hit) in hits.into_iter().enumerate() {
+            if hashes.contains_key(&hit.skill_id) {
+                continue;
+            }
+            let Some(record) = ctx.db.get_skill(&hit.skill_id)? else {
+                continue;
+            };
+            hashes.insert(record.id.clone(), record.content_hash.clone());
+            primary.push(Candidate::new(
+                record,
+                MemberRole::Query { rank: rank + 1 },
+                None,
+            ));
+        }
+    }
+
+    let mut candidates = Vec::new();
+    for candidate in primary {
+        if let Some(candidate) = deprecated(candidate, dropped) {
+            candidates.push(candidate);
+        }
+    }
+
+    let mut dependencies = Vec::new();
+    if request.deps != DepsMode::Off {
+        for candidate in &candidates {
+            let root = &candidate.record.id;
+            let Some(plan) = dependency_plan(ctx, &candidate.record, request.deps)? else {
+                continue;
+            };
+            warnings.extend(plan.missing.iter().map(|m| {
+                format!(
+                    "missing dependency capability {} (required by {})",
+                    m.capability, m.required_by
+                )
+            }));
+            for planned in plan.ordered {
+                if hashes.contains_key(&planned.skill_id) {
+                    continue;
+                }
+                let Some(record) = ctx.db.get_skill(&planned.skill_id)? else {
+                    continue;
+                };
+                hashes.insert(record.id.clone(), record.content_hash.clone());
+                let role = MemberRole::Dependency { of: root.clone() };
+                if let Some(dependency) = deprecated(
+                    Candidate::new(record, role, Some(planned.disclosure)),
+                    dropped,
+                ) {
+                    dependencies.push(dependency);
+                }
+            }
+        }
+    }
+    candidates.extend(dependencies);
+    Ok(candidates)
+}
+
+/// `candidate`, unless it is deprecated (then it is recorded as dropped).
+fn deprecated(candidate: Candidate, dropped: &mut Vec<DroppedSkill>) -> Option<Candidate> {
+    if !candidate.record.is_deprecated {
+        return Some(candidate);
+    }
+    let detail = candidate
+        .record
+        .deprecation_reason
+        .clone()
+        .unwrap_or_else(|| "marked deprecated".to_string());
+    dropped.push(candidate.dropped(DropReason::Deprecated, detail));
+    None
+}
+
+/// Drop every candidate that conflicts with a higher-priority one; either
+/// side may declare the conflict.
+fn drop_conflicts(candidates: Vec<Candidate>, dropped: &mut Vec<DroppedSkill>) -> Vec<Candidate> {
+    let mut kept: Vec<Candidate> = Vec::with_capacity(candidates.len());
+    for candidate in candidates {
+        let conflict = kept
+            .iter()
+            .find(|k| k.conflicts_with(&candidate) || candidate.conflicts_with(k))
+            .map(|k| k.record.id.clone());
+        match conflict {
+            Some(winner) => {
+                dropped.push(
+                    candidate.dropped(DropReason::Conflict, format!("conflicts with {winner}")),
+                );
+            }
+            None => kept.push(candidate),
+        }
+    }
+    kept
+}
+
+/// Coverage groups are scoped per skill so `--max-per-group` limits each
+/// skill separately instead of the pack as a whole.
+fn member_group(skill_id: &str, group: &str) -> String {
+    format!("{skill_id}:{group}")
+}
+
+/// Policy slices are mandatory and each requested skill gets a coverage
+/// quota.
+fn pack_constraints(
+    budget: usize,
+    max_per_group: usize,
+    skills: &[SkillSlices],
+    requested: &HashSet<&str>,
+) -> PackConstraints {
+    let mut constraints = PackConstraints::new(budget, max_per_group);
+    constraints.required_coverage = coverage_quotas(skills, requested);
+    // Improvement swaps only compare utility and could trade a quota slice
+    // away; the greedy fill after the quotas is enough for a pack.
+    constraints.max_improvement_passes = 0;
+    constraints
+        .mandatory_slices
+        .push(MandatorySlice::ByPredicate(MandatoryPredicate::Always));
+    constraints
+}
+
+/// One quota per requested skill on the first of its groups in
+/// [`LEAD_GROUPS`] order.
+fn coverage_quotas(skills: &[SkillSlices], requested: &HashSet<&str>) -> Vec<CoverageQuota> {
+    skills
+        .iter()
+        .filter(|skill| requested.contains(skill.skill_id.as_str()))
+        .filter_map(|skill| {
+            LEAD_GROUPS.iter().find_map(|lead| {
+                let group = member_group(&skill.skill_id, lead);
+                skill
+                    .slices
+                    .iter()
+                    .any(|slice| slice.coverage_group.as_deref() == Some(group.as_str()))
+                    .then_some(CoverageQuota {
+                        group,
+                        min_count: 1,
+                    })
+            })
+        })
+        .collect()
+}
+
+fn anchor(skill_id: &str) -> String {
+    format!("skill-{skill_id}")
+}
+
+/// Markdown of the pack: a summary, a table of contents and one attributed
+/// part per member in priority order.
+fn render_pack(report: &PackReport, packed: &MultiPackResult) -> String {
+    let mut out = String::from("# Context pack\n\n");
+    out.push_str(&format!(
+        "<!-- Generated by `ms pack`; rebuild with `ms pack --refresh {}` -->\n\n",
+        report.out.display()
+    ));
+    out.push_str(&format!(
+        "{} skills, {} of {} tokens ({} profile, {} tokenizer)\n\n",
+        report.members.len(),
+        report.tokens_used,
+        report.budget,
+        report.model_profile.name(),
+        report.tokenizer
+    ));
+
+    out.push_str("## Contents\n\n");
+    for (index, member) in report.members.iter().enumerate() {
+        out.push_str(&format!(
+            "{}. [{}](#{}) - {}\n",
+            index + 1,
+            member.name,
+            anchor(&member.skill_id),
+            member.role.describe()
+        ));
+    }
+
+    for member in &report.members {
+        let Some(selected) = packed.skills.iter().find(|s| s.skill_id == member.skill_id) else {
+            continue;
+        };
+        out.push_str(&format!(
+            "\n---\n\n<a id=\"{}\"></a>\n\n# {}\n\n",
+            anchor(&member.skill_id),
+            member.name
+        ));
+        out.push_str(&format!(
+            "> `{}` from the {} layer, {}; {} tokens",
+            member.skill_id,
+            member.layer,
+            member.role.describe(),
+            member.tokens
+        ));
+        if member.slices_dropped > 0 {
+            out.push_str(&format!(
+                ", {} slices left out for budget",
+                member.slices_dropped
+            ));
+        }
+        out.push_str("\n\n");
+        out.push_str(&render_packed_body(&selected.slices));
+        out.push('\n');
+    }
+    out
+}
+
+fn output(ctx: &AppContext, report: &PackReport, changed: Option<&[String]>) -> Result<()> {
+    if ctx.output_format != OutputFormat::Human {
+        let mut payload = serde_json::json!({
+            "status": "ok",
+            "pack": report,
+        });
+        if let Some(changed) = changed {
+            payload["rebuilt"] = true.into();
+            payload["changed"] = changed.into();
+        }
+        return emit_json(&payload);
+    }
+
+    let mut layout = HumanLayout::new();
+    layout.title("Context Pack");
+    if let Some(changed) = changed {
+        let reason = if changed.is_empty() {
+            "forced".to_string()
+        } else {
+            format!("changed: {}", changed.join(", "))
+        };
+        layout.kv("Rebuilt", &reason);
+    }
+    layout
+        .kv("File", &report.out.display().to_string())
+        .kv(
+            "Tokens",
+            &format!(
+                "{} / {} ({} profile, {} tokenizer)",
+                report.tokens_used,
+                report.budget,
+                report.model_profile.name(),
+                report.tokenizer
+            ),
+        )
+        .section("Members");
+    if report.members.is_empty() {
+        layout.push_line("(none)");
+    }
+    for member in &report.members {
+        let mut line = format!(
+            "{} - {}, {} tokens",
+            member.skill_id,
+            member.role.describe(),
+            member.tokens
+        );
+        if member.slices_dropped > 0 {
+            line.push_str(&format!(
+                " ({} slices left out for budget)",
+                member.slices_dropped
+            ));
+        }
+        layout.bullet(&line);
+    }
+    if !report.dropped.is_empty() {
+        layout.section("Dropped");
+        for skill in &report.dropped {
+            layout.bullet(&format!(
+                "{} ({}) - {}: {}",
+                skill.skill_id,
+                skill.role.describe(),
+                skill.reason.as_str(),
+                skill.detail
+            ));
+        }
+    }
+    if !report.warnings.is_empty() {
+        layout.section("Warnings");
+        for warning in &report.warnings {
+            layout.bullet(warning);
+        }
+    }
+    emit_human(layout);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::skill::{SkillSlice, SliceType};
+
+    fn slice(id: &str, slice_type: SliceType, group: &str, tokens: usize) -> SkillSlice {
+        SkillSlice {
+            id: id.to_string(),
+            slice_type,
+            token_estimate: tokens,
+            utility_score: 0.5,
+            coverage_group: Some(group.to_string()),
+            tags: Vec::new(),
+            requires: Vec::new(),
+            condition: None,
+            section_title: Some("Guide".to_string()),
+            content: format!("{id} content"),
+        }
+    }
+
+    fn record(id: &str, metadata_json: &str) -> SkillRecord {
+        SkillRecord {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            version: None,
+            author: None,
+            source_path: String::new(),
+            source_layer: "project".to_string(),
+            git_remote: None,
+            git_commit: None,
+            content_hash: format!("hash-{id}"),
+            body: String::new(),
+            metadata_json: metadata_json.to_string(),
+            assets_json: "{}".to_string(),
+            token_count: 0,
+            quality_score: 0.0,
+            indexed_at: String::new(),
+            modified_at: String::new(),
+            is_deprecated: false,
+            deprecation_reason: None,
+        }
+    }
+
+    #[test]
+    fn quotas_make_every_requested_skill_contribute() {
+        // A's slices are far denser, so without quotas they would take the
+        // whole budget before B gets any.
+        let skills = vec![
+            SkillSlices {
+                skill_id: "a".to_string(),
+                slices: (1..=6)
+                    .map(|n| slice(&format!("rule-{n}"), SliceType::Rule, "a:rules", 10))
+                    .collect(),
+            },
+            SkillSlices {
+                skill_id: "b".to_string(),
+                slices: vec![
+                    slice("policy-1", SliceType::Policy, "b:policy", 10),
+                    slice("command-1", SliceType::Command, "b:commands", 40),
+                ],
+            },
+        ];
+        let requested = HashSet::from(["a", "b"]);
+        let constraints = pack_constraints(60, 10, &skills, &requested);
+        let groups: Vec<&str> = constraints
+            .required_coverage
+            .iter()
+            .map(|q| q.group.as_str())
+            .collect();
+        assert_eq!(groups, ["a:rules", "b:commands"]);
+
+        let packed = pack_skills(&skills, &constraints, PackMode::Balanced).unwrap();
+        let b = packed.skills.iter().find(|s| s.skill_id == "b").unwrap();
+        let ids: Vec<&str> = b.slices.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, ["policy-1", "command-1"]);
+        assert_eq!(
+            packed.skills[0].skill_id, "a",
+            "members keep priority order"
+        );
+    }
+
+    #[test]
+    fn conflicts_drop_the_lower_priority_skill() {
+        let candidates = vec![
+            Candidate::new(
+                record("terraform", r#"{"provides":["iac"]}"#),
+                MemberRole::Requested,
+                None,
+            ),
+            Candidate::new(
+                record("pulumi", r#"{"conflicts_with":["iac"]}"#),
+                MemberRole::Query { rank: 1 },
+                None,
+            ),
+            Candidate::new(record("shell", "{}"), MemberRole::Query { rank: 2 }, None),
+        ];
+        let mut dropped = Vec::new();
+        let kept = drop_conflicts(candidates, &mut dropped);
+        let kept: Vec<&str> = kept.iter().map(|c| c.record.id.as_str()).collect();
+        assert_eq!(kept, ["terraform", "shell"]);
+        assert_eq!(dropped.len(), 1);
+        assert_eq!(dropped[0].skill_id, "pulumi");
+        assert_eq!(dropped[0].reason, DropReason::Conflict);
+        assert_eq!(dropped[0].detail, "conflicts with terraform");
+    }
+
+    #[test]
+    fn manifest_detects_changed_and_removed_skills() {
+        let manifest = PackManifest {
+            version: MANIFEST_VERSION,
+            generated_at: String::new(),
+            request: PackRequest {
+                skills: vec!["a".to_string()],
+                query: None,
+                query_limit: 5,
+                budget: Some(1000),
+                model_profile: ModelProfile::Claude,
+                deps: DepsMode::Auto,
+                max_per_group: 4,
+            },
+            skills: BTreeMap::from([
+                ("a".to_string(), "h1".to_string()),
+                ("b".to_string(), "h2".to_string()),
+                ("c".to_string(), "h3".to_string()),
+            ]),
+        };
+        let changed = manifest
+            .changed_skills(|id| {
+                Ok(match id {
+                    "a" => Some("h1".to_string()),
+                    "b" => Some("edited".to_string()),
+                    _ => None,
+                })
+            })
+            .unwrap();
+        assert_eq!(changed, ["b", "c"]);
+
+        let round_trip: PackManifest =
+            serde_json::from_str(&serde_json::to_string(&manifest).unwrap()).unwrap();
+        assert_eq!(round_trip.request, manifest.request);
+        assert_eq!(
+            PackManifest::path_for(Path::new("out/pack.md")),
+            PathBuf::from("out/pack.ms-pack.json")
+        );
+    }
+}
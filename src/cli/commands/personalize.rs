@@ -8,6 +8,7 @@ use colored::Colorize;
 
 use crate::app::AppContext;
 use crate::cass::CassClient;
+use crate::cli::commands::cass_client;
 use crate::cli::output::OutputFormat;
 use crate::dedup::{
     CaseStyle, CodePattern, CommentStyle, NamingConvention, PersonalizedSkill, Personalizer,
@@ -121,18 +122,17 @@ fn run_skill(ctx: &AppContext, args: &SkillArgs) -> Result<()> {
 
 fn run_extract(ctx: &AppContext, args: &ExtractArgs) -> Result<()> {
     // Check if CASS is available
-    let cass = CassClient::new();
+    let cass = cass_client(ctx, None, false);
 
-    if !cass.is_available() {
+    if let Some(reason) = cass.probe().unavailable_reason() {
         if ctx.output_format != OutputFormat::Human {
             let output = serde_json::json!({
                 "status": "error",
-                "error": "CASS server is not available"
+                "error": reason
             });
             println!("{}", serde_json::to_string_pretty(&output)?);
         } else {
-            eprintln!("{} CASS server is not available", "✗".red());
-            eprintln!("  Start CASS or configure CASS_URL in your config");
+            eprintln!("{} {reason}", "✗".red());
         }
         return Ok(());
     }
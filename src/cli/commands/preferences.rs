@@ -5,7 +5,7 @@ use clap::{Args, Subcommand};
 use crate::app::AppContext;
 use crate::cli::output::OutputFormat;
 use crate::cli::output::{HumanLayout, emit_json};
-use crate::error::{MsError, Result};
+use crate::error::Result;
 
 #[derive(Args, Debug)]
 pub struct PreferencesArgs {
@@ -82,7 +82,7 @@ pub fn run(ctx: &AppContext, args: &PreferencesArgs) -> Result<()> {
 }
 
 fn run_favorite(ctx: &AppContext, args: &FavoriteArgs) -> Result<()> {
-    let skill_id = resolve_skill_id(ctx, &args.skill)?;
+    let skill_id = ctx.resolve_skill_id(&args.skill)?;
 
     if args.remove {
         let removed = ctx.db.remove_user_preference(&skill_id, "favorite")?;
@@ -119,7 +119,7 @@ fn run_favorite(ctx: &AppContext, args: &FavoriteArgs) -> Result<()> {
 }
 
 fn run_hide(ctx: &AppContext, args: &HideArgs) -> Result<()> {
-    let skill_id = resolve_skill_id(ctx, &args.skill)?;
+    let skill_id = ctx.resolve_skill_id(&args.skill)?;
 
     let record = ctx.db.set_user_preference(&skill_id, "hidden")?;
 
@@ -141,7 +141,7 @@ fn run_hide(ctx: &AppContext, args: &HideArgs) -> Result<()> {
 }
 
 fn run_unhide(ctx: &AppContext, args: &UnhideArgs) -> Result<()> {
-    let skill_id = resolve_skill_id(ctx, &args.skill)?;
+    let skill_id = ctx.resolve_skill_id(&args.skill)?;
 
     let removed = ctx.db.remove_user_preference(&skill_id, "hidden")?;
 
@@ -214,18 +214,6 @@ fn run_list_hidden(ctx: &AppContext, args: &ListHiddenArgs) -> Result<()> {
     Ok(())
 }
 
-fn resolve_skill_id(ctx: &AppContext, input: &str) -> Result<String> {
-    if let Some(skill) = ctx.db.get_skill(input)? {
-        return Ok(skill.id);
-    }
-    if let Ok(Some(alias)) = ctx.db.resolve_alias(input) {
-        if let Some(skill) = ctx.db.get_skill(&alias.canonical_id)? {
-            return Ok(skill.id);
-        }
-    }
-    Err(MsError::SkillNotFound(format!("skill not found: {input}")))
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -4,6 +4,12 @@
 //! This command lists, purges, or restores tombstoned items. It also supports
 //! skill pruning analysis to surface low-usage, low-quality, and high-similarity
 //! candidates (proposal-first; no destructive actions).
+//!
+//! Policy flags (`--tombstoned-older-than`, `--orphaned-evidence`,
+//! `--stale-checkpoints`, `--unused-blobs`, `--expired-backups`,
+//! `--expired-trials`) select cleanup categories. They
+//! only report what would be reclaimed unless `--apply` is given; applied
+//! prunes are recorded as a commit in the Git archive.
 
 use clap::{Args, Subcommand};
 use colored::Colorize;
@@ -15,14 +21,19 @@ use which::which;
 
 use crate::app::AppContext;
 use crate::beads::{BeadsClient, CreateIssueRequest, IssueType, Priority};
+use crate::bundler::blob::BlobStore;
+use crate::bundler::registry::BundleRegistry;
+use crate::cass::brenner::{WizardCheckpoint, wizard_checkpoint_dir};
+use crate::cli::commands::backup::{expired_backups, list_backups, remove_backup};
 use crate::cli::output::OutputFormat;
 use crate::cli::output::{HumanLayout, emit_human};
 use crate::core::spec_lens::{compile_markdown, parse_markdown};
 use crate::error::{MsError, Result};
-use crate::search::embeddings::VectorIndex;
+use crate::quality::trials;
+use crate::search::embeddings::{VectorIndex, build_embedder};
 use crate::security::SafetyGate;
-use crate::storage::Database;
 use crate::storage::TombstoneManager;
+use crate::storage::{Database, Provenance};
 use fsqlite::compat::{ConnectionExt, RowExt};
 
 use crate::ms_params as params;
@@ -36,9 +47,48 @@ pub struct PruneArgs {
     #[arg(long, global = true)]
     pub dry_run: bool,
 
-    /// Older than N days (for list command)
+    /// Older than N days (for list, checkpoints and expired trials)
     #[arg(long, global = true)]
     pub older_than: Option<u32>,
+
+    /// Policy: tombstones older than this age (e.g. 30d, 4w)
+    #[arg(long, value_name = "AGE", value_parser = parse_age_days)]
+    pub tombstoned_older_than: Option<u32>,
+
+    /// Policy: evidence rows whose skill no longer exists
+    #[arg(long)]
+    pub orphaned_evidence: bool,
+
+    /// Policy: build wizard checkpoints untouched for 14 days (or --older-than)
+    #[arg(long)]
+    pub stale_checkpoints: bool,
+
+    /// Policy: bundle blobs not referenced by any installed bundle
+    #[arg(long)]
+    pub unused_blobs: bool,
+
+    /// Policy: backups past the `[backup]` retention (keep_last, max_age_days)
+    #[arg(long)]
+    pub expired_backups: bool,
+
+    /// Policy: skill trials left unreported for 7 days (or --older-than)
+    #[arg(long)]
+    pub expired_trials: bool,
+
+    /// Delete what the selected policies report (default is a dry run)
+    #[arg(long, conflicts_with = "dry_run")]
+    pub apply: bool,
+}
+
+impl PruneArgs {
+    fn has_policy(&self) -> bool {
+        self.tombstoned_older_than.is_some()
+            || self.orphaned_evidence
+            || self.stale_checkpoints
+            || self.unused_blobs
+            || self.expired_backups
+            || self.expired_trials
+    }
 }
 
 #[derive(Subcommand, Debug)]
@@ -66,6 +116,9 @@ pub enum PruneCommand {
 
     /// Apply a specific proposal
     Apply(ApplyArgs),
+
+    /// Remove finished or abandoned build wizard checkpoints
+    Checkpoints,
 }
 
 #[derive(Args, Debug)]
@@ -160,6 +213,22 @@ pub struct RestoreArgs {
 }
 
 pub fn run(ctx: &AppContext, args: &PruneArgs) -> Result<()> {
+    if args.has_policy() {
+        if args.command.is_some() {
+            return Err(MsError::ValidationFailed(
+                "policy flags (--tombstoned-older-than, --orphaned-evidence, --stale-checkpoints, \
+                 --unused-blobs, --expired-backups, --expired-trials) cannot be combined with a \
+                 prune subcommand"
+                    .to_string(),
+            ));
+        }
+        return run_policies(ctx, args);
+    }
+    if args.apply {
+        return Err(MsError::ValidationFailed(
+            "--apply needs at least one policy flag (e.g. --orphaned-evidence)".to_string(),
+        ));
+    }
     let command = args.command.as_ref().unwrap_or(&PruneCommand::List);
 
     match command {
@@ -171,9 +240,14 @@ pub fn run(ctx: &AppContext, args: &PruneArgs) -> Result<()> {
         PruneCommand::Proposals(proposals_args) => run_proposals(ctx, proposals_args, args.dry_run),
         PruneCommand::Review(review_args) => run_review(ctx, review_args, args.dry_run),
         PruneCommand::Apply(apply_args) => run_apply(ctx, apply_args, args.dry_run),
+        PruneCommand::Checkpoints => run_checkpoints(ctx, args),
     }
 }
 
+/// Days a wizard checkpoint may sit untouched before `prune checkpoints`
+/// removes it, when `--older-than` is not given.
+const CHECKPOINT_RETENTION_DAYS: u32 = 14;
+
 #[derive(Debug, Clone, serde::Serialize)]
 struct UsageCandidate {
     skill_id: String,
@@ -1266,14 +1340,21 @@ fn analyze_similarity(
     name_map: &HashMap<String, String>,
     args: &AnalyzeArgs,
 ) -> Result<Vec<SimilarityCandidate>> {
-    let embeddings = ctx.db.get_all_embeddings()?;
-    if embeddings.len() < 2 {
+    let records = ctx.db.get_all_embedding_records()?;
+    if records.len() < 2 {
         return Ok(Vec::new());
     }
-    let dims = embeddings[0].1.len();
-    let mut index = VectorIndex::new(dims);
-    for (id, embedding) in &embeddings {
-        index.insert(id.clone(), embedding.clone());
+    // Vectors from another backend are not comparable with the active one
+    let embedder = build_embedder(&ctx.config.search)?;
+    let mut index = VectorIndex::for_embedder(embedder.as_ref());
+    let mut embeddings = Vec::with_capacity(records.len());
+    for record in records {
+        index.insert_stored(
+            record.skill_id.clone(),
+            record.embedding.clone(),
+            &record.embedder_type,
+        )?;
+        embeddings.push((record.skill_id, record.embedding));
     }
 
     let mut seen: HashSet<(String, String)> = HashSet::new();
@@ -1544,7 +1625,7 @@ fn emit_beads(
     Ok(created)
 }
 
-fn beads_work_dir(ms_root: &PathBuf) -> PathBuf {
+pub(crate) fn beads_work_dir(ms_root: &PathBuf) -> PathBuf {
     let mut candidates = Vec::new();
     candidates.push(ms_root.clone());
     if let Some(parent) = ms_root.parent() {
@@ -1622,6 +1703,68 @@ fn run_list(ctx: &AppContext, args: &PruneArgs) -> Result<()> {
     Ok(())
 }
 
+fn run_checkpoints(ctx: &AppContext, args: &PruneArgs) -> Result<()> {
+    let days = args.older_than.unwrap_or(CHECKPOINT_RETENTION_DAYS);
+    let cutoff = chrono::Utc::now() - chrono::Duration::days(i64::from(days));
+    let stale: Vec<_> = WizardCheckpoint::load_all(&wizard_checkpoint_dir(&ctx.ms_root))?
+        .into_iter()
+        .filter(|(_, cp)| cp.updated_at < cutoff)
+        .collect();
+
+    if !args.dry_run {
+        for (path, _) in &stale {
+            std::fs::remove_file(path)?;
+        }
+    }
+
+    if ctx.output_format != OutputFormat::Human {
+        let items: Vec<Value> = stale
+            .iter()
+            .map(|(_, cp)| {
+                json!({
+                    "checkpoint_id": cp.id,
+                    "query": cp.query,
+                    "phase": cp.phase(),
+                    "status": if cp.is_finished() { "finished" } else { "abandoned" },
+                    "updated_at": cp.updated_at.to_rfc3339(),
+                })
+            })
+            .collect();
+        let output = json!({
+            "removed": items,
+            "count": items.len(),
+            "older_than_days": days,
+            "dry_run": args.dry_run,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    if stale.is_empty() {
+        println!("No wizard checkpoints older than {days} days.");
+        return Ok(());
+    }
+    for (_, cp) in &stale {
+        let status = if cp.is_finished() {
+            "finished"
+        } else {
+            "abandoned"
+        };
+        println!(
+            "  {} \"{}\" ({status}, last updated {})",
+            cp.id.dimmed(),
+            cp.query,
+            cp.updated_at.format("%Y-%m-%d %H:%M")
+        );
+    }
+    if args.dry_run {
+        println!("Would remove {} checkpoint(s) (dry run)", stale.len());
+    } else {
+        println!("{} Removed {} checkpoint(s)", "✓".green(), stale.len());
+    }
+    Ok(())
+}
+
 fn run_purge(ctx: &AppContext, args: &PurgeArgs) -> Result<()> {
     let manager = TombstoneManager::new(&ctx.ms_root);
     let gate = SafetyGate::from_context(ctx);
@@ -1845,7 +1988,369 @@ fn run_stats(ctx: &AppContext) -> Result<()> {
     Ok(())
 }
 
-fn format_size(bytes: u64) -> String {
+/// Parse `30d`, `4w` or a bare day count into days.
+pub(crate) fn parse_age_days(raw: &str) -> std::result::Result<u32, String> {
+    let raw = raw.trim();
+    let (count, unit) = if let Some(count) = raw.strip_suffix('w') {
+        (count, 7)
+    } else {
+        (raw.strip_suffix('d').unwrap_or(raw), 1)
+    };
+    count
+        .parse::<u32>()
+        .ok()
+        .and_then(|count| count.checked_mul(unit))
+        .ok_or_else(|| format!("invalid age {raw:?}: expected e.g. 30d, 4w or 30"))
+}
+
+/// Cleanup category selected by a policy flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+enum PolicyCategory {
+    Tombstones,
+    OrphanedEvidence,
+    StaleCheckpoints,
+    UnusedBlobs,
+    ExpiredBackups,
+    ExpiredTrials,
+}
+
+impl PolicyCategory {
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Tombstones => "tombstones",
+            Self::OrphanedEvidence => "orphaned evidence",
+            Self::StaleCheckpoints => "stale checkpoints",
+            Self::UnusedBlobs => "unused blobs",
+            Self::ExpiredBackups => "expired backups",
+            Self::ExpiredTrials => "expired trials",
+        }
+    }
+}
+
+/// What one policy found (and, with `--apply`, deleted).
+#[derive(Debug, Clone, serde::Serialize)]
+struct PolicyReport {
+    category: PolicyCategory,
+    reason: String,
+    count: usize,
+    bytes: u64,
+    /// Byte counts of `SQLite` rows are estimates from column lengths.
+    bytes_estimated: bool,
+    items: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    skipped: Option<String>,
+    deleted: usize,
+    bytes_reclaimed: u64,
+}
+
+impl PolicyReport {
+    fn new(category: PolicyCategory, reason: String) -> Self {
+        Self {
+            category,
+            reason,
+            count: 0,
+            bytes: 0,
+            bytes_estimated: false,
+            items: Vec::new(),
+            skipped: None,
+            deleted: 0,
+            bytes_reclaimed: 0,
+        }
+    }
+}
+
+fn run_policies(ctx: &AppContext, args: &PruneArgs) -> Result<()> {
+    let mut reports = Vec::new();
+    if let Some(days) = args.tombstoned_older_than {
+        reports.push(prune_tombstones(ctx, days, args.apply)?);
+    }
+    if args.orphaned_evidence {
+        reports.push(prune_orphaned_evidence(ctx, args.apply)?);
+    }
+    if args.stale_checkpoints {
+        let days = args.older_than.unwrap_or(CHECKPOINT_RETENTION_DAYS);
+        reports.push(prune_stale_checkpoints(ctx, days, args.apply)?);
+    }
+    if args.unused_blobs {
+        reports.push(prune_unused_blobs(ctx, args.apply)?);
+    }
+    if args.expired_backups {
+        reports.push(prune_expired_backups(ctx, args.apply)?);
+    }
+    if args.expired_trials {
+        let days = args.older_than.unwrap_or(trials::EXPIRY_DAYS);
+        reports.push(prune_expired_trials(ctx, days, args.apply)?);
+    }
+
+    let deleted: usize = reports.iter().map(|r| r.deleted).sum();
+    let commit = if deleted > 0 {
+        Some(record_prune_commit(ctx, &reports)?)
+    } else {
+        None
+    };
+
+    if ctx.output_format != OutputFormat::Human {
+        let output = json!({
+            "status": "ok",
+            "dry_run": !args.apply,
+            "categories": reports,
+            "total_count": reports.iter().map(|r| r.count).sum::<usize>(),
+            "total_bytes": reports.iter().map(|r| r.bytes).sum::<u64>(),
+            "deleted": deleted,
+            "bytes_reclaimed": reports.iter().map(|r| r.bytes_reclaimed).sum::<u64>(),
+            "commit": commit,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    let title = if args.apply {
+        "Prune"
+    } else {
+        "Prune (dry run)"
+    };
+    println!("{}", title.bold());
+    println!("{}", "─".repeat(72));
+    println!(
+        "{:<20} {:>8} {:>12}  Reason",
+        "Category", "Items", "Reclaimable"
+    );
+    for report in &reports {
+        let size = format_size(report.bytes);
+        let size = if report.bytes_estimated {
+            format!("~{size}")
+        } else {
+            size
+        };
+        println!(
+            "{:<20} {:>8} {:>12}  {}",
+            report.category.label(),
+            report.count,
+            size,
+            report.reason.dimmed()
+        );
+        if let Some(skipped) = &report.skipped {
+            println!("{:<20} {}", "", format!("skipped: {skipped}").yellow());
+        }
+    }
+    println!("{}", "─".repeat(72));
+    let total: u64 = reports.iter().map(|r| r.bytes).sum();
+    println!(
+        "{:<20} {:>8} {:>12}",
+        "total",
+        reports.iter().map(|r| r.count).sum::<usize>(),
+        format_size(total)
+    );
+    println!();
+    if args.apply {
+        let reclaimed: u64 = reports.iter().map(|r| r.bytes_reclaimed).sum();
+        println!(
+            "{} Deleted {deleted} item(s), reclaimed {}",
+            "✓".green(),
+            format_size(reclaimed).yellow()
+        );
+        if let Some(commit) = &commit {
+            println!("  Archive commit: {}", commit.oid.dimmed());
+        }
+    } else {
+        println!(
+            "Nothing deleted. Re-run with {} to prune.",
+            "--apply".cyan()
+        );
+    }
+    Ok(())
+}
+
+fn prune_tombstones(ctx: &AppContext, days: u32, apply: bool) -> Result<PolicyReport> {
+    let manager = TombstoneManager::new(&ctx.ms_root);
+    let records = manager.list_older_than(days)?;
+    let mut report = PolicyReport::new(
+        PolicyCategory::Tombstones,
+        format!("tombstoned more than {days} days ago"),
+    );
+    report.count = records.len();
+    report.bytes = records.iter().map(|r| r.size_bytes).sum();
+    report.items = records
+        .iter()
+        .map(|r| format!("{} ({})", r.original_path, r.id))
+        .collect();
+    if apply {
+        let gate = SafetyGate::from_context(ctx);
+        for record in &records {
+            gate.enforce(&format!("ms prune purge {}", record.id), None)?;
+            let result = manager.purge(&record.id)?;
+            report.deleted += 1;
+            report.bytes_reclaimed += result.bytes_freed;
+        }
+    }
+    Ok(report)
+}
+
+fn prune_orphaned_evidence(ctx: &AppContext, apply: bool) -> Result<PolicyReport> {
+    let orphaned = ctx.db.orphaned_evidence()?;
+    let mut report = PolicyReport::new(
+        PolicyCategory::OrphanedEvidence,
+        "evidence for skills that no longer exist".to_string(),
+    );
+    report.count = usize::try_from(orphaned.rows).unwrap_or(usize::MAX);
+    report.bytes = orphaned.estimated_bytes;
+    report.bytes_estimated = true;
+    report.items = orphaned.skill_ids;
+    if apply && orphaned.rows > 0 {
+        report.deleted = ctx.db.delete_orphaned_evidence()?;
+        report.bytes_reclaimed = orphaned.estimated_bytes;
+        if let Err(err) = ctx.db.checkpoint_wal() {
+            tracing::warn!(error = %err, "WAL checkpoint after pruning evidence failed");
+        }
+    }
+    Ok(report)
+}
+
+fn prune_stale_checkpoints(ctx: &AppContext, days: u32, apply: bool) -> Result<PolicyReport> {
+    let cutoff = chrono::Utc::now() - chrono::Duration::days(i64::from(days));
+    let stale: Vec<_> = WizardCheckpoint::load_all(&wizard_checkpoint_dir(&ctx.ms_root))?
+        .into_iter()
+        .filter(|(_, cp)| cp.updated_at < cutoff)
+        .collect();
+    let mut report = PolicyReport::new(
+        PolicyCategory::StaleCheckpoints,
+        format!("build wizard checkpoints untouched for {days} days"),
+    );
+    report.count = stale.len();
+    for (path, cp) in &stale {
+        let size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        report.bytes += size;
+        report.items.push(format!("{} \"{}\"", cp.id, cp.query));
+        if apply {
+            std::fs::remove_file(path)?;
+            report.deleted += 1;
+            report.bytes_reclaimed += size;
+        }
+    }
+    Ok(report)
+}
+
+fn prune_unused_blobs(ctx: &AppContext, apply: bool) -> Result<PolicyReport> {
+    let root = ctx.git.root();
+    let registry = BundleRegistry::open(root)?;
+    let mut report = PolicyReport::new(
+        PolicyCategory::UnusedBlobs,
+        "bundle blobs no installed bundle references".to_string(),
+    );
+    // Bundles registered before blob tracking reference blobs we cannot
+    // name, so nothing is safe to delete until they are reinstalled.
+    let untracked: Vec<&str> = registry
+        .list()
+        .filter(|bundle| bundle.blobs.is_empty() && !bundle.skills.is_empty())
+        .map(|bundle| bundle.id.as_str())
+        .collect();
+    if !untracked.is_empty() {
+        report.skipped = Some(format!(
+            "bundles installed before blob tracking ({}); reinstall them first",
+            untracked.join(", ")
+        ));
+        return Ok(report);
+    }
+
+    let referenced: HashSet<&str> = registry
+        .list()
+        .flat_map(|bundle| bundle.blobs.iter().map(String::as_str))
+        .collect();
+    let store = BlobStore::open(root.join("bundles"))?;
+    let unused: Vec<(String, u64)> = store
+        .list_blobs()?
+        .into_iter()
+        .filter(|(hash, _)| !referenced.contains(hash.as_str()))
+        .collect();
+    report.count = unused.len();
+    report.bytes = unused.iter().map(|(_, size)| size).sum();
+    for (hash, _) in &unused {
+        report.items.push(hash.clone());
+        if apply {
+            report.bytes_reclaimed += store.remove_blob(hash)?;
+            report.deleted += 1;
+        }
+    }
+    Ok(report)
+}
+
+fn prune_expired_backups(ctx: &AppContext, apply: bool) -> Result<PolicyReport> {
+    let retention = &ctx.config.backup;
+    let reason = if retention.max_age_days > 0 {
+        format!(
+            "backups beyond the newest {} or older than {} days",
+            retention.keep_last, retention.max_age_days
+        )
+    } else {
+        format!("backups beyond the newest {}", retention.keep_last)
+    };
+    let mut report = PolicyReport::new(PolicyCategory::ExpiredBackups, reason);
+    let backups = list_backups(&ctx.ms_root)?;
+    let expired = expired_backups(&backups, retention, chrono::Utc::now());
+    report.count = expired.len();
+    report.bytes = expired.iter().map(|backup| backup.bytes).sum();
+    for backup in expired {
+        report.items.push(backup.id.clone());
+        if apply {
+            report.bytes_reclaimed += remove_backup(backup)?;
+            report.deleted += 1;
+        }
+    }
+    Ok(report)
+}
+
+/// Trials are closed as `expired` rather than deleted, so trial stats still
+/// count them.
+fn prune_expired_trials(ctx: &AppContext, days: u32, apply: bool) -> Result<PolicyReport> {
+    let now = chrono::Utc::now();
+    let cutoff = (now - chrono::Duration::days(i64::from(days))).to_rfc3339();
+    let expired = ctx.db.list_skill_trials(true, Some(&cutoff))?;
+    let mut report = PolicyReport::new(
+        PolicyCategory::ExpiredTrials,
+        format!("skill trials unreported for {days} days"),
+    );
+    report.count = expired.len();
+    let closed_at = now.to_rfc3339();
+    for trial in expired {
+        let db = &ctx.db;
+        if apply && db.close_skill_trial(&trial.id, "expired", None, &closed_at)? {
+            report.deleted += 1;
+        }
+        report.items.push(trial.id);
+    }
+    Ok(report)
+}
+
+/// Commit a log of the applied prune to the Git archive.
+fn record_prune_commit(
+    ctx: &AppContext,
+    reports: &[PolicyReport],
+) -> Result<crate::storage::git::SkillCommit> {
+    let applied: Vec<&PolicyReport> = reports.iter().filter(|r| r.deleted > 0).collect();
+    let summary = applied
+        .iter()
+        .map(|r| format!("{} {}", r.deleted, r.category.label()))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut message = format!("Prune {summary}\n");
+    for report in &applied {
+        message.push_str(&format!(
+            "\n{}: {} ({} reclaimed)",
+            report.category.label(),
+            report.reason,
+            format_size(report.bytes_reclaimed)
+        ));
+    }
+    let log = serde_json::to_string_pretty(&json!({
+        "pruned_at": chrono::Utc::now().to_rfc3339(),
+        "categories": applied,
+    }))?;
+    ctx.git
+        .record_prune(&message, &log, &Provenance::new("prune"))
+}
+
+pub(crate) fn format_size(bytes: u64) -> String {
     const KB: u64 = 1024;
     const MB: u64 = KB * 1024;
     const GB: u64 = MB * 1024;
@@ -1885,6 +2390,49 @@ mod tests {
         assert!(cli.prune.older_than.is_none());
     }
 
+    #[test]
+    fn parse_prune_policy_flags() {
+        let cli = TestCli::try_parse_from([
+            "test",
+            "--tombstoned-older-than",
+            "30d",
+            "--orphaned-evidence",
+            "--stale-checkpoints",
+            "--unused-blobs",
+            "--expired-backups",
+            "--expired-trials",
+        ])
+        .unwrap();
+        assert_eq!(cli.prune.tombstoned_older_than, Some(30));
+        assert!(cli.prune.orphaned_evidence);
+        assert!(cli.prune.stale_checkpoints);
+        assert!(cli.prune.unused_blobs);
+        assert!(cli.prune.expired_backups);
+        assert!(cli.prune.expired_trials);
+        assert!(!cli.prune.apply);
+        assert!(cli.prune.has_policy());
+
+        let cli =
+            TestCli::try_parse_from(["test", "--tombstoned-older-than", "2w", "--apply"]).unwrap();
+        assert_eq!(cli.prune.tombstoned_older_than, Some(14));
+        assert!(cli.prune.apply);
+
+        assert!(
+            TestCli::try_parse_from(["test", "--orphaned-evidence", "--apply", "--dry-run"])
+                .is_err()
+        );
+        assert!(TestCli::try_parse_from(["test", "--tombstoned-older-than", "soon"]).is_err());
+    }
+
+    #[test]
+    fn parse_age_days_accepts_units() {
+        assert_eq!(parse_age_days("30d"), Ok(30));
+        assert_eq!(parse_age_days("4w"), Ok(28));
+        assert_eq!(parse_age_days("7"), Ok(7));
+        assert!(parse_age_days("d").is_err());
+        assert!(parse_age_days("-3d").is_err());
+    }
+
     #[test]
     fn parse_prune_dry_run() {
         let cli = TestCli::try_parse_from(["test", "--dry-run"]).unwrap();
@@ -6,9 +6,20 @@ use serde::Serialize;
 use crate::app::AppContext;
 use crate::cli::output::OutputFormat;
 use crate::cli::output::{HumanLayout, emit_json};
+use crate::core::SkillSpec;
 use crate::core::spec_lens::parse_markdown;
 use crate::error::{MsError, Result};
-use crate::quality::{QualityContext, QualityScorer};
+use crate::quality::efficacy::blend_quality;
+use crate::quality::trials::{TrialStats, adjust_quality};
+use crate::quality::{ExplainedIssue, QualityComponent, QualityContext, QualityScorer};
+use crate::storage::sqlite::SkillRecord;
+
+/// Stored scores further than this from every recomputation are stale.
+const STALE_EPSILON: f64 = 0.01;
+
+/// `config` table key holding the fingerprint of the weights stored scores
+/// were computed with.
+pub(crate) const WEIGHTS_HASH_KEY: &str = "quality.weights_hash";
 
 #[derive(Args, Debug)]
 pub struct QualityArgs {
@@ -41,6 +52,12 @@ struct QualityBreakdownOutput {
     usage: f32,
     toolchain: f32,
     freshness: f32,
+    /// Efficacy score blended in when `[efficacy] quality_weight` > 0.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    efficacy: Option<f32>,
+    /// Adjustment from reported `ms load --trial` outcomes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    trials: Option<f32>,
 }
 
 pub fn run(ctx: &AppContext, args: &QualityArgs) -> Result<()> {
@@ -48,7 +65,7 @@ pub fn run(ctx: &AppContext, args: &QualityArgs) -> Result<()> {
         return Err(MsError::Config("missing skill (or use --all)".to_string()));
     }
 
-    let scorer = QualityScorer::with_defaults();
+    let scorer = scorer(ctx);
     let mut outputs = Vec::new();
 
     let skill_files = if args.all {
@@ -66,33 +83,22 @@ pub fn run(ctx: &AppContext, args: &QualityArgs) -> Result<()> {
             .map_err(|err| MsError::InvalidSkill(format!("{}: {err}", skill_md.display())))?;
         let skill_id = spec.metadata.id.clone();
 
-        let (usage_count, evidence_count, modified_at) =
-            if let Ok(Some(record)) = ctx.db.get_skill(&skill_id) {
-                let usage = ctx.db.count_skill_usage(&skill_id).ok();
-                let evidence = ctx.db.count_skill_evidence(&skill_id).ok();
-                let modified = parse_modified_at(&record.modified_at);
-                (usage, evidence, modified)
-            } else {
-                (None, None, None)
-            };
-
-        let context = QualityContext {
-            usage_count,
-            evidence_count,
-            modified_at,
-            toolchain_match: true,
-        };
-
+        let context = quality_context(ctx, &skill_id);
         let score = scorer.score_spec(&spec, &context);
+        let efficacy = efficacy_score(ctx, &skill_id);
+        let trials = trial_stats(ctx, &skill_id);
+        let overall = adjust_quality(
+            blend_quality(score.overall, efficacy, ctx.config.efficacy.quality_weight),
+            trials.as_ref(),
+        );
 
         if args.update {
-            ctx.db
-                .update_skill_quality(&skill_id, f64::from(score.overall))?;
+            ctx.db.update_skill_quality(&skill_id, f64::from(overall))?;
         }
 
         let output = QualityOutput {
             skill_id: skill_id.clone(),
-            quality_score: score.overall,
+            quality_score: overall,
             breakdown: QualityBreakdownOutput {
                 structure: score.breakdown.structure,
                 content: score.breakdown.content,
@@ -100,6 +106,8 @@ pub fn run(ctx: &AppContext, args: &QualityArgs) -> Result<()> {
                 usage: score.breakdown.usage,
                 toolchain: score.breakdown.toolchain,
                 freshness: score.breakdown.freshness,
+                efficacy,
+                trials: trials.as_ref().map(TrialStats::quality_adjustment),
             },
             issues: score
                 .issues
@@ -130,8 +138,14 @@ pub fn run(ctx: &AppContext, args: &QualityArgs) -> Result<()> {
                 .kv("Evidence", &format!("{:.2}", output.breakdown.evidence))
                 .kv("Usage", &format!("{:.2}", output.breakdown.usage))
                 .kv("Toolchain", &format!("{:.2}", output.breakdown.toolchain))
-                .kv("Freshness", &format!("{:.2}", output.breakdown.freshness))
-                .blank();
+                .kv("Freshness", &format!("{:.2}", output.breakdown.freshness));
+            if let Some(efficacy) = output.breakdown.efficacy {
+                layout.kv("Efficacy", &format!("{efficacy:.2}"));
+            }
+            if let Some(adjustment) = output.breakdown.trials {
+                layout.kv("Trials", &format!("{adjustment:+.2}"));
+            }
+            layout.blank();
             if !output.issues.is_empty() {
                 layout.bullet("Issues:");
                 for issue in &output.issues {
@@ -152,6 +166,169 @@ pub fn run(ctx: &AppContext, args: &QualityArgs) -> Result<()> {
     }
 }
 
+/// Usage, evidence, and modification time of an indexed skill.
+fn quality_context(ctx: &AppContext, skill_id: &str) -> QualityContext {
+    let (usage_count, evidence_count, modified_at) =
+        if let Ok(Some(record)) = ctx.db.get_skill(skill_id) {
+            let usage = ctx.db.count_skill_usage(skill_id).ok();
+            let evidence = ctx.db.count_skill_evidence(skill_id).ok();
+            let modified = parse_modified_at(&record.modified_at);
+            (usage, evidence, modified)
+        } else {
+            (None, None, None)
+        };
+
+    QualityContext {
+        usage_count,
+        evidence_count,
+        modified_at,
+        toolchain_match: true,
+    }
+}
+
+/// A skill's stored quality score next to a fresh recomputation, broken
+/// down per component (`ms show --quality`, MCP `show`).
+#[derive(Debug, Serialize)]
+pub(crate) struct QualityReport {
+    pub stored: f64,
+    pub recomputed: f32,
+    /// The stored score matches no recomputation: the index is out of date.
+    pub stale: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub efficacy: Option<EfficacyBlend>,
+    /// Reported trials and the adjustment they make
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trials: Option<TrialAdjustment>,
+    pub components: Vec<QualityComponent>,
+    pub other_issues: Vec<ExplainedIssue>,
+}
+
+/// Efficacy score blended into the recomputed score.
+#[derive(Debug, Serialize)]
+pub(crate) struct EfficacyBlend {
+    pub score: f32,
+    pub weight: f32,
+}
+
+/// Trial outcomes applied to the recomputed score.
+#[derive(Debug, Serialize)]
+pub(crate) struct TrialAdjustment {
+    #[serde(flatten)]
+    pub stats: TrialStats,
+    pub success_rate: Option<f32>,
+    pub adjustment: f32,
+}
+
+/// Recompute and explain `record`'s quality from its SKILL.md on disk.
+pub(crate) fn quality_report(ctx: &AppContext, record: &SkillRecord) -> Result<QualityReport> {
+    let spec = current_spec(ctx, record)?;
+    let scorer = scorer(ctx);
+    let explanation = scorer.explain(&spec, &quality_context(ctx, &record.id));
+    let efficacy = efficacy_score(ctx, &record.id);
+    let weight = ctx.config.efficacy.quality_weight;
+    let trials = trial_stats(ctx, &record.id);
+    let recomputed = adjust_quality(
+        blend_quality(explanation.overall, efficacy, weight),
+        trials.as_ref(),
+    );
+
+    // `ms index` stores the score without usage context and
+    // `ms quality --update` with it; matching either one is current.
+    let indexed = scorer.score_spec(&spec, &QualityContext::default()).overall;
+    let stale = [recomputed, indexed]
+        .iter()
+        .all(|score| (record.quality_score - f64::from(*score)).abs() > STALE_EPSILON);
+
+    Ok(QualityReport {
+        stored: record.quality_score,
+        recomputed,
+        stale,
+        efficacy: efficacy.map(|score| EfficacyBlend { score, weight }),
+        trials: trials.map(|stats| TrialAdjustment {
+            success_rate: stats.success_rate(),
+            adjustment: stats.quality_adjustment(),
+            stats,
+        }),
+        components: explanation.components,
+        other_issues: explanation.other_issues,
+    })
+}
+
+/// Recompute a skill's quality (as `ms show --quality` does) and store it.
+/// Returns the stored score, or `None` when the skill is not indexed.
+pub(crate) fn refresh_quality(ctx: &AppContext, skill_id: &str) -> Result<Option<f32>> {
+    let Some(record) = ctx.db.get_skill(skill_id)? else {
+        return Ok(None);
+    };
+    let score = quality_report(ctx, &record)?.recomputed;
+    ctx.db.update_skill_quality(skill_id, f64::from(score))?;
+    Ok(Some(score))
+}
+
+/// Recompute `record`'s quality as `ms quality --update` stores it: with the
+/// configured weights, usage context, efficacy and trials.
+pub(crate) fn recompute_quality(ctx: &AppContext, record: &SkillRecord) -> Result<f32> {
+    let spec = current_spec(ctx, record)?;
+    let score = scorer(ctx).score_spec(&spec, &quality_context(ctx, &record.id));
+    Ok(adjust_quality(
+        blend_quality(
+            score.overall,
+            efficacy_score(ctx, &record.id),
+            ctx.config.efficacy.quality_weight,
+        ),
+        trial_stats(ctx, &record.id).as_ref(),
+    ))
+}
+
+/// A scorer using the `[quality]` weights.
+pub(crate) fn scorer(ctx: &AppContext) -> QualityScorer {
+    QualityScorer::new(ctx.config.quality.weights())
+}
+
+/// Record that stored scores use the configured weights, unless a
+/// fingerprint is stored already and `replace` is false.
+pub(crate) fn record_weights(ctx: &AppContext, replace: bool) -> Result<()> {
+    if !replace && ctx.db.get_config_value(WEIGHTS_HASH_KEY)?.is_some() {
+        return Ok(());
+    }
+    let fingerprint = ctx.config.quality.weights().fingerprint();
+    ctx.db.set_config_value(WEIGHTS_HASH_KEY, &fingerprint)
+}
+
+/// The skill as it is on disk now, or as archived if the file is gone.
+fn current_spec(ctx: &AppContext, record: &SkillRecord) -> Result<SkillSpec> {
+    match std::fs::read_to_string(&record.source_path) {
+        Ok(raw) => parse_markdown(&raw)
+            .map_err(|err| MsError::InvalidSkill(format!("{}: {err}", record.source_path))),
+        Err(_) => ctx.git.read_skill(&record.id),
+    }
+}
+
+/// Stored efficacy score, only when blending is enabled and both sides of
+/// the comparison have enough sessions.
+fn efficacy_score(ctx: &AppContext, skill_id: &str) -> Option<f32> {
+    if ctx.config.efficacy.quality_weight <= 0.0 {
+        return None;
+    }
+    ctx.db
+        .list_skill_efficacy(Some(skill_id))
+        .ok()?
+        .into_iter()
+        .next()
+        .filter(|record| record.has_samples(ctx.config.efficacy.min_samples))
+        .and_then(|record| record.score())
+}
+
+/// Trial counts of a skill, once at least one trial has been reported.
+fn trial_stats(ctx: &AppContext, skill_id: &str) -> Option<TrialStats> {
+    ctx.db
+        .list_trial_stats(Some(skill_id))
+        .ok()?
+        .into_iter()
+        .next()
+        .filter(|stats| stats.reported() > 0)
+}
+
 fn parse_modified_at(raw: &str) -> Option<chrono::DateTime<chrono::Utc>> {
     if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(raw) {
         return Some(dt.with_timezone(&chrono::Utc));
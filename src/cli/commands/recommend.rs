@@ -3,8 +3,6 @@
 //! Provides commands to inspect recommendation statistics, view history,
 //! and tune the contextual bandit parameters.
 
-use std::path::PathBuf;
-
 use clap::{Args, Subcommand};
 use colored::Colorize;
 
@@ -12,7 +10,6 @@ use crate::app::AppContext;
 use crate::cli::output::OutputFormat;
 use crate::cli::output::{HumanLayout, emit_json};
 use crate::error::Result;
-use crate::suggestions::bandit::contextual::ContextualBandit;
 use crate::suggestions::bandit::features::{FEATURE_DIM, UserHistory};
 
 #[derive(Args, Debug)]
@@ -83,11 +80,10 @@ pub fn run(ctx: &AppContext, args: &RecommendArgs) -> Result<()> {
 }
 
 fn stats(ctx: &AppContext, args: &StatsArgs) -> Result<()> {
-    let bandit_path = default_bandit_path();
+    let bandit_path = ctx.db_path();
     let history_path = UserHistory::default_path();
 
-    let bandit = ContextualBandit::load(&bandit_path)
-        .unwrap_or_else(|_| ContextualBandit::with_feature_dim(FEATURE_DIM));
+    let bandit = ctx.bandit(None)?;
     let user_history = UserHistory::load(&history_path);
 
     if ctx.output_format != OutputFormat::Human {
@@ -269,11 +265,8 @@ fn history(ctx: &AppContext, args: &HistoryArgs) -> Result<()> {
 }
 
 fn tune(ctx: &AppContext, args: &TuneArgs) -> Result<()> {
-    let bandit_path = default_bandit_path();
-
     if args.reset {
-        let bandit = ContextualBandit::with_feature_dim(FEATURE_DIM);
-        bandit.save(&bandit_path)?;
+        ctx.reset_bandit(None, FEATURE_DIM)?;
 
         if ctx.output_format != OutputFormat::Human {
             let payload = serde_json::json!({
@@ -296,26 +289,19 @@ fn tune(ctx: &AppContext, args: &TuneArgs) -> Result<()> {
         return Ok(());
     }
 
-    let mut bandit = ContextualBandit::load(&bandit_path)
-        .unwrap_or_else(|_| ContextualBandit::with_feature_dim(FEATURE_DIM));
-
-    let mut changed = false;
-
-    if let Some(exploration) = args.exploration {
-        let clamped = exploration.clamp(0.0, 1.0);
-        bandit.set_exploration_rate(clamped);
-        changed = true;
-    }
-
-    if let Some(learning_rate) = args.learning_rate {
-        let clamped = learning_rate.clamp(0.0, 1.0);
-        bandit.set_learning_rate(clamped);
-        changed = true;
-    }
-
-    if changed {
-        bandit.save(&bandit_path)?;
-    }
+    let changed = args.exploration.is_some() || args.learning_rate.is_some();
+    let bandit = if changed {
+        ctx.update_bandit(None, |bandit| {
+            if let Some(exploration) = args.exploration {
+                bandit.set_exploration_rate(exploration.clamp(0.0, 1.0));
+            }
+            if let Some(learning_rate) = args.learning_rate {
+                bandit.set_learning_rate(learning_rate.clamp(0.0, 1.0));
+            }
+        })?
+    } else {
+        ctx.bandit(None)?
+    };
 
     // Show current settings
     let config = bandit.config_summary();
@@ -368,11 +354,6 @@ fn tune(ctx: &AppContext, args: &TuneArgs) -> Result<()> {
     Ok(())
 }
 
-fn default_bandit_path() -> PathBuf {
-    let base = dirs::data_dir().unwrap_or_else(|| PathBuf::from("."));
-    base.join("ms").join("contextual_bandit.json")
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
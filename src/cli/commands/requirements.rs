@@ -1,31 +1,44 @@
 //! ms requirements - Check environment requirements
 //!
-//! Checks for the presence of external tools and dependencies
-//! that ms interacts with (git, cass, bd, etc.).
+//! Checks the tools skills declare under `requirements.tools` (with version
+//! constraints) for one skill or every indexed skill, and with `--system` the
+//! external tools ms itself interacts with (git, cass, bd, etc.).
 
 use std::path::PathBuf;
 use std::process::Command;
+use std::time::Duration;
 
 use clap::Args;
 use colored::Colorize;
 use which::which;
 
 use crate::app::AppContext;
-use crate::cli::output::OutputFormat;
+use crate::cli::output::{OutputFormat, emit_json};
+use crate::core::lookup::{self, ResolveOptions};
+use crate::core::requirements::{
+    self as reqs, InstallHints, RequirementCheck as ToolCheck, RequirementStatus,
+};
+use crate::core::skill::SkillRequirements;
 use crate::error::Result;
+use crate::storage::sqlite::SkillRecord;
 
 #[derive(Args, Debug)]
 pub struct RequirementsArgs {
-    /// Skill to check requirements for (optional, checks specific skill deps)
+    /// Check the requirements of one skill (default: every indexed skill)
+    #[arg(long, short, conflicts_with_all = ["all", "system"])]
     pub skill: Option<String>,
 
     /// Output format: text, json
     #[arg(long, default_value = "text")]
     pub format: String,
 
-    /// Check all indexed skills
+    /// Check all indexed skills (the default without --skill)
     #[arg(long)]
     pub all: bool,
+
+    /// Check the external tools ms itself uses instead of skill requirements
+    #[arg(long, conflicts_with = "all")]
+    pub system: bool,
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -83,14 +96,116 @@ impl RequirementCheck {
 }
 
 pub fn run(ctx: &AppContext, args: &RequirementsArgs) -> Result<()> {
-    if let Some(_skill) = &args.skill {
-        // TODO: Implement skill-specific requirements check (read from SKILL.md)
-        if ctx.output_format == OutputFormat::Human {
-            println!("Skill-specific requirements not yet implemented.");
+    if args.system {
+        return run_system(ctx, args);
+    }
+
+    let records = match &args.skill {
+        Some(skill) => {
+            vec![lookup::resolve(&ctx.db, skill, &ResolveOptions::default())?.into_record()?]
+        }
+        None => ctx.db.list_skills(10_000, 0)?,
+    };
+    let skills: Vec<(String, SkillRequirements)> = records
+        .iter()
+        .map(|record| (record.id.clone(), skill_requirements(record)))
+        .collect();
+
+    let settings = &ctx.config.requirements;
+    let managers = reqs::detect_package_managers();
+    let hints = InstallHints::with_overrides(&settings.install_hints);
+    let checks = reqs::check_requirements(
+        &skills,
+        &hints,
+        &managers,
+        Duration::from_secs(settings.probe_timeout_secs),
+    );
+    let count = |status: RequirementStatus| checks.iter().filter(|c| c.status == status).count();
+    let satisfied = count(RequirementStatus::Satisfied);
+    let unsatisfied = count(RequirementStatus::Unsatisfied);
+    let unknown = count(RequirementStatus::Unknown);
+
+    if ctx.output_format != OutputFormat::Human || args.format == "json" {
+        return emit_json(&serde_json::json!({
+            "status": "ok",
+            "skill": args.skill,
+            "skills_checked": skills.len(),
+            "platform": std::env::consts::OS,
+            "package_managers": managers,
+            "requirements": checks,
+            "summary": {
+                "satisfied": satisfied,
+                "unsatisfied": unsatisfied,
+                "unknown": unknown,
+            },
+        }));
+    }
+
+    println!("{}", "Skill Requirements".bold());
+    println!("{}", "─".repeat(60));
+    if checks.is_empty() {
+        println!();
+        match &args.skill {
+            Some(skill) => println!("{skill} declares no tool requirements."),
+            None => println!("No indexed skill declares tool requirements."),
         }
         return Ok(());
     }
+    println!();
+    for check in &checks {
+        print_tool_check(check, args.skill.is_none());
+    }
+    println!(
+        "{} satisfied, {} unsatisfied, {} unknown",
+        satisfied.to_string().green(),
+        unsatisfied.to_string().red(),
+        unknown.to_string().yellow()
+    );
+    Ok(())
+}
 
+fn skill_requirements(record: &SkillRecord) -> SkillRequirements {
+    serde_json::from_str::<serde_json::Value>(&record.metadata_json)
+        .ok()
+        .and_then(|meta| meta.get("requirements").cloned())
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+fn print_tool_check(check: &ToolCheck, show_skills: bool) {
+    let marker = match check.status {
+        RequirementStatus::Satisfied => "✓".green(),
+        RequirementStatus::Unsatisfied if check.required => "✗".red(),
+        RequirementStatus::Unsatisfied => "-".yellow(),
+        RequirementStatus::Unknown => "?".yellow(),
+    };
+    let constraint = check
+        .constraint
+        .as_deref()
+        .map(|c| format!(" {c}"))
+        .unwrap_or_default();
+    let version = check
+        .found_version
+        .as_deref()
+        .map(|v| format!(" (v{v})"))
+        .unwrap_or_default();
+    println!("{marker} {}{constraint}{version}", check.tool.bold());
+    if let Some(message) = &check.message {
+        println!("    {message}");
+    }
+    if let Some(hint) = &check.install_hint {
+        println!("    Install: {}", hint.command.cyan());
+    }
+    if !check.required {
+        println!("    Note: Optional");
+    }
+    if show_skills {
+        println!("    Skills: {}", check.skills.join(", ").dimmed());
+    }
+    println!();
+}
+
+fn run_system(ctx: &AppContext, args: &RequirementsArgs) -> Result<()> {
     let mut checks = vec![
         RequirementCheck::new("Git Version Control", "git", true),
         RequirementCheck::new("CASS (Context Aware Semantic Search)", "cass", false),
@@ -176,5 +291,21 @@ mod tests {
         assert!(args.skill.is_none());
         assert_eq!(args.format, "text");
         assert!(!args.all);
+        assert!(!args.system);
+    }
+
+    #[test]
+    fn parse_requirements_skill_and_system() {
+        let parsed = TestCli::parse_from(["test", "requirements", "--skill", "web-dev"]);
+        let TestCommand::Requirements(args) = parsed.cmd;
+        assert_eq!(args.skill.as_deref(), Some("web-dev"));
+
+        let parsed = TestCli::parse_from(["test", "requirements", "--system"]);
+        let TestCommand::Requirements(args) = parsed.cmd;
+        assert!(args.system);
+
+        assert!(
+            TestCli::try_parse_from(["test", "requirements", "--skill", "x", "--system"]).is_err()
+        );
     }
 }
@@ -0,0 +1,59 @@
+//! ms resolve - Show how a skill reference resolves
+//!
+//! Runs the same resolver as `ms load`, `ms show`, `ms edit`, and the MCP
+//! tools, and prints every stage it tried: meta-skill, exact id, alias, id
+//! prefix, layer scope, and near-miss suggestions. Stages that would have
+//! matched but lost to a higher-precedence one are reported as shadowed.
+
+use clap::Args;
+
+use crate::app::AppContext;
+use crate::cli::commands::load::meta_skill_ids;
+use crate::cli::output::{HumanLayout, OutputFormat, emit_json};
+use crate::core::lookup::{self, ResolveOptions};
+use crate::error::Result;
+
+#[derive(Args, Debug)]
+pub struct ResolveArgs {
+    /// Skill reference as you would pass it to `ms load`
+    pub name: String,
+
+    /// Only accept a skill from this layer (base|org|project|user)
+    #[arg(long)]
+    pub layer: Option<String>,
+
+    /// Disable unique-prefix matching
+    #[arg(long)]
+    pub no_prefix: bool,
+}
+
+pub fn run(ctx: &AppContext, args: &ResolveArgs) -> Result<()> {
+    let options = ResolveOptions {
+        prefix: !args.no_prefix,
+        layer: args.layer.clone(),
+        packs: meta_skill_ids(),
+    };
+    let trace = lookup::resolve(&ctx.db, &args.name, &options)?.trace;
+
+    if ctx.output_format != OutputFormat::Human {
+        let payload = serde_json::json!({
+            "status": if trace.winner.is_some() { "ok" } else { "not_found" },
+            "shadowing": trace.has_shadowing(),
+            "trace": trace,
+        });
+        return emit_json(&payload);
+    }
+
+    let mut layout = HumanLayout::new();
+    layout.title(&format!("Resolve {}", args.name));
+    for line in trace.render().into_iter().skip(1) {
+        layout.push_line(line);
+    }
+    if trace.has_shadowing() {
+        layout.blank().push_line(
+            "warning: a lower-precedence match was shadowed; see `ms doctor --check ambiguity`",
+        );
+    }
+    crate::cli::output::emit_human(layout);
+    Ok(())
+}
@@ -0,0 +1,587 @@
+//! ms review - Human review of low-confidence generalizations
+//!
+//! `ms build` sends near-miss patterns through the specific-to-general
+//! transformer; generalizations it cannot confirm are queued instead of
+//! dropped. `ms review list` groups the open items by reason, `show` prints
+//! the evidence for one, and `resolve` either promotes it into a draft skill
+//! (or an existing one) or rejects it, so the same instance is not queued
+//! again.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use clap::{Args, Subcommand};
+
+use crate::app::AppContext;
+use crate::cass::{Resolution, UncertaintyItem, UncertaintyStore};
+use crate::cli::commands::build::format_uncertainty_reason;
+use crate::cli::commands::edit::{apply_structured_edit, run_editor};
+use crate::cli::commands::expand_path;
+use crate::cli::commands::index::index_one;
+use crate::cli::commands::new::validate_skill_id;
+use crate::cli::output::{HumanLayout, OutputFormat, emit_human, emit_json};
+use crate::core::scaffold::title_from_id;
+use crate::core::spec_lens::{compile_markdown, positional_block_id, slugify};
+use crate::core::structured_edit::{DEFAULT_RULE_SECTION, EXAMPLE_SECTION, EditOperation, fenced};
+use crate::core::{BlockType, SkillBlock, SkillLayer, SkillSection, SkillSpec};
+use crate::error::{MsError, Result};
+use crate::security::SafetyGate;
+
+/// Words of the principle used for a drafted skill's id.
+const DRAFT_ID_WORDS: usize = 6;
+
+#[derive(Args, Debug)]
+pub struct ReviewArgs {
+    #[command(subcommand)]
+    pub command: ReviewCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ReviewCommand {
+    /// List items awaiting review, grouped by reason
+    List(ListArgs),
+    /// Show an item: instance, best cluster examples, scores and critique
+    Show(ShowArgs),
+    /// Accept an item into a skill, or reject it
+    Resolve(ResolveArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct ListArgs {
+    /// Include resolved, rejected and expired items
+    #[arg(long)]
+    pub all: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct ShowArgs {
+    /// Item id, or a unique prefix of it
+    pub id: String,
+}
+
+#[derive(Args, Debug)]
+pub struct ResolveArgs {
+    /// Item id, or a unique prefix of it
+    pub id: String,
+
+    /// Promote the generalization into a draft skill
+    #[arg(long, conflicts_with = "reject", required_unless_present = "reject")]
+    pub accept: bool,
+
+    /// Reject the generalization; its instance is not queued again
+    #[arg(long, requires = "reason")]
+    pub reject: bool,
+
+    /// Why the generalization is wrong
+    #[arg(long, requires = "reject")]
+    pub reason: Option<String>,
+
+    /// Append to this existing skill instead of drafting a new one
+    #[arg(long, requires = "accept")]
+    pub into: Option<String>,
+
+    /// Id of the drafted skill (default: derived from the generalization)
+    #[arg(long, requires = "accept", conflicts_with = "into")]
+    pub skill_id: Option<String>,
+
+    /// Directory for the draft (default: first project skill path)
+    #[arg(long, requires = "accept", conflicts_with = "into")]
+    pub dir: Option<PathBuf>,
+
+    /// Open the written SKILL.md in $EDITOR before indexing it
+    #[arg(long, requires = "accept")]
+    pub edit: bool,
+}
+
+pub fn run(ctx: &AppContext, args: &ReviewArgs) -> Result<()> {
+    match &args.command {
+        ReviewCommand::List(list_args) => run_list(ctx, list_args),
+        ReviewCommand::Show(show_args) => run_show(ctx, show_args),
+        ReviewCommand::Resolve(resolve_args) => run_resolve(ctx, resolve_args),
+    }
+}
+
+fn run_list(ctx: &AppContext, args: &ListArgs) -> Result<()> {
+    let store = UncertaintyStore::new(&ctx.db);
+    let expired = store.age_out(ctx.config.review.expiry())?;
+    let items = if args.all {
+        store.list(None)?
+    } else {
+        store.open()?
+    };
+
+    let mut groups: BTreeMap<&str, Vec<&UncertaintyItem>> = BTreeMap::new();
+    for item in &items {
+        groups.entry(item.reason.kind()).or_default().push(item);
+    }
+
+    if ctx.output_format != OutputFormat::Human {
+        let groups: Vec<serde_json::Value> = groups
+            .iter()
+            .map(|(reason, items)| {
+                serde_json::json!({
+                    "reason": reason,
+                    "count": items.len(),
+                    "items": items.iter().map(|item| item_summary(item)).collect::<Vec<_>>(),
+                })
+            })
+            .collect();
+        return emit_json(&serde_json::json!({
+            "status": "ok",
+            "count": items.len(),
+            "expired": expired.len(),
+            "max_pending": ctx.config.review.max_pending,
+            "groups": groups,
+        }));
+    }
+
+    let mut layout = HumanLayout::new();
+    layout.title("Review Queue");
+    if items.is_empty() {
+        layout.push_line("Nothing awaits review.");
+    }
+    for (reason, group) in &groups {
+        layout.section(&format!("{reason} ({})", group.len()));
+        for item in group {
+            layout.bullet(&format!(
+                "{}  {:>3.0}%  {:>3}d  {}{}",
+                short_id(&item.id),
+                item.confidence * 100.0,
+                age_days(item),
+                truncate(&principle(item), 60),
+                if item.status.is_open() {
+                    String::new()
+                } else {
+                    format!(" [{}]", item.status.name())
+                }
+            ));
+        }
+        layout.blank();
+    }
+    if !expired.is_empty() {
+        layout.push_line(format!(
+            "{} item(s) expired after {} days",
+            expired.len(),
+            ctx.config.review.expiry_days
+        ));
+    }
+    if items.len() > ctx.config.review.max_pending {
+        layout.push_line(format!(
+            "{} items exceed [review].max_pending = {}",
+            items.len(),
+            ctx.config.review.max_pending
+        ));
+    }
+    emit_human(layout);
+    Ok(())
+}
+
+fn run_show(ctx: &AppContext, args: &ShowArgs) -> Result<()> {
+    let item = UncertaintyStore::new(&ctx.db).get(&args.id)?;
+
+    if ctx.output_format != OutputFormat::Human {
+        return emit_json(&serde_json::json!({
+            "status": "ok",
+            "age_days": age_days(&item),
+            "item": item,
+        }));
+    }
+
+    let mut layout = HumanLayout::new();
+    layout
+        .title(&format!("Review item {}", item.id))
+        .kv("Status", item.status.name())
+        .kv("Reason", &format_uncertainty_reason(&item.reason))
+        .kv(
+            "Confidence",
+            &format!(
+                "{:.0}% (needs {:.0}%)",
+                item.confidence * 100.0,
+                item.threshold * 100.0
+            ),
+        )
+        .kv("Age", &format!("{} days", age_days(&item)))
+        .kv("Generalization", &principle(&item))
+        .blank();
+
+    if let Some(source) = &item.source_instance {
+        layout.section("Instance").kv("Session", &source.session_id);
+        if !source.description.is_empty() {
+            layout.kv("Description", &source.description);
+        }
+        if !source.tool_signatures.is_empty() {
+            layout.kv("Tags", &source.tool_signatures.join(", "));
+        }
+        layout.blank();
+    }
+
+    if let Some(cluster) = &item.cluster_info {
+        layout.section(&format!(
+            "Best examples (cluster of {})",
+            cluster.instance_count
+        ));
+        for example in &cluster.examples {
+            layout.bullet(&truncate(example, 120));
+        }
+        if !cluster.common_tools.is_empty() {
+            layout.kv("Conditions", &cluster.common_tools.join(", "));
+        }
+        layout.blank();
+    }
+
+    if let Some(validation) = &item.validation {
+        layout
+            .section("Validation")
+            .kv("Coverage", &format!("{:.2}", validation.coverage))
+            .kv(
+                "Predictive power",
+                &format!("{:.2}", validation.predictive_power),
+            )
+            .kv("Coherence", &format!("{:.2}", validation.coherence))
+            .kv("Specificity", &format!("{:.2}", validation.specificity))
+            .kv(
+                "Counterexamples",
+                &validation.counterexamples.len().to_string(),
+            )
+            .blank();
+    }
+
+    if let Some(critique) = &item.critique {
+        layout
+            .section("Critique")
+            .push_line(critique.summary.clone());
+        for refinement in &critique.suggested_refinements {
+            layout.bullet(refinement);
+        }
+        layout.blank();
+    }
+
+    layout.push_line(format!(
+        "Resolve with `ms review resolve {} --accept` or `--reject --reason \"...\"`",
+        short_id(&item.id)
+    ));
+    emit_human(layout);
+    Ok(())
+}
+
+fn run_resolve(ctx: &AppContext, args: &ResolveArgs) -> Result<()> {
+    let store = UncertaintyStore::new(&ctx.db);
+    let mut item = store.get(&args.id)?;
+    if !item.status.is_open() {
+        return Err(MsError::ValidationFailed(format!(
+            "review item {} is already {}",
+            item.id,
+            item.status.name()
+        )));
+    }
+
+    if args.reject {
+        let reason = args.reason.as_deref().unwrap_or_default();
+        store.reject(&mut item, reason)?;
+        if ctx.output_format != OutputFormat::Human {
+            return emit_json(&serde_json::json!({
+                "status": "ok",
+                "id": item.id,
+                "decision": "rejected",
+                "reason": reason,
+            }));
+        }
+        println!("Rejected {}: {reason}", item.id);
+        return Ok(());
+    }
+
+    let (skill_id, path, created) = match &args.into {
+        Some(skill) => {
+            let edit = apply_structured_edit(ctx, skill, &append_operations(&item), None)?;
+            (edit.skill_id, edit.path, false)
+        }
+        None => {
+            let (skill_id, path) = write_draft(ctx, args, &item)?;
+            (skill_id, path, true)
+        }
+    };
+    if args.edit {
+        let editor =
+            std::env::var("EDITOR").map_err(|_| MsError::Config("EDITOR not set".to_string()))?;
+        run_editor(&SafetyGate::from_context(ctx), &editor, &path)?;
+    }
+    if created || args.edit {
+        index_one(ctx, &path, SkillLayer::Project)?;
+    }
+    store.resolve(
+        &mut item,
+        Resolution::HumanClarified {
+            annotation: format!("accepted into {skill_id}"),
+        },
+    )?;
+
+    if ctx.output_format != OutputFormat::Human {
+        return emit_json(&serde_json::json!({
+            "status": "ok",
+            "id": item.id,
+            "decision": "accepted",
+            "skill_id": skill_id,
+            "path": path.display().to_string(),
+            "created": created,
+        }));
+    }
+    if created {
+        println!("Drafted {skill_id} from {}", item.id);
+    } else {
+        println!("Appended {} to {skill_id}", item.id);
+    }
+    println!("  Path: {}", path.display());
+    Ok(())
+}
+
+/// Write a draft skill for `item`; returns its id and SKILL.md path.
+fn write_draft(
+    ctx: &AppContext,
+    args: &ResolveArgs,
+    item: &UncertaintyItem,
+) -> Result<(String, PathBuf)> {
+    let skill_id = args.skill_id.clone().unwrap_or_else(|| draft_id(item));
+    validate_skill_id(&skill_id)?;
+
+    let root = args.dir.clone().unwrap_or_else(|| {
+        ctx.config
+            .skill_paths
+            .project
+            .first()
+            .map_or_else(|| PathBuf::from(".ms/skills"), |path| expand_path(path))
+    });
+    let skill_path = root.join(&skill_id).join("SKILL.md");
+    if skill_path.exists() {
+        return Err(MsError::ValidationFailed(format!(
+            "{} already exists; use --into {skill_id} to append to it",
+            skill_path.display()
+        )));
+    }
+
+    let spec = draft_spec(item, &skill_id);
+    if let Some(parent) = skill_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&skill_path, compile_markdown(&spec))?;
+    Ok((skill_id, skill_path))
+}
+
+/// A draft skill: the generalization as its rule, the best cluster examples
+/// as its examples.
+fn draft_spec(item: &UncertaintyItem, skill_id: &str) -> SkillSpec {
+    let principle = principle(item);
+    let mut spec = SkillSpec::new(skill_id, title_from_id(skill_id));
+    spec.metadata.description = principle.clone();
+    spec.metadata.tags = item.pattern_candidate.tags.clone();
+    spec.metadata.tags.push("draft".to_string());
+
+    spec.sections.push(SkillSection {
+        id: DEFAULT_RULE_SECTION.to_string(),
+        title: "Rules".to_string(),
+        blocks: vec![SkillBlock {
+            id: positional_block_id(DEFAULT_RULE_SECTION, 0),
+            block_type: BlockType::Rule,
+            content: principle,
+        }],
+    });
+    let examples: Vec<SkillBlock> = examples(item)
+        .iter()
+        .enumerate()
+        .map(|(index, example)| SkillBlock {
+            id: positional_block_id(EXAMPLE_SECTION, index),
+            block_type: BlockType::Code,
+            content: fenced("", example),
+        })
+        .collect();
+    if !examples.is_empty() {
+        spec.sections.push(SkillSection {
+            id: EXAMPLE_SECTION.to_string(),
+            title: "Examples".to_string(),
+            blocks: examples,
+        });
+    }
+    spec
+}
+
+/// Operations appending `item` to an existing skill.
+fn append_operations(item: &UncertaintyItem) -> Vec<EditOperation> {
+    let mut operations = vec![EditOperation::AddRule {
+        section: DEFAULT_RULE_SECTION.to_string(),
+        text: principle(item),
+    }];
+    operations.extend(
+        examples(item)
+            .into_iter()
+            .map(|code| EditOperation::AppendExample {
+                lang: String::new(),
+                code,
+            }),
+    );
+    operations
+}
+
+/// The generalization under review.
+fn principle(item: &UncertaintyItem) -> String {
+    let source = item
+        .source_instance
+        .as_ref()
+        .map(|source| source.description.as_str());
+    [item.pattern_candidate.description.as_deref(), source]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .find(|text| !text.is_empty())
+        .map_or_else(
+            || format!("Generalization of pattern {}", item.pattern_candidate.id),
+            str::to_string,
+        )
+}
+
+fn examples(item: &UncertaintyItem) -> Vec<String> {
+    item.cluster_info
+        .as_ref()
+        .map(|cluster| {
+            cluster
+                .examples
+                .iter()
+                .map(|example| example.trim().to_string())
+                .filter(|example| !example.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn draft_id(item: &UncertaintyItem) -> String {
+    let words: Vec<&str> = item
+        .pattern_candidate
+        .description
+        .as_deref()
+        .unwrap_or_default()
+        .split_whitespace()
+        .take(DRAFT_ID_WORDS)
+        .collect();
+    let id = slugify(&words.join(" "));
+    if id.is_empty() {
+        format!("review-{}", short_id(&item.id))
+    } else {
+        id
+    }
+}
+
+fn item_summary(item: &UncertaintyItem) -> serde_json::Value {
+    serde_json::json!({
+        "id": item.id,
+        "status": item.status.name(),
+        "reason": format_uncertainty_reason(&item.reason),
+        "confidence": item.confidence,
+        "threshold": item.threshold,
+        "age_days": age_days(item),
+        "generalization": principle(item),
+    })
+}
+
+fn age_days(item: &UncertaintyItem) -> i64 {
+    chrono::Utc::now()
+        .signed_duration_since(item.created_at)
+        .num_days()
+}
+
+fn short_id(id: &str) -> &str {
+    id.get(..8).unwrap_or(id)
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    let line = text.lines().next().unwrap_or_default();
+    if line.chars().count() <= max {
+        return line.to_string();
+    }
+    let cut: String = line.chars().take(max.saturating_sub(3)).collect();
+    format!("{cut}...")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cass::mining::{ExtractedPattern, PatternType};
+    use crate::cass::uncertainty::{ClusterSummary, SourceInstanceInfo};
+    use crate::cass::{UncertaintyReason, UncertaintyStatus};
+    use crate::core::spec_lens::parse_markdown;
+
+    fn item(description: Option<&str>) -> UncertaintyItem {
+        UncertaintyItem {
+            id: "0b5c2f9e-1111-2222-3333-444455556666".to_string(),
+            pattern_candidate: ExtractedPattern {
+                id: "pattern-1".to_string(),
+                pattern_type: PatternType::CommandPattern {
+                    commands: vec!["cargo test".to_string()],
+                    frequency: 3,
+                    contexts: vec![],
+                },
+                evidence: vec![],
+                confidence: 0.5,
+                frequency: 3,
+                tags: vec!["rust".to_string()],
+                description: description.map(str::to_string),
+                taint_label: None,
+            },
+            reason: UncertaintyReason::HighVariance {
+                variance_score: 0.6,
+                conflicting_aspects: vec![],
+            },
+            confidence: 0.5,
+            threshold: 0.8,
+            suggested_queries: vec![],
+            status: UncertaintyStatus::Pending,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            attempts: vec![],
+            critique: None,
+            source_instance: Some(SourceInstanceInfo {
+                instance_id: "fingerprint".to_string(),
+                session_id: "session-1".to_string(),
+                description: String::new(),
+                tool_signatures: vec![],
+            }),
+            cluster_info: Some(ClusterSummary {
+                cluster_id: "c1".to_string(),
+                instance_count: 2,
+                common_tools: vec![],
+                common_file_types: vec![],
+                examples: vec!["cargo test -- --nocapture".to_string(), "  ".to_string()],
+            }),
+            validation: None,
+        }
+    }
+
+    #[test]
+    fn draft_carries_principle_and_examples() {
+        let item = item(Some(
+            "Run the failing test alone with output before bisecting",
+        ));
+        let id = draft_id(&item);
+        assert_eq!(id, "run-the-failing-test-alone-with");
+
+        let spec = draft_spec(&item, &id);
+        let reparsed = parse_markdown(&compile_markdown(&spec)).unwrap();
+        assert_eq!(reparsed.metadata.tags, ["rust", "draft"]);
+        assert_eq!(reparsed.sections[0].blocks[0].block_type, BlockType::Rule);
+        assert!(
+            reparsed.sections[0].blocks[0]
+                .content
+                .starts_with("Run the failing test")
+        );
+        assert_eq!(reparsed.sections[1].blocks.len(), 1);
+        assert_eq!(
+            reparsed.sections[1].blocks[0].content,
+            "```\ncargo test -- --nocapture\n```"
+        );
+    }
+
+    #[test]
+    fn undescribed_items_fall_back_to_ids() {
+        let item = item(None);
+        assert_eq!(draft_id(&item), "review-0b5c2f9e");
+        assert_eq!(principle(&item), "Generalization of pattern pattern-1");
+        assert_eq!(append_operations(&item).len(), 2);
+    }
+}
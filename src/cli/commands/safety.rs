@@ -77,6 +77,7 @@ fn run_status(ctx: &AppContext) -> Result<()> {
             "dcg_bin": status.dcg_bin.display().to_string(),
             "packs": status.packs,
             "require_verbatim_approval": ctx.config.safety.require_verbatim_approval,
+            "profile": status.profile,
         });
         println!("{}", serde_json::to_string_pretty(&output)?);
     } else {
@@ -106,6 +107,13 @@ fn run_status(ctx: &AppContext) -> Result<()> {
                 "Disabled".dimmed()
             }
         );
+        println!(
+            "  Profile: {}",
+            status
+                .profile
+                .as_deref()
+                .map_or_else(|| "none".dimmed(), |profile| profile.cyan())
+        );
     }
 
     Ok(())
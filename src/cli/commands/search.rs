@@ -1,28 +1,60 @@
 //! ms search - Search for skills
 //!
 //! Provides hybrid search combining BM25 full-text and semantic vector
-//! similarity via RRF fusion.
+//! similarity via RRF fusion. `--batch-file` runs several queries in one
+//! process, opening the index and loading embeddings once.
+//!
+//! Results are boosted for the project around the working directory (or
+//! `--context-dir`): its project-layer skills and skills tagged with its
+//! project type, per `[search.context]`. `--no-context` ranks globally.
+//!
+//! Tag, layer, quality and deprecation filters run inside the BM25 query,
+//! where `[search.boosts]` also favors recent, high-quality skills; semantic
+//! candidates and the substring fallback are filtered afterwards.
+//!
+//! Results come in pages of at most [`MAX_PAGE_SIZE`]; `--cursor` resumes
+//! after the page that returned it, as long as the index has not changed.
+//! Each request ranks [`SEARCH_POOL_PAGES`] pages' worth of candidates;
+//! paging past them fails with `results_truncated`.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
 
 use clap::Args;
 use tracing::debug;
 
 use crate::app::AppContext;
 use crate::cli::formatters::SearchResults;
-use crate::cli::output::{Formattable, OutputFormat};
+use crate::cli::output::{Formattable, OutputFormat, emit_json};
+use crate::config::SearchConfig;
+use crate::context::DefaultDetector;
 use crate::error::{MsError, Result};
+use crate::search::cursor::{SEARCH_POOL_PAGES, query_hash, rank_order, score_key};
 use crate::search::{
-    RrfConfig, SearchFilters, SearchLayer, VectorIndex, build_embedder, fuse_simple,
+    BatchResults, Bm25Result, Embedder, MAX_PAGE_SIZE, Pager, QueryRanker, RankedSkill, RrfConfig,
+    SearchBoosts, SearchContext, SearchFilters, SearchLayer, SearchSnippet, VectorIndex,
+    build_embedder, fuse_bm25_results, parse_batch_queries, search_batch,
 };
+use crate::storage::sqlite::SkillRecord;
 
 #[derive(Args, Debug)]
 pub struct SearchArgs {
     /// Search query
-    pub query: String,
+    #[arg(required_unless_present = "batch_file", conflicts_with = "batch_file")]
+    pub query: Option<String>,
+
+    /// Run every query in FILE (one per line, `-` for stdin) in one pass
+    #[arg(long, value_name = "FILE")]
+    pub batch_file: Option<PathBuf>,
 
-    /// Maximum number of results
-    #[arg(long, short, default_value = "20")]
+    /// Maximum number of results (page size, at most 100)
+    #[arg(long, short, default_value = "20", value_parser = parse_page_size)]
     pub limit: usize,
 
+    /// Resume after the page that returned this `next_cursor`
+    #[arg(long, conflicts_with = "batch_file")]
+    pub cursor: Option<String>,
+
     /// Filter by tags (comma-separated)
     #[arg(long, short)]
     pub tags: Option<String>,
@@ -46,6 +78,42 @@ pub struct SearchArgs {
     /// Show snippets of matching content
     #[arg(long)]
     pub snippets: bool,
+
+    /// Fuse with BM25 alone (ignore semantic rankings)
+    #[arg(long, conflicts_with = "vector_only")]
+    pub bm25_only: bool,
+
+    /// Fuse with semantic rankings alone (ignore BM25)
+    #[arg(long)]
+    pub vector_only: bool,
+
+    /// RRF smoothing constant (overrides `search.rrf.k`)
+    #[arg(long, value_parser = parse_rrf_k)]
+    pub rrf_k: Option<f32>,
+
+    /// Rank without favoring the project around the working directory
+    #[arg(long, conflicts_with = "context_dir")]
+    pub no_context: bool,
+
+    /// Favor the project around DIR instead of the working directory
+    #[arg(long, value_name = "DIR")]
+    pub context_dir: Option<PathBuf>,
+}
+
+pub(crate) fn parse_page_size(raw: &str) -> std::result::Result<usize, String> {
+    raw.parse::<usize>()
+        .ok()
+        .filter(|n| (1..=MAX_PAGE_SIZE).contains(n))
+        .ok_or_else(|| format!("expected a page size between 1 and {MAX_PAGE_SIZE}, got {raw}"))
+}
+
+fn parse_rrf_k(raw: &str) -> std::result::Result<f32, String> {
+    let k: f32 = raw.parse().map_err(|_| format!("not a number: {raw}"))?;
+    if k.is_finite() && k > 0.0 {
+        Ok(k)
+    } else {
+        Err(format!("--rrf-k must be positive, got {raw}"))
+    }
 }
 
 pub fn run(ctx: &AppContext, args: &SearchArgs) -> Result<()> {
@@ -88,46 +156,348 @@ pub fn run(ctx: &AppContext, args: &SearchArgs) -> Result<()> {
 
     filters = filters.include_deprecated(args.include_deprecated);
 
+    let context = search_context(ctx, args);
+    if let Some(context) = &context {
+        debug!(target: "search", root = %context.root.display(), types = ?context.type_ids(), "project context");
+    }
+
+    if let Some(path) = &args.batch_file {
+        return run_batch(ctx, args, &filters, context.as_ref(), path);
+    }
+    let query = args.query.as_deref().unwrap_or_default();
+    let search_type = effective_search_type(ctx, args)?;
+    let pager = Pager::new(
+        search_query_hash(args, query, search_type, &filters, context.as_ref()),
+        ctx.search.generation()?,
+        args.limit,
+        args.cursor.as_deref(),
+    )?;
+
     // Execute search
+    match search_type {
+        "bm25" => search_bm25(ctx, args, query, &filters, context.as_ref(), &pager),
+        "semantic" => search_semantic(ctx, args, query, &filters, context.as_ref(), &pager),
+        _ => search_hybrid(ctx, args, query, &filters, context.as_ref(), &pager),
+    }
+}
+
+/// Everything that shapes the ranking, so a cursor only resumes the search
+/// it came from.
+fn search_query_hash(
+    args: &SearchArgs,
+    query: &str,
+    search_type: &str,
+    filters: &SearchFilters,
+    context: Option<&SearchContext>,
+) -> String {
+    let options = format!(
+        "{filters:?} {} {} {:?} {}",
+        args.bm25_only, args.vector_only, args.rrf_k, args.limit
+    );
+    let root = context
+        .map(|context| context.root.display().to_string())
+        .unwrap_or_default();
+    query_hash(&["search", query, search_type, &options, &root])
+}
+
+/// The project to rank for: `--context-dir`, else the working directory
+/// when `search.context.enabled`.
+fn search_context(ctx: &AppContext, args: &SearchArgs) -> Option<SearchContext> {
+    if args.no_context {
+        return None;
+    }
+    let dir = match &args.context_dir {
+        Some(dir) => dir.clone(),
+        None if ctx.config.search.context.enabled => std::env::current_dir().ok()?,
+        None => return None,
+    };
+    SearchContext::detect(&dir, &DefaultDetector::new())
+}
+
+/// Filter ranked `(skill_id, score)` candidates and keep the best `limit`.
+/// With a project context, scores are boosted first, so every candidate is
+/// considered before truncating.
+fn select_candidates(
+    ctx: &AppContext,
+    ranked: Vec<(String, f32)>,
+    filters: &SearchFilters,
+    context: Option<&SearchContext>,
+    limit: usize,
+) -> Result<Vec<(String, f32)>> {
+    let mut selected = Vec::new();
+    for (skill_id, score) in ranked {
+        // Check lightweight metadata first; full skills are loaded only for
+        // what is kept.
+        let Some(candidate) = ctx.db.get_skill_candidate(&skill_id)? else {
+            continue;
+        };
+        let mut skill_tags = parse_tags_from_metadata(&candidate.metadata_json);
+        skill_tags.extend(
+            ctx.db
+                .get_derived_tags(&skill_id)?
+                .into_iter()
+                .map(|derived| derived.tag),
+        );
+        if !filters.matches(
+            &skill_tags,
+            &candidate.source_layer,
+            candidate.quality_score as f32,
+            candidate.is_deprecated,
+        ) {
+            continue;
+        }
+        match context {
+            Some(context) => {
+                let boost = context.boost(
+                    &ctx.config.search.context,
+                    &candidate.source_layer,
+                    &candidate.source_path,
+                    &skill_tags,
+                );
+                selected.push((skill_id, score * boost));
+            }
+            None => {
+                selected.push((skill_id, score));
+                if selected.len() >= limit {
+                    break;
+                }
+            }
+        }
+    }
+    if context.is_some() {
+        selected.sort_by(|a, b| b.1.total_cmp(&a.1));
+        selected.truncate(limit);
+    }
+    Ok(selected)
+}
+
+/// Put `ranked` in cursor order, filter as much of it as `pager` needs, and
+/// split off the requested page with the cursor of the next one. `pool_full`
+/// when a ranking returned all [`Pager::pool_size`] candidates asked of it.
+fn page_candidates(
+    ctx: &AppContext,
+    mut ranked: Vec<(String, f32)>,
+    pool_full: bool,
+    filters: &SearchFilters,
+    context: Option<&SearchContext>,
+    pager: &Pager,
+) -> Result<(Vec<(String, f32)>, Option<String>)> {
+    ranked.sort_by(|a, b| rank_order((a.1, &a.0), (b.1, &b.0)));
+    let selected = select_candidates(ctx, ranked, filters, context, pager.window())?;
+    pager.page_pool(selected, pool_full, |(skill_id, score)| {
+        (score_key(*score), skill_id.as_str())
+    })
+}
+
+/// Load the full records of `selected`, with the snippets their ranking
+/// produced.
+fn load_results(
+    ctx: &AppContext,
+    selected: Vec<(String, f32)>,
+    mut snippets: HashMap<String, Vec<SearchSnippet>>,
+) -> Result<Vec<(SkillRecord, f32, Vec<SearchSnippet>)>> {
+    let mut results = Vec::with_capacity(selected.len());
+    for (skill_id, score) in selected {
+        if let Some(skill) = ctx.db.get_skill(&skill_id)? {
+            let snippets = snippets.remove(&skill_id).unwrap_or_default();
+            results.push((skill, score, snippets));
+        }
+    }
+    Ok(results)
+}
+
+/// `--search-type`, falling back to BM25 for hybrid search when embeddings
+/// are disabled.
+fn effective_search_type<'a>(ctx: &AppContext, args: &'a SearchArgs) -> Result<&'a str> {
     match args.search_type.as_str() {
-        "bm25" => search_bm25(ctx, args, &filters),
+        "bm25" => Ok("bm25"),
         "semantic" => {
             if !ctx.config.search.use_embeddings {
                 return Err(MsError::Config(
                     "semantic search disabled (search.use_embeddings=false)".to_string(),
                 ));
             }
-            search_semantic(ctx, args, &filters)
+            Ok("semantic")
         }
         "hybrid" | _ => {
             if !ctx.config.search.use_embeddings {
-                return search_bm25(ctx, args, &filters);
+                if args.vector_only {
+                    return Err(MsError::Config(
+                        "--vector-only needs embeddings (search.use_embeddings=false)".to_string(),
+                    ));
+                }
+                return Ok("bm25");
             }
-            search_hybrid(ctx, args, &filters)
+            Ok("hybrid")
         }
     }
 }
 
+/// Ranks one query of a batch the way a single `ms search` would, with the
+/// embedder and stored vectors loaded once for the whole batch.
+struct CliRanker<'a> {
+    ctx: &'a AppContext,
+    filters: &'a SearchFilters,
+    search_type: &'a str,
+    rrf: RrfConfig,
+    semantic: Option<(Box<dyn Embedder>, VectorIndex)>,
+    context: Option<&'a SearchContext>,
+}
+
+impl<'a> CliRanker<'a> {
+    fn open(
+        ctx: &'a AppContext,
+        args: &'a SearchArgs,
+        filters: &'a SearchFilters,
+        context: Option<&'a SearchContext>,
+    ) -> Result<Self> {
+        let search_type = effective_search_type(ctx, args)?;
+        let semantic = if search_type == "bm25" {
+            None
+        } else {
+            let embedder = build_embedder(&ctx.config.search)?;
+            let vectors = load_vector_index(ctx, embedder.as_ref())?;
+            Some((embedder, vectors))
+        };
+        Ok(Self {
+            ctx,
+            filters,
+            search_type,
+            rrf: rrf_config(&ctx.config.search, args),
+            semantic,
+            context,
+        })
+    }
+}
+
+impl QueryRanker for CliRanker<'_> {
+    fn rank(&self, query: &str, limit: usize) -> Result<Vec<RankedSkill>> {
+        let fetch_limit = limit * SEARCH_POOL_PAGES;
+        let semantic = match &self.semantic {
+            Some((embedder, vectors)) => vectors.search(&embedder.embed(query)?, fetch_limit),
+            None => Vec::new(),
+        };
+        let ranked: Vec<(String, f32)> = match self.search_type {
+            "semantic" => semantic,
+            "bm25" => bm25_ranked(self.ctx, query, Some(self.filters), fetch_limit)?
+                .into_iter()
+                .map(|hit| (hit.skill_id, hit.score))
+                .collect(),
+            _ => {
+                let bm25 = bm25_ranked(self.ctx, query, Some(self.filters), fetch_limit)?;
+                fuse_bm25_results(&bm25, &semantic, &self.rrf)
+                    .into_iter()
+                    .map(|fused| (fused.skill_id, fused.score))
+                    .collect()
+            }
+        };
+
+        Ok(
+            select_candidates(self.ctx, ranked, self.filters, self.context, limit)?
+                .into_iter()
+                .map(|(skill_id, score)| RankedSkill { skill_id, score })
+                .collect(),
+        )
+    }
+}
+
+/// `ms search --batch-file`: per-query results plus one merged ranking.
+fn run_batch(
+    ctx: &AppContext,
+    args: &SearchArgs,
+    filters: &SearchFilters,
+    context: Option<&SearchContext>,
+    path: &std::path::Path,
+) -> Result<()> {
+    let raw = if path.as_os_str() == "-" {
+        std::io::read_to_string(std::io::stdin())?
+    } else {
+        std::fs::read_to_string(path).map_err(|err| {
+            MsError::ValidationFailed(format!("read batch file {}: {err}", path.display()))
+        })?
+    };
+    let queries = parse_batch_queries(&raw);
+    let ranker = CliRanker::open(ctx, args, filters, context)?;
+    let batch = search_batch(&ranker, &queries, args.limit, &ctx.config.search.batch)?;
+
+    if ctx.output_format != OutputFormat::Human {
+        return emit_json(&serde_json::json!({
+            "status": "ok",
+            "search_type": ranker.search_type,
+            "context": context.map(context_json),
+            "queries": batch.queries,
+            "merged": batch.merged,
+        }));
+    }
+    if let Some(context) = context {
+        println!("context: {}\n", context.describe());
+    }
+    print_batch(&batch);
+    Ok(())
+}
+
+fn context_json(context: &SearchContext) -> serde_json::Value {
+    serde_json::json!({
+        "root": context.root.display().to_string(),
+        "project_types": context.type_ids(),
+    })
+}
+
+fn print_batch(batch: &BatchResults) {
+    for query in &batch.queries {
+        println!("{} ({} results)", query.query, query.results.len());
+        for hit in &query.results {
+            println!("  {:<40} {:.3}", hit.skill_id, hit.score);
+        }
+        println!();
+    }
+    println!("Merged ({} skills)", batch.merged.len());
+    for skill in &batch.merged {
+        println!(
+            "  {:<40} {:.4}  {}",
+            skill.skill_id,
+            skill.score,
+            skill.matched_queries.join(", ")
+        );
+    }
+}
+
 /// Ranked lexical (BM25) candidates for the CLI search path.
 ///
 /// Prefers the Tantivy BM25 index (`ctx.search`), which ranks by true BM25
 /// relevance across name/description/body/tags/aliases — the same engine the
-/// MCP server uses. Falls back to the SQLite substring scan only when the
-/// index is unavailable: never built / empty (e.g. a state dir produced by an
-/// older binary) or erroring (corrupt segment, unparsable query syntax). The
-/// fallback assigns descending pseudo-scores so downstream RRF fusion still
-/// sees a rank ordering (issue #144).
-fn bm25_ranked(ctx: &AppContext, query: &str, fetch_limit: usize) -> Result<Vec<(String, f32)>> {
+/// MCP server uses. With `filters`, they run inside the Tantivy query and
+/// `[search.boosts]` applies to the scores. Falls back to the SQLite
+/// substring scan only when the index is unavailable: never built / empty
+/// (e.g. a state dir produced by an older binary) or erroring (corrupt
+/// segment, unparsable query syntax). The fallback assigns descending
+/// pseudo-scores so downstream RRF fusion still sees a rank ordering (issue
+/// #144), carries no snippets, and leaves filtering to the caller.
+pub(crate) fn bm25_ranked(
+    ctx: &AppContext,
+    query: &str,
+    filters: Option<&SearchFilters>,
+    fetch_limit: usize,
+) -> Result<Vec<Bm25Result>> {
     if ctx.search.is_empty() {
         debug!(
             target: "search",
             "bm25: tantivy index empty; falling back to substring scan"
         );
     } else {
-        match ctx.search.search(query, fetch_limit) {
+        let hits = match filters {
+            Some(filters) => ctx.search.search_filtered(
+                query,
+                filters,
+                &SearchBoosts::from_search_config(&ctx.config.search),
+                fetch_limit,
+            ),
+            None => ctx.search.search(query, fetch_limit),
+        };
+        match hits {
             Ok(hits) => {
                 debug!(target: "search", backend = "tantivy", hits = hits.len(), "bm25 candidates");
-                return Ok(hits.into_iter().map(|r| (r.skill_id, r.score)).collect());
+                return Ok(hits);
             }
             Err(err) => {
                 debug!(
@@ -144,144 +514,137 @@ fn bm25_ranked(ctx: &AppContext, query: &str, fetch_limit: usize) -> Result<Vec<
     Ok(candidates
         .into_iter()
         .enumerate()
-        .map(|(i, c)| (c.id, 1.0 / (i + 1) as f32)) // Convert rank to pseudo-score
+        .map(|(i, c)| Bm25Result {
+            name: c.id.clone(),
+            skill_id: c.id,
+            score: 1.0 / (i + 1) as f32, // Convert rank to pseudo-score
+            layer: c.source_layer,
+            snippets: Vec::new(),
+        })
         .collect())
 }
 
-fn search_hybrid(ctx: &AppContext, args: &SearchArgs, filters: &SearchFilters) -> Result<()> {
+/// Load stored embeddings, refusing vectors built by a different backend or
+/// with a different dimension than `embedder`.
+fn load_vector_index(ctx: &AppContext, embedder: &dyn Embedder) -> Result<VectorIndex> {
+    let mut index = VectorIndex::for_embedder(embedder);
+    for record in ctx.db.get_all_embedding_records()? {
+        index.insert_stored(record.skill_id, record.embedding, &record.embedder_type)?;
+    }
+    Ok(index)
+}
+
+/// Ranked semantic candidates: `(skill_id, similarity)`, best first.
+pub(crate) fn semantic_ranked(
+    ctx: &AppContext,
+    query: &str,
+    fetch_limit: usize,
+) -> Result<Vec<(String, f32)>> {
+    let embedder = build_embedder(&ctx.config.search)?;
+    let query_embedding = embedder.embed(query)?;
+    let vector_index = load_vector_index(ctx, embedder.as_ref())?;
+    Ok(vector_index.search(&query_embedding, fetch_limit))
+}
+
+fn search_hybrid(
+    ctx: &AppContext,
+    args: &SearchArgs,
+    query: &str,
+    filters: &SearchFilters,
+    context: Option<&SearchContext>,
+    pager: &Pager,
+) -> Result<()> {
     // Fetch enough results from both systems for fusion
     // Increase limit to allow for filtering
-    let fetch_limit = args.limit * 50;
+    let fetch_limit = pager.pool_size();
 
     // BM25 search (Tantivy, with substring-scan fallback)
-    let bm25_results = bm25_ranked(ctx, &args.query, fetch_limit)?;
+    let bm25_results = bm25_ranked(ctx, query, Some(filters), fetch_limit)?;
 
-    // Build semantic search using embeddings
-    let embedder = build_embedder(&ctx.config.search)?;
-    let query_embedding = embedder.embed(&args.query);
+    // Semantic search using stored embeddings
+    let semantic_results = semantic_ranked(ctx, query, fetch_limit)?;
+    let pool_full = bm25_results.len() >= fetch_limit || semantic_results.len() >= fetch_limit;
 
-    // Load embeddings from database
-    let mut vector_index = VectorIndex::new(embedder.dims());
-    let all_embeddings = ctx.db.get_all_embeddings()?;
+    // RRF fusion
+    let config = rrf_config(&ctx.config.search, args);
+    let fused = fuse_bm25_results(&bm25_results, &semantic_results, &config);
 
-    for (id, embedding) in all_embeddings {
-        let _ = vector_index.insert(id, embedding);
+    let mut snippets = HashMap::new();
+    let mut ranked = Vec::with_capacity(fused.len());
+    for fused_result in fused {
+        snippets.insert(fused_result.skill_id.clone(), fused_result.snippets);
+        ranked.push((fused_result.skill_id, fused_result.score));
     }
+    let (selected, next_cursor) = page_candidates(ctx, ranked, pool_full, filters, context, pager)?;
+    let results = load_results(ctx, selected, snippets)?;
 
-    // Semantic search
-    let semantic_results = vector_index.search(&query_embedding, fetch_limit);
-
-    // RRF fusion
-    let config = RrfConfig::with_weights(
-        ctx.config.search.bm25_weight,
-        ctx.config.search.semantic_weight,
-    );
-    let fused = fuse_simple(&bm25_results, &semantic_results, &config);
-
-    // Fetch full skill records and apply filters
-    let mut results = Vec::new();
-    for (skill_id, score) in fused {
-        // Check lightweight metadata first; only load the full skill if it
-        // passes the filters.
-        if let Some(candidate) = ctx.db.get_skill_candidate(&skill_id)? {
-            let skill_tags = parse_tags_from_metadata(&candidate.metadata_json);
-
-            // Apply filters on metadata
-            if filters.matches(
-                &skill_tags,
-                &candidate.source_layer,
-                candidate.quality_score as f32,
-                candidate.is_deprecated,
-            ) {
-                if let Some(skill) = ctx.db.get_skill(&skill_id)? {
-                    results.push((skill, score));
-                }
-            }
-        }
+    display_results(ctx, &results, args, query, "hybrid", context, next_cursor)
+}
 
-        if results.len() >= args.limit {
-            break;
-        }
+/// `[search.rrf]` with this invocation's overrides applied
+fn rrf_config(search: &SearchConfig, args: &SearchArgs) -> RrfConfig {
+    let mut config = RrfConfig::from_search_config(search);
+    if let Some(k) = args.rrf_k {
+        config.k = k;
     }
-
-    display_results(ctx, &results, args, "hybrid")
+    if args.bm25_only {
+        config = config.bm25_only();
+    } else if args.vector_only {
+        config = config.vector_only();
+    }
+    config
 }
 
-fn search_bm25(ctx: &AppContext, args: &SearchArgs, filters: &SearchFilters) -> Result<()> {
+fn search_bm25(
+    ctx: &AppContext,
+    args: &SearchArgs,
+    query: &str,
+    filters: &SearchFilters,
+    context: Option<&SearchContext>,
+    pager: &Pager,
+) -> Result<()> {
     // Increase limit to allow for filtering
-    let ranked = bm25_ranked(ctx, &args.query, args.limit * 50)?;
+    let hits = bm25_ranked(ctx, query, Some(filters), pager.pool_size())?;
+    let pool_full = hits.len() >= pager.pool_size();
 
-    let mut results = Vec::new();
-    for (skill_id, score) in ranked {
-        if let Some(candidate) = ctx.db.get_skill_candidate(&skill_id)? {
-            let skill_tags = parse_tags_from_metadata(&candidate.metadata_json);
-
-            if filters.matches(
-                &skill_tags,
-                &candidate.source_layer,
-                candidate.quality_score as f32,
-                candidate.is_deprecated,
-            ) {
-                if let Some(skill) = ctx.db.get_skill(&skill_id)? {
-                    results.push((skill, score));
-                }
-            }
-        }
-
-        if results.len() >= args.limit {
-            break;
-        }
+    let mut snippets = HashMap::new();
+    let mut ranked = Vec::with_capacity(hits.len());
+    for hit in hits {
+        snippets.insert(hit.skill_id.clone(), hit.snippets);
+        ranked.push((hit.skill_id, hit.score));
     }
+    let (selected, next_cursor) = page_candidates(ctx, ranked, pool_full, filters, context, pager)?;
+    let results = load_results(ctx, selected, snippets)?;
 
-    display_results(ctx, &results, args, "bm25")
+    display_results(ctx, &results, args, query, "bm25", context, next_cursor)
 }
 
-fn search_semantic(ctx: &AppContext, args: &SearchArgs, filters: &SearchFilters) -> Result<()> {
-    let embedder = build_embedder(&ctx.config.search)?;
-    let query_embedding = embedder.embed(&args.query);
-
-    // Load embeddings
-    let mut vector_index = VectorIndex::new(embedder.dims());
-    let all_embeddings = ctx.db.get_all_embeddings()?;
-
-    for (id, embedding) in all_embeddings {
-        let _ = vector_index.insert(id, embedding);
-    }
-
+fn search_semantic(
+    ctx: &AppContext,
+    args: &SearchArgs,
+    query: &str,
+    filters: &SearchFilters,
+    context: Option<&SearchContext>,
+    pager: &Pager,
+) -> Result<()> {
     // Search more to allow filtering
-    let search_results = vector_index.search(&query_embedding, args.limit * 50);
-
-    let mut results = Vec::new();
-    for (skill_id, score) in search_results {
-        // Fetch metadata first
-        if let Some(candidate) = ctx.db.get_skill_candidate(&skill_id)? {
-            let skill_tags = parse_tags_from_metadata(&candidate.metadata_json);
-
-            if filters.matches(
-                &skill_tags,
-                &candidate.source_layer,
-                candidate.quality_score as f32,
-                candidate.is_deprecated,
-            ) {
-                if let Some(skill) = ctx.db.get_skill(&skill_id)? {
-                    results.push((skill, score));
-                }
-            }
-        }
+    let ranked = semantic_ranked(ctx, query, pager.pool_size())?;
+    let pool_full = ranked.len() >= pager.pool_size();
+    let (selected, next_cursor) = page_candidates(ctx, ranked, pool_full, filters, context, pager)?;
+    // Vector-only matches have nothing to highlight
+    let results = load_results(ctx, selected, HashMap::new())?;
 
-        if results.len() >= args.limit {
-            break;
-        }
-    }
-
-    display_results(ctx, &results, args, "semantic")
+    display_results(ctx, &results, args, query, "semantic", context, next_cursor)
 }
 
 fn display_results(
     ctx: &AppContext,
-    results: &[(crate::storage::sqlite::SkillRecord, f32)],
+    results: &[(SkillRecord, f32, Vec<SearchSnippet>)],
     args: &SearchArgs,
+    query: &str,
     search_type: &str,
+    context: Option<&SearchContext>,
+    next_cursor: Option<String>,
 ) -> Result<()> {
     debug!(target: "search", stage = "render_start");
     debug!(target: "search", results = results.len(), "rendering results");
@@ -290,13 +653,22 @@ fn display_results(
     let start = std::time::Instant::now();
 
     // Build SearchResults using the new formatter
-    let mut search_results = SearchResults::from_tuples(&args.query, search_type, results);
+    let mut search_results = SearchResults::new(query, search_type).with_next_cursor(next_cursor);
+    if let Some(context) = context {
+        search_results = search_results.with_context(context.clone());
+    }
+    for (skill, score, snippets) in results {
+        search_results.add_result(skill.clone(), *score);
+        if let Some(item) = search_results.results.last_mut() {
+            item.snippets.clone_from(snippets);
+        }
+    }
 
     // Add snippets if requested
     if args.snippets {
-        for (i, (skill, _)) in results.iter().enumerate() {
+        for (i, (skill, _, _)) in results.iter().enumerate() {
             if !skill.body.is_empty() {
-                if let Some(snippet) = find_snippet(&skill.body, &args.query) {
+                if let Some(snippet) = find_snippet(&skill.body, query) {
                     if i < search_results.results.len() {
                         search_results.results[i].snippet = Some(snippet);
                     }
@@ -506,7 +878,7 @@ mod tests {
         }
 
         let parsed = TestCli::parse_from(["test", "rust error handling"]);
-        assert_eq!(parsed.args.query, "rust error handling");
+        assert_eq!(parsed.args.query.as_deref(), Some("rust error handling"));
         assert_eq!(parsed.args.limit, 20);
         assert_eq!(parsed.args.search_type, "hybrid");
     }
@@ -576,6 +948,23 @@ mod tests {
         assert_eq!(parsed.args.tags, Some("testing".to_string()));
     }
 
+    #[test]
+    fn test_search_args_batch_file() {
+        use clap::Parser;
+
+        #[derive(Parser)]
+        struct TestCli {
+            #[command(flatten)]
+            args: SearchArgs,
+        }
+
+        let parsed = TestCli::parse_from(["test", "--batch-file", "queries.txt", "-l", "3"]);
+        assert!(parsed.args.query.is_none());
+        assert_eq!(parsed.args.batch_file, Some(PathBuf::from("queries.txt")));
+        assert!(TestCli::try_parse_from(["test"]).is_err());
+        assert!(TestCli::try_parse_from(["test", "q", "--batch-file", "queries.txt"]).is_err());
+    }
+
     #[test]
     fn test_find_snippet_unicode_expansion_bug() {
         // "İ" (U+0130) lowercases to "i\u{307}" (U+0069 U+0307)
@@ -6,8 +6,8 @@
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use clap::{Args, CommandFactory};
-use clap_complete::{Shell, generate};
+use clap::Args;
+use clap_complete::Shell;
 use colored::Colorize;
 use serde::Serialize;
 use tracing::{debug, info};
@@ -635,9 +635,10 @@ fn setup_shell_completions(shell: Shell, dry_run: bool) -> Result<SetupAction> {
 
     // Generate completions
     fs::create_dir_all(&dir)?;
-    let mut file = fs::File::create(&target_path)?;
-    let mut cmd = crate::cli::Cli::command();
-    generate(shell, &mut cmd, "ms", &mut file);
+    fs::write(
+        &target_path,
+        crate::cli::commands::completions::completion_script(shell),
+    )?;
 
     Ok(SetupAction::new(
         SetupActionType::GenerateCompletions,
@@ -2,18 +2,46 @@
 //!
 //! Displays skill information in multiple formats: rich terminal output with
 //! panels and styled metadata (Human mode), plain YAML-like key-value pairs
-//! (Plain mode), JSON, JSONL, TSV, and TOON.
-
+//! (Plain mode), JSON, JSONL, TSV, and TOON. `--quality` adds the quality
+//! score breakdown (human, JSON, and TOON output). Project overrides from
+//! `.ms/overrides.toml` are applied to the shown skill, and the blocks they
+//! touched are listed with the file they came from. `--effective` renders
+//! the skill with inheritance, includes, and overrides applied, each section
+//! and block annotated with its origin; `--base` renders only what the skill
+//! inherits. `--changelog` summarizes rule, example, and metadata changes
+//! between archived versions; `--resources` lists the files that travel with
+//! the skill.
+
+use std::path::PathBuf;
+
+use chrono::{NaiveDate, Utc};
 use clap::Args;
+use colored::Colorize;
 use tracing::debug;
 
 use crate::app::AppContext;
-use crate::cli::output::OutputFormat;
-use crate::error::{MsError, Result};
+use crate::cli::commands::index::parse_layer;
+use crate::cli::commands::quality::{QualityReport, quality_report};
+use crate::cli::commands::trial::describe_stats;
+use crate::cli::output::{OutputFormat, TableFormat, emit_json};
+use crate::core::applicability::{self, SkillWindow, SystemClock, WindowStatus};
+use crate::core::changelog::{ChangelogSince, build_changelog, group_by_version};
+use crate::core::deep_link::{AnchorKind, DeepLink, list_anchors};
+use crate::core::effective::{Origin, effective_skill, inherited_base};
+use crate::core::lookup::{self, ResolveOptions};
+use crate::core::overlay::{BlockAction, OverlayApplicationResult};
+use crate::core::overrides::ProjectOverrides;
+use crate::core::resolution::DbSkillRepository;
+use crate::core::skill::SkillLayer;
+use crate::core::spec_lens::{compile_markdown, parse_markdown};
+use crate::error::Result;
 use crate::output::{
-    is_agent_environment, is_ci_environment, key_value_table, skill_detail_panel, warning_panel,
+    RichOutput, is_agent_environment, is_ci_environment, key_value_table, quality_components_table,
+    skill_detail_panel, warning_panel,
 };
-use crate::storage::sqlite::SkillRecord;
+use crate::security::encryption;
+use crate::storage::sqlite::{DerivedTagRecord, SkillRecord};
+use crate::utils::format::format_size;
 
 #[derive(Args, Debug)]
 pub struct ShowArgs {
@@ -31,22 +59,56 @@ pub struct ShowArgs {
     /// Show dependency graph
     #[arg(long)]
     pub deps: bool,
+
+    /// List section/block anchors and their ms:// deep links
+    #[arg(long)]
+    pub anchors: bool,
+
+    /// List the archive commits that changed the skill, with their provenance
+    #[arg(long)]
+    pub history: bool,
+
+    /// Summarize what changed between archived versions: rules, examples,
+    /// and metadata, grouped by declared version
+    #[arg(long, conflicts_with = "history")]
+    pub changelog: bool,
+
+    /// Start the changelog after a version (v1.2.0), an age (30d), or a
+    /// date (2026-03-01)
+    #[arg(long, requires = "changelog")]
+    pub since: Option<String>,
+
+    /// List the resource files that travel with the skill, with their sizes
+    #[arg(long, conflicts_with_all = ["history", "changelog"])]
+    pub resources: bool,
+
+    /// Print how the skill reference was resolved (to stderr)
+    #[arg(long)]
+    pub trace_resolution: bool,
+
+    /// Recompute the quality score and explain each component
+    #[arg(long)]
+    pub quality: bool,
+
+    /// Show the skill as agents receive it: inheritance, includes, and
+    /// overrides applied, each section and block annotated with its origin
+    #[arg(long, conflicts_with = "base")]
+    pub effective: bool,
+
+    /// Show only what the skill inherits from its parent, for comparison
+    /// with --effective
+    #[arg(long)]
+    pub base: bool,
 }
 
 pub fn run(ctx: &AppContext, args: &ShowArgs) -> Result<()> {
-    // Try to find skill by ID or name
-    let skill = ctx
-        .db
-        .get_skill(&args.skill)?
-        .or_else(|| {
-            // Try alias resolution
-            ctx.db
-                .resolve_alias(&args.skill)
-                .ok()
-                .flatten()
-                .and_then(|res| ctx.db.get_skill(&res.canonical_id).ok().flatten())
-        })
-        .ok_or_else(|| MsError::SkillNotFound(format!("skill not found: {}", args.skill)))?;
+    let resolution = lookup::resolve(&ctx.db, &args.skill, &ResolveOptions::default())?;
+    if args.trace_resolution {
+        for line in resolution.trace.render() {
+            eprintln!("{line}");
+        }
+    }
+    let skill = resolution.into_record()?;
 
     display_skill(ctx, &skill, args)
 }
@@ -55,32 +117,317 @@ fn display_skill(ctx: &AppContext, skill: &SkillRecord, args: &ShowArgs) -> Resu
     debug!(target: "show", skill_id = %skill.id, "loading skill");
     debug!(target: "show", mode = ?ctx.output_format, "output mode selected");
 
+    if args.history {
+        return show_history(ctx, &skill.id);
+    }
+    if args.changelog {
+        return show_changelog(ctx, &skill.id, args.since.as_deref());
+    }
+    if args.resources {
+        return show_resources(ctx, &skill.id);
+    }
+
+    // Sealed bodies are decrypted only when the output needs them, so
+    // metadata views work without the skill key.
+    let needs_body = args.full
+        || args.anchors
+        || args.quality
+        || args.effective
+        || args.base
+        || ctx.output_format == OutputFormat::Plain;
+    let revealed;
+    let skill = if needs_body && encryption::is_sealed_body(&skill.body) {
+        revealed = SkillRecord {
+            body: encryption::reveal_body(&skill.id, &skill.body)?,
+            ..skill.clone()
+        };
+        &revealed
+    } else {
+        skill
+    };
+
+    if args.effective || args.base {
+        return show_effective(ctx, skill, args.base);
+    }
+
+    // Show the effective skill when the project overrides it
+    let overridden;
+    let (skill, overrides) = match OverrideInfo::apply(&ctx.project_overrides()?, skill)? {
+        Some((record, info)) => {
+            overridden = record;
+            (&overridden, Some(info))
+        }
+        None => (skill, None),
+    };
+    let overrides = overrides.as_ref();
+
+    if args.anchors {
+        return show_anchors(ctx, skill);
+    }
+
+    let today = applicability::today(&SystemClock, &ctx.config.applicability);
+    let quality = if args.quality {
+        Some(quality_report(ctx, skill)?)
+    } else {
+        None
+    };
+    let quality = quality.as_ref();
+    let derived = DerivedTags(ctx.db.get_derived_tags(&skill.id)?);
     let result = match ctx.output_format {
-        OutputFormat::Human => show_human(ctx, skill, args),
-        OutputFormat::Json => show_json(skill, args, true),
-        OutputFormat::Jsonl => show_json(skill, args, false),
+        OutputFormat::Human => show_human(ctx, skill, args, today, quality, overrides, &derived),
+        OutputFormat::Json => show_json(skill, args, true, today, quality, overrides, &derived),
+        OutputFormat::Jsonl => show_json(skill, args, false, today, quality, overrides, &derived),
         OutputFormat::Plain => show_plain(skill),
-        OutputFormat::Tsv => show_tsv(skill),
-        OutputFormat::Toon => show_toon(skill, args),
+        OutputFormat::Tsv => show_table(skill, TableFormat::Tsv),
+        OutputFormat::Csv => show_table(skill, TableFormat::Csv),
+        OutputFormat::Toon => show_toon(skill, args, today, quality, overrides, &derived),
     };
 
     debug!(target: "show", stage = "render_complete");
     result
 }
 
-fn show_human(_ctx: &AppContext, skill: &SkillRecord, args: &ShowArgs) -> Result<()> {
+fn show_human(
+    ctx: &AppContext,
+    skill: &SkillRecord,
+    args: &ShowArgs,
+    today: NaiveDate,
+    quality: Option<&QualityReport>,
+    overrides: Option<&OverrideInfo>,
+    derived: &DerivedTags,
+) -> Result<()> {
     let use_rich = should_use_rich_for_show();
     let width = terminal_width();
 
     if use_rich {
-        show_human_rich(skill, args, width)
+        show_human_rich(skill, args, width, today)?;
     } else {
-        show_human_plain(skill, args)
+        show_human_plain(skill, args, today)?;
+    }
+    derived.print();
+    if let Some(info) = overrides {
+        info.print();
+    }
+
+    if let Some(report) = quality {
+        let output = if use_rich {
+            RichOutput::new(&ctx.config, &ctx.output_format, ctx.robot_mode)
+        } else {
+            RichOutput::plain()
+        };
+        show_quality(&output, report);
+    }
+    Ok(())
+}
+
+/// Quality components table, the issues behind them, and a stale-index hint.
+fn show_quality(output: &RichOutput, report: &QualityReport) {
+    output.newline();
+    output.section("Quality");
+    let stored = format!("{:.2}", report.stored);
+    let recomputed = format!("{:.2}", report.recomputed);
+    let mut pairs = vec![("Stored", stored), ("Recomputed", recomputed)];
+    if let Some(efficacy) = &report.efficacy {
+        pairs.push((
+            "Efficacy",
+            format!(
+                "{:.2} (blended at {:.0}%)",
+                efficacy.score,
+                efficacy.weight * 100.0
+            ),
+        ));
+    }
+    if let Some(trials) = &report.trials {
+        let rate = trials
+            .success_rate
+            .map_or_else(|| "-".to_string(), |rate| format!("{:.0}%", rate * 100.0));
+        pairs.push((
+            "Trials",
+            format!(
+                "{}, {rate} success, {:+.2}",
+                describe_stats(&trials.stats),
+                trials.adjustment
+            ),
+        ));
+    }
+    let pairs: Vec<(&str, &str)> = pairs.iter().map(|(k, v)| (*k, v.as_str())).collect();
+    output.key_value_list(&pairs);
+
+    let rows: Vec<(&str, f32, f32, f32, &str)> = report
+        .components
+        .iter()
+        .map(|c| (c.name, c.weight, c.value, c.contribution, c.detail.as_str()))
+        .collect();
+    output.print_table(&quality_components_table(&rows));
+
+    let issues: Vec<String> = report
+        .components
+        .iter()
+        .flat_map(|component| {
+            component
+                .issues
+                .iter()
+                .map(move |issue| (component.name, issue))
+        })
+        .chain(report.other_issues.iter().map(|issue| ("other", issue)))
+        .map(|(component, issue)| {
+            let mut line = format!("[{component}] {}", issue.message);
+            match (&issue.section, &issue.block) {
+                (Some(section), Some(block)) => {
+                    line.push_str(&format!(" (section {section}, block {block})"));
+                }
+                (Some(section), None) => line.push_str(&format!(" (section {section})")),
+                _ => {}
+            }
+            if let Some(suggestion) = issue.suggestion {
+                line.push_str(&format!(" - {suggestion}"));
+            }
+            line
+        })
+        .collect();
+    if !issues.is_empty() {
+        output.newline();
+        output.println("Issues");
+        output.list(&issues.iter().map(String::as_str).collect::<Vec<_>>());
+    }
+
+    if report.stale {
+        output.newline();
+        output.warning(&format!(
+            "Stored score {:.2} differs from the recomputed {:.2}; the index is stale. Run `ms index`.",
+            report.stored, report.recomputed
+        ));
+    }
+}
+
+/// Applicability window of a skill, if it declares one or an expiry.
+struct WindowInfo {
+    status: WindowStatus,
+    expires: Option<NaiveDate>,
+}
+
+impl WindowInfo {
+    fn of(skill: &SkillRecord, today: NaiveDate) -> Option<Self> {
+        let window = SkillWindow::from_metadata_json(&skill.metadata_json);
+        (window.has_window() || window.expires_on().is_some()).then(|| Self {
+            status: window.status_on(today),
+            expires: window.expires_on(),
+        })
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "status": self.status.as_str(),
+            "expires": self.expires.map(|d| d.to_string()),
+        })
+    }
+}
+
+/// Project overrides applied to the shown skill.
+struct OverrideInfo {
+    source: PathBuf,
+    result: OverlayApplicationResult,
+}
+
+impl OverrideInfo {
+    /// The effective record of `skill` under the project's overrides, or
+    /// `None` when the project does not override it.
+    fn apply(
+        overrides: &ProjectOverrides,
+        skill: &SkillRecord,
+    ) -> Result<Option<(SkillRecord, Self)>> {
+        if !overrides.skills.contains_key(&skill.id) {
+            return Ok(None);
+        }
+        let mut spec = parse_markdown(&encryption::reveal_body(&skill.id, &skill.body)?)?;
+        spec.metadata.id.clone_from(&skill.id);
+        spec.metadata.name.clone_from(&skill.name);
+        spec.metadata.description.clone_from(&skill.description);
+        spec.metadata.version = skill.version.clone().unwrap_or_default();
+        spec.metadata.author.clone_from(&skill.author);
+        let Some(result) = overrides.apply(&mut spec) else {
+            return Ok(None);
+        };
+
+        let record = SkillRecord {
+            name: spec.metadata.name.clone(),
+            description: spec.metadata.description.clone(),
+            version: Some(spec.metadata.version.clone()).filter(|v| !v.is_empty()),
+            author: spec.metadata.author.clone(),
+            body: compile_markdown(&spec),
+            ..skill.clone()
+        };
+        let info = Self {
+            source: overrides.path.clone(),
+            result,
+        };
+        Ok(Some((record, info)))
+    }
+
+    fn print(&self) {
+        println!("\nOverrides");
+        println!("{}", "-".repeat(40));
+        for change in &self.result.blocks {
+            let action = match change.action {
+                BlockAction::Replaced => "replaced",
+                BlockAction::Removed => "removed",
+                BlockAction::Added => "added",
+            };
+            println!(
+                "{action:<9} {}/{}  (from {})",
+                change.section_id,
+                change.block_id,
+                self.source.display()
+            );
+        }
+        let metadata_changes = self.result.changes.iter().filter(|change| {
+            !["Replaced block:", "Removed block:", "Added block:"]
+                .iter()
+                .any(|prefix| change.starts_with(prefix))
+        });
+        for change in metadata_changes {
+            println!("{change}  (from {})", self.source.display());
+        }
+        for unmatched in &self.result.unmatched {
+            println!("WARNING {unmatched}  (in {})", self.source.display());
+        }
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "source": self.source.display().to_string(),
+            "blocks": self.result.blocks,
+            "changes": self.result.changes,
+            "unmatched": self.result.unmatched,
+        })
+    }
+}
+
+/// Tags `ms index` derived from the skill's content, kept apart from the
+/// tags its author wrote.
+struct DerivedTags(Vec<DerivedTagRecord>);
+
+impl DerivedTags {
+    fn print(&self) {
+        if self.0.is_empty() {
+            return;
+        }
+        let tags: Vec<String> = self
+            .0
+            .iter()
+            .map(|derived| format!("{} ({})", derived.tag, derived.kind))
+            .collect();
+        println!("\nDerived tags: {}", tags.join(", "));
     }
 }
 
 /// Rich terminal rendering using panels and styled tables.
-fn show_human_rich(skill: &SkillRecord, args: &ShowArgs, width: usize) -> Result<()> {
+fn show_human_rich(
+    skill: &SkillRecord,
+    args: &ShowArgs,
+    width: usize,
+    today: NaiveDate,
+) -> Result<()> {
     // Header panel with skill info
     let panel = skill_detail_panel(
         &skill.name,
@@ -104,6 +451,13 @@ fn show_human_rich(skill: &SkillRecord, args: &ShowArgs, width: usize) -> Result
     if let Some(ref author) = skill.author {
         pairs.push(("Author", author.clone()));
     }
+    let window = WindowInfo::of(skill, today);
+    if let Some(ref window) = window {
+        pairs.push(("Window", window.status.as_str().to_string()));
+        if let Some(expires) = window.expires {
+            pairs.push(("Expires", expires.to_string()));
+        }
+    }
 
     let table_data: Vec<(&str, &str)> = pairs.iter().map(|(k, v)| (*k, v.as_str())).collect();
     let table = key_value_table(&table_data);
@@ -118,6 +472,17 @@ fn show_human_rich(skill: &SkillRecord, args: &ShowArgs, width: usize) -> Result
         let warn = warning_panel("DEPRECATED", reason);
         println!("\n{warn}");
     }
+    if let Some(WindowInfo {
+        status: WindowStatus::Expired,
+        expires: Some(expires),
+    }) = window
+    {
+        let warn = warning_panel(
+            "EXPIRED",
+            &format!("Expired on {expires}; excluded from suggestions"),
+        );
+        println!("\n{warn}");
+    }
 
     // Stats
     println!("\nStats");
@@ -172,7 +537,7 @@ fn show_human_rich(skill: &SkillRecord, args: &ShowArgs, width: usize) -> Result
 }
 
 /// Plain text rendering without any ANSI/styling.
-fn show_human_plain(skill: &SkillRecord, args: &ShowArgs) -> Result<()> {
+fn show_human_plain(skill: &SkillRecord, args: &ShowArgs, today: NaiveDate) -> Result<()> {
     // Header
     println!("{}", skill.name);
     println!("{}", "=".repeat(skill.name.len()));
@@ -186,6 +551,13 @@ fn show_human_plain(skill: &SkillRecord, args: &ShowArgs) -> Result<()> {
     }
     println!("Layer:   {}", normalize_layer(&skill.source_layer));
     println!("Source:  {}", skill.source_path);
+    let window = WindowInfo::of(skill, today);
+    if let Some(ref window) = window {
+        println!("Window:  {}", window.status.as_str());
+        if let Some(expires) = window.expires {
+            println!("Expires: {expires}");
+        }
+    }
 
     // Description
     if !skill.description.is_empty() {
@@ -204,6 +576,14 @@ fn show_human_plain(skill: &SkillRecord, args: &ShowArgs) -> Result<()> {
                 .unwrap_or("No reason provided")
         );
     }
+    if let Some(WindowInfo {
+        status: WindowStatus::Expired,
+        expires: Some(expires),
+    }) = window
+    {
+        println!();
+        println!("WARNING EXPIRED: expired on {expires}; excluded from suggestions");
+    }
 
     // Stats
     println!();
@@ -261,6 +641,253 @@ fn show_human_plain(skill: &SkillRecord, args: &ShowArgs) -> Result<()> {
     Ok(())
 }
 
+/// List the anchors of a skill with ready-to-use deep links.
+fn show_anchors(ctx: &AppContext, skill: &SkillRecord) -> Result<()> {
+    let spec = parse_markdown(&skill.body)?;
+    let layer = normalize_layer(&skill.source_layer);
+    let link_for = |anchor: &str| DeepLink {
+        layer: Some(layer.clone()),
+        skill: skill.id.clone(),
+        anchor: Some(anchor.to_string()),
+    };
+    let anchors = list_anchors(&spec);
+
+    if ctx.output_format == OutputFormat::Human {
+        if anchors.is_empty() {
+            println!("No anchors in {}", skill.id);
+        }
+        for anchor in &anchors {
+            let indent = if anchor.kind == AnchorKind::Section {
+                ""
+            } else {
+                "  "
+            };
+            println!("{indent}{}  {}", link_for(&anchor.id), anchor.label);
+        }
+        return Ok(());
+    }
+
+    let entries: Vec<serde_json::Value> = anchors
+        .iter()
+        .map(|anchor| {
+            serde_json::json!({
+                "id": anchor.id,
+                "kind": anchor.kind,
+                "section_id": anchor.section_id,
+                "label": anchor.label,
+                "link": link_for(&anchor.id).to_string(),
+            })
+        })
+        .collect();
+    emit_json(&serde_json::json!({
+        "status": "ok",
+        "skill_id": skill.id,
+        "anchors": entries,
+    }))
+}
+
+/// Render the skill with its inheritance, includes, and overrides resolved
+/// (or only its inherited base), with the origin of each section and block.
+fn show_effective(ctx: &AppContext, skill: &SkillRecord, base: bool) -> Result<()> {
+    let mut spec = parse_markdown(&skill.body)?;
+    spec.metadata.id.clone_from(&skill.id);
+    let repository = DbSkillRepository::new(&ctx.db);
+    let effective = if base {
+        inherited_base(&spec, &repository)?
+    } else {
+        let layer =
+            parse_layer(&normalize_layer(&skill.source_layer)).unwrap_or(SkillLayer::Project);
+        effective_skill(&spec, layer, &repository, &ctx.project_overrides()?)?
+    };
+
+    if ctx.output_format.is_machine_readable() {
+        let sections: Vec<serde_json::Value> = effective
+            .spec
+            .sections
+            .iter()
+            .map(|section| {
+                let blocks: Vec<serde_json::Value> = section
+                    .blocks
+                    .iter()
+                    .map(|block| {
+                        serde_json::json!({
+                            "id": block.id,
+                            "type": block.block_type,
+                            "content": block.content,
+                            "origin": effective.block_origin(&section.id, &block.id),
+                        })
+                    })
+                    .collect();
+                serde_json::json!({
+                    "id": section.id,
+                    "title": section.title,
+                    "origin": effective.section_origin(&section.id),
+                    "blocks": blocks,
+                })
+            })
+            .collect();
+        return emit_json(&serde_json::json!({
+            "status": "ok",
+            "skill_id": skill.id,
+            "view": if base { "base" } else { "effective" },
+            "inheritance_chain": effective.inheritance_chain,
+            "included_from": effective.included_from,
+            "overlays": effective.overlays,
+            "warnings": effective.warnings,
+            "sections": sections,
+        }));
+    }
+
+    if ctx.output_format != OutputFormat::Human || !should_use_rich_for_show() {
+        print!("{}", effective.to_markdown());
+        return Ok(());
+    }
+
+    let origin = |origin: &Origin| format!("({origin})").dimmed();
+    println!("# {}", effective.spec.metadata.name);
+    if !effective.inheritance_chain.is_empty() {
+        println!(
+            "{}",
+            format!("extends: {}", effective.inheritance_chain.join(" -> ")).dimmed()
+        );
+    }
+    for section in &effective.spec.sections {
+        println!(
+            "\n## {}  {}",
+            section.title.bold(),
+            origin(effective.section_origin(&section.id))
+        );
+        for block in &section.blocks {
+            let mut lines = block.content.lines();
+            if let Some(first) = lines.next() {
+                println!(
+                    "\n{first}  {}",
+                    origin(effective.block_origin(&section.id, &block.id))
+                );
+            }
+            for line in lines {
+                println!("{line}");
+            }
+        }
+    }
+    for warning in &effective.warnings {
+        println!("\n{} {warning}", "WARNING".yellow());
+    }
+    Ok(())
+}
+
+/// List the archive commits that changed a skill, newest first.
+fn show_history(ctx: &AppContext, skill_id: &str) -> Result<()> {
+    let history = ctx.git.skill_history(skill_id)?;
+
+    if ctx.output_format == OutputFormat::Human {
+        if history.is_empty() {
+            println!("No archive history for {skill_id}");
+        }
+        for entry in &history {
+            println!(
+                "{}  {}  {}",
+                entry.committed_at.format("%Y-%m-%d %H:%M"),
+                &entry.oid[..entry.oid.len().min(8)],
+                entry.summary
+            );
+            let Some(provenance) = &entry.provenance else {
+                println!("    unknown provenance");
+                continue;
+            };
+            let mut details = vec![format!("source: {}", provenance.source)];
+            if !provenance.sessions.is_empty() {
+                details.push(format!("sessions: {}", provenance.sessions.join(", ")));
+            }
+            if let Some(bundle) = &provenance.bundle {
+                details.push(format!("bundle: {bundle}"));
+            }
+            details.push(format!("ms {}", provenance.ms_version));
+            details.push(format!("by {}", provenance.actor));
+            println!("    {}", details.join("  "));
+        }
+        return Ok(());
+    }
+
+    emit_json(&serde_json::json!({
+        "status": "ok",
+        "skill_id": skill_id,
+        "history": history,
+    }))
+}
+
+/// Summarize the changes between archived versions of a skill, newest
+/// release first.
+fn show_changelog(ctx: &AppContext, skill_id: &str, since: Option<&str>) -> Result<()> {
+    let mut entries = build_changelog(&ctx.git.skill_versions(skill_id)?);
+    if let Some(since) = since {
+        entries = ChangelogSince::parse(since, Utc::now())?.filter(entries)?;
+    }
+    let releases = group_by_version(entries);
+
+    if ctx.output_format != OutputFormat::Human {
+        return emit_json(&serde_json::json!({
+            "status": "ok",
+            "skill_id": skill_id,
+            "changelog": releases,
+        }));
+    }
+
+    if releases.is_empty() {
+        println!("No changes for {skill_id}");
+    }
+    for release in &releases {
+        println!(
+            "{} ({})",
+            release.version.as_deref().unwrap_or("unversioned"),
+            release.date.format("%Y-%m-%d")
+        );
+        for note in release.notes() {
+            println!("    {note}");
+        }
+        println!();
+    }
+    Ok(())
+}
+
+/// List the resource files recorded for a skill, by path.
+fn show_resources(ctx: &AppContext, skill_id: &str) -> Result<()> {
+    let resources = ctx.db.get_skill_resources(skill_id)?;
+    let total: i64 = resources.iter().map(|resource| resource.size).sum();
+
+    if ctx.output_format != OutputFormat::Human {
+        return emit_json(&serde_json::json!({
+            "status": "ok",
+            "skill_id": skill_id,
+            "resources": resources,
+            "total_bytes": total,
+        }));
+    }
+
+    if resources.is_empty() {
+        println!("No resources for {skill_id}");
+        return Ok(());
+    }
+    let width = resources
+        .iter()
+        .map(|resource| resource.path.len())
+        .max()
+        .unwrap_or(0);
+    for resource in &resources {
+        println!(
+            "{:<width$}  {:>10}",
+            resource.path,
+            format_size(u64::try_from(resource.size).unwrap_or(0))
+        );
+    }
+    println!(
+        "{} file(s), {}",
+        resources.len(),
+        format_size(u64::try_from(total).unwrap_or(0))
+    );
+    Ok(())
+}
+
 /// Show dependency information from metadata.
 fn show_deps(skill: &SkillRecord) {
     println!();
@@ -283,7 +910,15 @@ fn show_deps(skill: &SkillRecord) {
     }
 }
 
-fn show_json(skill: &SkillRecord, args: &ShowArgs, pretty: bool) -> Result<()> {
+fn show_json(
+    skill: &SkillRecord,
+    args: &ShowArgs,
+    pretty: bool,
+    today: NaiveDate,
+    quality: Option<&QualityReport>,
+    overrides: Option<&OverrideInfo>,
+    derived: &DerivedTags,
+) -> Result<()> {
     let mut output = serde_json::json!({
         "status": "ok",
         "skill": {
@@ -305,6 +940,16 @@ fn show_json(skill: &SkillRecord, args: &ShowArgs, pretty: bool) -> Result<()> {
             "deprecation_reason": skill.deprecation_reason,
         }
     });
+    if let Some(window) = WindowInfo::of(skill, today) {
+        output["skill"]["applicability"] = window.to_json();
+    }
+    if let Some(report) = quality {
+        output["skill"]["quality"] = serde_json::to_value(report)?;
+    }
+    if let Some(info) = overrides {
+        output["skill"]["overrides"] = info.to_json();
+    }
+    output["skill"]["derived_tags"] = serde_json::to_value(&derived.0)?;
 
     if args.meta || args.full {
         if let Ok(meta) = serde_json::from_str::<serde_json::Value>(&skill.metadata_json) {
@@ -398,20 +1043,29 @@ fn show_plain(skill: &SkillRecord) -> Result<()> {
     Ok(())
 }
 
-fn show_tsv(skill: &SkillRecord) -> Result<()> {
+fn show_table(skill: &SkillRecord, format: TableFormat) -> Result<()> {
     println!(
-        "{}\t{}\t{}\t{}\t{:.2}\t{}",
-        skill.id,
-        skill.name,
-        skill.source_layer,
-        skill.version.as_deref().unwrap_or("-"),
-        skill.quality_score,
-        skill.is_deprecated
+        "{}",
+        format.row(&[
+            skill.id.clone(),
+            skill.name.clone(),
+            skill.source_layer.clone(),
+            skill.version.clone().unwrap_or_else(|| "-".to_string()),
+            format!("{:.2}", skill.quality_score),
+            skill.is_deprecated.to_string(),
+        ])
     );
     Ok(())
 }
 
-fn show_toon(skill: &SkillRecord, args: &ShowArgs) -> Result<()> {
+fn show_toon(
+    skill: &SkillRecord,
+    args: &ShowArgs,
+    today: NaiveDate,
+    quality: Option<&QualityReport>,
+    overrides: Option<&OverrideInfo>,
+    derived: &DerivedTags,
+) -> Result<()> {
     let mut output = serde_json::json!({
         "status": "ok",
         "skill": {
@@ -433,6 +1087,16 @@ fn show_toon(skill: &SkillRecord, args: &ShowArgs) -> Result<()> {
             "deprecation_reason": skill.deprecation_reason,
         }
     });
+    if let Some(window) = WindowInfo::of(skill, today) {
+        output["skill"]["applicability"] = window.to_json();
+    }
+    if let Some(report) = quality {
+        output["skill"]["quality"] = serde_json::to_value(report)?;
+    }
+    if let Some(info) = overrides {
+        output["skill"]["overrides"] = info.to_json();
+    }
+    output["skill"]["derived_tags"] = serde_json::to_value(&derived.0)?;
 
     if args.meta || args.full {
         if let Ok(meta) = serde_json::from_str::<serde_json::Value>(&skill.metadata_json) {
@@ -821,4 +1485,21 @@ mod tests {
         assert!(deps.is_array());
         assert_eq!(deps.as_array().unwrap().len(), 2);
     }
+
+    // ── 19. test_show_applicability_window ────────────────────────────
+
+    #[test]
+    fn test_show_applicability_window() {
+        let today = NaiveDate::from_ymd_opt(2026, 2, 1).unwrap();
+        let mut skill = make_skill();
+        assert!(WindowInfo::of(&skill, today).is_none());
+
+        skill.metadata_json = r#"{"active_during":{"months":[12]},"expires":"2026-01-31"}"#.into();
+        let window = WindowInfo::of(&skill, today).unwrap();
+        assert_eq!(window.status, WindowStatus::Expired);
+        assert_eq!(
+            window.to_json(),
+            serde_json::json!({"status": "expired", "expires": "2026-01-31"})
+        );
+    }
 }
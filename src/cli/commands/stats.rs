@@ -0,0 +1,683 @@
+//! ms stats - Skill usage statistics
+//!
+//! By default, aggregates loads, suggestion acceptance, and feedback over a
+//! window (`--since 30d`) per skill or per layer, and lists skills that were
+//! never loaded. `--efficacy` instead correlates recorded skill loads with the
+//! quality of the CASS sessions they happened in and stores the per-skill
+//! result. That is correlation, not causation.
+//!
+//! Usage output also lists the reported outcomes of `ms load --trial` loads.
+//! With `-O csv` or `-O tsv` either report is printed as one table row per
+//! skill (or layer).
+
+use std::collections::HashMap;
+
+use clap::{Args, ValueEnum};
+
+use crate::app::AppContext;
+use crate::cass::QualityScorer;
+use crate::cli::commands::cass_client;
+use crate::cli::commands::trial::describe_stats;
+use crate::cli::output::{HumanLayout, OutputFormat, TableFormat, emit_json};
+use crate::error::Result;
+use crate::output::{RichOutput, sparkline, sparkline_plain, usage_stats_table};
+use crate::quality::efficacy::{
+    CORRELATION_CAVEAT, EfficacyReport, LoadEvent, SessionOutcome, SkillEfficacy, correlate,
+    parse_timestamp,
+};
+use crate::quality::trials::TrialStats;
+use crate::quality::usage::{
+    BucketSize, UsageRow, UsageWindow, group_by_layer, parse_since, skill_rows, sort_rows,
+};
+use crate::storage::sqlite::SessionQualityRecord;
+
+#[derive(Args, Debug)]
+pub struct StatsArgs {
+    /// Show statistics for a single skill
+    pub skill: Option<String>,
+
+    /// Window to aggregate usage over: 7d, 4w, or an ISO date
+    #[arg(long, default_value = "30d")]
+    pub since: String,
+
+    /// Group usage by skill or by layer
+    #[arg(long, value_enum, default_value_t = StatsGroupBy::Skill)]
+    pub by: StatsGroupBy,
+
+    /// List never-loaded skills with only negative feedback, for `ms prune`
+    #[arg(long)]
+    pub prune_candidates: bool,
+
+    /// Recompute skill efficacy from recorded loads and CASS session quality
+    #[arg(long, conflicts_with_all = ["since", "by", "prune_candidates"])]
+    pub efficacy: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum StatsGroupBy {
+    Skill,
+    Layer,
+}
+
+impl StatsGroupBy {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Skill => "skill",
+            Self::Layer => "layer",
+        }
+    }
+}
+
+pub fn run(ctx: &AppContext, args: &StatsArgs) -> Result<()> {
+    if args.efficacy {
+        run_efficacy(ctx, args)
+    } else {
+        run_usage(ctx, args)
+    }
+}
+
+fn run_usage(ctx: &AppContext, args: &StatsArgs) -> Result<()> {
+    let now = chrono::Utc::now();
+    let window = UsageWindow::new(parse_since(&args.since, now)?, now);
+    let cutoff = window.since.to_rfc3339();
+
+    let skill_id = args
+        .skill
+        .as_deref()
+        .map(|skill| ctx.resolve_skill_id(skill))
+        .transpose()?;
+    let mut skills = ctx.db.list_skill_keys()?;
+    if let Some(skill_id) = &skill_id {
+        skills.retain(|key| &key.id == skill_id);
+    }
+    let trials = ctx.db.list_trial_stats(skill_id.as_deref())?;
+    let loads = ctx.db.list_skill_loads_since(&cutoff)?;
+    let activity = ctx.db.skill_activity_since(&cutoff)?;
+    let per_skill = skill_rows(&skills, &loads, &activity, &window);
+
+    let never_loaded: Vec<&str> = per_skill
+        .iter()
+        .filter(|row| row.loads == 0)
+        .map(|row| row.key.as_str())
+        .collect();
+    let prune_candidates: Option<Vec<&str>> = args.prune_candidates.then(|| {
+        per_skill
+            .iter()
+            .filter(|row| row.is_prune_candidate())
+            .map(|row| row.key.as_str())
+            .collect()
+    });
+    let mut rows = match args.by {
+        StatsGroupBy::Skill => per_skill.clone(),
+        StatsGroupBy::Layer => group_by_layer(&per_skill),
+    };
+    sort_rows(&mut rows);
+
+    if let Some(format) = TableFormat::for_output(ctx.output_format) {
+        for line in usage_table(&rows, args.by, format) {
+            println!("{line}");
+        }
+        return Ok(());
+    }
+    if ctx.output_format != OutputFormat::Human {
+        let rows = rows
+            .iter()
+            .map(|row| {
+                let mut value = serde_json::to_value(row)?;
+                value["acceptance_rate"] = serde_json::json!(row.acceptance_rate());
+                Ok(value)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let mut payload = serde_json::json!({
+            "status": "ok",
+            "window": window,
+            "group_by": args.by.as_str(),
+            "rows": rows,
+            "never_loaded": never_loaded,
+        });
+        if let Some(candidates) = &prune_candidates {
+            payload["prune_candidates"] = serde_json::json!(candidates);
+        }
+        if !trials.is_empty() {
+            let trials = trials
+                .iter()
+                .map(|stats| {
+                    let mut value = serde_json::to_value(stats)?;
+                    value["success_rate"] = serde_json::json!(stats.success_rate());
+                    Ok(value)
+                })
+                .collect::<Result<Vec<_>>>()?;
+            payload["trials"] = serde_json::json!(trials);
+        }
+        return emit_json(&payload);
+    }
+
+    let output = RichOutput::new(&ctx.config, &ctx.output_format, ctx.robot_mode);
+    render_usage(
+        &output,
+        &window,
+        args.by,
+        &rows,
+        &never_loaded,
+        prune_candidates.as_deref(),
+    );
+    render_trials(&output, &trials);
+    Ok(())
+}
+
+fn render_usage(
+    output: &RichOutput,
+    window: &UsageWindow,
+    by: StatsGroupBy,
+    rows: &[UsageRow],
+    never_loaded: &[&str],
+    prune_candidates: Option<&[&str]>,
+) {
+    output.header("Skill Usage");
+    let since = window.since.format("%Y-%m-%d").to_string();
+    let bucket = match window.bucket {
+        BucketSize::Day => "per day",
+        BucketSize::Week => "per week",
+    };
+    output.key_value_list(&[("Since", since.as_str()), ("Trend", bucket)]);
+
+    if rows.is_empty() {
+        output.println("No skills indexed. Run `ms index` first.");
+        return;
+    }
+
+    let cells: Vec<(String, u64, String, String, String, String)> = rows
+        .iter()
+        .map(|row| {
+            let key = match by {
+                StatsGroupBy::Skill => row.key.clone(),
+                StatsGroupBy::Layer => format!("{} ({} skills)", row.key, row.skills),
+            };
+            let trend = if output.is_rich() {
+                sparkline(&row.trend)
+            } else {
+                sparkline_plain(&row.trend)
+            };
+            let acceptance = row.acceptance_rate().map_or_else(
+                || "-".to_string(),
+                |rate| format!("{:.0}% of {}", rate * 100.0, row.suggestions_decided),
+            );
+            let feedback = format!("+{} / -{}", row.positive_feedback, row.negative_feedback);
+            let last_loaded = row.last_loaded.map_or_else(
+                || "never".to_string(),
+                |at| at.format("%Y-%m-%d").to_string(),
+            );
+            (key, row.loads, trend, acceptance, feedback, last_loaded)
+        })
+        .collect();
+    let cells: Vec<(&str, u64, &str, &str, &str, &str)> = cells
+        .iter()
+        .map(|(key, loads, trend, acceptance, feedback, last)| {
+            (
+                key.as_str(),
+                *loads,
+                trend.as_str(),
+                acceptance.as_str(),
+                feedback.as_str(),
+                last.as_str(),
+            )
+        })
+        .collect();
+    let header = match by {
+        StatsGroupBy::Skill => "Skill",
+        StatsGroupBy::Layer => "Layer",
+    };
+    output.print_table(&usage_stats_table(header, &cells));
+
+    if !never_loaded.is_empty() {
+        output.newline();
+        output.println(&format!(
+            "Never loaded since {since} ({}):",
+            never_loaded.len()
+        ));
+        output.list(never_loaded);
+    }
+    if let Some(candidates) = prune_candidates {
+        output.newline();
+        if candidates.is_empty() {
+            output.println("No prune candidates.");
+        } else {
+            output.println(&format!(
+                "Prune candidates ({}): never loaded, negative feedback only",
+                candidates.len()
+            ));
+            output.list(candidates);
+            output.hint("Review them with `ms prune analyze`.");
+        }
+    }
+}
+
+/// Trial outcomes are all-time, not limited to `--since`.
+fn render_trials(output: &RichOutput, trials: &[TrialStats]) {
+    if trials.is_empty() {
+        return;
+    }
+    output.newline();
+    output.println("Trials (all time):");
+    let lines: Vec<String> = trials
+        .iter()
+        .map(|stats| {
+            let rate = stats
+                .success_rate()
+                .map_or_else(|| "-".to_string(), |rate| format!("{:.0}%", rate * 100.0));
+            format!(
+                "{}: {} success rate, {}, {} expired",
+                stats.skill_id,
+                rate,
+                describe_stats(stats),
+                stats.expired
+            )
+        })
+        .collect();
+    let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+    output.list(&lines);
+}
+
+fn run_efficacy(ctx: &AppContext, args: &StatsArgs) -> Result<()> {
+    let skill_id = args
+        .skill
+        .as_deref()
+        .map(|skill| ctx.resolve_skill_id(skill))
+        .transpose()?;
+
+    let report = compute_efficacy(ctx)?;
+    ctx.db.replace_skill_efficacy(&report.skills)?;
+    let records = ctx.db.list_skill_efficacy(skill_id.as_deref())?;
+    let min_samples = ctx.config.efficacy.min_samples;
+
+    if let Some(format) = TableFormat::for_output(ctx.output_format) {
+        for line in efficacy_table(&records, min_samples, format) {
+            println!("{line}");
+        }
+        return Ok(());
+    }
+    if ctx.output_format != OutputFormat::Human {
+        let skills: Vec<serde_json::Value> = records
+            .iter()
+            .map(|record| {
+                serde_json::json!({
+                    "efficacy": record,
+                    "quality_delta": record.quality_delta(),
+                    "sufficient_samples": record.has_samples(min_samples),
+                })
+            })
+            .collect();
+        let payload = serde_json::json!({
+            "status": "ok",
+            "recomputed": true,
+            "matched_loads": report.matched_loads,
+            "unmatched_loads": report.unmatched_loads,
+            "sessions_considered": report.sessions_considered,
+            "min_samples": min_samples,
+            "skills": skills,
+            "caveat": CORRELATION_CAVEAT,
+        });
+        return emit_json(&payload);
+    }
+
+    crate::cli::output::emit_human(render_human(&records, Some(&report), min_samples));
+    Ok(())
+}
+
+/// Usage rows as CSV/TSV lines, header first.
+fn usage_table(rows: &[UsageRow], by: StatsGroupBy, format: TableFormat) -> Vec<String> {
+    let header = [
+        by.as_str(),
+        "layer",
+        "skills",
+        "loads",
+        "last_loaded",
+        "suggestions_shown",
+        "suggestions_selected",
+        "acceptance_rate",
+        "positive_feedback",
+        "negative_feedback",
+    ];
+    let mut lines = vec![format.row(&header)];
+    lines.extend(rows.iter().map(|row| {
+        format.row(&[
+            row.key.clone(),
+            row.layer.clone(),
+            row.skills.to_string(),
+            row.loads.to_string(),
+            row.last_loaded
+                .map(|at| at.to_rfc3339())
+                .unwrap_or_default(),
+            row.suggestions_shown.to_string(),
+            row.suggestions_selected.to_string(),
+            optional_cell(row.acceptance_rate()),
+            row.positive_feedback.to_string(),
+            row.negative_feedback.to_string(),
+        ])
+    }));
+    lines
+}
+
+/// Efficacy records as CSV/TSV lines, header first.
+fn efficacy_table(records: &[SkillEfficacy], min_samples: u64, format: TableFormat) -> Vec<String> {
+    let header = [
+        "skill_id",
+        "sessions_with",
+        "mean_quality_with",
+        "failure_rate_with",
+        "sessions_without",
+        "mean_quality_without",
+        "failure_rate_without",
+        "quality_delta",
+        "sufficient_samples",
+    ];
+    let mut lines = vec![format.row(&header)];
+    lines.extend(records.iter().map(|record| {
+        format.row(&[
+            record.skill_id.clone(),
+            record.sessions_with.to_string(),
+            record.mean_quality_with.to_string(),
+            record.failure_rate_with.to_string(),
+            record.sessions_without.to_string(),
+            optional_cell(record.mean_quality_without),
+            optional_cell(record.failure_rate_without),
+            optional_cell(record.quality_delta()),
+            record.has_samples(min_samples).to_string(),
+        ])
+    }));
+    lines
+}
+
+/// Missing values are empty cells.
+fn optional_cell<T: ToString>(value: Option<T>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+/// Gather loads and session outcomes, then correlate them.
+fn compute_efficacy(ctx: &AppContext) -> Result<EfficacyReport> {
+    let loads: Vec<LoadEvent> = ctx
+        .db
+        .list_skill_loads()?
+        .into_iter()
+        .map(|(skill_id, session_id, project, used_at)| LoadEvent {
+            skill_id,
+            session_id,
+            project,
+            used_at: parse_timestamp(&used_at),
+        })
+        .collect();
+
+    let mut sessions: HashMap<String, SessionOutcome> = ctx
+        .db
+        .list_session_quality()?
+        .into_iter()
+        .map(|record| {
+            (
+                record.session_id.clone(),
+                outcome_from_record(&record, None, None),
+            )
+        })
+        .collect();
+    collect_cass_sessions(ctx, &mut sessions);
+
+    let sessions: Vec<SessionOutcome> = sessions.into_values().collect();
+    let window = chrono::Duration::minutes(ctx.config.efficacy.match_window_minutes as i64);
+    Ok(correlate(&loads, &sessions, window, chrono::Utc::now()))
+}
+
+/// Add recent CASS sessions, scoring (and caching) any not yet scored.
+///
+/// CASS is optional: when it is missing or fails, only cached session
+/// quality is used.
+fn collect_cass_sessions(ctx: &AppContext, sessions: &mut HashMap<String, SessionOutcome>) {
+    let cass = cass_client(ctx, None, false);
+    if let Some(reason) = cass.probe().unavailable_reason() {
+        tracing::debug!(%reason, "cass unavailable; using cached session quality");
+        return;
+    }
+    let matches = match cass.search("*", ctx.config.efficacy.session_limit) {
+        Ok(matches) => matches,
+        Err(err) => {
+            tracing::debug!(error = %err, "cass search failed; using cached session quality");
+            return;
+        }
+    };
+
+    let scorer = QualityScorer::with_defaults();
+    for hit in matches {
+        let started_at = hit.timestamp.as_deref().and_then(parse_timestamp);
+        let cached = ctx.db.get_session_quality(&hit.session_id).ok().flatten();
+        let record = match cached {
+            Some(record) => record,
+            None => match cass.get_session(&hit.path) {
+                Ok(session) => {
+                    let quality = scorer.score(&session);
+                    let record = SessionQualityRecord {
+                        session_id: hit.session_id.clone(),
+                        content_hash: session.content_hash.clone(),
+                        score: quality.score,
+                        signals: quality.signals,
+                        missing: quality
+                            .missing
+                            .iter()
+                            .map(|missing| format!("{missing:?}"))
+                            .collect(),
+                        computed_at: quality.computed_at.to_rfc3339(),
+                    };
+                    if let Err(err) = ctx.db.upsert_session_quality(&record) {
+                        tracing::debug!(error = %err, "failed to cache session quality");
+                    }
+                    record
+                }
+                Err(err) => {
+                    tracing::debug!(session = %hit.path, error = %err, "skipping session");
+                    continue;
+                }
+            },
+        };
+        sessions.insert(
+            record.session_id.clone(),
+            outcome_from_record(&record, hit.project.clone(), started_at),
+        );
+    }
+}
+
+fn outcome_from_record(
+    record: &SessionQualityRecord,
+    project: Option<String>,
+    started_at: Option<chrono::DateTime<chrono::Utc>>,
+) -> SessionOutcome {
+    SessionOutcome {
+        session_id: record.session_id.clone(),
+        project,
+        started_at,
+        quality: record.score,
+        failed: SessionOutcome::is_failure(&record.signals),
+    }
+}
+
+fn render_human(
+    records: &[SkillEfficacy],
+    report: Option<&EfficacyReport>,
+    min_samples: u64,
+) -> HumanLayout {
+    let mut layout = HumanLayout::new();
+    layout.title("Skill Efficacy");
+
+    if let Some(report) = report {
+        layout
+            .kv("Sessions", &report.sessions_considered.to_string())
+            .kv("Matched loads", &report.matched_loads.to_string())
+            .kv("Unmatched loads", &report.unmatched_loads.to_string())
+            .blank();
+    }
+
+    if records.is_empty() {
+        layout.push_line("No efficacy data. Run `ms stats --efficacy` to compute it.");
+    }
+    for record in records {
+        layout.section(&record.skill_id).kv(
+            "With",
+            &format!(
+                "{} sessions, quality {:.2}, failure rate {:.0}%",
+                record.sessions_with,
+                record.mean_quality_with,
+                record.failure_rate_with * 100.0
+            ),
+        );
+        match (record.mean_quality_without, record.failure_rate_without) {
+            (Some(quality), Some(failures)) => {
+                layout.kv(
+                    "Without",
+                    &format!(
+                        "{} sessions, quality {:.2}, failure rate {:.0}%",
+                        record.sessions_without,
+                        quality,
+                        failures * 100.0
+                    ),
+                );
+            }
+            _ => {
+                layout.kv("Without", "no comparable sessions");
+            }
+        }
+        if !record.has_samples(min_samples) {
+            layout.kv(
+                "Delta",
+                &format!("insufficient samples (need {min_samples} each side)"),
+            );
+        } else if let Some(delta) = record.quality_delta() {
+            layout.kv("Delta", &format!("{delta:+.2}"));
+        }
+        layout.blank();
+    }
+
+    layout.push_line(format!("Note: {CORRELATION_CAVEAT}."));
+    layout
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(sessions: u64) -> SkillEfficacy {
+        SkillEfficacy {
+            skill_id: "rust-errors".to_string(),
+            sessions_with: sessions,
+            mean_quality_with: 0.8,
+            failure_rate_with: 0.1,
+            sessions_without: sessions,
+            mean_quality_without: Some(0.6),
+            failure_rate_without: Some(0.3),
+            computed_at: "2026-03-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn render_marks_insufficient_samples_and_caveat() {
+        let text = render_human(&[record(1)], None, 3).build();
+        assert!(text.contains("insufficient samples"));
+        assert!(text.contains(CORRELATION_CAVEAT));
+
+        let text = render_human(&[record(5)], None, 3).build();
+        assert!(text.contains("+0.20"));
+    }
+
+    #[test]
+    fn efficacy_table_leaves_missing_baselines_empty() {
+        let mut without_baseline = record(1);
+        without_baseline.skill_id = "no, baseline".to_string();
+        without_baseline.mean_quality_without = None;
+        without_baseline.failure_rate_without = None;
+
+        let lines = efficacy_table(&[record(5), without_baseline], 3, TableFormat::Csv);
+        assert_eq!(
+            lines[0],
+            "skill_id,sessions_with,mean_quality_with,failure_rate_with,sessions_without,\
+             mean_quality_without,failure_rate_without,quality_delta,sufficient_samples"
+        );
+        assert!(lines[1].starts_with("rust-errors,5,0.8,0.1,5,0.6,0.3,"));
+        assert!(lines[1].ends_with(",true"));
+        assert_eq!(lines[2], "\"no, baseline\",1,0.8,0.1,1,,,,false");
+    }
+
+    #[test]
+    fn usage_table_names_the_grouping_column() {
+        let row = UsageRow {
+            key: "project".to_string(),
+            layer: "project".to_string(),
+            skills: 2,
+            loads: 7,
+            last_loaded: None,
+            suggestions_shown: 4,
+            suggestions_selected: 1,
+            suggestions_decided: 2,
+            positive_feedback: 1,
+            negative_feedback: 0,
+            trend: vec![3, 4],
+        };
+        let lines = usage_table(&[row], StatsGroupBy::Layer, TableFormat::Tsv);
+        assert!(lines[0].starts_with("layer\tlayer\tskills\tloads\t"));
+        assert_eq!(lines[1], "project\tproject\t2\t7\t\t4\t1\t0.5\t1\t0");
+    }
+
+    /// Write a fake cass that reports healthy, finds one session, and exports it.
+    #[cfg(unix)]
+    fn fake_cass(dir: &std::path::Path) -> String {
+        use std::os::unix::fs::PermissionsExt;
+
+        let bin = dir.join("cass");
+        let script = "#!/bin/sh\ncase \"$1\" in\n\
+             health) echo '{\"healthy\":true,\"version\":\"0.6.2\",\"database_ok\":true,\"index_ok\":true,\"session_count\":1,\"last_indexed\":null}' ;;\n\
+             search) echo '{\"hits\":[{\"source_path\":\"/sessions/s1.jsonl\",\"workspace\":\"/work/app\"}]}' ;;\n\
+             export) echo '[{\"role\":\"user\",\"content\":\"fix the failing test\"},{\"role\":\"assistant\",\"content\":\"[Tool: Bash - cargo test] all tests pass\"}]' ;;\n\
+             esac\n";
+        std::fs::write(&bin, script).unwrap();
+        std::fs::set_permissions(&bin, std::fs::Permissions::from_mode(0o755)).unwrap();
+        bin.display().to_string()
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn cass_sessions_are_scored_and_cached() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut ctx = AppContext::for_tests(&dir.path().join("ms"));
+        ctx.config.cass.cass_path = Some(fake_cass(dir.path()));
+
+        let mut sessions = HashMap::new();
+        collect_cass_sessions(&ctx, &mut sessions);
+
+        let outcome = sessions.get("s1").expect("session from the cass search");
+        assert_eq!(outcome.project.as_deref(), Some("/work/app"));
+        let cached = ctx.db.get_session_quality("s1").unwrap().unwrap();
+        assert!((outcome.quality - cached.score).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn missing_cass_leaves_cached_sessions_alone() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut ctx = AppContext::for_tests(&dir.path().join("ms"));
+        ctx.config.cass.cass_path = Some(dir.path().join("no-such-cass").display().to_string());
+
+        let mut sessions = HashMap::from([(
+            "cached".to_string(),
+            SessionOutcome {
+                session_id: "cached".to_string(),
+                project: None,
+                started_at: None,
+                quality: 0.7,
+                failed: false,
+            },
+        )]);
+        collect_cass_sessions(&ctx, &mut sessions);
+        assert_eq!(sessions.len(), 1);
+        assert!(sessions.contains_key("cached"));
+    }
+
+    #[test]
+    fn render_empty_points_at_recompute() {
+        let text = render_human(&[], None, 3).build();
+        assert!(text.contains("ms stats --efficacy"));
+    }
+}
@@ -5,20 +5,31 @@ use clap::Args;
 use tracing::debug;
 
 use crate::app::AppContext;
-use crate::cli::formatters::{
-    ScorePercentageBreakdown, SuggestionContext, SuggestionItem, SuggestionOutput,
-};
+use crate::cli::commands::bandit::default_bandit_path;
+use crate::cli::commands::search::{bm25_ranked, semantic_ranked};
+use crate::cli::formatters::{SuggestionContext, SuggestionItem, SuggestionOutput};
 use crate::cli::output::Formattable;
+use crate::cli::output::emit_json;
+use crate::config::ApplicabilityConfig;
 use crate::context::collector::{CollectedContext, ContextCollector, ContextCollectorConfig};
 use crate::context::{ContextCapture, ContextFingerprint};
+use crate::core::applicability::{self, SkillWindow, SystemClock, WindowStatus};
 use crate::error::Result;
+use crate::search::{RrfConfig, fuse_with_limit};
 use crate::storage::sqlite::SkillRecord;
-use crate::suggestions::SuggestionCooldownCache;
-use crate::suggestions::bandit::contextual::ContextualBandit;
+use crate::suggestions::bandit::contextual::{
+    CONTEXTUAL_WEIGHT, ContextualBandit, RecommendationComponents, THOMPSON_WEIGHT,
+};
 use crate::suggestions::bandit::features::{
     DefaultFeatureExtractor, FEATURE_DIM, FeatureExtractor, UserHistory,
 };
+use crate::suggestions::bandit::{SignalBandit, SignalType, SuggestionContext as BanditContext};
+use crate::suggestions::explanation::{
+    BanditArmSnapshot, ContextSummary, PreferenceSnapshot, SignalScores, SuggestionExplanation,
+    SuggestionOrigin,
+};
 use crate::suggestions::tracking::SuggestionTracker;
+use crate::suggestions::{ScoreBreakdown, ScoreSource, SuggestionCooldownCache};
 
 #[derive(Args, Debug)]
 pub struct SuggestArgs {
@@ -54,6 +65,11 @@ pub struct SuggestArgs {
     #[arg(long)]
     pub cwd: Option<String>,
 
+    /// What you are about to do; skills relevant to it are ranked alongside
+    /// the project context, and cooldowns don't apply
+    #[arg(long, value_name = "TEXT")]
+    pub task: Option<String>,
+
     /// Budget for packed output
     #[arg(long)]
     pub budget: Option<usize>,
@@ -66,7 +82,16 @@ pub struct SuggestArgs {
     #[arg(long)]
     pub reset_cooldowns: bool,
 
-    /// Disable bandit-based weighting
+    /// List active cooldowns with their remaining time, then exit
+    #[arg(long, conflicts_with = "clear_cooldowns")]
+    pub cooldowns: bool,
+
+    /// Clear cooldowns (for SKILL, or all), then exit
+    #[arg(long, value_name = "SKILL")]
+    pub clear_cooldowns: Option<Option<String>>,
+
+    /// Disable bandit-based weighting (fuse search signals with the static
+    /// `[search.rrf]` weights)
     #[arg(long)]
     pub no_bandit: bool,
 
@@ -77,6 +102,52 @@ pub struct SuggestArgs {
     /// Reset bandit state before suggesting
     #[arg(long)]
     pub reset_bandit: bool,
+
+    /// Agent session id recorded with the suggestions (see `ms why-suggested`)
+    #[arg(long, env = "MS_SESSION_ID")]
+    pub session_id: Option<String>,
+
+    /// Agent asking, when several share this machine: its own cooldowns apply
+    /// in full, other agents' for `[suggestions] cross_agent_cooldown_secs`
+    #[arg(long, env = "MS_AGENT_ID")]
+    pub agent_id: Option<String>,
+}
+
+impl SuggestArgs {
+    /// Plain suggestion arguments for `cwd`, as the MCP `suggest` tool uses.
+    #[must_use]
+    pub fn for_cwd(cwd: &std::path::Path, limit: usize, explain: bool) -> Self {
+        Self {
+            limit,
+            discover: false,
+            personal: false,
+            explain,
+            domain: None,
+            load: false,
+            top: 3,
+            cwd: Some(cwd.display().to_string()),
+            task: None,
+            budget: None,
+            ignore_cooldowns: false,
+            reset_cooldowns: false,
+            cooldowns: false,
+            clear_cooldowns: None,
+            no_bandit: false,
+            bandit_exploration: None,
+            reset_bandit: false,
+            session_id: None,
+            agent_id: None,
+        }
+    }
+
+    /// The `--task` description, unless blank.
+    #[must_use]
+    pub fn task_text(&self) -> Option<&str> {
+        self.task
+            .as_deref()
+            .map(str::trim)
+            .filter(|task| !task.is_empty())
+    }
 }
 
 /// A suggestion with score and metadata.
@@ -85,57 +156,97 @@ pub struct Suggestion {
     pub skill_id: String,
     pub name: String,
     pub description: String,
+    /// Final score; always the sum of the breakdown's contributions.
     pub score: f32,
     pub breakdown: ScoreBreakdown,
+    /// Bandit arm statistics at suggestion time.
+    pub pull_count: u64,
+    pub avg_reward: f64,
     pub is_discovery: bool,
     pub is_favorite: bool,
     pub tags: Vec<String>,
+    /// Applicability window status at suggestion time.
+    pub window: WindowStatus,
 }
 
-/// Score breakdown for explanation mode.
-#[derive(Debug, Clone, Default)]
-pub struct ScoreBreakdown {
-    pub contextual_score: f32,
-    pub thompson_score: f32,
-    pub exploration_bonus: f32,
-    pub personal_boost: f32,
-    pub pull_count: u64,
-    pub avg_reward: f64,
+impl Suggestion {
+    /// Add a weighted signal to the breakdown and refresh the score.
+    fn add_signal(&mut self, source: ScoreSource, value: f32, weight: f32, detail: Option<String>) {
+        self.breakdown.add(source, value, weight, detail);
+        self.score = self.breakdown.total();
+    }
+
+    /// Whether the `--task` description or the project context contributed
+    /// more to the score.
+    #[must_use]
+    pub fn origin(&self) -> SuggestionOrigin {
+        SuggestionOrigin::from_contributions(
+            self.breakdown.contribution(&[ScoreSource::Task]),
+            self.breakdown.contribution(&CONTEXT_SOURCES),
+        )
+    }
+}
+
+/// Suggestions from one pass of the pipeline, with the context they were
+/// ranked in.
+pub struct SuggestRun {
+    pub suggestions: Vec<Suggestion>,
+    pub discovery: Vec<Suggestion>,
+    pub fingerprint: ContextFingerprint,
+    pub context: CollectedContext,
+    /// Suggestions held back because they are cooling down in this context.
+    pub cooled_down: usize,
 }
 
 pub fn run(ctx: &AppContext, args: &SuggestArgs) -> Result<()> {
     debug!(target: "suggest", mode = ?ctx.output_format, "output mode selected");
 
+    if args.cooldowns {
+        return list_cooldowns(ctx);
+    }
+    if let Some(skill) = &args.clear_cooldowns {
+        return clear_cooldowns(ctx, skill.as_deref());
+    }
+
+    let outcome = suggest(ctx, args)?;
+    debug!(target: "suggest", count = outcome.suggestions.len(), "generating suggestions");
+    output_suggestions(ctx, args, &outcome)
+}
+
+/// Rank suggestions for the working context and record them (cooldowns,
+/// tracking, explanations). Shared by `ms suggest` and the MCP tool.
+pub fn suggest(ctx: &AppContext, args: &SuggestArgs) -> Result<SuggestRun> {
     // 1. Capture working context
     let cwd_path: Option<PathBuf> = args.cwd.as_ref().map(PathBuf::from);
     let capture = ContextCapture::capture_current(cwd_path.clone())?;
     let fingerprint = ContextFingerprint::capture(&capture);
 
-    // 2. Load cooldown cache
+    // 2. Load cooldowns (shared through the database; the legacy JSON file
+    // is only read until the first write)
     let cache_path = cooldown_path();
-    let mut cache = if args.reset_cooldowns {
-        SuggestionCooldownCache::new()
-    } else {
-        SuggestionCooldownCache::load(&cache_path).unwrap_or_else(|e| {
+    if args.reset_cooldowns {
+        ctx.db.delete_suggestion_cooldowns(None)?;
+        remove_legacy_cooldowns(&cache_path);
+    }
+    let agent_id = args.agent_id.as_deref();
+    let mut cache = SuggestionCooldownCache::load_from_db(&ctx.db, Some(&cache_path))
+        .unwrap_or_else(|e| {
             if !ctx.output_format.is_machine_readable() {
-                eprintln!("Warning: Failed to load cooldown cache: {e}. Starting fresh.");
+                eprintln!("Warning: Failed to load cooldowns: {e}. Starting fresh.");
             }
             SuggestionCooldownCache::new()
         })
-    };
-
-    if args.reset_cooldowns {
-        cache.save(&cache_path)?;
-    }
+        .for_agent(
+            args.agent_id.clone(),
+            ctx.config.suggestions.cross_agent_cooldown_secs,
+        );
 
-    // 3. Load contextual bandit
-    let contextual_bandit_path = contextual_bandit_path();
+    // 3. Load the agent's contextual bandit (persisted in the database; the
+    // legacy JSON file is only read until the first write)
     let mut contextual_bandit = if args.reset_bandit {
-        let bandit = ContextualBandit::with_feature_dim(FEATURE_DIM);
-        bandit.save(&contextual_bandit_path)?;
-        bandit
+        ctx.reset_bandit(agent_id, FEATURE_DIM)?
     } else {
-        ContextualBandit::load(&contextual_bandit_path).unwrap_or_else(|e| {
+        ctx.bandit(agent_id).unwrap_or_else(|e| {
             if !ctx.output_format.is_machine_readable() {
                 eprintln!("Warning: Failed to load bandit state: {e}. Starting fresh.");
             }
@@ -158,50 +269,77 @@ pub fn run(ctx: &AppContext, args: &SuggestArgs) -> Result<()> {
     // 6. Get all skills from database
     let all_skills = ctx.db.list_skills(1000, 0)?;
     if all_skills.is_empty() {
-        return output_empty_suggestions(ctx, args, &fingerprint, &cache);
+        return Ok(SuggestRun {
+            suggestions: Vec::new(),
+            discovery: Vec::new(),
+            fingerprint,
+            context: collected_context,
+            cooled_down: 0,
+        });
     }
 
     // Register all skills with the bandit
     let skill_ids: Vec<String> = all_skills.iter().map(|s| s.id.clone()).collect();
     contextual_bandit.register_skills(&skill_ids);
 
-    // 7. Get recommendations from bandit
+    // 7. Get recommendations from bandit. A task can make any skill
+    // relevant, so with one every skill is ranked.
+    let task = args.task_text();
     let fetch_limit = args.limit * 2; // Fetch extra for filtering
-    let recommendations = contextual_bandit.recommend(&context_features, fetch_limit);
+    let recommend_limit = if task.is_some() {
+        skill_ids.len()
+    } else {
+        fetch_limit
+    };
+    let recommendations = contextual_bandit.recommend(&context_features, recommend_limit);
 
     // 8. Build suggestions with metadata
     let skill_map: HashMap<String, &SkillRecord> =
         all_skills.iter().map(|s| (s.id.clone(), s)).collect();
+    let projects = detected_projects(&collected_context);
     let mut suggestions: Vec<Suggestion> = recommendations
         .iter()
         .filter_map(|rec| {
             let skill = skill_map.get(&rec.skill_id)?;
-            let tags = parse_tags_from_metadata(&skill.metadata_json);
             let is_favorite = ctx
                 .db
                 .has_user_preference(&rec.skill_id, "favorite")
                 .unwrap_or(false);
-
-            Some(Suggestion {
-                skill_id: rec.skill_id.clone(),
-                name: skill.name.clone(),
-                description: skill.description.clone(),
-                score: rec.score,
-                breakdown: ScoreBreakdown {
-                    contextual_score: rec.components.contextual_score,
-                    thompson_score: rec.components.thompson_score,
-                    exploration_bonus: rec.components.exploration_bonus,
-                    personal_boost: 0.0,
-                    pull_count: rec.components.pull_count,
-                    avg_reward: rec.components.avg_reward,
-                },
-                is_discovery: rec.components.pull_count < 5,
-                is_favorite,
-                tags,
-            })
+            let mut suggestion = bandit_suggestion(skill, &rec.components, projects.as_deref());
+            suggestion.is_discovery = rec.components.pull_count < 5;
+            suggestion.is_favorite = is_favorite;
+            Some(suggestion)
         })
         .collect();
 
+    // 8b. Blend in hybrid search relevance to the detected stack, fused with
+    // the signal bandit's learned weights unless --no-bandit
+    let rrf = suggestion_rrf_config(ctx, args, &collected_context);
+    let relevance = context_relevance(ctx, &collected_context, &rrf, fetch_limit * 10);
+    for suggestion in &mut suggestions {
+        if let Some(relevance) = relevance.get(&suggestion.skill_id) {
+            apply_relevance(suggestion, relevance);
+        }
+    }
+
+    // 8c. Blend in relevance to the --task description, fused the same way.
+    // BM25 gets the task's first terms; the embedding gets all of it.
+    if let Some(task) = task {
+        let relevance = search_relevance(
+            ctx,
+            &task_bm25_query(task),
+            task,
+            "the task",
+            &rrf,
+            fetch_limit * 10,
+        );
+        for suggestion in &mut suggestions {
+            if let Some(relevance) = relevance.get(&suggestion.skill_id) {
+                apply_task_relevance(suggestion, relevance);
+            }
+        }
+    }
+
     // 9. Filter out hidden skills and boost favorites
     suggestions.retain(|s| {
         !ctx.db
@@ -212,9 +350,7 @@ pub fn run(ctx: &AppContext, args: &SuggestArgs) -> Result<()> {
     // Apply favorites boost (always, not just in personal mode)
     for suggestion in &mut suggestions {
         if suggestion.is_favorite {
-            let favorites_boost = 0.25; // Significant boost for favorites
-            suggestion.breakdown.personal_boost += favorites_boost;
-            suggestion.score = (suggestion.score + favorites_boost).clamp(0.0, 1.0);
+            suggestion.add_signal(ScoreSource::Favorite, 1.0, FAVORITES_BOOST, None);
         }
     }
     // Re-sort after favorites boost
@@ -224,8 +360,28 @@ pub fn run(ctx: &AppContext, args: &SuggestArgs) -> Result<()> {
             .unwrap_or(std::cmp::Ordering::Equal)
     });
 
-    // 10. Apply domain filter if specified
+    // Seasonal windows: boost in-window skills, drop or demote expired ones
+    let windows: HashMap<String, SkillWindow> = all_skills
+        .iter()
+        .map(|s| {
+            (
+                s.id.clone(),
+                SkillWindow::from_metadata_json(&s.metadata_json),
+            )
+        })
+        .collect();
+    let today = applicability::today(&SystemClock, &ctx.config.applicability);
+    apply_applicability_windows(&mut suggestions, &windows, today, &ctx.config.applicability);
+
+    // 10. Apply domain filter if specified; derived language and framework
+    // tags count as tags here
     if let Some(ref domain) = args.domain {
+        let mut derived = ctx.db.derived_tags_by_skill()?;
+        for suggestion in &mut suggestions {
+            if let Some(tags) = derived.remove(&suggestion.skill_id) {
+                suggestion.tags.extend(tags);
+            }
+        }
         let domain_lower = domain.to_lowercase();
         suggestions.retain(|s| {
             s.tags
@@ -242,8 +398,12 @@ pub fn run(ctx: &AppContext, args: &SuggestArgs) -> Result<()> {
             let frequency = user_history.skill_frequency(&suggestion.skill_id);
             let recency = user_history.skill_recency(&suggestion.skill_id);
             let personal_boost = frequency * 0.3 + recency * 0.2;
-            suggestion.breakdown.personal_boost = personal_boost;
-            suggestion.score = (suggestion.score + personal_boost).clamp(0.0, 1.0);
+            suggestion.add_signal(
+                ScoreSource::History,
+                personal_boost,
+                1.0,
+                Some(format!("frequency {frequency:.2}, recency {recency:.2}")),
+            );
         }
         // Re-sort after personal boost
         suggestions.sort_by(|a, b| {
@@ -253,12 +413,15 @@ pub fn run(ctx: &AppContext, args: &SuggestArgs) -> Result<()> {
         });
     }
 
-    // 12. Apply cooldown filter (unless ignored)
+    // 12. Apply cooldown filter (unless ignored, or asked for by task)
     let fp = fingerprint.as_u64();
-    if !args.ignore_cooldowns {
+    let mut cooled_down = 0;
+    if !args.ignore_cooldowns && task.is_none() {
         use crate::suggestions::CooldownStatus;
+        let before = suggestions.len();
         suggestions
             .retain(|s| !matches!(cache.status(fp, &s.skill_id), CooldownStatus::Active { .. }));
+        cooled_down = before - suggestions.len();
     }
 
     // 13. Truncate to limit
@@ -276,56 +439,53 @@ pub fn run(ctx: &AppContext, args: &SuggestArgs) -> Result<()> {
             // Filter out hidden skills from discovery too
             .filter(|s| !ctx.db.has_user_preference(&s.id, "hidden").unwrap_or(false))
             .filter_map(|skill| {
-                let rec = recommendations.iter().find(|r| r.skill_id == skill.id);
-                let components = rec.map(|r| &r.components);
-                let pull_count = components.map(|c| c.pull_count).unwrap_or(0);
+                // Skills outside the bandit's shortlist start from a neutral prior
+                let components = recommendations
+                    .iter()
+                    .find(|r| r.skill_id == skill.id)
+                    .map_or_else(
+                        || RecommendationComponents {
+                            thompson_score: 0.5,
+                            exploration_bonus: 0.1,
+                            avg_reward: 0.5,
+                            ..RecommendationComponents::default()
+                        },
+                        |r| r.components.clone(),
+                    );
 
                 // Only include under-explored skills
-                if pull_count >= 10 {
+                if components.pull_count >= 10 {
                     return None;
                 }
 
-                let tags = parse_tags_from_metadata(&skill.metadata_json);
-                let is_favorite = ctx
+                let mut suggestion = bandit_suggestion(skill, &components, projects.as_deref());
+                suggestion.is_discovery = true;
+                suggestion.is_favorite = ctx
                     .db
                     .has_user_preference(&skill.id, "favorite")
                     .unwrap_or(false);
-                let mut base_score = rec.map(|r| r.score).unwrap_or(0.3);
-                let mut personal_boost = 0.0;
 
                 // Apply favorites boost to discovery suggestions too
-                if is_favorite {
-                    let favorites_boost = 0.25;
-                    personal_boost += favorites_boost;
-                    base_score = (base_score + favorites_boost).clamp(0.0, 1.0);
+                if suggestion.is_favorite {
+                    suggestion.add_signal(ScoreSource::Favorite, 1.0, FAVORITES_BOOST, None);
                 }
-
-                Some(Suggestion {
-                    skill_id: skill.id.clone(),
-                    name: skill.name.clone(),
-                    description: skill.description.clone(),
-                    score: base_score,
-                    breakdown: ScoreBreakdown {
-                        contextual_score: components.map(|c| c.contextual_score).unwrap_or(0.0),
-                        thompson_score: components.map(|c| c.thompson_score).unwrap_or(0.5),
-                        exploration_bonus: components.map(|c| c.exploration_bonus).unwrap_or(0.1),
-                        personal_boost,
-                        pull_count,
-                        avg_reward: components.map(|c| c.avg_reward).unwrap_or(0.5),
-                    },
-                    is_discovery: true,
-                    is_favorite,
-                    tags,
-                })
+                Some(suggestion)
             })
             .collect();
 
+        apply_applicability_windows(
+            &mut discovery_candidates,
+            &windows,
+            today,
+            &ctx.config.applicability,
+        );
+
         // Sort by exploration potential
         discovery_candidates.sort_by(|a, b| {
-            let a_potential = a.breakdown.exploration_bonus
-                + (1.0 - a.breakdown.pull_count as f32 / 10.0).max(0.0) * 0.2;
-            let b_potential = b.breakdown.exploration_bonus
-                + (1.0 - b.breakdown.pull_count as f32 / 10.0).max(0.0) * 0.2;
+            let a_potential = a.breakdown.value(ScoreSource::Exploration)
+                + (1.0 - a.pull_count as f32 / 10.0).max(0.0) * 0.2;
+            let b_potential = b.breakdown.value(ScoreSource::Exploration)
+                + (1.0 - b.pull_count as f32 / 10.0).max(0.0) * 0.2;
             b_potential
                 .partial_cmp(&a_potential)
                 .unwrap_or(std::cmp::Ordering::Equal)
@@ -335,7 +495,7 @@ pub fn run(ctx: &AppContext, args: &SuggestArgs) -> Result<()> {
     }
 
     // 15. Record suggestions for learning
-    let mut suggestion_tracker = SuggestionTracker::new();
+    let mut suggestion_tracker = SuggestionTracker::for_agent(args.agent_id.clone());
     let all_suggested_ids: Vec<String> = suggestions
         .iter()
         .chain(discovery_suggestions.iter())
@@ -343,48 +503,536 @@ pub fn run(ctx: &AppContext, args: &SuggestArgs) -> Result<()> {
         .collect();
     suggestion_tracker.record_suggestions(&all_suggested_ids, Some(fingerprint.as_u64()));
 
-    // 15. Update cooldowns for shown suggestions (default 5 minute cooldown)
-    let cooldown_seconds = 300; // 5 minutes
-    for suggestion in &suggestions {
-        cache.record(fp, suggestion.skill_id.clone(), cooldown_seconds);
+    // 15. Update cooldowns for shown suggestions (task queries start none)
+    let cooldown_seconds = ctx.config.suggestions.cooldown_secs;
+    if task.is_none() {
+        for suggestion in &suggestions {
+            cache.record(fp, suggestion.skill_id.clone(), cooldown_seconds);
+        }
+        cache.save_to_db(&ctx.db)?;
+        remove_legacy_cooldowns(&cache_path);
     }
-    cache.save(&cache_path)?;
 
-    // 16. Output results
-    debug!(target: "suggest", count = suggestions.len(), "generating suggestions");
-    output_suggestions(
-        ctx,
-        args,
-        &fingerprint,
-        &suggestions,
-        &discovery_suggestions,
-        &collected_context,
-        &contextual_bandit,
-    )
+    // 16. Persist explanations for `ms why-suggested`
+    let cooldown_until = chrono::Utc::now() + chrono::Duration::seconds(cooldown_seconds as i64);
+    let context_summary = ContextSummary::new(
+        fp,
+        Some(working_dir.display().to_string()),
+        collected_context
+            .git_context
+            .as_ref()
+            .map(|g| g.branch.clone()),
+        collected_context
+            .recent_files
+            .iter()
+            .map(|f| f.path.display().to_string()),
+    );
+    for (suggestion, discovery) in suggestions
+        .iter()
+        .map(|s| (s, false))
+        .chain(discovery_suggestions.iter().map(|s| (s, true)))
+    {
+        let Some(skill) = skill_map.get(&suggestion.skill_id) else {
+            continue;
+        };
+        let explanation = build_explanation(
+            suggestion,
+            skill,
+            args,
+            context_summary.clone(),
+            (!discovery && task.is_none()).then_some(cooldown_until),
+            discovery,
+        );
+        suggestion_tracker.attach_explanation(
+            &suggestion.skill_id,
+            args.session_id.as_deref(),
+            explanation,
+        );
+    }
+    persist_suggestion_records(ctx, &suggestion_tracker);
+
+    Ok(SuggestRun {
+        suggestions,
+        discovery: discovery_suggestions,
+        fingerprint,
+        context: collected_context,
+        cooled_down,
+    })
 }
 
-/// Output when no skills are available.
-fn output_empty_suggestions(
-    ctx: &AppContext,
-    _args: &SuggestArgs,
-    fingerprint: &ContextFingerprint,
-    _cache: &SuggestionCooldownCache,
-) -> Result<()> {
-    let output = SuggestionOutput::new().with_fingerprint(fingerprint.as_u64());
-    println!("{}", output.format(ctx.output_format));
-    Ok(())
+/// How much full search relevance can add to a suggestion's score.
+const SEARCH_RELEVANCE_WEIGHT: f32 = 0.2;
+
+/// How much full relevance to the `--task` description can add. Higher than
+/// [`SEARCH_RELEVANCE_WEIGHT`]: an explicit task says more than the stack.
+const TASK_RELEVANCE_WEIGHT: f32 = 0.5;
+
+/// Most terms of a task description used for its BM25 query.
+const TASK_QUERY_MAX_TERMS: usize = 32;
+
+/// Signals derived from the project context, as opposed to the task.
+const CONTEXT_SOURCES: [ScoreSource; 3] = [
+    ScoreSource::ProjectMatch,
+    ScoreSource::Bm25,
+    ScoreSource::Embedding,
+];
+
+/// Score added for favorited skills.
+const FAVORITES_BOOST: f32 = 0.25;
+
+/// A suggestion scored by the contextual bandit alone.
+fn bandit_suggestion(
+    skill: &SkillRecord,
+    components: &RecommendationComponents,
+    projects: Option<&str>,
+) -> Suggestion {
+    let breakdown = bandit_breakdown(components, projects);
+    Suggestion {
+        skill_id: skill.id.clone(),
+        name: skill.name.clone(),
+        description: skill.description.clone(),
+        score: breakdown.total(),
+        breakdown,
+        pull_count: components.pull_count,
+        avg_reward: components.avg_reward,
+        is_discovery: false,
+        is_favorite: false,
+        tags: parse_tags_from_metadata(&skill.metadata_json),
+        window: WindowStatus::Always,
+    }
 }
 
-/// Unified output function using the new formatter system.
-fn output_suggestions(
+/// The contextual bandit's score, one signal per component. Sums to
+/// [`RecommendationComponents::score`].
+fn bandit_breakdown(
+    components: &RecommendationComponents,
+    projects: Option<&str>,
+) -> ScoreBreakdown {
+    let mut breakdown = ScoreBreakdown::default();
+    breakdown.add(
+        ScoreSource::ProjectMatch,
+        components.contextual_score,
+        CONTEXTUAL_WEIGHT,
+        projects.map(str::to_string),
+    );
+    breakdown.add(
+        ScoreSource::BanditPrior,
+        components.thompson_score,
+        THOMPSON_WEIGHT,
+        Some(format!(
+            "avg reward {:.2} over {} uses",
+            components.avg_reward, components.pull_count
+        )),
+    );
+    if components.exploration_bonus > 0.0 {
+        breakdown.add(
+            ScoreSource::Exploration,
+            components.exploration_bonus,
+            1.0,
+            None,
+        );
+    }
+    breakdown
+}
+
+/// Detected project types with their confidence, e.g. `rust 90%, node 60%`.
+fn detected_projects(context: &CollectedContext) -> Option<String> {
+    let projects: Vec<String> = context
+        .detected_projects
+        .iter()
+        .filter(|p| p.project_type.id() != "unknown")
+        .map(|p| format!("{} {:.0}%", p.project_type.id(), p.confidence * 100.0))
+        .collect();
+    (!projects.is_empty()).then(|| projects.join(", "))
+}
+
+/// Search terms describing the working context: detected project types and
+/// tools.
+fn context_query(context: &CollectedContext) -> String {
+    let mut terms: Vec<&str> = context
+        .detected_projects
+        .iter()
+        .map(|p| p.project_type.id())
+        .filter(|id| *id != "unknown")
+        .collect();
+    let mut tools: Vec<&str> = context.detected_tools.iter().map(String::as_str).collect();
+    tools.sort_unstable();
+    terms.extend(tools);
+    terms.dedup();
+    terms.join(" ")
+}
+
+/// RRF settings for `ms suggest`: `[search.rrf]`, with the per-source weights
+/// replaced by what the signal bandit has learned (unless `--no-bandit`).
+fn suggestion_rrf_config(
     ctx: &AppContext,
     args: &SuggestArgs,
-    fingerprint: &ContextFingerprint,
-    suggestions: &[Suggestion],
-    discovery_suggestions: &[Suggestion],
     context: &CollectedContext,
-    _bandit: &ContextualBandit,
-) -> Result<()> {
+) -> RrfConfig {
+    let config = RrfConfig::from_search_config(&ctx.config.search);
+    if args.no_bandit {
+        return config;
+    }
+    let Ok(bandit) = SignalBandit::load(&default_bandit_path()) else {
+        return config;
+    };
+    let bandit_context = BanditContext {
+        tech_stack: context
+            .detected_projects
+            .first()
+            .map(|p| p.project_type.id().to_string()),
+        ..BanditContext::default()
+    };
+    match learned_fusion_weights(&bandit, &bandit_context) {
+        Some((bm25, semantic)) => config.with_learned_weights(bm25, semantic),
+        None => config,
+    }
+}
+
+/// The bandit's BM25 and embedding weights, once either arm has feedback.
+fn learned_fusion_weights(bandit: &SignalBandit, context: &BanditContext) -> Option<(f32, f32)> {
+    let learned = [SignalType::Bm25, SignalType::Embedding]
+        .iter()
+        .any(|signal| {
+            bandit
+                .arms
+                .get(signal)
+                .is_some_and(|arm| arm.observations() > 0.0)
+        });
+    if !learned {
+        return None;
+    }
+    let weights = bandit.estimated_weights(context);
+    Some((
+        weights.get(SignalType::Bm25) as f32,
+        weights.get(SignalType::Embedding) as f32,
+    ))
+}
+
+/// One skill's search relevance to a query, split into its BM25 and
+/// embedding parts and scaled so the best fused match totals 1.0.
+#[derive(Debug, Clone, Default)]
+struct SearchRelevance {
+    bm25: f32,
+    semantic: f32,
+    bm25_detail: Option<String>,
+    semantic_detail: Option<String>,
+}
+
+/// Add the search relevance signals that fired for a suggestion.
+fn apply_relevance(suggestion: &mut Suggestion, relevance: &SearchRelevance) {
+    if relevance.bm25 > 0.0 {
+        suggestion.add_signal(
+            ScoreSource::Bm25,
+            relevance.bm25,
+            SEARCH_RELEVANCE_WEIGHT,
+            relevance.bm25_detail.clone(),
+        );
+    }
+    if relevance.semantic > 0.0 {
+        suggestion.add_signal(
+            ScoreSource::Embedding,
+            relevance.semantic,
+            SEARCH_RELEVANCE_WEIGHT,
+            relevance.semantic_detail.clone(),
+        );
+    }
+}
+
+/// Add relevance to the `--task` description as one signal.
+fn apply_task_relevance(suggestion: &mut Suggestion, relevance: &SearchRelevance) {
+    let value = relevance.bm25 + relevance.semantic;
+    if value <= 0.0 {
+        return;
+    }
+    let detail: Vec<&str> = [&relevance.bm25_detail, &relevance.semantic_detail]
+        .into_iter()
+        .flatten()
+        .map(String::as_str)
+        .collect();
+    suggestion.add_signal(
+        ScoreSource::Task,
+        value,
+        TASK_RELEVANCE_WEIGHT,
+        (!detail.is_empty()).then(|| detail.join("; ")),
+    );
+}
+
+/// BM25 query for a task description: its first words, lowercased so
+/// nothing reads as query syntax.
+fn task_bm25_query(task: &str) -> String {
+    task.split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .take(TASK_QUERY_MAX_TERMS)
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Hybrid search relevance of each skill to the working context.
+fn context_relevance(
+    ctx: &AppContext,
+    context: &CollectedContext,
+    rrf: &RrfConfig,
+    fetch_limit: usize,
+) -> HashMap<String, SearchRelevance> {
+    let query = context_query(context);
+    let label = format!("query \"{query}\"");
+    search_relevance(ctx, &query, &query, &label, rrf, fetch_limit)
+}
+
+/// Hybrid search relevance of each skill: BM25 on `lexical`, embedding
+/// similarity to `semantic_query`, fused with `rrf`. `label` names the query
+/// in signal details. Best effort: retrieval errors mean no boost.
+fn search_relevance(
+    ctx: &AppContext,
+    lexical: &str,
+    semantic_query: &str,
+    label: &str,
+    rrf: &RrfConfig,
+    fetch_limit: usize,
+) -> HashMap<String, SearchRelevance> {
+    if lexical.is_empty() && semantic_query.is_empty() {
+        return HashMap::new();
+    }
+    let bm25: Vec<(String, f32)> = if lexical.is_empty() {
+        Vec::new()
+    } else {
+        bm25_ranked(ctx, lexical, None, fetch_limit)
+            .map(|hits| hits.into_iter().map(|h| (h.skill_id, h.score)).collect())
+            .unwrap_or_default()
+    };
+    let semantic = if ctx.config.search.use_embeddings && !semantic_query.is_empty() {
+        semantic_ranked(ctx, semantic_query, fetch_limit).unwrap_or_else(|err| {
+            debug!(target: "suggest", error = %err, "semantic relevance unavailable");
+            Vec::new()
+        })
+    } else {
+        Vec::new()
+    };
+
+    let fused = fuse_with_limit(&bm25, &semantic, rrf, fetch_limit);
+    let best = fused.first().map_or(0.0, |r| r.score);
+    if best <= 0.0 {
+        return HashMap::new();
+    }
+    fused
+        .into_iter()
+        .map(|r| {
+            let (lexical, semantic) = r.source_contributions(rrf);
+            let relevance = SearchRelevance {
+                bm25: lexical / best,
+                semantic: semantic / best,
+                bm25_detail: r.bm25_rank.map(|rank| {
+                    format!(
+                        "{label}: rank {rank}, score {:.2}",
+                        r.bm25_score.unwrap_or_default()
+                    )
+                }),
+                semantic_detail: r.semantic_rank.map(|rank| {
+                    format!(
+                        "similarity {:.2} to {label}, rank {rank}",
+                        r.semantic_score.unwrap_or_default()
+                    )
+                }),
+            };
+            (r.skill_id, relevance)
+        })
+        .collect()
+}
+
+/// Snapshot why a suggestion was made, for later `ms why-suggested` lookups.
+fn build_explanation(
+    s: &Suggestion,
+    skill: &SkillRecord,
+    args: &SuggestArgs,
+    context: ContextSummary,
+    cooldown_until: Option<chrono::DateTime<chrono::Utc>>,
+    discovery: bool,
+) -> SuggestionExplanation {
+    let mut triggers: Vec<String> = Vec::new();
+    if s.is_favorite {
+        triggers.push("favorite (pinned)".to_string());
+    }
+    let contextual = s.breakdown.value(ScoreSource::ProjectMatch);
+    let search_relevance =
+        s.breakdown.value(ScoreSource::Bm25) + s.breakdown.value(ScoreSource::Embedding);
+    if contextual > 0.5 {
+        triggers.push(format!("context match {:.0}%", contextual * 100.0));
+    }
+    if search_relevance > 0.5 {
+        triggers.push(format!("search match {:.0}%", search_relevance * 100.0));
+    }
+    let task_relevance = s.breakdown.value(ScoreSource::Task);
+    if task_relevance > 0.0 {
+        triggers.push(format!(
+            "task match {:.0}% (suggested from the {})",
+            task_relevance * 100.0,
+            s.origin().as_str()
+        ));
+    }
+    if s.pull_count > 10 {
+        triggers.push(format!("{} prior uses", s.pull_count));
+    }
+    if discovery {
+        triggers.push(format!("under-explored ({} uses)", s.pull_count));
+    }
+    match s.window {
+        WindowStatus::Active => triggers.push("inside applicability window".to_string()),
+        WindowStatus::Expired => triggers.push("expired (demoted)".to_string()),
+        WindowStatus::Always | WindowStatus::Inactive => {}
+    }
+    if args.personal {
+        triggers.push("personal history boost".to_string());
+    }
+    if let Some(domain) = &args.domain {
+        triggers.push(format!("domain filter '{domain}'"));
+    }
+
+    SuggestionExplanation {
+        score: s.score,
+        signals: SignalScores {
+            contextual,
+            thompson: s.breakdown.value(ScoreSource::BanditPrior),
+            exploration_bonus: s.breakdown.value(ScoreSource::Exploration),
+            personal_boost: s
+                .breakdown
+                .contribution(&[ScoreSource::Favorite, ScoreSource::History]),
+            search_relevance,
+            task_relevance,
+        },
+        bandit: BanditArmSnapshot {
+            sampled_probability: s.breakdown.value(ScoreSource::BanditPrior),
+            pull_count: s.pull_count,
+            avg_reward: s.avg_reward,
+        },
+        matched_triggers: triggers,
+        context,
+        preferences: PreferenceSnapshot {
+            favorite: s.is_favorite,
+            cooldowns_ignored: args.ignore_cooldowns,
+            cooldown_until,
+            domain_filter: args.domain.clone(),
+        },
+        skill_content_hash: skill.content_hash.clone(),
+        skill_modified_at: skill.modified_at.clone(),
+        discovery,
+        origin: s.origin(),
+        task: args.task_text().map(str::to_string),
+        truncated: false,
+    }
+}
+
+/// Store shown suggestions and drop records past the retention window.
+///
+/// Best effort: failing to persist never fails `ms suggest`.
+fn persist_suggestion_records(ctx: &AppContext, tracker: &SuggestionTracker) {
+    let config = &ctx.config.suggestions;
+    for record in tracker.all_suggestions() {
+        if let Err(err) = ctx
+            .db
+            .insert_suggestion_record(record, config.max_explanation_bytes)
+        {
+            debug!(target: "suggest", error = %err, "failed to persist suggestion record");
+        }
+    }
+    let cutoff = chrono::Utc::now() - chrono::Duration::days(config.retention_days as i64);
+    if let Err(err) = ctx.db.prune_suggestion_records(&cutoff.to_rfc3339()) {
+        debug!(target: "suggest", error = %err, "failed to prune suggestion records");
+    }
+}
+
+/// `ms suggest --cooldowns`: active cooldowns, soonest expiry first.
+fn list_cooldowns(ctx: &AppContext) -> Result<()> {
+    let cache = SuggestionCooldownCache::load_from_db(&ctx.db, None)?;
+    let now = chrono::Utc::now();
+    let active = cache.active();
+
+    if ctx.output_format.is_machine_readable() {
+        let cooldowns: Vec<_> = active
+            .iter()
+            .map(|entry| {
+                serde_json::json!({
+                    "skill_id": entry.skill_id,
+                    "context_fingerprint": format!("{:016x}", entry.fingerprint),
+                    "response": entry.response,
+                    "agent_id": entry.agent_id,
+                    "dismissals": entry.dismissals,
+                    "expires_at": entry.expires_at().to_rfc3339(),
+                    "remaining_seconds": entry.remaining_seconds(now),
+                })
+            })
+            .collect();
+        return emit_json(&serde_json::json!({
+            "status": "ok",
+            "count": cooldowns.len(),
+            "cooldowns": cooldowns,
+        }));
+    }
+
+    if active.is_empty() {
+        println!("No active cooldowns.");
+        return Ok(());
+    }
+    for entry in active {
+        let context = if entry.fingerprint == crate::suggestions::ANY_CONTEXT {
+            "any context".to_string()
+        } else {
+            format!("context {:016x}", entry.fingerprint)
+        };
+        let dismissals = if entry.dismissals > 0 {
+            format!(" (dismissed {}x)", entry.dismissals)
+        } else {
+            String::new()
+        };
+        let agent = entry
+            .agent_id
+            .as_deref()
+            .map_or_else(String::new, |agent| format!(" by {agent}"));
+        println!(
+            "{:<32} {:<9} {:>10} left  {context}{dismissals}{agent}",
+            entry.skill_id,
+            entry.response.as_str(),
+            crate::utils::format::format_duration(entry.remaining_seconds(now)),
+        );
+    }
+    Ok(())
+}
+
+/// `ms suggest --clear-cooldowns [SKILL]`.
+fn clear_cooldowns(ctx: &AppContext, skill: Option<&str>) -> Result<()> {
+    let skill_id = skill.map(|s| ctx.resolve_skill_id(s)).transpose()?;
+    let cleared = ctx.db.delete_suggestion_cooldowns(skill_id.as_deref())?;
+    if skill_id.is_none() {
+        remove_legacy_cooldowns(&cooldown_path());
+    }
+
+    if ctx.output_format.is_machine_readable() {
+        return emit_json(&serde_json::json!({
+            "status": "ok",
+            "skill_id": skill_id,
+            "cleared": cleared,
+        }));
+    }
+    match skill_id {
+        Some(id) => println!("Cleared {cleared} cooldown(s) for {id}"),
+        None => println!("Cleared {cleared} cooldown(s)"),
+    }
+    Ok(())
+}
+
+/// Drop the pre-database cooldown file once its entries have been written.
+fn remove_legacy_cooldowns(path: &std::path::Path) {
+    if path.exists()
+        && let Err(err) = std::fs::remove_file(path)
+    {
+        debug!(target: "suggest", error = %err, "failed to remove legacy cooldown file");
+    }
+}
+
+/// Unified output function using the new formatter system.
+fn output_suggestions(ctx: &AppContext, args: &SuggestArgs, outcome: &SuggestRun) -> Result<()> {
+    let context = &outcome.context;
     // Build context
     let suggestion_context = SuggestionContext {
         cwd: std::env::current_dir()
@@ -397,28 +1045,17 @@ fn output_suggestions(
             .take(10)
             .map(|f| f.path.display().to_string())
             .collect(),
-        fingerprint: Some(fingerprint.as_u64()),
+        fingerprint: Some(outcome.fingerprint.as_u64()),
     };
 
     // Build output
     let mut output = SuggestionOutput::new().with_context(suggestion_context);
 
     // Add main suggestions
-    for s in suggestions {
+    let origin = |s: &Suggestion| args.task_text().map(|_| s.origin());
+    for s in &outcome.suggestions {
         let reason = build_suggestion_reason(s);
 
-        // Calculate percentage breakdown if --explain flag is used
-        let breakdown = if args.explain {
-            Some(ScorePercentageBreakdown::from_components(
-                s.breakdown.contextual_score,
-                s.breakdown.thompson_score,
-                s.breakdown.exploration_bonus,
-                s.breakdown.personal_boost,
-            ))
-        } else {
-            None
-        };
-
         output.add_suggestion(SuggestionItem {
             skill_id: s.skill_id.clone(),
             name: s.name.clone(),
@@ -427,12 +1064,13 @@ fn output_suggestions(
             reason,
             is_discovery: false,
             tags: s.tags.clone(),
-            breakdown,
+            signals: explained_signals(args, s),
+            origin: origin(s),
         });
     }
 
     // Add discovery suggestions
-    for s in discovery_suggestions {
+    for s in &outcome.discovery {
         let mut reason_parts: Vec<String> = Vec::new();
 
         // Favorite status
@@ -441,19 +1079,7 @@ fn output_suggestions(
         }
 
         // Discovery-specific reason
-        reason_parts.push(format!("under-explored ({} uses)", s.breakdown.pull_count));
-
-        // Calculate percentage breakdown if --explain flag is used
-        let breakdown = if args.explain {
-            Some(ScorePercentageBreakdown::from_components(
-                s.breakdown.contextual_score,
-                s.breakdown.thompson_score,
-                s.breakdown.exploration_bonus,
-                s.breakdown.personal_boost,
-            ))
-        } else {
-            None
-        };
+        reason_parts.push(format!("under-explored ({} uses)", s.pull_count));
 
         output.add_suggestion(SuggestionItem {
             skill_id: s.skill_id.clone(),
@@ -463,7 +1089,8 @@ fn output_suggestions(
             reason: Some(reason_parts.join(", ")),
             is_discovery: true,
             tags: s.tags.clone(),
-            breakdown,
+            signals: explained_signals(args, s),
+            origin: origin(s),
         });
     }
 
@@ -472,6 +1099,15 @@ fn output_suggestions(
     Ok(())
 }
 
+/// The score breakdown, with `--explain`.
+fn explained_signals(args: &SuggestArgs, s: &Suggestion) -> Vec<crate::suggestions::ScoreSignal> {
+    if args.explain {
+        s.breakdown.signals.clone()
+    } else {
+        Vec::new()
+    }
+}
+
 /// Build a human-readable reason for why a skill was suggested.
 fn build_suggestion_reason(s: &Suggestion) -> Option<String> {
     let mut reasons: Vec<String> = Vec::new();
@@ -481,17 +1117,31 @@ fn build_suggestion_reason(s: &Suggestion) -> Option<String> {
         reasons.push("Favorite".to_string());
     }
 
-    // Context match
-    if s.breakdown.contextual_score > 0.5 {
+    // Task match, and whether the task outweighed the project context
+    let task = s.breakdown.value(ScoreSource::Task);
+    if task > 0.0 {
         reasons.push(format!(
-            "context match {:.0}%",
-            s.breakdown.contextual_score * 100.0
+            "task match {:.0}% (from {})",
+            task * 100.0,
+            s.origin().as_str()
         ));
     }
 
+    // Context match
+    let contextual = s.breakdown.value(ScoreSource::ProjectMatch);
+    if contextual > 0.5 {
+        reasons.push(format!("context match {:.0}%", contextual * 100.0));
+    }
+
     // Historical usage
-    if s.breakdown.pull_count > 10 {
-        reasons.push(format!("{} prior uses", s.breakdown.pull_count));
+    if s.pull_count > 10 {
+        reasons.push(format!("{} prior uses", s.pull_count));
+    }
+
+    match s.window {
+        WindowStatus::Active => reasons.push("in season".to_string()),
+        WindowStatus::Expired => reasons.push("expired".to_string()),
+        WindowStatus::Always | WindowStatus::Inactive => {}
     }
 
     if reasons.is_empty() {
@@ -501,6 +1151,57 @@ fn build_suggestion_reason(s: &Suggestion) -> Option<String> {
     }
 }
 
+/// Score multiplier for expired skills kept by `exclude_expired = false`.
+const EXPIRED_SCORE_FACTOR: f32 = 0.1;
+
+/// Apply applicability windows as of the local date `today`: boost skills
+/// inside their window, demote those outside it, and drop expired skills
+/// (or heavily demote them when the config keeps them). Re-sorts by score.
+fn apply_applicability_windows(
+    suggestions: &mut Vec<Suggestion>,
+    windows: &HashMap<String, SkillWindow>,
+    today: chrono::NaiveDate,
+    config: &ApplicabilityConfig,
+) {
+    for suggestion in suggestions.iter_mut() {
+        let Some(window) = windows.get(&suggestion.skill_id) else {
+            continue;
+        };
+        suggestion.window = window.status_on(today);
+        match suggestion.window {
+            WindowStatus::Always => {}
+            WindowStatus::Active => suggestion.add_signal(
+                ScoreSource::Window,
+                1.0,
+                config.in_window_boost,
+                Some("in season".to_string()),
+            ),
+            WindowStatus::Inactive => suggestion.add_signal(
+                ScoreSource::Window,
+                -1.0,
+                config.out_of_window_penalty,
+                Some("out of season".to_string()),
+            ),
+            WindowStatus::Expired => {
+                suggestion.breakdown.scale(
+                    ScoreSource::Window,
+                    EXPIRED_SCORE_FACTOR,
+                    Some("expired".to_string()),
+                );
+                suggestion.score = suggestion.breakdown.total();
+            }
+        }
+    }
+    if config.exclude_expired {
+        suggestions.retain(|s| s.window != WindowStatus::Expired);
+    }
+    suggestions.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+}
+
 /// Parse tags from skill metadata JSON.
 fn parse_tags_from_metadata(metadata_json: &str) -> Vec<String> {
     let Ok(value) = serde_json::from_str::<serde_json::Value>(metadata_json) else {
@@ -527,11 +1228,6 @@ fn cooldown_path() -> std::path::PathBuf {
     base.join("ms").join("cooldowns.json")
 }
 
-fn contextual_bandit_path() -> std::path::PathBuf {
-    let base = dirs::data_dir().unwrap_or_else(|| std::path::PathBuf::from("."));
-    base.join("ms").join("contextual_bandit.json")
-}
-
 /// Check whether the terminal supports rich output for suggest commands.
 #[allow(dead_code)]
 fn should_use_rich_for_suggest() -> bool {
@@ -594,6 +1290,65 @@ mod tests {
         assert_eq!(cli.suggest.cwd, Some("/path/to/dir".to_string()));
     }
 
+    #[test]
+    fn parse_suggest_with_task() {
+        let cli = TestCli::try_parse_from(["test", "--task", "add a database migration"]).unwrap();
+        assert_eq!(cli.suggest.task_text(), Some("add a database migration"));
+        let blank = TestCli::try_parse_from(["test", "--task", "  "]).unwrap();
+        assert_eq!(blank.suggest.task_text(), None);
+    }
+
+    #[test]
+    fn task_bm25_query_keeps_the_first_plain_terms() {
+        assert_eq!(
+            task_bm25_query("Fix the \"users\" table: add an index (Postgres)!"),
+            "fix the users table add an index postgres"
+        );
+        let long = "word ".repeat(TASK_QUERY_MAX_TERMS * 3);
+        assert_eq!(
+            task_bm25_query(&long).split(' ').count(),
+            TASK_QUERY_MAX_TERMS
+        );
+        assert_eq!(task_bm25_query("?!"), "");
+    }
+
+    #[test]
+    fn task_relevance_marks_the_task_as_origin() {
+        let mut s = make_test_suggestion(false, 0.2, 3);
+        assert_eq!(s.origin(), SuggestionOrigin::Context);
+        apply_task_relevance(
+            &mut s,
+            &SearchRelevance {
+                bm25: 0.7,
+                semantic: 0.3,
+                bm25_detail: Some("the task: rank 1, score 4.20".to_string()),
+                semantic_detail: Some("similarity 0.61 to the task, rank 1".to_string()),
+            },
+        );
+        assert!((s.breakdown.value(ScoreSource::Task) - 1.0).abs() < 1e-6);
+        assert_eq!(s.origin(), SuggestionOrigin::Task);
+        let detail = s
+            .breakdown
+            .signals
+            .last()
+            .unwrap()
+            .detail
+            .as_deref()
+            .unwrap();
+        assert!(detail.contains("rank 1, score 4.20; similarity 0.61"));
+        assert!(
+            build_suggestion_reason(&s)
+                .unwrap()
+                .starts_with("task match 100% (from task)")
+        );
+
+        // No task hit: no signal, origin stays with the context
+        let mut plain = make_test_suggestion(false, 0.8, 3);
+        apply_task_relevance(&mut plain, &SearchRelevance::default());
+        assert_eq!(plain.breakdown.value(ScoreSource::Task), 0.0);
+        assert_eq!(plain.origin(), SuggestionOrigin::Context);
+    }
+
     #[test]
     fn parse_suggest_with_budget() {
         let cli = TestCli::try_parse_from(["test", "--budget", "1000"]).unwrap();
@@ -606,12 +1361,55 @@ mod tests {
         assert!(cli.suggest.ignore_cooldowns);
     }
 
+    #[test]
+    fn parse_suggest_agent_id() {
+        let cli = TestCli::try_parse_from(["test", "--agent-id", "cursor"]).unwrap();
+        assert_eq!(cli.suggest.agent_id.as_deref(), Some("cursor"));
+    }
+
     #[test]
     fn parse_suggest_reset_cooldowns() {
         let cli = TestCli::try_parse_from(["test", "--reset-cooldowns"]).unwrap();
         assert!(cli.suggest.reset_cooldowns);
     }
 
+    #[test]
+    fn parse_suggest_cooldown_listing_and_clearing() {
+        let cli = TestCli::try_parse_from(["test", "--cooldowns"]).unwrap();
+        assert!(cli.suggest.cooldowns);
+        assert_eq!(cli.suggest.clear_cooldowns, None);
+
+        let cli = TestCli::try_parse_from(["test", "--clear-cooldowns"]).unwrap();
+        assert_eq!(cli.suggest.clear_cooldowns, Some(None));
+
+        let cli = TestCli::try_parse_from(["test", "--clear-cooldowns", "terraform"]).unwrap();
+        assert_eq!(
+            cli.suggest.clear_cooldowns,
+            Some(Some("terraform".to_string()))
+        );
+
+        assert!(TestCli::try_parse_from(["test", "--cooldowns", "--clear-cooldowns"]).is_err());
+    }
+
+    #[test]
+    fn learned_fusion_weights_need_feedback() {
+        use crate::suggestions::bandit::Reward;
+
+        let context = BanditContext::default();
+        let mut bandit = SignalBandit::new();
+        assert!(learned_fusion_weights(&bandit, &context).is_none());
+
+        for _ in 0..5 {
+            bandit.update(SignalType::Bm25, Reward::Success, &context);
+            bandit.update(SignalType::Embedding, Reward::Failure, &context);
+        }
+        let (bm25, semantic) = learned_fusion_weights(&bandit, &context).unwrap();
+        assert!(bm25 > semantic, "bm25 {bm25} vs semantic {semantic}");
+
+        let config = RrfConfig::default().with_learned_weights(bm25, semantic);
+        assert!(config.bm25_weight > config.semantic_weight);
+    }
+
     #[test]
     fn parse_suggest_no_bandit() {
         let cli = TestCli::try_parse_from(["test", "--no-bandit"]).unwrap();
@@ -690,14 +1488,14 @@ mod tests {
 
     #[test]
     fn contextual_bandit_path_ends_with_expected() {
-        let path = contextual_bandit_path();
+        let path = ContextualBandit::legacy_path();
         assert!(path.ends_with("ms/contextual_bandit.json"));
     }
 
     #[test]
     fn paths_are_in_same_directory() {
         let cooldown = cooldown_path();
-        let bandit = contextual_bandit_path();
+        let bandit = ContextualBandit::legacy_path();
 
         // Both should be in the same parent directory
         assert_eq!(cooldown.parent(), bandit.parent());
@@ -707,27 +1505,34 @@ mod tests {
     // Suggestion reason tests
     // =========================================================================
 
+    fn test_components(contextual_score: f32, pull_count: u64) -> RecommendationComponents {
+        RecommendationComponents {
+            contextual_score,
+            thompson_score: 0.5,
+            exploration_bonus: 0.1,
+            pull_count,
+            avg_reward: 0.5,
+        }
+    }
+
     fn make_test_suggestion(
         is_favorite: bool,
         contextual_score: f32,
         pull_count: u64,
     ) -> Suggestion {
+        let breakdown = bandit_breakdown(&test_components(contextual_score, pull_count), None);
         Suggestion {
             skill_id: "test-skill".to_string(),
             name: "Test Skill".to_string(),
             description: "A test skill".to_string(),
-            score: 0.5,
-            breakdown: ScoreBreakdown {
-                contextual_score,
-                thompson_score: 0.5,
-                exploration_bonus: 0.1,
-                personal_boost: 0.0,
-                pull_count,
-                avg_reward: 0.5,
-            },
+            score: breakdown.total(),
+            breakdown,
+            pull_count,
+            avg_reward: 0.5,
             is_discovery: false,
             is_favorite,
             tags: vec![],
+            window: WindowStatus::Always,
         }
     }
 
@@ -785,30 +1590,159 @@ mod tests {
         let mut s = make_test_suggestion(true, 0.5, 5);
         let original_score = s.score;
 
-        // Apply the same boost logic as in the main function
-        if s.is_favorite {
-            let favorites_boost = 0.25;
-            s.breakdown.personal_boost += favorites_boost;
-            s.score = (s.score + favorites_boost).clamp(0.0, 1.0);
-        }
+        s.add_signal(ScoreSource::Favorite, 1.0, FAVORITES_BOOST, None);
 
-        assert!(s.score > original_score);
-        assert_eq!(s.breakdown.personal_boost, 0.25);
-        assert_eq!(s.score, 0.75);
+        assert!((s.score - original_score - 0.25).abs() < 1e-6);
+        assert!((s.breakdown.contribution(&[ScoreSource::Favorite]) - 0.25).abs() < 1e-6);
     }
 
     #[test]
     fn favorites_boost_clamps_to_max() {
-        let mut s = make_test_suggestion(true, 0.5, 5);
-        s.score = 0.9; // High base score
+        // 0.7 context + 0.15 prior + 0.1 exploration
+        let mut s = make_test_suggestion(true, 1.0, 5);
+        assert!((s.score - 0.95).abs() < 1e-6);
 
-        if s.is_favorite {
-            let favorites_boost = 0.25;
-            s.breakdown.personal_boost += favorites_boost;
-            s.score = (s.score + favorites_boost).clamp(0.0, 1.0);
-        }
+        s.add_signal(ScoreSource::Favorite, 1.0, FAVORITES_BOOST, None);
 
         assert_eq!(s.score, 1.0); // Clamped to max
+        // Only what fit below the cap is credited to the favorite
+        assert!((s.breakdown.contribution(&[ScoreSource::Favorite]) - 0.05).abs() < 1e-6);
+    }
+
+    #[test]
+    fn breakdown_sums_to_score_through_fusion() {
+        let components = test_components(0.8, 3);
+        let mut s = make_test_suggestion(true, 0.8, 3);
+        assert!((s.score - components.score()).abs() < 1e-6);
+
+        apply_relevance(
+            &mut s,
+            &SearchRelevance {
+                bm25: 0.6,
+                semantic: 0.4,
+                bm25_detail: Some("query \"rust cargo\": rank 1, score 7.10".to_string()),
+                semantic_detail: None,
+            },
+        );
+        s.add_signal(ScoreSource::Favorite, 1.0, FAVORITES_BOOST, None);
+        s.add_signal(ScoreSource::History, 0.3, 1.0, None);
+        let windows = HashMap::from([(
+            s.skill_id.clone(),
+            SkillWindow::from_metadata_json(r#"{"expires":"2026-11-30"}"#),
+        )]);
+        let config = ApplicabilityConfig {
+            exclude_expired: false,
+            ..ApplicabilityConfig::default()
+        };
+        let mut list = vec![s];
+        apply_applicability_windows(
+            &mut list,
+            &windows,
+            chrono::NaiveDate::from_ymd_opt(2027, 1, 1).unwrap(),
+            &config,
+        );
+
+        let s = &list[0];
+        let sources: Vec<ScoreSource> = s.breakdown.signals.iter().map(|x| x.source).collect();
+        assert_eq!(
+            sources,
+            [
+                ScoreSource::ProjectMatch,
+                ScoreSource::BanditPrior,
+                ScoreSource::Exploration,
+                ScoreSource::Bm25,
+                ScoreSource::Embedding,
+                ScoreSource::Favorite,
+                ScoreSource::History,
+                ScoreSource::Window,
+            ]
+        );
+        let sum: f32 = s.breakdown.signals.iter().map(|x| x.contribution).sum();
+        assert!((sum - s.score).abs() < 1e-5, "{sum} vs {}", s.score);
+        assert!((s.score - 0.1).abs() < 1e-5);
+    }
+
+    #[test]
+    fn breakdown_with_only_the_bandit_signal() {
+        // No search relevance (e.g. empty vector index, no BM25 hits)
+        let mut s = make_test_suggestion(false, 0.0, 20);
+        apply_relevance(&mut s, &SearchRelevance::default());
+        assert_eq!(s.breakdown.signals.len(), 3);
+        let sum: f32 = s.breakdown.signals.iter().map(|x| x.contribution).sum();
+        assert!((sum - s.score).abs() < 1e-6);
+        assert!(
+            s.breakdown.signals[1]
+                .detail
+                .as_deref()
+                .unwrap()
+                .contains("20 uses")
+        );
+    }
+
+    // =========================================================================
+    // Applicability window tests
+    // =========================================================================
+
+    fn windowed(id: &str, score: f32) -> Suggestion {
+        let mut s = make_test_suggestion(false, 0.0, 0);
+        s.skill_id = id.to_string();
+        s.breakdown = ScoreBreakdown::default();
+        s.add_signal(ScoreSource::ProjectMatch, score, 1.0, None);
+        s
+    }
+
+    fn window_map() -> HashMap<String, SkillWindow> {
+        [
+            ("plain", r#"{"tags":[]}"#),
+            ("freeze", r#"{"active_during":{"months":[12]}}"#),
+            ("old", r#"{"expires":"2026-11-30"}"#),
+        ]
+        .into_iter()
+        .map(|(id, json)| (id.to_string(), SkillWindow::from_metadata_json(json)))
+        .collect()
+    }
+
+    #[test]
+    fn windows_boost_in_season_and_drop_expired() {
+        let day = |d| chrono::NaiveDate::from_ymd_opt(2026, 12, d).unwrap();
+        let mut suggestions = vec![
+            windowed("plain", 0.6),
+            windowed("freeze", 0.5),
+            windowed("old", 0.9),
+        ];
+        apply_applicability_windows(
+            &mut suggestions,
+            &window_map(),
+            day(1),
+            &ApplicabilityConfig::default(),
+        );
+        let ids: Vec<&str> = suggestions.iter().map(|s| s.skill_id.as_str()).collect();
+        assert_eq!(ids, ["freeze", "plain"]);
+        assert_eq!(suggestions[0].window, WindowStatus::Active);
+        assert_eq!(
+            build_suggestion_reason(&suggestions[0]).as_deref(),
+            Some("in season")
+        );
+    }
+
+    #[test]
+    fn windows_demote_out_of_season_and_keep_expired_when_configured() {
+        let today = chrono::NaiveDate::from_ymd_opt(2027, 3, 1).unwrap();
+        let config = ApplicabilityConfig {
+            exclude_expired: false,
+            ..ApplicabilityConfig::default()
+        };
+        let mut suggestions = vec![
+            windowed("old", 0.9),
+            windowed("freeze", 0.5),
+            windowed("plain", 0.3),
+        ];
+        apply_applicability_windows(&mut suggestions, &window_map(), today, &config);
+        let ids: Vec<&str> = suggestions.iter().map(|s| s.skill_id.as_str()).collect();
+        assert_eq!(ids, ["plain", "freeze", "old"]);
+        assert_eq!(suggestions[1].window, WindowStatus::Inactive);
+        assert_eq!(suggestions[2].window, WindowStatus::Expired);
+        assert!((suggestions[2].score - 0.09).abs() < 1e-6);
     }
 
     // =========================================================================
@@ -827,7 +1761,8 @@ mod tests {
             reason: Some(format!("context match {:.0}%", score * 100.0)),
             is_discovery: discovery,
             tags: vec!["cli".to_string(), "rust".to_string()],
-            breakdown: None,
+            signals: Vec::new(),
+            origin: None,
         }
     }
 
@@ -960,20 +1895,25 @@ mod tests {
 
     #[test]
     fn test_suggest_breakdown_display() {
-        let breakdown = ScoreBreakdown {
-            contextual_score: 0.6,
-            thompson_score: 0.5,
-            exploration_bonus: 0.1,
-            personal_boost: 0.2,
-            pull_count: 10,
-            avg_reward: 0.7,
-        };
-        let total = breakdown.contextual_score
-            + breakdown.thompson_score
-            + breakdown.exploration_bonus
-            + breakdown.personal_boost;
-        assert!(total > 0.0, "breakdown should have positive total");
-        assert_eq!(breakdown.pull_count, 10);
+        let s = make_test_suggestion(false, 0.6, 10);
+        let mut item = make_suggestion_item("explained", s.score, false);
+        item.signals = s.breakdown.signals.clone();
+        let mut output = SuggestionOutput::new();
+        output.add_suggestion(item);
+
+        let human = output.format(OutputFormat::Human);
+        assert!(human.contains("Project-type match"), "{human}");
+        assert!(human.contains("Bandit prior"), "{human}");
+
+        let json: serde_json::Value =
+            serde_json::from_str(&output.format(OutputFormat::Json)).unwrap();
+        let signals = json["suggestions"][0]["signals"].as_array().unwrap();
+        assert_eq!(signals[0]["source"], "project_match");
+        let sum: f64 = signals
+            .iter()
+            .map(|x| x["contribution"].as_f64().unwrap())
+            .sum();
+        assert!((sum - f64::from(s.score)).abs() < 1e-5);
     }
 
     // ── 12. test_suggest_rich_vs_plain_equivalence ───────────────────
@@ -7,7 +7,7 @@ use crate::app::AppContext;
 use crate::cli::output::OutputFormat;
 use crate::cli::output::{HumanLayout, emit_human, emit_json};
 use crate::error::Result;
-use crate::sync::{MachineIdentity, SyncConfig, SyncEngine, SyncOptions, SyncState};
+use crate::sync::{MachineIdentity, SyncConfig, SyncEngine, SyncOptions, SyncReport, SyncState};
 
 use super::index::{IndexArgs, run as run_index};
 
@@ -17,6 +17,15 @@ pub struct SyncArgs {
     #[arg(value_name = "REMOTE")]
     pub remote: Option<String>,
 
+    /// Team skill repository (git URL or path) to sync with; only skills
+    /// marked `share: true` are pushed
+    #[arg(
+        long = "remote",
+        value_name = "URL_OR_PATH",
+        conflicts_with_all = ["remote", "status"]
+    )]
+    pub remote_url: Option<String>,
+
     /// Show sync status without syncing
     #[arg(long)]
     pub status: bool,
@@ -64,14 +73,23 @@ pub fn run(ctx: &AppContext, args: &SyncArgs) -> Result<()> {
         pull_only: args.pull_only,
         dry_run: args.dry_run,
         force: args.force,
+        ..Default::default()
     };
 
-    let reports = if let Some(remote) = args.remote.as_deref() {
+    let reports = if let Some(url) = args.remote_url.as_deref() {
+        vec![engine.sync_url(url, &options)?]
+    } else if let Some(remote) = args.remote.as_deref() {
         vec![engine.sync_remote(remote, &options)?]
     } else {
         engine.sync_all(&options)?
     };
 
+    let reindexed = if args.dry_run {
+        0
+    } else {
+        reindex_changed(ctx, &reports)?
+    };
+
     // Check if we should auto-reindex after sync
     let should_reindex = !args.dry_run
         && ctx.config.ru.auto_index
@@ -83,6 +101,7 @@ pub fn run(ctx: &AppContext, args: &SyncArgs) -> Result<()> {
         let mut payload = serde_json::json!({
             "status": "ok",
             "reports": reports,
+            "reindexed": reindexed,
         });
 
         // Auto-reindex if configured and there were changes
@@ -111,11 +130,34 @@ pub fn run(ctx: &AppContext, args: &SyncArgs) -> Result<()> {
                 .kv("Pulled", &report.pulled.len().to_string())
                 .kv("Pushed", &report.pushed.len().to_string())
                 .kv("Resolved", &report.resolved.len().to_string())
+                .kv("Merged", &report.merged.len().to_string())
                 .kv("Conflicts", &report.conflicts.len().to_string())
                 .kv("Forked", &report.forked.len().to_string())
                 .kv("Skipped", &report.skipped.len().to_string())
                 .kv("Duration (ms)", &report.duration_ms.to_string())
                 .blank();
+            if args.dry_run {
+                let incoming: Vec<&String> = report.pulled.iter().chain(&report.merged).collect();
+                for (label, ids) in [
+                    ("Incoming", incoming),
+                    ("Outgoing", report.pushed.iter().collect()),
+                    ("Conflicting", report.conflicts.iter().collect()),
+                ] {
+                    if !ids.is_empty() {
+                        layout.section(label);
+                        for id in ids {
+                            layout.bullet(id);
+                        }
+                        layout.blank();
+                    }
+                }
+            } else if !report.conflict_files.is_empty() {
+                layout.section("Conflict files");
+                for path in &report.conflict_files {
+                    layout.bullet(path);
+                }
+                layout.blank();
+            }
         }
         emit_human(layout);
 
@@ -137,6 +179,28 @@ pub fn run(ctx: &AppContext, args: &SyncArgs) -> Result<()> {
     }
 }
 
+/// Refresh the search index for skills a sync wrote locally.
+fn reindex_changed(ctx: &AppContext, reports: &[SyncReport]) -> Result<usize> {
+    let mut count = 0;
+    for report in reports {
+        for id in report
+            .pulled
+            .iter()
+            .chain(&report.merged)
+            .chain(&report.forked)
+        {
+            if let Some(record) = ctx.db.get_skill(id)? {
+                ctx.search.index_skill(&record)?;
+                count += 1;
+            }
+        }
+    }
+    if count > 0 {
+        ctx.search.commit()?;
+    }
+    Ok(count)
+}
+
 /// Trigger auto-reindex of skills from ru-managed repositories
 fn auto_reindex(ctx: &AppContext) -> Result<()> {
     let index_args = IndexArgs {
@@ -145,6 +209,13 @@ fn auto_reindex(ctx: &AppContext) -> Result<()> {
         force: false,
         all: false,
         from_ru: true,
+        compact: false,
+        no_enrich: false,
+        no_ignore: false,
+        rescore: false,
+        dry_run: false,
+        layer: None,
+        tags: Vec::new(),
     };
     run_index(ctx, &index_args)
 }
@@ -249,4 +320,28 @@ mod tests {
             panic!("expected sync command");
         }
     }
+
+    #[test]
+    fn parse_sync_args_team_remote() {
+        let args = crate::cli::Cli::parse_from([
+            "ms",
+            "sync",
+            "--remote",
+            "git@example.com:team/skills.git",
+            "--dry-run",
+        ]);
+        if let crate::cli::Commands::Sync(sync) = args.command {
+            assert_eq!(
+                sync.remote_url.as_deref(),
+                Some("git@example.com:team/skills.git")
+            );
+            assert_eq!(sync.remote, None);
+        } else {
+            panic!("expected sync command");
+        }
+        assert!(
+            crate::cli::Cli::try_parse_from(["ms", "sync", "origin", "--remote", "/srv/skills"])
+                .is_err()
+        );
+    }
 }
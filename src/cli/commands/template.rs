@@ -8,7 +8,7 @@ use crate::app::AppContext;
 use crate::cli::output::OutputFormat;
 use crate::core::{SkillLayer, spec_lens::parse_markdown};
 use crate::error::{MsError, Result};
-use crate::storage::TxManager;
+use crate::storage::{Provenance, TxManager};
 use crate::templates::{TemplateContext, find_template, list_templates, render_template};
 
 #[derive(Args, Debug)]
@@ -164,7 +164,7 @@ fn run_apply(ctx: &AppContext, args: &TemplateApplyArgs) -> Result<()> {
         Arc::clone(&ctx.git),
         ctx.ms_root.clone(),
     )?;
-    tx_mgr.write_skill_with_layer(&spec, layer)?;
+    tx_mgr.write_skill_with_layer(&spec, layer, &Provenance::new("template"))?;
 
     if let Some(record) = ctx.db.get_skill(&spec.metadata.id)? {
         ctx.search.index_skill(&record)?;
@@ -3,10 +3,14 @@
 use clap::Args;
 
 use crate::app::AppContext;
+use crate::beads::{BeadsClient, CreateIssueRequest};
+use crate::cli::commands::prune::beads_work_dir;
 use crate::cli::output::OutputFormat;
 use crate::cli::output::{HumanLayout, emit_json};
-use crate::error::Result;
-use crate::testing::{SkillTestRunner, TestOptions, TestStatus};
+use crate::error::{MsError, Result};
+use crate::testing::coverage::CoverageSummary;
+use crate::testing::issues::{IssueAction, IssueActionKind, file_issues, plan_issues};
+use crate::testing::{SkillTestReport, SkillTestRunner, TestOptions, TestStatus};
 
 #[derive(Args, Debug)]
 pub struct TestArgs {
@@ -36,6 +40,44 @@ pub struct TestArgs {
     /// Stop on first failure
     #[arg(long)]
     pub fail_fast: bool,
+
+    /// Also execute annotated doctest examples (```bash doctest + ```output)
+    #[arg(long)]
+    pub doctests: bool,
+
+    /// Run destructive `run_command` steps in tests marked `allow_destructive`
+    #[arg(long)]
+    pub allow_destructive: bool,
+
+    /// Max bytes of captured stdout/stderr per command in JSON reports
+    #[arg(long, value_name = "BYTES")]
+    pub output_limit: Option<usize>,
+
+    /// Report which rule and example blocks the tests exercise
+    #[arg(long)]
+    pub coverage: bool,
+
+    /// With --coverage, fail when aggregate coverage is below this percentage
+    #[arg(long, value_name = "PCT", requires = "coverage")]
+    pub min: Option<f64>,
+
+    /// File (or update) a beads issue for each failing test
+    #[arg(long)]
+    pub file_issues: bool,
+
+    /// Close the issue of a test that passes again (default: add a note)
+    #[arg(long, requires = "file_issues")]
+    pub close_fixed: bool,
+
+    /// With --file-issues, print the issues that would be filed without calling bd
+    #[arg(long, requires = "file_issues")]
+    pub dry_run: bool,
+}
+
+/// Outcome of `--file-issues`.
+enum FiledIssues {
+    Planned(Vec<(IssueAction, CreateIssueRequest)>),
+    Applied(Vec<IssueAction>),
 }
 
 pub fn run(ctx: &AppContext, args: &TestArgs) -> Result<()> {
@@ -46,6 +88,10 @@ pub fn run(ctx: &AppContext, args: &TestArgs) -> Result<()> {
         include_tags: parse_tags(args.tags.as_deref()),
         exclude_tags: parse_tags(args.exclude_tags.as_deref()),
         timeout_override: args.timeout.as_deref().and_then(parse_duration),
+        doctests: args.doctests,
+        allow_destructive: args.allow_destructive,
+        output_limit: args.output_limit,
+        coverage: args.coverage,
     };
 
     let runner = SkillTestRunner::new(ctx, options);
@@ -53,28 +99,91 @@ pub fn run(ctx: &AppContext, args: &TestArgs) -> Result<()> {
     let reports = if args.all || args.skill.is_none() {
         runner.run_all()?
     } else {
-        vec![runner.run_for_skill(args.skill.as_ref().unwrap())?]
+        let skill_id = ctx.canonical_skill_id(args.skill.as_ref().unwrap())?;
+        vec![runner.run_for_skill(&skill_id)?]
     };
 
+    let filed = if !args.file_issues {
+        None
+    } else if args.dry_run {
+        Some(FiledIssues::Planned(plan_issues(&reports)))
+    } else {
+        let client = BeadsClient::new().with_work_dir(beads_work_dir(&ctx.ms_root));
+        if !client.is_available() {
+            return Err(MsError::BeadsUnavailable(
+                "bd not available (install beads or configure PATH)".to_string(),
+            ));
+        }
+        Some(FiledIssues::Applied(file_issues(
+            &client,
+            &reports,
+            args.close_fixed,
+        )?))
+    };
+
+    let coverage = args
+        .coverage
+        .then(|| CoverageSummary::aggregate(reports.iter().filter_map(|r| r.coverage.as_ref())));
+
     if ctx.output_format != OutputFormat::Human {
         let status = if reports.iter().any(|r| !r.success()) {
             "partial"
         } else {
             "ok"
         };
-        let payload = serde_json::json!({
+        let mut payload = serde_json::json!({
             "status": status,
             "count": reports.len(),
             "reports": reports,
         });
-        emit_json(&payload)
+        match &filed {
+            Some(FiledIssues::Planned(plan)) => {
+                payload["dry_run"] = serde_json::Value::Bool(true);
+                payload["issues"] = serde_json::json!(
+                    plan.iter()
+                        .map(|(action, request)| serde_json::json!({
+                            "action": action,
+                            "request": request,
+                        }))
+                        .collect::<Vec<_>>()
+                );
+            }
+            Some(FiledIssues::Applied(actions)) => {
+                payload["issues"] = serde_json::json!(actions);
+            }
+            None => {}
+        }
+        if let Some(summary) = &coverage {
+            // The per-block matrix is in each report's `coverage`
+            payload["coverage"] = serde_json::json!({
+                "covered": summary.covered,
+                "total": summary.total,
+                "percent": summary.percent,
+                "min": args.min,
+                "passed": args.min.is_none_or(|min| summary.percent >= min),
+            });
+        }
+        emit_json(&payload)?;
     } else {
-        render_human(&reports);
-        Ok(())
+        render_human(&reports, coverage.as_ref(), filed.as_ref());
+    }
+
+    match (coverage, args.min) {
+        (Some(summary), Some(min)) if summary.percent < min => {
+            Err(MsError::ValidationFailed(format!(
+                "test coverage {:.1}% is below the minimum {min}%",
+                summary.percent
+            )))
+        }
+        _ => Ok(()),
     }
 }
 
-fn render_human(reports: &[crate::testing::SkillTestReport]) {
+fn render_human(
+    reports: &[SkillTestReport],
+    coverage: Option<&CoverageSummary>,
+    filed: Option<&FiledIssues>,
+) {
     let mut layout = HumanLayout::new();
     layout.title("Skill Tests");
 
@@ -86,8 +195,11 @@ fn render_human(reports: &[crate::testing::SkillTestReport]) {
             .kv("Passed", &report.passed.to_string())
             .kv("Failed", &report.failed.to_string())
             .kv("Skipped", &report.skipped.to_string())
-            .kv("Duration", &format!("{}ms", report.duration_ms))
-            .blank();
+            .kv("Duration", &format!("{}ms", report.duration_ms));
+        if !report.covered_blocks.is_empty() {
+            layout.kv("Covered blocks", &report.covered_blocks.join(", "));
+        }
+        layout.blank();
 
         for result in &report.results {
             let line = match result.status {
@@ -104,6 +216,72 @@ fn render_human(reports: &[crate::testing::SkillTestReport]) {
             }
         }
         layout.blank();
+
+        if let Some(skill) = &report.coverage {
+            layout.kv(
+                "Coverage",
+                &format!(
+                    "{:.1}% ({}/{} blocks)",
+                    skill.percent, skill.covered, skill.total
+                ),
+            );
+            for block in skill.uncovered_rules() {
+                layout.push_line(format!("  [NO TEST] rule {}", block.block_id));
+            }
+            for block in skill.unexecuted_examples() {
+                layout.push_line(format!("  [NOT RUN] example {}", block.block_id));
+            }
+            layout.blank();
+        }
+    }
+
+    if let Some(summary) = coverage {
+        layout.section("Coverage").kv(
+            "Aggregate",
+            &format!(
+                "{:.1}% ({}/{} blocks)",
+                summary.percent, summary.covered, summary.total
+            ),
+        );
+        layout.blank();
+    }
+
+    match filed {
+        Some(FiledIssues::Planned(plan)) => {
+            layout.section("Issues (dry run)");
+            if plan.is_empty() {
+                layout.push_line("No failing tests; nothing to file");
+            }
+            for (_, request) in plan {
+                layout.push_line(format!("[NEW] {}", request.title));
+                layout.push_line(format!("  labels: {}", request.labels.join(", ")));
+                for line in request.description.as_deref().unwrap_or_default().lines() {
+                    layout.push_line(format!("  {line}"));
+                }
+                layout.blank();
+            }
+        }
+        Some(FiledIssues::Applied(actions)) => {
+            layout.section("Issues");
+            if actions.is_empty() {
+                layout.push_line("No issues filed or updated");
+            }
+            for action in actions {
+                let tag = match action.kind {
+                    IssueActionKind::Create => "NEW",
+                    IssueActionKind::Update => "UPDATE",
+                    IssueActionKind::Comment => "NOTE",
+                    IssueActionKind::Close => "CLOSE",
+                };
+                layout.push_line(format!(
+                    "[{tag}] {} {}",
+                    action.issue_id.as_deref().unwrap_or("-"),
+                    action.title
+                ));
+            }
+            layout.blank();
+        }
+        None => {}
     }
 
     crate::cli::output::emit_human(layout);
@@ -0,0 +1,335 @@
+//! ms trial - Report on and list skills loaded with `ms load --trial`.
+//!
+//! A trial records which skill was served, at what disclosure level, and in
+//! which working context. Reporting its outcome closes it and feeds the
+//! contextual bandit and the skill's quality score (see
+//! [`crate::quality::trials`]).
+
+use clap::{Args, Subcommand, ValueEnum};
+use serde::Serialize;
+use tracing::warn;
+
+use crate::app::AppContext;
+use crate::cli::commands::prune::parse_age_days;
+use crate::cli::commands::quality::refresh_quality;
+use crate::cli::output::{HumanLayout, OutputFormat, emit_json};
+use crate::context::{ContextCapture, ContextFingerprint};
+use crate::core::disclosure::DisclosureLevel;
+use crate::error::{MsError, Result};
+use crate::quality::trials::{EXPIRY_DAYS, TrialOutcome, TrialStats};
+use crate::storage::SkillTrialRecord;
+use crate::suggestions::bandit::ContextFeatures;
+
+#[derive(Args, Debug)]
+pub struct TrialArgs {
+    #[command(subcommand)]
+    pub command: TrialCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TrialCommand {
+    /// Close a trial with its outcome
+    Report(TrialReportArgs),
+    /// List trials
+    List(TrialListArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct TrialReportArgs {
+    /// Trial id printed by `ms load --trial`
+    pub trial_id: String,
+
+    /// How the trial went
+    #[arg(long, value_enum)]
+    pub outcome: CliTrialOutcome,
+
+    /// What helped or got in the way
+    #[arg(long)]
+    pub notes: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct TrialListArgs {
+    /// Only trials that have not been reported yet
+    #[arg(long)]
+    pub open: bool,
+
+    /// Only trials started longer ago than this (e.g. 7d, 2w)
+    #[arg(long, value_name = "AGE", value_parser = parse_age_days)]
+    pub older_than: Option<u32>,
+
+    /// Only trials of this skill
+    #[arg(long)]
+    pub skill: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CliTrialOutcome {
+    Success,
+    Failure,
+    Neutral,
+}
+
+impl From<CliTrialOutcome> for TrialOutcome {
+    fn from(value: CliTrialOutcome) -> Self {
+        match value {
+            CliTrialOutcome::Success => Self::Success,
+            CliTrialOutcome::Failure => Self::Failure,
+            CliTrialOutcome::Neutral => Self::Neutral,
+        }
+    }
+}
+
+pub fn run(ctx: &AppContext, args: &TrialArgs) -> Result<()> {
+    match &args.command {
+        TrialCommand::Report(report) => run_report(ctx, report),
+        TrialCommand::List(list) => run_list(ctx, list),
+    }
+}
+
+/// Open a trial for a skill just served at `level`; returns its id.
+pub(crate) fn start_trial(
+    ctx: &AppContext,
+    skill_id: &str,
+    level: DisclosureLevel,
+) -> Result<String> {
+    let record = SkillTrialRecord {
+        id: uuid::Uuid::new_v4().to_string(),
+        skill_id: skill_id.to_string(),
+        context_fingerprint: context_fingerprint(),
+        disclosure_level: level.name().to_string(),
+        started_at: chrono::Utc::now().to_rfc3339(),
+        outcome: None,
+        notes: None,
+        closed_at: None,
+    };
+    ctx.db.insert_skill_trial(&record)?;
+    Ok(record.id)
+}
+
+/// Fingerprint of the current working context, or `unknown` when it cannot
+/// be captured.
+fn context_fingerprint() -> String {
+    ContextCapture::capture_current(None).map_or_else(
+        |_| "unknown".to_string(),
+        |capture| format!("{:016x}", ContextFingerprint::capture(&capture).as_u64()),
+    )
+}
+
+/// A closed trial and what it changed.
+#[derive(Debug, Serialize)]
+pub(crate) struct TrialReport {
+    pub trial: SkillTrialRecord,
+    pub reward: f32,
+    /// Stored quality score after the report (`None` if not indexed)
+    pub quality_score: Option<f32>,
+    pub stats: TrialStats,
+}
+
+/// Close an open trial with `outcome`, then update the contextual bandit and
+/// the skill's stored quality score.
+pub(crate) fn report_trial(
+    ctx: &AppContext,
+    trial_id: &str,
+    outcome: TrialOutcome,
+    notes: Option<&str>,
+) -> Result<TrialReport> {
+    let trial = ctx
+        .db
+        .get_skill_trial(trial_id)?
+        .ok_or_else(|| MsError::NotFound(format!("trial not found: {trial_id}")))?;
+    let closed_at = chrono::Utc::now().to_rfc3339();
+    if !ctx
+        .db
+        .close_skill_trial(trial_id, outcome.as_str(), notes, &closed_at)?
+    {
+        return Err(MsError::ValidationFailed(format!(
+            "trial {trial_id} is already closed ({})",
+            trial.outcome.as_deref().unwrap_or("unknown")
+        )));
+    }
+
+    let reward = outcome.reward();
+    let features = ContextFeatures::default();
+    if let Err(e) = ctx.update_bandit(None, |bandit| {
+        bandit.update_with_reward(&trial.skill_id, &features, reward);
+    }) {
+        // Best-effort, as in `ms feedback add`
+        warn!("failed to update bandit: {e}");
+    }
+    let quality_score = match refresh_quality(ctx, &trial.skill_id) {
+        Ok(score) => score,
+        Err(e) => {
+            warn!("failed to update quality score: {e}");
+            None
+        }
+    };
+
+    let stats = ctx
+        .db
+        .list_trial_stats(Some(&trial.skill_id))?
+        .into_iter()
+        .next()
+        .unwrap_or_default();
+    let trial = ctx.db.get_skill_trial(trial_id)?.unwrap_or(trial);
+    Ok(TrialReport {
+        trial,
+        reward,
+        quality_score,
+        stats,
+    })
+}
+
+fn run_report(ctx: &AppContext, args: &TrialReportArgs) -> Result<()> {
+    let report = report_trial(
+        ctx,
+        &args.trial_id,
+        args.outcome.into(),
+        args.notes.as_deref(),
+    )?;
+
+    if ctx.output_format != OutputFormat::Human {
+        let payload = serde_json::json!({
+            "status": "ok",
+            "report": report,
+        });
+        return emit_json(&payload);
+    }
+
+    let mut layout = HumanLayout::new();
+    layout
+        .title("Trial Reported")
+        .kv("Trial", &report.trial.id)
+        .kv("Skill", &report.trial.skill_id)
+        .kv(
+            "Outcome",
+            report.trial.outcome.as_deref().unwrap_or_default(),
+        )
+        .kv("Reward", &format!("{:.2}", report.reward));
+    if let Some(score) = report.quality_score {
+        layout.kv("Quality", &format!("{score:.2}"));
+    }
+    layout.kv("Trials", &describe_stats(&report.stats));
+    crate::cli::output::emit_human(layout);
+    Ok(())
+}
+
+fn run_list(ctx: &AppContext, args: &TrialListArgs) -> Result<()> {
+    let now = chrono::Utc::now();
+    let started_before = args
+        .older_than
+        .map(|days| (now - chrono::Duration::days(i64::from(days))).to_rfc3339());
+    let mut trials = ctx
+        .db
+        .list_skill_trials(args.open, started_before.as_deref())?;
+    if let Some(skill) = &args.skill {
+        let skill_id = ctx.resolve_skill_id(skill)?;
+        trials.retain(|trial| trial.skill_id == skill_id);
+    }
+    let dangling_before = (now - chrono::Duration::days(i64::from(EXPIRY_DAYS))).to_rfc3339();
+    let is_dangling =
+        |trial: &SkillTrialRecord| trial.closed_at.is_none() && trial.started_at < dangling_before;
+
+    if ctx.output_format != OutputFormat::Human {
+        let trials = trials
+            .iter()
+            .map(|trial| {
+                let mut value = serde_json::to_value(trial)?;
+                value["dangling"] = serde_json::json!(is_dangling(trial));
+                Ok(value)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let payload = serde_json::json!({
+            "status": "ok",
+            "expiry_days": EXPIRY_DAYS,
+            "trials": trials,
+        });
+        return emit_json(&payload);
+    }
+
+    if trials.is_empty() {
+        println!("No trials.");
+        return Ok(());
+    }
+    let mut layout = HumanLayout::new();
+    layout.title("Skill Trials");
+    for trial in &trials {
+        let status = match &trial.outcome {
+            Some(outcome) => outcome.clone(),
+            None if is_dangling(trial) => "open, dangling".to_string(),
+            None => "open".to_string(),
+        };
+        layout.kv(
+            &trial.id,
+            &format!(
+                "{} · {} · {} · started {}",
+                trial.skill_id, trial.disclosure_level, status, trial.started_at
+            ),
+        );
+    }
+    if trials.iter().any(is_dangling) {
+        layout.blank();
+        layout.bullet(&format!(
+            "Trials open for more than {EXPIRY_DAYS} days are expired by `ms prune --expired-trials --apply`"
+        ));
+    }
+    crate::cli::output::emit_human(layout);
+    Ok(())
+}
+
+/// `3 reported (2 success, 1 failure, 0 neutral), 1 open`
+pub(crate) fn describe_stats(stats: &TrialStats) -> String {
+    format!(
+        "{} reported ({} success, {} failure, {} neutral), {} open",
+        stats.reported(),
+        stats.success,
+        stats.failure,
+        stats.neutral,
+        stats.open
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct TestCli {
+        #[command(subcommand)]
+        cmd: TrialCommand,
+    }
+
+    #[test]
+    fn parse_trial_report() {
+        let parsed = TestCli::parse_from([
+            "test",
+            "report",
+            "abc-123",
+            "--outcome",
+            "success",
+            "--notes",
+            "fixed the build",
+        ]);
+        let TrialCommand::Report(args) = parsed.cmd else {
+            panic!("expected report");
+        };
+        assert_eq!(args.trial_id, "abc-123");
+        assert_eq!(TrialOutcome::from(args.outcome), TrialOutcome::Success);
+        assert_eq!(args.notes.as_deref(), Some("fixed the build"));
+        assert!(TestCli::try_parse_from(["test", "report", "abc-123"]).is_err());
+        assert!(
+            TestCli::try_parse_from(["test", "report", "abc-123", "--outcome", "maybe"]).is_err()
+        );
+    }
+
+    #[test]
+    fn parse_trial_list_open() {
+        let parsed = TestCli::parse_from(["test", "list", "--open", "--older-than", "2w"]);
+        let TrialCommand::List(args) = parsed.cmd else {
+            panic!("expected list");
+        };
+        assert!(args.open);
+        assert_eq!(args.older_than, Some(14));
+    }
+}
@@ -7,7 +7,7 @@ use colored::Colorize;
 
 use crate::app::AppContext;
 use crate::cli::output::OutputFormat;
-use crate::error::{MsError, Result};
+use crate::error::Result;
 
 #[derive(Args, Debug)]
 pub struct UnhideArgs {
@@ -16,7 +16,7 @@ pub struct UnhideArgs {
 }
 
 pub fn run(ctx: &AppContext, args: &UnhideArgs) -> Result<()> {
-    let skill_id = resolve_skill_id(ctx, &args.skill)?;
+    let skill_id = ctx.resolve_skill_id(&args.skill)?;
 
     let removed = ctx.db.remove_user_preference(&skill_id, "hidden")?;
 
@@ -42,18 +42,6 @@ pub fn run(ctx: &AppContext, args: &UnhideArgs) -> Result<()> {
     Ok(())
 }
 
-fn resolve_skill_id(ctx: &AppContext, input: &str) -> Result<String> {
-    if let Some(skill) = ctx.db.get_skill(input)? {
-        return Ok(skill.id);
-    }
-    if let Ok(Some(alias)) = ctx.db.resolve_alias(input) {
-        if let Some(skill) = ctx.db.get_skill(&alias.canonical_id)? {
-            return Ok(skill.id);
-        }
-    }
-    Err(MsError::SkillNotFound(format!("skill not found: {input}")))
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
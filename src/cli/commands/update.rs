@@ -1,10 +1,12 @@
 //! ms update - Check for and apply updates
 
-use clap::Args;
+use clap::{Args, ValueEnum};
 use semver::Version;
 
 use crate::app::AppContext;
-use crate::cli::output::OutputFormat;
+use crate::bundler::local_safety::ConflictStrategy;
+use crate::cli::commands::bundle::{BundleUpdatePlan, BundleUpdateRequest, update_bundles};
+use crate::cli::output::{OutputFormat, emit_json};
 use crate::error::{MsError, Result};
 use crate::updater::{
     UpdateChannel, UpdateCheckResponse, UpdateChecker, UpdateDownloader, UpdateInstallResponse,
@@ -33,9 +35,50 @@ pub struct UpdateArgs {
     /// Update channel (stable, beta, nightly)
     #[arg(long)]
     pub channel: Option<String>,
+
+    /// Update installed bundles instead of ms itself (default: all bundles)
+    #[arg(long, num_args = 0.., value_name = "NAME")]
+    pub bundles: Option<Vec<String>>,
+
+    /// How to treat locally modified bundle files (default: skip the bundle)
+    #[arg(long, value_enum, requires = "bundles")]
+    pub strategy: Option<CliUpdateStrategy>,
+
+    /// Accept bundles signed by a key other than the one pinned at install
+    #[arg(long, requires = "bundles")]
+    pub accept_new_key: bool,
+
+    /// Show the bundle update plan without applying it
+    #[arg(long, requires = "bundles")]
+    pub dry_run: bool,
+}
+
+/// Conflict strategies offered by `ms update --bundles`.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CliUpdateStrategy {
+    /// Replace modified files with the bundle's
+    Overwrite,
+    /// Keep modified files, update the rest
+    KeepLocal,
+    /// Back up modified files, then replace them
+    Backup,
+}
+
+impl From<CliUpdateStrategy> for ConflictStrategy {
+    fn from(strategy: CliUpdateStrategy) -> Self {
+        match strategy {
+            CliUpdateStrategy::Overwrite => Self::PreferBundle,
+            CliUpdateStrategy::KeepLocal => Self::PreferLocal,
+            CliUpdateStrategy::Backup => Self::BackupAndReplace,
+        }
+    }
 }
 
 pub fn run(ctx: &AppContext, args: &UpdateArgs) -> Result<()> {
+    if let Some(names) = &args.bundles {
+        return run_bundles(ctx, args, names);
+    }
+
     let current_version =
         Version::parse(env!("CARGO_PKG_VERSION")).unwrap_or_else(|_| Version::new(0, 1, 0));
 
@@ -60,6 +103,85 @@ pub fn run(ctx: &AppContext, args: &UpdateArgs) -> Result<()> {
     Ok(())
 }
 
+fn run_bundles(ctx: &AppContext, args: &UpdateArgs, names: &[String]) -> Result<()> {
+    let dry_run = args.dry_run || args.check;
+    let request = BundleUpdateRequest {
+        names,
+        strategy: args.strategy.map_or(ConflictStrategy::Abort, Into::into),
+        accept_new_key: args.accept_new_key,
+        dry_run,
+    };
+    let plans = update_bundles(ctx, &request)?;
+
+    if ctx.output_format != OutputFormat::Human {
+        let payload = serde_json::json!({
+            "status": "ok",
+            "dry_run": dry_run,
+            "bundles": plans,
+        });
+        return emit_json(&payload);
+    }
+
+    if plans.is_empty() {
+        println!("No bundles installed.");
+        return Ok(());
+    }
+    println!("Bundle update plan:");
+    for plan in &plans {
+        print_bundle_plan(plan);
+    }
+    if dry_run && plans.iter().any(|plan| plan.update_available) {
+        println!("\nRun `ms update --bundles` to apply.");
+    }
+    Ok(())
+}
+
+fn print_bundle_plan(plan: &BundleUpdatePlan) {
+    let Some(available) = plan.available_version.as_deref() else {
+        let note = plan
+            .error
+            .as_deref()
+            .or(plan.skipped_reason.as_deref())
+            .unwrap_or("no release found");
+        println!("  {} {}: {note}", plan.bundle_id, plan.current_version);
+        return;
+    };
+    if !plan.update_available {
+        println!(
+            "  ✓ {} {} is up to date",
+            plan.bundle_id, plan.current_version
+        );
+        return;
+    }
+
+    let mut details = vec![format!("{} skills changed", plan.changed_skills)];
+    if !plan.locally_modified.is_empty() {
+        details.push(format!(
+            "locally modified: {}",
+            plan.locally_modified.join(", ")
+        ));
+    }
+    if plan.key_changed {
+        details.push("signing key changed".to_string());
+    }
+    let status = if plan.applied {
+        "updated".to_string()
+    } else if let Some(err) = &plan.error {
+        format!("failed: {err}")
+    } else {
+        plan.skipped_reason.as_deref().map_or_else(
+            || "pending".to_string(),
+            |reason| format!("skipped: {reason}"),
+        )
+    };
+    println!(
+        "  {} {} → {available} ({}) {status}",
+        plan.bundle_id,
+        plan.current_version,
+        details.join(", ")
+    );
+}
+
 fn run_robot(
     args: &UpdateArgs,
     checker: &UpdateChecker,
@@ -266,3 +388,50 @@ fn run_interactive(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct TestCli {
+        #[command(flatten)]
+        args: UpdateArgs,
+    }
+
+    #[test]
+    fn parse_bundles_with_and_without_names() {
+        let parsed = TestCli::parse_from(["test", "--bundles"]);
+        assert_eq!(parsed.args.bundles, Some(Vec::new()));
+
+        let parsed = TestCli::parse_from([
+            "test",
+            "--bundles",
+            "rust-patterns",
+            "go-patterns",
+            "--strategy",
+            "keep-local",
+            "--accept-new-key",
+            "--dry-run",
+        ]);
+        assert_eq!(
+            parsed.args.bundles,
+            Some(vec!["rust-patterns".to_string(), "go-patterns".to_string()])
+        );
+        assert_eq!(parsed.args.strategy, Some(CliUpdateStrategy::KeepLocal));
+        assert!(parsed.args.accept_new_key);
+        assert!(parsed.args.dry_run);
+        assert_eq!(
+            ConflictStrategy::from(CliUpdateStrategy::Backup),
+            ConflictStrategy::BackupAndReplace
+        );
+    }
+
+    #[test]
+    fn bundle_flags_require_bundles() {
+        assert!(TestCli::try_parse_from(["test", "--dry-run"]).is_err());
+        assert!(TestCli::try_parse_from(["test", "--strategy", "overwrite"]).is_err());
+        assert!(TestCli::try_parse_from(["test", "--accept-new-key"]).is_err());
+    }
+}
@@ -0,0 +1,290 @@
+//! ms why-suggested - Explain a past suggestion from its stored record
+//!
+//! Looks up the suggestion records persisted by `ms suggest` and renders the
+//! explanation captured at the time, plus what has changed since (skill
+//! edits, favorites removed, cooldowns lapsed, feedback received).
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use clap::Args;
+
+use crate::app::AppContext;
+use crate::cli::output::{HumanLayout, OutputFormat, emit_json};
+use crate::error::{MsError, Result};
+use crate::suggestions::SuggestionRecord;
+use crate::suggestions::explanation::ChangesSince;
+
+#[derive(Args, Debug)]
+pub struct WhySuggestedArgs {
+    /// Skill that was suggested
+    pub skill: String,
+
+    /// Only consider suggestions made in this agent session
+    #[arg(long)]
+    pub session: Option<String>,
+
+    /// Pick the suggestion closest to this time (RFC 3339 or YYYY-MM-DD[ HH:MM])
+    #[arg(long)]
+    pub at: Option<String>,
+
+    /// Number of matching suggestions to show
+    #[arg(long, default_value = "1")]
+    pub limit: usize,
+}
+
+pub fn run(ctx: &AppContext, args: &WhySuggestedArgs) -> Result<()> {
+    let at = args.at.as_deref().map(parse_at).transpose()?;
+    // Removed skills keep their records, so unknown ids pass through unchanged
+    let skill_id = ctx.canonical_skill_id(&args.skill)?;
+    let records = select_records(
+        ctx.db
+            .list_suggestion_records(&skill_id, args.session.as_deref())?,
+        at,
+        args.limit,
+    );
+    if records.is_empty() {
+        return Err(MsError::NotFound(format!(
+            "no stored suggestions of '{skill_id}'{} (records are kept {} days)",
+            args.session
+                .as_deref()
+                .map(|session| format!(" in session {session}"))
+                .unwrap_or_default(),
+            ctx.config.suggestions.retention_days
+        )));
+    }
+
+    let current = ctx.db.get_skill(&skill_id)?;
+    let favorite_now = ctx
+        .db
+        .has_user_preference(&skill_id, "favorite")
+        .unwrap_or(false);
+    let feedback = ctx.db.list_skill_feedback(Some(&skill_id), 100, 0)?;
+    let now = Utc::now();
+    let explained: Vec<(SuggestionRecord, Option<ChangesSince>)> = records
+        .into_iter()
+        .map(|record| {
+            let changes = record.explanation.as_ref().map(|explanation| {
+                ChangesSince::compute(
+                    explanation,
+                    record.shown_at,
+                    current.as_ref(),
+                    favorite_now,
+                    &feedback,
+                    now,
+                )
+            });
+            (record, changes)
+        })
+        .collect();
+
+    if ctx.output_format != OutputFormat::Human {
+        let records: Vec<serde_json::Value> = explained
+            .iter()
+            .map(|(record, changes)| {
+                serde_json::json!({
+                    "record": record,
+                    "changes_since": changes,
+                })
+            })
+            .collect();
+        let payload = serde_json::json!({
+            "status": "ok",
+            "skill_id": skill_id,
+            "records": records,
+        });
+        return emit_json(&payload);
+    }
+
+    crate::cli::output::emit_human(render_human(&skill_id, &explained));
+    Ok(())
+}
+
+/// Newest first, or closest to `at` when given.
+fn select_records(
+    mut records: Vec<SuggestionRecord>,
+    at: Option<DateTime<Utc>>,
+    limit: usize,
+) -> Vec<SuggestionRecord> {
+    match at {
+        Some(at) => records.sort_by_key(|record| (record.shown_at - at).abs()),
+        None => records.sort_by(|a, b| b.shown_at.cmp(&a.shown_at)),
+    }
+    records.truncate(limit.max(1));
+    records
+}
+
+fn parse_at(raw: &str) -> Result<DateTime<Utc>> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(raw) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+    if let Ok(dt) = NaiveDateTime::parse_from_str(raw, "%Y-%m-%d %H:%M") {
+        return Ok(dt.and_utc());
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(raw, "%Y-%m-%d") {
+        return Ok(date.and_time(chrono::NaiveTime::MIN).and_utc());
+    }
+    Err(MsError::ValidationFailed(format!(
+        "invalid --at '{raw}': expected RFC 3339 or YYYY-MM-DD[ HH:MM]"
+    )))
+}
+
+fn render_human(
+    skill_id: &str,
+    explained: &[(SuggestionRecord, Option<ChangesSince>)],
+) -> HumanLayout {
+    let mut layout = HumanLayout::new();
+    layout.title(&format!("Why {skill_id} was suggested"));
+
+    for (record, changes) in explained {
+        layout
+            .section(&format!(
+                "{} (position {})",
+                record.shown_at.to_rfc3339(),
+                record.position + 1
+            ))
+            .kv("Session", record.session_id.as_deref().unwrap_or("-"))
+            .kv("Outcome", record.outcome.as_str());
+
+        let Some(explanation) = &record.explanation else {
+            layout
+                .push_line("No explanation was stored with this suggestion.")
+                .blank();
+            continue;
+        };
+        let signals = &explanation.signals;
+        let context = &explanation.context;
+        let preferences = &explanation.preferences;
+        layout.kv("Score", &format!("{:.2}", explanation.score));
+        if let Some(task) = &explanation.task {
+            layout.kv(
+                "Task",
+                &format!(
+                    "{task} (suggested from the {})",
+                    explanation.origin.as_str()
+                ),
+            );
+        }
+        layout
+            .kv(
+                "Signals",
+                &format!(
+                    "context {:.2}, thompson {:.2}, exploration {:.2}, personal {:.2}, search {:.2}, task {:.2}",
+                    signals.contextual,
+                    signals.thompson,
+                    signals.exploration_bonus,
+                    signals.personal_boost,
+                    signals.search_relevance,
+                    signals.task_relevance
+                ),
+            )
+            .kv(
+                "Bandit arm",
+                &format!(
+                    "p={:.2} after {} pulls (avg reward {:.2})",
+                    explanation.bandit.sampled_probability,
+                    explanation.bandit.pull_count,
+                    explanation.bandit.avg_reward
+                ),
+            )
+            .kv(
+                "Context",
+                &format!(
+                    "{}{} [{}]",
+                    context.cwd.as_deref().unwrap_or("?"),
+                    context
+                        .git_branch
+                        .as_deref()
+                        .map(|branch| format!(" ({branch})"))
+                        .unwrap_or_default(),
+                    context.fingerprint
+                ),
+            )
+            .kv(
+                "Favorite",
+                if preferences.favorite {
+                    "yes (pinned)"
+                } else {
+                    "no"
+                },
+            );
+        let cooldown = match (preferences.cooldowns_ignored, preferences.cooldown_until) {
+            _ if explanation.task.is_some() => "not applied (--task)".to_string(),
+            (true, _) => "ignored (--ignore-cooldowns)".to_string(),
+            (false, Some(until)) => format!("until {}", until.to_rfc3339()),
+            (false, None) => "none".to_string(),
+        };
+        layout.kv("Cooldown", &cooldown);
+        if explanation.discovery {
+            layout.kv("Section", "discovery");
+        }
+        for trigger in &explanation.matched_triggers {
+            layout.bullet(trigger);
+        }
+        if !context.recent_files.is_empty() {
+            layout.kv("Recent files", &context.recent_files.join(", "));
+        }
+        if explanation.truncated {
+            layout.push_line("(explanation was truncated to fit the size cap)");
+        }
+
+        let lines = changes
+            .as_ref()
+            .map(ChangesSince::describe)
+            .unwrap_or_default();
+        if lines.is_empty() {
+            layout.kv("Since then", "nothing changed");
+        } else {
+            layout.push_line("Since then:");
+            for line in lines {
+                layout.bullet(&line);
+            }
+        }
+        layout.blank();
+    }
+    layout
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::suggestions::tracking::SuggestionOutcome;
+
+    fn record(hours_ago: i64) -> SuggestionRecord {
+        SuggestionRecord {
+            skill_id: "terraform".to_string(),
+            shown_at: Utc::now() - chrono::Duration::hours(hours_ago),
+            context_fingerprint_hash: None,
+            position: 0,
+            outcome: SuggestionOutcome::Pending,
+            session_id: None,
+            agent_id: None,
+            explanation: None,
+        }
+    }
+
+    #[test]
+    fn selects_newest_or_closest() {
+        let records = vec![record(30), record(1), record(10)];
+        let newest = select_records(records.clone(), None, 1);
+        assert_eq!(newest[0].shown_at, records[1].shown_at);
+
+        let at = Utc::now() - chrono::Duration::hours(28);
+        let closest = select_records(records.clone(), Some(at), 1);
+        assert_eq!(closest[0].shown_at, records[0].shown_at);
+
+        assert_eq!(select_records(records, None, 5).len(), 3);
+    }
+
+    #[test]
+    fn parses_at_formats() {
+        assert!(parse_at("2026-03-01T12:00:00Z").is_ok());
+        assert_eq!(
+            parse_at("2026-03-01 12:30").unwrap().to_rfc3339(),
+            "2026-03-01T12:30:00+00:00"
+        );
+        assert_eq!(
+            parse_at("2026-03-01").unwrap().to_rfc3339(),
+            "2026-03-01T00:00:00+00:00"
+        );
+        assert!(parse_at("yesterday").is_err());
+    }
+}
@@ -7,8 +7,9 @@
 use serde::Serialize;
 use tracing::debug;
 
-use crate::cli::output::{Formattable, OutputFormat};
+use crate::cli::output::{Formattable, OutputFormat, tsv_as_csv};
 use crate::output::{is_agent_environment, is_ci_environment, search_results_table, warning_panel};
+use crate::search::{SearchContext, SearchSnippet};
 use crate::storage::sqlite::SkillRecord;
 
 /// Search result item with score
@@ -20,6 +21,8 @@ pub struct SearchResultItem {
     pub score: f32,
     /// Optional snippet of matching content
     pub snippet: Option<String>,
+    /// Matched fragments with highlight offsets (empty for semantic-only matches)
+    pub snippets: Vec<SearchSnippet>,
 }
 
 /// Search results collection for formatted display
@@ -33,6 +36,10 @@ pub struct SearchResults {
     pub results: Vec<SearchResultItem>,
     /// Search duration in milliseconds
     pub duration_ms: Option<u64>,
+    /// Project the results were boosted for
+    pub context: Option<SearchContext>,
+    /// Cursor of the next page, when more results exist
+    pub next_cursor: Option<String>,
 }
 
 /// Serializable search result for JSON output
@@ -47,6 +54,7 @@ struct SearchResultJson {
     is_deprecated: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     snippet: Option<String>,
+    snippets: Vec<SearchSnippet>,
 }
 
 /// Serializable search response for JSON output
@@ -58,7 +66,18 @@ struct SearchResponseJson {
     count: usize,
     #[serde(skip_serializing_if = "Option::is_none")]
     duration_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    context: Option<SearchContextJson>,
     results: Vec<SearchResultJson>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_cursor: Option<String>,
+}
+
+/// Serializable project context for JSON output
+#[derive(Debug, Clone, Serialize)]
+struct SearchContextJson {
+    root: String,
+    project_types: Vec<&'static str>,
 }
 
 impl SearchResults {
@@ -69,6 +88,8 @@ impl SearchResults {
             search_type: search_type.into(),
             results: Vec::new(),
             duration_ms: None,
+            context: None,
+            next_cursor: None,
         }
     }
 
@@ -78,6 +99,7 @@ impl SearchResults {
             skill,
             score,
             snippet: None,
+            snippets: Vec::new(),
         });
     }
 
@@ -92,6 +114,7 @@ impl SearchResults {
             skill,
             score,
             snippet: Some(snippet.into()),
+            snippets: Vec::new(),
         });
     }
 
@@ -102,6 +125,20 @@ impl SearchResults {
         self
     }
 
+    /// Set the project context the results were boosted for
+    #[must_use]
+    pub fn with_context(mut self, context: SearchContext) -> Self {
+        self.context = Some(context);
+        self
+    }
+
+    /// Set the cursor of the next page
+    #[must_use]
+    pub fn with_next_cursor(mut self, next_cursor: Option<String>) -> Self {
+        self.next_cursor = next_cursor;
+        self
+    }
+
     /// Build from tuples (for compatibility with existing code)
     pub fn from_tuples(
         query: impl Into<String>,
@@ -122,6 +159,10 @@ impl SearchResults {
             search_type: self.search_type.clone(),
             count: self.results.len(),
             duration_ms: self.duration_ms,
+            context: self.context.as_ref().map(|context| SearchContextJson {
+                root: context.root.display().to_string(),
+                project_types: context.type_ids(),
+            }),
             results: self
                 .results
                 .iter()
@@ -134,8 +175,10 @@ impl SearchResults {
                     quality: r.skill.quality_score,
                     is_deprecated: r.skill.is_deprecated,
                     snippet: r.snippet.clone(),
+                    snippets: r.snippets.clone(),
                 })
                 .collect(),
+            next_cursor: self.next_cursor.clone(),
         }
     }
 
@@ -260,6 +303,10 @@ impl SearchResults {
             }
         }
 
+        if let Some(cursor) = &self.next_cursor {
+            out.push_str(&format!("\nMore results: --cursor {cursor}\n"));
+        }
+
         out
     }
 
@@ -274,6 +321,9 @@ impl SearchResults {
         if let Some(ms) = self.duration_ms {
             header.push_str(&format!(" in {ms}ms"));
         }
+        if let Some(context) = &self.context {
+            header.push_str(&format!("\ncontext: {}", context.describe()));
+        }
         header
     }
 
@@ -329,6 +379,7 @@ impl SearchResults {
                     quality: r.skill.quality_score,
                     is_deprecated: r.skill.is_deprecated,
                     snippet: r.snippet.clone(),
+                    snippets: r.snippets.clone(),
                 })
                 .ok()
             })
@@ -353,6 +404,7 @@ impl Formattable for SearchResults {
             OutputFormat::Jsonl => self.format_jsonl(),
             OutputFormat::Plain => self.format_plain(),
             OutputFormat::Tsv => self.format_tsv(),
+            OutputFormat::Csv => tsv_as_csv(&self.format_tsv()),
             OutputFormat::Toon => self.format_toon(),
         }
     }
@@ -677,6 +729,25 @@ mod tests {
         assert!(!first["is_deprecated"].as_bool().unwrap());
     }
 
+    #[test]
+    fn test_search_json_includes_snippets_array() {
+        let mut results = SearchResults::new("error", "hybrid");
+        results.add_result(test_skill("lexical"), 0.9);
+        results.add_result(test_skill("vector-only"), 0.4);
+        results.results[0].snippets = vec![SearchSnippet {
+            field: "body".to_string(),
+            fragment: "handle the error".to_string(),
+            highlights: vec![(11, 16)],
+        }];
+
+        let parsed: serde_json::Value =
+            serde_json::from_str(&results.format(OutputFormat::Json)).unwrap();
+        let first = &parsed["results"][0]["snippets"][0];
+        assert_eq!(first["field"], "body");
+        assert_eq!(first["highlights"], serde_json::json!([[11, 16]]));
+        assert_eq!(parsed["results"][1]["snippets"], serde_json::json!([]));
+    }
+
     #[test]
     fn test_search_robot_mode_no_ansi() {
         let mut results = SearchResults::new("test", "bm25");
@@ -3,7 +3,7 @@
 use console::style;
 use serde::Serialize;
 
-use crate::cli::output::{Formattable, HumanLayout, OutputFormat};
+use crate::cli::output::{Formattable, HumanLayout, OutputFormat, tsv_as_csv};
 use crate::storage::sqlite::SkillRecord;
 
 /// A formatted view of a skill for display
@@ -192,6 +192,7 @@ impl Formattable for SkillCard<'_> {
             OutputFormat::Jsonl => serde_json::to_string(&self.to_summary()).unwrap_or_default(),
             OutputFormat::Plain => self.format_plain(),
             OutputFormat::Tsv => self.format_tsv(),
+            OutputFormat::Csv => tsv_as_csv(&self.format_tsv()),
             OutputFormat::Toon => {
                 let summary = self.to_summary();
                 let json = serde_json::to_value(&summary).unwrap_or_default();
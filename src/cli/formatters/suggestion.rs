@@ -3,64 +3,9 @@
 use console::style;
 use serde::Serialize;
 
-use crate::cli::output::{Formattable, OutputFormat};
-
-/// Percentage breakdown of signal contributions to a suggestion score
-#[derive(Debug, Clone, Default, Serialize)]
-pub struct ScorePercentageBreakdown {
-    /// Project/context match percentage
-    pub context_pct: f32,
-    /// Historical usage percentage
-    pub history_pct: f32,
-    /// Exploration bonus percentage
-    pub exploration_pct: f32,
-    /// Favorites boost percentage
-    pub favorites_pct: f32,
-}
-
-impl ScorePercentageBreakdown {
-    /// Calculate percentages from raw score components
-    pub fn from_components(
-        contextual_score: f32,
-        thompson_score: f32,
-        exploration_bonus: f32,
-        personal_boost: f32,
-    ) -> Self {
-        // The final score is approximately: contextual*0.7 + thompson*0.3 + exploration + personal
-        // Normalize to percentages
-        let total =
-            contextual_score * 0.7 + thompson_score * 0.3 + exploration_bonus + personal_boost;
-        if total <= 0.0 {
-            return Self::default();
-        }
-
-        // Calculate contribution percentages
-        let context_raw = contextual_score * 0.7;
-        let history_raw = thompson_score * 0.3; // Thompson includes history signal
-        let exploration_raw = exploration_bonus;
-        let favorites_raw = personal_boost;
-
-        let sum = context_raw + history_raw + exploration_raw + favorites_raw;
-        if sum <= 0.0 {
-            return Self::default();
-        }
-
-        Self {
-            context_pct: (context_raw / sum * 100.0).round(),
-            history_pct: (history_raw / sum * 100.0).round(),
-            exploration_pct: (exploration_raw / sum * 100.0).round(),
-            favorites_pct: (favorites_raw / sum * 100.0).round(),
-        }
-    }
-
-    /// Check if any meaningful breakdown is available
-    pub fn has_breakdown(&self) -> bool {
-        self.context_pct > 0.0
-            || self.history_pct > 0.0
-            || self.exploration_pct > 0.0
-            || self.favorites_pct > 0.0
-    }
-}
+use crate::cli::output::{Formattable, OutputFormat, tsv_as_csv};
+use crate::suggestions::ScoreSignal;
+use crate::suggestions::explanation::SuggestionOrigin;
 
 /// A skill suggestion with confidence and explanation
 #[derive(Debug, Clone)]
@@ -79,8 +24,10 @@ pub struct SuggestionItem {
     pub is_discovery: bool,
     /// Tags for the skill
     pub tags: Vec<String>,
-    /// Percentage breakdown of score components (for --explain)
-    pub breakdown: Option<ScorePercentageBreakdown>,
+    /// Signals behind the confidence, in the order applied (for --explain)
+    pub signals: Vec<ScoreSignal>,
+    /// Whether the task or the project context drove it (with --task)
+    pub origin: Option<SuggestionOrigin>,
 }
 
 /// Context information for suggestions
@@ -122,8 +69,10 @@ struct SuggestionJson {
     reason: Option<String>,
     is_discovery: bool,
     tags: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    signals: Vec<ScoreSignal>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    breakdown: Option<ScorePercentageBreakdown>,
+    origin: Option<SuggestionOrigin>,
 }
 
 /// Serializable suggestion response for JSON output
@@ -177,7 +126,8 @@ impl SuggestionOutput {
             reason: item.reason.clone(),
             is_discovery: item.is_discovery,
             tags: item.tags.clone(),
-            breakdown: item.breakdown.clone(),
+            signals: item.signals.clone(),
+            origin: item.origin,
         }
     }
 
@@ -245,36 +195,8 @@ impl SuggestionOutput {
                     out.push_str(&format!("   {}\n", style(reason).dim()));
                 }
 
-                // Percentage breakdown (when --explain is used)
-                if let Some(ref breakdown) = suggestion.breakdown {
-                    if breakdown.has_breakdown() {
-                        // Format: "  - [80%] Context match"
-                        if breakdown.context_pct > 0.0 {
-                            out.push_str(&format!(
-                                "   {} Context/project match\n",
-                                style(format!("[{:.0}%]", breakdown.context_pct)).cyan()
-                            ));
-                        }
-                        if breakdown.history_pct > 0.0 {
-                            out.push_str(&format!(
-                                "   {} Historical usage patterns\n",
-                                style(format!("[{:.0}%]", breakdown.history_pct)).cyan()
-                            ));
-                        }
-                        if breakdown.exploration_pct > 0.0 {
-                            out.push_str(&format!(
-                                "   {} Exploration bonus\n",
-                                style(format!("[{:.0}%]", breakdown.exploration_pct)).cyan()
-                            ));
-                        }
-                        if breakdown.favorites_pct > 0.0 {
-                            out.push_str(&format!(
-                                "   {} Favorites boost\n",
-                                style(format!("[{:.0}%]", breakdown.favorites_pct)).magenta()
-                            ));
-                        }
-                    }
-                }
+                // Score breakdown (when --explain is used)
+                out.push_str(&format_signals(&suggestion.signals));
 
                 // Tags
                 if !suggestion.tags.is_empty() {
@@ -309,6 +231,7 @@ impl SuggestionOutput {
                 if !suggestion.description.is_empty() {
                     out.push_str(&format!("   {}\n", suggestion.description));
                 }
+                out.push_str(&format_signals(&suggestion.signals));
 
                 out.push('\n');
             }
@@ -366,6 +289,31 @@ impl SuggestionOutput {
     }
 }
 
+/// Indented `--explain` lines: each signal's contribution, its raw value and
+/// weight, and what it matched.
+fn format_signals(signals: &[ScoreSignal]) -> String {
+    let mut out = String::new();
+    for signal in signals {
+        let contribution = format!("{:+.3}", signal.contribution);
+        let contribution = if signal.contribution < 0.0 {
+            style(contribution).red()
+        } else {
+            style(contribution).cyan()
+        };
+        out.push_str(&format!(
+            "     {} {} {}",
+            contribution,
+            signal.source.label(),
+            style(format!("({:.2} x {:.2})", signal.value, signal.weight)).dim()
+        ));
+        if let Some(ref detail) = signal.detail {
+            out.push_str(&format!(" {}", style(format!("- {detail}")).dim()));
+        }
+        out.push('\n');
+    }
+    out
+}
+
 impl Default for SuggestionOutput {
     fn default() -> Self {
         Self::new()
@@ -382,6 +330,7 @@ impl Formattable for SuggestionOutput {
             OutputFormat::Jsonl => self.format_jsonl(),
             OutputFormat::Plain => self.format_plain(),
             OutputFormat::Tsv => self.format_tsv(),
+            OutputFormat::Csv => tsv_as_csv(&self.format_tsv()),
             OutputFormat::Toon => {
                 let json_response = self.to_json_response();
                 let json = serde_json::to_value(&json_response).unwrap_or_default();
@@ -404,7 +353,8 @@ mod tests {
             reason: Some("Recent git activity detected".to_string()),
             is_discovery: false,
             tags: vec!["git".to_string(), "vcs".to_string()],
-            breakdown: None,
+            signals: Vec::new(),
+            origin: None,
         }
     }
 
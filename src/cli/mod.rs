@@ -11,6 +11,7 @@ pub use output::OutputFormat;
 pub mod colors;
 pub mod commands;
 pub mod formatters;
+pub mod nudges;
 pub mod output;
 pub mod progress;
 
@@ -19,6 +20,7 @@ pub mod progress;
 #[command(name = "ms")]
 #[command(author, version, about, long_about = None)]
 #[command(propagate_version = true)]
+#[command(after_help = crate::error::EXIT_CODES_HELP)]
 pub struct Cli {
     /// **Deprecated:** Enable JSON output for machine consumption.
     /// Use --output-format=json or -m instead.
@@ -26,7 +28,7 @@ pub struct Cli {
     #[arg(long, global = true, hide = true)]
     pub robot: bool,
 
-    /// Output format (human, json, jsonl, plain, tsv)
+    /// Output format (human, json, jsonl, plain, tsv, csv, toon)
     #[arg(long, short = 'O', global = true, value_enum)]
     pub output_format: Option<OutputFormat>,
 
@@ -59,6 +61,18 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub config: Option<PathBuf>,
 
+    /// Fail on unknown config keys and out-of-range values instead of ignoring them
+    #[arg(long, global = true)]
+    pub strict_config: bool,
+
+    /// With machine-readable output, stream NDJSON progress events to stderr
+    #[arg(long, global = true)]
+    pub progress_events: bool,
+
+    /// Safety profile from `[safety.profiles]` for the commands ms runs
+    #[arg(long, global = true, env = "MS_SAFETY_PROFILE", value_name = "NAME")]
+    pub safety_profile: Option<String>,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -152,9 +166,15 @@ pub enum Commands {
     /// Get context-aware skill suggestions
     Suggest(commands::suggest::SuggestArgs),
 
+    /// Explain why a skill was suggested, from the stored suggestion record
+    WhySuggested(commands::why_suggested::WhySuggestedArgs),
+
     /// Show skill details
     Show(commands::show::ShowArgs),
 
+    /// Resolve an ms:// deep link to a skill section or block
+    Open(commands::open::OpenArgs),
+
     /// List all indexed skills
     List(commands::list::ListArgs),
 
@@ -170,6 +190,9 @@ pub enum Commands {
     /// Edit a skill (structured round-trip)
     Edit(commands::edit::EditArgs),
 
+    /// Scaffold a new skill from a template
+    New(commands::new::NewArgs),
+
     /// Format skill files
     Fmt(commands::fmt::FmtArgs),
 
@@ -182,9 +205,15 @@ pub enum Commands {
     /// Manage skill aliases
     Alias(commands::alias::AliasArgs),
 
+    /// Show how a skill reference resolves (id, alias, prefix, shadowing)
+    Resolve(commands::resolve::ResolveArgs),
+
     /// Check environment requirements
     Requirements(commands::requirements::RequirementsArgs),
 
+    /// Review low-confidence generalizations queued by ms build
+    Review(commands::review::ReviewArgs),
+
     /// Record and inspect skill feedback
     Feedback(commands::feedback::FeedbackArgs),
 
@@ -197,6 +226,9 @@ pub enum Commands {
     /// Record implicit success/failure outcomes
     Outcome(commands::outcome::OutcomeArgs),
 
+    /// Report outcomes of skills loaded with --trial
+    Trial(commands::trial::TrialArgs),
+
     /// Personalize skills to user coding style
     Personalize(commands::personalize::PersonalizeArgs),
 
@@ -248,12 +280,24 @@ pub enum Commands {
     /// Suggestion bandit controls
     Bandit(commands::bandit::BanditArgs),
 
+    /// Report context budget consumption per layer and pack
+    Budget(commands::budget::BudgetArgs),
+
+    /// Pack skills into one markdown file sized for a model's context window
+    Pack(commands::pack::PackArgs),
+
     /// Backup and restore ms state
     Backup(commands::backup::BackupArgs),
 
+    /// Restore ms state from a backup archive
+    Restore(commands::backup::RestoreArgs),
+
     /// Health checks and repairs
     Doctor(commands::doctor::DoctorArgs),
 
+    /// Machine-readable error codes and exit codes
+    Errors(commands::errors::ErrorsArgs),
+
     /// Pre-commit hook: run UBS on staged files
     PreCommit(commands::pre_commit::PreCommitArgs),
 
@@ -272,6 +316,17 @@ pub enum Commands {
     /// Shell integration hooks
     Shell(commands::shell::ShellArgs),
 
+    /// Print shell completions (bash, zsh, fish, elvish, powershell)
+    Completions(commands::completions::CompletionsArgs),
+
+    /// Print completion candidates for the shell scripts
+    #[command(name = "__complete", hide = true)]
+    Complete(commands::completions::CompleteArgs),
+
+    /// Format suggestions for agent hooks (see `ms init --install-hooks`)
+    #[command(name = "__hook", hide = true)]
+    Hook(commands::hook::HookArgs),
+
     /// Command safety (DCG) logs and status
     Safety(commands::safety::SafetyArgs),
 
@@ -287,6 +342,9 @@ pub enum Commands {
     /// Compute skill quality scores
     Quality(commands::quality::QualityArgs),
 
+    /// Skill usage statistics (`--efficacy` correlates loads with session quality)
+    Stats(commands::stats::StatsArgs),
+
     /// View and tune recommendation engine (stats/history/tune)
     Recommend(commands::recommend::RecommendArgs),
 
@@ -304,4 +362,10 @@ pub enum Commands {
 
     /// Test embedding backends
     Embed(commands::embed::EmbedArgs),
+
+    /// Export skills into an agent's native skill directory (claude-code, cursor)
+    Export(commands::export::ExportArgs),
+
+    /// Export the skill catalog with embeddings for client-side docs search
+    ExportSite(commands::export_site::ExportSiteArgs),
 }
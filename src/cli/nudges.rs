@@ -0,0 +1,395 @@
+//! Local, telemetry-free usage nudges.
+//!
+//! After a successful human-mode command, `ms` may print one short insight
+//! about the local store (stale skills, unreviewed quarantine records, layers
+//! over budget) together with the command that acts on it. Everything is
+//! computed from the local SQLite database; nothing leaves the machine.
+//!
+//! Nudges are frequency-capped through a small state file in the ms root
+//! (`nudges.json`), never shown to robot/JSON consumers, agents, MCP
+//! sessions, or non-interactive terminals, and skipped silently if the
+//! checks take longer than [`CHECK_DEADLINE`].
+
+use std::collections::BTreeMap;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::app::AppContext;
+use crate::cli::output::OutputFormat;
+use crate::cli::{Cli, Commands};
+use crate::config::NudgesConfig;
+use crate::error::{MsError, Result};
+use crate::storage::Database;
+
+/// Upper bound on time spent computing a nudge.
+pub const CHECK_DEADLINE: Duration = Duration::from_millis(50);
+
+const STATE_FILE: &str = "nudges.json";
+
+/// Kind of insight a nudge reports, in priority order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NudgeCategory {
+    PendingReviews,
+    BudgetOverrun,
+    StaleSkills,
+}
+
+impl NudgeCategory {
+    pub const ALL: [Self; 3] = [Self::PendingReviews, Self::BudgetOverrun, Self::StaleSkills];
+
+    /// Config / state key for this category.
+    #[must_use]
+    pub const fn key(self) -> &'static str {
+        match self {
+            Self::PendingReviews => "pending_reviews",
+            Self::BudgetOverrun => "budget_overrun",
+            Self::StaleSkills => "stale_skills",
+        }
+    }
+
+    /// Command that acts on this insight.
+    #[must_use]
+    pub const fn action(self) -> &'static str {
+        match self {
+            Self::PendingReviews => "ms security quarantine list",
+            Self::BudgetOverrun => "ms budget",
+            Self::StaleSkills => "ms prune analyze",
+        }
+    }
+}
+
+/// A single insight ready to print.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Nudge {
+    pub category: NudgeCategory,
+    pub message: String,
+}
+
+impl Nudge {
+    /// One-line rendering, e.g. `tip: 12 skills unused in 180 days — run `ms prune analyze``.
+    #[must_use]
+    pub fn render(&self) -> String {
+        format!(
+            "tip: {} — run `{}` (disable with [nudges] enabled = false)",
+            self.message,
+            self.category.action()
+        )
+    }
+}
+
+/// Persisted frequency-capping state.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NudgeState {
+    #[serde(default)]
+    pub last_shown_at: Option<DateTime<Utc>>,
+    /// Last time each category was shown, keyed by [`NudgeCategory::key`].
+    #[serde(default)]
+    pub categories: BTreeMap<String, DateTime<Utc>>,
+}
+
+impl NudgeState {
+    #[must_use]
+    pub fn path(ms_root: &Path) -> PathBuf {
+        ms_root.join(STATE_FILE)
+    }
+
+    /// Load state, treating a missing or unreadable file as empty.
+    #[must_use]
+    pub fn load(ms_root: &Path) -> Self {
+        std::fs::read_to_string(Self::path(ms_root))
+            .ok()
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, ms_root: &Path) -> Result<()> {
+        let raw = serde_json::to_string_pretty(self)
+            .map_err(|err| MsError::Config(format!("encode nudge state: {err}")))?;
+        std::fs::write(Self::path(ms_root), raw)?;
+        Ok(())
+    }
+
+    /// Whether the global interval has elapsed since the last nudge.
+    #[must_use]
+    pub fn interval_elapsed(&self, config: &NudgesConfig, now: DateTime<Utc>) -> bool {
+        self.last_shown_at
+            .is_none_or(|last| now - last >= chrono::Duration::hours(config.interval_hours as i64))
+    }
+
+    /// Whether `category` is enabled and outside its cooldown.
+    #[must_use]
+    pub fn category_ready(
+        &self,
+        config: &NudgesConfig,
+        category: NudgeCategory,
+        now: DateTime<Utc>,
+    ) -> bool {
+        config.category_enabled(category.key())
+            && self.categories.get(category.key()).is_none_or(|last| {
+                now - *last >= chrono::Duration::days(config.cooldown_days as i64)
+            })
+    }
+
+    pub fn record(&mut self, category: NudgeCategory, now: DateTime<Utc>) {
+        self.last_shown_at = Some(now);
+        self.categories.insert(category.key().to_string(), now);
+    }
+}
+
+/// Invocation properties that decide whether a nudge may be shown at all.
+#[derive(Debug, Clone, Copy)]
+pub struct NudgeEnvironment {
+    pub format: OutputFormat,
+    pub quiet: bool,
+    pub interactive: bool,
+    pub agent: bool,
+    pub mcp: bool,
+}
+
+impl NudgeEnvironment {
+    #[must_use]
+    pub fn detect(cli: &Cli) -> Self {
+        Self {
+            format: cli.output_format(),
+            quiet: cli.quiet,
+            interactive: std::io::stdout().is_terminal() && std::io::stderr().is_terminal(),
+            agent: crate::output::is_agent_environment(),
+            mcp: matches!(cli.command, Commands::Mcp(_)),
+        }
+    }
+
+    /// Nudges are for humans at a terminal only.
+    #[must_use]
+    pub fn allows_nudges(&self) -> bool {
+        self.format == OutputFormat::Human
+            && !self.quiet
+            && self.interactive
+            && !self.agent
+            && !self.mcp
+    }
+}
+
+/// Compute the highest-priority ready nudge, giving up after `deadline`.
+pub fn select_nudge(
+    db: &Database,
+    ctx_config: &crate::config::Config,
+    state: &NudgeState,
+    now: DateTime<Utc>,
+    deadline: Duration,
+) -> Result<Option<Nudge>> {
+    let started = Instant::now();
+    for category in NudgeCategory::ALL {
+        if started.elapsed() > deadline {
+            return Ok(None);
+        }
+        if !state.category_ready(&ctx_config.nudges, category, now) {
+            continue;
+        }
+        if let Some(message) = check_category(db, ctx_config, category, now)? {
+            if started.elapsed() > deadline {
+                return Ok(None);
+            }
+            return Ok(Some(Nudge { category, message }));
+        }
+    }
+    Ok(None)
+}
+
+fn check_category(
+    db: &Database,
+    config: &crate::config::Config,
+    category: NudgeCategory,
+    now: DateTime<Utc>,
+) -> Result<Option<String>> {
+    match category {
+        NudgeCategory::PendingReviews => {
+            let pending = db.count_unreviewed_quarantine()?;
+            Ok((pending > 0).then(|| {
+                format!(
+                    "{pending} quarantined session excerpt{} awaiting review",
+                    plural(pending)
+                )
+            }))
+        }
+        NudgeCategory::BudgetOverrun => {
+            let totals = db.layer_token_totals()?;
+            let over: Vec<&str> = config
+                .budget
+                .layer_budgets()
+                .filter(|(layer, budget)| {
+                    totals.get(*layer).copied().unwrap_or(0) > u64::from(*budget)
+                })
+                .map(|(layer, _)| layer)
+                .collect();
+            Ok((!over.is_empty()).then(|| {
+                format!(
+                    "{} layer{} over token budget ({})",
+                    over.len(),
+                    plural(over.len() as u64),
+                    over.join(", ")
+                )
+            }))
+        }
+        NudgeCategory::StaleSkills => {
+            let cutoff = now - chrono::Duration::days(config.nudges.stale_days as i64);
+            let stale = db.count_skills_unused_since(&cutoff.to_rfc3339())?;
+            Ok((stale > 0).then(|| {
+                format!(
+                    "{stale} skill{} unused in {} days",
+                    plural(stale),
+                    config.nudges.stale_days
+                )
+            }))
+        }
+    }
+}
+
+const fn plural(count: u64) -> &'static str {
+    if count == 1 { "" } else { "s" }
+}
+
+/// Print at most one nudge to stderr after a successful command.
+///
+/// Never fails the command: errors computing or persisting a nudge are
+/// logged at debug level and otherwise ignored.
+pub fn maybe_show(ctx: &AppContext, cli: &Cli) {
+    if !ctx.config.nudges.enabled || !NudgeEnvironment::detect(cli).allows_nudges() {
+        return;
+    }
+    let now = Utc::now();
+    let mut state = NudgeState::load(&ctx.ms_root);
+    if !state.interval_elapsed(&ctx.config.nudges, now) {
+        return;
+    }
+    match select_nudge(&ctx.db, &ctx.config, &state, now, CHECK_DEADLINE) {
+        Ok(Some(nudge)) => {
+            eprintln!("{}", nudge.render());
+            state.record(nudge.category, now);
+            if let Err(err) = state.save(&ctx.ms_root) {
+                tracing::debug!(error = %err, "failed to persist nudge state");
+            }
+        }
+        Ok(None) => {}
+        Err(err) => tracing::debug!(error = %err, "nudge check failed"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn human_env() -> NudgeEnvironment {
+        NudgeEnvironment {
+            format: OutputFormat::Human,
+            quiet: false,
+            interactive: true,
+            agent: false,
+            mcp: false,
+        }
+    }
+
+    #[test]
+    fn environment_suppresses_non_interactive_consumers() {
+        assert!(human_env().allows_nudges());
+        for env in [
+            NudgeEnvironment {
+                format: OutputFormat::Json,
+                ..human_env()
+            },
+            NudgeEnvironment {
+                quiet: true,
+                ..human_env()
+            },
+            NudgeEnvironment {
+                interactive: false,
+                ..human_env()
+            },
+            NudgeEnvironment {
+                agent: true,
+                ..human_env()
+            },
+            NudgeEnvironment {
+                mcp: true,
+                ..human_env()
+            },
+        ] {
+            assert!(!env.allows_nudges(), "{env:?}");
+        }
+    }
+
+    #[test]
+    fn interval_caps_frequency() {
+        let config = NudgesConfig::default();
+        let now = Utc::now();
+        let mut state = NudgeState::default();
+        assert!(state.interval_elapsed(&config, now));
+
+        state.record(NudgeCategory::StaleSkills, now);
+        assert!(!state.interval_elapsed(&config, now + chrono::Duration::hours(23)));
+        assert!(state.interval_elapsed(&config, now + chrono::Duration::hours(24)));
+    }
+
+    #[test]
+    fn category_cooldown_and_switches() {
+        let mut config = NudgesConfig::default();
+        let now = Utc::now();
+        let mut state = NudgeState::default();
+        state.record(NudgeCategory::StaleSkills, now);
+
+        let later = now + chrono::Duration::days(2);
+        assert!(!state.category_ready(&config, NudgeCategory::StaleSkills, later));
+        assert!(state.category_ready(&config, NudgeCategory::BudgetOverrun, later));
+
+        let much_later = now + chrono::Duration::days(config.cooldown_days as i64);
+        assert!(state.category_ready(&config, NudgeCategory::StaleSkills, much_later));
+
+        config.categories.insert("stale_skills".to_string(), false);
+        assert!(!state.category_ready(&config, NudgeCategory::StaleSkills, much_later));
+    }
+
+    #[test]
+    fn state_round_trips_and_tolerates_garbage() {
+        let dir = tempdir().unwrap();
+        assert!(NudgeState::load(dir.path()).last_shown_at.is_none());
+
+        let mut state = NudgeState::default();
+        state.record(NudgeCategory::PendingReviews, Utc::now());
+        state.save(dir.path()).unwrap();
+        let loaded = NudgeState::load(dir.path());
+        assert!(loaded.categories.contains_key("pending_reviews"));
+
+        std::fs::write(NudgeState::path(dir.path()), "not json").unwrap();
+        assert!(NudgeState::load(dir.path()).last_shown_at.is_none());
+    }
+
+    #[test]
+    fn empty_store_produces_no_nudge() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("ms.db")).unwrap();
+        let nudge = select_nudge(
+            &db,
+            &crate::config::Config::default(),
+            &NudgeState::default(),
+            Utc::now(),
+            Duration::from_secs(5),
+        )
+        .unwrap();
+        assert!(nudge.is_none());
+    }
+
+    #[test]
+    fn render_names_the_action() {
+        let nudge = Nudge {
+            category: NudgeCategory::StaleSkills,
+            message: "3 skills unused in 180 days".to_string(),
+        };
+        let line = nudge.render();
+        assert!(line.starts_with("tip: 3 skills"));
+        assert!(line.contains("`ms prune analyze`"));
+    }
+}
@@ -26,6 +26,8 @@ pub enum OutputFormat {
     Plain,
     /// Tab-separated values (for shell scripting)
     Tsv,
+    /// Comma-separated values (for spreadsheets)
+    Csv,
     /// Token-optimized output notation (40-60% fewer tokens than JSON)
     Toon,
 }
@@ -52,7 +54,11 @@ impl OutputFormat {
     pub const fn is_machine_readable(&self) -> bool {
         matches!(
             self,
-            OutputFormat::Json | OutputFormat::Jsonl | OutputFormat::Tsv | OutputFormat::Toon
+            OutputFormat::Json
+                | OutputFormat::Jsonl
+                | OutputFormat::Tsv
+                | OutputFormat::Csv
+                | OutputFormat::Toon
         )
     }
 }
@@ -445,6 +451,7 @@ pub fn emit_formatted<T: Serialize>(
         }
         OutputFormat::Plain => println!("{}", plain_fn(value)),
         OutputFormat::Tsv => println!("{}", tsv_fn(value)),
+        OutputFormat::Csv => println!("{}", tsv_as_csv(&tsv_fn(value))),
         OutputFormat::Toon => emit_toon(value)?,
     }
     Ok(())
@@ -471,10 +478,110 @@ where
     }
 }
 
+/// Delimited table format behind `-O csv` and `-O tsv`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableFormat {
+    /// Comma-separated values (RFC 4180 quoting)
+    Csv,
+    /// Tab-separated values (backslash escapes for tab, newline, backslash)
+    Tsv,
+}
+
+impl TableFormat {
+    /// The table format for a global output format, if it is one.
+    #[must_use]
+    pub const fn for_output(format: OutputFormat) -> Option<Self> {
+        match format {
+            OutputFormat::Csv => Some(Self::Csv),
+            OutputFormat::Tsv => Some(Self::Tsv),
+            _ => None,
+        }
+    }
+
+    /// Escape one field for this format.
+    #[must_use]
+    pub fn escape(self, field: &str) -> String {
+        match self {
+            Self::Csv => {
+                if field.contains([',', '"', '\n', '\r']) {
+                    format!("\"{}\"", field.replace('"', "\"\""))
+                } else {
+                    field.to_string()
+                }
+            }
+            Self::Tsv => field
+                .replace('\\', "\\\\")
+                .replace('\t', "\\t")
+                .replace('\n', "\\n")
+                .replace('\r', "\\r"),
+        }
+    }
+
+    /// Render one row, escaping every field.
+    #[must_use]
+    pub fn row<S: AsRef<str>>(self, fields: &[S]) -> String {
+        let sep = match self {
+            Self::Csv => ",",
+            Self::Tsv => "\t",
+        };
+        fields
+            .iter()
+            .map(|f| self.escape(f.as_ref()))
+            .collect::<Vec<_>>()
+            .join(sep)
+    }
+}
+
+/// Re-render tab-separated lines as CSV, for commands whose table output
+/// is built as TSV.
+#[must_use]
+pub fn tsv_as_csv(tsv: &str) -> String {
+    let mut csv = tsv
+        .lines()
+        .map(|line| TableFormat::Csv.row(&line.split('\t').collect::<Vec<_>>()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    if tsv.ends_with('\n') {
+        csv.push('\n');
+    }
+    csv
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn table_format_csv_quotes_special_fields() {
+        let csv = TableFormat::Csv;
+        assert_eq!(csv.escape("plain"), "plain");
+        assert_eq!(csv.escape("a,b"), "\"a,b\"");
+        assert_eq!(csv.escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv.escape("two\nlines"), "\"two\nlines\"");
+        assert_eq!(csv.row(&["x", "y,z"]), "x,\"y,z\"");
+    }
+
+    #[test]
+    fn table_format_tsv_escapes_control_characters() {
+        let tsv = TableFormat::Tsv;
+        assert_eq!(tsv.escape("a\tb\nc\\d"), "a\\tb\\nc\\\\d");
+        assert_eq!(tsv.row(&["x", "y,z"]), "x\ty,z");
+    }
+
+    #[test]
+    fn table_format_follows_global_output_format() {
+        assert_eq!(
+            TableFormat::for_output(OutputFormat::Csv),
+            Some(TableFormat::Csv)
+        );
+        assert_eq!(
+            TableFormat::for_output(OutputFormat::Tsv),
+            Some(TableFormat::Tsv)
+        );
+        assert_eq!(TableFormat::for_output(OutputFormat::Json), None);
+        assert_eq!(tsv_as_csv("id\tname\na\tb,c\n"), "id,name\na,\"b,c\"\n");
+    }
+
     #[test]
     fn output_format_from_args_robot_overrides() {
         // Robot flag overrides explicit format
@@ -506,6 +613,7 @@ mod tests {
         assert!(!OutputFormat::Jsonl.use_colors());
         assert!(!OutputFormat::Plain.use_colors());
         assert!(!OutputFormat::Tsv.use_colors());
+        assert!(!OutputFormat::Csv.use_colors());
         assert!(!OutputFormat::Toon.use_colors());
     }
 
@@ -516,6 +624,7 @@ mod tests {
         assert!(OutputFormat::Jsonl.is_machine_readable());
         assert!(!OutputFormat::Plain.is_machine_readable());
         assert!(OutputFormat::Tsv.is_machine_readable());
+        assert!(OutputFormat::Csv.is_machine_readable());
         assert!(OutputFormat::Toon.is_machine_readable());
     }
 
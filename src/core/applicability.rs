@@ -0,0 +1,547 @@
+//! Skill applicability windows
+//!
+//! Seasonal or time-bound skills declare when they apply in frontmatter:
+//!
+//! ```yaml
+//! active_during:
+//!   months: [11, 12]            # numbers or names ("nov", "December")
+//!   days_of_week: [mon, fri]
+//!   date_ranges:
+//!     - { from: "12-15", to: "01-05" }           # recurring, may wrap the year
+//!     - { from: "2026-03-01", to: "2026-03-31" } # one-off, inclusive
+//! expires: "2026-01-31"
+//! ```
+//!
+//! Every non-empty constraint list must match (months AND weekdays AND
+//! ranges); within a list any entry matches. A skill is expired once the
+//! local date is past `expires`. Dates are always evaluated in an explicit
+//! [`WindowTimezone`] against a [`Clock`], so tests can pin the instant.
+
+use chrono::{DateTime, Datelike, FixedOffset, Local, NaiveDate, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+
+use crate::core::skill::SkillMetadata;
+use crate::error::{MsError, Result};
+
+/// `active_during` frontmatter block.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActiveDuring {
+    /// Months (1-12 or English names).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub months: Vec<MonthValue>,
+    /// Weekdays ("mon", "Tuesday", ...).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub days_of_week: Vec<String>,
+    /// Inclusive date ranges, `YYYY-MM-DD` or recurring `MM-DD`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub date_ranges: Vec<DateRange>,
+}
+
+impl ActiveDuring {
+    /// Whether no constraint is declared.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.months.is_empty() && self.days_of_week.is_empty() && self.date_ranges.is_empty()
+    }
+}
+
+/// A month given as a number or a name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum MonthValue {
+    Number(u32),
+    Name(String),
+}
+
+/// Inclusive `from`..=`to` range.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DateRange {
+    pub from: String,
+    pub to: String,
+}
+
+/// Where a skill stands relative to its window at a given instant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowStatus {
+    /// No window declared; always applicable.
+    #[default]
+    Always,
+    /// Inside its declared window.
+    Active,
+    /// Outside its declared window.
+    Inactive,
+    /// Past its `expires` date.
+    Expired,
+}
+
+impl WindowStatus {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Always => "always",
+            Self::Active => "active",
+            Self::Inactive => "inactive",
+            Self::Expired => "expired",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ParsedRange {
+    Absolute(NaiveDate, NaiveDate),
+    /// `(month, day)` pairs; `from > to` wraps across the new year.
+    Recurring((u32, u32), (u32, u32)),
+}
+
+impl ParsedRange {
+    fn contains(self, date: NaiveDate) -> bool {
+        match self {
+            Self::Absolute(from, to) => from <= date && date <= to,
+            Self::Recurring(from, to) => {
+                let day = (date.month(), date.day());
+                if from <= to {
+                    from <= day && day <= to
+                } else {
+                    day >= from || day <= to
+                }
+            }
+        }
+    }
+}
+
+/// Parsed applicability window of one skill.
+///
+/// Invalid entries are skipped (and reported by [`SkillWindow::errors`]) so a
+/// typo never hides a skill entirely.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SkillWindow {
+    months: Vec<u32>,
+    weekdays: Vec<Weekday>,
+    ranges: Vec<ParsedRange>,
+    expires: Option<NaiveDate>,
+    errors: Vec<String>,
+}
+
+impl SkillWindow {
+    /// Window declared by skill metadata.
+    #[must_use]
+    pub fn from_metadata(meta: &SkillMetadata) -> Self {
+        Self::new(meta.active_during.as_ref(), meta.expires.as_deref())
+    }
+
+    /// Window from an indexed `metadata_json` column; empty if unparsable.
+    #[must_use]
+    pub fn from_metadata_json(metadata_json: &str) -> Self {
+        let Ok(value) = serde_json::from_str::<serde_json::Value>(metadata_json) else {
+            return Self::default();
+        };
+        let active_during = value
+            .get("active_during")
+            .and_then(|v| serde_json::from_value::<ActiveDuring>(v.clone()).ok());
+        let expires = value.get("expires").and_then(|v| v.as_str());
+        Self::new(active_during.as_ref(), expires)
+    }
+
+    fn new(active_during: Option<&ActiveDuring>, expires: Option<&str>) -> Self {
+        let mut window = Self::default();
+        if let Some(active) = active_during {
+            for month in &active.months {
+                match parse_month(month) {
+                    Some(m) => window.months.push(m),
+                    None => window
+                        .errors
+                        .push(format!("invalid month: {}", month_label(month))),
+                }
+            }
+            for day in &active.days_of_week {
+                match parse_weekday(day) {
+                    Some(d) => window.weekdays.push(d),
+                    None => window.errors.push(format!("invalid day of week: {day}")),
+                }
+            }
+            for range in &active.date_ranges {
+                match parse_range(range) {
+                    Ok(r) => window.ranges.push(r),
+                    Err(err) => window.errors.push(err),
+                }
+            }
+        }
+        if let Some(raw) = expires {
+            match NaiveDate::parse_from_str(raw.trim(), "%Y-%m-%d") {
+                Ok(date) => window.expires = Some(date),
+                Err(_) => window.errors.push(format!(
+                    "invalid expires date '{raw}' (expected YYYY-MM-DD)"
+                )),
+            }
+        }
+        window
+    }
+
+    /// Problems found while parsing the declared window.
+    #[must_use]
+    pub fn errors(&self) -> &[String] {
+        &self.errors
+    }
+
+    /// Whether any window constraint (besides `expires`) is declared.
+    #[must_use]
+    pub fn has_window(&self) -> bool {
+        !(self.months.is_empty() && self.weekdays.is_empty() && self.ranges.is_empty())
+    }
+
+    #[must_use]
+    pub const fn expires_on(&self) -> Option<NaiveDate> {
+        self.expires
+    }
+
+    /// Status on the local calendar date `today`.
+    #[must_use]
+    pub fn status_on(&self, today: NaiveDate) -> WindowStatus {
+        if self.expires.is_some_and(|expires| today > expires) {
+            return WindowStatus::Expired;
+        }
+        if !self.has_window() {
+            return WindowStatus::Always;
+        }
+        let month_ok = self.months.is_empty() || self.months.contains(&today.month());
+        let weekday_ok = self.weekdays.is_empty() || self.weekdays.contains(&today.weekday());
+        let range_ok = self.ranges.is_empty() || self.ranges.iter().any(|r| r.contains(today));
+        if month_ok && weekday_ok && range_ok {
+            WindowStatus::Active
+        } else {
+            WindowStatus::Inactive
+        }
+    }
+
+    /// Status at `now`, evaluated in `tz`.
+    #[must_use]
+    pub fn status_at(&self, now: DateTime<Utc>, tz: WindowTimezone) -> WindowStatus {
+        self.status_on(tz.local_date(now))
+    }
+
+    /// Days from `today` until expiry (0 on the last valid day, negative once expired).
+    #[must_use]
+    pub fn days_until_expiry(&self, today: NaiveDate) -> Option<i64> {
+        self.expires.map(|expires| (expires - today).num_days())
+    }
+}
+
+/// Timezone used to turn an instant into a calendar date.
+///
+/// chrono-tz is not a dependency, so named zones other than UTC are not
+/// supported; use a fixed offset such as `+01:00` or `local`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WindowTimezone {
+    #[default]
+    Utc,
+    /// The host's local timezone.
+    Local,
+    Fixed(FixedOffset),
+}
+
+impl WindowTimezone {
+    /// Parse `UTC`, `local`, or an offset like `+02:00`.
+    pub fn parse(raw: &str) -> Result<Self> {
+        let trimmed = raw.trim();
+        match trimmed.to_ascii_lowercase().as_str() {
+            "" | "utc" | "z" | "etc/utc" => return Ok(Self::Utc),
+            "local" => return Ok(Self::Local),
+            _ => {}
+        }
+        trimmed.parse::<FixedOffset>().map(Self::Fixed).map_err(|_| {
+            MsError::Config(format!(
+                "invalid applicability timezone '{raw}' (expected UTC, local, or an offset like +02:00)"
+            ))
+        })
+    }
+
+    /// Configured timezone, falling back to UTC when the setting is invalid.
+    #[must_use]
+    pub fn from_config(raw: &str) -> Self {
+        Self::parse(raw).unwrap_or_else(|err| {
+            tracing::warn!(target: "applicability", error = %err, "falling back to UTC");
+            Self::Utc
+        })
+    }
+
+    /// Calendar date of `now` in this timezone.
+    #[must_use]
+    pub fn local_date(self, now: DateTime<Utc>) -> NaiveDate {
+        match self {
+            Self::Utc => now.date_naive(),
+            Self::Local => now.with_timezone(&Local).date_naive(),
+            Self::Fixed(offset) => now.with_timezone(&offset).date_naive(),
+        }
+    }
+}
+
+/// Source of the current instant; injectable so window logic is testable.
+pub trait Clock {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// The system clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A clock frozen at a fixed instant.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock(pub DateTime<Utc>);
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.0
+    }
+}
+
+/// Today's date in the configured applicability timezone.
+#[must_use]
+pub fn today(clock: &dyn Clock, config: &crate::config::ApplicabilityConfig) -> NaiveDate {
+    WindowTimezone::from_config(&config.timezone).local_date(clock.now())
+}
+
+const MONTH_NAMES: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+fn parse_month(value: &MonthValue) -> Option<u32> {
+    match value {
+        MonthValue::Number(n) => (1..=12).contains(n).then_some(*n),
+        MonthValue::Name(name) => {
+            let lower = name.trim().to_ascii_lowercase();
+            if let Ok(n) = lower.parse::<u32>() {
+                return (1..=12).contains(&n).then_some(n);
+            }
+            if lower.len() < 3 {
+                return None;
+            }
+            MONTH_NAMES
+                .iter()
+                .position(|full| full.starts_with(&lower))
+                .map(|idx| idx as u32 + 1)
+        }
+    }
+}
+
+fn month_label(value: &MonthValue) -> String {
+    match value {
+        MonthValue::Number(n) => n.to_string(),
+        MonthValue::Name(name) => name.clone(),
+    }
+}
+
+fn parse_weekday(raw: &str) -> Option<Weekday> {
+    let lower = raw.trim().to_ascii_lowercase();
+    if lower.len() < 3 {
+        return None;
+    }
+    [
+        ("monday", Weekday::Mon),
+        ("tuesday", Weekday::Tue),
+        ("wednesday", Weekday::Wed),
+        ("thursday", Weekday::Thu),
+        ("friday", Weekday::Fri),
+        ("saturday", Weekday::Sat),
+        ("sunday", Weekday::Sun),
+    ]
+    .iter()
+    .find(|(name, _)| name.starts_with(&lower))
+    .map(|(_, day)| *day)
+}
+
+/// Either a full `YYYY-MM-DD` date or a recurring `(month, day)`.
+enum DateSpec {
+    Full(NaiveDate),
+    MonthDay(u32, u32),
+}
+
+fn parse_date_spec(raw: &str) -> Option<DateSpec> {
+    let trimmed = raw.trim();
+    if let Ok(date) = NaiveDate::parse_from_str(trimmed, "%Y-%m-%d") {
+        return Some(DateSpec::Full(date));
+    }
+    let (month, day) = trimmed.split_once('-')?;
+    let (month, day) = (month.parse::<u32>().ok()?, day.parse::<u32>().ok()?);
+    // 2000 is a leap year, so Feb 29 is accepted.
+    NaiveDate::from_ymd_opt(2000, month, day)?;
+    Some(DateSpec::MonthDay(month, day))
+}
+
+fn parse_range(range: &DateRange) -> std::result::Result<ParsedRange, String> {
+    let from = parse_date_spec(&range.from).ok_or_else(|| {
+        format!(
+            "invalid date_ranges.from '{}' (expected YYYY-MM-DD or MM-DD)",
+            range.from
+        )
+    })?;
+    let to = parse_date_spec(&range.to).ok_or_else(|| {
+        format!(
+            "invalid date_ranges.to '{}' (expected YYYY-MM-DD or MM-DD)",
+            range.to
+        )
+    })?;
+    match (from, to) {
+        (DateSpec::Full(from), DateSpec::Full(to)) if from <= to => {
+            Ok(ParsedRange::Absolute(from, to))
+        }
+        (DateSpec::Full(_), DateSpec::Full(_)) => Err(format!(
+            "date range {} to {} ends before it starts",
+            range.from, range.to
+        )),
+        (DateSpec::MonthDay(fm, fd), DateSpec::MonthDay(tm, td)) => {
+            Ok(ParsedRange::Recurring((fm, fd), (tm, td)))
+        }
+        _ => Err(format!(
+            "date range {} to {} mixes full and recurring dates",
+            range.from, range.to
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn window(yaml: &str) -> SkillWindow {
+        let meta: SkillMetadata = serde_yaml::from_str(yaml).unwrap();
+        SkillWindow::from_metadata(&meta)
+    }
+
+    #[test]
+    fn no_window_is_always_applicable() {
+        let w = window("id: plain\n");
+        assert!(!w.has_window());
+        assert_eq!(w.status_on(date(2026, 3, 1)), WindowStatus::Always);
+    }
+
+    #[test]
+    fn months_accept_numbers_and_names() {
+        let w = window("active_during:\n  months: [11, Dec]\n");
+        assert!(w.errors().is_empty());
+        assert_eq!(w.status_on(date(2026, 11, 5)), WindowStatus::Active);
+        assert_eq!(w.status_on(date(2026, 12, 31)), WindowStatus::Active);
+        assert_eq!(w.status_on(date(2026, 3, 1)), WindowStatus::Inactive);
+    }
+
+    #[test]
+    fn constraints_combine_with_and() {
+        // Fridays in December only.
+        let w = window("active_during:\n  months: [12]\n  days_of_week: [fri]\n");
+        assert_eq!(w.status_on(date(2026, 12, 4)), WindowStatus::Active);
+        assert_eq!(w.status_on(date(2026, 12, 3)), WindowStatus::Inactive);
+        assert_eq!(w.status_on(date(2026, 11, 27)), WindowStatus::Inactive);
+    }
+
+    #[test]
+    fn recurring_ranges_wrap_the_year() {
+        let w =
+            window("active_during:\n  date_ranges:\n    - { from: \"12-15\", to: \"01-05\" }\n");
+        assert_eq!(w.status_on(date(2026, 12, 14)), WindowStatus::Inactive);
+        assert_eq!(w.status_on(date(2026, 12, 15)), WindowStatus::Active);
+        assert_eq!(w.status_on(date(2027, 1, 5)), WindowStatus::Active);
+        assert_eq!(w.status_on(date(2027, 1, 6)), WindowStatus::Inactive);
+    }
+
+    #[test]
+    fn expiry_is_inclusive_of_the_last_day() {
+        let w = window("expires: 2026-01-31\n");
+        assert_eq!(w.status_on(date(2026, 1, 31)), WindowStatus::Always);
+        assert_eq!(w.status_on(date(2026, 2, 1)), WindowStatus::Expired);
+        assert_eq!(w.days_until_expiry(date(2026, 1, 1)), Some(30));
+        assert_eq!(w.days_until_expiry(date(2026, 2, 2)), Some(-2));
+    }
+
+    #[test]
+    fn boundary_instants_respect_timezone() {
+        let w = window("active_during:\n  months: [12]\n");
+        let tz = WindowTimezone::parse("+02:00").unwrap();
+        // 21:59:59Z is Nov 30 23:59:59 at +02:00; one second later is Dec 1.
+        let before = FixedClock(Utc.with_ymd_and_hms(2026, 11, 30, 21, 59, 59).unwrap());
+        let after = FixedClock(Utc.with_ymd_and_hms(2026, 11, 30, 22, 0, 0).unwrap());
+        assert_eq!(w.status_at(before.now(), tz), WindowStatus::Inactive);
+        assert_eq!(w.status_at(after.now(), tz), WindowStatus::Active);
+        // Same instant in UTC is still November.
+        assert_eq!(
+            w.status_at(after.now(), WindowTimezone::Utc),
+            WindowStatus::Inactive
+        );
+    }
+
+    #[test]
+    fn expiry_boundary_follows_local_midnight() {
+        let w = window("expires: 2026-01-31\n");
+        let tz = WindowTimezone::parse("-05:00").unwrap();
+        let last = Utc.with_ymd_and_hms(2026, 2, 1, 4, 59, 59).unwrap();
+        let first = Utc.with_ymd_and_hms(2026, 2, 1, 5, 0, 0).unwrap();
+        assert_eq!(w.status_at(last, tz), WindowStatus::Always);
+        assert_eq!(w.status_at(first, tz), WindowStatus::Expired);
+    }
+
+    #[test]
+    fn invalid_entries_are_reported_and_skipped() {
+        let w = window(
+            "active_during:\n  months: [13, smarch]\n  days_of_week: [funday]\n  date_ranges:\n    - { from: \"2026-03-10\", to: \"2026-03-01\" }\n    - { from: \"02-30\", to: \"03-01\" }\n    - { from: \"2026-01-01\", to: \"01-31\" }\nexpires: next week\n",
+        );
+        assert_eq!(w.errors().len(), 7);
+        assert!(!w.has_window());
+        assert_eq!(w.expires_on(), None);
+    }
+
+    #[test]
+    fn window_round_trips_through_metadata_json() {
+        let meta: SkillMetadata = serde_yaml::from_str(
+            "id: freeze\nactive_during:\n  months: [dec]\nexpires: \"2027-01-15\"\n",
+        )
+        .unwrap();
+        let json = serde_json::to_string(&meta).unwrap();
+        assert_eq!(
+            SkillWindow::from_metadata_json(&json),
+            SkillWindow::from_metadata(&meta)
+        );
+        assert_eq!(
+            SkillWindow::from_metadata_json("not json"),
+            SkillWindow::default()
+        );
+    }
+
+    #[test]
+    fn timezone_parsing() {
+        assert_eq!(WindowTimezone::parse("UTC").unwrap(), WindowTimezone::Utc);
+        assert_eq!(
+            WindowTimezone::parse("local").unwrap(),
+            WindowTimezone::Local
+        );
+        assert!(matches!(
+            WindowTimezone::parse("+05:30").unwrap(),
+            WindowTimezone::Fixed(_)
+        ));
+        assert!(WindowTimezone::parse("Mars/Olympus").is_err());
+        assert_eq!(
+            WindowTimezone::from_config("Mars/Olympus"),
+            WindowTimezone::Utc
+        );
+    }
+}
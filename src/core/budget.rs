@@ -0,0 +1,392 @@
+//! Token budget accounting for auto-loaded context.
+//!
+//! Budgets are declared per layer and per pack (meta-skill) in the `[budget]`
+//! config section. This module measures consumption against those budgets,
+//! lazily recomputes stale stored token counts, and provides the
+//! priority-ordered degradation used when a budget has to be enforced.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::config::BudgetConfig;
+use crate::error::Result;
+use crate::lint::diagnostic::{Diagnostic, RuleCategory};
+use crate::storage::Database;
+use crate::storage::sqlite::SkillRecord;
+
+use super::slicing::SkillSlicer;
+use super::spec_lens::parse_markdown;
+
+/// Rule id used for budget advisories on pack definitions.
+pub const PACK_BUDGET_RULE_ID: &str = "pack-budget-exceeded";
+
+/// Number of contributors listed per budget by default.
+pub const DEFAULT_TOP_CONTRIBUTORS: usize = 5;
+
+/// What a budget applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetScope {
+    Layer,
+    Pack,
+}
+
+impl std::fmt::Display for BudgetScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Layer => write!(f, "layer"),
+            Self::Pack => write!(f, "pack"),
+        }
+    }
+}
+
+/// A single item counted against a budget.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BudgetContributor {
+    pub id: String,
+    pub tokens: usize,
+}
+
+/// Consumption of one budget.
+#[derive(Debug, Clone, Serialize)]
+pub struct BudgetUsage {
+    pub scope: BudgetScope,
+    pub name: String,
+    pub budget: usize,
+    pub used: usize,
+    pub percent_used: f64,
+    pub over_budget: bool,
+    pub contributor_count: usize,
+    /// Largest contributors, in descending token order.
+    pub top_contributors: Vec<BudgetContributor>,
+}
+
+impl BudgetUsage {
+    /// Build a usage entry from every contributor, keeping only the `top_n` largest.
+    #[must_use]
+    pub fn new(
+        scope: BudgetScope,
+        name: impl Into<String>,
+        budget: usize,
+        contributors: Vec<BudgetContributor>,
+        top_n: usize,
+    ) -> Self {
+        let used: usize = contributors.iter().map(|c| c.tokens).sum();
+        let contributor_count = contributors.len();
+        Self::from_totals(
+            scope,
+            name,
+            budget,
+            used,
+            contributor_count,
+            contributors,
+            top_n,
+        )
+    }
+
+    /// Build a usage entry from precomputed totals and a candidate list of
+    /// the largest contributors, keeping only the `top_n` largest of them.
+    #[must_use]
+    pub fn from_totals(
+        scope: BudgetScope,
+        name: impl Into<String>,
+        budget: usize,
+        used: usize,
+        contributor_count: usize,
+        mut contributors: Vec<BudgetContributor>,
+        top_n: usize,
+    ) -> Self {
+        contributors.sort_by(|a, b| b.tokens.cmp(&a.tokens).then_with(|| a.id.cmp(&b.id)));
+        contributors.truncate(top_n);
+        let percent_used = if budget == 0 {
+            if used == 0 { 0.0 } else { 100.0 }
+        } else {
+            (used as f64 / budget as f64) * 100.0
+        };
+        Self {
+            scope,
+            name: name.into(),
+            budget,
+            used,
+            percent_used,
+            over_budget: used > budget,
+            contributor_count,
+            top_contributors: contributors,
+        }
+    }
+
+    /// Tokens by which this budget is exceeded (0 when within budget).
+    #[must_use]
+    pub const fn overage(&self) -> usize {
+        self.used.saturating_sub(self.budget)
+    }
+}
+
+/// Full budget report across layers and packs.
+#[derive(Debug, Clone, Serialize)]
+pub struct BudgetReport {
+    pub generated_at: String,
+    pub budgets: Vec<BudgetUsage>,
+    /// Number of stored token counts that were stale and recomputed.
+    pub recomputed_token_counts: usize,
+    /// Lint-style advisories for pack definitions that exceed their budget.
+    pub advisories: Vec<Diagnostic>,
+}
+
+impl BudgetReport {
+    #[must_use]
+    pub fn new(budgets: Vec<BudgetUsage>, recomputed_token_counts: usize) -> Self {
+        let advisories = budgets
+            .iter()
+            .filter(|usage| usage.scope == BudgetScope::Pack && usage.over_budget)
+            .map(pack_budget_advisory)
+            .collect();
+        Self {
+            generated_at: chrono::Utc::now().to_rfc3339(),
+            budgets,
+            recomputed_token_counts,
+            advisories,
+        }
+    }
+
+    #[must_use]
+    pub fn over_budget(&self) -> Vec<&BudgetUsage> {
+        self.budgets.iter().filter(|b| b.over_budget).collect()
+    }
+}
+
+/// Lint-style advisory for a pack whose full content exceeds its budget.
+#[must_use]
+pub fn pack_budget_advisory(usage: &BudgetUsage) -> Diagnostic {
+    Diagnostic::warning(
+        PACK_BUDGET_RULE_ID,
+        format!(
+            "pack '{}' needs {} tokens but its budget is {} ({} over)",
+            usage.name,
+            usage.used,
+            usage.budget,
+            usage.overage()
+        ),
+    )
+    .with_category(RuleCategory::Performance)
+    .with_suggestion(
+        "lower slice levels, mark low-value slices non-required, or raise budget.pack.<id>",
+    )
+}
+
+/// Token count for a skill, recomputing it from the body when the stored
+/// value is stale (zero or negative, as left behind by re-indexing).
+///
+/// Returns the token count and whether it had to be recomputed.
+#[must_use]
+pub fn effective_token_count(record: &SkillRecord) -> (usize, bool) {
+    if record.token_count > 0 {
+        return (record.token_count as usize, false);
+    }
+    let tokens = match parse_markdown(&record.body) {
+        Ok(spec) => SkillSlicer::estimate_total_tokens(&spec),
+        Err(_) => record.body.chars().count().div_ceil(4),
+    };
+    (tokens, true)
+}
+
+/// Compute per-layer consumption for every configured layer budget.
+///
+/// Totals come from an aggregate query over stored token counts; stale
+/// counts are recomputed and written back first so later reports are cheap.
+pub fn layer_usage(
+    db: &Database,
+    budgets: &BudgetConfig,
+    top_n: usize,
+) -> Result<(Vec<BudgetUsage>, usize)> {
+    if budgets.layer_budgets().next().is_none() {
+        return Ok((Vec::new(), 0));
+    }
+
+    let stale = db.skills_with_stale_token_counts()?;
+    for record in &stale {
+        let (tokens, _) = effective_token_count(record);
+        db.update_skill_token_count(&record.id, tokens as i64)?;
+    }
+
+    // Stored layer names may differ in case; merge them per budget layer
+    let mut by_layer: BTreeMap<String, (usize, usize, Vec<String>)> = BTreeMap::new();
+    for usage in db.layer_token_usage()? {
+        let entry = by_layer.entry(usage.layer.to_lowercase()).or_default();
+        entry.0 += usage.tokens as usize;
+        entry.1 += usage.skills as usize;
+        entry.2.push(usage.layer);
+    }
+
+    let mut usages = Vec::new();
+    for (layer, budget) in budgets.layer_budgets() {
+        let (used, count, stored_names) = by_layer.get(layer).cloned().unwrap_or_default();
+        let mut top = Vec::new();
+        for stored in &stored_names {
+            top.extend(
+                db.largest_layer_skills(stored, top_n)?
+                    .into_iter()
+                    .map(|(id, tokens)| BudgetContributor {
+                        id,
+                        tokens: tokens as usize,
+                    }),
+            );
+        }
+        usages.push(BudgetUsage::from_totals(
+            BudgetScope::Layer,
+            layer,
+            budget as usize,
+            used,
+            count,
+            top,
+            top_n,
+        ));
+    }
+    Ok((usages, stale.len()))
+}
+
+/// Greedily keep items in priority order while they fit within `budget`.
+///
+/// Items must already be sorted highest priority first. Returns the indices
+/// of kept and dropped items. `ms load --auto` applies it per layer budget;
+/// pack budgets cap the meta-skill packer, which degrades optional slices
+/// the same way.
+#[must_use]
+pub fn degrade_to_budget(costs: &[usize], budget: usize) -> (Vec<usize>, Vec<usize>) {
+    let mut kept = Vec::new();
+    let mut dropped = Vec::new();
+    let mut used = 0usize;
+    for (idx, cost) in costs.iter().enumerate() {
+        if used + cost <= budget {
+            used += cost;
+            kept.push(idx);
+        } else {
+            dropped.push(idx);
+        }
+    }
+    (kept, dropped)
+}
+
+/// Human-readable warning listing what a budget forced out.
+#[must_use]
+pub fn dropped_warning(
+    scope: BudgetScope,
+    name: &str,
+    budget: usize,
+    dropped: &[String],
+) -> String {
+    format!(
+        "{scope} budget '{name}' ({budget} tokens) exceeded; dropped: {}",
+        dropped.join(", ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contributor(id: &str, tokens: usize) -> BudgetContributor {
+        BudgetContributor {
+            id: id.to_string(),
+            tokens,
+        }
+    }
+
+    #[test]
+    fn usage_sorts_and_truncates_contributors() {
+        let usage = BudgetUsage::new(
+            BudgetScope::Layer,
+            "project",
+            100,
+            vec![
+                contributor("a", 10),
+                contributor("b", 90),
+                contributor("c", 30),
+            ],
+            2,
+        );
+        assert_eq!(usage.used, 130);
+        assert!(usage.over_budget);
+        assert_eq!(usage.overage(), 30);
+        assert_eq!(usage.contributor_count, 3);
+        assert_eq!(
+            usage.top_contributors,
+            vec![contributor("b", 90), contributor("c", 30)]
+        );
+    }
+
+    #[test]
+    fn usage_within_budget_has_no_overage() {
+        let usage = BudgetUsage::new(BudgetScope::Pack, "p", 100, vec![contributor("a", 40)], 5);
+        assert!(!usage.over_budget);
+        assert_eq!(usage.overage(), 0);
+        assert!((usage.percent_used - 40.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn report_emits_advisories_only_for_over_budget_packs() {
+        let report = BudgetReport::new(
+            vec![
+                BudgetUsage::new(BudgetScope::Pack, "big", 10, vec![contributor("a", 20)], 5),
+                BudgetUsage::new(BudgetScope::Pack, "ok", 10, vec![contributor("a", 5)], 5),
+                BudgetUsage::new(BudgetScope::Layer, "user", 1, vec![contributor("a", 5)], 5),
+            ],
+            0,
+        );
+        assert_eq!(report.advisories.len(), 1);
+        assert_eq!(report.advisories[0].rule_id, PACK_BUDGET_RULE_ID);
+        assert!(report.advisories[0].message.contains("big"));
+        assert_eq!(report.over_budget().len(), 2);
+    }
+
+    #[test]
+    fn degrade_keeps_priority_order_and_skips_oversized() {
+        let (kept, dropped) = degrade_to_budget(&[50, 60, 30, 10], 100);
+        assert_eq!(kept, vec![0, 2, 3]);
+        assert_eq!(dropped, vec![1]);
+    }
+
+    #[test]
+    fn dropped_warning_lists_items() {
+        let msg = dropped_warning(
+            BudgetScope::Layer,
+            "project",
+            800,
+            &["a".to_string(), "b".to_string()],
+        );
+        assert!(msg.contains("layer budget 'project'"));
+        assert!(msg.contains("a, b"));
+    }
+
+    #[test]
+    fn effective_token_count_recomputes_stale_values() {
+        let mut record = SkillRecord {
+            id: "s".to_string(),
+            name: "S".to_string(),
+            description: String::new(),
+            version: None,
+            author: None,
+            source_path: String::new(),
+            source_layer: "project".to_string(),
+            git_remote: None,
+            git_commit: None,
+            content_hash: String::new(),
+            body: "# S\n\nSome body text that is long enough.".to_string(),
+            metadata_json: "{}".to_string(),
+            assets_json: "{}".to_string(),
+            token_count: 0,
+            quality_score: 0.0,
+            indexed_at: String::new(),
+            modified_at: String::new(),
+            is_deprecated: false,
+            deprecation_reason: None,
+        };
+        let (tokens, stale) = effective_token_count(&record);
+        assert!(stale);
+        assert!(tokens > 0);
+
+        record.token_count = 42;
+        assert_eq!(effective_token_count(&record), (42, false));
+    }
+}
@@ -0,0 +1,561 @@
+//! Bulk metadata edits across many skills.
+//!
+//! A bulk edit selects skills with the same `tag:`/`layer:`/`quality:>` filter
+//! syntax used by search, applies metadata and tag operations (never body
+//! content), and commits the result as one unit. Every skill is changed and
+//! validated before anything is written; if a write fails part way through,
+//! the failed skill and the skills already written are restored in reverse
+//! order.
+
+use ms_search_core::ParsedQuery;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{MsError, Result};
+use crate::search::context::SearchFilters;
+
+use super::skill::SkillSpec;
+use super::validation::validate;
+
+/// Scalar metadata fields that `set` may change.
+pub const SETTABLE_SCALAR_FIELDS: &[&str] =
+    &["name", "description", "version", "author", "license"];
+
+/// List metadata fields that `set` may replace (comma-separated values).
+pub const SETTABLE_LIST_FIELDS: &[&str] = &["tags", "requires", "provides", "platforms"];
+
+/// A single metadata operation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum BulkOperation {
+    /// Set a metadata field, e.g. `metadata.author=data-team`.
+    Set { field: String, value: String },
+    /// Add a tag if it is not already present.
+    AddTag { tag: String },
+    /// Remove a tag if present.
+    RemoveTag { tag: String },
+}
+
+impl BulkOperation {
+    /// Parse a `--set` expression of the form `metadata.<field>=<value>`.
+    pub fn parse_set(expr: &str) -> Result<Self> {
+        let (field, value) = expr.split_once('=').ok_or_else(|| {
+            MsError::ValidationFailed(format!("invalid --set '{expr}': expected FIELD=VALUE"))
+        })?;
+        let op = Self::Set {
+            field: field.trim().to_string(),
+            value: value.trim().to_string(),
+        };
+        op.check()?;
+        Ok(op)
+    }
+
+    /// Reject operations that target unsupported fields or are empty.
+    pub fn check(&self) -> Result<()> {
+        match self {
+            Self::Set { field, .. } => {
+                let name = field_name(field);
+                if SETTABLE_SCALAR_FIELDS.contains(&name) || SETTABLE_LIST_FIELDS.contains(&name) {
+                    Ok(())
+                } else {
+                    Err(MsError::ValidationFailed(format!(
+                        "cannot bulk-set '{field}': supported fields are metadata.{{{},{}}}",
+                        SETTABLE_SCALAR_FIELDS.join(","),
+                        SETTABLE_LIST_FIELDS.join(",")
+                    )))
+                }
+            }
+            Self::AddTag { tag } | Self::RemoveTag { tag } => {
+                if tag.trim().is_empty() {
+                    Err(MsError::ValidationFailed(
+                        "tag must not be empty".to_string(),
+                    ))
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    }
+}
+
+/// Operations document accepted in robot mode (`ms edit --bulk --ops FILE`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BulkOpsDocument {
+    /// Selection filter, same syntax as `--filter`.
+    #[serde(default)]
+    pub filter: Option<String>,
+    pub operations: Vec<BulkOperation>,
+}
+
+/// Skill selection parsed from a filter string.
+#[derive(Debug, Clone, Default)]
+pub struct BulkSelector {
+    pub filters: SearchFilters,
+    /// Free-text terms and phrases; each must appear in the id, name, or description.
+    pub terms: Vec<String>,
+    /// Words or phrases that must not appear there.
+    pub excluded: Vec<String>,
+}
+
+impl BulkSelector {
+    /// Parse `tag:X layer:Y quality:>0.5 free text` ([`ParsedQuery`] syntax)
+    /// into a selector.
+    pub fn parse(filter: &str) -> Result<Self> {
+        let query = ParsedQuery::parse(filter);
+        Ok(Self {
+            filters: SearchFilters::from_query(&query)?,
+            terms: query.terms.into_iter().chain(query.phrases).collect(),
+            excluded: query.excluded,
+        })
+    }
+
+    /// Whether a skill passes the selector.
+    #[must_use]
+    pub fn matches(
+        &self,
+        spec: &SkillSpec,
+        layer: &str,
+        quality: f32,
+        is_deprecated: bool,
+    ) -> bool {
+        let tags: Vec<String> = spec
+            .metadata
+            .tags
+            .iter()
+            .map(|t| t.to_lowercase())
+            .collect();
+        if !self.filters.matches(&tags, layer, quality, is_deprecated) {
+            return false;
+        }
+        let haystack = format!(
+            "{} {} {}",
+            spec.metadata.id, spec.metadata.name, spec.metadata.description
+        )
+        .to_lowercase();
+        self.terms.iter().all(|term| haystack.contains(term))
+            && !self.excluded.iter().any(|term| haystack.contains(term))
+    }
+}
+
+/// One field changed by a bulk edit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: serde_json::Value,
+    pub new: serde_json::Value,
+}
+
+/// Outcome for one selected skill.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkEditStatus {
+    /// Would change (plan / dry-run).
+    Planned,
+    /// Already in the requested state.
+    Unchanged,
+    /// Failed validation after the change; nothing was applied.
+    Invalid,
+    /// Written successfully.
+    Applied,
+    /// Written, then restored because another skill failed.
+    RolledBack,
+    /// Never attempted because the transaction aborted first.
+    Skipped,
+}
+
+/// Planned or applied change for one skill.
+#[derive(Debug, Clone, Serialize)]
+pub struct BulkEditEntry {
+    pub skill_id: String,
+    pub layer: String,
+    pub status: BulkEditStatus,
+    pub changes: Vec<FieldChange>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip)]
+    pub before: SkillSpec,
+    #[serde(skip)]
+    pub after: SkillSpec,
+}
+
+/// A full bulk edit plan.
+#[derive(Debug, Clone, Serialize)]
+pub struct BulkEditPlan {
+    pub operations: Vec<BulkOperation>,
+    pub entries: Vec<BulkEditEntry>,
+}
+
+impl BulkEditPlan {
+    /// Apply `operations` to every selected skill and validate the results.
+    ///
+    /// `skills` holds each selected spec with its source layer.
+    #[must_use]
+    pub fn build(operations: &[BulkOperation], skills: Vec<(SkillSpec, String)>) -> Self {
+        let entries = skills
+            .into_iter()
+            .map(|(before, layer)| {
+                let mut after = before.clone();
+                let changes = apply_operations(&mut after, operations);
+                let (status, error) = if changes.is_empty() {
+                    (BulkEditStatus::Unchanged, None)
+                } else {
+                    match validate(&after) {
+                        Ok(_) => (BulkEditStatus::Planned, None),
+                        Err(err) => (BulkEditStatus::Invalid, Some(err.to_string())),
+                    }
+                };
+                BulkEditEntry {
+                    skill_id: before.metadata.id.clone(),
+                    layer,
+                    status,
+                    changes,
+                    error,
+                    before,
+                    after,
+                }
+            })
+            .collect();
+        Self {
+            operations: operations.to_vec(),
+            entries,
+        }
+    }
+
+    /// Number of skills that would be (or were) written.
+    #[must_use]
+    pub fn changed_count(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|e| matches!(e.status, BulkEditStatus::Planned | BulkEditStatus::Applied))
+            .count()
+    }
+
+    #[must_use]
+    pub fn has_invalid(&self) -> bool {
+        self.entries
+            .iter()
+            .any(|e| e.status == BulkEditStatus::Invalid)
+    }
+
+    /// Write every planned entry as one unit.
+    ///
+    /// Nothing is written if any entry is invalid. If `write` fails for an
+    /// entry, that entry (which may be partly written) and every entry already
+    /// written are passed to `restore`, most recent first, and the first error
+    /// is returned. Statuses are updated in place
+    /// so callers can report per-skill results either way.
+    pub fn apply<W, R>(&mut self, mut write: W, mut restore: R) -> Result<()>
+    where
+        W: FnMut(&BulkEditEntry) -> Result<()>,
+        R: FnMut(&BulkEditEntry) -> Result<()>,
+    {
+        if self.has_invalid() {
+            for entry in &mut self.entries {
+                if entry.status == BulkEditStatus::Planned {
+                    entry.status = BulkEditStatus::Skipped;
+                }
+            }
+            return Err(MsError::ValidationFailed(
+                "bulk edit aborted: one or more skills failed validation".to_string(),
+            ));
+        }
+
+        let mut written: Vec<usize> = Vec::new();
+        let mut failure: Option<(usize, MsError)> = None;
+        for (idx, entry) in self.entries.iter().enumerate() {
+            if entry.status != BulkEditStatus::Planned {
+                continue;
+            }
+            match write(entry) {
+                Ok(()) => written.push(idx),
+                Err(err) => {
+                    failure = Some((idx, err));
+                    break;
+                }
+            }
+        }
+
+        let Some((failed_idx, err)) = failure else {
+            for idx in written {
+                self.entries[idx].status = BulkEditStatus::Applied;
+            }
+            return Ok(());
+        };
+
+        let mut restore_errors = Vec::new();
+        if let Err(restore_err) = restore(&self.entries[failed_idx]) {
+            restore_errors.push(format!(
+                "{}: {restore_err}",
+                self.entries[failed_idx].skill_id
+            ));
+        }
+        for &idx in written.iter().rev() {
+            if let Err(restore_err) = restore(&self.entries[idx]) {
+                restore_errors.push(format!("{}: {restore_err}", self.entries[idx].skill_id));
+            }
+            self.entries[idx].status = BulkEditStatus::RolledBack;
+        }
+        for (idx, entry) in self.entries.iter_mut().enumerate() {
+            if idx == failed_idx {
+                entry.status = BulkEditStatus::Invalid;
+                entry.error = Some(err.to_string());
+            } else if entry.status == BulkEditStatus::Planned {
+                entry.status = BulkEditStatus::Skipped;
+            }
+        }
+
+        let mut message = format!(
+            "bulk edit rolled back after {} failed: {err}",
+            self.entries[failed_idx].skill_id
+        );
+        if !restore_errors.is_empty() {
+            message.push_str(&format!("; restore errors: {}", restore_errors.join("; ")));
+        }
+        Err(MsError::TransactionFailed(message))
+    }
+}
+
+/// Apply operations to a spec, returning the fields that actually changed.
+pub fn apply_operations(spec: &mut SkillSpec, operations: &[BulkOperation]) -> Vec<FieldChange> {
+    let before_tags = spec.metadata.tags.clone();
+    let mut changes = Vec::new();
+
+    for op in operations {
+        match op {
+            BulkOperation::Set { field, value } => {
+                let name = field_name(field);
+                if name == "tags" {
+                    spec.metadata.tags = split_list(value);
+                    continue;
+                }
+                let (old, new) = match name {
+                    "name" => replace_string(&mut spec.metadata.name, value),
+                    "description" => replace_string(&mut spec.metadata.description, value),
+                    "version" => replace_string(&mut spec.metadata.version, value),
+                    "author" => replace_option(&mut spec.metadata.author, value),
+                    "license" => replace_option(&mut spec.metadata.license, value),
+                    "requires" => replace_list(&mut spec.metadata.requires, value),
+                    "provides" => replace_list(&mut spec.metadata.provides, value),
+                    "platforms" => replace_list(&mut spec.metadata.platforms, value),
+                    _ => continue,
+                };
+                if old != new {
+                    changes.push(FieldChange {
+                        field: format!("metadata.{name}"),
+                        old,
+                        new,
+                    });
+                }
+            }
+            BulkOperation::AddTag { tag } => {
+                let tag = tag.trim();
+                if !spec.metadata.tags.iter().any(|t| t == tag) {
+                    spec.metadata.tags.push(tag.to_string());
+                }
+            }
+            BulkOperation::RemoveTag { tag } => {
+                let tag = tag.trim();
+                spec.metadata.tags.retain(|t| t != tag);
+            }
+        }
+    }
+
+    if spec.metadata.tags != before_tags {
+        changes.push(FieldChange {
+            field: "metadata.tags".to_string(),
+            old: serde_json::Value::from(before_tags),
+            new: serde_json::Value::from(spec.metadata.tags.clone()),
+        });
+    }
+    changes
+}
+
+fn field_name(field: &str) -> &str {
+    field.strip_prefix("metadata.").unwrap_or(field)
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn replace_string(slot: &mut String, value: &str) -> (serde_json::Value, serde_json::Value) {
+    let old = serde_json::Value::from(std::mem::replace(slot, value.to_string()));
+    (old, serde_json::Value::from(value))
+}
+
+fn replace_option(
+    slot: &mut Option<String>,
+    value: &str,
+) -> (serde_json::Value, serde_json::Value) {
+    let new = if value.is_empty() {
+        None
+    } else {
+        Some(value.to_string())
+    };
+    let old = serde_json::to_value(std::mem::replace(slot, new.clone())).unwrap_or_default();
+    (old, serde_json::to_value(new).unwrap_or_default())
+}
+
+fn replace_list(slot: &mut Vec<String>, value: &str) -> (serde_json::Value, serde_json::Value) {
+    let new = split_list(value);
+    let old = serde_json::Value::from(std::mem::replace(slot, new.clone()));
+    (old, serde_json::Value::from(new))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::skill::SkillMetadata;
+
+    fn spec(id: &str, tags: &[&str]) -> SkillSpec {
+        SkillSpec {
+            metadata: SkillMetadata {
+                id: id.to_string(),
+                name: id.to_string(),
+                description: format!("{id} skill"),
+                tags: tags.iter().map(|t| (*t).to_string()).collect(),
+                ..Default::default()
+            },
+            ..SkillSpec::new(id, id)
+        }
+    }
+
+    fn ops() -> Vec<BulkOperation> {
+        vec![
+            BulkOperation::parse_set("metadata.author=data-team").unwrap(),
+            BulkOperation::AddTag {
+                tag: "reviewed-2025".to_string(),
+            },
+        ]
+    }
+
+    #[test]
+    fn parse_set_rejects_unknown_and_malformed_fields() {
+        assert!(BulkOperation::parse_set("metadata.author=x").is_ok());
+        assert!(BulkOperation::parse_set("metadata.body=x").is_err());
+        assert!(BulkOperation::parse_set("metadata.author").is_err());
+    }
+
+    #[test]
+    fn selector_uses_search_filter_syntax() {
+        let selector = BulkSelector::parse("tag:database layer:project pool").unwrap();
+        let mut db = spec("db-pool", &["database"]);
+        db.metadata.description = "connection pool tuning".to_string();
+        assert!(selector.matches(&db, "project", 0.5, false));
+        assert!(!selector.matches(&db, "user", 0.5, false));
+        assert!(!selector.matches(&spec("db-other", &["database"]), "project", 0.5, false));
+        assert!(BulkSelector::parse("layer:nowhere").is_err());
+
+        // Repeated tags are all required and `-word` excludes
+        let both = BulkSelector::parse("tag:database tag:sql").unwrap();
+        assert!(!both.matches(&db, "project", 0.5, false));
+        let excluding = BulkSelector::parse("tag:database -tuning").unwrap();
+        assert!(!excluding.matches(&db, "project", 0.5, false));
+    }
+
+    #[test]
+    fn plan_records_field_changes() {
+        let plan = BulkEditPlan::build(&ops(), vec![(spec("a", &["database"]), "project".into())]);
+        let entry = &plan.entries[0];
+        assert_eq!(entry.status, BulkEditStatus::Planned);
+        let fields: Vec<_> = entry.changes.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(fields, vec!["metadata.author", "metadata.tags"]);
+        assert_eq!(entry.after.metadata.author.as_deref(), Some("data-team"));
+    }
+
+    #[test]
+    fn reapplying_is_idempotent() {
+        let mut first = BulkEditPlan::build(&ops(), vec![(spec("a", &["x"]), "project".into())]);
+        first.apply(|_| Ok(()), |_| Ok(())).unwrap();
+        assert_eq!(first.entries[0].status, BulkEditStatus::Applied);
+
+        let updated = first.entries[0].after.clone();
+        let mut second = BulkEditPlan::build(&ops(), vec![(updated, "project".into())]);
+        assert_eq!(second.entries[0].status, BulkEditStatus::Unchanged);
+        assert!(second.entries[0].changes.is_empty());
+        let mut writes = 0;
+        second
+            .apply(
+                |_| {
+                    writes += 1;
+                    Ok(())
+                },
+                |_| Ok(()),
+            )
+            .unwrap();
+        assert_eq!(writes, 0);
+        assert_eq!(second.changed_count(), 0);
+    }
+
+    #[test]
+    fn invalid_skill_aborts_before_any_write() {
+        let renamed = vec![BulkOperation::Set {
+            field: "metadata.name".to_string(),
+            value: String::new(),
+        }];
+        let mut plan = BulkEditPlan::build(
+            &renamed,
+            vec![
+                (spec("a", &["x"]), "project".into()),
+                (spec("b", &["x"]), "project".into()),
+            ],
+        );
+        assert!(plan.has_invalid());
+        let mut writes = 0;
+        let result = plan.apply(
+            |_| {
+                writes += 1;
+                Ok(())
+            },
+            |_| Ok(()),
+        );
+        assert!(result.is_err());
+        assert_eq!(writes, 0);
+    }
+
+    #[test]
+    fn write_failure_rolls_back_written_skills() {
+        let mut plan = BulkEditPlan::build(
+            &ops(),
+            vec![
+                (spec("a", &[]), "project".into()),
+                (spec("b", &[]), "project".into()),
+                (spec("c", &[]), "project".into()),
+                (spec("d", &[]), "project".into()),
+            ],
+        );
+        let mut restored = Vec::new();
+        let result = plan.apply(
+            |entry| {
+                if entry.skill_id == "c" {
+                    Err(MsError::ValidationFailed("boom".to_string()))
+                } else {
+                    Ok(())
+                }
+            },
+            |entry| {
+                restored.push(entry.skill_id.clone());
+                Ok(())
+            },
+        );
+        assert!(matches!(result, Err(MsError::TransactionFailed(_))));
+        assert_eq!(
+            restored,
+            vec!["c".to_string(), "b".to_string(), "a".to_string()]
+        );
+        let statuses: Vec<_> = plan.entries.iter().map(|e| e.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                BulkEditStatus::RolledBack,
+                BulkEditStatus::RolledBack,
+                BulkEditStatus::Invalid,
+                BulkEditStatus::Skipped,
+            ]
+        );
+    }
+}
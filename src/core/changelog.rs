@@ -0,0 +1,601 @@
+//! Per-skill changelogs from archive history (`ms show --changelog`).
+//!
+//! Consecutive archived versions of a skill are compared section by section
+//! with [`compute_section_diff`]. Each added, removed, or modified block gets
+//! a one-line summary, and the changes are grouped under the `version` the
+//! skill declared at the time. A version that differs from its predecessor
+//! only in whitespace (what `ms fmt` produces) is reported as formatting.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::layering::compute_section_diff;
+use super::skill::{BlockType, SkillBlock, SkillMetadata, SkillSection, SkillSpec};
+use crate::error::{MsError, Result};
+use crate::quality::usage::parse_since;
+
+/// Longest block summary, in characters.
+const SUMMARY_CHARS: usize = 80;
+
+/// One archived version of a skill.
+#[derive(Debug, Clone)]
+pub struct SkillVersion {
+    pub oid: String,
+    pub committed_at: DateTime<Utc>,
+    /// First line of the commit message
+    pub message: String,
+    /// `None` when the commit removed the skill
+    pub spec: Option<SkillSpec>,
+}
+
+/// What happened to a block or field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+impl ChangeKind {
+    const fn marker(self) -> char {
+        match self {
+            Self::Added => '+',
+            Self::Removed => '-',
+            Self::Modified => '~',
+        }
+    }
+}
+
+/// An added, removed, or modified block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockChange {
+    pub section: String,
+    pub block_id: String,
+    pub block_type: BlockType,
+    pub change: ChangeKind,
+    /// First line of the block's (new) content
+    pub summary: String,
+}
+
+/// A metadata field whose value changed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MetadataChange {
+    pub field: String,
+    pub before: String,
+    pub after: String,
+}
+
+/// What kind of version an entry records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryKind {
+    /// First archived version
+    Created,
+    /// Content or metadata changed
+    Changed,
+    /// Only whitespace changed
+    Formatting,
+    /// The skill was removed from the archive
+    Deleted,
+}
+
+/// Changes one archived version made.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangelogEntry {
+    pub oid: String,
+    pub committed_at: DateTime<Utc>,
+    pub message: String,
+    /// Skill `version` after the change
+    pub version: Option<String>,
+    pub kind: EntryKind,
+    /// Rule and pitfall blocks
+    pub rules: Vec<BlockChange>,
+    /// Code and command blocks
+    pub examples: Vec<BlockChange>,
+    /// Text and checklist blocks
+    pub other: Vec<BlockChange>,
+    pub metadata: Vec<MetadataChange>,
+}
+
+/// Entries sharing one declared version, newest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangelogRelease {
+    pub version: Option<String>,
+    /// Date of the newest entry
+    pub date: DateTime<Utc>,
+    pub entries: Vec<ChangelogEntry>,
+}
+
+impl ChangelogRelease {
+    /// One line per change, rules first; formatting-only entries collapse
+    /// into a single line.
+    #[must_use]
+    pub fn notes(&self) -> Vec<String> {
+        let mut notes = Vec::new();
+        let block_line = |label: &str, change: &BlockChange| {
+            format!(
+                "{} {label} {}: {}",
+                change.change.marker(),
+                change.block_id,
+                change.summary
+            )
+        };
+        for entry in &self.entries {
+            match entry.kind {
+                EntryKind::Created => notes.push("created".to_string()),
+                EntryKind::Deleted => notes.push("deleted".to_string()),
+                EntryKind::Changed | EntryKind::Formatting => {}
+            }
+            notes.extend(entry.rules.iter().map(|c| block_line("rule", c)));
+            notes.extend(entry.examples.iter().map(|c| block_line("example", c)));
+            notes.extend(entry.other.iter().map(|c| block_line("block", c)));
+            notes.extend(
+                entry
+                    .metadata
+                    .iter()
+                    .map(|m| format!("~ {}: {} -> {}", m.field, m.before, m.after)),
+            );
+        }
+        let formatting = self
+            .entries
+            .iter()
+            .filter(|e| e.kind == EntryKind::Formatting)
+            .count();
+        match formatting {
+            0 => {}
+            1 => notes.push("formatting".to_string()),
+            n => notes.push(format!("formatting ({n} versions)")),
+        }
+        notes
+    }
+}
+
+/// Where a changelog starts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangelogSince {
+    /// Versions committed at or after this time
+    Time(DateTime<Utc>),
+    /// Versions after the last one declaring this version
+    Version(String),
+}
+
+impl ChangelogSince {
+    /// A relative age or date as accepted by `ms stats --since` (`30d`,
+    /// `2026-03-01`), otherwise a skill version (`v1.2.0`).
+    pub fn parse(input: &str, now: DateTime<Utc>) -> Result<Self> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(MsError::ValidationFailed(
+                "--since needs a version, age, or date".to_string(),
+            ));
+        }
+        Ok(parse_since(input, now).map_or_else(
+            |_| Self::Version(normalize_version(input).to_string()),
+            Self::Time,
+        ))
+    }
+
+    /// The entries (newest first) that come after this point. A version
+    /// never declared in the history is compared as semver; if it is not
+    /// semver either, that is an error.
+    pub fn filter(&self, entries: Vec<ChangelogEntry>) -> Result<Vec<ChangelogEntry>> {
+        match self {
+            Self::Time(since) => Ok(entries
+                .into_iter()
+                .filter(|entry| entry.committed_at >= *since)
+                .collect()),
+            Self::Version(version) => {
+                let declared = |entry: &ChangelogEntry| {
+                    entry
+                        .version
+                        .as_deref()
+                        .is_some_and(|v| normalize_version(v) == version.as_str())
+                };
+                if let Some(boundary) = entries.iter().position(declared) {
+                    return Ok(entries.into_iter().take(boundary).collect());
+                }
+                let since = semver::Version::parse(version).map_err(|_| {
+                    MsError::NotFound(format!("version {version} does not appear in the history"))
+                })?;
+                Ok(entries
+                    .into_iter()
+                    .filter(|entry| {
+                        entry
+                            .version
+                            .as_deref()
+                            .and_then(|v| semver::Version::parse(normalize_version(v)).ok())
+                            .is_some_and(|v| v > since)
+                    })
+                    .collect())
+            }
+        }
+    }
+}
+
+fn normalize_version(version: &str) -> &str {
+    let version = version.trim();
+    version.strip_prefix('v').unwrap_or(version)
+}
+
+/// Changelog entries for `versions` (oldest first), newest first.
+#[must_use]
+pub fn build_changelog(versions: &[SkillVersion]) -> Vec<ChangelogEntry> {
+    let mut entries = Vec::with_capacity(versions.len());
+    let mut previous: Option<&SkillSpec> = None;
+    for version in versions {
+        let entry = match (previous, version.spec.as_ref()) {
+            (_, None) => {
+                if previous.is_none() {
+                    continue;
+                }
+                new_entry(version, None, EntryKind::Deleted)
+            }
+            (None, Some(spec)) => new_entry(version, Some(spec), EntryKind::Created),
+            (Some(old), Some(new)) => {
+                let mut changed = new_entry(version, Some(new), EntryKind::Changed);
+                diff_specs(old, new, &mut changed);
+                if changed.rules.is_empty()
+                    && changed.examples.is_empty()
+                    && changed.other.is_empty()
+                    && changed.metadata.is_empty()
+                {
+                    changed.kind = EntryKind::Formatting;
+                }
+                changed
+            }
+        };
+        entries.push(entry);
+        previous = version.spec.as_ref();
+    }
+    entries.reverse();
+    entries
+}
+
+/// Group entries (newest first) by consecutive declared version.
+#[must_use]
+pub fn group_by_version(entries: Vec<ChangelogEntry>) -> Vec<ChangelogRelease> {
+    let mut releases: Vec<ChangelogRelease> = Vec::new();
+    for entry in entries {
+        match releases.last_mut() {
+            Some(release) if release.version == entry.version => release.entries.push(entry),
+            _ => releases.push(ChangelogRelease {
+                version: entry.version.clone(),
+                date: entry.committed_at,
+                entries: vec![entry],
+            }),
+        }
+    }
+    releases
+}
+
+fn new_entry(version: &SkillVersion, spec: Option<&SkillSpec>, kind: EntryKind) -> ChangelogEntry {
+    ChangelogEntry {
+        oid: version.oid.clone(),
+        committed_at: version.committed_at,
+        message: version.message.clone(),
+        version: spec
+            .map(|spec| spec.metadata.version.trim())
+            .filter(|v| !v.is_empty())
+            .map(str::to_string),
+        kind,
+        rules: Vec::new(),
+        examples: Vec::new(),
+        other: Vec::new(),
+        metadata: Vec::new(),
+    }
+}
+
+fn diff_specs(old: &SkillSpec, new: &SkillSpec, entry: &mut ChangelogEntry) {
+    entry.metadata = diff_metadata(&old.metadata, &new.metadata);
+
+    let mut changes = Vec::new();
+    for section in &new.sections {
+        let before = old.sections.iter().find(|s| s.id == section.id);
+        diff_section(before, section, &mut changes);
+    }
+    for section in &old.sections {
+        if !new.sections.iter().any(|s| s.id == section.id) {
+            changes.extend(
+                section
+                    .blocks
+                    .iter()
+                    .map(|block| block_change(section, block, ChangeKind::Removed)),
+            );
+        }
+    }
+
+    for change in changes {
+        match change.block_type {
+            BlockType::Rule | BlockType::Pitfall => entry.rules.push(change),
+            BlockType::Code | BlockType::Command => entry.examples.push(change),
+            BlockType::Text | BlockType::Checklist => entry.other.push(change),
+        }
+    }
+}
+
+/// Block changes from `before` (if the section existed) to `after`.
+fn diff_section(
+    before: Option<&SkillSection>,
+    after: &SkillSection,
+    changes: &mut Vec<BlockChange>,
+) {
+    let empty = SkillSection {
+        id: after.id.clone(),
+        title: after.title.clone(),
+        blocks: Vec::new(),
+    };
+    let before = before.unwrap_or(&empty);
+    let diff = compute_section_diff(after, before);
+    for block_id in &diff.higher_only {
+        if let Some(block) = find_block(after, block_id) {
+            changes.push(block_change(after, block, ChangeKind::Added));
+        }
+    }
+    for modified in &diff.modified {
+        if normalize_content(&modified.higher_content) == normalize_content(&modified.lower_content)
+        {
+            continue;
+        }
+        if let Some(block) = find_block(after, &modified.block_id) {
+            changes.push(block_change(after, block, ChangeKind::Modified));
+        }
+    }
+    for block_id in &diff.lower_only {
+        if let Some(block) = find_block(before, block_id) {
+            changes.push(block_change(after, block, ChangeKind::Removed));
+        }
+    }
+}
+
+fn find_block<'a>(section: &'a SkillSection, block_id: &str) -> Option<&'a SkillBlock> {
+    section.blocks.iter().find(|b| b.id == block_id)
+}
+
+fn block_change(section: &SkillSection, block: &SkillBlock, change: ChangeKind) -> BlockChange {
+    BlockChange {
+        section: section.title.clone(),
+        block_id: block.id.clone(),
+        block_type: block.block_type.clone(),
+        change,
+        summary: summarize(&block.content),
+    }
+}
+
+/// Block content with trailing whitespace and blank-line runs removed, so
+/// re-wrapping and re-indenting by `ms fmt` does not count as a change.
+fn normalize_content(content: &str) -> String {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// First meaningful line of a block: list markers and code fences are
+/// skipped, long lines are cut at [`SUMMARY_CHARS`].
+fn summarize(content: &str) -> String {
+    let line = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("```") && !line.starts_with("~~~"))
+        .map(|line| {
+            line.trim_start_matches(['-', '*', '+', '>'])
+                .trim_start_matches("[ ]")
+                .trim_start_matches("[x]")
+                .trim()
+        })
+        .find(|line| !line.is_empty())
+        .unwrap_or_default();
+    if line.chars().count() <= SUMMARY_CHARS {
+        return line.to_string();
+    }
+    let cut: String = line.chars().take(SUMMARY_CHARS - 3).collect();
+    format!("{}...", cut.trim_end())
+}
+
+fn diff_metadata(old: &SkillMetadata, new: &SkillMetadata) -> Vec<MetadataChange> {
+    let fields: [(&str, String, String); 8] = [
+        ("name", old.name.clone(), new.name.clone()),
+        (
+            "description",
+            old.description.clone(),
+            new.description.clone(),
+        ),
+        ("version", old.version.clone(), new.version.clone()),
+        ("tags", old.tags.join(", "), new.tags.join(", ")),
+        ("requires", old.requires.join(", "), new.requires.join(", ")),
+        ("provides", old.provides.join(", "), new.provides.join(", ")),
+        (
+            "recommends",
+            old.recommends.join(", "),
+            new.recommends.join(", "),
+        ),
+        (
+            "conflicts_with",
+            old.conflicts_with.join(", "),
+            new.conflicts_with.join(", "),
+        ),
+    ];
+    fields
+        .into_iter()
+        .filter(|(_, before, after)| before.trim() != after.trim())
+        .map(|(field, before, after)| MetadataChange {
+            field: field.to_string(),
+            before: if before.is_empty() {
+                "(none)".to_string()
+            } else {
+                before
+            },
+            after: if after.is_empty() {
+                "(none)".to_string()
+            } else {
+                after
+            },
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(version: &str, blocks: &[(&str, BlockType, &str)]) -> SkillSpec {
+        let mut spec = SkillSpec::new("deploy", "Deploy");
+        spec.metadata.version = version.to_string();
+        spec.sections.push(SkillSection {
+            id: "rules".to_string(),
+            title: "Rules".to_string(),
+            blocks: blocks
+                .iter()
+                .map(|(id, block_type, content)| SkillBlock {
+                    id: (*id).to_string(),
+                    block_type: block_type.clone(),
+                    content: (*content).to_string(),
+                })
+                .collect(),
+        });
+        spec
+    }
+
+    fn version(n: i64, spec: Option<SkillSpec>) -> SkillVersion {
+        SkillVersion {
+            oid: format!("c{n}"),
+            committed_at: DateTime::from_timestamp(1_700_000_000 + n * 86_400, 0).unwrap(),
+            message: format!("commit {n}"),
+            spec,
+        }
+    }
+
+    fn history() -> Vec<SkillVersion> {
+        vec![
+            version(
+                1,
+                Some(spec(
+                    "1.0.0",
+                    &[
+                        ("rule-1", BlockType::Rule, "- Plan before apply"),
+                        ("example-1", BlockType::Code, "```bash\nterraform plan\n```"),
+                    ],
+                )),
+            ),
+            // ms fmt re-indented the rule
+            version(
+                2,
+                Some(spec(
+                    "1.0.0",
+                    &[
+                        ("rule-1", BlockType::Rule, "- Plan before apply  \n\n"),
+                        ("example-1", BlockType::Code, "```bash\nterraform plan\n```"),
+                    ],
+                )),
+            ),
+            version(
+                3,
+                Some(spec(
+                    "1.1.0",
+                    &[
+                        ("rule-1", BlockType::Rule, "- Plan, review, then apply"),
+                        ("rule-2", BlockType::Rule, "Pin provider versions"),
+                        (
+                            "example-1",
+                            BlockType::Code,
+                            "```bash\nterraform plan -out plan.tfplan\n```",
+                        ),
+                    ],
+                )),
+            ),
+        ]
+    }
+
+    #[test]
+    fn entries_record_block_and_metadata_changes() {
+        let entries = build_changelog(&history());
+        let kinds: Vec<EntryKind> = entries.iter().map(|e| e.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                EntryKind::Changed,
+                EntryKind::Formatting,
+                EntryKind::Created
+            ]
+        );
+
+        let latest = &entries[0];
+        assert_eq!(latest.version.as_deref(), Some("1.1.0"));
+        let rules: Vec<(&str, ChangeKind, &str)> = latest
+            .rules
+            .iter()
+            .map(|c| (c.block_id.as_str(), c.change, c.summary.as_str()))
+            .collect();
+        assert_eq!(
+            rules,
+            [
+                ("rule-2", ChangeKind::Added, "Pin provider versions"),
+                ("rule-1", ChangeKind::Modified, "Plan, review, then apply"),
+            ]
+        );
+        assert_eq!(
+            latest.examples[0].summary,
+            "terraform plan -out plan.tfplan"
+        );
+        assert_eq!(
+            latest.metadata,
+            [MetadataChange {
+                field: "version".to_string(),
+                before: "1.0.0".to_string(),
+                after: "1.1.0".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn releases_group_by_version_and_collapse_formatting() {
+        let mut versions = history();
+        versions.push(version(4, None));
+        let releases = group_by_version(build_changelog(&versions));
+        let names: Vec<Option<&str>> = releases.iter().map(|r| r.version.as_deref()).collect();
+        assert_eq!(names, [None, Some("1.1.0"), Some("1.0.0")]);
+        assert_eq!(releases[0].notes(), ["deleted"]);
+        assert_eq!(releases[2].notes(), ["created", "formatting"]);
+        assert!(
+            releases[1]
+                .notes()
+                .contains(&"+ rule rule-2: Pin provider versions".to_string())
+        );
+    }
+
+    #[test]
+    fn since_filters_by_version_or_time() {
+        let now = DateTime::from_timestamp(1_700_000_000 + 10 * 86_400, 0).unwrap();
+        let entries = build_changelog(&history());
+
+        let since = ChangelogSince::parse("v1.0.0", now).unwrap();
+        assert_eq!(since, ChangelogSince::Version("1.0.0".to_string()));
+        let kept = since.filter(entries.clone()).unwrap();
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].oid, "c3");
+
+        // Never declared, but older than everything in the history
+        let kept = ChangelogSince::Version("0.9.0".to_string())
+            .filter(entries.clone())
+            .unwrap();
+        assert_eq!(kept.len(), 3);
+        assert!(
+            ChangelogSince::Version("next".to_string())
+                .filter(entries.clone())
+                .is_err()
+        );
+
+        let since = ChangelogSince::parse("7d", now).unwrap();
+        assert!(matches!(since, ChangelogSince::Time(_)));
+        let kept: Vec<String> = since
+            .filter(entries)
+            .unwrap()
+            .into_iter()
+            .map(|e| e.oid)
+            .collect();
+        assert_eq!(kept, ["c3"]);
+    }
+}
@@ -0,0 +1,490 @@
+//! Model context window awareness for loads.
+//!
+//! Tracks the running total of tokens ms has served within an agent session
+//! and warns when it crosses a fraction of the consuming model's context
+//! window. This is advisory only: a warning never blocks a load, and unknown
+//! models skip the check entirely. Ledgers untouched for longer than
+//! `[context_window] ledger_retention_days` are pruned as loads are tracked.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::debug;
+
+use crate::agent_detection::AgentDetectionService;
+use crate::config::ContextWindowConfig;
+use crate::error::{MsError, Result};
+
+use super::budget::BudgetContributor;
+
+/// Built-in context window sizes (tokens) keyed by model family prefix.
+///
+/// Matching uses the longest prefix, so `gpt-4o` wins over `gpt-4`. Entries
+/// can be overridden or extended via `[context_window] windows` in config.
+pub const KNOWN_CONTEXT_WINDOWS: &[(&str, usize)] = &[
+    ("claude", 200_000),
+    ("gpt-5", 400_000),
+    ("gpt-4.1", 1_047_576),
+    ("gpt-4o", 128_000),
+    ("gpt-4-turbo", 128_000),
+    ("gpt-4", 8_192),
+    ("o1", 200_000),
+    ("o3", 200_000),
+    ("o4-mini", 200_000),
+    ("gemini", 1_048_576),
+    ("llama", 128_000),
+    ("mistral", 128_000),
+    ("codestral", 256_000),
+    ("deepseek", 128_000),
+    ("qwen", 131_072),
+];
+
+/// Directory (under the ms root) holding per-session served-token ledgers.
+pub const SESSION_LEDGER_DIR: &str = "load_sessions";
+
+/// Number of largest served items listed in a warning.
+const BREAKDOWN_LIMIT: usize = 5;
+
+/// Look up the context window for a model name.
+///
+/// Provider prefixes such as `anthropic/` are ignored. Config overrides are
+/// consulted before the built-in table; within each, the longest matching
+/// family prefix wins.
+#[must_use]
+pub fn context_window_for(model: &str, overrides: &BTreeMap<String, usize>) -> Option<usize> {
+    let normalized = model.trim().to_lowercase();
+    let name = normalized.rsplit('/').next().unwrap_or(&normalized);
+
+    longest_prefix_match(name, overrides.iter().map(|(k, v)| (k.as_str(), *v)))
+        .or_else(|| longest_prefix_match(name, KNOWN_CONTEXT_WINDOWS.iter().copied()))
+}
+
+fn longest_prefix_match<'a>(
+    name: &str,
+    entries: impl Iterator<Item = (&'a str, usize)>,
+) -> Option<usize> {
+    entries
+        .filter(|(family, _)| name.starts_with(&family.to_lowercase()))
+        .max_by_key(|(family, _)| family.len())
+        .map(|(_, window)| window)
+}
+
+/// Resolve the consuming model: explicit value first, then agent config
+/// inference when enabled.
+#[must_use]
+pub fn resolve_model(explicit: Option<&str>, config: &ContextWindowConfig) -> Option<String> {
+    if let Some(model) = explicit.map(str::trim).filter(|m| !m.is_empty()) {
+        return Some(model.to_string());
+    }
+    if !config.infer_model {
+        return None;
+    }
+    AgentDetectionService::new()
+        .configured_model()
+        .map(|(_, model)| model)
+}
+
+/// One item served during a session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServedItem {
+    pub id: String,
+    pub tokens: usize,
+    pub served_at: String,
+}
+
+/// Running record of what has been served within one agent session.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SessionLedger {
+    pub session_id: String,
+    #[serde(default)]
+    pub served: Vec<ServedItem>,
+}
+
+impl SessionLedger {
+    #[must_use]
+    pub fn new(session_id: impl Into<String>) -> Self {
+        Self {
+            session_id: session_id.into(),
+            served: Vec::new(),
+        }
+    }
+
+    /// Ledger file path. The session id is sanitized for readability and
+    /// suffixed with a hash of the raw id, so ids that sanitize alike
+    /// (`a/b`, `a:b`) still get separate ledgers.
+    #[must_use]
+    pub fn path(ms_root: &Path, session_id: &str) -> PathBuf {
+        let readable: String = session_id
+            .chars()
+            .take(64)
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let hash = hex::encode(&Sha256::digest(session_id.as_bytes())[..8]);
+        ms_root
+            .join(SESSION_LEDGER_DIR)
+            .join(format!("{readable}-{hash}.json"))
+    }
+
+    /// Load a session ledger, starting a fresh one if none exists.
+    pub fn load(ms_root: &Path, session_id: &str) -> Result<Self> {
+        let path = Self::path(ms_root, session_id);
+        if !path.exists() {
+            return Ok(Self::new(session_id));
+        }
+        let content = fs::read_to_string(&path)?;
+        serde_json::from_str(&content).map_err(|e| {
+            MsError::Config(format!(
+                "Failed to parse session ledger {}: {e}",
+                path.display()
+            ))
+        })
+    }
+
+    pub fn save(&self, ms_root: &Path) -> Result<()> {
+        let path = Self::path(ms_root, &self.session_id);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn record(&mut self, id: impl Into<String>, tokens: usize) {
+        self.served.push(ServedItem {
+            id: id.into(),
+            tokens,
+            served_at: chrono::Utc::now().to_rfc3339(),
+        });
+    }
+
+    #[must_use]
+    pub fn total_tokens(&self) -> usize {
+        self.served.iter().map(|item| item.tokens).sum()
+    }
+
+    /// Served tokens aggregated per item, largest first.
+    #[must_use]
+    pub fn breakdown(&self) -> Vec<BudgetContributor> {
+        aggregate(
+            self.served
+                .iter()
+                .map(|item| (item.id.as_str(), item.tokens)),
+        )
+    }
+}
+
+/// Delete session ledgers last written more than `max_age` ago.
+///
+/// Returns the number of ledgers removed.
+pub fn prune_session_ledgers(ms_root: &Path, max_age: Duration) -> Result<usize> {
+    let dir = ms_root.join(SESSION_LEDGER_DIR);
+    if !dir.exists() {
+        return Ok(0);
+    }
+    let Some(cutoff) = SystemTime::now().checked_sub(max_age) else {
+        return Ok(0);
+    };
+    let mut removed = 0;
+    for entry in fs::read_dir(&dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        if entry.metadata()?.modified()? < cutoff {
+            fs::remove_file(&path)?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+fn aggregate<'a>(items: impl Iterator<Item = (&'a str, usize)>) -> Vec<BudgetContributor> {
+    let mut totals: BTreeMap<&str, usize> = BTreeMap::new();
+    for (id, tokens) in items {
+        *totals.entry(id).or_insert(0) += tokens;
+    }
+    let mut breakdown: Vec<BudgetContributor> = totals
+        .into_iter()
+        .map(|(id, tokens)| BudgetContributor {
+            id: id.to_string(),
+            tokens,
+        })
+        .collect();
+    breakdown.sort_by(|a, b| b.tokens.cmp(&a.tokens).then_with(|| a.id.cmp(&b.id)));
+    breakdown
+}
+
+/// Served content is approaching the model's context window.
+#[derive(Debug, Clone, Serialize)]
+pub struct ContextWindowWarning {
+    pub model: String,
+    pub context_window: usize,
+    pub served_tokens: usize,
+    pub threshold_tokens: usize,
+    pub percent_of_window: f64,
+    pub session_id: Option<String>,
+    /// Largest served items, in descending token order.
+    pub breakdown: Vec<BudgetContributor>,
+    pub suggestion: String,
+}
+
+impl std::fmt::Display for ContextWindowWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let scope = self
+            .session_id
+            .as_deref()
+            .map_or_else(|| "this load".to_string(), |id| format!("session {id}"));
+        let largest = self
+            .breakdown
+            .iter()
+            .map(|c| format!("{} ({})", c.id, c.tokens))
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(
+            f,
+            "{scope} has served {} tokens, {:.0}% of the {} token context window of {}; largest: {largest}. {}",
+            self.served_tokens,
+            self.percent_of_window,
+            self.context_window,
+            self.model,
+            self.suggestion
+        )
+    }
+}
+
+/// Compare served tokens against a fraction of the context window.
+#[must_use]
+pub fn check_served(
+    model: &str,
+    context_window: usize,
+    warn_fraction: f32,
+    served_tokens: usize,
+    mut breakdown: Vec<BudgetContributor>,
+    session_id: Option<&str>,
+) -> Option<ContextWindowWarning> {
+    let threshold_tokens = (context_window as f64 * f64::from(warn_fraction)) as usize;
+    if context_window == 0 || served_tokens <= threshold_tokens {
+        return None;
+    }
+    breakdown.truncate(BREAKDOWN_LIMIT);
+    Some(ContextWindowWarning {
+        model: model.to_string(),
+        context_window,
+        served_tokens,
+        threshold_tokens,
+        percent_of_window: (served_tokens as f64 / context_window as f64) * 100.0,
+        session_id: session_id.map(str::to_string),
+        breakdown,
+        suggestion: "Use budgeted loads (--pack <tokens>) or a lower --level to slice skills."
+            .to_string(),
+    })
+}
+
+/// Record newly served items and check the session total against the model's
+/// context window.
+///
+/// Without a session id only the items from this call are counted. This never
+/// fails: ledger I/O problems and unknown models are logged and skipped.
+#[must_use]
+pub fn track_served(
+    ms_root: &Path,
+    config: &ContextWindowConfig,
+    model: Option<&str>,
+    session_id: Option<&str>,
+    served: &[(String, usize)],
+) -> Option<ContextWindowWarning> {
+    let (total, breakdown) = match session_id {
+        Some(session_id) => {
+            let mut ledger = SessionLedger::load(ms_root, session_id).unwrap_or_else(|err| {
+                debug!(session_id, error = %err, "starting fresh session ledger");
+                SessionLedger::new(session_id)
+            });
+            for (id, tokens) in served {
+                ledger.record(id.clone(), *tokens);
+            }
+            if let Err(err) = ledger.save(ms_root) {
+                debug!(session_id, error = %err, "failed to save session ledger");
+            }
+            let retention = Duration::from_secs(config.ledger_retention_days * 24 * 60 * 60);
+            if let Err(err) = prune_session_ledgers(ms_root, retention) {
+                debug!(error = %err, "failed to prune session ledgers");
+            }
+            (ledger.total_tokens(), ledger.breakdown())
+        }
+        None => (
+            served.iter().map(|(_, tokens)| tokens).sum(),
+            aggregate(served.iter().map(|(id, tokens)| (id.as_str(), *tokens))),
+        ),
+    };
+
+    let Some(model) = resolve_model(model, config) else {
+        debug!("no model known; skipping context window check");
+        return None;
+    };
+    let Some(window) = context_window_for(&model, &config.windows) else {
+        debug!(model = %model, "unknown model context window; skipping check");
+        return None;
+    };
+    check_served(
+        &model,
+        window,
+        config.warn_fraction,
+        total,
+        breakdown,
+        session_id,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_inference() -> ContextWindowConfig {
+        ContextWindowConfig {
+            infer_model: false,
+            ..ContextWindowConfig::default()
+        }
+    }
+
+    #[test]
+    fn lookup_uses_longest_family_prefix() {
+        let none = BTreeMap::new();
+        assert_eq!(context_window_for("gpt-4o-mini", &none), Some(128_000));
+        assert_eq!(context_window_for("gpt-4-0613", &none), Some(8_192));
+        assert_eq!(
+            context_window_for("anthropic/claude-sonnet-4", &none),
+            Some(200_000)
+        );
+        assert_eq!(context_window_for("totally-unknown", &none), None);
+    }
+
+    #[test]
+    fn config_overrides_take_precedence() {
+        let overrides = BTreeMap::from([
+            ("claude".to_string(), 100_000),
+            ("my-local".to_string(), 8_192),
+        ]);
+        assert_eq!(context_window_for("claude-opus", &overrides), Some(100_000));
+        assert_eq!(context_window_for("my-local-7b", &overrides), Some(8_192));
+        assert_eq!(context_window_for("gpt-4o", &overrides), Some(128_000));
+    }
+
+    #[test]
+    fn check_warns_only_above_fraction() {
+        assert!(check_served("m", 1000, 0.5, 500, Vec::new(), None).is_none());
+        let warning = check_served(
+            "m",
+            1000,
+            0.5,
+            700,
+            vec![BudgetContributor {
+                id: "big".to_string(),
+                tokens: 700,
+            }],
+            Some("s1"),
+        )
+        .unwrap();
+        assert_eq!(warning.threshold_tokens, 500);
+        let message = warning.to_string();
+        assert!(message.contains("session s1"));
+        assert!(message.contains("big (700)"));
+        assert!(message.contains("--pack"));
+    }
+
+    #[test]
+    fn session_ledger_accumulates_across_loads() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ContextWindowConfig {
+            warn_fraction: 0.5,
+            ..no_inference()
+        };
+        let first = track_served(
+            dir.path(),
+            &config,
+            Some("gpt-4"),
+            Some("session/1"),
+            &[("a".to_string(), 3_000)],
+        );
+        assert!(first.is_none());
+
+        let second = track_served(
+            dir.path(),
+            &config,
+            Some("gpt-4"),
+            Some("session/1"),
+            &[("b".to_string(), 2_000)],
+        )
+        .expect("cumulative total crosses half of 8192");
+        assert_eq!(second.served_tokens, 5_000);
+        assert_eq!(second.breakdown[0].id, "a");
+
+        let ledger = SessionLedger::load(dir.path(), "session/1").unwrap();
+        assert_eq!(ledger.served.len(), 2);
+    }
+
+    #[test]
+    fn ledgers_for_ids_that_sanitize_alike_stay_separate() {
+        let dir = tempfile::tempdir().unwrap();
+        let slash = SessionLedger::path(dir.path(), "a/b");
+        let colon = SessionLedger::path(dir.path(), "a:b");
+        assert_ne!(slash, colon);
+        assert!(
+            slash
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .starts_with("a_b-")
+        );
+
+        let mut ledger = SessionLedger::new("a/b");
+        ledger.record("x", 10);
+        ledger.save(dir.path()).unwrap();
+        assert!(
+            SessionLedger::load(dir.path(), "a:b")
+                .unwrap()
+                .served
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn prune_removes_only_stale_ledgers() {
+        let dir = tempfile::tempdir().unwrap();
+        SessionLedger::new("old").save(dir.path()).unwrap();
+        let old = SessionLedger::path(dir.path(), "old");
+        let two_weeks_ago = SystemTime::now() - Duration::from_secs(14 * 24 * 60 * 60);
+        fs::File::options()
+            .write(true)
+            .open(&old)
+            .unwrap()
+            .set_modified(two_weeks_ago)
+            .unwrap();
+        SessionLedger::new("fresh").save(dir.path()).unwrap();
+
+        let week = Duration::from_secs(7 * 24 * 60 * 60);
+        assert_eq!(prune_session_ledgers(dir.path(), week).unwrap(), 1);
+        assert!(!old.exists());
+        assert!(SessionLedger::path(dir.path(), "fresh").exists());
+    }
+
+    #[test]
+    fn unknown_or_missing_model_skips_check() {
+        let dir = tempfile::tempdir().unwrap();
+        let served = [("a".to_string(), 1_000_000)];
+        assert!(track_served(dir.path(), &no_inference(), None, None, &served).is_none());
+        assert!(
+            track_served(dir.path(), &no_inference(), Some("mystery"), None, &served).is_none()
+        );
+    }
+}
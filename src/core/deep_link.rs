@@ -0,0 +1,485 @@
+//! Stable deep links into skill content.
+//!
+//! A deep link names a skill, optionally pinned to a layer, and optionally a
+//! section or block inside it:
+//!
+//! ```text
+//! link   = "ms://" [ layer "/" ] skill [ "#" anchor ]
+//! layer  = "base" / "org" / "project" / "user"
+//! skill  = 1*( ALPHA / DIGIT / "-" / "_" / "." )
+//! anchor = 1*( ALPHA / DIGIT / "-" / "_" / "." )
+//! ```
+//!
+//! e.g. `ms://project/rust-error-handling#rule-no-unwrap-in-handlers`.
+//!
+//! Anchors are section and block ids. Authors pin them in SKILL.md with
+//! `## Title {#id}` for sections and a `<!-- ms:anchor id -->` line before a
+//! block; the round-trip parser preserves both, so links survive edits.
+//! Unpinned blocks fall back to positional ids (`<section>-block-N`).
+
+use serde::Serialize;
+
+use super::lookup::{self, ResolveOptions, Stage};
+use super::skill::SkillSpec;
+use super::slicing::SkillSlicer;
+use super::spec_lens::{compile_markdown, is_valid_anchor_id, parse_markdown};
+use crate::error::{MsError, Result, suggest_similar_skills};
+use crate::storage::Database;
+
+/// URI scheme for deep links.
+pub const SCHEME: &str = "ms://";
+
+const LAYERS: [&str; 4] = ["base", "org", "project", "user"];
+
+/// A parsed `ms://` link.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DeepLink {
+    pub layer: Option<String>,
+    pub skill: String,
+    pub anchor: Option<String>,
+}
+
+impl DeepLink {
+    /// Parse a deep link, reporting what is wrong with malformed input.
+    pub fn parse(input: &str) -> Result<Self> {
+        let invalid =
+            |reason: String| MsError::ValidationFailed(format!("invalid link '{input}': {reason}"));
+
+        let rest = input
+            .trim()
+            .strip_prefix(SCHEME)
+            .ok_or_else(|| invalid(format!("must start with '{SCHEME}'")))?;
+        let (path, anchor) = match rest.split_once('#') {
+            Some((path, anchor)) => (path, Some(anchor)),
+            None => (rest, None),
+        };
+
+        let segments: Vec<&str> = path.split('/').collect();
+        let (layer, skill) = match segments.as_slice() {
+            [skill] => (None, *skill),
+            [layer, skill] => {
+                let layer = normalize_layer(layer).ok_or_else(|| {
+                    invalid(format!(
+                        "unknown layer '{layer}' (expected one of: {})",
+                        LAYERS.join(", ")
+                    ))
+                })?;
+                (Some(layer), *skill)
+            }
+            _ => {
+                return Err(invalid(
+                    "expected at most one '/' between layer and skill".to_string(),
+                ));
+            }
+        };
+
+        if skill.is_empty() {
+            return Err(invalid("missing skill id".to_string()));
+        }
+        if !is_valid_anchor_id(skill) {
+            return Err(invalid(format!(
+                "skill id '{skill}' may only contain letters, digits, '-', '_' and '.'"
+            )));
+        }
+        let anchor = match anchor {
+            None => None,
+            Some("") => return Err(invalid("empty anchor after '#'".to_string())),
+            Some(anchor) if !is_valid_anchor_id(anchor) => {
+                return Err(invalid(format!(
+                    "anchor '{anchor}' may only contain letters, digits, '-', '_' and '.'"
+                )));
+            }
+            Some(anchor) => Some(anchor.to_string()),
+        };
+
+        Ok(Self {
+            layer,
+            skill: skill.to_string(),
+            anchor,
+        })
+    }
+}
+
+impl std::fmt::Display for DeepLink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{SCHEME}")?;
+        if let Some(layer) = &self.layer {
+            write!(f, "{layer}/")?;
+        }
+        write!(f, "{}", self.skill)?;
+        if let Some(anchor) = &self.anchor {
+            write!(f, "#{anchor}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Map a layer name (including legacy aliases) to its canonical form.
+#[must_use]
+pub fn normalize_layer(layer: &str) -> Option<String> {
+    let lowered = layer.to_lowercase();
+    let canonical = match lowered.as_str() {
+        "system" => "base",
+        "global" => "org",
+        "local" => "user",
+        other => other,
+    };
+    LAYERS.contains(&canonical).then(|| canonical.to_string())
+}
+
+/// What an anchor points at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnchorKind {
+    Section,
+    Block,
+}
+
+/// An addressable location inside a skill.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SkillAnchor {
+    pub id: String,
+    pub kind: AnchorKind,
+    pub section_id: String,
+    /// Section title, or the first line of a block.
+    pub label: String,
+}
+
+/// All anchors of a skill, in document order.
+#[must_use]
+pub fn list_anchors(spec: &SkillSpec) -> Vec<SkillAnchor> {
+    let mut anchors = Vec::new();
+    for section in &spec.sections {
+        if !section.title.is_empty() {
+            anchors.push(SkillAnchor {
+                id: section.id.clone(),
+                kind: AnchorKind::Section,
+                section_id: section.id.clone(),
+                label: section.title.clone(),
+            });
+        }
+        for block in &section.blocks {
+            if block.id.is_empty() || block.content.trim().is_empty() {
+                continue;
+            }
+            let first_line = block
+                .content
+                .lines()
+                .map(str::trim)
+                .find(|line| !line.is_empty() && !line.starts_with("```"))
+                .unwrap_or_default();
+            anchors.push(SkillAnchor {
+                id: block.id.clone(),
+                kind: AnchorKind::Block,
+                section_id: section.id.clone(),
+                label: first_line.chars().take(60).collect(),
+            });
+        }
+    }
+    anchors
+}
+
+/// Outcome of looking up an anchor in the current skill content.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum AnchorResolution {
+    Found {
+        anchor: SkillAnchor,
+    },
+    Missing {
+        requested: String,
+        nearest: Option<SkillAnchor>,
+    },
+}
+
+/// Find `anchor` among the skill's anchors, with a fuzzy nearest match when absent.
+#[must_use]
+pub fn resolve_anchor(spec: &SkillSpec, anchor: &str) -> AnchorResolution {
+    let anchors = list_anchors(spec);
+    if let Some(found) = anchors.iter().find(|candidate| candidate.id == anchor) {
+        return AnchorResolution::Found {
+            anchor: found.clone(),
+        };
+    }
+    let ids: Vec<&str> = anchors
+        .iter()
+        .map(|candidate| candidate.id.as_str())
+        .collect();
+    let nearest = suggest_similar_skills(anchor, &ids, 1)
+        .into_iter()
+        .next()
+        .and_then(|id| anchors.into_iter().find(|candidate| candidate.id == id));
+    AnchorResolution::Missing {
+        requested: anchor.to_string(),
+        nearest,
+    }
+}
+
+/// Markdown for just the anchored slice, headed by its section title.
+#[must_use]
+pub fn anchor_content(spec: &SkillSpec, anchor: &SkillAnchor) -> String {
+    let Some(section) = spec.sections.iter().find(|s| s.id == anchor.section_id) else {
+        return String::new();
+    };
+    let block_ids: Vec<&str> = match anchor.kind {
+        AnchorKind::Section => section.blocks.iter().map(|b| b.id.as_str()).collect(),
+        AnchorKind::Block => vec![anchor.id.as_str()],
+    };
+    let slices = SkillSlicer::slice(spec).slices;
+    let mut parts: Vec<String> = Vec::new();
+    if !section.title.is_empty() {
+        parts.push(format!("## {}", section.title));
+    }
+    parts.extend(
+        block_ids
+            .iter()
+            .filter_map(|id| slices.iter().find(|slice| slice.id == *id))
+            .map(|slice| slice.content.clone()),
+    );
+    parts.join("\n\n") + "\n"
+}
+
+/// A deep link resolved against the local index.
+#[derive(Debug, Clone, Serialize)]
+pub struct ResolvedLink {
+    pub link: DeepLink,
+    /// Canonical skill id (differs from `link.skill` when reached via an alias).
+    pub skill_id: String,
+    pub layer: String,
+    pub via_alias: bool,
+    pub anchor: Option<AnchorResolution>,
+    #[serde(skip)]
+    pub spec: SkillSpec,
+}
+
+impl ResolvedLink {
+    /// The anchored slice, or the whole skill when there is no usable anchor.
+    #[must_use]
+    pub fn content(&self) -> String {
+        match &self.anchor {
+            Some(AnchorResolution::Found { anchor }) => anchor_content(&self.spec, anchor),
+            _ => compile_markdown(&self.spec),
+        }
+    }
+
+    /// Canonical link to the resolved location (current id, layer and anchor).
+    #[must_use]
+    pub fn canonical_link(&self) -> DeepLink {
+        DeepLink {
+            layer: Some(self.layer.clone()),
+            skill: self.skill_id.clone(),
+            anchor: match &self.anchor {
+                Some(AnchorResolution::Found { anchor }) => Some(anchor.id.clone()),
+                _ => self.link.anchor.clone(),
+            },
+        }
+    }
+
+    /// Fill a `{layer}`/`{skill}`/`{anchor}` URL template for this location.
+    #[must_use]
+    pub fn web_url(&self, template: &str) -> String {
+        let link = self.canonical_link();
+        let url = template
+            .replace("{layer}", &self.layer)
+            .replace("{skill}", &self.skill_id);
+        match link.anchor {
+            Some(anchor) => url.replace("{anchor}", &anchor),
+            None => url.replace("#{anchor}", "").replace("{anchor}", ""),
+        }
+    }
+}
+
+/// Resolve a link to a skill (following aliases) and, if present, its anchor.
+pub fn resolve_link(db: &Database, link: &DeepLink) -> Result<ResolvedLink> {
+    // Links are persisted, so no prefix matching: a new skill must not
+    // silently change where an existing link points.
+    let resolution = lookup::resolve(db, &link.skill, &ResolveOptions::exact())?;
+    let via_alias = resolution
+        .trace
+        .winner
+        .as_ref()
+        .is_some_and(|winner| winner.via == Stage::Alias);
+    let record = resolution.into_record()?;
+
+    let layer =
+        normalize_layer(&record.source_layer).unwrap_or_else(|| record.source_layer.clone());
+    if let Some(wanted) = &link.layer {
+        if *wanted != layer {
+            return Err(MsError::SkillNotFound(format!(
+                "skill '{}' is in layer '{layer}', not '{wanted}'",
+                record.id
+            )));
+        }
+    }
+
+    let spec = parse_markdown(&record.body)?;
+    let anchor = link
+        .anchor
+        .as_deref()
+        .map(|anchor| resolve_anchor(&spec, anchor));
+
+    Ok(ResolvedLink {
+        link: link.clone(),
+        skill_id: record.id,
+        layer,
+        via_alias,
+        anchor,
+        spec,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::sqlite::SkillRecord;
+    use tempfile::tempdir;
+
+    const BODY: &str = "# Rust Error Handling\n\nHandle errors.\n\n## Rules {#rules}\n\n<!-- ms:anchor rule-no-unwrap-in-handlers -->\nNever unwrap in handlers.\n\n<!-- ms:anchor rule-context -->\nAdd context to errors.\n\n## Examples\n\n```rust\nlet x = f()?;\n```\n";
+
+    fn record(id: &str, layer: &str) -> SkillRecord {
+        SkillRecord {
+            id: id.to_string(),
+            name: "Rust Error Handling".to_string(),
+            description: "Handle errors.".to_string(),
+            version: None,
+            author: None,
+            source_path: format!("/skills/{id}"),
+            source_layer: layer.to_string(),
+            git_remote: None,
+            git_commit: None,
+            content_hash: id.to_string(),
+            body: BODY.to_string(),
+            metadata_json: "{}".to_string(),
+            assets_json: "{}".to_string(),
+            token_count: 10,
+            quality_score: 0.5,
+            indexed_at: "2026-01-01T00:00:00Z".to_string(),
+            modified_at: "2026-01-01T00:00:00Z".to_string(),
+            is_deprecated: false,
+            deprecation_reason: None,
+        }
+    }
+
+    #[test]
+    fn parses_layer_qualified_links() {
+        let link =
+            DeepLink::parse("ms://project/rust-error-handling#rule-no-unwrap-in-handlers").unwrap();
+        assert_eq!(link.layer.as_deref(), Some("project"));
+        assert_eq!(link.skill, "rust-error-handling");
+        assert_eq!(link.anchor.as_deref(), Some("rule-no-unwrap-in-handlers"));
+        assert_eq!(
+            link.to_string(),
+            "ms://project/rust-error-handling#rule-no-unwrap-in-handlers"
+        );
+
+        let bare = DeepLink::parse("ms://rust-error-handling").unwrap();
+        assert_eq!(bare.layer, None);
+        assert_eq!(bare.anchor, None);
+
+        let legacy = DeepLink::parse("ms://local/x").unwrap();
+        assert_eq!(legacy.layer.as_deref(), Some("user"));
+    }
+
+    #[test]
+    fn parse_errors_explain_the_problem() {
+        let cases = [
+            ("https://x", "must start with"),
+            ("ms://", "missing skill id"),
+            ("ms://team/x", "unknown layer 'team'"),
+            ("ms://a/b/c", "at most one '/'"),
+            ("ms://x#", "empty anchor"),
+            ("ms://x#a b", "anchor 'a b'"),
+            ("ms://bad id", "skill id 'bad id'"),
+        ];
+        for (input, expected) in cases {
+            let err = DeepLink::parse(input).unwrap_err().to_string();
+            assert!(err.contains(expected), "{input}: {err}");
+        }
+    }
+
+    #[test]
+    fn anchors_list_sections_and_blocks() {
+        let spec = parse_markdown(BODY).unwrap();
+        let ids: Vec<String> = list_anchors(&spec).into_iter().map(|a| a.id).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "rules",
+                "rule-no-unwrap-in-handlers",
+                "rule-context",
+                "examples",
+                "examples-block-1"
+            ]
+        );
+    }
+
+    #[test]
+    fn block_anchor_renders_only_its_slice() {
+        let spec = parse_markdown(BODY).unwrap();
+        let AnchorResolution::Found { anchor } =
+            resolve_anchor(&spec, "rule-no-unwrap-in-handlers")
+        else {
+            panic!("anchor should resolve");
+        };
+        let content = anchor_content(&spec, &anchor);
+        assert_eq!(content, "## Rules\n\nNever unwrap in handlers.\n");
+    }
+
+    #[test]
+    fn missing_anchor_suggests_nearest_match() {
+        let spec = parse_markdown(BODY).unwrap();
+        match resolve_anchor(&spec, "rule-no-unwrap-in-handler") {
+            AnchorResolution::Missing { requested, nearest } => {
+                assert_eq!(requested, "rule-no-unwrap-in-handler");
+                assert_eq!(nearest.unwrap().id, "rule-no-unwrap-in-handlers");
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn links_resolve_through_rename_alias_and_check_layer() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("ms.db")).unwrap();
+        db.upsert_skill(&record("rust-errors", "project")).unwrap();
+        db.upsert_alias(
+            "rust-error-handling",
+            "rust-errors",
+            "legacy",
+            "2026-01-01T00:00:00Z",
+        )
+        .unwrap();
+
+        let link =
+            DeepLink::parse("ms://project/rust-error-handling#rule-no-unwrap-in-handlers").unwrap();
+        let resolved = resolve_link(&db, &link).unwrap();
+        assert!(resolved.via_alias);
+        assert_eq!(resolved.skill_id, "rust-errors");
+        assert!(resolved.content().contains("Never unwrap in handlers."));
+        assert!(!resolved.content().contains("Add context"));
+        assert_eq!(
+            resolved.canonical_link().to_string(),
+            "ms://project/rust-errors#rule-no-unwrap-in-handlers"
+        );
+        assert_eq!(
+            resolved.web_url("https://docs.example/{layer}/{skill}#{anchor}"),
+            "https://docs.example/project/rust-errors#rule-no-unwrap-in-handlers"
+        );
+
+        let wrong_layer = DeepLink::parse("ms://user/rust-errors").unwrap();
+        let err = resolve_link(&db, &wrong_layer).unwrap_err().to_string();
+        assert!(err.contains("is in layer 'project'"), "{err}");
+
+        let missing = DeepLink::parse("ms://rust-errors#gone").unwrap();
+        let resolved = resolve_link(&db, &missing).unwrap();
+        assert!(matches!(
+            resolved.anchor,
+            Some(AnchorResolution::Missing { .. })
+        ));
+        assert!(resolved.content().contains("# Rust Error Handling"));
+        assert_eq!(
+            resolved.web_url("https://docs.example/{skill}#{anchor}"),
+            "https://docs.example/rust-errors#gone"
+        );
+    }
+}
@@ -3,6 +3,10 @@
 //! Skills declare dependencies (`requires`), capabilities (`provides`), and environment
 //! requirements in metadata. ms builds a dependency graph to resolve load order,
 //! detect cycles, and auto-load prerequisites.
+//!
+//! Besides hard `requires`, a skill may `recommends` capabilities (loaded when
+//! available, a warning otherwise) and declare `conflicts_with` (the plan
+//! refuses to co-load both skills). Only `requires` edges count for cycles.
 
 use std::collections::{HashMap, HashSet, VecDeque};
 
@@ -23,6 +27,22 @@ pub enum DependencyLoadMode {
     Full,
     /// Dependencies at overview/minimal
     Overview,
+    /// Like `Auto`, but recommendations are not followed
+    #[serde(rename = "required-only")]
+    RequiredOnly,
+}
+
+/// How one skill depends on another
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyKind {
+    /// Must be loaded; missing capabilities are reported in `missing`
+    #[default]
+    Requires,
+    /// Loaded when available; missing capabilities only warn
+    Recommends,
+    /// Must not be loaded in the same plan
+    ConflictsWith,
 }
 
 /// Disclosure level for a skill
@@ -47,6 +67,22 @@ pub struct DependencyNode {
     pub provides: Vec<String>,
     /// Capabilities this skill requires
     pub requires: Vec<String>,
+    /// Capabilities this skill recommends
+    pub recommends: Vec<String>,
+    /// Skills or capabilities this skill conflicts with
+    pub conflicts_with: Vec<String>,
+}
+
+impl DependencyNode {
+    /// Declared targets of one kind
+    #[must_use]
+    pub fn declared(&self, kind: DependencyKind) -> &[String] {
+        match kind {
+            DependencyKind::Requires => &self.requires,
+            DependencyKind::Recommends => &self.recommends,
+            DependencyKind::ConflictsWith => &self.conflicts_with,
+        }
+    }
 }
 
 /// An edge in the dependency graph (from -> to means "from depends on to")
@@ -58,6 +94,8 @@ pub struct DependencyEdge {
     pub to: String,
     /// The capability that created this edge
     pub capability: String,
+    /// `Requires` or `Recommends`; conflicts never become edges
+    pub kind: DependencyKind,
 }
 
 /// The dependency graph for skills
@@ -94,11 +132,25 @@ impl DependencyGraph {
                 skill_id,
                 provides,
                 requires,
+                recommends: vec![],
+                conflicts_with: vec![],
             },
         );
     }
 
-    /// Build edges based on requires/provides relationships
+    /// Add `recommends` or `conflicts_with` declarations to an existing node
+    pub fn declare(&mut self, skill_id: &str, kind: DependencyKind, targets: Vec<String>) {
+        let Some(node) = self.nodes.get_mut(skill_id) else {
+            return;
+        };
+        match kind {
+            DependencyKind::Requires => node.requires.extend(targets),
+            DependencyKind::Recommends => node.recommends.extend(targets),
+            DependencyKind::ConflictsWith => node.conflicts_with.extend(targets),
+        }
+    }
+
+    /// Build edges based on requires/recommends/provides relationships
     pub fn build_edges(&mut self) {
         self.edges.clear();
 
@@ -107,21 +159,24 @@ impl DependencyGraph {
         sorted_nodes.sort_by(|a, b| a.skill_id.cmp(&b.skill_id));
 
         for node in sorted_nodes {
-            let mut requires = node.requires.clone();
-            requires.sort(); // Deterministic capability processing
-
-            for required_cap in &requires {
-                if let Some(providers) = self.capability_providers.get(required_cap) {
-                    let mut sorted_providers = providers.clone();
-                    sorted_providers.sort(); // Deterministic provider order
-
-                    for provider in sorted_providers {
-                        if provider != node.skill_id {
-                            self.edges.push(DependencyEdge {
-                                from: node.skill_id.clone(),
-                                to: provider,
-                                capability: required_cap.clone(),
-                            });
+            for kind in [DependencyKind::Requires, DependencyKind::Recommends] {
+                let mut caps = node.declared(kind).to_vec();
+                caps.sort(); // Deterministic capability processing
+
+                for cap in &caps {
+                    if let Some(providers) = self.capability_providers.get(cap) {
+                        let mut sorted_providers = providers.clone();
+                        sorted_providers.sort(); // Deterministic provider order
+
+                        for provider in sorted_providers {
+                            if provider != node.skill_id {
+                                self.edges.push(DependencyEdge {
+                                    from: node.skill_id.clone(),
+                                    to: provider,
+                                    capability: cap.clone(),
+                                    kind,
+                                });
+                            }
                         }
                     }
                 }
@@ -146,12 +201,18 @@ impl DependencyGraph {
         &self.edges
     }
 
-    /// Get direct dependencies of a skill
+    /// Get direct `requires` dependencies of a skill
     #[must_use]
     pub fn direct_dependencies(&self, skill_id: &str) -> Vec<&str> {
+        self.direct_of_kind(skill_id, DependencyKind::Requires)
+    }
+
+    /// Get direct dependencies of one kind
+    #[must_use]
+    pub fn direct_of_kind(&self, skill_id: &str, kind: DependencyKind) -> Vec<&str> {
         self.edges
             .iter()
-            .filter(|e| e.from == skill_id)
+            .filter(|e| e.from == skill_id && e.kind == kind)
             .map(|e| e.to.as_str())
             .collect()
     }
@@ -182,6 +243,16 @@ pub struct SkillLoadPlan {
     pub disclosure: DisclosureLevel,
     /// Reason for loading (e.g., "root", "dependency of X")
     pub reason: String,
+    /// How the root reaches this skill (`requires` for the root itself)
+    #[serde(default)]
+    pub kind: DependencyKind,
+    /// Whether everything this skill requires and recommends was found
+    #[serde(default = "default_satisfied")]
+    pub satisfied: bool,
+}
+
+const fn default_satisfied() -> bool {
+    true
 }
 
 /// Result of resolving dependencies
@@ -191,6 +262,12 @@ pub struct ResolvedDependencyPlan {
     pub ordered: Vec<SkillLoadPlan>,
     /// Missing capabilities that could not be resolved
     pub missing: Vec<MissingCapability>,
+    /// Recommended capabilities that no available skill provides
+    #[serde(default)]
+    pub unsatisfied_recommends: Vec<MissingCapability>,
+    /// Pairs of planned skills that must not be loaded together
+    #[serde(default)]
+    pub conflicts: Vec<DependencyConflict>,
     /// Detected cycles (each cycle is a vec of skill IDs)
     pub cycles: Vec<Vec<String>>,
 }
@@ -204,17 +281,42 @@ pub struct MissingCapability {
     pub capability: String,
 }
 
+/// Two skills in one plan where one declares `conflicts_with` the other
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DependencyConflict {
+    /// The skill declaring the conflict
+    pub skill_id: String,
+    /// The planned skill it conflicts with
+    pub conflicts_with: String,
+    /// The `conflicts_with` entry that matched (skill ID or capability)
+    pub declared: String,
+}
+
+impl std::fmt::Display for DependencyConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} conflicts with {}",
+            self.skill_id, self.conflicts_with
+        )?;
+        if self.declared != self.conflicts_with {
+            write!(f, " (via {})", self.declared)?;
+        }
+        Ok(())
+    }
+}
+
 impl ResolvedDependencyPlan {
-    /// Check if resolution was successful (no missing deps, no cycles)
+    /// Check if resolution was successful (no missing deps, conflicts or cycles)
     #[must_use]
     pub fn is_ok(&self) -> bool {
-        self.missing.is_empty() && self.cycles.is_empty()
+        self.missing.is_empty() && self.conflicts.is_empty() && self.cycles.is_empty()
     }
 
-    /// Check if there are any issues
+    /// Check if there are any issues, including unsatisfied recommendations
     #[must_use]
     pub fn has_issues(&self) -> bool {
-        !self.missing.is_empty() || !self.cycles.is_empty()
+        !self.is_ok() || !self.unsatisfied_recommends.is_empty()
     }
 }
 
@@ -254,21 +356,43 @@ impl<'a> DependencyResolver<'a> {
                     skill_id: root_skill_id.to_string(),
                     disclosure: root_disclosure,
                     reason: "root".to_string(),
+                    kind: DependencyKind::Requires,
+                    satisfied: true,
                 }],
-                missing: vec![],
-                cycles: vec![],
+                ..Default::default()
             });
         }
 
-        // Step 1: Expand dependency closure (BFS)
-        let (closure, missing) = self.expand_closure(root_skill_id)?;
+        let followed: &[DependencyKind] = if mode == DependencyLoadMode::RequiredOnly {
+            &[DependencyKind::Requires]
+        } else {
+            &[DependencyKind::Requires, DependencyKind::Recommends]
+        };
+
+        // Step 1: Expand dependency closure (BFS); the requires-only closure
+        // tells hard dependencies apart from recommended ones
+        let required = self.expand_closure(root_skill_id, &[DependencyKind::Requires]);
+        let closure = self.expand_closure(root_skill_id, followed);
+        let missing = self.missing_in(&closure, DependencyKind::Requires);
+        let unsatisfied_recommends = if followed.contains(&DependencyKind::Recommends) {
+            self.missing_in(&closure, DependencyKind::Recommends)
+        } else {
+            vec![]
+        };
+        let conflicts = self.detect_conflicts(&closure);
 
-        // Step 2: Detect cycles using Tarjan's algorithm
+        // Step 2: Detect cycles (requires edges only)
         let cycles = self.detect_cycles(&closure);
 
-        // Step 3: Topological sort (if no cycles)
+        // Step 3: Topological sort (if no cycles). A cycle made only of
+        // recommendations falls back to ordering by requires edges.
         let topo_order = if cycles.is_empty() {
-            self.topological_sort(&closure, root_skill_id)?
+            let order = self.topological_sort(&closure, followed);
+            if order.len() == closure.len() {
+                order
+            } else {
+                self.topological_sort(&closure, &[DependencyKind::Requires])
+            }
         } else {
             // If there are cycles, we can't do a clean topo sort
             // Return partial ordering with cycle members at the end
@@ -278,21 +402,33 @@ impl<'a> DependencyResolver<'a> {
             sorted
         };
 
-        // Step 4: Assign disclosure levels
-        let ordered =
-            self.assign_disclosure_levels(&topo_order, root_skill_id, root_disclosure, mode)?;
+        // Step 4: Assign disclosure levels and annotate kinds
+        let unsatisfied: HashSet<&str> = missing
+            .iter()
+            .chain(&unsatisfied_recommends)
+            .map(|m| m.required_by.as_str())
+            .collect();
+        let ordered = self.assign_disclosure_levels(
+            &topo_order,
+            root_skill_id,
+            root_disclosure,
+            mode,
+            &required,
+            &unsatisfied,
+        )?;
 
         Ok(ResolvedDependencyPlan {
             ordered,
             missing,
+            unsatisfied_recommends,
+            conflicts,
             cycles,
         })
     }
 
-    /// Expand dependency closure using BFS
-    fn expand_closure(&self, root: &str) -> Result<(HashSet<String>, Vec<MissingCapability>)> {
+    /// Expand dependency closure using BFS, following edges of `kinds`
+    fn expand_closure(&self, root: &str, kinds: &[DependencyKind]) -> HashSet<String> {
         let mut closure = HashSet::new();
-        let mut missing = Vec::new();
         let mut queue = VecDeque::new();
         let mut depth = 0;
 
@@ -307,29 +443,25 @@ impl<'a> DependencyResolver<'a> {
             // We just need to ensure insertion order is deterministic.
 
             for _ in 0..level_size {
-                if let Some(skill_id) = queue.pop_front() {
-                    if let Some(node) = self.graph.get_node(&skill_id) {
-                        // Sort requirements for deterministic processing
-                        let mut requires = node.requires.clone();
-                        requires.sort();
-
-                        for required_cap in &requires {
-                            if let Some(providers) = self.graph.find_providers(required_cap) {
-                                // Sort providers for deterministic queue insertion
-                                let mut sorted_providers = providers.clone();
-                                sorted_providers.sort();
-
-                                for provider in sorted_providers {
-                                    if !closure.contains(&provider) {
-                                        closure.insert(provider.clone());
-                                        queue.push_back(provider);
-                                    }
+                if let Some(skill_id) = queue.pop_front()
+                    && let Some(node) = self.graph.get_node(&skill_id)
+                {
+                    // Sort capabilities for deterministic processing
+                    let mut caps: Vec<&String> =
+                        kinds.iter().flat_map(|kind| node.declared(*kind)).collect();
+                    caps.sort();
+
+                    for cap in caps {
+                        if let Some(providers) = self.graph.find_providers(cap) {
+                            // Sort providers for deterministic queue insertion
+                            let mut sorted_providers = providers.clone();
+                            sorted_providers.sort();
+
+                            for provider in sorted_providers {
+                                if !closure.contains(&provider) {
+                                    closure.insert(provider.clone());
+                                    queue.push_back(provider);
                                 }
-                            } else {
-                                missing.push(MissingCapability {
-                                    required_by: skill_id.clone(),
-                                    capability: required_cap.clone(),
-                                });
                             }
                         }
                     }
@@ -338,7 +470,70 @@ impl<'a> DependencyResolver<'a> {
             depth += 1;
         }
 
-        Ok((closure, missing))
+        closure
+    }
+
+    /// Capabilities of `kind` declared inside the closure that nothing provides
+    fn missing_in(
+        &self,
+        closure: &HashSet<String>,
+        kind: DependencyKind,
+    ) -> Vec<MissingCapability> {
+        let mut skill_ids: Vec<&String> = closure.iter().collect();
+        skill_ids.sort();
+
+        let mut missing = Vec::new();
+        for skill_id in skill_ids {
+            let Some(node) = self.graph.get_node(skill_id) else {
+                continue;
+            };
+            let mut caps = node.declared(kind).to_vec();
+            caps.sort();
+            for capability in caps {
+                if self.graph.find_providers(&capability).is_none() {
+                    missing.push(MissingCapability {
+                        required_by: skill_id.clone(),
+                        capability,
+                    });
+                }
+            }
+        }
+        missing
+    }
+
+    /// Pairs of closure members where one's `conflicts_with` names the other
+    /// by skill ID or by a capability it provides
+    fn detect_conflicts(&self, closure: &HashSet<String>) -> Vec<DependencyConflict> {
+        let mut skill_ids: Vec<&String> = closure.iter().collect();
+        skill_ids.sort();
+
+        let mut conflicts = Vec::new();
+        for skill_id in &skill_ids {
+            let Some(node) = self.graph.get_node(skill_id) else {
+                continue;
+            };
+            let mut declared = node.conflicts_with.clone();
+            declared.sort();
+            for target in declared {
+                for other in &skill_ids {
+                    if other == skill_id {
+                        continue;
+                    }
+                    let provides = self
+                        .graph
+                        .get_node(other)
+                        .is_some_and(|n| n.provides.contains(&target));
+                    if **other == target || provides {
+                        conflicts.push(DependencyConflict {
+                            skill_id: (*skill_id).clone(),
+                            conflicts_with: (*other).clone(),
+                            declared: target.clone(),
+                        });
+                    }
+                }
+            }
+        }
+        conflicts
     }
 
     /// Detect cycles using DFS with back-edge detection
@@ -397,8 +592,9 @@ impl<'a> DependencyResolver<'a> {
         rec_stack.remove(node);
     }
 
-    /// Topological sort using Kahn's algorithm
-    fn topological_sort(&self, closure: &HashSet<String>, _root: &str) -> Result<Vec<String>> {
+    /// Topological sort using Kahn's algorithm over edges of `kinds`. Members
+    /// of a cycle are left out of the result.
+    fn topological_sort(&self, closure: &HashSet<String>, kinds: &[DependencyKind]) -> Vec<String> {
         let mut in_degree: HashMap<&str, usize> = HashMap::new();
         let mut adj: HashMap<&str, Vec<&str>> = HashMap::new();
 
@@ -410,7 +606,10 @@ impl<'a> DependencyResolver<'a> {
 
         // Build adjacency list and in-degrees
         for skill_id in closure {
-            for dep in self.graph.direct_dependencies(skill_id) {
+            let deps = kinds
+                .iter()
+                .flat_map(|kind| self.graph.direct_of_kind(skill_id, *kind));
+            for dep in deps {
                 if closure.contains(dep) {
                     // Use if-let to safely handle missing keys (defensive against graph bugs)
                     if let Some(adj_list) = adj.get_mut(skill_id.as_str()) {
@@ -458,7 +657,7 @@ impl<'a> DependencyResolver<'a> {
         // Reverse so dependencies come first
         result.reverse();
 
-        Ok(result)
+        result
     }
 
     /// Assign disclosure levels based on mode
@@ -468,6 +667,8 @@ impl<'a> DependencyResolver<'a> {
         root_skill_id: &str,
         root_disclosure: DisclosureLevel,
         mode: DependencyLoadMode,
+        required: &HashSet<String>,
+        unsatisfied: &HashSet<&str>,
     ) -> Result<Vec<SkillLoadPlan>> {
         let dep_disclosure = match mode {
             DependencyLoadMode::Off => {
@@ -475,24 +676,41 @@ impl<'a> DependencyResolver<'a> {
                     "dependency resolution requested with load mode off".to_string(),
                 ));
             }
-            DependencyLoadMode::Auto => DisclosureLevel::Overview,
+            DependencyLoadMode::Auto
+            | DependencyLoadMode::Overview
+            | DependencyLoadMode::RequiredOnly => DisclosureLevel::Overview,
             DependencyLoadMode::Full => DisclosureLevel::Full,
-            DependencyLoadMode::Overview => DisclosureLevel::Overview,
         };
 
         Ok(topo_order
             .iter()
             .map(|skill_id| {
-                let (disclosure, reason) = if skill_id == root_skill_id {
-                    (root_disclosure, "root".to_string())
+                let (disclosure, reason, kind) = if skill_id == root_skill_id {
+                    (
+                        root_disclosure,
+                        "root".to_string(),
+                        DependencyKind::Requires,
+                    )
+                } else if required.contains(skill_id) {
+                    (
+                        dep_disclosure,
+                        format!("dependency of {root_skill_id}"),
+                        DependencyKind::Requires,
+                    )
                 } else {
-                    (dep_disclosure, format!("dependency of {root_skill_id}"))
+                    (
+                        dep_disclosure,
+                        format!("recommended by {root_skill_id}"),
+                        DependencyKind::Recommends,
+                    )
                 };
 
                 SkillLoadPlan {
                     skill_id: skill_id.clone(),
                     disclosure,
                     reason,
+                    kind,
+                    satisfied: !unsatisfied.contains(skill_id.as_str()),
                 }
             })
             .collect())
@@ -747,4 +965,119 @@ mod tests {
         // So expected: D, B, C, A
         // If sorting isn't implemented, this might be D, C, B, A randomly.
     }
+
+    #[test]
+    fn test_recommends_load_when_present_and_warn_when_missing() {
+        let mut graph = DependencyGraph::new();
+        graph.add_skill("root".to_string(), vec!["cap-a".to_string()], vec![]);
+        graph.declare(
+            "root",
+            DependencyKind::Recommends,
+            vec!["cap-b".to_string(), "cap-gone".to_string()],
+        );
+        graph.add_skill("a".to_string(), vec![], vec!["cap-a".to_string()]);
+        graph.add_skill("b".to_string(), vec![], vec!["cap-b".to_string()]);
+        graph.build_edges();
+
+        let resolver = DependencyResolver::new(&graph);
+        let plan = resolver
+            .resolve("root", DisclosureLevel::Full, DependencyLoadMode::Auto)
+            .unwrap();
+
+        let kinds: Vec<_> = plan
+            .ordered
+            .iter()
+            .map(|p| (p.skill_id.as_str(), p.kind, p.satisfied))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("b", DependencyKind::Recommends, true),
+                ("a", DependencyKind::Requires, true),
+                ("root", DependencyKind::Requires, false),
+            ]
+        );
+        assert_eq!(plan.unsatisfied_recommends.len(), 1);
+        assert_eq!(plan.unsatisfied_recommends[0].capability, "cap-gone");
+        assert!(plan.missing.is_empty());
+        assert!(plan.is_ok());
+        assert!(plan.has_issues());
+
+        let plan = resolver
+            .resolve(
+                "root",
+                DisclosureLevel::Full,
+                DependencyLoadMode::RequiredOnly,
+            )
+            .unwrap();
+        let order: Vec<_> = plan.ordered.iter().map(|p| p.skill_id.as_str()).collect();
+        assert_eq!(order, vec!["a", "root"]);
+        assert!(plan.unsatisfied_recommends.is_empty());
+        assert!(plan.ordered.iter().all(|p| p.satisfied));
+    }
+
+    #[test]
+    fn test_conflicts_name_both_skills() {
+        let mut graph = DependencyGraph::new();
+        graph.add_skill(
+            "root".to_string(),
+            vec!["cap-a".to_string(), "cap-b".to_string()],
+            vec![],
+        );
+        graph.add_skill("a".to_string(), vec![], vec!["cap-a".to_string()]);
+        graph.declare(
+            "a",
+            DependencyKind::ConflictsWith,
+            vec!["cap-b".to_string()],
+        );
+        graph.add_skill("b".to_string(), vec![], vec!["cap-b".to_string()]);
+        graph.build_edges();
+
+        let resolver = DependencyResolver::new(&graph);
+        let plan = resolver
+            .resolve("root", DisclosureLevel::Full, DependencyLoadMode::Auto)
+            .unwrap();
+
+        assert_eq!(
+            plan.conflicts,
+            vec![DependencyConflict {
+                skill_id: "a".to_string(),
+                conflicts_with: "b".to_string(),
+                declared: "cap-b".to_string(),
+            }]
+        );
+        assert_eq!(
+            plan.conflicts[0].to_string(),
+            "a conflicts with b (via cap-b)"
+        );
+        assert!(!plan.is_ok());
+    }
+
+    #[test]
+    fn test_recommendation_cycle_is_not_a_cycle() {
+        let mut graph = DependencyGraph::new();
+        graph.add_skill(
+            "a".to_string(),
+            vec!["cap-b".to_string()],
+            vec!["cap-a".to_string()],
+        );
+        graph.add_skill("b".to_string(), vec![], vec!["cap-b".to_string()]);
+        graph.declare("b", DependencyKind::Recommends, vec!["cap-a".to_string()]);
+        graph.build_edges();
+
+        assert!(graph.direct_dependencies("b").is_empty());
+        assert_eq!(
+            graph.direct_of_kind("b", DependencyKind::Recommends),
+            vec!["a"]
+        );
+
+        let resolver = DependencyResolver::new(&graph);
+        let plan = resolver
+            .resolve("a", DisclosureLevel::Full, DependencyLoadMode::Auto)
+            .unwrap();
+
+        assert!(plan.cycles.is_empty());
+        let order: Vec<_> = plan.ordered.iter().map(|p| p.skill_id.as_str()).collect();
+        assert_eq!(order, vec!["b", "a"]);
+    }
 }
@@ -0,0 +1,321 @@
+//! Language and framework tags derived from skill content at index time.
+//!
+//! Most mined skills carry no tags beyond "auto-extracted", so `ms index`
+//! tags them with the programming languages of their code blocks (from the
+//! fence info string, else content heuristics) and the frameworks and tools
+//! their content mentions. Derived tags live in `skill_derived_tags`, apart
+//! from author tags, and tags an author already wrote are not derived again.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::skill::{BlockType, SkillSpec};
+use crate::config::EnrichmentConfig;
+use crate::storage::sqlite::DerivedTagRecord;
+
+pub const LANGUAGE: &str = "language";
+pub const FRAMEWORK: &str = "framework";
+
+/// Fence info strings (lowercased) and the language they name. Data formats
+/// such as JSON or YAML are deliberately absent.
+const FENCE_LANGUAGES: &[(&str, &str)] = &[
+    ("rust", "rust"),
+    ("rs", "rust"),
+    ("python", "python"),
+    ("python3", "python"),
+    ("py", "python"),
+    ("javascript", "javascript"),
+    ("js", "javascript"),
+    ("jsx", "javascript"),
+    ("mjs", "javascript"),
+    ("typescript", "typescript"),
+    ("ts", "typescript"),
+    ("tsx", "typescript"),
+    ("go", "go"),
+    ("golang", "go"),
+    ("bash", "shell"),
+    ("sh", "shell"),
+    ("shell", "shell"),
+    ("zsh", "shell"),
+    ("console", "shell"),
+    ("ruby", "ruby"),
+    ("rb", "ruby"),
+    ("java", "java"),
+    ("kotlin", "kotlin"),
+    ("kt", "kotlin"),
+    ("swift", "swift"),
+    ("c", "c"),
+    ("cpp", "cpp"),
+    ("c++", "cpp"),
+    ("csharp", "csharp"),
+    ("cs", "csharp"),
+    ("php", "php"),
+    ("sql", "sql"),
+    ("hcl", "hcl"),
+    ("terraform", "hcl"),
+    ("tf", "hcl"),
+    ("lua", "lua"),
+    ("elixir", "elixir"),
+    ("haskell", "haskell"),
+    ("scala", "scala"),
+    ("dockerfile", "dockerfile"),
+];
+
+/// Markers of a language in unlabeled code; two distinct markers identify it.
+const CONTENT_LANGUAGES: &[(&str, &[&str])] = &[
+    (
+        "rust",
+        &[
+            "fn ",
+            "let mut ",
+            "impl ",
+            "use std::",
+            "-> result<",
+            "println!",
+            "&str",
+        ],
+    ),
+    (
+        "python",
+        &[
+            "def ", "import ", "self.", "elif ", "print(", "__init__", "none:",
+        ],
+    ),
+    (
+        "go",
+        &[
+            "package main",
+            "func ",
+            ":= ",
+            "fmt.",
+            "err != nil",
+            "go func",
+        ],
+    ),
+    (
+        "javascript",
+        &[
+            "const ",
+            "=> {",
+            "console.log",
+            "require(",
+            "function ",
+            "await ",
+        ],
+    ),
+    (
+        "shell",
+        &[
+            "#!/bin/bash",
+            "#!/usr/bin/env bash",
+            "#!/bin/sh",
+            "$ ",
+            "export ",
+            " | grep",
+            "echo ",
+        ],
+    ),
+    (
+        "sql",
+        &[
+            "select ",
+            " from ",
+            " where ",
+            "insert into",
+            "create table",
+        ],
+    ),
+];
+
+/// Framework and tool tags and the tokens that reveal them (whole words,
+/// case-insensitive). `[enrichment.frameworks]` adds to or replaces these.
+const FRAMEWORKS: &[(&str, &[&str])] = &[
+    ("cargo", &["cargo", "cargo.toml"]),
+    ("tokio", &["tokio"]),
+    ("pytest", &["pytest", "conftest.py"]),
+    ("django", &["django", "manage.py"]),
+    ("flask", &["flask"]),
+    ("fastapi", &["fastapi"]),
+    ("npm", &["npm", "package.json"]),
+    ("react", &["usestate", "useeffect", "react-dom"]),
+    ("jest", &["jest"]),
+    ("kubernetes", &["kubectl", "kubeconfig"]),
+    ("helm", &["helm"]),
+    ("terraform", &["terraform", "tfstate"]),
+    ("docker", &["docker", "dockerfile", "docker-compose"]),
+    ("ansible", &["ansible", "ansible-playbook"]),
+    ("github-actions", &[".github/workflows"]),
+    ("postgres", &["postgres", "postgresql", "psql"]),
+];
+
+/// Derives language and framework tags from skill specs.
+#[derive(Debug, Clone)]
+pub struct TagDeriver {
+    frameworks: BTreeMap<String, Vec<String>>,
+}
+
+impl TagDeriver {
+    /// Built-in framework table plus `[enrichment.frameworks]`.
+    #[must_use]
+    pub fn new(config: &EnrichmentConfig) -> Self {
+        let mut frameworks: BTreeMap<String, Vec<String>> = FRAMEWORKS
+            .iter()
+            .map(|(tag, tokens)| {
+                (
+                    (*tag).to_string(),
+                    tokens.iter().map(|token| (*token).to_string()).collect(),
+                )
+            })
+            .collect();
+        for (tag, tokens) in &config.frameworks {
+            frameworks.insert(
+                tag.trim().to_lowercase(),
+                tokens.iter().map(|token| token.to_lowercase()).collect(),
+            );
+        }
+        Self { frameworks }
+    }
+
+    /// Tags for `spec`, sorted by tag, without the ones its author already
+    /// wrote.
+    #[must_use]
+    pub fn derive(&self, spec: &SkillSpec) -> Vec<DerivedTagRecord> {
+        let author: BTreeSet<String> = spec
+            .metadata
+            .tags
+            .iter()
+            .map(|tag| tag.trim().to_lowercase())
+            .collect();
+        let mut tags: BTreeMap<String, &'static str> = BTreeMap::new();
+
+        let mut text = String::new();
+        for block in spec.sections.iter().flat_map(|section| &section.blocks) {
+            if block.block_type == BlockType::Code
+                && let Some(language) = code_language(&block.content)
+            {
+                tags.entry(language.to_string()).or_insert(LANGUAGE);
+            }
+            text.push_str(&block.content.to_lowercase());
+            text.push('\n');
+        }
+        for (tag, tokens) in &self.frameworks {
+            if tokens.iter().any(|token| contains_word(&text, token)) {
+                tags.entry(tag.clone()).or_insert(FRAMEWORK);
+            }
+        }
+
+        tags.into_iter()
+            .filter(|(tag, _)| !author.contains(tag))
+            .map(|(tag, kind)| DerivedTagRecord {
+                tag,
+                kind: kind.to_string(),
+            })
+            .collect()
+    }
+}
+
+/// Language of a fenced code block: its info string, else its content.
+fn code_language(block: &str) -> Option<&'static str> {
+    let first = block.lines().next().unwrap_or_default().trim_start();
+    let info = first.trim_start_matches(['`', '~']).trim();
+    let info = info
+        .split(|c: char| c.is_whitespace() || c == '{' || c == ',')
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    if let Some((_, language)) = FENCE_LANGUAGES.iter().find(|(label, _)| *label == info) {
+        return Some(*language);
+    }
+    // Unlabeled, or labeled with a data format or an unknown language
+    let code = block.to_lowercase();
+    CONTENT_LANGUAGES
+        .iter()
+        .find(|(_, markers)| markers.iter().filter(|m| code.contains(*m)).count() >= 2)
+        .map(|(language, _)| *language)
+}
+
+/// Whether `token` occurs in `text` as a whole word.
+fn contains_word(text: &str, token: &str) -> bool {
+    if token.is_empty() {
+        return false;
+    }
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    text.match_indices(token).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + token.len()..].chars().next();
+        !before.is_some_and(is_word) && !after.is_some_and(is_word)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::spec_lens::parse_markdown;
+
+    const MIXED: &str = "---\nid: deploy-service\nname: Deploy Service\ntags: [python]\n---\n\n\
+# Deploy Service\n\nShip the service.\n\n## Build\n\n\
+```rust\nfn main() {}\n```\n\n\
+```\nlet mut total = 0;\nprintln!(\"{total}\");\n```\n\n\
+```py\nimport os\n```\n\n\
+Run `cargo build --release` then `kubectl apply -f deploy.yaml`.\n\n\
+```yaml\nreplicas: 2\n```\n\n\
+The flasks in the lab are unrelated.\n";
+
+    fn tags(records: &[DerivedTagRecord], kind: &str) -> Vec<String> {
+        records
+            .iter()
+            .filter(|record| record.kind == kind)
+            .map(|record| record.tag.clone())
+            .collect()
+    }
+
+    #[test]
+    fn derives_languages_and_frameworks_from_mixed_blocks() {
+        let spec = parse_markdown(MIXED).unwrap();
+        let derived = TagDeriver::new(&EnrichmentConfig::default()).derive(&spec);
+
+        // `python` is an author tag, YAML is data, the unlabeled block is Rust
+        assert_eq!(tags(&derived, LANGUAGE), ["rust"]);
+        // "flasks" is not the word "flask"
+        assert_eq!(tags(&derived, FRAMEWORK), ["cargo", "kubernetes"]);
+        // Deterministic, so re-indexing writes the same rows
+        assert_eq!(
+            derived,
+            TagDeriver::new(&EnrichmentConfig::default()).derive(&spec)
+        );
+    }
+
+    #[test]
+    fn config_extends_the_framework_table() {
+        let spec = parse_markdown(
+            "# Monorepo\n\nBuild it.\n\n## Build\n\n```bash\nbazel build //...\n```\n",
+        )
+        .unwrap();
+        let mut config = EnrichmentConfig::default();
+        config
+            .frameworks
+            .insert("Bazel".to_string(), vec!["BAZEL".to_string()]);
+
+        let derived = TagDeriver::new(&config).derive(&spec);
+        assert_eq!(tags(&derived, LANGUAGE), ["shell"]);
+        assert_eq!(tags(&derived, FRAMEWORK), ["bazel"]);
+        assert!(
+            TagDeriver::new(&EnrichmentConfig::default())
+                .derive(&spec)
+                .iter()
+                .all(|record| record.tag != "bazel")
+        );
+    }
+
+    #[test]
+    fn unlabeled_code_needs_two_markers() {
+        assert_eq!(
+            code_language("```\npackage main\n\nfunc main() {}\n```"),
+            Some("go")
+        );
+        assert_eq!(code_language("```\nfunc\n```"), None);
+        assert_eq!(code_language("```json\n{\"a\": 1}\n```"), None);
+        assert!(contains_word("run cargo test", "cargo"));
+        assert!(!contains_word("cargoship", "cargo"));
+        assert!(contains_word("edit cargo.toml", "cargo.toml"));
+    }
+}
@@ -10,6 +10,7 @@ use super::skill::{
     ReferenceFile, ScriptFile, SkillAssets, SkillMetadata, SkillSection, SkillSpec,
 };
 use super::slicing::SkillSlicer;
+use super::tokenizer::TokenCounter;
 
 // =============================================================================
 // DISCLOSURE LEVELS
@@ -285,21 +286,27 @@ pub enum RequestType {
 // DISCLOSURE LOGIC
 // =============================================================================
 
-/// Generate content at a specified disclosure plan
+/// Generate content at a specified disclosure plan, sized with `counter`
 #[must_use]
-pub fn disclose(spec: &SkillSpec, assets: &SkillAssets, plan: &DisclosurePlan) -> DisclosedContent {
+pub fn disclose(
+    spec: &SkillSpec,
+    assets: &SkillAssets,
+    plan: &DisclosurePlan,
+    counter: &dyn TokenCounter,
+) -> DisclosedContent {
     match plan {
-        DisclosurePlan::Level(level) => disclose_level(spec, assets, *level),
-        DisclosurePlan::Pack(budget) => disclose_packed(spec, assets, budget),
+        DisclosurePlan::Level(level) => disclose_level(spec, assets, *level, counter),
+        DisclosurePlan::Pack(budget) => disclose_packed(spec, assets, budget, counter),
     }
 }
 
-/// Generate content at a specified disclosure level
+/// Generate content at a specified disclosure level, sized with `counter`
 #[must_use]
 pub fn disclose_level(
     spec: &SkillSpec,
     assets: &SkillAssets,
     level: DisclosureLevel,
+    counter: &dyn TokenCounter,
 ) -> DisclosedContent {
     match level {
         DisclosureLevel::Minimal => {
@@ -309,7 +316,7 @@ pub fn disclose_level(
                 body: None,
                 scripts: vec![],
                 references: vec![],
-                token_estimate: estimate_tokens_frontmatter(&spec.metadata, true),
+                token_estimate: estimate_tokens_frontmatter(&spec.metadata, true, counter),
                 level,
                 slices_included: None,
             }
@@ -317,8 +324,8 @@ pub fn disclose_level(
         DisclosureLevel::Overview => {
             let frontmatter = DisclosedFrontmatter::from(&spec.metadata);
             let body = Some(extract_headings(&spec.sections));
-            let token_estimate = estimate_tokens_frontmatter(&spec.metadata, false)
-                + estimate_tokens_body(body.as_deref());
+            let token_estimate = estimate_tokens_frontmatter(&spec.metadata, false, counter)
+                + estimate_tokens_body(body.as_deref(), counter);
             DisclosedContent {
                 frontmatter,
                 body,
@@ -332,9 +339,9 @@ pub fn disclose_level(
         DisclosureLevel::Standard => {
             let frontmatter = DisclosedFrontmatter::from(&spec.metadata);
             let full_body = render_sections(&spec.sections);
-            let body = Some(truncate_examples(&full_body, 1500));
-            let token_estimate = estimate_tokens_frontmatter(&spec.metadata, false)
-                + estimate_tokens_body(body.as_deref());
+            let body = Some(truncate_examples(&full_body, 1500, counter));
+            let token_estimate = estimate_tokens_frontmatter(&spec.metadata, false, counter)
+                + estimate_tokens_body(body.as_deref(), counter);
             DisclosedContent {
                 frontmatter,
                 body,
@@ -348,8 +355,8 @@ pub fn disclose_level(
         DisclosureLevel::Full => {
             let frontmatter = DisclosedFrontmatter::from(&spec.metadata);
             let body = Some(render_sections(&spec.sections));
-            let token_estimate = estimate_tokens_frontmatter(&spec.metadata, false)
-                + estimate_tokens_body(body.as_deref());
+            let token_estimate = estimate_tokens_frontmatter(&spec.metadata, false, counter)
+                + estimate_tokens_body(body.as_deref(), counter);
             DisclosedContent {
                 frontmatter,
                 body,
@@ -363,8 +370,8 @@ pub fn disclose_level(
         DisclosureLevel::Complete => {
             let frontmatter = DisclosedFrontmatter::from(&spec.metadata);
             let body = Some(render_sections(&spec.sections));
-            let token_estimate = estimate_tokens_frontmatter(&spec.metadata, false)
-                + estimate_tokens_body(body.as_deref())
+            let token_estimate = estimate_tokens_frontmatter(&spec.metadata, false, counter)
+                + estimate_tokens_body(body.as_deref(), counter)
                 + estimate_tokens_assets(assets);
             DisclosedContent {
                 frontmatter,
@@ -378,7 +385,7 @@ pub fn disclose_level(
         }
         DisclosureLevel::Auto => {
             // Default to Standard for Auto
-            disclose_level(spec, assets, DisclosureLevel::Standard)
+            disclose_level(spec, assets, DisclosureLevel::Standard, counter)
         }
     }
 }
@@ -388,10 +395,11 @@ fn disclose_packed(
     spec: &SkillSpec,
     _assets: &SkillAssets,
     budget: &TokenBudget,
+    counter: &dyn TokenCounter,
 ) -> DisclosedContent {
     // Start with frontmatter (always included)
     let frontmatter = DisclosedFrontmatter::from(&spec.metadata);
-    let frontmatter_tokens = estimate_tokens_frontmatter(&spec.metadata, false);
+    let frontmatter_tokens = estimate_tokens_frontmatter(&spec.metadata, false, counter);
 
     let slice_budget = budget.tokens.saturating_sub(frontmatter_tokens);
     if slice_budget < 50 {
@@ -407,7 +415,7 @@ fn disclose_packed(
         };
     }
 
-    let slice_index = SkillSlicer::slice(spec);
+    let slice_index = SkillSlicer::slice_with(spec, counter);
     let mut constraints = PackConstraints::new(slice_budget, budget.max_per_group);
     constraints.contract = budget.contract.clone();
     constraints
@@ -463,7 +471,7 @@ fn disclose_packed(
     }
 }
 
-fn render_packed_body(slices: &[crate::core::skill::SkillSlice]) -> String {
+pub(crate) fn render_packed_body(slices: &[crate::core::skill::SkillSlice]) -> String {
     let mut out = String::new();
     let mut last_section = None;
 
@@ -598,12 +606,13 @@ fn render_sections(sections: &[SkillSection]) -> String {
 }
 
 /// Truncate examples and code blocks to fit within token budget
-fn truncate_examples(body: &str, max_tokens: usize) -> String {
-    // Simple heuristic: 4 chars per token
-    let max_chars = max_tokens * 4;
-    if body.len() <= max_chars {
+fn truncate_examples(body: &str, max_tokens: usize, counter: &dyn TokenCounter) -> String {
+    let tokens = counter.count(body);
+    if tokens <= max_tokens {
         return body.to_string();
     }
+    // Keep the same share of characters as of tokens
+    let max_chars = body.chars().count() * max_tokens / tokens;
 
     // Try to truncate at a good boundary (end of section)
     let truncated: String = body.chars().take(max_chars).collect();
@@ -617,29 +626,29 @@ fn truncate_examples(body: &str, max_tokens: usize) -> String {
 }
 
 /// Estimate tokens for frontmatter
-fn estimate_tokens_frontmatter(meta: &SkillMetadata, minimal: bool) -> usize {
-    // Rough estimate: id + name + version + description
-    let base = meta.id.len() + meta.name.len() + meta.version.len() + meta.description.len();
-    let extras = if minimal {
-        0
-    } else {
-        meta.tags
-            .iter()
-            .map(std::string::String::len)
-            .sum::<usize>()
-            + meta
-                .requires
-                .iter()
-                .map(std::string::String::len)
-                .sum::<usize>()
-    };
-    // Rough: 4 chars per token
-    (base + extras) / 4 + 20 // +20 for formatting overhead
+fn estimate_tokens_frontmatter(
+    meta: &SkillMetadata,
+    minimal: bool,
+    counter: &dyn TokenCounter,
+) -> usize {
+    // id + name + version + description
+    let mut text = [
+        meta.id.as_str(),
+        &meta.name,
+        &meta.version,
+        &meta.description,
+    ]
+    .concat();
+    if !minimal {
+        text.push_str(&meta.tags.concat());
+        text.push_str(&meta.requires.concat());
+    }
+    counter.count(&text) + 20 // +20 for formatting overhead
 }
 
 /// Estimate tokens for body content
-fn estimate_tokens_body(body: Option<&str>) -> usize {
-    body.map_or(0, |b| b.len() / 4)
+fn estimate_tokens_body(body: Option<&str>, counter: &dyn TokenCounter) -> usize {
+    body.map_or(0, |b| counter.count(b))
 }
 
 /// Estimate tokens for assets
@@ -666,6 +675,7 @@ fn estimate_tokens_assets(assets: &SkillAssets) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::tokenizer::HeuristicCounter;
 
     #[test]
     fn test_disclosure_level_from_str() {
@@ -809,7 +819,7 @@ mod tests {
     #[test]
     fn test_truncate_examples() {
         let body = "## Section 1\n\nContent here.\n\n## Section 2\n\nMore content.";
-        let truncated = truncate_examples(body, 10); // Very small budget
+        let truncated = truncate_examples(body, 10, &HeuristicCounter); // Very small budget
         assert!(truncated.contains("[... truncated ...]"));
     }
 
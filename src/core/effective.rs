@@ -0,0 +1,359 @@
+//! A skill as agents receive it, with the origin of each section and block.
+//!
+//! [`effective_skill`] runs the same resolution as `ms load`: inheritance
+//! (`extends`), composition (`includes`), then the skill's layer through a
+//! [`LayeredRegistry`] carrying the project overrides as overlays. Origins are
+//! attributed afterwards by finding each block in the skill itself, its
+//! ancestors (nearest first), the skills it includes, and the overlays'
+//! block changes. [`inherited_base`] resolves only what the skill inherits.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::Serialize;
+
+use super::layering::{LayeredRegistry, SkillCandidate};
+use super::overlay::BlockAction;
+use super::overrides::ProjectOverrides;
+use super::resolution::{SkillRepository, resolve_full};
+use super::skill::{SkillLayer, SkillSpec};
+use super::spec_lens::compile_markdown_with_origins;
+use crate::error::{MsError, Result};
+
+/// Where a section or block of an effective skill came from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Origin {
+    /// Written in the skill itself.
+    Own,
+    /// Inherited through `extends` from the ancestor `from`.
+    Inherited { from: String },
+    /// Composed in through `includes` from `from`.
+    Included { from: String },
+    /// Replaced or added by the overlay `by` (project overrides, for one).
+    Overridden { by: String },
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Own => f.write_str("own"),
+            Self::Inherited { from } => write!(f, "inherited-from {from}"),
+            Self::Included { from } => write!(f, "included-from {from}"),
+            Self::Overridden { by } => write!(f, "overridden-by {by}"),
+        }
+    }
+}
+
+/// A resolved skill and the origins of its sections and blocks.
+#[derive(Debug, Clone)]
+pub struct EffectiveSkill {
+    pub spec: SkillSpec,
+    /// `extends` chain, oldest ancestor first.
+    pub inheritance_chain: Vec<String>,
+    /// Skills composed in through `includes`.
+    pub included_from: Vec<String>,
+    /// Overlays that changed the skill.
+    pub overlays: Vec<String>,
+    pub warnings: Vec<String>,
+    sections: HashMap<String, Origin>,
+    blocks: HashMap<(String, String), Origin>,
+}
+
+impl EffectiveSkill {
+    /// Origin of the section `section_id`.
+    #[must_use]
+    pub fn section_origin(&self, section_id: &str) -> &Origin {
+        self.sections.get(section_id).unwrap_or(&Origin::Own)
+    }
+
+    /// Origin of the block `block_id` in the section `section_id`.
+    #[must_use]
+    pub fn block_origin(&self, section_id: &str, block_id: &str) -> &Origin {
+        self.blocks
+            .get(&(section_id.to_string(), block_id.to_string()))
+            .unwrap_or(&Origin::Own)
+    }
+
+    /// The merged spec as SKILL.md, with an `<!-- ms:origin ... -->` comment
+    /// before each section and block.
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        compile_markdown_with_origins(&self.spec, |section, block| {
+            Some(match block {
+                Some(block) => self.block_origin(section, block).to_string(),
+                None => self.section_origin(section).to_string(),
+            })
+        })
+    }
+}
+
+/// Resolve `spec`, indexed at `layer`, the way `ms load` serves it.
+pub fn effective_skill<R: SkillRepository + ?Sized>(
+    spec: &SkillSpec,
+    layer: SkillLayer,
+    repository: &R,
+    overrides: &ProjectOverrides,
+) -> Result<EffectiveSkill> {
+    let id = &spec.metadata.id;
+    let resolved = resolve_full(spec, repository)?;
+
+    let mut registry = LayeredRegistry::new();
+    registry.register(SkillCandidate {
+        spec: resolved.spec,
+        layer,
+        source_path: String::new(),
+        modified_ms: None,
+    });
+    overrides.register_into(&mut registry);
+    let layered = registry
+        .effective(id)?
+        .ok_or_else(|| MsError::SkillNotFound(id.clone()))?;
+
+    let mut sources = vec![(Origin::Own, spec.clone())];
+    sources.extend(ancestors(&resolved.inheritance_chain, repository)?);
+    sources.extend(included(&resolved.included_from, repository)?);
+    let mut effective = attribute(layered.spec, &sources, Origin::Own);
+
+    for result in layered
+        .overlay_results
+        .iter()
+        .filter(|result| result.applied)
+    {
+        effective.overlays.push(result.overlay_id.clone());
+        for change in &result.blocks {
+            if change.action != BlockAction::Removed {
+                effective.blocks.insert(
+                    (change.section_id.clone(), change.block_id.clone()),
+                    Origin::Overridden {
+                        by: result.overlay_id.clone(),
+                    },
+                );
+            }
+        }
+        effective.warnings.extend(result.unmatched.iter().cloned());
+    }
+    effective.inheritance_chain = resolved.inheritance_chain;
+    effective.included_from = resolved.included_from;
+    effective.warnings.extend(
+        resolved
+            .warnings
+            .iter()
+            .map(|warning| format!("{warning:?}")),
+    );
+    Ok(effective)
+}
+
+/// What `spec` inherits: its parent fully resolved, without `spec`'s own
+/// content or overrides. Errors when `spec` extends nothing.
+pub fn inherited_base<R: SkillRepository + ?Sized>(
+    spec: &SkillSpec,
+    repository: &R,
+) -> Result<EffectiveSkill> {
+    let Some(parent_id) = spec.extends.as_deref() else {
+        return Err(MsError::ValidationFailed(format!(
+            "{} does not extend another skill, so it has no inherited base",
+            spec.metadata.id
+        )));
+    };
+    // Resolving the child first reports missing parents and cycles with the
+    // chain starting at the skill that was asked for
+    let chain = resolve_full(spec, repository)?.inheritance_chain;
+    let parent = repository
+        .get(parent_id)?
+        .ok_or_else(|| MsError::ParentSkillNotFound {
+            parent_id: parent_id.to_string(),
+            child_id: spec.metadata.id.clone(),
+            chain: vec![spec.metadata.id.clone(), parent_id.to_string()],
+        })?;
+    let resolved = resolve_full(&parent, repository)?;
+
+    let mut sources = ancestors(&chain, repository)?;
+    sources.extend(included(&resolved.included_from, repository)?);
+    let mut effective = attribute(
+        resolved.spec,
+        &sources,
+        Origin::Inherited {
+            from: parent_id.to_string(),
+        },
+    );
+    effective.inheritance_chain = resolved.inheritance_chain;
+    effective.included_from = resolved.included_from;
+    effective.warnings = resolved
+        .warnings
+        .iter()
+        .map(|warning| format!("{warning:?}"))
+        .collect();
+    Ok(effective)
+}
+
+/// Raw specs of the ancestors in `chain` (oldest first, ending with the
+/// skill itself), nearest first.
+fn ancestors<R: SkillRepository + ?Sized>(
+    chain: &[String],
+    repository: &R,
+) -> Result<Vec<(Origin, SkillSpec)>> {
+    let mut sources = Vec::new();
+    for id in chain.iter().rev().skip(1) {
+        if let Some(spec) = repository.get(id)? {
+            sources.push((Origin::Inherited { from: id.clone() }, spec));
+        }
+    }
+    Ok(sources)
+}
+
+/// Resolved specs of the included skills.
+fn included<R: SkillRepository + ?Sized>(
+    ids: &[String],
+    repository: &R,
+) -> Result<Vec<(Origin, SkillSpec)>> {
+    let mut sources = Vec::new();
+    for id in ids {
+        if let Some(spec) = repository.get(id)? {
+            let spec = resolve_full(&spec, repository)?.spec;
+            sources.push((Origin::Included { from: id.clone() }, spec));
+        }
+    }
+    Ok(sources)
+}
+
+/// Attribute each section and block of `spec` to the first of `sources`
+/// holding it: a block with the same id and content, else one with the same
+/// id (included blocks may be prefixed). Anything not found is `fallback`.
+fn attribute(spec: SkillSpec, sources: &[(Origin, SkillSpec)], fallback: Origin) -> EffectiveSkill {
+    let mut sections = HashMap::new();
+    let mut blocks = HashMap::new();
+    for section in &spec.sections {
+        let origin = sources
+            .iter()
+            .find(|(_, source)| source.sections.iter().any(|s| s.id == section.id))
+            .map_or(&fallback, |(origin, _)| origin);
+        sections.insert(section.id.clone(), origin.clone());
+
+        for block in &section.blocks {
+            let holds = |source: &SkillSpec, same_content: bool| {
+                source
+                    .sections
+                    .iter()
+                    .flat_map(|s| &s.blocks)
+                    .any(|b| b.id == block.id && (!same_content || b.content == block.content))
+            };
+            let origin = sources
+                .iter()
+                .find(|(_, source)| holds(source, true))
+                .or_else(|| sources.iter().find(|(_, source)| holds(source, false)))
+                .map_or(&fallback, |(origin, _)| origin);
+            blocks.insert((section.id.clone(), block.id.clone()), origin.clone());
+        }
+    }
+    EffectiveSkill {
+        spec,
+        inheritance_chain: Vec::new(),
+        included_from: Vec::new(),
+        overlays: Vec::new(),
+        warnings: Vec::new(),
+        sections,
+        blocks,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::spec_lens::parse_markdown;
+
+    struct Repo(HashMap<String, SkillSpec>);
+
+    impl Repo {
+        fn new(skills: &[&str]) -> Self {
+            Self(
+                skills
+                    .iter()
+                    .map(|md| {
+                        let spec = parse_markdown(md).unwrap();
+                        (spec.metadata.id.clone(), spec)
+                    })
+                    .collect(),
+            )
+        }
+    }
+
+    impl SkillRepository for Repo {
+        fn get(&self, skill_id: &str) -> Result<Option<SkillSpec>> {
+            Ok(self.0.get(skill_id).cloned())
+        }
+    }
+
+    const BASE: &str = "---\nid: errors-base\nname: Errors Base\n---\n\n# Errors Base\n\n## Rules\n\n<!-- ms:anchor explicit -->\nHandle errors explicitly.\n\n## Pitfalls\n\nSwallowing errors.\n";
+    const RUST: &str = "---\nid: rust-errors\nname: Rust Errors\nextends: errors-base\n---\n\n# Rust Errors\n\n## Rules\n\n<!-- ms:anchor thiserror -->\nUse thiserror.\n\n## Tooling\n\nRun clippy.\n";
+
+    #[test]
+    fn blocks_are_attributed_to_their_origin() {
+        let repo = Repo::new(&[BASE, RUST]);
+        let overrides = ProjectOverrides::parse(
+            std::path::Path::new("overrides.toml"),
+            "[override.\"rust-errors\".blocks.thiserror]\ncontent = \"Use snafu.\"\n",
+        )
+        .unwrap();
+        let rust = repo.0["rust-errors"].clone();
+
+        let effective = effective_skill(&rust, SkillLayer::Project, &repo, &overrides).unwrap();
+        assert_eq!(effective.inheritance_chain, ["errors-base", "rust-errors"]);
+        let inherited = Origin::Inherited {
+            from: "errors-base".to_string(),
+        };
+        assert_eq!(effective.block_origin("rules", "explicit"), &inherited);
+        assert_eq!(effective.section_origin("pitfalls"), &inherited);
+        assert_eq!(effective.section_origin("tooling"), &Origin::Own);
+        assert_eq!(
+            effective.block_origin("tooling", "tooling-block-1"),
+            &Origin::Own
+        );
+        assert_eq!(
+            effective.block_origin("rules", "thiserror"),
+            &Origin::Overridden {
+                by: "project-overrides".to_string()
+            }
+        );
+
+        // Annotated markdown is still the same skill
+        let markdown = effective.to_markdown();
+        assert!(markdown.contains("<!-- ms:origin inherited-from errors-base -->\n## Pitfalls"));
+        assert!(markdown.contains("<!-- ms:origin overridden-by project-overrides -->"));
+        assert_eq!(
+            serde_json::to_value(parse_markdown(&markdown).unwrap()).unwrap(),
+            serde_json::to_value(&effective.spec).unwrap()
+        );
+
+        let base = inherited_base(&rust, &repo).unwrap();
+        assert!(base.spec.sections.iter().all(|s| s.id != "tooling"));
+        assert_eq!(base.block_origin("rules", "explicit"), &inherited);
+    }
+
+    #[test]
+    fn missing_parents_and_cycles_name_the_chain() {
+        let leaf = "---\nid: leaf\nname: Leaf\nextends: rust-errors\n---\n\n# Leaf\n\nBody.\n";
+        let repo = Repo::new(&[RUST, leaf]);
+        let err = effective_skill(
+            &repo.0["leaf"],
+            SkillLayer::Project,
+            &repo,
+            &ProjectOverrides::default(),
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("chain: leaf -> rust-errors -> errors-base"),
+            "{err}"
+        );
+
+        let a = "---\nid: a\nname: A\nextends: b\n---\n\n# A\n\nBody.\n";
+        let b = "---\nid: b\nname: B\nextends: a\n---\n\n# B\n\nBody.\n";
+        let repo = Repo::new(&[a, b]);
+        assert!(matches!(
+            inherited_base(&repo.0["a"], &repo).unwrap_err(),
+            MsError::CyclicInheritance { .. }
+        ));
+        assert!(inherited_base(&parse_markdown(BASE).unwrap(), &repo).is_err());
+    }
+}
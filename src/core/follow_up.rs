@@ -0,0 +1,272 @@
+//! Post-load follow-up declarations
+//!
+//! A skill may end with structured next steps instead of prose:
+//!
+//! ```yaml
+//! follow_ups:
+//!   - kind: load
+//!     target: rust-async-pitfalls
+//!     when: the change touches async code
+//!   - kind: verify
+//!     target: cargo test --workspace
+//!   - kind: feedback
+//! ```
+//!
+//! Follow-ups are declarations only. `ms load` and the MCP `load` tool emit
+//! them with an exact invocation; nothing is executed on the agent's behalf.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+/// What a follow-up asks the agent to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FollowUpKind {
+    /// Load another skill.
+    Load,
+    /// Record feedback on a skill after using it.
+    Feedback,
+    /// Run a verification command.
+    Verify,
+}
+
+impl FollowUpKind {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Load => "load",
+            Self::Feedback => "feedback",
+            Self::Verify => "verify",
+        }
+    }
+}
+
+/// A follow-up as declared in skill frontmatter.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FollowUp {
+    pub kind: FollowUpKind,
+    /// Skill id for `load`/`feedback` (defaults to this skill for
+    /// `feedback`), shell command for `verify`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    /// When the follow-up applies, in plain words.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<String>,
+}
+
+impl FollowUp {
+    /// Structural problems (missing targets), one message per problem.
+    #[must_use]
+    pub fn errors(&self) -> Vec<String> {
+        let target = self.target.as_deref().map(str::trim).unwrap_or_default();
+        match self.kind {
+            FollowUpKind::Load if target.is_empty() => {
+                vec!["load follow-up needs a target skill id".to_string()]
+            }
+            FollowUpKind::Verify if target.is_empty() => {
+                vec!["verify follow-up needs a target command".to_string()]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Skill id this follow-up loads, if it is a valid `load`.
+    #[must_use]
+    pub fn load_target(&self) -> Option<&str> {
+        match self.kind {
+            FollowUpKind::Load => self
+                .target
+                .as_deref()
+                .map(str::trim)
+                .filter(|t| !t.is_empty()),
+            _ => None,
+        }
+    }
+}
+
+/// A follow-up resolved for output: what, on what, when, and how.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FollowUpAction {
+    pub kind: FollowUpKind,
+    pub target: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub condition: Option<String>,
+    /// Exact command an agent can run.
+    pub invocation: String,
+}
+
+impl FollowUpAction {
+    /// One-line summary for the human "Next steps" footer.
+    #[must_use]
+    pub fn summary(&self) -> String {
+        match &self.condition {
+            Some(condition) => format!("{}  (if {condition})", self.invocation),
+            None => self.invocation.clone(),
+        }
+    }
+}
+
+/// Resolve a skill's declared follow-ups into actions; invalid entries are
+/// skipped (the `valid-follow-ups` lint rule reports them).
+#[must_use]
+pub fn actions_for(skill_id: &str, follow_ups: &[FollowUp]) -> Vec<FollowUpAction> {
+    follow_ups
+        .iter()
+        .filter(|f| f.errors().is_empty())
+        .map(|f| {
+            let target = f
+                .target
+                .as_deref()
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+                .unwrap_or(skill_id)
+                .to_string();
+            let invocation = match f.kind {
+                FollowUpKind::Load => format!("ms load {target}"),
+                FollowUpKind::Feedback => format!("ms feedback add {target} --rating <1-5>"),
+                FollowUpKind::Verify => target.clone(),
+            };
+            FollowUpAction {
+                kind: f.kind,
+                target,
+                condition: f.when.clone(),
+                invocation,
+            }
+        })
+        .collect()
+}
+
+/// Follow-ups stored in an indexed skill's `metadata_json`.
+#[must_use]
+pub fn from_metadata_json(metadata_json: &str) -> Vec<FollowUp> {
+    serde_json::from_str::<serde_json::Value>(metadata_json)
+        .ok()
+        .and_then(|value| value.get("follow_ups").cloned())
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+/// Find a `load` follow-up loop starting at `start`, e.g. `a → b → a`.
+///
+/// `follow_ups_of` returns the declared follow-ups of a skill, or `None` if it
+/// is unknown. The first loop found is returned as the id path.
+pub fn find_loop<F>(
+    start: &str,
+    start_follow_ups: &[FollowUp],
+    follow_ups_of: F,
+) -> Option<Vec<String>>
+where
+    F: Fn(&str) -> Option<Vec<FollowUp>>,
+{
+    let mut path = vec![start.to_string()];
+    let mut visited = HashSet::new();
+    visit(start_follow_ups, &mut path, &mut visited, &follow_ups_of)
+}
+
+fn visit<F>(
+    follow_ups: &[FollowUp],
+    path: &mut Vec<String>,
+    visited: &mut HashSet<String>,
+    follow_ups_of: &F,
+) -> Option<Vec<String>>
+where
+    F: Fn(&str) -> Option<Vec<FollowUp>>,
+{
+    for target in follow_ups.iter().filter_map(FollowUp::load_target) {
+        if path.first().is_some_and(|start| start == target) {
+            let mut cycle = path.clone();
+            cycle.push(target.to_string());
+            return Some(cycle);
+        }
+        if path.iter().any(|id| id == target) || !visited.insert(target.to_string()) {
+            continue;
+        }
+        let Some(next) = follow_ups_of(target) else {
+            continue;
+        };
+        path.push(target.to_string());
+        if let Some(cycle) = visit(&next, path, visited, follow_ups_of) {
+            return Some(cycle);
+        }
+        path.pop();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn load(target: &str) -> FollowUp {
+        FollowUp {
+            kind: FollowUpKind::Load,
+            target: Some(target.to_string()),
+            when: None,
+        }
+    }
+
+    #[test]
+    fn parses_frontmatter_and_builds_invocations() {
+        let yaml = "- kind: load\n  target: rust-async\n  when: the change touches async code\n\
+                    - kind: verify\n  target: cargo test\n- kind: feedback\n- kind: load\n";
+        let follow_ups: Vec<FollowUp> = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(follow_ups.len(), 4);
+        assert_eq!(follow_ups[3].errors().len(), 1);
+
+        let actions = actions_for("rust-errors", &follow_ups);
+        let invocations: Vec<&str> = actions.iter().map(|a| a.invocation.as_str()).collect();
+        assert_eq!(
+            invocations,
+            vec![
+                "ms load rust-async",
+                "cargo test",
+                "ms feedback add rust-errors --rating <1-5>"
+            ]
+        );
+        assert_eq!(
+            actions[0].summary(),
+            "ms load rust-async  (if the change touches async code)"
+        );
+    }
+
+    #[test]
+    fn reads_follow_ups_from_metadata_json() {
+        let json = r#"{"id":"a","follow_ups":[{"kind":"load","target":"b"}]}"#;
+        assert_eq!(from_metadata_json(json), vec![load("b")]);
+        assert!(from_metadata_json("{}").is_empty());
+        assert!(from_metadata_json("not json").is_empty());
+    }
+
+    #[test]
+    fn detects_loops_through_other_skills() {
+        let graph: HashMap<&str, Vec<FollowUp>> = HashMap::from([
+            ("b", vec![load("c")]),
+            ("c", vec![load("a"), load("d")]),
+            ("d", vec![]),
+        ]);
+        let lookup = |id: &str| graph.get(id).cloned();
+        assert_eq!(
+            find_loop("a", &[load("b")], lookup),
+            Some(vec![
+                "a".to_string(),
+                "b".to_string(),
+                "c".to_string(),
+                "a".to_string()
+            ])
+        );
+        assert_eq!(find_loop("a", &[load("d")], lookup), None);
+        // A loop that does not pass through the start is not this skill's loop.
+        let graph: HashMap<&str, Vec<FollowUp>> =
+            HashMap::from([("b", vec![load("c")]), ("c", vec![load("b")])]);
+        assert_eq!(
+            find_loop("a", &[load("b")], |id| graph.get(id).cloned()),
+            None
+        );
+        assert_eq!(
+            find_loop("a", &[load("a")], |_| None),
+            Some(vec!["a".to_string(), "a".to_string()])
+        );
+    }
+}
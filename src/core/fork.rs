@@ -0,0 +1,122 @@
+//! Project forks of read-only skills.
+//!
+//! Community and bundle-installed skills are read-only by default
+//! (`[layers.community]`, `[layers.bundle]`). Editing one goes through a fork:
+//! a copy in the project layer whose `metadata.forked_from` names the origin
+//! file and the hash it had when the fork was made. The layered registry
+//! prefers a fork over its origin, and [`upstream_status`] tells whether the
+//! origin has changed since.
+
+use std::path::Path;
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use super::skill::{ForkProvenance, SkillLayer, SkillSpec};
+use crate::error::{MsError, Result};
+
+/// SHA-256 of a SKILL.md, as recorded in [`ForkProvenance::content_hash`].
+#[must_use]
+pub fn content_hash(raw: &[u8]) -> String {
+    hex::encode(Sha256::digest(raw))
+}
+
+/// Copy of `origin` that records where it came from. `raw` is the origin
+/// SKILL.md as read from `path`.
+#[must_use]
+pub fn fork_spec(origin: &SkillSpec, layer: SkillLayer, path: &Path, raw: &str) -> SkillSpec {
+    let mut fork = origin.clone();
+    fork.metadata.forked_from = Some(ForkProvenance {
+        layer,
+        path: path.display().to_string(),
+        content_hash: content_hash(raw.as_bytes()),
+    });
+    fork
+}
+
+/// State of a fork's origin relative to the fork point.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Upstream {
+    /// The origin is byte for byte what was forked.
+    Current,
+    /// The origin changed after the fork.
+    Drifted { current_hash: String },
+    /// The origin file is gone.
+    Missing,
+}
+
+impl Upstream {
+    #[must_use]
+    pub const fn drifted(&self) -> bool {
+        matches!(self, Self::Drifted { .. })
+    }
+}
+
+/// Compare a fork's origin file with the hash recorded at fork time.
+pub fn upstream_status(provenance: &ForkProvenance) -> Result<Upstream> {
+    let path = Path::new(&provenance.path);
+    let raw = match std::fs::read(path) {
+        Ok(raw) => raw,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Upstream::Missing),
+        Err(err) => {
+            return Err(MsError::Config(format!("read {}: {err}", path.display())));
+        }
+    };
+    let current_hash = content_hash(&raw);
+    if current_hash == provenance.content_hash {
+        Ok(Upstream::Current)
+    } else {
+        Ok(Upstream::Drifted { current_hash })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::spec_lens::{compile_markdown, parse_markdown};
+
+    const ORIGIN: &str = "# Rust Style\n\nHouse style.\n\n## Rules\n\nUse rustfmt.\n";
+
+    #[test]
+    fn fork_records_origin_and_survives_round_trip() {
+        let origin = parse_markdown(ORIGIN).unwrap();
+        let path = Path::new("/community/rust-style/SKILL.md");
+        let fork = fork_spec(&origin, SkillLayer::Base, path, ORIGIN);
+
+        let provenance = fork.metadata.forked_from.clone().unwrap();
+        assert_eq!(provenance.layer, SkillLayer::Base);
+        assert_eq!(provenance.path, path.display().to_string());
+        assert_eq!(provenance.content_hash, content_hash(ORIGIN.as_bytes()));
+        assert_eq!(fork.sections.len(), origin.sections.len());
+
+        let reparsed = parse_markdown(&compile_markdown(&fork)).unwrap();
+        assert_eq!(reparsed.metadata.forked_from, Some(provenance));
+    }
+
+    #[test]
+    fn detects_upstream_drift() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("SKILL.md");
+        std::fs::write(&path, ORIGIN).unwrap();
+        let origin = parse_markdown(ORIGIN).unwrap();
+        let fork = fork_spec(&origin, SkillLayer::Base, &path, ORIGIN);
+        let provenance = fork.metadata.forked_from.unwrap();
+
+        assert_eq!(upstream_status(&provenance).unwrap(), Upstream::Current);
+
+        let updated = format!("{ORIGIN}\nPrefer `?` over `unwrap`.\n");
+        std::fs::write(&path, &updated).unwrap();
+        let status = upstream_status(&provenance).unwrap();
+        assert!(status.drifted());
+        assert_eq!(
+            status,
+            Upstream::Drifted {
+                current_hash: content_hash(updated.as_bytes())
+            }
+        );
+
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(upstream_status(&provenance).unwrap(), Upstream::Missing);
+    }
+}
@@ -28,6 +28,51 @@ pub enum ConflictStrategy {
     PreferLower,
     /// Require interactive resolution
     Interactive,
+    /// Prefer a specific layer, falling back to the highest when it has no candidate
+    PreferLayer(SkillLayer),
+    /// Prefer the most recently modified candidate
+    Newest,
+    /// Keep the highest layer's blocks and add blocks and sections only lower layers have
+    Merge,
+}
+
+impl ConflictStrategy {
+    /// Parse a CLI/storage name: `prefer-higher`, `prefer-lower`,
+    /// `interactive`, `newest`, `merge`, or `prefer-<layer>` where the layer
+    /// accepts the config bucket aliases (`prefer-global` is the org layer).
+    #[must_use]
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_lowercase().as_str() {
+            "prefer-higher" => Some(Self::PreferHigher),
+            "prefer-lower" => Some(Self::PreferLower),
+            "interactive" => Some(Self::Interactive),
+            "newest" => Some(Self::Newest),
+            "merge" => Some(Self::Merge),
+            other => {
+                let layer = other.strip_prefix("prefer-")?;
+                let layer = match super::deep_link::normalize_layer(layer)?.as_str() {
+                    "base" => SkillLayer::Base,
+                    "org" => SkillLayer::Org,
+                    "project" => SkillLayer::Project,
+                    _ => SkillLayer::User,
+                };
+                Some(Self::PreferLayer(layer))
+            }
+        }
+    }
+
+    /// Name accepted by [`ConflictStrategy::parse`]
+    #[must_use]
+    pub fn name(&self) -> String {
+        match self {
+            Self::PreferHigher => "prefer-higher".to_string(),
+            Self::PreferLower => "prefer-lower".to_string(),
+            Self::Interactive => "interactive".to_string(),
+            Self::PreferLayer(layer) => format!("prefer-{layer}"),
+            Self::Newest => "newest".to_string(),
+            Self::Merge => "merge".to_string(),
+        }
+    }
 }
 
 /// Strategy for merging sections from different layers
@@ -155,6 +200,8 @@ pub struct SkillCandidate {
     pub layer: SkillLayer,
     /// Path to the skill source
     pub source_path: String,
+    /// Source modification time (ms since the epoch), used by `Newest`
+    pub modified_ms: Option<i64>,
 }
 
 // =============================================================================
@@ -252,25 +299,13 @@ impl LayeredRegistry {
 
         let candidate_layers: Vec<SkillLayer> = sorted.iter().map(|(layer, _)| **layer).collect();
 
-        // Single candidate - no conflicts possible
-        if sorted.len() == 1 {
+        // Single candidate - no conflicts possible, but overlays still apply
+        let mut resolved = if sorted.len() == 1 {
             let (layer, candidate) = sorted[0];
-            return Ok(Some(ResolvedSkill::from_single(
-                candidate.spec.clone(),
-                *layer,
-            )));
-        }
-
-        // Multiple candidates - resolve conflicts
-        let mut resolved = match conflict_strategy {
-            ConflictStrategy::PreferHigher => {
-                self.resolve_prefer_higher(&sorted, merge_strategy, candidate_layers)
-            }
-            ConflictStrategy::PreferLower => {
-                self.resolve_prefer_lower(&sorted, merge_strategy, candidate_layers)
-            }
-            ConflictStrategy::Interactive => self.resolve_interactive(&sorted, candidate_layers),
-        }?;
+            Some(ResolvedSkill::from_single(candidate.spec.clone(), *layer))
+        } else {
+            self.resolve_conflicts(&sorted, conflict_strategy, merge_strategy, candidate_layers)?
+        };
 
         if let Some(ref mut resolved_skill) = resolved {
             let overlay_results =
@@ -281,6 +316,54 @@ impl LayeredRegistry {
         Ok(resolved)
     }
 
+    /// Resolve a skill defined in more than one layer
+    fn resolve_conflicts(
+        &self,
+        sorted: &[(&SkillLayer, &SkillCandidate)],
+        conflict_strategy: ConflictStrategy,
+        merge_strategy: MergeStrategy,
+        candidate_layers: Vec<SkillLayer>,
+    ) -> Result<Option<ResolvedSkill>> {
+        // A fork replaces its origin outright unless a layer was asked for.
+        if matches!(
+            conflict_strategy,
+            ConflictStrategy::PreferHigher
+                | ConflictStrategy::PreferLower
+                | ConflictStrategy::Newest
+        ) && let Some(fork) = fork_position(sorted)
+        {
+            return self.resolve_prefer_candidate(
+                sorted,
+                fork,
+                MergeStrategy::Replace,
+                candidate_layers,
+            );
+        }
+        match conflict_strategy {
+            ConflictStrategy::PreferHigher => {
+                self.resolve_prefer_higher(sorted, merge_strategy, candidate_layers)
+            }
+            ConflictStrategy::PreferLower => {
+                self.resolve_prefer_lower(sorted, merge_strategy, candidate_layers)
+            }
+            ConflictStrategy::Interactive => self.resolve_interactive(sorted, candidate_layers),
+            ConflictStrategy::PreferLayer(layer) => {
+                let winner = sorted.iter().position(|(l, _)| **l == layer).unwrap_or(0);
+                self.resolve_prefer_candidate(sorted, winner, merge_strategy, candidate_layers)
+            }
+            ConflictStrategy::Newest => {
+                // Ties (and unknown times) go to the higher layer
+                let winner = sorted
+                    .iter()
+                    .enumerate()
+                    .max_by_key(|(idx, (_, c))| (c.modified_ms, std::cmp::Reverse(*idx)))
+                    .map_or(0, |(idx, _)| idx);
+                self.resolve_prefer_candidate(sorted, winner, merge_strategy, candidate_layers)
+            }
+            ConflictStrategy::Merge => self.resolve_merge(sorted, candidate_layers),
+        }
+    }
+
     /// Resolve by preferring higher layer
     fn resolve_prefer_higher(
         &self,
@@ -369,6 +452,99 @@ impl LayeredRegistry {
         }))
     }
 
+    /// Resolve by preferring `sorted[winner]`; candidates above it in the
+    /// order are reported as `LowerWins`, those below as `HigherWins`
+    fn resolve_prefer_candidate(
+        &self,
+        sorted: &[(&SkillLayer, &SkillCandidate)],
+        winner: usize,
+        merge_strategy: MergeStrategy,
+        candidate_layers: Vec<SkillLayer>,
+    ) -> Result<Option<ResolvedSkill>> {
+        let (winner_layer, winner_candidate) = sorted[winner];
+        let mut conflicts = Vec::new();
+        let mut ordered = vec![sorted[winner]];
+
+        for (idx, (layer, candidate)) in sorted.iter().enumerate() {
+            if idx == winner {
+                continue;
+            }
+            ordered.push((*layer, *candidate));
+            if idx < winner {
+                for mut conflict in detect_section_conflicts(
+                    &candidate.spec,
+                    **layer,
+                    &winner_candidate.spec,
+                    *winner_layer,
+                    merge_strategy,
+                ) {
+                    conflict.resolution = ConflictResolution::LowerWins;
+                    conflicts.push(conflict);
+                }
+            } else {
+                conflicts.extend(detect_section_conflicts(
+                    &winner_candidate.spec,
+                    *winner_layer,
+                    &candidate.spec,
+                    **layer,
+                    merge_strategy,
+                ));
+            }
+        }
+
+        let spec = match merge_strategy {
+            MergeStrategy::Replace => winner_candidate.spec.clone(),
+            MergeStrategy::Auto | MergeStrategy::PreferSections => {
+                merge_specs(&ordered, merge_strategy)
+            }
+        };
+
+        Ok(Some(ResolvedSkill {
+            spec,
+            source_layer: *winner_layer,
+            candidate_layers,
+            conflicts,
+            needs_resolution: false,
+            overlay_results: vec![],
+        }))
+    }
+
+    /// Resolve by merging blocks: the highest layer's content wins where
+    /// blocks collide, blocks and sections only lower layers have are kept
+    fn resolve_merge(
+        &self,
+        sorted: &[(&SkillLayer, &SkillCandidate)],
+        candidate_layers: Vec<SkillLayer>,
+    ) -> Result<Option<ResolvedSkill>> {
+        let (winner_layer, winner) = sorted[0];
+        let mut spec = winner.spec.clone();
+        let mut conflicts = Vec::new();
+
+        for (lower_layer, lower_candidate) in sorted.iter().skip(1) {
+            for mut conflict in detect_section_conflicts(
+                &winner.spec,
+                *winner_layer,
+                &lower_candidate.spec,
+                **lower_layer,
+                MergeStrategy::Auto,
+            ) {
+                conflict.resolution = ConflictResolution::Merged;
+                conflicts.push(conflict);
+            }
+            merge_section_blocks(&mut spec, &lower_candidate.spec);
+            merge_non_overlapping_sections(&mut spec, &lower_candidate.spec);
+        }
+
+        Ok(Some(ResolvedSkill {
+            spec,
+            source_layer: *winner_layer,
+            candidate_layers,
+            conflicts,
+            needs_resolution: false,
+            overlay_results: vec![],
+        }))
+    }
+
     /// Resolve interactively (marks as needing resolution)
     fn resolve_interactive(
         &self,
@@ -409,6 +585,27 @@ impl LayeredRegistry {
         self.skills.keys().cloned().collect()
     }
 
+    /// Skill IDs with candidates in more than one layer, sorted
+    #[must_use]
+    pub fn multi_layer_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self
+            .skills
+            .iter()
+            .filter(|(_, by_layer)| by_layer.len() > 1)
+            .map(|(id, _)| id.clone())
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    /// Layer holding a fork of another candidate for `id`, if any
+    #[must_use]
+    pub fn fork_layer(&self, id: &str) -> Option<SkillLayer> {
+        let mut sorted: Vec<_> = self.skills.get(id)?.iter().collect();
+        sorted.sort_by(|a, b| b.0.cmp(a.0));
+        fork_position(&sorted).map(|idx| *sorted[idx].0)
+    }
+
     /// Get candidate at a specific layer
     #[must_use]
     pub fn get_at_layer(&self, id: &str, layer: SkillLayer) -> Option<&SkillCandidate> {
@@ -482,8 +679,20 @@ fn detect_section_conflicts(
     conflicts
 }
 
+/// Index of the first candidate forked from another candidate's layer
+fn fork_position(sorted: &[(&SkillLayer, &SkillCandidate)]) -> Option<usize> {
+    sorted.iter().position(|(_, candidate)| {
+        candidate
+            .spec
+            .metadata
+            .forked_from
+            .as_ref()
+            .is_some_and(|origin| sorted.iter().any(|(layer, _)| **layer == origin.layer))
+    })
+}
+
 /// Compute diff between two sections
-fn compute_section_diff(higher: &SkillSection, lower: &SkillSection) -> SectionDiff {
+pub fn compute_section_diff(higher: &SkillSection, lower: &SkillSection) -> SectionDiff {
     let higher_blocks_map: HashMap<&str, &str> = higher
         .blocks
         .iter()
@@ -577,6 +786,27 @@ fn merge_non_overlapping_sections(target: &mut SkillSpec, source: &SkillSpec) {
     target.sections.extend(to_add);
 }
 
+/// Append blocks that only `source` has to sections both specs share
+fn merge_section_blocks(target: &mut SkillSpec, source: &SkillSpec) {
+    let source_sections: HashMap<&str, &SkillSection> =
+        source.sections.iter().map(|s| (s.id.as_str(), s)).collect();
+
+    for section in &mut target.sections {
+        let Some(source_section) = source_sections.get(section.id.as_str()) else {
+            continue;
+        };
+        let existing: std::collections::HashSet<String> =
+            section.blocks.iter().map(|b| b.id.clone()).collect();
+        section.blocks.extend(
+            source_section
+                .blocks
+                .iter()
+                .filter(|block| !existing.contains(&block.id))
+                .cloned(),
+        );
+    }
+}
+
 /// Merge by section preference: keep higher-layer rules/pitfalls, add lower-layer examples
 fn merge_by_section_preference(target: &mut SkillSpec, source: &SkillSpec) {
     let existing_ids: std::collections::HashSet<String> =
@@ -703,6 +933,7 @@ mod tests {
             spec: spec.clone(),
             layer: SkillLayer::Project,
             source_path: "project/.ms/skills/test".to_string(),
+            modified_ms: None,
         });
 
         let resolved = registry.effective("test-skill").unwrap().unwrap();
@@ -711,6 +942,42 @@ mod tests {
         assert!(!resolved.needs_resolution);
     }
 
+    #[test]
+    fn test_single_candidate_applies_overlays() {
+        use crate::core::overlay::{OverlayCondition, OverlayModification};
+
+        let mut registry = LayeredRegistry::new();
+        registry.register(SkillCandidate {
+            spec: make_skill_spec(
+                "test-skill",
+                "Test Skill",
+                vec![make_section(
+                    "rules",
+                    "Rules",
+                    vec![("width", "100 columns")],
+                )],
+            ),
+            layer: SkillLayer::Base,
+            source_path: "base/test".to_string(),
+            modified_ms: None,
+        });
+        registry.register_overlay(SkillOverlay {
+            id: "project-overrides".to_string(),
+            skill_id: "test-skill".to_string(),
+            priority: i32::MAX,
+            conditions: vec![OverlayCondition::Always],
+            modifications: vec![OverlayModification::ReplaceBlock {
+                block_id: "width".to_string(),
+                content: "120 columns".to_string(),
+            }],
+        });
+
+        let resolved = registry.effective("test-skill").unwrap().unwrap();
+        assert_eq!(resolved.spec.sections[0].blocks[0].content, "120 columns");
+        assert_eq!(resolved.overlay_results.len(), 1);
+        assert!(resolved.overlay_results[0].applied);
+    }
+
     #[test]
     fn test_layer_precedence_higher_wins() {
         let mut registry = LayeredRegistry::new();
@@ -738,11 +1005,13 @@ mod tests {
             spec: base_spec,
             layer: SkillLayer::Base,
             source_path: "base".to_string(),
+            modified_ms: None,
         });
         registry.register(SkillCandidate {
             spec: user_spec,
             layer: SkillLayer::User,
             source_path: "user".to_string(),
+            modified_ms: None,
         });
 
         let resolved = registry.effective("test-skill").unwrap().unwrap();
@@ -759,16 +1028,19 @@ mod tests {
             spec: make_skill_spec("test", "Test", vec![]),
             layer: SkillLayer::User,
             source_path: "user".to_string(),
+            modified_ms: None,
         });
         registry.register(SkillCandidate {
             spec: make_skill_spec("test", "Test", vec![]),
             layer: SkillLayer::Base,
             source_path: "base".to_string(),
+            modified_ms: None,
         });
         registry.register(SkillCandidate {
             spec: make_skill_spec("test", "Test", vec![]),
             layer: SkillLayer::Project,
             source_path: "project".to_string(),
+            modified_ms: None,
         });
 
         let layers = registry.candidate_layers("test");
@@ -798,11 +1070,13 @@ mod tests {
             spec: base_spec,
             layer: SkillLayer::Base,
             source_path: "base".to_string(),
+            modified_ms: None,
         });
         registry.register(SkillCandidate {
             spec: user_spec,
             layer: SkillLayer::User,
             source_path: "user".to_string(),
+            modified_ms: None,
         });
 
         let resolved = registry.effective("test").unwrap().unwrap();
@@ -839,11 +1113,13 @@ mod tests {
             spec: base_spec,
             layer: SkillLayer::Base,
             source_path: "base".to_string(),
+            modified_ms: None,
         });
         registry.register(SkillCandidate {
             spec: user_spec,
             layer: SkillLayer::User,
             source_path: "user".to_string(),
+            modified_ms: None,
         });
 
         let resolved = registry.effective("test").unwrap().unwrap();
@@ -859,6 +1135,7 @@ mod tests {
             spec: make_skill_spec("test", "Test", vec![]),
             layer: SkillLayer::User,
             source_path: "user".to_string(),
+            modified_ms: None,
         });
 
         assert!(registry.effective("test").unwrap().is_some());
@@ -894,11 +1171,13 @@ mod tests {
             spec: base_spec,
             layer: SkillLayer::Base,
             source_path: "base".to_string(),
+            modified_ms: None,
         });
         registry.register(SkillCandidate {
             spec: user_spec,
             layer: SkillLayer::User,
             source_path: "user".to_string(),
+            modified_ms: None,
         });
 
         let resolved = registry.effective("test").unwrap().unwrap();
@@ -910,6 +1189,67 @@ mod tests {
         assert_eq!(resolved.spec.metadata.name, "Base Skill");
     }
 
+    #[test]
+    fn test_fork_wins_over_its_origin() {
+        let origin = make_skill_spec(
+            "style",
+            "Style",
+            vec![
+                make_section("rules", "Rules", vec![("r1", "upstream rule")]),
+                make_section("legacy", "Legacy", vec![("l1", "old notes")]),
+            ],
+        );
+        let mut fork = make_skill_spec(
+            "style",
+            "Style",
+            vec![make_section("rules", "Rules", vec![("r1", "our rule")])],
+        );
+        fork.metadata.forked_from = Some(crate::core::ForkProvenance {
+            layer: SkillLayer::Base,
+            path: "community/style/SKILL.md".to_string(),
+            content_hash: "abc".to_string(),
+        });
+
+        let mut registry =
+            LayeredRegistry::with_strategies(ConflictStrategy::PreferLower, MergeStrategy::Auto);
+        registry.register(SkillCandidate {
+            spec: origin,
+            layer: SkillLayer::Base,
+            source_path: "community/style/SKILL.md".to_string(),
+            modified_ms: Some(2),
+        });
+        registry.register(SkillCandidate {
+            spec: fork,
+            layer: SkillLayer::Project,
+            source_path: ".ms/skills/style/SKILL.md".to_string(),
+            modified_ms: Some(1),
+        });
+        assert_eq!(registry.fork_layer("style"), Some(SkillLayer::Project));
+
+        // Neither the lower origin nor the newer one wins, and sections the
+        // fork dropped are not merged back in.
+        for strategy in [ConflictStrategy::PreferLower, ConflictStrategy::Newest] {
+            let resolved = registry
+                .effective_with_strategies("style", strategy, MergeStrategy::Auto)
+                .unwrap()
+                .unwrap();
+            assert_eq!(resolved.source_layer, SkillLayer::Project);
+            assert_eq!(resolved.spec.sections.len(), 1);
+            assert_eq!(resolved.spec.sections[0].blocks[0].content, "our rule");
+        }
+
+        // An explicit layer choice still applies
+        let resolved = registry
+            .effective_with_strategies(
+                "style",
+                ConflictStrategy::PreferLayer(SkillLayer::Base),
+                MergeStrategy::Replace,
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(resolved.source_layer, SkillLayer::Base);
+    }
+
     #[test]
     fn test_conflict_determinism() {
         // Create specs with multiple sections in a specific order
@@ -943,4 +1283,99 @@ mod tests {
         assert_eq!(conflicts[1].section_id, "s2");
         assert_eq!(conflicts[2].section_id, "s3");
     }
+
+    fn conflicting_registry(strategy: ConflictStrategy) -> LayeredRegistry {
+        let mut registry = LayeredRegistry::with_strategies(strategy, MergeStrategy::Replace);
+        registry.register(SkillCandidate {
+            spec: make_skill_spec(
+                "test",
+                "Org",
+                vec![make_section(
+                    "s1",
+                    "Section",
+                    vec![("b1", "org"), ("b2", "org only")],
+                )],
+            ),
+            layer: SkillLayer::Org,
+            source_path: "org".to_string(),
+            modified_ms: Some(2_000),
+        });
+        registry.register(SkillCandidate {
+            spec: make_skill_spec(
+                "test",
+                "Project",
+                vec![make_section("s1", "Section", vec![("b1", "project")])],
+            ),
+            layer: SkillLayer::Project,
+            source_path: "project".to_string(),
+            modified_ms: Some(1_000),
+        });
+        registry
+    }
+
+    #[test]
+    fn test_conflict_strategy_parse_roundtrip() {
+        assert_eq!(
+            ConflictStrategy::parse("prefer-global"),
+            Some(ConflictStrategy::PreferLayer(SkillLayer::Org))
+        );
+        assert_eq!(
+            ConflictStrategy::parse("prefer-project"),
+            Some(ConflictStrategy::PreferLayer(SkillLayer::Project))
+        );
+        assert_eq!(ConflictStrategy::parse("bogus"), None);
+        assert_eq!(ConflictStrategy::parse("prefer-nowhere"), None);
+        for strategy in [
+            ConflictStrategy::PreferHigher,
+            ConflictStrategy::PreferLayer(SkillLayer::User),
+            ConflictStrategy::Newest,
+            ConflictStrategy::Merge,
+        ] {
+            assert_eq!(ConflictStrategy::parse(&strategy.name()), Some(strategy));
+        }
+    }
+
+    #[test]
+    fn test_prefer_layer_picks_requested_layer() {
+        let registry = conflicting_registry(ConflictStrategy::PreferLayer(SkillLayer::Org));
+        let resolved = registry.effective("test").unwrap().unwrap();
+        assert_eq!(resolved.source_layer, SkillLayer::Org);
+        assert_eq!(resolved.spec.metadata.name, "Org");
+        assert!(
+            resolved
+                .conflicts
+                .iter()
+                .all(|c| matches!(c.resolution, ConflictResolution::LowerWins))
+        );
+
+        // Missing layer falls back to the highest candidate
+        let registry = conflicting_registry(ConflictStrategy::PreferLayer(SkillLayer::Base));
+        let resolved = registry.effective("test").unwrap().unwrap();
+        assert_eq!(resolved.source_layer, SkillLayer::Project);
+    }
+
+    #[test]
+    fn test_newest_prefers_latest_modification() {
+        let registry = conflicting_registry(ConflictStrategy::Newest);
+        let resolved = registry.effective("test").unwrap().unwrap();
+        assert_eq!(resolved.source_layer, SkillLayer::Org);
+    }
+
+    #[test]
+    fn test_merge_keeps_higher_blocks_and_adds_lower_only() {
+        let registry = conflicting_registry(ConflictStrategy::Merge);
+        let resolved = registry.effective("test").unwrap().unwrap();
+        assert_eq!(resolved.source_layer, SkillLayer::Project);
+        let blocks = &resolved.spec.sections[0].blocks;
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].content, "project");
+        assert_eq!(blocks[1].content, "org only");
+        assert!(
+            resolved
+                .conflicts
+                .iter()
+                .all(|c| matches!(c.resolution, ConflictResolution::Merged))
+        );
+        assert_eq!(registry.multi_layer_ids(), vec!["test".to_string()]);
+    }
 }
@@ -0,0 +1,965 @@
+//! Skill reference resolution
+//!
+//! One resolver turns what a user typed (`ms load foo`) into an indexed skill
+//! for `load`, `show`, `edit`, deep links, and the MCP tools, and records each
+//! step it took so `ms resolve` and `--trace-resolution` show what happened.
+//!
+//! Precedence (first match wins):
+//!
+//! 1. meta-skill (pack) id, when the caller supplies packs (`ms load` only)
+//! 2. exact skill id; the index holds one record per id, taken from the
+//!    highest-precedence layer at index time
+//! 3. alias to a canonical id, following aliases that name other aliases
+//!    (a cycle is an error)
+//! 4. unique id prefix (at least [`MIN_PREFIX_LEN`] characters); an
+//!    ambiguous prefix is rejected rather than guessed
+//!
+//! Ids and aliases match case-insensitively when there is no exact match. A
+//! layer scope rejects a match from another layer instead of falling through.
+//! Near-miss ids are listed as suggestions but never resolve.
+
+use std::collections::HashSet;
+
+use serde::Serialize;
+
+use crate::core::deep_link::normalize_layer;
+use crate::error::{MsError, Result, suggest_similar_skills};
+use crate::storage::Database;
+use crate::storage::sqlite::{AliasResolution, SkillKey, SkillRecord};
+
+/// Shortest input considered for prefix matching.
+pub const MIN_PREFIX_LEN: usize = 3;
+
+/// What the resolver may match and where.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolveOptions {
+    /// Accept a unique id prefix.
+    pub prefix: bool,
+    /// Only accept a skill from this layer.
+    pub layer: Option<String>,
+    /// Meta-skill ids, which take precedence over skills.
+    pub packs: Vec<String>,
+}
+
+impl Default for ResolveOptions {
+    fn default() -> Self {
+        Self {
+            prefix: true,
+            layer: None,
+            packs: Vec::new(),
+        }
+    }
+}
+
+impl ResolveOptions {
+    /// Exact id or alias only, for stable references such as deep links.
+    #[must_use]
+    pub fn exact() -> Self {
+        Self {
+            prefix: false,
+            ..Self::default()
+        }
+    }
+}
+
+/// Resolver stage, in precedence order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Stage {
+    Pack,
+    ExactId,
+    Alias,
+    Prefix,
+    LayerScope,
+    Fuzzy,
+}
+
+impl Stage {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Pack => "pack",
+            Self::ExactId => "exact id",
+            Self::Alias => "alias",
+            Self::Prefix => "prefix",
+            Self::LayerScope => "layer scope",
+            Self::Fuzzy => "fuzzy",
+        }
+    }
+}
+
+/// Result of one stage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Matched,
+    NoMatch,
+    /// Considered but refused (ambiguous, wrong layer, suggestion only).
+    Rejected,
+    /// Would have matched, but a higher-precedence stage won.
+    Shadowed,
+}
+
+impl Outcome {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Matched => "matched",
+            Self::NoMatch => "no match",
+            Self::Rejected => "rejected",
+            Self::Shadowed => "shadowed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TraceStep {
+    pub stage: Stage,
+    pub outcome: Outcome,
+    pub detail: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub candidates: Vec<String>,
+}
+
+/// What a reference resolved to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ResolvedTarget {
+    pub id: String,
+    pub is_pack: bool,
+    pub via: Stage,
+    pub layer: Option<String>,
+    pub source_path: Option<String>,
+}
+
+/// Every step taken to resolve `query`, ending with the winner (if any).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ResolutionTrace {
+    pub query: String,
+    pub steps: Vec<TraceStep>,
+    pub winner: Option<ResolvedTarget>,
+}
+
+impl ResolutionTrace {
+    fn step(&mut self, stage: Stage, outcome: Outcome, detail: String) {
+        self.steps.push(TraceStep {
+            stage,
+            outcome,
+            detail,
+            candidates: Vec::new(),
+        });
+    }
+
+    fn step_with(
+        &mut self,
+        stage: Stage,
+        outcome: Outcome,
+        detail: String,
+        candidates: Vec<String>,
+    ) {
+        self.steps.push(TraceStep {
+            stage,
+            outcome,
+            detail,
+            candidates,
+        });
+    }
+
+    /// Whether a lower-precedence match was silently passed over.
+    #[must_use]
+    pub fn has_shadowing(&self) -> bool {
+        self.steps.iter().any(|s| s.outcome == Outcome::Shadowed)
+    }
+
+    /// Human-readable trace, one line per step plus the result.
+    #[must_use]
+    pub fn render(&self) -> Vec<String> {
+        let mut lines = vec![format!("Resolving '{}'", self.query)];
+        for (idx, step) in self.steps.iter().enumerate() {
+            lines.push(format!(
+                "  {}. [{}] {}: {}",
+                idx + 1,
+                step.stage.as_str(),
+                step.outcome.as_str(),
+                step.detail
+            ));
+            if !step.candidates.is_empty() {
+                lines.push(format!("       candidates: {}", step.candidates.join(", ")));
+            }
+        }
+        match &self.winner {
+            Some(w) if w.is_pack => lines.push(format!("  => meta-skill '{}'", w.id)),
+            Some(w) => lines.push(format!(
+                "  => '{}' via {} ({}, {})",
+                w.id,
+                w.via.as_str(),
+                w.layer.as_deref().unwrap_or("-"),
+                w.source_path.as_deref().unwrap_or("-")
+            )),
+            None => lines.push("  => not found".to_string()),
+        }
+        lines
+    }
+
+    /// Not-found error carrying the most useful hint from the trace.
+    fn not_found(&self) -> MsError {
+        let rejected = |stage: Stage| {
+            self.steps
+                .iter()
+                .find(|s| s.stage == stage && s.outcome == Outcome::Rejected)
+        };
+        let message = if let Some(step) = rejected(Stage::LayerScope) {
+            step.detail.clone()
+        } else if let Some(step) = rejected(Stage::Prefix) {
+            format!(
+                "{} ({}: {})",
+                self.query,
+                step.detail,
+                step.candidates.join(", ")
+            )
+        } else if let Some(step) = rejected(Stage::Fuzzy) {
+            format!(
+                "{} (did you mean: {}?)",
+                self.query,
+                step.candidates.join(", ")
+            )
+        } else {
+            self.query.clone()
+        };
+        MsError::SkillNotFound(format!("skill not found: {message}"))
+    }
+}
+
+/// A trace plus the resolved record (`None` for packs and misses).
+#[derive(Debug, Clone)]
+pub struct Resolution {
+    pub trace: ResolutionTrace,
+    pub record: Option<SkillRecord>,
+}
+
+impl Resolution {
+    /// The resolved skill, or a not-found error explaining why.
+    pub fn into_record(self) -> Result<SkillRecord> {
+        match self.record {
+            Some(record) => Ok(record),
+            None => Err(self.trace.not_found()),
+        }
+    }
+}
+
+/// Resolve `query` with default options (id, alias, unique prefix).
+pub fn resolve_skill(db: &Database, query: &str) -> Result<SkillRecord> {
+    resolve(db, query, &ResolveOptions::default())?.into_record()
+}
+
+/// Resolve `query`, recording every step.
+pub fn resolve(db: &Database, query: &str, options: &ResolveOptions) -> Result<Resolution> {
+    let mut trace = ResolutionTrace {
+        query: query.to_string(),
+        steps: Vec::new(),
+        winner: None,
+    };
+    let mut keys: Option<Vec<SkillKey>> = None;
+
+    if options.packs.iter().any(|p| p == query) {
+        trace.step(
+            Stage::Pack,
+            Outcome::Matched,
+            format!("meta-skill '{query}' (packs take precedence over skills)"),
+        );
+        if let Some(record) = db.get_skill(query)? {
+            trace.step(
+                Stage::ExactId,
+                Outcome::Shadowed,
+                format!(
+                    "skill '{}' ({}) is shadowed by the meta-skill",
+                    record.id, record.source_path
+                ),
+            );
+        }
+        trace.winner = Some(ResolvedTarget {
+            id: query.to_string(),
+            is_pack: true,
+            via: Stage::Pack,
+            layer: None,
+            source_path: None,
+        });
+        return Ok(Resolution {
+            trace,
+            record: None,
+        });
+    } else if !options.packs.is_empty() {
+        trace.step(
+            Stage::Pack,
+            Outcome::NoMatch,
+            format!("no meta-skill '{query}'"),
+        );
+    }
+
+    let mut found: Option<(SkillRecord, Stage)> = None;
+
+    match exact_skill(db, query, &mut keys)? {
+        Some(record) => {
+            let detail = if record.id == query {
+                format!(
+                    "skill id '{}' indexed from layer '{}'",
+                    record.id, record.source_layer
+                )
+            } else {
+                format!(
+                    "skill id '{}' (ignoring case) indexed from layer '{}'",
+                    record.id, record.source_layer
+                )
+            };
+            trace.step(Stage::ExactId, Outcome::Matched, detail);
+            if let Some(alias) = db.resolve_alias(query)?
+                && alias.canonical_id != record.id
+            {
+                trace.step(
+                    Stage::Alias,
+                    Outcome::Shadowed,
+                    format!(
+                        "alias '{query}' -> '{}' ({}) never applies because a skill has this id",
+                        alias.canonical_id, alias.alias_type
+                    ),
+                );
+            }
+            found = Some((record, Stage::ExactId));
+        }
+        None => trace.step(
+            Stage::ExactId,
+            Outcome::NoMatch,
+            format!("no skill with id '{query}'"),
+        ),
+    }
+
+    if found.is_none() {
+        match follow_alias(db, query)? {
+            Some(alias) => match db.get_skill(&alias.canonical_id)? {
+                Some(record) => {
+                    trace.step(
+                        Stage::Alias,
+                        Outcome::Matched,
+                        format!("alias {} ({})", alias.render_path(), alias.alias_type),
+                    );
+                    found = Some((record, Stage::Alias));
+                }
+                None => trace.step(
+                    Stage::Alias,
+                    Outcome::Rejected,
+                    format!(
+                        "alias {} points at a skill that is not indexed",
+                        alias.render_path()
+                    ),
+                ),
+            },
+            None => trace.step(
+                Stage::Alias,
+                Outcome::NoMatch,
+                format!("no alias '{query}'"),
+            ),
+        }
+    }
+
+    if found.is_none() && options.prefix {
+        if query.chars().count() < MIN_PREFIX_LEN {
+            trace.step(
+                Stage::Prefix,
+                Outcome::NoMatch,
+                format!("prefix matching needs at least {MIN_PREFIX_LEN} characters"),
+            );
+        } else {
+            let lowered = query.to_lowercase();
+            let matches: Vec<String> = load_keys(db, &mut keys)?
+                .iter()
+                .filter(|k| k.id.to_lowercase().starts_with(&lowered))
+                .map(|k| k.id.clone())
+                .collect();
+            match matches.as_slice() {
+                [] => trace.step(
+                    Stage::Prefix,
+                    Outcome::NoMatch,
+                    format!("no skill id starts with '{query}'"),
+                ),
+                [only] => {
+                    trace.step(
+                        Stage::Prefix,
+                        Outcome::Matched,
+                        format!("'{query}' is a unique prefix of '{only}'"),
+                    );
+                    if let Some(record) = db.get_skill(only)? {
+                        found = Some((record, Stage::Prefix));
+                    }
+                }
+                _ => trace.step_with(
+                    Stage::Prefix,
+                    Outcome::Rejected,
+                    format!("ambiguous prefix matches {} skills", matches.len()),
+                    matches,
+                ),
+            }
+        }
+    }
+
+    let mut scoped_out = false;
+    if let (Some(wanted), Some((record, _))) = (&options.layer, &found) {
+        let wanted = normalize_layer(wanted).unwrap_or_else(|| wanted.clone());
+        let actual =
+            normalize_layer(&record.source_layer).unwrap_or_else(|| record.source_layer.clone());
+        if actual == wanted {
+            trace.step(
+                Stage::LayerScope,
+                Outcome::Matched,
+                format!("'{}' is in layer '{wanted}'", record.id),
+            );
+        } else {
+            trace.step(
+                Stage::LayerScope,
+                Outcome::Rejected,
+                format!(
+                    "skill '{}' is in layer '{actual}', not '{wanted}'",
+                    record.id
+                ),
+            );
+            found = None;
+            scoped_out = true;
+        }
+    }
+
+    match found {
+        Some((record, via)) => {
+            trace.winner = Some(ResolvedTarget {
+                id: record.id.clone(),
+                is_pack: false,
+                via,
+                layer: Some(record.source_layer.clone()),
+                source_path: Some(record.source_path.clone()),
+            });
+            Ok(Resolution {
+                trace,
+                record: Some(record),
+            })
+        }
+        None if scoped_out => Ok(Resolution {
+            trace,
+            record: None,
+        }),
+        None => {
+            let ids: Vec<&str> = load_keys(db, &mut keys)?
+                .iter()
+                .map(|k| k.id.as_str())
+                .collect();
+            let near = suggest_similar_skills(query, &ids, 3);
+            if !near.is_empty() {
+                trace.step_with(
+                    Stage::Fuzzy,
+                    Outcome::Rejected,
+                    "near matches are suggestions only".to_string(),
+                    near,
+                );
+            }
+            Ok(Resolution {
+                trace,
+                record: None,
+            })
+        }
+    }
+}
+
+/// Where an alias leads once aliases naming other aliases are followed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AliasTarget {
+    /// Id the chain ends at (may not be indexed).
+    pub canonical_id: String,
+    /// Type of the first alias in the chain.
+    pub alias_type: String,
+    /// Every name visited, starting with the query and ending with
+    /// `canonical_id`.
+    pub path: Vec<String>,
+}
+
+impl AliasTarget {
+    fn render_path(&self) -> String {
+        self.path
+            .iter()
+            .map(|name| format!("'{name}'"))
+            .collect::<Vec<_>>()
+            .join(" -> ")
+    }
+}
+
+/// Map `name` through the alias table, case-insensitively. When the target
+/// is a deprecated (or unindexed) skill that is itself aliased to a
+/// replacement, as `ms prune` and `ms dedup` leave behind, the chain is
+/// followed to the replacement. Returns `None` if `name` is not an alias; an
+/// alias cycle (`a -> b -> a`) is an error.
+pub fn follow_alias(db: &Database, name: &str) -> Result<Option<AliasTarget>> {
+    let Some(first) = find_alias(db, name)? else {
+        return Ok(None);
+    };
+    let alias_type = first.alias_type.clone();
+    let mut path = vec![name.to_string()];
+    let mut current = first;
+    loop {
+        let target = current.canonical_id;
+        let seen = path
+            .iter()
+            .any(|p| p.to_lowercase() == target.to_lowercase());
+        path.push(target.clone());
+        if seen {
+            return Err(MsError::ValidationFailed(format!(
+                "alias cycle: {}",
+                path.join(" -> ")
+            )));
+        }
+        let forwards = db
+            .get_skill(&target)?
+            .is_none_or(|skill| skill.is_deprecated);
+        match find_alias(db, &target)? {
+            Some(next) if forwards && next.canonical_id != target => current = next,
+            _ => {
+                return Ok(Some(AliasTarget {
+                    canonical_id: target,
+                    alias_type,
+                    path,
+                }));
+            }
+        }
+    }
+}
+
+/// One alias-table lookup: exact first, then ignoring case. Aliases that
+/// differ only in case and point at different skills are refused.
+fn find_alias(db: &Database, name: &str) -> Result<Option<AliasResolution>> {
+    if let Some(alias) = db.resolve_alias(name)? {
+        return Ok(Some(alias));
+    }
+    let lowered = name.to_lowercase();
+    let mut matches: Vec<_> = db
+        .list_aliases(None)?
+        .into_iter()
+        .filter(|a| a.alias.to_lowercase() == lowered)
+        .collect();
+    matches.dedup_by(|a, b| a.skill_id == b.skill_id);
+    match matches.len() {
+        0 => Ok(None),
+        1 => {
+            let alias = matches.remove(0);
+            Ok(Some(AliasResolution {
+                canonical_id: alias.skill_id,
+                alias_type: alias.alias_type,
+            }))
+        }
+        _ => {
+            let candidates: Vec<String> = matches
+                .iter()
+                .map(|a| format!("{} -> {}", a.alias, a.skill_id))
+                .collect();
+            Err(MsError::ValidationFailed(format!(
+                "alias '{name}' is ambiguous ignoring case: {}",
+                candidates.join(", ")
+            )))
+        }
+    }
+}
+
+/// The skill with id `query`, or the only one whose id equals it ignoring
+/// case.
+fn exact_skill(
+    db: &Database,
+    query: &str,
+    keys: &mut Option<Vec<SkillKey>>,
+) -> Result<Option<SkillRecord>> {
+    if let Some(record) = db.get_skill(query)? {
+        return Ok(Some(record));
+    }
+    let lowered = query.to_lowercase();
+    let folded: Vec<&SkillKey> = load_keys(db, keys)?
+        .iter()
+        .filter(|k| k.id.to_lowercase() == lowered)
+        .collect();
+    match folded.as_slice() {
+        [only] => db.get_skill(&only.id),
+        _ => Ok(None),
+    }
+}
+
+fn load_keys<'a>(db: &Database, keys: &'a mut Option<Vec<SkillKey>>) -> Result<&'a [SkillKey]> {
+    if keys.is_none() {
+        *keys = Some(db.list_skill_keys()?);
+    }
+    Ok(keys.as_deref().unwrap_or_default())
+}
+
+/// Kind of surprising resolution found by [`find_ambiguities`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AmbiguityKind {
+    /// An alias named like an existing skill id; the id always wins.
+    AliasShadowedById,
+    /// An alias whose target is not indexed.
+    DanglingAlias,
+    /// A skill's name, typed as a reference, resolves to a different skill.
+    NameResolvesElsewhere,
+    /// A skill's name is an ambiguous id prefix.
+    AmbiguousName,
+}
+
+/// A name that resolves differently than a user would expect.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Ambiguity {
+    pub kind: AmbiguityKind,
+    pub name: String,
+    pub detail: String,
+    pub fix: String,
+}
+
+/// Enumerate aliases and skill names that resolve surprisingly.
+pub fn find_ambiguities(db: &Database) -> Result<Vec<Ambiguity>> {
+    let keys = db.list_skill_keys()?;
+    let ids: HashSet<&str> = keys.iter().map(|k| k.id.as_str()).collect();
+    let mut found = Vec::new();
+
+    for alias in db.list_aliases(None)? {
+        if ids.contains(alias.alias.as_str()) && alias.skill_id != alias.alias {
+            found.push(Ambiguity {
+                kind: AmbiguityKind::AliasShadowedById,
+                name: alias.alias.clone(),
+                detail: format!(
+                    "alias '{}' -> '{}' never applies: a skill has the id '{}'",
+                    alias.alias, alias.skill_id, alias.alias
+                ),
+                fix: format!("ms alias remove {}", alias.alias),
+            });
+        } else if !ids.contains(alias.skill_id.as_str()) {
+            found.push(Ambiguity {
+                kind: AmbiguityKind::DanglingAlias,
+                name: alias.alias.clone(),
+                detail: format!(
+                    "alias '{}' points at '{}', which is not indexed",
+                    alias.alias, alias.skill_id
+                ),
+                fix: format!("ms alias remove {}", alias.alias),
+            });
+        }
+    }
+
+    let mut seen = HashSet::new();
+    for key in &keys {
+        let naive = naive_reference(&key.name);
+        if naive.is_empty() || naive == key.id || !seen.insert(naive.clone()) {
+            continue;
+        }
+        let resolution = resolve(db, &naive, &ResolveOptions::default())?;
+        if let Some(winner) = &resolution.trace.winner {
+            if winner.id != key.id && !keys.iter().any(|k| k.id == winner.id && k.name == key.name)
+            {
+                found.push(Ambiguity {
+                    kind: AmbiguityKind::NameResolvesElsewhere,
+                    name: naive.clone(),
+                    detail: format!(
+                        "'{naive}' (the name of '{}') resolves to '{}' via {}",
+                        key.id,
+                        winner.id,
+                        winner.via.as_str()
+                    ),
+                    fix: format!("ms alias add {naive} --target {}", key.id),
+                });
+            }
+            continue;
+        }
+        if let Some(step) = resolution
+            .trace
+            .steps
+            .iter()
+            .find(|s| s.stage == Stage::Prefix && s.outcome == Outcome::Rejected)
+        {
+            let newest = keys
+                .iter()
+                .filter(|k| step.candidates.contains(&k.id))
+                .max_by(|a, b| a.indexed_at.cmp(&b.indexed_at));
+            let newest = newest
+                .map(|k| format!("; newest is '{}' (indexed {})", k.id, k.indexed_at))
+                .unwrap_or_default();
+            found.push(Ambiguity {
+                kind: AmbiguityKind::AmbiguousName,
+                name: naive.clone(),
+                detail: format!(
+                    "'{naive}' (the name of '{}') is an ambiguous prefix of {}{newest}",
+                    key.id,
+                    step.candidates.join(", ")
+                ),
+                fix: format!("ms alias add {naive} --target {}", key.id),
+            });
+        }
+    }
+    Ok(found)
+}
+
+/// What a user would likely type for a skill name: lowercase, dash-separated.
+fn naive_reference(name: &str) -> String {
+    name.to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn record(id: &str, name: &str, layer: &str) -> SkillRecord {
+        SkillRecord {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: String::new(),
+            version: None,
+            author: None,
+            source_path: format!("/skills/{id}/SKILL.md"),
+            source_layer: layer.to_string(),
+            git_remote: None,
+            git_commit: None,
+            content_hash: id.to_string(),
+            body: String::new(),
+            metadata_json: "{}".to_string(),
+            assets_json: "[]".to_string(),
+            token_count: 1,
+            quality_score: 0.5,
+            indexed_at: "2026-01-01T00:00:00Z".to_string(),
+            modified_at: "2026-01-01T00:00:00Z".to_string(),
+            is_deprecated: false,
+            deprecation_reason: None,
+        }
+    }
+
+    fn db_with(skills: &[SkillRecord], aliases: &[(&str, &str)]) -> (tempfile::TempDir, Database) {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("ms.db")).unwrap();
+        for skill in skills {
+            db.upsert_skill(skill).unwrap();
+        }
+        for (alias, target) in aliases {
+            db.upsert_alias(alias, target, "alternate", "2026-01-01T00:00:00Z")
+                .unwrap();
+        }
+        (dir, db)
+    }
+
+    fn stages(trace: &ResolutionTrace) -> Vec<(Stage, Outcome)> {
+        trace.steps.iter().map(|s| (s.stage, s.outcome)).collect()
+    }
+
+    #[test]
+    fn exact_id_beats_alias_and_reports_the_shadowed_alias() {
+        let (_dir, db) = db_with(
+            &[
+                record("deploy", "Deploy", "project"),
+                record("deploy-v1", "Deploy v1", "org"),
+            ],
+            &[("deploy", "deploy-v1")],
+        );
+
+        let res = resolve(&db, "deploy", &ResolveOptions::default()).unwrap();
+        assert_eq!(
+            stages(&res.trace),
+            vec![
+                (Stage::ExactId, Outcome::Matched),
+                (Stage::Alias, Outcome::Shadowed)
+            ]
+        );
+        assert!(res.trace.has_shadowing());
+        assert_eq!(res.record.unwrap().id, "deploy");
+    }
+
+    #[test]
+    fn alias_beats_prefix() {
+        let (_dir, db) = db_with(
+            &[
+                record("rust-errors", "Rust Errors", "project"),
+                record("rust-async", "Rust Async", "project"),
+            ],
+            &[("rust", "rust-errors")],
+        );
+        let res = resolve(&db, "rust", &ResolveOptions::default()).unwrap();
+        let winner = res.trace.winner.clone().unwrap();
+        assert_eq!(winner.id, "rust-errors");
+        assert_eq!(winner.via, Stage::Alias);
+        assert_eq!(
+            stages(&res.trace),
+            vec![
+                (Stage::ExactId, Outcome::NoMatch),
+                (Stage::Alias, Outcome::Matched)
+            ]
+        );
+    }
+
+    #[test]
+    fn aliases_follow_chains_ignore_case_and_reject_cycles() {
+        let deprecated = |id: &str| {
+            let mut skill = record(id, id, "project");
+            skill.is_deprecated = true;
+            skill
+        };
+        let (_dir, db) = db_with(
+            &[
+                record("deploy-checklist", "Deploy Checklist", "project"),
+                deprecated("deploy-v1"),
+                deprecated("loop-a"),
+                deprecated("loop-b"),
+                record("other", "Other", "project"),
+            ],
+            &[
+                ("dc", "deploy-v1"),
+                ("deploy-v1", "deploy-checklist"),
+                ("Loop", "loop-a"),
+                ("loop-a", "loop-b"),
+                ("loop-b", "loop-a"),
+                ("Twin", "deploy-checklist"),
+                ("twin", "other"),
+            ],
+        );
+
+        let res = resolve(&db, "DC", &ResolveOptions::default()).unwrap();
+        assert_eq!(res.trace.winner.clone().unwrap().via, Stage::Alias);
+        let step = res.trace.steps.last().unwrap();
+        assert_eq!(
+            step.detail,
+            "alias 'DC' -> 'deploy-v1' -> 'deploy-checklist' (alternate)"
+        );
+
+        let err = resolve(&db, "loop", &ResolveOptions::default()).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("alias cycle: loop -> loop-a -> loop-b -> loop-a"),
+            "{err}"
+        );
+
+        let err = resolve(&db, "TWIN", &ResolveOptions::default()).unwrap_err();
+        assert!(
+            err.to_string().contains("Twin -> deploy-checklist"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn unique_prefix_resolves_and_ambiguous_prefix_is_rejected() {
+        let (_dir, db) = db_with(
+            &[
+                record("rust-errors", "Rust Errors", "project"),
+                record("rust-async", "Rust Async", "project"),
+            ],
+            &[],
+        );
+        let res = resolve(&db, "rust-e", &ResolveOptions::default()).unwrap();
+        assert_eq!(res.trace.winner.unwrap().via, Stage::Prefix);
+
+        let res = resolve(&db, "rust", &ResolveOptions::default()).unwrap();
+        let step = res
+            .trace
+            .steps
+            .iter()
+            .find(|s| s.stage == Stage::Prefix)
+            .unwrap();
+        assert_eq!(step.outcome, Outcome::Rejected);
+        assert_eq!(step.candidates, vec!["rust-async", "rust-errors"]);
+        let err = res.into_record().unwrap_err().to_string();
+        assert!(err.contains("ambiguous prefix"), "{err}");
+
+        let res = resolve(&db, "rust-e", &ResolveOptions::exact()).unwrap();
+        assert!(res.record.is_none());
+    }
+
+    #[test]
+    fn packs_take_precedence_over_skills() {
+        let (_dir, db) = db_with(&[record("onboarding", "Onboarding", "project")], &[]);
+        let options = ResolveOptions {
+            packs: vec!["onboarding".to_string()],
+            ..ResolveOptions::default()
+        };
+        let res = resolve(&db, "onboarding", &options).unwrap();
+        assert!(res.trace.winner.unwrap().is_pack);
+        assert_eq!(
+            stages(&res.trace),
+            vec![
+                (Stage::Pack, Outcome::Matched),
+                (Stage::ExactId, Outcome::Shadowed)
+            ]
+        );
+    }
+
+    #[test]
+    fn layer_scope_rejects_instead_of_falling_through() {
+        let (_dir, db) = db_with(&[record("deploy", "Deploy", "project")], &[]);
+        let options = ResolveOptions {
+            layer: Some("user".to_string()),
+            ..ResolveOptions::default()
+        };
+        let res = resolve(&db, "deploy", &options).unwrap();
+        assert!(res.record.is_none());
+        let err = res.into_record().unwrap_err().to_string();
+        assert!(err.contains("is in layer 'project', not 'user'"), "{err}");
+    }
+
+    #[test]
+    fn misses_list_fuzzy_candidates_without_resolving() {
+        let (_dir, db) = db_with(&[record("rust-errors", "Rust Errors", "project")], &[]);
+        let res = resolve(&db, "rust-eror", &ResolveOptions::default()).unwrap();
+        let last = res.trace.steps.last().unwrap();
+        assert_eq!(
+            (last.stage, last.outcome),
+            (Stage::Fuzzy, Outcome::Rejected)
+        );
+        assert_eq!(last.candidates, vec!["rust-errors"]);
+        let err = res.into_record().unwrap_err().to_string();
+        assert!(err.contains("did you mean: rust-errors"), "{err}");
+    }
+
+    #[test]
+    fn ambiguities_cover_shadowed_aliases_and_misleading_names() {
+        let mut newer = record("deploy-freeze-2026", "Deploy Freeze", "project");
+        newer.indexed_at = "2026-10-01T00:00:00Z".to_string();
+        let (_dir, db) = db_with(
+            &[
+                record("deploy-freeze", "Deploy Freeze Legacy", "org"),
+                newer,
+                record("git", "Git", "project"),
+                record("git-basics", "Git Basics", "project"),
+                record("rust-errors", "Rust Errors", "project"),
+                record("errors-v2", "Errors", "project"),
+                record("errors-legacy", "Errors Legacy", "project"),
+            ],
+            &[("errors", "errors-legacy")],
+        );
+        db.upsert_alias("git", "git-basics", "short", "2026-01-01T00:00:00Z")
+            .unwrap();
+
+        let found = find_ambiguities(&db).unwrap();
+        let kinds: Vec<(AmbiguityKind, &str)> =
+            found.iter().map(|a| (a.kind, a.name.as_str())).collect();
+        assert!(kinds.contains(&(AmbiguityKind::AliasShadowedById, "git")));
+        // "errors" (the name of errors-v2) is taken by an alias to another skill.
+        assert!(kinds.contains(&(AmbiguityKind::NameResolvesElsewhere, "errors")));
+        // "deploy-freeze" resolves exactly to the legacy skill, not the newer one.
+        assert!(kinds.contains(&(AmbiguityKind::NameResolvesElsewhere, "deploy-freeze")));
+        let fix = &found
+            .iter()
+            .find(|a| a.name == "deploy-freeze")
+            .unwrap()
+            .fix;
+        assert_eq!(
+            fix,
+            "ms alias add deploy-freeze --target deploy-freeze-2026"
+        );
+        assert!(!kinds.iter().any(|(_, name)| *name == "rust-errors"));
+    }
+
+    #[test]
+    fn naive_reference_slugifies_names() {
+        assert_eq!(
+            naive_reference("Rust Error Handling"),
+            "rust-error-handling"
+        );
+        assert_eq!(naive_reference("  CI/CD: Deploys! "), "ci-cd-deploys");
+    }
+}
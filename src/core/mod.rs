@@ -1,25 +1,43 @@
 //! Core skill types and logic
 
+pub mod applicability;
+pub mod budget;
+pub mod bulk_edit;
+pub mod changelog;
+pub mod context_window;
+pub mod deep_link;
 pub mod dependencies;
+pub mod derived_tags;
 pub mod disclosure;
+pub mod effective;
+pub mod follow_up;
+pub mod fork;
 pub mod layering;
+pub mod lookup;
 pub mod overlay;
+pub mod overrides;
 pub mod pack_contracts;
 pub mod packing;
+pub mod project_setup;
 pub mod recovery;
 pub mod requirements;
 pub mod resolution;
 pub mod resolution_cache;
+pub mod resources;
 pub mod safety;
+pub mod scaffold;
 pub mod skill;
+pub mod slice_query;
 pub mod slicing;
 pub mod spec_lens;
 pub mod spec_migration;
+pub mod structured_edit;
+pub mod tokenizer;
 pub mod validation;
 
 pub use dependencies::{
-    DependencyGraph, DependencyLoadMode, DependencyResolver, DisclosureLevel,
-    ResolvedDependencyPlan, SkillLoadPlan,
+    DependencyConflict, DependencyGraph, DependencyKind, DependencyLoadMode, DependencyResolver,
+    DisclosureLevel, ResolvedDependencyPlan, SkillLoadPlan,
 };
 pub use layering::{
     BlockDiff, ConflictDetail, ConflictResolution, ConflictStrategy, LayeredRegistry,
@@ -27,8 +45,8 @@ pub use layering::{
 };
 pub use pack_contracts::{PackContractPreset, contract_from_name};
 pub use packing::{
-    ConstrainedPacker, CoverageQuota, MandatoryPredicate, MandatorySlice, PackConstraints,
-    PackError, PackResult,
+    ConstrainedPacker, CoverageQuota, MandatoryPredicate, MandatorySlice, MultiPackResult,
+    PackConstraints, PackError, PackResult, SkillSlices, pack_skills,
 };
 pub use recovery::{
     Checkpoint, FailureMode, RecoveryIssue, RecoveryManager, RecoveryReport, RetryConfig,
@@ -44,8 +62,9 @@ pub use resolution_cache::{
     ResolutionCache,
 };
 pub use skill::{
-    BlockType, EvidenceCoverage, EvidenceLevel, EvidenceRef, Skill, SkillBlock, SkillEvidenceIndex,
-    SkillLayer, SkillMetadata, SkillSection, SkillSpec,
+    BlockType, EvidenceCoverage, EvidenceLevel, EvidenceRef, ForkProvenance, Skill, SkillBlock,
+    SkillEvidenceIndex, SkillLayer, SkillMetadata, SkillSection, SkillSpec,
 };
 pub use slicing::{SkillSliceIndex, SkillSlicer};
 pub use spec_migration::migrate_spec;
+pub use tokenizer::{HeuristicCounter, TokenCounter, TokenizerKind, token_counter};
@@ -6,7 +6,7 @@
 
 use serde::{Deserialize, Serialize};
 
-use super::skill::SkillSpec;
+use super::skill::{SkillBlock, SkillSpec};
 
 /// Result of applying an overlay to a skill
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -17,6 +17,29 @@ pub struct OverlayApplicationResult {
     pub applied: bool,
     /// Description of what changed (if anything)
     pub changes: Vec<String>,
+    /// Blocks the overlay replaced, removed or added
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blocks: Vec<BlockChange>,
+    /// Modifications whose target block or section does not exist
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unmatched: Vec<String>,
+}
+
+/// A block touched by an overlay
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockChange {
+    pub block_id: String,
+    pub section_id: String,
+    pub action: BlockAction,
+}
+
+/// What an overlay did to a block
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockAction {
+    Replaced,
+    Removed,
+    Added,
 }
 
 /// Context for overlay application (e.g., environment, user preferences)
@@ -66,12 +89,13 @@ impl SkillOverlay {
         if !self.conditions_met(context) {
             return OverlayApplicationResult {
                 overlay_id: self.id.clone(),
-                applied: false,
-                changes: vec![],
+                ..OverlayApplicationResult::default()
             };
         }
 
         let mut changes = Vec::new();
+        let mut blocks = Vec::new();
+        let mut unmatched = Vec::new();
 
         for modification in &self.modifications {
             match modification {
@@ -80,8 +104,67 @@ impl SkillOverlay {
                     changes.push(format!("Appended to description: {text}"));
                 }
                 OverlayModification::AddTag(tag) => {
-                    spec.metadata.tags.push(tag.clone());
-                    changes.push(format!("Added tag: {tag}"));
+                    if !spec.metadata.tags.contains(tag) {
+                        spec.metadata.tags.push(tag.clone());
+                        changes.push(format!("Added tag: {tag}"));
+                    }
+                }
+                OverlayModification::RemoveTag(tag) => {
+                    let before = spec.metadata.tags.len();
+                    spec.metadata.tags.retain(|t| t != tag);
+                    if spec.metadata.tags.len() < before {
+                        changes.push(format!("Removed tag: {tag}"));
+                    }
+                }
+                OverlayModification::ReplaceBlock { block_id, content } => {
+                    match find_block_mut(spec, block_id) {
+                        Some((section_id, block)) => {
+                            block.content.clone_from(content);
+                            changes.push(format!("Replaced block: {block_id}"));
+                            blocks.push(BlockChange {
+                                block_id: block_id.clone(),
+                                section_id,
+                                action: BlockAction::Replaced,
+                            });
+                        }
+                        None => unmatched.push(format!("block '{block_id}' not found")),
+                    }
+                }
+                OverlayModification::RemoveBlock { block_id } => {
+                    let section = spec
+                        .sections
+                        .iter_mut()
+                        .find(|s| s.blocks.iter().any(|b| b.id == *block_id));
+                    match section {
+                        Some(section) => {
+                            section.blocks.retain(|b| b.id != *block_id);
+                            changes.push(format!("Removed block: {block_id}"));
+                            blocks.push(BlockChange {
+                                block_id: block_id.clone(),
+                                section_id: section.id.clone(),
+                                action: BlockAction::Removed,
+                            });
+                        }
+                        None => unmatched.push(format!("block '{block_id}' not found")),
+                    }
+                }
+                OverlayModification::AddBlock { section_id, block } => {
+                    if find_block_mut(spec, &block.id).is_some() {
+                        unmatched.push(format!("block '{}' already exists", block.id));
+                        continue;
+                    }
+                    match spec.sections.iter_mut().find(|s| s.id == *section_id) {
+                        Some(section) => {
+                            section.blocks.push(block.clone());
+                            changes.push(format!("Added block: {}", block.id));
+                            blocks.push(BlockChange {
+                                block_id: block.id.clone(),
+                                section_id: section_id.clone(),
+                                action: BlockAction::Added,
+                            });
+                        }
+                        None => unmatched.push(format!("section '{section_id}' not found")),
+                    }
                 }
                 OverlayModification::SetMetadata { key, value } => {
                     let key_str = key.as_str();
@@ -102,6 +185,8 @@ impl SkillOverlay {
             overlay_id: self.id.clone(),
             applied: true,
             changes,
+            blocks,
+            unmatched,
         }
     }
 
@@ -119,6 +204,20 @@ impl SkillOverlay {
     }
 }
 
+fn find_block_mut<'a>(
+    spec: &'a mut SkillSpec,
+    block_id: &str,
+) -> Option<(String, &'a mut SkillBlock)> {
+    spec.sections.iter_mut().find_map(|section| {
+        let id = section.id.clone();
+        section
+            .blocks
+            .iter_mut()
+            .find(|b| b.id == block_id)
+            .map(|block| (id, block))
+    })
+}
+
 impl PartialEq for SkillOverlay {
     fn eq(&self, other: &Self) -> bool {
         self.id == other.id
@@ -158,10 +257,21 @@ impl OverlayCondition {
 pub enum OverlayModification {
     /// Append text to description
     AppendDescription(String),
-    /// Add a tag
+    /// Add a tag (no-op when already present)
     AddTag(String),
+    /// Remove a tag
+    RemoveTag(String),
     /// Set arbitrary metadata
     SetMetadata { key: String, value: String },
+    /// Replace the content of a block, keeping its id and type
+    ReplaceBlock { block_id: String, content: String },
+    /// Drop a block
+    RemoveBlock { block_id: String },
+    /// Append a block to a section
+    AddBlock {
+        section_id: String,
+        block: SkillBlock,
+    },
 }
 
 #[cfg(test)]
@@ -279,4 +389,82 @@ mod tests {
                 .any(|c| c.contains("Set metadata unknown"))
         );
     }
+
+    #[test]
+    fn test_overlay_block_modifications() {
+        use crate::core::skill::{BlockType, SkillSection};
+
+        let mut spec = SkillSpec::new("test", "Test");
+        spec.metadata.tags = vec!["rust".into()];
+        spec.sections = vec![SkillSection {
+            id: "rules".into(),
+            title: "Rules".into(),
+            blocks: vec![
+                SkillBlock {
+                    id: "line-length".into(),
+                    block_type: BlockType::Rule,
+                    content: "Use 100 columns".into(),
+                },
+                SkillBlock {
+                    id: "tabs".into(),
+                    block_type: BlockType::Rule,
+                    content: "Use tabs".into(),
+                },
+            ],
+        }];
+
+        let overlay = SkillOverlay {
+            id: "project".into(),
+            skill_id: "test".into(),
+            priority: 1,
+            conditions: vec![OverlayCondition::Always],
+            modifications: vec![
+                OverlayModification::AddTag("rust".into()),
+                OverlayModification::ReplaceBlock {
+                    block_id: "line-length".into(),
+                    content: "Use 120 columns".into(),
+                },
+                OverlayModification::RemoveBlock {
+                    block_id: "tabs".into(),
+                },
+                OverlayModification::AddBlock {
+                    section_id: "rules".into(),
+                    block: SkillBlock {
+                        id: "no-unsafe".into(),
+                        block_type: BlockType::Rule,
+                        content: "No unsafe".into(),
+                    },
+                },
+                OverlayModification::RemoveBlock {
+                    block_id: "missing".into(),
+                },
+                OverlayModification::AddBlock {
+                    section_id: "nowhere".into(),
+                    block: SkillBlock {
+                        id: "orphan".into(),
+                        block_type: BlockType::Text,
+                        content: "x".into(),
+                    },
+                },
+            ],
+        };
+
+        let result = overlay.apply_to(&mut spec, &OverlayContext::default());
+
+        assert_eq!(spec.metadata.tags, ["rust"]);
+        let blocks = &spec.sections[0].blocks;
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].content, "Use 120 columns");
+        assert_eq!(blocks[0].block_type, BlockType::Rule);
+        assert_eq!(blocks[1].id, "no-unsafe");
+        assert_eq!(
+            result.blocks.iter().map(|c| c.action).collect::<Vec<_>>(),
+            [
+                BlockAction::Replaced,
+                BlockAction::Removed,
+                BlockAction::Added
+            ]
+        );
+        assert_eq!(result.unmatched.len(), 2);
+    }
 }
@@ -0,0 +1,330 @@
+//! Per-project skill overrides (`.ms/overrides.toml`).
+//!
+//! A project can tweak individual blocks and metadata fields of a skill it
+//! does not own without forking it:
+//!
+//! ```toml
+//! [override."community/rust-style".blocks.line-length]
+//! content = "Use 120 columns"
+//!
+//! [override."community/rust-style".blocks.tabs]
+//! remove = true
+//!
+//! [[override."community/rust-style".add]]
+//! section = "rules"
+//! id = "ci-command"
+//! type = "command"
+//! content = "cargo xtask ci"
+//!
+//! [override."community/rust-style".metadata]
+//! description = "Rust style, as this project uses it"
+//! add_tags = ["house-style"]
+//! ```
+//!
+//! Each skill's entry becomes a [`SkillOverlay`] with the highest priority.
+//! Overrides are applied when a skill is read (load, show, layered
+//! resolution), never written back, so the indexed skill stays the upstream
+//! one and `ms diff --overrides` can compare the two.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use super::layering::LayeredRegistry;
+use super::overlay::{
+    OverlayApplicationResult, OverlayCondition, OverlayContext, OverlayModification, SkillOverlay,
+};
+use super::skill::{BlockType, SkillBlock, SkillSpec};
+use crate::error::{MsError, Result};
+
+/// File name of the overrides file inside the ms root.
+pub const OVERRIDES_FILE: &str = "overrides.toml";
+
+/// Overlay id used for project overrides.
+pub const OVERRIDES_OVERLAY_ID: &str = "project-overrides";
+
+/// Parsed `.ms/overrides.toml`.
+#[derive(Debug, Clone, Default)]
+pub struct ProjectOverrides {
+    /// File the overrides came from.
+    pub path: PathBuf,
+    /// Overrides by skill id.
+    pub skills: BTreeMap<String, SkillOverride>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OverridesFile {
+    #[serde(default, rename = "override")]
+    overrides: BTreeMap<String, SkillOverride>,
+}
+
+/// Overrides for one skill.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SkillOverride {
+    #[serde(default)]
+    pub metadata: MetadataOverride,
+    /// Replaced or removed blocks, by block id.
+    #[serde(default)]
+    pub blocks: BTreeMap<String, BlockOverride>,
+    /// Blocks appended to a section.
+    #[serde(default)]
+    pub add: Vec<BlockAddition>,
+}
+
+/// Metadata fields to set, and tags to add or remove.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MetadataOverride {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub version: Option<String>,
+    pub author: Option<String>,
+    pub license: Option<String>,
+    #[serde(default)]
+    pub add_tags: Vec<String>,
+    #[serde(default)]
+    pub remove_tags: Vec<String>,
+}
+
+/// New content for a block, or its removal.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BlockOverride {
+    pub content: Option<String>,
+    #[serde(default)]
+    pub remove: bool,
+}
+
+/// A block added to a section.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BlockAddition {
+    pub section: String,
+    pub id: String,
+    pub content: String,
+    #[serde(default, rename = "type")]
+    pub block_type: BlockType,
+}
+
+impl ProjectOverrides {
+    /// Overrides from `<ms_root>/overrides.toml`; empty when the file does
+    /// not exist.
+    pub fn load(ms_root: &Path) -> Result<Self> {
+        Self::load_from(&ms_root.join(OVERRIDES_FILE))
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self {
+                path: path.to_path_buf(),
+                ..Self::default()
+            });
+        }
+        let contents = std::fs::read_to_string(path)
+            .map_err(|err| MsError::Config(format!("read overrides {}: {err}", path.display())))?;
+        Self::parse(path, &contents)
+    }
+
+    pub fn parse(path: &Path, contents: &str) -> Result<Self> {
+        let file: OverridesFile = toml::from_str(contents)
+            .map_err(|err| MsError::Config(format!("parse overrides {}: {err}", path.display())))?;
+        for (skill_id, skill) in &file.overrides {
+            for (block_id, block) in &skill.blocks {
+                if block.remove == block.content.is_some() {
+                    return Err(MsError::Config(format!(
+                        "override {skill_id} block {block_id}: set exactly one of `content` or `remove`"
+                    )));
+                }
+            }
+        }
+        Ok(Self {
+            path: path.to_path_buf(),
+            skills: file.overrides,
+        })
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.skills.is_empty()
+    }
+
+    /// The overlay for `skill_id`, if the project overrides it.
+    #[must_use]
+    pub fn overlay_for(&self, skill_id: &str) -> Option<SkillOverlay> {
+        let skill = self.skills.get(skill_id)?;
+        Some(SkillOverlay {
+            id: OVERRIDES_OVERLAY_ID.to_string(),
+            skill_id: skill_id.to_string(),
+            priority: i32::MAX,
+            conditions: vec![OverlayCondition::Always],
+            modifications: skill.modifications(),
+        })
+    }
+
+    /// Apply the overrides of `spec`'s skill to it. `None` when the project
+    /// does not override that skill.
+    pub fn apply(&self, spec: &mut SkillSpec) -> Option<OverlayApplicationResult> {
+        let overlay = self.overlay_for(&spec.metadata.id)?;
+        Some(overlay.apply_to(spec, &OverlayContext::default()))
+    }
+
+    /// Register every override with a layered registry, so layered
+    /// resolution applies them after picking a winner.
+    pub fn register_into(&self, registry: &mut LayeredRegistry) {
+        for skill_id in self.skills.keys() {
+            if let Some(overlay) = self.overlay_for(skill_id) {
+                registry.register_overlay(overlay);
+            }
+        }
+    }
+}
+
+impl SkillOverride {
+    fn modifications(&self) -> Vec<OverlayModification> {
+        let meta = &self.metadata;
+        let mut modifications: Vec<OverlayModification> = [
+            ("name", &meta.name),
+            ("description", &meta.description),
+            ("version", &meta.version),
+            ("author", &meta.author),
+            ("license", &meta.license),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| {
+            value
+                .as_ref()
+                .map(|value| OverlayModification::SetMetadata {
+                    key: key.to_string(),
+                    value: value.clone(),
+                })
+        })
+        .collect();
+        modifications.extend(
+            meta.add_tags
+                .iter()
+                .cloned()
+                .map(OverlayModification::AddTag),
+        );
+        modifications.extend(
+            meta.remove_tags
+                .iter()
+                .cloned()
+                .map(OverlayModification::RemoveTag),
+        );
+        for (block_id, block) in &self.blocks {
+            modifications.push(match &block.content {
+                Some(content) if !block.remove => OverlayModification::ReplaceBlock {
+                    block_id: block_id.clone(),
+                    content: content.clone(),
+                },
+                _ => OverlayModification::RemoveBlock {
+                    block_id: block_id.clone(),
+                },
+            });
+        }
+        modifications.extend(self.add.iter().map(|add| OverlayModification::AddBlock {
+            section_id: add.section.clone(),
+            block: SkillBlock {
+                id: add.id.clone(),
+                block_type: add.block_type.clone(),
+                content: add.content.clone(),
+            },
+        }));
+        modifications
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::overlay::BlockAction;
+    use crate::core::spec_lens::parse_markdown;
+
+    const OVERRIDES: &str = r#"
+[override."community/rust-style".blocks.line-length]
+content = "Use 120 columns"
+
+[override."community/rust-style".blocks.tabs]
+remove = true
+
+[[override."community/rust-style".add]]
+section = "rules"
+id = "ci-command"
+type = "command"
+content = "cargo xtask ci"
+
+[override."community/rust-style".metadata]
+description = "House Rust style"
+add_tags = ["house-style"]
+remove_tags = ["community"]
+"#;
+
+    fn upstream() -> SkillSpec {
+        let mut spec = parse_markdown(
+            "# Rust Style\n\nCommunity Rust style.\n\n## Rules {#rules}\n\n\
+             <!-- ms:anchor line-length -->\nUse 100 columns.\n\n\
+             <!-- ms:anchor tabs -->\nIndent with tabs.\n",
+        )
+        .unwrap();
+        spec.metadata.id = "community/rust-style".to_string();
+        spec.metadata.tags = vec!["community".to_string(), "rust".to_string()];
+        spec
+    }
+
+    #[test]
+    fn overrides_replace_remove_and_add_blocks() {
+        let overrides = ProjectOverrides::parse(Path::new("overrides.toml"), OVERRIDES).unwrap();
+        let mut spec = upstream();
+        let result = overrides.apply(&mut spec).unwrap();
+
+        assert!(result.unmatched.is_empty(), "{:?}", result.unmatched);
+        let rules = spec.sections.iter().find(|s| s.id == "rules").unwrap();
+        let ids: Vec<&str> = rules.blocks.iter().map(|b| b.id.as_str()).collect();
+        assert_eq!(ids, ["line-length", "ci-command"]);
+        assert_eq!(rules.blocks[0].content, "Use 120 columns");
+        assert_eq!(rules.blocks[1].block_type, BlockType::Command);
+        assert_eq!(spec.metadata.description, "House Rust style");
+        assert_eq!(spec.metadata.tags, ["rust", "house-style"]);
+        assert_eq!(
+            result.blocks.iter().map(|c| c.action).collect::<Vec<_>>(),
+            [
+                BlockAction::Replaced,
+                BlockAction::Removed,
+                BlockAction::Added
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_targets_are_reported() {
+        let overrides = ProjectOverrides::parse(
+            Path::new("overrides.toml"),
+            "[override.\"community/rust-style\".blocks.nope]\ncontent = \"x\"\n",
+        )
+        .unwrap();
+        let mut spec = upstream();
+        let result = overrides.apply(&mut spec).unwrap();
+        assert_eq!(result.unmatched, ["block 'nope' not found"]);
+
+        let mut other = SkillSpec::new("other", "Other");
+        assert!(overrides.apply(&mut other).is_none());
+    }
+
+    #[test]
+    fn invalid_files_are_rejected() {
+        let path = Path::new("overrides.toml");
+        assert!(ProjectOverrides::parse(path, "[override.a.blocks.b]\n").is_err());
+        assert!(
+            ProjectOverrides::parse(
+                path,
+                "[override.a.blocks.b]\ncontent = \"x\"\nremove = true\n"
+            )
+            .is_err()
+        );
+        assert!(ProjectOverrides::parse(path, "[override.a]\ncolour = \"red\"\n").is_err());
+        assert!(ProjectOverrides::parse(path, "").unwrap().is_empty());
+    }
+}
@@ -99,6 +99,115 @@ pub enum PackError {
     },
 }
 
+impl std::fmt::Display for PackError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MandatorySliceOmitted {
+                slice_id,
+                required_tokens,
+                available_tokens,
+            } => write!(
+                f,
+                "{slice_id} slices need {required_tokens} tokens but only {available_tokens} are available"
+            ),
+            Self::InsufficientBudget {
+                required,
+                available,
+            } => write!(
+                f,
+                "budget of {available} tokens is below the required {required}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PackError {}
+
+/// The slices one skill contributes to a multi-skill pack.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillSlices {
+    pub skill_id: String,
+    pub slices: Vec<SkillSlice>,
+}
+
+/// Result of packing several skills under one budget.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiPackResult {
+    /// Selected slices per skill, in input order; slices keep their document
+    /// order and skills with nothing selected are omitted.
+    pub skills: Vec<SkillSlices>,
+    pub total_tokens: usize,
+    /// Candidate slices left out to stay within budget.
+    pub dropped: usize,
+    /// Mandatory slices that were kept, as `skill_id/slice_id`.
+    pub mandatory_kept: Vec<String>,
+}
+
+/// Pack the slices of several skills (e.g. a skill and its dependencies)
+/// under one shared budget.
+///
+/// Slice ids are namespaced per skill so ids like `rule-1` do not collide;
+/// `MandatorySlice::ById` entries therefore use `skill_id/slice_id`.
+pub fn pack_skills(
+    skills: &[SkillSlices],
+    constraints: &PackConstraints,
+    mode: PackMode,
+) -> Result<MultiPackResult, PackError> {
+    let mut flat = Vec::new();
+    let mut origin: HashMap<String, (usize, usize)> = HashMap::new();
+    for (skill_index, skill) in skills.iter().enumerate() {
+        for (slice_index, slice) in skill.slices.iter().enumerate() {
+            let mut scoped = slice.clone();
+            scoped.id = scoped_slice_id(&skill.skill_id, &slice.id);
+            scoped.requires = slice
+                .requires
+                .iter()
+                .map(|req| scoped_slice_id(&skill.skill_id, req))
+                .collect();
+            origin.insert(scoped.id.clone(), (skill_index, slice_index));
+            flat.push(scoped);
+        }
+    }
+
+    let packed = ConstrainedPacker.pack(&flat, constraints, mode)?;
+    let selected: HashSet<&str> = packed.slices.iter().map(|s| s.id.as_str()).collect();
+    let mandatory_kept = collect_mandatory_slices(&flat, constraints)
+        .into_iter()
+        .map(|slice| slice.id)
+        .filter(|id| selected.contains(id.as_str()))
+        .collect();
+
+    let mut picked: Vec<(usize, usize)> = packed
+        .slices
+        .iter()
+        .filter_map(|slice| origin.get(&slice.id).copied())
+        .collect();
+    picked.sort_unstable();
+    let mut result: Vec<SkillSlices> = Vec::new();
+    for (skill_index, slice_index) in picked {
+        let skill = &skills[skill_index];
+        let slice = skill.slices[slice_index].clone();
+        match result.last_mut() {
+            Some(last) if last.skill_id == skill.skill_id => last.slices.push(slice),
+            _ => result.push(SkillSlices {
+                skill_id: skill.skill_id.clone(),
+                slices: vec![slice],
+            }),
+        }
+    }
+
+    Ok(MultiPackResult {
+        skills: result,
+        total_tokens: packed.total_tokens,
+        dropped: flat.len() - packed.slices.len(),
+        mandatory_kept,
+    })
+}
+
+fn scoped_slice_id(skill_id: &str, slice_id: &str) -> String {
+    format!("{skill_id}/{slice_id}")
+}
+
 /// Constrained packer implementation.
 pub struct ConstrainedPacker;
 
@@ -712,6 +821,74 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_pack_skills_shares_budget_and_reports_mandatory() {
+        let skills = vec![
+            SkillSlices {
+                skill_id: "root".to_string(),
+                slices: vec![
+                    make_slice("policy-1", SliceType::Policy, 20, 0.2, "policy"),
+                    make_slice("rule-1", SliceType::Rule, 30, 0.9, "rules"),
+                ],
+            },
+            SkillSlices {
+                skill_id: "dep".to_string(),
+                slices: vec![
+                    make_slice("rule-1", SliceType::Rule, 30, 0.8, "dep-rules"),
+                    make_slice("example-1", SliceType::Example, 200, 0.9, "examples"),
+                ],
+            },
+        ];
+        let mut constraints = PackConstraints::new(100, 2);
+        constraints
+            .mandatory_slices
+            .push(MandatorySlice::ByPredicate(MandatoryPredicate::Always));
+        let result = pack_skills(&skills, &constraints, PackMode::Balanced).unwrap();
+
+        assert_eq!(result.total_tokens, 80);
+        assert_eq!(result.dropped, 1);
+        assert_eq!(result.mandatory_kept, vec!["root/policy-1".to_string()]);
+        let layout: Vec<(&str, Vec<&str>)> = result
+            .skills
+            .iter()
+            .map(|s| {
+                (
+                    s.skill_id.as_str(),
+                    s.slices.iter().map(|slice| slice.id.as_str()).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            layout,
+            vec![
+                ("root", vec!["policy-1", "rule-1"]),
+                ("dep", vec!["rule-1"])
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pack_skills_fails_when_mandatory_exceeds_budget() {
+        let skills = vec![SkillSlices {
+            skill_id: "root".to_string(),
+            slices: vec![make_slice("policy-1", SliceType::Policy, 80, 0.2, "policy")],
+        }];
+        let mut constraints = PackConstraints::new(50, 2);
+        constraints
+            .mandatory_slices
+            .push(MandatorySlice::ByPredicate(MandatoryPredicate::Always));
+        let err = pack_skills(&skills, &constraints, PackMode::Balanced).unwrap_err();
+        assert!(matches!(
+            err,
+            PackError::MandatorySliceOmitted {
+                required_tokens: 80,
+                available_tokens: 50,
+                ..
+            }
+        ));
+        assert!(err.to_string().contains("need 80 tokens"));
+    }
+
     #[test]
     fn test_mandatory_policy_included() {
         let slices = vec![
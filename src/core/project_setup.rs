@@ -0,0 +1,506 @@
+//! Project-level bootstrap for `ms init`.
+//!
+//! [`find_project_root`] places `.ms/` at the repository root (or at an
+//! existing `.ms/` below it), so `ms init` from a subdirectory sets up the
+//! same project that nested invocations find. [`detect_assets`] looks for
+//! skill-like content already in the repository, and [`ProjectPlan`] lists
+//! the file changes init would make. Changes merge into existing files, so
+//! planning again after applying a plan finds nothing to do.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use toml_edit::{Array, DocumentMut, Item, Table, value};
+use walkdir::WalkDir;
+
+use crate::agent_detection::line_diff;
+use crate::error::{MsError, Result};
+
+/// Skill path every new project config starts with.
+pub const DEFAULT_PROJECT_PATH: &str = "./skills";
+
+/// Where init writes skills imported from other formats.
+pub const PROJECT_SKILLS_DIR: &str = ".ms/skills";
+
+/// `.gitignore` entries for ms-generated local state. The database, index,
+/// archive, and caches are ignored; config, overrides, and project skills
+/// stay tracked.
+pub const GITIGNORE_ENTRIES: &[&str] = &[
+    ".ms/*",
+    "!.ms/config.toml",
+    "!.ms/overrides.toml",
+    "!.ms/skills/",
+];
+
+const GITIGNORE_HEADER: &str = "# ms local state";
+
+/// Version control system of a project root.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Vcs {
+    Git,
+    Jujutsu,
+    Mercurial,
+}
+
+impl Vcs {
+    const MARKERS: &[(&str, Self)] = &[
+        (".jj", Self::Jujutsu),
+        (".git", Self::Git),
+        (".hg", Self::Mercurial),
+    ];
+
+    /// Whether the VCS reads `.gitignore`.
+    #[must_use]
+    pub const fn uses_gitignore(self) -> bool {
+        matches!(self, Self::Git | Self::Jujutsu)
+    }
+}
+
+/// Where a project's `.ms/` lives.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProjectRoot {
+    pub path: PathBuf,
+    pub vcs: Option<Vcs>,
+}
+
+/// The project root for `start`: the nearest directory at or above `start`
+/// holding `.ms/` (up to the VCS root), else the VCS root, else `start`.
+#[must_use]
+pub fn find_project_root(start: &Path) -> ProjectRoot {
+    let vcs_root = start.ancestors().find_map(|dir| {
+        Vcs::MARKERS
+            .iter()
+            .find(|(marker, _)| dir.join(marker).exists())
+            .map(|(_, vcs)| (dir, *vcs))
+    });
+    let limit = vcs_root.map_or(start, |(dir, _)| dir);
+    let existing = start
+        .ancestors()
+        .take_while(|dir| dir.starts_with(limit))
+        .find(|dir| dir.join(".ms").is_dir());
+    ProjectRoot {
+        path: existing.unwrap_or(limit).to_path_buf(),
+        vcs: vcs_root.map(|(_, vcs)| vcs),
+    }
+}
+
+/// Kind of skill-like asset found in a repository.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetKind {
+    /// `.claude/skills/`: Claude Code skills, already `SKILL.md` directories.
+    ClaudeSkills,
+    /// `.cursor/rules/`: Cursor `.mdc` rules.
+    CursorRules,
+    /// `docs/ai/`: agent-facing docs, as skills or plain Markdown.
+    DocsAi,
+}
+
+impl AssetKind {
+    pub const ALL: [Self; 3] = [Self::ClaudeSkills, Self::CursorRules, Self::DocsAi];
+
+    /// Location relative to the project root.
+    #[must_use]
+    pub const fn path(self) -> &'static str {
+        match self {
+            Self::ClaudeSkills => ".claude/skills",
+            Self::CursorRules => ".cursor/rules",
+            Self::DocsAi => "docs/ai",
+        }
+    }
+}
+
+/// What init does with a detected asset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetAction {
+    /// Add the directory to `skill_paths.project`.
+    Register,
+    /// Convert its files into skills under [`PROJECT_SKILLS_DIR`].
+    Import,
+    /// Leave it alone.
+    Skip,
+}
+
+/// A skill-like asset found in the project.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProjectAsset {
+    pub kind: AssetKind,
+    /// Location relative to the project root.
+    pub path: String,
+    /// `SKILL.md` files for registered assets, source files for imported ones.
+    pub files: usize,
+    pub action: AssetAction,
+}
+
+/// Skill-like assets under `root`, each with its default action: directories
+/// of `SKILL.md` files are registered, other content is imported.
+#[must_use]
+pub fn detect_assets(root: &Path) -> Vec<ProjectAsset> {
+    AssetKind::ALL
+        .into_iter()
+        .filter_map(|kind| {
+            let dir = root.join(kind.path());
+            if !dir.is_dir() {
+                return None;
+            }
+            let skills = count_files(&dir, |name| name == "SKILL.md");
+            let (files, action) = match kind {
+                AssetKind::ClaudeSkills => (skills, AssetAction::Register),
+                AssetKind::CursorRules => (
+                    count_files(&dir, |name| name.ends_with(".mdc")),
+                    AssetAction::Import,
+                ),
+                AssetKind::DocsAi if skills > 0 => (skills, AssetAction::Register),
+                AssetKind::DocsAi => (
+                    count_files(&dir, |name| name.ends_with(".md")),
+                    AssetAction::Import,
+                ),
+            };
+            (files > 0).then(|| ProjectAsset {
+                kind,
+                path: kind.path().to_string(),
+                files,
+                action,
+            })
+        })
+        .collect()
+}
+
+fn count_files(dir: &Path, matches: impl Fn(&str) -> bool) -> usize {
+    WalkDir::new(dir)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(std::result::Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| matches(&entry.file_name().to_string_lossy()))
+        .count()
+}
+
+/// One file init creates or updates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileChange {
+    pub path: PathBuf,
+    /// Contents before the change (`None` if the file does not exist).
+    pub before: Option<String>,
+    pub after: String,
+}
+
+impl FileChange {
+    /// Planned contents of `path` against what is on disk now.
+    pub fn new(path: PathBuf, after: String) -> Result<Self> {
+        let before = read_optional(&path)?;
+        Ok(Self {
+            path,
+            before,
+            after,
+        })
+    }
+
+    #[must_use]
+    pub fn changed(&self) -> bool {
+        self.before.as_deref() != Some(self.after.as_str())
+    }
+
+    /// Line diff between the current and the planned contents.
+    #[must_use]
+    pub fn diff(&self) -> String {
+        let path = self.path.display();
+        let mut out = format!("--- {path}\n+++ {path}\n");
+        out.push_str(&line_diff(
+            self.before.as_deref().unwrap_or(""),
+            &self.after,
+        ));
+        out
+    }
+
+    /// Write the planned contents, creating parent directories.
+    pub fn apply(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, &self.after)?;
+        Ok(())
+    }
+}
+
+/// Everything `ms init` would do to a project.
+#[derive(Debug, Clone)]
+pub struct ProjectPlan {
+    pub root: PathBuf,
+    pub vcs: Option<Vcs>,
+    pub ms_dir: PathBuf,
+    /// Whether `.ms/config.toml` already exists.
+    pub initialized: bool,
+    pub assets: Vec<ProjectAsset>,
+    /// Config and `.gitignore` changes; callers append imported skills.
+    pub changes: Vec<FileChange>,
+}
+
+impl ProjectPlan {
+    /// Config and `.gitignore` changes for `root` with `assets` decided.
+    pub fn build(root: &ProjectRoot, assets: Vec<ProjectAsset>) -> Result<Self> {
+        let ms_dir = root.path.join(".ms");
+        let config_path = ms_dir.join("config.toml");
+        let initialized = config_path.exists();
+
+        let mut detected = Vec::new();
+        if assets
+            .iter()
+            .any(|asset| asset.action == AssetAction::Import)
+        {
+            detected.push(PROJECT_SKILLS_DIR.to_string());
+        }
+        detected.extend(
+            assets
+                .iter()
+                .filter(|asset| asset.action == AssetAction::Register)
+                .map(|asset| asset.path.clone()),
+        );
+        let config = match read_optional(&config_path)? {
+            Some(existing) => merge_project_paths(&existing, &detected)?,
+            None => {
+                let mut project_paths = vec![DEFAULT_PROJECT_PATH.to_string()];
+                project_paths.extend(detected);
+                default_project_config(&project_paths)
+            }
+        };
+        let mut changes = vec![FileChange::new(config_path, config)?];
+
+        if root.vcs.is_some_and(Vcs::uses_gitignore) {
+            let path = root.path.join(".gitignore");
+            let existing = read_optional(&path)?.unwrap_or_default();
+            changes.push(FileChange::new(path, merge_gitignore(&existing))?);
+        }
+
+        Ok(Self {
+            root: root.path.clone(),
+            vcs: root.vcs,
+            ms_dir,
+            initialized,
+            assets,
+            changes,
+        })
+    }
+
+    /// Changes that would modify the project.
+    pub fn pending(&self) -> impl Iterator<Item = &FileChange> {
+        self.changes.iter().filter(|change| change.changed())
+    }
+
+    #[must_use]
+    pub fn is_up_to_date(&self) -> bool {
+        self.pending().next().is_none()
+    }
+
+    /// Write every pending change.
+    pub fn apply(&self) -> Result<()> {
+        self.pending().try_for_each(FileChange::apply)
+    }
+}
+
+fn read_optional(path: &Path) -> Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+fn default_project_config(project_paths: &[String]) -> String {
+    let project = project_paths
+        .iter()
+        .map(|path| format!("{path:?}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        r#"# ms configuration (project-local)
+
+[skill_paths]
+# Project skill paths, relative to the project root
+project = [{project}]
+
+# Local overrides
+local = []
+
+[search]
+# Embedding backend configuration
+use_embeddings = true
+embedding_backend = "hash"
+embedding_dims = 384
+bm25_weight = 0.5
+semantic_weight = 0.5
+
+[cm]
+# cass-memory (cm) integration
+enabled = true
+# cm_path = "cm"
+# default_flags = []
+
+[safety]
+# Destructive Command Guard configuration
+dcg_bin = "dcg"
+dcg_packs = []
+dcg_explain_format = "json"
+require_verbatim_approval = true
+"#
+    )
+}
+
+/// `existing` with any of `project_paths` missing from
+/// `skill_paths.project` appended, preserving comments and layout.
+fn merge_project_paths(existing: &str, project_paths: &[String]) -> Result<String> {
+    let mut doc: DocumentMut = existing
+        .parse()
+        .map_err(|err| MsError::Config(format!("parse .ms/config.toml: {err}")))?;
+    let skill_paths = doc
+        .entry("skill_paths")
+        .or_insert_with(|| Item::Table(Table::new()))
+        .as_table_like_mut()
+        .ok_or_else(|| MsError::Config("skill_paths is not a table".to_string()))?;
+    let project = skill_paths
+        .entry("project")
+        .or_insert_with(|| value(Array::new()))
+        .as_array_mut()
+        .ok_or_else(|| MsError::Config("skill_paths.project is not an array".to_string()))?;
+
+    let normalize = |path: &str| {
+        path.trim_start_matches("./")
+            .trim_end_matches('/')
+            .to_string()
+    };
+    for path in project_paths {
+        let present = project
+            .iter()
+            .filter_map(toml_edit::Value::as_str)
+            .any(|entry| normalize(entry) == normalize(path));
+        if !present {
+            project.push(path.as_str());
+        }
+    }
+    Ok(doc.to_string())
+}
+
+/// `existing` with the [`GITIGNORE_ENTRIES`] it lacks appended.
+fn merge_gitignore(existing: &str) -> String {
+    let present: Vec<&str> = existing.lines().map(str::trim).collect();
+    let missing: Vec<&str> = GITIGNORE_ENTRIES
+        .iter()
+        .copied()
+        .filter(|entry| !present.contains(entry))
+        .collect();
+    if missing.is_empty() {
+        return existing.to_string();
+    }
+
+    let mut merged = existing.to_string();
+    if !merged.is_empty() && !merged.ends_with('\n') {
+        merged.push('\n');
+    }
+    if !present.contains(&GITIGNORE_HEADER) {
+        if !merged.is_empty() {
+            merged.push('\n');
+        }
+        merged.push_str(GITIGNORE_HEADER);
+        merged.push('\n');
+    }
+    for entry in missing {
+        merged.push_str(entry);
+        merged.push('\n');
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repo() -> tempfile::TempDir {
+        let temp = tempfile::tempdir().unwrap();
+        fs::create_dir(temp.path().join(".git")).unwrap();
+        let skill = temp.path().join(".claude/skills/deploy");
+        fs::create_dir_all(&skill).unwrap();
+        fs::write(skill.join("SKILL.md"), "# Deploy\n").unwrap();
+        fs::create_dir_all(temp.path().join(".cursor/rules")).unwrap();
+        fs::write(temp.path().join(".cursor/rules/style.mdc"), "Use tabs.\n").unwrap();
+        fs::create_dir_all(temp.path().join("src/nested")).unwrap();
+        temp
+    }
+
+    #[test]
+    fn nested_directories_resolve_to_the_vcs_root() {
+        let temp = repo();
+        let root = find_project_root(&temp.path().join("src/nested"));
+        assert_eq!(root.path, temp.path());
+        assert_eq!(root.vcs, Some(Vcs::Git));
+
+        // An existing .ms below the VCS root wins, as it does for lookups
+        fs::create_dir(temp.path().join("src/.ms")).unwrap();
+        let root = find_project_root(&temp.path().join("src/nested"));
+        assert_eq!(root.path, temp.path().join("src"));
+    }
+
+    #[test]
+    fn detects_assets_with_default_actions() {
+        let temp = repo();
+        let assets = detect_assets(temp.path());
+        let summary: Vec<_> = assets
+            .iter()
+            .map(|asset| (asset.kind, asset.files, asset.action))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (AssetKind::ClaudeSkills, 1, AssetAction::Register),
+                (AssetKind::CursorRules, 1, AssetAction::Import),
+            ]
+        );
+    }
+
+    #[test]
+    fn applying_twice_changes_nothing() {
+        let temp = repo();
+        fs::write(temp.path().join(".gitignore"), "target/").unwrap();
+        let root = find_project_root(temp.path());
+
+        let plan = ProjectPlan::build(&root, detect_assets(temp.path())).unwrap();
+        assert!(!plan.initialized);
+        assert_eq!(plan.pending().count(), 2);
+        plan.apply().unwrap();
+
+        let config = fs::read_to_string(temp.path().join(".ms/config.toml")).unwrap();
+        assert!(config.contains(r#"project = ["./skills", ".ms/skills", ".claude/skills"]"#));
+        let gitignore = fs::read_to_string(temp.path().join(".gitignore")).unwrap();
+        assert!(gitignore.starts_with("target/\n\n# ms local state\n.ms/*\n"));
+
+        let again = ProjectPlan::build(&root, detect_assets(temp.path())).unwrap();
+        assert!(again.initialized);
+        assert!(
+            again.is_up_to_date(),
+            "{:?}",
+            again.pending().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn merges_into_an_existing_config() {
+        let existing = "# mine\n[skill_paths]\nproject = [\"./skills\", \".ms/skills/\"]\n";
+        let merged =
+            merge_project_paths(existing, &[PROJECT_SKILLS_DIR.into(), "docs/ai".into()]).unwrap();
+        assert!(merged.starts_with("# mine\n"));
+        assert!(merged.contains(r#"project = ["./skills", ".ms/skills/", "docs/ai"]"#));
+
+        let change = FileChange {
+            path: PathBuf::from(".ms/config.toml"),
+            before: Some(existing.to_string()),
+            after: merged,
+        };
+        assert!(
+            change
+                .diff()
+                .contains("+project = [\"./skills\", \".ms/skills/\", \"docs/ai\"]")
+        );
+    }
+}
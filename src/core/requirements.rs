@@ -1,16 +1,634 @@
 //! Environment requirement checks
+//!
+//! Skills declare the tools they need under `requirements.tools` in their
+//! frontmatter, either as `node >= 20` / `rg` shorthand or as maps with a
+//! `min_version`. Checking a requirement runs `<tool> --version` with a
+//! timeout, pulls the first `x.y[.z]` out of the output and compares it with
+//! the constraint. Unsatisfied requirements carry an install hint for a
+//! package manager present on this machine.
 
-use crate::error::Result;
+use std::collections::{BTreeMap, HashMap};
+use std::io::Read;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
 
-/// Check if all requirements are met
-pub const fn check_requirements() -> Result<Vec<RequirementCheck>> {
-    Ok(vec![])
+use regex::Regex;
+use semver::{Version, VersionReq};
+use serde::Serialize;
+
+use super::skill::{SkillRequirements, ToolRequirement};
+use crate::error::{MsError, Result};
+
+static VERSION_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(\d+)\.(\d+)(?:\.(\d+))?").expect("version regex"));
+
+/// Built-in install commands: tool -> `(package manager, command)`.
+const BUILTIN_HINTS: &[(&str, &[(&str, &str)])] = &[
+    (
+        "git",
+        &[
+            ("brew", "brew install git"),
+            ("apt", "sudo apt-get install -y git"),
+        ],
+    ),
+    (
+        "rg",
+        &[
+            ("brew", "brew install ripgrep"),
+            ("apt", "sudo apt-get install -y ripgrep"),
+            ("cargo", "cargo install ripgrep"),
+        ],
+    ),
+    (
+        "fd",
+        &[
+            ("brew", "brew install fd"),
+            ("apt", "sudo apt-get install -y fd-find"),
+            ("cargo", "cargo install fd-find"),
+        ],
+    ),
+    (
+        "jq",
+        &[
+            ("brew", "brew install jq"),
+            ("apt", "sudo apt-get install -y jq"),
+        ],
+    ),
+    (
+        "node",
+        &[
+            ("brew", "brew install node"),
+            ("apt", "sudo apt-get install -y nodejs"),
+        ],
+    ),
+    (
+        "npm",
+        &[
+            ("brew", "brew install node"),
+            ("apt", "sudo apt-get install -y npm"),
+        ],
+    ),
+    (
+        "pnpm",
+        &[
+            ("brew", "brew install pnpm"),
+            ("npm", "npm install -g pnpm"),
+        ],
+    ),
+    (
+        "yarn",
+        &[
+            ("brew", "brew install yarn"),
+            ("npm", "npm install -g yarn"),
+        ],
+    ),
+    ("tsc", &[("npm", "npm install -g typescript")]),
+    (
+        "python3",
+        &[
+            ("brew", "brew install python"),
+            ("apt", "sudo apt-get install -y python3"),
+        ],
+    ),
+    (
+        "go",
+        &[
+            ("brew", "brew install go"),
+            ("apt", "sudo apt-get install -y golang-go"),
+        ],
+    ),
+    (
+        "cargo",
+        &[
+            ("brew", "brew install rust"),
+            ("apt", "sudo apt-get install -y cargo"),
+        ],
+    ),
+    (
+        "docker",
+        &[
+            ("brew", "brew install --cask docker"),
+            ("apt", "sudo apt-get install -y docker.io"),
+        ],
+    ),
+    (
+        "gh",
+        &[
+            ("brew", "brew install gh"),
+            ("apt", "sudo apt-get install -y gh"),
+        ],
+    ),
+    (
+        "sqlite3",
+        &[
+            ("brew", "brew install sqlite"),
+            ("apt", "sudo apt-get install -y sqlite3"),
+        ],
+    ),
+    (
+        "shellcheck",
+        &[
+            ("brew", "brew install shellcheck"),
+            ("apt", "sudo apt-get install -y shellcheck"),
+        ],
+    ),
+    (
+        "tokei",
+        &[
+            ("brew", "brew install tokei"),
+            ("cargo", "cargo install tokei"),
+        ],
+    ),
+    (
+        "curl",
+        &[
+            ("brew", "brew install curl"),
+            ("apt", "sudo apt-get install -y curl"),
+        ],
+    ),
+];
+
+/// Outcome of checking one requirement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RequirementStatus {
+    Satisfied,
+    Unsatisfied,
+    /// The tool exists but its version could not be determined or compared.
+    Unknown,
+}
+
+/// What `<tool> --version` revealed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ToolProbe {
+    /// Not on PATH.
+    Missing,
+    /// On PATH; `version` is `None` when the output had no version in it.
+    Found {
+        path: PathBuf,
+        version: Option<Version>,
+    },
+    /// On PATH, but `--version` did not finish in time.
+    TimedOut { path: PathBuf },
+}
+
+/// A command that installs a missing tool.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InstallHint {
+    pub manager: String,
+    pub command: String,
+}
+
+/// Install commands per tool and package manager.
+#[derive(Debug, Clone, Default)]
+pub struct InstallHints {
+    table: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl InstallHints {
+    /// The built-in table with `overrides` (tool -> manager -> command)
+    /// applied on top; an empty command removes a built-in hint.
+    #[must_use]
+    pub fn with_overrides(overrides: &BTreeMap<String, BTreeMap<String, String>>) -> Self {
+        let mut table: BTreeMap<String, BTreeMap<String, String>> = BUILTIN_HINTS
+            .iter()
+            .map(|(tool, hints)| {
+                let hints = hints
+                    .iter()
+                    .map(|(manager, command)| ((*manager).to_string(), (*command).to_string()))
+                    .collect();
+                ((*tool).to_string(), hints)
+            })
+            .collect();
+        for (tool, hints) in overrides {
+            let entry = table.entry(tool.clone()).or_default();
+            for (manager, command) in hints {
+                if command.trim().is_empty() {
+                    entry.remove(manager);
+                } else {
+                    entry.insert(manager.clone(), command.clone());
+                }
+            }
+        }
+        Self { table }
+    }
+
+    /// The hint for `tool` using the first of `managers` that has one.
+    #[must_use]
+    pub fn hint_for(&self, tool: &str, managers: &[String]) -> Option<InstallHint> {
+        let hints = self.table.get(tool)?;
+        managers.iter().find_map(|manager| {
+            hints.get(manager).map(|command| InstallHint {
+                manager: manager.clone(),
+                command: command.clone(),
+            })
+        })
+    }
+}
+
+/// Package managers available here, best first: the platform's own manager,
+/// then language ones.
+#[must_use]
+pub fn detect_package_managers() -> Vec<String> {
+    let native = match std::env::consts::OS {
+        "macos" => Some(("brew", "brew")),
+        "linux" => Some(("apt", "apt-get")),
+        _ => None,
+    };
+    native
+        .into_iter()
+        .chain([("cargo", "cargo"), ("npm", "npm")])
+        .filter(|(_, bin)| which::which(bin).is_ok())
+        .map(|(manager, _)| manager.to_string())
+        .collect()
+}
+
+/// Parse a version constraint. A bare version is a minimum (`20` means
+/// `>=20`); anything else is a semver range, with spaces after operators
+/// allowed (`>= 1.2, < 2`).
+pub fn parse_constraint(raw: &str) -> Result<VersionReq> {
+    let raw = raw.trim();
+    let normalized = if raw.starts_with(|c: char| c.is_ascii_digit()) {
+        format!(">={raw}")
+    } else {
+        raw.split(',')
+            .map(|part| part.split_whitespace().collect::<String>())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    VersionReq::parse(&normalized).map_err(|err| {
+        MsError::ValidationFailed(format!("invalid version constraint '{raw}': {err}"))
+    })
+}
+
+/// The first `x.y[.z]` in `--version` output (`git version 2.43.0`,
+/// `v20.11.1`, `go version go1.22.0 linux/amd64`, `jq-1.7.1`).
+#[must_use]
+pub fn extract_version(output: &str) -> Option<Version> {
+    let caps = VERSION_RE.captures(output)?;
+    let part = |i: usize| {
+        caps.get(i)
+            .map_or(Some(0), |m| m.as_str().parse::<u64>().ok())
+    };
+    Some(Version::new(part(1)?, part(2)?, part(3)?))
+}
+
+/// Find `tool` on PATH and run `<tool> --version`, giving up after `timeout`.
+#[must_use]
+pub fn probe_tool(tool: &str, timeout: Duration) -> ToolProbe {
+    let Ok(path) = which::which(tool) else {
+        return ToolProbe::Missing;
+    };
+    let Ok(mut child) = Command::new(&path)
+        .arg("--version")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+    else {
+        return ToolProbe::Found {
+            path,
+            version: None,
+        };
+    };
+
+    let start = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => break,
+            Ok(None) if start.elapsed() < timeout => {
+                std::thread::sleep(Duration::from_millis(10));
+            }
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return ToolProbe::TimedOut { path };
+            }
+        }
+    }
+
+    // Some tools (older java, python2) print their version on stderr.
+    let mut output = String::new();
+    if let Some(mut stdout) = child.stdout.take() {
+        let _ = stdout.read_to_string(&mut output);
+    }
+    if let Some(mut stderr) = child.stderr.take() {
+        let _ = stderr.read_to_string(&mut output);
+    }
+    ToolProbe::Found {
+        path,
+        version: extract_version(&output),
+    }
 }
 
-/// Result of a requirement check
-#[derive(Debug)]
+/// Check result for one tool requirement.
+#[derive(Debug, Clone, Serialize)]
 pub struct RequirementCheck {
-    pub name: String,
-    pub satisfied: bool,
+    pub tool: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub constraint: Option<String>,
+    pub required: bool,
+    pub status: RequirementStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub found_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub install_hint: Option<InstallHint>,
+    /// Skills that declare this requirement.
+    pub skills: Vec<String>,
+}
+
+/// Compare a probe with a requirement. The install hint is left to the caller.
+#[must_use]
+pub fn evaluate(requirement: &ToolRequirement, probe: &ToolProbe) -> RequirementCheck {
+    let constraint = requirement.min_version.as_deref().map(str::trim);
+    let mut check = RequirementCheck {
+        tool: requirement.name.clone(),
+        constraint: constraint.map(str::to_string),
+        required: requirement.required,
+        status: RequirementStatus::Unknown,
+        found_version: None,
+        path: None,
+        message: None,
+        install_hint: None,
+        skills: Vec::new(),
+    };
+
+    let (path, version) = match probe {
+        ToolProbe::Missing => {
+            check.status = RequirementStatus::Unsatisfied;
+            check.message = Some("not found on PATH".to_string());
+            return check;
+        }
+        ToolProbe::Found { path, version } => (path, version.as_ref()),
+        ToolProbe::TimedOut { path } => {
+            check.path = Some(path.clone());
+            if constraint.is_some() {
+                check.message = Some(format!("`{} --version` timed out", requirement.name));
+            } else {
+                check.status = RequirementStatus::Satisfied;
+            }
+            return check;
+        }
+    };
+    check.path = Some(path.clone());
+    check.found_version = version.map(ToString::to_string);
+
+    let Some(constraint) = constraint else {
+        check.status = RequirementStatus::Satisfied;
+        return check;
+    };
+    let req = match parse_constraint(constraint) {
+        Ok(req) => req,
+        Err(err) => {
+            check.message = Some(err.to_string());
+            return check;
+        }
+    };
+    match version {
+        Some(version) if req.matches(version) => check.status = RequirementStatus::Satisfied,
+        Some(version) => {
+            check.status = RequirementStatus::Unsatisfied;
+            check.message = Some(format!("found {version}, need {constraint}"));
+        }
+        None => {
+            check.message = Some(format!(
+                "no version in `{} --version` output",
+                requirement.name
+            ));
+        }
+    }
+    check
+}
+
+/// Collect the tool requirements of `skills`, deduplicated by tool and
+/// constraint. A tool is required if any skill requires it.
+#[must_use]
+pub fn collect_tool_requirements(
+    skills: &[(String, SkillRequirements)],
+) -> Vec<(ToolRequirement, Vec<String>)> {
+    let mut merged: BTreeMap<(String, Option<String>), (ToolRequirement, Vec<String>)> =
+        BTreeMap::new();
+    for (skill_id, requirements) in skills {
+        for tool in &requirements.tools {
+            let constraint = tool.min_version.as_deref().map(|c| c.trim().to_string());
+            let (entry, declared_by) = merged
+                .entry((tool.name.clone(), constraint))
+                .or_insert_with(|| (tool.clone(), Vec::new()));
+            entry.required |= tool.required;
+            if !declared_by.contains(skill_id) {
+                declared_by.push(skill_id.clone());
+            }
+        }
+    }
+    merged.into_values().collect()
+}
+
+/// Check the tool requirements of `skills`, probing each tool once.
+#[must_use]
+pub fn check_requirements(
+    skills: &[(String, SkillRequirements)],
+    hints: &InstallHints,
+    managers: &[String],
+    timeout: Duration,
+) -> Vec<RequirementCheck> {
+    let mut probes: HashMap<String, ToolProbe> = HashMap::new();
+    collect_tool_requirements(skills)
+        .into_iter()
+        .map(|(requirement, skills)| {
+            let probe = probes
+                .entry(requirement.name.clone())
+                .or_insert_with(|| probe_tool(&requirement.name, timeout));
+            let mut check = evaluate(&requirement, probe);
+            if check.status == RequirementStatus::Unsatisfied {
+                check.install_hint = hints.hint_for(&requirement.name, managers);
+            }
+            check.skills = skills;
+            check
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::SkillMetadata;
+
+    fn found(version: &str) -> ToolProbe {
+        ToolProbe::Found {
+            path: PathBuf::from("/usr/bin/tool"),
+            version: extract_version(version),
+        }
+    }
+
+    #[test]
+    fn versions_parse_from_common_formats() {
+        let cases = [
+            ("git version 2.43.0", "2.43.0"),
+            ("v20.11.1\n", "20.11.1"),
+            ("ripgrep 14.1.0 (rev e50df40a19)\n+SIMD", "14.1.0"),
+            ("go version go1.22.0 linux/amd64", "1.22.0"),
+            ("jq-1.7.1", "1.7.1"),
+            ("GNU Make 4.3", "4.3.0"),
+            ("Docker version 24.0.7, build afdd53b", "24.0.7"),
+        ];
+        for (output, expected) in cases {
+            assert_eq!(
+                extract_version(output).map(|v| v.to_string()).as_deref(),
+                Some(expected),
+                "{output}"
+            );
+        }
+        assert_eq!(extract_version("usage: tool [options]"), None);
+    }
+
+    #[test]
+    fn constraints_accept_minimums_and_ranges() {
+        let v = |s: &str| Version::parse(s).unwrap();
+        assert!(parse_constraint("20").unwrap().matches(&v("20.11.1")));
+        assert!(!parse_constraint("20").unwrap().matches(&v("18.19.0")));
+        assert!(parse_constraint(">= 20").unwrap().matches(&v("22.0.0")));
+        assert!(parse_constraint("^1.4").unwrap().matches(&v("1.7.1")));
+        assert!(!parse_constraint("^1.4").unwrap().matches(&v("2.0.0")));
+        assert!(
+            parse_constraint(">= 1.2, < 2")
+                .unwrap()
+                .matches(&v("1.9.0"))
+        );
+        assert!(parse_constraint("newest").is_err());
+    }
+
+    #[test]
+    fn shorthand_and_map_forms_deserialize() {
+        let meta: SkillMetadata = serde_yaml::from_str(
+            "id: web\nrequirements:\n  tools:\n    - node >= 20\n    - rg\n    - name: docker\n      version: '24'\n",
+        )
+        .unwrap();
+        let tools = &meta.requirements.tools;
+        assert_eq!(tools[0].name, "node");
+        assert_eq!(tools[0].min_version.as_deref(), Some(">= 20"));
+        assert!(tools[0].required);
+        assert_eq!(tools[1].name, "rg");
+        assert_eq!(tools[1].min_version, None);
+        assert_eq!(tools[2].name, "docker");
+        assert_eq!(tools[2].min_version.as_deref(), Some("24"));
+        assert!(!tools[2].required);
+        assert_eq!(
+            ToolRequirement::parse("python3>=3.10")
+                .min_version
+                .as_deref(),
+            Some(">=3.10")
+        );
+    }
+
+    #[test]
+    fn evaluation_reports_each_status() {
+        let node = ToolRequirement::parse("node >= 20");
+        assert_eq!(
+            evaluate(&node, &found("v20.11.1")).status,
+            RequirementStatus::Satisfied
+        );
+        let old = evaluate(&node, &found("v18.19.0"));
+        assert_eq!(old.status, RequirementStatus::Unsatisfied);
+        assert_eq!(old.found_version.as_deref(), Some("18.19.0"));
+        assert_eq!(old.message.as_deref(), Some("found 18.19.0, need >= 20"));
+        assert_eq!(
+            evaluate(&node, &found("node")).status,
+            RequirementStatus::Unknown
+        );
+        assert_eq!(
+            evaluate(&node, &ToolProbe::Missing).status,
+            RequirementStatus::Unsatisfied
+        );
+
+        let rg = ToolRequirement::parse("rg");
+        let timed_out = ToolProbe::TimedOut {
+            path: PathBuf::from("/usr/bin/rg"),
+        };
+        assert_eq!(
+            evaluate(&rg, &timed_out).status,
+            RequirementStatus::Satisfied
+        );
+        assert_eq!(
+            evaluate(&node, &timed_out).status,
+            RequirementStatus::Unknown
+        );
+    }
+
+    #[test]
+    fn requirements_dedupe_across_skills() {
+        let reqs = |tools: &[&str]| SkillRequirements {
+            tools: tools.iter().map(|t| ToolRequirement::parse(t)).collect(),
+            ..SkillRequirements::default()
+        };
+        let skills = vec![
+            ("web".to_string(), reqs(&["node >= 20", "rg"])),
+            ("api".to_string(), reqs(&["node >= 20", "node >= 22"])),
+        ];
+        let collected = collect_tool_requirements(&skills);
+        let summary: Vec<_> = collected
+            .iter()
+            .map(|(tool, skills)| {
+                (
+                    tool.name.as_str(),
+                    tool.min_version.as_deref(),
+                    skills.len(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("node", Some(">= 20"), 2),
+                ("node", Some(">= 22"), 1),
+                ("rg", None, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn hints_prefer_available_managers_and_honor_overrides() {
+        let mut overrides = BTreeMap::new();
+        overrides.insert(
+            "node".to_string(),
+            BTreeMap::from([
+                (
+                    "apt".to_string(),
+                    "sudo apt-get install -y nodejs=20.*".to_string(),
+                ),
+                ("brew".to_string(), String::new()),
+            ]),
+        );
+        overrides.insert(
+            "internal-cli".to_string(),
+            BTreeMap::from([("npm".to_string(), "npm i -g @corp/cli".to_string())]),
+        );
+        let hints = InstallHints::with_overrides(&overrides);
+        let managers = |names: &[&str]| names.iter().map(|m| (*m).to_string()).collect::<Vec<_>>();
+
+        let rg = hints.hint_for("rg", &managers(&["apt", "cargo"])).unwrap();
+        assert_eq!(rg.manager, "apt");
+        assert_eq!(
+            hints.hint_for("rg", &managers(&["cargo"])).unwrap().command,
+            "cargo install ripgrep"
+        );
+        assert_eq!(
+            hints.hint_for("node", &managers(&["apt"])).unwrap().command,
+            "sudo apt-get install -y nodejs=20.*"
+        );
+        assert_eq!(hints.hint_for("node", &managers(&["brew"])), None);
+        assert_eq!(
+            hints
+                .hint_for("internal-cli", &managers(&["brew", "npm"]))
+                .unwrap()
+                .manager,
+            "npm"
+        );
+        assert_eq!(hints.hint_for("unknown-tool", &managers(&["brew"])), None);
+    }
 }
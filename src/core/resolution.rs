@@ -239,10 +239,25 @@ pub fn resolve_extends<R: SkillRepository + ?Sized>(
         .ok_or_else(|| MsError::ParentSkillNotFound {
             parent_id: parent_id.clone(),
             child_id: skill.metadata.id.clone(),
+            chain: vec![skill.metadata.id.clone(), parent_id.clone()],
         })?;
 
-    // Recursively resolve parent
-    let resolved_parent = resolve_extends(&parent, repository)?;
+    // Recursively resolve parent; a missing ancestor names the whole chain
+    let resolved_parent = resolve_extends(&parent, repository).map_err(|err| match err {
+        MsError::ParentSkillNotFound {
+            parent_id,
+            child_id,
+            mut chain,
+        } => {
+            chain.insert(0, skill.metadata.id.clone());
+            MsError::ParentSkillNotFound {
+                parent_id,
+                child_id,
+                chain,
+            }
+        }
+        other => other,
+    })?;
 
     // Check inheritance depth
     let depth = resolved_parent.inheritance_chain.len() + 1;
@@ -280,6 +295,8 @@ fn merge_skills(
 
     // Always replace these from child
     result.metadata.id = child.metadata.id.clone();
+    result.metadata.imported_from = child.metadata.imported_from.clone();
+    result.metadata.forked_from = child.metadata.forked_from.clone();
     result.format_version = child.format_version.clone();
 
     // Replace metadata if child provides it
@@ -307,12 +324,21 @@ fn merge_skills(
     if !child.metadata.provides.is_empty() {
         result.metadata.provides = child.metadata.provides.clone();
     }
+    if !child.metadata.recommends.is_empty() {
+        result.metadata.recommends = child.metadata.recommends.clone();
+    }
+    if !child.metadata.conflicts_with.is_empty() {
+        result.metadata.conflicts_with = child.metadata.conflicts_with.clone();
+    }
     if !child.metadata.platforms.is_empty() {
         result.metadata.platforms = child.metadata.platforms.clone();
     }
     if !child.metadata.context.is_empty() {
         result.metadata.context = child.metadata.context.clone();
     }
+    if !child.metadata.follow_ups.is_empty() {
+        result.metadata.follow_ups = child.metadata.follow_ups.clone();
+    }
 
     // Clear extends from result (it's now resolved)
     result.extends = None;
@@ -764,9 +790,25 @@ mod tests {
             MsError::ParentSkillNotFound {
                 parent_id,
                 child_id,
+                chain,
             } => {
                 assert_eq!(parent_id, "nonexistent");
                 assert_eq!(child_id, "child");
+                assert_eq!(chain, ["child", "nonexistent"]);
+            }
+            _ => assert!(false, "Expected ParentSkillNotFound error"),
+        }
+
+        // Deeper down, the chain starts at the skill being resolved
+        let mut repo = TestRepository::new();
+        repo.add(child);
+        let grandchild = make_skill_with_parent("grandchild", "Grandchild", "child");
+        match resolve_extends(&grandchild, &repo).unwrap_err() {
+            MsError::ParentSkillNotFound {
+                child_id, chain, ..
+            } => {
+                assert_eq!(child_id, "child");
+                assert_eq!(chain, ["grandchild", "child", "nonexistent"]);
             }
             _ => assert!(false, "Expected ParentSkillNotFound error"),
         }
@@ -0,0 +1,301 @@
+//! Companion files of a skill directory.
+//!
+//! A skill is its `SKILL.md` plus the files it brings along: everything under
+//! a `resources/` folder next to it, and any file the body links to with a
+//! relative path (`[schema](schemas/input.json)`). Resources are recorded per
+//! skill with their size and content hash, archived next to the archived
+//! `SKILL.md` and shipped inside bundles, always keyed by their path relative
+//! to the skill directory so links keep working wherever the skill lands.
+
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::LazyLock;
+
+use regex::Regex;
+use sha2::{Digest, Sha256};
+use walkdir::WalkDir;
+
+use crate::error::{MsError, Result};
+use crate::storage::sqlite::SkillResourceRecord;
+
+/// Folder next to `SKILL.md` whose files are all resources.
+pub const RESOURCES_DIR: &str = "resources";
+
+/// Text resources up to this size are inlined by `ms load --with-resources`.
+pub const DEFAULT_INLINE_RESOURCE_BYTES: u64 = 8 * 1024;
+
+/// Inline links and images: `[text](target "title")`, `![alt](<target>)`.
+static INLINE_LINK: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"\]\(\s*(?:<([^>\n]+)>|([^)\s]+))(?:\s+(?:"[^"\n]*"|'[^'\n]*'))?\s*\)"#).unwrap()
+});
+
+/// Reference definitions: `[id]: target`, but not footnotes (`[^1]: text`).
+static REFERENCE_LINK: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s{0,3}\[[^\]\n^][^\]\n]*\]:\s*(?:<([^>\n]+)>|(\S+))").unwrap());
+
+/// A relative link target found in skill markdown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelativeLink {
+    /// Target as written, without any `#fragment` or `?query`
+    pub target: String,
+    /// Byte range of the target in the markdown
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Why a relative link cannot be a resource.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkProblem {
+    /// Points above the skill directory
+    Escapes,
+    /// Points at the skill directory itself
+    Empty,
+}
+
+/// A resource file found next to a skill.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceFile {
+    /// Path relative to the skill directory, with `/` separators
+    pub path: String,
+    pub size: u64,
+    /// Hex SHA-256 of the content
+    pub content_hash: String,
+    /// Where the file was read from
+    pub source: PathBuf,
+}
+
+impl ResourceFile {
+    #[must_use]
+    pub fn record(&self) -> SkillResourceRecord {
+        SkillResourceRecord {
+            path: self.path.clone(),
+            size: i64::try_from(self.size).unwrap_or(i64::MAX),
+            content_hash: self.content_hash.clone(),
+        }
+    }
+}
+
+/// Relative link targets in `markdown`, skipping fenced code blocks. URLs,
+/// anchors and absolute paths are not relative links.
+#[must_use]
+pub fn relative_links(markdown: &str) -> Vec<RelativeLink> {
+    let mut links = Vec::new();
+    let mut fence: Option<&str> = None;
+    let mut offset = 0;
+    for line in markdown.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+        let trimmed = line.trim_start();
+        if let Some(open) = fence {
+            if trimmed.starts_with(open) {
+                fence = None;
+            }
+            continue;
+        }
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            fence = Some(&trimmed[..3]);
+            continue;
+        }
+        let captures = INLINE_LINK
+            .captures_iter(line)
+            .chain(REFERENCE_LINK.captures_iter(line));
+        for caps in captures {
+            let Some(target) = caps.get(1).or_else(|| caps.get(2)) else {
+                continue;
+            };
+            let raw = target.as_str();
+            let path = raw.split(['#', '?']).next().unwrap_or_default();
+            if !is_relative_target(raw) || path.is_empty() {
+                continue;
+            }
+            links.push(RelativeLink {
+                target: path.to_string(),
+                start: line_start + target.start(),
+                end: line_start + target.start() + path.len(),
+            });
+        }
+    }
+    links
+}
+
+/// Whether a link target is a path relative to the skill directory.
+fn is_relative_target(target: &str) -> bool {
+    if target.is_empty()
+        || target.starts_with('#')
+        || target.starts_with('/')
+        || target.starts_with('\\')
+        || target.starts_with('~')
+    {
+        return false;
+    }
+    // `https:`, `mailto:`, `C:` and friends
+    match target.find(':') {
+        Some(colon) => target[..colon].contains(['/', '\\']),
+        None => true,
+    }
+}
+
+/// Resolve a relative link lexically against the skill directory.
+pub fn normalize_link(target: &str) -> std::result::Result<PathBuf, LinkProblem> {
+    let mut normalized = PathBuf::new();
+    for component in Path::new(&target.replace('\\', "/")).components() {
+        match component {
+            Component::Normal(part) => normalized.push(part),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    return Err(LinkProblem::Escapes);
+                }
+            }
+            Component::RootDir | Component::Prefix(_) => return Err(LinkProblem::Escapes),
+        }
+    }
+    if normalized.as_os_str().is_empty() {
+        return Err(LinkProblem::Empty);
+    }
+    Ok(normalized)
+}
+
+/// Resources of the skill whose `SKILL.md` is at `skill_md`: the files under
+/// `resources/` plus the existing files `markdown` links to, sorted by path.
+///
+/// Symlinks, hidden files, nested skill packages and links that leave the
+/// skill directory are never resources.
+pub fn collect_resources(skill_md: &Path, markdown: &str) -> Result<Vec<ResourceFile>> {
+    let Some(skill_dir) = skill_md.parent() else {
+        return Ok(Vec::new());
+    };
+    let mut found: BTreeMap<String, PathBuf> = BTreeMap::new();
+
+    let resources_dir = skill_dir.join(RESOURCES_DIR);
+    if resources_dir.is_dir() {
+        let walker = WalkDir::new(&resources_dir)
+            .follow_links(false)
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.depth() == 0
+                    || !(entry.file_name().to_string_lossy().starts_with('.')
+                        || (entry.file_type().is_dir() && entry.path().join("SKILL.md").is_file()))
+            });
+        for entry in walker.filter_map(std::result::Result::ok) {
+            if entry.file_type().is_file()
+                && let Ok(rel) = entry.path().strip_prefix(skill_dir)
+            {
+                found.insert(slash_path(rel), entry.path().to_path_buf());
+            }
+        }
+    }
+
+    for link in relative_links(markdown) {
+        let Ok(rel) = normalize_link(&link.target) else {
+            continue;
+        };
+        let path = skill_dir.join(&rel);
+        let is_file = path
+            .symlink_metadata()
+            .is_ok_and(|meta| meta.file_type().is_file());
+        let hidden = rel
+            .components()
+            .any(|c| c.as_os_str().to_string_lossy().starts_with('.'));
+        if is_file && !hidden && rel != Path::new("SKILL.md") {
+            found.insert(slash_path(&rel), path);
+        }
+    }
+
+    found
+        .into_iter()
+        .map(|(path, source)| {
+            let bytes = std::fs::read(&source)
+                .map_err(|err| MsError::Config(format!("read {}: {err}", source.display())))?;
+            Ok(ResourceFile {
+                path,
+                size: bytes.len() as u64,
+                content_hash: hash_bytes(&bytes),
+                source,
+            })
+        })
+        .collect()
+}
+
+/// Hex SHA-256 of resource content.
+#[must_use]
+pub fn hash_bytes(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+/// Whether resource content is text worth inlining.
+#[must_use]
+pub fn is_text(bytes: &[u8]) -> bool {
+    !bytes.contains(&0) && std::str::from_utf8(bytes).is_ok()
+}
+
+fn slash_path(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn relative_links_skip_urls_anchors_and_code() {
+        let markdown = "See [run](scripts/run.sh#usage) and ![img](<img/a b.png> \"t\").\n\
+                        [web](https://example.com) [top](#top) [abs](/etc/hosts)\n\
+                        ```\n[x](inside/code.md)\n```\n\
+                        [schema]: ./schemas/input.json\n\
+                        [^1]: Footnote text\n";
+        let targets: Vec<String> = relative_links(markdown)
+            .into_iter()
+            .map(|link| link.target)
+            .collect();
+        assert_eq!(
+            targets,
+            vec!["scripts/run.sh", "img/a b.png", "./schemas/input.json"]
+        );
+        let link = &relative_links(markdown)[0];
+        assert_eq!(&markdown[link.start..link.end], "scripts/run.sh");
+    }
+
+    #[test]
+    fn normalize_link_rejects_escapes() {
+        assert_eq!(
+            normalize_link("./a/../b/c.txt").unwrap(),
+            PathBuf::from("b/c.txt")
+        );
+        assert_eq!(normalize_link("../secret"), Err(LinkProblem::Escapes));
+        assert_eq!(normalize_link("a/../../b"), Err(LinkProblem::Escapes));
+        assert_eq!(normalize_link("./"), Err(LinkProblem::Empty));
+    }
+
+    #[test]
+    fn collect_resources_takes_resources_dir_and_linked_files() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().join("skill");
+        fs::create_dir_all(dir.join("resources/templates")).unwrap();
+        fs::create_dir_all(dir.join("schemas")).unwrap();
+        fs::write(dir.join("resources/templates/pr.md"), "## Summary\n").unwrap();
+        fs::write(dir.join("resources/.DS_Store"), "x").unwrap();
+        fs::write(dir.join("schemas/input.json"), "{}").unwrap();
+        fs::write(dir.join("notes.txt"), "not linked").unwrap();
+        fs::write(temp.path().join("outside.txt"), "outside").unwrap();
+        let markdown = "[schema](schemas/input.json) [gone](missing.sh) [up](../outside.txt)";
+        let skill_md = dir.join("SKILL.md");
+        fs::write(&skill_md, markdown).unwrap();
+
+        let resources = collect_resources(&skill_md, markdown).unwrap();
+        let paths: Vec<&str> = resources.iter().map(|r| r.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec!["resources/templates/pr.md", "schemas/input.json"]
+        );
+        assert_eq!(resources[1].size, 2);
+        assert_eq!(resources[1].content_hash, hash_bytes(b"{}"));
+    }
+}
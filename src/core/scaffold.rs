@@ -0,0 +1,332 @@
+//! Skill scaffolding for `ms new`.
+//!
+//! Built-in templates are assembled as [`SkillSpec`]s and written with
+//! `compile_markdown`, so the generated file is exactly what the parser
+//! expects. Every example block is a `TODO` placeholder that the
+//! `non-empty-blocks` lint reports until it is filled in.
+//!
+//! User templates are `<name>.md` files in `~/.config/ms/templates/`. They use
+//! the `{{id}}` and `{{name}}` placeholders and replace a built-in of the same
+//! name.
+
+use std::path::{Path, PathBuf};
+
+use super::skill::{BlockType, SkillBlock, SkillSection, SkillSpec};
+use super::spec_lens::{parse_markdown, positional_block_id, slugify};
+use crate::error::{MsError, Result};
+
+/// Marker that starts every placeholder line.
+pub const TODO_MARKER: &str = "TODO";
+
+/// Built-in scaffold shapes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScaffoldKind {
+    Checklist,
+    Playbook,
+    CommandRecipe,
+    Pitfalls,
+}
+
+impl ScaffoldKind {
+    pub const ALL: [Self; 4] = [
+        Self::Checklist,
+        Self::Playbook,
+        Self::CommandRecipe,
+        Self::Pitfalls,
+    ];
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Checklist => "checklist",
+            Self::Playbook => "playbook",
+            Self::CommandRecipe => "command-recipe",
+            Self::Pitfalls => "pitfalls",
+        }
+    }
+
+    #[must_use]
+    pub const fn summary(self) -> &'static str {
+        match self {
+            Self::Checklist => "Items to verify before or after a task",
+            Self::Playbook => "Ordered steps with a verification pass",
+            Self::CommandRecipe => "A command to run and what success looks like",
+            Self::Pitfalls => "Mistakes to avoid and what to do instead",
+        }
+    }
+
+    /// Example sections as `(title, block type, placeholder content)`.
+    const fn sections(self) -> &'static [(&'static str, BlockType, &'static str)] {
+        match self {
+            Self::Checklist => &[
+                (
+                    "When to use",
+                    BlockType::Text,
+                    "TODO: describe the situation that calls for this checklist.",
+                ),
+                (
+                    "Checklist",
+                    BlockType::Checklist,
+                    "- [ ] TODO: first thing to verify\n- [ ] TODO: next thing to verify",
+                ),
+            ],
+            Self::Playbook => &[
+                (
+                    "When to use",
+                    BlockType::Text,
+                    "TODO: describe the situation that calls for this playbook.",
+                ),
+                (
+                    "Steps",
+                    BlockType::Text,
+                    "1. TODO: first step\n2. TODO: next step",
+                ),
+                (
+                    "Verification",
+                    BlockType::Text,
+                    "- TODO: how to confirm the steps worked",
+                ),
+            ],
+            Self::CommandRecipe => &[
+                (
+                    "When to use",
+                    BlockType::Text,
+                    "TODO: describe when to reach for this command.",
+                ),
+                (
+                    "Command",
+                    BlockType::Code,
+                    "```bash\n# TODO: the command to run\n```",
+                ),
+                (
+                    "Expected output",
+                    BlockType::Text,
+                    "TODO: what success looks like, and what to do on failure.",
+                ),
+            ],
+            Self::Pitfalls => &[
+                (
+                    "When to use",
+                    BlockType::Text,
+                    "TODO: describe the work these pitfalls apply to.",
+                ),
+                (
+                    "Pitfalls",
+                    BlockType::Pitfall,
+                    "- TODO: a mistake to avoid, and why it bites",
+                ),
+                ("Instead", BlockType::Rule, "- TODO: what to do instead"),
+            ],
+        }
+    }
+}
+
+/// Where a template comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateSource {
+    Builtin(ScaffoldKind),
+    User(PathBuf),
+}
+
+/// A template `ms new` can render.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScaffoldTemplate {
+    pub name: String,
+    pub summary: String,
+    pub source: TemplateSource,
+}
+
+impl ScaffoldTemplate {
+    /// Render the template as a skill with the given id.
+    pub fn render(&self, skill_id: &str) -> Result<SkillSpec> {
+        match &self.source {
+            TemplateSource::Builtin(kind) => Ok(scaffold(*kind, skill_id)),
+            TemplateSource::User(path) => {
+                let raw = std::fs::read_to_string(path).map_err(|err| {
+                    MsError::Config(format!("read template {}: {err}", path.display()))
+                })?;
+                let name = title_from_id(skill_id);
+                let mut spec =
+                    parse_markdown(&raw.replace("{{id}}", skill_id).replace("{{name}}", &name))?;
+                spec.metadata.id = skill_id.to_string();
+                if spec.metadata.name.is_empty() {
+                    spec.metadata.name = name;
+                }
+                Ok(spec)
+            }
+        }
+    }
+}
+
+/// `~/.config/ms/templates`, where user templates live.
+#[must_use]
+pub fn user_templates_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("ms").join("templates"))
+}
+
+/// Built-in templates followed by the `*.md` files in `user_dir`, sorted by
+/// name. A user template replaces the built-in it shares a name with.
+pub fn discover_templates(user_dir: Option<&Path>) -> Result<Vec<ScaffoldTemplate>> {
+    let mut templates: Vec<ScaffoldTemplate> = ScaffoldKind::ALL
+        .into_iter()
+        .map(|kind| ScaffoldTemplate {
+            name: kind.as_str().to_string(),
+            summary: kind.summary().to_string(),
+            source: TemplateSource::Builtin(kind),
+        })
+        .collect();
+
+    let Some(dir) = user_dir.filter(|dir| dir.is_dir()) else {
+        return Ok(templates);
+    };
+    let mut user = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("md") {
+            continue;
+        }
+        let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        let summary = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|raw| parse_markdown(&raw).ok())
+            .map(|spec| spec.metadata.description)
+            .filter(|description| !description.trim().is_empty())
+            .unwrap_or_else(|| "User template".to_string());
+        user.push(ScaffoldTemplate {
+            name: name.to_string(),
+            summary,
+            source: TemplateSource::User(path.clone()),
+        });
+    }
+
+    templates.retain(|builtin| !user.iter().any(|u| u.name == builtin.name));
+    templates.extend(user);
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(templates)
+}
+
+/// A skeleton skill of the given shape, every block a `TODO` placeholder.
+#[must_use]
+pub fn scaffold(kind: ScaffoldKind, skill_id: &str) -> SkillSpec {
+    let mut spec = SkillSpec::new(skill_id, title_from_id(skill_id));
+    spec.metadata.version = "0.1.0".to_string();
+    spec.metadata.description =
+        format!("{TODO_MARKER}: describe what this skill does and when to use it.");
+    spec.metadata.tags = vec![kind.as_str().to_string()];
+    spec.sections = kind
+        .sections()
+        .iter()
+        .map(|(title, block_type, content)| {
+            let id = slugify(title);
+            SkillSection {
+                blocks: vec![SkillBlock {
+                    id: positional_block_id(&id, 0),
+                    block_type: block_type.clone(),
+                    content: (*content).to_string(),
+                }],
+                id,
+                title: (*title).to_string(),
+            }
+        })
+        .collect();
+    spec
+}
+
+/// Whether block content is only unfilled `TODO` placeholders: every line,
+/// ignoring code fences and list, checkbox, heading, and comment markers,
+/// starts with [`TODO_MARKER`].
+#[must_use]
+pub fn is_placeholder(content: &str) -> bool {
+    let mut lines = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("```") && !line.starts_with("~~~"))
+        .peekable();
+    lines.peek().is_some()
+        && lines.all(|line| {
+            line.trim_start_matches(|c: char| {
+                c.is_ascii_digit() || c.is_whitespace() || "-*#>.)[]".contains(c)
+            })
+            .starts_with(TODO_MARKER)
+        })
+}
+
+/// `rust-error-handling` -> `Rust Error Handling`.
+pub(crate) fn title_from_id(skill_id: &str) -> String {
+    skill_id
+        .split(['-', '_', '.'])
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_uppercase().chain(chars).collect()
+            })
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::spec_lens::compile_markdown;
+
+    #[test]
+    fn every_builtin_round_trips_through_the_parser() {
+        for kind in ScaffoldKind::ALL {
+            let spec = scaffold(kind, "deploy-checks");
+            let markdown = compile_markdown(&spec);
+            let parsed = parse_markdown(&markdown).unwrap();
+            assert_eq!(parsed.metadata.id, "deploy-checks");
+            assert_eq!(parsed.metadata.name, "Deploy Checks");
+            assert_eq!(parsed.metadata.tags, vec![kind.as_str().to_string()]);
+            assert_eq!(parsed.sections.len(), spec.sections.len(), "{kind:?}");
+            for section in &parsed.sections {
+                assert!(
+                    section.blocks.iter().all(|b| is_placeholder(&b.content)),
+                    "{kind:?} section {} has filled content",
+                    section.id
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn placeholder_detection() {
+        assert!(is_placeholder("TODO: fill me"));
+        assert!(is_placeholder("- [ ] TODO: one\n- [ ] TODO: two"));
+        assert!(is_placeholder("1. TODO: step\n2. TODO: step"));
+        assert!(is_placeholder("```bash\n# TODO: command\n```"));
+        assert!(!is_placeholder("- [ ] TODO: one\n- [ ] run cargo test"));
+        assert!(!is_placeholder("Run the tests. TODO: more"));
+        assert!(!is_placeholder("   "));
+    }
+
+    #[test]
+    fn user_templates_override_builtins() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("checklist.md"),
+            "---\nid: \"{{id}}\"\ndescription: Team checklist\n---\n\n# {{name}}\n\n## Gates\n\n- [ ] TODO: gate\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let templates = discover_templates(Some(dir.path())).unwrap();
+        let names: Vec<_> = templates.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["checklist", "command-recipe", "pitfalls", "playbook"]
+        );
+
+        let checklist = &templates[0];
+        assert!(matches!(checklist.source, TemplateSource::User(_)));
+        assert_eq!(checklist.summary, "Team checklist");
+        let spec = checklist.render("release-gates").unwrap();
+        assert_eq!(spec.metadata.id, "release-gates");
+        assert_eq!(spec.metadata.name, "Release Gates");
+        assert_eq!(spec.sections[0].title, "Gates");
+    }
+}
@@ -4,6 +4,9 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
+use crate::core::applicability::ActiveDuring;
+use crate::core::follow_up::FollowUp;
+
 fn default_format_version() -> String {
     SkillSpec::FORMAT_VERSION.to_string()
 }
@@ -191,6 +194,12 @@ pub struct SkillMetadata {
     /// Provided capabilities
     #[serde(default)]
     pub provides: Vec<String>,
+    /// Capabilities loaded alongside when available; missing ones only warn.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recommends: Vec<String>,
+    /// Skills or capabilities that must not be loaded together with this one.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conflicts_with: Vec<String>,
     /// Supported platforms
     #[serde(default)]
     pub platforms: Vec<String>,
@@ -203,6 +212,54 @@ pub struct SkillMetadata {
     /// Context tags for auto-loading relevance matching.
     #[serde(default, skip_serializing_if = "ContextTags::is_empty")]
     pub context: ContextTags,
+    /// Seasonal/time-bound applicability window.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_during: Option<ActiveDuring>,
+    /// Date (`YYYY-MM-DD`) after which the skill no longer applies.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<String>,
+    /// Declared next steps after loading (load, feedback, verify).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub follow_ups: Vec<FollowUp>,
+    /// Source file this skill was imported from (`ms import --from-repo`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub imported_from: Option<ImportProvenance>,
+    /// Read-only skill this one is a project fork of (`--fork-to project`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forked_from: Option<ForkProvenance>,
+    /// Encrypt the body at rest (needs `MS_SKILL_KEY` or a keychain key).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub sensitive: bool,
+    /// Push to team skill repositories on `ms sync --remote`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub share: bool,
+    /// External tools and environment the skill needs (`ms requirements`).
+    #[serde(default, skip_serializing_if = "SkillRequirements::is_empty")]
+    pub requirements: SkillRequirements,
+}
+
+/// Where an imported skill came from.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ImportProvenance {
+    /// Source convention, e.g. `cursor-rule` or `copilot-instructions`.
+    pub format: String,
+    /// Source file, relative to the imported repository root.
+    pub path: String,
+    /// The source rule was attached to every request (Cursor `alwaysApply`).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub always_apply: bool,
+}
+
+/// Where a forked skill was copied from.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ForkProvenance {
+    /// Layer of the origin skill.
+    pub layer: SkillLayer,
+    /// Origin SKILL.md at fork time.
+    pub path: String,
+    /// SHA-256 of the origin SKILL.md at fork time; `ms diff --upstream`
+    /// reports drift when the origin no longer hashes to it.
+    pub content_hash: String,
 }
 
 /// A section in a skill
@@ -423,6 +480,17 @@ pub struct SkillRequirements {
     pub network: NetworkRequirement,
 }
 
+impl SkillRequirements {
+    /// Whether nothing is required.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.platforms.is_empty()
+            && self.tools.is_empty()
+            && self.env.is_empty()
+            && self.network == NetworkRequirement::default()
+    }
+}
+
 /// Platform constraint
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -434,11 +502,15 @@ pub enum Platform {
 }
 
 /// Tool requirement specification
+///
+/// Written either as a map or as a shorthand string such as `rg` or
+/// `node >= 20`; shorthand requirements are required.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "ToolRequirementRepr")]
 pub struct ToolRequirement {
     /// Tool name (e.g., "git", "docker")
     pub name: String,
-    /// Minimum version
+    /// Minimum version (`20`, `>= 20`) or a semver range (`^1.4`, `>=1.2, <2`)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub min_version: Option<String>,
     /// Whether the tool is required (vs. optional)
@@ -446,6 +518,55 @@ pub struct ToolRequirement {
     pub required: bool,
 }
 
+impl ToolRequirement {
+    /// Parse the shorthand form: a tool name optionally followed by a
+    /// version constraint (`node >= 20`, `python3>=3.10`, `jq 1.6`).
+    #[must_use]
+    pub fn parse(spec: &str) -> Self {
+        let spec = spec.trim();
+        let split = spec
+            .find(|c: char| c.is_whitespace() || "<>=^~".contains(c))
+            .unwrap_or(spec.len());
+        let (name, constraint) = spec.split_at(split);
+        let constraint = constraint.trim();
+        Self {
+            name: name.to_string(),
+            min_version: (!constraint.is_empty()).then(|| constraint.to_string()),
+            required: true,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ToolRequirementRepr {
+    Short(String),
+    Full {
+        name: String,
+        #[serde(default, alias = "version")]
+        min_version: Option<String>,
+        #[serde(default)]
+        required: bool,
+    },
+}
+
+impl From<ToolRequirementRepr> for ToolRequirement {
+    fn from(repr: ToolRequirementRepr) -> Self {
+        match repr {
+            ToolRequirementRepr::Short(spec) => Self::parse(&spec),
+            ToolRequirementRepr::Full {
+                name,
+                min_version,
+                required,
+            } => Self {
+                name,
+                min_version,
+                required,
+            },
+        }
+    }
+}
+
 /// Network access requirement
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
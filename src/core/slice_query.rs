@@ -0,0 +1,436 @@
+//! Query-driven slice selection for fine-grained context injection.
+//!
+//! `ms load --slices` and the MCP `load_slices` tool serve only the slices of
+//! a skill that answer a need, instead of a whole disclosure level. Slices are
+//! ranked with BM25 over their content; when no content matches, over their
+//! section titles. Explicit selectors pick slices by block id or section
+//! instead. Every result names the slices before and after it, so an agent can
+//! fetch "the block after pitfalls-block-3" with a follow-up selector.
+
+use std::collections::HashSet;
+
+use serde::Serialize;
+
+use super::disclosure::PackMode;
+use super::packing::{ConstrainedPacker, PackConstraints};
+use super::skill::{SkillSlice, SkillSpec, SliceType};
+use super::slicing::SkillSlicer;
+use super::tokenizer::TokenCounter;
+use crate::error::{MsError, Result};
+
+/// Slices returned for a need when the caller does not say how many.
+pub const DEFAULT_TOP_K: usize = 5;
+
+/// BM25 term-frequency saturation.
+const K1: f32 = 1.2;
+/// BM25 length normalization.
+const B: f32 = 0.75;
+
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "do", "for", "from", "how", "i", "in", "is",
+    "it", "my", "of", "on", "or", "the", "to", "what", "when", "with",
+];
+
+/// What to select from a skill.
+#[derive(Debug, Clone, Default)]
+pub struct SliceQuery {
+    /// Natural-language need to rank slices against
+    pub need: Option<String>,
+    /// Block ids, section ids or section titles; take precedence over `need`
+    pub selectors: Vec<String>,
+    /// Most slices to return for a need (selectors return every match)
+    pub top_k: usize,
+    /// Token cap on the returned slices, enforced by the packer
+    pub max_tokens: Option<usize>,
+}
+
+/// How the returned slices were matched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchedOn {
+    Content,
+    SectionTitle,
+    Selector,
+}
+
+/// One slice served for a query.
+#[derive(Debug, Clone, Serialize)]
+pub struct SelectedSlice {
+    /// Block id; usable as a selector
+    pub id: String,
+    pub slice_type: SliceType,
+    pub section_id: String,
+    /// Enclosing titles, outermost first (skill name, then section)
+    pub ancestors: Vec<String>,
+    /// Position among the skill's slices, in document order
+    pub position: usize,
+    /// Id of the slice before this one in the document
+    pub previous: Option<String>,
+    /// Id of the slice after this one in the document
+    pub next: Option<String>,
+    pub token_estimate: usize,
+    pub score: f32,
+    pub content: String,
+}
+
+/// Result of [`select_slices`].
+#[derive(Debug, Clone, Serialize)]
+pub struct SliceSelection {
+    /// `None` when nothing matched
+    pub matched_on: Option<MatchedOn>,
+    /// Best match first for a need, document order for selectors
+    pub slices: Vec<SelectedSlice>,
+    pub total_tokens: usize,
+    /// Matching slices left out by `top_k` or `max_tokens`
+    pub dropped: usize,
+    /// Slices in the skill
+    pub available: usize,
+}
+
+/// A slice with where it sits in the skill.
+struct Located {
+    slice: SkillSlice,
+    section_id: String,
+    section_title: String,
+}
+
+/// Select the slices of `spec` that answer `query`.
+pub fn select_slices(
+    spec: &SkillSpec,
+    query: &SliceQuery,
+    counter: &dyn TokenCounter,
+) -> Result<SliceSelection> {
+    let need = query.need.as_deref().map(str::trim).unwrap_or_default();
+    if query.selectors.is_empty() && need.is_empty() {
+        return Err(MsError::ValidationFailed(
+            "slice selection needs a need or at least one selector".to_string(),
+        ));
+    }
+
+    let located = locate(spec, counter);
+    let (matched_on, mut ranked) = if query.selectors.is_empty() {
+        rank_by_need(&located, need)
+    } else {
+        (
+            Some(MatchedOn::Selector),
+            select_by_selectors(&located, &query.selectors)?,
+        )
+    };
+    let matching = ranked.len();
+    if matched_on != Some(MatchedOn::Selector) {
+        ranked.truncate(query.top_k.max(1));
+    }
+    if let Some(max_tokens) = query.max_tokens {
+        ranked = pack_ranked(&located, ranked, max_tokens)?;
+    }
+
+    let slices: Vec<SelectedSlice> = ranked
+        .into_iter()
+        .map(|(position, score)| selected(spec, &located, position, score))
+        .collect();
+    Ok(SliceSelection {
+        matched_on: matched_on.filter(|_| matching > 0),
+        total_tokens: slices.iter().map(|slice| slice.token_estimate).sum(),
+        dropped: matching - slices.len(),
+        available: located.len(),
+        slices,
+    })
+}
+
+/// Slice `spec`, pairing each slice with its section. Token estimates cover
+/// the content alone, as that is all a selection serves.
+fn locate(spec: &SkillSpec, counter: &dyn TokenCounter) -> Vec<Located> {
+    // The slicer emits one slice per non-empty block, in document order
+    let sections = spec.sections.iter().flat_map(|section| {
+        section
+            .blocks
+            .iter()
+            .filter(|block| !block.content.trim().is_empty())
+            .map(move |_| section)
+    });
+    SkillSlicer::slice_with(spec, counter)
+        .slices
+        .into_iter()
+        .zip(sections)
+        .map(|(mut slice, section)| {
+            slice.token_estimate = counter.count(&slice.content);
+            Located {
+                slice,
+                section_id: section.id.clone(),
+                section_title: section.title.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Positions and scores of the slices matching `need`, best first.
+fn rank_by_need(located: &[Located], need: &str) -> (Option<MatchedOn>, Vec<(usize, f32)>) {
+    let terms = terms(need);
+    let content: Vec<Vec<String>> = located.iter().map(|l| terms_of(&l.slice.content)).collect();
+    let scores = bm25(&content, &terms);
+    if scores.iter().any(|score| *score > 0.0) {
+        return (Some(MatchedOn::Content), ranked(scores));
+    }
+    let titles: Vec<Vec<String>> = located.iter().map(|l| terms_of(&l.section_title)).collect();
+    (Some(MatchedOn::SectionTitle), ranked(bm25(&titles, &terms)))
+}
+
+fn ranked(scores: Vec<f32>) -> Vec<(usize, f32)> {
+    let mut ranked: Vec<(usize, f32)> = scores
+        .into_iter()
+        .enumerate()
+        .filter(|(_, score)| *score > 0.0)
+        .collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    ranked
+}
+
+/// Positions of the slices named by `selectors`, in document order. A
+/// selector is a block id, or a section id or title (every slice in it).
+fn select_by_selectors(located: &[Located], selectors: &[String]) -> Result<Vec<(usize, f32)>> {
+    let mut picked = HashSet::new();
+    for selector in selectors {
+        let selector = selector.trim();
+        let by_id = located.iter().position(|l| l.slice.id == selector);
+        let matches: Vec<usize> = match by_id {
+            Some(position) => vec![position],
+            None => located
+                .iter()
+                .enumerate()
+                .filter(|(_, l)| {
+                    l.section_id == selector || l.section_title.eq_ignore_ascii_case(selector)
+                })
+                .map(|(position, _)| position)
+                .collect(),
+        };
+        if matches.is_empty() {
+            return Err(MsError::NotFound(format!(
+                "no block or section matches selector '{selector}'"
+            )));
+        }
+        picked.extend(matches);
+    }
+    let mut positions: Vec<usize> = picked.into_iter().collect();
+    positions.sort_unstable();
+    Ok(positions
+        .into_iter()
+        .map(|position| (position, 1.0))
+        .collect())
+}
+
+/// Keep what the packer fits in `max_tokens`, preferring higher scores.
+fn pack_ranked(
+    located: &[Located],
+    ranked: Vec<(usize, f32)>,
+    max_tokens: usize,
+) -> Result<Vec<(usize, f32)>> {
+    let best = ranked.iter().map(|(_, score)| *score).fold(0.0, f32::max);
+    let candidates: Vec<SkillSlice> = ranked
+        .iter()
+        .map(|&(position, score)| {
+            let mut slice = located[position].slice.clone();
+            slice.utility_score = if best > 0.0 { score / best } else { 0.0 };
+            slice
+        })
+        .collect();
+    let constraints = PackConstraints::new(max_tokens, usize::MAX);
+    let packed = ConstrainedPacker
+        .pack(&candidates, &constraints, PackMode::UtilityFirst)
+        .map_err(|err| MsError::ValidationFailed(format!("max_tokens {max_tokens}: {err}")))?;
+    let kept: HashSet<&str> = packed.slices.iter().map(|s| s.id.as_str()).collect();
+    Ok(ranked
+        .into_iter()
+        .filter(|(position, _)| kept.contains(located[*position].slice.id.as_str()))
+        .collect())
+}
+
+fn selected(spec: &SkillSpec, located: &[Located], position: usize, score: f32) -> SelectedSlice {
+    let entry = &located[position];
+    let ancestors = [spec.metadata.name.as_str(), entry.section_title.as_str()]
+        .into_iter()
+        .filter(|title| !title.trim().is_empty())
+        .map(str::to_string)
+        .collect();
+    SelectedSlice {
+        id: entry.slice.id.clone(),
+        slice_type: entry.slice.slice_type,
+        section_id: entry.section_id.clone(),
+        ancestors,
+        position,
+        previous: position
+            .checked_sub(1)
+            .map(|before| located[before].slice.id.clone()),
+        next: located
+            .get(position + 1)
+            .map(|after| after.slice.id.clone()),
+        token_estimate: entry.slice.token_estimate,
+        score,
+        content: entry.slice.content.clone(),
+    }
+}
+
+/// Query terms: lowercased words, without stopwords and duplicates.
+fn terms(text: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    terms_of(text)
+        .into_iter()
+        .filter(|term| !STOPWORDS.contains(&term.as_str()) && seen.insert(term.clone()))
+        .collect()
+}
+
+/// Document terms: lowercased alphanumeric runs, so `unwrap_or` in code
+/// matches "unwrap" in a need.
+fn terms_of(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Okapi BM25 score of each document for `query`.
+fn bm25(docs: &[Vec<String>], query: &[String]) -> Vec<f32> {
+    let mut scores = vec![0.0; docs.len()];
+    if docs.is_empty() {
+        return scores;
+    }
+    let count = docs.len() as f32;
+    let average_len = docs.iter().map(Vec::len).sum::<usize>() as f32 / count;
+    for term in query {
+        let frequency = docs.iter().filter(|doc| doc.contains(term)).count() as f32;
+        if frequency == 0.0 {
+            continue;
+        }
+        let idf = ((count - frequency + 0.5) / (frequency + 0.5) + 1.0).ln();
+        for (doc, score) in docs.iter().zip(&mut scores) {
+            let tf = doc.iter().filter(|word| *word == term).count() as f32;
+            if tf == 0.0 {
+                continue;
+            }
+            let norm = 1.0 - B + B * doc.len() as f32 / average_len.max(1.0);
+            *score += idf * tf * (K1 + 1.0) / (tf + K1 * norm);
+        }
+    }
+    scores
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::spec_lens::parse_markdown;
+    use crate::core::tokenizer::HeuristicCounter;
+
+    const CODE_HEAVY: &str = "# Rust Errors\n\nError handling patterns.\n\n\
+## Propagation\n\n\
+```rust\nfn read(path: &Path) -> Result<String> {\n    let text = fs::read_to_string(path)?;\n    Ok(text)\n}\n```\n\n\
+```rust\nlet port = env::var(\"PORT\").map_err(|err| ConfigError::Missing(err))?;\n```\n\n\
+## Pitfalls\n\n\
+```rust\nlet value = map.get(key).unwrap();\n```\n\n\
+Calling unwrap in a request handler panics the whole worker.\n\n\
+```rust\nlet value = map.get(key).unwrap_or_default();\n```\n";
+
+    const PROSE_HEAVY: &str = "# Code Review\n\nHow to review changes.\n\n\
+## Scope\n\n\
+Read the description first and check the change does one thing.\n\n\
+Large changes should be split before review starts.\n\n\
+## Tone\n\n\
+Phrase feedback as questions and explain the reasoning behind each request.\n\n\
+Praise good decisions too, not only problems.\n\n\
+## Approval\n\n\
+Approve once every blocking comment is resolved and tests pass.\n";
+
+    fn query(need: &str) -> SliceQuery {
+        SliceQuery {
+            need: Some(need.to_string()),
+            top_k: DEFAULT_TOP_K,
+            ..SliceQuery::default()
+        }
+    }
+
+    #[test]
+    fn code_heavy_skill_ranks_matching_code() {
+        let spec = parse_markdown(CODE_HEAVY).unwrap();
+        let selection =
+            select_slices(&spec, &query("avoid unwrap panics"), &HeuristicCounter).unwrap();
+
+        assert_eq!(selection.matched_on, Some(MatchedOn::Content));
+        assert_eq!(selection.available, 5);
+        let top = &selection.slices[0];
+        assert_eq!(top.id, "pitfalls-block-2");
+        assert_eq!(top.ancestors, ["Rust Errors", "Pitfalls"]);
+        assert_eq!(top.previous.as_deref(), Some("pitfalls-block-1"));
+        assert_eq!(top.next.as_deref(), Some("pitfalls-block-3"));
+        assert!(
+            selection
+                .slices
+                .iter()
+                .all(|slice| slice.section_id == "pitfalls")
+        );
+        assert_eq!(
+            selection.total_tokens,
+            selection
+                .slices
+                .iter()
+                .map(|s| s.token_estimate)
+                .sum::<usize>()
+        );
+    }
+
+    #[test]
+    fn prose_heavy_skill_falls_back_to_section_titles() {
+        let spec = parse_markdown(PROSE_HEAVY).unwrap();
+
+        let by_content =
+            select_slices(&spec, &query("explain reasoning"), &HeuristicCounter).unwrap();
+        assert_eq!(by_content.matched_on, Some(MatchedOn::Content));
+        assert_eq!(by_content.slices[0].section_id, "tone");
+        assert_eq!(by_content.slices.len(), 1);
+
+        // No block mentions "approval"; its section title does
+        let by_title = select_slices(&spec, &query("approval"), &HeuristicCounter).unwrap();
+        assert_eq!(by_title.matched_on, Some(MatchedOn::SectionTitle));
+        assert_eq!(by_title.slices.len(), 1);
+        assert_eq!(by_title.slices[0].section_id, "approval");
+        assert!(by_title.slices[0].next.is_none());
+
+        let nothing = select_slices(&spec, &query("kubernetes"), &HeuristicCounter).unwrap();
+        assert_eq!(nothing.matched_on, None);
+        assert!(nothing.slices.is_empty());
+    }
+
+    #[test]
+    fn selectors_pick_blocks_and_sections_in_document_order() {
+        let spec = parse_markdown(PROSE_HEAVY).unwrap();
+        let selection = select_slices(
+            &spec,
+            &SliceQuery {
+                selectors: vec!["approval-block-1".to_string(), "Scope".to_string()],
+                ..SliceQuery::default()
+            },
+            &HeuristicCounter,
+        )
+        .unwrap();
+        let ids: Vec<&str> = selection.slices.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, ["scope-block-1", "scope-block-2", "approval-block-1"]);
+        assert_eq!(selection.matched_on, Some(MatchedOn::Selector));
+
+        let missing = SliceQuery {
+            selectors: vec!["nope".to_string()],
+            ..SliceQuery::default()
+        };
+        assert!(select_slices(&spec, &missing, &HeuristicCounter).is_err());
+        assert!(select_slices(&spec, &SliceQuery::default(), &HeuristicCounter).is_err());
+    }
+
+    #[test]
+    fn max_tokens_caps_the_selection() {
+        let spec = parse_markdown(CODE_HEAVY).unwrap();
+        let mut capped = query("unwrap");
+        let uncapped = select_slices(&spec, &capped, &HeuristicCounter).unwrap();
+        capped.max_tokens = Some(uncapped.slices[0].token_estimate);
+
+        let selection = select_slices(&spec, &capped, &HeuristicCounter).unwrap();
+        assert!(selection.total_tokens <= uncapped.slices[0].token_estimate);
+        assert_eq!(selection.slices.len(), 1);
+        assert_eq!(selection.dropped, uncapped.slices.len() - 1);
+    }
+}
@@ -6,6 +6,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use super::skill::{BlockType, SkillBlock, SkillSection, SkillSlice, SkillSpec, SliceType};
+use super::tokenizer::TokenCounter;
 
 /// Index of slices generated for a skill.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,11 +21,21 @@ pub struct SkillSlicer;
 impl SkillSlicer {
     #[must_use]
     pub fn slice(spec: &SkillSpec) -> SkillSliceIndex {
+        Self::slice_counted(spec, &estimate_tokens)
+    }
+
+    /// Like [`slice`](Self::slice), with token estimates from `counter`.
+    #[must_use]
+    pub fn slice_with(spec: &SkillSpec, counter: &dyn TokenCounter) -> SkillSliceIndex {
+        Self::slice_counted(spec, &|text| counter.count(text).max(1))
+    }
+
+    fn slice_counted(spec: &SkillSpec, count: &dyn Fn(&str) -> usize) -> SkillSliceIndex {
         let mut slices = Vec::new();
         let mut counters: HashMap<&'static str, usize> = HashMap::new();
 
         for section in &spec.sections {
-            slice_section(spec, section, &mut slices, &mut counters);
+            slice_section(spec, section, &mut slices, &mut counters, count);
         }
 
         SkillSliceIndex {
@@ -50,6 +61,7 @@ fn slice_section(
     section: &SkillSection,
     slices: &mut Vec<SkillSlice>,
     counters: &mut HashMap<&'static str, usize>,
+    count: &dyn Fn(&str) -> usize,
 ) {
     let mut first = true;
     for block in &section.blocks {
@@ -65,11 +77,11 @@ fn slice_section(
 
         // Calculate token estimate conservatively: includes header cost for the first slice.
         let header_cost = if first && !section.title.trim().is_empty() {
-            estimate_tokens(&format!("## {}\n\n", section.title.trim()))
+            count(&format!("## {}\n\n", section.title.trim()))
         } else {
             0
         };
-        let token_estimate = estimate_tokens(&content) + header_cost;
+        let token_estimate = count(&content) + header_cost;
 
         let utility_score = utility_score(slice_type);
         let coverage_group = coverage_group(slice_type);
@@ -237,6 +249,37 @@ mod tests {
         assert_eq!(index.slices[0].slice_type, SliceType::Policy);
     }
 
+    #[test]
+    fn test_slice_with_counter() {
+        struct PerChar;
+        impl TokenCounter for PerChar {
+            fn name(&self) -> &'static str {
+                "per-char"
+            }
+            fn count(&self, text: &str) -> usize {
+                text.chars().count()
+            }
+        }
+
+        let spec = SkillSpec {
+            sections: vec![SkillSection {
+                id: "s1".to_string(),
+                title: "Intro".to_string(),
+                blocks: vec![SkillBlock {
+                    id: "rule-1".to_string(),
+                    block_type: BlockType::Rule,
+                    content: "Always sanitize input.".to_string(),
+                }],
+            }],
+            ..Default::default()
+        };
+
+        let index = SkillSlicer::slice_with(&spec, &PerChar);
+        // "## Intro\n\n" header (10) + content (22)
+        assert_eq!(index.slices[0].token_estimate, 32);
+        assert_eq!(SkillSlicer::slice(&spec).slices[0].token_estimate, 9);
+    }
+
     #[test]
     fn test_token_estimate_nonzero() {
         let estimate = estimate_tokens("abcd");
@@ -1,12 +1,61 @@
 //! Round-trip spec <-> markdown mapping
 
+use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 
-use super::skill::{BlockType, SkillBlock, SkillMetadata, SkillSection, SkillSpec};
+use super::skill::{BlockType, SkillBlock, SkillInclude, SkillMetadata, SkillSection, SkillSpec};
 use crate::error::{MsError, Result};
 
 const PREAMBLE_SECTION_ID: &str = "__preamble";
 
+/// Comment marker that pins the id of the block following it.
+const ANCHOR_MARKER_PREFIX: &str = "<!-- ms:anchor ";
+const ANCHOR_MARKER_SUFFIX: &str = "-->";
+
+/// Comment marker naming where the section or block following it came from
+/// (`ms show --effective`). The parser skips it.
+const ORIGIN_MARKER_PREFIX: &str = "<!-- ms:origin ";
+
+/// Inheritance and composition fields, written in the frontmatter beside the
+/// metadata.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CompositionFrontmatter {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    extends: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    replace_rules: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    replace_examples: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    replace_pitfalls: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    replace_checklist: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    includes: Vec<SkillInclude>,
+}
+
+impl CompositionFrontmatter {
+    fn of(spec: &SkillSpec) -> Self {
+        Self {
+            extends: spec.extends.clone(),
+            replace_rules: spec.replace_rules,
+            replace_examples: spec.replace_examples,
+            replace_pitfalls: spec.replace_pitfalls,
+            replace_checklist: spec.replace_checklist,
+            includes: spec.includes.clone(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.extends.is_none()
+            && !(self.replace_rules
+                || self.replace_examples
+                || self.replace_pitfalls
+                || self.replace_checklist)
+            && self.includes.is_empty()
+    }
+}
+
 /// Bidirectional mapping between `SkillSpec` and SKILL.md.
 pub struct SpecLens;
 
@@ -41,10 +90,12 @@ pub fn parse_markdown(content: &str) -> Result<SkillSpec> {
     let mut description_lines = Vec::new();
     let mut sections: Vec<SkillSection> = Vec::new();
     let mut metadata = SkillMetadata::default();
+    let mut composition = CompositionFrontmatter::default();
 
     let mut current_section: Option<SkillSection> = None;
     let mut in_description = false;
-    let mut in_code_block = false;
+    // Marker character and length of the open code fence, if any.
+    let mut fence: Option<(char, usize)> = None;
     let mut code_lines: Vec<String> = Vec::new();
     let mut paragraph_lines: Vec<String> = Vec::new();
     let mut in_frontmatter = false;
@@ -59,21 +110,27 @@ pub fn parse_markdown(content: &str) -> Result<SkillSpec> {
         }
     }
 
-    let flush_paragraph = |section: &mut SkillSection, lines: &mut Vec<String>| {
-        if lines.is_empty() {
-            return;
-        }
-        let content = lines.join("\n").trim_end().to_string();
-        lines.clear();
-        if content.is_empty() {
-            return;
-        }
-        section.blocks.push(SkillBlock {
-            id: format!("{}-block-{}", section.id, section.blocks.len() + 1),
-            block_type: BlockType::Text,
-            content,
-        });
-    };
+    // Explicit anchor (`<!-- ms:anchor id -->`) waiting for the next block.
+    let mut pending_anchor: Option<String> = None;
+
+    let flush_paragraph =
+        |section: &mut SkillSection, lines: &mut Vec<String>, anchor: &mut Option<String>| {
+            if lines.is_empty() {
+                return;
+            }
+            let content = lines.join("\n").trim_end().to_string();
+            lines.clear();
+            if content.is_empty() {
+                return;
+            }
+            section.blocks.push(SkillBlock {
+                id: anchor
+                    .take()
+                    .unwrap_or_else(|| positional_block_id(&section.id, section.blocks.len())),
+                block_type: BlockType::Text,
+                content,
+            });
+        };
 
     for line in lines_iter {
         if in_frontmatter {
@@ -84,6 +141,9 @@ pub fn parse_markdown(content: &str) -> Result<SkillSpec> {
                     Ok(meta) => metadata = meta,
                     Err(e) => eprintln!("Failed to parse frontmatter: {e}\nYAML:\n{yaml}"),
                 }
+                if let Ok(fields) = serde_yaml::from_str::<CompositionFrontmatter>(&yaml) {
+                    composition = fields;
+                }
                 continue;
             }
             frontmatter_lines.push(line.to_string());
@@ -97,6 +157,7 @@ pub fn parse_markdown(content: &str) -> Result<SkillSpec> {
         // never reach the code accumulator below — truncating/emptying the
         // fenced block and mis-splitting sections in the normalized SKILL.md
         // that `ms load --full` and the MCP `load` server serve (issue #134).
+        let in_code_block = fence.is_some();
         if let Some(title) = line.strip_prefix("# ").filter(|_| !in_code_block) {
             name = title.trim().to_string();
             // The first paragraph may be either the canonical description
@@ -110,20 +171,29 @@ pub fn parse_markdown(content: &str) -> Result<SkillSpec> {
 
         if let Some(title) = line.strip_prefix("## ").filter(|_| !in_code_block) {
             if let Some(section) = current_section.as_mut() {
-                flush_paragraph(section, &mut paragraph_lines);
+                flush_paragraph(section, &mut paragraph_lines, &mut pending_anchor);
             }
             if let Some(section) = current_section.take() {
                 sections.push(section);
             }
+            pending_anchor = None;
+            let (title, explicit_id) = split_heading_anchor(title);
             current_section = Some(SkillSection {
-                id: slugify(title),
-                title: title.trim().to_string(),
+                id: explicit_id.unwrap_or_else(|| slugify(&title)),
+                title,
                 blocks: Vec::new(),
             });
             in_description = false;
             continue;
         }
 
+        if is_origin_marker(line) && !in_code_block {
+            if let Some(section) = current_section.as_mut() {
+                flush_paragraph(section, &mut paragraph_lines, &mut pending_anchor);
+            }
+            continue;
+        }
+
         if in_description {
             if line.trim().is_empty() {
                 if !description_lines.is_empty() {
@@ -147,45 +217,55 @@ pub fn parse_markdown(content: &str) -> Result<SkillSpec> {
             continue;
         };
 
-        if line.trim_start().starts_with("```") {
-            if in_code_block {
-                code_lines.push(line.to_string());
+        if let Some(anchor) = parse_anchor_marker(line).filter(|_| !in_code_block) {
+            flush_paragraph(section, &mut paragraph_lines, &mut pending_anchor);
+            pending_anchor = Some(anchor);
+            continue;
+        }
+
+        // Fenced lines are kept byte-for-byte. Only a bare run of at least
+        // as many of the opening marker closes the fence, so a ```` fence
+        // can show ``` examples and a ~~~ fence can hold backtick lines.
+        if let Some(open) = fence {
+            code_lines.push(line.to_string());
+            if closes_fence(line, open) {
                 let content = code_lines.join("\n");
                 code_lines.clear();
-                in_code_block = false;
-                flush_paragraph(section, &mut paragraph_lines);
+                fence = None;
                 section.blocks.push(SkillBlock {
-                    id: format!("{}-block-{}", section.id, section.blocks.len() + 1),
+                    id: pending_anchor
+                        .take()
+                        .unwrap_or_else(|| positional_block_id(&section.id, section.blocks.len())),
                     block_type: BlockType::Code,
                     content,
                 });
-            } else {
-                flush_paragraph(section, &mut paragraph_lines);
-                in_code_block = true;
-                code_lines.push(line.to_string());
             }
             continue;
         }
 
-        if in_code_block {
+        if let Some(open) = fence_marker(line) {
+            flush_paragraph(section, &mut paragraph_lines, &mut pending_anchor);
+            fence = Some(open);
             code_lines.push(line.to_string());
             continue;
         }
 
         if line.trim().is_empty() {
-            flush_paragraph(section, &mut paragraph_lines);
+            flush_paragraph(section, &mut paragraph_lines, &mut pending_anchor);
         } else {
             paragraph_lines.push(line.trim_end().to_string());
         }
     }
 
     if let Some(section) = current_section.as_mut() {
-        flush_paragraph(section, &mut paragraph_lines);
+        flush_paragraph(section, &mut paragraph_lines, &mut pending_anchor);
     }
-    if in_code_block && !code_lines.is_empty() {
+    if fence.is_some() && !code_lines.is_empty() {
         if let Some(section) = current_section.as_mut() {
             section.blocks.push(SkillBlock {
-                id: format!("{}-block-{}", section.id, section.blocks.len() + 1),
+                id: pending_anchor
+                    .take()
+                    .unwrap_or_else(|| positional_block_id(&section.id, section.blocks.len())),
                 block_type: BlockType::Code,
                 content: code_lines.join("\n"),
             });
@@ -247,8 +327,13 @@ pub fn parse_markdown(content: &str) -> Result<SkillSpec> {
                 content: extracted_description,
             },
         );
+        // Shift positional ids; explicit anchors keep their id.
         for (index, block) in preamble.blocks.iter_mut().enumerate() {
-            block.id = format!("{PREAMBLE_SECTION_ID}-block-{}", index + 1);
+            if block.id.is_empty()
+                || (index > 0 && block.id == positional_block_id(PREAMBLE_SECTION_ID, index - 1))
+            {
+                block.id = positional_block_id(PREAMBLE_SECTION_ID, index);
+            }
         }
     }
 
@@ -267,26 +352,58 @@ pub fn parse_markdown(content: &str) -> Result<SkillSpec> {
         format_version: SkillSpec::FORMAT_VERSION.to_string(),
         metadata,
         sections,
-        // Inheritance fields - not parsed from markdown (use YAML frontmatter)
-        extends: None,
-        replace_rules: false,
-        replace_examples: false,
-        replace_pitfalls: false,
-        replace_checklist: false,
-        // Composition fields - not parsed from markdown (use YAML frontmatter)
-        includes: Vec::new(),
+        // Inheritance and composition come from the frontmatter
+        extends: composition.extends,
+        replace_rules: composition.replace_rules,
+        replace_examples: composition.replace_examples,
+        replace_pitfalls: composition.replace_pitfalls,
+        replace_checklist: composition.replace_checklist,
+        includes: composition.includes,
     })
 }
 
 /// Compile a `SkillSpec` back to markdown.
 #[must_use]
 pub fn compile_markdown(spec: &SkillSpec) -> String {
+    compile(spec, None)
+}
+
+/// [`compile_markdown`], preceding section headings and blocks with an
+/// `<!-- ms:origin ... -->` comment. `origin` is called with the section id
+/// and, for blocks, the block id; `None` writes no comment. The parser skips
+/// these comments, so the output parses to the same spec.
+#[must_use]
+pub fn compile_markdown_with_origins(
+    spec: &SkillSpec,
+    origin: impl Fn(&str, Option<&str>) -> Option<String>,
+) -> String {
+    compile(spec, Some(&origin))
+}
+
+type OriginFn<'a> = &'a dyn Fn(&str, Option<&str>) -> Option<String>;
+
+fn compile(spec: &SkillSpec, origin: Option<OriginFn<'_>>) -> String {
     let mut output = String::new();
+    let annotate = |output: &mut String, section_id: &str, block_id: Option<&str>| {
+        if let Some(note) = origin.and_then(|origin| origin(section_id, block_id)) {
+            output.push_str(&format!(
+                "{ORIGIN_MARKER_PREFIX}{} {ANCHOR_MARKER_SUFFIX}\n",
+                note.replace("--", "-")
+            ));
+        }
+    };
 
     // Serialize metadata to YAML frontmatter
     if let Ok(yaml) = serde_yaml::to_string(&spec.metadata) {
         output.push_str("---\n");
         output.push_str(yaml.trim());
+        let composition = CompositionFrontmatter::of(spec);
+        if !composition.is_empty()
+            && let Ok(yaml) = serde_yaml::to_string(&composition)
+        {
+            output.push('\n');
+            output.push_str(yaml.trim());
+        }
         output.push_str("\n---\n\n");
     }
 
@@ -299,12 +416,24 @@ pub fn compile_markdown(spec: &SkillSpec) -> String {
 
     for section in &spec.sections {
         if section.id != PREAMBLE_SECTION_ID || !section.title.is_empty() {
-            output.push_str(&format!("## {}\n\n", section.title));
+            annotate(&mut output, &section.id, None);
+            if section.id == slugify(&section.title) {
+                output.push_str(&format!("## {}\n\n", section.title));
+            } else {
+                output.push_str(&format!("## {} {{#{}}}\n\n", section.title, section.id));
+            }
         }
-        for block in &section.blocks {
+        for (index, block) in section.blocks.iter().enumerate() {
+            annotate(&mut output, &section.id, Some(&block.id));
+            if !block.id.is_empty() && block.id != positional_block_id(&section.id, index) {
+                output.push_str(&format!(
+                    "{ANCHOR_MARKER_PREFIX}{} {ANCHOR_MARKER_SUFFIX}\n",
+                    block.id
+                ));
+            }
             if block.block_type == BlockType::Code {
                 let content = block.content.trim_end();
-                if content.starts_with("```") {
+                if fence_marker(content).is_some() {
                     output.push_str(content);
                     output.push_str("\n\n");
                 } else {
@@ -322,7 +451,94 @@ pub fn compile_markdown(spec: &SkillSpec) -> String {
     output.trim_end().to_string() + "\n"
 }
 
-fn slugify(input: &str) -> String {
+/// Canonical formatting used by `ms fmt`: parse, then compile.
+///
+/// Unlike [`parse_markdown`], which warns and carries on, invalid or
+/// unterminated frontmatter is an error here so formatting never drops
+/// metadata.
+pub fn format_markdown(content: &str) -> Result<String> {
+    check_frontmatter(content)?;
+    Ok(compile_markdown(&parse_markdown(content)?))
+}
+
+fn check_frontmatter(content: &str) -> Result<()> {
+    let mut lines = content.lines();
+    if lines.next().map(str::trim) != Some("---") {
+        return Ok(());
+    }
+    let mut yaml = Vec::new();
+    for line in lines {
+        if line.trim() == "---" {
+            return serde_yaml::from_str::<SkillMetadata>(&yaml.join("\n"))
+                .map(|_| ())
+                .map_err(|err| MsError::ValidationFailed(format!("invalid frontmatter: {err}")));
+        }
+        yaml.push(line);
+    }
+    Err(MsError::ValidationFailed(
+        "unterminated frontmatter (missing closing `---`)".to_string(),
+    ))
+}
+
+/// Opening code fence: three or more backticks or tildes, after optional
+/// indentation. Returns the marker character and run length.
+fn fence_marker(line: &str) -> Option<(char, usize)> {
+    let trimmed = line.trim_start();
+    let marker = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let len = trimmed.chars().take_while(|c| *c == marker).count();
+    (len >= 3).then_some((marker, len))
+}
+
+fn closes_fence(line: &str, (marker, len): (char, usize)) -> bool {
+    let trimmed = line.trim();
+    trimmed.chars().count() >= len && trimmed.chars().all(|c| c == marker)
+}
+
+/// Id given to the `index`-th (zero-based) block of a section without an explicit anchor.
+#[must_use]
+pub fn positional_block_id(section_id: &str, index: usize) -> String {
+    format!("{section_id}-block-{}", index + 1)
+}
+
+/// Whether `id` is usable as an explicit anchor (`[A-Za-z0-9_.-]+`).
+#[must_use]
+pub fn is_valid_anchor_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.'))
+}
+
+/// Whether `line` is an `<!-- ms:origin ... -->` comment.
+fn is_origin_marker(line: &str) -> bool {
+    let line = line.trim();
+    line.starts_with(ORIGIN_MARKER_PREFIX) && line.ends_with(ANCHOR_MARKER_SUFFIX)
+}
+
+/// Parse a `<!-- ms:anchor id -->` marker line.
+fn parse_anchor_marker(line: &str) -> Option<String> {
+    let id = line
+        .trim()
+        .strip_prefix(ANCHOR_MARKER_PREFIX)?
+        .strip_suffix(ANCHOR_MARKER_SUFFIX)?
+        .trim();
+    is_valid_anchor_id(id).then(|| id.to_string())
+}
+
+/// Split `Title {#custom-id}` into its title and explicit section id.
+fn split_heading_anchor(raw: &str) -> (String, Option<String>) {
+    let trimmed = raw.trim();
+    if let Some(rest) = trimmed.strip_suffix('}') {
+        if let Some((title, id)) = rest.rsplit_once(" {#") {
+            if is_valid_anchor_id(id) {
+                return (title.trim_end().to_string(), Some(id.to_string()));
+            }
+        }
+    }
+    (trimmed.to_string(), None)
+}
+
+pub(crate) fn slugify(input: &str) -> String {
     let lowered = input.trim().to_lowercase();
     let mut out = String::with_capacity(lowered.len());
     let mut last_was_dash = false;
@@ -358,7 +574,105 @@ fn json_equivalent(left: &JsonValue, right: &JsonValue) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use super::{BlockType, PREAMBLE_SECTION_ID, compile_markdown, parse_markdown};
+    use super::{
+        BlockType, PREAMBLE_SECTION_ID, compile_markdown, compile_markdown_with_origins,
+        parse_markdown,
+    };
+
+    #[test]
+    fn composition_frontmatter_roundtrips() {
+        let md = "---\nid: rust-errors\nname: Rust Errors\nextends: errors-base\nreplace_rules: true\nincludes:\n  - skill: logging\n    into: pitfalls\n---\n\n# Rust Errors\n\n## Rules\n\nUse thiserror.\n";
+        let spec = parse_markdown(md).expect("parse");
+        assert_eq!(spec.extends.as_deref(), Some("errors-base"));
+        assert!(spec.replace_rules && !spec.replace_examples);
+        assert_eq!(spec.includes[0].skill, "logging");
+
+        let compiled = compile_markdown(&spec);
+        assert!(compiled.contains("extends: errors-base"));
+        let reparsed = parse_markdown(&compiled).expect("reparse");
+        assert_eq!(
+            serde_json::to_value(&reparsed).unwrap(),
+            serde_json::to_value(&spec).unwrap()
+        );
+        // Skills without composition keep the frontmatter they had
+        assert!(
+            !compile_markdown(&parse_markdown("# Plain\n\nBody.\n").unwrap()).contains("extends")
+        );
+    }
+
+    #[test]
+    fn origin_comments_leave_the_spec_unchanged() {
+        let md = "# Errors\n\n## Rules {#rules}\n\n<!-- ms:anchor no-unwrap -->\nNever unwrap.\n\n```rust\n<!-- ms:origin kept in code -->\n```\n\n## Pitfalls\n\nPanics.\n";
+        let spec = parse_markdown(md).expect("parse");
+        let annotated = compile_markdown_with_origins(&spec, |section, block| {
+            Some(match block {
+                Some(block) => format!("{section}/{block}"),
+                None => format!("{section} -- section"),
+            })
+        });
+        assert!(annotated.contains("<!-- ms:origin rules -->\n## Rules {#rules}"));
+        assert!(
+            annotated.contains("<!-- ms:origin rules/no-unwrap -->\n<!-- ms:anchor no-unwrap -->")
+        );
+        assert!(annotated.contains("<!-- ms:origin pitfalls - section -->"));
+        // Markers are skipped, except inside code where they are content
+        let reparsed = parse_markdown(&annotated).expect("reparse");
+        assert_eq!(
+            serde_json::to_value(&reparsed).unwrap(),
+            serde_json::to_value(&spec).unwrap()
+        );
+        assert!(
+            reparsed.sections[0].blocks[1]
+                .content
+                .contains("kept in code")
+        );
+    }
+
+    #[test]
+    fn explicit_anchors_survive_roundtrip_and_edits() {
+        let md = "# Errors\n\nHandle errors.\n\n## Rules {#rules}\n\n<!-- ms:anchor rule-no-unwrap-in-handlers -->\nNever unwrap in handlers.\n\nPrefer `?`.\n\n<!-- ms:anchor example-question-mark -->\n```rust\nlet x = f()?;\n```\n";
+        let parsed = parse_markdown(md).expect("parse");
+        let section = &parsed.sections[0];
+        assert_eq!(section.id, "rules");
+        assert_eq!(section.title, "Rules");
+        let ids: Vec<&str> = section.blocks.iter().map(|b| b.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "rule-no-unwrap-in-handlers",
+                "rules-block-2",
+                "example-question-mark"
+            ]
+        );
+
+        // Compiling keeps the markers, so ids are stable across re-parses.
+        let compiled = compile_markdown(&parsed);
+        assert!(compiled.contains("<!-- ms:anchor rule-no-unwrap-in-handlers -->"));
+        assert!(!compiled.contains("ms:anchor rules-block-2"));
+        assert_eq!(parse_markdown(&compiled).unwrap().sections, parsed.sections);
+
+        // Inserting a block before an anchored one does not move its anchor.
+        let edited = md.replace(
+            "## Rules {#rules}\n\n",
+            "## Rules {#rules}\n\nNew intro.\n\n",
+        );
+        let reparsed = parse_markdown(&edited).unwrap();
+        assert_eq!(
+            reparsed.sections[0].blocks[1].id,
+            "rule-no-unwrap-in-handlers"
+        );
+    }
+
+    #[test]
+    fn renamed_section_keeps_explicit_id() {
+        let parsed = parse_markdown("# S\n\nD.\n\n## Error Rules {#rules}\n\nBody.\n").unwrap();
+        assert_eq!(parsed.sections[0].id, "rules");
+        assert!(compile_markdown(&parsed).contains("## Error Rules {#rules}"));
+
+        // Invalid ids are left as part of the title.
+        let literal = parse_markdown("# S\n\nD.\n\n## Sets {#a b}\n\nBody.\n").unwrap();
+        assert_eq!(literal.sections[0].title, "Sets {#a b}");
+    }
 
     #[test]
     fn fenced_hash_comments_are_not_parsed_as_headings() {
@@ -424,6 +738,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn format_preserves_fenced_code_byte_for_byte() {
+        let yaml = "```yaml\nsteps:\n  - name: build\n    run: |\n      cargo build\n\n\n  - name: test   \n```";
+        let python =
+            "    ```python\ndef f(x):\n\tif x:\n        return 1  \n## not a heading\n    ```";
+        let nested = "````markdown\n```bash\necho hi\n```\n````";
+        let tilde = "~~~\n```\n# still code\n~~~";
+        let md = format!(
+            "# Fences\n\nCode examples.\n\n## Examples\n\n{yaml}\n\n{python}\n\n{nested}\n\n{tilde}\n"
+        );
+
+        let once = format_markdown(&md).unwrap();
+        for block in [yaml, python, nested, tilde] {
+            assert!(
+                once.contains(block),
+                "lost fenced block {block:?} in:\n{once}"
+            );
+        }
+        let spec = parse_markdown(&once).unwrap();
+        assert_eq!(spec.sections.len(), 1);
+        assert_eq!(spec.sections[0].blocks.len(), 4);
+        assert_eq!(format_markdown(&once).unwrap(), once);
+    }
+
+    #[test]
+    fn format_rejects_broken_frontmatter() {
+        assert!(format_markdown("---\nid: [unclosed\n---\n\n# S\n").is_err());
+        assert!(format_markdown("---\nid: s\n\n# S\n").is_err());
+    }
+
     #[test]
     fn roundtrip_simple_markdown() {
         let md = "# Sample Skill\n\nA short description.\n\n## Usage\n\nDo the thing.\n\n```bash\nls -la\n```\n";
@@ -0,0 +1,349 @@
+//! Structured, non-interactive edits of one skill.
+//!
+//! `ms edit <skill> --add-rule ... --remove-block ...` turns its flags into
+//! [`EditOperation`]s, applied in command-line order to a copy of the parsed
+//! spec. Any failing operation rejects the whole edit, so the caller either
+//! writes every change or none of them.
+
+use serde::Serialize;
+
+use crate::error::{MsError, Result};
+
+use super::bulk_edit::{BulkOperation, apply_operations};
+use super::skill::{BlockType, SkillBlock, SkillSection, SkillSpec};
+use super::spec_lens::{positional_block_id, slugify};
+
+/// Section `--add-rule` targets when none is given.
+pub const DEFAULT_RULE_SECTION: &str = "rules";
+
+/// Section `--append-example` targets.
+pub const EXAMPLE_SECTION: &str = "examples";
+
+/// One structured edit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum EditOperation {
+    /// Append a rule block to a section, creating the section if needed.
+    AddRule { section: String, text: String },
+    /// Remove a block by id.
+    RemoveBlock { block_id: String },
+    /// Set a metadata field, with the same fields as `ms edit --bulk --set`.
+    SetMeta { field: String, value: String },
+    /// Retitle a section; its id stays the same.
+    RenameSection { section: String, title: String },
+    /// Append a fenced code example to the examples section.
+    AppendExample { lang: String, code: String },
+}
+
+impl EditOperation {
+    /// Parse `--set-meta key=value`.
+    pub fn parse_set_meta(expr: &str) -> Result<Self> {
+        let BulkOperation::Set { field, value } = BulkOperation::parse_set(expr)? else {
+            unreachable!("parse_set only returns Set");
+        };
+        Ok(Self::SetMeta { field, value })
+    }
+
+    /// Parse `--rename-section old=new`.
+    pub fn parse_rename(expr: &str) -> Result<Self> {
+        let (section, title) = expr
+            .split_once('=')
+            .map(|(old, new)| (old.trim(), new.trim()))
+            .filter(|(old, new)| !old.is_empty() && !new.is_empty())
+            .ok_or_else(|| {
+                MsError::ValidationFailed(format!(
+                    "invalid --rename-section '{expr}': expected OLD=NEW"
+                ))
+            })?;
+        Ok(Self::RenameSection {
+            section: section.to_string(),
+            title: title.to_string(),
+        })
+    }
+
+    /// Short description, used in commit messages and errors.
+    #[must_use]
+    pub fn describe(&self) -> String {
+        match self {
+            Self::AddRule { section, .. } => format!("add rule to {section}"),
+            Self::RemoveBlock { block_id } => format!("remove block {block_id}"),
+            Self::SetMeta { field, .. } => {
+                format!(
+                    "set metadata.{}",
+                    field.strip_prefix("metadata.").unwrap_or(field)
+                )
+            }
+            Self::RenameSection { section, title } => {
+                format!("rename section {section} to {title}")
+            }
+            Self::AppendExample { lang, .. } => format!("append {lang} example"),
+        }
+    }
+}
+
+/// What one operation touched, for callers chaining further edits.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EditChange {
+    pub operation: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub section_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+}
+
+/// Apply `operations` in order to a copy of `spec`. The first failing
+/// operation fails the whole edit and `spec` is left as it was.
+pub fn apply_edits(
+    spec: &SkillSpec,
+    operations: &[EditOperation],
+) -> Result<(SkillSpec, Vec<EditChange>)> {
+    let mut edited = spec.clone();
+    let mut changes = Vec::with_capacity(operations.len());
+    for (index, op) in operations.iter().enumerate() {
+        let change = apply_one(&mut edited, op).map_err(|err| {
+            MsError::ValidationFailed(format!(
+                "operation {} ({}): {err}",
+                index + 1,
+                op.describe()
+            ))
+        })?;
+        changes.push(change);
+    }
+    Ok((edited, changes))
+}
+
+fn apply_one(spec: &mut SkillSpec, op: &EditOperation) -> Result<EditChange> {
+    let mut change = EditChange {
+        operation: op.describe(),
+        section_id: None,
+        block_id: None,
+        field: None,
+    };
+    match op {
+        EditOperation::AddRule { section, text } => {
+            let text = text.trim();
+            if text.is_empty() {
+                return Err(MsError::ValidationFailed(
+                    "rule text must not be empty".to_string(),
+                ));
+            }
+            let (section_id, block_id) = append_block(spec, section, BlockType::Rule, text);
+            change.section_id = Some(section_id);
+            change.block_id = Some(block_id);
+        }
+        EditOperation::RemoveBlock { block_id } => {
+            let section = spec
+                .sections
+                .iter_mut()
+                .find(|section| section.blocks.iter().any(|block| block.id == *block_id))
+                .ok_or_else(|| MsError::NotFound(format!("no block with id {block_id}")))?;
+            section.blocks.retain(|block| block.id != *block_id);
+            change.section_id = Some(section.id.clone());
+            change.block_id = Some(block_id.clone());
+        }
+        EditOperation::SetMeta { field, value } => {
+            let set = BulkOperation::Set {
+                field: field.clone(),
+                value: value.clone(),
+            };
+            set.check()?;
+            apply_operations(spec, &[set]);
+            change.field = Some(format!(
+                "metadata.{}",
+                field.strip_prefix("metadata.").unwrap_or(field)
+            ));
+        }
+        EditOperation::RenameSection { section, title } => {
+            let index = find_section(spec, section)
+                .ok_or_else(|| MsError::NotFound(format!("no section {section}")))?;
+            spec.sections[index].title.clone_from(title);
+            change.section_id = Some(spec.sections[index].id.clone());
+        }
+        EditOperation::AppendExample { lang, code } => {
+            let code = code.trim_end();
+            if code.trim().is_empty() {
+                return Err(MsError::ValidationFailed(
+                    "example file is empty".to_string(),
+                ));
+            }
+            let content = fenced(lang, code);
+            let (section_id, block_id) =
+                append_block(spec, EXAMPLE_SECTION, BlockType::Code, &content);
+            change.section_id = Some(section_id);
+            change.block_id = Some(block_id);
+        }
+    }
+    Ok(change)
+}
+
+/// Section matching `key` by id, slugified title, or title.
+fn find_section(spec: &SkillSpec, key: &str) -> Option<usize> {
+    let slug = slugify(key);
+    spec.sections.iter().position(|section| {
+        section.id == key || section.id == slug || section.title.eq_ignore_ascii_case(key.trim())
+    })
+}
+
+/// Append a block to the section named `key`, creating it at the end if it
+/// does not exist. Returns the section and block ids.
+fn append_block(
+    spec: &mut SkillSpec,
+    key: &str,
+    block_type: BlockType,
+    content: &str,
+) -> (String, String) {
+    let index = find_section(spec, key).unwrap_or_else(|| {
+        spec.sections.push(SkillSection {
+            id: slugify(key),
+            title: title_case(key.trim()),
+            blocks: Vec::new(),
+        });
+        spec.sections.len() - 1
+    });
+    let block_id = new_block_id(spec, index);
+    let section = &mut spec.sections[index];
+    section.blocks.push(SkillBlock {
+        id: block_id.clone(),
+        block_type,
+        content: content.to_string(),
+    });
+    (section.id.clone(), block_id)
+}
+
+/// The positional id of a block appended to section `index`, unless another
+/// block already uses it (after a removal); then the next free one, which
+/// compiles to an explicit anchor.
+fn new_block_id(spec: &SkillSpec, index: usize) -> String {
+    let section = &spec.sections[index];
+    let taken = |id: &str| {
+        spec.sections
+            .iter()
+            .any(|section| section.blocks.iter().any(|block| block.id == id))
+    };
+    (section.blocks.len()..)
+        .map(|n| positional_block_id(&section.id, n))
+        .find(|id| !taken(id))
+        .unwrap_or_default()
+}
+
+fn title_case(key: &str) -> String {
+    let mut chars = key.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(chars).collect::<String>()
+    })
+}
+
+/// `code` in a fence longer than any backtick run inside it.
+#[must_use]
+pub fn fenced(lang: &str, code: &str) -> String {
+    let fence = "`".repeat(longest_backtick_run(code).max(2) + 1);
+    format!("{fence}{lang}\n{code}\n{fence}")
+}
+
+fn longest_backtick_run(code: &str) -> usize {
+    code.split(|ch| ch != '`').map(str::len).max().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::spec_lens::{compile_markdown, parse_markdown};
+
+    const SKILL: &str = "---\nid: rust-style\nname: Rust Style\n---\n\n# Rust Style\n\nHow we write Rust.\n\n\
+                         ## Rules\n\nUse rustfmt.\n\nNo unwrap in handlers.\n\n## Pitfalls\n\nShadowing in loops.\n";
+
+    fn spec() -> SkillSpec {
+        parse_markdown(SKILL).unwrap()
+    }
+
+    #[test]
+    fn operations_apply_in_order_and_ids_survive_roundtrip() {
+        let ops = vec![
+            EditOperation::RemoveBlock {
+                block_id: "rules-block-1".to_string(),
+            },
+            EditOperation::AddRule {
+                section: DEFAULT_RULE_SECTION.to_string(),
+                text: "Prefer `?` over match.".to_string(),
+            },
+            EditOperation::AddRule {
+                section: "pitfalls".to_string(),
+                text: "Holding locks across await.".to_string(),
+            },
+            EditOperation::parse_rename("pitfalls=Gotchas").unwrap(),
+            EditOperation::parse_set_meta("author=platform").unwrap(),
+            EditOperation::AppendExample {
+                lang: "rust".to_string(),
+                code: "let x = f()?;\n".to_string(),
+            },
+        ];
+        let (edited, changes) = apply_edits(&spec(), &ops).unwrap();
+
+        let ids: Vec<Option<&str>> = changes.iter().map(|c| c.block_id.as_deref()).collect();
+        assert_eq!(
+            ids,
+            [
+                Some("rules-block-1"),
+                Some("rules-block-3"),
+                Some("pitfalls-block-2"),
+                None,
+                None,
+                Some("examples-block-1"),
+            ]
+        );
+        assert_eq!(changes[3].section_id.as_deref(), Some("pitfalls"));
+        assert_eq!(changes[4].field.as_deref(), Some("metadata.author"));
+        assert_eq!(edited.metadata.author.as_deref(), Some("platform"));
+
+        let reparsed = parse_markdown(&compile_markdown(&edited)).unwrap();
+        let gotchas = reparsed
+            .sections
+            .iter()
+            .find(|s| s.id == "pitfalls")
+            .unwrap();
+        assert_eq!(gotchas.title, "Gotchas");
+        let rules = &reparsed.sections[0];
+        let rule_ids: Vec<&str> = rules.blocks.iter().map(|b| b.id.as_str()).collect();
+        assert_eq!(rule_ids, ["rules-block-2", "rules-block-3"]);
+        let example = &reparsed.sections.last().unwrap().blocks[0];
+        assert_eq!(example.id, "examples-block-1");
+        assert_eq!(example.content, "```rust\nlet x = f()?;\n```");
+    }
+
+    #[test]
+    fn failing_operation_rejects_the_whole_edit() {
+        let original = spec();
+        let before = compile_markdown(&original);
+        let ops = vec![
+            EditOperation::AddRule {
+                section: DEFAULT_RULE_SECTION.to_string(),
+                text: "Kept only if everything applies.".to_string(),
+            },
+            EditOperation::RemoveBlock {
+                block_id: "nope".to_string(),
+            },
+        ];
+        let err = apply_edits(&original, &ops).unwrap_err().to_string();
+        assert!(err.contains("operation 2 (remove block nope)"), "{err}");
+        assert_eq!(compile_markdown(&original), before);
+    }
+
+    #[test]
+    fn malformed_flags_are_rejected() {
+        assert!(EditOperation::parse_rename("rules").is_err());
+        assert!(EditOperation::parse_rename("rules=").is_err());
+        assert!(EditOperation::parse_set_meta("body=x").is_err());
+        assert!(
+            apply_edits(
+                &spec(),
+                &[EditOperation::AddRule {
+                    section: "rules".to_string(),
+                    text: "  ".to_string(),
+                }]
+            )
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,291 @@
+//! Token counting for disclosure, packing and the `token-budget` lint.
+//!
+//! Every size ms reports goes through a [`TokenCounter`]. The default
+//! [`HeuristicCounter`] is the historical 4-bytes-per-token estimate; it is
+//! fast but undercounts code-heavy skills by a third or more. Builds with the
+//! `bpe` cargo feature can count with the cl100k or o200k BPE vocabularies
+//! instead, selected by `[packing].tokenizer`.
+
+use std::str::FromStr;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::{MsError, Result};
+use crate::storage::Database;
+
+/// Counts the tokens a piece of text costs in an agent's context.
+pub trait TokenCounter {
+    /// Name reported next to the numbers, e.g. `heuristic` or `cl100k`.
+    fn name(&self) -> &'static str;
+
+    /// Tokens in `text`.
+    fn count(&self, text: &str) -> usize;
+}
+
+/// Tokenizer selected by `[packing].tokenizer`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenizerKind {
+    /// ~4 bytes per token; no vocabulary needed
+    #[default]
+    Heuristic,
+    /// BPE vocabulary of GPT-4 era models
+    Cl100k,
+    /// BPE vocabulary of GPT-4o era models
+    O200k,
+}
+
+impl TokenizerKind {
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Heuristic => "heuristic",
+            Self::Cl100k => "cl100k",
+            Self::O200k => "o200k",
+        }
+    }
+}
+
+impl FromStr for TokenizerKind {
+    type Err = MsError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "heuristic" => Ok(Self::Heuristic),
+            "cl100k" | "cl100k_base" => Ok(Self::Cl100k),
+            "o200k" | "o200k_base" => Ok(Self::O200k),
+            _ => Err(MsError::Config(format!(
+                "unknown tokenizer {s} (expected heuristic|cl100k|o200k)"
+            ))),
+        }
+    }
+}
+
+/// The counter for `kind`. BPE kinds fail unless ms was built with the `bpe`
+/// feature.
+pub fn token_counter(kind: TokenizerKind) -> Result<Arc<dyn TokenCounter + Send + Sync>> {
+    match kind {
+        TokenizerKind::Heuristic => Ok(Arc::new(HeuristicCounter)),
+        #[cfg(feature = "bpe")]
+        TokenizerKind::Cl100k | TokenizerKind::O200k => Ok(Arc::new(BpeCounter::new(kind)?)),
+        #[cfg(not(feature = "bpe"))]
+        TokenizerKind::Cl100k | TokenizerKind::O200k => Err(MsError::Config(format!(
+            "tokenizer {} needs ms built with the `bpe` feature",
+            kind.name()
+        ))),
+    }
+}
+
+/// ~4 bytes per token.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HeuristicCounter;
+
+impl TokenCounter for HeuristicCounter {
+    fn name(&self) -> &'static str {
+        TokenizerKind::Heuristic.name()
+    }
+
+    fn count(&self, text: &str) -> usize {
+        text.len() / 4
+    }
+}
+
+/// Exact counts from a tiktoken BPE vocabulary.
+#[cfg(feature = "bpe")]
+pub struct BpeCounter {
+    kind: TokenizerKind,
+    bpe: &'static tiktoken_rs::CoreBPE,
+}
+
+#[cfg(feature = "bpe")]
+impl BpeCounter {
+    /// Load the vocabulary for `kind`; each one is built once per process.
+    pub fn new(kind: TokenizerKind) -> Result<Self> {
+        use std::sync::OnceLock;
+
+        static CL100K: OnceLock<tiktoken_rs::CoreBPE> = OnceLock::new();
+        static O200K: OnceLock<tiktoken_rs::CoreBPE> = OnceLock::new();
+
+        let (cell, build): (_, fn() -> anyhow::Result<tiktoken_rs::CoreBPE>) = match kind {
+            TokenizerKind::Cl100k => (&CL100K, tiktoken_rs::cl100k_base),
+            TokenizerKind::O200k => (&O200K, tiktoken_rs::o200k_base),
+            TokenizerKind::Heuristic => {
+                return Err(MsError::Config(
+                    "the heuristic tokenizer has no BPE vocabulary".to_string(),
+                ));
+            }
+        };
+        let bpe = match cell.get() {
+            Some(bpe) => bpe,
+            None => {
+                let loaded = build().map_err(|err| {
+                    MsError::Config(format!("load {} vocabulary: {err}", kind.name()))
+                })?;
+                cell.get_or_init(|| loaded)
+            }
+        };
+        Ok(Self { kind, bpe })
+    }
+}
+
+#[cfg(feature = "bpe")]
+impl TokenCounter for BpeCounter {
+    fn name(&self) -> &'static str {
+        self.kind.name()
+    }
+
+    fn count(&self, text: &str) -> usize {
+        self.bpe.encode_ordinary(text).len()
+    }
+}
+
+/// Wraps a counter with the `token_counts` table, keyed by tokenizer and the
+/// SHA-256 of the text, so unchanged blocks are not re-tokenized on every
+/// load. Cache failures fall back to counting.
+pub struct CachedCounter<'a> {
+    inner: Arc<dyn TokenCounter + Send + Sync>,
+    db: &'a Database,
+}
+
+impl<'a> CachedCounter<'a> {
+    #[must_use]
+    pub fn new(inner: Arc<dyn TokenCounter + Send + Sync>, db: &'a Database) -> Self {
+        Self { inner, db }
+    }
+}
+
+impl TokenCounter for CachedCounter<'_> {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn count(&self, text: &str) -> usize {
+        let hash = hex::encode(Sha256::digest(text.as_bytes()));
+        let name = self.inner.name();
+        if let Ok(Some(tokens)) = self.db.cached_token_count(name, &hash) {
+            return tokens;
+        }
+        let tokens = self.inner.count(text);
+        if let Err(err) = self.db.cache_token_count(name, &hash, tokens) {
+            tracing::debug!(target: "tokenizer", error = %err, "token count not cached");
+        }
+        tokens
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Code-heavy skill content: short identifiers, punctuation and
+    /// indentation, which BPE splits far finer than prose.
+    const CODE_FIXTURE: &str = r#"```rust
+fn parse(args: &[String]) -> Result<Config, Error> {
+    let mut cfg = Config::default();
+    for (i, a) in args.iter().enumerate() {
+        match a.as_str() {
+            "-v" | "--verbose" => cfg.verbose += 1,
+            "-o" => cfg.out = args.get(i + 1).cloned().ok_or(Error::Missing("-o"))?,
+            _ if a.starts_with("--") => return Err(Error::Unknown(a.clone())),
+            _ => cfg.inputs.push(a.into()),
+        }
+    }
+    Ok(cfg)
+}
+```
+
+```bash
+cargo build --release && ./target/release/app -vv -o out.json src/{a,b}.rs
+jq '.items[] | select(.ok == false) | {id, err: .error.msg}' out.json
+```
+"#;
+
+    #[test]
+    fn heuristic_counts_four_bytes_per_token() {
+        assert_eq!(HeuristicCounter.count(""), 0);
+        assert_eq!(HeuristicCounter.count("1234"), 1);
+        assert_eq!(HeuristicCounter.count("12345678"), 2);
+        assert_eq!(HeuristicCounter.name(), "heuristic");
+    }
+
+    #[test]
+    fn tokenizer_kinds_parse() {
+        assert_eq!(
+            "heuristic".parse::<TokenizerKind>().unwrap(),
+            TokenizerKind::Heuristic
+        );
+        assert_eq!(
+            "cl100k_base".parse::<TokenizerKind>().unwrap(),
+            TokenizerKind::Cl100k
+        );
+        assert_eq!(
+            "O200K".parse::<TokenizerKind>().unwrap(),
+            TokenizerKind::O200k
+        );
+        assert!("gpt2".parse::<TokenizerKind>().is_err());
+    }
+
+    #[cfg(not(feature = "bpe"))]
+    #[test]
+    fn bpe_kinds_need_the_feature() {
+        assert_eq!(
+            token_counter(TokenizerKind::Heuristic).unwrap().name(),
+            "heuristic"
+        );
+        let err = token_counter(TokenizerKind::Cl100k).err().unwrap();
+        assert!(err.to_string().contains("`bpe` feature"));
+    }
+
+    #[cfg(feature = "bpe")]
+    #[test]
+    fn bpe_counts_exceed_the_heuristic_on_code() {
+        let heuristic = HeuristicCounter.count(CODE_FIXTURE);
+        for kind in [TokenizerKind::Cl100k, TokenizerKind::O200k] {
+            let bpe = token_counter(kind).unwrap();
+            assert_eq!(bpe.name(), kind.name());
+            let exact = bpe.count(CODE_FIXTURE);
+            // The heuristic undercounts code by well over 10%; if these ever
+            // agree, the BPE path has silently fallen back to it.
+            assert!(
+                exact * 10 > heuristic * 11,
+                "{}: {exact} tokens vs heuristic {heuristic}",
+                kind.name()
+            );
+        }
+    }
+
+    #[cfg(feature = "bpe")]
+    #[test]
+    fn bpe_counts_are_exact_for_known_text() {
+        let cl100k = token_counter(TokenizerKind::Cl100k).unwrap();
+        assert_eq!(cl100k.count("hello world"), 2);
+        assert_eq!(cl100k.count(""), 0);
+    }
+
+    #[test]
+    fn cached_counts_are_reused() {
+        struct Counting(std::sync::atomic::AtomicUsize);
+        impl TokenCounter for Counting {
+            fn name(&self) -> &'static str {
+                "counting"
+            }
+            fn count(&self, text: &str) -> usize {
+                self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                text.len()
+            }
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(dir.path().join("ms.db")).unwrap();
+        let inner = Arc::new(Counting(std::sync::atomic::AtomicUsize::new(0)));
+        let cached = CachedCounter::new(inner.clone(), &db);
+
+        assert_eq!(cached.count(CODE_FIXTURE), CODE_FIXTURE.len());
+        assert_eq!(cached.count(CODE_FIXTURE), CODE_FIXTURE.len());
+        assert_eq!(cached.count("other"), 5);
+        assert_eq!(inner.0.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert_eq!(cached.name(), "counting");
+    }
+}
@@ -0,0 +1,573 @@
+//! Duplicate clusters, rule/example diffs and merged drafts.
+//!
+//! Pairs from [`DeduplicationEngine::scan_all`](super::DeduplicationEngine::scan_all)
+//! are joined transitively: if A matches B and B matches C, all three form
+//! one cluster. Members are ranked by quality score.
+//!
+//! Rules are list items and rule/pitfall blocks; examples are code blocks.
+//! Items are compared after lowercasing and collapsing whitespace. Items
+//! every member has count as shared; the rest are listed with the skills
+//! that contain them.
+//!
+//! A merged draft is the union of the members' rules and examples. Exact
+//! duplicates are collapsed. Close but different rules from different
+//! skills are kept side by side under a conflict comment, each tagged with
+//! the skill it came from.
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+use serde::Serialize;
+
+use crate::core::skill::{BlockType, SkillBlock, SkillSection, SkillSpec};
+use crate::core::spec_lens::{parse_markdown, positional_block_id};
+use crate::error::{MsError, Result};
+use crate::storage::sqlite::SkillRecord;
+
+use super::{DuplicatePair, word_overlap_similarity};
+
+/// Word overlap at which two different rules are treated as conflicting
+/// variants of one rule rather than independent rules.
+const CONFLICT_OVERLAP: f32 = 0.35;
+
+/// A skill in a duplicate cluster.
+#[derive(Debug, Clone, Serialize)]
+pub struct ClusterMember {
+    pub skill_id: String,
+    pub name: String,
+    pub layer: String,
+    pub quality_score: f64,
+}
+
+/// A rule or example that not every member of a cluster has.
+#[derive(Debug, Clone, Serialize)]
+pub struct ClusterItem {
+    pub text: String,
+    /// Members containing the item, in member order
+    pub skills: Vec<String>,
+}
+
+/// How the rules and examples of a cluster's members differ.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ClusterDiff {
+    pub shared_rules: usize,
+    pub shared_examples: usize,
+    pub differing_rules: Vec<ClusterItem>,
+    pub differing_examples: Vec<ClusterItem>,
+}
+
+/// Skills that are transitively near-duplicates of each other.
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicateCluster {
+    /// Members, highest quality first
+    pub members: Vec<ClusterMember>,
+    pub max_similarity: f32,
+    pub pairs: Vec<DuplicatePair>,
+    pub diff: ClusterDiff,
+}
+
+impl DuplicateCluster {
+    /// Ids of the members, highest quality first.
+    #[must_use]
+    pub fn skill_ids(&self) -> Vec<&str> {
+        self.members.iter().map(|m| m.skill_id.as_str()).collect()
+    }
+}
+
+/// A merged skill drafted from a cluster.
+#[derive(Debug, Clone)]
+pub struct MergeDraft {
+    pub spec: SkillSpec,
+    /// Ids of the merged skills, highest quality first
+    pub sources: Vec<String>,
+    /// Rules and examples dropped because another member had them verbatim
+    pub collapsed: usize,
+    /// Groups of conflicting rules flagged for review
+    pub conflicts: usize,
+}
+
+/// Rules and examples of one skill, in document order.
+#[derive(Debug, Default)]
+struct SkillItems {
+    rules: Vec<String>,
+    examples: Vec<String>,
+}
+
+/// Group `pairs` into clusters over `skills`. Pairs naming skills missing
+/// from `skills`, or sealed ones, are ignored.
+#[must_use]
+pub fn build_clusters(pairs: &[DuplicatePair], skills: &[SkillRecord]) -> Vec<DuplicateCluster> {
+    let by_id: HashMap<&str, &SkillRecord> = skills
+        .iter()
+        .filter(|s| !s.is_sealed())
+        .map(|s| (s.id.as_str(), s))
+        .collect();
+    let pairs: Vec<&DuplicatePair> = pairs
+        .iter()
+        .filter(|p| {
+            by_id.contains_key(p.skill_a_id.as_str()) && by_id.contains_key(p.skill_b_id.as_str())
+        })
+        .collect();
+
+    let mut parent: HashMap<&str, &str> = HashMap::new();
+    for pair in &pairs {
+        let a = find(&mut parent, &pair.skill_a_id);
+        let b = find(&mut parent, &pair.skill_b_id);
+        if a != b {
+            let (root, child) = if a < b { (a, b) } else { (b, a) };
+            parent.insert(child, root);
+        }
+    }
+
+    let mut groups: HashMap<&str, Vec<&DuplicatePair>> = HashMap::new();
+    for pair in pairs {
+        let root = find(&mut parent, &pair.skill_a_id);
+        groups.entry(root).or_default().push(pair);
+    }
+
+    let mut clusters: Vec<DuplicateCluster> = groups
+        .into_values()
+        .map(|pairs| {
+            let mut ids: Vec<&str> = pairs
+                .iter()
+                .flat_map(|p| [p.skill_a_id.as_str(), p.skill_b_id.as_str()])
+                .collect();
+            ids.sort_unstable();
+            ids.dedup();
+            let mut records: Vec<&SkillRecord> = ids.iter().map(|id| by_id[id]).collect();
+            records.sort_by(|a, b| rank(a, b));
+            let max_similarity = pairs.iter().map(|p| p.similarity).fold(0.0, f32::max);
+            DuplicateCluster {
+                members: records
+                    .iter()
+                    .map(|r| ClusterMember {
+                        skill_id: r.id.clone(),
+                        name: r.name.clone(),
+                        layer: r.source_layer.clone(),
+                        quality_score: r.quality_score,
+                    })
+                    .collect(),
+                max_similarity,
+                pairs: pairs.into_iter().cloned().collect(),
+                diff: diff_members(&records),
+            }
+        })
+        .collect();
+    clusters.sort_by(|a, b| {
+        b.max_similarity
+            .partial_cmp(&a.max_similarity)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| a.members[0].skill_id.cmp(&b.members[0].skill_id))
+    });
+    clusters
+}
+
+/// Draft a merged skill `merged_id` from the members of `cluster`.
+pub fn draft_merge(
+    cluster: &DuplicateCluster,
+    skills: &[SkillRecord],
+    merged_id: &str,
+) -> Result<MergeDraft> {
+    let mut specs = Vec::new();
+    for member in &cluster.members {
+        if let Some(record) = skills.iter().find(|s| s.id == member.skill_id) {
+            specs.push((record.id.clone(), parse_markdown(&record.body)?));
+        }
+    }
+    let Some((_, base)) = specs.first() else {
+        return Err(MsError::ValidationFailed(
+            "cluster has no readable members".to_string(),
+        ));
+    };
+
+    let mut spec = SkillSpec::new(merged_id, base.metadata.name.clone());
+    spec.metadata = base.metadata.clone();
+    spec.metadata.id = merged_id.to_string();
+    for (_, other) in specs.iter().skip(1) {
+        for tag in &other.metadata.tags {
+            if !spec.metadata.tags.contains(tag) {
+                spec.metadata.tags.push(tag.clone());
+            }
+        }
+    }
+
+    let mut rules: Vec<ClusterItem> = Vec::new();
+    let mut examples: Vec<ClusterItem> = Vec::new();
+    let mut collapsed = 0;
+    for (id, member) in &specs {
+        let items = extract_items(member);
+        collapsed += union_into(&mut rules, items.rules, id);
+        collapsed += union_into(&mut examples, items.examples, id);
+    }
+
+    let groups = conflict_groups(&rules);
+    let conflicts = groups.values().filter(|g| g.len() > 1).count();
+    let mut rule_lines = Vec::new();
+    let mut emitted: HashSet<usize> = HashSet::new();
+    for (idx, rule) in rules.iter().enumerate() {
+        if !emitted.insert(idx) {
+            continue;
+        }
+        let group = groups.values().find(|g| g.contains(&idx) && g.len() > 1);
+        match group {
+            Some(group) => {
+                rule_lines.push(
+                    "<!-- ms:dedup conflict: the rules below disagree; keep one -->".to_string(),
+                );
+                for &member in group {
+                    emitted.insert(member);
+                    let rule = &rules[member];
+                    rule_lines.push(format!(
+                        "- {} <!-- from {} -->",
+                        rule.text,
+                        rule.skills.join(", ")
+                    ));
+                }
+            }
+            None => rule_lines.push(format!("- {}", rule.text)),
+        }
+    }
+
+    let sources: Vec<String> = specs.iter().map(|(id, _)| id.clone()).collect();
+    let mut sections = vec![SkillSection {
+        id: "overview".to_string(),
+        title: "Overview".to_string(),
+        blocks: vec![SkillBlock {
+            id: positional_block_id("overview", 0),
+            block_type: BlockType::Text,
+            content: format!(
+                "Merged from {} by `ms dedup`.",
+                sources
+                    .iter()
+                    .map(|id| format!("`{id}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }],
+    }];
+    if !rule_lines.is_empty() {
+        sections.push(SkillSection {
+            id: "rules".to_string(),
+            title: "Rules".to_string(),
+            blocks: vec![SkillBlock {
+                id: positional_block_id("rules", 0),
+                block_type: BlockType::Text,
+                content: rule_lines.join("\n"),
+            }],
+        });
+    }
+    if !examples.is_empty() {
+        sections.push(SkillSection {
+            id: "examples".to_string(),
+            title: "Examples".to_string(),
+            blocks: examples
+                .into_iter()
+                .enumerate()
+                .map(|(idx, example)| SkillBlock {
+                    id: positional_block_id("examples", idx),
+                    block_type: BlockType::Code,
+                    content: example.text,
+                })
+                .collect(),
+        });
+    }
+    spec.sections = sections;
+
+    Ok(MergeDraft {
+        spec,
+        sources,
+        collapsed,
+        conflicts,
+    })
+}
+
+fn find<'a>(parent: &mut HashMap<&'a str, &'a str>, id: &'a str) -> &'a str {
+    let mut root = id;
+    while let Some(&next) = parent.get(root) {
+        root = next;
+    }
+    if root != id {
+        parent.insert(id, root);
+    }
+    root
+}
+
+/// Highest quality first, then non-deprecated, then by id.
+fn rank(a: &SkillRecord, b: &SkillRecord) -> Ordering {
+    b.quality_score
+        .partial_cmp(&a.quality_score)
+        .unwrap_or(Ordering::Equal)
+        .then_with(|| a.is_deprecated.cmp(&b.is_deprecated))
+        .then_with(|| a.id.cmp(&b.id))
+}
+
+fn diff_members(records: &[&SkillRecord]) -> ClusterDiff {
+    let items: Vec<(String, SkillItems)> = records
+        .iter()
+        .map(|r| {
+            let items = parse_markdown(&r.body)
+                .map(|spec| extract_items(&spec))
+                .unwrap_or_default();
+            (r.id.clone(), items)
+        })
+        .collect();
+
+    let mut rules = Vec::new();
+    let mut examples = Vec::new();
+    for (id, member) in items {
+        union_into(&mut rules, member.rules, &id);
+        union_into(&mut examples, member.examples, &id);
+    }
+    let (shared_rules, differing_rules) = split_shared(rules, records.len());
+    let (shared_examples, differing_examples) = split_shared(examples, records.len());
+    ClusterDiff {
+        shared_rules,
+        shared_examples,
+        differing_rules,
+        differing_examples: differing_examples
+            .into_iter()
+            .map(|mut item| {
+                item.text = example_summary(&item.text);
+                item
+            })
+            .collect(),
+    }
+}
+
+fn split_shared(items: Vec<ClusterItem>, members: usize) -> (usize, Vec<ClusterItem>) {
+    let (shared, differing): (Vec<_>, Vec<_>) =
+        items.into_iter().partition(|i| i.skills.len() == members);
+    (shared.len(), differing)
+}
+
+/// Add `texts` from `skill_id` to `items`, merging exact duplicates.
+/// Returns how many were duplicates.
+fn union_into(items: &mut Vec<ClusterItem>, texts: Vec<String>, skill_id: &str) -> usize {
+    let mut collapsed = 0;
+    for text in texts {
+        let key = normalize(&text);
+        if let Some(item) = items.iter_mut().find(|i| normalize(&i.text) == key) {
+            if !item.skills.iter().any(|s| s == skill_id) {
+                item.skills.push(skill_id.to_string());
+            }
+            collapsed += 1;
+        } else {
+            items.push(ClusterItem {
+                text,
+                skills: vec![skill_id.to_string()],
+            });
+        }
+    }
+    collapsed
+}
+
+/// Group rules from disjoint skills that are close but not identical.
+/// Keys are the first rule of each group; singletons are included.
+fn conflict_groups(rules: &[ClusterItem]) -> HashMap<usize, Vec<usize>> {
+    let mut groups: HashMap<usize, Vec<usize>> = HashMap::new();
+    let mut assigned: HashMap<usize, usize> = HashMap::new();
+    for (idx, rule) in rules.iter().enumerate() {
+        let leader = rules[..idx].iter().position(|other| {
+            let disjoint = !other.skills.iter().any(|s| rule.skills.contains(s));
+            disjoint && word_overlap_similarity(&other.text, &rule.text) >= CONFLICT_OVERLAP
+        });
+        let leader = leader.map_or(idx, |other| assigned[&other]);
+        assigned.insert(idx, leader);
+        groups.entry(leader).or_default().push(idx);
+    }
+    groups
+}
+
+fn extract_items(spec: &SkillSpec) -> SkillItems {
+    let mut items = SkillItems::default();
+    for block in spec.sections.iter().flat_map(|s| &s.blocks) {
+        match block.block_type {
+            BlockType::Code => items.examples.push(block.content.trim_end().to_string()),
+            BlockType::Rule | BlockType::Pitfall => {
+                items.rules.push(block.content.trim().to_string());
+            }
+            _ => items
+                .rules
+                .extend(block.content.lines().filter_map(list_item)),
+        }
+    }
+    items
+}
+
+/// The text of a markdown list item line, without its marker.
+fn list_item(line: &str) -> Option<String> {
+    let line = line.trim_start();
+    let rest = ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|marker| line.strip_prefix(marker))
+        .or_else(|| {
+            let digits = line.chars().take_while(char::is_ascii_digit).count();
+            (digits > 0)
+                .then_some(&line[digits..])
+                .and_then(|rest| rest.strip_prefix(". "))
+        })?;
+    let rest = rest
+        .strip_prefix("[ ] ")
+        .or_else(|| rest.strip_prefix("[x] "))
+        .unwrap_or(rest)
+        .trim();
+    (!rest.is_empty()).then(|| rest.to_string())
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_end_matches(['.', ';'])
+        .to_lowercase()
+}
+
+/// First code line of an example, with its fence language.
+fn example_summary(example: &str) -> String {
+    let mut lines = example.lines();
+    let lang = lines
+        .next()
+        .map(|fence| fence.trim_start_matches(['`', '~']).trim())
+        .unwrap_or_default();
+    let first = lines
+        .find(|l| !l.trim().is_empty() && !l.trim_start().starts_with("```"))
+        .unwrap_or_default()
+        .trim();
+    if lang.is_empty() {
+        first.to_string()
+    } else {
+        format!("[{lang}] {first}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dedup::{DeduplicationAction, StructuralDetails};
+
+    fn record(id: &str, quality: f64, body: &str) -> SkillRecord {
+        SkillRecord {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            version: None,
+            author: None,
+            source_path: format!("/skills/{id}/SKILL.md"),
+            source_layer: "project".to_string(),
+            git_remote: None,
+            git_commit: None,
+            content_hash: String::new(),
+            body: body.to_string(),
+            metadata_json: "{}".to_string(),
+            assets_json: "[]".to_string(),
+            token_count: 0,
+            quality_score: quality,
+            indexed_at: String::new(),
+            modified_at: String::new(),
+            is_deprecated: false,
+            deprecation_reason: None,
+        }
+    }
+
+    fn pair(a: &str, b: &str, similarity: f32) -> DuplicatePair {
+        DuplicatePair {
+            skill_a_id: a.to_string(),
+            skill_a_name: a.to_string(),
+            skill_b_id: b.to_string(),
+            skill_b_name: b.to_string(),
+            similarity,
+            semantic_score: similarity,
+            structural_score: similarity,
+            structural_details: StructuralDetails::default(),
+            recommendation: DeduplicationAction::Review,
+        }
+    }
+
+    const ERRORS_A: &str = "# Errors A\n\n## Rules\n\n- Use thiserror for library errors\n- Wrap errors with context\n\n```rust\nfn main() -> anyhow::Result<()> { Ok(()) }\n```\n";
+    const ERRORS_B: &str = "# Errors B\n\n## Rules\n\n- Wrap errors with  context.\n- Prefer thiserror for library error types\n";
+    const ERRORS_C: &str =
+        "# Errors C\n\n## Rules\n\n1. Wrap errors with context\n2. Never unwrap in libraries\n";
+
+    #[test]
+    fn pairs_cluster_transitively_ranked_by_quality() {
+        let skills = vec![
+            record("errors-a", 0.6, ERRORS_A),
+            record("errors-b", 0.9, ERRORS_B),
+            record("errors-c", 0.7, ERRORS_C),
+            record("go-testing", 0.8, "# Go\n"),
+        ];
+        let pairs = vec![
+            pair("errors-a", "errors-b", 0.92),
+            pair("errors-b", "errors-c", 0.88),
+        ];
+        let clusters = build_clusters(&pairs, &skills);
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(
+            clusters[0].skill_ids(),
+            ["errors-b", "errors-c", "errors-a"]
+        );
+        assert!((clusters[0].max_similarity - 0.92).abs() < f32::EPSILON);
+
+        let diff = &clusters[0].diff;
+        assert_eq!(diff.shared_rules, 1);
+        assert!(
+            diff.differing_rules
+                .iter()
+                .any(|r| r.text == "Never unwrap in libraries" && r.skills == ["errors-c"])
+        );
+        assert_eq!(diff.differing_examples.len(), 1);
+        assert!(
+            diff.differing_examples[0]
+                .text
+                .starts_with("[rust] fn main()")
+        );
+    }
+
+    #[test]
+    fn sealed_skills_stay_out_of_clusters() {
+        let sealed = format!(
+            "# Errors D\n\n## Sealed\n\n{}AAAA\n",
+            crate::security::encryption::ENVELOPE_PREFIX
+        );
+        let skills = vec![
+            record("errors-a", 0.6, ERRORS_A),
+            record("errors-b", 0.9, ERRORS_B),
+            record("errors-d", 0.8, &sealed),
+        ];
+        let pairs = vec![
+            pair("errors-a", "errors-d", 0.95),
+            pair("errors-b", "errors-d", 0.9),
+        ];
+        assert!(build_clusters(&pairs, &skills).is_empty());
+    }
+
+    #[test]
+    fn merge_collapses_duplicates_and_flags_conflicts() {
+        let skills = vec![
+            record("errors-a", 0.6, ERRORS_A),
+            record("errors-b", 0.9, ERRORS_B),
+        ];
+        let clusters = build_clusters(&[pair("errors-a", "errors-b", 0.9)], &skills);
+        let draft = draft_merge(&clusters[0], &skills, "errors").unwrap();
+
+        assert_eq!(draft.spec.metadata.id, "errors");
+        assert_eq!(draft.sources, ["errors-b", "errors-a"]);
+        assert_eq!(draft.collapsed, 1);
+        assert_eq!(draft.conflicts, 1);
+
+        let rules = &draft.spec.sections[1].blocks[0].content;
+        assert_eq!(rules.matches("Wrap errors with").count(), 1);
+        assert!(rules.contains("ms:dedup conflict"));
+        assert!(rules.contains("Prefer thiserror for library error types <!-- from errors-b -->"));
+        assert!(rules.contains("Use thiserror for library errors <!-- from errors-a -->"));
+        assert_eq!(draft.spec.sections[2].blocks.len(), 1);
+    }
+
+    #[test]
+    fn list_items_strip_markers() {
+        assert_eq!(list_item("- [ ] run tests").as_deref(), Some("run tests"));
+        assert_eq!(list_item("12. ship it").as_deref(), Some("ship it"));
+        assert_eq!(list_item("plain prose"), None);
+        assert_eq!(list_item("-"), None);
+    }
+}
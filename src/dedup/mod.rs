@@ -8,6 +8,10 @@
 //! 2. **Structural similarity**: Compare triggers, tags, requirements
 //! 3. **Hybrid scoring**: Weighted combination of semantic + structural
 //!
+//! Embeddings already stored for the vector index are reused when they were
+//! built by the active embedder from the skill's current content. Pairs a
+//! reviewer marked "not duplicates" are never reported again.
+//!
 //! ## Usage
 //!
 //! ```ignore
@@ -24,7 +28,9 @@ use serde::{Deserialize, Serialize};
 
 use crate::error::Result;
 use crate::search::Embedder;
-use crate::storage::sqlite::{Database, SkillRecord};
+use crate::storage::sqlite::{Database, EmbeddingRecord, SkillRecord};
+
+pub mod clusters;
 
 /// Configuration for deduplication
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -143,12 +149,63 @@ pub enum DeduplicationAction {
 pub struct DeduplicationEngine<'a> {
     config: DedupConfig,
     embedder: &'a dyn Embedder,
+    /// Stored vectors by skill id, with the content hash they were built from
+    stored: HashMap<String, (Option<String>, Vec<f32>)>,
+    /// Pairs marked "not duplicates", in id order
+    dismissed: HashSet<(String, String)>,
 }
 
 impl<'a> DeduplicationEngine<'a> {
     /// Create a new deduplication engine
     pub fn new(config: DedupConfig, embedder: &'a dyn Embedder) -> Self {
-        Self { config, embedder }
+        Self {
+            config,
+            embedder,
+            stored: HashMap::new(),
+            dismissed: HashSet::new(),
+        }
+    }
+
+    /// Reuse stored embeddings instead of re-embedding skills. Vectors from
+    /// another backend or dimension are ignored, as are vectors whose skill
+    /// content changed since they were computed.
+    #[must_use]
+    pub fn with_stored_embeddings(mut self, records: Vec<EmbeddingRecord>) -> Self {
+        self.stored = records
+            .into_iter()
+            .filter(|r| r.embedder_type == self.embedder.name() && r.dims == self.embedder.dims())
+            .map(|r| (r.skill_id, (r.content_hash, r.embedding)))
+            .collect();
+        self
+    }
+
+    /// Suppress pairs already reviewed as "not duplicates".
+    #[must_use]
+    pub fn with_dismissed(mut self, pairs: impl IntoIterator<Item = (String, String)>) -> Self {
+        self.dismissed = pairs
+            .into_iter()
+            .map(|(a, b)| if a <= b { (a, b) } else { (b, a) })
+            .collect();
+        self
+    }
+
+    /// Number of skills whose stored embedding can be reused.
+    #[must_use]
+    pub fn stored_embeddings(&self) -> usize {
+        self.stored.len()
+    }
+
+    fn embedding_for(&self, skill: &SkillRecord) -> Result<Vec<f32>> {
+        match self.stored.get(&skill.id) {
+            Some((hash, embedding))
+                if hash
+                    .as_deref()
+                    .is_none_or(|hash| hash == skill.content_hash) =>
+            {
+                Ok(embedding.clone())
+            }
+            _ => self.embedder.embed(&self.skill_to_text(skill)),
+        }
     }
 
     /// Find duplicates for a given skill from the database
@@ -157,24 +214,31 @@ impl<'a> DeduplicationEngine<'a> {
         db: &Database,
         skill: &SkillRecord,
     ) -> Result<Vec<DuplicateMatch>> {
+        // Sealed bodies are ciphertext; comparing them would be noise
+        if skill.is_sealed() {
+            return Ok(Vec::new());
+        }
+
         // Get all skills from DB
         let all_skills = db.list_skills(self.config.max_candidates * 2, 0)?;
 
         // Compute embedding for target skill
-        let target_text = self.skill_to_text(skill);
-        let target_embedding = self.embedder.embed(&target_text);
+        let target_embedding = self.embedding_for(skill)?;
 
         let mut matches = Vec::new();
 
         for candidate in &all_skills {
-            // Skip self
-            if candidate.id == skill.id {
+            // Skip self and sealed skills
+            if candidate.id == skill.id || candidate.is_sealed() {
+                continue;
+            }
+
+            if self.is_dismissed(&skill.id, &candidate.id) {
                 continue;
             }
 
             // Compute semantic similarity
-            let candidate_text = self.skill_to_text(candidate);
-            let candidate_embedding = self.embedder.embed(&candidate_text);
+            let candidate_embedding = self.embedding_for(candidate)?;
             let semantic_score = cosine_similarity(&target_embedding, &candidate_embedding);
 
             // Compute structural similarity
@@ -222,18 +286,16 @@ impl<'a> DeduplicationEngine<'a> {
 
     /// Scan all skills for duplicates
     pub fn scan_all(&self, db: &Database) -> Result<Vec<DuplicatePair>> {
-        let all_skills = db.list_skills(10000, 0)?;
+        let mut all_skills = db.list_skills(10000, 0)?;
+        all_skills.retain(|skill| !skill.is_sealed());
         let mut pairs: Vec<DuplicatePair> = Vec::new();
         let mut seen: HashSet<(String, String)> = HashSet::new();
 
         // Precompute embeddings for all skills
         let embeddings: Vec<(String, Vec<f32>)> = all_skills
             .iter()
-            .map(|s| {
-                let text = self.skill_to_text(s);
-                (s.id.clone(), self.embedder.embed(&text))
-            })
-            .collect();
+            .map(|s| Ok((s.id.clone(), self.embedding_for(s)?)))
+            .collect::<Result<_>>()?;
 
         for (i, skill_a) in all_skills.iter().enumerate() {
             for (j, skill_b) in all_skills.iter().enumerate() {
@@ -248,7 +310,7 @@ impl<'a> DeduplicationEngine<'a> {
                     (skill_b.id.clone(), skill_a.id.clone())
                 };
 
-                if seen.contains(&key) {
+                if seen.contains(&key) || self.dismissed.contains(&key) {
                     continue;
                 }
 
@@ -306,6 +368,12 @@ impl<'a> DeduplicationEngine<'a> {
         Ok(pairs)
     }
 
+    fn is_dismissed(&self, a: &str, b: &str) -> bool {
+        let key = if a <= b { (a, b) } else { (b, a) };
+        self.dismissed
+            .contains(&(key.0.to_string(), key.1.to_string()))
+    }
+
     /// Convert skill to text for embedding
     fn skill_to_text(&self, skill: &SkillRecord) -> String {
         let mut text = String::new();
@@ -709,7 +777,12 @@ impl Personalizer {
     /// Returns true if the style profile has patterns or tech preferences that
     /// could be applied, or if naming conventions differ from defaults.
     #[must_use]
-    pub fn should_personalize(&self, _skill: &SkillRecord) -> bool {
+    pub fn should_personalize(&self, skill: &SkillRecord) -> bool {
+        // A sealed body has no code or wording to adapt
+        if skill.is_sealed() {
+            return false;
+        }
+
         // Check if we have any non-default style preferences
         let has_naming_prefs = self.style.naming.variable_case != CaseStyle::SnakeCase
             || self.style.naming.use_abbreviations
@@ -13,237 +13,225 @@
 
 use serde::{Deserialize, Serialize};
 
-/// Standardized error codes for robot mode output.
+/// Process exit code groups, so scripts can branch without parsing output.
 ///
-/// Each variant maps to a numeric code (e.g., `SkillNotFound` -> E101).
-/// Codes are grouped by category for easy identification.
+/// Usage errors caught by argument parsing also exit with 2.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum ErrorCode {
-    // ========================================
+#[serde(rename_all = "snake_case")]
+pub enum ExitGroup {
+    /// 1: Internal or unexpected failure
+    Failure,
+    /// 2: Bad input: unknown skill, invalid arguments, config or skill content
+    User,
+    /// 3: Environment or dependency problem: index, database, network, tools
+    Environment,
+    /// 4: Blocked by a safety policy
+    Safety,
+}
+
+impl ExitGroup {
+    pub const ALL: [Self; 4] = [Self::Failure, Self::User, Self::Environment, Self::Safety];
+
+    /// Process exit code for this group.
+    #[must_use]
+    pub const fn code(self) -> u8 {
+        match self {
+            Self::Failure => 1,
+            Self::User => 2,
+            Self::Environment => 3,
+            Self::Safety => 4,
+        }
+    }
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Failure => "failure",
+            Self::User => "user",
+            Self::Environment => "environment",
+            Self::Safety => "safety",
+        }
+    }
+
+    #[must_use]
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::Failure => "internal or unexpected error",
+            Self::User => "user error (bad arguments, unknown skill, invalid input)",
+            Self::Environment => {
+                "environment or dependency error (index, database, network, tools)"
+            }
+            Self::Safety => "blocked by a safety policy (approval required, destructive command)",
+        }
+    }
+}
+
+/// Exit code section of `ms --help`.
+pub const EXIT_CODES_HELP: &str = "\
+Exit codes:
+  0  success
+  1  internal or unexpected error
+  2  user error (bad arguments, unknown skill, invalid input)
+  3  environment or dependency error (index, database, network, tools)
+  4  blocked by a safety policy (approval required, destructive command)
+
+Run `ms errors list` for every error code.";
+
+/// Defines [`ErrorCode`] and its catalog accessors from one table, so the
+/// enum, `ms errors list`, and [`ErrorCode::all`] cannot drift apart.
+macro_rules! error_codes {
+    ($($variant:ident = $numeric:literal, $id:literal, $exit:ident, $description:literal;)*) => {
+        /// Standardized error codes for robot mode output.
+        ///
+        /// Each variant maps to a numeric code (e.g., `SkillNotFound` -> E101)
+        /// and a stable snake_case id (`skill_not_found`).
+        /// Codes are grouped by category for easy identification.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+        #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+        pub enum ErrorCode {
+            $(
+                #[doc = concat!("E", stringify!($numeric), ": ", $description)]
+                $variant,
+            )*
+        }
+
+        impl ErrorCode {
+            /// Every error code, in catalog order.
+            pub const ALL: &'static [Self] = &[$(Self::$variant),*];
+
+            /// Get the numeric error code (e.g., `SkillNotFound` -> 101).
+            #[must_use]
+            pub const fn numeric(&self) -> u16 {
+                match self {
+                    $(Self::$variant => $numeric,)*
+                }
+            }
+
+            /// Stable machine-readable id (e.g., `skill_not_found`).
+            #[must_use]
+            pub const fn as_str(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $id,)*
+                }
+            }
+
+            /// One-line description for the error catalog.
+            #[must_use]
+            pub const fn description(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $description,)*
+                }
+            }
+
+            /// Exit code group the CLI exits with for this error.
+            #[must_use]
+            pub const fn exit_group(&self) -> ExitGroup {
+                match self {
+                    $(Self::$variant => ExitGroup::$exit,)*
+                }
+            }
+        }
+    };
+}
+
+error_codes! {
     // Skill errors (1xx)
-    // ========================================
-    /// E101: Requested skill was not found in the index
-    SkillNotFound,
-    /// E102: Skill file exists but has invalid format
-    SkillInvalid,
-    /// E103: Failed to parse skill markdown/spec
-    SkillParseError,
-    /// E104: Skill depends on another skill that doesn't exist
-    SkillDependencyMissing,
-    /// E105: Circular dependency chain detected
-    SkillCyclicDependency,
-    /// E106: Parent skill not found during inheritance resolution
-    SkillParentNotFound,
-
-    // ========================================
+    SkillNotFound = 101, "skill_not_found", User, "Requested skill was not found in the index";
+    SkillInvalid = 102, "skill_invalid", User, "Skill file exists but has invalid format";
+    SkillParseError = 103, "skill_parse_error", User, "Failed to parse skill markdown/spec";
+    SkillDependencyMissing = 104, "skill_dependency_missing", User,
+        "Skill depends on another skill that doesn't exist";
+    SkillCyclicDependency = 105, "skill_cyclic_dependency", User,
+        "Circular dependency chain detected";
+    SkillParentNotFound = 106, "skill_parent_not_found", User,
+        "Parent skill not found during inheritance resolution";
+    PackBudgetExceeded = 107, "pack_budget_exceeded", User,
+        "Mandatory slices do not fit the token budget";
+
     // Index errors (2xx)
-    // ========================================
-    /// E201: No skills have been indexed yet
-    IndexEmpty,
-    /// E202: Index files are corrupted or unreadable
-    IndexCorrupted,
-    /// E203: Another process is currently indexing
-    IndexBusy,
-    /// E204: Index version doesn't match current ms version
-    IndexVersionMismatch,
-
-    // ========================================
+    IndexEmpty = 201, "index_empty", User, "No skills have been indexed yet";
+    IndexCorrupted = 202, "index_corrupted", Environment,
+        "Index files are corrupted or unreadable";
+    IndexBusy = 203, "index_busy", Environment, "Another process is currently indexing";
+    IndexVersionMismatch = 204, "index_version_mismatch", Environment,
+        "Index version doesn't match current ms version";
+    IndexEmbeddingMismatch = 205, "index_embedding_mismatch", Environment,
+        "Stored embeddings come from a different backend or dimension";
+
     // Config errors (3xx)
-    // ========================================
-    /// E301: Config file not found
-    ConfigNotFound,
-    /// E302: Config file has invalid syntax or values
-    ConfigInvalid,
-    /// E303: Cannot read/write config file due to permissions
-    ConfigPermissionDenied,
-    /// E304: Required config value is missing
-    ConfigMissingRequired,
-
-    // ========================================
+    ConfigNotFound = 301, "config_not_found", User, "Config file not found";
+    ConfigInvalid = 302, "config_invalid", User, "Config file has invalid syntax or values";
+    ConfigPermissionDenied = 303, "config_permission_denied", Environment,
+        "Cannot read/write config file due to permissions";
+    ConfigMissingRequired = 304, "config_missing_required", User,
+        "Required config value is missing";
+
     // Search errors (4xx)
-    // ========================================
-    /// E401: Search query has invalid syntax
-    SearchQueryInvalid,
-    /// E402: Search operation timed out
-    SearchTimeout,
-    /// E403: Search returned zero results
-    SearchNoResults,
-
-    // ========================================
+    SearchQueryInvalid = 401, "search_query_invalid", User, "Search query has invalid syntax";
+    SearchTimeout = 402, "search_timeout", Environment, "Search operation timed out";
+    SearchNoResults = 403, "search_no_results", User, "Search returned zero results";
+    CursorStale = 404, "cursor_stale", User,
+        "Pagination cursor was issued against an older index generation";
+    ResultsTruncated = 405, "results_truncated", User,
+        "Paging went past the candidates a search ranks";
+
     // Network errors (5xx)
-    // ========================================
-    /// E501: Cannot reach remote server
-    NetworkUnreachable,
-    /// E502: Network request timed out
-    NetworkTimeout,
-    /// E503: Authentication with remote failed
-    NetworkAuthFailed,
-
-    // ========================================
+    NetworkUnreachable = 501, "network_unreachable", Environment, "Cannot reach remote server";
+    NetworkTimeout = 502, "network_timeout", Environment, "Network request timed out";
+    NetworkAuthFailed = 503, "network_auth_failed", Environment,
+        "Authentication with remote failed";
+
     // Storage errors (6xx)
-    // ========================================
-    /// E601: Failed to read from storage
-    StorageReadError,
-    /// E602: Failed to write to storage
-    StorageWriteError,
-    /// E603: Storage device is full
-    StorageFull,
-    /// E604: Database operation failed
-    DatabaseError,
-    /// E605: Serialization/deserialization failed
-    SerializationError,
-
-    // ========================================
+    StorageReadError = 601, "storage_read_error", Environment, "Failed to read from storage";
+    StorageWriteError = 602, "storage_write_error", Environment, "Failed to write to storage";
+    StorageFull = 603, "storage_full", Environment, "Storage device is full";
+    DatabaseError = 604, "database_error", Environment, "Database operation failed";
+    SerializationError = 605, "serialization_error", Failure,
+        "Serialization/deserialization failed";
+    JsonError = 606, "json_error", User, "JSON input could not be parsed or written";
+    YamlError = 607, "yaml_error", User, "YAML input could not be parsed or written";
+
     // Git errors (7xx)
-    // ========================================
-    /// E701: Directory is not a git repository
-    GitNotRepository,
-    /// E702: Git merge conflict detected
-    GitConflict,
-    /// E703: Git remote operation failed
-    GitRemoteError,
-    /// E704: General git error
-    GitError,
-
-    // ========================================
+    GitNotRepository = 701, "git_not_repository", User, "Directory is not a git repository";
+    GitConflict = 702, "git_conflict", User, "Git merge conflict detected";
+    GitRemoteError = 703, "git_remote_error", Environment, "Git remote operation failed";
+    GitError = 704, "git_error", Environment, "General git error";
+
     // Validation errors (8xx)
-    // ========================================
-    /// E801: Validation rules failed
-    ValidationFailed,
-    /// E802: Operation requires explicit approval
-    ApprovalRequired,
-    /// E803: Security policy violation detected
-    SecurityViolation,
-    /// E804: ACIP (Agent Content Injection Prevention) blocked content
-    AcipBlocked,
-    /// E805: Destructive operation was blocked
-    DestructiveBlocked,
-
-    // ========================================
+    ValidationFailed = 801, "validation_failed", User, "Validation rules failed";
+    ApprovalRequired = 802, "approval_required", Safety, "Operation requires explicit approval";
+    SecurityViolation = 803, "security_violation", Safety, "Security policy violation detected";
+    AcipBlocked = 804, "acip_blocked", Safety,
+        "ACIP (Agent Content Injection Prevention) blocked content";
+    DestructiveBlocked = 805, "destructive_blocked", Safety, "Destructive operation was blocked";
+
     // Lock/Transaction errors (85x)
-    // ========================================
-    /// E851: Failed to acquire lock within timeout
-    LockTimeout,
-    /// E852: Failed to acquire lock
-    LockFailed,
-    /// E853: Transaction operation failed
-    TransactionFailed,
-    /// E854: Two-phase commit failed
-    TwoPhaseCommitFailed,
-
-    // ========================================
+    LockTimeout = 851, "lock_timeout", Environment, "Failed to acquire lock within timeout";
+    LockFailed = 852, "lock_failed", Environment, "Failed to acquire lock";
+    TransactionFailed = 853, "transaction_failed", Environment, "Transaction operation failed";
+    TwoPhaseCommitFailed = 854, "two_phase_commit_failed", Failure, "Two-phase commit failed";
+    Busy = 855, "busy", Environment, "Another ms process holds a write lock";
+
     // Integration errors (88x)
-    // ========================================
-    /// E881: CASS (session search) is not available
-    CassUnavailable,
-    /// E882: CM (CASS Memory) is not available
-    CmUnavailable,
-    /// E883: Beads issue tracker is not available
-    BeadsUnavailable,
-    /// E884: Mining operation failed
-    MiningFailed,
-    /// E885: Import operation failed
-    ImportFailed,
-    /// E886: Authentication failed
-    AuthenticationFailed,
-
-    // ========================================
+    CassUnavailable = 881, "cass_unavailable", Environment,
+        "CASS (session search) is not available";
+    CmUnavailable = 882, "cm_unavailable", Environment, "CM (CASS Memory) is not available";
+    BeadsUnavailable = 883, "beads_unavailable", Environment,
+        "Beads issue tracker is not available";
+    MiningFailed = 884, "mining_failed", Environment, "Mining operation failed";
+    ImportFailed = 885, "import_failed", User, "Import operation failed";
+    AuthenticationFailed = 886, "authentication_failed", User, "Authentication failed";
+
     // Internal errors (9xx)
-    // ========================================
-    /// E901: Unexpected internal error
-    InternalError,
-    /// E902: Feature not yet implemented
-    NotImplemented,
-    /// E903: Operation timed out
-    Timeout,
-    /// E904: Internal assertion failed
-    AssertionFailed,
-    /// E905: Generic not found (catch-all)
-    NotFound,
-    /// E906: IO operation failed
-    IoError,
+    InternalError = 901, "internal_error", Failure, "Unexpected internal error";
+    NotImplemented = 902, "not_implemented", Failure, "Feature not yet implemented";
+    Timeout = 903, "timeout", Environment, "Operation timed out";
+    AssertionFailed = 904, "assertion_failed", Failure, "Internal assertion failed";
+    NotFound = 905, "not_found", User, "Generic not found (catch-all)";
+    IoError = 906, "io_error", Environment, "IO operation failed";
 }
 
 impl ErrorCode {
-    /// Get the numeric error code (e.g., `SkillNotFound` -> 101).
-    #[must_use]
-    pub const fn numeric(&self) -> u16 {
-        match self {
-            // Skill errors (1xx)
-            Self::SkillNotFound => 101,
-            Self::SkillInvalid => 102,
-            Self::SkillParseError => 103,
-            Self::SkillDependencyMissing => 104,
-            Self::SkillCyclicDependency => 105,
-            Self::SkillParentNotFound => 106,
-
-            // Index errors (2xx)
-            Self::IndexEmpty => 201,
-            Self::IndexCorrupted => 202,
-            Self::IndexBusy => 203,
-            Self::IndexVersionMismatch => 204,
-
-            // Config errors (3xx)
-            Self::ConfigNotFound => 301,
-            Self::ConfigInvalid => 302,
-            Self::ConfigPermissionDenied => 303,
-            Self::ConfigMissingRequired => 304,
-
-            // Search errors (4xx)
-            Self::SearchQueryInvalid => 401,
-            Self::SearchTimeout => 402,
-            Self::SearchNoResults => 403,
-
-            // Network errors (5xx)
-            Self::NetworkUnreachable => 501,
-            Self::NetworkTimeout => 502,
-            Self::NetworkAuthFailed => 503,
-
-            // Storage errors (6xx)
-            Self::StorageReadError => 601,
-            Self::StorageWriteError => 602,
-            Self::StorageFull => 603,
-            Self::DatabaseError => 604,
-            Self::SerializationError => 605,
-
-            // Git errors (7xx)
-            Self::GitNotRepository => 701,
-            Self::GitConflict => 702,
-            Self::GitRemoteError => 703,
-            Self::GitError => 704,
-
-            // Validation errors (8xx)
-            Self::ValidationFailed => 801,
-            Self::ApprovalRequired => 802,
-            Self::SecurityViolation => 803,
-            Self::AcipBlocked => 804,
-            Self::DestructiveBlocked => 805,
-
-            // Lock/Transaction errors (85x)
-            Self::LockTimeout => 851,
-            Self::LockFailed => 852,
-            Self::TransactionFailed => 853,
-            Self::TwoPhaseCommitFailed => 854,
-
-            // Integration errors (88x)
-            Self::CassUnavailable => 881,
-            Self::CmUnavailable => 882,
-            Self::BeadsUnavailable => 883,
-            Self::MiningFailed => 884,
-            Self::ImportFailed => 885,
-            Self::AuthenticationFailed => 886,
-
-            // Internal errors (9xx)
-            Self::InternalError => 901,
-            Self::NotImplemented => 902,
-            Self::Timeout => 903,
-            Self::AssertionFailed => 904,
-            Self::NotFound => 905,
-            Self::IoError => 906,
-        }
-    }
-
     /// Get the error code as a formatted string (e.g., "E101").
     #[must_use]
     pub fn code_string(&self) -> String {
@@ -273,6 +261,9 @@ impl ErrorCode {
             Self::SkillParentNotFound => {
                 "Ensure the parent skill exists before defining the child. Check `extends` field for typos"
             }
+            Self::PackBudgetExceeded => {
+                "Raise --max-tokens, or load dependencies separately with `--deps required-only`"
+            }
 
             // Index errors
             Self::IndexEmpty => "Run `ms index <path>` to index skills from a directory",
@@ -283,6 +274,9 @@ impl ErrorCode {
             Self::IndexVersionMismatch => {
                 "Run `ms migrate` to update the index to the current version"
             }
+            Self::IndexEmbeddingMismatch => {
+                "Run `ms doctor --fix` to re-embed skills with the active embedding backend"
+            }
 
             // Config errors
             Self::ConfigNotFound => {
@@ -306,6 +300,12 @@ impl ErrorCode {
             Self::SearchNoResults => {
                 "Try broader search terms, or run `ms list` to see all available skills"
             }
+            Self::CursorStale => {
+                "The index changed since the cursor was issued. Repeat the request without a cursor"
+            }
+            Self::ResultsTruncated => {
+                "Search pages stop after the top candidates. Narrow the query or filters"
+            }
 
             // Network errors
             Self::NetworkUnreachable => {
@@ -332,6 +332,8 @@ impl ErrorCode {
             Self::SerializationError => {
                 "The data format may be corrupted. Check input data for validity"
             }
+            Self::JsonError => "Check the JSON input for syntax errors",
+            Self::YamlError => "Check the YAML input (e.g. skill frontmatter) for syntax errors",
 
             // Git errors
             Self::GitNotRepository => {
@@ -375,6 +377,10 @@ impl ErrorCode {
             Self::TwoPhaseCommitFailed => {
                 "Two-phase commit failed. Data may be partially committed. Run `ms doctor`"
             }
+            Self::Busy => {
+                "Another ms process is writing. Retry when it finishes, raise MS_LOCK_TIMEOUT, \
+                 or run `ms doctor --check-lock` to see the holder"
+            }
 
             // Integration errors
             Self::CassUnavailable => {
@@ -423,9 +429,11 @@ impl ErrorCode {
             | Self::SkillDependencyMissing
             | Self::SkillCyclicDependency
             | Self::SkillParentNotFound
+            | Self::PackBudgetExceeded
             | Self::IndexEmpty
             | Self::IndexBusy
             | Self::IndexVersionMismatch
+            | Self::IndexEmbeddingMismatch
             | Self::ConfigNotFound
             | Self::ConfigInvalid
             | Self::ConfigPermissionDenied
@@ -433,12 +441,16 @@ impl ErrorCode {
             | Self::SearchQueryInvalid
             | Self::SearchTimeout
             | Self::SearchNoResults
+            | Self::CursorStale
+            | Self::ResultsTruncated
             | Self::NetworkUnreachable
             | Self::NetworkTimeout
             | Self::NetworkAuthFailed
             | Self::StorageReadError
             | Self::StorageWriteError
             | Self::StorageFull
+            | Self::JsonError
+            | Self::YamlError
             | Self::GitNotRepository
             | Self::GitConflict
             | Self::GitRemoteError
@@ -450,6 +462,7 @@ impl ErrorCode {
             | Self::DestructiveBlocked
             | Self::LockTimeout
             | Self::LockFailed
+            | Self::Busy
             | Self::TransactionFailed
             | Self::CassUnavailable
             | Self::CmUnavailable
@@ -500,58 +513,7 @@ impl ErrorCode {
 
     /// Iterate over all error codes.
     pub fn all() -> impl Iterator<Item = Self> {
-        [
-            Self::SkillNotFound,
-            Self::SkillInvalid,
-            Self::SkillParseError,
-            Self::SkillDependencyMissing,
-            Self::SkillCyclicDependency,
-            Self::SkillParentNotFound,
-            Self::IndexEmpty,
-            Self::IndexCorrupted,
-            Self::IndexBusy,
-            Self::IndexVersionMismatch,
-            Self::ConfigNotFound,
-            Self::ConfigInvalid,
-            Self::ConfigPermissionDenied,
-            Self::ConfigMissingRequired,
-            Self::SearchQueryInvalid,
-            Self::SearchTimeout,
-            Self::SearchNoResults,
-            Self::NetworkUnreachable,
-            Self::NetworkTimeout,
-            Self::NetworkAuthFailed,
-            Self::StorageReadError,
-            Self::StorageWriteError,
-            Self::StorageFull,
-            Self::DatabaseError,
-            Self::SerializationError,
-            Self::GitNotRepository,
-            Self::GitConflict,
-            Self::GitRemoteError,
-            Self::GitError,
-            Self::ValidationFailed,
-            Self::ApprovalRequired,
-            Self::SecurityViolation,
-            Self::AcipBlocked,
-            Self::DestructiveBlocked,
-            Self::LockTimeout,
-            Self::LockFailed,
-            Self::TransactionFailed,
-            Self::TwoPhaseCommitFailed,
-            Self::CassUnavailable,
-            Self::CmUnavailable,
-            Self::BeadsUnavailable,
-            Self::MiningFailed,
-            Self::ImportFailed,
-            Self::InternalError,
-            Self::NotImplemented,
-            Self::Timeout,
-            Self::AssertionFailed,
-            Self::NotFound,
-            Self::IoError,
-        ]
-        .into_iter()
+        Self::ALL.iter().copied()
     }
 }
 
@@ -674,4 +636,40 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_ids_are_unique_snake_case() {
+        let mut seen = std::collections::HashSet::new();
+        for code in ErrorCode::all() {
+            let id = code.as_str();
+            assert!(seen.insert(id), "Duplicate id: {id}");
+            assert!(
+                id.chars().all(|c| c.is_ascii_lowercase() || c == '_'),
+                "ErrorCode::{code:?} id {id} is not snake_case"
+            );
+            assert!(!code.description().is_empty());
+        }
+        assert_eq!(ErrorCode::SkillNotFound.as_str(), "skill_not_found");
+        assert_eq!(ErrorCode::ApprovalRequired.as_str(), "approval_required");
+        assert_eq!(
+            ErrorCode::DestructiveBlocked.as_str(),
+            "destructive_blocked"
+        );
+    }
+
+    #[test]
+    fn test_exit_groups() {
+        assert_eq!(ErrorCode::SkillNotFound.exit_group().code(), 2);
+        assert_eq!(ErrorCode::DatabaseError.exit_group().code(), 3);
+        assert_eq!(ErrorCode::DestructiveBlocked.exit_group().code(), 4);
+        assert_eq!(ErrorCode::AssertionFailed.exit_group().code(), 1);
+        for group in ExitGroup::ALL {
+            let line = format!("  {}  {}", group.code(), group.description());
+            assert!(
+                EXIT_CODES_HELP.contains(&line),
+                "--help is missing exit code {}",
+                group.code()
+            );
+        }
+    }
 }
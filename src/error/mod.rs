@@ -15,7 +15,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use thiserror::Error;
 
-pub use codes::ErrorCode;
+pub use codes::{EXIT_CODES_HELP, ErrorCode, ExitGroup};
 pub use suggestions::{suggest_for_error, suggest_similar_skills};
 
 /// Main error type for meta_skill operations.
@@ -49,6 +49,9 @@ pub enum MsError {
     #[error("{0}")]
     SearchIndexReadOnly(String),
 
+    #[error("Re-index required: {0}")]
+    ReindexRequired(String),
+
     #[error("JSON serialization error: {0}")]
     Json(#[from] serde_json::Error),
 
@@ -58,6 +61,14 @@ pub enum MsError {
     #[error("Query parse error: {0}")]
     QueryParse(String),
 
+    /// A pagination cursor was issued against an older index generation.
+    #[error("Cursor is stale: {0}")]
+    CursorStale(String),
+
+    /// Paging went past the candidates a search ranked.
+    #[error("Results truncated: {0}")]
+    ResultsTruncated(String),
+
     #[error("CASS not available: {0}")]
     CassUnavailable(String),
 
@@ -97,6 +108,15 @@ pub enum MsError {
     #[error("Lock failed: {0}")]
     LockFailed(String),
 
+    /// A write lock (`ms.lock`, the search index writer, or the database)
+    /// stayed held by another process past the lock timeout. `pid` is the
+    /// holder when it is known.
+    #[error(
+        "{resource} is busy{}; retry once the other ms process finishes",
+        .pid.map(|pid| format!(": held by pid {pid}")).unwrap_or_default()
+    )]
+    Busy { resource: String, pid: Option<u32> },
+
     #[error("Serialization error: {0}")]
     Serialization(String),
 
@@ -112,20 +132,47 @@ pub enum MsError {
     #[error("Assertion failed: {0}")]
     AssertionFailed(String),
 
+    /// Mandatory content needs more tokens than the budget allows.
+    #[error("Pack budget exceeded: {message}")]
+    PackBudgetExceeded {
+        budget: usize,
+        required: usize,
+        message: String,
+    },
+
     #[error("Cyclic inheritance detected for skill '{skill_id}': {}", .cycle.join(" -> "))]
     CyclicInheritance {
         skill_id: String,
         cycle: Vec<String>,
     },
 
-    #[error("Parent skill not found: {parent_id} (required by {child_id})")]
-    ParentSkillNotFound { parent_id: String, child_id: String },
+    #[error("Parent skill not found: {parent_id} (required by {child_id}; chain: {})", .chain.join(" -> "))]
+    ParentSkillNotFound {
+        parent_id: String,
+        child_id: String,
+        /// Extends chain from the skill being resolved to the missing parent
+        chain: Vec<String>,
+    },
 
     #[error("Import error: {0}")]
     Import(String),
 
     #[error("Authentication error: {0}")]
     AuthError(String),
+
+    /// Agent mail request failure. `status` is the HTTP status when the
+    /// server answered; `retries_exhausted` is set when a transient failure
+    /// persisted through every retry.
+    #[error(
+        "Agent mail error: {message}{}{}",
+        .status.map(|s| format!(" (HTTP {s})")).unwrap_or_default(),
+        if *.retries_exhausted { " after exhausting retries" } else { "" }
+    )]
+    AgentMail {
+        status: Option<u16>,
+        retries_exhausted: bool,
+        message: String,
+    },
 }
 
 impl MsError {
@@ -141,8 +188,13 @@ impl MsError {
             Self::ValidationFailed(_) => ErrorCode::ValidationFailed,
             Self::SearchIndex(_) => ErrorCode::IndexCorrupted,
             Self::SearchIndexReadOnly(_) => ErrorCode::IndexBusy,
-            Self::Json(_) | Self::Yaml(_) | Self::Serialization(_) => ErrorCode::SerializationError,
+            Self::ReindexRequired(_) => ErrorCode::IndexEmbeddingMismatch,
+            Self::Json(_) => ErrorCode::JsonError,
+            Self::Yaml(_) => ErrorCode::YamlError,
+            Self::Serialization(_) => ErrorCode::SerializationError,
             Self::QueryParse(_) => ErrorCode::SearchQueryInvalid,
+            Self::CursorStale(_) => ErrorCode::CursorStale,
+            Self::ResultsTruncated(_) => ErrorCode::ResultsTruncated,
             Self::CassUnavailable(_) => ErrorCode::CassUnavailable,
             Self::CmUnavailable(_) => ErrorCode::CmUnavailable,
             Self::BeadsUnavailable(_) => ErrorCode::BeadsUnavailable,
@@ -156,14 +208,21 @@ impl MsError {
             Self::AcipError(_) => ErrorCode::AcipBlocked,
             Self::LockTimeout(_) => ErrorCode::LockTimeout,
             Self::LockFailed(_) => ErrorCode::LockFailed,
+            Self::Busy { .. } => ErrorCode::Busy,
             Self::NotImplemented(_) => ErrorCode::NotImplemented,
             Self::NotFound(_) => ErrorCode::NotFound,
             Self::Timeout(_) => ErrorCode::Timeout,
             Self::AssertionFailed(_) => ErrorCode::AssertionFailed,
+            Self::PackBudgetExceeded { .. } => ErrorCode::PackBudgetExceeded,
             Self::CyclicInheritance { .. } => ErrorCode::SkillCyclicDependency,
             Self::ParentSkillNotFound { .. } => ErrorCode::SkillParentNotFound,
             Self::Import(_) => ErrorCode::ImportFailed,
             Self::AuthError(_) => ErrorCode::AuthenticationFailed,
+            Self::AgentMail {
+                status: Some(401 | 403),
+                ..
+            } => ErrorCode::NetworkAuthFailed,
+            Self::AgentMail { .. } => ErrorCode::NetworkUnreachable,
         }
     }
 
@@ -179,15 +238,65 @@ impl MsError {
             Self::ParentSkillNotFound {
                 parent_id,
                 child_id,
-            } => Some(serde_json::json!({ "parent_id": parent_id, "child_id": child_id })),
+                chain,
+            } => Some(
+                serde_json::json!({ "parent_id": parent_id, "child_id": child_id, "chain": chain }),
+            ),
             Self::MissingConfig(key) => Some(serde_json::json!({ "config_key": key })),
+            Self::PackBudgetExceeded {
+                budget, required, ..
+            } => Some(serde_json::json!({
+                "expected_max_tokens": budget,
+                "actual_tokens": required,
+            })),
+            Self::Io(err) => Some(serde_json::json!({ "io_kind": err.kind().to_string() })),
             Self::TwoPhaseCommitFailed { phase, reason } => {
                 Some(serde_json::json!({ "phase": phase, "reason": reason }))
             }
+            Self::Busy { resource, pid } => {
+                Some(serde_json::json!({ "resource": resource, "holder_pid": pid }))
+            }
+            Self::AgentMail {
+                status,
+                retries_exhausted,
+                ..
+            } => Some(serde_json::json!({
+                "http_status": status,
+                "retries_exhausted": retries_exhausted,
+            })),
             _ => None,
         }
     }
 
+    /// Process exit code for this error (see [`ExitGroup`]).
+    #[must_use]
+    pub fn exit_code(&self) -> u8 {
+        self.code().exit_group().code()
+    }
+
+    /// Robot-mode error payload: the stable `code` id, the message, and a
+    /// `details` object when the variant carries any.
+    #[must_use]
+    pub fn to_robot_json(&self) -> Value {
+        let code = self.code();
+        let message = match self {
+            // Robot output has always carried these without the Display prefix
+            Self::ApprovalRequired(msg) | Self::DestructiveBlocked(msg) => msg.clone(),
+            _ => self.to_string(),
+        };
+        let mut payload = serde_json::json!({
+            "error": true,
+            "code": code.as_str(),
+            "numeric_code": code.numeric(),
+            "exit_code": code.exit_group().code(),
+            "message": message,
+        });
+        if let Some(details) = self.context() {
+            payload["details"] = details;
+        }
+        payload
+    }
+
     /// Convert this error to a structured error.
     #[must_use]
     pub fn to_structured(&self) -> StructuredError {
@@ -343,6 +452,30 @@ mod tests {
         assert_eq!(ctx.get("skill_id").unwrap(), "my-skill");
     }
 
+    #[test]
+    fn test_agent_mail_error_reports_status() {
+        let err = MsError::AgentMail {
+            status: Some(503),
+            retries_exhausted: true,
+            message: "fetch_inbox failed".into(),
+        };
+        assert_eq!(err.code(), ErrorCode::NetworkUnreachable);
+        assert_eq!(
+            err.to_string(),
+            "Agent mail error: fetch_inbox failed (HTTP 503) after exhausting retries"
+        );
+        let ctx = err.context().unwrap();
+        assert_eq!(ctx["http_status"], 503);
+        assert_eq!(ctx["retries_exhausted"], true);
+
+        let auth = MsError::AgentMail {
+            status: Some(401),
+            retries_exhausted: false,
+            message: "denied".into(),
+        };
+        assert_eq!(auth.code(), ErrorCode::NetworkAuthFailed);
+    }
+
     #[test]
     fn test_structured_error_from_ms_error() {
         let err = MsError::SkillNotFound("test-skill".into());
@@ -390,7 +523,12 @@ mod tests {
         let err = MsError::ParentSkillNotFound {
             parent_id: "parent".into(),
             child_id: "child".into(),
+            chain: vec!["grandchild".into(), "child".into(), "parent".into()],
         };
+        assert!(
+            err.to_string()
+                .contains("chain: grandchild -> child -> parent")
+        );
         let structured = err.to_structured();
 
         assert_eq!(structured.code, ErrorCode::SkillParentNotFound);
@@ -410,4 +548,178 @@ mod tests {
         let structured2: StructuredError = (&err2).into();
         assert_eq!(structured2.code, ErrorCode::ConfigInvalid);
     }
+
+    /// One error of every variant. `variant_index` has no wildcard arm, so a
+    /// new variant does not compile until it is added here too.
+    fn one_of_each() -> Vec<MsError> {
+        vec![
+            MsError::Database(fsqlite_error::FrankenError::TypeMismatch {
+                expected: "a".into(),
+                actual: "b".into(),
+            }),
+            MsError::Git(git2::Error::from_str("bad ref")),
+            MsError::Io(io::Error::new(io::ErrorKind::NotFound, "gone")),
+            MsError::SkillNotFound("a".into()),
+            MsError::InvalidSkill("a".into()),
+            MsError::ValidationFailed("a".into()),
+            MsError::SearchIndex(tantivy::TantivyError::InvalidArgument("a".into())),
+            MsError::SearchIndexReadOnly("a".into()),
+            MsError::ReindexRequired("a".into()),
+            MsError::Json(serde_json::from_str::<Value>("{").unwrap_err()),
+            MsError::Yaml(serde_yaml::from_str::<serde_yaml::Value>("a: [").unwrap_err()),
+            MsError::QueryParse("a".into()),
+            MsError::CursorStale("a".into()),
+            MsError::ResultsTruncated("a".into()),
+            MsError::CassUnavailable("a".into()),
+            MsError::CmUnavailable("a".into()),
+            MsError::BeadsUnavailable("a".into()),
+            MsError::MiningFailed("a".into()),
+            MsError::Config("a".into()),
+            MsError::MissingConfig("a".into()),
+            MsError::TransactionFailed("a".into()),
+            MsError::TwoPhaseCommitFailed {
+                phase: "prepare".into(),
+                reason: "a".into(),
+            },
+            MsError::ApprovalRequired("a".into()),
+            MsError::DestructiveBlocked("a".into()),
+            MsError::AcipError("a".into()),
+            MsError::LockTimeout("a".into()),
+            MsError::LockFailed("a".into()),
+            MsError::Busy {
+                resource: "a".into(),
+                pid: Some(1),
+            },
+            MsError::Serialization("a".into()),
+            MsError::NotImplemented("a".into()),
+            MsError::NotFound("a".into()),
+            MsError::Timeout("a".into()),
+            MsError::AssertionFailed("a".into()),
+            MsError::PackBudgetExceeded {
+                budget: 50,
+                required: 80,
+                message: "a".into(),
+            },
+            MsError::CyclicInheritance {
+                skill_id: "a".into(),
+                cycle: vec!["a".into(), "a".into()],
+            },
+            MsError::ParentSkillNotFound {
+                parent_id: "a".into(),
+                child_id: "b".into(),
+                chain: vec!["b".into(), "a".into()],
+            },
+            MsError::Import("a".into()),
+            MsError::AuthError("a".into()),
+            MsError::AgentMail {
+                status: Some(401),
+                retries_exhausted: false,
+                message: "a".into(),
+            },
+            MsError::AgentMail {
+                status: None,
+                retries_exhausted: true,
+                message: "a".into(),
+            },
+        ]
+    }
+
+    const VARIANTS: usize = 39;
+
+    const fn variant_index(err: &MsError) -> usize {
+        match err {
+            MsError::Database(_) => 0,
+            MsError::Git(_) => 1,
+            MsError::Io(_) => 2,
+            MsError::SkillNotFound(_) => 3,
+            MsError::InvalidSkill(_) => 4,
+            MsError::ValidationFailed(_) => 5,
+            MsError::SearchIndex(_) => 6,
+            MsError::SearchIndexReadOnly(_) => 7,
+            MsError::ReindexRequired(_) => 8,
+            MsError::Json(_) => 9,
+            MsError::Yaml(_) => 10,
+            MsError::QueryParse(_) => 11,
+            MsError::CassUnavailable(_) => 12,
+            MsError::CmUnavailable(_) => 13,
+            MsError::BeadsUnavailable(_) => 14,
+            MsError::MiningFailed(_) => 15,
+            MsError::Config(_) => 16,
+            MsError::MissingConfig(_) => 17,
+            MsError::TransactionFailed(_) => 18,
+            MsError::TwoPhaseCommitFailed { .. } => 19,
+            MsError::ApprovalRequired(_) => 20,
+            MsError::DestructiveBlocked(_) => 21,
+            MsError::AcipError(_) => 22,
+            MsError::LockTimeout(_) => 23,
+            MsError::LockFailed(_) => 24,
+            MsError::Serialization(_) => 25,
+            MsError::NotImplemented(_) => 26,
+            MsError::NotFound(_) => 27,
+            MsError::Timeout(_) => 28,
+            MsError::AssertionFailed(_) => 29,
+            MsError::PackBudgetExceeded { .. } => 30,
+            MsError::CyclicInheritance { .. } => 31,
+            MsError::ParentSkillNotFound { .. } => 32,
+            MsError::Import(_) => 33,
+            MsError::AuthError(_) => 34,
+            MsError::AgentMail { .. } => 35,
+            MsError::Busy { .. } => 36,
+            MsError::CursorStale(_) => 37,
+            MsError::ResultsTruncated(_) => 38,
+        }
+    }
+
+    #[test]
+    fn test_every_variant_has_its_own_code() {
+        let errors = one_of_each();
+        let covered: std::collections::BTreeSet<usize> = errors.iter().map(variant_index).collect();
+        assert_eq!(covered.len(), VARIANTS, "one_of_each misses a variant");
+
+        let mut owners = std::collections::HashMap::new();
+        for err in &errors {
+            let owner = *owners.entry(err.code()).or_insert(variant_index(err));
+            assert_eq!(
+                owner,
+                variant_index(err),
+                "{:?} is shared by more than one MsError variant",
+                err.code()
+            );
+        }
+    }
+
+    #[test]
+    fn test_robot_json_carries_code_and_details() {
+        let err = MsError::PackBudgetExceeded {
+            budget: 50,
+            required: 80,
+            message: "--max-tokens 50: policy slices need 80 tokens".into(),
+        };
+        let json = err.to_robot_json();
+        assert_eq!(json["error"], true);
+        assert_eq!(json["code"], "pack_budget_exceeded");
+        assert_eq!(json["exit_code"], 2);
+        assert_eq!(json["details"]["expected_max_tokens"], 50);
+        assert_eq!(json["details"]["actual_tokens"], 80);
+
+        let blocked = MsError::DestructiveBlocked("rm -rf /".into()).to_robot_json();
+        assert_eq!(blocked["code"], "destructive_blocked");
+        assert_eq!(blocked["message"], "rm -rf /");
+        assert_eq!(blocked["exit_code"], 4);
+        assert!(blocked.get("details").is_none());
+    }
+
+    #[test]
+    fn test_busy_names_the_holder() {
+        let err = MsError::Busy {
+            resource: "search index writer".into(),
+            pid: Some(4242),
+        };
+        assert!(err.to_string().contains("held by pid 4242"));
+        let json = err.to_robot_json();
+        assert_eq!(json["code"], "busy");
+        assert_eq!(json["exit_code"], 3);
+        assert_eq!(json["details"]["holder_pid"], 4242);
+        assert!(err.to_structured().suggestion.contains("4242"));
+    }
 }
@@ -30,6 +30,7 @@ pub fn suggest_for_error(code: ErrorCode, context: Option<&Value>) -> String {
         ErrorCode::ConfigMissingRequired => suggest_config_missing_required(context),
         ErrorCode::SearchNoResults => suggest_search_no_results(context),
         ErrorCode::ValidationFailed => suggest_validation_failed(context),
+        ErrorCode::Busy => suggest_busy(context),
         // Fall back to static suggestion for other codes
         _ => code.suggestion().to_string(),
     }
@@ -221,6 +222,19 @@ fn suggest_validation_failed(context: Option<&Value>) -> String {
     }
 }
 
+fn suggest_busy(context: Option<&Value>) -> String {
+    let pid = context
+        .and_then(|c| c.get("holder_pid"))
+        .and_then(Value::as_u64);
+
+    match pid {
+        Some(pid) => format!(
+            "Process {pid} is writing. Retry when it finishes, or raise MS_LOCK_TIMEOUT to wait longer. If pid {pid} is not an ms process, run `ms doctor --check-lock`"
+        ),
+        None => ErrorCode::Busy.suggestion().to_string(),
+    }
+}
+
 /// Get suggestions for similar skills based on a misspelled skill ID.
 ///
 /// This is a helper for `SkillNotFound` errors that can suggest
@@ -0,0 +1,647 @@
+//! Native skill relationship graph (extends, includes, requires).
+//!
+//! Unlike the bv-backed analyses, this graph is built directly from the index
+//! so `ms graph` can render it without external tools. Edges point from a
+//! skill to what it depends on; targets that are not indexed skills are kept
+//! as `missing` edges so they can be reported.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::fmt::Write as _;
+
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+
+use crate::core::SkillSpec;
+use crate::graph::skills::parse_meta;
+use crate::output::TreeChars;
+use crate::storage::sqlite::SkillRecord;
+
+/// How one skill depends on another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EdgeKind {
+    Extends,
+    Includes,
+    Requires,
+}
+
+impl EdgeKind {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Extends => "extends",
+            Self::Includes => "includes",
+            Self::Requires => "requires",
+        }
+    }
+}
+
+/// A dependency edge. `to` is a skill id, or the unresolved reference when
+/// `missing` is set.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SkillEdge {
+    pub from: String,
+    pub to: String,
+    pub kind: EdgeKind,
+    /// Capability named by a `requires` entry, when it is not the target id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capability: Option<String>,
+    pub missing: bool,
+}
+
+/// Relationship fields of one indexed skill.
+#[derive(Debug, Clone, Default)]
+pub struct SkillNode {
+    pub id: String,
+    pub name: String,
+    pub extends: Option<String>,
+    pub includes: Vec<String>,
+    pub requires: Vec<String>,
+    pub provides: Vec<String>,
+}
+
+impl SkillNode {
+    /// Combine the indexed record with its raw (unresolved) spec, which is
+    /// the only place `extends` and `includes` survive indexing.
+    #[must_use]
+    pub fn from_record(record: &SkillRecord, raw: Option<&SkillSpec>) -> Self {
+        let meta = parse_meta(&record.metadata_json);
+        Self {
+            id: record.id.clone(),
+            name: record.name.clone(),
+            extends: raw.and_then(|spec| spec.extends.clone()),
+            includes: raw
+                .map(|spec| spec.includes.iter().map(|inc| inc.skill.clone()).collect())
+                .unwrap_or_default(),
+            requires: meta.requires,
+            provides: meta.provides,
+        }
+    }
+}
+
+/// Directed graph of skills and their dependencies.
+#[derive(Debug, Clone, Default)]
+pub struct SkillGraph {
+    names: BTreeMap<String, String>,
+    edges: Vec<SkillEdge>,
+}
+
+impl SkillGraph {
+    /// Build the graph. `requires` entries resolve to a skill id first and
+    /// then to skills providing the capability (case-insensitive), matching
+    /// how skills are exported to bv.
+    #[must_use]
+    pub fn build(nodes: &[SkillNode]) -> Self {
+        let names: BTreeMap<String, String> = nodes
+            .iter()
+            .map(|node| (node.id.clone(), node.name.clone()))
+            .collect();
+        let mut providers: HashMap<String, BTreeSet<String>> = HashMap::new();
+        for node in nodes {
+            for cap in &node.provides {
+                providers
+                    .entry(cap.to_lowercase())
+                    .or_default()
+                    .insert(node.id.clone());
+            }
+        }
+
+        let mut edges = Vec::new();
+        let mut push = |from: &str, to: &str, kind, capability: Option<&str>, missing| {
+            let edge = SkillEdge {
+                from: from.to_string(),
+                to: to.to_string(),
+                kind,
+                capability: capability.map(str::to_string),
+                missing,
+            };
+            if !edges.contains(&edge) {
+                edges.push(edge);
+            }
+        };
+
+        for node in nodes {
+            let id = node.id.as_str();
+            if let Some(parent) = &node.extends {
+                push(
+                    id,
+                    parent,
+                    EdgeKind::Extends,
+                    None,
+                    !names.contains_key(parent),
+                );
+            }
+            for included in &node.includes {
+                let missing = !names.contains_key(included);
+                push(id, included, EdgeKind::Includes, None, missing);
+            }
+            for req in &node.requires {
+                if names.contains_key(req) {
+                    if req != id {
+                        push(id, req, EdgeKind::Requires, None, false);
+                    }
+                } else if let Some(ids) = providers.get(&req.to_lowercase()) {
+                    for provider in ids.iter().filter(|p| p.as_str() != id) {
+                        push(id, provider, EdgeKind::Requires, Some(req), false);
+                    }
+                } else {
+                    push(id, req, EdgeKind::Requires, Some(req), true);
+                }
+            }
+        }
+
+        Self { names, edges }
+    }
+
+    #[must_use]
+    pub fn node_count(&self) -> usize {
+        self.names.len()
+    }
+
+    #[must_use]
+    pub fn edges(&self) -> &[SkillEdge] {
+        &self.edges
+    }
+
+    #[must_use]
+    pub fn contains(&self, id: &str) -> bool {
+        self.names.contains_key(id)
+    }
+
+    /// Edges whose target is not an indexed skill.
+    pub fn missing(&self) -> impl Iterator<Item = &SkillEdge> {
+        self.edges.iter().filter(|edge| edge.missing)
+    }
+
+    fn outgoing(&self, id: &str) -> impl Iterator<Item = &SkillEdge> {
+        self.edges.iter().filter(move |edge| edge.from == id)
+    }
+
+    /// Keep only `root` and what it transitively depends on, optionally
+    /// limited to `depth` hops.
+    #[must_use]
+    pub fn restrict(&self, root: &str, depth: Option<usize>) -> Self {
+        let mut distance = BTreeMap::from([(root.to_string(), 0usize)]);
+        let mut queue = VecDeque::from([root.to_string()]);
+        let mut edges = Vec::new();
+        while let Some(id) = queue.pop_front() {
+            let dist = distance[&id];
+            if depth.is_some_and(|max| dist >= max) {
+                continue;
+            }
+            for edge in self.outgoing(&id) {
+                edges.push(edge.clone());
+                if !edge.missing && !distance.contains_key(&edge.to) {
+                    distance.insert(edge.to.clone(), dist + 1);
+                    queue.push_back(edge.to.clone());
+                }
+            }
+        }
+        let names = self
+            .names
+            .iter()
+            .filter(|(id, _)| distance.contains_key(*id))
+            .map(|(id, name)| (id.clone(), name.clone()))
+            .collect();
+        Self { names, edges }
+    }
+
+    /// Strongly connected groups of skills that depend on each other,
+    /// including skills that depend on themselves. Each cycle is sorted.
+    #[must_use]
+    pub fn cycles(&self) -> Vec<Vec<String>> {
+        let mut tarjan = Tarjan::default();
+        for id in self.names.keys() {
+            if !tarjan.index.contains_key(id.as_str()) {
+                tarjan.visit(self, id);
+            }
+        }
+        let mut cycles: Vec<Vec<String>> = tarjan
+            .components
+            .into_iter()
+            .filter(|component| {
+                component.len() > 1
+                    || self
+                        .outgoing(&component[0])
+                        .any(|edge| !edge.missing && edge.to == component[0])
+            })
+            .map(|mut component| {
+                component.sort();
+                component
+            })
+            .collect();
+        cycles.sort();
+        cycles
+    }
+
+    fn cycle_edge_flags(&self) -> Vec<bool> {
+        let mut component = HashMap::new();
+        for (idx, cycle) in self.cycles().iter().enumerate() {
+            for id in cycle {
+                component.insert(id.clone(), idx);
+            }
+        }
+        self.edges
+            .iter()
+            .map(|edge| {
+                !edge.missing
+                    && component.get(&edge.from).is_some()
+                    && component.get(&edge.from) == component.get(&edge.to)
+            })
+            .collect()
+    }
+
+    /// Graphviz DOT. Cycle edges are red; missing targets are dashed.
+    #[must_use]
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph skills {\n  rankdir=LR;\n  node [shape=box];\n");
+        for (id, name) in &self.names {
+            let _ = writeln!(
+                out,
+                "  {} [label={}];",
+                dot_quote(id),
+                dot_quote(&node_label(id, name))
+            );
+        }
+        let missing: BTreeSet<&str> = self.missing().map(|edge| edge.to.as_str()).collect();
+        for id in missing {
+            let _ = writeln!(
+                out,
+                "  {} [label={}, style=dashed, color=red];",
+                dot_quote(id),
+                dot_quote(&format!("{id} (missing)"))
+            );
+        }
+        for (edge, in_cycle) in self.edges.iter().zip(self.cycle_edge_flags()) {
+            let mut attrs = vec![format!("label={}", dot_quote(&edge_label(edge)))];
+            if in_cycle {
+                attrs.push("color=red".to_string());
+            }
+            if edge.missing {
+                attrs.push("style=dashed".to_string());
+            }
+            let _ = writeln!(
+                out,
+                "  {} -> {} [{}];",
+                dot_quote(&edge.from),
+                dot_quote(&edge.to),
+                attrs.join(", ")
+            );
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Mermaid flowchart. Cycle edges and missing targets are styled red.
+    #[must_use]
+    pub fn to_mermaid(&self) -> String {
+        let mut ids: BTreeMap<&str, String> = BTreeMap::new();
+        let mut out = String::from("graph LR\n");
+        for (id, name) in &self.names {
+            let key = format!("n{}", ids.len());
+            let _ = writeln!(
+                out,
+                "  {key}[\"{}\"]",
+                mermaid_escape(&node_label(id, name))
+            );
+            ids.insert(id, key);
+        }
+        let mut missing_keys = Vec::new();
+        for edge in self.missing() {
+            if !ids.contains_key(edge.to.as_str()) {
+                let key = format!("n{}", ids.len());
+                let _ = writeln!(out, "  {key}[\"{} (missing)\"]", mermaid_escape(&edge.to));
+                missing_keys.push(key.clone());
+                ids.insert(&edge.to, key);
+            }
+        }
+        let mut cycle_links = Vec::new();
+        for (idx, (edge, in_cycle)) in self.edges.iter().zip(self.cycle_edge_flags()).enumerate() {
+            let arrow = if edge.missing { "-.->" } else { "-->" };
+            let _ = writeln!(
+                out,
+                "  {} {arrow}|{}| {}",
+                ids[edge.from.as_str()],
+                mermaid_escape(&edge_label(edge)),
+                ids[edge.to.as_str()]
+            );
+            if in_cycle {
+                cycle_links.push(idx.to_string());
+            }
+        }
+        if !missing_keys.is_empty() {
+            out.push_str("  classDef missing stroke:#d33,stroke-dasharray:4 4\n");
+            let _ = writeln!(out, "  class {} missing", missing_keys.join(","));
+        }
+        if !cycle_links.is_empty() {
+            let _ = writeln!(out, "  linkStyle {} stroke:#d33", cycle_links.join(","));
+        }
+        out
+    }
+
+    /// Adjacency list for robot output.
+    #[must_use]
+    pub fn to_json(&self) -> JsonValue {
+        let nodes: Vec<JsonValue> = self
+            .names
+            .iter()
+            .map(|(id, name)| {
+                let depends_on: Vec<&SkillEdge> = self.outgoing(id).collect();
+                serde_json::json!({
+                    "id": id,
+                    "name": name,
+                    "depends_on": depends_on,
+                })
+            })
+            .collect();
+        let missing: Vec<&SkillEdge> = self.missing().collect();
+        serde_json::json!({
+            "node_count": self.node_count(),
+            "edge_count": self.edges.len(),
+            "nodes": nodes,
+            "cycles": self.cycles(),
+            "missing": missing,
+        })
+    }
+
+    /// Indented dependency tree. Roots are skills nothing depends on (or
+    /// `roots` when given); skills left unreached, such as pure cycles, are
+    /// added as extra roots. A skill is expanded once; later mentions are
+    /// marked instead of repeated.
+    #[must_use]
+    pub fn render_tree(&self, roots: &[String], chars: TreeChars, depth: Option<usize>) -> String {
+        let mut roots = roots.to_vec();
+        if roots.is_empty() {
+            let targets: BTreeSet<&str> = self
+                .edges
+                .iter()
+                .filter(|edge| !edge.missing && edge.from != edge.to)
+                .map(|edge| edge.to.as_str())
+                .collect();
+            roots = self
+                .names
+                .keys()
+                .filter(|id| !targets.contains(id.as_str()))
+                .cloned()
+                .collect();
+        }
+
+        let mut tree = TreeWriter {
+            graph: self,
+            chars,
+            depth,
+            expanded: BTreeSet::new(),
+            path: Vec::new(),
+            out: String::new(),
+        };
+        for root in &roots {
+            tree.root(root);
+        }
+        for id in self.names.keys() {
+            if !tree.expanded.contains(id) {
+                tree.root(id);
+            }
+        }
+        tree.out
+    }
+}
+
+fn node_label(id: &str, name: &str) -> String {
+    if name.is_empty() || name == id {
+        id.to_string()
+    } else {
+        format!("{name}\n{id}")
+    }
+}
+
+fn edge_label(edge: &SkillEdge) -> String {
+    match &edge.capability {
+        Some(cap) if cap != &edge.to => format!("{} {cap}", edge.kind.as_str()),
+        _ => edge.kind.as_str().to_string(),
+    }
+}
+
+fn dot_quote(value: &str) -> String {
+    format!(
+        "\"{}\"",
+        value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+    )
+}
+
+fn mermaid_escape(value: &str) -> String {
+    value
+        .replace('"', "#quot;")
+        .replace('|', "#124;")
+        .replace('\n', "<br/>")
+}
+
+#[derive(Default)]
+struct Tarjan<'a> {
+    next: usize,
+    index: HashMap<&'a str, usize>,
+    lowlink: HashMap<&'a str, usize>,
+    stack: Vec<&'a str>,
+    on_stack: BTreeSet<&'a str>,
+    components: Vec<Vec<String>>,
+}
+
+impl<'a> Tarjan<'a> {
+    fn visit(&mut self, graph: &'a SkillGraph, id: &'a str) {
+        self.index.insert(id, self.next);
+        self.lowlink.insert(id, self.next);
+        self.next += 1;
+        self.stack.push(id);
+        self.on_stack.insert(id);
+
+        for edge in graph.outgoing(id).filter(|edge| !edge.missing) {
+            let to = edge.to.as_str();
+            if !self.index.contains_key(to) {
+                self.visit(graph, to);
+                let low = self.lowlink[id].min(self.lowlink[to]);
+                self.lowlink.insert(id, low);
+            } else if self.on_stack.contains(to) {
+                let low = self.lowlink[id].min(self.index[to]);
+                self.lowlink.insert(id, low);
+            }
+        }
+
+        if self.lowlink[id] == self.index[id] {
+            let mut component = Vec::new();
+            while let Some(member) = self.stack.pop() {
+                self.on_stack.remove(member);
+                component.push(member.to_string());
+                if member == id {
+                    break;
+                }
+            }
+            self.components.push(component);
+        }
+    }
+}
+
+struct TreeWriter<'a> {
+    graph: &'a SkillGraph,
+    chars: TreeChars,
+    depth: Option<usize>,
+    expanded: BTreeSet<String>,
+    path: Vec<String>,
+    out: String,
+}
+
+impl TreeWriter<'_> {
+    fn root(&mut self, id: &str) {
+        let name = self.graph.names.get(id).map_or("", String::as_str);
+        if name.is_empty() || name == id {
+            let _ = writeln!(self.out, "{id}");
+        } else {
+            let _ = writeln!(self.out, "{id} ({name})");
+        }
+        self.expand(id, "");
+    }
+
+    fn expand(&mut self, id: &str, prefix: &str) {
+        self.expanded.insert(id.to_string());
+        if self.depth.is_some_and(|max| self.path.len() >= max) {
+            return;
+        }
+        self.path.push(id.to_string());
+        let children: Vec<SkillEdge> = self.graph.outgoing(id).cloned().collect();
+        for (idx, edge) in children.iter().enumerate() {
+            let last = idx + 1 == children.len();
+            let guide = if last {
+                self.chars.last
+            } else {
+                self.chars.branch
+            };
+            let marker = if edge.missing {
+                " (missing)"
+            } else if self.path.contains(&edge.to) {
+                " (cycle)"
+            } else if self.expanded.contains(&edge.to) {
+                " (see above)"
+            } else {
+                ""
+            };
+            let _ = writeln!(
+                self.out,
+                "{prefix}{guide}{h}{h} {} {}{marker}",
+                edge_label(edge),
+                edge.to,
+                h = self.chars.horizontal
+            );
+            if marker.is_empty() {
+                let child_prefix = if last {
+                    format!("{prefix}    ")
+                } else {
+                    format!("{prefix}{}   ", self.chars.vertical)
+                };
+                self.expand(&edge.to, &child_prefix);
+            }
+        }
+        self.path.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::TreeGuides;
+
+    fn node(id: &str) -> SkillNode {
+        SkillNode {
+            id: id.to_string(),
+            name: id.to_string(),
+            ..SkillNode::default()
+        }
+    }
+
+    /// app extends base, requires "logging" (provided by log) and "metrics"
+    /// (provided by nothing); a and b extend each other.
+    fn sample() -> SkillGraph {
+        let mut app = node("app");
+        app.extends = Some("base".to_string());
+        app.requires = vec!["Logging".to_string(), "metrics".to_string()];
+        let mut log = node("log");
+        log.provides = vec!["logging".to_string()];
+        log.includes = vec!["base".to_string()];
+        let mut a = node("a");
+        a.extends = Some("b".to_string());
+        let mut b = node("b");
+        b.extends = Some("a".to_string());
+        SkillGraph::build(&[app, node("base"), log, a, b])
+    }
+
+    #[test]
+    fn build_resolves_capabilities_and_flags_missing() {
+        let graph = sample();
+        let missing: Vec<_> = graph.missing().map(|edge| edge.to.as_str()).collect();
+        assert_eq!(missing, vec!["metrics"]);
+        assert!(graph.edges().iter().any(|edge| edge.from == "app"
+            && edge.to == "log"
+            && edge.capability.as_deref() == Some("Logging")));
+        assert_eq!(graph.cycles(), vec![vec!["a".to_string(), "b".to_string()]]);
+    }
+
+    #[test]
+    fn restrict_follows_transitive_closure_and_depth() {
+        let graph = sample();
+        let closure = graph.restrict("app", None);
+        assert_eq!(closure.node_count(), 3);
+        assert!(closure.contains("base") && closure.contains("log"));
+        assert!(!closure.contains("a"));
+
+        let shallow = graph.restrict("log", Some(0));
+        assert_eq!(shallow.node_count(), 1);
+        assert!(shallow.edges().is_empty());
+    }
+
+    #[test]
+    fn dot_and_mermaid_highlight_problems() {
+        let graph = sample();
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph skills {"));
+        assert!(
+            dot.contains("\"metrics\" [label=\"metrics (missing)\", style=dashed, color=red];")
+        );
+        assert!(dot.contains("\"a\" -> \"b\" [label=\"extends\", color=red];"));
+
+        let mermaid = graph.to_mermaid();
+        assert!(mermaid.starts_with("graph LR\n"));
+        assert!(mermaid.contains("-.->|requires| "));
+        assert!(mermaid.contains("class n5 missing"));
+        assert!(mermaid.contains("linkStyle "));
+    }
+
+    #[test]
+    fn json_is_an_adjacency_list() {
+        let json = sample().to_json();
+        assert_eq!(json["node_count"], 5);
+        let app = json["nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|node| node["id"] == "app")
+            .unwrap();
+        assert_eq!(app["depends_on"].as_array().unwrap().len(), 3);
+        assert_eq!(json["missing"][0]["to"], "metrics");
+    }
+
+    #[test]
+    fn tree_marks_cycles_missing_and_repeats() {
+        let tree = sample().render_tree(&[], TreeGuides::Ascii.chars(), None);
+        let expected = "\
+app
++-- extends base
++-- requires Logging log
+|   `-- includes base (see above)
+`-- requires metrics (missing)
+a
+`-- extends b
+    `-- extends a (cycle)
+";
+        assert_eq!(tree, expected);
+    }
+}
@@ -1,4 +1,5 @@
 //! Graph analysis modules.
 
 pub mod bv;
+pub mod deps;
 pub mod skills;
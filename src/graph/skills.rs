@@ -9,10 +9,10 @@ use crate::error::Result;
 use crate::storage::sqlite::SkillRecord;
 
 #[derive(Debug, Default, Clone)]
-struct SkillMeta {
-    tags: Vec<String>,
-    requires: Vec<String>,
-    provides: Vec<String>,
+pub(crate) struct SkillMeta {
+    pub(crate) tags: Vec<String>,
+    pub(crate) requires: Vec<String>,
+    pub(crate) provides: Vec<String>,
 }
 
 pub fn skills_to_issues(skills: &[SkillRecord]) -> Result<Vec<Issue>> {
@@ -113,7 +113,7 @@ pub fn skills_to_issues(skills: &[SkillRecord]) -> Result<Vec<Issue>> {
     Ok(issues)
 }
 
-fn parse_meta(metadata_json: &str) -> SkillMeta {
+pub(crate) fn parse_meta(metadata_json: &str) -> SkillMeta {
     let parsed: serde_json::Value = serde_json::from_str(metadata_json).unwrap_or_default();
     SkillMeta {
         tags: parse_list(&parsed, "tags"),
@@ -11,6 +11,9 @@
 //! 2. **Block Classifiers** - Classify each block by type
 //! 3. **Skill Generator** - Transform classified blocks into SkillSpec
 //!
+//! [`repo_rules`] wraps the pipeline to import Cursor and Copilot rule files
+//! discovered in a repository, one SkillSpec per file.
+//!
 //! # Example
 //!
 //! ```ignore
@@ -32,6 +35,7 @@ mod classifiers;
 pub mod formatting;
 mod generator;
 mod parser;
+pub mod repo_rules;
 mod types;
 
 pub use classifiers::*;
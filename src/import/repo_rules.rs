@@ -0,0 +1,448 @@
+//! Discovery and import of agent rule files checked into a repository.
+//!
+//! Recognizes the Cursor and GitHub Copilot file conventions:
+//!
+//! - `.cursor/rules/*.mdc` (MDC frontmatter with `description`, `globs`, `alwaysApply`)
+//! - `.cursorrules` (legacy single-file Cursor rules)
+//! - `.github/copilot-instructions.md` (repository-wide Copilot instructions)
+//! - `.github/instructions/*.instructions.md` (path-scoped, `applyTo` frontmatter)
+//!
+//! Each discovered file becomes one `SkillSpec`: the body runs through the
+//! regular [`ContentParser`] and [`SkillGenerator`] pipeline, frontmatter globs
+//! become `context.file_patterns`, and the source is recorded in
+//! `metadata.imported_from`.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use walkdir::WalkDir;
+
+use super::formatting::slugify;
+use super::generator::{GeneratedSkill, GeneratorConfig, ImportHints, SkillGenerator};
+use super::parser::ContentParser;
+use crate::core::skill::ImportProvenance;
+use crate::error::{MsError, Result};
+
+/// Directories never descended into while looking for rule files.
+const SKIP_DIRS: &[&str] = &[".git", "node_modules", "target", "dist", "build", ".venv"];
+
+/// Known rule file conventions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RuleFileFormat {
+    /// `.cursor/rules/*.mdc`
+    CursorRule,
+    /// `.cursorrules`
+    CursorRulesFile,
+    /// `.github/copilot-instructions.md`
+    CopilotInstructions,
+    /// `.github/instructions/*.instructions.md`
+    CopilotScopedInstructions,
+}
+
+impl RuleFileFormat {
+    /// Stable identifier recorded in import provenance.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::CursorRule => "cursor-rule",
+            Self::CursorRulesFile => "cursorrules",
+            Self::CopilotInstructions => "copilot-instructions",
+            Self::CopilotScopedInstructions => "copilot-scoped-instructions",
+        }
+    }
+
+    /// Classify a file by its path relative to the repository root.
+    #[must_use]
+    pub fn detect(relative: &Path) -> Option<Self> {
+        let file_name = relative.file_name()?.to_str()?;
+        let parent = relative.parent().map(components_of).unwrap_or_default();
+        let parent_ends_with = |suffix: &[&str]| parent.ends_with(suffix);
+
+        if file_name == ".cursorrules" {
+            Some(Self::CursorRulesFile)
+        } else if file_name.ends_with(".mdc") && parent_ends_with(&[".cursor", "rules"]) {
+            Some(Self::CursorRule)
+        } else if file_name == "copilot-instructions.md" && parent_ends_with(&[".github"]) {
+            Some(Self::CopilotInstructions)
+        } else if file_name.ends_with(".instructions.md")
+            && parent_ends_with(&[".github", "instructions"])
+        {
+            Some(Self::CopilotScopedInstructions)
+        } else {
+            None
+        }
+    }
+}
+
+impl std::fmt::Display for RuleFileFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+fn components_of(path: &Path) -> Vec<&str> {
+    path.components()
+        .filter_map(|c| c.as_os_str().to_str())
+        .collect()
+}
+
+/// A rule file found under a repository root.
+#[derive(Debug, Clone)]
+pub struct RuleFile {
+    /// Which convention the file follows.
+    pub format: RuleFileFormat,
+    /// Absolute (or caller-relative) path to the file.
+    pub path: PathBuf,
+    /// Path relative to the repository root, using `/` separators.
+    pub relative: String,
+}
+
+/// Frontmatter fields understood by rule file conventions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RuleFrontmatter {
+    /// Cursor `description`.
+    pub description: Option<String>,
+    /// Cursor `globs` or Copilot `applyTo`, split into individual patterns.
+    pub globs: Vec<String>,
+    /// Cursor `alwaysApply`.
+    pub always_apply: bool,
+}
+
+/// One skill produced from one rule file.
+#[derive(Debug, Clone)]
+pub struct RepoRuleImport {
+    /// The source file.
+    pub source: RuleFile,
+    /// Parsed frontmatter of the source file.
+    pub frontmatter: RuleFrontmatter,
+    /// The generated skill, with ID, applicability, and provenance applied.
+    pub generated: GeneratedSkill,
+}
+
+/// Find all known rule files under `root`, sorted by relative path.
+pub fn discover_rule_files(root: &Path) -> Result<Vec<RuleFile>> {
+    if !root.is_dir() {
+        return Err(MsError::Import(format!(
+            "Repository root is not a directory: {}",
+            root.display()
+        )));
+    }
+
+    let walker = WalkDir::new(root)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|entry| {
+            if !entry.file_type().is_dir() || entry.depth() == 0 {
+                return true;
+            }
+            let name = entry.file_name().to_string_lossy();
+            !SKIP_DIRS.contains(&name.as_ref())
+        })
+        .filter_map(std::result::Result::ok);
+
+    let mut files = Vec::new();
+    for entry in walker {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let Ok(relative) = entry.path().strip_prefix(root) else {
+            continue;
+        };
+        if let Some(format) = RuleFileFormat::detect(relative) {
+            files.push(RuleFile {
+                format,
+                path: entry.path().to_path_buf(),
+                relative: components_of(relative).join("/"),
+            });
+        }
+    }
+
+    files.sort_by(|a, b| a.relative.cmp(&b.relative));
+    Ok(files)
+}
+
+/// Split a leading `---` frontmatter block off `content`.
+///
+/// MDC frontmatter is YAML-like but not always valid YAML (`globs: *.ts` is an
+/// alias in YAML), so fields are read line by line. Unknown keys are ignored.
+/// Content without frontmatter is returned unchanged.
+#[must_use]
+pub fn split_frontmatter(content: &str) -> (RuleFrontmatter, &str) {
+    let mut frontmatter = RuleFrontmatter::default();
+
+    let Some(rest) = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))
+    else {
+        return (frontmatter, content);
+    };
+
+    let mut header_len = 0;
+    let mut closed = false;
+    for line in rest.split_inclusive('\n') {
+        header_len += line.len();
+        if line.trim() == "---" {
+            closed = true;
+            break;
+        }
+    }
+    if !closed {
+        return (frontmatter, content);
+    }
+
+    let header = &rest[..header_len];
+    let body = &rest[header_len..];
+
+    let mut list_key: Option<String> = None;
+    for line in header.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed == "---" || trimmed.starts_with('#') {
+            continue;
+        }
+
+        if let Some(item) = trimmed.strip_prefix("- ") {
+            if matches!(list_key.as_deref(), Some("globs" | "applyTo")) {
+                frontmatter.globs.extend(split_globs(item));
+            }
+            continue;
+        }
+
+        let Some((key, value)) = trimmed.split_once(':') else {
+            continue;
+        };
+        let key = key.trim();
+        let value = value.trim();
+        list_key = value.is_empty().then(|| key.to_string());
+
+        match key {
+            "description" if !value.is_empty() => {
+                frontmatter.description = Some(unquote(value).to_string());
+            }
+            "globs" | "applyTo" => frontmatter.globs.extend(split_globs(value)),
+            "alwaysApply" => frontmatter.always_apply = value.eq_ignore_ascii_case("true"),
+            _ => {}
+        }
+    }
+
+    (frontmatter, body)
+}
+
+fn unquote(value: &str) -> &str {
+    let value = value.trim();
+    for quote in ['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|v| v.strip_suffix(quote))
+        {
+            return inner;
+        }
+    }
+    value
+}
+
+fn split_globs(value: &str) -> Vec<String> {
+    let value = value.trim();
+    let value = value
+        .strip_prefix('[')
+        .and_then(|v| v.strip_suffix(']'))
+        .unwrap_or(value);
+    value
+        .split(',')
+        .map(unquote)
+        .filter(|glob| !glob.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Skill ID derived from a rule file name alone.
+fn base_id(file: &RuleFile) -> String {
+    let file_name = file.relative.rsplit('/').next().unwrap_or(&file.relative);
+    let stem = match file.format {
+        RuleFileFormat::CursorRule => file_name.trim_end_matches(".mdc"),
+        RuleFileFormat::CursorRulesFile => "cursorrules",
+        RuleFileFormat::CopilotInstructions => "copilot-instructions",
+        RuleFileFormat::CopilotScopedInstructions => file_name.trim_end_matches(".instructions.md"),
+    };
+    let id = slugify(stem);
+    if id.is_empty() {
+        slugify(&file.relative)
+    } else {
+        id
+    }
+}
+
+/// Assign a skill ID to every file, falling back to the slugified relative
+/// path for files whose name-derived IDs collide.
+fn assign_ids(files: &[RuleFile]) -> Vec<String> {
+    let base: Vec<String> = files.iter().map(base_id).collect();
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for id in &base {
+        *counts.entry(id.as_str()).or_default() += 1;
+    }
+
+    files
+        .iter()
+        .zip(&base)
+        .map(|(file, id)| {
+            if counts[id.as_str()] > 1 {
+                let without_ext = file
+                    .relative
+                    .trim_end_matches(".instructions.md")
+                    .trim_end_matches(".mdc")
+                    .trim_end_matches(".md");
+                slugify(without_ext)
+            } else {
+                id.clone()
+            }
+        })
+        .collect()
+}
+
+/// Discover and import every rule file under `root`.
+///
+/// `hints` supplies the domain and extra tags; per-file IDs are always derived
+/// from the file names so `hints.suggested_id` is ignored.
+pub fn import_repo_rules(
+    root: &Path,
+    config: &GeneratorConfig,
+    hints: &ImportHints,
+) -> Result<Vec<RepoRuleImport>> {
+    let files = discover_rule_files(root)?;
+    let ids = assign_ids(&files);
+    let parser = ContentParser::new();
+    let generator = SkillGenerator::with_config(config.clone());
+
+    let mut imports = Vec::with_capacity(files.len());
+    for (file, id) in files.into_iter().zip(ids) {
+        let content = std::fs::read_to_string(&file.path)
+            .map_err(|e| MsError::Import(format!("Failed to read {}: {e}", file.path.display())))?;
+        let (frontmatter, body) = split_frontmatter(&content);
+
+        let file_hints = ImportHints {
+            suggested_id: Some(id.clone()),
+            suggested_name: None,
+            source_filename: Some(id),
+            domain: hints.domain.clone(),
+            tags: hints.tags.clone(),
+        };
+        let mut generated = generator.generate(parser.parse(body), &file_hints);
+
+        let metadata = &mut generated.skill.metadata;
+        if let Some(description) = &frontmatter.description {
+            metadata.description = description.clone();
+        } else if metadata.description.is_empty() {
+            metadata.description = format!("Imported from {}", file.relative);
+        }
+        for glob in &frontmatter.globs {
+            if !metadata.context.file_patterns.contains(glob) {
+                metadata.context.file_patterns.push(glob.clone());
+            }
+        }
+        metadata.imported_from = Some(ImportProvenance {
+            format: file.format.as_str().to_string(),
+            path: file.relative.clone(),
+            always_apply: frontmatter.always_apply,
+        });
+
+        imports.push(RepoRuleImport {
+            source: file,
+            frontmatter,
+            generated,
+        });
+    }
+
+    Ok(imports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule_file(relative: &str) -> RuleFile {
+        RuleFile {
+            format: RuleFileFormat::detect(Path::new(relative)).unwrap(),
+            path: PathBuf::from(relative),
+            relative: relative.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_detect_formats() {
+        let cases = [
+            (".cursorrules", Some(RuleFileFormat::CursorRulesFile)),
+            (
+                ".cursor/rules/testing.mdc",
+                Some(RuleFileFormat::CursorRule),
+            ),
+            (
+                "apps/web/.cursor/rules/ui.mdc",
+                Some(RuleFileFormat::CursorRule),
+            ),
+            (
+                ".github/copilot-instructions.md",
+                Some(RuleFileFormat::CopilotInstructions),
+            ),
+            (
+                ".github/instructions/docs.instructions.md",
+                Some(RuleFileFormat::CopilotScopedInstructions),
+            ),
+            ("docs/testing.mdc", None),
+            ("copilot-instructions.md", None),
+            (".github/workflows/ci.yml", None),
+        ];
+        for (path, expected) in cases {
+            assert_eq!(RuleFileFormat::detect(Path::new(path)), expected, "{path}");
+        }
+    }
+
+    #[test]
+    fn test_split_frontmatter_inline_globs() {
+        let content = "---\ndescription: \"Rust tests\"\nglobs: src/**/*.rs, *.toml\nalwaysApply: true\n---\n\n- Always test.\n";
+        let (fm, body) = split_frontmatter(content);
+        assert_eq!(fm.description.as_deref(), Some("Rust tests"));
+        assert_eq!(fm.globs, vec!["src/**/*.rs", "*.toml"]);
+        assert!(fm.always_apply);
+        assert_eq!(body, "\n- Always test.\n");
+    }
+
+    #[test]
+    fn test_split_frontmatter_list_globs_and_apply_to() {
+        let (fm, _) = split_frontmatter("---\nglobs:\n  - \"a/**\"\n  - 'b/*.ts'\n---\nbody");
+        assert_eq!(fm.globs, vec!["a/**", "b/*.ts"]);
+        assert!(!fm.always_apply);
+
+        let (fm, _) = split_frontmatter("---\napplyTo: \"docs/**/*.md,README.md\"\n---\nbody");
+        assert_eq!(fm.globs, vec!["docs/**/*.md", "README.md"]);
+    }
+
+    #[test]
+    fn test_split_frontmatter_absent_or_unclosed() {
+        let (fm, body) = split_frontmatter("# Title\n\nText");
+        assert_eq!(fm, RuleFrontmatter::default());
+        assert_eq!(body, "# Title\n\nText");
+
+        let unclosed = "---\ndescription: x\nno end";
+        let (fm, body) = split_frontmatter(unclosed);
+        assert_eq!(fm, RuleFrontmatter::default());
+        assert_eq!(body, unclosed);
+    }
+
+    #[test]
+    fn test_assign_ids_disambiguates_collisions() {
+        let files = vec![
+            rule_file(".cursor/rules/testing.mdc"),
+            rule_file(".cursor/rules/commits.mdc"),
+            rule_file("packages/web/.cursor/rules/testing.mdc"),
+            rule_file(".github/instructions/docs.instructions.md"),
+        ];
+        assert_eq!(
+            assign_ids(&files),
+            vec![
+                "cursor-rules-testing",
+                "commits",
+                "packages-web-cursor-rules-testing",
+                "docs",
+            ]
+        );
+    }
+}
@@ -21,6 +21,7 @@ pub mod output;
 pub mod quality;
 pub mod search;
 pub mod security;
+pub mod signals;
 pub mod simulation;
 pub mod skill_md;
 pub mod storage;
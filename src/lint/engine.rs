@@ -264,6 +264,7 @@ impl ValidationEngine {
                 category: r.category(),
                 default_severity: r.default_severity(),
                 can_fix: r.can_fix(),
+                opt_in: r.opt_in(),
                 disabled: self.config.is_rule_disabled(r.id()),
             })
             .collect()
@@ -285,6 +286,8 @@ pub struct RuleInfo {
     pub default_severity: Severity,
     /// Whether the rule supports auto-fix
     pub can_fix: bool,
+    /// Whether the rule only runs when requested
+    pub opt_in: bool,
     /// Whether the rule is disabled
     pub disabled: bool,
 }
@@ -57,5 +57,6 @@ pub use rule::{BoxedRule, ValidationRule};
 
 // Re-export rule collection functions
 pub use rules::{
-    all_rules, performance_rules, quality_rules, reference_rules, security_rules, structural_rules,
+    all_rules, all_rules_with_secrets, configured_rules, performance_rules, quality_rules,
+    reference_rules, security_rules, structural_rules,
 };
@@ -34,6 +34,11 @@ pub trait ValidationRule: Send + Sync {
         false
     }
 
+    /// Does this rule only run when named in `ms lint --rules`?
+    fn opt_in(&self) -> bool {
+        false
+    }
+
     /// Apply auto-fix for issues (if `can_fix()` is true).
     /// Returns Ok(()) if fix was applied successfully.
     fn fix(&self, _skill: &mut SkillSpec, _diagnostic: &Diagnostic) -> Result<()> {
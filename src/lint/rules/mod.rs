@@ -25,18 +25,24 @@ pub mod reference;
 pub mod security;
 pub mod structural;
 
+use std::sync::Arc;
+
+use crate::core::tokenizer::TokenCounter;
 use crate::lint::rule::BoxedRule;
+use crate::security::SecretScanner;
 
 // Re-export individual rules for direct use
 pub use quality::{
-    ActionableRulesRule, BalancedContentRule, EmbeddingQualityRule, ExamplesHaveCodeRule,
-    MeaningfulDescriptionRule, TokenBudgetRule,
+    ActionableRulesRule, BalancedContentRule, DoctestExpectedOutputRule, EmbeddingQualityRule,
+    ExamplesHaveCodeRule, MeaningfulDescriptionRule, TokenBudgetRule,
+};
+pub use reference::{
+    DeepInheritanceRule, FormatVersionRule, NoCycleRule, ValidExtendsRule, ValidFollowUpsRule,
 };
-pub use reference::{DeepInheritanceRule, FormatVersionRule, NoCycleRule, ValidExtendsRule};
 pub use security::{InputSanitizationRule, NoPromptInjectionRule, NoSecretsRule, SafePathsRule};
 pub use structural::{
     NonEmptyBlocksRule, RequiredMetadataRule, UniqueBlockIdsRule, UniqueSectionIdsRule,
-    ValidVersionRule,
+    ValidApplicabilityRule, ValidVersionRule,
 };
 
 /// Returns all structural validation rules.
@@ -82,6 +88,42 @@ pub fn all_rules() -> Vec<BoxedRule> {
     rules
 }
 
+/// All built-in rules, with `no-secrets` using the given scanner's custom
+/// patterns and allowlist (see `[security.secrets]`).
+#[must_use]
+pub fn all_rules_with_secrets(scanner: &SecretScanner) -> Vec<BoxedRule> {
+    all_rules()
+        .into_iter()
+        .map(|rule| {
+            if rule.id() == "no-secrets" {
+                Box::new(NoSecretsRule::default().with_scanner(scanner.clone())) as BoxedRule
+            } else {
+                rule
+            }
+        })
+        .collect()
+}
+
+/// All built-in rules as configured: `no-secrets` as in
+/// [`all_rules_with_secrets`], and `token-budget` counting with `counter`
+/// (see `[packing].tokenizer`).
+#[must_use]
+pub fn configured_rules(
+    scanner: &SecretScanner,
+    counter: &Arc<dyn TokenCounter + Send + Sync>,
+) -> Vec<BoxedRule> {
+    all_rules_with_secrets(scanner)
+        .into_iter()
+        .map(|rule| {
+            if rule.id() == "token-budget" {
+                Box::new(TokenBudgetRule::default().with_counter(Arc::clone(counter))) as BoxedRule
+            } else {
+                rule
+            }
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -90,20 +132,20 @@ mod tests {
     fn test_all_rules_not_empty() {
         let rules = all_rules();
         assert!(!rules.is_empty());
-        // Should have: 5 structural + 4 reference + 4 security + 4 quality + 2 performance = 19
-        assert!(rules.len() >= 19);
+        // Should have: 6 structural + 5 reference + 4 security + 5 quality + 2 performance = 22
+        assert!(rules.len() >= 22);
     }
 
     #[test]
     fn test_structural_rules_count() {
         let rules = structural_rules();
-        assert_eq!(rules.len(), 5);
+        assert_eq!(rules.len(), 6);
     }
 
     #[test]
     fn test_reference_rules_count() {
         let rules = reference_rules();
-        assert_eq!(rules.len(), 4);
+        assert_eq!(rules.len(), 5);
     }
 
     #[test]
@@ -115,7 +157,7 @@ mod tests {
     #[test]
     fn test_quality_rules_count() {
         let rules = quality_rules();
-        assert_eq!(rules.len(), 4);
+        assert_eq!(rules.len(), 5);
     }
 
     #[test]
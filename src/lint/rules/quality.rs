@@ -3,7 +3,11 @@
 //! These rules check for content quality (descriptions, actionable rules, examples)
 //! and provide performance guidance (token budgets, embedding quality).
 
+use std::path::Path;
+use std::sync::Arc;
+
 use crate::core::skill::{BlockType, SkillSpec};
+use crate::core::tokenizer::{HeuristicCounter, TokenCounter};
 use crate::lint::config::ValidationContext;
 use crate::lint::diagnostic::{Diagnostic, RuleCategory, Severity};
 use crate::lint::rule::ValidationRule;
@@ -66,7 +70,7 @@ fn estimate_code_ratio(content: &str) -> f64 {
 }
 
 /// Estimate token count from text (rough: ~4 chars per token).
-const fn estimate_tokens(text: &str) -> usize {
+pub const fn estimate_tokens(text: &str) -> usize {
     text.len() / 4
 }
 
@@ -394,6 +398,107 @@ impl ValidationRule for ExamplesHaveCodeRule {
     }
 }
 
+/// Rule that checks every annotated doctest example has an expected-output block.
+pub struct DoctestExpectedOutputRule;
+
+impl ValidationRule for DoctestExpectedOutputRule {
+    fn id(&self) -> &'static str {
+        "doctest-expected-output"
+    }
+
+    fn name(&self) -> &'static str {
+        "Doctest Expected Output"
+    }
+
+    fn description(&self) -> &'static str {
+        "Examples annotated as doctests must be followed by an expected-output block"
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Quality
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn validate(&self, ctx: &ValidationContext<'_>) -> Vec<Diagnostic> {
+        crate::testing::doctest::extract_doctests(ctx.skill)
+            .into_iter()
+            .filter(|doctest| doctest.expected.is_none())
+            .map(|doctest| {
+                Diagnostic::warning(
+                    self.id(),
+                    format!(
+                        "Doctest example '{}' has no expected-output block",
+                        doctest.block_id
+                    ),
+                )
+                .with_suggestion(
+                    "Follow the example with a ```output block holding its expected output",
+                )
+                .with_category(RuleCategory::Quality)
+            })
+            .collect()
+    }
+}
+
+/// Opt-in rule flagging skills that no test exercises: no YAML test
+/// definitions next to the skill file and no doctest examples.
+pub struct HasTestsRule;
+
+impl ValidationRule for HasTestsRule {
+    fn id(&self) -> &'static str {
+        "has-tests"
+    }
+
+    fn name(&self) -> &'static str {
+        "Has Tests"
+    }
+
+    fn description(&self) -> &'static str {
+        "Skills should have tests/*.yaml definitions or doctest examples (opt-in)"
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Quality
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+
+    fn opt_in(&self) -> bool {
+        true
+    }
+
+    fn validate(&self, ctx: &ValidationContext<'_>) -> Vec<Diagnostic> {
+        // Test definitions live beside the skill file, so without a path
+        // there is nothing to check
+        let Some(dir) = ctx.file_path.and_then(Path::parent) else {
+            return Vec::new();
+        };
+        let has_definitions = std::fs::read_dir(dir.join("tests")).is_ok_and(|entries| {
+            entries.flatten().any(|entry| {
+                entry
+                    .path()
+                    .extension()
+                    .is_some_and(|ext| ext == "yaml" || ext == "yml")
+            })
+        });
+        if has_definitions || !crate::testing::doctest::extract_doctests(ctx.skill).is_empty() {
+            return Vec::new();
+        }
+        vec![
+            Diagnostic::info(self.id(), "Skill has no tests")
+                .with_suggestion(
+                    "Add a tests/*.yaml definition (see `ms test`) or a doctest example",
+                )
+                .with_category(RuleCategory::Quality),
+        ]
+    }
+}
+
 /// Rule that checks for balanced content (not too much code, not too little).
 pub struct BalancedContentRule {
     max_code_ratio: f64,
@@ -462,26 +567,33 @@ impl ValidationRule for BalancedContentRule {
 pub struct TokenBudgetRule {
     total_warn_threshold: usize,
     total_error_threshold: usize,
+    counter: Arc<dyn TokenCounter + Send + Sync>,
 }
 
 impl Default for TokenBudgetRule {
     fn default() -> Self {
-        Self {
-            total_warn_threshold: 4000,
-            total_error_threshold: 8000,
-        }
+        Self::with_thresholds(4000, 8000)
     }
 }
 
 impl TokenBudgetRule {
     /// Create a rule with custom thresholds.
     #[must_use]
-    pub const fn with_thresholds(warn: usize, error: usize) -> Self {
+    pub fn with_thresholds(warn: usize, error: usize) -> Self {
         Self {
             total_warn_threshold: warn,
             total_error_threshold: error,
+            counter: Arc::new(HeuristicCounter),
         }
     }
+
+    /// Count with `counter` (see `[packing].tokenizer`) instead of the
+    /// heuristic.
+    #[must_use]
+    pub fn with_counter(mut self, counter: Arc<dyn TokenCounter + Send + Sync>) -> Self {
+        self.counter = counter;
+        self
+    }
 }
 
 impl ValidationRule for TokenBudgetRule {
@@ -508,14 +620,15 @@ impl ValidationRule for TokenBudgetRule {
     fn validate(&self, ctx: &ValidationContext<'_>) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
         let content = extract_all_content(ctx.skill);
-        let estimated_tokens = estimate_tokens(&content);
+        let estimated_tokens = self.counter.count(&content);
+        let tokenizer = self.counter.name();
 
         if estimated_tokens > self.total_error_threshold {
             diagnostics.push(
                 Diagnostic::warning(
                     self.id(),
                     format!(
-                        "Skill is very large (~{estimated_tokens} tokens). Consider splitting."
+                        "Skill is very large (~{estimated_tokens} tokens, {tokenizer}). Consider splitting."
                     ),
                 )
                 .with_suggestion("Large skills load slowly. Split into focused skills.")
@@ -526,8 +639,8 @@ impl ValidationRule for TokenBudgetRule {
                 Diagnostic::info(
                     self.id(),
                     format!(
-                        "Skill has ~{} tokens (suggested: <{})",
-                        estimated_tokens, self.total_warn_threshold
+                        "Skill has ~{} tokens, {} (suggested: <{})",
+                        estimated_tokens, tokenizer, self.total_warn_threshold
                     ),
                 )
                 .with_suggestion("Consider moving content to higher disclosure levels")
@@ -631,6 +744,8 @@ pub fn quality_rules() -> Vec<Box<dyn ValidationRule>> {
         Box::new(MeaningfulDescriptionRule::default()),
         Box::new(ActionableRulesRule::default()),
         Box::new(ExamplesHaveCodeRule),
+        Box::new(DoctestExpectedOutputRule),
+        Box::new(HasTestsRule),
         Box::new(BalancedContentRule::default()),
     ]
 }
@@ -870,6 +985,31 @@ mod tests {
         let diagnostics = rule.validate(&ctx);
         assert!(!diagnostics.is_empty());
         assert!(diagnostics[0].message.contains("large"));
+        assert!(diagnostics[0].message.contains("heuristic"));
+    }
+
+    #[test]
+    fn test_token_budget_uses_counter() {
+        struct PerByte;
+        impl TokenCounter for PerByte {
+            fn name(&self) -> &'static str {
+                "per-byte"
+            }
+            fn count(&self, text: &str) -> usize {
+                text.len()
+            }
+        }
+
+        // ~150 heuristic tokens stays under the threshold; per-byte does not.
+        let content = "x".repeat(600);
+        let config = ValidationConfig::new();
+        let skill = skill_with_content(&content);
+        let ctx = make_context(&skill, &config);
+
+        let rule = TokenBudgetRule::with_thresholds(200, 400);
+        assert!(rule.validate(&ctx).is_empty());
+        let diagnostics = rule.with_counter(Arc::new(PerByte)).validate(&ctx);
+        assert!(diagnostics[0].message.contains("per-byte"));
     }
 
     // EmbeddingQualityRule tests
@@ -953,12 +1093,62 @@ mod tests {
         assert!(estimate_code_ratio(mostly_code) > 0.5);
     }
 
+    #[test]
+    fn test_doctest_without_expected_output_warns() {
+        let config = ValidationConfig::default();
+        let missing = crate::core::spec_lens::parse_markdown(
+            "# T\n\nDesc.\n\n## Examples\n\n```bash doctest\necho hi\n```\n",
+        )
+        .unwrap();
+        let diagnostics = DoctestExpectedOutputRule.validate(&make_context(&missing, &config));
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule_id, "doctest-expected-output");
+
+        let complete = crate::core::spec_lens::parse_markdown(
+            "# T\n\nDesc.\n\n## Examples\n\n```bash doctest\necho hi\n```\n\n```output\nhi\n```\n",
+        )
+        .unwrap();
+        assert!(
+            DoctestExpectedOutputRule
+                .validate(&make_context(&complete, &config))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_has_tests_flags_untested_skills() {
+        let config = ValidationConfig::default();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("SKILL.md");
+        let skill = skill_with_content("Deploy with care.");
+        let ctx = || make_context(&skill, &config).with_file_path(&path);
+
+        assert!(HasTestsRule.opt_in());
+        let diagnostics = HasTestsRule.validate(&ctx());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Info);
+        // Without a file path the tests directory cannot be found
+        assert!(
+            HasTestsRule
+                .validate(&make_context(&skill, &config))
+                .is_empty()
+        );
+
+        std::fs::create_dir(dir.path().join("tests")).unwrap();
+        std::fs::write(
+            dir.path().join("tests/smoke.yaml"),
+            "name: smoke\nsteps: []\n",
+        )
+        .unwrap();
+        assert!(HasTestsRule.validate(&ctx()).is_empty());
+    }
+
     // Rule collection tests
 
     #[test]
     fn test_quality_rules_count() {
         let rules = quality_rules();
-        assert_eq!(rules.len(), 4);
+        assert_eq!(rules.len(), 6);
     }
 
     #[test]
@@ -970,7 +1160,7 @@ mod tests {
     #[test]
     fn test_all_rules_count() {
         let rules = quality_and_performance_rules();
-        assert_eq!(rules.len(), 6);
+        assert_eq!(rules.len(), 8);
     }
 
     #[test]
@@ -3,6 +3,7 @@
 //! These rules check reference integrity, including inheritance chains,
 //! cycle detection, and reference validity.
 
+use crate::core::follow_up::{FollowUp, FollowUpKind, find_loop};
 use crate::core::resolution::{
     CycleDetectionResult, MAX_INHERITANCE_DEPTH, detect_inheritance_cycle,
 };
@@ -23,7 +24,7 @@ impl ValidationRule for ValidExtendsRule {
     }
 
     fn description(&self) -> &'static str {
-        "The extends field must reference an existing skill"
+        "The extends field must reference an existing skill; recommended skills should exist"
     }
 
     fn category(&self) -> RuleCategory {
@@ -35,8 +36,10 @@ impl ValidationRule for ValidExtendsRule {
     }
 
     fn validate(&self, ctx: &ValidationContext<'_>) -> Vec<Diagnostic> {
+        let mut diagnostics = self.validate_recommends(ctx);
+
         let Some(parent_id) = &ctx.skill.extends else {
-            return vec![];
+            return diagnostics;
         };
 
         let Some(repository) = ctx.repository else {
@@ -51,24 +54,49 @@ impl ValidationRule for ValidExtendsRule {
         };
 
         match repository.get(parent_id) {
-            Ok(Some(_)) => vec![], // Parent exists
+            Ok(Some(_)) => {} // Parent exists
             Ok(None) => {
-                vec![
+                diagnostics.push(
                     Diagnostic::error(self.id(), format!("Parent skill '{parent_id}' not found"))
                         .with_suggestion("Check that the parent skill ID is correct and indexed")
                         .with_category(RuleCategory::Reference),
-                ]
+                );
             }
             Err(e) => {
-                vec![
+                diagnostics.push(
                     Diagnostic::warning(
                         self.id(),
                         format!("Could not validate parent skill '{parent_id}': {e}"),
                     )
                     .with_category(RuleCategory::Reference),
-                ]
+                );
             }
         }
+        diagnostics
+    }
+}
+
+impl ValidExtendsRule {
+    /// `recommends` entries are optional at load time, so a missing skill is
+    /// only a warning.
+    fn validate_recommends(&self, ctx: &ValidationContext<'_>) -> Vec<Diagnostic> {
+        let Some(repository) = ctx.repository else {
+            return vec![];
+        };
+
+        ctx.skill
+            .metadata
+            .recommends
+            .iter()
+            .filter(|id| matches!(repository.get(id), Ok(None)))
+            .map(|id| {
+                Diagnostic::warning(self.id(), format!("Recommended skill '{id}' not found"))
+                    .with_suggestion(
+                        "It will be skipped at load time; install it or drop the entry",
+                    )
+                    .with_category(RuleCategory::Reference)
+            })
+            .collect()
     }
 }
 
@@ -298,6 +326,113 @@ impl ValidationRule for FormatVersionRule {
     }
 }
 
+/// Rule that validates declared follow-ups: targets present and indexed,
+/// and no `load` follow-up loop back to this skill.
+pub struct ValidFollowUpsRule;
+
+impl ValidationRule for ValidFollowUpsRule {
+    fn id(&self) -> &'static str {
+        "valid-follow-ups"
+    }
+
+    fn name(&self) -> &'static str {
+        "Valid Follow-ups"
+    }
+
+    fn description(&self) -> &'static str {
+        "Follow-up targets must exist and load follow-ups must not loop"
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Reference
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn validate(&self, ctx: &ValidationContext<'_>) -> Vec<Diagnostic> {
+        let follow_ups = &ctx.skill.metadata.follow_ups;
+        if follow_ups.is_empty() {
+            return vec![];
+        }
+
+        let mut diagnostics: Vec<Diagnostic> = follow_ups
+            .iter()
+            .flat_map(FollowUp::errors)
+            .map(|message| {
+                Diagnostic::error(self.id(), message)
+                    .with_suggestion("Set `target:` to a skill id (load) or a command (verify)")
+                    .with_category(RuleCategory::Reference)
+            })
+            .collect();
+
+        let Some(repository) = ctx.repository else {
+            diagnostics.push(
+                Diagnostic::info(
+                    self.id(),
+                    "Cannot validate follow-up targets without repository access",
+                )
+                .with_category(RuleCategory::Reference),
+            );
+            return diagnostics;
+        };
+
+        let skill_id = &ctx.skill.metadata.id;
+        let feedback_targets = follow_ups
+            .iter()
+            .filter(|f| f.kind == FollowUpKind::Feedback)
+            .filter_map(|f| f.target.as_deref().map(str::trim))
+            .filter(|target| !target.is_empty() && target != skill_id);
+        for target in follow_ups
+            .iter()
+            .filter_map(FollowUp::load_target)
+            .chain(feedback_targets)
+        {
+            match repository.get(target) {
+                Ok(Some(_)) => {}
+                Ok(None) => diagnostics.push(
+                    Diagnostic::error(
+                        self.id(),
+                        format!("Follow-up target skill '{target}' not found"),
+                    )
+                    .with_suggestion("Check that the target skill ID is correct and indexed")
+                    .with_category(RuleCategory::Reference),
+                ),
+                Err(e) => diagnostics.push(
+                    Diagnostic::warning(
+                        self.id(),
+                        format!("Could not validate follow-up target '{target}': {e}"),
+                    )
+                    .with_category(RuleCategory::Reference),
+                ),
+            }
+        }
+
+        let follow_ups_of = |id: &str| {
+            repository
+                .get(id)
+                .ok()
+                .flatten()
+                .map(|spec| spec.metadata.follow_ups)
+        };
+        if let Some(cycle) = find_loop(skill_id, follow_ups, follow_ups_of) {
+            diagnostics.push(
+                Diagnostic::warning(
+                    self.id(),
+                    format!("Follow-up loop detected: {}", cycle.join(" → ")),
+                )
+                .with_suggestion(
+                    "Drop one of the load follow-ups so agents do not bounce between skills",
+                )
+                .with_category(RuleCategory::Reference),
+            );
+        }
+
+        diagnostics
+    }
+}
+
 /// Returns all reference validation rules.
 #[must_use]
 pub fn reference_rules() -> Vec<Box<dyn ValidationRule>> {
@@ -306,6 +441,7 @@ pub fn reference_rules() -> Vec<Box<dyn ValidationRule>> {
         Box::new(NoCycleRule),
         Box::new(DeepInheritanceRule::default()),
         Box::new(FormatVersionRule),
+        Box::new(ValidFollowUpsRule),
     ]
 }
 
@@ -397,6 +533,24 @@ mod tests {
         assert!(diagnostics[0].message.contains("not found"));
     }
 
+    #[test]
+    fn test_valid_extends_missing_recommendation_warns() {
+        let rule = ValidExtendsRule;
+        let config = ValidationConfig::new();
+
+        let mut repo = TestRepository::new();
+        repo.add(SkillSpec::new("present", "Present Skill"));
+
+        let mut skill = SkillSpec::new("child", "Child Skill");
+        skill.metadata.recommends = vec!["present".to_string(), "absent".to_string()];
+
+        let ctx = make_context(&skill, &config, Some(&repo));
+        let diagnostics = rule.validate(&ctx);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert!(diagnostics[0].message.contains("absent"));
+    }
+
     #[test]
     fn test_no_cycle_rule_no_inheritance() {
         let rule = NoCycleRule;
@@ -477,4 +631,63 @@ mod tests {
         assert_eq!(diagnostics.len(), 1);
         assert!(diagnostics[0].message.contains("newer"));
     }
+
+    fn with_follow_ups(id: &str, targets: &[&str]) -> SkillSpec {
+        let mut skill = SkillSpec::new(id, id);
+        skill.metadata.follow_ups = targets
+            .iter()
+            .map(|target| FollowUp {
+                kind: FollowUpKind::Load,
+                target: Some((*target).to_string()),
+                when: None,
+            })
+            .collect();
+        skill
+    }
+
+    #[test]
+    fn test_valid_follow_ups_missing_target_and_loop() {
+        let rule = ValidFollowUpsRule;
+        let config = ValidationConfig::new();
+
+        let mut repo = TestRepository::new();
+        repo.add(with_follow_ups("skill-b", &["skill-a"]));
+        let skill_a = with_follow_ups("skill-a", &["skill-b", "skill-missing"]);
+        repo.add(skill_a.clone());
+
+        let ctx = make_context(&skill_a, &config, Some(&repo));
+        let diagnostics = rule.validate(&ctx);
+        assert_eq!(diagnostics.len(), 2);
+        assert!(diagnostics[0].message.contains("'skill-missing' not found"));
+        assert!(
+            diagnostics[1]
+                .message
+                .contains("skill-a → skill-b → skill-a")
+        );
+    }
+
+    #[test]
+    fn test_valid_follow_ups_requires_targets() {
+        let rule = ValidFollowUpsRule;
+        let config = ValidationConfig::new();
+        let mut skill = SkillSpec::new("test", "Test");
+        skill.metadata.follow_ups = vec![
+            FollowUp {
+                kind: FollowUpKind::Verify,
+                target: None,
+                when: None,
+            },
+            FollowUp {
+                kind: FollowUpKind::Feedback,
+                target: None,
+                when: None,
+            },
+        ];
+        let repo = TestRepository::new();
+        let ctx = make_context(&skill, &config, Some(&repo));
+
+        let diagnostics = rule.validate(&ctx);
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].message.contains("verify follow-up needs"));
+    }
 }
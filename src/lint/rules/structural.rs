@@ -5,6 +5,10 @@
 
 use std::collections::HashSet;
 
+use crate::error::MsError;
+
+use crate::core::applicability::SkillWindow;
+use crate::core::scaffold::is_placeholder;
 use crate::core::skill::SkillSpec;
 use crate::error::Result;
 use crate::lint::config::ValidationContext;
@@ -44,6 +48,7 @@ impl ValidationRule for RequiredMetadataRule {
             diagnostics.push(
                 Diagnostic::error(self.id(), "Skill must have an 'id' field")
                     .with_suggestion("Add 'id: your-skill-id' to the metadata")
+                    .with_fix()
                     .with_category(RuleCategory::Structure),
             );
         }
@@ -53,6 +58,7 @@ impl ValidationRule for RequiredMetadataRule {
             diagnostics.push(
                 Diagnostic::error(self.id(), "Skill must have a 'name' field")
                     .with_suggestion("Add 'name: Your Skill Name' to the metadata")
+                    .with_fix()
                     .with_category(RuleCategory::Structure),
             );
         }
@@ -74,18 +80,48 @@ impl ValidationRule for RequiredMetadataRule {
         true
     }
 
+    /// Fill missing keys with placeholders: the id is derived from the name
+    /// (and vice versa) when one of them is present.
     fn fix(&self, skill: &mut SkillSpec, diagnostic: &Diagnostic) -> Result<()> {
-        if diagnostic.message.contains("description") {
-            skill.metadata.description = format!("TODO: Add description for {}", skill.metadata.id);
-            Ok(())
-        } else {
-            Err(crate::error::MsError::NotImplemented(
-                "Cannot auto-fix id or name - please provide manually".into(),
-            ))
+        let metadata = &mut skill.metadata;
+        if diagnostic.message.contains("'id'") {
+            if metadata.id.is_empty() {
+                let slug = crate::core::spec_lens::slugify(&metadata.name);
+                metadata.id = if slug.is_empty() {
+                    "untitled-skill".to_string()
+                } else {
+                    slug
+                };
+            }
+        } else if diagnostic.message.contains("'name'") {
+            if metadata.name.is_empty() {
+                metadata.name = if metadata.id.is_empty() {
+                    "Untitled Skill".to_string()
+                } else {
+                    title_from_id(&metadata.id)
+                };
+            }
+        } else if metadata.description.is_empty() {
+            metadata.description = format!("TODO: Add description for {}", metadata.id);
         }
+        Ok(())
     }
 }
 
+/// `rust-error-handling` -> `Rust Error Handling`
+fn title_from_id(id: &str) -> String {
+    id.split(['-', '_', '.'])
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_uppercase().chain(chars).collect()
+            })
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 /// Rule that checks for valid version format.
 pub struct ValidVersionRule;
 
@@ -117,6 +153,7 @@ impl ValidationRule for ValidVersionRule {
             return vec![
                 Diagnostic::warning(self.id(), "Skill should have a version")
                     .with_suggestion("Add 'version: 1.0.0' to the metadata")
+                    .with_fix()
                     .with_category(RuleCategory::Structure),
             ];
         }
@@ -124,18 +161,54 @@ impl ValidationRule for ValidVersionRule {
         // Simple semver check: X.Y.Z pattern
         let parts: Vec<&str> = version.split('.').collect();
         if parts.len() != 3 || !parts.iter().all(|p| p.parse::<u32>().is_ok()) {
-            return vec![
-                Diagnostic::warning(
-                    self.id(),
-                    format!("Version '{version}' is not valid semver (expected X.Y.Z)"),
-                )
-                .with_suggestion("Use semantic versioning like '1.0.0' or '2.1.3'")
-                .with_category(RuleCategory::Structure),
-            ];
+            let mut diagnostic = Diagnostic::warning(
+                self.id(),
+                format!("Version '{version}' is not valid semver (expected X.Y.Z)"),
+            )
+            .with_suggestion("Use semantic versioning like '1.0.0' or '2.1.3'")
+            .with_category(RuleCategory::Structure);
+            if normalize_version(version).is_some() {
+                diagnostic = diagnostic.with_fix();
+            }
+            return vec![diagnostic];
         }
 
         vec![]
     }
+
+    fn can_fix(&self) -> bool {
+        true
+    }
+
+    fn fix(&self, skill: &mut SkillSpec, _diagnostic: &Diagnostic) -> Result<()> {
+        let version = &mut skill.metadata.version;
+        if version.is_empty() {
+            *version = "0.1.0".to_string();
+            return Ok(());
+        }
+        *version = normalize_version(version).ok_or_else(|| {
+            MsError::ValidationFailed(format!("cannot normalize version '{version}' to X.Y.Z"))
+        })?;
+        Ok(())
+    }
+}
+
+/// Normalize `v1`, `1.2`, ` V1.2.3 ` and similar to `X.Y.Z`.
+///
+/// Returns `None` for anything that is not one to three numeric components,
+/// e.g. pre-release suffixes, which need a human decision.
+fn normalize_version(version: &str) -> Option<String> {
+    let trimmed = version.trim();
+    let bare = trimmed.strip_prefix(['v', 'V']).unwrap_or(trimmed);
+    let mut parts = bare
+        .split('.')
+        .map(|part| part.parse::<u32>().ok())
+        .collect::<Option<Vec<u32>>>()?;
+    if parts.is_empty() || parts.len() > 3 {
+        return None;
+    }
+    parts.resize(3, 0);
+    Some(format!("{}.{}.{}", parts[0], parts[1], parts[2]))
 }
 
 /// Rule that checks for unique section IDs.
@@ -221,6 +294,7 @@ impl ValidationRule for UniqueBlockIdsRule {
                             ),
                         )
                         .with_suggestion("Each block must have a unique ID within its section")
+                        .with_fix()
                         .with_category(RuleCategory::Structure),
                     );
                 }
@@ -229,6 +303,32 @@ impl ValidationRule for UniqueBlockIdsRule {
 
         diagnostics
     }
+
+    fn can_fix(&self) -> bool {
+        true
+    }
+
+    /// Renumber every colliding block id in the skill (`id` -> `id-2`, `id-3`,
+    /// ...); the first block keeps its id. Later diagnostics find nothing left.
+    fn fix(&self, skill: &mut SkillSpec, _diagnostic: &Diagnostic) -> Result<()> {
+        for section in &mut skill.sections {
+            let mut taken: HashSet<String> = section.blocks.iter().map(|b| b.id.clone()).collect();
+            let mut seen: HashSet<String> = HashSet::new();
+            for block in &mut section.blocks {
+                if seen.insert(block.id.clone()) {
+                    continue;
+                }
+                let renamed = (2..)
+                    .map(|n| format!("{}-{n}", block.id))
+                    .find(|candidate| !taken.contains(candidate))
+                    .unwrap_or_default();
+                taken.insert(renamed.clone());
+                seen.insert(renamed.clone());
+                block.id = renamed;
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Rule that checks for non-empty block content.
@@ -272,6 +372,18 @@ impl ValidationRule for NonEmptyBlocksRule {
                         .with_suggestion("Add meaningful content or remove the empty block")
                         .with_category(RuleCategory::Structure),
                     );
+                } else if is_placeholder(&block.content) {
+                    diagnostics.push(
+                        Diagnostic::warning(
+                            self.id(),
+                            format!(
+                                "Block '{}' in section '{}' is still a TODO placeholder",
+                                block.id, section.id
+                            ),
+                        )
+                        .with_suggestion("Replace the TODO lines with real content")
+                        .with_category(RuleCategory::Structure),
+                    );
                 }
             }
         }
@@ -280,6 +392,58 @@ impl ValidationRule for NonEmptyBlocksRule {
     }
 }
 
+/// Rule that checks `active_during` / `expires` applicability metadata.
+pub struct ValidApplicabilityRule;
+
+impl ValidationRule for ValidApplicabilityRule {
+    fn id(&self) -> &'static str {
+        "valid-applicability"
+    }
+
+    fn name(&self) -> &'static str {
+        "Valid Applicability Window"
+    }
+
+    fn description(&self) -> &'static str {
+        "active_during months, weekdays, date ranges, and expires must be valid dates"
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Structure
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn validate(&self, ctx: &ValidationContext<'_>) -> Vec<Diagnostic> {
+        let metadata = &ctx.skill.metadata;
+        let mut diagnostics: Vec<Diagnostic> = SkillWindow::from_metadata(metadata)
+            .errors()
+            .iter()
+            .map(|err| {
+                Diagnostic::error(self.id(), format!("Invalid applicability window: {err}"))
+                    .with_suggestion(
+                        "Use months 1-12 or names, weekday names, and YYYY-MM-DD or MM-DD dates",
+                    )
+                    .with_category(RuleCategory::Structure)
+            })
+            .collect();
+        if metadata
+            .active_during
+            .as_ref()
+            .is_some_and(crate::core::applicability::ActiveDuring::is_empty)
+        {
+            diagnostics.push(
+                Diagnostic::warning(self.id(), "'active_during' declares no constraints")
+                    .with_suggestion("Add months, days_of_week, or date_ranges, or remove it")
+                    .with_category(RuleCategory::Structure),
+            );
+        }
+        diagnostics
+    }
+}
+
 /// Returns all structural validation rules.
 #[must_use]
 pub fn structural_rules() -> Vec<Box<dyn ValidationRule>> {
@@ -289,6 +453,7 @@ pub fn structural_rules() -> Vec<Box<dyn ValidationRule>> {
         Box::new(UniqueSectionIdsRule),
         Box::new(UniqueBlockIdsRule),
         Box::new(NonEmptyBlocksRule),
+        Box::new(ValidApplicabilityRule),
     ]
 }
 
@@ -428,6 +593,82 @@ mod tests {
         assert!(diagnostics[0].message.contains("Duplicate block ID"));
     }
 
+    #[test]
+    fn test_unique_block_ids_fix_renumbers_duplicates() {
+        let rule = UniqueBlockIdsRule;
+        let config = ValidationConfig::new();
+        let block = |id: &str| SkillBlock {
+            id: id.to_string(),
+            block_type: Default::default(),
+            content: "Content".to_string(),
+        };
+        let mut skill = SkillSpec::new("test", "Test");
+        skill.sections = vec![SkillSection {
+            id: "section-1".to_string(),
+            title: "Section 1".to_string(),
+            blocks: vec![block("step"), block("step"), block("step-2"), block("step")],
+        }];
+        let diagnostics = rule.validate(&make_context(&skill, &config));
+        assert!(diagnostics.iter().all(|d| d.fix_available));
+        for diagnostic in &diagnostics {
+            rule.fix(&mut skill, diagnostic).unwrap();
+        }
+        let ids: Vec<&str> = skill.sections[0]
+            .blocks
+            .iter()
+            .map(|b| b.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["step", "step-3", "step-2", "step-4"]);
+        assert!(rule.validate(&make_context(&skill, &config)).is_empty());
+    }
+
+    #[test]
+    fn test_valid_version_fix_normalizes() {
+        let rule = ValidVersionRule;
+        let config = ValidationConfig::new();
+        for (raw, expected) in [
+            ("v2", "2.0.0"),
+            ("1.4", "1.4.0"),
+            (" V1.2.3 ", "1.2.3"),
+            ("", "0.1.0"),
+        ] {
+            let mut skill = SkillSpec::new("test", "Test");
+            skill.metadata.version = raw.to_string();
+            let diagnostics = rule.validate(&make_context(&skill, &config));
+            assert!(diagnostics[0].fix_available, "{raw}");
+            rule.fix(&mut skill, &diagnostics[0]).unwrap();
+            assert_eq!(skill.metadata.version, expected);
+            assert!(rule.validate(&make_context(&skill, &config)).is_empty());
+        }
+
+        let mut skill = SkillSpec::new("test", "Test");
+        skill.metadata.version = "1.0.0-beta".to_string();
+        let diagnostics = rule.validate(&make_context(&skill, &config));
+        assert!(!diagnostics[0].fix_available);
+        assert!(rule.fix(&mut skill, &diagnostics[0]).is_err());
+        assert_eq!(skill.metadata.version, "1.0.0-beta");
+    }
+
+    #[test]
+    fn test_required_metadata_fix_fills_placeholders() {
+        let rule = RequiredMetadataRule;
+        let config = ValidationConfig::new();
+        let mut skill = SkillSpec::new("", "Rust Error Handling");
+        let diagnostics = rule.validate(&make_context(&skill, &config));
+        for diagnostic in &diagnostics {
+            rule.fix(&mut skill, diagnostic).unwrap();
+        }
+        assert_eq!(skill.metadata.id, "rust-error-handling");
+        assert!(skill.metadata.description.starts_with("TODO"));
+        assert!(rule.validate(&make_context(&skill, &config)).is_empty());
+
+        let mut skill = SkillSpec::new("async-pitfalls", "");
+        skill.metadata.description = "Pitfalls.".to_string();
+        let diagnostics = rule.validate(&make_context(&skill, &config));
+        rule.fix(&mut skill, &diagnostics[0]).unwrap();
+        assert_eq!(skill.metadata.name, "Async Pitfalls");
+    }
+
     #[test]
     fn test_non_empty_blocks() {
         let rule = NonEmptyBlocksRule;
@@ -447,5 +688,42 @@ mod tests {
         let diagnostics = rule.validate(&ctx);
         assert_eq!(diagnostics.len(), 1);
         assert!(diagnostics[0].message.contains("no content"));
+
+        skill.sections[0].blocks[0].content = "- [ ] TODO: first check".to_string();
+        let diagnostics = rule.validate(&make_context(&skill, &config));
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].message.contains("TODO placeholder"));
+
+        skill.sections[0].blocks[0].content = "- [ ] run cargo test".to_string();
+        assert!(rule.validate(&make_context(&skill, &config)).is_empty());
+    }
+
+    #[test]
+    fn test_valid_applicability() {
+        use crate::core::applicability::{ActiveDuring, DateRange, MonthValue};
+
+        let rule = ValidApplicabilityRule;
+        let config = ValidationConfig::new();
+        let mut skill = SkillSpec::new("test", "Test");
+        skill.metadata.active_during = Some(ActiveDuring {
+            months: vec![MonthValue::Number(12)],
+            days_of_week: vec!["fri".to_string()],
+            date_ranges: vec![DateRange {
+                from: "12-15".to_string(),
+                to: "01-05".to_string(),
+            }],
+        });
+        skill.metadata.expires = Some("2027-01-31".to_string());
+        assert!(rule.validate(&make_context(&skill, &config)).is_empty());
+
+        skill.metadata.active_during = Some(ActiveDuring {
+            months: vec![MonthValue::Number(13)],
+            ..ActiveDuring::default()
+        });
+        skill.metadata.expires = Some("31/01/2027".to_string());
+        let diagnostics = rule.validate(&make_context(&skill, &config));
+        assert_eq!(diagnostics.len(), 2);
+        assert!(diagnostics[0].message.contains("invalid month"));
+        assert!(diagnostics[1].message.contains("expires"));
     }
 }
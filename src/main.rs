@@ -5,7 +5,7 @@
 use std::io::IsTerminal;
 use std::process::ExitCode;
 
-use clap::Parser;
+use clap::{ArgMatches, CommandFactory, FromArgMatches};
 use tracing_subscriber::{EnvFilter, fmt, prelude::*};
 
 use ms::Result;
@@ -13,29 +13,27 @@ use ms::app::AppContext;
 use ms::cli::{Cli, Commands};
 
 fn main() -> ExitCode {
-    let cli = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
     init_tracing(&cli);
+    ms::security::command_safety::set_invocation(ms::security::Invocation {
+        subcommand: subcommand_path(&matches),
+        safety_profile: cli.safety_profile.clone(),
+    });
 
     match run(&cli) {
         Ok(()) => ExitCode::SUCCESS,
         Err(e) => {
             if cli.robot {
                 // Robot mode: JSON error output to stdout
-                let (code, message) = match &e {
-                    ms::MsError::ApprovalRequired(msg) => ("approval_required", msg.clone()),
-                    ms::MsError::DestructiveBlocked(msg) => ("destructive_blocked", msg.clone()),
-                    _ => ("error", e.to_string()),
-                };
-                let error_json = serde_json::json!({
-                    "error": true,
-                    "code": code,
-                    "message": message,
-                });
-                println!("{}", serde_json::to_string(&error_json).unwrap_or_default());
+                println!(
+                    "{}",
+                    serde_json::to_string(&e.to_robot_json()).unwrap_or_default()
+                );
             } else {
                 eprintln!("Error: {e}");
             }
-            ExitCode::FAILURE
+            ExitCode::from(e.exit_code())
         }
     }
 }
@@ -44,8 +42,41 @@ fn run(cli: &Cli) -> Result<()> {
     if let Commands::Init(args) = &cli.command {
         return ms::cli::commands::init::run_without_context(cli.robot, args);
     }
-    let ctx = AppContext::from_cli(cli)?;
-    ms::cli::commands::run(&ctx, &cli.command)
+    // Completion must be fast and work before `ms init`.
+    match &cli.command {
+        Commands::Completions(args) => return ms::cli::commands::completions::run(args),
+        Commands::Complete(args) => return ms::cli::commands::completions::run_complete(args),
+        // Agent hooks must not depend on a working ms root.
+        Commands::Hook(args) => return ms::cli::commands::hook::run(args),
+        Commands::Errors(args) => return ms::cli::commands::errors::run(cli.output_format(), args),
+        _ => {}
+    }
+    // Must work on databases that refuse to open because they are partially migrated.
+    if let Commands::Doctor(args) = &cli.command {
+        if args.migrations {
+            return ms::cli::commands::doctor::run_migrations_check(cli);
+        }
+    }
+    // Doctor must start even when the search index is corrupt.
+    let ctx = if matches!(cli.command, Commands::Doctor(_)) {
+        AppContext::for_doctor(cli)?
+    } else {
+        AppContext::from_cli(cli)?
+    };
+    ms::cli::commands::run(&ctx, &cli.command)?;
+    ms::cli::nudges::maybe_show(&ctx, cli);
+    Ok(())
+}
+
+/// The subcommand chain that was run, e.g. `prune purge`.
+fn subcommand_path(matches: &ArgMatches) -> Option<String> {
+    let mut names = Vec::new();
+    let mut current = matches;
+    while let Some((name, sub)) = current.subcommand() {
+        names.push(name);
+        current = sub;
+    }
+    (!names.is_empty()).then(|| names.join(" "))
 }
 
 fn init_tracing(cli: &Cli) {
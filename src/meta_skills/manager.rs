@@ -30,6 +30,21 @@ pub struct MetaSkillLoadResult {
     pub packed_content: String,
 }
 
+impl MetaSkillLoadResult {
+    /// Slices dropped because they did not fit the token budget, as `skill:slice`.
+    #[must_use]
+    pub fn budget_dropped(&self) -> Vec<String> {
+        self.skipped
+            .iter()
+            .filter(|skip| matches!(skip.reason, SkipReason::BudgetExceeded))
+            .map(|skip| match &skip.slice_id {
+                Some(slice_id) => format!("{}:{slice_id}", skip.skill_id),
+                None => skip.skill_id.clone(),
+            })
+            .collect()
+    }
+}
+
 /// A resolved slice from a meta-skill.
 #[derive(Debug, Clone)]
 pub struct ResolvedSlice {
@@ -686,4 +701,27 @@ mod tests {
         assert!(debug.contains("MetaSkillLoadResult"));
         assert!(debug.contains("meta-1"));
     }
+
+    #[test]
+    fn load_result_budget_dropped_lists_only_budget_skips() {
+        let result = MetaSkillLoadResult {
+            meta_skill_id: "meta-1".to_string(),
+            slices: vec![],
+            tokens_used: 0,
+            skipped: vec![
+                SkippedSlice {
+                    skill_id: "a".to_string(),
+                    slice_id: Some("rules".to_string()),
+                    reason: SkipReason::BudgetExceeded,
+                },
+                SkippedSlice {
+                    skill_id: "b".to_string(),
+                    slice_id: None,
+                    reason: SkipReason::SkillNotFound,
+                },
+            ],
+            packed_content: String::new(),
+        };
+        assert_eq!(result.budget_dropped(), vec!["a:rules".to_string()]);
+    }
 }
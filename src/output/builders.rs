@@ -486,6 +486,88 @@ pub fn quality_indicator(score: f64) -> String {
     format!("{} ({:.0}%)", label, score * 100.0)
 }
 
+/// Build a table of quality score components.
+///
+/// # Arguments
+/// * `rows` - Tuples of (component, weight, value, contribution, detail)
+#[must_use]
+pub fn quality_components_table(rows: &[(&str, f32, f32, f32, &str)]) -> Table {
+    let mut table = Table::new()
+        .with_column(Column::new("Component").style(Style::new().bold()))
+        .with_column(Column::new("Weight").justify(JustifyMethod::Right))
+        .with_column(Column::new("Value").justify(JustifyMethod::Right))
+        .with_column(Column::new("Adds").justify(JustifyMethod::Right))
+        .with_column(Column::new("Measured"));
+
+    for (name, weight, value, contribution, detail) in rows {
+        table = table.with_row_cells([
+            (*name).to_string(),
+            format!("{weight:.2}"),
+            format!("{value:.2}"),
+            format!("{contribution:.3}"),
+            (*detail).to_string(),
+        ]);
+    }
+
+    table
+}
+
+/// Build a table of skill usage, one row per skill or layer.
+///
+/// # Arguments
+/// * `key_header` - Header for the first column ("Skill" or "Layer")
+/// * `rows` - Tuples of (key, loads, trend, acceptance, feedback, last loaded)
+#[must_use]
+pub fn usage_stats_table(key_header: &str, rows: &[(&str, u64, &str, &str, &str, &str)]) -> Table {
+    let mut table = Table::new()
+        .with_column(Column::new(key_header).style(Style::new().bold()))
+        .with_column(Column::new("Loads").justify(JustifyMethod::Right))
+        .with_column(Column::new("Trend"))
+        .with_column(Column::new("Accepted").justify(JustifyMethod::Right))
+        .with_column(Column::new("Feedback +/-").justify(JustifyMethod::Right))
+        .with_column(Column::new("Last loaded"));
+
+    for (key, loads, trend, acceptance, feedback, last_loaded) in rows {
+        table = table.with_row_cells([
+            (*key).to_string(),
+            loads.to_string(),
+            (*trend).to_string(),
+            (*acceptance).to_string(),
+            (*feedback).to_string(),
+            (*last_loaded).to_string(),
+        ]);
+    }
+
+    table
+}
+
+/// Build a sparkline of counts, scaled to the largest.
+#[must_use]
+pub fn sparkline(values: &[u64]) -> String {
+    scaled_marks(values, &['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'], '·')
+}
+
+/// Build a plain text sparkline (ASCII only).
+#[must_use]
+pub fn sparkline_plain(values: &[u64]) -> String {
+    scaled_marks(values, &['.', ':', '-', '=', '+', '*', '#', '@'], '_')
+}
+
+fn scaled_marks(values: &[u64], marks: &[char], zero: char) -> String {
+    let max = values.iter().copied().max().unwrap_or(0).max(1);
+    values
+        .iter()
+        .map(|&value| {
+            if value == 0 {
+                zero
+            } else {
+                let level = (value * (marks.len() as u64 - 1) / max) as usize;
+                marks[level.min(marks.len() - 1)]
+            }
+        })
+        .collect()
+}
+
 // ============================================================================
 // Key-Value Display
 // ============================================================================
@@ -600,6 +682,14 @@ mod tests {
         assert!(!bar.contains("[green]"));
     }
 
+    #[test]
+    fn test_sparkline_scales_to_max() {
+        assert_eq!(sparkline(&[0, 1, 7, 14]), "·▁▄█");
+        assert_eq!(sparkline_plain(&[0, 1, 7, 14]), "_.=@");
+        assert_eq!(sparkline(&[0, 0]), "··");
+        assert_eq!(sparkline(&[]), "");
+    }
+
     #[test]
     fn test_quality_indicator() {
         assert!(quality_indicator(0.95).contains("Excellent"));
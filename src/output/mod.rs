@@ -69,6 +69,7 @@ pub mod fallback;
 pub mod messages;
 pub mod plain_format;
 pub mod progress;
+pub mod progress_sink;
 pub mod rich_output;
 pub mod safe;
 pub mod theme;
@@ -105,10 +106,11 @@ pub use builders::{
     CheckResult, CheckStatus, bulleted_list, bulleted_list_plain, error_panel,
     error_panel_with_hint, error_panel_with_hint_and_width, error_panel_with_width,
     key_value_plain, key_value_table, numbered_list, progress_line, progress_line_plain,
-    quality_bar, quality_bar_plain, quality_indicator, search_results_table,
-    search_results_table_with_id, skill_detail_panel, skill_detail_panel_with_width, skill_panel,
-    skill_panel_with_width, status_tree, status_tree_with_title, success_panel,
-    success_panel_with_width, warning_panel, warning_panel_with_width,
+    quality_bar, quality_bar_plain, quality_components_table, quality_indicator,
+    search_results_table, search_results_table_with_id, skill_detail_panel,
+    skill_detail_panel_with_width, skill_panel, skill_panel_with_width, sparkline, sparkline_plain,
+    status_tree, status_tree_with_title, success_panel, success_panel_with_width,
+    usage_stats_table, warning_panel, warning_panel_with_width,
 };
 
 // Re-export plain format types
@@ -137,3 +139,4 @@ pub use progress::{
     BarStyle, MultiProgress, MultiProgressHandle, ProgressBar, ProgressEvent, ProgressEventType,
     ProgressGuard, Spinner, SpinnerStyle,
 };
+pub use progress_sink::{NdjsonSink, NullSink, PhaseEvent, PhaseProgress, ProgressSink};
@@ -0,0 +1,346 @@
+//! Phase progress for long-running commands.
+//!
+//! Commands report progress through a [`PhaseProgress`], which timestamps
+//! phases and forwards [`PhaseEvent`]s to a [`ProgressSink`]. Human output
+//! renders them with [`RichOutput`]'s progress bar; with `--robot
+//! --progress-events` they become NDJSON lines on stderr, so both views are
+//! fed by the same calls and stdout keeps only the final result:
+//!
+//! ```text
+//! {"event":"start","phase":"indexing","total":800}
+//! {"event":"progress","phase":"indexing","current":120,"total":800,"skill":"rust-errors"}
+//! {"event":"done","phase":"indexing","duration_ms":5210}
+//! ```
+
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use super::rich_output::RichOutput;
+
+/// Minimum gap between NDJSON `progress` events of one phase.
+pub const DEFAULT_EVENT_INTERVAL: Duration = Duration::from_millis(250);
+
+/// One progress event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum PhaseEvent {
+    Start {
+        phase: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        total: Option<u64>,
+    },
+    Progress {
+        phase: String,
+        current: u64,
+        total: u64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        skill: Option<String>,
+    },
+    Done {
+        phase: String,
+        duration_ms: u64,
+    },
+    Error {
+        phase: String,
+        message: String,
+        duration_ms: u64,
+    },
+}
+
+/// Destination for [`PhaseEvent`]s.
+pub trait ProgressSink: Send + Sync {
+    fn emit(&self, event: &PhaseEvent);
+}
+
+/// Discards every event.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NullSink;
+
+impl ProgressSink for NullSink {
+    fn emit(&self, _event: &PhaseEvent) {}
+}
+
+/// Writes events as NDJSON, throttling `progress` events per phase. The
+/// first and last update of a phase are always written.
+pub struct NdjsonSink<W: Write + Send> {
+    writer: Mutex<W>,
+    interval: Duration,
+    last_progress: Mutex<Option<(String, Instant)>>,
+}
+
+impl NdjsonSink<io::Stderr> {
+    /// NDJSON on stderr at the default rate.
+    #[must_use]
+    pub fn stderr() -> Self {
+        Self::new(io::stderr(), DEFAULT_EVENT_INTERVAL)
+    }
+}
+
+impl<W: Write + Send> NdjsonSink<W> {
+    #[must_use]
+    pub fn new(writer: W, interval: Duration) -> Self {
+        Self {
+            writer: Mutex::new(writer),
+            interval,
+            last_progress: Mutex::new(None),
+        }
+    }
+
+    /// Consume the sink and return its writer.
+    pub fn into_inner(self) -> W {
+        self.writer.into_inner()
+    }
+
+    fn due(&self, phase: &str, current: u64, total: u64) -> bool {
+        let mut last = self.last_progress.lock();
+        let now = Instant::now();
+        let due = current >= total
+            || last.as_ref().is_none_or(|(last_phase, at)| {
+                last_phase != phase || now.duration_since(*at) >= self.interval
+            });
+        if due {
+            *last = Some((phase.to_string(), now));
+        }
+        due
+    }
+}
+
+impl<W: Write + Send> ProgressSink for NdjsonSink<W> {
+    fn emit(&self, event: &PhaseEvent) {
+        if let PhaseEvent::Progress {
+            phase,
+            current,
+            total,
+            ..
+        } = event
+            && !self.due(phase, *current, *total)
+        {
+            return;
+        }
+        if let Ok(line) = serde_json::to_string(event) {
+            let mut writer = self.writer.lock();
+            let _ = writeln!(writer, "{line}");
+            let _ = writer.flush();
+        }
+    }
+}
+
+impl ProgressSink for RichOutput {
+    fn emit(&self, event: &PhaseEvent) {
+        match event {
+            PhaseEvent::Start { .. } => {}
+            PhaseEvent::Progress {
+                phase,
+                current,
+                total,
+                skill,
+            } => self.progress(*current, *total, skill.as_deref().unwrap_or(phase)),
+            PhaseEvent::Done { .. } | PhaseEvent::Error { .. } => self.clear_status(),
+        }
+    }
+}
+
+/// Tracks the open phase of a command and reports it to a sink.
+pub struct PhaseProgress {
+    sink: Box<dyn ProgressSink>,
+    open: Mutex<Option<(String, Instant)>>,
+}
+
+impl PhaseProgress {
+    #[must_use]
+    pub fn new(sink: Box<dyn ProgressSink>) -> Self {
+        Self {
+            sink,
+            open: Mutex::new(None),
+        }
+    }
+
+    /// Progress that goes nowhere.
+    #[must_use]
+    pub fn disabled() -> Self {
+        Self::new(Box::new(NullSink))
+    }
+
+    /// Start `phase`, finishing the previous phase if one is open.
+    pub fn phase(&self, phase: &str, total: Option<u64>) {
+        self.finish();
+        *self.open.lock() = Some((phase.to_string(), Instant::now()));
+        self.sink.emit(&PhaseEvent::Start {
+            phase: phase.to_string(),
+            total,
+        });
+    }
+
+    /// Report `current` of `total` items in the open phase.
+    pub fn update(&self, current: u64, total: u64, skill: Option<&str>) {
+        let Some(phase) = self.open.lock().as_ref().map(|(phase, _)| phase.clone()) else {
+            return;
+        };
+        self.sink.emit(&PhaseEvent::Progress {
+            phase,
+            current,
+            total,
+            skill: skill.map(str::to_string),
+        });
+    }
+
+    /// Finish the open phase, if any.
+    pub fn finish(&self) {
+        if let Some((phase, started)) = self.open.lock().take() {
+            self.sink.emit(&PhaseEvent::Done {
+                phase,
+                duration_ms: millis(started),
+            });
+        }
+    }
+
+    /// Fail the open phase, if any.
+    pub fn fail(&self, message: &str) {
+        if let Some((phase, started)) = self.open.lock().take() {
+            self.sink.emit(&PhaseEvent::Error {
+                phase,
+                message: message.to_string(),
+                duration_ms: millis(started),
+            });
+        }
+    }
+
+    /// Finish or fail the open phase according to `result`.
+    pub fn settle<T, E: std::fmt::Display>(&self, result: &Result<T, E>) {
+        match result {
+            Ok(_) => self.finish(),
+            Err(err) => self.fail(&err.to_string()),
+        }
+    }
+}
+
+fn millis(started: Instant) -> u64 {
+    u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    /// Shared buffer so the test can read what the boxed sink wrote.
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Buffer {
+        fn events(&self) -> Vec<PhaseEvent> {
+            String::from_utf8(self.0.lock().clone())
+                .unwrap()
+                .lines()
+                .map(|line| serde_json::from_str(line).unwrap())
+                .collect()
+        }
+    }
+
+    fn progress(interval: Duration) -> (PhaseProgress, Buffer) {
+        let buffer = Buffer::default();
+        let sink = NdjsonSink::new(buffer.clone(), interval);
+        (PhaseProgress::new(Box::new(sink)), buffer)
+    }
+
+    #[test]
+    fn phases_emit_start_progress_done() {
+        let (progress, buffer) = progress(Duration::ZERO);
+        progress.phase("indexing", Some(2));
+        progress.update(1, 2, Some("rust-errors"));
+        progress.update(2, 2, Some("go-errors"));
+        progress.phase("reconcile", None);
+        progress.fail("layer conflict");
+
+        let events = buffer.events();
+        assert_eq!(events.len(), 6);
+        assert_eq!(
+            events[0],
+            PhaseEvent::Start {
+                phase: "indexing".to_string(),
+                total: Some(2)
+            }
+        );
+        assert_eq!(
+            events[1],
+            PhaseEvent::Progress {
+                phase: "indexing".to_string(),
+                current: 1,
+                total: 2,
+                skill: Some("rust-errors".to_string())
+            }
+        );
+        assert!(matches!(&events[3], PhaseEvent::Done { phase, .. } if phase == "indexing"));
+        assert!(
+            matches!(&events[4], PhaseEvent::Start { phase, total: None } if phase == "reconcile")
+        );
+        assert!(
+            matches!(&events[5], PhaseEvent::Error { phase, message, .. } if phase == "reconcile" && message == "layer conflict")
+        );
+    }
+
+    #[test]
+    fn progress_events_are_throttled_but_keep_first_and_last() {
+        let (progress, buffer) = progress(Duration::from_secs(3600));
+        progress.phase("indexing", Some(100));
+        for current in 1..=100 {
+            progress.update(current, 100, None);
+        }
+        progress.finish();
+
+        let currents: Vec<u64> = buffer
+            .events()
+            .iter()
+            .filter_map(|event| match event {
+                PhaseEvent::Progress { current, .. } => Some(*current),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(currents, [1, 100]);
+    }
+
+    #[test]
+    fn wire_format_matches_documented_shape() {
+        let line = serde_json::to_value(PhaseEvent::Progress {
+            phase: "indexing".to_string(),
+            current: 120,
+            total: 800,
+            skill: Some("rust-errors".to_string()),
+        })
+        .unwrap();
+        assert_eq!(
+            line,
+            serde_json::json!({
+                "event": "progress",
+                "phase": "indexing",
+                "current": 120,
+                "total": 800,
+                "skill": "rust-errors",
+            })
+        );
+    }
+
+    #[test]
+    fn updates_outside_a_phase_are_ignored() {
+        let (progress, buffer) = progress(Duration::ZERO);
+        progress.update(1, 2, None);
+        progress.finish();
+        progress.fail("nothing open");
+        assert!(buffer.events().is_empty());
+    }
+}
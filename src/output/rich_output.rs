@@ -306,7 +306,7 @@ impl RichOutput {
     ) -> Self {
         let mode = match format {
             OutputFormat::Json | OutputFormat::Jsonl | OutputFormat::Toon => OutputMode::Json,
-            OutputFormat::Plain | OutputFormat::Tsv => OutputMode::Plain,
+            OutputFormat::Plain | OutputFormat::Tsv | OutputFormat::Csv => OutputMode::Plain,
             OutputFormat::Human => {
                 if decision.use_rich {
                     OutputMode::Rich
@@ -0,0 +1,373 @@
+//! Skill efficacy: correlating skill loads with downstream session outcomes.
+//!
+//! Recorded loads are matched to CASS sessions, first by session id and
+//! otherwise by project plus timestamp proximity. For each skill we compare
+//! the average session quality (and failure rate) of sessions that loaded it
+//! against sessions in the same projects that did not. Loads that cannot be
+//! matched are excluded and counted.
+//!
+//! This is correlation, not causation: skills tend to be loaded for harder
+//! tasks, and small samples are noisy. The score only feeds quality scoring
+//! when explicitly enabled (`[efficacy] quality_weight`).
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Caveat shown alongside every efficacy report.
+pub const CORRELATION_CAVEAT: &str = "correlation, not causation: sessions that load a skill may differ in difficulty from those that do not";
+
+/// Session signals counted as failures.
+const FAILURE_SIGNALS: [&str; 2] = ["abandoned", "backtracking"];
+
+/// A recorded skill load.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadEvent {
+    pub skill_id: String,
+    pub session_id: Option<String>,
+    pub project: Option<String>,
+    pub used_at: Option<DateTime<Utc>>,
+}
+
+/// Quality outcome of one agent session.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionOutcome {
+    pub session_id: String,
+    pub project: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub quality: f32,
+    pub failed: bool,
+}
+
+impl SessionOutcome {
+    /// Whether the session's quality signals include a failure signal.
+    #[must_use]
+    pub fn is_failure(signals: &[String]) -> bool {
+        signals
+            .iter()
+            .any(|signal| FAILURE_SIGNALS.contains(&signal.as_str()))
+    }
+}
+
+/// Per-skill efficacy statistics.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkillEfficacy {
+    pub skill_id: String,
+    /// Sessions in which the skill was loaded.
+    pub sessions_with: u64,
+    pub mean_quality_with: f32,
+    pub failure_rate_with: f32,
+    /// Comparable sessions (same projects) in which it was not loaded.
+    pub sessions_without: u64,
+    pub mean_quality_without: Option<f32>,
+    pub failure_rate_without: Option<f32>,
+    pub computed_at: String,
+}
+
+impl SkillEfficacy {
+    /// Quality difference with vs without the skill, when a baseline exists.
+    #[must_use]
+    pub fn quality_delta(&self) -> Option<f32> {
+        self.mean_quality_without
+            .map(|without| self.mean_quality_with - without)
+    }
+
+    /// Whether both sides have at least `min_samples` sessions.
+    #[must_use]
+    pub const fn has_samples(&self, min_samples: u64) -> bool {
+        self.sessions_with >= min_samples && self.sessions_without >= min_samples
+    }
+
+    /// Map the quality delta onto a 0..1 score (0.5 = no difference).
+    #[must_use]
+    pub fn score(&self) -> Option<f32> {
+        self.quality_delta()
+            .map(|delta| (0.5 + delta).clamp(0.0, 1.0))
+    }
+}
+
+/// Result of a correlation run.
+#[derive(Debug, Clone, Serialize)]
+pub struct EfficacyReport {
+    pub skills: Vec<SkillEfficacy>,
+    pub matched_loads: usize,
+    pub unmatched_loads: usize,
+    pub sessions_considered: usize,
+    pub caveat: &'static str,
+}
+
+/// Find the session a load belongs to.
+///
+/// Session ids win; otherwise the closest session in the same project that
+/// started within `window` of the load.
+#[must_use]
+pub fn match_load<'a>(
+    load: &LoadEvent,
+    sessions: &'a [SessionOutcome],
+    window: Duration,
+) -> Option<&'a SessionOutcome> {
+    if let Some(session_id) = &load.session_id {
+        if let Some(session) = sessions.iter().find(|s| &s.session_id == session_id) {
+            return Some(session);
+        }
+    }
+    let project = normalize_project(load.project.as_deref()?);
+    let used_at = load.used_at?;
+    sessions
+        .iter()
+        .filter(|s| {
+            s.project
+                .as_deref()
+                .is_some_and(|p| normalize_project(p) == project)
+        })
+        .filter_map(|s| {
+            let gap = (s.started_at? - used_at).abs();
+            (gap <= window).then_some((gap, s))
+        })
+        .min_by_key(|(gap, _)| *gap)
+        .map(|(_, s)| s)
+}
+
+fn normalize_project(project: &str) -> &str {
+    project.trim_end_matches('/')
+}
+
+/// Match loads to sessions and aggregate per-skill efficacy.
+#[must_use]
+pub fn correlate(
+    loads: &[LoadEvent],
+    sessions: &[SessionOutcome],
+    window: Duration,
+    now: DateTime<Utc>,
+) -> EfficacyReport {
+    let mut loaded_in: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    let mut matched_loads = 0;
+    for load in loads {
+        if let Some(session) = match_load(load, sessions, window) {
+            matched_loads += 1;
+            loaded_in
+                .entry(load.skill_id.as_str())
+                .or_default()
+                .insert(session.session_id.as_str());
+        }
+    }
+
+    let by_id: HashMap<&str, &SessionOutcome> = sessions
+        .iter()
+        .map(|s| (s.session_id.as_str(), s))
+        .collect();
+    let computed_at = now.to_rfc3339();
+
+    let skills = loaded_in
+        .into_iter()
+        .map(|(skill_id, with_ids)| {
+            let with: Vec<&SessionOutcome> = with_ids
+                .iter()
+                .filter_map(|id| by_id.get(id).copied())
+                .collect();
+            let projects: BTreeSet<&str> = with
+                .iter()
+                .filter_map(|s| s.project.as_deref().map(normalize_project))
+                .collect();
+            let without: Vec<&SessionOutcome> = sessions
+                .iter()
+                .filter(|s| !with_ids.contains(s.session_id.as_str()))
+                .filter(|s| {
+                    s.project
+                        .as_deref()
+                        .is_some_and(|p| projects.contains(normalize_project(p)))
+                })
+                .collect();
+            let (mean_with, failures_with) = summarize(&with);
+            let (mean_without, failures_without) = summarize(&without);
+            SkillEfficacy {
+                skill_id: skill_id.to_string(),
+                sessions_with: with.len() as u64,
+                mean_quality_with: mean_with.unwrap_or(0.0),
+                failure_rate_with: failures_with.unwrap_or(0.0),
+                sessions_without: without.len() as u64,
+                mean_quality_without: mean_without,
+                failure_rate_without: failures_without,
+                computed_at: computed_at.clone(),
+            }
+        })
+        .collect();
+
+    EfficacyReport {
+        skills,
+        matched_loads,
+        unmatched_loads: loads.len() - matched_loads,
+        sessions_considered: sessions.len(),
+        caveat: CORRELATION_CAVEAT,
+    }
+}
+
+/// Mean quality and failure rate of a set of sessions.
+fn summarize(sessions: &[&SessionOutcome]) -> (Option<f32>, Option<f32>) {
+    if sessions.is_empty() {
+        return (None, None);
+    }
+    let n = sessions.len() as f32;
+    let quality = sessions.iter().map(|s| s.quality).sum::<f32>() / n;
+    let failures = sessions.iter().filter(|s| s.failed).count() as f32 / n;
+    (Some(quality), Some(failures))
+}
+
+/// Parse a load or session timestamp (RFC 3339 or epoch milliseconds).
+#[must_use]
+pub fn parse_timestamp(raw: &str) -> Option<DateTime<Utc>> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(raw) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    raw.parse::<i64>()
+        .ok()
+        .and_then(DateTime::<Utc>::from_timestamp_millis)
+}
+
+/// Blend an efficacy score into an overall quality score.
+///
+/// `weight` of 0 (the default) leaves the quality score untouched.
+#[must_use]
+pub fn blend_quality(overall: f32, efficacy: Option<f32>, weight: f32) -> f32 {
+    match efficacy {
+        Some(score) if weight > 0.0 => {
+            let weight = weight.clamp(0.0, 1.0);
+            overall * (1.0 - weight) + score * weight
+        }
+        _ => overall,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ts(minute: i64) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-03-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+            + Duration::minutes(minute)
+    }
+
+    fn session(id: &str, project: &str, minute: i64, quality: f32, failed: bool) -> SessionOutcome {
+        SessionOutcome {
+            session_id: id.to_string(),
+            project: Some(project.to_string()),
+            started_at: Some(ts(minute)),
+            quality,
+            failed,
+        }
+    }
+
+    fn load(skill: &str, session: Option<&str>, project: Option<&str>, minute: i64) -> LoadEvent {
+        LoadEvent {
+            skill_id: skill.to_string(),
+            session_id: session.map(str::to_string),
+            project: project.map(str::to_string),
+            used_at: Some(ts(minute)),
+        }
+    }
+
+    #[test]
+    fn session_id_match_beats_proximity() {
+        let sessions = vec![
+            session("s1", "/p", 0, 0.9, false),
+            session("s2", "/p", 100, 0.1, false),
+        ];
+        let by_id = load("a", Some("s2"), Some("/p"), 1);
+        assert_eq!(
+            match_load(&by_id, &sessions, Duration::minutes(30))
+                .unwrap()
+                .session_id,
+            "s2"
+        );
+        let by_time = load("a", Some("unknown"), Some("/p/"), 5);
+        assert_eq!(
+            match_load(&by_time, &sessions, Duration::minutes(30))
+                .unwrap()
+                .session_id,
+            "s1"
+        );
+        let too_far = load("a", None, Some("/p"), 50);
+        assert!(match_load(&too_far, &sessions, Duration::minutes(30)).is_none());
+        let other_project = load("a", None, Some("/q"), 0);
+        assert!(match_load(&other_project, &sessions, Duration::minutes(30)).is_none());
+    }
+
+    #[test]
+    fn aggregates_with_and_without_in_comparable_projects() {
+        let sessions = vec![
+            session("s1", "/p", 0, 0.8, false),
+            session("s2", "/p", 100, 0.6, true),
+            session("s3", "/p", 200, 0.4, false),
+            session("s4", "/p", 300, 0.2, true),
+            // Different project: never part of the baseline.
+            session("s5", "/q", 0, 0.0, true),
+        ];
+        let loads = vec![
+            load("a", Some("s1"), None, 0),
+            load("a", Some("s2"), None, 100),
+            // Second load in the same session counts once.
+            load("a", None, Some("/p"), 101),
+            // No session id, no project: unmatched.
+            load("a", None, None, 0),
+        ];
+
+        let report = correlate(&loads, &sessions, Duration::minutes(30), ts(0));
+        assert_eq!(report.matched_loads, 3);
+        assert_eq!(report.unmatched_loads, 1);
+        assert_eq!(report.sessions_considered, 5);
+        assert_eq!(report.caveat, CORRELATION_CAVEAT);
+
+        let a = &report.skills[0];
+        assert_eq!(a.sessions_with, 2);
+        assert!((a.mean_quality_with - 0.7).abs() < 1e-6);
+        assert!((a.failure_rate_with - 0.5).abs() < 1e-6);
+        assert_eq!(a.sessions_without, 2);
+        assert!((a.mean_quality_without.unwrap() - 0.3).abs() < 1e-6);
+        assert!((a.failure_rate_without.unwrap() - 0.5).abs() < 1e-6);
+        assert!((a.quality_delta().unwrap() - 0.4).abs() < 1e-6);
+        assert!((a.score().unwrap() - 0.9).abs() < 1e-6);
+        assert!(a.has_samples(2));
+        assert!(!a.has_samples(3));
+    }
+
+    #[test]
+    fn skill_without_baseline_has_no_delta() {
+        let sessions = vec![session("s1", "/p", 0, 0.5, false)];
+        let report = correlate(
+            &[load("a", Some("s1"), None, 0)],
+            &sessions,
+            Duration::minutes(30),
+            ts(0),
+        );
+        let a = &report.skills[0];
+        assert_eq!(a.sessions_without, 0);
+        assert_eq!(a.quality_delta(), None);
+        assert_eq!(a.score(), None);
+    }
+
+    #[test]
+    fn blend_is_noop_by_default() {
+        assert!((blend_quality(0.6, Some(1.0), 0.0) - 0.6).abs() < 1e-6);
+        assert!((blend_quality(0.6, None, 0.5) - 0.6).abs() < 1e-6);
+        assert!((blend_quality(0.6, Some(1.0), 0.5) - 0.8).abs() < 1e-6);
+    }
+
+    #[test]
+    fn parses_rfc3339_and_epoch_millis() {
+        assert_eq!(parse_timestamp("2026-03-01T12:00:00Z"), Some(ts(0)));
+        assert_eq!(
+            parse_timestamp(&ts(0).timestamp_millis().to_string()),
+            Some(ts(0))
+        );
+        assert_eq!(parse_timestamp("yesterday"), None);
+    }
+
+    #[test]
+    fn failure_signals_are_detected() {
+        assert!(SessionOutcome::is_failure(&["abandoned".to_string()]));
+        assert!(!SessionOutcome::is_failure(&["tests_passed".to_string()]));
+    }
+}
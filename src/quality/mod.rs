@@ -1,8 +1,12 @@
 //! Quality tooling integrations.
 
+pub mod efficacy;
 pub mod skill;
+pub mod trials;
 pub mod ubs;
+pub mod usage;
 
 pub use skill::{
-    QualityBreakdown, QualityContext, QualityIssue, QualityScore, QualityScorer, QualityWeights,
+    ExplainedIssue, QualityBreakdown, QualityComponent, QualityContext, QualityExplanation,
+    QualityIssue, QualityScore, QualityScorer, QualityWeights,
 };
@@ -1,6 +1,9 @@
 //! Skill quality scoring.
 
+use std::fmt;
+
 use chrono::{DateTime, Utc};
+use serde::Serialize;
 
 use crate::core::{BlockType, SkillSpec};
 
@@ -55,6 +58,105 @@ impl QualityScorer {
             suggestions,
         }
     }
+
+    /// Score `spec` and break the result down per component: weight, raw
+    /// value, what was measured, and the issues lowering it.
+    #[must_use]
+    pub fn explain(&self, spec: &SkillSpec, context: &QualityContext) -> QualityExplanation {
+        let score = self.score_spec(spec, context);
+        let weights = &self.weights;
+        let weight_sum = weights.structure_weight
+            + weights.content_weight
+            + weights.evidence_weight
+            + weights.usage_weight
+            + weights.toolchain_weight
+            + weights.freshness_weight;
+        let (chars, code_blocks) = content_stats(spec);
+        let rows = [
+            (
+                "structure",
+                weights.structure_weight,
+                score.breakdown.structure,
+                format!("{} section(s)", spec.sections.len()),
+            ),
+            (
+                "content",
+                weights.content_weight,
+                score.breakdown.content,
+                format!("{chars} chars, {code_blocks} code block(s)"),
+            ),
+            (
+                "evidence",
+                weights.evidence_weight,
+                score.breakdown.evidence,
+                format!("{} evidence record(s)", context.evidence_count.unwrap_or(0)),
+            ),
+            (
+                "usage",
+                weights.usage_weight,
+                score.breakdown.usage,
+                format!("{} load(s)", context.usage_count.unwrap_or(0)),
+            ),
+            (
+                "toolchain",
+                weights.toolchain_weight,
+                score.breakdown.toolchain,
+                if context.toolchain_match {
+                    "matches the project toolchain".to_string()
+                } else {
+                    "does not match the project toolchain".to_string()
+                },
+            ),
+            (
+                "freshness",
+                weights.freshness_weight,
+                score.breakdown.freshness,
+                context.modified_at.map_or_else(
+                    || "modification time unknown".to_string(),
+                    |at| {
+                        let days = Utc::now().signed_duration_since(at).num_days().max(0);
+                        format!("modified {days} day(s) ago")
+                    },
+                ),
+            ),
+        ];
+
+        let explained: Vec<(Option<&'static str>, ExplainedIssue)> = score
+            .issues
+            .iter()
+            .map(|issue| (issue.component(), ExplainedIssue::new(issue, spec)))
+            .collect();
+        let components = rows
+            .into_iter()
+            .map(|(name, weight, value, detail)| QualityComponent {
+                name,
+                weight,
+                value,
+                contribution: if weight_sum == 0.0 {
+                    0.0
+                } else {
+                    value * weight / weight_sum
+                },
+                detail,
+                issues: explained
+                    .iter()
+                    .filter(|(component, _)| *component == Some(name))
+                    .map(|(_, issue)| issue.clone())
+                    .collect(),
+            })
+            .collect();
+        let other_issues = explained
+            .into_iter()
+            .filter(|(component, _)| component.is_none())
+            .map(|(_, issue)| issue)
+            .collect();
+
+        QualityExplanation {
+            overall: score.overall,
+            components,
+            other_issues,
+        }
+    }
 }
 
 impl Default for QualityScorer {
@@ -105,6 +207,26 @@ impl Default for QualityWeights {
     }
 }
 
+impl QualityWeights {
+    /// Short hash identifying these weights, stored next to computed scores
+    /// so scores from other weights can be detected.
+    #[must_use]
+    pub fn fingerprint(&self) -> String {
+        use sha2::{Digest, Sha256};
+
+        let canonical = format!(
+            "structure={};content={};evidence={};usage={};toolchain={};freshness={}",
+            self.structure_weight,
+            self.content_weight,
+            self.evidence_weight,
+            self.usage_weight,
+            self.toolchain_weight,
+            self.freshness_weight
+        );
+        hex::encode(&Sha256::digest(canonical.as_bytes())[..8])
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct QualityScore {
     pub overall: f32,
@@ -133,6 +255,140 @@ pub enum QualityIssue {
     NoTags,
 }
 
+impl QualityIssue {
+    /// Stable identifier for machine output.
+    #[must_use]
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::MissingSection(_) => "missing_section",
+            Self::ShortContent(..) => "short_content",
+            Self::NoExamples => "no_examples",
+            Self::LowEvidence(_) => "low_evidence",
+            Self::LowUsage(_) => "low_usage",
+            Self::NoTags => "no_tags",
+        }
+    }
+
+    /// Score component this issue lowers, if any (tags are not scored).
+    #[must_use]
+    pub const fn component(&self) -> Option<&'static str> {
+        match self {
+            Self::MissingSection(_) => Some("structure"),
+            Self::ShortContent(..) | Self::NoExamples => Some("content"),
+            Self::LowEvidence(_) => Some("evidence"),
+            Self::LowUsage(_) => Some("usage"),
+            Self::NoTags => None,
+        }
+    }
+
+    /// How to fix it, if there is an authoring fix.
+    #[must_use]
+    pub const fn suggestion(&self) -> Option<&'static str> {
+        match self {
+            Self::MissingSection(_) => Some("Add a brief overview section"),
+            Self::ShortContent(..) => Some("Expand core sections with more detail"),
+            Self::NoExamples => Some("Add at least one code example"),
+            Self::LowEvidence(_) => Some("Add provenance/evidence links"),
+            Self::LowUsage(_) => None,
+            Self::NoTags => Some("Add tags for discoverability"),
+        }
+    }
+}
+
+impl fmt::Display for QualityIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingSection(name) => write!(f, "missing {name} section"),
+            Self::ShortContent(_, sections) => {
+                write!(f, "little content across {sections} section(s)")
+            }
+            Self::NoExamples => write!(f, "no code examples"),
+            Self::LowEvidence(count) => write!(f, "only {count} evidence record(s)"),
+            Self::LowUsage(count) => write!(f, "only {count} load(s)"),
+            Self::NoTags => write!(f, "no tags"),
+        }
+    }
+}
+
+/// Per-component breakdown of a quality score.
+#[derive(Debug, Clone, Serialize)]
+pub struct QualityExplanation {
+    pub overall: f32,
+    pub components: Vec<QualityComponent>,
+    /// Issues that do not lower any component (e.g. missing tags).
+    pub other_issues: Vec<ExplainedIssue>,
+}
+
+/// One weighted input to the overall score.
+#[derive(Debug, Clone, Serialize)]
+pub struct QualityComponent {
+    pub name: &'static str,
+    pub weight: f32,
+    /// Raw component score (0.0 - 1.0)
+    pub value: f32,
+    /// Share of the overall score this component supplies
+    pub contribution: f32,
+    /// What was measured, e.g. "3 section(s)"
+    pub detail: String,
+    pub issues: Vec<ExplainedIssue>,
+}
+
+/// A [`QualityIssue`] with the section/block it points at.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExplainedIssue {
+    pub kind: &'static str,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub section: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<&'static str>,
+}
+
+impl ExplainedIssue {
+    fn new(issue: &QualityIssue, spec: &SkillSpec) -> Self {
+        let (section, block) = match issue {
+            // The thinnest section and its shortest block are where
+            // expanding pays off first.
+            QualityIssue::ShortContent(..) => spec
+                .sections
+                .iter()
+                .min_by_key(|section| {
+                    section
+                        .blocks
+                        .iter()
+                        .map(|block| block.content.len())
+                        .sum::<usize>()
+                })
+                .map_or((None, None), |section| {
+                    let block = section
+                        .blocks
+                        .iter()
+                        .min_by_key(|block| block.content.len())
+                        .map(|block| block.id.clone());
+                    (Some(section.id.clone()), block)
+                }),
+            // Prose-only sections are where an example would go.
+            QualityIssue::NoExamples => (
+                spec.sections
+                    .iter()
+                    .find(|section| !section.blocks.is_empty())
+                    .map(|section| section.id.clone()),
+                None,
+            ),
+            _ => (None, None),
+        };
+        Self {
+            kind: issue.kind(),
+            message: issue.to_string(),
+            section,
+            block,
+            suggestion: issue.suggestion(),
+        }
+    }
+}
+
 fn score_structure(spec: &SkillSpec) -> f32 {
     let sections = spec.sections.len();
     match sections {
@@ -143,7 +399,8 @@ fn score_structure(spec: &SkillSpec) -> f32 {
     }
 }
 
-fn score_content(spec: &SkillSpec) -> f32 {
+/// Total block characters and number of code blocks.
+fn content_stats(spec: &SkillSpec) -> (usize, usize) {
     let mut chars = 0usize;
     let mut code_blocks = 0;
     for section in &spec.sections {
@@ -154,6 +411,11 @@ fn score_content(spec: &SkillSpec) -> f32 {
             }
         }
     }
+    (chars, code_blocks)
+}
+
+fn score_content(spec: &SkillSpec) -> f32 {
+    let (chars, code_blocks) = content_stats(spec);
 
     let base: f32 = if chars > 2000 {
         1.0
@@ -229,11 +491,9 @@ fn collect_issues(
     usage: f32,
 ) -> (Vec<QualityIssue>, Vec<String>) {
     let mut issues = Vec::new();
-    let mut suggestions = Vec::new();
 
     if spec.sections.is_empty() || structure < 0.5 {
         issues.push(QualityIssue::MissingSection("overview".to_string()));
-        suggestions.push("Add a brief overview section".to_string());
     }
 
     if content < 0.4 {
@@ -241,7 +501,6 @@ fn collect_issues(
             "overall".to_string(),
             spec.sections.len(),
         ));
-        suggestions.push("Expand core sections with more detail".to_string());
     }
 
     let has_examples = spec.sections.iter().any(|section| {
@@ -253,13 +512,11 @@ fn collect_issues(
     });
     if !has_examples {
         issues.push(QualityIssue::NoExamples);
-        suggestions.push("Add at least one code example".to_string());
     }
 
     if evidence < 0.5 {
         let count = context.evidence_count.unwrap_or(0);
         issues.push(QualityIssue::LowEvidence(count));
-        suggestions.push("Add provenance/evidence links".to_string());
     }
 
     if usage < 0.3 {
@@ -269,9 +526,13 @@ fn collect_issues(
 
     if spec.metadata.tags.is_empty() {
         issues.push(QualityIssue::NoTags);
-        suggestions.push("Add tags for discoverability".to_string());
     }
 
+    let suggestions = issues
+        .iter()
+        .filter_map(QualityIssue::suggestion)
+        .map(str::to_string)
+        .collect();
     (issues, suggestions)
 }
 
@@ -307,4 +568,35 @@ mod tests {
         let score = scorer.score_spec(&minimal_spec(), &QualityContext::default());
         assert!(score.overall >= 0.0 && score.overall <= 1.0);
     }
+
+    #[test]
+    fn explanation_locates_issues_and_sums_to_overall() {
+        let scorer = QualityScorer::with_defaults();
+        let explanation = scorer.explain(&minimal_spec(), &QualityContext::default());
+
+        let total: f32 = explanation.components.iter().map(|c| c.contribution).sum();
+        assert!((total - explanation.overall).abs() < 1e-5);
+        assert_eq!(explanation.components.len(), 6);
+
+        let content = explanation
+            .components
+            .iter()
+            .find(|c| c.name == "content")
+            .unwrap();
+        assert_eq!(content.detail, "5 chars, 0 code block(s)");
+        let short = content
+            .issues
+            .iter()
+            .find(|issue| issue.kind == "short_content")
+            .unwrap();
+        assert_eq!(short.section.as_deref(), Some("overview"));
+        assert_eq!(short.block.as_deref(), Some("b1"));
+        assert_eq!(
+            short.suggestion,
+            Some("Expand core sections with more detail")
+        );
+
+        let kinds: Vec<&str> = explanation.other_issues.iter().map(|i| i.kind).collect();
+        assert_eq!(kinds, vec!["no_tags"]);
+    }
 }
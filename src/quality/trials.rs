@@ -0,0 +1,165 @@
+//! Skill trials: loading a skill on probation and reporting whether it helped.
+//!
+//! `ms load --trial` opens a trial for the served skill; `ms trial report`
+//! closes it with an outcome. Reported outcomes feed the contextual bandit
+//! and move the skill's quality score by at most [`MAX_ADJUSTMENT`], reaching
+//! the full amount once [`FULL_WEIGHT_TRIALS`] trials have been reported.
+//! Trials nobody reports on are expired by `ms prune --expired-trials` and
+//! count for nothing.
+
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Most reported trials can move a quality score, in either direction.
+pub const MAX_ADJUSTMENT: f32 = 0.1;
+
+/// Reported trials needed for the full adjustment.
+pub const FULL_WEIGHT_TRIALS: u64 = 10;
+
+/// Days after which an unreported trial is considered dangling.
+pub const EXPIRY_DAYS: u32 = 7;
+
+/// How a trial went, as reported by the agent or user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrialOutcome {
+    Success,
+    Failure,
+    Neutral,
+}
+
+impl TrialOutcome {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::Failure => "failure",
+            Self::Neutral => "neutral",
+        }
+    }
+
+    /// Bandit reward for this outcome.
+    #[must_use]
+    pub const fn reward(self) -> f32 {
+        match self {
+            Self::Success => 1.0,
+            Self::Failure => 0.0,
+            Self::Neutral => 0.5,
+        }
+    }
+}
+
+impl FromStr for TrialOutcome {
+    type Err = String;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        match raw {
+            "success" => Ok(Self::Success),
+            "failure" => Ok(Self::Failure),
+            "neutral" => Ok(Self::Neutral),
+            other => Err(format!(
+                "unknown trial outcome '{other}' (expected success, failure or neutral)"
+            )),
+        }
+    }
+}
+
+/// Trial counts of one skill.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct TrialStats {
+    pub skill_id: String,
+    /// Trials started
+    pub trials: u64,
+    /// Trials not reported or expired yet
+    pub open: u64,
+    pub success: u64,
+    pub failure: u64,
+    pub neutral: u64,
+    pub expired: u64,
+}
+
+impl TrialStats {
+    /// Trials closed with an outcome.
+    #[must_use]
+    pub const fn reported(&self) -> u64 {
+        self.success + self.failure + self.neutral
+    }
+
+    /// Mean reward of the reported trials (neutral counts half).
+    #[must_use]
+    pub fn success_rate(&self) -> Option<f32> {
+        let reported = self.reported();
+        (reported > 0).then(|| (self.success as f32 + self.neutral as f32 * 0.5) / reported as f32)
+    }
+
+    /// Quality score adjustment: the success rate's distance from 0.5,
+    /// scaled to [`MAX_ADJUSTMENT`] and by how many trials back it.
+    #[must_use]
+    pub fn quality_adjustment(&self) -> f32 {
+        let Some(rate) = self.success_rate() else {
+            return 0.0;
+        };
+        let confidence = self.reported().min(FULL_WEIGHT_TRIALS) as f32 / FULL_WEIGHT_TRIALS as f32;
+        (rate - 0.5) * 2.0 * MAX_ADJUSTMENT * confidence
+    }
+}
+
+/// Apply a skill's trial record to a quality score.
+#[must_use]
+pub fn adjust_quality(overall: f32, stats: Option<&TrialStats>) -> f32 {
+    stats.map_or(overall, |stats| {
+        (overall + stats.quality_adjustment()).clamp(0.0, 1.0)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(success: u64, failure: u64, neutral: u64) -> TrialStats {
+        TrialStats {
+            skill_id: "rust-errors".to_string(),
+            trials: success + failure + neutral,
+            success,
+            failure,
+            neutral,
+            ..TrialStats::default()
+        }
+    }
+
+    #[test]
+    fn outcomes_parse_and_map_to_rewards() {
+        for outcome in [
+            TrialOutcome::Success,
+            TrialOutcome::Failure,
+            TrialOutcome::Neutral,
+        ] {
+            assert_eq!(outcome.as_str().parse::<TrialOutcome>(), Ok(outcome));
+        }
+        assert!("maybe".parse::<TrialOutcome>().is_err());
+        assert!(TrialOutcome::Success.reward() > TrialOutcome::Neutral.reward());
+        assert!(TrialOutcome::Neutral.reward() > TrialOutcome::Failure.reward());
+    }
+
+    #[test]
+    fn adjustment_grows_with_reported_trials() {
+        assert!(stats(0, 0, 0).success_rate().is_none());
+        assert!(stats(0, 0, 0).quality_adjustment().abs() < f32::EPSILON);
+        assert!(stats(1, 1, 2).quality_adjustment().abs() < f32::EPSILON);
+
+        let one = stats(1, 0, 0).quality_adjustment();
+        let ten = stats(10, 0, 0).quality_adjustment();
+        assert!(one > 0.0 && one < ten);
+        assert!((ten - MAX_ADJUSTMENT).abs() < f32::EPSILON);
+        assert!((stats(40, 0, 0).quality_adjustment() - MAX_ADJUSTMENT).abs() < f32::EPSILON);
+        assert!((stats(0, 10, 0).quality_adjustment() + MAX_ADJUSTMENT).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn adjusted_quality_stays_in_range() {
+        assert!((adjust_quality(0.6, None) - 0.6).abs() < f32::EPSILON);
+        assert!((adjust_quality(0.98, Some(&stats(10, 0, 0))) - 1.0).abs() < f32::EPSILON);
+        assert!(adjust_quality(0.02, Some(&stats(0, 10, 0))).abs() < f32::EPSILON);
+    }
+}
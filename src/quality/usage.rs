@@ -0,0 +1,365 @@
+//! Skill usage analytics for `ms stats`.
+//!
+//! Folds the loads, shown suggestions, and feedback recorded inside a time
+//! window into one row per skill (or per layer). Each row carries a load
+//! trend bucketed by day, or by week once the window is longer than four
+//! weeks.
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::Serialize;
+
+use crate::error::{MsError, Result};
+use crate::storage::sqlite::{SkillActivity, SkillKey};
+
+/// Start of the window described by `input`: a relative age (`7d`, `4w`) or
+/// an ISO date (`2026-03-01`) or timestamp.
+pub fn parse_since(input: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    let input = input.trim();
+    let invalid = || {
+        MsError::ValidationFailed(format!(
+            "invalid --since value {input:?}: expected e.g. 7d, 4w, or 2026-03-01"
+        ))
+    };
+
+    let since = if let Some(count) = input.strip_suffix('d') {
+        now - relative_days(count, 1).ok_or_else(invalid)?
+    } else if let Some(count) = input.strip_suffix('w') {
+        now - relative_days(count, 7).ok_or_else(invalid)?
+    } else if let Ok(at) = DateTime::parse_from_rfc3339(input) {
+        at.with_timezone(&Utc)
+    } else {
+        NaiveDate::parse_from_str(input, "%Y-%m-%d")
+            .map_err(|_| invalid())?
+            .and_hms_opt(0, 0, 0)
+            .ok_or_else(invalid)?
+            .and_utc()
+    };
+
+    if since > now {
+        return Err(MsError::ValidationFailed(format!(
+            "--since {input} is in the future"
+        )));
+    }
+    Ok(since)
+}
+
+fn relative_days(count: &str, unit_days: i64) -> Option<Duration> {
+    let count: i64 = count.parse().ok().filter(|count| *count > 0)?;
+    Duration::try_days(count.checked_mul(unit_days)?)
+}
+
+/// Width of one trend bucket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BucketSize {
+    Day,
+    Week,
+}
+
+impl BucketSize {
+    const fn duration(self) -> Duration {
+        match self {
+            Self::Day => Duration::days(1),
+            Self::Week => Duration::weeks(1),
+        }
+    }
+}
+
+/// The time range `ms stats` aggregates over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct UsageWindow {
+    pub since: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    pub bucket: BucketSize,
+}
+
+impl UsageWindow {
+    #[must_use]
+    pub fn new(since: DateTime<Utc>, until: DateTime<Utc>) -> Self {
+        let bucket = if until - since > Duration::weeks(4) {
+            BucketSize::Week
+        } else {
+            BucketSize::Day
+        };
+        Self {
+            since,
+            until,
+            bucket,
+        }
+    }
+
+    /// Number of trend buckets; the last one may be partial.
+    #[must_use]
+    pub fn bucket_count(&self) -> usize {
+        let span = (self.until - self.since).num_seconds().max(1) as u64;
+        let width = self.bucket.duration().num_seconds() as u64;
+        span.div_ceil(width) as usize
+    }
+
+    /// Bucket `at` falls in, or `None` outside the window.
+    #[must_use]
+    pub fn bucket_of(&self, at: DateTime<Utc>) -> Option<usize> {
+        if at < self.since || at > self.until {
+            return None;
+        }
+        let index = (at - self.since).num_seconds() / self.bucket.duration().num_seconds();
+        Some((index as usize).min(self.bucket_count() - 1))
+    }
+}
+
+/// Usage of one skill, or of every skill in a layer.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UsageRow {
+    /// Skill id, or the layer name when grouped by layer
+    pub key: String,
+    pub layer: String,
+    /// Skills folded into this row
+    pub skills: usize,
+    pub loads: u64,
+    pub last_loaded: Option<DateTime<Utc>>,
+    pub suggestions_shown: u64,
+    pub suggestions_selected: u64,
+    /// Shown suggestions that were selected, ignored, or hidden
+    pub suggestions_decided: u64,
+    pub positive_feedback: u64,
+    pub negative_feedback: u64,
+    /// Loads per bucket, oldest first
+    pub trend: Vec<u64>,
+}
+
+impl UsageRow {
+    fn empty(key: String, layer: String, buckets: usize) -> Self {
+        Self {
+            key,
+            layer,
+            skills: 0,
+            loads: 0,
+            last_loaded: None,
+            suggestions_shown: 0,
+            suggestions_selected: 0,
+            suggestions_decided: 0,
+            positive_feedback: 0,
+            negative_feedback: 0,
+            trend: vec![0; buckets],
+        }
+    }
+
+    /// Selected share of suggestions with a final outcome.
+    #[must_use]
+    pub fn acceptance_rate(&self) -> Option<f64> {
+        (self.suggestions_decided > 0)
+            .then(|| self.suggestions_selected as f64 / self.suggestions_decided as f64)
+    }
+
+    /// Never loaded in the window, and only ever rated negatively.
+    #[must_use]
+    pub const fn is_prune_candidate(&self) -> bool {
+        self.loads == 0 && self.negative_feedback > 0 && self.positive_feedback == 0
+    }
+
+    fn add_activity(&mut self, activity: &SkillActivity) {
+        self.suggestions_shown += activity.suggestions_shown;
+        self.suggestions_selected += activity.suggestions_selected;
+        self.suggestions_decided += activity.suggestions_decided;
+        self.positive_feedback += activity.positive_feedback;
+        self.negative_feedback += activity.negative_feedback;
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.skills += other.skills;
+        self.loads += other.loads;
+        self.last_loaded = self.last_loaded.max(other.last_loaded);
+        self.suggestions_shown += other.suggestions_shown;
+        self.suggestions_selected += other.suggestions_selected;
+        self.suggestions_decided += other.suggestions_decided;
+        self.positive_feedback += other.positive_feedback;
+        self.negative_feedback += other.negative_feedback;
+        for (total, count) in self.trend.iter_mut().zip(&other.trend) {
+            *total += count;
+        }
+    }
+}
+
+/// One row per indexed skill. Loads of skills no longer indexed are ignored.
+#[must_use]
+pub fn skill_rows(
+    skills: &[SkillKey],
+    loads: &[(String, String)],
+    activity: &HashMap<String, SkillActivity>,
+    window: &UsageWindow,
+) -> Vec<UsageRow> {
+    let buckets = window.bucket_count();
+    let mut rows: Vec<UsageRow> = skills
+        .iter()
+        .map(|skill| {
+            let mut row =
+                UsageRow::empty(skill.id.clone(), skill.source_layer.to_lowercase(), buckets);
+            row.skills = 1;
+            if let Some(activity) = activity.get(&skill.id) {
+                row.add_activity(activity);
+            }
+            row
+        })
+        .collect();
+    let index: HashMap<&str, usize> = skills
+        .iter()
+        .enumerate()
+        .map(|(i, skill)| (skill.id.as_str(), i))
+        .collect();
+
+    for (skill_id, used_at) in loads {
+        let Some(&i) = index.get(skill_id.as_str()) else {
+            continue;
+        };
+        let Ok(at) = DateTime::parse_from_rfc3339(used_at) else {
+            continue;
+        };
+        let at = at.with_timezone(&Utc);
+        let row = &mut rows[i];
+        row.loads += 1;
+        row.last_loaded = row.last_loaded.max(Some(at));
+        if let Some(bucket) = window.bucket_of(at) {
+            row.trend[bucket] += 1;
+        }
+    }
+    rows
+}
+
+/// Fold skill rows into one row per layer.
+#[must_use]
+pub fn group_by_layer(rows: &[UsageRow]) -> Vec<UsageRow> {
+    let mut layers: BTreeMap<&str, UsageRow> = BTreeMap::new();
+    for row in rows {
+        layers
+            .entry(row.layer.as_str())
+            .or_insert_with(|| {
+                UsageRow::empty(row.layer.clone(), row.layer.clone(), row.trend.len())
+            })
+            .merge(row);
+    }
+    layers.into_values().collect()
+}
+
+/// Most loaded first, then by acceptance rate, then by key.
+pub fn sort_rows(rows: &mut [UsageRow]) {
+    rows.sort_by(|a, b| {
+        b.loads
+            .cmp(&a.loads)
+            .then_with(|| {
+                let rate = |row: &UsageRow| row.acceptance_rate().unwrap_or(-1.0);
+                rate(b).total_cmp(&rate(a))
+            })
+            .then_with(|| a.key.cmp(&b.key))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(input: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(input)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn key(id: &str, layer: &str) -> SkillKey {
+        SkillKey {
+            id: id.to_string(),
+            name: id.to_string(),
+            source_layer: layer.to_string(),
+            source_path: format!("/skills/{id}"),
+            indexed_at: "2026-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn parse_since_accepts_relative_and_iso_forms() {
+        let now = at("2026-03-31T12:00:00Z");
+        assert_eq!(parse_since("7d", now).unwrap(), at("2026-03-24T12:00:00Z"));
+        assert_eq!(parse_since("4w", now).unwrap(), at("2026-03-03T12:00:00Z"));
+        assert_eq!(
+            parse_since("2026-03-01", now).unwrap(),
+            at("2026-03-01T00:00:00Z")
+        );
+        assert_eq!(
+            parse_since("2026-03-01T08:30:00+02:00", now).unwrap(),
+            at("2026-03-01T06:30:00Z")
+        );
+        for bad in ["", "d", "0d", "-3d", "3x", "2026-13-01", "2026-04-01"] {
+            assert!(parse_since(bad, now).is_err(), "{bad} should be rejected");
+        }
+    }
+
+    #[test]
+    fn window_picks_bucket_size_and_clamps_the_last_bucket() {
+        let until = at("2026-03-31T12:00:00Z");
+        let week = UsageWindow::new(at("2026-03-24T12:00:00Z"), until);
+        assert_eq!(week.bucket, BucketSize::Day);
+        assert_eq!(week.bucket_count(), 7);
+        assert_eq!(week.bucket_of(at("2026-03-24T12:00:00Z")), Some(0));
+        assert_eq!(week.bucket_of(until), Some(6));
+        assert_eq!(week.bucket_of(at("2026-03-20T00:00:00Z")), None);
+
+        let quarter = UsageWindow::new(at("2026-01-01T00:00:00Z"), until);
+        assert_eq!(quarter.bucket, BucketSize::Week);
+        assert_eq!(quarter.bucket_count(), 13);
+    }
+
+    #[test]
+    fn rows_aggregate_loads_activity_and_layers() {
+        let window = UsageWindow::new(at("2026-03-29T00:00:00Z"), at("2026-03-31T00:00:00Z"));
+        let skills = [
+            key("alpha", "Project"),
+            key("beta", "project"),
+            key("gamma", "user"),
+        ];
+        let loads = [
+            ("alpha".to_string(), "2026-03-29T10:00:00+00:00".to_string()),
+            ("alpha".to_string(), "2026-03-30T10:00:00+00:00".to_string()),
+            ("beta".to_string(), "2026-03-30T11:00:00+00:00".to_string()),
+            (
+                "removed".to_string(),
+                "2026-03-30T11:00:00+00:00".to_string(),
+            ),
+        ];
+        let activity = HashMap::from([
+            (
+                "beta".to_string(),
+                SkillActivity {
+                    suggestions_shown: 4,
+                    suggestions_selected: 3,
+                    suggestions_decided: 4,
+                    ..SkillActivity::default()
+                },
+            ),
+            (
+                "gamma".to_string(),
+                SkillActivity {
+                    negative_feedback: 2,
+                    ..SkillActivity::default()
+                },
+            ),
+        ]);
+
+        let mut rows = skill_rows(&skills, &loads, &activity, &window);
+        sort_rows(&mut rows);
+        let keys: Vec<&str> = rows.iter().map(|row| row.key.as_str()).collect();
+        assert_eq!(keys, vec!["alpha", "beta", "gamma"]);
+        assert_eq!(rows[0].trend, vec![1, 1]);
+        assert_eq!(rows[0].last_loaded, Some(at("2026-03-30T10:00:00Z")));
+        assert_eq!(rows[1].acceptance_rate(), Some(0.75));
+        assert!(rows[2].is_prune_candidate());
+        assert!(!rows[1].is_prune_candidate());
+
+        let layers = group_by_layer(&rows);
+        assert_eq!(layers.len(), 2);
+        assert_eq!(layers[0].key, "project");
+        assert_eq!(layers[0].skills, 2);
+        assert_eq!(layers[0].loads, 3);
+        assert_eq!(layers[0].trend, vec![1, 2]);
+        assert_eq!(layers[1].key, "user");
+    }
+}
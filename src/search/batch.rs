@@ -0,0 +1,271 @@
+//! Batch search: several queries in one call.
+//!
+//! `ms search --batch-file` and the `search_batch` MCP tool run every query
+//! of a batch through one [`QueryRanker`], which holds what is expensive to
+//! open (the index searcher, the embedder, the stored vectors), so a batch
+//! pays for it once instead of once per query. Results come back per query
+//! and merged: each skill once, ranked by reciprocal rank summed over the
+//! queries it matched.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use super::tantivy::Bm25Index;
+use crate::config::BatchSettings;
+use crate::error::{MsError, Result};
+
+/// RRF constant for merging the per-query rankings.
+const MERGE_K: f32 = 60.0;
+
+/// Ranks skills for one query at a time.
+pub trait QueryRanker {
+    /// Best matches for `query`, best first.
+    fn rank(&self, query: &str, limit: usize) -> Result<Vec<RankedSkill>>;
+}
+
+impl QueryRanker for Bm25Index {
+    fn rank(&self, query: &str, limit: usize) -> Result<Vec<RankedSkill>> {
+        Ok(self
+            .search(query, limit)?
+            .into_iter()
+            .map(|hit| RankedSkill {
+                skill_id: hit.skill_id,
+                score: hit.score,
+            })
+            .collect())
+    }
+}
+
+/// One hit of one query.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RankedSkill {
+    pub skill_id: String,
+    pub score: f32,
+}
+
+/// Results of one query of a batch.
+#[derive(Debug, Clone, Serialize)]
+pub struct QueryResults {
+    pub query: String,
+    pub results: Vec<RankedSkill>,
+}
+
+/// A skill in the merged list, with the queries that found it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MergedSkill {
+    pub skill_id: String,
+    /// Reciprocal rank summed over the matching queries
+    pub score: f32,
+    /// Matching queries, in batch order
+    pub matched_queries: Vec<String>,
+}
+
+/// Per-query and merged results of a batch.
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchResults {
+    pub queries: Vec<QueryResults>,
+    pub merged: Vec<MergedSkill>,
+}
+
+/// Queries from a batch file: one per line, blank lines and `#` comments
+/// skipped, repeated queries dropped.
+#[must_use]
+pub fn parse_batch_queries(raw: &str) -> Vec<String> {
+    let mut queries: Vec<String> = Vec::new();
+    for line in raw.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') || queries.iter().any(|q| q == line) {
+            continue;
+        }
+        queries.push(line.to_string());
+    }
+    queries
+}
+
+/// Refuse batches over the `[search.batch]` limits.
+pub fn check_batch_limits(queries: usize, limit: usize, settings: &BatchSettings) -> Result<()> {
+    if queries == 0 {
+        return Err(MsError::ValidationFailed(
+            "batch search needs at least one query".to_string(),
+        ));
+    }
+    if queries > settings.max_queries {
+        return Err(MsError::ValidationFailed(format!(
+            "batch has {queries} queries, more than search.batch.max_queries ({})",
+            settings.max_queries
+        )));
+    }
+    let requested = queries.saturating_mul(limit);
+    if requested > settings.max_results {
+        return Err(MsError::ValidationFailed(format!(
+            "batch asks for {requested} results ({queries} queries x limit {limit}), more than \
+             search.batch.max_results ({}); lower the limit or split the batch",
+            settings.max_results
+        )));
+    }
+    Ok(())
+}
+
+/// Run `queries` through `ranker`, `limit` results each.
+pub fn search_batch<R: QueryRanker + ?Sized>(
+    ranker: &R,
+    queries: &[String],
+    limit: usize,
+    settings: &BatchSettings,
+) -> Result<BatchResults> {
+    check_batch_limits(queries.len(), limit, settings)?;
+
+    let mut per_query = Vec::with_capacity(queries.len());
+    let mut merged: Vec<MergedSkill> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    for query in queries {
+        let results = ranker.rank(query, limit)?;
+        for (rank, hit) in results.iter().enumerate() {
+            let score = 1.0 / (MERGE_K + (rank + 1) as f32);
+            let position = *positions.entry(hit.skill_id.clone()).or_insert_with(|| {
+                merged.push(MergedSkill {
+                    skill_id: hit.skill_id.clone(),
+                    score: 0.0,
+                    matched_queries: Vec::new(),
+                });
+                merged.len() - 1
+            });
+            let entry = &mut merged[position];
+            entry.score += score;
+            entry.matched_queries.push(query.clone());
+        }
+        per_query.push(QueryResults {
+            query: query.clone(),
+            results,
+        });
+    }
+
+    merged.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.skill_id.cmp(&b.skill_id))
+    });
+    Ok(BatchResults {
+        queries: per_query,
+        merged,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::storage::sqlite::SkillRecord;
+
+    /// Index wrapper counting how often it is opened and queried.
+    struct CountingIndex {
+        index: Bm25Index,
+        searches: Cell<usize>,
+    }
+
+    impl CountingIndex {
+        fn open(opens: &Cell<usize>, skills: &[(&str, &str)]) -> Self {
+            opens.set(opens.get() + 1);
+            let index = Bm25Index::open_in_memory().unwrap();
+            for (id, body) in skills {
+                index.index_skill(&skill(id, body)).unwrap();
+            }
+            index.commit().unwrap();
+            Self {
+                index,
+                searches: Cell::new(0),
+            }
+        }
+    }
+
+    impl QueryRanker for CountingIndex {
+        fn rank(&self, query: &str, limit: usize) -> Result<Vec<RankedSkill>> {
+            self.searches.set(self.searches.get() + 1);
+            self.index.rank(query, limit)
+        }
+    }
+
+    fn skill(id: &str, body: &str) -> SkillRecord {
+        SkillRecord {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            version: None,
+            author: None,
+            source_path: format!("/skills/{id}"),
+            source_layer: "project".to_string(),
+            git_remote: None,
+            git_commit: None,
+            content_hash: id.to_string(),
+            body: body.to_string(),
+            metadata_json: "{}".to_string(),
+            assets_json: "{}".to_string(),
+            token_count: 10,
+            quality_score: 0.5,
+            indexed_at: "2025-01-01T00:00:00Z".to_string(),
+            modified_at: "2025-01-01T00:00:00Z".to_string(),
+            is_deprecated: false,
+            deprecation_reason: None,
+        }
+    }
+
+    fn queries(raw: &[&str]) -> Vec<String> {
+        raw.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn batch_opens_the_index_once_and_merges_across_queries() {
+        let opens = Cell::new(0);
+        let ranker = CountingIndex::open(
+            &opens,
+            &[
+                ("error-handling", "error handling with retry and backoff"),
+                ("retry-policy", "retry policy for flaky network calls"),
+                ("logging", "logging conventions and structured fields"),
+            ],
+        );
+        let batch = queries(&["error handling", "retry", "logging conventions"]);
+        let results = search_batch(&ranker, &batch, 5, &BatchSettings::default()).unwrap();
+
+        assert_eq!(opens.get(), 1);
+        assert_eq!(ranker.searches.get(), 3);
+        assert_eq!(results.queries.len(), 3);
+        assert_eq!(results.queries[1].query, "retry");
+
+        // Each skill appears once; the one matching two queries ranks first
+        let ids: Vec<&str> = results.merged.iter().map(|m| m.skill_id.as_str()).collect();
+        let mut unique = ids.clone();
+        unique.sort_unstable();
+        unique.dedup();
+        assert_eq!(ids.len(), unique.len());
+        assert_eq!(results.merged[0].skill_id, "error-handling");
+        assert_eq!(
+            results.merged[0].matched_queries,
+            ["error handling", "retry"]
+        );
+    }
+
+    #[test]
+    fn limits_are_enforced_before_searching() {
+        let opens = Cell::new(0);
+        let ranker = CountingIndex::open(&opens, &[("a", "alpha")]);
+        let settings = BatchSettings {
+            max_queries: 2,
+            max_results: 10,
+        };
+
+        let err = search_batch(&ranker, &queries(&["a", "b", "c"]), 1, &settings).unwrap_err();
+        assert!(err.to_string().contains("max_queries (2)"));
+        let err = search_batch(&ranker, &queries(&["a", "b"]), 6, &settings).unwrap_err();
+        assert!(err.to_string().contains("max_results (10)"));
+        assert!(search_batch(&ranker, &[], 5, &settings).is_err());
+        assert_eq!(ranker.searches.get(), 0);
+    }
+
+    #[test]
+    fn batch_file_skips_blanks_comments_and_repeats() {
+        let raw = "# queries\nerror handling\n\n  retry policy  \nerror handling\n";
+        assert_eq!(parse_batch_queries(raw), ["error handling", "retry policy"]);
+    }
+}
@@ -312,6 +312,7 @@ mod tests {
             semantic_rank: Some(1),
             bm25_score: Some(0.9),
             semantic_score: Some(0.85),
+            snippets: Vec::new(),
         }];
         cache.put_query("test query", 10, results.clone());
 
@@ -331,6 +332,7 @@ mod tests {
             semantic_rank: Some(1),
             bm25_score: Some(0.85),
             semantic_score: Some(0.8),
+            snippets: Vec::new(),
         }];
         let results_20 = vec![
             HybridResult {
@@ -340,6 +342,7 @@ mod tests {
                 semantic_rank: Some(1),
                 bm25_score: Some(0.85),
                 semantic_score: Some(0.8),
+                snippets: Vec::new(),
             },
             HybridResult {
                 skill_id: "skill-2".to_string(),
@@ -348,6 +351,7 @@ mod tests {
                 semantic_rank: Some(2),
                 bm25_score: Some(0.75),
                 semantic_score: Some(0.7),
+                snippets: Vec::new(),
             },
         ];
 
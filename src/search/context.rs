@@ -1,19 +1,107 @@
 //! Context-aware search ranking and filtering
 //!
-//! Provides `SearchContext` for personalized ranking and `SearchFilters`
-//! for structured result filtering.
+//! Provides `SearchContext` for ranking the skills of the project a search
+//! runs in first, and `SearchFilters` for structured result filtering.
 
+use std::path::{Path, PathBuf};
+
+use ms_search_core::ParsedQuery;
 use serde::{Deserialize, Serialize};
 
-/// Context for personalized search ranking
-#[derive(Debug, Clone, Default)]
+use crate::config::SearchContextSettings;
+use crate::context::{ProjectDetector, ProjectType};
+use crate::error::{MsError, Result};
+
+/// The project a search runs in
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SearchContext {
-    /// Current working directory
-    pub cwd: Option<String>,
-    /// Recent skills accessed
-    pub recent_skills: Vec<String>,
-    /// Project tech stack
-    pub tech_stack: Vec<String>,
+    /// Nearest ancestor of the search directory with project markers
+    pub root: PathBuf,
+    /// Detected project types, most confident first
+    pub project_types: Vec<ProjectType>,
+}
+
+impl SearchContext {
+    /// The project enclosing `dir`: the nearest ancestor (or `dir` itself)
+    /// where `detector` finds markers, so a monorepo subdirectory resolves to
+    /// its own package. `None` outside any project.
+    pub fn detect(dir: &Path, detector: &dyn ProjectDetector) -> Option<Self> {
+        let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+        dir.ancestors().find_map(|candidate| {
+            let project_types: Vec<ProjectType> = detector
+                .detect_with_confidence(candidate)
+                .into_iter()
+                .map(|(project_type, _)| project_type)
+                .filter(|project_type| *project_type != ProjectType::Unknown)
+                .collect();
+            (!project_types.is_empty()).then(|| Self {
+                root: candidate.to_path_buf(),
+                project_types,
+            })
+        })
+    }
+
+    /// Score multiplier for a skill: `project_boost` when it is a
+    /// project-layer skill under the root, times `type_boost` when it is
+    /// tagged with a detected project type.
+    #[must_use]
+    pub fn boost(
+        &self,
+        settings: &SearchContextSettings,
+        source_layer: &str,
+        source_path: &str,
+        tags: &[String],
+    ) -> f32 {
+        let mut boost = 1.0;
+        if SearchLayer::from_str(source_layer) == Some(SearchLayer::Project)
+            && self.contains(Path::new(source_path))
+        {
+            boost *= settings.project_boost;
+        }
+        let tagged = self.project_types.iter().any(|project_type| {
+            tags.iter().any(|tag| {
+                tag.eq_ignore_ascii_case(project_type.id())
+                    || tag.eq_ignore_ascii_case(project_type.name())
+            })
+        });
+        if tagged {
+            boost *= settings.type_boost;
+        }
+        boost
+    }
+
+    /// Whether `path` lies under the root. Indexed paths may be relative or
+    /// go through symlinks, so they are resolved first when they exist.
+    fn contains(&self, path: &Path) -> bool {
+        path.canonicalize().map_or_else(
+            |_| path.starts_with(&self.root),
+            |path| path.starts_with(&self.root),
+        )
+    }
+
+    /// Lowercase ids of the detected project types
+    #[must_use]
+    pub fn type_ids(&self) -> Vec<&'static str> {
+        self.project_types.iter().map(ProjectType::id).collect()
+    }
+
+    /// One line for output headers: `rust project at ~/code/foo`
+    #[must_use]
+    pub fn describe(&self) -> String {
+        let root = dirs::home_dir()
+            .and_then(|home| {
+                self.root
+                    .strip_prefix(&home)
+                    .ok()
+                    .map(|rest| Path::new("~").join(rest))
+            })
+            .unwrap_or_else(|| self.root.clone());
+        format!(
+            "{} project at {}",
+            self.type_ids().join("/"),
+            root.display()
+        )
+    }
 }
 
 /// Search layer (source of skills)
@@ -63,14 +151,21 @@ impl std::fmt::Display for SearchLayer {
 
 /// Filters for narrowing search results
 ///
-/// Filters are applied post-fusion (after BM25 + vector RRF) to the
-/// merged result set. They can be combined for fine-grained control.
+/// BM25 search runs them inside the Tantivy query
+/// ([`Bm25Index::search_filtered`](super::tantivy::Bm25Index::search_filtered));
+/// semantic and fused results are filtered afterwards. They can be combined
+/// for fine-grained control.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SearchFilters {
-    /// Filter by tags (any-match: result passes if it has ANY of these tags)
+    /// Filter by tags (any-match: result passes if it has ANY of these tags,
+    /// or ALL of them with `match_all_tags`)
     #[serde(default)]
     pub tags: Vec<String>,
 
+    /// Require every tag in `tags`, as the `tag:` query syntax does
+    #[serde(default)]
+    pub match_all_tags: bool,
+
     /// Filter by source layer
     #[serde(skip_serializing_if = "Option::is_none")]
     pub layer: Option<SearchLayer>,
@@ -197,10 +292,14 @@ impl SearchFilters {
             }
         }
 
-        // Check tags filter (any-match)
+        // Check tags filter (any-match unless every tag is required)
         if !self.tags.is_empty() {
-            let has_matching_tag = self.tags.iter().any(|t| skill_tags.contains(t));
-            if !has_matching_tag {
+            let passes = if self.match_all_tags {
+                self.tags.iter().all(|t| skill_tags.contains(t))
+            } else {
+                self.tags.iter().any(|t| skill_tags.contains(t))
+            };
+            if !passes {
                 return false;
             }
         }
@@ -208,6 +307,30 @@ impl SearchFilters {
         true
     }
 
+    /// Filters from the query syntax shared with the docs site
+    /// ([`ParsedQuery`]): every `tag:` is required, `layer:` must name a
+    /// known layer, and `is:deprecated` includes deprecated skills.
+    pub fn from_query(query: &ParsedQuery) -> Result<Self> {
+        let layer = query
+            .layer
+            .as_deref()
+            .map(|layer| {
+                SearchLayer::from_str(layer).ok_or_else(|| {
+                    MsError::ValidationFailed(format!(
+                        "invalid layer '{layer}' in query (expected base, org, project, user)"
+                    ))
+                })
+            })
+            .transpose()?;
+        Ok(Self {
+            tags: query.tags.clone(),
+            match_all_tags: true,
+            layer,
+            min_quality: query.min_quality,
+            include_deprecated: query.include_deprecated,
+        })
+    }
+
     /// Parse tags from comma-separated string
     #[must_use]
     pub fn parse_tags(tags_str: &str) -> Vec<String> {
@@ -439,4 +562,72 @@ mod tests {
         assert_eq!(result.total_after, 0);
         assert_eq!(result.filtered_by_tags, 0);
     }
+
+    #[test]
+    fn test_from_query_requires_every_tag() {
+        let query =
+            ParsedQuery::parse("tag:rust tag:Async layer:project error handling quality:>0.5");
+        let filters = SearchFilters::from_query(&query).unwrap();
+        assert_eq!(query.text(), "error handling");
+        assert_eq!(filters.tags, ["rust", "async"]);
+        assert_eq!(filters.layer, Some(SearchLayer::Project));
+        assert_eq!(filters.min_quality, Some(0.5));
+        assert!(!filters.include_deprecated);
+        let both = ["rust".to_string(), "async".to_string()];
+        assert!(filters.matches(&both, "project", 0.6, false));
+        assert!(!filters.matches(&both[..1], "project", 0.6, false));
+
+        let filters =
+            SearchFilters::from_query(&ParsedQuery::parse("tag:git is:deprecated")).unwrap();
+        assert!(filters.include_deprecated);
+
+        assert!(SearchFilters::from_query(&ParsedQuery::parse("layer:team")).is_err());
+    }
+
+    fn write(path: &Path, contents: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn context_is_the_nearest_enclosing_project() {
+        let temp = tempfile::tempdir().unwrap();
+        let mono = temp.path().join("mono");
+        write(&mono.join("Cargo.toml"), "[workspace]\n");
+        write(&mono.join("web/package.json"), "{}\n");
+        write(&mono.join("crates/app/Cargo.toml"), "[package]\n");
+        std::fs::create_dir_all(mono.join("crates/app/src")).unwrap();
+        std::fs::create_dir_all(mono.join("docs")).unwrap();
+        let detector = crate::context::DefaultDetector::new();
+
+        let app = SearchContext::detect(&mono.join("crates/app/src"), &detector).unwrap();
+        assert!(app.root.ends_with("mono/crates/app"));
+        assert_eq!(app.type_ids(), ["rust"]);
+        let web = SearchContext::detect(&mono.join("web"), &detector).unwrap();
+        assert_eq!(web.type_ids(), ["node"]);
+        let docs = SearchContext::detect(&mono.join("docs"), &detector).unwrap();
+        assert!(docs.root.ends_with("mono"));
+    }
+
+    #[test]
+    fn boost_favors_local_project_skills_and_matching_tags() {
+        let temp = tempfile::tempdir().unwrap();
+        let app = temp.path().join("crates/app");
+        write(&app.join("Cargo.toml"), "[package]\n");
+        let local = app.join("skills/app-retry/SKILL.md");
+        let elsewhere = temp.path().join("other/skills/retry/SKILL.md");
+        write(&local, "# Retry\n");
+        write(&elsewhere, "# Retry\n");
+        let context = SearchContext::detect(&app, &crate::context::DefaultDetector::new()).unwrap();
+        let settings = SearchContextSettings::default();
+        let rust = ["rust".to_string()];
+
+        let local = local.display().to_string();
+        let elsewhere = elsewhere.display().to_string();
+        assert!((context.boost(&settings, "project", &local, &[]) - 1.5).abs() < 1e-6);
+        assert!((context.boost(&settings, "project", &elsewhere, &[]) - 1.0).abs() < 1e-6);
+        assert!((context.boost(&settings, "org", &local, &[]) - 1.0).abs() < 1e-6);
+        assert!((context.boost(&settings, "org", &elsewhere, &rust) - 1.2).abs() < 1e-6);
+        assert!((context.boost(&settings, "project", &local, &rust) - 1.8).abs() < 1e-6);
+    }
 }
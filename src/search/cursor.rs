@@ -0,0 +1,337 @@
+//! Stable cursors for paging through search and list results.
+//!
+//! A cursor pins a position in one ordering of one index snapshot: a hash of
+//! the query (and every option that shapes the ordering), the index
+//! generation it was issued against, and the `(sort key, id)` of the last
+//! result returned, where the sort key is the value the results are ordered
+//! by. The next page is everything after that result. Offsets shift
+//! when skills are added or removed between calls; a cursor instead fails
+//! with `cursor_stale` once the generation moves, so callers restart rather
+//! than see duplicates or gaps.
+
+use std::cmp::Ordering;
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use sha2::{Digest, Sha256};
+
+use crate::error::{MsError, Result};
+
+/// Largest page `ms search`, `ms list` and the MCP `search`/`list` tools return.
+pub const MAX_PAGE_SIZE: usize = 100;
+
+/// Pages' worth of candidates `ms search` ranks per request; paging a search
+/// goes no deeper than that.
+pub const SEARCH_POOL_PAGES: usize = 50;
+
+/// Version tag of the encoded cursor format.
+const CURSOR_VERSION: &str = "v2";
+
+/// Decoded page cursor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageCursor {
+    /// [`query_hash`] of the request that issued the cursor
+    pub query_hash: String,
+    /// Index generation the cursor was issued against
+    pub generation: u64,
+    /// Sort key of the last result returned
+    pub key: String,
+    /// Id of the last result returned
+    pub id: String,
+}
+
+impl PageCursor {
+    /// Opaque, URL-safe form handed to callers.
+    #[must_use]
+    pub fn encode(&self) -> String {
+        let raw = format!(
+            "{CURSOR_VERSION}.{}.{}.{}.{}",
+            self.query_hash,
+            self.generation,
+            URL_SAFE_NO_PAD.encode(&self.key),
+            self.id
+        );
+        URL_SAFE_NO_PAD.encode(raw)
+    }
+
+    pub fn decode(encoded: &str) -> Result<Self> {
+        let invalid = || MsError::ValidationFailed(format!("invalid cursor: {encoded}"));
+        let bytes = URL_SAFE_NO_PAD
+            .decode(encoded.trim())
+            .map_err(|_| invalid())?;
+        let raw = String::from_utf8(bytes).map_err(|_| invalid())?;
+        // The id comes last and may itself contain dots
+        let mut parts = raw.splitn(5, '.');
+        if parts.next() != Some(CURSOR_VERSION) {
+            return Err(invalid());
+        }
+        let (Some(query_hash), Some(generation), Some(key), Some(id)) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+        Ok(Self {
+            query_hash: query_hash.to_string(),
+            generation: generation.parse().map_err(|_| invalid())?,
+            key: String::from_utf8(URL_SAFE_NO_PAD.decode(key).map_err(|_| invalid())?)
+                .map_err(|_| invalid())?,
+            id: id.to_string(),
+        })
+    }
+}
+
+/// Short hash identifying a query and the options that shape its ordering.
+#[must_use]
+pub fn query_hash(parts: &[&str]) -> String {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
+    }
+    hex::encode(&hasher.finalize()[..8])
+}
+
+/// Sort key of a ranked result, exact to the bit.
+#[must_use]
+pub fn score_key(score: f32) -> String {
+    format!("{:08x}", score.to_bits())
+}
+
+/// Result order cursors rely on: score descending, then id ascending, so
+/// ties come out the same in every process.
+#[must_use]
+pub fn rank_order(a: (f32, &str), b: (f32, &str)) -> Ordering {
+    b.0.total_cmp(&a.0).then_with(|| a.1.cmp(b.1))
+}
+
+/// Paging state of one search or list request.
+#[derive(Debug, Clone)]
+pub struct Pager {
+    query_hash: String,
+    generation: u64,
+    limit: usize,
+    after: Option<PageCursor>,
+}
+
+impl Pager {
+    /// Pages of `limit` results for the query hashed as `query_hash`,
+    /// resuming after `cursor` when given.
+    ///
+    /// Fails on a page size above [`MAX_PAGE_SIZE`], on a cursor from a
+    /// different query, and with `cursor_stale` when the index generation
+    /// moved since the cursor was issued.
+    pub fn new(
+        query_hash: String,
+        generation: u64,
+        limit: usize,
+        cursor: Option<&str>,
+    ) -> Result<Self> {
+        if limit == 0 || limit > MAX_PAGE_SIZE {
+            return Err(MsError::ValidationFailed(format!(
+                "page size must be between 1 and {MAX_PAGE_SIZE}, got {limit}"
+            )));
+        }
+        let after = cursor.map(PageCursor::decode).transpose()?;
+        if let Some(after) = &after {
+            if after.query_hash != query_hash {
+                return Err(MsError::ValidationFailed(
+                    "cursor was issued for a different query or options".to_string(),
+                ));
+            }
+            if after.generation != generation {
+                return Err(MsError::CursorStale(format!(
+                    "index generation is {generation}, cursor was issued at {}; \
+                     restart from the first page",
+                    after.generation
+                )));
+            }
+        }
+        Ok(Self {
+            query_hash,
+            generation,
+            limit,
+            after,
+        })
+    }
+
+    #[must_use]
+    pub const fn limit(&self) -> usize {
+        self.limit
+    }
+
+    #[must_use]
+    pub const fn is_resuming(&self) -> bool {
+        self.after.is_some()
+    }
+
+    /// How many leading results the caller has to produce: everything up to
+    /// the cursor when resuming, otherwise one past the page, which tells
+    /// whether another page follows.
+    #[must_use]
+    pub const fn window(&self) -> usize {
+        if self.after.is_some() {
+            usize::MAX
+        } else {
+            self.limit + 1
+        }
+    }
+
+    /// Candidates a search ranks for this request: [`SEARCH_POOL_PAGES`]
+    /// pages of `limit`.
+    #[must_use]
+    pub const fn pool_size(&self) -> usize {
+        self.limit.saturating_mul(SEARCH_POOL_PAGES)
+    }
+
+    /// The page of `items` (in final order) after the cursor, and the cursor
+    /// of the page that follows when there is one. `key` gives each item's
+    /// `(sort key, id)`, the sort key being the value `items` are ordered by.
+    ///
+    /// A cursor whose result is no longer present means the results changed
+    /// without the generation moving; that is reported as stale too.
+    pub fn page<T>(
+        &self,
+        items: Vec<T>,
+        key: impl Fn(&T) -> (String, &str),
+    ) -> Result<(Vec<T>, Option<String>)> {
+        let mut items = items.into_iter();
+        if let Some(after) = &self.after {
+            let found = items.by_ref().any(|item| {
+                let (sort_key, id) = key(&item);
+                id == after.id && sort_key == after.key
+            });
+            if !found {
+                return Err(MsError::CursorStale(format!(
+                    "{} is no longer at the cursor position; restart from the first page",
+                    after.id
+                )));
+            }
+        }
+        let page: Vec<T> = items.by_ref().take(self.limit).collect();
+        let next_cursor = if items.next().is_some() {
+            page.last().map(|last| self.cursor_after(key(last)))
+        } else {
+            None
+        };
+        Ok((page, next_cursor))
+    }
+
+    /// [`Self::page`] over a search's ranked candidates, `full` when the
+    /// ranking returned all [`Self::pool_size`] it was asked for and may
+    /// have cut off more. The last page of a full pool still carries a
+    /// cursor, and resuming past it fails with `results_truncated` rather
+    /// than looking like the end of the results.
+    pub fn page_pool<T>(
+        &self,
+        items: Vec<T>,
+        full: bool,
+        key: impl Fn(&T) -> (String, &str),
+    ) -> Result<(Vec<T>, Option<String>)> {
+        let (page, next_cursor) = self.page(items, &key)?;
+        if !full || next_cursor.is_some() {
+            return Ok((page, next_cursor));
+        }
+        if page.is_empty() && self.is_resuming() {
+            return Err(MsError::ResultsTruncated(format!(
+                "search pages end after the top {} candidates; narrow the query or filters",
+                self.pool_size()
+            )));
+        }
+        let next_cursor = page.last().map(|last| self.cursor_after(key(last)));
+        Ok((page, next_cursor))
+    }
+
+    fn cursor_after(&self, (key, id): (String, &str)) -> String {
+        PageCursor {
+            query_hash: self.query_hash.clone(),
+            generation: self.generation,
+            key,
+            id: id.to_string(),
+        }
+        .encode()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranked(n: usize) -> Vec<(f32, String)> {
+        let mut items: Vec<(f32, String)> = (0..n)
+            .map(|i| ((i / 2) as f32 * 0.5, format!("skill-{i:02}")))
+            .collect();
+        items.sort_by(|a, b| rank_order((a.0, &a.1), (b.0, &b.1)));
+        items
+    }
+
+    #[test]
+    fn cursor_round_trips_ids_with_dots() {
+        let cursor = PageCursor {
+            query_hash: query_hash(&["search", "git"]),
+            generation: 42,
+            key: "org.team skill".to_string(),
+            id: "org.team.skill".to_string(),
+        };
+        assert_eq!(PageCursor::decode(&cursor.encode()).unwrap(), cursor);
+        assert!(PageCursor::decode("not a cursor").is_err());
+        assert!(PageCursor::decode(&URL_SAFE_NO_PAD.encode("v0.a.1.0.x")).is_err());
+    }
+
+    #[test]
+    fn pages_cover_every_result_once() {
+        let items = ranked(8);
+        let hash = query_hash(&["q"]);
+        let mut seen = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let pager = Pager::new(hash.clone(), 7, 3, cursor.as_deref()).unwrap();
+            let (page, next) = pager
+                .page(items.clone(), |item| (score_key(item.0), item.1.as_str()))
+                .unwrap();
+            assert!(page.len() <= 3);
+            seen.extend(page.into_iter().map(|item| item.1));
+            match next {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+        let expected: Vec<String> = items.into_iter().map(|item| item.1).collect();
+        assert_eq!(seen, expected);
+    }
+
+    #[test]
+    fn pager_rejects_stale_and_foreign_cursors() {
+        let hash = query_hash(&["q"]);
+        let pager = Pager::new(hash.clone(), 1, 2, None).unwrap();
+        let (_, next) = pager
+            .page(ranked(4), |item| (score_key(item.0), item.1.as_str()))
+            .unwrap();
+        let next = next.unwrap();
+
+        let stale = Pager::new(hash, 2, 2, Some(next.as_str())).unwrap_err();
+        assert_eq!(stale.to_robot_json()["code"], "cursor_stale");
+        let foreign = Pager::new(query_hash(&["other"]), 1, 2, Some(next.as_str())).unwrap_err();
+        assert!(matches!(foreign, MsError::ValidationFailed(_)));
+        assert!(Pager::new(query_hash(&["q"]), 1, MAX_PAGE_SIZE + 1, None).is_err());
+    }
+
+    #[test]
+    fn full_pool_ends_with_results_truncated() {
+        fn key(item: &(f32, String)) -> (String, &str) {
+            (score_key(item.0), item.1.as_str())
+        }
+        let hash = query_hash(&["q"]);
+        let pager = Pager::new(hash.clone(), 1, 2, None).unwrap();
+        let (page, next) = pager.page_pool(ranked(2), true, key).unwrap();
+        assert_eq!(page.len(), 2);
+        let next = next.expect("a full pool keeps a cursor on its last page");
+
+        let pager = Pager::new(hash.clone(), 1, 2, Some(next.as_str())).unwrap();
+        let err = pager.page_pool(ranked(2), true, key).unwrap_err();
+        assert_eq!(err.to_robot_json()["code"], "results_truncated");
+
+        let pager = Pager::new(hash, 1, 2, None).unwrap();
+        let (_, next) = pager.page_pool(ranked(2), false, key).unwrap();
+        assert!(next.is_none());
+    }
+}
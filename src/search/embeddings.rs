@@ -1,28 +1,41 @@
 //! Pluggable embedding backends
 //!
 //! Supports multiple embedding strategies:
-//! - Hash: FNV-1a based, zero dependencies, fully deterministic
+//! - Hash: FNV-1a based, zero dependencies, fully deterministic (implemented
+//!   in `ms-search-core` so the docs-site WASM build embeds queries identically)
 //! - API: External embedding services (`OpenAI`, Voyage, etc.)
-//! - Local: ONNX runtime (not yet implemented)
+//! - ONNX: local sentence-transformer model (`onnx` cargo feature)
+//!
+//! Stored vectors are tagged with the embedder's [`Embedder::name`] and
+//! dimension; [`VectorIndex`] refuses to mix vectors from different
+//! backends and reports that a re-index is required instead.
 
 use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Mutex;
 use std::time::{Duration, Instant};
 
+use ms_search_core::embedding::{cosine_similarity, dot_product, hash_embed};
+use tracing::warn;
+
 use crate::config::SearchConfig;
 use crate::error::{MsError, Result};
+use crate::search::embeddings_local::LocalEmbedder;
 
 /// Pluggable embedding backend interface
 pub trait Embedder: Send + Sync {
-    fn embed(&self, text: &str) -> Vec<f32>;
+    /// Embed `text`. Backend failures are returned rather than papered over
+    /// with a zero vector, which would silently skew similarity ranking.
+    fn embed(&self, text: &str) -> Result<Vec<f32>>;
     fn dims(&self) -> usize;
+    /// Backend identifier stored alongside every vector this embedder produces
     fn name(&self) -> &str;
 }
 
 /// Build an embedder from search config.
 pub fn build_embedder(config: &SearchConfig) -> Result<Box<dyn Embedder>> {
-    let backend = config.embedding_backend.trim().to_lowercase();
+    let backend = config.effective_embedding_backend().trim().to_lowercase();
     let dims = config.embedding_dims as usize;
     if dims == 0 {
         return Err(MsError::Config(
@@ -32,10 +45,15 @@ pub fn build_embedder(config: &SearchConfig) -> Result<Box<dyn Embedder>> {
 
     match backend.as_str() {
         "" | "hash" => Ok(Box::new(HashEmbedder::new(dims))),
-        "local" => Err(MsError::Config(
-            "search.embedding_backend=local requires ONNX runtime (not yet implemented)"
-                .to_string(),
-        )),
+        "onnx" | "local" => {
+            let model_path = config.embeddings.model_path.as_deref().ok_or_else(|| {
+                MsError::MissingConfig("search.embeddings.model_path".to_string())
+            })?;
+            Ok(Box::new(LocalEmbedder::new(
+                Path::new(model_path),
+                config.embeddings.tokenizer_path.as_deref().map(Path::new),
+            )?))
+        }
         "api" => {
             let api_key = std::env::var(&config.api_key_env).map_err(|_| {
                 MsError::Config(format!(
@@ -84,52 +102,19 @@ impl HashEmbedder {
     /// Embed text into vector
     #[must_use]
     pub fn embed(&self, text: &str) -> Vec<f32> {
-        if self.dim == 0 {
-            return Vec::new();
-        }
-
-        let tokens = tokenize(text);
-        let mut embedding = vec![0.0; self.dim];
-
-        if tokens.is_empty() {
-            return embedding;
-        }
-
-        for token in &tokens {
-            accumulate_embedding(&mut embedding, token, 1.0);
-        }
-
-        for window in tokens.windows(2) {
-            let bigram = format!("{} {}", window[0], window[1]);
-            accumulate_embedding(&mut embedding, &bigram, 0.5);
-        }
-
-        l2_normalize(&mut embedding);
-        embedding
+        hash_embed(text, self.dim)
     }
 
     /// Compute cosine similarity between two embeddings
     #[must_use]
     pub fn similarity(&self, a: &[f32], b: &[f32]) -> f32 {
-        if a.len() != b.len() {
-            return 0.0;
-        }
-
-        let dot: f32 = a.iter().zip(b.iter()).map(|(x, y)| x * y).sum();
-        let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
-        let norm_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
-
-        if norm_a == 0.0 || norm_b == 0.0 {
-            0.0
-        } else {
-            dot / (norm_a * norm_b)
-        }
+        cosine_similarity(a, b)
     }
 }
 
 impl Embedder for HashEmbedder {
-    fn embed(&self, text: &str) -> Vec<f32> {
-        Self::embed(self, text)
+    fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        Ok(Self::embed(self, text))
     }
 
     fn dims(&self) -> usize {
@@ -236,15 +221,11 @@ impl ApiEmbedder {
 }
 
 impl Embedder for ApiEmbedder {
-    fn embed(&self, text: &str) -> Vec<f32> {
-        match self.call_api(text) {
-            Ok(embedding) => embedding,
-            Err(e) => {
-                eprintln!("API embedding error: {e}");
-                // Return zero vector on error (graceful degradation)
-                vec![0.0; self.dims]
-            }
-        }
+    fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        self.call_api(text).map_err(|err| {
+            warn!(error = %err, "API embedding failed");
+            MsError::Config(format!("API embedding failed: {err}"))
+        })
     }
 
     fn dims(&self) -> usize {
@@ -260,24 +241,80 @@ impl Embedder for ApiEmbedder {
 pub struct VectorIndex {
     embeddings: HashMap<String, Vec<f32>>,
     dims: usize,
+    backend: String,
 }
 
 impl VectorIndex {
-    /// Create a new empty vector index
+    /// Create a new empty vector index for hash embeddings
     #[must_use]
     pub fn new(dims: usize) -> Self {
+        Self::with_backend(dims, "hash")
+    }
+
+    /// Create a new empty vector index for vectors from `backend`
+    #[must_use]
+    pub fn with_backend(dims: usize, backend: impl Into<String>) -> Self {
         Self {
             embeddings: HashMap::new(),
             dims,
+            backend: backend.into(),
         }
     }
 
+    /// Create a new empty vector index matching `embedder`
+    #[must_use]
+    pub fn for_embedder(embedder: &dyn Embedder) -> Self {
+        Self::with_backend(embedder.dims(), embedder.name())
+    }
+
     /// Current embedding dimension
     #[must_use]
     pub const fn dims(&self) -> usize {
         self.dims
     }
 
+    /// Backend identifier of the vectors in this index
+    #[must_use]
+    pub fn backend(&self) -> &str {
+        &self.backend
+    }
+
+    /// Check that vectors from `backend` with `dims` dimensions can be
+    /// compared against this index.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MsError::ReindexRequired`] on a backend or dimension mismatch.
+    pub fn check_compatible(&self, backend: &str, dims: usize) -> Result<()> {
+        if backend == self.backend && dims == self.dims {
+            return Ok(());
+        }
+        Err(MsError::ReindexRequired(format!(
+            "stored embeddings were built with backend '{backend}' ({dims} dims) \
+             but the active backend is '{}' ({} dims); run `ms index --force` or \
+             `ms doctor --fix` to re-embed skills",
+            self.backend, self.dims
+        )))
+    }
+
+    /// Insert an embedding loaded from storage, tagged with the backend that
+    /// produced it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MsError::ReindexRequired`] when the stored vector comes from
+    /// a different backend or has a different dimension.
+    pub fn insert_stored(
+        &mut self,
+        skill_id: impl Into<String>,
+        embedding: Vec<f32>,
+        backend: &str,
+    ) -> Result<()> {
+        self.check_compatible(backend, embedding.len())?;
+        self.embeddings.insert(skill_id.into(), embedding);
+        Ok(())
+    }
+
     /// Number of embeddings stored
     #[must_use]
     pub fn len(&self) -> usize {
@@ -290,6 +327,15 @@ impl VectorIndex {
         self.embeddings.is_empty()
     }
 
+    /// Approximate heap bytes held by the stored ids and vectors
+    #[must_use]
+    pub fn memory_bytes(&self) -> usize {
+        self.embeddings
+            .iter()
+            .map(|(id, vector)| id.capacity() + vector.capacity() * std::mem::size_of::<f32>())
+            .sum()
+    }
+
     /// Insert or replace an embedding
     pub fn insert(&mut self, skill_id: impl Into<String>, embedding: Vec<f32>) -> bool {
         if embedding.len() != self.dims {
@@ -304,6 +350,21 @@ impl VectorIndex {
         self.embeddings.remove(skill_id)
     }
 
+    /// Remove embeddings of skills that no longer exist; returns removed ids (sorted)
+    pub fn remove_stale(&mut self, is_live: impl Fn(&str) -> bool) -> Vec<String> {
+        let mut stale: Vec<String> = self
+            .embeddings
+            .keys()
+            .filter(|id| !is_live(id))
+            .cloned()
+            .collect();
+        stale.sort();
+        for id in &stale {
+            self.embeddings.remove(id);
+        }
+        stale
+    }
+
     /// Cosine similarity search (expects embeddings to be L2 normalized)
     #[must_use]
     pub fn search(&self, query_embedding: &[f32], limit: usize) -> Vec<(String, f32)> {
@@ -323,63 +384,10 @@ impl VectorIndex {
     }
 }
 
-fn tokenize(text: &str) -> Vec<String> {
-    let lowered = text.to_lowercase();
-    lowered
-        .split(|c: char| !(c.is_alphanumeric() || c == '+' || c == '#'))
-        .filter(|token| !token.is_empty()) // Allow 1-char tokens (like "c", "r")
-        .map(std::string::ToString::to_string)
-        .collect()
-}
-
-fn accumulate_embedding(embedding: &mut [f32], token: &str, weight: f32) {
-    let token_hash = fnv1a_hash(token.as_bytes());
-
-    for (i, slot) in embedding.iter_mut().enumerate() {
-        let dim_hash = fnv1a_hash_with_salt(token_hash, i as u64);
-        // Use 16 bits to create a continuous value in [-weight, weight]
-        // This reduces the probability of exact cancellation from ~1/2^n to ~0
-        let bits = (dim_hash & 0xFFFF) as f32;
-        let normalized = (bits / 32767.5) - 1.0; // Maps [0, 65535] to [-1, 1]
-        *slot += normalized * weight;
-    }
-}
-
-fn fnv1a_hash_with_salt(seed: u64, salt: u64) -> u64 {
-    let mut bytes = [0u8; 16];
-    bytes[..8].copy_from_slice(&seed.to_le_bytes());
-    bytes[8..].copy_from_slice(&salt.to_le_bytes());
-    fnv1a_hash(&bytes)
-}
-
-fn fnv1a_hash(data: &[u8]) -> u64 {
-    const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
-    const FNV_PRIME: u64 = 0x0100_0000_01b3;
-
-    let mut hash = FNV_OFFSET;
-    for byte in data {
-        hash ^= u64::from(*byte);
-        hash = hash.wrapping_mul(FNV_PRIME);
-    }
-    hash
-}
-
-fn l2_normalize(vec: &mut [f32]) {
-    let norm = vec.iter().map(|x| x * x).sum::<f32>().sqrt();
-    if norm > 0.0 {
-        for value in vec.iter_mut() {
-            *value /= norm;
-        }
-    }
-}
-
-fn dot_product(a: &[f32], b: &[f32]) -> f32 {
-    a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ms_search_core::embedding::{fnv1a_hash, tokenize};
 
     #[test]
     fn test_fnv1a_hash_known_value() {
@@ -456,6 +464,102 @@ mod tests {
         assert_eq!(results[0].0, "git");
     }
 
+    /// Fixed-output embedder standing in for a real model backend.
+    struct MockEmbedder {
+        dims: usize,
+        name: &'static str,
+    }
+
+    impl Embedder for MockEmbedder {
+        fn embed(&self, _text: &str) -> Result<Vec<f32>> {
+            let mut v = vec![0.0; self.dims];
+            v[0] = 1.0;
+            Ok(v)
+        }
+
+        fn dims(&self) -> usize {
+            self.dims
+        }
+
+        fn name(&self) -> &str {
+            self.name
+        }
+    }
+
+    #[test]
+    fn test_vector_index_tracks_embedder_backend() {
+        let embedder = MockEmbedder {
+            dims: 8,
+            name: "onnx:mini",
+        };
+        let mut index = VectorIndex::for_embedder(&embedder);
+        assert_eq!(index.dims(), 8);
+        assert_eq!(index.backend(), "onnx:mini");
+
+        index
+            .insert_stored("skill", embedder.embed("x").unwrap(), "onnx:mini")
+            .unwrap();
+        let results = index.search(&embedder.embed("query").unwrap(), 5);
+        assert_eq!(results.len(), 1);
+    }
+
+    #[test]
+    fn test_vector_index_dimension_mismatch_requires_reindex() {
+        let embedder = MockEmbedder {
+            dims: 16,
+            name: "onnx:mini",
+        };
+        let mut index = VectorIndex::for_embedder(&embedder);
+
+        let err = index
+            .insert_stored("skill", vec![0.5; 384], "onnx:mini")
+            .unwrap_err();
+        assert!(matches!(err, MsError::ReindexRequired(_)));
+        assert!(err.to_string().contains("384 dims"));
+        assert!(err.to_string().contains("ms doctor --fix"));
+        assert!(index.is_empty());
+    }
+
+    #[test]
+    fn test_vector_index_backend_switch_requires_reindex() {
+        let embedder = MockEmbedder {
+            dims: 32,
+            name: "onnx:mini",
+        };
+        let mut index = VectorIndex::for_embedder(&embedder);
+        let stored = HashEmbedder::new(32).embed("git commit workflow");
+
+        let err = index.insert_stored("git", stored, "hash").unwrap_err();
+        assert!(matches!(err, MsError::ReindexRequired(_)));
+        assert!(err.to_string().contains("'hash'"));
+        assert!(index.check_compatible("onnx:mini", 32).is_ok());
+    }
+
+    #[test]
+    fn test_build_embedder_onnx_requires_model_path() {
+        let mut config = SearchConfig::default();
+        config.embeddings.backend = Some("onnx".to_string());
+        let err = build_embedder(&config).err().unwrap();
+        assert!(matches!(err, MsError::MissingConfig(_)));
+
+        config.embeddings.backend = None;
+        assert_eq!(build_embedder(&config).unwrap().name(), "hash");
+    }
+
+    #[test]
+    fn test_vector_index_remove_stale() {
+        let embedder = HashEmbedder::new(32);
+        let mut index = VectorIndex::new(32);
+        for id in ["kept", "gone-b", "gone-a"] {
+            index.insert(id, embedder.embed(id));
+        }
+
+        let removed = index.remove_stale(|id| id == "kept");
+        assert_eq!(removed, vec!["gone-a".to_string(), "gone-b".to_string()]);
+        assert_eq!(index.len(), 1);
+        assert!(index.remove_stale(|_| true).is_empty());
+    }
+
     #[test]
     fn test_embedding_normalized_random_inputs() {
         let embedder = HashEmbedder::new(64);
@@ -1,98 +1,290 @@
-//! Local ML embedder (ONNX runtime)
+//! Local sentence-transformer embedder (ONNX runtime)
 //!
-//! This module provides local ML-based embeddings using ONNX runtime.
-//! Currently a placeholder - requires `ort` crate to be implemented.
+//! Runs a small sentence-transformer (e.g. all-MiniLM-L6-v2) locally, with no
+//! network access. Built only with the `onnx` cargo feature so default builds
+//! stay lean:
 //!
-//! ## Future Implementation
-//!
-//! When implemented, this will:
-//! - Load ONNX models (e.g., all-MiniLM-L6-v2)
-//! - Tokenize input using `HuggingFace` tokenizers
-//! - Run inference locally without network access
-//! - Provide higher quality embeddings than hash-based
+//! ```text
+//! cargo install --path . --features onnx
+//! ```
 //!
 //! ## Configuration
 //!
 //! ```toml
-//! [search]
-//! embedding_backend = "local"
-//! model_path = "~/.cache/ms/models/all-MiniLM-L6-v2.onnx"
+//! [search.embeddings]
+//! backend = "onnx"
+//! model_path = "~/.cache/ms/models/all-MiniLM-L6-v2/model.onnx"
+//! # tokenizer_path defaults to tokenizer.json next to the model
 //! ```
 //!
-//! ## Dependencies Required
-//!
-//! To implement this backend, add to Cargo.toml:
-//! ```toml
-//! ort = { version = "2.0", features = ["download-binaries"] }
-//! tokenizers = "0.15"
-//! ```
+//! Token embeddings are mean-pooled over the attention mask and L2
+//! normalized, so they compare with the same dot product as the hash
+//! backend. The dimension comes from the model; `search.embedding_dims` is
+//! ignored for this backend.
+
+use std::path::{Path, PathBuf};
 
 use crate::error::{MsError, Result};
 use crate::search::embeddings::Embedder;
 
-/// Local ONNX-based embedder (placeholder)
-///
-/// This struct exists as a placeholder for future ONNX integration.
-/// When the `ort` crate is added, this will load and run local ML models.
+/// Local ONNX-based embedder
 pub struct LocalEmbedder {
+    #[cfg(feature = "onnx")]
+    session: std::sync::Mutex<ort::session::Session>,
+    #[cfg(feature = "onnx")]
+    tokenizer: tokenizers::Tokenizer,
     dims: usize,
+    name: String,
 }
 
 impl LocalEmbedder {
-    /// Create a new local embedder
+    /// Load the model at `model_path` and its tokenizer.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotImplemented` when built without the `onnx` feature, and a
+    /// config error when the model or tokenizer cannot be loaded.
+    #[cfg(feature = "onnx")]
+    pub fn new(model_path: &Path, tokenizer_path: Option<&Path>) -> Result<Self> {
+        let model_path = expand_home(model_path);
+        let tokenizer_path =
+            tokenizer_path.map_or_else(|| model_path.with_file_name("tokenizer.json"), expand_home);
+        let (mut session, tokenizer) = runtime::load(&model_path, &tokenizer_path)?;
+        let probe = runtime::embed(&mut session, &tokenizer, "probe").map_err(|err| {
+            MsError::Config(format!(
+                "ONNX model {} produced no embedding: {err}",
+                model_path.display()
+            ))
+        })?;
+
+        Ok(Self {
+            session: std::sync::Mutex::new(session),
+            tokenizer,
+            dims: probe.len(),
+            name: backend_name(&model_path),
+        })
+    }
+
+    /// Load the model at `model_path` and its tokenizer.
     ///
     /// # Errors
     ///
-    /// Currently always returns `NotImplemented` error.
-    pub fn new(_model_path: &std::path::Path, _dims: usize) -> Result<Self> {
+    /// Always returns `NotImplemented`: this build lacks the `onnx` feature.
+    #[cfg(not(feature = "onnx"))]
+    pub fn new(_model_path: &Path, _tokenizer_path: Option<&Path>) -> Result<Self> {
         Err(MsError::NotImplemented(
-            "Local ONNX embeddings require the 'ort' crate. \
-             Use embedding_backend='hash' or 'api' instead."
+            "ONNX embeddings require building ms with `--features onnx`. \
+             Use [search.embeddings] backend = \"hash\" or \"api\" instead."
                 .to_string(),
         ))
     }
 
-    /// Check if local embeddings are available
+    /// Check if local embeddings are available in this build
     #[must_use]
     pub const fn is_available() -> bool {
-        false
+        cfg!(feature = "onnx")
     }
 }
 
 impl Embedder for LocalEmbedder {
-    fn embed(&self, _text: &str) -> Vec<f32> {
-        // This code is unreachable since new() always fails,
-        // but we provide an implementation for trait completeness
-        vec![0.0; self.dims]
+    #[cfg(feature = "onnx")]
+    fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        let mut session = self.session.lock().expect("lock poisoned");
+        runtime::embed(&mut session, &self.tokenizer, text)
+            .and_then(|embedding| {
+                if embedding.len() == self.dims {
+                    Ok(embedding)
+                } else {
+                    Err(format!(
+                        "expected {} dims, got {}",
+                        self.dims,
+                        embedding.len()
+                    ))
+                }
+            })
+            .map_err(|err| {
+                tracing::warn!(backend = %self.name, error = %err, "ONNX embedding failed");
+                MsError::Config(format!("ONNX embedding failed: {err}"))
+            })
+    }
+
+    #[cfg(not(feature = "onnx"))]
+    fn embed(&self, _text: &str) -> Result<Vec<f32>> {
+        // Unreachable since new() always fails without the feature
+        Err(MsError::NotImplemented(
+            "ONNX embeddings require building ms with `--features onnx`".to_string(),
+        ))
     }
 
     fn dims(&self) -> usize {
         self.dims
     }
 
-    fn name(&self) -> &'static str {
-        "local"
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Backend identifier stored with each vector: `onnx:<model>`, so swapping
+/// models is detected like swapping backends. For the common
+/// `<model>/model.onnx` layout the directory names the model.
+fn backend_name(model_path: &Path) -> String {
+    let stem = model_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("model");
+    let model = if stem == "model" {
+        model_path
+            .parent()
+            .and_then(Path::file_name)
+            .and_then(|s| s.to_str())
+            .unwrap_or(stem)
+    } else {
+        stem
+    };
+    format!("onnx:{model}")
+}
+
+fn expand_home(path: &Path) -> PathBuf {
+    crate::utils::path::expand_path(&path.to_string_lossy())
+}
+
+#[cfg(feature = "onnx")]
+mod runtime {
+    use std::path::Path;
+
+    use ort::session::Session;
+    use ort::value::Tensor;
+    use tokenizers::Tokenizer;
+
+    use crate::error::{MsError, Result};
+
+    pub(super) fn load(model_path: &Path, tokenizer_path: &Path) -> Result<(Session, Tokenizer)> {
+        let session = Session::builder()
+            .and_then(|builder| builder.commit_from_file(model_path))
+            .map_err(|err| {
+                MsError::Config(format!(
+                    "failed to load ONNX model {}: {err}",
+                    model_path.display()
+                ))
+            })?;
+        let tokenizer = Tokenizer::from_file(tokenizer_path).map_err(|err| {
+            MsError::Config(format!(
+                "failed to load tokenizer {}: {err}",
+                tokenizer_path.display()
+            ))
+        })?;
+        Ok((session, tokenizer))
+    }
+
+    pub(super) fn embed(
+        session: &mut Session,
+        tokenizer: &Tokenizer,
+        text: &str,
+    ) -> std::result::Result<Vec<f32>, String> {
+        let encoding = tokenizer
+            .encode(text, true)
+            .map_err(|e| format!("tokenization failed: {e}"))?;
+        let widen = |values: &[u32]| values.iter().map(|&v| i64::from(v)).collect::<Vec<_>>();
+        let mask = widen(encoding.get_attention_mask());
+        let shape = [1_usize, mask.len()];
+        let tensor = |values: Vec<i64>| {
+            Tensor::from_array((shape, values)).map_err(|e| format!("invalid input tensor: {e}"))
+        };
+
+        let wants_type_ids = session
+            .inputs
+            .iter()
+            .any(|input| input.name == "token_type_ids");
+        let mut inputs = ort::inputs! {
+            "input_ids" => tensor(widen(encoding.get_ids()))?,
+            "attention_mask" => tensor(mask.clone())?,
+        };
+        if wants_type_ids {
+            inputs.push((
+                "token_type_ids".into(),
+                tensor(widen(encoding.get_type_ids()))?.into(),
+            ));
+        }
+
+        let outputs = session
+            .run(inputs)
+            .map_err(|e| format!("inference failed: {e}"))?;
+        let (out_shape, data) = outputs[0]
+            .try_extract_tensor::<f32>()
+            .map_err(|e| format!("unexpected model output: {e}"))?;
+
+        let mut embedding = match **out_shape {
+            // Already pooled: [batch, hidden]
+            [_, hidden] => data[..hidden as usize].to_vec(),
+            // Token embeddings: [batch, tokens, hidden]
+            [_, tokens, hidden] => mean_pool(data, &mask, tokens as usize, hidden as usize),
+            ref other => return Err(format!("unexpected model output shape {other:?}")),
+        };
+        let norm = embedding.iter().map(|x| x * x).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            for value in &mut embedding {
+                *value /= norm;
+            }
+        }
+        Ok(embedding)
+    }
+
+    fn mean_pool(data: &[f32], mask: &[i64], tokens: usize, hidden: usize) -> Vec<f32> {
+        let mut pooled = vec![0.0f32; hidden];
+        let mut count = 0.0f32;
+        for (token, &m) in mask.iter().enumerate().take(tokens) {
+            if m == 0 {
+                continue;
+            }
+            let row = &data[token * hidden..(token + 1) * hidden];
+            for (acc, value) in pooled.iter_mut().zip(row) {
+                *acc += value;
+            }
+            count += 1.0;
+        }
+        if count > 0.0 {
+            for value in &mut pooled {
+                *value /= count;
+            }
+        }
+        pooled
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::path::Path;
 
+    #[cfg(not(feature = "onnx"))]
     #[test]
-    fn local_embedder_returns_not_implemented() {
-        let result = LocalEmbedder::new(Path::new("/fake/model.onnx"), 384);
-        assert!(result.is_err());
-        if let Err(MsError::NotImplemented(msg)) = result {
-            assert!(msg.contains("ort"));
-        } else {
-            panic!("Expected NotImplemented error");
+    fn local_embedder_requires_onnx_feature() {
+        let result = LocalEmbedder::new(Path::new("/fake/model.onnx"), None);
+        match result {
+            Err(MsError::NotImplemented(msg)) => assert!(msg.contains("--features onnx")),
+            Err(other) => panic!("Expected NotImplemented error, got {other}"),
+            Ok(_) => panic!("Expected NotImplemented error"),
         }
+        assert!(!LocalEmbedder::is_available());
     }
 
+    #[cfg(feature = "onnx")]
     #[test]
-    fn local_embedder_not_available() {
-        assert!(!LocalEmbedder::is_available());
+    fn local_embedder_reports_missing_model() {
+        let result = LocalEmbedder::new(Path::new("/fake/model.onnx"), None);
+        assert!(matches!(result, Err(MsError::Config(_))));
+        assert!(LocalEmbedder::is_available());
+    }
+
+    #[test]
+    fn backend_name_identifies_model() {
+        assert_eq!(
+            backend_name(Path::new("/models/all-MiniLM-L6-v2/model.onnx")),
+            "onnx:all-MiniLM-L6-v2"
+        );
+        assert_eq!(
+            backend_name(Path::new("/models/bge-small.onnx")),
+            "onnx:bge-small"
+        );
     }
 }
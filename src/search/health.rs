@@ -0,0 +1,326 @@
+//! Search index health checks
+//!
+//! Classifies why an on-disk Tantivy index cannot be used: a lock file left
+//! behind by a crashed process, a missing `meta.json`, an index built with an
+//! older schema, segment files that fail checksum validation, or an index
+//! that cannot be opened or queried.
+//! The index is derived data (SQLite skill records are the source of truth),
+//! so anything worse than a stale lock is fixed by rebuilding it.
+
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use fs2::FileExt;
+use serde::Serialize;
+
+use super::tantivy::{Bm25Index, REBUILD_MARKER_FILE, SCHEMA_VERSION, WRITER_LOCK_FILE};
+use crate::error::Result;
+
+/// Lock files in the index directory: Tantivy's, and the writer lock `ms`
+/// takes around Tantivy's writer.
+pub const LOCK_FILES: [&str; 3] = [
+    ".tantivy-writer.lock",
+    ".tantivy-meta.lock",
+    WRITER_LOCK_FILE,
+];
+
+/// One thing wrong with (or notable about) an index directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum IndexProblem {
+    /// Lock file that no live process holds
+    StaleLock { path: PathBuf },
+    /// Lock file held by a running process (not a problem by itself)
+    LockHeld { path: PathBuf },
+    /// Index files present but `meta.json` is missing
+    MissingMeta,
+    /// Built with an older schema, or recreated empty for the current one
+    /// and not rebuilt yet
+    OutdatedSchema,
+    /// Segment files that fail checksum validation
+    ChecksumMismatch { files: Vec<PathBuf> },
+    /// The index cannot be opened or queried
+    Unreadable { error: String },
+}
+
+impl IndexProblem {
+    /// Whether this needs fixing at all.
+    #[must_use]
+    pub const fn is_issue(&self) -> bool {
+        !matches!(self, Self::LockHeld { .. })
+    }
+
+    /// Whether fixing this means rebuilding the index from the database.
+    #[must_use]
+    pub const fn needs_rebuild(&self) -> bool {
+        matches!(
+            self,
+            Self::MissingMeta
+                | Self::OutdatedSchema
+                | Self::ChecksumMismatch { .. }
+                | Self::Unreadable { .. }
+        )
+    }
+}
+
+impl std::fmt::Display for IndexProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::StaleLock { path } => write!(f, "stale lock file {}", path.display()),
+            Self::LockHeld { path } => {
+                write!(f, "lock {} held by a running process", path.display())
+            }
+            Self::MissingMeta => write!(f, "meta.json is missing"),
+            Self::OutdatedSchema => write!(
+                f,
+                "index predates schema v{SCHEMA_VERSION} and must be rebuilt"
+            ),
+            Self::ChecksumMismatch { files } => {
+                write!(
+                    f,
+                    "{} segment file(s) fail checksum validation",
+                    files.len()
+                )
+            }
+            Self::Unreadable { error } => write!(f, "index cannot be read: {error}"),
+        }
+    }
+}
+
+/// Open the index at `path`, validate segment checksums, and run a trivial
+/// query, classifying any failure. A missing directory is not a problem (it
+/// is created on first use).
+///
+/// Tantivy leaves its lock files on disk and only holds them with `flock`,
+/// so lock files are reported only when the index also fails to open or
+/// query: that is when a crashed writer is the likely cause.
+#[must_use]
+pub fn diagnose(path: &Path) -> Vec<IndexProblem> {
+    if !path.is_dir() {
+        return Vec::new();
+    }
+    let failures = index_failures(path);
+    if failures.is_empty() {
+        return failures;
+    }
+
+    let mut problems: Vec<IndexProblem> = LOCK_FILES
+        .iter()
+        .map(|name| path.join(name))
+        .filter(|lock| lock.exists())
+        .map(|lock| {
+            if lock_is_held(&lock) {
+                IndexProblem::LockHeld { path: lock }
+            } else {
+                IndexProblem::StaleLock { path: lock }
+            }
+        })
+        .collect();
+    problems.extend(failures);
+    problems
+}
+
+fn index_failures(path: &Path) -> Vec<IndexProblem> {
+    if !path.join("meta.json").exists() {
+        let has_index_files = std::fs::read_dir(path).is_ok_and(|entries| {
+            entries.flatten().any(|entry| {
+                let name = entry.file_name();
+                !LOCK_FILES.iter().any(|lock| name == *lock) && name != REBUILD_MARKER_FILE
+            })
+        });
+        return if has_index_files {
+            vec![IndexProblem::MissingMeta]
+        } else {
+            Vec::new()
+        };
+    }
+    // An index that fails to open is reported as unreadable below
+    if path.join(REBUILD_MARKER_FILE).exists()
+        || !Bm25Index::has_current_schema(path).unwrap_or(true)
+    {
+        return vec![IndexProblem::OutdatedSchema];
+    }
+
+    let unreadable = |err: crate::error::MsError| {
+        vec![IndexProblem::Unreadable {
+            error: err.to_string(),
+        }]
+    };
+    let index = match Bm25Index::open_readonly(path) {
+        Ok(index) => index,
+        Err(err) => return unreadable(err),
+    };
+    match index.corrupted_files() {
+        Ok(files) if !files.is_empty() => vec![IndexProblem::ChecksumMismatch { files }],
+        Ok(_) => index
+            .search("ms", 1)
+            .err()
+            .map(unreadable)
+            .unwrap_or_default(),
+        Err(err) => unreadable(err),
+    }
+}
+
+/// Remove lock files in `path` that no live process holds. Returns how many
+/// were removed; held locks are left alone.
+pub fn remove_stale_locks(path: &Path) -> Result<usize> {
+    let mut removed = 0;
+    for name in LOCK_FILES {
+        let lock = path.join(name);
+        if lock.exists() && !lock_is_held(&lock) {
+            std::fs::remove_file(&lock)?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+/// Total size in bytes of the files under the index directory (0 when it
+/// does not exist).
+#[must_use]
+pub fn index_size(path: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(kind) if kind.is_dir() => index_size(&entry.path()),
+            Ok(_) => entry.metadata().map_or(0, |meta| meta.len()),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+/// Tantivy holds its locks with `flock`, which the OS releases when the
+/// holder exits; a lock file we can lock ourselves is stale.
+fn lock_is_held(lock: &Path) -> bool {
+    let Ok(file) = File::open(lock) else {
+        return false;
+    };
+    if file.try_lock_exclusive().is_ok() {
+        let _ = FileExt::unlock(&file);
+        false
+    } else {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::sqlite::SkillRecord;
+
+    fn record(id: &str) -> SkillRecord {
+        SkillRecord {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: "health check fixture".to_string(),
+            version: None,
+            author: None,
+            source_path: "/test/path".to_string(),
+            source_layer: "project".to_string(),
+            git_remote: None,
+            git_commit: None,
+            content_hash: "hash".to_string(),
+            body: "searchable body".to_string(),
+            metadata_json: "{}".to_string(),
+            assets_json: "{}".to_string(),
+            token_count: 10,
+            quality_score: 0.5,
+            indexed_at: "2025-01-01T00:00:00Z".to_string(),
+            modified_at: "2025-01-01T00:00:00Z".to_string(),
+            is_deprecated: false,
+            deprecation_reason: None,
+        }
+    }
+
+    fn healthy_index(path: &Path) {
+        let index = Bm25Index::open(path).unwrap();
+        index.index_skills(&[record("alpha")]).unwrap();
+    }
+
+    #[test]
+    fn healthy_and_missing_indexes_have_no_problems() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(diagnose(&dir.path().join("absent")).is_empty());
+        assert_eq!(index_size(&dir.path().join("absent")), 0);
+
+        let path = dir.path().join("index");
+        healthy_index(&path);
+        // Lock files left behind by earlier runs are normal.
+        std::fs::write(path.join(LOCK_FILES[0]), "").unwrap();
+        assert_eq!(diagnose(&path), Vec::new());
+        assert!(index_size(&path) > 0);
+    }
+
+    #[test]
+    fn classifies_unreadable_index_and_missing_meta() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("index");
+        healthy_index(&path);
+
+        std::fs::write(path.join("meta.json"), "{ not json").unwrap();
+        let problems = diagnose(&path);
+        assert!(
+            matches!(problems.as_slice(), [IndexProblem::Unreadable { .. }]),
+            "{problems:?}"
+        );
+        assert!(problems[0].needs_rebuild());
+
+        std::fs::remove_file(path.join("meta.json")).unwrap();
+        assert_eq!(diagnose(&path), vec![IndexProblem::MissingMeta]);
+    }
+
+    #[test]
+    fn classifies_outdated_schema() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("index");
+        std::fs::create_dir_all(&path).unwrap();
+        let mut builder = tantivy::schema::Schema::builder();
+        builder.add_text_field("id", tantivy::schema::STRING | tantivy::schema::STORED);
+        tantivy::Index::create_in_dir(&path, builder.build()).unwrap();
+
+        assert_eq!(diagnose(&path), vec![IndexProblem::OutdatedSchema]);
+        assert!(IndexProblem::OutdatedSchema.needs_rebuild());
+
+        // Opening recreates it empty, and it stays flagged until rebuilt
+        let index = Bm25Index::open(&path).unwrap();
+        assert_eq!(diagnose(&path), vec![IndexProblem::OutdatedSchema]);
+        index.index_skills(&[record("alpha")]).unwrap();
+        index.mark_rebuilt().unwrap();
+        assert_eq!(diagnose(&path), Vec::new());
+    }
+
+    #[test]
+    fn classifies_locks_on_a_broken_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("index");
+        healthy_index(&path);
+        std::fs::remove_file(path.join("meta.json")).unwrap();
+        let lock = path.join(LOCK_FILES[0]);
+        std::fs::write(&lock, "").unwrap();
+
+        // A lock another handle holds is reported as held and left alone.
+        let holder = File::open(&lock).unwrap();
+        holder.try_lock_exclusive().unwrap();
+        let problems = diagnose(&path);
+        assert_eq!(problems[0], IndexProblem::LockHeld { path: lock.clone() });
+        assert!(!problems[0].is_issue());
+        assert_eq!(remove_stale_locks(&path).unwrap(), 0);
+        FileExt::unlock(&holder).unwrap();
+        drop(holder);
+
+        let problems = diagnose(&path);
+        assert_eq!(
+            problems,
+            vec![
+                IndexProblem::StaleLock { path: lock.clone() },
+                IndexProblem::MissingMeta
+            ]
+        );
+        assert!(problems[0].is_issue() && !problems[0].needs_rebuild());
+        assert_eq!(remove_stale_locks(&path).unwrap(), 1);
+        assert!(!lock.exists());
+    }
+}
@@ -15,9 +15,17 @@
 //! - k is a smoothing constant (typically 60)
 //! - `rank_i(d)` is the 1-indexed position of d in list i
 //! - `weight_i` is the importance weight for list i
+//!
+//! A list with weight 0 is left out entirely, so `semantic_weight = 0` is a
+//! pure BM25 ranking. Fused scores below `min_score` are dropped.
 
 use std::collections::HashMap;
 
+use ms_search_core::rrf::{DEFAULT_RRF_K, rrf_contribution};
+
+use super::tantivy::{Bm25Result, SearchSnippet};
+use crate::config::SearchConfig;
+
 /// Reciprocal Rank Fusion configuration
 #[derive(Debug, Clone)]
 pub struct RrfConfig {
@@ -28,14 +36,17 @@ pub struct RrfConfig {
     pub bm25_weight: f32,
     /// Weight for semantic (vector) results
     pub semantic_weight: f32,
+    /// Fused results scoring below this are dropped
+    pub min_score: f32,
 }
 
 impl Default for RrfConfig {
     fn default() -> Self {
         Self {
-            k: 60.0,
+            k: DEFAULT_RRF_K,
             bm25_weight: 1.0,
             semantic_weight: 1.0,
+            min_score: 0.0,
         }
     }
 }
@@ -59,6 +70,46 @@ impl RrfConfig {
             ..Default::default()
         }
     }
+
+    /// Config from `[search]` / `[search.rrf]`
+    #[must_use]
+    pub fn from_search_config(config: &SearchConfig) -> Self {
+        let (bm25_weight, semantic_weight) = config.fusion_weights();
+        Self {
+            k: config.rrf.k,
+            bm25_weight,
+            semantic_weight,
+            min_score: config.rrf.min_score,
+        }
+    }
+
+    /// Use learned per-source weights (e.g. from the suggestion bandit),
+    /// rescaled to the same total as the current weights. Keeps the current
+    /// weights when nothing has been learned for either source.
+    #[must_use]
+    pub fn with_learned_weights(mut self, bm25: f32, semantic: f32) -> Self {
+        let learned = bm25 + semantic;
+        if learned > 0.0 {
+            let total = self.bm25_weight + self.semantic_weight;
+            self.bm25_weight = total * bm25 / learned;
+            self.semantic_weight = total * semantic / learned;
+        }
+        self
+    }
+
+    /// Rank by BM25 alone
+    #[must_use]
+    pub fn bm25_only(mut self) -> Self {
+        self.semantic_weight = 0.0;
+        self
+    }
+
+    /// Rank by semantic similarity alone
+    #[must_use]
+    pub fn vector_only(mut self) -> Self {
+        self.bm25_weight = 0.0;
+        self
+    }
 }
 
 /// A single hybrid search result
@@ -76,6 +127,23 @@ pub struct HybridResult {
     pub bm25_score: Option<f32>,
     /// Original semantic score (if present)
     pub semantic_score: Option<f32>,
+    /// Snippets from the BM25 side (empty for semantic-only matches)
+    pub snippets: Vec<SearchSnippet>,
+}
+
+impl HybridResult {
+    /// The BM25 and semantic parts of `score` under `config`.
+    #[must_use]
+    pub fn source_contributions(&self, config: &RrfConfig) -> (f32, f32) {
+        let part = |weight: f32, rank: Option<usize>| {
+            rank.filter(|_| weight > 0.0)
+                .map_or(0.0, |rank| rrf_contribution(weight, config.k, rank))
+        };
+        (
+            part(config.bm25_weight, self.bm25_rank),
+            part(config.semantic_weight, self.semantic_rank),
+        )
+    }
 }
 
 /// Fuse BM25 and semantic results using Reciprocal Rank Fusion
@@ -100,61 +168,106 @@ pub fn fuse_results(
 ) -> Vec<HybridResult> {
     let mut scores: HashMap<String, HybridResult> = HashMap::new();
 
+    let bm25_results = if config.bm25_weight > 0.0 {
+        bm25_results
+    } else {
+        &[]
+    };
+    let semantic_results = if config.semantic_weight > 0.0 {
+        semantic_results
+    } else {
+        &[]
+    };
+
     // Process BM25 results
     for (rank, (skill_id, score)) in bm25_results.iter().enumerate() {
         let rank_1_indexed = rank + 1;
-        let rrf_contribution = config.bm25_weight / (config.k + rank_1_indexed as f32);
+        let contribution = rrf_contribution(config.bm25_weight, config.k, rank_1_indexed);
 
         scores
             .entry(skill_id.clone())
             .and_modify(|r| {
-                r.score += rrf_contribution;
+                r.score += contribution;
                 r.bm25_rank = Some(rank_1_indexed);
                 r.bm25_score = Some(*score);
             })
             .or_insert(HybridResult {
                 skill_id: skill_id.clone(),
-                score: rrf_contribution,
+                score: contribution,
                 bm25_rank: Some(rank_1_indexed),
                 semantic_rank: None,
                 bm25_score: Some(*score),
                 semantic_score: None,
+                snippets: Vec::new(),
             });
     }
 
     // Process semantic results
     for (rank, (skill_id, score)) in semantic_results.iter().enumerate() {
         let rank_1_indexed = rank + 1;
-        let rrf_contribution = config.semantic_weight / (config.k + rank_1_indexed as f32);
+        let contribution = rrf_contribution(config.semantic_weight, config.k, rank_1_indexed);
 
         scores
             .entry(skill_id.clone())
             .and_modify(|r| {
-                r.score += rrf_contribution;
+                r.score += contribution;
                 r.semantic_rank = Some(rank_1_indexed);
                 r.semantic_score = Some(*score);
             })
             .or_insert(HybridResult {
                 skill_id: skill_id.clone(),
-                score: rrf_contribution,
+                score: contribution,
                 bm25_rank: None,
                 semantic_rank: Some(rank_1_indexed),
                 bm25_score: None,
                 semantic_score: Some(*score),
+                snippets: Vec::new(),
             });
     }
 
-    // Sort by RRF score (descending)
-    let mut results: Vec<HybridResult> = scores.into_values().collect();
+    // Drop weak matches, then sort by RRF score (descending)
+    let mut results: Vec<HybridResult> = scores
+        .into_values()
+        .filter(|r| r.score >= config.min_score)
+        .collect();
     results.sort_by(|a, b| {
         b.score
             .partial_cmp(&a.score)
             .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.skill_id.cmp(&b.skill_id))
     });
 
     results
 }
 
+/// Fuse full BM25 results with semantic results, keeping BM25 snippets
+///
+/// Same ranking as [`fuse_results`]; each fused result carries the snippets
+/// of its BM25 hit. Results that only matched semantically get no snippets.
+#[must_use]
+pub fn fuse_bm25_results(
+    bm25_results: &[Bm25Result],
+    semantic_results: &[(String, f32)],
+    config: &RrfConfig,
+) -> Vec<HybridResult> {
+    let pairs: Vec<(String, f32)> = bm25_results
+        .iter()
+        .map(|r| (r.skill_id.clone(), r.score))
+        .collect();
+    let mut snippets: HashMap<&str, &[SearchSnippet]> = HashMap::new();
+    for r in bm25_results {
+        snippets.entry(r.skill_id.as_str()).or_insert(&r.snippets);
+    }
+
+    let mut results = fuse_results(&pairs, semantic_results, config);
+    for result in &mut results {
+        if let Some(found) = snippets.get(result.skill_id.as_str()) {
+            result.snippets = found.to_vec();
+        }
+    }
+    results
+}
+
 /// Simple fusion returning only (`skill_id`, score) pairs
 #[must_use]
 pub fn fuse_simple(
@@ -286,6 +399,19 @@ mod tests {
         assert!((results[0].score - expected_score).abs() < 0.001);
     }
 
+    #[test]
+    fn test_source_contributions_sum_to_score() {
+        let config = RrfConfig::with_weights(2.0, 1.0);
+        let bm25 = vec![("a".to_string(), 5.0), ("b".to_string(), 3.0)];
+        let semantic = vec![("b".to_string(), 0.9)];
+
+        for result in fuse_results(&bm25, &semantic, &config) {
+            let (lexical, semantic) = result.source_contributions(&config);
+            assert!((lexical + semantic - result.score).abs() < 1e-6);
+            assert_eq!(semantic > 0.0, result.semantic_rank.is_some());
+        }
+    }
+
     #[test]
     fn test_weighted_fusion() {
         // Give more weight to BM25
@@ -342,4 +468,117 @@ mod tests {
         assert_eq!(results[0].bm25_score, Some(5.5));
         assert_eq!(results[0].semantic_score, Some(0.88));
     }
+
+    /// Small fixture corpus: lexical and semantic retrieval disagree about
+    /// the best skill, and one skill ranks well in both.
+    fn corpus() -> (Vec<(String, f32)>, Vec<(String, f32)>) {
+        let bm25 = ["grep-tips", "rust-errors", "git-bisect", "shell-quoting"]
+            .iter()
+            .zip([9.0, 7.0, 4.0, 1.0])
+            .map(|(id, score)| ((*id).to_string(), score))
+            .collect();
+        let semantic = ["debugging", "rust-errors", "profiling", "grep-tips"]
+            .iter()
+            .zip([0.92, 0.88, 0.61, 0.20])
+            .map(|(id, score)| ((*id).to_string(), score))
+            .collect();
+        (bm25, semantic)
+    }
+
+    fn ranking(config: &RrfConfig) -> Vec<String> {
+        let (bm25, semantic) = corpus();
+        fuse_with_limit(&bm25, &semantic, config, 10)
+            .into_iter()
+            .map(|r| r.skill_id)
+            .collect()
+    }
+
+    fn position(ranking: &[String], id: &str) -> usize {
+        ranking.iter().position(|r| r == id).unwrap()
+    }
+
+    #[test]
+    fn test_tuning_changes_ranking_in_expected_directions() {
+        let balanced = ranking(&RrfConfig::default());
+        // Agreement wins under balanced weights.
+        assert_eq!(balanced[0], "rust-errors");
+        assert_eq!(balanced.len(), 6);
+
+        // Single-source rankings reproduce that source's order exactly.
+        let bm25_only = ranking(&RrfConfig::default().bm25_only());
+        assert_eq!(
+            bm25_only,
+            ["grep-tips", "rust-errors", "git-bisect", "shell-quoting"]
+        );
+        let vector_only = ranking(&RrfConfig::default().vector_only());
+        assert_eq!(
+            vector_only,
+            ["debugging", "rust-errors", "profiling", "grep-tips"]
+        );
+
+        // Weighting a source pulls its favourite up.
+        let lexical_heavy = ranking(&RrfConfig::with_weights(3.0, 1.0));
+        assert_eq!(lexical_heavy[0], "grep-tips");
+        assert!(position(&lexical_heavy, "git-bisect") < position(&lexical_heavy, "profiling"));
+        let semantic_heavy = ranking(&RrfConfig::with_weights(1.0, 3.0));
+        assert!(position(&semantic_heavy, "profiling") < position(&semantic_heavy, "git-bisect"));
+        assert!(position(&semantic_heavy, "debugging") < position(&semantic_heavy, "git-bisect"));
+
+        // A small k sharpens the gap between rank 1 and rank 2, so a
+        // single-list #1 overtakes a skill that is #2 in both lists.
+        let sharp = ranking(&RrfConfig::with_k(0.5));
+        assert!(position(&sharp, "grep-tips") < position(&sharp, "rust-errors"));
+
+        // The score floor drops skills only one source found deep down.
+        let floored = ranking(&RrfConfig {
+            min_score: 0.02,
+            ..RrfConfig::default()
+        });
+        assert!(floored.len() < balanced.len());
+        assert!(!floored.contains(&"shell-quoting".to_string()));
+        assert!(floored.contains(&"rust-errors".to_string()));
+    }
+
+    #[test]
+    fn test_learned_weights_keep_total_and_shift_ranking() {
+        let config = RrfConfig::with_weights(0.5, 0.5).with_learned_weights(0.3, 0.1);
+        assert!((config.bm25_weight - 0.75).abs() < 1e-6);
+        assert!((config.semantic_weight - 0.25).abs() < 1e-6);
+        assert_eq!(ranking(&config)[0], "grep-tips");
+
+        let untouched = RrfConfig::default().with_learned_weights(0.0, 0.0);
+        assert_eq!(untouched.bm25_weight, 1.0);
+        assert_eq!(untouched.semantic_weight, 1.0);
+    }
+
+    #[test]
+    fn test_fuse_bm25_results_preserves_snippets() {
+        let config = RrfConfig::default();
+        let snippet = SearchSnippet {
+            field: "body".to_string(),
+            fragment: "handle the error".to_string(),
+            highlights: vec![(11, 16)],
+        };
+        let bm25 = vec![Bm25Result {
+            skill_id: "lexical".to_string(),
+            score: 4.0,
+            name: "Lexical".to_string(),
+            layer: "project".to_string(),
+            snippets: vec![snippet.clone()],
+        }];
+        let semantic = vec![
+            ("vector-only".to_string(), 0.9),
+            ("lexical".to_string(), 0.5),
+        ];
+
+        let results = fuse_bm25_results(&bm25, &semantic, &config);
+        assert_eq!(results.len(), 2);
+        let lexical = results.iter().find(|r| r.skill_id == "lexical").unwrap();
+        assert_eq!(lexical.snippets, vec![snippet]);
+        let vector_only = results
+            .iter()
+            .find(|r| r.skill_id == "vector-only")
+            .unwrap();
+        assert!(vector_only.snippets.is_empty());
+    }
 }
@@ -1,6 +1,7 @@
 //! Search engine for skills
 //!
-//! Implements hybrid search: BM25 full-text + hash embeddings + RRF fusion.
+//! Implements hybrid search: BM25 full-text + embeddings (hash by default,
+//! optionally a local ONNX model) + RRF fusion.
 //!
 //! ## Architecture
 //!
@@ -12,7 +13,7 @@
 //!                     ▼                          ▼
 //! ┌──────────────────────────────┐  ┌──────────────────────────────┐
 //! │       Bm25Index              │  │       VectorIndex            │
-//! │   (Tantivy BM25 search)      │  │   (Hash embeddings)          │
+//! │   (Tantivy BM25 search)      │  │   (Hash / ONNX embeddings)   │
 //! └──────────────────────────────┘  └──────────────────────────────┘
 //!                     │                          │
 //!                     └──────────┬───────────────┘
@@ -30,21 +31,34 @@
 //! The `cache` module provides LRU caching for query results and embeddings
 //! to reduce latency for repeated operations. See `CacheLayer` for details.
 
+pub mod batch;
 pub mod cache;
 pub mod context;
+pub mod cursor;
 pub mod embeddings;
 pub mod embeddings_local;
 pub mod filters;
+pub mod health;
 pub mod hybrid;
 pub mod tantivy;
 pub mod tantivy_index;
 
 // Re-export main types
+pub use batch::{
+    BatchResults, MergedSkill, QueryRanker, QueryResults, RankedSkill, check_batch_limits,
+    parse_batch_queries, search_batch,
+};
 pub use cache::{CacheLayer, CacheStats, CachedQueryResult, SessionFingerprint};
 pub use context::{FilterResult, SearchContext, SearchFilters, SearchLayer};
+pub use cursor::{MAX_PAGE_SIZE, PageCursor, Pager};
 pub use embeddings::{ApiEmbedder, Embedder, HashEmbedder, VectorIndex, build_embedder};
 pub use embeddings_local::LocalEmbedder;
 pub use filters::{filter_hybrid_results, filter_skill_ids, matches_skill_record};
-pub use hybrid::{HybridResult, RrfConfig, fuse_results, fuse_simple, fuse_with_limit};
-pub use tantivy::{Bm25Index, Bm25Result};
+pub use health::IndexProblem;
+pub use hybrid::{
+    HybridResult, RrfConfig, fuse_bm25_results, fuse_results, fuse_simple, fuse_with_limit,
+};
+pub use tantivy::{
+    Bm25Index, Bm25Result, Compaction, IndexStats, SCHEMA_VERSION, SearchBoosts, SearchSnippet,
+};
 pub use tantivy_index::SearchIndex;
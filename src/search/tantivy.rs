@@ -2,29 +2,84 @@
 //!
 //! Implements BM25 full-text search using Tantivy. Part of the hybrid search
 //! system that combines BM25 with hash embeddings via RRF fusion.
-
-use std::path::Path;
-use std::sync::RwLock;
-
+//!
+//! Besides the searchable text, each document carries its tags, layer, last
+//! update time, quality score and deprecation flag as indexed fields, so
+//! [`SearchFilters`] run inside the Tantivy query and `[search.boosts]` can
+//! favor recent, high-quality skills. Indexes built with an older schema are
+//! recreated empty on open and flagged for a rebuild (see
+//! [`SCHEMA_VERSION`]).
+
+use std::ops::Bound;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use serde::Serialize;
 use tantivy::collector::TopDocs;
-use tantivy::query::QueryParser;
+use tantivy::directory::error::LockError;
+use tantivy::query::{
+    AllQuery, BooleanQuery, ConstScoreQuery, Occur, Query, QueryParser, RangeQuery, TermQuery,
+};
 use tantivy::schema::{
-    Field, IndexRecordOption, STORED, STRING, Schema, TextFieldIndexing, TextOptions, Value,
+    FAST, Field, INDEXED, IndexRecordOption, STORED, STRING, Schema, TextFieldIndexing,
+    TextOptions, Value,
+};
+use tantivy::snippet::SnippetGenerator;
+use tantivy::{
+    DateTime, Index, IndexReader, IndexWriter, ReloadPolicy, Searcher, TantivyDocument,
+    TantivyError, Term,
 };
-use tantivy::{Index, IndexReader, IndexWriter, ReloadPolicy, TantivyDocument};
+use tracing::warn;
 
+use super::context::{SearchFilters, SearchLayer};
+use crate::config::SearchConfig;
 use crate::error::{MsError, Result};
 use crate::storage::sqlite::SkillRecord;
+use crate::storage::tx::{GlobalLock, lock_timeout};
+
+/// Lock file in the index directory, beside Tantivy's own, serializing index
+/// writers across processes.
+pub const WRITER_LOCK_FILE: &str = ".ms-writer.lock";
+
+/// Marker left in the index directory when [`Bm25Index::open`] recreated an
+/// index built with an older schema; removed once the index is rebuilt.
+pub const REBUILD_MARKER_FILE: &str = ".ms-needs-rebuild";
+
+/// Version of the index schema built by [`build_schema`]. Bump it with every
+/// schema change: older indexes no longer match and are rebuilt.
+///
+/// - 1: searchable text, layer, quality and deprecation (stored only)
+/// - 2: tag and layer terms, `last_updated` date, f64 quality and indexed
+///   deprecation flag, for filters and boosts inside the query
+pub const SCHEMA_VERSION: u32 = 2;
 
 /// BM25 search index using Tantivy
+///
+/// The `IndexWriter` is opened on the first write and released by
+/// [`commit`](Self::commit), so a handle only holds the writer while it has
+/// uncommitted changes. Searches never wait on it.
 pub struct Bm25Index {
     index: Index,
     reader: IndexReader,
-    writer: Option<RwLock<IndexWriter>>,
+    /// `None` for read-only indexes; otherwise the writer, when open.
+    writer: Option<Mutex<Option<Writer>>>,
+    /// Lock file serializing writers across processes (`None` in memory).
+    writer_lock_path: Option<PathBuf>,
+    /// Memory budget of the writer, in bytes.
+    writer_budget: usize,
+    /// [`REBUILD_MARKER_FILE`] of an on-disk index (`None` in memory).
+    rebuild_marker: Option<PathBuf>,
     // Field handles for fast access
     fields: BM25Fields,
 }
 
+/// An open `IndexWriter` and the cross-process lock held while it lives.
+/// Fields drop in order: the writer (and Tantivy's own lock) goes first.
+struct Writer {
+    writer: IndexWriter,
+    _lock: Option<GlobalLock>,
+}
+
 /// Field handles for the BM25 schema
 #[derive(Clone)]
 struct BM25Fields {
@@ -34,11 +89,16 @@ struct BM25Fields {
     body: Field,
     tags: Field,
     aliases: Field,
+    tag: Field,
     layer: Field,
+    last_updated: Field,
     quality_score: Field,
     deprecated: Field,
 }
 
+/// Maximum fragment length (in characters) for search snippets
+const SNIPPET_MAX_CHARS: usize = 160;
+
 /// A single BM25 search result
 #[derive(Debug, Clone)]
 pub struct Bm25Result {
@@ -50,6 +110,101 @@ pub struct Bm25Result {
     pub name: String,
     /// Source layer
     pub layer: String,
+    /// Best matching fragment per field (empty when nothing is highlightable)
+    pub snippets: Vec<SearchSnippet>,
+}
+
+/// Best matching fragment of one field, with the matched terms highlighted
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SearchSnippet {
+    /// Field the fragment came from (name, description, body, tags, aliases)
+    pub field: String,
+    /// Fragment text
+    pub fragment: String,
+    /// Byte ranges `[start, end)` of matched terms within `fragment`
+    pub highlights: Vec<(usize, usize)>,
+}
+
+/// Segment and document counts of the committed index
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct IndexStats {
+    /// Searchable segments
+    pub segments: usize,
+    /// Documents visible to searches
+    pub live_docs: u64,
+    /// Deleted documents still taking space in their segments
+    pub deleted_docs: u64,
+}
+
+impl IndexStats {
+    /// Share of stored documents that are deleted (0.0 for an empty index).
+    #[must_use]
+    pub fn deleted_ratio(&self) -> f64 {
+        let total = self.live_docs + self.deleted_docs;
+        if total == 0 {
+            0.0
+        } else {
+            self.deleted_docs as f64 / total as f64
+        }
+    }
+}
+
+/// Index stats before and after [`Bm25Index::compact`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Compaction {
+    pub before: IndexStats,
+    pub after: IndexStats,
+}
+
+/// Score multipliers favoring recently updated and high-quality skills
+/// (`[search.boosts]`). The default applies no boost.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SearchBoosts {
+    /// Extra score share for a skill updated just now
+    pub recency_weight: f32,
+    /// Age in days at which the recency boost has halved
+    pub recency_half_life_days: f32,
+    /// Extra score share for a skill of quality 1.0
+    pub quality_weight: f32,
+}
+
+impl SearchBoosts {
+    #[must_use]
+    pub const fn from_search_config(config: &SearchConfig) -> Self {
+        Self {
+            recency_weight: config.boosts.recency_weight,
+            recency_half_life_days: config.boosts.recency_half_life_days,
+            quality_weight: config.boosts.quality_weight,
+        }
+    }
+
+    /// Whether every boost is off, leaving BM25 scores as they are.
+    #[must_use]
+    pub fn is_neutral(&self) -> bool {
+        self.recency_weight <= 0.0 && self.quality_weight <= 0.0
+    }
+
+    /// Score multiplier for a skill of `quality` (0.0-1.0) last updated
+    /// `age_days` ago: `1 + quality_weight * quality` times
+    /// `1 + recency_weight * 0.5^(age / half_life)`. Skills without a known
+    /// update time get no recency boost.
+    #[must_use]
+    pub fn factor(&self, quality: f64, age_days: Option<f64>) -> f32 {
+        let quality = if quality.is_finite() {
+            quality.clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let mut factor = f64::from(self.quality_weight.max(0.0)).mul_add(quality, 1.0);
+        if let Some(age) = age_days
+            && self.recency_weight > 0.0
+            && self.recency_half_life_days > 0.0
+        {
+            let decay = 0.5_f64.powf(age.max(0.0) / f64::from(self.recency_half_life_days));
+            factor *= f64::from(self.recency_weight).mul_add(decay, 1.0);
+        }
+        factor as f32
+    }
 }
 
 impl Bm25Index {
@@ -63,32 +218,54 @@ impl Bm25Index {
 
         // Try to open existing index, or create new one
         let index = if path.join("meta.json").exists() {
-            Index::open_in_dir(path)?
+            let existing = Index::open_in_dir(path)?;
+            if is_current_schema(&existing.schema()) {
+                existing
+            } else {
+                // The index is derived data: recreate it empty with the
+                // current schema (search falls back to the SQLite scan) and
+                // leave a marker so `ms index` and `ms doctor` rebuild it.
+                warn!(
+                    path = %path.display(),
+                    version = SCHEMA_VERSION,
+                    "search index schema is outdated; recreating it, run `ms index` to rebuild"
+                );
+                drop(existing);
+                std::fs::remove_dir_all(path)?;
+                std::fs::create_dir_all(path)?;
+                let index = Index::create_in_dir(path, schema)?;
+                std::fs::write(
+                    path.join(REBUILD_MARKER_FILE),
+                    format!("{SCHEMA_VERSION}\n"),
+                )?;
+                index
+            }
         } else {
             Index::create_in_dir(path, schema)?
         };
 
-        // Create reader with manual reload (we control when to refresh)
+        // Reload on commits, including other processes' (our own commits
+        // also reload explicitly, so they are visible right away)
         let reader = index
             .reader_builder()
-            .reload_policy(ReloadPolicy::Manual)
+            .reload_policy(ReloadPolicy::OnCommitWithDelay)
             .try_into()?;
 
-        // Create writer with 50MB buffer
-        let writer = index.writer(50_000_000)?;
-
         Ok(Self {
             index,
             reader,
-            writer: Some(RwLock::new(writer)),
+            writer: Some(Mutex::new(None)),
+            writer_lock_path: Some(path.join(WRITER_LOCK_FILE)),
+            // 50MB buffer
+            writer_budget: 50_000_000,
+            rebuild_marker: Some(path.join(REBUILD_MARKER_FILE)),
             fields,
         })
     }
 
-    /// Open an existing index in read-only mode (no write lock acquired).
-    ///
-    /// This allows concurrent readers without blocking on the Tantivy write lock,
-    /// making it suitable for the MCP server and other read-only access patterns.
+    /// Open an existing index in read-only mode, for directories that cannot
+    /// be written to. [`open`](Self::open) takes no lock either until the
+    /// first write.
     pub fn open_readonly(path: impl AsRef<Path>) -> Result<Self> {
         let path = path.as_ref();
         if !path.join("meta.json").exists() {
@@ -103,6 +280,9 @@ impl Bm25Index {
         let schema = build_schema();
         let fields = extract_fields(&schema)?;
         let index = Index::open_in_dir(path)?;
+        if !is_current_schema(&index.schema()) {
+            return Err(outdated_schema_error(path));
+        }
 
         let reader = index
             .reader_builder()
@@ -113,6 +293,9 @@ impl Bm25Index {
             index,
             reader,
             writer: None,
+            writer_lock_path: None,
+            writer_budget: 0,
+            rebuild_marker: Some(path.join(REBUILD_MARKER_FILE)),
             fields,
         })
     }
@@ -127,12 +310,14 @@ impl Bm25Index {
             .reader_builder()
             .reload_policy(ReloadPolicy::Manual)
             .try_into()?;
-        let writer = index.writer(15_000_000)?;
 
         Ok(Self {
             index,
             reader,
-            writer: Some(RwLock::new(writer)),
+            writer: Some(Mutex::new(None)),
+            writer_lock_path: None,
+            writer_budget: 15_000_000,
+            rebuild_marker: None,
             fields,
         })
     }
@@ -142,49 +327,142 @@ impl Bm25Index {
         self.writer.is_none()
     }
 
-    fn require_writer(&self) -> Result<&RwLock<IndexWriter>> {
-        self.writer.as_ref().ok_or_else(|| {
-            MsError::SearchIndex(tantivy::TantivyError::InternalError(
+    /// Whether the index was recreated for a new schema and still has to be
+    /// rebuilt from the database.
+    pub fn needs_rebuild(&self) -> bool {
+        self.rebuild_marker.as_ref().is_some_and(|m| m.exists())
+    }
+
+    /// Record that the index was rebuilt, after its documents are committed.
+    pub fn mark_rebuilt(&self) -> Result<()> {
+        if let Some(marker) = &self.rebuild_marker
+            && marker.exists()
+        {
+            std::fs::remove_file(marker)?;
+        }
+        Ok(())
+    }
+
+    /// Whether the index at `path` was built with the current schema. A
+    /// missing index counts as current: it is created on first use.
+    pub fn has_current_schema(path: impl AsRef<Path>) -> Result<bool> {
+        let path = path.as_ref();
+        if !path.join("meta.json").exists() {
+            return Ok(true);
+        }
+        Ok(is_current_schema(&Index::open_in_dir(path)?.schema()))
+    }
+
+    /// Open the writer now, waiting up to the lock timeout for other
+    /// writers, so that a busy index fails a command before it touches the
+    /// database or the archive.
+    pub fn reserve_writer(&self) -> Result<()> {
+        self.with_writer(|_| Ok(()))
+    }
+
+    fn writer_slot(&self) -> Result<MutexGuard<'_, Option<Writer>>> {
+        let slot = self.writer.as_ref().ok_or_else(|| {
+            MsError::SearchIndex(TantivyError::InternalError(
                 "Index opened in read-only mode; write operations are not available".to_string(),
             ))
+        })?;
+        // A panic mid-write leaves the writer in an unknown state: drop it
+        // with its uncommitted changes rather than fail every later write.
+        Ok(slot.lock().unwrap_or_else(|poisoned| {
+            slot.clear_poison();
+            let mut guard = poisoned.into_inner();
+            *guard = None;
+            guard
+        }))
+    }
+
+    /// Run `f` with the writer, opening it (and taking the writer lock) if
+    /// this handle does not hold it yet.
+    fn with_writer<T>(&self, f: impl FnOnce(&mut IndexWriter) -> Result<T>) -> Result<T> {
+        let mut guard = self.writer_slot()?;
+        let writer = match &mut *guard {
+            Some(writer) => writer,
+            empty => empty.insert(self.open_writer()?),
+        };
+        f(&mut writer.writer)
+    }
+
+    fn open_writer(&self) -> Result<Writer> {
+        const RESOURCE: &str = "search index writer";
+        let lock = self
+            .writer_lock_path
+            .as_deref()
+            .map(|path| GlobalLock::acquire_at_or_busy(path, RESOURCE, lock_timeout()))
+            .transpose()?;
+        // Holding our lock, Tantivy's own is only busy when a process that
+        // predates it is writing
+        let writer = self
+            .index
+            .writer(self.writer_budget)
+            .map_err(|err| match err {
+                TantivyError::LockFailure(LockError::LockBusy, _) => MsError::Busy {
+                    resource: RESOURCE.to_string(),
+                    pid: self
+                        .writer_lock_path
+                        .as_deref()
+                        .and_then(GlobalLock::other_holder_pid),
+                },
+                other => other.into(),
+            })?;
+        Ok(Writer {
+            writer,
+            _lock: lock,
         })
     }
 
     /// Index a skill record
     pub fn index_skill(&self, skill: &SkillRecord) -> Result<()> {
+        self.index_skill_tagged(skill, &[])
+    }
+
+    /// Index a skill with the tags `ms index` derived for it, which match
+    /// like author tags.
+    pub fn index_skill_tagged(&self, skill: &SkillRecord, derived_tags: &[String]) -> Result<()> {
         // Parse tags and aliases from metadata JSON
-        let (tags, aliases) = parse_metadata(&skill.metadata_json);
+        let (mut tags, aliases) = parse_metadata(&skill.metadata_json);
+        tags.extend(derived_tags.iter().map(|tag| tag.to_lowercase()));
 
         let mut doc = TantivyDocument::new();
         doc.add_text(self.fields.id, &skill.id);
         doc.add_text(self.fields.name, &skill.name);
         doc.add_text(self.fields.description, &skill.description);
-        doc.add_text(self.fields.body, &skill.body);
-        doc.add_text(self.fields.tags, &tags);
+        // Sensitive skills are found by title and description only; their
+        // body (plaintext or sealed) never reaches the index.
+        if !is_sensitive_record(skill) {
+            doc.add_text(self.fields.body, &skill.body);
+        }
+        doc.add_text(self.fields.tags, tags.join(" "));
         doc.add_text(self.fields.aliases, &aliases);
-        doc.add_text(self.fields.layer, &skill.source_layer);
-        // Safely convert quality_score to u64, handling NaN/Inf/negative values
-        let quality_u64 = if skill.quality_score.is_nan() || skill.quality_score.is_infinite() {
-            0u64
+        // One exact term per tag, for tag filters
+        for tag in &tags {
+            doc.add_text(self.fields.tag, tag);
+        }
+        doc.add_text(self.fields.layer, layer_term(&skill.source_layer));
+        if let Some(updated) = last_updated(skill) {
+            doc.add_date(self.fields.last_updated, updated);
+        }
+        // NaN/Inf would poison range filters and boosts
+        let quality = if skill.quality_score.is_finite() {
+            skill.quality_score
         } else {
-            (skill.quality_score.clamp(0.0, 100.0) * 100.0) as u64
+            0.0
         };
-        doc.add_u64(self.fields.quality_score, quality_u64);
+        doc.add_f64(self.fields.quality_score, quality);
         doc.add_bool(self.fields.deprecated, skill.is_deprecated);
 
         // Delete any existing document with this ID first
         let id_term = tantivy::Term::from_field_text(self.fields.id, &skill.id);
 
-        let writer = self.require_writer()?.write().map_err(|e| {
-            MsError::SearchIndex(tantivy::TantivyError::InternalError(format!(
-                "Failed to acquire write lock: {e}"
-            )))
-        })?;
-
-        writer.delete_term(id_term);
-        writer.add_document(doc)?;
-
-        Ok(())
+        self.with_writer(|writer| {
+            writer.delete_term(id_term);
+            writer.add_document(doc)?;
+            Ok(())
+        })
     }
 
     /// Index multiple skills in a batch
@@ -202,16 +480,13 @@ impl Bm25Index {
         Ok(count)
     }
 
-    /// Commit pending changes and reload the reader
+    /// Commit pending changes, release the writer, and reload the reader
     pub fn commit(&self) -> Result<()> {
-        let mut writer = self.require_writer()?.write().map_err(|e| {
-            MsError::SearchIndex(tantivy::TantivyError::InternalError(format!(
-                "Failed to acquire write lock: {e}"
-            )))
-        })?;
-
-        writer.commit()?;
-        drop(writer); // Release lock before reload
+        let mut slot = self.writer_slot()?;
+        if let Some(mut writer) = slot.take() {
+            writer.writer.commit()?;
+        }
+        drop(slot); // Release lock before reload
 
         self.reader.reload()?;
         Ok(())
@@ -221,30 +496,19 @@ impl Bm25Index {
     pub fn delete_skill(&self, skill_id: &str) -> Result<()> {
         let id_term = tantivy::Term::from_field_text(self.fields.id, skill_id);
 
-        let writer = self.require_writer()?.write().map_err(|e| {
-            MsError::SearchIndex(tantivy::TantivyError::InternalError(format!(
-                "Failed to acquire write lock: {e}"
-            )))
-        })?;
-
-        writer.delete_term(id_term);
-        Ok(())
+        self.with_writer(|writer| {
+            writer.delete_term(id_term);
+            Ok(())
+        })
     }
 
     /// Clear the entire index
     pub fn clear(&self) -> Result<()> {
-        let mut writer = self.require_writer()?.write().map_err(|e| {
-            MsError::SearchIndex(tantivy::TantivyError::InternalError(format!(
-                "Failed to acquire write lock: {e}"
-            )))
+        self.with_writer(|writer| {
+            writer.delete_all_documents()?;
+            Ok(())
         })?;
-
-        writer.delete_all_documents()?;
-        writer.commit()?;
-        drop(writer);
-
-        self.reader.reload()?;
-        Ok(())
+        self.commit()
     }
 
     /// Search skills by query
@@ -267,40 +531,81 @@ impl Bm25Index {
             .parse_query(query)
             .map_err(|e| MsError::QueryParse(format!("Failed to parse query: {e}")))?;
 
-        let top_docs =
-            searcher.search(&parsed_query, &TopDocs::with_limit(limit).order_by_score())?;
-
-        let mut results = Vec::with_capacity(top_docs.len());
-        for (score, doc_address) in top_docs {
-            let doc: TantivyDocument = searcher.doc(doc_address)?;
-
-            let skill_id = doc
-                .get_first(self.fields.id)
-                .and_then(|v| v.as_str())
-                .unwrap_or_default()
-                .to_string();
+        self.collect_results(&searcher, &*parsed_query, limit, None)
+    }
 
-            let name = doc
-                .get_first(self.fields.name)
-                .and_then(|v| v.as_str())
-                .unwrap_or_default()
-                .to_string();
+    /// Search with `filters` applied inside the query and BM25 scores
+    /// multiplied by `boosts`.
+    ///
+    /// Tags (any of), layer, minimum quality and deprecation become term,
+    /// range and exclusion clauses that never add to the score. A blank
+    /// query with tag, layer or quality filters lists every matching skill.
+    /// Boosts reorder the `limit` best matches.
+    pub fn search_filtered(
+        &self,
+        query: &str,
+        filters: &SearchFilters,
+        boosts: &SearchBoosts,
+        limit: usize,
+    ) -> Result<Vec<Bm25Result>> {
+        let searcher = self.reader.searcher();
 
-            let layer = doc
-                .get_first(self.fields.layer)
-                .and_then(|v| v.as_str())
-                .unwrap_or_default()
-                .to_string();
+        let restricts =
+            !filters.tags.is_empty() || filters.layer.is_some() || filters.min_quality.is_some();
+        let text_query: Box<dyn Query> = if query.trim().is_empty() && restricts {
+            Box::new(AllQuery)
+        } else {
+            let query_parser = QueryParser::for_index(
+                &self.index,
+                vec![
+                    self.fields.name,
+                    self.fields.description,
+                    self.fields.body,
+                    self.fields.tags,
+                    self.fields.aliases,
+                ],
+            );
+            query_parser
+                .parse_query(query)
+                .map_err(|e| MsError::QueryParse(format!("Failed to parse query: {e}")))?
+        };
 
-            results.push(Bm25Result {
-                skill_id,
-                score,
-                name,
-                layer,
-            });
+        let mut clauses: Vec<(Occur, Box<dyn Query>)> = vec![(Occur::Must, text_query)];
+        if !filters.tags.is_empty() {
+            let occur = if filters.match_all_tags {
+                Occur::Must
+            } else {
+                Occur::Should
+            };
+            let tag_clauses = filters
+                .tags
+                .iter()
+                .map(|tag| {
+                    let term = Term::from_field_text(self.fields.tag, &tag.to_lowercase());
+                    (occur, term_query(term))
+                })
+                .collect();
+            clauses.push((Occur::Must, filter_clause(BooleanQuery::new(tag_clauses))));
+        }
+        if let Some(layer) = filters.layer {
+            let term = Term::from_field_text(self.fields.layer, layer.as_str());
+            clauses.push((
+                Occur::Must,
+                filter_clause(TermQuery::new(term, IndexRecordOption::Basic)),
+            ));
+        }
+        if let Some(min_quality) = filters.min_quality {
+            let lower = Term::from_field_f64(self.fields.quality_score, f64::from(min_quality));
+            let range = RangeQuery::new(Bound::Included(lower), Bound::Unbounded);
+            clauses.push((Occur::Must, filter_clause(range)));
+        }
+        if !filters.include_deprecated {
+            let term = Term::from_field_bool(self.fields.deprecated, true);
+            clauses.push((Occur::MustNot, term_query(term)));
         }
 
-        Ok(results)
+        let boosts = (!boosts.is_neutral()).then_some(boosts);
+        self.collect_results(&searcher, &BooleanQuery::new(clauses), limit, boosts)
     }
 
     /// Search with layer filter
@@ -335,13 +640,56 @@ impl Bm25Index {
             .parse_query(&filter_query)
             .map_err(|e| MsError::QueryParse(format!("Failed to parse query: {e}")))?;
 
-        let top_docs =
-            searcher.search(&parsed_query, &TopDocs::with_limit(limit).order_by_score())?;
+        self.collect_results(&searcher, &*parsed_query, limit, None)
+    }
+
+    /// Run a parsed query and build results, including per-field snippets,
+    /// with scores multiplied by `boosts` when given
+    fn collect_results(
+        &self,
+        searcher: &Searcher,
+        query: &dyn Query,
+        limit: usize,
+        boosts: Option<&SearchBoosts>,
+    ) -> Result<Vec<Bm25Result>> {
+        let top_docs = searcher.search(query, &TopDocs::with_limit(limit).order_by_score())?;
+        if top_docs.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut generators = Vec::new();
+        for (label, field) in [
+            ("name", self.fields.name),
+            ("description", self.fields.description),
+            ("body", self.fields.body),
+            ("tags", self.fields.tags),
+            ("aliases", self.fields.aliases),
+        ] {
+            let mut generator = SnippetGenerator::create(searcher, query, field)?;
+            generator.set_max_num_chars(SNIPPET_MAX_CHARS);
+            generators.push((label, generator));
+        }
 
+        let now = DateTime::from_timestamp_secs(chrono::Utc::now().timestamp());
         let mut results = Vec::with_capacity(top_docs.len());
-        for (score, doc_address) in top_docs {
+        for (mut score, doc_address) in top_docs {
             let doc: TantivyDocument = searcher.doc(doc_address)?;
 
+            if let Some(boosts) = boosts {
+                let quality = doc
+                    .get_first(self.fields.quality_score)
+                    .and_then(|v| v.as_f64())
+                    .unwrap_or_default();
+                let age_days = doc
+                    .get_first(self.fields.last_updated)
+                    .and_then(|v| v.as_datetime())
+                    .map(|updated| {
+                        (now.into_timestamp_secs() - updated.into_timestamp_secs()) as f64
+                            / 86_400.0
+                    });
+                score *= boosts.factor(quality, age_days);
+            }
+
             let skill_id = doc
                 .get_first(self.fields.id)
                 .and_then(|v| v.as_str())
@@ -354,20 +702,32 @@ impl Bm25Index {
                 .unwrap_or_default()
                 .to_string();
 
-            let layer_val = doc
+            let layer = doc
                 .get_first(self.fields.layer)
                 .and_then(|v| v.as_str())
                 .unwrap_or_default()
                 .to_string();
 
+            let snippets = generators
+                .iter()
+                .filter_map(|(label, generator)| {
+                    let snippet = generator.snippet_from_doc(&doc);
+                    to_search_snippet(label, snippet.fragment(), snippet.highlighted())
+                })
+                .collect();
+
             results.push(Bm25Result {
                 skill_id,
                 score,
                 name,
-                layer: layer_val,
+                layer,
+                snippets,
             });
         }
 
+        if boosts.is_some() {
+            results.sort_by(|a, b| b.score.total_cmp(&a.score));
+        }
         Ok(results)
     }
 
@@ -381,6 +741,89 @@ impl Bm25Index {
     pub fn is_empty(&self) -> bool {
         self.num_docs() == 0
     }
+
+    /// Opstamp of the last commit. It moves on every commit, so result
+    /// orderings (and page cursors) are only comparable within a generation.
+    pub fn generation(&self) -> Result<u64> {
+        Ok(self.index.load_metas()?.opstamp)
+    }
+
+    /// Segment and document counts as of the last commit.
+    pub fn stats(&self) -> IndexStats {
+        let searcher = self.reader.searcher();
+        let readers = searcher.segment_readers();
+        IndexStats {
+            segments: readers.len(),
+            live_docs: readers.iter().map(|r| u64::from(r.num_docs())).sum(),
+            deleted_docs: readers
+                .iter()
+                .map(|r| u64::from(r.num_deleted_docs()))
+                .sum(),
+        }
+    }
+
+    /// Merge all segments into one, dropping deleted documents, then remove
+    /// the files no longer referenced.
+    ///
+    /// Pending changes are committed first. Old segment files are only
+    /// garbage collected after the merged segment has been committed, so an
+    /// interrupted compaction leaves the previous index intact.
+    pub fn compact(&self) -> Result<Compaction> {
+        let before = self.with_writer(|writer| {
+            writer.commit()?;
+            self.reader.reload()?;
+            let before = self.stats();
+
+            if before.segments > 1 || before.deleted_docs > 0 {
+                let segment_ids = self.index.searchable_segment_ids()?;
+                writer.merge(&segment_ids).wait()?;
+                self.reader.reload()?;
+            }
+            writer.garbage_collect_files().wait()?;
+            Ok(before)
+        })?;
+        // Releases the writer; nothing is left to commit
+        self.commit()?;
+
+        Ok(Compaction {
+            before,
+            after: self.stats(),
+        })
+    }
+
+    /// Segment files whose checksum does not match their contents.
+    pub fn corrupted_files(&self) -> Result<Vec<std::path::PathBuf>> {
+        let mut files: Vec<_> = self.index.validate_checksum()?.into_iter().collect();
+        files.sort();
+        Ok(files)
+    }
+}
+
+/// Build a snippet from a highlighted fragment, dropping any range that does
+/// not fall on UTF-8 character boundaries. Returns `None` if nothing matched.
+fn to_search_snippet(
+    field: &str,
+    fragment: &str,
+    highlighted: &[std::ops::Range<usize>],
+) -> Option<SearchSnippet> {
+    let highlights: Vec<(usize, usize)> = highlighted
+        .iter()
+        .filter(|r| {
+            r.start < r.end
+                && r.end <= fragment.len()
+                && fragment.is_char_boundary(r.start)
+                && fragment.is_char_boundary(r.end)
+        })
+        .map(|r| (r.start, r.end))
+        .collect();
+    if highlights.is_empty() {
+        return None;
+    }
+    Some(SearchSnippet {
+        field: field.to_string(),
+        fragment: fragment.to_string(),
+        highlights,
+    })
 }
 
 fn normalize_layer(input: &str) -> &'static str {
@@ -409,20 +852,68 @@ fn build_schema() -> Schema {
     builder.add_text_field("id", STRING | STORED);
     builder.add_text_field("name", text_options.clone() | STORED);
 
-    // Searchable content (not stored, just indexed)
-    builder.add_text_field("description", text_options.clone());
-    builder.add_text_field("body", text_options.clone());
-    builder.add_text_field("tags", text_options.clone());
-    builder.add_text_field("aliases", text_options);
+    // Searchable content (stored so search results can carry snippets;
+    // indexes built before this change return no snippets until rebuilt)
+    builder.add_text_field("description", text_options.clone() | STORED);
+    builder.add_text_field("body", text_options.clone() | STORED);
+    builder.add_text_field("tags", text_options.clone() | STORED);
+    builder.add_text_field("aliases", text_options | STORED);
 
-    // Metadata for filtering (stored)
+    // Metadata for filters and boosts (indexed terms, stored)
+    builder.add_text_field("tag", STRING | STORED);
     builder.add_text_field("layer", STRING | STORED);
-    builder.add_u64_field("quality_score", tantivy::schema::FAST | STORED);
-    builder.add_bool_field("deprecated", STORED);
+    builder.add_date_field("last_updated", INDEXED | FAST | STORED);
+    builder.add_f64_field("quality_score", INDEXED | FAST | STORED);
+    builder.add_bool_field("deprecated", INDEXED | STORED);
 
     builder.build()
 }
 
+/// Whether an existing index was built with [`build_schema`] (the schema of
+/// [`SCHEMA_VERSION`]); any difference means it must be rebuilt.
+fn is_current_schema(schema: &Schema) -> bool {
+    match (
+        serde_json::to_value(schema),
+        serde_json::to_value(build_schema()),
+    ) {
+        (Ok(existing), Ok(current)) => existing == current,
+        _ => false,
+    }
+}
+
+fn outdated_schema_error(path: &Path) -> MsError {
+    MsError::SearchIndex(TantivyError::SchemaError(format!(
+        "search index at {} predates schema v{SCHEMA_VERSION}; run `ms index` to rebuild it",
+        path.display()
+    )))
+}
+
+/// A filter clause: matches like `query` but adds nothing to the score.
+fn filter_clause(query: impl Query + 'static) -> Box<dyn Query> {
+    Box::new(ConstScoreQuery::new(Box::new(query), 0.0))
+}
+
+fn term_query(term: Term) -> Box<dyn Query> {
+    Box::new(TermQuery::new(term, IndexRecordOption::Basic))
+}
+
+/// Canonical layer name stored in the `layer` field (`global` is `org`,
+/// `local` is `user`), as layer filters name it.
+fn layer_term(source_layer: &str) -> String {
+    SearchLayer::from_str(source_layer).map_or_else(
+        || source_layer.to_lowercase(),
+        |layer| layer.as_str().to_string(),
+    )
+}
+
+/// When the skill last changed: `modified_at`, else `indexed_at` (RFC 3339).
+fn last_updated(skill: &SkillRecord) -> Option<DateTime> {
+    [&skill.modified_at, &skill.indexed_at]
+        .into_iter()
+        .find_map(|raw| chrono::DateTime::parse_from_rfc3339(raw).ok())
+        .map(|updated| DateTime::from_timestamp_secs(updated.timestamp()))
+}
+
 /// Extract field handles from schema
 fn extract_fields(schema: &Schema) -> Result<BM25Fields> {
     Ok(BM25Fields {
@@ -456,11 +947,21 @@ fn extract_fields(schema: &Schema) -> Result<BM25Fields> {
                 "missing aliases field".into(),
             ))
         })?,
+        tag: schema.get_field("tag").map_err(|_| {
+            MsError::SearchIndex(tantivy::TantivyError::SchemaError(
+                "missing tag field".into(),
+            ))
+        })?,
         layer: schema.get_field("layer").map_err(|_| {
             MsError::SearchIndex(tantivy::TantivyError::SchemaError(
                 "missing layer field".into(),
             ))
         })?,
+        last_updated: schema.get_field("last_updated").map_err(|_| {
+            MsError::SearchIndex(tantivy::TantivyError::SchemaError(
+                "missing last_updated field".into(),
+            ))
+        })?,
         quality_score: schema.get_field("quality_score").map_err(|_| {
             MsError::SearchIndex(tantivy::TantivyError::SchemaError(
                 "missing quality_score field".into(),
@@ -475,8 +976,16 @@ fn extract_fields(schema: &Schema) -> Result<BM25Fields> {
 }
 
 /// Parse tags and aliases from metadata JSON
-fn parse_metadata(metadata_json: &str) -> (String, String) {
-    let mut tags = String::new();
+fn is_sensitive_record(skill: &SkillRecord) -> bool {
+    crate::security::encryption::is_sealed_body(&skill.body)
+        || serde_json::from_str::<serde_json::Value>(&skill.metadata_json)
+            .ok()
+            .and_then(|meta| meta.get("sensitive").and_then(serde_json::Value::as_bool))
+            .unwrap_or(false)
+}
+
+fn parse_metadata(metadata_json: &str) -> (Vec<String>, String) {
+    let mut tags = Vec::new();
     let mut aliases = String::new();
 
     if let Ok(meta) = serde_json::from_str::<serde_json::Value>(metadata_json) {
@@ -486,8 +995,7 @@ fn parse_metadata(metadata_json: &str) -> (String, String) {
                 .iter()
                 .filter_map(|v| v.as_str())
                 .map(str::to_lowercase)
-                .collect::<Vec<_>>()
-                .join(" ");
+                .collect();
         }
 
         // Extract aliases
@@ -562,6 +1070,20 @@ mod tests {
         assert!(results[0].score > 0.0);
     }
 
+    #[test]
+    fn test_generation_moves_on_commit() {
+        let index = Bm25Index::open_in_memory().unwrap();
+        let before = index.generation().unwrap();
+        index.commit().unwrap();
+        assert_eq!(index.generation().unwrap(), before);
+
+        index
+            .index_skill(&make_test_skill("a", "A", "first", "body"))
+            .unwrap();
+        index.commit().unwrap();
+        assert!(index.generation().unwrap() > before);
+    }
+
     #[test]
     fn test_search_multiple_skills() {
         let index = Bm25Index::open_in_memory().unwrap();
@@ -678,11 +1200,321 @@ mod tests {
         assert_eq!(results.len(), 1);
     }
 
+    #[test]
+    fn test_search_returns_snippets_with_highlights() {
+        let index = Bm25Index::open_in_memory().unwrap();
+        let skill = make_test_skill(
+            "rust-error",
+            "Rust Error Handling",
+            "Best practices for error handling",
+            "Überprüfung: use Result and propagate every error with ? — 🦀 rust.",
+        );
+        index.index_skill(&skill).unwrap();
+        index.commit().unwrap();
+
+        let results = index.search("error", 10).unwrap();
+        assert_eq!(results.len(), 1);
+        let snippets = &results[0].snippets;
+        assert!(snippets.iter().any(|s| s.field == "name"));
+        let body = snippets.iter().find(|s| s.field == "body").unwrap();
+        for &(start, end) in &body.highlights {
+            assert_eq!(body.fragment[start..end].to_lowercase(), "error");
+        }
+        // Fields without a match carry no snippet.
+        assert!(!snippets.iter().any(|s| s.field == "tags"));
+    }
+
+    #[test]
+    fn test_open_recreates_index_without_stored_text() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut builder = Schema::builder();
+        builder.add_text_field("id", STRING | STORED);
+        builder.add_text_field("body", tantivy::schema::TEXT);
+        Index::create_in_dir(dir.path(), builder.build()).unwrap();
+
+        let index = Bm25Index::open(dir.path()).unwrap();
+        assert!(is_current_schema(&index.index.schema()));
+        assert!(index.is_empty());
+        assert!(index.needs_rebuild());
+        assert!(Bm25Index::has_current_schema(dir.path()).unwrap());
+
+        index.mark_rebuilt().unwrap();
+        assert!(!index.needs_rebuild());
+        assert!(!Bm25Index::open(dir.path()).unwrap().needs_rebuild());
+    }
+
+    fn tagged_skill(id: &str, tags: &[&str], layer: &str, quality: f64) -> SkillRecord {
+        let mut skill = make_test_skill(id, id, "Handling errors", "Report errors early.");
+        skill.metadata_json = serde_json::json!({ "tags": tags }).to_string();
+        skill.source_layer = layer.to_string();
+        skill.quality_score = quality;
+        skill
+    }
+
+    fn ids(results: &[Bm25Result]) -> Vec<&str> {
+        let mut ids: Vec<&str> = results.iter().map(|r| r.skill_id.as_str()).collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    #[test]
+    fn test_search_filtered_applies_filters_in_query() {
+        let index = Bm25Index::open_in_memory().unwrap();
+        let mut deprecated = tagged_skill("rust-old", &["rust"], "project", 0.9);
+        deprecated.is_deprecated = true;
+        index
+            .index_skill(&tagged_skill("rust-errors", &["Rust"], "project", 0.9))
+            .unwrap();
+        index.index_skill(&deprecated).unwrap();
+        index
+            .index_skill(&tagged_skill("python-errors", &["python"], "local", 0.3))
+            .unwrap();
+        index
+            .index_skill_tagged(
+                &tagged_skill("go-errors", &[], "global", 0.6),
+                &["go".to_string()],
+            )
+            .unwrap();
+        index.commit().unwrap();
+
+        let search = |query: &str, filters: &SearchFilters| {
+            index
+                .search_filtered(query, filters, &SearchBoosts::default(), 10)
+                .unwrap()
+        };
+        let rust = SearchFilters::with_tags(vec!["rust".to_string()]);
+        assert_eq!(ids(&search("errors", &rust)), ["rust-errors"]);
+        assert_eq!(
+            ids(&search("errors", &rust.clone().include_deprecated(true))),
+            ["rust-errors", "rust-old"]
+        );
+        // Layer aliases are stored under their canonical name
+        assert_eq!(
+            ids(&search(
+                "errors",
+                &SearchFilters::with_layer(SearchLayer::User)
+            )),
+            ["python-errors"]
+        );
+        // Derived tags filter like author tags
+        let go_org = SearchFilters::with_tags(vec!["go".to_string(), "zig".to_string()])
+            .layer(SearchLayer::Org);
+        assert_eq!(ids(&search("errors", &go_org)), ["go-errors"]);
+        assert_eq!(
+            ids(&search("errors", &SearchFilters::with_min_quality(0.5))),
+            ["go-errors", "rust-errors"]
+        );
+
+        // Filters alone list matching skills; a blank query alone lists none
+        let python = SearchFilters::with_tags(vec!["python".to_string()]);
+        assert_eq!(ids(&search("", &python)), ["python-errors"]);
+        assert!(search("", &SearchFilters::new()).is_empty());
+
+        // Filter clauses leave BM25 scores alone
+        let plain = index.search("errors", 10).unwrap();
+        let filtered = search("errors", &SearchFilters::with_min_quality(0.0));
+        let score = |results: &[Bm25Result], id: &str| {
+            results.iter().find(|r| r.skill_id == id).unwrap().score
+        };
+        assert!((score(&plain, "go-errors") - score(&filtered, "go-errors")).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_search_filtered_boosts_recent_quality_skills() {
+        let index = Bm25Index::open_in_memory().unwrap();
+        let now = chrono::Utc::now();
+        let mut fresh = tagged_skill("fresh", &[], "project", 0.9);
+        fresh.modified_at = now.to_rfc3339();
+        let mut stale = tagged_skill("stale", &[], "project", 0.1);
+        stale.modified_at = (now - chrono::Duration::days(720)).to_rfc3339();
+        index.index_skill(&stale).unwrap();
+        index.index_skill(&fresh).unwrap();
+        index.commit().unwrap();
+
+        let filters = SearchFilters::new();
+        let plain = index
+            .search_filtered("errors", &filters, &SearchBoosts::default(), 10)
+            .unwrap();
+        assert!((plain[0].score - plain[1].score).abs() < 1e-6);
+
+        let boosts = SearchBoosts {
+            recency_weight: 0.5,
+            recency_half_life_days: 30.0,
+            quality_weight: 0.5,
+        };
+        let boosted = index
+            .search_filtered("errors", &filters, &boosts, 10)
+            .unwrap();
+        assert_eq!(boosted[0].skill_id, "fresh");
+        assert!(boosted[0].score > boosted[1].score);
+        assert!(boosted[1].score > plain[0].score);
+    }
+
+    #[test]
+    fn test_search_boost_factor() {
+        let boosts = SearchBoosts {
+            recency_weight: 0.1,
+            recency_half_life_days: 90.0,
+            quality_weight: 0.1,
+        };
+        assert!((boosts.factor(1.0, Some(0.0)) - 1.21).abs() < 1e-6);
+        assert!((boosts.factor(0.0, Some(90.0)) - 1.05).abs() < 1e-6);
+        assert!((boosts.factor(0.0, None) - 1.0).abs() < f32::EPSILON);
+        assert!((boosts.factor(f64::NAN, None) - 1.0).abs() < f32::EPSILON);
+        assert!(SearchBoosts::default().is_neutral());
+        assert!(!boosts.is_neutral());
+    }
+
+    #[test]
+    fn test_open_readonly_rejects_outdated_schema() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut builder = Schema::builder();
+        builder.add_text_field("id", STRING | STORED);
+        builder.add_u64_field("quality_score", FAST | STORED);
+        Index::create_in_dir(dir.path(), builder.build()).unwrap();
+
+        assert!(!Bm25Index::has_current_schema(dir.path()).unwrap());
+        assert!(Bm25Index::open_readonly(dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_sensitive_body_never_reaches_index_dir() {
+        use crate::core::skill::{BlockType, SkillBlock, SkillSection, SkillSpec};
+        use crate::core::spec_lens::compile_markdown;
+        use crate::security::encryption::{ENVELOPE_PREFIX, SkillKey, seal_spec};
+
+        let mut spec = SkillSpec::new("internal-deploy", "Internal Deploy");
+        spec.metadata.description = "Deploy to the internal cluster".to_string();
+        spec.metadata.sensitive = true;
+        spec.sections = vec![SkillSection {
+            id: "steps".to_string(),
+            title: "Steps".to_string(),
+            blocks: vec![SkillBlock {
+                id: "steps-0".to_string(),
+                block_type: BlockType::Text,
+                content: "ssh deploy@buildhost07.corp.internal".to_string(),
+            }],
+        }];
+        let key = SkillKey::from_encoded(&"11".repeat(32)).unwrap();
+        let body = compile_markdown(&seal_spec(&spec, &key).unwrap());
+        let ciphertext = body
+            .split(ENVELOPE_PREFIX)
+            .nth(1)
+            .and_then(|rest| rest.split_whitespace().next())
+            .unwrap()
+            .to_string();
+
+        let mut record = make_test_skill(
+            "internal-deploy",
+            "Internal Deploy",
+            "Deploy to the internal cluster",
+            &body,
+        );
+        record.metadata_json = r#"{"tags": ["deploy"], "sensitive": true}"#.to_string();
+
+        let dir = tempfile::tempdir().unwrap();
+        let index = Bm25Index::open(dir.path()).unwrap();
+        index.index_skill(&record).unwrap();
+        index.commit().unwrap();
+
+        assert_eq!(index.search("internal cluster", 10).unwrap().len(), 1);
+        assert!(index.search("buildhost07", 10).unwrap().is_empty());
+
+        let needles = [
+            "buildhost07",
+            ENVELOPE_PREFIX,
+            &ciphertext[..24],
+            &ciphertext[ciphertext.len() - 24..],
+        ];
+        for entry in walkdir::WalkDir::new(dir.path()) {
+            let entry = entry.unwrap();
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let bytes = std::fs::read(entry.path()).unwrap();
+            for needle in needles {
+                assert!(
+                    !bytes
+                        .windows(needle.len())
+                        .any(|window| window == needle.as_bytes()),
+                    "{needle} found in {}",
+                    entry.path().display()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_to_search_snippet_drops_invalid_ranges() {
+        let fragment = "🦀 crab";
+        let snippet = to_search_snippet("body", fragment, &[1..3, 5..9]).unwrap();
+        assert_eq!(snippet.highlights, vec![(5, 9)]);
+        assert_eq!(&fragment[5..9], "crab");
+        assert!(to_search_snippet("body", fragment, &[1..2]).is_none());
+        assert!(to_search_snippet("body", "", &[]).is_none());
+    }
+
+    #[test]
+    fn test_compact_drops_deleted_docs() {
+        let dir = tempfile::tempdir().unwrap();
+        let index = Bm25Index::open(dir.path()).unwrap();
+        let skills: Vec<_> = (0..20)
+            .map(|i| make_test_skill(&format!("skill-{i}"), "Skill", "A skill", "Body text."))
+            .collect();
+        index.index_skills(&skills).unwrap();
+        for skill in skills.iter().step_by(2) {
+            index.delete_skill(&skill.id).unwrap();
+        }
+        index.commit().unwrap();
+
+        let stats = index.stats();
+        assert_eq!(stats.live_docs, 10);
+        assert_eq!(stats.deleted_docs, 10);
+        assert!((stats.deleted_ratio() - 0.5).abs() < f64::EPSILON);
+
+        let compaction = index.compact().unwrap();
+        assert_eq!(compaction.before, stats);
+        assert_eq!(compaction.after.live_docs, 10);
+        assert_eq!(compaction.after.deleted_docs, 0);
+        assert_eq!(compaction.after.segments, 1);
+        assert_eq!(index.search("skill", 20).unwrap().len(), 10);
+
+        // A compact index is left as is.
+        assert_eq!(index.compact().unwrap().after, compaction.after);
+    }
+
+    #[test]
+    fn test_writer_is_held_until_commit() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = Bm25Index::open(dir.path()).unwrap();
+        let second = Bm25Index::open(dir.path()).unwrap();
+
+        first
+            .index_skill(&make_test_skill("one", "One", "First", "Body text."))
+            .unwrap();
+        let holder = GlobalLock::holder_at(&dir.path().join(WRITER_LOCK_FILE)).unwrap();
+        assert_eq!(holder.map(|h| h.pid), Some(std::process::id()));
+        // Readers never wait on the writer
+        assert!(second.search("body", 10).unwrap().is_empty());
+
+        first.commit().unwrap();
+        assert!(
+            GlobalLock::holder_at(&dir.path().join(WRITER_LOCK_FILE))
+                .unwrap()
+                .is_none()
+        );
+        second
+            .index_skill(&make_test_skill("two", "Two", "Second", "Body text."))
+            .unwrap();
+        second.commit().unwrap();
+        assert_eq!(second.num_docs(), 2);
+    }
+
     #[test]
     fn test_parse_metadata() {
         let json = r#"{"tags": ["git", "workflow"], "aliases": ["commit", "version-control"]}"#;
         let (tags, aliases) = parse_metadata(json);
-        assert_eq!(tags, "git workflow");
+        assert_eq!(tags, ["git", "workflow"]);
         assert_eq!(aliases, "commit version-control");
     }
 
@@ -1,17 +1,32 @@
 //! Command safety gate backed by DCG (Destructive Command Guard).
-
+//!
+//! A `[safety.profiles.<name>]` selected with `--safety-profile` or
+//! `MS_SAFETY_PROFILE` is checked before dcg: deny patterns and path scopes
+//! block outright, allow patterns skip dcg. Every decision is appended to the
+//! `safety-audit.jsonl` log in the ms root, read back by `ms security audit`.
+
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
+use serde::{Deserialize, Serialize};
 use tracing::{info, warn};
 
 use crate::app::AppContext;
-use crate::config::Config;
+use crate::config::{Config, SafetyConfig, SafetyProfile};
 use crate::core::safety::{DcgDecision, DcgGuard, SafetyTier};
 use crate::error::{MsError, Result};
+use crate::security::path_policy::{is_under_root, normalize_path};
 use crate::storage::Database;
 
-#[derive(Debug, Clone, serde::Serialize)]
+/// File name of the audit log in the ms root.
+pub const AUDIT_LOG_FILE: &str = "safety-audit.jsonl";
+
+/// Environment variable selecting a safety profile.
+pub const PROFILE_ENV: &str = "MS_SAFETY_PROFILE";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommandSafetyEvent {
     pub session_id: Option<String>,
     pub command: String,
@@ -19,6 +34,98 @@ pub struct CommandSafetyEvent {
     pub dcg_pack: Option<String>,
     pub decision: DcgDecision,
     pub created_at: String,
+    /// What happened to the command.
+    #[serde(default)]
+    pub outcome: SafetyOutcome,
+    /// Profile pattern or dcg rule that decided, e.g. `ci:deny:git push*`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matched_rule: Option<String>,
+    /// Safety profile in effect.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    /// ms subcommand that ran the command, e.g. `prune purge`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subcommand: Option<String>,
+}
+
+/// Outcome of one gate evaluation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SafetyOutcome {
+    #[default]
+    Allowed,
+    /// Allowed because `MS_APPROVE_COMMAND` matched verbatim.
+    Approved,
+    Blocked,
+    /// Blocked until approved verbatim.
+    ApprovalRequired,
+}
+
+impl SafetyOutcome {
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Allowed => "allowed",
+            Self::Approved => "approved",
+            Self::Blocked => "blocked",
+            Self::ApprovalRequired => "approval_required",
+        }
+    }
+
+    /// The outcome recorded for a decision without more context.
+    #[must_use]
+    pub const fn of(decision: &DcgDecision) -> Self {
+        match (decision.allowed, decision.approved) {
+            (true, true) => Self::Approved,
+            (true, false) => Self::Allowed,
+            (false, _) => Self::Blocked,
+        }
+    }
+}
+
+impl std::str::FromStr for SafetyOutcome {
+    type Err = MsError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().replace('-', "_").as_str() {
+            "allowed" | "allow" => Ok(Self::Allowed),
+            "approved" => Ok(Self::Approved),
+            "blocked" | "block" => Ok(Self::Blocked),
+            "approval_required" => Ok(Self::ApprovalRequired),
+            _ => Err(MsError::ValidationFailed(format!(
+                "unknown decision {s} (expected allowed|approved|blocked|approval_required)"
+            ))),
+        }
+    }
+}
+
+/// The ms subcommand and `--safety-profile` of this process.
+#[derive(Debug, Clone, Default)]
+pub struct Invocation {
+    pub subcommand: Option<String>,
+    pub safety_profile: Option<String>,
+}
+
+static INVOCATION: OnceLock<Invocation> = OnceLock::new();
+
+/// Record this process's invocation; only the first call has an effect.
+pub fn set_invocation(invocation: Invocation) {
+    let _ = INVOCATION.set(invocation);
+}
+
+fn invocation() -> Invocation {
+    INVOCATION.get().cloned().unwrap_or_default()
+}
+
+/// Name of the selected safety profile: `--safety-profile`, else
+/// `MS_SAFETY_PROFILE`.
+#[must_use]
+pub fn selected_profile() -> Option<String> {
+    invocation()
+        .safety_profile
+        .or_else(|| std::env::var(PROFILE_ENV).ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
 }
 
 /// Status of the safety gate and DCG availability.
@@ -30,6 +137,8 @@ pub struct SafetyStatus {
     pub dcg_bin: PathBuf,
     /// Loaded packs.
     pub packs: Vec<String>,
+    /// Selected safety profile.
+    pub profile: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -38,23 +147,20 @@ pub struct SafetyGate {
     dcg_version: Option<String>,
     require_verbatim_approval: bool,
     db: Option<Arc<Database>>,
+    /// Selected profile, or why it cannot be used (enforce then fails closed).
+    profile: Option<std::result::Result<ProfileRules, String>>,
+    audit_log: Option<PathBuf>,
+    subcommand: Option<String>,
 }
 
 impl SafetyGate {
     #[must_use]
     pub fn from_context(ctx: &AppContext) -> Self {
-        let guard = DcgGuard::new(
-            ctx.config.safety.dcg_bin.clone(),
-            ctx.config.safety.dcg_packs.clone(),
-            ctx.config.safety.dcg_explain_format.clone(),
-        );
-        let dcg_version = guard.version();
-        Self {
-            guard,
-            dcg_version,
-            require_verbatim_approval: ctx.config.safety.require_verbatim_approval,
-            db: Some(ctx.db.clone()),
-        }
+        Self::build(
+            &ctx.config.safety,
+            Some(ctx.db.clone()),
+            Some(ctx.ms_root.join(AUDIT_LOG_FILE)),
+        )
     }
 
     pub fn from_env() -> Result<Self> {
@@ -71,18 +177,37 @@ impl SafetyGate {
                 None
             }
         };
+        Ok(Self::build(
+            &config.safety,
+            db,
+            Some(ms_root.join(AUDIT_LOG_FILE)),
+        ))
+    }
+
+    fn build(config: &SafetyConfig, db: Option<Arc<Database>>, audit_log: Option<PathBuf>) -> Self {
         let guard = DcgGuard::new(
-            config.safety.dcg_bin.clone(),
-            config.safety.dcg_packs.clone(),
-            config.safety.dcg_explain_format.clone(),
+            config.dcg_bin.clone(),
+            config.dcg_packs.clone(),
+            config.dcg_explain_format.clone(),
         );
         let dcg_version = guard.version();
-        Ok(Self {
+        let profile = selected_profile()
+            .map(|name| Self::validate_profile(config, &name).map_err(|err| err.to_string()));
+        Self {
             guard,
             dcg_version,
-            require_verbatim_approval: config.safety.require_verbatim_approval,
+            require_verbatim_approval: config.require_verbatim_approval,
             db,
-        })
+            profile,
+            audit_log,
+            subcommand: invocation().subcommand,
+        }
+    }
+
+    /// Compile the profile called `name`, failing on unknown names and
+    /// malformed patterns.
+    pub fn validate_profile(config: &SafetyConfig, name: &str) -> Result<ProfileRules> {
+        ProfileRules::compile(name, config.profile(name)?)
     }
 
     /// Get the current status of the safety gate.
@@ -92,10 +217,70 @@ impl SafetyGate {
             dcg_version: self.dcg_version.clone(),
             dcg_bin: self.guard.dcg_bin.clone(),
             packs: self.guard.packs.clone(),
+            profile: self.profile_name(),
+        }
+    }
+
+    fn profile_name(&self) -> Option<String> {
+        match self.profile.as_ref()? {
+            Ok(rules) => Some(rules.name.clone()),
+            Err(_) => selected_profile(),
         }
     }
 
     pub fn enforce(&self, command: &str, session_id: Option<&str>) -> Result<()> {
+        if let Some(profile) = &self.profile {
+            let rules = match profile {
+                Ok(rules) => rules,
+                Err(err) => {
+                    let decision = rule_decision(false, err.clone(), None);
+                    self.log_event(command, &decision, SafetyOutcome::Blocked, session_id)?;
+                    return Err(MsError::DestructiveBlocked(format!(
+                        "command blocked: {err}"
+                    )));
+                }
+            };
+            match rules.evaluate(command) {
+                ProfileVerdict::Denied(pattern) => {
+                    let rule = rules.rule_id("deny", &pattern);
+                    let reason = format!(
+                        "safety profile '{}' denies it (deny pattern `{pattern}`)",
+                        rules.name
+                    );
+                    return self.block(command, &reason, rule, session_id);
+                }
+                ProfileVerdict::OutOfScope(path) => {
+                    let reason = format!(
+                        "{path} is outside the path scopes of safety profile '{}' ({})",
+                        rules.name,
+                        rules.scopes.join(", ")
+                    );
+                    return self.block(command, &reason, rules.rule_id("paths", &path), session_id);
+                }
+                ProfileVerdict::Allowed(pattern) => {
+                    let decision = rule_decision(
+                        true,
+                        format!("allowed by safety profile '{}'", rules.name),
+                        Some(rules.rule_id("allow", &pattern)),
+                    );
+                    return self.log_event(command, &decision, SafetyOutcome::Allowed, session_id);
+                }
+                ProfileVerdict::Unlisted if rules.allowlist_only => {
+                    let reason = format!(
+                        "safety profile '{}' allows only its allow patterns and none matched",
+                        rules.name
+                    );
+                    return self.block(
+                        command,
+                        &reason,
+                        rules.rule_id("allowlist_only", "*"),
+                        session_id,
+                    );
+                }
+                ProfileVerdict::Unlisted => {}
+            }
+        }
+
         let (mut decision, dcg_unavailable) = match self.guard.evaluate_command(command) {
             Ok(decision) => (decision, false),
             Err(err) => {
@@ -110,7 +295,7 @@ impl SafetyGate {
         if !decision.allowed {
             // If DCG is unavailable, provide a specific error explaining the situation
             if dcg_unavailable {
-                self.log_event(command, &decision, session_id)?;
+                self.log_event(command, &decision, SafetyOutcome::Blocked, session_id)?;
                 return Err(MsError::DestructiveBlocked(format!(
                     "command blocked (safety system unavailable): {}. {}",
                     decision.reason,
@@ -126,19 +311,27 @@ impl SafetyGate {
                     decision.approved = true;
                     decision.allowed = true;
                 } else {
-                    self.log_event(command, &decision, session_id)?;
+                    self.log_event(
+                        command,
+                        &decision,
+                        SafetyOutcome::ApprovalRequired,
+                        session_id,
+                    )?;
                     return Err(MsError::ApprovalRequired(approval_hint(command)));
                 }
             } else {
-                self.log_event(command, &decision, session_id)?;
+                self.log_event(command, &decision, SafetyOutcome::Blocked, session_id)?;
                 return Err(MsError::DestructiveBlocked(format!(
-                    "blocked by dcg: {}",
-                    decision.reason
+                    "blocked by dcg: {}{}",
+                    decision.reason,
+                    dcg_rule(&decision)
+                        .map(|rule| format!(" (rule {rule})"))
+                        .unwrap_or_default()
                 )));
             }
         }
 
-        self.log_event(command, &decision, session_id)?;
+        self.log_event(command, &decision, SafetyOutcome::of(&decision), session_id)?;
 
         if decision.approved {
             info!("command approved by verbatim match: {command}");
@@ -147,15 +340,27 @@ impl SafetyGate {
         Ok(())
     }
 
+    fn block(
+        &self,
+        command: &str,
+        reason: &str,
+        rule: String,
+        session_id: Option<&str>,
+    ) -> Result<()> {
+        let decision = rule_decision(false, reason.to_string(), Some(rule));
+        self.log_event(command, &decision, SafetyOutcome::Blocked, session_id)?;
+        Err(MsError::DestructiveBlocked(format!(
+            "blocked: {reason}; edit the profile or pick another with --safety-profile"
+        )))
+    }
+
     fn log_event(
         &self,
         command: &str,
         decision: &DcgDecision,
+        outcome: SafetyOutcome,
         session_id: Option<&str>,
     ) -> Result<()> {
-        let Some(db) = self.db.as_ref() else {
-            return Ok(());
-        };
         let event = CommandSafetyEvent {
             session_id: session_id.map(str::to_string),
             command: command.to_string(),
@@ -163,11 +368,216 @@ impl SafetyGate {
             dcg_pack: decision.pack.clone(),
             decision: decision.clone(),
             created_at: chrono::Utc::now().to_rfc3339(),
+            outcome,
+            matched_rule: dcg_rule(decision),
+            profile: self.profile_name(),
+            subcommand: self.subcommand.clone(),
         };
-        db.insert_command_safety_event(&event)
+        if let Some(path) = &self.audit_log {
+            append_audit_event(path, &event)?;
+        }
+        if let Some(db) = self.db.as_ref() {
+            db.insert_command_safety_event(&event)?;
+        }
+        Ok(())
+    }
+}
+
+/// A decision made by a profile rule rather than dcg.
+fn rule_decision(allowed: bool, reason: String, rule_id: Option<String>) -> DcgDecision {
+    DcgDecision {
+        allowed,
+        tier: if allowed {
+            SafetyTier::Safe
+        } else {
+            SafetyTier::Critical
+        },
+        reason,
+        remediation: None,
+        rule_id,
+        pack: None,
+        approved: false,
     }
 }
 
+/// `pack:rule` for dcg decisions, the rule id alone for profile decisions.
+fn dcg_rule(decision: &DcgDecision) -> Option<String> {
+    let rule = decision.rule_id.as_deref()?;
+    Some(match &decision.pack {
+        Some(pack) => format!("{pack}:{rule}"),
+        None => rule.to_string(),
+    })
+}
+
+// =============================================================================
+// Profiles
+// =============================================================================
+
+/// A profile with its patterns compiled.
+#[derive(Debug, Clone)]
+pub struct ProfileRules {
+    pub name: String,
+    allow: Vec<(String, glob::Pattern)>,
+    deny: Vec<(String, glob::Pattern)>,
+    scopes: Vec<String>,
+    scope_roots: Vec<PathBuf>,
+    allowlist_only: bool,
+}
+
+/// What a profile says about one command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProfileVerdict {
+    /// Matched this deny pattern.
+    Denied(String),
+    /// Names a path outside every scope.
+    OutOfScope(String),
+    /// Matched this allow pattern.
+    Allowed(String),
+    /// Matched nothing; dcg decides unless the profile is allowlist-only.
+    Unlisted,
+}
+
+impl ProfileRules {
+    pub fn compile(name: &str, profile: &SafetyProfile) -> Result<Self> {
+        let compile = |kind: &str, patterns: &[String]| {
+            patterns
+                .iter()
+                .map(|pattern| {
+                    glob::Pattern::new(pattern.trim())
+                        .map(|compiled| (pattern.trim().to_string(), compiled))
+                        .map_err(|err| {
+                            MsError::Config(format!(
+                                "safety profile '{name}': invalid {kind} pattern `{pattern}`: {err}"
+                            ))
+                        })
+                })
+                .collect::<Result<Vec<_>>>()
+        };
+        let cwd = std::env::current_dir().ok();
+        Ok(Self {
+            name: name.to_string(),
+            allow: compile("allow", &profile.allow)?,
+            deny: compile("deny", &profile.deny)?,
+            scopes: profile.paths.clone(),
+            scope_roots: profile
+                .paths
+                .iter()
+                .map(|scope| absolute(scope, cwd.as_deref()))
+                .collect(),
+            allowlist_only: profile.allowlist_only,
+        })
+    }
+
+    /// Deny patterns first, then path scopes, then allow patterns.
+    #[must_use]
+    pub fn evaluate(&self, command: &str) -> ProfileVerdict {
+        let command = command.trim();
+        if let Some((pattern, _)) = self.deny.iter().find(|(_, glob)| glob.matches(command)) {
+            return ProfileVerdict::Denied(pattern.clone());
+        }
+        if !self.scope_roots.is_empty() {
+            let cwd = std::env::current_dir().ok();
+            if let Some(path) = path_arguments(command).find(|path| {
+                let path = absolute(path, cwd.as_deref());
+                !self
+                    .scope_roots
+                    .iter()
+                    .any(|root| is_under_root(&path, root))
+            }) {
+                return ProfileVerdict::OutOfScope(path.to_string());
+            }
+        }
+        if let Some((pattern, _)) = self.allow.iter().find(|(_, glob)| glob.matches(command)) {
+            return ProfileVerdict::Allowed(pattern.clone());
+        }
+        ProfileVerdict::Unlisted
+    }
+
+    fn rule_id(&self, kind: &str, pattern: &str) -> String {
+        format!("{}:{kind}:{pattern}", self.name)
+    }
+}
+
+/// Arguments of `command` that look like filesystem paths, including the
+/// value of `--flag=/path`. URLs are not paths.
+fn path_arguments(command: &str) -> impl Iterator<Item = &str> {
+    command
+        .split_whitespace()
+        .map(|word| word.trim_matches(|ch| ch == '\'' || ch == '"'))
+        .map(|word| {
+            if word.starts_with('-') {
+                word.split_once('=').map_or("", |(_, value)| value)
+            } else {
+                word
+            }
+        })
+        .filter(|word| {
+            !word.contains("://")
+                && (word.starts_with('/')
+                    || word.starts_with('~')
+                    || word.starts_with("./")
+                    || word.starts_with("../")
+                    || matches!(*word, "." | ".."))
+        })
+}
+
+/// `path` with `~` expanded, made absolute against `cwd`, and normalized.
+fn absolute(path: &str, cwd: Option<&Path>) -> PathBuf {
+    let expanded = match path.strip_prefix('~') {
+        Some(rest) => dirs::home_dir().map_or_else(
+            || PathBuf::from(path),
+            |home| home.join(rest.trim_start_matches('/')),
+        ),
+        None => PathBuf::from(path),
+    };
+    let joined = match cwd {
+        Some(cwd) if expanded.is_relative() => cwd.join(expanded),
+        _ => expanded,
+    };
+    normalize_path(&joined)
+}
+
+// =============================================================================
+// Audit log
+// =============================================================================
+
+fn append_audit_event(path: &Path, event: &CommandSafetyEvent) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut line = serde_json::to_string(event)?;
+    line.push('\n');
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+/// Events in the audit log at `path`, oldest first. A missing log is empty;
+/// lines that do not parse are skipped.
+pub fn read_audit_log(path: &Path) -> Result<Vec<CommandSafetyEvent>> {
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+    let mut events = Vec::new();
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str(&line) {
+            Ok(event) => events.push(event),
+            Err(err) => warn!(
+                "{}:{}: skipping audit entry: {err}",
+                path.display(),
+                index + 1
+            ),
+        }
+    }
+    Ok(events)
+}
+
 fn approval_matches(command: &str) -> bool {
     let candidates = [
         std::env::var("MS_APPROVE_COMMAND").ok(),
@@ -293,6 +703,7 @@ mod tests {
             dcg_version: Some("1.0.0".to_string()),
             dcg_bin: PathBuf::from("/usr/bin/dcg"),
             packs: vec!["default".to_string()],
+            profile: Some("ci".to_string()),
         };
 
         assert_eq!(status.dcg_version, Some("1.0.0".to_string()));
@@ -306,6 +717,7 @@ mod tests {
             dcg_version: None,
             dcg_bin: PathBuf::from("/nonexistent/dcg"),
             packs: vec![],
+            profile: None,
         };
 
         assert!(status.dcg_version.is_none());
@@ -335,6 +747,10 @@ mod tests {
                 approved: false,
             },
             created_at: "2024-01-01T00:00:00Z".to_string(),
+            outcome: SafetyOutcome::Blocked,
+            matched_rule: Some("default:R001".to_string()),
+            profile: None,
+            subcommand: Some("prune purge".to_string()),
         };
 
         let json = serde_json::to_string(&event).unwrap();
@@ -342,4 +758,130 @@ mod tests {
         assert!(json.contains("destructive command"));
         assert!(json.contains("test-session"));
     }
+
+    // =========================================================================
+    // Profile tests
+    // =========================================================================
+
+    fn profile(allow: &[&str], deny: &[&str], paths: &[&str]) -> SafetyProfile {
+        SafetyProfile {
+            allow: allow.iter().map(ToString::to_string).collect(),
+            deny: deny.iter().map(ToString::to_string).collect(),
+            paths: paths.iter().map(ToString::to_string).collect(),
+            allowlist_only: false,
+        }
+    }
+
+    #[test]
+    fn profile_checks_deny_then_paths_then_allow() {
+        let rules = ProfileRules::compile(
+            "ci",
+            &profile(
+                &["git *", "rm -rf /work/*"],
+                &["git push*"],
+                &["/work", "/tmp"],
+            ),
+        )
+        .unwrap();
+
+        assert_eq!(
+            rules.evaluate("git push --force origin main"),
+            ProfileVerdict::Denied("git push*".to_string())
+        );
+        assert_eq!(
+            rules.evaluate("git status"),
+            ProfileVerdict::Allowed("git *".to_string())
+        );
+        assert_eq!(
+            rules.evaluate("rm -rf /work/../etc"),
+            ProfileVerdict::OutOfScope("/work/../etc".to_string())
+        );
+        assert_eq!(
+            rules.evaluate("cp --target=/etc/cron.d job /tmp/job"),
+            ProfileVerdict::OutOfScope("/etc/cron.d".to_string())
+        );
+        assert_eq!(
+            rules.evaluate("curl https://example.com/install.sh -o /tmp/i.sh"),
+            ProfileVerdict::Unlisted
+        );
+    }
+
+    #[test]
+    fn malformed_profile_patterns_are_rejected() {
+        let err = ProfileRules::compile("dev", &profile(&["ls [a-"], &[], &[]))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("invalid allow pattern `ls [a-`"), "{err}");
+    }
+
+    #[test]
+    fn gate_reports_matched_pattern_and_audits_every_decision() {
+        let temp = TempDir::new().unwrap();
+        let audit_log = temp.path().join(AUDIT_LOG_FILE);
+        let mut rules =
+            ProfileRules::compile("ci", &profile(&["cargo test*"], &["git push*"], &[])).unwrap();
+        rules.allowlist_only = true;
+        let gate = SafetyGate {
+            guard: DcgGuard::new(
+                PathBuf::from("/nonexistent/dcg"),
+                vec![],
+                "json".to_string(),
+            ),
+            dcg_version: None,
+            require_verbatim_approval: true,
+            db: None,
+            profile: Some(Ok(rules)),
+            audit_log: Some(audit_log.clone()),
+            subcommand: Some("test".to_string()),
+        };
+
+        let err = gate.enforce("git push origin main", None).unwrap_err();
+        assert!(
+            err.to_string().contains("deny pattern `git push*`"),
+            "{err}"
+        );
+        gate.enforce("cargo test --workspace", Some("s1")).unwrap();
+        let err = gate.enforce("make", None).unwrap_err();
+        assert!(err.to_string().contains("none matched"), "{err}");
+
+        let events = read_audit_log(&audit_log).unwrap();
+        let summary: Vec<(SafetyOutcome, Option<&str>)> = events
+            .iter()
+            .map(|event| (event.outcome, event.matched_rule.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (SafetyOutcome::Blocked, Some("ci:deny:git push*")),
+                (SafetyOutcome::Allowed, Some("ci:allow:cargo test*")),
+                (SafetyOutcome::Blocked, Some("ci:allowlist_only:*")),
+            ]
+        );
+        assert!(events.iter().all(|event| {
+            event.profile.as_deref() == Some("ci") && event.subcommand.as_deref() == Some("test")
+        }));
+        assert_eq!(events[1].session_id.as_deref(), Some("s1"));
+    }
+
+    #[test]
+    fn audit_log_skips_malformed_lines() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join(AUDIT_LOG_FILE);
+        assert!(read_audit_log(&path).unwrap().is_empty());
+        std::fs::write(&path, "not json\n\n").unwrap();
+        assert!(read_audit_log(&path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn outcomes_parse_from_filters() {
+        assert_eq!(
+            "blocked".parse::<SafetyOutcome>().unwrap(),
+            SafetyOutcome::Blocked
+        );
+        assert_eq!(
+            "approval-required".parse::<SafetyOutcome>().unwrap(),
+            SafetyOutcome::ApprovalRequired
+        );
+        assert!("maybe".parse::<SafetyOutcome>().is_err());
+    }
 }
@@ -0,0 +1,299 @@
+//! At-rest encryption for skills marked `sensitive: true`.
+//!
+//! Sealing replaces a spec's sections with a single block holding the
+//! ChaCha20-Poly1305 ciphertext of the original sections. The transaction
+//! log, the `SQLite` body column and the Git archive only ever see the sealed
+//! spec; metadata (id, name, description, tags) stays readable so sensitive
+//! skills can still be listed and found by title.
+//!
+//! The 32-byte key comes from `MS_SKILL_KEY` (hex or base64), falling back to
+//! the OS keychain entry `ms-skill-key`. Nonces are derived from the
+//! plaintext with a keyed HMAC, so re-sealing unchanged content yields the
+//! same ciphertext and does not churn the archive.
+
+use std::fmt;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use ring::aead::{Aad, CHACHA20_POLY1305, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use ring::hmac;
+
+use crate::core::skill::{BlockType, SkillBlock, SkillSection, SkillSpec};
+use crate::core::spec_lens::{compile_markdown, parse_markdown, positional_block_id};
+use crate::error::{MsError, Result};
+
+/// Prefix of the sealed-content envelope.
+pub const ENVELOPE_PREFIX: &str = "ms-enc:v1:";
+/// Environment variable holding the skill key.
+pub const KEY_ENV: &str = "MS_SKILL_KEY";
+
+const KEYCHAIN_SERVICE: &str = "ms-skill-key";
+const KEYCHAIN_ACCOUNT: &str = "default";
+const SEALED_SECTION_ID: &str = "encrypted";
+
+/// Where the skill key was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeySource {
+    Env,
+    Keychain,
+}
+
+impl fmt::Display for KeySource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Env => write!(f, "{KEY_ENV}"),
+            Self::Keychain => write!(f, "keychain"),
+        }
+    }
+}
+
+/// A 32-byte ChaCha20-Poly1305 key.
+pub struct SkillKey([u8; 32]);
+
+impl fmt::Debug for SkillKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SkillKey(..)")
+    }
+}
+
+impl SkillKey {
+    /// Decode a key written as 64 hex characters or base64.
+    pub fn from_encoded(raw: &str) -> Result<Self> {
+        let raw = raw.trim();
+        let bytes = if raw.len() == 64 {
+            hex::decode(raw).ok()
+        } else {
+            BASE64.decode(raw).ok()
+        }
+        .ok_or_else(|| MsError::Config("skill key must be hex or base64".to_string()))?;
+        let key: [u8; 32] = bytes.try_into().map_err(|bytes: Vec<u8>| {
+            MsError::Config(format!("skill key must be 32 bytes, got {}", bytes.len()))
+        })?;
+        Ok(Self(key))
+    }
+
+    /// The key from `MS_SKILL_KEY`, else the OS keychain. `Ok(None)` when
+    /// neither has one; a malformed key is an error.
+    pub fn locate() -> Result<Option<(Self, KeySource)>> {
+        if let Ok(raw) = std::env::var(KEY_ENV)
+            && !raw.trim().is_empty()
+        {
+            return Self::from_encoded(&raw).map(|key| Some((key, KeySource::Env)));
+        }
+        let stored = keyring::Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT)
+            .ok()
+            .and_then(|entry| entry.get_password().ok());
+        match stored {
+            Some(raw) => Self::from_encoded(&raw).map(|key| Some((key, KeySource::Keychain))),
+            None => Ok(None),
+        }
+    }
+
+    /// Like [`SkillKey::locate`], but a missing key is an error naming the
+    /// sensitive skill that needed it.
+    pub fn require(skill_id: &str) -> Result<Self> {
+        Self::locate()?.map(|(key, _)| key).ok_or_else(|| {
+            MsError::MissingConfig(format!(
+                "{KEY_ENV} (skill '{skill_id}' is sensitive; set {KEY_ENV} or store the key \
+                 in the OS keychain as '{KEYCHAIN_SERVICE}')"
+            ))
+        })
+    }
+
+    fn aead(&self) -> Result<LessSafeKey> {
+        UnboundKey::new(&CHACHA20_POLY1305, &self.0)
+            .map(LessSafeKey::new)
+            .map_err(|_| MsError::Config("invalid skill key".to_string()))
+    }
+
+    /// Synthetic nonce: HMAC of the plaintext under a key derived from ours.
+    fn nonce_for(&self, skill_id: &str, plaintext: &[u8]) -> [u8; NONCE_LEN] {
+        let root = hmac::Key::new(hmac::HMAC_SHA256, &self.0);
+        let derived = hmac::sign(&root, b"ms skill nonce");
+        let nonce_key = hmac::Key::new(hmac::HMAC_SHA256, derived.as_ref());
+        let mut ctx = hmac::Context::with_key(&nonce_key);
+        ctx.update(skill_id.as_bytes());
+        ctx.update(&[0]);
+        ctx.update(plaintext);
+        let tag = ctx.sign();
+        let mut nonce = [0u8; NONCE_LEN];
+        nonce.copy_from_slice(&tag.as_ref()[..NONCE_LEN]);
+        nonce
+    }
+}
+
+/// Whether the spec is marked sensitive.
+#[must_use]
+pub fn is_sensitive(spec: &SkillSpec) -> bool {
+    spec.metadata.sensitive
+}
+
+/// Whether the spec's sections are an encrypted envelope.
+#[must_use]
+pub fn is_sealed(spec: &SkillSpec) -> bool {
+    envelope(spec).is_some()
+}
+
+/// Whether a stored markdown body contains an encrypted envelope.
+#[must_use]
+pub fn is_sealed_body(body: &str) -> bool {
+    body.contains(ENVELOPE_PREFIX)
+}
+
+/// Replace the sections of `spec` with their encrypted envelope.
+pub fn seal_spec(spec: &SkillSpec, key: &SkillKey) -> Result<SkillSpec> {
+    if is_sealed(spec) {
+        return Ok(spec.clone());
+    }
+    let skill_id = spec.metadata.id.as_str();
+    let mut in_out = serde_json::to_vec(&spec.sections)?;
+    let nonce = key.nonce_for(skill_id, &in_out);
+    key.aead()?
+        .seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(skill_id.as_bytes()),
+            &mut in_out,
+        )
+        .map_err(|_| MsError::Config(format!("encrypt skill {skill_id}")))?;
+
+    let mut payload = nonce.to_vec();
+    payload.extend_from_slice(&in_out);
+    let mut sealed = spec.clone();
+    sealed.sections = vec![SkillSection {
+        id: SEALED_SECTION_ID.to_string(),
+        title: "Encrypted".to_string(),
+        blocks: vec![SkillBlock {
+            id: positional_block_id(SEALED_SECTION_ID, 0),
+            block_type: BlockType::Text,
+            content: format!("{ENVELOPE_PREFIX}{}", BASE64.encode(payload)),
+        }],
+    }];
+    Ok(sealed)
+}
+
+/// Restore the sections of a sealed spec. Unsealed specs are returned as is.
+pub fn unseal_spec(spec: &SkillSpec, key: &SkillKey) -> Result<SkillSpec> {
+    let Some(encoded) = envelope(spec) else {
+        return Ok(spec.clone());
+    };
+    let skill_id = spec.metadata.id.as_str();
+    let corrupt = || {
+        MsError::Config(format!(
+            "cannot decrypt skill {skill_id}: wrong key or corrupted content"
+        ))
+    };
+    let payload = BASE64.decode(encoded).map_err(|_| corrupt())?;
+    if payload.len() < NONCE_LEN {
+        return Err(corrupt());
+    }
+    let (nonce, ciphertext) = payload.split_at(NONCE_LEN);
+    let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| corrupt())?;
+    let mut in_out = ciphertext.to_vec();
+    let plaintext = key
+        .aead()?
+        .open_in_place(nonce, Aad::from(skill_id.as_bytes()), &mut in_out)
+        .map_err(|_| corrupt())?;
+
+    let mut unsealed = spec.clone();
+    unsealed.sections = serde_json::from_slice(plaintext)?;
+    Ok(unsealed)
+}
+
+/// Seal `spec` if it is sensitive. Sensitive skills are never stored in
+/// plaintext, so a missing key is an error.
+pub fn seal_for_storage(spec: &SkillSpec) -> Result<SkillSpec> {
+    if !is_sensitive(spec) || is_sealed(spec) {
+        return Ok(spec.clone());
+    }
+    let key = SkillKey::require(&spec.metadata.id)?;
+    seal_spec(spec, &key)
+}
+
+/// Plaintext markdown for a stored skill body, decrypting it if sealed.
+pub fn reveal_body(skill_id: &str, body: &str) -> Result<String> {
+    if !is_sealed_body(body) {
+        return Ok(body.to_string());
+    }
+    let key = SkillKey::require(skill_id)?;
+    let spec = parse_markdown(body)?;
+    Ok(compile_markdown(&unseal_spec(&spec, &key)?))
+}
+
+fn envelope(spec: &SkillSpec) -> Option<&str> {
+    spec.sections
+        .iter()
+        .flat_map(|section| &section.blocks)
+        .find_map(|block| block.content.trim().strip_prefix(ENVELOPE_PREFIX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(byte: u8) -> SkillKey {
+        SkillKey([byte; 32])
+    }
+
+    fn sensitive_spec() -> SkillSpec {
+        let mut spec = SkillSpec::new("internal-deploy", "Internal Deploy");
+        spec.metadata.description = "Deploy to the internal cluster".to_string();
+        spec.metadata.sensitive = true;
+        spec.sections = vec![SkillSection {
+            id: "steps".to_string(),
+            title: "Steps".to_string(),
+            blocks: vec![SkillBlock {
+                id: "steps-0".to_string(),
+                block_type: BlockType::Text,
+                content: "ssh deploy@build-07.corp.internal".to_string(),
+            }],
+        }];
+        spec
+    }
+
+    #[test]
+    fn seal_round_trips_through_markdown() {
+        let spec = sensitive_spec();
+        let sealed = seal_spec(&spec, &key(7)).unwrap();
+        let markdown = compile_markdown(&sealed);
+        assert!(!markdown.contains("corp.internal"));
+        assert!(markdown.contains("Deploy to the internal cluster"));
+        assert!(is_sealed_body(&markdown));
+
+        let parsed = parse_markdown(&markdown).unwrap();
+        assert!(parsed.metadata.sensitive);
+        let unsealed = unseal_spec(&parsed, &key(7)).unwrap();
+        assert_eq!(
+            unsealed.sections[0].blocks[0].content,
+            "ssh deploy@build-07.corp.internal"
+        );
+    }
+
+    #[test]
+    fn sealing_is_deterministic_per_key() {
+        let spec = sensitive_spec();
+        let a = seal_spec(&spec, &key(1)).unwrap();
+        let b = seal_spec(&spec, &key(1)).unwrap();
+        let c = seal_spec(&spec, &key(2)).unwrap();
+        assert_eq!(envelope(&a), envelope(&b));
+        assert_ne!(envelope(&a), envelope(&c));
+    }
+
+    #[test]
+    fn wrong_key_or_other_skill_fails_to_open() {
+        let sealed = seal_spec(&sensitive_spec(), &key(1)).unwrap();
+        assert!(unseal_spec(&sealed, &key(2)).is_err());
+
+        let mut moved = sealed;
+        moved.metadata.id = "other-skill".to_string();
+        assert!(unseal_spec(&moved, &key(1)).is_err());
+    }
+
+    #[test]
+    fn keys_decode_from_hex_or_base64() {
+        let hex_key = "ab".repeat(32);
+        assert!(SkillKey::from_encoded(&hex_key).is_ok());
+        assert!(SkillKey::from_encoded(&BASE64.encode([9u8; 32])).is_ok());
+        assert!(SkillKey::from_encoded("abcd").is_err());
+        assert!(SkillKey::from_encoded("not a key!").is_err());
+    }
+}
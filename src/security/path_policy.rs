@@ -5,6 +5,11 @@
 //! - Symlink escape attacks
 //! - Unexpected path roots
 //!
+//! On Windows, comparisons ignore case and the verbatim prefix (`\\?\`) that
+//! `canonicalize` adds, and canonical paths are returned without it. The
+//! string-level helpers behind that ([`strip_verbatim_prefix`],
+//! [`windows_path_key`]) work on every platform.
+//!
 //! # Example
 //!
 //! ```rust,ignore
@@ -21,9 +26,11 @@
 //! let canonical = canonicalize_with_root(user_path, root).unwrap();
 //! ```
 
+use std::borrow::Cow;
 use std::path::{Component, Path, PathBuf};
 
 use crate::error::{MsError, Result};
+use crate::utils::path::has_drive_prefix;
 
 /// Errors specific to path policy violations.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -178,7 +185,7 @@ pub fn canonicalize_with_root(path: &Path, root: &Path) -> Result<PathBuf> {
     })?;
 
     // Verify the path is under root
-    if !canonical_path.starts_with(&canonical_root) {
+    if !path_starts_with(&canonical_path, &canonical_root) {
         return Err(PathPolicyViolation::EscapesRoot {
             path: canonical_path,
             root: canonical_root,
@@ -186,7 +193,7 @@ pub fn canonicalize_with_root(path: &Path, root: &Path) -> Result<PathBuf> {
         .into());
     }
 
-    Ok(canonical_path)
+    Ok(simplify(canonical_path))
 }
 
 /// Check if a path would escape root via symlinks.
@@ -264,7 +271,7 @@ pub fn deny_symlink_escape(
 
                             // Check if symlink target escapes root
                             if let Ok(canonical_target) = resolved.canonicalize() {
-                                if !canonical_target.starts_with(&canonical_root) {
+                                if !path_starts_with(&canonical_target, &canonical_root) {
                                     return Err(PathPolicyViolation::SymlinkEscape {
                                         symlink: current.clone(),
                                         target: canonical_target,
@@ -312,6 +319,19 @@ pub fn safe_join(root: &Path, relative: &str, check_escape: bool) -> Result<Path
         .into());
     }
 
+    // Reject Windows-style absolute and traversing paths on every platform,
+    // so a path that is harmless here cannot escape once used on Windows
+    if has_windows_root(relative) {
+        return Err(PathPolicyViolation::InvalidComponent {
+            component: relative.to_string(),
+            reason: "must be a relative path".to_string(),
+        }
+        .into());
+    }
+    if relative.split(['/', '\\']).any(|part| part == "..") {
+        return Err(PathPolicyViolation::TraversalAttempt.into());
+    }
+
     // Parse and validate each component
     let rel_path = Path::new(relative);
     for component in rel_path.components() {
@@ -368,6 +388,7 @@ pub fn safe_join(root: &Path, relative: &str, check_escape: bool) -> Result<Path
 /// ```
 #[must_use]
 pub fn normalize_path(path: &Path) -> PathBuf {
+    let path = simplify(path.to_path_buf());
     let mut normalized = PathBuf::new();
 
     for component in path.components() {
@@ -411,7 +432,89 @@ pub fn is_under_root(path: &Path, root: &Path) -> bool {
     let normalized_path = normalize_path(path);
     let normalized_root = normalize_path(root);
 
-    normalized_path.starts_with(&normalized_root)
+    path_starts_with(&normalized_path, &normalized_root)
+}
+
+/// `path` without a Windows verbatim prefix: `\\?\C:\x` becomes `C:\x` and
+/// `\\?\UNC\server\share` becomes `\\server\share`. Other paths are
+/// returned unchanged.
+#[must_use]
+pub fn strip_verbatim_prefix(path: &str) -> Cow<'_, str> {
+    let Some(rest) = path.strip_prefix(r"\\?\") else {
+        return Cow::Borrowed(path);
+    };
+    if let Some(unc) = rest
+        .strip_prefix("UNC\\")
+        .or_else(|| rest.strip_prefix("unc\\"))
+    {
+        return Cow::Owned(format!(r"\\{unc}"));
+    }
+    Cow::Borrowed(rest)
+}
+
+/// Key for comparing Windows paths: verbatim prefix removed, `/` read as
+/// `\`, repeated and trailing separators dropped, case folded.
+#[must_use]
+pub fn windows_path_key(path: &str) -> String {
+    let path = strip_verbatim_prefix(path).replace('/', "\\");
+    let unc = path.starts_with(r"\\");
+    let mut key = String::with_capacity(path.len());
+    if unc {
+        key.push('\\');
+    }
+    let mut previous_separator = false;
+    for c in path.chars() {
+        if c == '\\' {
+            if !previous_separator {
+                key.push(c);
+            }
+            previous_separator = true;
+        } else {
+            key.extend(c.to_lowercase());
+            previous_separator = false;
+        }
+    }
+    // Keep the separator of a drive root (`c:\`)
+    if key.ends_with('\\') && key.len() > 1 && !(key.len() == 3 && has_drive_prefix(&key)) {
+        key.pop();
+    }
+    key
+}
+
+/// Whether Windows path `path` is `root` or under it, compared by
+/// [`windows_path_key`] component by component.
+#[must_use]
+pub fn windows_starts_with(path: &str, root: &str) -> bool {
+    let path = windows_path_key(path);
+    let root = windows_path_key(root);
+    path == root
+        || (path.starts_with(&root)
+            && (root.ends_with('\\') || path[root.len()..].starts_with('\\')))
+}
+
+/// Component-wise prefix check, following Windows rules on Windows.
+fn path_starts_with(path: &Path, root: &Path) -> bool {
+    if cfg!(windows) {
+        windows_starts_with(&path.to_string_lossy(), &root.to_string_lossy())
+    } else {
+        path.starts_with(root)
+    }
+}
+
+/// Drop the verbatim prefix `canonicalize` adds on Windows.
+fn simplify(path: PathBuf) -> PathBuf {
+    if cfg!(windows) {
+        let raw = path.to_string_lossy();
+        if raw.starts_with(r"\\?\") {
+            return PathBuf::from(strip_verbatim_prefix(&raw).into_owned());
+        }
+    }
+    path
+}
+
+/// Drive (`C:`), rooted (`\x`), UNC or verbatim path in Windows syntax.
+fn has_windows_root(path: &str) -> bool {
+    has_drive_prefix(path) || path.starts_with('\\')
 }
 
 #[cfg(test)]
@@ -623,4 +726,86 @@ mod tests {
 
         assert!(deny_symlink_escape(&inside, &root).is_ok());
     }
+
+    #[test]
+    fn test_strip_verbatim_prefix() {
+        assert_eq!(strip_verbatim_prefix(r"\\?\C:\skills\a"), r"C:\skills\a");
+        assert_eq!(
+            strip_verbatim_prefix(r"\\?\UNC\server\share\a"),
+            r"\\server\share\a"
+        );
+        assert_eq!(strip_verbatim_prefix(r"C:\skills"), r"C:\skills");
+        assert_eq!(strip_verbatim_prefix("/data/skills"), "/data/skills");
+    }
+
+    #[test]
+    fn test_windows_path_key() {
+        assert_eq!(windows_path_key(r"\\?\C:\Skills\A\"), r"c:\skills\a");
+        assert_eq!(windows_path_key("C:/Skills//a/"), r"c:\skills\a");
+        assert_eq!(windows_path_key(r"C:\"), r"c:\");
+        assert_eq!(windows_path_key(r"\\?\UNC\Server\Share"), r"\\server\share");
+    }
+
+    #[test]
+    fn test_windows_starts_with() {
+        let root = r"C:\Users\Me\Skills";
+        assert!(windows_starts_with(r"\\?\c:\users\me\skills\rust", root));
+        assert!(windows_starts_with(
+            "C:/Users/me/skills/rust/SKILL.md",
+            root
+        ));
+        assert!(windows_starts_with(r"c:\users\me\skills", root));
+        assert!(windows_starts_with(r"C:\anything", r"c:\"));
+
+        assert!(!windows_starts_with(r"C:\Users\Me\Skills-old\x", root));
+        assert!(!windows_starts_with(r"D:\Users\Me\Skills\x", root));
+    }
+
+    #[test]
+    fn test_safe_join_rejects_windows_escapes() {
+        let root = Path::new("/data/skills");
+
+        for relative in [
+            r"C:\Windows\system32",
+            "c:relative",
+            r"\\?\C:\x",
+            r"\\server\share",
+            r"\rooted",
+            r"..\escape",
+            r"foo\..\..\escape",
+        ] {
+            assert!(safe_join(root, relative, false).is_err(), "{relative}");
+        }
+        assert!(safe_join(root, r"a\b", false).is_ok());
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_windows_canonical_paths_drop_verbatim_prefix() {
+        let temp = TempDir::new().unwrap();
+        let file = temp.path().join("sub").join("file.txt");
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(&file, "test").unwrap();
+
+        let canonical = canonicalize_with_root(&file, temp.path()).unwrap();
+        assert!(!canonical.to_string_lossy().starts_with(r"\\?\"));
+
+        // Root given in another case and with forward slashes
+        let root = temp
+            .path()
+            .to_string_lossy()
+            .to_uppercase()
+            .replace('\\', "/");
+        assert!(canonicalize_with_root(&file, Path::new(&root)).is_ok());
+        assert!(is_under_root(&file, Path::new(&root)));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_windows_normalize_path_strips_verbatim_prefix() {
+        assert_eq!(
+            normalize_path(Path::new(r"\\?\C:\skills\.\a\..\b")),
+            PathBuf::from(r"C:\skills\b")
+        );
+    }
 }
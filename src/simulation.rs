@@ -19,7 +19,6 @@ use crate::core::skill::{BlockType, SkillSpec};
 use crate::core::spec_lens::parse_markdown;
 use crate::error::{MsError, Result};
 use crate::security::SafetyGate;
-use crate::storage::sqlite::SkillRecord;
 
 #[derive(Debug, Clone)]
 pub struct SimulationConfig {
@@ -154,7 +153,7 @@ impl<'a> SimulationEngine<'a> {
         fixtures: Option<&Path>,
         config: SimulationConfig,
     ) -> Result<SimulationReport> {
-        let skill = resolve_skill(self.ctx, skill_ref)?;
+        let skill = self.ctx.resolve_skill(skill_ref)?;
         let spec = parse_markdown(&skill.body).map_err(|err| {
             MsError::ValidationFailed(format!("failed to parse skill body: {err}"))
         })?;
@@ -323,20 +322,6 @@ impl<'a> SimulationEngine<'a> {
     }
 }
 
-fn resolve_skill(ctx: &AppContext, skill_ref: &str) -> Result<SkillRecord> {
-    if let Some(skill) = ctx.db.get_skill(skill_ref)? {
-        return Ok(skill);
-    }
-    if let Some(alias) = ctx.db.resolve_alias(skill_ref)? {
-        if let Some(skill) = ctx.db.get_skill(&alias.canonical_id)? {
-            return Ok(skill);
-        }
-    }
-    Err(MsError::SkillNotFound(format!(
-        "skill not found: {skill_ref}"
-    )))
-}
-
 fn extract_elements(spec: &SkillSpec) -> Vec<SimElement> {
     let mut elements = Vec::new();
     for section in &spec.sections {
@@ -1,4 +1,9 @@
 //! Git archive layer for skill versioning
+//!
+//! Every archive commit goes through one choke point that appends a
+//! provenance trailer (`Ms-Source`, `Ms-Session`, `Ms-Bundle`, `Ms-Version`,
+//! `Ms-Actor` lines) recording which command made the change and on whose
+//! behalf, so [`GitArchive::skill_history`] can explain each version.
 
 use std::collections::{HashMap, HashSet};
 use std::fs;
@@ -8,6 +13,7 @@ use chrono::{DateTime, Utc};
 use git2::{Commit, ErrorCode, Oid, Repository, Signature};
 use serde::{Deserialize, Serialize};
 
+use crate::core::changelog::SkillVersion;
 use crate::core::{SkillMetadata, SkillSpec};
 use crate::error::{MsError, Result};
 
@@ -24,6 +30,132 @@ pub struct SkillCommit {
     pub message: String,
 }
 
+/// Files `commit_with_provenance` writes into each archived skill directory;
+/// resources may not shadow them.
+const ARCHIVE_SKILL_FILES: &[&str] = &[
+    "metadata.yaml",
+    "skill.spec.json",
+    "spec.lens.json",
+    "SKILL.md",
+    "evidence.json",
+    "slices.json",
+    "usage-log.jsonl",
+];
+
+const TRAILER_SOURCE: &str = "Ms-Source";
+const TRAILER_SESSION: &str = "Ms-Session";
+const TRAILER_BUNDLE: &str = "Ms-Bundle";
+const TRAILER_VERSION: &str = "Ms-Version";
+const TRAILER_ACTOR: &str = "Ms-Actor";
+
+/// Why an archive commit happened, written as trailer lines of its message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Provenance {
+    /// The `ms` command that made the change (`index`, `edit`, `sync`, ...)
+    pub source: String,
+    /// CASS sessions the change was mined from
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sessions: Vec<String>,
+    /// Bundle the change was installed from (`id@version`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bundle: Option<String>,
+    /// `ms` version that made the commit
+    pub ms_version: String,
+    /// User the command ran as
+    pub actor: String,
+}
+
+impl Provenance {
+    /// Provenance for a change made by `source`, run by the current user with
+    /// this `ms` version.
+    #[must_use]
+    pub fn new(source: impl Into<String>) -> Self {
+        let actor = std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .unwrap_or_else(|_| "unknown".to_string());
+        Self {
+            source: source.into(),
+            sessions: Vec::new(),
+            bundle: None,
+            ms_version: env!("CARGO_PKG_VERSION").to_string(),
+            actor,
+        }
+    }
+
+    #[must_use]
+    pub fn with_sessions(mut self, sessions: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.sessions = sessions.into_iter().map(Into::into).collect();
+        self
+    }
+
+    #[must_use]
+    pub fn with_bundle(mut self, bundle: impl Into<String>) -> Self {
+        self.bundle = Some(bundle.into());
+        self
+    }
+
+    /// The trailer block appended to commit messages.
+    #[must_use]
+    pub fn trailers(&self) -> String {
+        let mut lines = vec![format!("{TRAILER_SOURCE}: {}", one_line(&self.source))];
+        for session in &self.sessions {
+            lines.push(format!("{TRAILER_SESSION}: {}", one_line(session)));
+        }
+        if let Some(bundle) = &self.bundle {
+            lines.push(format!("{TRAILER_BUNDLE}: {}", one_line(bundle)));
+        }
+        lines.push(format!("{TRAILER_VERSION}: {}", one_line(&self.ms_version)));
+        lines.push(format!("{TRAILER_ACTOR}: {}", one_line(&self.actor)));
+        lines.join("\n")
+    }
+
+    /// Read the trailer block of a commit message. `None` when it has no
+    /// `Ms-Source` trailer, as with commits made before provenance existed.
+    #[must_use]
+    pub fn parse(message: &str) -> Option<Self> {
+        let trailers = message.trim_end().rsplit("\n\n").next()?;
+        let mut source = None;
+        let mut provenance = Self {
+            source: String::new(),
+            sessions: Vec::new(),
+            bundle: None,
+            ms_version: String::new(),
+            actor: String::new(),
+        };
+        for line in trailers.lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim().to_string();
+            match key.trim() {
+                TRAILER_SOURCE => source = Some(value),
+                TRAILER_SESSION => provenance.sessions.push(value),
+                TRAILER_BUNDLE => provenance.bundle = Some(value),
+                TRAILER_VERSION => provenance.ms_version = value,
+                TRAILER_ACTOR => provenance.actor = value,
+                _ => {}
+            }
+        }
+        provenance.source = source?;
+        Some(provenance)
+    }
+}
+
+/// Trailer values must stay on their line.
+fn one_line(value: &str) -> String {
+    value.replace(['\n', '\r'], " ")
+}
+
+/// One archive commit that changed a skill.
+#[derive(Debug, Clone, Serialize)]
+pub struct SkillHistoryEntry {
+    pub oid: String,
+    pub summary: String,
+    pub committed_at: DateTime<Utc>,
+    /// `None` for commits without a provenance trailer
+    pub provenance: Option<Provenance>,
+}
+
 impl GitArchive {
     /// Open existing archive or initialize new one
     pub fn open(path: impl AsRef<Path>) -> Result<Self> {
@@ -34,6 +166,13 @@ impl GitArchive {
             Ok(repo) => repo,
             Err(_) => Repository::init(&root)?,
         };
+        // Archive files are written with LF endings; a global
+        // `core.autocrlf` (the Windows default) would convert them on checkout
+        // and make every skill look modified.
+        let mut config = repo.config()?;
+        if config.get_bool("core.autocrlf").unwrap_or(false) {
+            config.set_bool("core.autocrlf", false)?;
+        }
 
         Self::ensure_structure(&root)?;
 
@@ -253,8 +392,15 @@ impl GitArchive {
         Ok(commits)
     }
 
-    /// Write a skill spec + compiled markdown into the archive and commit.
-    pub fn write_skill(&self, spec: &SkillSpec) -> Result<SkillCommit> {
+    /// Write a skill spec + compiled markdown into the archive and commit,
+    /// recording `provenance`. `message` replaces the default summary
+    /// `Update skill <id>`.
+    pub fn commit_with_provenance(
+        &self,
+        spec: &SkillSpec,
+        message: Option<&str>,
+        provenance: &Provenance,
+    ) -> Result<SkillCommit> {
         let skill_id = spec.metadata.id.trim();
         if skill_id.is_empty() {
             return Err(MsError::ValidationFailed(
@@ -301,13 +447,135 @@ impl GitArchive {
 
         let tree_id = index.write_tree()?;
         let tree = self.repo.find_tree(tree_id)?;
-        let message = format!("Update skill {skill_id}");
-        let oid = commit_with_parents(&self.repo, &self.signature, &tree, &message)?;
+        let message = message.map_or_else(|| format!("Update skill {skill_id}"), str::to_string);
+        self.commit(&tree, &message, provenance)
+    }
 
-        Ok(SkillCommit {
-            oid: oid.to_string(),
-            message,
-        })
+    /// Stage everything under `dirs` (inside the archive root) and commit, for
+    /// changes written to the archive tree by other means, such as bundle
+    /// installs.
+    pub fn commit_dirs(
+        &self,
+        dirs: &[PathBuf],
+        message: &str,
+        provenance: &Provenance,
+    ) -> Result<SkillCommit> {
+        let mut index = self.repo.index()?;
+        for dir in dirs {
+            add_dir_recursive(&mut index, &self.root, dir)?;
+        }
+        index.write()?;
+        let tree_id = index.write_tree()?;
+        let tree = self.repo.find_tree(tree_id)?;
+        self.commit(&tree, message, provenance)
+    }
+
+    /// Path of a skill resource in the archive, relative to the skill
+    /// directory like the archived `SKILL.md` that links to it.
+    ///
+    /// Returns None for unsafe skill ids, paths that leave the skill
+    /// directory, and the archive's own files.
+    #[must_use]
+    pub fn resource_path(&self, skill_id: &str, resource: &str) -> Option<PathBuf> {
+        let skill_dir = self.skill_path(skill_id)?;
+        let rel = crate::core::resources::normalize_link(resource).ok()?;
+        let reserved = rel.components().count() == 1
+            && ARCHIVE_SKILL_FILES
+                .iter()
+                .any(|name| rel == Path::new(name));
+        (!reserved).then(|| skill_dir.join(rel))
+    }
+
+    /// Write a skill's resource files next to its archived `SKILL.md`, remove
+    /// the `removed` ones, and commit.
+    pub fn commit_resources(
+        &self,
+        skill_id: &str,
+        files: &[(String, Vec<u8>)],
+        removed: &[String],
+        provenance: &Provenance,
+    ) -> Result<SkillCommit> {
+        let resolve = |resource: &str| {
+            self.resource_path(skill_id, resource).ok_or_else(|| {
+                MsError::ValidationFailed(format!(
+                    "invalid resource path for {skill_id}: {resource}"
+                ))
+            })
+        };
+
+        let mut index = self.repo.index()?;
+        for (resource, bytes) in files {
+            let path = resolve(resource)?;
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, bytes)?;
+            add_path(&mut index, &self.root, &path)?;
+        }
+        for resource in removed {
+            let path = resolve(resource)?;
+            if path.is_file() {
+                fs::remove_file(&path)?;
+            }
+            let rel = path.strip_prefix(&self.root).map_err(|_| {
+                MsError::ValidationFailed("path not under archive root".to_string())
+            })?;
+            index.remove_path(rel)?;
+        }
+        index.write()?;
+
+        let tree_id = index.write_tree()?;
+        let tree = self.repo.find_tree(tree_id)?;
+        let message = format!("Update resources of skill {skill_id}");
+        self.commit(&tree, &message, provenance)
+    }
+
+    /// Commits that changed a skill's directory, newest first.
+    pub fn skill_history(&self, skill_id: &str) -> Result<Vec<SkillHistoryEntry>> {
+        if self.skill_path(skill_id).is_none() {
+            return Err(MsError::ValidationFailed(
+                "skill id contains path traversal sequences".to_string(),
+            ));
+        }
+        let mut revwalk = self.repo.revwalk()?;
+        match self.repo.head() {
+            Ok(head) => match head.target() {
+                Some(oid) => revwalk.push(oid)?,
+                None => return Ok(Vec::new()),
+            },
+            Err(err) if err.code() == ErrorCode::UnbornBranch => return Ok(Vec::new()),
+            Err(err) if err.code() == ErrorCode::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(MsError::Git(err)),
+        }
+
+        let path = Path::new("skills/by-id").join(skill_id);
+        let dir_id = |commit: &Commit<'_>| -> Result<Option<Oid>> {
+            match commit.tree()?.get_path(&path) {
+                Ok(entry) => Ok(Some(entry.id())),
+                Err(err) if err.code() == ErrorCode::NotFound => Ok(None),
+                Err(err) => Err(MsError::Git(err)),
+            }
+        };
+        let mut history = Vec::new();
+        for oid in revwalk {
+            let commit = self.repo.find_commit(oid.map_err(MsError::Git)?)?;
+            let before = match commit.parent(0) {
+                Ok(parent) => dir_id(&parent)?,
+                Err(_) => None,
+            };
+            if dir_id(&commit)? == before {
+                continue;
+            }
+            let message = String::from_utf8_lossy(commit.message_bytes()).into_owned();
+            history.push(SkillHistoryEntry {
+                oid: commit.id().to_string(),
+                summary: message.lines().next().unwrap_or_default().to_string(),
+                committed_at: DateTime::from_timestamp(commit.time().seconds(), 0)
+                    .unwrap_or_default(),
+                provenance: Provenance::parse(&message),
+            });
+        }
+        Ok(history)
     }
 
     /// Read a skill spec from the archive.
@@ -332,8 +600,138 @@ impl GitArchive {
         Ok(metadata)
     }
 
+    /// Read a skill's SKILL.md as it was committed at `revision`
+    /// (any rev-parse spec: `HEAD~3`, a tag, a commit id).
+    pub fn read_markdown_at(&self, skill_id: &str, revision: &str) -> Result<String> {
+        if self.skill_path(skill_id).is_none() {
+            return Err(MsError::ValidationFailed(
+                "skill id contains path traversal sequences".to_string(),
+            ));
+        }
+        let commit = self
+            .repo
+            .revparse_single(revision)
+            .and_then(|object| object.peel_to_commit())
+            .map_err(|err| match err.code() {
+                ErrorCode::NotFound | ErrorCode::Ambiguous | ErrorCode::InvalidSpec => {
+                    MsError::NotFound(format!("unknown revision '{revision}' in skill archive"))
+                }
+                _ => MsError::Git(err),
+            })?;
+        let path = Path::new("skills/by-id").join(skill_id).join("SKILL.md");
+        let entry = match commit.tree()?.get_path(&path) {
+            Ok(entry) => entry,
+            Err(e) if e.code() == ErrorCode::NotFound => {
+                return Err(MsError::NotFound(format!(
+                    "skill '{skill_id}' did not exist at revision '{revision}'"
+                )));
+            }
+            Err(e) => return Err(MsError::Git(e)),
+        };
+        let blob = entry.to_object(&self.repo)?.peel_to_blob()?;
+        String::from_utf8(blob.content().to_vec()).map_err(|_| {
+            MsError::ValidationFailed(format!(
+                "SKILL.md for '{skill_id}' at '{revision}' is not valid UTF-8"
+            ))
+        })
+    }
+
+    /// Read a skill's spec as it was committed at `revision`; `None` when the
+    /// skill did not exist there.
+    pub fn read_spec_at(&self, skill_id: &str, revision: &str) -> Result<Option<SkillSpec>> {
+        if self.skill_path(skill_id).is_none() {
+            return Err(MsError::ValidationFailed(
+                "skill id contains path traversal sequences".to_string(),
+            ));
+        }
+        let commit = self
+            .repo
+            .revparse_single(revision)
+            .and_then(|object| object.peel_to_commit())
+            .map_err(|err| match err.code() {
+                ErrorCode::NotFound | ErrorCode::Ambiguous | ErrorCode::InvalidSpec => {
+                    MsError::NotFound(format!("unknown revision '{revision}' in skill archive"))
+                }
+                _ => MsError::Git(err),
+            })?;
+        let path = Path::new("skills/by-id")
+            .join(skill_id)
+            .join("skill.spec.json");
+        let entry = match commit.tree()?.get_path(&path) {
+            Ok(entry) => entry,
+            Err(e) if e.code() == ErrorCode::NotFound => return Ok(None),
+            Err(e) => return Err(MsError::Git(e)),
+        };
+        let blob = self.repo.find_blob(entry.id())?;
+        Ok(Some(serde_json::from_slice(blob.content())?))
+    }
+
+    /// Every committed version of a skill's spec, oldest first, as input
+    /// for [`build_changelog`](crate::core::changelog::build_changelog).
+    pub fn skill_versions(&self, skill_id: &str) -> Result<Vec<SkillVersion>> {
+        let mut history = self.skill_history(skill_id)?;
+        history.reverse();
+        history
+            .into_iter()
+            .map(|entry| {
+                Ok(SkillVersion {
+                    spec: self.read_spec_at(skill_id, &entry.oid)?,
+                    oid: entry.oid,
+                    committed_at: entry.committed_at,
+                    message: entry.summary,
+                })
+            })
+            .collect()
+    }
+
+    /// The newest committed version of a skill's spec for which `matches`
+    /// returns true, walking history back from HEAD. Each distinct spec blob
+    /// is parsed once; unparseable versions are skipped.
+    pub fn find_skill_in_history(
+        &self,
+        skill_id: &str,
+        mut matches: impl FnMut(&SkillSpec) -> bool,
+    ) -> Result<Option<SkillSpec>> {
+        if self.skill_path(skill_id).is_none() {
+            return Err(MsError::ValidationFailed(
+                "skill id contains path traversal sequences".to_string(),
+            ));
+        }
+        let mut revwalk = self.repo.revwalk()?;
+        match self.repo.head() {
+            Ok(head) => match head.target() {
+                Some(oid) => revwalk.push(oid)?,
+                None => return Ok(None),
+            },
+            Err(err) if err.code() == ErrorCode::UnbornBranch => return Ok(None),
+            Err(err) if err.code() == ErrorCode::NotFound => return Ok(None),
+            Err(err) => return Err(MsError::Git(err)),
+        }
+
+        let path = Path::new("skills/by-id")
+            .join(skill_id)
+            .join("skill.spec.json");
+        let mut seen = HashSet::new();
+        for oid in revwalk {
+            let commit = self.repo.find_commit(oid.map_err(MsError::Git)?)?;
+            let Ok(entry) = commit.tree()?.get_path(&path) else {
+                continue;
+            };
+            if !seen.insert(entry.id()) {
+                continue;
+            }
+            let blob = self.repo.find_blob(entry.id())?;
+            if let Ok(spec) = serde_json::from_slice::<SkillSpec>(blob.content())
+                && matches(&spec)
+            {
+                return Ok(Some(spec));
+            }
+        }
+        Ok(None)
+    }
+
     /// Delete a skill directory and commit the removal.
-    pub fn delete_skill(&self, skill_id: &str) -> Result<SkillCommit> {
+    pub fn delete_skill(&self, skill_id: &str, provenance: &Provenance) -> Result<SkillCommit> {
         let skill_dir = self.skill_path(skill_id).ok_or_else(|| {
             MsError::ValidationFailed("skill id contains path traversal sequences".to_string())
         })?;
@@ -353,11 +751,42 @@ impl GitArchive {
         let tree_id = index.write_tree()?;
         let tree = self.repo.find_tree(tree_id)?;
         let message = format!("Tombstone skill {skill_id}");
-        let oid = commit_with_parents(&self.repo, &self.signature, &tree, &message)?;
+        self.commit(&tree, &message, provenance)
+    }
+
+    /// Commit a prune log to `prune/<timestamp>.json` so the archive
+    /// history records what `ms prune --apply` deleted and why.
+    pub fn record_prune(
+        &self,
+        message: &str,
+        log: &str,
+        provenance: &Provenance,
+    ) -> Result<SkillCommit> {
+        let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%S%.3fZ");
+        let path = self.root.join("prune").join(format!("{stamp}.json"));
+        write_string(&path, log)?;
+
+        let mut index = self.repo.index()?;
+        add_path(&mut index, &self.root, &path)?;
+        index.write()?;
+        let tree_id = index.write_tree()?;
+        let tree = self.repo.find_tree(tree_id)?;
+        self.commit(&tree, message, provenance)
+    }
 
+    /// The one place archive commits are made: `message`, a blank line, and
+    /// the provenance trailers.
+    fn commit(
+        &self,
+        tree: &git2::Tree<'_>,
+        message: &str,
+        provenance: &Provenance,
+    ) -> Result<SkillCommit> {
+        let full = format!("{}\n\n{}\n", message.trim_end(), provenance.trailers());
+        let oid = commit_with_parents(&self.repo, &self.signature, tree, &full)?;
         Ok(SkillCommit {
             oid: oid.to_string(),
-            message,
+            message: message.to_string(),
         })
     }
 
@@ -373,6 +802,11 @@ impl GitArchive {
                 "# ms archive\n\nThis directory contains the ms skill archive.\n",
             )?;
         }
+        // Also keeps clones made with the git CLI from converting line endings
+        let attributes = root.join(".gitattributes");
+        if !attributes.exists() {
+            write_string(&attributes, "* -text\n")?;
+        }
         Ok(())
     }
 }
@@ -448,7 +882,8 @@ fn add_path(index: &mut git2::Index, root: &Path, path: &Path) -> Result<()> {
 
 fn add_dir_recursive(index: &mut git2::Index, root: &Path, dir: &Path) -> Result<()> {
     for entry in walkdir::WalkDir::new(dir) {
-        let entry = entry.map_err(|err| MsError::Config(format!("walk tombstone: {err}")))?;
+        let entry =
+            entry.map_err(|err| MsError::Config(format!("walk {}: {err}", dir.display())))?;
         if entry.file_type().is_file() {
             add_path(index, root, entry.path())?;
         }
@@ -492,6 +927,10 @@ mod tests {
     use super::*;
     use tempfile::tempdir;
 
+    fn provenance() -> Provenance {
+        Provenance::new("test")
+    }
+
     fn sample_spec(id: &str) -> SkillSpec {
         SkillSpec {
             format_version: SkillSpec::FORMAT_VERSION.to_string(),
@@ -532,7 +971,9 @@ mod tests {
         let archive = GitArchive::open(dir.path()).unwrap();
 
         let spec = sample_spec("test-skill");
-        archive.write_skill(&spec).unwrap();
+        archive
+            .commit_with_provenance(&spec, None, &provenance())
+            .unwrap();
 
         let skill_dir = dir.path().join("skills/by-id/test-skill");
         assert!(skill_dir.join("skill.spec.json").exists());
@@ -555,32 +996,141 @@ mod tests {
         let archive = GitArchive::open(dir.path()).unwrap();
 
         let spec = sample_spec("hist-skill");
-        let commit = archive.write_skill(&spec).unwrap();
+        let commit = archive
+            .commit_with_provenance(&spec, None, &provenance())
+            .unwrap();
 
         assert!(!commit.oid.is_empty());
         assert!(commit.message.contains("hist-skill"));
     }
 
+    #[test]
+    fn test_archive_keeps_lf_line_endings() {
+        let dir = tempdir().unwrap();
+        let archive = GitArchive::open(dir.path()).unwrap();
+        archive
+            .repo()
+            .config()
+            .unwrap()
+            .set_bool("core.autocrlf", true)
+            .unwrap();
+
+        let archive = GitArchive::open(dir.path()).unwrap();
+        let config = archive.repo().config().unwrap();
+        assert!(!config.get_bool("core.autocrlf").unwrap());
+        assert_eq!(
+            fs::read_to_string(dir.path().join(".gitattributes")).unwrap(),
+            "* -text\n"
+        );
+    }
+
     #[test]
     fn test_skill_delete() {
         let dir = tempdir().unwrap();
         let archive = GitArchive::open(dir.path()).unwrap();
 
         let spec = sample_spec("delete-skill");
-        archive.write_skill(&spec).unwrap();
-        let commit = archive.delete_skill("delete-skill").unwrap();
+        archive
+            .commit_with_provenance(&spec, None, &provenance())
+            .unwrap();
+        let commit = archive.delete_skill("delete-skill", &provenance()).unwrap();
         assert!(commit.message.contains("delete-skill"));
         assert!(!dir.path().join("skills/by-id/delete-skill").exists());
     }
 
+    #[test]
+    fn test_commit_resources_keeps_relative_paths() {
+        let dir = tempdir().unwrap();
+        let archive = GitArchive::open(dir.path()).unwrap();
+        archive
+            .commit_with_provenance(&sample_spec("res-skill"), None, &provenance())
+            .unwrap();
+
+        let files = vec![
+            ("resources/template.md".to_string(), b"# T\n".to_vec()),
+            ("schemas/input.json".to_string(), b"{}".to_vec()),
+        ];
+        archive
+            .commit_resources("res-skill", &files, &[], &provenance())
+            .unwrap();
+        let skill_dir = dir.path().join("skills/by-id/res-skill");
+        assert_eq!(
+            fs::read(skill_dir.join("schemas/input.json")).unwrap(),
+            b"{}"
+        );
+        let tree = archive.repo().head().unwrap().peel_to_tree().unwrap();
+        assert!(
+            tree.get_path(Path::new("skills/by-id/res-skill/resources/template.md"))
+                .is_ok()
+        );
+
+        archive
+            .commit_resources(
+                "res-skill",
+                &[],
+                &["schemas/input.json".to_string()],
+                &provenance(),
+            )
+            .unwrap();
+        assert!(!skill_dir.join("schemas/input.json").exists());
+        let tree = archive.repo().head().unwrap().peel_to_tree().unwrap();
+        assert!(
+            tree.get_path(Path::new("skills/by-id/res-skill/schemas/input.json"))
+                .is_err()
+        );
+
+        for bad in ["../escape.txt", "SKILL.md", "/etc/passwd"] {
+            let files = vec![(bad.to_string(), b"x".to_vec())];
+            assert!(
+                archive
+                    .commit_resources("res-skill", &files, &[], &provenance())
+                    .is_err()
+            );
+        }
+        assert!(
+            archive
+                .resource_path("res-skill", "docs/SKILL.md")
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_record_prune_commits_log() {
+        let dir = tempdir().unwrap();
+        let archive = GitArchive::open(dir.path()).unwrap();
+        archive
+            .commit_with_provenance(&sample_spec("kept"), None, &provenance())
+            .unwrap();
+
+        let commit = archive
+            .record_prune(
+                "Prune 2 orphaned evidence rows",
+                "{\"deleted\":2}",
+                &Provenance::new("prune"),
+            )
+            .unwrap();
+        let head = archive.repo().head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.id().to_string(), commit.oid);
+        let message = head.message().unwrap();
+        assert!(message.starts_with("Prune 2 orphaned evidence rows\n\n"));
+        assert_eq!(Provenance::parse(message).unwrap().source, "prune");
+        let tree = head.tree().unwrap();
+        assert!(tree.get_path(Path::new("prune")).is_ok());
+        assert!(tree.get_path(Path::new("skills/by-id/kept")).is_ok());
+    }
+
     #[test]
     fn test_list_skill_ids() {
         let dir = tempdir().unwrap();
         let archive = GitArchive::open(dir.path()).unwrap();
         let spec_a = sample_spec("alpha");
         let spec_b = sample_spec("beta");
-        archive.write_skill(&spec_b).unwrap();
-        archive.write_skill(&spec_a).unwrap();
+        archive
+            .commit_with_provenance(&spec_b, None, &provenance())
+            .unwrap();
+        archive
+            .commit_with_provenance(&spec_a, None, &provenance())
+            .unwrap();
 
         let ids = archive.list_skill_ids().unwrap();
         assert_eq!(ids, vec!["alpha".to_string(), "beta".to_string()]);
@@ -591,7 +1141,9 @@ mod tests {
         let dir = tempdir().unwrap();
         let archive = GitArchive::open(dir.path()).unwrap();
         let spec = sample_spec("recent-skill");
-        archive.write_skill(&spec).unwrap();
+        archive
+            .commit_with_provenance(&spec, None, &provenance())
+            .unwrap();
 
         let commits = archive.recent_commits(5).unwrap();
         assert!(!commits.is_empty());
@@ -613,14 +1165,18 @@ mod tests {
         assert!(archive.skill_path("valid-skill").is_some());
         assert!(archive.skill_path("skill_123").is_some());
 
-        // Test that write_skill rejects path traversal
+        // Test that commit_with_provenance rejects path traversal
         let mut spec = sample_spec("../malicious");
-        let err = archive.write_skill(&spec).unwrap_err();
+        let err = archive
+            .commit_with_provenance(&spec, None, &provenance())
+            .unwrap_err();
         assert!(err.to_string().contains("path traversal"));
 
         // Test that read_skill rejects path traversal
         spec.metadata.id = "valid-skill".to_string();
-        archive.write_skill(&spec).unwrap();
+        archive
+            .commit_with_provenance(&spec, None, &provenance())
+            .unwrap();
         let err = archive.read_skill("../malicious").unwrap_err();
         assert!(err.to_string().contains("path traversal"));
 
@@ -650,7 +1206,9 @@ mod tests {
         assert!(!archive.skill_committed("comm-skill").unwrap());
 
         // Write and commit
-        archive.write_skill(&spec).unwrap();
+        archive
+            .commit_with_provenance(&spec, None, &provenance())
+            .unwrap();
 
         // Now committed
         assert!(archive.skill_committed("comm-skill").unwrap());
@@ -664,4 +1222,164 @@ mod tests {
         assert!(archive.skill_exists("uncomm-skill"));
         assert!(!archive.skill_committed("uncomm-skill").unwrap());
     }
+
+    #[test]
+    fn test_read_markdown_at_revision() {
+        let dir = tempdir().unwrap();
+        let archive = GitArchive::open(dir.path()).unwrap();
+
+        let mut spec = sample_spec("rev-skill");
+        archive
+            .commit_with_provenance(&spec, None, &provenance())
+            .unwrap();
+        spec.sections[0].blocks[0].content = "Goodbye".to_string();
+        archive
+            .commit_with_provenance(&spec, None, &provenance())
+            .unwrap();
+        archive
+            .commit_with_provenance(&sample_spec("later-skill"), None, &provenance())
+            .unwrap();
+
+        let old = archive.read_markdown_at("rev-skill", "HEAD~2").unwrap();
+        assert!(old.contains("Hello"));
+        let current = archive.read_markdown_at("rev-skill", "HEAD").unwrap();
+        assert!(current.contains("Goodbye"));
+
+        let missing = archive
+            .read_markdown_at("later-skill", "HEAD~1")
+            .unwrap_err()
+            .to_string();
+        assert!(missing.contains("did not exist at revision 'HEAD~1'"));
+        let unknown = archive
+            .read_markdown_at("rev-skill", "no-such-tag")
+            .unwrap_err()
+            .to_string();
+        assert!(unknown.contains("unknown revision 'no-such-tag'"));
+    }
+
+    #[test]
+    fn test_find_skill_in_history() {
+        let dir = tempdir().unwrap();
+        let archive = GitArchive::open(dir.path()).unwrap();
+        assert!(
+            archive
+                .find_skill_in_history("hist-skill", |_| true)
+                .unwrap()
+                .is_none()
+        );
+
+        let mut spec = sample_spec("hist-skill");
+        archive
+            .commit_with_provenance(&spec, None, &provenance())
+            .unwrap();
+        spec.sections[0].blocks[0].content = "Goodbye".to_string();
+        archive
+            .commit_with_provenance(&spec, None, &provenance())
+            .unwrap();
+
+        let newest = archive
+            .find_skill_in_history("hist-skill", |_| true)
+            .unwrap()
+            .unwrap();
+        assert_eq!(newest.sections[0].blocks[0].content, "Goodbye");
+        let oldest = archive
+            .find_skill_in_history("hist-skill", |s| s.sections[0].blocks[0].content == "Hello")
+            .unwrap()
+            .unwrap();
+        assert_eq!(oldest.sections[0].blocks[0].content, "Hello");
+        assert!(
+            archive
+                .find_skill_in_history("hist-skill", |_| false)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_provenance_trailers_round_trip() {
+        let provenance = Provenance::new("build")
+            .with_sessions(["sess-1", "sess-2"])
+            .with_bundle("rust-kit@1.2.0");
+        let message = format!(
+            "Update skill x\n\nBody text.\n\n{}\n",
+            provenance.trailers()
+        );
+        assert_eq!(Provenance::parse(&message), Some(provenance));
+
+        assert_eq!(Provenance::parse("Update skill x"), None);
+        assert_eq!(
+            Provenance::parse("Update skill x\n\nSigned-off-by: someone"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_skill_history_reads_provenance() {
+        let dir = tempdir().unwrap();
+        let archive = GitArchive::open(dir.path()).unwrap();
+
+        let mut spec = sample_spec("hist-skill");
+        archive
+            .commit_with_provenance(&spec, None, &Provenance::new("index"))
+            .unwrap();
+        archive
+            .commit_with_provenance(&sample_spec("other-skill"), None, &provenance())
+            .unwrap();
+
+        // A commit from before provenance trailers existed
+        spec.sections[0].blocks[0].content = "Goodbye".to_string();
+        let skill_dir = archive.skill_path("hist-skill").unwrap();
+        fs::write(
+            skill_dir.join("skill.spec.json"),
+            serde_json::to_string_pretty(&spec).unwrap(),
+        )
+        .unwrap();
+        let mut index = archive.repo().index().unwrap();
+        add_dir_recursive(&mut index, archive.root(), &skill_dir).unwrap();
+        let tree = archive
+            .repo()
+            .find_tree(index.write_tree().unwrap())
+            .unwrap();
+        commit_with_parents(archive.repo(), &archive.signature, &tree, "Hand edit").unwrap();
+
+        spec.sections[0].blocks[0].content = "Hello again".to_string();
+        archive
+            .commit_with_provenance(
+                &spec,
+                Some("Edit hist-skill"),
+                &Provenance::new("edit").with_sessions(["sess-9"]),
+            )
+            .unwrap();
+
+        let history = archive.skill_history("hist-skill").unwrap();
+        let summaries: Vec<&str> = history.iter().map(|e| e.summary.as_str()).collect();
+        assert_eq!(
+            summaries,
+            ["Edit hist-skill", "Hand edit", "Update skill hist-skill"]
+        );
+        let edit = history[0].provenance.as_ref().unwrap();
+        assert_eq!(edit.source, "edit");
+        assert_eq!(edit.sessions, ["sess-9"]);
+        assert!(history[1].provenance.is_none());
+        assert_eq!(history[2].provenance.as_ref().unwrap().source, "index");
+
+        let first = archive
+            .read_spec_at("hist-skill", &history[2].oid)
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.sections[0].blocks[0].content, "Hello");
+        assert!(
+            archive
+                .read_spec_at("other-skill", &history[2].oid)
+                .unwrap()
+                .is_none()
+        );
+
+        let versions = archive.skill_versions("hist-skill").unwrap();
+        assert_eq!(versions.len(), history.len());
+        assert_eq!(versions[0].oid, history[2].oid);
+
+        assert!(archive.skill_history("no-such-skill").unwrap().is_empty());
+        assert!(archive.skill_history("../escape").is_err());
+    }
 }
@@ -1,10 +1,25 @@
 //! Database migrations
-
+//!
+//! Several processes may open the same data directory at once (e.g. a shared
+//! NFS home), so pending migrations are applied under an exclusive
+//! [`MigrationLock`], the version is re-read after the lock is taken, and each
+//! migration commits atomically together with its `user_version` bump. A
+//! schema whose recorded version disagrees with the tables present is refused
+//! rather than migrated further; `ms doctor --migrations` reports the details.
+
+use std::collections::HashSet;
+use std::fs::{File, OpenOptions};
+use std::path::{Path, PathBuf};
+
+use fs2::FileExt;
 use fsqlite::Connection;
+use fsqlite::compat::{ConnectionExt, RowExt};
+use serde::Serialize;
 
 use crate::error::{MsError, Result};
+use crate::ms_params as params;
 
-const MIGRATIONS: [&str; 13] = [
+const MIGRATIONS: [&str; 29] = [
     include_str!("../../migrations/001_initial_schema.sql"),
     include_str!("../../migrations/002_add_fts.sql"),
     include_str!("../../migrations/003_add_vectors.sql"),
@@ -18,40 +33,306 @@ const MIGRATIONS: [&str; 13] = [
     include_str!("../../migrations/011_add_user_preferences.sql"),
     include_str!("../../migrations/012_add_resolution_warnings.sql"),
     include_str!("../../migrations/013_fix_fts.sql"),
+    include_str!("../../migrations/014_add_skill_efficacy.sql"),
+    include_str!("../../migrations/015_add_suggestion_records.sql"),
+    include_str!("../../migrations/016_add_skill_source_files.sql"),
+    include_str!("../../migrations/017_add_bandit_state.sql"),
+    include_str!("../../migrations/018_add_quarantine_content.sql"),
+    include_str!("../../migrations/019_add_layer_resolutions.sql"),
+    include_str!("../../migrations/020_add_suggestion_cooldowns.sql"),
+    include_str!("../../migrations/021_add_skill_tags.sql"),
+    include_str!("../../migrations/022_add_dedup_dismissals.sql"),
+    include_str!("../../migrations/023_add_token_counts.sql"),
+    include_str!("../../migrations/024_add_uncertainty_review.sql"),
+    include_str!("../../migrations/025_add_mined_feedback.sql"),
+    include_str!("../../migrations/026_add_skill_trials.sql"),
+    include_str!("../../migrations/027_add_suggestion_agents.sql"),
+    include_str!("../../migrations/028_add_derived_tags.sql"),
+    include_str!("../../migrations/029_add_skill_resources.sql"),
 ];
 
 pub const SCHEMA_VERSION: u32 = MIGRATIONS.len() as u32;
 
+/// Exclusive advisory lock held while pending migrations are applied.
+///
+/// The lock file sits next to the database so every process sharing the data
+/// directory serializes on it. Released when dropped.
+pub struct MigrationLock {
+    #[allow(dead_code)]
+    lock_file: File,
+}
+
+impl MigrationLock {
+    /// Lock file path for `db_path` (`ms.db` -> `ms.db.migrate.lock`).
+    #[must_use]
+    pub fn path_for(db_path: &Path) -> PathBuf {
+        let mut name = db_path
+            .file_name()
+            .map(std::ffi::OsStr::to_os_string)
+            .unwrap_or_else(|| "ms.db".into());
+        name.push(".migrate.lock");
+        db_path.with_file_name(name)
+    }
+
+    /// Acquire the lock, blocking until other migrating processes finish.
+    pub fn acquire(db_path: &Path) -> Result<Self> {
+        let lock_path = Self::path_for(db_path);
+        let lock_file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&lock_path)
+            .map_err(|e| MsError::TransactionFailed(format!("open migration lock: {e}")))?;
+        lock_file
+            .lock_exclusive()
+            .map_err(|e| MsError::TransactionFailed(format!("acquire migration lock: {e}")))?;
+        Ok(Self { lock_file })
+    }
+}
+
+/// A migration whose recorded version and schema disagree.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PartialMigration {
+    pub version: u32,
+    /// Whether `user_version` records the migration as applied.
+    pub recorded: bool,
+    /// Tables that are missing (if recorded) or already present (if not).
+    pub tables: Vec<String>,
+}
+
+impl std::fmt::Display for PartialMigration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.recorded {
+            write!(
+                f,
+                "migration {} is recorded as applied but table(s) {} are missing",
+                self.version,
+                self.tables.join(", ")
+            )
+        } else {
+            write!(
+                f,
+                "migration {} is not recorded but table(s) {} already exist",
+                self.version,
+                self.tables.join(", ")
+            )
+        }
+    }
+}
+
+/// Migration state of a database, as reported by `ms doctor --migrations`.
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationReport {
+    pub db_path: PathBuf,
+    pub version: u32,
+    pub latest: u32,
+    pub pending: Vec<u32>,
+    pub partial: Vec<PartialMigration>,
+}
+
+/// Bring a freshly opened database up to date.
+///
+/// Up-to-date databases only get the consistency check; otherwise migrations
+/// run under [`MigrationLock`].
+pub fn migrate(conn: &Connection, db_path: &Path) -> Result<u32> {
+    let version = current_version(conn)?;
+    if version >= SCHEMA_VERSION {
+        ensure_consistent(conn, version)?;
+        return Ok(SCHEMA_VERSION);
+    }
+    let _lock = MigrationLock::acquire(db_path)?;
+    run_migrations(conn)
+}
+
 /// Run all migrations on the database
+///
+/// Callers sharing the database with other processes must hold the
+/// [`MigrationLock`]; the version is read here, after the lock is taken.
 pub fn run_migrations(conn: &Connection) -> Result<u32> {
-    use fsqlite::compat::RowExt;
-
-    let current_version: u32 = conn
-        .query_row("PRAGMA user_version;")
-        .and_then(|row| row.get_typed::<u32>(0))
-        .map_err(|err| MsError::TransactionFailed(err.to_string()))?;
+    let current_version = current_version(conn)?;
+    ensure_consistent(conn, current_version)?;
 
     for (idx, sql) in MIGRATIONS.iter().enumerate() {
         let target_version = (idx + 1) as u32;
         if current_version >= target_version {
             continue;
         }
+        apply_migration(conn, target_version, sql)?;
+    }
+
+    Ok(SCHEMA_VERSION)
+}
+
+/// Inspect a database's migration state without applying anything.
+pub fn inspect(db_path: &Path) -> Result<MigrationReport> {
+    let conn = Connection::open(db_path.to_string_lossy().into_owned())?;
+    let version = current_version(&conn)?;
+    Ok(MigrationReport {
+        db_path: db_path.to_path_buf(),
+        version,
+        latest: SCHEMA_VERSION,
+        pending: (version + 1..=SCHEMA_VERSION).collect(),
+        partial: find_partial_migrations(&conn, version)?,
+    })
+}
+
+pub(crate) fn current_version(conn: &Connection) -> Result<u32> {
+    conn.query_row("PRAGMA user_version;")
+        .and_then(|row| row.get_typed::<u32>(0))
+        .map_err(|err| MsError::TransactionFailed(err.to_string()))
+}
 
-        conn.execute_batch(sql).map_err(|err| {
-            MsError::TransactionFailed(format!("migration {target_version} failed: {err}"))
-        })?;
-        // fsqlite has no `pragma_update` analogue; PRAGMA writes are issued as
-        // plain SQL statements through `execute`. This matches how the rest of
-        // the fsqlite ecosystem (beads_rust, frankensearch) sets user_version.
+/// Apply one migration and record its version in the same transaction.
+fn apply_migration(conn: &Connection, target_version: u32, sql: &str) -> Result<()> {
+    conn.execute("BEGIN IMMEDIATE").map_err(|err| {
+        MsError::TransactionFailed(format!("migration {target_version}: begin failed: {err}"))
+    })?;
+    // fsqlite has no `pragma_update` analogue; PRAGMA writes are issued as
+    // plain SQL statements through `execute`. This matches how the rest of
+    // the fsqlite ecosystem (beads_rust, frankensearch) sets user_version.
+    let applied = conn.execute_batch(sql).and_then(|_| {
         conn.execute(&format!("PRAGMA user_version = {target_version}"))
-            .map_err(|err| {
-                MsError::TransactionFailed(format!(
-                    "failed to set user_version {target_version}: {err}"
-                ))
-            })?;
+            .map(|_| ())
+    });
+    match applied {
+        Ok(()) => conn.execute("COMMIT").map(|_| ()).map_err(|err| {
+            MsError::TransactionFailed(format!("migration {target_version}: commit failed: {err}"))
+        }),
+        Err(err) => {
+            if let Err(rollback) = conn.execute("ROLLBACK") {
+                tracing::warn!(error = %rollback, "rollback of migration {target_version} failed");
+            }
+            Err(MsError::TransactionFailed(format!(
+                "migration {target_version} failed: {err}"
+            )))
+        }
     }
+}
 
-    Ok(SCHEMA_VERSION)
+/// Refuse to touch a schema whose version and tables disagree.
+fn ensure_consistent(conn: &Connection, version: u32) -> Result<()> {
+    match find_partial_migrations(conn, version)?.first() {
+        Some(partial) => Err(MsError::TransactionFailed(format!(
+            "{partial}; the database looks partially migrated. \
+             Run `ms doctor --migrations` for details"
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Compare the tables each migration creates with the tables present.
+///
+/// Recorded migrations must have their tables (unless a later recorded
+/// migration drops them). Unrecorded migrations must not, except for
+/// `CREATE TABLE IF NOT EXISTS` tables, which are safe to re-apply.
+pub fn find_partial_migrations(conn: &Connection, version: u32) -> Result<Vec<PartialMigration>> {
+    let existing: HashSet<String> = conn
+        .query_map_collect(
+            "SELECT name FROM sqlite_master WHERE type='table'",
+            params![],
+            |row| row.get_typed::<String>(0),
+        )?
+        .into_iter()
+        .collect();
+    let recorded = (version as usize).min(MIGRATIONS.len());
+
+    let mut partial = Vec::new();
+    for (idx, sql) in MIGRATIONS.iter().enumerate() {
+        let migration = (idx + 1) as u32;
+        let tables: Vec<String> = if idx < recorded {
+            created_tables(sql)
+                .into_iter()
+                .filter(|(name, _)| {
+                    !MIGRATIONS[idx + 1..recorded]
+                        .iter()
+                        .any(|later| dropped_tables(later).contains(name))
+                })
+                .filter(|(name, _)| !existing.contains(name))
+                .map(|(name, _)| name)
+                .collect()
+        } else {
+            created_tables(sql)
+                .into_iter()
+                .filter(|(name, if_not_exists)| !if_not_exists && existing.contains(name))
+                .filter(|(name, _)| {
+                    !MIGRATIONS[..recorded]
+                        .iter()
+                        .any(|earlier| created_tables(earlier).iter().any(|(t, _)| t == name))
+                })
+                .map(|(name, _)| name)
+                .collect()
+        };
+        if !tables.is_empty() {
+            partial.push(PartialMigration {
+                version: migration,
+                recorded: idx < recorded,
+                tables,
+            });
+        }
+    }
+    Ok(partial)
+}
+
+/// Plain tables created by a migration, with whether `IF NOT EXISTS` was used.
+fn created_tables(sql: &str) -> Vec<(String, bool)> {
+    sql.lines()
+        .filter_map(|line| {
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            match tokens.as_slice() {
+                [create, table, rest @ ..]
+                    if create.eq_ignore_ascii_case("CREATE")
+                        && table.eq_ignore_ascii_case("TABLE") =>
+                {
+                    let if_not_exists = rest.len() > 3
+                        && rest[0].eq_ignore_ascii_case("IF")
+                        && rest[1].eq_ignore_ascii_case("NOT")
+                        && rest[2].eq_ignore_ascii_case("EXISTS");
+                    let name = if if_not_exists {
+                        rest[3]
+                    } else {
+                        *rest.first()?
+                    };
+                    Some((table_name(name), if_not_exists))
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Tables dropped by a migration.
+fn dropped_tables(sql: &str) -> Vec<String> {
+    sql.lines()
+        .filter_map(|line| {
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            match tokens.as_slice() {
+                [drop, table, rest @ ..]
+                    if drop.eq_ignore_ascii_case("DROP") && table.eq_ignore_ascii_case("TABLE") =>
+                {
+                    let name = match rest {
+                        [if_kw, exists, name, ..]
+                            if if_kw.eq_ignore_ascii_case("IF")
+                                && exists.eq_ignore_ascii_case("EXISTS") =>
+                        {
+                            name
+                        }
+                        [name, ..] => name,
+                        [] => return None,
+                    };
+                    Some(table_name(name))
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+fn table_name(token: &str) -> String {
+    token
+        .trim_end_matches(['(', ';'])
+        .trim_matches('"')
+        .to_string()
 }
 
 #[cfg(test)]
@@ -75,8 +356,8 @@ mod tests {
     }
 
     #[test]
-    fn schema_version_is_13() {
-        assert_eq!(SCHEMA_VERSION, 13);
+    fn schema_version_is_29() {
+        assert_eq!(SCHEMA_VERSION, 29);
     }
 
     // =========================================================================
@@ -209,6 +490,96 @@ mod tests {
         assert!(count >= 3, "Expected at least 3 tables, got {count}");
     }
 
+    #[test]
+    fn migration_markers_are_parsed() {
+        assert_eq!(
+            created_tables("CREATE TABLE skills (\n  id TEXT\n);"),
+            vec![("skills".to_string(), false)]
+        );
+        assert_eq!(
+            created_tables("CREATE TABLE IF NOT EXISTS user_preferences ("),
+            vec![("user_preferences".to_string(), true)]
+        );
+        assert!(created_tables("CREATE VIRTUAL TABLE skills_fts USING fts5(").is_empty());
+        assert_eq!(
+            dropped_tables("DROP TABLE IF EXISTS skills_fts;"),
+            vec!["skills_fts".to_string()]
+        );
+    }
+
+    #[test]
+    fn fully_migrated_database_is_consistent() {
+        let conn = Connection::open(":memory:").unwrap();
+        run_migrations(&conn).unwrap();
+        assert!(
+            find_partial_migrations(&conn, SCHEMA_VERSION)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn recorded_migration_with_missing_table_is_refused() {
+        let conn = Connection::open(":memory:").unwrap();
+        run_migrations(&conn).unwrap();
+        conn.execute("DROP TABLE skill_efficacy").unwrap();
+
+        let partial = find_partial_migrations(&conn, SCHEMA_VERSION).unwrap();
+        assert_eq!(partial.len(), 1);
+        assert_eq!(partial[0].version, 14);
+        assert!(partial[0].recorded);
+
+        let err = run_migrations(&conn).unwrap_err().to_string();
+        assert!(err.contains("ms doctor --migrations"), "{err}");
+    }
+
+    #[test]
+    fn unrecorded_migration_with_existing_table_is_refused() {
+        let conn = Connection::open(":memory:").unwrap();
+        run_migrations(&conn).unwrap();
+        // Simulate a crash after migration 14's DDL but before its version bump.
+        conn.execute("PRAGMA user_version = 13").unwrap();
+
+        let partial = find_partial_migrations(&conn, 13).unwrap();
+        assert_eq!(partial[0].version, 14);
+        assert!(!partial[0].recorded);
+        assert_eq!(partial[0].tables, vec!["skill_efficacy".to_string()]);
+        assert!(run_migrations(&conn).is_err());
+    }
+
+    #[test]
+    fn concurrent_opens_apply_each_migration_once() {
+        use std::sync::{Arc, Barrier};
+
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("ms.db");
+        let workers = 8;
+        let barrier = Arc::new(Barrier::new(workers));
+
+        // Non-idempotent `CREATE TABLE`s in the migrations make any double
+        // application fail the open, so every worker succeeding means each
+        // migration ran exactly once.
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                let barrier = Arc::clone(&barrier);
+                let db_path = db_path.clone();
+                std::thread::spawn(move || {
+                    barrier.wait();
+                    crate::storage::Database::open(&db_path).map(|db| db.schema_version())
+                })
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap().unwrap(), SCHEMA_VERSION);
+        }
+
+        let report = inspect(&db_path).unwrap();
+        assert_eq!(report.version, SCHEMA_VERSION);
+        assert!(report.pending.is_empty());
+        assert!(report.partial.is_empty());
+        assert!(MigrationLock::path_for(&db_path).ends_with("ms.db.migrate.lock"));
+    }
+
     #[test]
     fn run_migrations_creates_indexes() {
         let conn = Connection::open(":memory:").unwrap();
@@ -9,7 +9,10 @@ pub mod sqlite_compat;
 pub mod tombstone;
 pub mod tx;
 
-pub use git::GitArchive;
-pub use sqlite::{Database, SkillRecord};
+pub use git::{GitArchive, Provenance, SkillHistoryEntry};
+pub use sqlite::{
+    BanditStateRecord, Database, MinedFeedbackRecord, SkillRecord, SkillTrialRecord,
+    SourceFileRecord, UncertaintyRecord,
+};
 pub use tombstone::{PurgeResult, RestoreResult, TombstoneManager, TombstoneRecord};
-pub use tx::{GlobalLock, RecoveryReport, TxManager, TxPhase, TxRecord};
+pub use tx::{GlobalLock, RecoveryReport, TxManager, TxPhase, TxRecord, lock_timeout};
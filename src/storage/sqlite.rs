@@ -5,7 +5,7 @@
 //! adapters, and the `RowExt`/`OptionalExtension` traits needed for
 //! rusqlite-style ergonomics.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use fsqlite::Connection;
 use fsqlite::Row;
@@ -17,8 +17,9 @@ use uuid::Uuid;
 
 use crate::error::{MsError, Result};
 use crate::ms_params as params;
-use crate::security::{CommandSafetyEvent, QuarantineRecord};
+use crate::security::{CommandSafetyEvent, QuarantineRecord, SafetyOutcome};
 use crate::storage::migrations;
+use crate::storage::tx::GlobalLock;
 
 /// Convenience type alias for row decoders. fsqlite's row mappers return
 /// `FrankenError` so closures can use `?` against `get_typed` cleanly.
@@ -42,6 +43,9 @@ type CommandSafetyRawRow = (
 pub struct Database {
     conn: Connection,
     schema_version: u32,
+    /// The global writer lock next to the database file, read to name the
+    /// process holding the database when a write stays busy
+    writer_lock: Option<PathBuf>,
 }
 
 impl std::fmt::Debug for Database {
@@ -75,6 +79,56 @@ pub struct SkillRecord {
     pub deprecation_reason: Option<String>,
 }
 
+impl SkillRecord {
+    /// Whether the body is the encrypted envelope of a `sensitive: true`
+    /// skill. Commands that show a skill to its owner decrypt it with
+    /// [`reveal_body`](crate::security::encryption::reveal_body); duplicate
+    /// detection and exports skip it rather than handle ciphertext or write
+    /// the plaintext elsewhere.
+    #[must_use]
+    pub fn is_sealed(&self) -> bool {
+        crate::security::encryption::is_sealed_body(&self.body)
+    }
+}
+
+/// Evidence left behind by deleted skills ([`Database::orphaned_evidence`]).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OrphanedEvidence {
+    pub skill_ids: Vec<String>,
+    pub rows: u64,
+    /// Sum of the rows' column lengths; the on-disk size is somewhat larger.
+    pub estimated_bytes: u64,
+}
+
+/// Order of [`Database::list_skills_filtered`] results; ties break on id.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SkillListSort {
+    #[default]
+    Name,
+    /// Most recently modified first
+    Updated,
+    /// Highest quality first
+    Quality,
+}
+
+/// Predicates pushed into the `ms list` query. Unset fields match everything.
+#[derive(Debug, Clone, Default)]
+pub struct SkillListQuery {
+    /// Tags the skill must carry, all of them (case-insensitive); author and
+    /// derived tags both count
+    pub tags: Vec<String>,
+    /// Accepted `source_layer` values
+    pub layers: Vec<String>,
+    pub min_quality: Option<f64>,
+    /// `Some(true)` only deprecated skills, `Some(false)` only active ones
+    pub deprecated: Option<bool>,
+    /// Only skills modified at or after this instant
+    pub updated_since: Option<chrono::DateTime<chrono::Utc>>,
+    pub sort: SkillListSort,
+    pub limit: usize,
+    pub offset: usize,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct EmbeddingRecord {
     pub skill_id: String,
@@ -89,6 +143,7 @@ pub struct EmbeddingRecord {
 pub struct SkillSearchCandidate {
     pub id: String,
     pub source_layer: String,
+    pub source_path: String,
     pub metadata_json: String,
     pub quality_score: f64,
     pub is_deprecated: bool,
@@ -109,6 +164,49 @@ pub struct AliasRecord {
     pub created_at: String,
 }
 
+/// Identity fields of an indexed skill, for resolving names without loading bodies
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkillKey {
+    pub id: String,
+    pub name: String,
+    pub source_layer: String,
+    pub source_path: String,
+    pub indexed_at: String,
+}
+
+/// Token consumption of one stored `source_layer` value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayerTokenUsage {
+    pub layer: String,
+    /// Sum of positive stored token counts
+    pub tokens: u64,
+    /// Number of non-deprecated skills
+    pub skills: u64,
+}
+
+/// Indexed state of one `SKILL.md` file, for incremental `ms index`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceFileRecord {
+    pub path: String,
+    pub skill_id: String,
+    pub layer: String,
+    /// SHA-256 of the file bytes
+    pub content_hash: String,
+    pub mtime_ms: i64,
+    pub size: i64,
+    pub indexed_at: String,
+}
+
+/// Serialized bandit state with its optimistic-concurrency version
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BanditStateRecord {
+    pub name: String,
+    pub state_json: String,
+    /// Incremented on every write; 0 means no row exists yet
+    pub version: i64,
+    pub updated_at: String,
+}
+
 /// Cached session quality score
 #[derive(Debug, Clone, PartialEq)]
 pub struct SessionQualityRecord {
@@ -138,6 +236,118 @@ pub struct QuarantineReview {
     pub created_at: String,
 }
 
+/// Raw content held for a quarantine record (`injection_quarantine_content`).
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct QuarantineContent {
+    pub quarantine_id: String,
+    pub source: String,
+    pub content: String,
+    pub released_at: Option<String>,
+}
+
+/// A remembered choice for a layer conflict (`layer_resolutions`).
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct LayerResolutionRecord {
+    pub skill_id: String,
+    /// Hash of the conflicting candidates' layers and content hashes
+    pub candidates_hash: String,
+    /// Strategy applied, e.g. `prefer-project` or `merge`
+    pub strategy: String,
+    pub resolved_at: String,
+}
+
+/// A tag `ms index` derived from a skill's content (`skill_derived_tags`).
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, serde::Serialize)]
+pub struct DerivedTagRecord {
+    pub tag: String,
+    /// `language` or `framework`
+    pub kind: String,
+}
+
+/// A file that travels with a skill (`skill_resources`).
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct SkillResourceRecord {
+    /// Relative to the skill directory, `/`-separated
+    pub path: String,
+    pub size: i64,
+    /// Hex SHA-256 of the content
+    pub content_hash: String,
+}
+
+/// A persisted suggestion cooldown (`suggestion_cooldowns`).
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct SuggestionCooldownRecord {
+    pub skill_id: String,
+    /// Context fingerprint as 16 hex digits
+    pub context_fingerprint: String,
+    /// Agent that triggered the cooldown, if known
+    pub agent_id: Option<String>,
+    /// `shown`, `accepted` or `dismissed`
+    pub response: String,
+    /// Negative feedback count when the cooldown started
+    pub dismissals: u32,
+    pub started_at: String,
+    pub expires_at: String,
+}
+
+/// A skill pair marked "not duplicates" (`dedup_dismissals`).
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct DedupDismissalRecord {
+    /// The lesser skill id of the pair
+    pub skill_a: String,
+    pub skill_b: String,
+    pub reason: Option<String>,
+    pub dismissed_at: String,
+}
+
+/// A (session, skill) pair scored by `ms feedback mine` (`mined_feedback`).
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct MinedFeedbackRecord {
+    pub session_id: String,
+    pub skill_id: String,
+    /// `success`, `failure` or `inconclusive`
+    pub verdict: String,
+    pub reward: f64,
+    /// The session signals the verdict was inferred from, as JSON
+    pub signals_json: String,
+    pub mined_at: String,
+}
+
+/// A skill loaded on trial (`skill_trials`).
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct SkillTrialRecord {
+    pub id: String,
+    pub skill_id: String,
+    /// Hex fingerprint of the working context at load time
+    pub context_fingerprint: String,
+    /// Disclosure level served (`minimal`, `overview`, `standard`, ...)
+    pub disclosure_level: String,
+    pub started_at: String,
+    /// `success`, `failure`, `neutral` or `expired`; `None` while open
+    pub outcome: Option<String>,
+    pub notes: Option<String>,
+    pub closed_at: Option<String>,
+}
+
+/// A queued low-confidence generalization (`uncertainty_queue`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct UncertaintyRecord {
+    pub id: String,
+    /// The generalized instance; rejected keys are not queued again
+    pub instance_key: Option<String>,
+    /// `UncertaintyReason` type, e.g. `high_variance`
+    pub reason: String,
+    pub confidence: f64,
+    /// `UncertaintyStatus` name, e.g. `pending` or `rejected`
+    pub status: String,
+    pub pattern_json: String,
+    pub suggested_queries: String,
+    /// The full `UncertaintyItem`
+    pub item_json: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct SkillFeedbackRecord {
     pub id: String,
@@ -148,6 +358,17 @@ pub struct SkillFeedbackRecord {
     pub created_at: String,
 }
 
+/// Suggestion and feedback counts for one skill inside a time window.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct SkillActivity {
+    pub suggestions_shown: u64,
+    pub suggestions_selected: u64,
+    /// Shown suggestions with a final outcome (selected, ignored, or hidden)
+    pub suggestions_decided: u64,
+    pub positive_feedback: u64,
+    pub negative_feedback: u64,
+}
+
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct UserPreferenceRecord {
     pub id: String,
@@ -197,11 +418,33 @@ impl Database {
         let conn = Connection::open(path.to_string_lossy().into_owned())?;
 
         Self::configure_pragmas(&conn)?;
-        let schema_version = migrations::run_migrations(&conn)?;
+        let schema_version = migrations::migrate(&conn, path)?;
+
+        Ok(Self {
+            conn,
+            schema_version,
+            writer_lock: path.parent().map(GlobalLock::lock_path),
+        })
+    }
 
+    /// Open an existing database as is: no migrations, no directories
+    /// created. For read-only fast paths such as shell completion, which
+    /// must not pay for (or fail on) a full open.
+    pub fn open_existing(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.is_file() {
+            return Err(MsError::NotFound(format!(
+                "database not found at {}",
+                path.display()
+            )));
+        }
+        let conn = Connection::open(path.to_string_lossy().into_owned())?;
+        conn.execute("PRAGMA busy_timeout = 200")?;
+        let schema_version = migrations::current_version(&conn)?;
         Ok(Self {
             conn,
             schema_version,
+            writer_lock: path.parent().map(GlobalLock::lock_path),
         })
     }
 
@@ -241,6 +484,227 @@ impl Database {
         Ok(results)
     }
 
+    /// Up to `limit` skills with ids after `after`, in id order, for cursor
+    /// paging. Deprecated skills are skipped unless `include_deprecated`.
+    pub fn list_skills_after(
+        &self,
+        after: Option<&str>,
+        limit: usize,
+        include_deprecated: bool,
+    ) -> Result<Vec<SkillRecord>> {
+        let sql = "SELECT id, name, description, version, author, source_path, source_layer, \
+             git_remote, git_commit, content_hash, body, metadata_json, assets_json, \
+             token_count, quality_score, indexed_at, modified_at, is_deprecated, deprecation_reason \
+             FROM skills WHERE id > ? AND (? = 1 OR is_deprecated = 0) ORDER BY id LIMIT ?";
+        let results = self.conn.query_map_collect(
+            sql,
+            params![
+                after.unwrap_or_default(),
+                i32::from(include_deprecated),
+                limit as i64
+            ],
+            skill_from_row,
+        )?;
+        Ok(results)
+    }
+
+    /// List skills matching `query`, filtered, sorted, and paged in SQL.
+    ///
+    /// Tag predicates read the denormalized `skill_tags` table, so skills
+    /// indexed before it existed only match after [`Self::backfill_skill_tags`].
+    pub fn list_skills_filtered(&self, query: &SkillListQuery) -> Result<Vec<SkillRecord>> {
+        use crate::storage::sqlite_compat::ToParam;
+
+        let mut sql = "SELECT id, name, description, version, author, source_path, source_layer, \
+             git_remote, git_commit, content_hash, body, metadata_json, assets_json, \
+             token_count, quality_score, indexed_at, modified_at, is_deprecated, deprecation_reason \
+             FROM skills WHERE 1 = 1"
+            .to_string();
+        let mut values = Vec::new();
+
+        for tag in &query.tags {
+            sql.push_str(
+                " AND (id IN (SELECT skill_id FROM skill_tags WHERE tag = ?) \
+                 OR id IN (SELECT skill_id FROM skill_derived_tags WHERE tag = ?))",
+            );
+            let tag = tag.trim().to_lowercase();
+            values.push(tag.to_param());
+            values.push(tag.to_param());
+        }
+        if !query.layers.is_empty() {
+            let placeholders = vec!["?"; query.layers.len()].join(", ");
+            sql.push_str(&format!(" AND source_layer IN ({placeholders})"));
+            values.extend(query.layers.iter().map(ToParam::to_param));
+        }
+        if let Some(min_quality) = query.min_quality {
+            sql.push_str(" AND quality_score >= ?");
+            values.push(min_quality.to_param());
+        }
+        if let Some(deprecated) = query.deprecated {
+            sql.push_str(" AND is_deprecated = ?");
+            values.push(i32::from(deprecated).to_param());
+        }
+        if let Some(since) = query.updated_since {
+            // `modified_at` is RFC 3339 from `ms index` but `datetime('now')`
+            // from the 2PC path; normalize both sides before comparing.
+            sql.push_str(" AND datetime(modified_at) >= datetime(?)");
+            values.push(since.format("%Y-%m-%d %H:%M:%S").to_string().to_param());
+        }
+
+        sql.push_str(match query.sort {
+            SkillListSort::Name => " ORDER BY name ASC, id ASC",
+            SkillListSort::Updated => " ORDER BY modified_at DESC, id ASC",
+            SkillListSort::Quality => " ORDER BY quality_score DESC, id ASC",
+        });
+        sql.push_str(" LIMIT ? OFFSET ?");
+        values.push((query.limit as i64).to_param());
+        values.push((query.offset as i64).to_param());
+
+        let results = self
+            .conn
+            .query_map_collect(&sql, values.as_slice(), skill_from_row)?;
+        Ok(results)
+    }
+
+    /// Replace the denormalized tag rows for a skill.
+    fn replace_skill_tags(&self, skill_id: &str, tags: &[String]) -> Result<()> {
+        self.conn.execute_compat(
+            "DELETE FROM skill_tags WHERE skill_id = ?",
+            params![skill_id],
+        )?;
+        for tag in normalize_tags(tags.iter().map(String::as_str)) {
+            self.conn.execute_compat(
+                "INSERT INTO skill_tags (skill_id, tag) VALUES (?, ?)",
+                params![skill_id, tag],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Populate `skill_tags` for skills that have no tag rows yet (indexed
+    /// before the table existed). Returns how many skills gained tags.
+    pub fn backfill_skill_tags(&self) -> Result<usize> {
+        let rows: Vec<(String, String)> = self.conn.query_map_collect(
+            "SELECT id, metadata_json FROM skills \
+             WHERE id NOT IN (SELECT DISTINCT skill_id FROM skill_tags)",
+            params![],
+            |row| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+        )?;
+        let mut filled = 0;
+        for (id, metadata_json) in rows {
+            let tags = tags_from_metadata_json(&metadata_json);
+            if !tags.is_empty() {
+                self.in_transaction(|_| self.replace_skill_tags(&id, &tags))?;
+                filled += 1;
+            }
+        }
+        Ok(filled)
+    }
+
+    /// Replace the tags `ms index` derived for a skill.
+    pub fn replace_derived_tags(&self, skill_id: &str, tags: &[DerivedTagRecord]) -> Result<()> {
+        self.in_transaction(|conn| {
+            conn.execute_compat(
+                "DELETE FROM skill_derived_tags WHERE skill_id = ?",
+                params![skill_id],
+            )?;
+            for tag in tags {
+                conn.execute_compat(
+                    "INSERT OR REPLACE INTO skill_derived_tags (skill_id, tag, kind) \
+                     VALUES (?, ?, ?)",
+                    params![skill_id, tag.tag, tag.kind],
+                )?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Derived tags of one skill, sorted by tag.
+    pub fn get_derived_tags(&self, skill_id: &str) -> Result<Vec<DerivedTagRecord>> {
+        let tags = self.conn.query_map_collect(
+            "SELECT tag, kind FROM skill_derived_tags WHERE skill_id = ? ORDER BY tag",
+            params![skill_id],
+            |row| {
+                Ok(DerivedTagRecord {
+                    tag: row.get_typed(0)?,
+                    kind: row.get_typed(1)?,
+                })
+            },
+        )?;
+        Ok(tags)
+    }
+
+    /// Replace the resource files recorded for a skill.
+    pub fn replace_skill_resources(
+        &self,
+        skill_id: &str,
+        resources: &[SkillResourceRecord],
+    ) -> Result<()> {
+        self.in_transaction(|conn| {
+            conn.execute_compat(
+                "DELETE FROM skill_resources WHERE skill_id = ?",
+                params![skill_id],
+            )?;
+            for resource in resources {
+                conn.execute_compat(
+                    "INSERT OR REPLACE INTO skill_resources (skill_id, path, size, content_hash) \
+                     VALUES (?, ?, ?, ?)",
+                    params![
+                        skill_id,
+                        resource.path,
+                        resource.size,
+                        resource.content_hash
+                    ],
+                )?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Resource files of one skill, sorted by path.
+    pub fn get_skill_resources(&self, skill_id: &str) -> Result<Vec<SkillResourceRecord>> {
+        let resources = self.conn.query_map_collect(
+            "SELECT path, size, content_hash FROM skill_resources \
+             WHERE skill_id = ? ORDER BY path",
+            params![skill_id],
+            |row| {
+                Ok(SkillResourceRecord {
+                    path: row.get_typed(0)?,
+                    size: row.get_typed(1)?,
+                    content_hash: row.get_typed(2)?,
+                })
+            },
+        )?;
+        Ok(resources)
+    }
+
+    /// Ids of skills without derived tags: indexed before enrichment existed,
+    /// or with nothing to derive.
+    pub fn skills_without_derived_tags(&self) -> Result<Vec<String>> {
+        let ids = self.conn.query_map_collect(
+            "SELECT id FROM skills \
+             WHERE id NOT IN (SELECT DISTINCT skill_id FROM skill_derived_tags) ORDER BY id",
+            params![],
+            |row| row.get_typed(0),
+        )?;
+        Ok(ids)
+    }
+
+    /// Derived tag names of every skill that has any, keyed by skill id.
+    pub fn derived_tags_by_skill(&self) -> Result<std::collections::HashMap<String, Vec<String>>> {
+        let rows: Vec<(String, String)> = self.conn.query_map_collect(
+            "SELECT skill_id, tag FROM skill_derived_tags ORDER BY skill_id, tag",
+            params![],
+            |row| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+        )?;
+        let mut tags: std::collections::HashMap<String, Vec<String>> =
+            std::collections::HashMap::new();
+        for (skill_id, tag) in rows {
+            tags.entry(skill_id).or_default().push(tag);
+        }
+        Ok(tags)
+    }
+
     /// Update quality score for a skill.
     pub fn update_skill_quality(&self, skill_id: &str, quality_score: f64) -> Result<()> {
         self.conn.execute_compat(
@@ -250,6 +714,40 @@ impl Database {
         Ok(())
     }
 
+    /// Update the stored token count for a skill.
+    pub fn update_skill_token_count(&self, skill_id: &str, token_count: i64) -> Result<()> {
+        self.conn.execute_compat(
+            "UPDATE skills SET token_count = ? WHERE id = ?",
+            params![token_count, skill_id],
+        )?;
+        Ok(())
+    }
+
+    /// A value from the `config` key-value table.
+    pub fn get_config_value(&self, key: &str) -> Result<Option<String>> {
+        let value = self
+            .conn
+            .query_row_map(
+                "SELECT value FROM config WHERE key = ?",
+                params![key],
+                |row| row.get_typed::<String>(0),
+            )
+            .optional()?;
+        Ok(value)
+    }
+
+    /// Store a value in the `config` key-value table.
+    pub fn set_config_value(&self, key: &str, value: &str) -> Result<()> {
+        self.conn.execute_compat(
+            "INSERT INTO config (key, value, updated_at) VALUES (?, ?, ?)
+             ON CONFLICT(key) DO UPDATE SET
+                value=excluded.value,
+                updated_at=excluded.updated_at",
+            params![key, value, chrono::Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
     /// Update deprecation status and reason for a skill.
     pub fn update_skill_deprecation(
         &self,
@@ -264,6 +762,16 @@ impl Database {
         Ok(())
     }
 
+    /// Count indexed skills.
+    pub fn count_skills(&self) -> Result<u64> {
+        let count: i64 =
+            self.conn
+                .query_row_map("SELECT COUNT(*) FROM skills", params![], |row| {
+                    row.get_typed::<i64>(0)
+                })?;
+        Ok(count.max(0) as u64)
+    }
+
     /// Count usage events for a skill.
     pub fn count_skill_usage(&self, skill_id: &str) -> Result<u64> {
         let count: i64 = self.conn.query_row_map(
@@ -332,6 +840,137 @@ impl Database {
         ))
     }
 
+    /// Identity fields of every indexed skill, ordered by id.
+    pub fn list_skill_keys(&self) -> Result<Vec<SkillKey>> {
+        let keys = self.conn.query_map_collect(
+            "SELECT id, name, source_layer, source_path, indexed_at FROM skills ORDER BY id",
+            params![],
+            |row| {
+                Ok(SkillKey {
+                    id: row.get_typed(0)?,
+                    name: row.get_typed(1)?,
+                    source_layer: row.get_typed(2)?,
+                    source_path: row.get_typed(3)?,
+                    indexed_at: row.get_typed(4)?,
+                })
+            },
+        )?;
+        Ok(keys)
+    }
+
+    /// Count non-deprecated skills indexed before `cutoff` with no recorded
+    /// load at or after it. Skills indexed inside the window have not had the
+    /// chance to be used yet, so they are not counted.
+    pub fn count_skills_unused_since(&self, cutoff: &str) -> Result<u64> {
+        let count: i64 = self.conn.query_row_map(
+            "SELECT COUNT(*) FROM skills s
+             WHERE s.is_deprecated = 0
+               AND s.indexed_at < ?
+               AND NOT EXISTS (
+                   SELECT 1 FROM skill_usage u WHERE u.skill_id = s.id AND u.used_at >= ?
+               )",
+            params![cutoff, cutoff],
+            |row| row.get_typed::<i64>(0),
+        )?;
+        Ok(count.max(0) as u64)
+    }
+
+    /// Count quarantined injection records that have no review yet.
+    pub fn count_unreviewed_quarantine(&self) -> Result<u64> {
+        let count: i64 = self.conn.query_row_map(
+            "SELECT COUNT(*) FROM injection_quarantine q
+             WHERE NOT EXISTS (
+                 SELECT 1 FROM injection_quarantine_reviews r
+                 WHERE r.quarantine_id = q.quarantine_id
+             )",
+            params![],
+            |row| row.get_typed::<i64>(0),
+        )?;
+        Ok(count.max(0) as u64)
+    }
+
+    /// Ids of skills whose metadata marks them `sensitive`.
+    pub fn sensitive_skill_ids(&self) -> Result<Vec<String>> {
+        let rows: Vec<(String, String)> = self.conn.query_map_collect(
+            "SELECT id, metadata_json FROM skills ORDER BY id",
+            params![],
+            |row| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+        )?;
+        Ok(rows
+            .into_iter()
+            .filter(|(_, metadata_json)| {
+                serde_json::from_str::<serde_json::Value>(metadata_json)
+                    .ok()
+                    .and_then(|meta| meta.get("sensitive").and_then(serde_json::Value::as_bool))
+                    .unwrap_or(false)
+            })
+            .map(|(id, _)| id)
+            .collect())
+    }
+
+    /// Stored token totals of non-deprecated skills, keyed by lowercase layer.
+    ///
+    /// Uses the stored counts as-is; see `core::budget` for recomputation.
+    pub fn layer_token_totals(&self) -> Result<std::collections::BTreeMap<String, u64>> {
+        let mut totals = std::collections::BTreeMap::new();
+        for usage in self.layer_token_usage()? {
+            *totals.entry(usage.layer.to_lowercase()).or_insert(0) += usage.tokens;
+        }
+        Ok(totals)
+    }
+
+    /// Stored tokens and skill count of non-deprecated skills per
+    /// `source_layer` value as stored (callers merge case variants).
+    pub fn layer_token_usage(&self) -> Result<Vec<LayerTokenUsage>> {
+        let rows: Vec<(String, i64, i64)> = self.conn.query_map_collect(
+            "SELECT source_layer, \
+             SUM(CASE WHEN token_count > 0 THEN token_count ELSE 0 END), COUNT(*) \
+             FROM skills WHERE is_deprecated = 0 GROUP BY source_layer",
+            params![],
+            |row| Ok((row.get_typed(0)?, row.get_typed(1)?, row.get_typed(2)?)),
+        )?;
+        Ok(rows
+            .into_iter()
+            .map(|(layer, tokens, skills)| LayerTokenUsage {
+                layer,
+                tokens: tokens.max(0) as u64,
+                skills: skills.max(0) as u64,
+            })
+            .collect())
+    }
+
+    /// The `limit` non-deprecated skills stored under `source_layer` with the
+    /// largest stored token counts, as `(id, tokens)`.
+    pub fn largest_layer_skills(
+        &self,
+        source_layer: &str,
+        limit: usize,
+    ) -> Result<Vec<(String, u64)>> {
+        let rows: Vec<(String, i64)> = self.conn.query_map_collect(
+            "SELECT id, token_count FROM skills \
+             WHERE is_deprecated = 0 AND source_layer = ? \
+             ORDER BY token_count DESC, id LIMIT ?",
+            params![source_layer, limit as i64],
+            |row| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+        )?;
+        Ok(rows
+            .into_iter()
+            .map(|(id, tokens)| (id, tokens.max(0) as u64))
+            .collect())
+    }
+
+    /// Non-deprecated skills whose stored token count is stale (zero or
+    /// negative, as left behind by re-indexing).
+    pub fn skills_with_stale_token_counts(&self) -> Result<Vec<SkillRecord>> {
+        let sql = "SELECT id, name, description, version, author, source_path, source_layer, \
+             git_remote, git_commit, content_hash, body, metadata_json, assets_json, \
+             token_count, quality_score, indexed_at, modified_at, is_deprecated, deprecation_reason \
+             FROM skills WHERE is_deprecated = 0 AND token_count <= 0 ORDER BY id";
+        Ok(self
+            .conn
+            .query_map_collect(sql, params![], skill_from_row)?)
+    }
+
     /// Record a skill usage entry (lightweight summary table).
     pub fn record_skill_usage(
         &self,
@@ -341,6 +980,7 @@ impl Database {
         context_keywords: Option<&[String]>,
         experiment_id: Option<&str>,
         variant_id: Option<&str>,
+        session_id: Option<&str>,
     ) -> Result<()> {
         let used_at = chrono::Utc::now().to_rfc3339();
         let keywords_json = if let Some(keys) = context_keywords {
@@ -354,8 +994,8 @@ impl Database {
 
         self.conn.execute_compat(
             "INSERT INTO skill_usage (
-                skill_id, project_path, used_at, disclosure_level, context_keywords, success_signal, experiment_id, variant_id
-             ) VALUES (?, ?, ?, ?, ?, NULL, ?, ?)",
+                skill_id, project_path, used_at, disclosure_level, context_keywords, success_signal, experiment_id, variant_id, session_id
+             ) VALUES (?, ?, ?, ?, ?, NULL, ?, ?, ?)",
             params![
                 skill_id,
                 project_path,
@@ -363,87 +1003,189 @@ impl Database {
                 i64::from(disclosure_level),
                 keywords_json,
                 experiment_id,
-                variant_id
+                variant_id,
+                session_id
             ],
         )?;
         Ok(())
     }
 
-    /// Count evidence records for a skill.
-    pub fn count_skill_evidence(&self, skill_id: &str) -> Result<u64> {
-        let count: i64 = self.conn.query_row_map(
-            "SELECT COUNT(*) FROM skill_evidence WHERE skill_id = ?",
-            params![skill_id],
-            |row| row.get_typed::<i64>(0),
-        )?;
-        Ok(count.max(0) as u64)
-    }
-
-    pub fn upsert_skill(&self, skill: &SkillRecord) -> Result<()> {
-        self.conn.execute_compat(
-            "INSERT INTO skills (
-                id, name, description, version, author, source_path, source_layer,
-                git_remote, git_commit, content_hash, body, metadata_json, assets_json,
-                token_count, quality_score, indexed_at, modified_at, is_deprecated, deprecation_reason
-             ) VALUES (
-                ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?
-             )
-             ON CONFLICT(id) DO UPDATE SET
-                name=excluded.name,
-                description=excluded.description,
-                version=excluded.version,
-                author=excluded.author,
-                source_path=excluded.source_path,
-                source_layer=excluded.source_layer,
-                git_remote=excluded.git_remote,
-                git_commit=excluded.git_commit,
-                content_hash=excluded.content_hash,
-                body=excluded.body,
-                metadata_json=excluded.metadata_json,
-                assets_json=excluded.assets_json,
-                token_count=excluded.token_count,
-                quality_score=excluded.quality_score,
-                indexed_at=excluded.indexed_at,
-                modified_at=excluded.modified_at,
-                is_deprecated=excluded.is_deprecated,
-                deprecation_reason=excluded.deprecation_reason",
-            params![
-                skill.id,
-                skill.name,
-                skill.description,
-                skill.version,
-                skill.author,
-                skill.source_path,
-                skill.source_layer,
-                skill.git_remote,
-                skill.git_commit,
-                skill.content_hash,
-                skill.body,
-                skill.metadata_json,
-                skill.assets_json,
-                skill.token_count,
-                skill.quality_score,
-                skill.indexed_at,
-                skill.modified_at,
-                i32::from(skill.is_deprecated),
-                skill.deprecation_reason,
-            ],
+    /// All recorded loads as (skill_id, session_id, project_path, used_at).
+    pub fn list_skill_loads(
+        &self,
+    ) -> Result<Vec<(String, Option<String>, Option<String>, String)>> {
+        let rows = self.conn.query_map_collect(
+            "SELECT skill_id, session_id, project_path, used_at FROM skill_usage ORDER BY used_at",
+            params![],
+            |row| {
+                Ok((
+                    row.get_typed(0)?,
+                    row.get_typed(1)?,
+                    row.get_typed(2)?,
+                    row.get_typed(3)?,
+                ))
+            },
         )?;
-        Ok(())
+        Ok(rows)
+    }
+
+    /// Loads recorded at or after `cutoff` as (skill_id, used_at), oldest first.
+    pub fn list_skill_loads_since(&self, cutoff: &str) -> Result<Vec<(String, String)>> {
+        let rows = self.conn.query_map_collect(
+            "SELECT skill_id, used_at FROM skill_usage WHERE used_at >= ? ORDER BY used_at",
+            params![cutoff],
+            |row| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+        )?;
+        Ok(rows)
+    }
+
+    /// Suggestion outcomes and feedback per skill, recorded at or after `cutoff`.
+    ///
+    /// Ratings of 4-5 count as positive feedback and 1-2 as negative.
+    pub fn skill_activity_since(
+        &self,
+        cutoff: &str,
+    ) -> Result<std::collections::HashMap<String, SkillActivity>> {
+        let mut activity: std::collections::HashMap<String, SkillActivity> =
+            std::collections::HashMap::new();
+
+        let suggestions: Vec<(String, String, i64)> = self.conn.query_map_collect(
+            "SELECT skill_id, outcome, COUNT(*) FROM suggestion_records
+             WHERE shown_at >= ? GROUP BY skill_id, outcome",
+            params![cutoff],
+            |row| Ok((row.get_typed(0)?, row.get_typed(1)?, row.get_typed(2)?)),
+        )?;
+        for (skill_id, outcome, count) in suggestions {
+            let count = count.max(0) as u64;
+            let entry = activity.entry(skill_id).or_default();
+            entry.suggestions_shown += count;
+            match crate::suggestions::SuggestionOutcome::from_key(&outcome) {
+                crate::suggestions::SuggestionOutcome::Pending => {}
+                crate::suggestions::SuggestionOutcome::Selected => {
+                    entry.suggestions_selected += count;
+                    entry.suggestions_decided += count;
+                }
+                _ => entry.suggestions_decided += count,
+            }
+        }
+
+        let feedback: Vec<(String, String, Option<i64>, i64)> = self.conn.query_map_collect(
+            "SELECT skill_id, feedback_type, rating, COUNT(*) FROM skill_feedback
+             WHERE created_at >= ? GROUP BY skill_id, feedback_type, rating",
+            params![cutoff],
+            |row| {
+                Ok((
+                    row.get_typed(0)?,
+                    row.get_typed(1)?,
+                    row.get_typed(2)?,
+                    row.get_typed(3)?,
+                ))
+            },
+        )?;
+        for (skill_id, feedback_type, rating, count) in feedback {
+            let count = count.max(0) as u64;
+            let entry = activity.entry(skill_id).or_default();
+            match (feedback_type.as_str(), rating) {
+                ("positive", _) | ("rating", Some(4..)) => entry.positive_feedback += count,
+                ("negative", _) | ("rating", Some(..=2)) => entry.negative_feedback += count,
+                _ => {}
+            }
+        }
+
+        Ok(activity)
+    }
+
+    /// Count evidence records for a skill.
+    pub fn count_skill_evidence(&self, skill_id: &str) -> Result<u64> {
+        let count: i64 = self.conn.query_row_map(
+            "SELECT COUNT(*) FROM skill_evidence WHERE skill_id = ?",
+            params![skill_id],
+            |row| row.get_typed::<i64>(0),
+        )?;
+        Ok(count.max(0) as u64)
+    }
+
+    pub fn upsert_skill(&self, skill: &SkillRecord) -> Result<()> {
+        self.in_transaction(|conn| {
+            conn.execute_compat(
+                "INSERT INTO skills (
+                    id, name, description, version, author, source_path, source_layer,
+                    git_remote, git_commit, content_hash, body, metadata_json, assets_json,
+                    token_count, quality_score, indexed_at, modified_at, is_deprecated, deprecation_reason
+                 ) VALUES (
+                    ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?
+                 )
+                 ON CONFLICT(id) DO UPDATE SET
+                    name=excluded.name,
+                    description=excluded.description,
+                    version=excluded.version,
+                    author=excluded.author,
+                    source_path=excluded.source_path,
+                    source_layer=excluded.source_layer,
+                    git_remote=excluded.git_remote,
+                    git_commit=excluded.git_commit,
+                    content_hash=excluded.content_hash,
+                    body=excluded.body,
+                    metadata_json=excluded.metadata_json,
+                    assets_json=excluded.assets_json,
+                    token_count=excluded.token_count,
+                    quality_score=excluded.quality_score,
+                    indexed_at=excluded.indexed_at,
+                    modified_at=excluded.modified_at,
+                    is_deprecated=excluded.is_deprecated,
+                    deprecation_reason=excluded.deprecation_reason",
+                params![
+                    skill.id,
+                    skill.name,
+                    skill.description,
+                    skill.version,
+                    skill.author,
+                    skill.source_path,
+                    skill.source_layer,
+                    skill.git_remote,
+                    skill.git_commit,
+                    skill.content_hash,
+                    skill.body,
+                    skill.metadata_json,
+                    skill.assets_json,
+                    skill.token_count,
+                    skill.quality_score,
+                    skill.indexed_at,
+                    skill.modified_at,
+                    i32::from(skill.is_deprecated),
+                    skill.deprecation_reason,
+                ],
+            )?;
+            self.replace_skill_tags(&skill.id, &tags_from_metadata_json(&skill.metadata_json))
+        })
     }
 
     pub fn delete_skill(&self, id: &str) -> Result<()> {
-        self.conn
-            .execute_compat("DELETE FROM skills WHERE id = ?", params![id])?;
-        Ok(())
+        self.in_transaction(|conn| {
+            conn.execute_compat("DELETE FROM skills WHERE id = ?", params![id])?;
+            conn.execute_compat("DELETE FROM skill_tags WHERE skill_id = ?", params![id])?;
+            conn.execute_compat(
+                "DELETE FROM skill_derived_tags WHERE skill_id = ?",
+                params![id],
+            )?;
+            conn.execute_compat(
+                "DELETE FROM skill_resources WHERE skill_id = ?",
+                params![id],
+            )?;
+            Ok(())
+        })
     }
 
     /// Delete a skill only if it has pending status
     pub fn delete_pending_skill(&self, id: &str) -> Result<()> {
-        self.conn.execute_compat(
+        let deleted = self.conn.execute_compat(
             "DELETE FROM skills WHERE id = ? AND source_path = 'pending'",
             params![id],
         )?;
+        if deleted > 0 {
+            self.conn
+                .execute_compat("DELETE FROM skill_tags WHERE skill_id = ?", params![id])?;
+        }
         Ok(())
     }
 
@@ -499,6 +1241,36 @@ impl Database {
         Ok(count > 0)
     }
 
+    /// Skill ids starting with `prefix`, sorted, at most `limit`
+    pub fn skill_ids_with_prefix(&self, prefix: &str, limit: usize) -> Result<Vec<String>> {
+        self.values_with_prefix("skills", "id", prefix, limit)
+    }
+
+    /// Aliases starting with `prefix`, sorted, at most `limit`
+    pub fn aliases_with_prefix(&self, prefix: &str, limit: usize) -> Result<Vec<String>> {
+        self.values_with_prefix("skill_aliases", "alias", prefix, limit)
+    }
+
+    fn values_with_prefix(
+        &self,
+        table: &str,
+        column: &str,
+        prefix: &str,
+        limit: usize,
+    ) -> Result<Vec<String>> {
+        // `substr` rather than LIKE: ids may contain `_` and `%`.
+        let sql = format!(
+            "SELECT {column} FROM {table} WHERE substr({column}, 1, ?) = ? \
+             ORDER BY {column} LIMIT ?"
+        );
+        let values = self.conn.query_map_collect(
+            &sql,
+            params![prefix.chars().count() as i64, prefix, limit as i64],
+            |row| row.get_typed::<String>(0),
+        )?;
+        Ok(values)
+    }
+
     /// List all aliases, optionally filtered by `skill_id`
     pub fn list_aliases(&self, skill_id: Option<&str>) -> Result<Vec<AliasRecord>> {
         let records = if let Some(sid) = skill_id {
@@ -579,7 +1351,7 @@ impl Database {
         }
         let rows: Vec<(SkillSearchCandidate, String)> = self.conn.query_map_collect(
             "SELECT id, source_layer, metadata_json, quality_score, is_deprecated, \
-                    name, description, body \
+                    source_path, name, description, body \
              FROM skills ORDER BY quality_score DESC, id ASC",
             params![],
             |row| {
@@ -589,12 +1361,13 @@ impl Database {
                     metadata_json: row.get_typed(2)?,
                     quality_score: row.get_typed(3)?,
                     is_deprecated: row.get_typed::<i64>(4)? != 0,
+                    source_path: row.get_typed(5)?,
                 };
                 let haystack = format!(
                     "{}\n{}\n{}",
-                    row.get_typed::<String>(5)?,
                     row.get_typed::<String>(6)?,
                     row.get_typed::<String>(7)?,
+                    row.get_typed::<String>(8)?,
                 )
                 .to_lowercase();
                 Ok((candidate, haystack))
@@ -618,7 +1391,7 @@ impl Database {
         let result = self
             .conn
             .query_row_map(
-                "SELECT id, source_layer, metadata_json, quality_score, is_deprecated
+                "SELECT id, source_layer, metadata_json, quality_score, is_deprecated, source_path
                  FROM skills WHERE id = ?",
                 params![id],
                 |row| {
@@ -628,6 +1401,7 @@ impl Database {
                         metadata_json: row.get_typed(2)?,
                         quality_score: row.get_typed(3)?,
                         is_deprecated: row.get_typed::<i64>(4)? != 0,
+                        source_path: row.get_typed(5)?,
                     })
                 },
             )
@@ -744,6 +1518,19 @@ impl Database {
         Ok(results)
     }
 
+    /// Load every stored embedding with its backend and dimension, so the
+    /// caller can reject vectors built by a different embedder.
+    pub fn get_all_embedding_records(&self) -> Result<Vec<EmbeddingRecord>> {
+        let raw = self.conn.query_map_collect(
+            "SELECT skill_id, embedding, dims, embedder_type, content_hash, computed_at, created_at
+             FROM skill_embeddings
+             ORDER BY skill_id",
+            params![],
+            embedding_raw_row,
+        )?;
+        raw.into_iter().map(decode_raw_embedding).collect()
+    }
+
     pub fn insert_quarantine_record(&self, record: &QuarantineRecord) -> Result<()> {
         let classification_json =
             serde_json::to_string(&record.acip_classification).map_err(|err| {
@@ -819,8 +1606,12 @@ impl Database {
                 command,
                 dcg_version,
                 dcg_pack,
+                outcome: SafetyOutcome::of(&decision),
+                matched_rule: decision.rule_id.clone(),
                 decision,
                 created_at,
+                profile: None,
+                subcommand: None,
             });
         }
         Ok(out)
@@ -910,6 +1701,101 @@ impl Database {
         Ok(out)
     }
 
+    /// Store the raw content and source behind a quarantine record.
+    pub fn insert_quarantine_content(
+        &self,
+        quarantine_id: &str,
+        source: &str,
+        content: &str,
+    ) -> Result<()> {
+        self.conn.execute_compat(
+            "INSERT INTO injection_quarantine_content (
+                quarantine_id, source, content, released_at
+             ) VALUES (?, ?, ?, NULL)",
+            params![quarantine_id, source, content],
+        )?;
+        Ok(())
+    }
+
+    /// Raw content for a quarantine record; `None` for records quarantined
+    /// before the content store existed.
+    pub fn get_quarantine_content(&self, quarantine_id: &str) -> Result<Option<QuarantineContent>> {
+        use fsqlite::compat::OptionalExtension;
+        let result = self
+            .conn
+            .query_row_map(
+                "SELECT quarantine_id, source, content, released_at
+                 FROM injection_quarantine_content
+                 WHERE quarantine_id = ?",
+                params![quarantine_id],
+                |row| {
+                    Ok(QuarantineContent {
+                        quarantine_id: row.get_typed(0)?,
+                        source: row.get_typed(1)?,
+                        content: row.get_typed(2)?,
+                        released_at: row.get_typed(3)?,
+                    })
+                },
+            )
+            .optional()?;
+        Ok(result)
+    }
+
+    /// Mark a quarantine record released and log a `release` review.
+    ///
+    /// Returns the review id.
+    pub fn release_quarantine_record(&self, quarantine_id: &str) -> Result<String> {
+        let released_at = chrono::Utc::now().to_rfc3339();
+        let updated = self.conn.execute_compat(
+            "UPDATE injection_quarantine_content SET released_at = ?
+             WHERE quarantine_id = ? AND released_at IS NULL",
+            params![released_at, quarantine_id],
+        )?;
+        if updated == 0 {
+            return Err(MsError::NotFound(format!(
+                "no unreleased quarantined content for {quarantine_id}"
+            )));
+        }
+        self.insert_quarantine_review(quarantine_id, "release", None)
+    }
+
+    /// Delete quarantine records (with their content and reviews) created
+    /// before `cutoff`. Returns the ids removed.
+    pub fn purge_quarantine_before(
+        &self,
+        cutoff: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<String>> {
+        let rows: Vec<(String, String)> = self.conn.query_map_collect(
+            "SELECT quarantine_id, created_at FROM injection_quarantine",
+            params![],
+            |row| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+        )?;
+        let expired: Vec<String> = rows
+            .into_iter()
+            .filter(|(_, created_at)| {
+                chrono::DateTime::parse_from_rfc3339(created_at)
+                    .is_ok_and(|created| created.with_timezone(&chrono::Utc) < cutoff)
+            })
+            .map(|(id, _)| id)
+            .collect();
+
+        for id in &expired {
+            self.conn.execute_compat(
+                "DELETE FROM injection_quarantine_reviews WHERE quarantine_id = ?",
+                params![id],
+            )?;
+            self.conn.execute_compat(
+                "DELETE FROM injection_quarantine_content WHERE quarantine_id = ?",
+                params![id],
+            )?;
+            self.conn.execute_compat(
+                "DELETE FROM injection_quarantine WHERE quarantine_id = ?",
+                params![id],
+            )?;
+        }
+        Ok(expired)
+    }
+
     // =========================================================================
     // TRANSACTION LOG METHODS (for 2PC)
     // =========================================================================
@@ -1032,6 +1918,7 @@ impl Database {
                 token_count,
             ],
         )?;
+        self.replace_skill_tags(&skill.metadata.id, &skill.metadata.tags)?;
         Ok(())
     }
 
@@ -1093,54 +1980,888 @@ impl Database {
                 },
             )
             .optional()?;
-
-        match raw {
-            Some((session_id, content_hash, score, signals_json, missing_json, computed_at)) => {
-                let signals: Vec<String> = serde_json::from_str(&signals_json)
-                    .map_err(|err| MsError::Config(format!("decode signals: {err}")))?;
-                let missing: Vec<String> = serde_json::from_str(&missing_json)
-                    .map_err(|err| MsError::Config(format!("decode missing: {err}")))?;
-                Ok(Some(SessionQualityRecord {
-                    session_id,
-                    content_hash,
-                    score: score as f32,
-                    signals,
-                    missing,
-                    computed_at,
-                }))
-            }
-            None => Ok(None),
-        }
+
+        match raw {
+            Some((session_id, content_hash, score, signals_json, missing_json, computed_at)) => {
+                let signals: Vec<String> = serde_json::from_str(&signals_json)
+                    .map_err(|err| MsError::Config(format!("decode signals: {err}")))?;
+                let missing: Vec<String> = serde_json::from_str(&missing_json)
+                    .map_err(|err| MsError::Config(format!("decode missing: {err}")))?;
+                Ok(Some(SessionQualityRecord {
+                    session_id,
+                    content_hash,
+                    score: score as f32,
+                    signals,
+                    missing,
+                    computed_at,
+                }))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Upsert session quality record
+    pub fn upsert_session_quality(&self, record: &SessionQualityRecord) -> Result<()> {
+        let signals_json = serde_json::to_string(&record.signals)
+            .map_err(|err| MsError::Config(format!("encode signals: {err}")))?;
+        let missing_json = serde_json::to_string(&record.missing)
+            .map_err(|err| MsError::Config(format!("encode missing: {err}")))?;
+
+        self.conn.execute_compat(
+            "INSERT INTO session_quality (session_id, content_hash, score, signals_json, missing_json, computed_at)
+             VALUES (?, ?, ?, ?, ?, ?)
+             ON CONFLICT(session_id) DO UPDATE SET
+                content_hash=excluded.content_hash,
+                score=excluded.score,
+                signals_json=excluded.signals_json,
+                missing_json=excluded.missing_json,
+                computed_at=excluded.computed_at",
+            params![
+                record.session_id,
+                record.content_hash,
+                f64::from(record.score),
+                signals_json,
+                missing_json,
+                record.computed_at,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// All cached session quality records.
+    pub fn list_session_quality(&self) -> Result<Vec<SessionQualityRecord>> {
+        let raw: Vec<(String, String, f64, String, String, String)> = self.conn.query_map_collect(
+            "SELECT session_id, content_hash, score, signals_json, missing_json, computed_at
+             FROM session_quality",
+            params![],
+            |row| {
+                Ok((
+                    row.get_typed(0)?,
+                    row.get_typed(1)?,
+                    row.get_typed(2)?,
+                    row.get_typed(3)?,
+                    row.get_typed(4)?,
+                    row.get_typed(5)?,
+                ))
+            },
+        )?;
+        raw.into_iter()
+            .map(
+                |(session_id, content_hash, score, signals_json, missing_json, computed_at)| {
+                    let signals: Vec<String> = serde_json::from_str(&signals_json)
+                        .map_err(|err| MsError::Config(format!("decode signals: {err}")))?;
+                    let missing: Vec<String> = serde_json::from_str(&missing_json)
+                        .map_err(|err| MsError::Config(format!("decode missing: {err}")))?;
+                    Ok(SessionQualityRecord {
+                        session_id,
+                        content_hash,
+                        score: score as f32,
+                        signals,
+                        missing,
+                        computed_at,
+                    })
+                },
+            )
+            .collect()
+    }
+
+    // =========================================================================
+    // SKILL EFFICACY METHODS
+    // =========================================================================
+
+    /// Replace all stored efficacy statistics with a fresh correlation run.
+    pub fn replace_skill_efficacy(
+        &self,
+        records: &[crate::quality::efficacy::SkillEfficacy],
+    ) -> Result<()> {
+        self.conn
+            .execute_compat("DELETE FROM skill_efficacy", params![])?;
+        for record in records {
+            self.conn.execute_compat(
+                "INSERT INTO skill_efficacy (
+                    skill_id, sessions_with, mean_quality_with, failure_rate_with,
+                    sessions_without, mean_quality_without, failure_rate_without, computed_at
+                 ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+                params![
+                    record.skill_id,
+                    record.sessions_with as i64,
+                    f64::from(record.mean_quality_with),
+                    f64::from(record.failure_rate_with),
+                    record.sessions_without as i64,
+                    record.mean_quality_without.map(f64::from),
+                    record.failure_rate_without.map(f64::from),
+                    record.computed_at,
+                ],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Stored efficacy statistics, optionally for a single skill.
+    pub fn list_skill_efficacy(
+        &self,
+        skill_id: Option<&str>,
+    ) -> Result<Vec<crate::quality::efficacy::SkillEfficacy>> {
+        let map = |row: &Row| -> RowResult<crate::quality::efficacy::SkillEfficacy> {
+            let sessions_with: i64 = row.get_typed(1)?;
+            let mean_quality_with: f64 = row.get_typed(2)?;
+            let failure_rate_with: f64 = row.get_typed(3)?;
+            let sessions_without: i64 = row.get_typed(4)?;
+            let mean_quality_without: Option<f64> = row.get_typed(5)?;
+            let failure_rate_without: Option<f64> = row.get_typed(6)?;
+            Ok(crate::quality::efficacy::SkillEfficacy {
+                skill_id: row.get_typed(0)?,
+                sessions_with: sessions_with.max(0) as u64,
+                mean_quality_with: mean_quality_with as f32,
+                failure_rate_with: failure_rate_with as f32,
+                sessions_without: sessions_without.max(0) as u64,
+                mean_quality_without: mean_quality_without.map(|v| v as f32),
+                failure_rate_without: failure_rate_without.map(|v| v as f32),
+                computed_at: row.get_typed(7)?,
+            })
+        };
+        let sql = "SELECT skill_id, sessions_with, mean_quality_with, failure_rate_with,
+                          sessions_without, mean_quality_without, failure_rate_without, computed_at
+                   FROM skill_efficacy";
+        let records = if let Some(skill_id) = skill_id {
+            self.conn.query_map_collect(
+                &format!("{sql} WHERE skill_id = ?"),
+                params![skill_id],
+                map,
+            )?
+        } else {
+            self.conn
+                .query_map_collect(&format!("{sql} ORDER BY skill_id"), params![], map)?
+        };
+        Ok(records)
+    }
+
+    // =========================================================================
+    // SUGGESTION RECORD METHODS
+    // =========================================================================
+
+    /// Persist a shown suggestion, capping its explanation at `max_explanation_bytes`.
+    pub fn insert_suggestion_record(
+        &self,
+        record: &crate::suggestions::SuggestionRecord,
+        max_explanation_bytes: usize,
+    ) -> Result<()> {
+        let explanation_json = record
+            .explanation
+            .as_ref()
+            .map(|explanation| explanation.encode(max_explanation_bytes))
+            .transpose()?;
+        self.conn.execute_compat(
+            "INSERT INTO suggestion_records (
+                id, skill_id, session_id, agent_id, shown_at, position, context_fingerprint, outcome, explanation_json
+             ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                Uuid::new_v4().to_string(),
+                record.skill_id,
+                record.session_id,
+                record.agent_id,
+                record.shown_at.to_rfc3339(),
+                record.position as i64,
+                record.context_fingerprint_hash.map(|fp| format!("{fp:016x}")),
+                record.outcome.as_str(),
+                explanation_json,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Stored suggestions of a skill, newest first, optionally within one session.
+    pub fn list_suggestion_records(
+        &self,
+        skill_id: &str,
+        session_id: Option<&str>,
+    ) -> Result<Vec<crate::suggestions::SuggestionRecord>> {
+        type RawSuggestion = (
+            String,
+            Option<String>,
+            Option<String>,
+            String,
+            i64,
+            Option<String>,
+            String,
+            Option<String>,
+        );
+        let map = |row: &Row| -> RowResult<RawSuggestion> {
+            Ok((
+                row.get_typed(0)?,
+                row.get_typed(1)?,
+                row.get_typed(2)?,
+                row.get_typed(3)?,
+                row.get_typed(4)?,
+                row.get_typed(5)?,
+                row.get_typed(6)?,
+                row.get_typed(7)?,
+            ))
+        };
+        let sql = "SELECT skill_id, session_id, agent_id, shown_at, position, context_fingerprint, outcome, explanation_json
+                   FROM suggestion_records WHERE skill_id = ?";
+        let raw = if let Some(session_id) = session_id {
+            self.conn.query_map_collect(
+                &format!("{sql} AND session_id = ? ORDER BY shown_at DESC"),
+                params![skill_id, session_id],
+                map,
+            )?
+        } else {
+            self.conn.query_map_collect(
+                &format!("{sql} ORDER BY shown_at DESC"),
+                params![skill_id],
+                map,
+            )?
+        };
+
+        raw.into_iter()
+            .map(
+                |(
+                    skill_id,
+                    session_id,
+                    agent_id,
+                    shown_at,
+                    position,
+                    fingerprint,
+                    outcome,
+                    explanation,
+                )| {
+                    let shown_at = chrono::DateTime::parse_from_rfc3339(&shown_at)
+                        .map_err(|err| MsError::Config(format!("decode shown_at: {err}")))?
+                        .with_timezone(&chrono::Utc);
+                    Ok(crate::suggestions::SuggestionRecord {
+                        skill_id,
+                        shown_at,
+                        context_fingerprint_hash: fingerprint
+                            .and_then(|fp| u64::from_str_radix(&fp, 16).ok()),
+                        position: position.max(0) as usize,
+                        outcome: crate::suggestions::SuggestionOutcome::from_key(&outcome),
+                        session_id,
+                        agent_id,
+                        explanation: explanation
+                            .as_deref()
+                            .map(crate::suggestions::explanation::SuggestionExplanation::decode)
+                            .transpose()?,
+                    })
+                },
+            )
+            .collect()
+    }
+
+    /// Delete suggestion records shown before `cutoff` (RFC 3339).
+    pub fn prune_suggestion_records(&self, cutoff: &str) -> Result<usize> {
+        let deleted = self.conn.execute_compat(
+            "DELETE FROM suggestion_records WHERE shown_at < ?",
+            params![cutoff],
+        )?;
+        Ok(deleted)
+    }
+
+    /// Settle the pending suggestions of a skill shown in one session.
+    /// Returns how many records changed.
+    pub fn settle_session_suggestions(
+        &self,
+        skill_id: &str,
+        session_id: &str,
+        outcome: &crate::suggestions::SuggestionOutcome,
+    ) -> Result<usize> {
+        let updated = self.conn.execute_compat(
+            "UPDATE suggestion_records SET outcome = ?
+             WHERE skill_id = ? AND session_id = ? AND outcome = 'pending'",
+            params![outcome.as_str(), skill_id, session_id],
+        )?;
+        Ok(updated)
+    }
+
+    // =========================================================================
+    // MINED FEEDBACK METHODS
+    // =========================================================================
+
+    /// Loads of skills recorded in one agent session as (skill_id, used_at),
+    /// oldest first.
+    pub fn list_session_skill_loads(&self, session_id: &str) -> Result<Vec<(String, String)>> {
+        let rows = self.conn.query_map_collect(
+            "SELECT skill_id, used_at FROM skill_usage WHERE session_id = ? ORDER BY used_at",
+            params![session_id],
+            |row| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+        )?;
+        Ok(rows)
+    }
+
+    /// Store mined feedback unless the (session, skill) pair was already
+    /// mined. Returns whether the record was stored.
+    pub fn insert_mined_feedback(&self, record: &MinedFeedbackRecord) -> Result<bool> {
+        let inserted = self.conn.execute_compat(
+            "INSERT INTO mined_feedback (session_id, skill_id, verdict, reward, signals_json, mined_at)
+             VALUES (?, ?, ?, ?, ?, ?)
+             ON CONFLICT(session_id, skill_id) DO NOTHING",
+            params![
+                record.session_id,
+                record.skill_id,
+                record.verdict,
+                record.reward,
+                record.signals_json,
+                record.mined_at
+            ],
+        )?;
+        Ok(inserted > 0)
+    }
+
+    /// Mined feedback of one session, by skill id.
+    pub fn list_mined_feedback(&self, session_id: &str) -> Result<Vec<MinedFeedbackRecord>> {
+        let records = self.conn.query_map_collect(
+            "SELECT session_id, skill_id, verdict, reward, signals_json, mined_at
+             FROM mined_feedback WHERE session_id = ? ORDER BY skill_id",
+            params![session_id],
+            |row| {
+                Ok(MinedFeedbackRecord {
+                    session_id: row.get_typed(0)?,
+                    skill_id: row.get_typed(1)?,
+                    verdict: row.get_typed(2)?,
+                    reward: row.get_typed(3)?,
+                    signals_json: row.get_typed(4)?,
+                    mined_at: row.get_typed(5)?,
+                })
+            },
+        )?;
+        Ok(records)
+    }
+
+    /// Mark a skill's loads in one session as successful or not.
+    pub fn record_session_outcome(
+        &self,
+        skill_id: &str,
+        session_id: &str,
+        success: bool,
+    ) -> Result<usize> {
+        let updated = self.conn.execute_compat(
+            "UPDATE skill_usage SET success_signal = ? WHERE skill_id = ? AND session_id = ?",
+            params![i32::from(success), skill_id, session_id],
+        )?;
+        Ok(updated)
+    }
+
+    // =========================================================================
+    // SKILL TRIAL METHODS
+    // =========================================================================
+
+    pub fn insert_skill_trial(&self, record: &SkillTrialRecord) -> Result<()> {
+        self.conn.execute_compat(
+            "INSERT INTO skill_trials (id, skill_id, context_fingerprint, disclosure_level, started_at, outcome, notes, closed_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                record.id,
+                record.skill_id,
+                record.context_fingerprint,
+                record.disclosure_level,
+                record.started_at,
+                record.outcome,
+                record.notes,
+                record.closed_at
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_skill_trial(&self, id: &str) -> Result<Option<SkillTrialRecord>> {
+        use fsqlite::compat::OptionalExtension;
+        let result = self
+            .conn
+            .query_row_map(
+                "SELECT id, skill_id, context_fingerprint, disclosure_level, started_at, outcome, notes, closed_at
+                 FROM skill_trials WHERE id = ?",
+                params![id],
+                skill_trial_from_row,
+            )
+            .optional()?;
+        Ok(result)
+    }
+
+    /// Close an open trial. Returns false when it does not exist or was
+    /// already closed.
+    pub fn close_skill_trial(
+        &self,
+        id: &str,
+        outcome: &str,
+        notes: Option<&str>,
+        closed_at: &str,
+    ) -> Result<bool> {
+        let updated = self.conn.execute_compat(
+            "UPDATE skill_trials SET outcome = ?, notes = ?, closed_at = ?
+             WHERE id = ? AND closed_at IS NULL",
+            params![outcome, notes, closed_at, id],
+        )?;
+        Ok(updated > 0)
+    }
+
+    /// Trials, oldest first; `open_only` keeps the unreported ones and
+    /// `started_before` those started before an RFC 3339 timestamp.
+    pub fn list_skill_trials(
+        &self,
+        open_only: bool,
+        started_before: Option<&str>,
+    ) -> Result<Vec<SkillTrialRecord>> {
+        let mut sql = "SELECT id, skill_id, context_fingerprint, disclosure_level, started_at, outcome, notes, closed_at
+                       FROM skill_trials WHERE 1 = 1"
+            .to_string();
+        if open_only {
+            sql.push_str(" AND closed_at IS NULL");
+        }
+        let records = if let Some(before) = started_before {
+            sql.push_str(" AND started_at < ? ORDER BY started_at");
+            self.conn
+                .query_map_collect(&sql, params![before], skill_trial_from_row)?
+        } else {
+            sql.push_str(" ORDER BY started_at");
+            self.conn
+                .query_map_collect(&sql, params![], skill_trial_from_row)?
+        };
+        Ok(records)
+    }
+
+    /// Trial counts per skill (or for one skill), sorted by skill id.
+    pub fn list_trial_stats(
+        &self,
+        skill_id: Option<&str>,
+    ) -> Result<Vec<crate::quality::trials::TrialStats>> {
+        let rows: Vec<(String, Option<String>)> = if let Some(skill_id) = skill_id {
+            self.conn.query_map_collect(
+                "SELECT skill_id, outcome FROM skill_trials WHERE skill_id = ?",
+                params![skill_id],
+                |row| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+            )?
+        } else {
+            self.conn.query_map_collect(
+                "SELECT skill_id, outcome FROM skill_trials",
+                params![],
+                |row| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+            )?
+        };
+
+        let mut stats: std::collections::BTreeMap<String, crate::quality::trials::TrialStats> =
+            std::collections::BTreeMap::new();
+        for (skill_id, outcome) in rows {
+            let entry = stats.entry(skill_id.clone()).or_insert_with(|| {
+                crate::quality::trials::TrialStats {
+                    skill_id,
+                    ..Default::default()
+                }
+            });
+            entry.trials += 1;
+            match outcome.as_deref() {
+                None => entry.open += 1,
+                Some("success") => entry.success += 1,
+                Some("failure") => entry.failure += 1,
+                Some("neutral") => entry.neutral += 1,
+                Some(_) => entry.expired += 1,
+            }
+        }
+        Ok(stats.into_values().collect())
+    }
+
+    // =========================================================================
+    // SOURCE FILE METHODS (INCREMENTAL INDEXING)
+    // =========================================================================
+
+    pub fn get_source_file(&self, path: &str) -> Result<Option<SourceFileRecord>> {
+        use fsqlite::compat::OptionalExtension;
+        let record = self
+            .conn
+            .query_row_map(
+                "SELECT path, skill_id, layer, content_hash, mtime_ms, size, indexed_at
+                 FROM skill_source_files WHERE path = ?",
+                params![path],
+                source_file_row,
+            )
+            .optional()?;
+        Ok(record)
+    }
+
+    pub fn list_source_files(&self) -> Result<Vec<SourceFileRecord>> {
+        let records = self.conn.query_map_collect(
+            "SELECT path, skill_id, layer, content_hash, mtime_ms, size, indexed_at
+             FROM skill_source_files ORDER BY path",
+            params![],
+            source_file_row,
+        )?;
+        Ok(records)
+    }
+
+    pub fn upsert_source_file(&self, record: &SourceFileRecord) -> Result<()> {
+        self.conn.execute_compat(
+            "INSERT INTO skill_source_files (
+                path, skill_id, layer, content_hash, mtime_ms, size, indexed_at
+             ) VALUES (?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(path) DO UPDATE SET
+                skill_id=excluded.skill_id,
+                layer=excluded.layer,
+                content_hash=excluded.content_hash,
+                mtime_ms=excluded.mtime_ms,
+                size=excluded.size,
+                indexed_at=excluded.indexed_at",
+            params![
+                record.path,
+                record.skill_id,
+                record.layer,
+                record.content_hash,
+                record.mtime_ms,
+                record.size,
+                record.indexed_at,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn delete_source_file(&self, path: &str) -> Result<()> {
+        self.conn.execute_compat(
+            "DELETE FROM skill_source_files WHERE path = ?",
+            params![path],
+        )?;
+        Ok(())
+    }
+
+    // =========================================================================
+    // LAYER RESOLUTION METHODS
+    // =========================================================================
+
+    /// Stored resolution for this exact set of conflicting candidates.
+    pub fn get_layer_resolution(
+        &self,
+        skill_id: &str,
+        candidates_hash: &str,
+    ) -> Result<Option<LayerResolutionRecord>> {
+        use fsqlite::compat::OptionalExtension;
+        let record = self
+            .conn
+            .query_row_map(
+                "SELECT skill_id, candidates_hash, strategy, resolved_at
+                 FROM layer_resolutions WHERE skill_id = ? AND candidates_hash = ?",
+                params![skill_id, candidates_hash],
+                layer_resolution_row,
+            )
+            .optional()?;
+        Ok(record)
+    }
+
+    pub fn list_layer_resolutions(&self) -> Result<Vec<LayerResolutionRecord>> {
+        let records = self.conn.query_map_collect(
+            "SELECT skill_id, candidates_hash, strategy, resolved_at
+             FROM layer_resolutions ORDER BY skill_id, resolved_at",
+            params![],
+            layer_resolution_row,
+        )?;
+        Ok(records)
+    }
+
+    pub fn upsert_layer_resolution(&self, record: &LayerResolutionRecord) -> Result<()> {
+        self.conn.execute_compat(
+            "INSERT INTO layer_resolutions (skill_id, candidates_hash, strategy, resolved_at)
+             VALUES (?, ?, ?, ?)
+             ON CONFLICT(skill_id, candidates_hash) DO UPDATE SET
+                strategy=excluded.strategy,
+                resolved_at=excluded.resolved_at",
+            params![
+                record.skill_id,
+                record.candidates_hash,
+                record.strategy,
+                record.resolved_at,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Forget stored resolutions for one skill, or all of them.
+    ///
+    /// Returns the number of rows removed.
+    pub fn delete_layer_resolutions(&self, skill_id: Option<&str>) -> Result<usize> {
+        let deleted = match skill_id {
+            Some(id) => self.conn.execute_compat(
+                "DELETE FROM layer_resolutions WHERE skill_id = ?",
+                params![id],
+            )?,
+            None => self
+                .conn
+                .execute_compat("DELETE FROM layer_resolutions", params![])?,
+        };
+        Ok(deleted)
+    }
+
+    // =========================================================================
+    // SUGGESTION COOLDOWN METHODS
+    // =========================================================================
+
+    /// Cooldowns still active at `now` (RFC 3339), soonest expiry first.
+    ///
+    /// Expired rows are deleted first, so every reader sees the same state.
+    pub fn list_suggestion_cooldowns(&self, now: &str) -> Result<Vec<SuggestionCooldownRecord>> {
+        self.conn.execute_compat(
+            "DELETE FROM suggestion_cooldowns WHERE expires_at <= ?",
+            params![now],
+        )?;
+        let records = self.conn.query_map_collect(
+            "SELECT skill_id, context_fingerprint, agent_id, response, dismissals, started_at, expires_at
+             FROM suggestion_cooldowns ORDER BY expires_at, skill_id",
+            params![],
+            suggestion_cooldown_row,
+        )?;
+        Ok(records)
+    }
+
+    pub fn upsert_suggestion_cooldown(&self, record: &SuggestionCooldownRecord) -> Result<()> {
+        self.conn.execute_compat(
+            "INSERT INTO suggestion_cooldowns (
+                skill_id, context_fingerprint, agent_id, response, dismissals, started_at, expires_at
+             ) VALUES (?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(skill_id, context_fingerprint, agent_id) DO UPDATE SET
+                response=excluded.response,
+                dismissals=excluded.dismissals,
+                started_at=excluded.started_at,
+                expires_at=excluded.expires_at",
+            params![
+                record.skill_id,
+                record.context_fingerprint,
+                record.agent_id.as_deref().unwrap_or_default(),
+                record.response,
+                i64::from(record.dismissals),
+                record.started_at,
+                record.expires_at,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Clear cooldowns for one skill, or all of them.
+    ///
+    /// Returns the number of rows removed.
+    pub fn delete_suggestion_cooldowns(&self, skill_id: Option<&str>) -> Result<usize> {
+        let deleted = match skill_id {
+            Some(id) => self.conn.execute_compat(
+                "DELETE FROM suggestion_cooldowns WHERE skill_id = ?",
+                params![id],
+            )?,
+            None => self
+                .conn
+                .execute_compat("DELETE FROM suggestion_cooldowns", params![])?,
+        };
+        Ok(deleted)
+    }
+
+    /// Remember that two skills are not duplicates. The pair is stored in
+    /// id order, so the argument order does not matter.
+    pub fn dismiss_dedup_pair(
+        &self,
+        skill_a: &str,
+        skill_b: &str,
+        reason: Option<&str>,
+        dismissed_at: &str,
+    ) -> Result<()> {
+        let (a, b) = ordered_pair(skill_a, skill_b);
+        self.conn.execute_compat(
+            "INSERT INTO dedup_dismissals (skill_a, skill_b, reason, dismissed_at)
+             VALUES (?, ?, ?, ?)
+             ON CONFLICT(skill_a, skill_b) DO UPDATE SET
+                reason=excluded.reason,
+                dismissed_at=excluded.dismissed_at",
+            params![a, b, reason, dismissed_at],
+        )?;
+        Ok(())
+    }
+
+    pub fn list_dedup_dismissals(&self) -> Result<Vec<DedupDismissalRecord>> {
+        let records = self.conn.query_map_collect(
+            "SELECT skill_a, skill_b, reason, dismissed_at
+             FROM dedup_dismissals ORDER BY skill_a, skill_b",
+            params![],
+            |row| {
+                Ok(DedupDismissalRecord {
+                    skill_a: row.get_typed(0)?,
+                    skill_b: row.get_typed(1)?,
+                    reason: row.get_typed(2)?,
+                    dismissed_at: row.get_typed(3)?,
+                })
+            },
+        )?;
+        Ok(records)
+    }
+
+    /// Forget a dismissal. Returns whether one existed.
+    pub fn delete_dedup_dismissal(&self, skill_a: &str, skill_b: &str) -> Result<bool> {
+        let (a, b) = ordered_pair(skill_a, skill_b);
+        let deleted = self.conn.execute_compat(
+            "DELETE FROM dedup_dismissals WHERE skill_a = ? AND skill_b = ?",
+            params![a, b],
+        )?;
+        Ok(deleted > 0)
+    }
+
+    /// Insert or replace a queued uncertainty.
+    pub fn upsert_uncertainty(&self, record: &UncertaintyRecord) -> Result<()> {
+        self.conn.execute_compat(
+            "INSERT INTO uncertainty_queue
+                (id, pattern_json, reason, confidence, suggested_queries, status,
+                 created_at, item_json, instance_key, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(id) DO UPDATE SET
+                pattern_json=excluded.pattern_json,
+                reason=excluded.reason,
+                confidence=excluded.confidence,
+                suggested_queries=excluded.suggested_queries,
+                status=excluded.status,
+                item_json=excluded.item_json,
+                instance_key=excluded.instance_key,
+                updated_at=excluded.updated_at",
+            params![
+                record.id.as_str(),
+                record.pattern_json.as_str(),
+                record.reason.as_str(),
+                record.confidence,
+                record.suggested_queries.as_str(),
+                record.status.as_str(),
+                record.created_at.as_str(),
+                record.item_json.as_str(),
+                record.instance_key.as_deref(),
+                record.updated_at.as_str()
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_uncertainty(&self, id: &str) -> Result<Option<UncertaintyRecord>> {
+        use fsqlite::compat::OptionalExtension;
+
+        let record = self
+            .conn
+            .query_row_map(
+                &format!("SELECT {UNCERTAINTY_COLUMNS} FROM uncertainty_queue WHERE id = ?"),
+                params![id],
+                uncertainty_from_row,
+            )
+            .optional()?;
+        Ok(record)
+    }
+
+    /// Queued uncertainties, oldest first, optionally only those in `status`.
+    pub fn list_uncertainties(&self, status: Option<&str>) -> Result<Vec<UncertaintyRecord>> {
+        let records = match status {
+            Some(status) => self.conn.query_map_collect(
+                &format!(
+                    "SELECT {UNCERTAINTY_COLUMNS} FROM uncertainty_queue
+                     WHERE status = ? ORDER BY created_at, id"
+                ),
+                params![status],
+                uncertainty_from_row,
+            )?,
+            None => self.conn.query_map_collect(
+                &format!(
+                    "SELECT {UNCERTAINTY_COLUMNS} FROM uncertainty_queue ORDER BY created_at, id"
+                ),
+                params![],
+                uncertainty_from_row,
+            )?,
+        };
+        Ok(records)
+    }
+
+    /// Every uncertainty queued for `instance_key`, oldest first.
+    pub fn uncertainties_for_instance(&self, instance_key: &str) -> Result<Vec<UncertaintyRecord>> {
+        let records = self.conn.query_map_collect(
+            &format!(
+                "SELECT {UNCERTAINTY_COLUMNS} FROM uncertainty_queue
+                 WHERE instance_key = ? ORDER BY created_at, id"
+            ),
+            params![instance_key],
+            uncertainty_from_row,
+        )?;
+        Ok(records)
+    }
+
+    /// Token count cached for `content_hash` under `tokenizer`.
+    pub fn cached_token_count(&self, tokenizer: &str, content_hash: &str) -> Result<Option<usize>> {
+        use fsqlite::compat::OptionalExtension;
+
+        let tokens = self
+            .conn
+            .query_row_map(
+                "SELECT tokens FROM token_counts WHERE tokenizer = ? AND content_hash = ?",
+                params![tokenizer, content_hash],
+                |row| row.get_typed::<i64>(0),
+            )
+            .optional()?;
+        Ok(tokens.map(|tokens| usize::try_from(tokens).unwrap_or(0)))
     }
 
-    /// Upsert session quality record
-    pub fn upsert_session_quality(&self, record: &SessionQualityRecord) -> Result<()> {
-        let signals_json = serde_json::to_string(&record.signals)
-            .map_err(|err| MsError::Config(format!("encode signals: {err}")))?;
-        let missing_json = serde_json::to_string(&record.missing)
-            .map_err(|err| MsError::Config(format!("encode missing: {err}")))?;
-
+    pub fn cache_token_count(
+        &self,
+        tokenizer: &str,
+        content_hash: &str,
+        tokens: usize,
+    ) -> Result<()> {
         self.conn.execute_compat(
-            "INSERT INTO session_quality (session_id, content_hash, score, signals_json, missing_json, computed_at)
-             VALUES (?, ?, ?, ?, ?, ?)
-             ON CONFLICT(session_id) DO UPDATE SET
-                content_hash=excluded.content_hash,
-                score=excluded.score,
-                signals_json=excluded.signals_json,
-                missing_json=excluded.missing_json,
-                computed_at=excluded.computed_at",
+            "INSERT OR REPLACE INTO token_counts (tokenizer, content_hash, tokens)
+             VALUES (?, ?, ?)",
             params![
-                record.session_id,
-                record.content_hash,
-                f64::from(record.score),
-                signals_json,
-                missing_json,
-                record.computed_at,
+                tokenizer,
+                content_hash,
+                i64::try_from(tokens).unwrap_or(i64::MAX)
             ],
         )?;
         Ok(())
     }
 
+    pub fn delete_embedding(&self, skill_id: &str) -> Result<()> {
+        self.conn.execute_compat(
+            "DELETE FROM skill_embeddings WHERE skill_id = ?",
+            params![skill_id],
+        )?;
+        Ok(())
+    }
+
+    // =========================================================================
+    // BANDIT STATE METHODS
+    // =========================================================================
+
+    pub fn get_bandit_state(&self, name: &str) -> Result<Option<BanditStateRecord>> {
+        use fsqlite::compat::OptionalExtension;
+        let record = self
+            .conn
+            .query_row_map(
+                "SELECT name, state_json, version, updated_at FROM bandit_state WHERE name = ?",
+                params![name],
+                bandit_state_row,
+            )
+            .optional()?;
+        Ok(record)
+    }
+
+    /// Write bandit state if the stored version still equals `expected_version`.
+    ///
+    /// Returns `false` when another process wrote in between; the caller should
+    /// reload and reapply its change.
+    pub fn put_bandit_state(
+        &self,
+        name: &str,
+        state_json: &str,
+        expected_version: i64,
+    ) -> Result<bool> {
+        let now = chrono::Utc::now().to_rfc3339();
+        let changed = if expected_version == 0 {
+            self.conn.execute_compat(
+                "INSERT INTO bandit_state (name, state_json, version, updated_at)
+                 VALUES (?, ?, 1, ?)
+                 ON CONFLICT(name) DO NOTHING",
+                params![name, state_json, now],
+            )?
+        } else {
+            self.conn.execute_compat(
+                "UPDATE bandit_state
+                 SET state_json = ?, version = version + 1, updated_at = ?
+                 WHERE name = ? AND version = ?",
+                params![state_json, now, name, expected_version],
+            )?
+        };
+        Ok(changed > 0)
+    }
+
     // =========================================================================
     // SKILL EVIDENCE METHODS (PROVENANCE GRAPH)
     // =========================================================================
@@ -1274,6 +2995,16 @@ impl Database {
         Ok(records)
     }
 
+    /// Skill ids that have any evidence recorded, sorted.
+    pub fn list_evidence_skill_ids(&self) -> Result<Vec<String>> {
+        let ids = self.conn.query_map_collect(
+            "SELECT DISTINCT skill_id FROM skill_evidence ORDER BY skill_id",
+            params![],
+            |row| row.get_typed(0),
+        )?;
+        Ok(ids)
+    }
+
     /// Delete all evidence for a skill.
     pub fn delete_skill_evidence(&self, skill_id: &str) -> Result<usize> {
         let count = self.conn.execute_compat(
@@ -1283,6 +3014,117 @@ impl Database {
         Ok(count)
     }
 
+    /// Evidence rows whose skill no longer exists, with an estimate of the
+    /// bytes they hold.
+    pub fn orphaned_evidence(&self) -> Result<OrphanedEvidence> {
+        let skill_ids = self.conn.query_map_collect(
+            "SELECT DISTINCT skill_id FROM skill_evidence
+             WHERE skill_id NOT IN (SELECT id FROM skills)
+             ORDER BY skill_id",
+            params![],
+            |row| row.get_typed(0),
+        )?;
+        let (rows, bytes): (i64, i64) = self.conn.query_row_map(
+            "SELECT COUNT(*),
+                    COALESCE(SUM(LENGTH(skill_id) + LENGTH(rule_id) + LENGTH(evidence_json)
+                                 + LENGTH(coverage_json) + LENGTH(updated_at)), 0)
+             FROM skill_evidence
+             WHERE skill_id NOT IN (SELECT id FROM skills)",
+            params![],
+            |row| Ok((row.get_typed(0)?, row.get_typed(1)?)),
+        )?;
+        Ok(OrphanedEvidence {
+            skill_ids,
+            rows: rows.max(0) as u64,
+            estimated_bytes: bytes.max(0) as u64,
+        })
+    }
+
+    /// Delete evidence rows whose skill no longer exists, in one transaction.
+    pub fn delete_orphaned_evidence(&self) -> Result<usize> {
+        self.in_transaction(|conn| {
+            Ok(conn.execute_compat(
+                "DELETE FROM skill_evidence WHERE skill_id NOT IN (SELECT id FROM skills)",
+                params![],
+            )?)
+        })
+    }
+
+    /// Fold the WAL back into the database file and truncate it.
+    pub fn checkpoint_wal(&self) -> Result<()> {
+        self.conn
+            .execute("PRAGMA wal_checkpoint(TRUNCATE)")
+            .map_err(|err| MsError::TransactionFailed(format!("wal checkpoint: {err}")))?;
+        Ok(())
+    }
+
+    /// Write a consistent copy of the database to `dest`, which must not
+    /// exist yet.
+    ///
+    /// fsqlite has no online-backup handle, so this uses `VACUUM INTO`: it
+    /// reads one transaction's view of the database, including pages still
+    /// in the WAL, which a plain file copy of a live database would miss.
+    pub fn snapshot_to(&self, dest: &Path) -> Result<()> {
+        self.conn
+            .execute_compat(
+                "VACUUM INTO ?",
+                params![dest.to_string_lossy().into_owned()],
+            )
+            .map_err(|err| {
+                MsError::TransactionFailed(format!("snapshot to {}: {err}", dest.display()))
+            })?;
+        Ok(())
+    }
+
+    /// Run `f` inside `BEGIN IMMEDIATE` / `COMMIT`, rolling back on error.
+    fn in_transaction<T>(&self, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+        self.begin_immediate()?;
+        match f(&self.conn) {
+            Ok(value) => {
+                self.conn
+                    .execute("COMMIT")
+                    .map_err(|err| MsError::TransactionFailed(format!("commit failed: {err}")))?;
+                Ok(value)
+            }
+            Err(err) => {
+                if let Err(rollback) = self.conn.execute("ROLLBACK") {
+                    tracing::warn!(error = %rollback, "rollback failed");
+                }
+                Err(err)
+            }
+        }
+    }
+
+    /// `BEGIN IMMEDIATE` takes the write lock up front, so another writer
+    /// shows up here as SQLITE_BUSY rather than halfway through `f`. Past
+    /// `busy_timeout`, the begin is retried with backoff until the lock
+    /// timeout (`MS_LOCK_TIMEOUT`) and then fails with [`MsError::Busy`].
+    fn begin_immediate(&self) -> Result<()> {
+        let deadline = std::time::Instant::now() + super::tx::lock_timeout();
+        let mut backoff = std::time::Duration::from_millis(10);
+        loop {
+            match self.conn.execute("BEGIN IMMEDIATE") {
+                Ok(_) => return Ok(()),
+                Err(err) if is_busy(&err) => {
+                    if std::time::Instant::now() >= deadline {
+                        return Err(MsError::Busy {
+                            resource: "database".to_string(),
+                            pid: self
+                                .writer_lock
+                                .as_deref()
+                                .and_then(GlobalLock::other_holder_pid),
+                        });
+                    }
+                    std::thread::sleep(backoff);
+                    backoff = (backoff * 2).min(std::time::Duration::from_millis(250));
+                }
+                Err(err) => {
+                    return Err(MsError::TransactionFailed(format!("begin failed: {err}")));
+                }
+            }
+        }
+    }
+
     pub fn record_skill_outcome(&self, skill_id: &str, success: bool) -> Result<()> {
         let id = Uuid::new_v4().to_string();
         let created_at = chrono::Utc::now().to_rfc3339();
@@ -1338,6 +3180,16 @@ impl Database {
         })
     }
 
+    /// Number of feedback records of `feedback_type` for a skill.
+    pub fn count_skill_feedback(&self, skill_id: &str, feedback_type: &str) -> Result<u64> {
+        let count: i64 = self.conn.query_row_map(
+            "SELECT COUNT(*) FROM skill_feedback WHERE skill_id = ? AND feedback_type = ?",
+            params![skill_id, feedback_type],
+            |row| row.get_typed::<i64>(0),
+        )?;
+        Ok(count.max(0) as u64)
+    }
+
     pub fn list_skill_feedback(
         &self,
         skill_id: Option<&str>,
@@ -1667,8 +3519,11 @@ impl Database {
     fn configure_pragmas(conn: &Connection) -> Result<()> {
         // fsqlite's `execute_batch` happily takes multi-statement PRAGMAs;
         // semicolon-split is handled internally by the compat splitter.
+        // `busy_timeout` is set explicitly because concurrent processes
+        // opening one data directory wait on each other's migration writes.
         conn.execute_batch(
-            "PRAGMA journal_mode = WAL;
+            "PRAGMA busy_timeout = 5000;
+             PRAGMA journal_mode = WAL;
              PRAGMA synchronous = NORMAL;
              PRAGMA cache_size = -64000;
              PRAGMA mmap_size = 268435456;
@@ -1679,6 +3534,36 @@ impl Database {
     }
 }
 
+/// Whether `err` is SQLITE_BUSY ("database is locked"): another connection
+/// holds the write lock.
+fn is_busy(err: &FrankenError) -> bool {
+    matches!(err.error_code(), fsqlite_error::ErrorCode::Busy)
+}
+
+/// Tags recorded in a skill's `metadata_json`, as a JSON array or a
+/// comma-separated string, normalized like [`normalize_tags`].
+fn tags_from_metadata_json(metadata_json: &str) -> Vec<String> {
+    let Ok(meta) = serde_json::from_str::<JsonValue>(metadata_json) else {
+        return Vec::new();
+    };
+    match meta.get("tags") {
+        Some(JsonValue::Array(tags)) => normalize_tags(tags.iter().filter_map(JsonValue::as_str)),
+        Some(JsonValue::String(tags)) => normalize_tags(tags.split(',')),
+        _ => Vec::new(),
+    }
+}
+
+/// Trimmed, lowercased, sorted, and deduplicated tags.
+fn normalize_tags<'a>(tags: impl Iterator<Item = &'a str>) -> Vec<String> {
+    let mut tags: Vec<String> = tags
+        .map(|tag| tag.trim().to_lowercase())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
 fn skill_from_row(row: &Row) -> RowResult<SkillRecord> {
     Ok(SkillRecord {
         id: row.get_typed(0)?,
@@ -1703,6 +3588,19 @@ fn skill_from_row(row: &Row) -> RowResult<SkillRecord> {
     })
 }
 
+fn skill_trial_from_row(row: &Row) -> RowResult<SkillTrialRecord> {
+    Ok(SkillTrialRecord {
+        id: row.get_typed(0)?,
+        skill_id: row.get_typed(1)?,
+        context_fingerprint: row.get_typed(2)?,
+        disclosure_level: row.get_typed(3)?,
+        started_at: row.get_typed(4)?,
+        outcome: row.get_typed(5)?,
+        notes: row.get_typed(6)?,
+        closed_at: row.get_typed(7)?,
+    })
+}
+
 /// Raw row extraction for embedding rows. The `decode_embedding_f16` step
 /// returns `MsError` (not `FrankenError`), so the actual decode happens in the
 /// caller, after the row mapper returns.
@@ -1716,6 +3614,71 @@ struct EmbeddingRawRow {
     created_at: String,
 }
 
+fn layer_resolution_row(row: &Row) -> RowResult<LayerResolutionRecord> {
+    Ok(LayerResolutionRecord {
+        skill_id: row.get_typed(0)?,
+        candidates_hash: row.get_typed(1)?,
+        strategy: row.get_typed(2)?,
+        resolved_at: row.get_typed(3)?,
+    })
+}
+
+/// Rows queued before migration 024 have no `updated_at`.
+const UNCERTAINTY_COLUMNS: &str = "id, instance_key, reason, confidence, status, pattern_json, \
+     suggested_queries, item_json, created_at, COALESCE(updated_at, created_at)";
+
+fn uncertainty_from_row(row: &Row) -> RowResult<UncertaintyRecord> {
+    Ok(UncertaintyRecord {
+        id: row.get_typed(0)?,
+        instance_key: row.get_typed(1)?,
+        reason: row.get_typed(2)?,
+        confidence: row.get_typed(3)?,
+        status: row.get_typed(4)?,
+        pattern_json: row.get_typed(5)?,
+        suggested_queries: row.get_typed(6)?,
+        item_json: row.get_typed(7)?,
+        created_at: row.get_typed(8)?,
+        updated_at: row.get_typed(9)?,
+    })
+}
+
+fn ordered_pair<'a>(a: &'a str, b: &'a str) -> (&'a str, &'a str) {
+    if a <= b { (a, b) } else { (b, a) }
+}
+
+fn suggestion_cooldown_row(row: &Row) -> RowResult<SuggestionCooldownRecord> {
+    Ok(SuggestionCooldownRecord {
+        skill_id: row.get_typed(0)?,
+        context_fingerprint: row.get_typed(1)?,
+        agent_id: Some(row.get_typed::<String>(2)?).filter(|agent| !agent.is_empty()),
+        response: row.get_typed(3)?,
+        dismissals: row.get_typed::<i64>(4)?.clamp(0, i64::from(u32::MAX)) as u32,
+        started_at: row.get_typed(5)?,
+        expires_at: row.get_typed(6)?,
+    })
+}
+
+fn source_file_row(row: &Row) -> RowResult<SourceFileRecord> {
+    Ok(SourceFileRecord {
+        path: row.get_typed(0)?,
+        skill_id: row.get_typed(1)?,
+        layer: row.get_typed(2)?,
+        content_hash: row.get_typed(3)?,
+        mtime_ms: row.get_typed(4)?,
+        size: row.get_typed(5)?,
+        indexed_at: row.get_typed(6)?,
+    })
+}
+
+fn bandit_state_row(row: &Row) -> RowResult<BanditStateRecord> {
+    Ok(BanditStateRecord {
+        name: row.get_typed(0)?,
+        state_json: row.get_typed(1)?,
+        version: row.get_typed(2)?,
+        updated_at: row.get_typed(3)?,
+    })
+}
+
 fn embedding_raw_row(row: &Row) -> RowResult<EmbeddingRawRow> {
     Ok(EmbeddingRawRow {
         skill_id: row.get_typed(0)?,
@@ -1821,6 +3784,19 @@ mod tests {
         assert_eq!(db.schema_version(), migrations::SCHEMA_VERSION);
     }
 
+    #[test]
+    fn test_config_values_round_trip() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        assert_eq!(db.get_config_value("quality.weights_hash").unwrap(), None);
+        db.set_config_value("quality.weights_hash", "abc").unwrap();
+        db.set_config_value("quality.weights_hash", "def").unwrap();
+        assert_eq!(
+            db.get_config_value("quality.weights_hash").unwrap(),
+            Some("def".to_string())
+        );
+    }
+
     #[test]
     fn test_wal_mode_enabled() {
         let dir = tempdir().unwrap();
@@ -1842,8 +3818,8 @@ mod tests {
         //   - `PRAGMA synchronous` returns the text label ("NORMAL"), not the
         //     integer (1). rusqlite returns the integer. Verified 2026-05-30.
         //   - `PRAGMA busy_timeout` defaults to 5000ms in fsqlite (rusqlite
-        //     defaults to 0). meta_skill does not set this explicitly, so the
-        //     fsqlite default applies and is a behavioral upgrade.
+        //     defaults to 0). meta_skill sets the same value explicitly so
+        //     concurrent opens keep waiting on locks whatever the default.
         let dir = tempdir().unwrap();
         let db = Database::open(dir.path().join("test.db")).unwrap();
         let conn = db.conn();
@@ -1887,6 +3863,12 @@ mod tests {
             .and_then(|row| row.get_typed::<i64>(0))
             .unwrap();
         assert_eq!(mm, 268_435_456, "mmap_size mismatch: got {mm}");
+
+        let bt: i64 = conn
+            .query_row("PRAGMA busy_timeout")
+            .and_then(|row| row.get_typed::<i64>(0))
+            .unwrap();
+        assert_eq!(bt, 5000, "busy_timeout != 5000: got {bt}");
     }
 
     #[test]
@@ -2062,6 +4044,20 @@ mod tests {
             "tx_log",
             "cass_fingerprints",
             "session_quality",
+            "skill_efficacy",
+            "suggestion_records",
+            "skill_source_files",
+            "bandit_state",
+            "injection_quarantine_content",
+            "layer_resolutions",
+            "suggestion_cooldowns",
+            "skill_tags",
+            "skill_derived_tags",
+            "skill_resources",
+            "dedup_dismissals",
+            "token_counts",
+            "mined_feedback",
+            "skill_trials",
         ];
 
         for table in tables {
@@ -2078,34 +4074,327 @@ mod tests {
     }
 
     #[test]
-    fn test_upsert_and_get_skill() {
+    fn test_upsert_and_get_skill() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let record = SkillRecord {
+            id: "git-commit".to_string(),
+            name: "Git Commit Patterns".to_string(),
+            description: "Best practices for commits".to_string(),
+            version: Some("1.0.0".to_string()),
+            author: Some("Example".to_string()),
+            source_path: "/skills/git".to_string(),
+            source_layer: "base".to_string(),
+            git_remote: None,
+            git_commit: None,
+            content_hash: "abc123".to_string(),
+            body: "Write good commit messages".to_string(),
+            metadata_json: r#"{"tags":"git,workflow"}"#.to_string(),
+            assets_json: "{}".to_string(),
+            token_count: 500,
+            quality_score: 0.85,
+            indexed_at: "2026-01-01T00:00:00Z".to_string(),
+            modified_at: "2026-01-01T00:00:00Z".to_string(),
+            is_deprecated: false,
+            deprecation_reason: None,
+        };
+
+        db.upsert_skill(&record).unwrap();
+        let fetched = db.get_skill("git-commit").unwrap().unwrap();
+        assert_eq!(record, fetched);
+    }
+
+    #[test]
+    fn test_nudge_summary_queries() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        for (id, layer, tokens) in [
+            ("a", "Project", 100),
+            ("b", "project", 50),
+            ("c", "user", 10),
+        ] {
+            db.upsert_skill(&SkillRecord {
+                id: id.to_string(),
+                name: id.to_string(),
+                description: String::new(),
+                version: None,
+                author: None,
+                source_path: format!("/skills/{id}"),
+                source_layer: layer.to_string(),
+                git_remote: None,
+                git_commit: None,
+                content_hash: id.to_string(),
+                body: String::new(),
+                metadata_json: "{}".to_string(),
+                assets_json: "{}".to_string(),
+                token_count: tokens,
+                quality_score: 0.5,
+                indexed_at: "2026-01-01T00:00:00Z".to_string(),
+                modified_at: "2026-01-01T00:00:00Z".to_string(),
+                is_deprecated: false,
+                deprecation_reason: None,
+            })
+            .unwrap();
+        }
+        db.record_skill_usage("a", None, 1, None, None, None, None)
+            .unwrap();
+
+        assert_eq!(
+            db.count_skills_unused_since("2026-06-01T00:00:00Z")
+                .unwrap(),
+            2
+        );
+        // Skills indexed inside the window are too new to be stale
+        assert_eq!(
+            db.count_skills_unused_since("2000-01-01T00:00:00Z")
+                .unwrap(),
+            0
+        );
+        let totals = db.layer_token_totals().unwrap();
+        assert_eq!(totals.get("project"), Some(&150));
+        assert_eq!(totals.get("user"), Some(&10));
+        assert_eq!(db.count_unreviewed_quarantine().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_skill_activity_since_counts_window() {
+        use crate::suggestions::{SuggestionOutcome, SuggestionRecord};
+
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        for id in ["a", "b"] {
+            db.upsert_skill(&SkillRecord {
+                id: id.to_string(),
+                name: id.to_string(),
+                description: String::new(),
+                version: None,
+                author: None,
+                source_path: format!("/skills/{id}"),
+                source_layer: "project".to_string(),
+                git_remote: None,
+                git_commit: None,
+                content_hash: id.to_string(),
+                body: String::new(),
+                metadata_json: "{}".to_string(),
+                assets_json: "{}".to_string(),
+                token_count: 10,
+                quality_score: 0.5,
+                indexed_at: "2026-01-01T00:00:00Z".to_string(),
+                modified_at: "2026-01-01T00:00:00Z".to_string(),
+                is_deprecated: false,
+                deprecation_reason: None,
+            })
+            .unwrap();
+        }
+        let now = chrono::Utc::now();
+        for (outcome, days_ago) in [
+            (SuggestionOutcome::Selected, 1),
+            (SuggestionOutcome::Ignored, 2),
+            (SuggestionOutcome::Pending, 3),
+            (SuggestionOutcome::Selected, 40),
+        ] {
+            db.insert_suggestion_record(
+                &SuggestionRecord {
+                    skill_id: "a".to_string(),
+                    shown_at: now - chrono::Duration::days(days_ago),
+                    context_fingerprint_hash: None,
+                    position: 0,
+                    outcome,
+                    session_id: None,
+                    agent_id: None,
+                    explanation: None,
+                },
+                4096,
+            )
+            .unwrap();
+        }
+        db.record_skill_feedback("a", "positive", None, None)
+            .unwrap();
+        db.record_skill_feedback("a", "rating", Some(5), None)
+            .unwrap();
+        db.record_skill_feedback("b", "rating", Some(1), None)
+            .unwrap();
+        db.record_skill_feedback("b", "rating", Some(3), None)
+            .unwrap();
+        db.record_skill_usage("b", None, 1, None, None, None, None)
+            .unwrap();
+
+        let cutoff = (now - chrono::Duration::days(30)).to_rfc3339();
+        let activity = db.skill_activity_since(&cutoff).unwrap();
+        assert_eq!(
+            activity["a"],
+            SkillActivity {
+                suggestions_shown: 3,
+                suggestions_selected: 1,
+                suggestions_decided: 2,
+                positive_feedback: 2,
+                negative_feedback: 0,
+            }
+        );
+        assert_eq!(activity["b"].negative_feedback, 1);
+        assert_eq!(activity["b"].positive_feedback, 0);
+
+        let loads = db.list_skill_loads_since(&cutoff).unwrap();
+        assert_eq!(loads.len(), 1);
+        assert_eq!(loads[0].0, "b");
+        let future = (now + chrono::Duration::days(1)).to_rfc3339();
+        assert!(db.list_skill_loads_since(&future).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_suggestion_with_expired_pin_stays_explainable() {
+        use crate::suggestions::SuggestionRecord;
+        use crate::suggestions::explanation::{
+            ChangesSince, ContextSummary, FavoriteChange, PreferenceSnapshot, SuggestionExplanation,
+        };
+        use crate::suggestions::tracking::SuggestionOutcome;
+
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let skill = SkillRecord {
+            id: "terraform".to_string(),
+            name: "Terraform".to_string(),
+            description: String::new(),
+            version: None,
+            author: None,
+            source_path: "/skills/terraform".to_string(),
+            source_layer: "project".to_string(),
+            git_remote: None,
+            git_commit: None,
+            content_hash: "hash-1".to_string(),
+            body: String::new(),
+            metadata_json: "{}".to_string(),
+            assets_json: "{}".to_string(),
+            token_count: 10,
+            quality_score: 0.5,
+            indexed_at: "2026-01-01T00:00:00Z".to_string(),
+            modified_at: "2026-01-01T00:00:00Z".to_string(),
+            is_deprecated: false,
+            deprecation_reason: None,
+        };
+        db.upsert_skill(&skill).unwrap();
+        db.set_user_preference("terraform", "favorite").unwrap();
+
+        let shown_at = chrono::Utc::now() - chrono::Duration::hours(1);
+        let explanation = SuggestionExplanation {
+            score: 0.8,
+            signals: Default::default(),
+            bandit: Default::default(),
+            matched_triggers: vec!["Favorite".to_string()],
+            context: ContextSummary::new(7, Some("/py-repo".to_string()), None, Vec::new()),
+            preferences: PreferenceSnapshot {
+                favorite: true,
+                ..PreferenceSnapshot::default()
+            },
+            skill_content_hash: skill.content_hash.clone(),
+            skill_modified_at: skill.modified_at.clone(),
+            discovery: false,
+            origin: Default::default(),
+            task: None,
+            truncated: false,
+        };
+        db.insert_suggestion_record(
+            &SuggestionRecord {
+                skill_id: "terraform".to_string(),
+                shown_at,
+                context_fingerprint_hash: Some(7),
+                position: 0,
+                outcome: SuggestionOutcome::Pending,
+                session_id: Some("sess-1".to_string()),
+                agent_id: Some("cursor".to_string()),
+                explanation: Some(explanation.clone()),
+            },
+            4096,
+        )
+        .unwrap();
+
+        // The pin goes away after the suggestion was made.
+        db.remove_user_preference("terraform", "favorite").unwrap();
+
+        let records = db
+            .list_suggestion_records("terraform", Some("sess-1"))
+            .unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].context_fingerprint_hash, Some(7));
+        assert_eq!(records[0].agent_id.as_deref(), Some("cursor"));
+        let stored = records[0].explanation.as_ref().unwrap();
+        assert_eq!(stored, &explanation);
+        assert!(stored.preferences.favorite);
+
+        let changes = ChangesSince::compute(
+            stored,
+            records[0].shown_at,
+            db.get_skill("terraform").unwrap().as_ref(),
+            db.has_user_preference("terraform", "favorite").unwrap(),
+            &[],
+            chrono::Utc::now(),
+        );
+        assert_eq!(changes.favorite_changed, Some(FavoriteChange::Removed));
+        assert!(!changes.skill_edited);
+
+        assert!(
+            db.list_suggestion_records("terraform", Some("other"))
+                .unwrap()
+                .is_empty()
+        );
+        let cutoff = chrono::Utc::now().to_rfc3339();
+        assert_eq!(db.prune_suggestion_records(&cutoff).unwrap(), 1);
+        assert!(
+            db.list_suggestion_records("terraform", None)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_source_file_roundtrip() {
         let dir = tempdir().unwrap();
         let db = Database::open(dir.path().join("test.db")).unwrap();
-        let record = SkillRecord {
-            id: "git-commit".to_string(),
-            name: "Git Commit Patterns".to_string(),
-            description: "Best practices for commits".to_string(),
-            version: Some("1.0.0".to_string()),
-            author: Some("Example".to_string()),
-            source_path: "/skills/git".to_string(),
-            source_layer: "base".to_string(),
-            git_remote: None,
-            git_commit: None,
-            content_hash: "abc123".to_string(),
-            body: "Write good commit messages".to_string(),
-            metadata_json: r#"{"tags":"git,workflow"}"#.to_string(),
-            assets_json: "{}".to_string(),
-            token_count: 500,
-            quality_score: 0.85,
+
+        let mut record = SourceFileRecord {
+            path: "/skills/deploy/SKILL.md".to_string(),
+            skill_id: "deploy".to_string(),
+            layer: "project".to_string(),
+            content_hash: "abc".to_string(),
+            mtime_ms: 1_700_000_000_000,
+            size: 42,
             indexed_at: "2026-01-01T00:00:00Z".to_string(),
-            modified_at: "2026-01-01T00:00:00Z".to_string(),
-            is_deprecated: false,
-            deprecation_reason: None,
         };
+        db.upsert_source_file(&record).unwrap();
+        record.content_hash = "def".to_string();
+        db.upsert_source_file(&record).unwrap();
 
-        db.upsert_skill(&record).unwrap();
-        let fetched = db.get_skill("git-commit").unwrap().unwrap();
-        assert_eq!(record, fetched);
+        assert_eq!(
+            db.get_source_file(&record.path).unwrap(),
+            Some(record.clone())
+        );
+        assert_eq!(db.list_source_files().unwrap(), vec![record.clone()]);
+
+        db.delete_source_file(&record.path).unwrap();
+        assert!(db.get_source_file(&record.path).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_bandit_state_version_check() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+
+        assert!(db.get_bandit_state("contextual").unwrap().is_none());
+        assert!(db.put_bandit_state("contextual", "{\"a\":1}", 0).unwrap());
+        // A second writer that also saw "no row" loses
+        assert!(!db.put_bandit_state("contextual", "{\"b\":1}", 0).unwrap());
+
+        let stored = db.get_bandit_state("contextual").unwrap().unwrap();
+        assert_eq!(
+            (stored.state_json.as_str(), stored.version),
+            ("{\"a\":1}", 1)
+        );
+        assert!(db.put_bandit_state("contextual", "{\"c\":1}", 1).unwrap());
+        assert!(!db.put_bandit_state("contextual", "{\"d\":1}", 1).unwrap());
+        assert_eq!(
+            db.get_bandit_state("contextual").unwrap().unwrap().version,
+            2
+        );
     }
 
     #[test]
@@ -2256,6 +4545,234 @@ mod tests {
         assert_eq!(db.search_fts("deploy", 10).unwrap().len(), 0);
     }
 
+    #[test]
+    fn test_list_skills_filtered_pushes_predicates_into_sql() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let record =
+            |id: &str, layer: &str, tags: &str, quality: f64, modified_at: &str| SkillRecord {
+                id: id.to_string(),
+                name: id.to_string(),
+                description: String::new(),
+                version: None,
+                author: None,
+                source_path: format!("/skills/{id}"),
+                source_layer: layer.to_string(),
+                git_remote: None,
+                git_commit: None,
+                content_hash: id.to_string(),
+                body: String::new(),
+                metadata_json: format!(r#"{{"tags":{tags}}}"#),
+                assets_json: "{}".to_string(),
+                token_count: 10,
+                quality_score: quality,
+                indexed_at: modified_at.to_string(),
+                modified_at: modified_at.to_string(),
+                is_deprecated: false,
+                deprecation_reason: None,
+            };
+        db.upsert_skill(&record(
+            "alpha",
+            "project",
+            r#"["Rust","cli"]"#,
+            0.9,
+            "2026-03-01T00:00:00Z",
+        ))
+        .unwrap();
+        db.upsert_skill(&record(
+            "beta",
+            "org",
+            r#""rust, git""#,
+            0.5,
+            "2026-01-01T00:00:00Z",
+        ))
+        .unwrap();
+        db.upsert_skill(&record(
+            "gamma",
+            "project",
+            "[]",
+            0.8,
+            "2026-02-01 12:00:00",
+        ))
+        .unwrap();
+        db.update_skill_deprecation("gamma", true, Some("old"))
+            .unwrap();
+
+        let ids = |query: &SkillListQuery| -> Vec<String> {
+            db.list_skills_filtered(query)
+                .unwrap()
+                .into_iter()
+                .map(|s| s.id)
+                .collect()
+        };
+        let all = SkillListQuery {
+            limit: 50,
+            ..SkillListQuery::default()
+        };
+
+        assert_eq!(ids(&all), ["alpha", "beta", "gamma"]);
+        // Tags are ANDed and matched case-insensitively, from arrays or strings.
+        let tagged = |tags: &[&str]| SkillListQuery {
+            tags: tags.iter().map(ToString::to_string).collect(),
+            ..all.clone()
+        };
+        assert_eq!(ids(&tagged(&["rust"])), ["alpha", "beta"]);
+        assert_eq!(ids(&tagged(&["RUST", "cli"])), ["alpha"]);
+        assert!(ids(&tagged(&["cli", "git"])).is_empty());
+
+        let layered = SkillListQuery {
+            layers: vec!["project".to_string()],
+            ..all.clone()
+        };
+        assert_eq!(ids(&layered), ["alpha", "gamma"]);
+        let active = SkillListQuery {
+            deprecated: Some(false),
+            min_quality: Some(0.6),
+            ..all.clone()
+        };
+        assert_eq!(ids(&active), ["alpha"]);
+        // Both timestamp spellings compare correctly.
+        let recent = SkillListQuery {
+            updated_since: Some("2026-02-01T00:00:00Z".parse().unwrap()),
+            sort: SkillListSort::Updated,
+            ..all.clone()
+        };
+        assert_eq!(ids(&recent), ["alpha", "gamma"]);
+        let by_quality = SkillListQuery {
+            sort: SkillListSort::Quality,
+            limit: 2,
+            offset: 1,
+            ..all.clone()
+        };
+        assert_eq!(ids(&by_quality), ["gamma", "beta"]);
+
+        // Re-upserting replaces tags; deleting removes them.
+        db.upsert_skill(&record(
+            "alpha",
+            "project",
+            r#"["go"]"#,
+            0.9,
+            "2026-03-01T00:00:00Z",
+        ))
+        .unwrap();
+        assert_eq!(ids(&tagged(&["rust"])), ["beta"]);
+        db.delete_skill("beta").unwrap();
+        assert!(ids(&tagged(&["rust"])).is_empty());
+
+        // Rows written before `skill_tags` existed are picked up by the backfill.
+        db.conn().execute("DELETE FROM skill_tags").unwrap();
+        assert!(ids(&tagged(&["go"])).is_empty());
+        assert_eq!(db.backfill_skill_tags().unwrap(), 1);
+        assert_eq!(ids(&tagged(&["go"])), ["alpha"]);
+    }
+
+    #[test]
+    fn test_derived_tags_filter_alongside_author_tags() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        for (id, tags) in [("alpha", r#"["cli"]"#), ("beta", "[]")] {
+            db.upsert_skill(&SkillRecord {
+                id: id.to_string(),
+                name: id.to_string(),
+                description: String::new(),
+                version: None,
+                author: None,
+                source_path: format!("/skills/{id}"),
+                source_layer: "project".to_string(),
+                git_remote: None,
+                git_commit: None,
+                content_hash: id.to_string(),
+                body: String::new(),
+                metadata_json: format!(r#"{{"tags":{tags}}}"#),
+                assets_json: "{}".to_string(),
+                token_count: 10,
+                quality_score: 0.5,
+                indexed_at: "2026-03-01T00:00:00Z".to_string(),
+                modified_at: "2026-03-01T00:00:00Z".to_string(),
+                is_deprecated: false,
+                deprecation_reason: None,
+            })
+            .unwrap();
+        }
+        let derived = |tag: &str, kind: &str| DerivedTagRecord {
+            tag: tag.to_string(),
+            kind: kind.to_string(),
+        };
+        let tagged = |tags: &[&str]| -> Vec<String> {
+            db.list_skills_filtered(&SkillListQuery {
+                tags: tags.iter().map(ToString::to_string).collect(),
+                limit: 50,
+                ..SkillListQuery::default()
+            })
+            .unwrap()
+            .into_iter()
+            .map(|s| s.id)
+            .collect()
+        };
+
+        assert_eq!(db.skills_without_derived_tags().unwrap(), ["alpha", "beta"]);
+        let tags = [derived("rust", "language"), derived("cargo", "framework")];
+        db.replace_derived_tags("alpha", &tags).unwrap();
+        // Re-deriving the same tags writes the same rows
+        db.replace_derived_tags("alpha", &tags).unwrap();
+        assert_eq!(
+            db.get_derived_tags("alpha").unwrap(),
+            [derived("cargo", "framework"), derived("rust", "language")]
+        );
+        assert_eq!(db.skills_without_derived_tags().unwrap(), ["beta"]);
+
+        // Author and derived tags are ANDed like any other tags
+        assert_eq!(tagged(&["Rust"]), ["alpha"]);
+        assert_eq!(tagged(&["rust", "cli"]), ["alpha"]);
+        assert!(tagged(&["rust", "go"]).is_empty());
+        assert_eq!(
+            db.derived_tags_by_skill().unwrap()["alpha"],
+            ["cargo", "rust"]
+        );
+
+        db.delete_skill("alpha").unwrap();
+        assert!(db.get_derived_tags("alpha").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_skill_resources_replace_and_delete() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let resource = |path: &str, size: i64| SkillResourceRecord {
+            path: path.to_string(),
+            size,
+            content_hash: format!("hash-{path}"),
+        };
+
+        db.replace_skill_resources(
+            "alpha",
+            &[
+                resource("schemas/input.json", 2),
+                resource("resources/a.md", 10),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            db.get_skill_resources("alpha").unwrap(),
+            [
+                resource("resources/a.md", 10),
+                resource("schemas/input.json", 2)
+            ]
+        );
+
+        // Re-indexing replaces the set, dropping removed files
+        db.replace_skill_resources("alpha", &[resource("resources/a.md", 12)])
+            .unwrap();
+        assert_eq!(
+            db.get_skill_resources("alpha").unwrap(),
+            [resource("resources/a.md", 12)]
+        );
+        assert!(db.get_skill_resources("beta").unwrap().is_empty());
+
+        db.delete_skill("alpha").unwrap();
+        assert!(db.get_skill_resources("alpha").unwrap().is_empty());
+    }
+
     #[test]
     fn test_embedding_roundtrip_and_cache() {
         let dir = tempdir().unwrap();
@@ -2313,6 +4830,11 @@ mod tests {
             .unwrap()
             .unwrap();
         assert_eq!(cached.skill_id, "git");
+
+        let all = db.get_all_embedding_records().unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].embedder_type, "hash");
+        assert_eq!(all[0].embedding.len(), 32);
     }
 
     #[test]
@@ -2402,6 +4924,301 @@ mod tests {
         assert_eq!(reviews[0].action, "confirm_injection");
     }
 
+    #[test]
+    fn test_layer_resolution_roundtrip_and_forget() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+
+        let record = |skill_id: &str, hash: &str, strategy: &str| LayerResolutionRecord {
+            skill_id: skill_id.to_string(),
+            candidates_hash: hash.to_string(),
+            strategy: strategy.to_string(),
+            resolved_at: chrono::Utc::now().to_rfc3339(),
+        };
+        db.upsert_layer_resolution(&record("a", "h1", "prefer-project"))
+            .unwrap();
+        db.upsert_layer_resolution(&record("a", "h1", "merge"))
+            .unwrap();
+        db.upsert_layer_resolution(&record("b", "h2", "newest"))
+            .unwrap();
+
+        let stored = db.get_layer_resolution("a", "h1").unwrap().unwrap();
+        assert_eq!(stored.strategy, "merge");
+        assert!(db.get_layer_resolution("a", "h-other").unwrap().is_none());
+        assert_eq!(db.list_layer_resolutions().unwrap().len(), 2);
+
+        assert_eq!(db.delete_layer_resolutions(Some("a")).unwrap(), 1);
+        assert!(db.get_layer_resolution("a", "h1").unwrap().is_none());
+        assert_eq!(db.delete_layer_resolutions(None).unwrap(), 1);
+        assert!(db.list_layer_resolutions().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_skill_trials_close_once_and_aggregate() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let trial = |id: &str, skill: &str, started_at: &str| SkillTrialRecord {
+            id: id.to_string(),
+            skill_id: skill.to_string(),
+            context_fingerprint: "00000000000000ff".to_string(),
+            disclosure_level: "standard".to_string(),
+            started_at: started_at.to_string(),
+            outcome: None,
+            notes: None,
+            closed_at: None,
+        };
+        db.insert_skill_trial(&trial("t1", "rust-errors", "2026-01-01T00:00:00Z"))
+            .unwrap();
+        db.insert_skill_trial(&trial("t2", "rust-errors", "2026-01-05T00:00:00Z"))
+            .unwrap();
+        db.insert_skill_trial(&trial("t3", "git-flow", "2026-01-09T00:00:00Z"))
+            .unwrap();
+
+        assert!(
+            db.close_skill_trial("t1", "success", Some("fixed it"), "2026-01-02T00:00:00Z")
+                .unwrap()
+        );
+        assert!(
+            !db.close_skill_trial("t1", "failure", None, "2026-01-03T00:00:00Z")
+                .unwrap()
+        );
+        assert!(
+            !db.close_skill_trial("missing", "failure", None, "2026-01-03T00:00:00Z")
+                .unwrap()
+        );
+        let closed = db.get_skill_trial("t1").unwrap().unwrap();
+        assert_eq!(closed.outcome.as_deref(), Some("success"));
+        assert_eq!(closed.notes.as_deref(), Some("fixed it"));
+
+        let open = db.list_skill_trials(true, None).unwrap();
+        assert_eq!(
+            open.iter().map(|t| t.id.as_str()).collect::<Vec<_>>(),
+            ["t2", "t3"]
+        );
+        let dangling = db
+            .list_skill_trials(true, Some("2026-01-08T00:00:00Z"))
+            .unwrap();
+        assert_eq!(dangling.len(), 1);
+        assert_eq!(dangling[0].id, "t2");
+
+        let stats = db.list_trial_stats(None).unwrap();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].skill_id, "git-flow");
+        assert_eq!(stats[1].trials, 2);
+        assert_eq!(stats[1].success, 1);
+        assert_eq!(stats[1].open, 1);
+        assert_eq!(db.list_trial_stats(Some("git-flow")).unwrap()[0].open, 1);
+    }
+
+    #[test]
+    fn test_mined_feedback_is_recorded_once_per_session_and_skill() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        db.record_skill_usage("rust-errors", None, 2, None, None, None, Some("sess-1"))
+            .unwrap();
+        db.record_skill_usage("rust-errors", None, 2, None, None, None, Some("sess-2"))
+            .unwrap();
+
+        let loads = db.list_session_skill_loads("sess-1").unwrap();
+        assert_eq!(loads.len(), 1);
+        assert_eq!(loads[0].0, "rust-errors");
+
+        let record = MinedFeedbackRecord {
+            session_id: "sess-1".to_string(),
+            skill_id: "rust-errors".to_string(),
+            verdict: "success".to_string(),
+            reward: 0.8,
+            signals_json: "{}".to_string(),
+            mined_at: "2026-01-01T00:00:00Z".to_string(),
+        };
+        assert!(db.insert_mined_feedback(&record).unwrap());
+        let again = MinedFeedbackRecord {
+            verdict: "failure".to_string(),
+            reward: 0.2,
+            ..record.clone()
+        };
+        assert!(!db.insert_mined_feedback(&again).unwrap());
+        assert_eq!(db.list_mined_feedback("sess-1").unwrap(), vec![record]);
+        assert!(db.list_mined_feedback("sess-2").unwrap().is_empty());
+
+        assert_eq!(
+            db.record_session_outcome("rust-errors", "sess-1", true)
+                .unwrap(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_dedup_dismissals_are_order_independent() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        db.dismiss_dedup_pair(
+            "rust-errors-b",
+            "rust-errors-a",
+            None,
+            "2026-01-01T00:00:00Z",
+        )
+        .unwrap();
+        db.dismiss_dedup_pair(
+            "rust-errors-a",
+            "rust-errors-b",
+            Some("different audiences"),
+            "2026-01-02T00:00:00Z",
+        )
+        .unwrap();
+
+        let records = db.list_dedup_dismissals().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].skill_a, "rust-errors-a");
+        assert_eq!(records[0].reason.as_deref(), Some("different audiences"));
+
+        assert!(
+            db.delete_dedup_dismissal("rust-errors-b", "rust-errors-a")
+                .unwrap()
+        );
+        assert!(
+            !db.delete_dedup_dismissal("rust-errors-b", "rust-errors-a")
+                .unwrap()
+        );
+        assert!(db.list_dedup_dismissals().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_uncertainties_roundtrip_by_status_and_instance() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let record = |id: &str, status: &str, created_at: &str| UncertaintyRecord {
+            id: id.to_string(),
+            instance_key: Some("pattern-a".to_string()),
+            reason: "high_variance".to_string(),
+            confidence: 0.5,
+            status: status.to_string(),
+            pattern_json: "{}".to_string(),
+            suggested_queries: "[]".to_string(),
+            item_json: "{}".to_string(),
+            created_at: created_at.to_string(),
+            updated_at: created_at.to_string(),
+        };
+        db.upsert_uncertainty(&record("u2", "pending", "2026-01-02T00:00:00Z"))
+            .unwrap();
+        db.upsert_uncertainty(&record("u1", "pending", "2026-01-01T00:00:00Z"))
+            .unwrap();
+        db.upsert_uncertainty(&record("u1", "rejected", "2026-01-01T00:00:00Z"))
+            .unwrap();
+
+        let all: Vec<String> = db
+            .list_uncertainties(None)
+            .unwrap()
+            .into_iter()
+            .map(|r| r.id)
+            .collect();
+        assert_eq!(all, ["u1", "u2"]);
+        assert_eq!(db.list_uncertainties(Some("pending")).unwrap().len(), 1);
+        assert_eq!(
+            db.get_uncertainty("u1").unwrap().unwrap().status,
+            "rejected"
+        );
+        assert!(db.get_uncertainty("u3").unwrap().is_none());
+        assert_eq!(db.uncertainties_for_instance("pattern-a").unwrap().len(), 2);
+        assert!(
+            db.uncertainties_for_instance("pattern-b")
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_suggestion_cooldowns_expire_on_read() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+
+        let now = chrono::Utc::now();
+        let record = |skill_id: &str, expires_in: i64| SuggestionCooldownRecord {
+            skill_id: skill_id.to_string(),
+            context_fingerprint: format!("{:016x}", 42),
+            agent_id: None,
+            response: "shown".to_string(),
+            dismissals: 0,
+            started_at: now.to_rfc3339(),
+            expires_at: (now + chrono::Duration::seconds(expires_in)).to_rfc3339(),
+        };
+        db.upsert_suggestion_cooldown(&record("a", 300)).unwrap();
+        db.upsert_suggestion_cooldown(&record("b", -1)).unwrap();
+        db.upsert_suggestion_cooldown(&record("c", 60)).unwrap();
+
+        let active = db.list_suggestion_cooldowns(&now.to_rfc3339()).unwrap();
+        let ids: Vec<_> = active.iter().map(|r| r.skill_id.as_str()).collect();
+        assert_eq!(ids, ["c", "a"]);
+
+        // Each agent keeps its own row for the same skill and context
+        let cursor = SuggestionCooldownRecord {
+            agent_id: Some("cursor".to_string()),
+            ..record("a", 600)
+        };
+        db.upsert_suggestion_cooldown(&cursor).unwrap();
+        let active = db.list_suggestion_cooldowns(&now.to_rfc3339()).unwrap();
+        assert_eq!(active.len(), 3);
+        assert_eq!(active[2], cursor);
+        assert_eq!(active[1].agent_id, None);
+
+        assert_eq!(db.delete_suggestion_cooldowns(Some("c")).unwrap(), 1);
+        // "b" was already deleted by the read above
+        assert_eq!(db.delete_suggestion_cooldowns(None).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_quarantine_content_release_and_purge() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+
+        let record = |id: &str, created_at: &str| QuarantineRecord {
+            quarantine_id: id.to_string(),
+            session_id: "sess_1".to_string(),
+            message_index: 0,
+            content_hash: format!("hash-{id}"),
+            safe_excerpt: "[redacted]".to_string(),
+            acip_classification: AcipClassification::Disallowed {
+                category: "prompt_injection".to_string(),
+                action: "quarantine".to_string(),
+            },
+            audit_tag: None,
+            created_at: created_at.to_string(),
+            replay_command: String::new(),
+        };
+        db.insert_quarantine_record(&record("q_old", "2020-01-01T00:00:00Z"))
+            .unwrap();
+        db.insert_quarantine_record(&record("q_new", &chrono::Utc::now().to_rfc3339()))
+            .unwrap();
+        db.insert_quarantine_content("q_old", "tool", "ignore previous instructions")
+            .unwrap();
+        db.insert_quarantine_content("q_new", "user", "please ignore all instructions")
+            .unwrap();
+
+        let content = db.get_quarantine_content("q_new").unwrap().unwrap();
+        assert_eq!(content.source, "user");
+        assert!(content.released_at.is_none());
+        assert!(db.get_quarantine_content("q_missing").unwrap().is_none());
+
+        db.release_quarantine_record("q_new").unwrap();
+        let released = db.get_quarantine_content("q_new").unwrap().unwrap();
+        assert!(released.released_at.is_some());
+        assert_eq!(
+            db.list_quarantine_reviews("q_new").unwrap()[0].action,
+            "release"
+        );
+        assert!(db.release_quarantine_record("q_new").is_err());
+
+        db.insert_quarantine_review("q_old", "confirm_injection", None)
+            .unwrap();
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(30);
+        let purged = db.purge_quarantine_before(cutoff).unwrap();
+        assert_eq!(purged, vec!["q_old".to_string()]);
+        assert!(db.get_quarantine_record("q_old").unwrap().is_none());
+        assert!(db.get_quarantine_content("q_old").unwrap().is_none());
+        assert!(db.list_quarantine_reviews("q_old").unwrap().is_empty());
+        assert!(db.get_quarantine_record("q_new").unwrap().is_some());
+    }
+
     #[test]
     fn test_list_skills_order_and_pagination() {
         let dir = tempdir().unwrap();
@@ -2459,6 +5276,28 @@ mod tests {
         let second = db.list_skills(1, 1).unwrap();
         assert_eq!(second.len(), 1);
         assert_eq!(second[0].id, "skill-older");
+
+        // Cursor paging walks ids in order and skips deprecated skills
+        let page = db.list_skills_after(None, 1, false).unwrap();
+        assert_eq!(page[0].id, "skill-newer");
+        let page = db.list_skills_after(Some("skill-newer"), 1, false).unwrap();
+        assert_eq!(page[0].id, "skill-older");
+        assert!(
+            db.list_skills_after(Some("skill-older"), 1, false)
+                .unwrap()
+                .is_empty()
+        );
+        db.update_skill_deprecation("skill-newer", true, Some("replaced"))
+            .unwrap();
+        let ids = |records: Vec<SkillRecord>| records.into_iter().map(|r| r.id).collect::<Vec<_>>();
+        assert_eq!(
+            ids(db.list_skills_after(None, 10, false).unwrap()),
+            ["skill-older"]
+        );
+        assert_eq!(
+            ids(db.list_skills_after(None, 10, true).unwrap()),
+            ["skill-newer", "skill-older"]
+        );
     }
 
     #[test]
@@ -2537,6 +5376,94 @@ mod tests {
         assert_eq!(count, 1); // One rule with evidence
     }
 
+    #[test]
+    fn test_orphaned_evidence_is_found_and_deleted() {
+        use crate::core::EvidenceCoverage;
+
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        for id in ["kept", "gone"] {
+            db.upsert_skill(&SkillRecord {
+                id: id.to_string(),
+                name: id.to_string(),
+                description: String::new(),
+                version: None,
+                author: None,
+                source_path: format!("/skills/{id}"),
+                source_layer: "project".to_string(),
+                git_remote: None,
+                git_commit: None,
+                content_hash: id.to_string(),
+                body: String::new(),
+                metadata_json: "{}".to_string(),
+                assets_json: "{}".to_string(),
+                token_count: 0,
+                quality_score: 0.5,
+                indexed_at: "2026-01-01T00:00:00Z".to_string(),
+                modified_at: "2026-01-01T00:00:00Z".to_string(),
+                is_deprecated: false,
+                deprecation_reason: None,
+            })
+            .unwrap();
+            for rule in ["r1", "r2"] {
+                db.upsert_evidence(id, rule, &[], &EvidenceCoverage::default())
+                    .unwrap();
+            }
+        }
+        assert_eq!(db.orphaned_evidence().unwrap().rows, 0);
+
+        db.conn().execute("PRAGMA foreign_keys = OFF").unwrap();
+        db.delete_skill("gone").unwrap();
+        let orphaned = db.orphaned_evidence().unwrap();
+        assert_eq!(orphaned.skill_ids, vec!["gone".to_string()]);
+        assert_eq!(orphaned.rows, 2);
+        assert!(orphaned.estimated_bytes > 0);
+
+        assert_eq!(db.delete_orphaned_evidence().unwrap(), 2);
+        assert_eq!(db.orphaned_evidence().unwrap(), OrphanedEvidence::default());
+        assert_eq!(db.count_skill_evidence("kept").unwrap(), 2);
+        db.checkpoint_wal().unwrap();
+    }
+
+    #[test]
+    fn test_snapshot_includes_uncheckpointed_writes() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        db.upsert_skill(&SkillRecord {
+            id: "snap".to_string(),
+            name: "Snap".to_string(),
+            description: String::new(),
+            version: None,
+            author: None,
+            source_path: "/skills/snap".to_string(),
+            source_layer: "project".to_string(),
+            git_remote: None,
+            git_commit: None,
+            content_hash: "snap".to_string(),
+            body: String::new(),
+            metadata_json: "{}".to_string(),
+            assets_json: "{}".to_string(),
+            token_count: 0,
+            quality_score: 0.5,
+            indexed_at: "2026-01-01T00:00:00Z".to_string(),
+            modified_at: "2026-01-01T00:00:00Z".to_string(),
+            is_deprecated: false,
+            deprecation_reason: None,
+        })
+        .unwrap();
+
+        let dest = dir.path().join("snapshot.db");
+        db.snapshot_to(&dest).unwrap();
+        assert!(db.snapshot_to(&dest).is_err());
+
+        let snapshot = Database::open_existing(&dest).unwrap();
+        assert_eq!(snapshot.count_skills().unwrap(), 1);
+        assert_eq!(
+            migrations::current_version(snapshot.conn()).unwrap(),
+            migrations::SCHEMA_VERSION
+        );
+    }
+
     #[test]
     fn test_evidence_multiple_rules_and_list_all() {
         use crate::core::{EvidenceCoverage, EvidenceLevel, EvidenceRef};
@@ -2595,6 +5522,10 @@ mod tests {
         assert_eq!(all_evidence[0].skill_id, "multi-rule-skill");
         assert_eq!(all_evidence[0].rule_id, "rule-1");
         assert_eq!(all_evidence[2].rule_id, "rule-3");
+        assert_eq!(
+            db.list_evidence_skill_ids().unwrap(),
+            vec!["multi-rule-skill".to_string()]
+        );
 
         // Get evidence index
         let index = db.get_evidence("multi-rule-skill").unwrap();
@@ -18,7 +18,7 @@ use uuid::Uuid;
 use crate::core::{SkillLayer, SkillSlicer, SkillSpec, spec_lens::compile_markdown};
 use crate::error::{MsError, Result};
 
-use super::git::GitArchive;
+use super::git::{GitArchive, Provenance};
 use super::sqlite::Database;
 
 // =============================================================================
@@ -103,6 +103,21 @@ impl TxRecord {
 // GLOBAL FILE LOCK
 // =============================================================================
 
+/// How long writers wait for a lock before failing with [`MsError::Busy`]
+/// when `MS_LOCK_TIMEOUT` (seconds) is unset.
+const DEFAULT_LOCK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long writers wait for `ms.lock`, the search index writer, or the
+/// database write lock: `MS_LOCK_TIMEOUT` seconds, default 30.
+#[must_use]
+pub fn lock_timeout() -> Duration {
+    std::env::var("MS_LOCK_TIMEOUT")
+        .ok()
+        .and_then(|value| value.trim().parse::<f64>().ok())
+        .filter(|secs| secs.is_finite() && *secs >= 0.0)
+        .map_or(DEFAULT_LOCK_TIMEOUT, Duration::from_secs_f64)
+}
+
 /// Advisory file lock for coordinating dual-persistence writes
 pub struct GlobalLock {
     #[allow(dead_code)]
@@ -116,16 +131,9 @@ impl GlobalLock {
 
     /// Acquire exclusive lock (blocking)
     pub fn acquire(ms_root: &Path) -> Result<Self> {
-        let lock_path = ms_root.join(Self::LOCK_FILENAME);
         fs::create_dir_all(ms_root)?;
-
-        let lock_file = OpenOptions::new()
-            .read(true)
-            .write(true)
-            .create(true)
-            .truncate(false)
-            .open(&lock_path)
-            .map_err(|e| MsError::TransactionFailed(format!("open lock file: {e}")))?;
+        let lock_path = ms_root.join(Self::LOCK_FILENAME);
+        let lock_file = Self::open_lock_file(&lock_path)?;
 
         // Use fs2's cross-platform exclusive lock (blocking)
         lock_file
@@ -144,16 +152,15 @@ impl GlobalLock {
 
     /// Try to acquire lock without blocking
     pub fn try_acquire(ms_root: &Path) -> Result<Option<Self>> {
-        let lock_path = ms_root.join(Self::LOCK_FILENAME);
         fs::create_dir_all(ms_root)?;
+        Self::try_acquire_at(&ms_root.join(Self::LOCK_FILENAME))
+    }
 
-        let lock_file = OpenOptions::new()
-            .read(true)
-            .write(true)
-            .create(true)
-            .truncate(false)
-            .open(&lock_path)
-            .map_err(|e| MsError::TransactionFailed(format!("open lock file: {e}")))?;
+    /// Try to acquire the lock file at `lock_path` without blocking. The
+    /// global lock lives at `<ms_root>/ms.lock`; other write locks (such as
+    /// the search index writer's) use their own file with the same protocol.
+    pub fn try_acquire_at(lock_path: &Path) -> Result<Option<Self>> {
+        let lock_file = Self::open_lock_file(lock_path)?;
 
         // Use fs2's cross-platform try_lock (non-blocking)
         match lock_file.try_lock_exclusive() {
@@ -172,20 +179,25 @@ impl GlobalLock {
         // Write lock holder info through the locked file handle
         Self::write_holder_info(&lock_file)?;
 
-        debug!("Acquired global lock (non-blocking) at {:?}", lock_path);
+        debug!("Acquired lock (non-blocking) at {:?}", lock_path);
         Ok(Some(Self {
             lock_file,
-            lock_path,
+            lock_path: lock_path.to_path_buf(),
         }))
     }
 
     /// Acquire with timeout (polling)
     pub fn acquire_timeout(ms_root: &Path, timeout: Duration) -> Result<Option<Self>> {
+        fs::create_dir_all(ms_root)?;
+        Self::acquire_timeout_at(&ms_root.join(Self::LOCK_FILENAME), timeout)
+    }
+
+    fn acquire_timeout_at(lock_path: &Path, timeout: Duration) -> Result<Option<Self>> {
         let start = std::time::Instant::now();
         let poll_interval = Duration::from_millis(50);
 
         while start.elapsed() < timeout {
-            if let Some(lock) = Self::try_acquire(ms_root)? {
+            if let Some(lock) = Self::try_acquire_at(lock_path)? {
                 return Ok(Some(lock));
             }
             std::thread::sleep(poll_interval);
@@ -195,18 +207,60 @@ impl GlobalLock {
         Ok(None)
     }
 
+    /// Acquire the global lock, waiting up to `timeout`; past it, fail with
+    /// [`MsError::Busy`] naming the holder.
+    pub fn acquire_or_busy(ms_root: &Path, timeout: Duration) -> Result<Self> {
+        fs::create_dir_all(ms_root)?;
+        Self::acquire_at_or_busy(&ms_root.join(Self::LOCK_FILENAME), "ms.lock", timeout)
+    }
+
+    /// [`acquire_or_busy`](Self::acquire_or_busy) for the lock file at
+    /// `lock_path`; `resource` names what it guards in the error.
+    pub fn acquire_at_or_busy(lock_path: &Path, resource: &str, timeout: Duration) -> Result<Self> {
+        if let Some(lock) = Self::acquire_timeout_at(lock_path, timeout)? {
+            return Ok(lock);
+        }
+        Err(MsError::Busy {
+            resource: resource.to_string(),
+            pid: Self::holder_at(lock_path)?.map(|holder| holder.pid),
+        })
+    }
+
+    /// Path of the global lock file under `ms_root`.
+    #[must_use]
+    pub fn lock_path(ms_root: &Path) -> PathBuf {
+        ms_root.join(Self::LOCK_FILENAME)
+    }
+
+    /// Pid of another live process holding the lock file at `lock_path`, to
+    /// name it in [`MsError::Busy`]. `None` when the lock is free, held by
+    /// this process, or its holder cannot be read.
+    #[must_use]
+    pub fn other_holder_pid(lock_path: &Path) -> Option<u32> {
+        Self::holder_at(lock_path)
+            .ok()
+            .flatten()
+            .map(|holder| holder.pid)
+            .filter(|pid| *pid != std::process::id())
+    }
+
     /// Check lock status without acquiring.
     ///
     /// **Note**: This returns cached holder info from the lock file, which may
     /// be stale. The actual OS-level flock is authoritative - use `is_locked()`
     /// for a definitive check of whether the lock is currently held.
     pub fn status(ms_root: &Path) -> Result<Option<LockHolder>> {
-        let lock_path = ms_root.join(Self::LOCK_FILENAME);
+        Self::holder_at(&ms_root.join(Self::LOCK_FILENAME))
+    }
+
+    /// Holder of the lock file at `lock_path`, or `None` when it is free or
+    /// its recorded holder is stale.
+    pub fn holder_at(lock_path: &Path) -> Result<Option<LockHolder>> {
         if !lock_path.exists() {
             return Ok(None);
         }
 
-        let content = fs::read_to_string(&lock_path)?;
+        let content = fs::read_to_string(lock_path)?;
         if content.is_empty() {
             return Ok(None);
         }
@@ -214,15 +268,10 @@ impl GlobalLock {
         let holder: LockHolder = serde_json::from_str(&content)
             .map_err(|e| MsError::TransactionFailed(format!("parse lock holder: {e}")))?;
 
-        // Cheap fast path: check if the recorded process is still alive using
-        // /proc on Linux. A dead holder means the lock is stale.
-        #[cfg(target_os = "linux")]
-        {
-            let proc_path = format!("/proc/{}", holder.pid);
-            if !std::path::Path::new(&proc_path).exists() {
-                // Process no longer exists - lock is stale
-                return Ok(None);
-            }
+        // Cheap fast path: a recorded process that no longer exists, or whose
+        // pid now belongs to a process started at another time, is stale.
+        if holder.is_stale() {
+            return Ok(None);
         }
 
         // Cross-platform authoritative check: the holder info in the file is
@@ -231,7 +280,7 @@ impl GlobalLock {
         // recorded holder can outlive the actual OS-level flock. If nobody
         // holds the flock right now, the recorded holder is stale and the
         // lock is effectively free.
-        if !Self::is_locked(ms_root)? {
+        if !Self::is_locked_at(lock_path) {
             return Ok(None);
         }
 
@@ -243,14 +292,16 @@ impl GlobalLock {
     /// Returns true if another process holds the lock, false if it's available.
     /// This performs an actual flock check rather than reading cached info.
     pub fn is_locked(ms_root: &Path) -> Result<bool> {
-        let lock_path = ms_root.join(Self::LOCK_FILENAME);
+        Ok(Self::is_locked_at(&ms_root.join(Self::LOCK_FILENAME)))
+    }
+
+    fn is_locked_at(lock_path: &Path) -> bool {
         if !lock_path.exists() {
-            return Ok(false);
+            return false;
         }
 
-        let lock_file = match OpenOptions::new().read(true).write(true).open(&lock_path) {
-            Ok(f) => f,
-            Err(_) => return Ok(false),
+        let Ok(lock_file) = OpenOptions::new().read(true).write(true).open(lock_path) else {
+            return false;
         };
 
         // Try to acquire lock non-blocking
@@ -258,19 +309,25 @@ impl GlobalLock {
             Ok(()) => {
                 // We got the lock - it wasn't held. Release it.
                 lock_file.unlock().ok();
-                Ok(false)
-            }
-            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
-                // Lock is held by another process
-                Ok(true)
-            }
-            Err(_) => {
-                // Some other error - assume not locked
-                Ok(false)
+                false
             }
+            // Lock is held by another process
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => true,
+            // Some other error - assume not locked
+            Err(_) => false,
         }
     }
 
+    fn open_lock_file(lock_path: &Path) -> Result<File> {
+        OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(lock_path)
+            .map_err(|e| MsError::TransactionFailed(format!("open lock file: {e}")))
+    }
+
     /// Break a stale lock.
     ///
     /// # Safety
@@ -345,14 +402,7 @@ impl GlobalLock {
     fn write_holder_info(file: &File) -> Result<()> {
         use std::io::{Seek, SeekFrom, Write};
 
-        let holder = LockHolder {
-            pid: std::process::id(),
-            acquired_at: Utc::now(),
-            hostname: hostname::get()
-                .ok()
-                .and_then(|h| h.into_string().ok())
-                .unwrap_or_else(|| "unknown".to_string()),
-        };
+        let holder = LockHolder::current();
         let holder_json = serde_json::to_string(&holder)
             .map_err(|e| MsError::TransactionFailed(format!("serialize holder: {e}")))?;
 
@@ -402,6 +452,54 @@ pub struct LockHolder {
     pub acquired_at: DateTime<Utc>,
     /// Hostname of the lock holder
     pub hostname: String,
+    /// Start time of the holder process (clock ticks since boot, Linux
+    /// only), telling a live holder from an unrelated process that reused
+    /// its pid. Absent in lock files written by older versions.
+    #[serde(default)]
+    pub started_at: Option<u64>,
+}
+
+impl LockHolder {
+    fn current() -> Self {
+        let pid = std::process::id();
+        Self {
+            pid,
+            acquired_at: Utc::now(),
+            hostname: hostname::get()
+                .ok()
+                .and_then(|h| h.into_string().ok())
+                .unwrap_or_else(|| "unknown".to_string()),
+            started_at: process_start_time(pid),
+        }
+    }
+
+    /// Whether the recorded process is gone, or its pid now belongs to a
+    /// process started at another time. Only decidable on Linux; elsewhere
+    /// the flock check in [`GlobalLock::status`] is the only signal.
+    #[must_use]
+    pub fn is_stale(&self) -> bool {
+        if !cfg!(target_os = "linux") || !Path::new("/proc/self").exists() {
+            return false;
+        }
+        match (process_start_time(self.pid), self.started_at) {
+            (None, _) => true,
+            (Some(now), Some(recorded)) => now != recorded,
+            (Some(_), None) => false,
+        }
+    }
+}
+
+/// Start time of `pid` from `/proc/<pid>/stat` (field 22), or `None` when
+/// the process does not exist or the platform has no procfs.
+fn process_start_time(pid: u32) -> Option<u64> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+    let stat = fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    // The command name (field 2) is parenthesized and may contain spaces, so
+    // count fields from the last ')': the state is field 3.
+    let rest = &stat[stat.rfind(')')? + 1..];
+    rest.split_whitespace().nth(19)?.parse().ok()
 }
 
 // =============================================================================
@@ -431,12 +529,32 @@ impl TxManager {
     }
 
     /// Write a skill with 2PC guarantees (without global lock)
-    pub fn write_skill(&self, skill: &SkillSpec) -> Result<()> {
-        self.write_skill_with_layer(skill, SkillLayer::Project)
+    pub fn write_skill(&self, skill: &SkillSpec, provenance: &Provenance) -> Result<()> {
+        self.write_skill_with_layer(skill, SkillLayer::Project, provenance)
     }
 
     /// Write a skill with 2PC guarantees and an explicit layer
-    pub fn write_skill_with_layer(&self, skill: &SkillSpec, layer: SkillLayer) -> Result<()> {
+    pub fn write_skill_with_layer(
+        &self,
+        skill: &SkillSpec,
+        layer: SkillLayer,
+        provenance: &Provenance,
+    ) -> Result<()> {
+        self.write_skill_with_message(skill, layer, None, provenance)
+    }
+
+    /// Write a skill with 2PC guarantees, committing it to the archive with
+    /// `message` instead of the default `Update skill <id>`.
+    pub fn write_skill_with_message(
+        &self,
+        skill: &SkillSpec,
+        layer: SkillLayer,
+        message: Option<&str>,
+        provenance: &Provenance,
+    ) -> Result<()> {
+        // Sensitive skills are sealed before anything touches disk.
+        let sealed = crate::security::encryption::seal_for_storage(skill)?;
+        let skill = &sealed;
         let tx = TxRecord::prepare("skill", &skill.metadata.id, skill)?;
         debug!(
             "Starting 2PC transaction {} for skill {}",
@@ -450,7 +568,7 @@ impl TxManager {
         let tx = self.db_write_pending(&tx, layer)?;
 
         // Phase 3: Commit - write to Git
-        let tx = self.git_commit(&tx)?;
+        let tx = self.git_commit(&tx, message, provenance)?;
 
         // Phase 4: Complete - finalize SQLite
         let tx = self.db_mark_committed(&tx)?;
@@ -466,17 +584,17 @@ impl TxManager {
     }
 
     /// Write a skill with global lock coordination
-    pub fn write_skill_locked(&self, skill: &SkillSpec) -> Result<()> {
+    pub fn write_skill_locked(&self, skill: &SkillSpec, provenance: &Provenance) -> Result<()> {
         let _lock = GlobalLock::acquire_timeout(&self.ms_root, Duration::from_secs(30))?
             .ok_or_else(|| {
                 MsError::TransactionFailed("timeout waiting for global lock".to_string())
             })?;
 
-        self.write_skill_with_layer(skill, SkillLayer::Project)
+        self.write_skill_with_layer(skill, SkillLayer::Project, provenance)
     }
 
     /// Batch write skills with a single lock acquisition
-    pub fn write_skills_batch(&self, skills: &[SkillSpec]) -> Result<()> {
+    pub fn write_skills_batch(&self, skills: &[SkillSpec], provenance: &Provenance) -> Result<()> {
         if skills.is_empty() {
             return Ok(());
         }
@@ -484,19 +602,23 @@ impl TxManager {
         let _lock = GlobalLock::acquire(&self.ms_root)?;
 
         for skill in skills {
-            self.write_skill_with_layer(skill, SkillLayer::Project)?;
+            self.write_skill_with_layer(skill, SkillLayer::Project, provenance)?;
         }
 
         Ok(())
     }
 
     /// Delete a skill with 2PC guarantees
-    pub fn delete_skill_locked(&self, skill_id: &str) -> Result<()> {
+    pub fn delete_skill_locked(&self, skill_id: &str, provenance: &Provenance) -> Result<()> {
         let _lock = GlobalLock::acquire_timeout(&self.ms_root, Duration::from_secs(30))?
             .ok_or_else(|| {
                 MsError::TransactionFailed("timeout waiting for global lock".to_string())
             })?;
+        self.delete_skill(skill_id, provenance)
+    }
 
+    /// Delete a skill with 2PC guarantees (without global lock)
+    pub fn delete_skill(&self, skill_id: &str, provenance: &Provenance) -> Result<()> {
         // Create delete transaction record
         let tx = TxRecord {
             id: Uuid::new_v4().to_string(),
@@ -515,7 +637,7 @@ impl TxManager {
         self.write_tx_record(&tx)?;
 
         // Phase 2: Commit - delete from Git (creates commit)
-        self.git.delete_skill(skill_id)?;
+        self.git.delete_skill(skill_id, provenance)?;
         let mut tx = tx;
         tx.phase = TxPhase::Committed;
         self.db.update_tx_phase(&tx.id, TxPhase::Committed)?;
@@ -586,14 +708,20 @@ impl TxManager {
     }
 
     /// Commit to Git archive
-    fn git_commit(&self, tx: &TxRecord) -> Result<TxRecord> {
+    fn git_commit(
+        &self,
+        tx: &TxRecord,
+        message: Option<&str>,
+        provenance: &Provenance,
+    ) -> Result<TxRecord> {
         debug!("Phase: committed (tx={})", tx.id);
 
         let skill: SkillSpec = serde_json::from_str(&tx.payload_json)
             .map_err(|e| MsError::TransactionFailed(format!("deserialize skill: {e}")))?;
 
         // Write to Git
-        self.git.write_skill(&skill)?;
+        self.git
+            .commit_with_provenance(&skill, message, provenance)?;
 
         // Update phase
         let mut tx = tx.clone();
@@ -1040,6 +1168,7 @@ mod tests {
             pid: std::process::id(),
             acquired_at: Utc::now(),
             hostname: "stale-host".to_string(),
+            started_at: None,
         };
         std::fs::write(
             ms_root.join("ms.lock"),
@@ -1053,6 +1182,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_reused_pid_is_stale() {
+        let live = LockHolder::current();
+        assert!(!live.is_stale());
+        if cfg!(target_os = "linux") && Path::new("/proc/self").exists() {
+            // Same pid, different start time: another process reused the pid
+            let reused = LockHolder {
+                started_at: live.started_at.map(|started| started + 1),
+                ..live.clone()
+            };
+            assert!(reused.is_stale());
+        }
+        // Written by an older version: no start time to compare
+        let legacy = LockHolder {
+            started_at: None,
+            ..live
+        };
+        assert!(!legacy.is_stale());
+    }
+
+    #[test]
+    fn test_acquire_or_busy_names_the_holder() {
+        let dir = tempdir().unwrap();
+        let ms_root = dir.path().to_path_buf();
+
+        let _lock = GlobalLock::acquire(&ms_root).unwrap();
+        match GlobalLock::acquire_or_busy(&ms_root, Duration::from_millis(100)) {
+            Err(MsError::Busy { resource, pid }) => {
+                assert_eq!(resource, "ms.lock");
+                assert_eq!(pid, Some(std::process::id()));
+            }
+            Err(other) => panic!("expected Busy, got {other}"),
+            Ok(_) => panic!("acquired a held lock"),
+        }
+    }
+
     #[test]
     fn test_successful_2pc() {
         let dir = tempdir().unwrap();
@@ -1065,7 +1230,9 @@ mod tests {
         let tx_mgr = TxManager::new(db.clone(), git.clone(), ms_root).unwrap();
 
         let skill = sample_skill("2pc-test");
-        tx_mgr.write_skill(&skill).unwrap();
+        tx_mgr
+            .write_skill(&skill, &Provenance::new("test"))
+            .unwrap();
 
         // Verify skill exists in Git
         let git_skill = git.read_skill("2pc-test").unwrap();
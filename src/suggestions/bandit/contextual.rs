@@ -5,17 +5,34 @@
 //! using Thompson sampling with linear contextual features.
 
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use rand::rng;
 use rand_distr::{Beta, Distribution};
 use serde::{Deserialize, Serialize};
 
 use crate::error::{MsError, Result};
+use crate::storage::Database;
 
 use super::features::ContextFeatures;
 use super::rewards::SkillFeedback;
 
+/// `bandit_state` row that holds the contextual bandit.
+pub const DB_STATE_NAME: &str = "contextual";
+
+/// `bandit_state` row of `agent_id`'s bandit. Each agent learns from its own
+/// feedback; feedback from no particular agent goes to [`DB_STATE_NAME`].
+#[must_use]
+pub fn db_state_name(agent_id: Option<&str>) -> String {
+    agent_id.map_or_else(
+        || DB_STATE_NAME.to_string(),
+        |agent| format!("{DB_STATE_NAME}:{agent}"),
+    )
+}
+
+/// Attempts before a contended read-modify-write gives up.
+const MAX_DB_WRITE_ATTEMPTS: usize = 8;
+
 /// Configuration for the contextual bandit.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContextualBanditConfig {
@@ -141,6 +158,24 @@ pub struct RecommendationComponents {
     pub avg_reward: f64,
 }
 
+/// Weight of the contextual prediction in a recommendation score.
+pub const CONTEXTUAL_WEIGHT: f32 = 0.7;
+
+/// Weight of the Thompson sample in a recommendation score.
+pub const THOMPSON_WEIGHT: f32 = 0.3;
+
+impl RecommendationComponents {
+    /// The recommendation score: weighted contextual and Thompson scores
+    /// plus the exploration bonus, clamped to 0-1.
+    #[must_use]
+    pub fn score(&self) -> f32 {
+        (self.contextual_score * CONTEXTUAL_WEIGHT
+            + self.thompson_score * THOMPSON_WEIGHT
+            + self.exploration_bonus)
+            .clamp(0.0, 1.0)
+    }
+}
+
 /// Contextual multi-armed bandit for skill recommendations.
 ///
 /// Uses Thompson sampling with linear contextual features to learn
@@ -203,34 +238,8 @@ impl ContextualBandit {
     ///
     /// Combines contextual prediction with Thompson sampling and exploration bonus.
     pub fn sample(&mut self, skill_id: &str, features: &ContextFeatures) -> f32 {
-        // Copy config values to avoid borrow conflicts
-        let cold_start_threshold = self.config.cold_start_threshold;
-        let exploration_rate = self.config.exploration_rate;
-        let total_recommendations = self.total_recommendations;
-
-        let arm = self.get_or_create_arm(skill_id);
-
-        // Contextual score from feature weights
-        let contextual_score = sigmoid(features.dot(&arm.feature_weights));
-
-        // Thompson sample for exploration
-        let thompson_sample = arm.thompson_sample();
-
-        // Combine: weighted average of contextual and thompson
-        let combined = contextual_score * 0.7 + thompson_sample * 0.3;
-
-        // Exploration bonus for under-explored arms (UCB-style)
-        let exploration_bonus = if arm.pulls < cold_start_threshold {
-            let exploration_factor = 1.0 - (arm.pulls as f32 / cold_start_threshold as f32);
-            exploration_rate * exploration_factor
-        } else {
-            // UCB bonus decreases as we learn more
-            let t = total_recommendations.max(1) as f32;
-            let n = arm.pulls.max(1) as f32;
-            exploration_rate * (2.0 * t.ln() / n).sqrt()
-        };
-
-        (combined + exploration_bonus).clamp(0.0, 1.0)
+        self.get_or_create_arm(skill_id);
+        self.get_components(skill_id, features).score()
     }
 
     /// Get top-k skill recommendations for the given context.
@@ -245,8 +254,9 @@ impl ContextualBandit {
         let mut recommendations: Vec<Recommendation> = skill_ids
             .iter()
             .map(|skill_id| {
-                let score = self.sample(skill_id, features);
+                // One Thompson draw per arm, so the components add up to the score
                 let components = self.get_components(skill_id, features);
+                let score = components.score();
                 let reason = self.explain_score(skill_id, &components);
 
                 Recommendation {
@@ -402,40 +412,90 @@ impl ContextualBandit {
         }
     }
 
-    /// Save the bandit state to a file.
-    pub fn save(&self, path: &Path) -> Result<()> {
-        if let Some(parent) = path.parent() {
-            std::fs::create_dir_all(parent).map_err(MsError::Io)?;
+    /// Location of the JSON file bandit state was kept in before it moved
+    /// to the database. It is only read to seed the shared bandit.
+    #[must_use]
+    pub fn legacy_path() -> PathBuf {
+        let base = dirs::data_dir().unwrap_or_else(|| PathBuf::from("."));
+        base.join("ms").join("contextual_bandit.json")
+    }
+
+    /// State from the legacy JSON file, or a default bandit without one.
+    fn read_legacy(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
         }
+        let contents = std::fs::read_to_string(path).map_err(MsError::Io)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
 
-        let json = serde_json::to_string_pretty(self)?;
-        let temp_path = path.with_extension("tmp");
-        std::fs::write(&temp_path, json).map_err(MsError::Io)?;
-
-        // Atomic rename
-        match std::fs::rename(&temp_path, path) {
-            Ok(()) => Ok(()),
-            Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => {
-                std::fs::remove_file(path).map_err(MsError::Io)?;
-                std::fs::rename(&temp_path, path).map_err(MsError::Io)?;
-                Ok(())
-            }
-            Err(err) => {
-                let _ = std::fs::remove_file(&temp_path);
-                Err(MsError::Io(err))
+    /// Load `agent_id`'s bandit persisted in the database.
+    ///
+    /// An agent without state of its own starts from the shared bandit.
+    /// Before anything is stored there, state from the legacy JSON file at
+    /// `legacy_path` is used if it exists, otherwise a default bandit.
+    pub fn load_from_db(
+        db: &Database,
+        agent_id: Option<&str>,
+        legacy_path: Option<&Path>,
+    ) -> Result<Self> {
+        Ok(Self::read_db(db, agent_id, legacy_path)?.0)
+    }
+
+    /// Apply `change` to the persisted bandit and write it back.
+    ///
+    /// The row version acts as an optimistic lock: if another `ms` process
+    /// wrote in between, the state is reloaded and `change` reapplied, so
+    /// concurrent updates are neither lost nor interleaved.
+    pub fn update_in_db<F>(
+        db: &Database,
+        agent_id: Option<&str>,
+        legacy_path: Option<&Path>,
+        mut change: F,
+    ) -> Result<Self>
+    where
+        F: FnMut(&mut Self),
+    {
+        let name = db_state_name(agent_id);
+        for _ in 0..MAX_DB_WRITE_ATTEMPTS {
+            let (mut bandit, version) = Self::read_db(db, agent_id, legacy_path)?;
+            change(&mut bandit);
+            let json = serde_json::to_string(&bandit)?;
+            if db.put_bandit_state(&name, &json, version)? {
+                return Ok(bandit);
             }
         }
+        Err(MsError::TransactionFailed(format!(
+            "bandit state kept changing; gave up after {MAX_DB_WRITE_ATTEMPTS} attempts"
+        )))
     }
 
-    /// Load bandit state from a file.
-    pub fn load(path: &Path) -> Result<Self> {
-        if !path.exists() {
-            return Ok(Self::default());
-        }
+    /// Replace `agent_id`'s persisted bandit with a fresh one, discarding
+    /// learned state.
+    pub fn reset_in_db(db: &Database, agent_id: Option<&str>, feature_dim: usize) -> Result<Self> {
+        Self::update_in_db(db, agent_id, None, |bandit| {
+            *bandit = Self::with_feature_dim(feature_dim);
+        })
+    }
 
-        let contents = std::fs::read_to_string(path).map_err(MsError::Io)?;
-        let bandit: Self = serde_json::from_str(&contents)?;
-        Ok(bandit)
+    /// The stored state and its version; 0 when `agent_id` has no row yet.
+    fn read_db(
+        db: &Database,
+        agent_id: Option<&str>,
+        legacy_path: Option<&Path>,
+    ) -> Result<(Self, i64)> {
+        if let Some(record) = db.get_bandit_state(&db_state_name(agent_id))? {
+            return Ok((serde_json::from_str(&record.state_json)?, record.version));
+        }
+        if agent_id.is_some() {
+            let (shared, _) = Self::read_db(db, None, legacy_path)?;
+            return Ok((shared, 0));
+        }
+        let bandit = match legacy_path {
+            Some(path) => Self::read_legacy(path)?,
+            None => Self::default(),
+        };
+        Ok((bandit, 0))
     }
 
     /// Get statistics about the bandit.
@@ -728,6 +788,17 @@ mod tests {
         assert!(recommendations[0].score >= recommendations[1].score);
     }
 
+    #[test]
+    fn test_recommendation_score_matches_components() {
+        let mut bandit = ContextualBandit::with_feature_dim(10);
+        bandit.register_skill("skill-a");
+        bandit.register_skill("skill-b");
+
+        for rec in bandit.recommend(&sample_features(), 2) {
+            assert!((rec.score - rec.components.score()).abs() < f32::EPSILON);
+        }
+    }
+
     #[test]
     fn test_learning_improves_predictions() {
         let mut bandit = ContextualBandit::with_feature_dim(10);
@@ -788,24 +859,95 @@ mod tests {
     }
 
     #[test]
-    fn test_persistence_roundtrip() {
+    fn test_db_persistence_across_processes() {
         let temp_dir = tempfile::tempdir().unwrap();
-        let path = temp_dir.path().join("bandit.json");
+        let db_path = temp_dir.path().join("ms.db");
+        let features = sample_features();
 
-        let mut bandit = ContextualBandit::with_feature_dim(10);
-        bandit.register_skill("skill-a");
-        bandit.update(
-            "skill-a",
-            &sample_features(),
-            &SkillFeedback::ExplicitHelpful,
+        // First "process": record rewards and drop the connection
+        {
+            let db = Database::open(&db_path).unwrap();
+            for _ in 0..20 {
+                ContextualBandit::update_in_db(&db, None, None, |bandit| {
+                    bandit.update("good-skill", &features, &SkillFeedback::ExplicitHelpful);
+                    bandit.update(
+                        "bad-skill",
+                        &features,
+                        &SkillFeedback::ExplicitNotHelpful { reason: None },
+                    );
+                })
+                .unwrap();
+            }
+        }
+
+        // Second "process": a fresh connection ranks by the learned rewards
+        let db = Database::open(&db_path).unwrap();
+        let mut bandit = ContextualBandit::load_from_db(&db, None, None).unwrap();
+        assert_eq!(bandit.arms.get("good-skill").unwrap().pulls, 20);
+        let ranked = bandit.recommend(&features, 2);
+        assert_eq!(ranked[0].skill_id, "good-skill");
+        assert_eq!(
+            db.get_bandit_state(DB_STATE_NAME).unwrap().unwrap().version,
+            20
         );
 
-        bandit.save(&path).unwrap();
+        let reset = ContextualBandit::reset_in_db(&db, None, 10).unwrap();
+        assert_eq!(reset.num_skills(), 0);
+        assert_eq!(
+            ContextualBandit::load_from_db(&db, None, None)
+                .unwrap()
+                .num_skills(),
+            0
+        );
+    }
 
-        let loaded = ContextualBandit::load(&path).unwrap();
-        assert_eq!(loaded.num_skills(), 1);
+    #[test]
+    fn test_db_state_is_kept_per_agent() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Database::open(temp_dir.path().join("ms.db")).unwrap();
+        let features = sample_features();
+
+        ContextualBandit::update_in_db(&db, None, None, |bandit| {
+            bandit.update("shared-skill", &features, &SkillFeedback::ExplicitHelpful);
+        })
+        .unwrap();
+        // An agent starts from the shared state, then learns on its own
+        ContextualBandit::update_in_db(&db, Some("cursor"), None, |bandit| {
+            assert!(bandit.has_skill("shared-skill"));
+            bandit.update(
+                "cursor-skill",
+                &features,
+                &SkillFeedback::ExplicitNotHelpful { reason: None },
+            );
+        })
+        .unwrap();
+
+        let cursor = ContextualBandit::load_from_db(&db, Some("cursor"), None).unwrap();
+        assert!(cursor.has_skill("cursor-skill"));
+        let shared = ContextualBandit::load_from_db(&db, None, None).unwrap();
+        assert!(!shared.has_skill("cursor-skill"));
+        let claude = ContextualBandit::load_from_db(&db, Some("claude-code"), None).unwrap();
+        assert!(!claude.has_skill("cursor-skill"));
+        assert!(db.get_bandit_state("contextual:cursor").unwrap().is_some());
+    }
+
+    #[test]
+    fn test_db_load_imports_legacy_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let legacy = temp_dir.path().join("contextual_bandit.json");
+        let db = Database::open(temp_dir.path().join("ms.db")).unwrap();
+
+        let mut bandit = ContextualBandit::with_feature_dim(10);
+        bandit.register_skill("skill-a");
+        std::fs::write(&legacy, serde_json::to_string(&bandit).unwrap()).unwrap();
+
+        let loaded = ContextualBandit::load_from_db(&db, None, Some(&legacy)).unwrap();
         assert!(loaded.has_skill("skill-a"));
-        assert_eq!(loaded.arms.get("skill-a").unwrap().pulls, 1);
+
+        // Once the database has state, the legacy file is no longer consulted
+        ContextualBandit::reset_in_db(&db, None, 10).unwrap();
+        let loaded = ContextualBandit::load_from_db(&db, None, Some(&legacy)).unwrap();
+        assert!(!loaded.has_skill("skill-a"));
     }
 
     /// Integration test demonstrating learning over time with auto-load feedback.
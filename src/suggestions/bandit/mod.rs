@@ -13,7 +13,10 @@ pub mod types;
 
 pub use bandit::{BanditConfig, SignalBandit};
 pub use context::{ContextKey, ContextModifier, ProjectSize, SuggestionContext, TimeOfDay};
-pub use contextual::{ContextualArm, ContextualBandit, ContextualBanditConfig, Recommendation};
+pub use contextual::{
+    ContextualArm, ContextualBandit, ContextualBanditConfig, Recommendation,
+    RecommendationComponents,
+};
 pub use features::{ContextFeatures, DefaultFeatureExtractor, FeatureExtractor, UserHistory};
 pub use rewards::{SkillFeedback, compute_reward};
 pub use types::{BanditArm, BetaDistribution, Reward, SignalType, SignalWeights};
@@ -1,14 +1,34 @@
 //! Suggestion cooldown cache and helpers.
+//!
+//! Cooldowns live in the `suggestion_cooldowns` table so that every `ms`
+//! process (one per MCP call, typically) sees the same state. A cache is
+//! loaded with [`SuggestionCooldownCache::load_from_db`]; only entries
+//! recorded since then are written back by
+//! [`SuggestionCooldownCache::save_to_db`].
+//!
+//! Each cooldown records the agent that triggered it. A cache viewed for one
+//! agent ([`SuggestionCooldownCache::for_agent`]) honours its own cooldowns in
+//! full and other agents' for at most
+//! [`SuggestionsConfig::cross_agent_cooldown_secs`], so agents sharing a
+//! machine don't surface the same skill moments apart.
 
+use std::collections::HashSet;
 use std::path::Path;
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::error::Result;
+use crate::config::SuggestionsConfig;
+use crate::error::{MsError, Result};
+use crate::storage::Database;
+use crate::storage::sqlite::SuggestionCooldownRecord;
 
 use super::cooldown_storage;
 
+/// Fingerprint of cooldowns that apply in every context, e.g. a dismissal
+/// of a skill that was never suggested.
+pub const ANY_CONTEXT: u64 = 0;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SuggestionResponse {
     pub fingerprint: u64,
@@ -24,12 +44,133 @@ pub enum CooldownStatus {
     NotFound,
 }
 
+/// Why a skill is cooling down.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CooldownResponse {
+    /// Shown by `ms suggest`; held back briefly in the same context
+    #[default]
+    Shown,
+    /// Positive feedback; the agent already has the skill
+    Accepted,
+    /// Negative feedback; held back longer with each repeat
+    Dismissed,
+}
+
+impl CooldownResponse {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Shown => "shown",
+            Self::Accepted => "accepted",
+            Self::Dismissed => "dismissed",
+        }
+    }
+
+    #[must_use]
+    pub fn from_key(key: &str) -> Self {
+        match key {
+            "accepted" => Self::Accepted,
+            "dismissed" => Self::Dismissed,
+            _ => Self::Shown,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CooldownEntry {
     pub fingerprint: u64,
     pub skill_id: String,
     pub suggested_at: DateTime<Utc>,
     pub cooldown_seconds: u64,
+    #[serde(default)]
+    pub response: CooldownResponse,
+    /// Negative feedback count when a dismissal cooldown started
+    #[serde(default)]
+    pub dismissals: u32,
+    /// Agent whose suggestion or feedback started the cooldown
+    #[serde(default)]
+    pub agent_id: Option<String>,
+}
+
+impl CooldownEntry {
+    /// Seconds left at `now`; zero once expired.
+    #[must_use]
+    pub fn remaining_seconds(&self, now: DateTime<Utc>) -> u64 {
+        cooldown_remaining_seconds(self, now)
+    }
+
+    /// Seconds left at `now` as seen by `agent_id`: the full cooldown for
+    /// the agent that started it, at most `cross_agent_secs` from its start
+    /// for everyone else.
+    #[must_use]
+    pub fn remaining_seconds_for(
+        &self,
+        agent_id: Option<&str>,
+        cross_agent_secs: u64,
+        now: DateTime<Utc>,
+    ) -> u64 {
+        let remaining = cooldown_remaining_seconds(self, now);
+        if self.agent_id.as_deref() == agent_id {
+            return remaining;
+        }
+        let elapsed = now
+            .signed_duration_since(self.suggested_at)
+            .num_seconds()
+            .max(0) as u64;
+        remaining.min(cross_agent_secs.saturating_sub(elapsed))
+    }
+
+    #[must_use]
+    pub fn expires_at(&self) -> DateTime<Utc> {
+        let secs = i64::try_from(self.cooldown_seconds).unwrap_or(i64::MAX);
+        self.suggested_at
+            .checked_add_signed(chrono::Duration::seconds(secs))
+            .unwrap_or(DateTime::<Utc>::MAX_UTC)
+    }
+
+    fn key(&self) -> CooldownKey {
+        (
+            self.fingerprint,
+            self.skill_id.clone(),
+            self.agent_id.clone(),
+        )
+    }
+
+    fn to_record(&self) -> SuggestionCooldownRecord {
+        SuggestionCooldownRecord {
+            skill_id: self.skill_id.clone(),
+            context_fingerprint: format!("{:016x}", self.fingerprint),
+            agent_id: self.agent_id.clone(),
+            response: self.response.as_str().to_string(),
+            dismissals: self.dismissals,
+            started_at: self.suggested_at.to_rfc3339(),
+            expires_at: self.expires_at().to_rfc3339(),
+        }
+    }
+
+    fn from_record(record: SuggestionCooldownRecord) -> Result<Self> {
+        let parse = |raw: &str| {
+            DateTime::parse_from_rfc3339(raw)
+                .map(|ts| ts.with_timezone(&Utc))
+                .map_err(|err| MsError::Config(format!("decode cooldown time: {err}")))
+        };
+        let suggested_at = parse(&record.started_at)?;
+        let expires_at = parse(&record.expires_at)?;
+        Ok(Self {
+            fingerprint: u64::from_str_radix(&record.context_fingerprint, 16)
+                .unwrap_or(ANY_CONTEXT),
+            skill_id: record.skill_id,
+            suggested_at,
+            cooldown_seconds: expires_at
+                .signed_duration_since(suggested_at)
+                .num_seconds()
+                .max(0) as u64,
+            response: CooldownResponse::from_key(&record.response),
+            dismissals: record.dismissals,
+            agent_id: record.agent_id,
+        })
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,17 +180,27 @@ pub struct CooldownStats {
     pub expired_pending_cleanup: usize,
 }
 
+/// Key of one cooldown: fingerprint, skill id and agent.
+type CooldownKey = (u64, String, Option<String>);
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct SuggestionCooldownCache {
     entries: Vec<CooldownEntry>,
+    /// Keys of entries recorded since loading
+    #[serde(skip)]
+    touched: HashSet<CooldownKey>,
+    /// Agent this cache records for and checks cooldowns as
+    #[serde(skip)]
+    agent_id: Option<String>,
+    /// How long other agents' cooldowns apply to this agent
+    #[serde(skip)]
+    cross_agent_secs: u64,
 }
 
 impl SuggestionCooldownCache {
     #[must_use]
-    pub const fn new() -> Self {
-        Self {
-            entries: Vec::new(),
-        }
+    pub fn new() -> Self {
+        Self::default()
     }
 
     pub fn load(path: &Path) -> Result<Self> {
@@ -60,6 +211,60 @@ impl SuggestionCooldownCache {
         cooldown_storage::save_cache(path, self)
     }
 
+    /// View the cooldowns as `agent_id`, which new entries are attributed to.
+    /// Other agents' cooldowns apply for at most `cross_agent_secs`.
+    #[must_use]
+    pub fn for_agent(mut self, agent_id: Option<String>, cross_agent_secs: u64) -> Self {
+        self.agent_id = agent_id;
+        self.cross_agent_secs = cross_agent_secs;
+        self
+    }
+
+    /// Load the active cooldowns from the database (expired rows are dropped
+    /// on read). Active entries of a legacy JSON cache at `legacy_path` are
+    /// carried over and written by the next [`Self::save_to_db`].
+    pub fn load_from_db(db: &Database, legacy_path: Option<&Path>) -> Result<Self> {
+        let records = db.list_suggestion_cooldowns(&Utc::now().to_rfc3339())?;
+        let mut cache = Self::new();
+        for record in records {
+            cache.entries.push(CooldownEntry::from_record(record)?);
+        }
+        if let Some(path) = legacy_path.filter(|p| p.exists()) {
+            let now = Utc::now();
+            for entry in Self::load(path)?.entries {
+                if entry.remaining_seconds(now) > 0 && cache.find(&entry.key()).is_none() {
+                    cache.touched.insert(entry.key());
+                    cache.entries.push(entry);
+                }
+            }
+        }
+        Ok(cache)
+    }
+
+    /// Write entries recorded since loading. Rows other processes wrote in
+    /// the meantime are left alone unless this cache changed the same key.
+    pub fn save_to_db(&self, db: &Database) -> Result<()> {
+        for entry in &self.entries {
+            if self.touched.contains(&entry.key()) {
+                db.upsert_suggestion_cooldown(&entry.to_record())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Active cooldowns, soonest expiry first.
+    #[must_use]
+    pub fn active(&self) -> Vec<&CooldownEntry> {
+        let now = Utc::now();
+        let mut active: Vec<_> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.remaining_seconds(now) > 0)
+            .collect();
+        active.sort_by_key(|entry| entry.expires_at());
+        active
+    }
+
     #[must_use]
     pub fn stats(&self) -> CooldownStats {
         let now = Utc::now();
@@ -79,18 +284,28 @@ impl SuggestionCooldownCache {
         }
     }
 
+    /// Cooldown of `skill_id` in this context, for this cache's agent.
+    /// Entries recorded for [`ANY_CONTEXT`] apply everywhere; the longest
+    /// active one wins.
     #[must_use]
     pub fn status(&self, fingerprint: u64, skill_id: &str) -> CooldownStatus {
         let now = Utc::now();
-        let Some(entry) = self
-            .entries
-            .iter()
-            .find(|entry| entry.fingerprint == fingerprint && entry.skill_id == skill_id)
-        else {
-            return CooldownStatus::NotFound;
-        };
-        let remaining = cooldown_remaining_seconds(entry, now);
-        if remaining == 0 {
+        let mut found = false;
+        let mut remaining = 0;
+        for entry in self.entries.iter().filter(|entry| {
+            entry.skill_id == skill_id
+                && (entry.fingerprint == fingerprint || entry.fingerprint == ANY_CONTEXT)
+        }) {
+            found = true;
+            remaining = remaining.max(entry.remaining_seconds_for(
+                self.agent_id.as_deref(),
+                self.cross_agent_secs,
+                now,
+            ));
+        }
+        if !found {
+            CooldownStatus::NotFound
+        } else if remaining == 0 {
             CooldownStatus::Expired
         } else {
             CooldownStatus::Active {
@@ -99,23 +314,75 @@ impl SuggestionCooldownCache {
         }
     }
 
+    /// Start a `shown` cooldown. A longer active cooldown (such as a
+    /// dismissal) for the same key is kept.
     pub fn record(&mut self, fingerprint: u64, skill_id: String, cooldown_seconds: u64) {
+        self.upsert(
+            fingerprint,
+            skill_id,
+            cooldown_seconds,
+            CooldownResponse::Shown,
+            0,
+        );
+    }
+
+    /// Start an `accepted` cooldown after positive feedback.
+    pub fn record_acceptance(&mut self, fingerprint: u64, skill_id: String, cooldown_seconds: u64) {
+        self.upsert(
+            fingerprint,
+            skill_id,
+            cooldown_seconds,
+            CooldownResponse::Accepted,
+            0,
+        );
+    }
+
+    /// Start a dismissal cooldown after the `dismissals`-th negative feedback.
+    pub fn record_dismissal(
+        &mut self,
+        fingerprint: u64,
+        skill_id: String,
+        dismissals: u32,
+        cooldown_seconds: u64,
+    ) {
+        self.upsert(
+            fingerprint,
+            skill_id,
+            cooldown_seconds,
+            CooldownResponse::Dismissed,
+            dismissals,
+        );
+    }
+
+    fn find(&self, key: &CooldownKey) -> Option<usize> {
+        self.entries.iter().position(|entry| entry.key() == *key)
+    }
+
+    fn upsert(
+        &mut self,
+        fingerprint: u64,
+        skill_id: String,
+        cooldown_seconds: u64,
+        response: CooldownResponse,
+        dismissals: u32,
+    ) {
         let now = Utc::now();
-        if let Some(entry) = self
-            .entries
-            .iter_mut()
-            .find(|entry| entry.fingerprint == fingerprint && entry.skill_id == skill_id)
-        {
-            entry.suggested_at = now;
-            entry.cooldown_seconds = cooldown_seconds;
-            return;
-        }
-        self.entries.push(CooldownEntry {
+        let entry = CooldownEntry {
             fingerprint,
             skill_id,
             suggested_at: now,
             cooldown_seconds,
-        });
+            response,
+            dismissals,
+            agent_id: self.agent_id.clone(),
+        };
+        let key = entry.key();
+        match self.find(&key) {
+            Some(idx) if self.entries[idx].remaining_seconds(now) > cooldown_seconds => return,
+            Some(idx) => self.entries[idx] = entry,
+            None => self.entries.push(entry),
+        }
+        self.touched.insert(key);
     }
 
     pub fn purge_expired(&mut self) -> usize {
@@ -127,6 +394,76 @@ impl SuggestionCooldownCache {
     }
 }
 
+/// Start a dismissal cooldown for `skill_id` after `agent_id`'s negative
+/// feedback.
+///
+/// The cooldown applies to the context the skill was last suggested in (or
+/// every context if it never was) and lasts
+/// [`SuggestionsConfig::dismissal_cooldown_secs`] for the skill's total
+/// number of negative feedback records.
+pub fn start_dismissal(
+    db: &Database,
+    config: &SuggestionsConfig,
+    skill_id: &str,
+    agent_id: Option<&str>,
+) -> Result<CooldownEntry> {
+    let dismissals = u32::try_from(db.count_skill_feedback(skill_id, "negative")?)
+        .unwrap_or(u32::MAX)
+        .max(1);
+    start_feedback_cooldown(db, config, skill_id, agent_id, |cache, fingerprint| {
+        cache.record_dismissal(
+            fingerprint,
+            skill_id.to_string(),
+            dismissals,
+            config.dismissal_cooldown_secs(dismissals),
+        );
+    })
+}
+
+/// Start an `accepted` cooldown for `skill_id` after `agent_id`'s positive
+/// feedback, lasting [`SuggestionsConfig::cooldown_secs`] in the context the
+/// skill was last suggested in.
+pub fn start_acceptance(
+    db: &Database,
+    config: &SuggestionsConfig,
+    skill_id: &str,
+    agent_id: Option<&str>,
+) -> Result<CooldownEntry> {
+    start_feedback_cooldown(db, config, skill_id, agent_id, |cache, fingerprint| {
+        cache.record_acceptance(fingerprint, skill_id.to_string(), config.cooldown_secs);
+    })
+}
+
+fn start_feedback_cooldown(
+    db: &Database,
+    config: &SuggestionsConfig,
+    skill_id: &str,
+    agent_id: Option<&str>,
+    record: impl FnOnce(&mut SuggestionCooldownCache, u64),
+) -> Result<CooldownEntry> {
+    let fingerprint = db
+        .list_suggestion_records(skill_id, None)?
+        .first()
+        .and_then(|record| record.context_fingerprint_hash)
+        .unwrap_or(ANY_CONTEXT);
+
+    let mut cache = SuggestionCooldownCache::load_from_db(db, None)?.for_agent(
+        agent_id.map(str::to_string),
+        config.cross_agent_cooldown_secs,
+    );
+    record(&mut cache, fingerprint);
+    cache.save_to_db(db)?;
+    let key = (
+        fingerprint,
+        skill_id.to_string(),
+        agent_id.map(str::to_string),
+    );
+    let idx = cache
+        .find(&key)
+        .ok_or_else(|| MsError::NotFound(format!("cooldown for {skill_id}")))?;
+    Ok(cache.entries.swap_remove(idx))
+}
+
 fn cooldown_remaining_seconds(entry: &CooldownEntry, now: DateTime<Utc>) -> u64 {
     let elapsed = now
         .signed_duration_since(entry.suggested_at)
@@ -143,3 +480,107 @@ fn cooldown_remaining_seconds(entry: &CooldownEntry, now: DateTime<Utc>) -> u64
         entry.cooldown_seconds - elapsed_u64
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open_db() -> (tempfile::TempDir, Database) {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(dir.path().join("ms.db")).unwrap();
+        (dir, db)
+    }
+
+    #[test]
+    fn cooldowns_are_shared_through_the_database() {
+        let (_dir, db) = open_db();
+        let mut cache = SuggestionCooldownCache::load_from_db(&db, None).unwrap();
+        cache.record(7, "rust-errors".to_string(), 300);
+        cache.save_to_db(&db).unwrap();
+
+        // A second process sees the cooldown in that context only
+        let other = SuggestionCooldownCache::load_from_db(&db, None).unwrap();
+        assert!(matches!(
+            other.status(7, "rust-errors"),
+            CooldownStatus::Active { .. }
+        ));
+        assert_eq!(other.status(8, "rust-errors"), CooldownStatus::NotFound);
+        assert_eq!(other.active().len(), 1);
+    }
+
+    #[test]
+    fn other_agents_honour_cooldowns_for_the_cross_agent_window() {
+        let (_dir, db) = open_db();
+        let claude = || Some("claude-code".to_string());
+        let cursor = || Some("cursor".to_string());
+
+        let mut cache = SuggestionCooldownCache::load_from_db(&db, None)
+            .unwrap()
+            .for_agent(claude(), 120);
+        cache.record(7, "rust-errors".to_string(), 300);
+        cache.save_to_db(&db).unwrap();
+
+        let remaining = |agent: Option<String>, cross_agent_secs: u64| {
+            match SuggestionCooldownCache::load_from_db(&db, None)
+                .unwrap()
+                .for_agent(agent, cross_agent_secs)
+                .status(7, "rust-errors")
+            {
+                CooldownStatus::Active { remaining_seconds } => remaining_seconds,
+                _ => 0,
+            }
+        };
+        // The agent that was shown the skill waits out the full cooldown;
+        // another agent on the same fingerprint only the cross-agent window
+        assert!(remaining(claude(), 120) > 120);
+        let other = remaining(cursor(), 120);
+        assert!(other > 0 && other <= 120);
+        assert_eq!(remaining(cursor(), 0), 0);
+
+        // A dismissal by the other agent is its own entry, and cools the
+        // skill down for the first agent too
+        let config = SuggestionsConfig {
+            cross_agent_cooldown_secs: 900,
+            ..SuggestionsConfig::default()
+        };
+        db.record_skill_feedback("rust-errors", "negative", Some(-1), None)
+            .unwrap();
+        let dismissal = start_dismissal(&db, &config, "rust-errors", Some("cursor")).unwrap();
+        assert_eq!(dismissal.agent_id, cursor());
+        let active = SuggestionCooldownCache::load_from_db(&db, None).unwrap();
+        assert_eq!(active.active().len(), 2);
+        assert!(remaining(cursor(), 900) > 900);
+        let first = remaining(claude(), 900);
+        assert!(first > 300 && first <= 900);
+    }
+
+    #[test]
+    fn repeated_dismissals_lengthen_the_cooldown() {
+        let (_dir, db) = open_db();
+        let config = SuggestionsConfig::default();
+
+        db.record_skill_feedback("noisy", "negative", Some(-1), None)
+            .unwrap();
+        let first = start_dismissal(&db, &config, "noisy", None).unwrap();
+        assert_eq!(first.response, CooldownResponse::Dismissed);
+        assert_eq!(first.fingerprint, ANY_CONTEXT);
+        assert_eq!(first.cooldown_seconds, config.dismiss_cooldown_secs);
+
+        db.record_skill_feedback("noisy", "negative", Some(-1), None)
+            .unwrap();
+        let second = start_dismissal(&db, &config, "noisy", None).unwrap();
+        assert_eq!(second.dismissals, 2);
+        assert!(second.cooldown_seconds > first.cooldown_seconds);
+
+        // Dismissals without a recorded context apply everywhere, and a later
+        // short `shown` cooldown does not cut them short
+        let mut cache = SuggestionCooldownCache::load_from_db(&db, None).unwrap();
+        cache.record(ANY_CONTEXT, "noisy".to_string(), 300);
+        match cache.status(1234, "noisy") {
+            CooldownStatus::Active { remaining_seconds } => {
+                assert!(remaining_seconds > 300);
+            }
+            other => panic!("expected active cooldown, got {other:?}"),
+        }
+    }
+}
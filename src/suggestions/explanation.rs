@@ -0,0 +1,395 @@
+//! Persisted suggestion explanations for `ms why-suggested`.
+//!
+//! Each suggestion shown by `ms suggest` stores a compact, size-capped snapshot
+//! of why it ranked where it did: signal scores, matched triggers, the bandit
+//! arm's statistics, a context summary, and the preferences and cooldowns in
+//! effect. Later lookups render the snapshot verbatim and compare it with the
+//! current state to report what has changed since.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{MsError, Result};
+use crate::storage::sqlite::{SkillFeedbackRecord, SkillRecord};
+
+/// Default cap on a serialized explanation, in bytes.
+pub const DEFAULT_MAX_EXPLANATION_BYTES: usize = 4096;
+
+/// Recent files kept in the context summary before size capping.
+const MAX_RECENT_FILES: usize = 5;
+
+/// Why a skill was suggested, captured at suggestion time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SuggestionExplanation {
+    /// Final score after boosts.
+    pub score: f32,
+    pub signals: SignalScores,
+    pub bandit: BanditArmSnapshot,
+    /// Human-readable reasons the skill matched (context, favorite, domain, ...).
+    pub matched_triggers: Vec<String>,
+    pub context: ContextSummary,
+    pub preferences: PreferenceSnapshot,
+    /// Skill content hash and modification time when suggested.
+    pub skill_content_hash: String,
+    pub skill_modified_at: String,
+    /// Shown in the discovery section rather than the main list.
+    pub discovery: bool,
+    /// Whether the `--task` description or the project context drove the
+    /// suggestion.
+    #[serde(default)]
+    pub origin: SuggestionOrigin,
+    /// The `--task` description, if one was given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task: Option<String>,
+    /// Parts of the snapshot were dropped to fit the size cap.
+    #[serde(default)]
+    pub truncated: bool,
+}
+
+/// Component scores behind the final suggestion score.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SignalScores {
+    pub contextual: f32,
+    pub thompson: f32,
+    pub exploration_bonus: f32,
+    pub personal_boost: f32,
+    /// Hybrid search relevance to the detected stack.
+    #[serde(default)]
+    pub search_relevance: f32,
+    /// Hybrid search relevance to the `--task` description.
+    #[serde(default)]
+    pub task_relevance: f32,
+}
+
+/// What a suggestion mainly came from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SuggestionOrigin {
+    /// The detected project context (the default without `--task`).
+    #[default]
+    Context,
+    /// The `--task` description.
+    Task,
+}
+
+impl SuggestionOrigin {
+    /// Origin of a suggestion whose score got `task` from task relevance and
+    /// `context` from the project-context signals.
+    #[must_use]
+    pub const fn from_contributions(task: f32, context: f32) -> Self {
+        if task > context {
+            Self::Task
+        } else {
+            Self::Context
+        }
+    }
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Context => "context",
+            Self::Task => "task",
+        }
+    }
+}
+
+/// The skill's bandit arm at suggestion time.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BanditArmSnapshot {
+    /// Thompson-sampled success probability for this arm.
+    pub sampled_probability: f32,
+    pub pull_count: u64,
+    pub avg_reward: f64,
+}
+
+/// Summary of the working context the suggestion was made in.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ContextSummary {
+    /// Context fingerprint as hex.
+    pub fingerprint: String,
+    pub cwd: Option<String>,
+    pub git_branch: Option<String>,
+    pub recent_files: Vec<String>,
+}
+
+impl ContextSummary {
+    #[must_use]
+    pub fn new(
+        fingerprint: u64,
+        cwd: Option<String>,
+        git_branch: Option<String>,
+        recent_files: impl IntoIterator<Item = String>,
+    ) -> Self {
+        Self {
+            fingerprint: format!("{fingerprint:016x}"),
+            cwd,
+            git_branch,
+            recent_files: recent_files.into_iter().take(MAX_RECENT_FILES).collect(),
+        }
+    }
+}
+
+/// Preferences and cooldowns in effect when the suggestion was made.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PreferenceSnapshot {
+    /// The skill was favorited (pinned) and received the favorites boost.
+    pub favorite: bool,
+    /// Cooldowns were bypassed with `--ignore-cooldowns`.
+    pub cooldowns_ignored: bool,
+    /// The cooldown started by this suggestion, if any.
+    pub cooldown_until: Option<DateTime<Utc>>,
+    /// `--domain` filter the suggestion passed, if any.
+    pub domain_filter: Option<String>,
+}
+
+impl SuggestionExplanation {
+    /// Serialize to JSON, dropping detail until it fits in `max_bytes`.
+    pub fn encode(&self, max_bytes: usize) -> Result<String> {
+        let mut explanation = self.clone();
+        let mut json = explanation.to_json()?;
+        while json.len() > max_bytes && explanation.shrink() {
+            explanation.truncated = true;
+            json = explanation.to_json()?;
+        }
+        Ok(json)
+    }
+
+    /// Decode a stored explanation.
+    pub fn decode(raw: &str) -> Result<Self> {
+        serde_json::from_str(raw)
+            .map_err(|err| MsError::Config(format!("decode suggestion explanation: {err}")))
+    }
+
+    fn to_json(&self) -> Result<String> {
+        serde_json::to_string(self)
+            .map_err(|err| MsError::Config(format!("encode suggestion explanation: {err}")))
+    }
+
+    /// Drop one piece of low-value detail; false once nothing is left to drop.
+    fn shrink(&mut self) -> bool {
+        if self.context.recent_files.pop().is_some() {
+            return true;
+        }
+        if self.matched_triggers.pop().is_some() {
+            return true;
+        }
+        if self.task.take().is_some() {
+            return true;
+        }
+        if self.context.cwd.take().is_some() {
+            return true;
+        }
+        self.context.git_branch.take().is_some()
+    }
+}
+
+/// What changed between a stored suggestion and now.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ChangesSince {
+    /// The skill's content changed since it was suggested.
+    pub skill_edited: bool,
+    /// The skill no longer exists.
+    pub skill_removed: bool,
+    /// Whether the skill was a favorite then vs now, when that differs.
+    pub favorite_changed: Option<FavoriteChange>,
+    /// The cooldown started by the suggestion has since lapsed.
+    pub cooldown_expired: bool,
+    /// Feedback recorded for the skill after the suggestion.
+    pub feedback_since: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FavoriteChange {
+    Added,
+    Removed,
+}
+
+impl ChangesSince {
+    /// Compare a stored explanation with the skill's current state.
+    #[must_use]
+    pub fn compute(
+        explanation: &SuggestionExplanation,
+        shown_at: DateTime<Utc>,
+        current: Option<&SkillRecord>,
+        favorite_now: bool,
+        feedback: &[SkillFeedbackRecord],
+        now: DateTime<Utc>,
+    ) -> Self {
+        let favorite_changed = match (explanation.preferences.favorite, favorite_now) {
+            (true, false) => Some(FavoriteChange::Removed),
+            (false, true) => Some(FavoriteChange::Added),
+            _ => None,
+        };
+        let feedback_since = feedback
+            .iter()
+            .filter(|record| {
+                DateTime::parse_from_rfc3339(&record.created_at)
+                    .is_ok_and(|created| created.with_timezone(&Utc) > shown_at)
+            })
+            .map(|record| format!("{} ({})", record.feedback_type, record.created_at))
+            .collect();
+        Self {
+            skill_edited: current
+                .is_some_and(|skill| skill.content_hash != explanation.skill_content_hash),
+            skill_removed: current.is_none(),
+            favorite_changed,
+            cooldown_expired: explanation
+                .preferences
+                .cooldown_until
+                .is_some_and(|until| until <= now),
+            feedback_since,
+        }
+    }
+
+    /// One line per change, for human output.
+    #[must_use]
+    pub fn describe(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        if self.skill_removed {
+            lines.push("skill has since been removed".to_string());
+        } else if self.skill_edited {
+            lines.push("skill has been edited since".to_string());
+        }
+        match self.favorite_changed {
+            Some(FavoriteChange::Removed) => {
+                lines.push("no longer a favorite (the pin boost no longer applies)".to_string());
+            }
+            Some(FavoriteChange::Added) => lines.push("favorited since".to_string()),
+            None => {}
+        }
+        if self.cooldown_expired {
+            lines.push("cooldown has expired; it may be suggested again".to_string());
+        }
+        for feedback in &self.feedback_since {
+            lines.push(format!("feedback since: {feedback}"));
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn explanation(favorite: bool) -> SuggestionExplanation {
+        SuggestionExplanation {
+            score: 0.9,
+            signals: SignalScores {
+                contextual: 0.6,
+                thompson: 0.5,
+                exploration_bonus: 0.1,
+                personal_boost: if favorite { 0.25 } else { 0.0 },
+                search_relevance: 0.0,
+                task_relevance: 0.0,
+            },
+            bandit: BanditArmSnapshot {
+                sampled_probability: 0.5,
+                pull_count: 3,
+                avg_reward: 0.4,
+            },
+            matched_triggers: vec!["context match 60%".to_string()],
+            context: ContextSummary::new(
+                0xabc,
+                Some("/repo".to_string()),
+                Some("main".to_string()),
+                (0..10).map(|i| format!("src/file_{i}.rs")),
+            ),
+            preferences: PreferenceSnapshot {
+                favorite,
+                ..PreferenceSnapshot::default()
+            },
+            skill_content_hash: "hash-1".to_string(),
+            skill_modified_at: "2026-01-01T00:00:00Z".to_string(),
+            discovery: false,
+            origin: SuggestionOrigin::Context,
+            task: None,
+            truncated: false,
+        }
+    }
+
+    #[test]
+    fn context_summary_keeps_few_files() {
+        let summary = explanation(false).context;
+        assert_eq!(summary.fingerprint, "0000000000000abc");
+        assert_eq!(summary.recent_files.len(), MAX_RECENT_FILES);
+    }
+
+    #[test]
+    fn encode_round_trips_and_respects_cap() {
+        let original = explanation(true);
+        let full = original.encode(DEFAULT_MAX_EXPLANATION_BYTES).unwrap();
+        assert_eq!(SuggestionExplanation::decode(&full).unwrap(), original);
+
+        let capped = original.encode(full.len() - 20).unwrap();
+        assert!(capped.len() <= full.len() - 20);
+        let decoded = SuggestionExplanation::decode(&capped).unwrap();
+        assert!(decoded.truncated);
+        assert!(decoded.preferences.favorite);
+        assert!((decoded.score - original.score).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn origin_follows_the_larger_contribution_and_decodes_by_default() {
+        assert_eq!(
+            SuggestionOrigin::from_contributions(0.3, 0.1),
+            SuggestionOrigin::Task
+        );
+        assert_eq!(
+            SuggestionOrigin::from_contributions(0.1, 0.3),
+            SuggestionOrigin::Context
+        );
+        assert_eq!(
+            SuggestionOrigin::from_contributions(0.0, 0.0),
+            SuggestionOrigin::Context
+        );
+
+        // Explanations stored before `--task` existed still decode
+        let mut legacy = serde_json::to_value(explanation(false)).unwrap();
+        legacy.as_object_mut().unwrap().remove("origin");
+        legacy["signals"]
+            .as_object_mut()
+            .unwrap()
+            .remove("task_relevance");
+        let decoded = SuggestionExplanation::decode(&legacy.to_string()).unwrap();
+        assert_eq!(decoded.origin, SuggestionOrigin::Context);
+        assert!(decoded.task.is_none());
+    }
+
+    #[test]
+    fn changes_since_reports_edits_and_feedback() {
+        let shown_at = Utc::now() - chrono::Duration::hours(2);
+        let mut stored = explanation(false);
+        stored.preferences.cooldown_until = Some(shown_at + chrono::Duration::minutes(5));
+        let feedback = vec![
+            SkillFeedbackRecord {
+                id: "f1".to_string(),
+                skill_id: "s".to_string(),
+                feedback_type: "negative".to_string(),
+                rating: None,
+                comment: None,
+                created_at: Utc::now().to_rfc3339(),
+            },
+            SkillFeedbackRecord {
+                id: "f0".to_string(),
+                skill_id: "s".to_string(),
+                feedback_type: "positive".to_string(),
+                rating: None,
+                comment: None,
+                created_at: (shown_at - chrono::Duration::days(1)).to_rfc3339(),
+            },
+        ];
+
+        let changes = ChangesSince::compute(&stored, shown_at, None, false, &feedback, Utc::now());
+        assert!(changes.skill_removed);
+        assert!(changes.cooldown_expired);
+        assert_eq!(changes.feedback_since.len(), 1);
+        assert!(changes.feedback_since[0].starts_with("negative"));
+        assert!(
+            changes
+                .describe()
+                .iter()
+                .any(|line| line.contains("removed"))
+        );
+    }
+}
@@ -3,10 +3,17 @@
 pub mod bandit;
 pub mod cooldown;
 pub mod cooldown_storage;
+pub mod explanation;
+pub mod score;
+pub mod session_outcome;
 pub mod tracking;
 
 pub use bandit::{BanditConfig, SignalBandit};
-pub use cooldown::{CooldownStats, CooldownStatus, SuggestionCooldownCache, SuggestionResponse};
+pub use cooldown::{
+    ANY_CONTEXT, CooldownEntry, CooldownResponse, CooldownStats, CooldownStatus,
+    SuggestionCooldownCache, SuggestionResponse,
+};
+pub use score::{ScoreBreakdown, ScoreSignal, ScoreSource};
 pub use tracking::{
     FeedbackCollector, InteractionType, SessionEvent, SessionStats, SessionTracker,
     SkillInteraction, SkillSession, SuggestionOutcome, SuggestionRecord, SuggestionTracker,
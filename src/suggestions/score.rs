@@ -0,0 +1,147 @@
+//! Per-signal score breakdowns for `ms suggest --explain`.
+//!
+//! A suggestion's score is built up signal by signal: the contextual
+//! bandit's prediction, its Thompson-sampled prior and exploration bonus,
+//! search relevance to the detected stack and to a `--task` description,
+//! favorites, personal history and applicability windows. [`ScoreBreakdown`] records each step with the
+//! amount it actually moved the score, so the contributions always add up to
+//! the reported score, even where the score was clamped to 0-1.
+
+use serde::{Deserialize, Serialize};
+
+/// Where a score contribution came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScoreSource {
+    /// Contextual bandit prediction from the detected project types, files
+    /// and tools.
+    ProjectMatch,
+    /// Thompson sample of the skill's feedback history.
+    BanditPrior,
+    /// Bonus for skills with few uses.
+    Exploration,
+    /// BM25 match of the skill against the context query.
+    Bm25,
+    /// Embedding similarity to the context query.
+    Embedding,
+    /// Hybrid search relevance to the `--task` description.
+    Task,
+    /// Favorited skill.
+    Favorite,
+    /// Frequency and recency of past use (`--personal`).
+    History,
+    /// Applicability window.
+    Window,
+}
+
+impl ScoreSource {
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::ProjectMatch => "Project-type match",
+            Self::BanditPrior => "Bandit prior",
+            Self::Exploration => "Exploration bonus",
+            Self::Bm25 => "BM25 match",
+            Self::Embedding => "Embedding similarity",
+            Self::Task => "Task relevance",
+            Self::Favorite => "Favorite",
+            Self::History => "Recency/usage boost",
+            Self::Window => "Applicability window",
+        }
+    }
+}
+
+/// One signal's part of a score.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoreSignal {
+    pub source: ScoreSource,
+    /// Raw signal value, before weighting.
+    pub value: f32,
+    pub weight: f32,
+    /// How much the signal moved the score. Equal to `value * weight` unless
+    /// the score was clamped.
+    pub contribution: f32,
+    /// What the signal matched, e.g. the query terms or project types.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// The signals behind a score, in the order they were applied.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScoreBreakdown {
+    pub signals: Vec<ScoreSignal>,
+}
+
+impl ScoreBreakdown {
+    /// The score: the sum of all contributions.
+    #[must_use]
+    pub fn total(&self) -> f32 {
+        self.signals.iter().map(|signal| signal.contribution).sum()
+    }
+
+    /// Add `value * weight` to the score, keeping it within 0-1.
+    pub fn add(&mut self, source: ScoreSource, value: f32, weight: f32, detail: Option<String>) {
+        let before = self.total();
+        let after = (before + value * weight).clamp(0.0, 1.0);
+        self.signals.push(ScoreSignal {
+            source,
+            value,
+            weight,
+            contribution: after - before,
+            detail,
+        });
+    }
+
+    /// Multiply the score by `factor`, recorded as the (negative) difference.
+    pub fn scale(&mut self, source: ScoreSource, factor: f32, detail: Option<String>) {
+        let before = self.total();
+        self.signals.push(ScoreSignal {
+            source,
+            value: factor,
+            weight: 1.0,
+            contribution: (before * factor).clamp(0.0, 1.0) - before,
+            detail,
+        });
+    }
+
+    /// Raw value of the first signal from `source`, or 0.
+    #[must_use]
+    pub fn value(&self, source: ScoreSource) -> f32 {
+        self.signals
+            .iter()
+            .find(|signal| signal.source == source)
+            .map_or(0.0, |signal| signal.value)
+    }
+
+    /// Summed contributions of the signals from `sources`.
+    #[must_use]
+    pub fn contribution(&self, sources: &[ScoreSource]) -> f32 {
+        self.signals
+            .iter()
+            .filter(|signal| sources.contains(&signal.source))
+            .map(|signal| signal.contribution)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contributions_sum_to_the_clamped_score() {
+        let mut breakdown = ScoreBreakdown::default();
+        breakdown.add(ScoreSource::ProjectMatch, 0.9, 0.7, None);
+        breakdown.add(ScoreSource::BanditPrior, 0.6, 0.3, None);
+        breakdown.add(ScoreSource::Favorite, 1.0, 0.25, None);
+        assert!((breakdown.total() - 1.0).abs() < 1e-6);
+        // Clamped: the favorite only added what was left below 1.0
+        let favorite = &breakdown.signals[2];
+        assert!((favorite.contribution - (1.0 - 0.63 - 0.18)).abs() < 1e-6);
+
+        breakdown.scale(ScoreSource::Window, 0.1, Some("expired".to_string()));
+        assert!((breakdown.total() - 0.1).abs() < 1e-6);
+        assert_eq!(breakdown.value(ScoreSource::Window), 0.1);
+        assert_eq!(breakdown.value(ScoreSource::Bm25), 0.0);
+    }
+}
@@ -0,0 +1,422 @@
+//! Implicit skill feedback inferred from how an agent session ended.
+//!
+//! Explicit ratings are rare, so `ms feedback mine` scores the skills loaded
+//! in a session from what the transcript already shows: whether the last
+//! validation phase ended with passing tests, whether the session reached
+//! wrap-up (commit, push, summary), and how many errors tool results
+//! reported after the skill was loaded.
+
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use regex::Regex;
+use serde::Serialize;
+
+use crate::cass::mining::{SegmentedSession, SessionPhase};
+use crate::cass::{Session, SessionMessage, ToolCall};
+use crate::suggestions::bandit::{SkillFeedback, compute_reward};
+
+/// Reward taken off for each error seen after the skill was loaded.
+pub const ERROR_PENALTY: f32 = 0.05;
+
+/// Most reward that errors can take off.
+pub const MAX_ERROR_PENALTY: f32 = 0.3;
+
+/// `N failed` / `N errors` with a non-zero count, as printed by cargo,
+/// pytest, jest and most other runners.
+static FAILED_COUNT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b[1-9]\d* (failed|failing|failures?|errors?)\b").expect("valid regex")
+});
+
+static PASSED_COUNT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b[1-9]\d* (passed|passing)\b").expect("valid regex"));
+
+/// What the session says about a skill's usefulness.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Success,
+    Failure,
+    /// No tests ran and the session never wrapped up.
+    Inconclusive,
+}
+
+impl Verdict {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::Failure => "failure",
+            Self::Inconclusive => "inconclusive",
+        }
+    }
+}
+
+/// Signals read from a session for one loaded skill.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OutcomeSignals {
+    /// Index of the message that loaded the skill, 0 when the transcript
+    /// does not show the load.
+    pub loaded_at: usize,
+    /// Result of the last test run in the final validation phase after the
+    /// load, `None` when no test run was seen.
+    pub tests_passed: Option<bool>,
+    pub ended_in_wrap_up: bool,
+    pub errors_after_load: usize,
+}
+
+impl OutcomeSignals {
+    /// Failing tests outweigh everything else; passing tests or a wrap-up
+    /// count as success.
+    #[must_use]
+    pub const fn verdict(&self) -> Verdict {
+        match self.tests_passed {
+            Some(false) => Verdict::Failure,
+            Some(true) => Verdict::Success,
+            None if self.ended_in_wrap_up => Verdict::Success,
+            None => Verdict::Inconclusive,
+        }
+    }
+
+    /// Bandit feedback for the verdict. `used_for` is how long the skill was
+    /// loaded; unknown durations earn no speed bonus.
+    #[must_use]
+    pub fn feedback(&self, used_for: Option<Duration>) -> SkillFeedback {
+        let duration = used_for.unwrap_or(Duration::from_secs(600));
+        match self.verdict() {
+            Verdict::Success => SkillFeedback::TaskCompleted {
+                success: true,
+                duration,
+            },
+            Verdict::Failure => SkillFeedback::TaskCompleted {
+                success: false,
+                duration,
+            },
+            Verdict::Inconclusive => SkillFeedback::LoadedOnly,
+        }
+    }
+
+    /// Reward for [`Self::feedback`], less [`ERROR_PENALTY`] per error seen
+    /// after the load.
+    #[must_use]
+    pub fn reward(&self, used_for: Option<Duration>) -> f32 {
+        let penalty = (self.errors_after_load as f32 * ERROR_PENALTY).min(MAX_ERROR_PENALTY);
+        (compute_reward(&self.feedback(used_for)) - penalty).max(0.0)
+    }
+}
+
+/// Read the outcome signals for a skill loaded at message `loaded_at`.
+#[must_use]
+pub fn infer_outcome(
+    session: &Session,
+    segmented: &SegmentedSession,
+    loaded_at: usize,
+) -> OutcomeSignals {
+    let messages = &session.messages;
+    let tests_passed = segmented
+        .segments
+        .iter()
+        .rev()
+        .find(|segment| segment.phase == SessionPhase::Validation && segment.end_idx > loaded_at)
+        .and_then(|segment| {
+            let start = segment.start_idx.max(loaded_at).min(messages.len());
+            let end = segment.end_idx.min(messages.len());
+            messages[start..end]
+                .iter()
+                .flat_map(message_outputs)
+                .filter_map(test_run_result)
+                .last()
+        });
+    let ended_in_wrap_up = segmented
+        .segments
+        .last()
+        .is_some_and(|segment| segment.phase == SessionPhase::WrapUp);
+    // The output of a load is the skill itself, which may well talk about
+    // errors
+    let load_calls: Vec<&str> = messages
+        .iter()
+        .flat_map(|message| &message.tool_calls)
+        .filter(|call| loaded_skill(call).is_some())
+        .map(|call| call.id.as_str())
+        .collect();
+    let errors_after_load = messages
+        .iter()
+        .skip(loaded_at)
+        .flat_map(|message| &message.tool_results)
+        .filter(|result| !load_calls.contains(&result.tool_call_id.as_str()))
+        .filter(|result| result.is_error || is_error_output(&result.content))
+        .count();
+
+    OutcomeSignals {
+        loaded_at,
+        tests_passed,
+        ended_in_wrap_up,
+        errors_after_load,
+    }
+}
+
+/// Skills the transcript loads with `ms load <skill>` or the MCP `load`
+/// tool, as (skill reference, message index) in order of first load.
+#[must_use]
+pub fn loaded_skill_refs(session: &Session) -> Vec<(String, usize)> {
+    let mut refs: Vec<(String, usize)> = Vec::new();
+    for (idx, message) in session.messages.iter().enumerate() {
+        for skill in message.tool_calls.iter().filter_map(loaded_skill) {
+            if !refs.iter().any(|(seen, _)| *seen == skill) {
+                refs.push((skill, idx));
+            }
+        }
+    }
+    refs
+}
+
+fn loaded_skill(call: &ToolCall) -> Option<String> {
+    let name = call.name.to_lowercase();
+    if name == "load" || name.ends_with("__load") {
+        return call
+            .arguments
+            .get("skill")
+            .and_then(serde_json::Value::as_str)
+            .map(str::to_string);
+    }
+    let command = call.arguments.get("command")?.as_str()?;
+    let mut tokens = command
+        .split(|c: char| c.is_whitespace() || matches!(c, ';' | '&' | '|'))
+        .filter(|token| !token.is_empty())
+        .skip_while(|token| !is_ms_binary(token));
+    tokens.next()?;
+    if tokens.next()? != "load" {
+        return None;
+    }
+    tokens
+        .find(|token| !token.starts_with('-'))
+        .map(|token| token.trim_matches(['\'', '"']).to_string())
+}
+
+fn is_ms_binary(token: &str) -> bool {
+    matches!(token.rsplit(['/', '\\']).next(), Some("ms" | "ms.exe"))
+}
+
+/// Tool output in a message; cass exports without structured results carry
+/// it inline in the content.
+fn message_outputs(message: &SessionMessage) -> Vec<&str> {
+    if message.tool_results.is_empty() {
+        vec![message.content.as_str()]
+    } else {
+        message
+            .tool_results
+            .iter()
+            .map(|result| result.content.as_str())
+            .collect()
+    }
+}
+
+/// Whether `output` is a test run, and if so whether it passed.
+fn test_run_result(output: &str) -> Option<bool> {
+    let lower = output.to_lowercase();
+    if lower.contains("test result: failed")
+        || lower.contains("tests failed")
+        || lower.contains("--- fail")
+        || FAILED_COUNT_RE.is_match(&lower)
+    {
+        return Some(false);
+    }
+    if lower.contains("test result: ok")
+        || lower.contains("all tests passed")
+        || PASSED_COUNT_RE.is_match(&lower)
+    {
+        return Some(true);
+    }
+    None
+}
+
+/// The error indicators pattern mining uses, minus passing test summaries
+/// that mention `0 failed`.
+fn is_error_output(output: &str) -> bool {
+    if test_run_result(output) == Some(true) {
+        return false;
+    }
+    let lower = output.to_lowercase();
+    ["error", "failed", "panic", "exception"]
+        .iter()
+        .any(|indicator| lower.contains(indicator))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cass::mining::segment_session;
+    use crate::cass::{SessionMetadata, ToolResult};
+    use serde_json::json;
+
+    fn bash(idx: usize, command: &str, output: &str) -> SessionMessage {
+        SessionMessage {
+            index: idx,
+            role: "assistant".to_string(),
+            content: String::new(),
+            tool_calls: vec![ToolCall {
+                id: format!("call-{idx}"),
+                name: "Bash".to_string(),
+                arguments: json!({ "command": command }),
+            }],
+            tool_results: vec![ToolResult {
+                tool_call_id: format!("call-{idx}"),
+                content: output.to_string(),
+                is_error: false,
+            }],
+        }
+    }
+
+    fn edit(idx: usize) -> SessionMessage {
+        SessionMessage {
+            index: idx,
+            role: "assistant".to_string(),
+            content: String::new(),
+            tool_calls: vec![ToolCall {
+                id: format!("call-{idx}"),
+                name: "Edit".to_string(),
+                arguments: json!({ "file_path": "src/lib.rs" }),
+            }],
+            tool_results: Vec::new(),
+        }
+    }
+
+    fn session(messages: Vec<SessionMessage>) -> Session {
+        Session {
+            id: "sess-1".to_string(),
+            path: "/tmp/sess-1.jsonl".to_string(),
+            messages,
+            metadata: SessionMetadata::default(),
+            content_hash: String::new(),
+        }
+    }
+
+    fn signals(session: &Session, loaded_at: usize) -> OutcomeSignals {
+        infer_outcome(session, &segment_session(session), loaded_at)
+    }
+
+    /// Loads a skill, fixes a failing build, passes the tests and commits.
+    fn good_session() -> Session {
+        session(vec![
+            bash(0, "ms load rust-errors --full", "# Rust errors"),
+            edit(1),
+            bash(2, "cargo test", "test result: FAILED. 3 passed; 1 failed"),
+            edit(3),
+            bash(4, "cargo test", "test result: ok. 4 passed; 0 failed"),
+            bash(5, "git commit -m 'Fix parser'", "[main 1a2b3c4] Fix parser"),
+        ])
+    }
+
+    /// Loads a skill, hits errors and gives up with the tests still failing.
+    fn bad_session() -> Session {
+        session(vec![
+            bash(0, "ms load rust-errors", "# Rust errors"),
+            edit(1),
+            bash(2, "cargo build", "error[E0308]: mismatched types"),
+            edit(3),
+            bash(
+                4,
+                "cargo test",
+                "error: test failed, to rerun pass `--lib`\n2 failed",
+            ),
+        ])
+    }
+
+    #[test]
+    fn good_session_is_a_success() {
+        let signals = signals(&good_session(), 0);
+        assert_eq!(signals.tests_passed, Some(true));
+        assert!(signals.ended_in_wrap_up);
+        assert_eq!(signals.errors_after_load, 1);
+        assert_eq!(signals.verdict(), Verdict::Success);
+        let reward = signals.reward(None);
+        assert!((reward - (0.7 - ERROR_PENALTY)).abs() < 1e-6, "{reward}");
+    }
+
+    #[test]
+    fn bad_session_is_a_failure() {
+        let signals = signals(&bad_session(), 0);
+        assert_eq!(signals.tests_passed, Some(false));
+        assert!(!signals.ended_in_wrap_up);
+        assert_eq!(signals.errors_after_load, 2);
+        assert_eq!(signals.verdict(), Verdict::Failure);
+        assert!(signals.reward(Some(Duration::from_secs(60))) < 0.2);
+    }
+
+    #[test]
+    fn errors_before_the_load_do_not_count() {
+        let mut messages = vec![bash(9, "cargo build", "error: could not compile")];
+        messages.extend(good_session().messages);
+        let session = session(messages);
+        assert_eq!(signals(&session, 0).errors_after_load, 2);
+        assert_eq!(signals(&session, 1).errors_after_load, 1);
+    }
+
+    #[test]
+    fn session_without_tests_or_wrap_up_is_inconclusive() {
+        let session = session(vec![
+            bash(0, "ms load rust-errors", "# Rust errors"),
+            edit(1),
+        ]);
+        let signals = signals(&session, 0);
+        assert_eq!(signals.tests_passed, None);
+        assert_eq!(signals.verdict(), Verdict::Inconclusive);
+        assert!(matches!(signals.feedback(None), SkillFeedback::LoadedOnly));
+    }
+
+    #[test]
+    fn error_penalty_is_capped() {
+        let signals = OutcomeSignals {
+            loaded_at: 0,
+            tests_passed: Some(true),
+            ended_in_wrap_up: true,
+            errors_after_load: 100,
+        };
+        let reward = signals.reward(None);
+        assert!(
+            (reward - (0.7 - MAX_ERROR_PENALTY)).abs() < 1e-6,
+            "{reward}"
+        );
+    }
+
+    #[test]
+    fn classifies_test_runner_summaries() {
+        assert_eq!(
+            test_run_result("test result: ok. 12 passed; 0 failed"),
+            Some(true)
+        );
+        assert_eq!(test_run_result("===== 5 passed in 0.12s ====="), Some(true));
+        assert_eq!(test_run_result("Tests: 1 failed, 5 passed"), Some(false));
+        assert_eq!(test_run_result("--- FAIL: TestParse (0.00s)"), Some(false));
+        assert_eq!(test_run_result("Compiling ms v0.1.0"), None);
+        assert!(!is_error_output("test result: ok. 3 passed; 0 failed"));
+        assert!(is_error_output("thread 'main' panicked at src/main.rs:3"));
+    }
+
+    #[test]
+    fn finds_skill_loads_in_shell_and_mcp_calls() {
+        let mut mcp = edit(2);
+        mcp.tool_calls = vec![ToolCall {
+            id: "call-2".to_string(),
+            name: "mcp__ms__load".to_string(),
+            arguments: json!({ "skill": "git-workflow" }),
+        }];
+        let session = session(vec![
+            bash(
+                0,
+                "cd repo && /usr/local/bin/ms load --full rust-errors",
+                "",
+            ),
+            bash(1, "ms load rust-errors", ""),
+            mcp,
+            bash(3, "ms search load", ""),
+        ]);
+        assert_eq!(
+            loaded_skill_refs(&session),
+            vec![
+                ("rust-errors".to_string(), 0),
+                ("git-workflow".to_string(), 2)
+            ]
+        );
+    }
+}
@@ -12,6 +12,7 @@ use serde::{Deserialize, Serialize};
 use crate::suggestions::bandit::ContextualBandit;
 use crate::suggestions::bandit::features::ContextFeatures;
 use crate::suggestions::bandit::rewards::SkillFeedback;
+use crate::suggestions::explanation::SuggestionExplanation;
 
 /// Tracks skill loading sessions for implicit feedback.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -284,6 +285,10 @@ pub struct SuggestionTracker {
 
     /// Timestamp of the last suggestion display.
     last_suggestion_time: Option<DateTime<Utc>>,
+
+    /// Agent the suggestions are shown to, if known.
+    #[serde(default)]
+    agent_id: Option<String>,
 }
 
 /// Record of a suggestion that was shown.
@@ -303,6 +308,19 @@ pub struct SuggestionRecord {
 
     /// What happened with this suggestion.
     pub outcome: SuggestionOutcome,
+
+    /// Agent session the suggestion was shown in, if known.
+    #[serde(default)]
+    pub session_id: Option<String>,
+
+    /// Agent the suggestion was shown to (`--agent-id`, `MS_AGENT_ID`, or
+    /// the MCP client name), if known.
+    #[serde(default)]
+    pub agent_id: Option<String>,
+
+    /// Why the skill was suggested, for `ms why-suggested`.
+    #[serde(default)]
+    pub explanation: Option<SuggestionExplanation>,
 }
 
 /// Outcome of a suggestion.
@@ -318,6 +336,30 @@ pub enum SuggestionOutcome {
     Hidden,
 }
 
+impl SuggestionOutcome {
+    /// Storage key for this outcome.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Selected => "selected",
+            Self::Ignored => "ignored",
+            Self::Hidden => "hidden",
+        }
+    }
+
+    /// Parse a storage key, treating unknown values as pending.
+    #[must_use]
+    pub fn from_key(key: &str) -> Self {
+        match key {
+            "selected" => Self::Selected,
+            "ignored" => Self::Ignored,
+            "hidden" => Self::Hidden,
+            _ => Self::Pending,
+        }
+    }
+}
+
 impl SuggestionTracker {
     /// Create a new suggestion tracker.
     #[must_use]
@@ -325,6 +367,15 @@ impl SuggestionTracker {
         Self::default()
     }
 
+    /// Create a tracker whose records are attributed to `agent_id`.
+    #[must_use]
+    pub fn for_agent(agent_id: Option<String>) -> Self {
+        Self {
+            agent_id,
+            ..Self::default()
+        }
+    }
+
     /// Record that suggestions were shown to the user.
     pub fn record_suggestions(
         &mut self,
@@ -349,12 +400,28 @@ impl SuggestionTracker {
                         context_fingerprint_hash,
                         position,
                         outcome: SuggestionOutcome::Pending,
+                        session_id: None,
+                        agent_id: self.agent_id.clone(),
+                        explanation: None,
                     },
                 );
             }
         }
     }
 
+    /// Attach the session and explanation captured when `skill_id` was shown.
+    pub fn attach_explanation(
+        &mut self,
+        skill_id: &str,
+        session_id: Option<&str>,
+        explanation: SuggestionExplanation,
+    ) {
+        if let Some(record) = self.shown_suggestions.get_mut(skill_id) {
+            record.session_id = session_id.map(str::to_string);
+            record.explanation = Some(explanation);
+        }
+    }
+
     /// Record that a user selected a suggestion.
     pub fn on_suggestion_selected(&mut self, skill_id: &str) {
         if let Some(record) = self.shown_suggestions.get_mut(skill_id) {
@@ -595,6 +662,15 @@ mod tests {
         assert_eq!(pending[0].skill_id, "skill-b");
     }
 
+    #[test]
+    fn test_suggestion_tracker_attributes_records_to_agent() {
+        let mut tracker = SuggestionTracker::for_agent(Some("cursor".to_string()));
+        tracker.record_suggestions(&["skill-a".to_string()], Some(7));
+
+        let records = tracker.all_suggestions();
+        assert_eq!(records[0].agent_id.as_deref(), Some("cursor"));
+    }
+
     #[test]
     fn test_suggestion_end_tracking() {
         let mut tracker = SuggestionTracker::new();
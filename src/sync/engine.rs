@@ -4,7 +4,7 @@ use std::sync::Arc;
 use std::time::Instant;
 
 use chrono::{DateTime, Utc};
-use git2::{Cred, RemoteCallbacks, Repository, build::CheckoutBuilder};
+use git2::{Cred, CredentialType, RemoteCallbacks, Repository, build::CheckoutBuilder};
 use serde::Serialize;
 use sha2::{Digest, Sha256};
 use tracing::{info, warn};
@@ -12,7 +12,7 @@ use tracing::{info, warn};
 use crate::config::RuConfig;
 use crate::core::SkillSpec;
 use crate::error::{MsError, Result};
-use crate::storage::{Database, GitArchive, TxManager};
+use crate::storage::{Database, GitArchive, Provenance, TxManager};
 
 use super::SyncConfig;
 use super::config::{ConflictStrategy, RemoteAuth, RemoteConfig, RemoteType, validate_remote_name};
@@ -24,6 +24,7 @@ use super::jfp::{
 /// Type alias for the tuple used in push operations.
 type PushItemTuple = (String, JfpPushItem, JfpChangeType, Option<i64>, bool);
 use super::machine::MachineIdentity;
+use super::merge::{merge_specs, resolve_conflict_file};
 use super::ru::{RuClient, RuExitCode, RuSyncOptions};
 use super::state::{SkillSyncState, SkillSyncStatus, SyncState};
 
@@ -33,6 +34,8 @@ pub struct SyncOptions {
     pub pull_only: bool,
     pub dry_run: bool,
     pub force: bool,
+    /// Only push skills marked `share: true`.
+    pub shared_only: bool,
 }
 
 #[derive(Debug, Clone, Default, Serialize)]
@@ -42,7 +45,11 @@ pub struct SyncReport {
     pub pushed: Vec<String>,
     pub pulled: Vec<String>,
     pub resolved: Vec<String>,
+    /// Changed on both sides and merged section by section.
+    pub merged: Vec<String>,
     pub conflicts: Vec<String>,
+    /// `.conflict` files written for conflicts the merge could not settle.
+    pub conflict_files: Vec<String>,
     pub forked: Vec<String>,
     pub skipped: Vec<String>,
     pub errors: Vec<String>,
@@ -140,26 +147,44 @@ impl SyncEngine {
         else {
             return Err(MsError::Config(format!("remote not found: {remote_name}")));
         };
+        self.sync_config(&remote, options)
+    }
+
+    /// Sync with a team skill repository given by git URL or path, without
+    /// configuring it as a remote. Only skills marked `share: true` are
+    /// pushed. The clone is cached under `sync/remotes/` like a configured
+    /// git remote, and `.conflict` files go to `sync/conflicts/`.
+    pub fn sync_url(&mut self, url: &str, options: &SyncOptions) -> Result<SyncReport> {
+        let remote = team_remote(url);
+        let options = SyncOptions {
+            shared_only: true,
+            ..options.clone()
+        };
+        let mut report = self.sync_config(&remote, &options)?;
+        report.remote = url.to_string();
+        Ok(report)
+    }
 
+    fn sync_config(&mut self, remote: &RemoteConfig, options: &SyncOptions) -> Result<SyncReport> {
         let start = Instant::now();
         let mut report = SyncReport {
-            remote: remote_name.to_string(),
+            remote: remote.name.clone(),
             ..Default::default()
         };
 
         match remote.remote_type {
             RemoteType::FileSystem => {
-                self.sync_filesystem(&remote, options, &mut report)?;
+                self.sync_filesystem(remote, options, &mut report)?;
             }
             RemoteType::Git => {
-                let remote_git = open_git_remote(&remote, &self.ms_root)?;
-                self.sync_with_archive(&remote, options, &mut report, &remote_git)?;
+                let remote_git = open_git_remote(remote, &self.ms_root)?;
+                self.sync_with_archive(remote, options, &mut report, &remote_git)?;
             }
             RemoteType::Ru => {
-                self.sync_ru(&remote, options, &mut report)?;
+                self.sync_ru(remote, options, &mut report)?;
             }
             RemoteType::JfpCloud => {
-                self.sync_jfp_cloud(&remote, options, &mut report)?;
+                self.sync_jfp_cloud(remote, options, &mut report)?;
             }
         }
 
@@ -441,7 +466,7 @@ impl SyncEngine {
                             spec.metadata.id = fork_id.clone();
                             spec.metadata.name = format!("{} (cloud)", spec.metadata.name);
                             if !options.dry_run {
-                                tx_mgr.write_skill_locked(&spec)?;
+                                tx_mgr.write_skill_locked(&spec, &Provenance::new("sync"))?;
                             }
                             report.forked.push(fork_id.clone());
                             report.resolved.push(skill_id.clone());
@@ -747,7 +772,7 @@ impl SyncEngine {
         let spec = payload_to_skill_spec(payload)?;
 
         if !options.dry_run {
-            tx_mgr.write_skill_locked(&spec)?;
+            tx_mgr.write_skill_locked(&spec, &Provenance::new("sync"))?;
         }
         report.pulled.push(skill_id.clone());
 
@@ -845,16 +870,21 @@ impl SyncEngine {
             let status = determine_sync_status(local, remote_snap, base_hash);
 
             let mut final_status = status.clone();
+            let mut merged_base = None;
 
             match status {
                 SkillSyncStatus::Synced => {
                     report.skipped.push(id.clone());
                 }
                 SkillSyncStatus::LocalAhead | SkillSyncStatus::LocalOnly => {
-                    if allow_push {
+                    if allow_push && self.shareable(&id, options)? {
                         if !options.dry_run {
                             let spec = self.git.read_skill(&id)?;
-                            remote_git.write_skill(&spec)?;
+                            remote_git.commit_with_provenance(
+                                &spec,
+                                None,
+                                &Provenance::new("sync"),
+                            )?;
                             if remote.remote_type == RemoteType::Git {
                                 needs_git_push = true;
                             }
@@ -869,7 +899,7 @@ impl SyncEngine {
                     if allow_pull {
                         if !options.dry_run {
                             let spec = remote_git.read_skill(&id)?;
-                            tx_mgr.write_skill_locked(&spec)?;
+                            tx_mgr.write_skill_locked(&spec, &Provenance::new("sync"))?;
                         }
                         report.pulled.push(id.clone());
                         final_status = SkillSyncStatus::Synced;
@@ -902,6 +932,20 @@ impl SyncEngine {
                         if final_status == SkillSyncStatus::Synced {
                             report.resolved.push(id.clone());
                         }
+                    } else if allow_pull {
+                        let (status, base) = self.merge_diverged(
+                            &id,
+                            base_hash,
+                            remote,
+                            remote_git,
+                            allow_push,
+                            options,
+                            &tx_mgr,
+                            report,
+                            &mut needs_git_push,
+                        )?;
+                        final_status = status;
+                        merged_base = base;
                     } else {
                         report.conflicts.push(id.clone());
                         final_status = SkillSyncStatus::Conflict;
@@ -917,10 +961,12 @@ impl SyncEngine {
                 .map(|entry| entry.remote_modified.clone())
                 .unwrap_or_default();
 
-            // Only update the base hash if we are fully synchronized.
-            // Otherwise, we must preserve the last known common ancestor to correctly
-            // detect future conflicts/directions.
-            if final_status == SkillSyncStatus::Synced {
+            // Only update the base hash if we are fully synchronized (or merged
+            // the remote's changes in). Otherwise, we must preserve the last known
+            // common ancestor to correctly detect future conflicts/directions.
+            if let Some(hash) = merged_base {
+                remote_hashes.insert(remote.name.clone(), hash);
+            } else if final_status == SkillSyncStatus::Synced {
                 if let Some(snap) = remote_snap {
                     remote_hashes.insert(remote.name.clone(), snap.hash.clone());
                 } else {
@@ -947,6 +993,10 @@ impl SyncEngine {
             self.state.skill_states.insert(id, state_entry);
         }
 
+        // Also push commits an interrupted run left in the clone.
+        let needs_git_push = needs_git_push
+            || (git_auth.is_some()
+                && has_unpushed_commits(remote_git.repo(), remote.branch.as_deref()));
         if needs_git_push && !options.dry_run {
             if let Some(auth) = git_auth.as_ref() {
                 push_git_repo(
@@ -961,6 +1011,111 @@ impl SyncEngine {
         Ok(())
     }
 
+    /// Whether `id` may be pushed under `options`.
+    fn shareable(&self, id: &str, options: &SyncOptions) -> Result<bool> {
+        Ok(!options.shared_only || self.git.read_skill(id)?.metadata.share)
+    }
+
+    /// Three-way merge a skill changed on both sides since the last sync,
+    /// with the version both last agreed on as the base. A clean merge, or a
+    /// `.conflict` file the user has since resolved, is written locally and
+    /// pushed; otherwise the merge goes to the skill's `.conflict` file and
+    /// the local skill is left alone.
+    ///
+    /// Returns the skill's status and, when it was merged, the new base hash.
+    fn merge_diverged(
+        &self,
+        id: &str,
+        base_hash: Option<&str>,
+        remote: &RemoteConfig,
+        remote_git: &GitArchive,
+        allow_push: bool,
+        options: &SyncOptions,
+        tx_mgr: &TxManager,
+        report: &mut SyncReport,
+        needs_git_push: &mut bool,
+    ) -> Result<(SkillSyncStatus, Option<String>)> {
+        let local = self.git.read_skill(id)?;
+        let theirs = remote_git.read_skill(id)?;
+        let conflict_path = conflict_file_path(&self.ms_root, id);
+
+        let resolved = if conflict_path.exists() {
+            let contents = std::fs::read_to_string(&conflict_path)?;
+            let resolved = resolve_conflict_file(&contents, &local)?;
+            if resolved.is_none() {
+                // Still being resolved: leave the user's edits alone.
+                report.conflicts.push(id.to_string());
+                report
+                    .conflict_files
+                    .push(conflict_path.display().to_string());
+                return Ok((SkillSyncStatus::Conflict, None));
+            }
+            resolved
+        } else {
+            None
+        };
+
+        let merged = if let Some(spec) = resolved {
+            spec
+        } else {
+            let base = self
+                .find_base(id, base_hash, remote_git)?
+                .unwrap_or_else(|| SkillSpec::new(id, ""));
+            let merge = merge_specs(&base, &local, &theirs)?;
+            if !merge.is_clean() {
+                if !options.dry_run {
+                    write_conflict_file(&conflict_path, &merge.render_conflict(&remote.name))?;
+                }
+                report.conflicts.push(id.to_string());
+                report
+                    .conflict_files
+                    .push(conflict_path.display().to_string());
+                return Ok((SkillSyncStatus::Conflict, None));
+            }
+            merge.spec
+        };
+
+        let push = allow_push && (merged.metadata.share || !options.shared_only);
+        if !options.dry_run {
+            tx_mgr.write_skill_locked(&merged, &Provenance::new("sync"))?;
+            if push {
+                remote_git.commit_with_provenance(&merged, None, &Provenance::new("sync"))?;
+                if remote.remote_type == RemoteType::Git {
+                    *needs_git_push = true;
+                }
+            }
+            if conflict_path.exists() {
+                std::fs::remove_file(&conflict_path)?;
+            }
+        }
+        report.merged.push(id.to_string());
+
+        if push {
+            Ok((SkillSyncStatus::Synced, Some(hash_skill_spec(&merged)?)))
+        } else {
+            // Local now has the remote's changes, so the remote is the base.
+            Ok((SkillSyncStatus::LocalAhead, Some(hash_skill_spec(&theirs)?)))
+        }
+    }
+
+    /// The version of `id` whose hash is `base_hash`, from local history or
+    /// else the remote's.
+    fn find_base(
+        &self,
+        id: &str,
+        base_hash: Option<&str>,
+        remote_git: &GitArchive,
+    ) -> Result<Option<SkillSpec>> {
+        let Some(base_hash) = base_hash else {
+            return Ok(None);
+        };
+        let matches = |spec: &SkillSpec| hash_skill_spec(spec).is_ok_and(|hash| hash == base_hash);
+        if let Some(spec) = self.git.find_skill_in_history(id, matches)? {
+            return Ok(Some(spec));
+        }
+        remote_git.find_skill_in_history(id, matches)
+    }
+
     #[allow(dead_code)]
     fn sync_git(
         &mut self,
@@ -993,7 +1148,7 @@ impl SyncEngine {
                 if allow_push {
                     if !options.dry_run {
                         let spec = self.git.read_skill(id)?;
-                        remote_git.write_skill(&spec)?;
+                        remote_git.commit_with_provenance(&spec, None, &Provenance::new("sync"))?;
                         if remote_is_git {
                             *needs_git_push = true;
                         }
@@ -1009,7 +1164,7 @@ impl SyncEngine {
                 if allow_pull {
                     if !options.dry_run {
                         let spec = remote_git.read_skill(id)?;
-                        tx_mgr.write_skill_locked(&spec)?;
+                        tx_mgr.write_skill_locked(&spec, &Provenance::new("sync"))?;
                     }
                     report.pulled.push(id.to_string());
                     Ok(SkillSyncStatus::Synced)
@@ -1064,14 +1219,14 @@ impl SyncEngine {
                     let mut spec = remote_git.read_skill(id)?;
                     spec.metadata.id = fork_id.clone();
                     spec.metadata.name = format!("{} (remote)", spec.metadata.name);
-                    tx_mgr.write_skill_locked(&spec)?;
+                    tx_mgr.write_skill_locked(&spec, &Provenance::new("sync"))?;
                 }
                 report.forked.push(fork_id);
 
                 if allow_push {
                     if !options.dry_run {
                         let spec = self.git.read_skill(id)?;
-                        remote_git.write_skill(&spec)?;
+                        remote_git.commit_with_provenance(&spec, None, &Provenance::new("sync"))?;
                         if remote_is_git {
                             *needs_git_push = true;
                         }
@@ -1169,6 +1324,36 @@ fn unique_fork_id(archive: &GitArchive, id: &str) -> Result<String> {
     )))
 }
 
+/// Ad-hoc git remote for `ms sync --remote <url>`, named after the URL so
+/// each team repository gets its own clone and base hashes.
+fn team_remote(url: &str) -> RemoteConfig {
+    let digest = hex::encode(Sha256::digest(url.trim().as_bytes()));
+    RemoteConfig::new(
+        format!("team-{}", &digest[..12]),
+        RemoteType::Git,
+        url.trim(),
+    )
+}
+
+/// Where the `.conflict` file of `id` goes.
+fn conflict_file_path(ms_root: &Path, id: &str) -> PathBuf {
+    ms_root
+        .join("sync")
+        .join("conflicts")
+        .join(format!("{id}.conflict"))
+}
+
+fn write_conflict_file(path: &Path, contents: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|err| MsError::Config(format!("create conflicts dir: {err}")))?;
+    }
+    let tmp = path.with_extension("conflict.tmp");
+    std::fs::write(&tmp, contents)
+        .and_then(|()| std::fs::rename(&tmp, path))
+        .map_err(|err| MsError::Config(format!("write {}: {err}", path.display())))
+}
+
 fn resolve_archive_root(path: &Path) -> Result<PathBuf> {
     if path.join("skills").join("by-id").exists() {
         return Ok(path.to_path_buf());
@@ -1177,6 +1362,13 @@ fn resolve_archive_root(path: &Path) -> Result<PathBuf> {
     if candidate.join("skills").join("by-id").exists() {
         return Ok(candidate);
     }
+    // A new, empty team repository becomes an archive on first push.
+    if Repository::open(path)
+        .and_then(|repo| repo.is_empty())
+        .unwrap_or(false)
+    {
+        return Ok(path.to_path_buf());
+    }
     Err(MsError::Config(format!(
         "remote path {} does not look like an ms archive (expected skills/by-id)",
         path.display()
@@ -1199,7 +1391,16 @@ fn open_git_remote(remote: &RemoteConfig, ms_root: &Path) -> Result<GitArchive>
             std::fs::create_dir_all(parent)
                 .map_err(|err| MsError::Config(format!("create git cache dir: {err}")))?;
         }
-        clone_remote(remote, &cache_root, &auth)?;
+        // Clone next to the cache and move it in place, so an interrupted
+        // clone is started over instead of being mistaken for a cache.
+        let partial = cache_root.with_file_name(format!("{}.partial", remote.name));
+        if partial.exists() {
+            std::fs::remove_dir_all(&partial)
+                .map_err(|err| MsError::Config(format!("remove partial clone: {err}")))?;
+        }
+        clone_remote(remote, &partial, &auth)?;
+        std::fs::rename(&partial, &cache_root)
+            .map_err(|err| MsError::Config(format!("move clone into cache: {err}")))?;
     }
 
     let repo = Repository::open(&cache_root).map_err(MsError::Git)?;
@@ -1247,11 +1448,23 @@ fn sync_git_repo(
         )
         .map_err(MsError::Git)?;
 
+    // Nothing to fast-forward in an empty repository.
+    if repo.is_empty().map_err(MsError::Git)? {
+        return Ok(());
+    }
+
     let branch = resolve_branch_name(repo, branch_override)?;
     let remote_ref = format!("refs/remotes/origin/{branch}");
-    let remote_ref = repo
-        .find_reference(&remote_ref)
-        .map_err(|_| MsError::Config(format!("remote branch not found: {branch}")))?;
+    let remote_ref = match repo.find_reference(&remote_ref) {
+        Ok(reference) => reference,
+        // The branch only exists here, from a push that never completed.
+        Err(_) if repo.find_reference(&format!("refs/heads/{branch}")).is_ok() => return Ok(()),
+        Err(_) => {
+            return Err(MsError::Config(format!(
+                "remote branch not found: {branch}"
+            )));
+        }
+    };
     let Some(target) = remote_ref.target() else {
         return Err(MsError::Config(format!(
             "remote branch has no target: {branch}"
@@ -1311,6 +1524,18 @@ fn push_git_repo(
     Ok(())
 }
 
+/// Whether the local branch has commits the remote branch lacks.
+fn has_unpushed_commits(repo: &Repository, branch_override: Option<&str>) -> bool {
+    let Ok(branch) = resolve_branch_name(repo, branch_override) else {
+        return false;
+    };
+    let local = repo.refname_to_id(&format!("refs/heads/{branch}")).ok();
+    let remote = repo
+        .refname_to_id(&format!("refs/remotes/origin/{branch}"))
+        .ok();
+    local.is_some() && local != remote
+}
+
 fn ensure_origin_url(repo: &Repository, url: &str) -> Result<()> {
     match repo.find_remote("origin") {
         Ok(remote) => {
@@ -1386,11 +1611,36 @@ fn resolve_auth(remote: &RemoteConfig) -> Result<ResolvedAuth> {
     }
 }
 
+/// Credential rounds before giving up on ssh-agent / credential helpers.
+const MAX_DEFAULT_AUTH_ATTEMPTS: u32 = 3;
+
 fn build_callbacks(auth: &ResolvedAuth) -> Result<RemoteCallbacks<'static>> {
     let auth = auth.clone();
+    let mut attempts = 0_u32;
     let mut callbacks = RemoteCallbacks::new();
-    callbacks.credentials(move |_url, username_from_url, _allowed| match &auth {
-        ResolvedAuth::Default => Cred::default(),
+    callbacks.credentials(move |url, username_from_url, allowed| match &auth {
+        // Defer to the user's git setup: ssh-agent for ssh remotes, credential
+        // helpers for https. libgit2 keeps asking while credentials are
+        // rejected, so give up after a few rounds.
+        ResolvedAuth::Default => {
+            attempts += 1;
+            if attempts > MAX_DEFAULT_AUTH_ATTEMPTS {
+                return Err(git2::Error::from_str(
+                    "authentication failed (check ssh-agent or git credential helper)",
+                ));
+            }
+            let user = username_from_url.unwrap_or("git");
+            if allowed.contains(CredentialType::USERNAME) {
+                Cred::username(user)
+            } else if allowed.contains(CredentialType::SSH_KEY) {
+                Cred::ssh_key_from_agent(user)
+            } else if allowed.contains(CredentialType::USER_PASS_PLAINTEXT) {
+                git2::Config::open_default()
+                    .and_then(|config| Cred::credential_helper(&config, url, username_from_url))
+            } else {
+                Cred::default()
+            }
+        }
         ResolvedAuth::Token { token, username } => {
             let user = username
                 .as_deref()
@@ -0,0 +1,447 @@
+//! Three-way merge of skill specs for team repositories.
+//!
+//! When a skill changed both locally and in a team repository since the last
+//! sync, the version both sides last agreed on is the merge base. Sections
+//! are the unit of merging: a section changed on one side only takes that
+//! side's version, and a section changed on both sides to different content
+//! is a conflict. Metadata merges field by field the same way.
+//!
+//! A conflicted merge is rendered as the skill's markdown with conflict
+//! markers around each disputed section. Once the markers are gone the file
+//! parses back into the resolved spec ([`resolve_conflict_file`]).
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde_json::{Map, Value};
+
+use crate::core::layering::compute_section_diff;
+use crate::core::spec_lens::{compile_markdown, parse_markdown};
+use crate::core::{BlockType, SkillBlock, SkillSection, SkillSpec};
+use crate::error::Result;
+
+/// Section holding conflicting metadata fields in a `.conflict` file.
+const FIELDS_SECTION_ID: &str = "ms-sync-fields";
+
+const METADATA_PREFIX: &str = "metadata.";
+
+/// A section both sides changed differently.
+#[derive(Debug, Clone)]
+pub struct SectionConflict {
+    pub section_id: String,
+    /// Local version; `None` when the section was deleted locally.
+    pub local: Option<SkillSection>,
+    /// Remote version; `None` when the section was deleted remotely.
+    pub remote: Option<SkillSection>,
+}
+
+/// A spec field both sides changed differently, e.g. `metadata.description`.
+#[derive(Debug, Clone)]
+pub struct FieldConflict {
+    pub field: String,
+    pub local: Option<Value>,
+    pub remote: Option<Value>,
+}
+
+/// Result of [`merge_specs`]. Conflicting parts keep their local version in
+/// `spec`.
+#[derive(Debug, Clone)]
+pub struct SpecMerge {
+    pub spec: SkillSpec,
+    pub sections: Vec<SectionConflict>,
+    pub fields: Vec<FieldConflict>,
+}
+
+impl SpecMerge {
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.sections.is_empty() && self.fields.is_empty()
+    }
+
+    /// The merged skill as markdown, with conflict markers around every
+    /// disputed section and a leading section listing disputed fields.
+    #[must_use]
+    pub fn render_conflict(&self, remote_name: &str) -> String {
+        let mut spec = self.spec.clone();
+        let mut sections = Vec::new();
+
+        if !self.fields.is_empty() {
+            let value = |value: &Option<Value>| {
+                value
+                    .as_ref()
+                    .map_or_else(|| "(unset)".to_string(), Value::to_string)
+            };
+            let local: Vec<String> = self
+                .fields
+                .iter()
+                .map(|c| format!("{}: {}", c.field, value(&c.local)))
+                .collect();
+            let remote: Vec<String> = self
+                .fields
+                .iter()
+                .map(|c| format!("{}: {}", c.field, value(&c.remote)))
+                .collect();
+            sections.push(SkillSection {
+                id: FIELDS_SECTION_ID.to_string(),
+                title: "Conflicting fields".to_string(),
+                blocks: vec![text_block(format!(
+                    "The frontmatter holds the local values. Edit it, then delete this section.\n\n{}",
+                    markers(&local.join("\n"), &remote.join("\n"), remote_name)
+                ))],
+            });
+        }
+
+        let conflicts: HashMap<&str, &SectionConflict> = self
+            .sections
+            .iter()
+            .map(|c| (c.section_id.as_str(), c))
+            .collect();
+        for section in &spec.sections {
+            match conflicts.get(section.id.as_str()) {
+                Some(conflict) => sections.push(conflict_section(conflict, remote_name)),
+                None => sections.push(section.clone()),
+            }
+        }
+        // Sections deleted locally are not in the merged spec.
+        for conflict in self.sections.iter().filter(|c| c.local.is_none()) {
+            sections.push(conflict_section(conflict, remote_name));
+        }
+
+        spec.sections = sections;
+        compile_markdown(&spec)
+    }
+}
+
+/// Merge `local` and `remote` against their common ancestor `base`.
+pub fn merge_specs(base: &SkillSpec, local: &SkillSpec, remote: &SkillSpec) -> Result<SpecMerge> {
+    let base_fields = fields(base)?;
+    let local_fields = fields(local)?;
+    let remote_fields = fields(remote)?;
+
+    let mut merged_fields = BTreeMap::new();
+    let mut field_conflicts = Vec::new();
+    let mut keys: Vec<&String> = local_fields.keys().chain(remote_fields.keys()).collect();
+    keys.sort();
+    keys.dedup();
+    for key in keys {
+        let (b, l, r) = (
+            base_fields.get(key),
+            local_fields.get(key),
+            remote_fields.get(key),
+        );
+        let value = match pick(b, l, r, |a, b| a == b) {
+            Pick::Take(value) => value,
+            Pick::Conflict => {
+                field_conflicts.push(FieldConflict {
+                    field: key.clone(),
+                    local: l.cloned(),
+                    remote: r.cloned(),
+                });
+                l
+            }
+        };
+        if let Some(value) = value {
+            merged_fields.insert(key.clone(), value.clone());
+        }
+    }
+
+    let by_id = |spec: &SkillSpec| -> HashMap<String, SkillSection> {
+        spec.sections
+            .iter()
+            .map(|s| (s.id.clone(), s.clone()))
+            .collect()
+    };
+    let (base_sections, local_sections, remote_sections) =
+        (by_id(base), by_id(local), by_id(remote));
+
+    // Local order first, then sections only the remote has.
+    let mut order: Vec<&str> = local.sections.iter().map(|s| s.id.as_str()).collect();
+    for section in &remote.sections {
+        if !local_sections.contains_key(&section.id) {
+            order.push(&section.id);
+        }
+    }
+
+    let mut sections = Vec::new();
+    let mut section_conflicts = Vec::new();
+    for id in order {
+        let (b, l, r) = (
+            base_sections.get(id),
+            local_sections.get(id),
+            remote_sections.get(id),
+        );
+        let section = match pick(b, l, r, |a, b| !section_changed(a, b)) {
+            Pick::Take(section) => section,
+            Pick::Conflict => {
+                section_conflicts.push(SectionConflict {
+                    section_id: id.to_string(),
+                    local: l.cloned(),
+                    remote: r.cloned(),
+                });
+                l
+            }
+        };
+        if let Some(section) = section {
+            sections.push(section.clone());
+        }
+    }
+
+    Ok(SpecMerge {
+        spec: from_fields(merged_fields, sections)?,
+        sections: section_conflicts,
+        fields: field_conflicts,
+    })
+}
+
+/// The spec a resolved `.conflict` file describes, or `None` while conflict
+/// markers remain. Fields markdown cannot carry (`extends`, `includes`, ...)
+/// come from `local`.
+pub fn resolve_conflict_file(contents: &str, local: &SkillSpec) -> Result<Option<SkillSpec>> {
+    if has_markers(contents) {
+        return Ok(None);
+    }
+    let parsed = parse_markdown(contents)?;
+    let mut spec = local.clone();
+    spec.metadata = parsed.metadata;
+    spec.metadata.id.clone_from(&local.metadata.id);
+    spec.sections = parsed
+        .sections
+        .into_iter()
+        .filter(|s| s.id != FIELDS_SECTION_ID)
+        .collect();
+    Ok(Some(spec))
+}
+
+/// Whether `contents` still has conflict markers.
+#[must_use]
+pub fn has_markers(contents: &str) -> bool {
+    contents
+        .lines()
+        .any(|line| line.starts_with("<<<<<<<") || line.starts_with(">>>>>>>") || line == "=======")
+}
+
+enum Pick<'a, T> {
+    Take(Option<&'a T>),
+    Conflict,
+}
+
+/// Three-way choice for one unit (`None` = absent on that side).
+fn pick<'a, T>(
+    base: Option<&'a T>,
+    local: Option<&'a T>,
+    remote: Option<&'a T>,
+    same: impl Fn(&T, &T) -> bool,
+) -> Pick<'a, T> {
+    let eq = |a: Option<&T>, b: Option<&T>| match (a, b) {
+        (None, None) => true,
+        (Some(a), Some(b)) => same(a, b),
+        _ => false,
+    };
+    if eq(local, base) {
+        Pick::Take(remote)
+    } else if eq(remote, base) || eq(local, remote) {
+        Pick::Take(local)
+    } else {
+        Pick::Conflict
+    }
+}
+
+/// Whether two versions of a section differ in title, block order or types,
+/// or block content.
+fn section_changed(a: &SkillSection, b: &SkillSection) -> bool {
+    let shape = |s: &SkillSection| -> Vec<(String, BlockType)> {
+        s.blocks
+            .iter()
+            .map(|block| (block.id.clone(), block.block_type.clone()))
+            .collect()
+    };
+    if a.title != b.title || shape(a) != shape(b) {
+        return true;
+    }
+    let diff = compute_section_diff(a, b);
+    !diff.higher_only.is_empty() || !diff.lower_only.is_empty() || !diff.modified.is_empty()
+}
+
+/// Spec fields other than sections, flattened: `metadata.<field>` for
+/// metadata, the field name otherwise.
+fn fields(spec: &SkillSpec) -> Result<BTreeMap<String, Value>> {
+    let Value::Object(mut top) = serde_json::to_value(spec)? else {
+        return Ok(BTreeMap::new());
+    };
+    top.remove("sections");
+    let mut fields = BTreeMap::new();
+    if let Some(Value::Object(metadata)) = top.remove("metadata") {
+        for (key, value) in metadata {
+            fields.insert(format!("{METADATA_PREFIX}{key}"), value);
+        }
+    }
+    fields.extend(top);
+    Ok(fields)
+}
+
+fn from_fields(fields: BTreeMap<String, Value>, sections: Vec<SkillSection>) -> Result<SkillSpec> {
+    let mut top = Map::new();
+    let mut metadata = Map::new();
+    for (key, value) in fields {
+        match key.strip_prefix(METADATA_PREFIX) {
+            Some(field) => metadata.insert(field.to_string(), value),
+            None => top.insert(key, value),
+        };
+    }
+    top.insert("metadata".to_string(), Value::Object(metadata));
+    top.insert("sections".to_string(), serde_json::to_value(sections)?);
+    Ok(serde_json::from_value(Value::Object(top))?)
+}
+
+fn conflict_section(conflict: &SectionConflict, remote_name: &str) -> SkillSection {
+    let title = conflict
+        .local
+        .as_ref()
+        .or(conflict.remote.as_ref())
+        .map(|s| s.title.clone())
+        .unwrap_or_default();
+    let body = |section: &Option<SkillSection>| {
+        section.as_ref().map_or_else(
+            || "(section deleted)".to_string(),
+            |s| {
+                let mut text = String::new();
+                if conflict
+                    .local
+                    .as_ref()
+                    .zip(conflict.remote.as_ref())
+                    .is_some_and(|(l, r)| l.title != r.title)
+                {
+                    text.push_str(&format!("Title: {}\n\n", s.title));
+                }
+                let blocks: Vec<String> = s.blocks.iter().map(block_markdown).collect();
+                text.push_str(&blocks.join("\n\n"));
+                text
+            },
+        )
+    };
+    SkillSection {
+        id: conflict.section_id.clone(),
+        title,
+        blocks: vec![text_block(markers(
+            &body(&conflict.local),
+            &body(&conflict.remote),
+            remote_name,
+        ))],
+    }
+}
+
+fn block_markdown(block: &SkillBlock) -> String {
+    let content = block.content.trim_end();
+    if block.block_type == BlockType::Code && !content.starts_with("```") {
+        format!("```\n{content}\n```")
+    } else {
+        content.to_string()
+    }
+}
+
+fn markers(local: &str, remote: &str, remote_name: &str) -> String {
+    format!("<<<<<<< local\n{local}\n=======\n{remote}\n>>>>>>> {remote_name}")
+}
+
+fn text_block(content: String) -> SkillBlock {
+    SkillBlock {
+        id: String::new(),
+        block_type: BlockType::Text,
+        content,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(description: &str, sections: &[(&str, &str)]) -> SkillSpec {
+        let mut spec = SkillSpec::new("team-skill", "Team Skill");
+        spec.metadata.description = description.to_string();
+        spec.sections = sections
+            .iter()
+            .map(|(id, content)| SkillSection {
+                id: (*id).to_string(),
+                title: (*id).to_string(),
+                blocks: vec![SkillBlock {
+                    id: format!("{id}-0"),
+                    block_type: BlockType::Rule,
+                    content: (*content).to_string(),
+                }],
+            })
+            .collect();
+        spec
+    }
+
+    fn contents(spec: &SkillSpec) -> Vec<(&str, &str)> {
+        spec.sections
+            .iter()
+            .map(|s| (s.id.as_str(), s.blocks[0].content.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn changes_to_different_sections_merge_cleanly() {
+        let base = spec("base", &[("rules", "a"), ("pitfalls", "b")]);
+        let local = spec("base", &[("rules", "a2"), ("pitfalls", "b")]);
+        let remote = spec(
+            "remote",
+            &[("rules", "a"), ("pitfalls", "b2"), ("checklist", "c")],
+        );
+
+        let merge = merge_specs(&base, &local, &remote).unwrap();
+        assert!(merge.is_clean());
+        assert_eq!(
+            contents(&merge.spec),
+            [("rules", "a2"), ("pitfalls", "b2"), ("checklist", "c")]
+        );
+        assert_eq!(merge.spec.metadata.description, "remote");
+    }
+
+    #[test]
+    fn deletions_on_one_side_are_kept() {
+        let base = spec("base", &[("rules", "a"), ("pitfalls", "b")]);
+        let local = spec("base", &[("rules", "a")]);
+        let remote = spec("base", &[("rules", "a"), ("pitfalls", "b")]);
+
+        let merge = merge_specs(&base, &local, &remote).unwrap();
+        assert!(merge.is_clean());
+        assert_eq!(contents(&merge.spec), [("rules", "a")]);
+    }
+
+    #[test]
+    fn same_section_changed_on_both_sides_conflicts() {
+        let base = spec("base", &[("rules", "a"), ("pitfalls", "b")]);
+        let local = spec("local", &[("rules", "mine"), ("pitfalls", "b")]);
+        let remote = spec("theirs", &[("rules", "yours"), ("pitfalls", "b2")]);
+
+        let merge = merge_specs(&base, &local, &remote).unwrap();
+        assert!(!merge.is_clean());
+        assert_eq!(merge.sections.len(), 1);
+        assert_eq!(merge.sections[0].section_id, "rules");
+        assert_eq!(merge.fields[0].field, "metadata.description");
+        assert_eq!(
+            contents(&merge.spec),
+            [("rules", "mine"), ("pitfalls", "b2")]
+        );
+
+        let rendered = merge.render_conflict("team");
+        assert!(rendered.contains("<<<<<<< local\nmine\n=======\nyours\n>>>>>>> team"));
+        assert!(rendered.contains("metadata.description: \"local\""));
+        assert!(has_markers(&rendered));
+        assert!(resolve_conflict_file(&rendered, &local).unwrap().is_none());
+    }
+
+    #[test]
+    fn resolved_conflict_file_parses_back() {
+        let local = spec("local", &[("rules", "mine")]);
+        let resolved = "---\nid: team-skill\nname: Team Skill\ndescription: agreed\n---\n\n\
+                        # Team Skill\n\nagreed\n\n## rules\n\nboth of ours\n\n\
+                        ## Conflicting fields {#ms-sync-fields}\n\nleftover\n";
+
+        let spec = resolve_conflict_file(resolved, &local).unwrap().unwrap();
+        assert_eq!(spec.metadata.id, "team-skill");
+        assert_eq!(spec.metadata.description, "agreed");
+        assert_eq!(spec.sections.len(), 1);
+        assert_eq!(spec.sections[0].blocks[0].content, "both of ours");
+    }
+}
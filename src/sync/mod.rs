@@ -4,6 +4,7 @@ pub mod config;
 pub mod engine;
 pub mod jfp;
 pub mod machine;
+pub mod merge;
 pub mod ru;
 pub mod state;
 
@@ -13,6 +14,7 @@ pub use config::{
 };
 pub use engine::{SyncEngine, SyncOptions, SyncReport};
 pub use machine::{MachineIdentity, MachineMetadata};
+pub use merge::{SpecMerge, merge_specs};
 pub use ru::{
     RuClient, RuConflict, RuError, RuExitCode, RuRepoStatus, RuSyncOptions, RuSyncResult,
 };
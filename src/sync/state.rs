@@ -66,9 +66,13 @@ impl SyncState {
                 .map_err(|err| MsError::Config(format!("create sync state dir: {err}")))?;
         }
         let rendered = serde_json::to_string_pretty(self)?;
-        std::fs::write(&path, rendered).map_err(|err| {
-            MsError::Config(format!("write sync state {}: {err}", path.display()))
-        })?;
+        // Write then rename, so an interrupted sync never leaves a torn state.
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, rendered)
+            .and_then(|()| std::fs::rename(&tmp, &path))
+            .map_err(|err| {
+                MsError::Config(format!("write sync state {}: {err}", path.display()))
+            })?;
         Ok(())
     }
 }
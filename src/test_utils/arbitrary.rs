@@ -1,10 +1,11 @@
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 use proptest::prelude::*;
 
 use crate::config::{
-    AgentMailConfig, CacheConfig, CassConfig, Config, DisclosureConfig, LayersConfig, RobotConfig,
-    SafetyConfig, SearchConfig, SecurityConfig, SkillPathsConfig, UpdateConfig,
+    AgentMailConfig, CacheConfig, CassConfig, Config, DisclosureConfig, LayerPolicy, LayersConfig,
+    RobotConfig, SafetyConfig, SearchConfig, SecurityConfig, SkillPathsConfig, UpdateConfig,
 };
 use crate::core::skill::{BlockType, SkillBlock, SkillMetadata, SkillSection, SkillSpec};
 use crate::security::{AcipConfig, TrustBoundaryConfig, TrustLevel};
@@ -72,10 +73,20 @@ fn arb_skill_metadata() -> impl Strategy<Value = SkillMetadata> {
                 tags,
                 requires,
                 provides,
+                recommends: Vec::new(),
+                conflicts_with: Vec::new(),
                 platforms,
                 author,
                 license,
                 context: Default::default(),
+                active_during: None,
+                expires: None,
+                follow_ups: Vec::new(),
+                imported_from: None,
+                forked_from: None,
+                sensitive: false,
+                share: false,
+                requirements: Default::default(),
             },
         )
 }
@@ -102,6 +113,7 @@ fn arb_skill_paths() -> impl Strategy<Value = SkillPathsConfig> {
             project,
             community,
             local,
+            ignore: BTreeMap::new(),
         },
     )
 }
@@ -117,12 +129,24 @@ fn arb_layers() -> impl Strategy<Value = LayersConfig> {
         prop::collection::vec(layer, 0..4),
         any::<bool>(),
         any::<bool>(),
+        any::<bool>(),
+        any::<bool>(),
     )
-        .prop_map(|(priority, auto_detect, project_overrides)| LayersConfig {
-            priority,
-            auto_detect,
-            project_overrides,
-        })
+        .prop_map(
+            |(priority, auto_detect, project_overrides, community_read_only, bundle_read_only)| {
+                LayersConfig {
+                    priority,
+                    auto_detect,
+                    project_overrides,
+                    community: LayerPolicy {
+                        read_only: community_read_only,
+                    },
+                    bundle: LayerPolicy {
+                        read_only: bundle_read_only,
+                    },
+                }
+            },
+        )
 }
 
 fn arb_disclosure() -> impl Strategy<Value = DisclosureConfig> {
@@ -162,6 +186,12 @@ fn arb_search() -> impl Strategy<Value = SearchConfig> {
                     api_endpoint: "https://api.openai.com/v1/embeddings".to_string(),
                     api_model: "text-embedding-3-small".to_string(),
                     api_key_env: "OPENAI_API_KEY".to_string(),
+                    embeddings: Default::default(),
+                    rrf: Default::default(),
+                    batch: Default::default(),
+                    context: Default::default(),
+                    compaction: Default::default(),
+                    boosts: Default::default(),
                 }
             },
         )
@@ -172,12 +202,16 @@ fn arb_cass() -> impl Strategy<Value = CassConfig> {
         any::<bool>(),
         prop::option::of("[a-zA-Z0-9_./-]{1,24}"),
         prop_oneof![Just("*.jsonl".to_string()), Just("*.ndjson".to_string())],
+        0u64..3_600u64,
     )
-        .prop_map(|(auto_detect, cass_path, session_pattern)| CassConfig {
-            auto_detect,
-            cass_path,
-            session_pattern,
-        })
+        .prop_map(
+            |(auto_detect, cass_path, session_pattern, cache_ttl_secs)| CassConfig {
+                auto_detect,
+                cass_path,
+                session_pattern,
+                cache_ttl_secs,
+            },
+        )
 }
 
 fn arb_cache() -> impl Strategy<Value = CacheConfig> {
@@ -216,14 +250,25 @@ fn arb_agent_mail() -> impl Strategy<Value = AgentMailConfig> {
         "[a-z0-9_-]{1,20}",
         "[a-z0-9_-]{1,20}",
         1u64..60u64,
+        0u32..600u32,
     )
         .prop_map(
-            |(enabled, endpoint, project_key, agent_name, timeout_secs)| AgentMailConfig {
+            |(
                 enabled,
                 endpoint,
                 project_key,
                 agent_name,
                 timeout_secs,
+                max_requests_per_minute,
+            )| {
+                AgentMailConfig {
+                    enabled,
+                    endpoint,
+                    project_key,
+                    agent_name,
+                    timeout_secs,
+                    max_requests_per_minute,
+                }
             },
         )
 }
@@ -264,7 +309,10 @@ fn arb_acip() -> impl Strategy<Value = AcipConfig> {
 }
 
 fn arb_security() -> impl Strategy<Value = SecurityConfig> {
-    arb_acip().prop_map(|acip| SecurityConfig { acip })
+    arb_acip().prop_map(|acip| SecurityConfig {
+        acip,
+        ..Default::default()
+    })
 }
 
 fn arb_safety() -> impl Strategy<Value = SafetyConfig> {
@@ -273,6 +321,7 @@ fn arb_safety() -> impl Strategy<Value = SafetyConfig> {
         dcg_packs: vec![],
         dcg_explain_format: String::new(),
         require_verbatim_approval,
+        profiles: BTreeMap::new(),
     })
 }
 
@@ -321,6 +370,21 @@ pub fn arb_config() -> impl Strategy<Value = Config> {
                     safety,
                     auto_load: crate::config::AutoLoadConfig::default(),
                     output: crate::config::OutputConfig::default(),
+                    budget: crate::config::BudgetConfig::default(),
+                    context_window: crate::config::ContextWindowConfig::default(),
+                    nudges: crate::config::NudgesConfig::default(),
+                    links: crate::config::LinksConfig::default(),
+                    efficacy: crate::config::EfficacyConfig::default(),
+                    quality: crate::config::QualityConfig::default(),
+                    suggestions: crate::config::SuggestionsConfig::default(),
+                    applicability: crate::config::ApplicabilityConfig::default(),
+                    bundles: crate::config::BundlesConfig::default(),
+                    requirements: crate::config::RequirementsConfig::default(),
+                    packing: crate::config::PackingConfig::default(),
+                    enrichment: crate::config::EnrichmentConfig::default(),
+                    review: crate::config::ReviewConfig::default(),
+                    backup: crate::config::BackupConfig::default(),
+                    build: crate::config::BuildConfig::default(),
                 }
             },
         )
@@ -0,0 +1,366 @@
+//! Block coverage of skill tests (`ms test --coverage`).
+//!
+//! Rules and pitfalls count as covered when a test lists them in `covers:`
+//! or runs a command their inline code names. Code and command examples count
+//! as covered when listed, when a test runs one of their command lines, or
+//! when they pass as doctests. Command matching is fuzzy: prompts, comments
+//! and whitespace are normalized away, and a match may be a prefix of the
+//! other command as long as it is at least two words long.
+
+use serde::{Deserialize, Serialize};
+
+use super::definition::{TestDefinition, TestStep};
+use crate::core::skill::{BlockType, SkillBlock, SkillSpec};
+
+/// What a coverable block is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CoverageKind {
+    /// Rule or pitfall.
+    Rule,
+    /// Code or command example.
+    Example,
+}
+
+/// How a test reached a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CoverageVia {
+    /// Listed in the test's `covers:`.
+    Covers,
+    /// The test runs a command the block contains.
+    Command,
+    /// The block passed as a doctest.
+    Doctest,
+}
+
+/// A test exercising a block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CoveringTest {
+    pub test: String,
+    pub via: CoverageVia,
+}
+
+/// Coverage of one rule or example block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockCoverage {
+    pub block_id: String,
+    pub section: String,
+    pub kind: CoverageKind,
+    pub block_type: BlockType,
+    /// Tests exercising the block; empty when uncovered.
+    pub covered_by: Vec<CoveringTest>,
+}
+
+impl BlockCoverage {
+    #[must_use]
+    pub fn is_covered(&self) -> bool {
+        !self.covered_by.is_empty()
+    }
+}
+
+/// Per-block coverage of one skill.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillCoverage {
+    pub blocks: Vec<BlockCoverage>,
+    pub covered: usize,
+    pub total: usize,
+    /// Covered blocks as a percentage; 100 when there is nothing to cover.
+    pub percent: f64,
+}
+
+impl SkillCoverage {
+    /// Maps `tests` (the ones that ran) and passing doctest blocks onto the
+    /// rule and example blocks of `skill`.
+    #[must_use]
+    pub fn compute(
+        skill: &SkillSpec,
+        tests: &[&TestDefinition],
+        doctest_blocks: &[String],
+    ) -> Self {
+        let commands: Vec<(&str, Vec<String>)> = tests
+            .iter()
+            .map(|test| (test.name.as_str(), test_commands(test)))
+            .collect();
+
+        let mut blocks = Vec::new();
+        for section in &skill.sections {
+            for block in &section.blocks {
+                let Some(kind) = coverage_kind(&block.block_type) else {
+                    continue;
+                };
+                let mut covered_by = Vec::new();
+                for test in tests {
+                    if test.covers.iter().any(|id| *id == block.id) {
+                        covered_by.push(CoveringTest {
+                            test: test.name.clone(),
+                            via: CoverageVia::Covers,
+                        });
+                    }
+                }
+                let candidates = block_commands(block);
+                for (test, run) in &commands {
+                    let already = covered_by.iter().any(|c| c.test == *test);
+                    if !already
+                        && run
+                            .iter()
+                            .any(|cmd| candidates.iter().any(|c| commands_match(cmd, c)))
+                    {
+                        covered_by.push(CoveringTest {
+                            test: (*test).to_string(),
+                            via: CoverageVia::Command,
+                        });
+                    }
+                }
+                if doctest_blocks.contains(&block.id) {
+                    covered_by.push(CoveringTest {
+                        test: format!("doctest:{}", block.id),
+                        via: CoverageVia::Doctest,
+                    });
+                }
+                blocks.push(BlockCoverage {
+                    block_id: block.id.clone(),
+                    section: section.id.clone(),
+                    kind,
+                    block_type: block.block_type.clone(),
+                    covered_by,
+                });
+            }
+        }
+
+        let covered = blocks.iter().filter(|b| b.is_covered()).count();
+        let total = blocks.len();
+        Self {
+            blocks,
+            covered,
+            total,
+            percent: percent(covered, total),
+        }
+    }
+
+    /// Rule blocks no test covers.
+    pub fn uncovered_rules(&self) -> impl Iterator<Item = &BlockCoverage> {
+        self.uncovered(CoverageKind::Rule)
+    }
+
+    /// Example blocks no test executed.
+    pub fn unexecuted_examples(&self) -> impl Iterator<Item = &BlockCoverage> {
+        self.uncovered(CoverageKind::Example)
+    }
+
+    fn uncovered(&self, kind: CoverageKind) -> impl Iterator<Item = &BlockCoverage> {
+        self.blocks
+            .iter()
+            .filter(move |b| b.kind == kind && !b.is_covered())
+    }
+}
+
+/// Aggregate coverage over several skills.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CoverageSummary {
+    pub covered: usize,
+    pub total: usize,
+    pub percent: f64,
+}
+
+impl CoverageSummary {
+    #[must_use]
+    pub fn aggregate<'a>(skills: impl IntoIterator<Item = &'a SkillCoverage>) -> Self {
+        let (covered, total) = skills
+            .into_iter()
+            .fold((0, 0), |(c, t), s| (c + s.covered, t + s.total));
+        Self {
+            covered,
+            total,
+            percent: percent(covered, total),
+        }
+    }
+}
+
+fn percent(covered: usize, total: usize) -> f64 {
+    if total == 0 {
+        100.0
+    } else {
+        covered as f64 * 100.0 / total as f64
+    }
+}
+
+const fn coverage_kind(block_type: &BlockType) -> Option<CoverageKind> {
+    match block_type {
+        BlockType::Rule | BlockType::Pitfall => Some(CoverageKind::Rule),
+        BlockType::Code | BlockType::Command => Some(CoverageKind::Example),
+        BlockType::Text | BlockType::Checklist => None,
+    }
+}
+
+/// Normalized command lines of every shell step in `test`.
+fn test_commands(test: &TestDefinition) -> Vec<String> {
+    test.all_steps()
+        .into_iter()
+        .filter_map(|step| match step {
+            TestStep::Run { run } => Some(run.cmd.as_str()),
+            TestStep::RunCommand { run_command } => Some(run_command.command.as_str()),
+            _ => None,
+        })
+        .flat_map(|cmd| {
+            cmd.lines()
+                .filter_map(normalize_command)
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Normalized commands a block shows: its code lines for examples, its
+/// inline code spans for rules.
+fn block_commands(block: &SkillBlock) -> Vec<String> {
+    match block.block_type {
+        BlockType::Code | BlockType::Command => block
+            .content
+            .lines()
+            .filter(|line| {
+                let line = line.trim_start();
+                !line.starts_with("```") && !line.starts_with("~~~")
+            })
+            .filter_map(normalize_command)
+            .collect(),
+        _ => block
+            .content
+            .split('`')
+            .skip(1)
+            .step_by(2)
+            .filter_map(normalize_command)
+            .collect(),
+    }
+}
+
+/// Strips shell prompts, comments and redundant whitespace; `None` for lines
+/// holding no command.
+fn normalize_command(line: &str) -> Option<String> {
+    let line = line.trim();
+    let line = line
+        .strip_prefix("$ ")
+        .or_else(|| line.strip_prefix("> "))
+        .unwrap_or(line);
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let words: Vec<&str> = line.split_whitespace().collect();
+    Some(words.join(" "))
+}
+
+/// Whether two normalized commands are the same invocation, allowing one to
+/// be a word-aligned prefix of the other when that prefix has two words.
+fn commands_match(a: &str, b: &str) -> bool {
+    if a == b {
+        return true;
+    }
+    let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    short.split(' ').count() >= 2 && long.starts_with(short) && long[short.len()..].starts_with(' ')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::skill::SkillSection;
+
+    fn block(id: &str, block_type: BlockType, content: &str) -> SkillBlock {
+        SkillBlock {
+            id: id.to_string(),
+            block_type,
+            content: content.to_string(),
+        }
+    }
+
+    fn skill() -> SkillSpec {
+        let mut skill = SkillSpec::new("release", "Release");
+        skill.sections.push(SkillSection {
+            id: "steps".to_string(),
+            title: "Steps".to_string(),
+            blocks: vec![
+                block("intro", BlockType::Text, "Cut a release."),
+                block(
+                    "rule-fmt",
+                    BlockType::Rule,
+                    "Always run `cargo fmt --check` first",
+                ),
+                block("rule-tag", BlockType::Rule, "Tag every release"),
+                block(
+                    "example-build",
+                    BlockType::Code,
+                    "```bash\n# build it\n$ cargo build   --release\n```",
+                ),
+                block(
+                    "example-publish",
+                    BlockType::Code,
+                    "```bash\ncargo publish\n```",
+                ),
+                block("example-doc", BlockType::Code, "```bash\necho ok\n```"),
+            ],
+        });
+        skill
+    }
+
+    #[test]
+    fn maps_tests_onto_rules_and_examples() {
+        let test = TestDefinition::from_yaml(
+            r#"
+name: build
+covers: [rule-tag]
+steps:
+  - run:
+      cmd: "cargo fmt --check && true"
+  - run_command:
+      command: "cargo build --release --locked"
+"#,
+        )
+        .unwrap();
+        let coverage = SkillCoverage::compute(&skill(), &[&test], &["example-doc".to_string()]);
+
+        // Text blocks are not coverable
+        assert_eq!(coverage.total, 5);
+        assert_eq!(coverage.covered, 4);
+        assert!((coverage.percent - 80.0).abs() < f64::EPSILON);
+
+        let by_id = |id: &str| coverage.blocks.iter().find(|b| b.block_id == id).unwrap();
+        assert_eq!(by_id("rule-tag").covered_by[0].via, CoverageVia::Covers);
+        assert_eq!(
+            by_id("example-build").covered_by[0].via,
+            CoverageVia::Command
+        );
+        assert_eq!(
+            by_id("example-doc").covered_by[0].test,
+            "doctest:example-doc"
+        );
+        // The rule's inline `cargo fmt --check` starts the test's command
+        assert_eq!(by_id("rule-fmt").covered_by[0].via, CoverageVia::Command);
+        assert_eq!(coverage.uncovered_rules().count(), 0);
+        let examples: Vec<_> = coverage
+            .unexecuted_examples()
+            .map(|b| &b.block_id)
+            .collect();
+        assert_eq!(examples, ["example-publish"]);
+    }
+
+    #[test]
+    fn fuzzy_matching_needs_word_aligned_two_word_prefixes() {
+        assert!(commands_match("cargo test", "cargo test --all"));
+        assert!(!commands_match("cargo", "cargo test"));
+        assert!(!commands_match("cargo te", "cargo test"));
+        assert_eq!(
+            normalize_command("  $ ls   -la "),
+            Some("ls -la".to_string())
+        );
+        assert_eq!(normalize_command("# comment"), None);
+    }
+
+    #[test]
+    fn aggregate_weights_by_block() {
+        let empty = SkillCoverage::compute(&SkillSpec::new("empty", "Empty"), &[], &[]);
+        assert!((empty.percent - 100.0).abs() < f64::EPSILON);
+
+        let partial = SkillCoverage::compute(&skill(), &[], &["example-doc".to_string()]);
+        let summary = CoverageSummary::aggregate([&empty, &partial]);
+        assert_eq!((summary.covered, summary.total), (1, 5));
+        assert!((summary.percent - 20.0).abs() < f64::EPSILON);
+    }
+}
@@ -6,6 +6,7 @@ use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 
+use crate::core::skill::SkillSpec;
 use crate::error::{MsError, Result};
 
 /// A complete test definition (YAML spec)
@@ -50,6 +51,15 @@ pub struct TestDefinition {
     /// System requirements
     #[serde(default)]
     pub requires: Option<Vec<Requirement>>,
+
+    /// Let `run_command` steps run commands the safety gate blocks
+    /// (also requires `ms test --allow-destructive`)
+    #[serde(default)]
+    pub allow_destructive: bool,
+
+    /// Block IDs of the skill this test exercises (for `ms test --coverage`)
+    #[serde(default)]
+    pub covers: Vec<String>,
 }
 
 /// Alias for backward compatibility
@@ -71,6 +81,9 @@ pub enum TestStep {
     /// Run a shell command
     Run { run: RunStep },
 
+    /// Run a command through the safety gate and assert on its result
+    RunCommand { run_command: RunCommandStep },
+
     /// Assert conditions
     Assert { assert: AssertStep },
 
@@ -140,6 +153,43 @@ pub struct RunStep {
     pub timeout: Option<Duration>,
 }
 
+/// Run a command step with inline assertions
+///
+/// ```yaml
+/// - run_command:
+///     command: "cargo --version"
+///     exit_code: 0
+///     stdout_matches: "^cargo \\d+"
+///     timeout: 10s
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunCommandStep {
+    /// Command to run (via `sh -c`)
+    pub command: String,
+
+    /// Expected exit code
+    #[serde(default)]
+    pub exit_code: i32,
+
+    /// Regex stdout must match
+    pub stdout_matches: Option<String>,
+
+    /// Regex stderr must match
+    pub stderr_matches: Option<String>,
+
+    /// Working directory
+    pub cwd: Option<String>,
+
+    /// Environment variables
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+
+    /// Command timeout; the whole process group is killed when it expires
+    #[serde(default)]
+    #[serde(with = "humantime_serde")]
+    pub timeout: Option<Duration>,
+}
+
 /// Assert conditions step
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AssertStep {
@@ -386,6 +436,45 @@ impl TestSpec {
     pub fn has_tag(&self, tag: &str) -> bool {
         self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
     }
+
+    /// `covers` entries naming no block of `skill`
+    #[must_use]
+    pub fn unknown_covers(&self, skill: &SkillSpec) -> Vec<String> {
+        self.covers
+            .iter()
+            .filter(|id| {
+                !skill
+                    .sections
+                    .iter()
+                    .flat_map(|section| &section.blocks)
+                    .any(|block| &block.id == *id)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Every step the test runs: setup, steps, cleanup, and both branches of
+    /// conditionals
+    #[must_use]
+    pub fn all_steps(&self) -> Vec<&TestStep> {
+        fn walk<'a>(steps: &'a [TestStep], out: &mut Vec<&'a TestStep>) {
+            for step in steps {
+                out.push(step);
+                if let TestStep::If { if_step } = step {
+                    walk(&if_step.then_steps, out);
+                    if let Some(else_steps) = &if_step.else_steps {
+                        walk(else_steps, out);
+                    }
+                }
+            }
+        }
+
+        let mut out = Vec::new();
+        walk(self.setup.as_deref().unwrap_or_default(), &mut out);
+        walk(&self.steps, &mut out);
+        walk(self.cleanup.as_deref().unwrap_or_default(), &mut out);
+        out
+    }
 }
 
 #[cfg(test)]
@@ -514,6 +603,38 @@ steps:
         assert!(spec.tags.is_empty());
         assert!(spec.skip_if.is_none());
         assert!(spec.requires.is_none());
+        assert!(spec.covers.is_empty());
+    }
+
+    #[test]
+    fn parse_covers_and_reject_unknown_ids() {
+        let yaml = r#"
+name: test
+covers: [rule-1, no-such-block]
+steps:
+  - if:
+      condition:
+        platform: linux
+      then:
+        - run:
+            cmd: "cargo test"
+"#;
+        let spec = TestSpec::from_yaml(yaml).unwrap();
+        assert_eq!(spec.covers, ["rule-1", "no-such-block"]);
+        // The nested `run` is reachable for command matching
+        assert_eq!(spec.all_steps().len(), 2);
+
+        let mut skill = SkillSpec::new("test-skill", "Test Skill");
+        skill.sections.push(crate::core::skill::SkillSection {
+            id: "rules".to_string(),
+            title: "Rules".to_string(),
+            blocks: vec![crate::core::skill::SkillBlock {
+                id: "rule-1".to_string(),
+                block_type: crate::core::skill::BlockType::Rule,
+                content: "Always run the tests".to_string(),
+            }],
+        });
+        assert_eq!(spec.unknown_covers(&skill), ["no-such-block"]);
     }
 
     #[test]
@@ -829,4 +950,37 @@ steps:
             _ => panic!("expected If step"),
         }
     }
+
+    #[test]
+    fn parse_run_command_step() {
+        let yaml = r#"
+name: recipe
+allow_destructive: true
+steps:
+  - run_command:
+      command: "cargo --version"
+      stdout_matches: "^cargo \\d+"
+      cwd: /tmp
+      timeout: 5s
+"#;
+        let spec = TestSpec::from_yaml(yaml).unwrap();
+        assert!(spec.allow_destructive);
+        match &spec.steps[0] {
+            TestStep::RunCommand { run_command } => {
+                assert_eq!(run_command.command, "cargo --version");
+                assert_eq!(run_command.exit_code, 0);
+                assert_eq!(run_command.stdout_matches.as_deref(), Some("^cargo \\d+"));
+                assert!(run_command.stderr_matches.is_none());
+                assert_eq!(run_command.cwd.as_deref(), Some("/tmp"));
+                assert_eq!(run_command.timeout, Some(Duration::from_secs(5)));
+            }
+            _ => panic!("expected RunCommand step"),
+        }
+    }
+
+    #[test]
+    fn allow_destructive_defaults_off() {
+        let spec = TestSpec::from_yaml(SAMPLE_TEST).unwrap();
+        assert!(!spec.allow_destructive);
+    }
 }
@@ -0,0 +1,402 @@
+//! Runnable doctests embedded in skill examples.
+//!
+//! Authors opt in per example by marking a fenced block as a doctest, either
+//! in the fence info string (```` ```bash doctest ````) or with a
+//! `<!-- ms:doctest -->` comment directly above the block. The next fenced
+//! block tagged `output` (or `expected`) holds the expected standard output;
+//! stderr is not compared but is shown, labeled, when a doctest fails. Expected
+//! lines may mask volatile tokens with inline placeholders: `<...>` matches
+//! anything, and `<uuid>`, `<timestamp>`, `<number>`, `<hex>` match those
+//! shapes. Unannotated examples are never executed.
+
+use std::time::Duration;
+
+use regex::Regex;
+use serde::Serialize;
+
+use crate::core::skill::{BlockType, SkillBlock, SkillSpec};
+use crate::error::{MsError, Result};
+use crate::security::SafetyGate;
+
+use super::definition::{RunStep, TestStep};
+use super::steps::{TestContext, execute_step};
+
+/// Fence info token that marks a block as a doctest.
+pub const DOCTEST_MARKER: &str = "doctest";
+
+/// Attribute comment that marks the following block as a doctest.
+pub const DOCTEST_ATTRIBUTE: &str = "<!-- ms:doctest -->";
+
+/// Fence languages that hold expected output.
+pub const EXPECTED_OUTPUT_LANGS: &[&str] = &["output", "expected"];
+
+/// Languages executed through the shell.
+const SHELL_LANGS: &[&str] = &["bash", "sh", "shell", "zsh", "console"];
+
+/// Default per-doctest timeout.
+pub const DEFAULT_DOCTEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// An annotated example extracted from a skill.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Doctest {
+    /// Block id of the example (used for coverage mapping).
+    pub block_id: String,
+    pub section: String,
+    pub language: String,
+    pub code: String,
+    /// Expected output, or `None` when the expected-output block is missing.
+    pub expected: Option<String>,
+}
+
+/// First line where actual output diverges from the expected output.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Divergence {
+    /// 1-based line number in the normalized output.
+    pub line: usize,
+    pub expected: Option<String>,
+    pub actual: Option<String>,
+}
+
+impl std::fmt::Display for Divergence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "output diverges at line {}: expected {}, got {}",
+            self.line,
+            self.expected
+                .as_deref()
+                .map_or_else(|| "<end of output>".to_string(), |l| format!("`{l}`")),
+            self.actual
+                .as_deref()
+                .map_or_else(|| "<end of output>".to_string(), |l| format!("`{l}`")),
+        )
+    }
+}
+
+/// Outcome of running a single doctest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DoctestOutcome {
+    Passed,
+    Failed(String),
+    Skipped(String),
+}
+
+/// Split a fenced block into its info string and body.
+fn fence_parts(content: &str) -> Option<(&str, String)> {
+    let mut lines = content.lines();
+    let first = lines.next()?.trim_start();
+    let info = first.strip_prefix("```")?.trim();
+    let mut body: Vec<&str> = lines.collect();
+    if body
+        .last()
+        .is_some_and(|line| line.trim_start().starts_with("```"))
+    {
+        body.pop();
+    }
+    Some((info, body.join("\n")))
+}
+
+fn is_code(block: &SkillBlock) -> bool {
+    block.block_type == BlockType::Code && block.content.trim_start().starts_with("```")
+}
+
+/// Extract every annotated doctest from a skill, in document order.
+#[must_use]
+pub fn extract_doctests(spec: &SkillSpec) -> Vec<Doctest> {
+    let mut doctests = Vec::new();
+    for section in &spec.sections {
+        let blocks = &section.blocks;
+        for (idx, block) in blocks.iter().enumerate() {
+            if !is_code(block) {
+                continue;
+            }
+            let Some((info, code)) = fence_parts(&block.content) else {
+                continue;
+            };
+            let mut tokens = info.split_whitespace();
+            let language = tokens.next().unwrap_or_default().to_lowercase();
+            let fence_marked = tokens.any(|t| t == DOCTEST_MARKER);
+            let comment_marked = idx > 0
+                && blocks[idx - 1].block_type != BlockType::Code
+                && blocks[idx - 1]
+                    .content
+                    .trim_end()
+                    .ends_with(DOCTEST_ATTRIBUTE);
+            if !fence_marked && !comment_marked {
+                continue;
+            }
+
+            let expected = blocks
+                .get(idx + 1)
+                .filter(|next| is_code(next))
+                .and_then(|next| fence_parts(&next.content))
+                .filter(|(info, _)| {
+                    let lang = info.split_whitespace().next().unwrap_or_default();
+                    EXPECTED_OUTPUT_LANGS.contains(&lang)
+                })
+                .map(|(_, body)| body);
+
+            doctests.push(Doctest {
+                block_id: block.id.clone(),
+                section: section.id.clone(),
+                language,
+                code,
+                expected,
+            });
+        }
+    }
+    doctests
+}
+
+/// Normalize output for comparison: trailing whitespace is dropped, runs of
+/// whitespace collapse to one space, and leading/trailing blank lines go.
+#[must_use]
+pub fn normalize_output(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = text
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .collect();
+    while lines.first().is_some_and(String::is_empty) {
+        lines.remove(0);
+    }
+    while lines.last().is_some_and(String::is_empty) {
+        lines.pop();
+    }
+    lines
+}
+
+/// Build a matcher for one expected line, expanding inline placeholders.
+fn line_pattern(expected: &str) -> Regex {
+    const PLACEHOLDERS: &[(&str, &str)] = &[
+        ("<...>", ".*"),
+        (
+            "<uuid>",
+            "[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}",
+        ),
+        (
+            "<timestamp>",
+            r"\d{4}-\d{2}-\d{2}[T ]\d{2}:\d{2}:\d{2}(?:\.\d+)?(?:Z|[+-]\d{2}:?\d{2})?",
+        ),
+        ("<number>", r"-?\d+(?:\.\d+)?"),
+        ("<hex>", "[0-9a-fA-F]+"),
+    ];
+
+    let mut pattern = String::from("^");
+    let mut rest = expected;
+    'outer: while !rest.is_empty() {
+        for (placeholder, regex) in PLACEHOLDERS {
+            if let Some(tail) = rest.strip_prefix(placeholder) {
+                pattern.push_str(regex);
+                rest = tail;
+                continue 'outer;
+            }
+        }
+        let ch = rest.chars().next().unwrap_or_default();
+        pattern.push_str(&regex::escape(&ch.to_string()));
+        rest = &rest[ch.len_utf8()..];
+    }
+    pattern.push('$');
+    Regex::new(&pattern).expect("doctest pattern escapes every literal")
+}
+
+/// Compare actual output to expected output, returning the first divergence.
+#[must_use]
+pub fn compare_output(expected: &str, actual: &str) -> Option<Divergence> {
+    let expected = normalize_output(expected);
+    let actual = normalize_output(actual);
+    let len = expected.len().max(actual.len());
+    for idx in 0..len {
+        let exp = expected.get(idx);
+        let act = actual.get(idx);
+        let matches = match (exp, act) {
+            (Some(e), Some(a)) => line_pattern(e).is_match(a),
+            _ => false,
+        };
+        if !matches {
+            return Some(Divergence {
+                line: idx + 1,
+                expected: exp.cloned(),
+                actual: act.cloned(),
+            });
+        }
+    }
+    None
+}
+
+/// Strip `$ ` prompts so console-style transcripts run as plain commands.
+fn command_text(code: &str) -> String {
+    code.lines()
+        .map(|line| line.strip_prefix("$ ").unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Run one doctest in a fresh temporary workspace.
+///
+/// Commands the safety gate refuses are skipped and reported rather than
+/// failed, so destructive examples never run.
+pub fn run_doctest(
+    doctest: &Doctest,
+    gate: Option<&SafetyGate>,
+    timeout: Duration,
+) -> Result<DoctestOutcome> {
+    if !SHELL_LANGS.contains(&doctest.language.as_str()) {
+        return Ok(DoctestOutcome::Skipped(format!(
+            "unsupported doctest language '{}'",
+            doctest.language
+        )));
+    }
+    let Some(expected) = doctest.expected.as_deref() else {
+        return Ok(DoctestOutcome::Skipped(
+            "no expected-output block follows the example".to_string(),
+        ));
+    };
+
+    let cmd = command_text(&doctest.code);
+    if let Some(gate) = gate {
+        if let Err(err) = gate.enforce(&cmd, None) {
+            return Ok(DoctestOutcome::Skipped(format!(
+                "blocked by safety gate: {err}"
+            )));
+        }
+    }
+
+    let workspace = tempfile::tempdir()
+        .map_err(|err| MsError::Config(format!("create doctest workspace: {err}")))?;
+    let step = TestStep::Run {
+        run: RunStep {
+            cmd,
+            cwd: Some(workspace.path().display().to_string()),
+            env: std::collections::HashMap::new(),
+            stdin: None,
+            timeout: Some(timeout),
+        },
+    };
+    let mut test_ctx = TestContext::default();
+    if let Err(err) = execute_step(&step, &mut test_ctx, false, None, None) {
+        return Ok(DoctestOutcome::Failed(err.to_string()));
+    }
+
+    Ok(match compare_output(expected, &test_ctx.last_stdout) {
+        None => DoctestOutcome::Passed,
+        Some(divergence) => {
+            DoctestOutcome::Failed(failure_message(&divergence, &test_ctx.last_stderr))
+        }
+    })
+}
+
+/// Describe a stdout divergence, with any stderr appended under its own label.
+fn failure_message(divergence: &Divergence, stderr: &str) -> String {
+    let stderr = stderr.trim_end();
+    if stderr.is_empty() {
+        divergence.to_string()
+    } else {
+        format!("{divergence}\nstderr:\n{stderr}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::spec_lens::parse_markdown;
+
+    const SKILL: &str = "# Demo\n\nDemo skill.\n\n## Examples\n\n```bash doctest\necho hello\n```\n\n```output\nhello\n```\n\n<!-- ms:doctest -->\n\n```sh\necho missing\n```\n\n```bash\necho not annotated\n```\n";
+
+    #[test]
+    fn extracts_only_annotated_examples() {
+        let spec = parse_markdown(SKILL).unwrap();
+        let doctests = extract_doctests(&spec);
+        assert_eq!(doctests.len(), 2);
+        assert_eq!(doctests[0].code, "echo hello");
+        assert_eq!(doctests[0].expected.as_deref(), Some("hello"));
+        assert_eq!(doctests[1].language, "sh");
+        assert!(doctests[1].expected.is_none());
+    }
+
+    #[test]
+    fn normalization_ignores_whitespace_noise() {
+        assert!(compare_output("a  b\n\n", "\n a b   \n").is_none());
+    }
+
+    #[test]
+    fn placeholders_mask_volatile_tokens() {
+        let expected = "id=<uuid> at <timestamp>\ncount: <number> (<...>)";
+        let actual =
+            "id=550e8400-e29b-41d4-a716-446655440000 at 2025-01-02T03:04:05Z\ncount: 42 (took 3ms)";
+        assert!(compare_output(expected, actual).is_none());
+        assert!(compare_output("id=<uuid>", "id=not-a-uuid").is_some());
+    }
+
+    #[test]
+    fn divergence_pinpoints_first_mismatched_line() {
+        let divergence = compare_output("one\ntwo\nthree", "one\n2\nthree").unwrap();
+        assert_eq!(divergence.line, 2);
+        assert_eq!(divergence.expected.as_deref(), Some("two"));
+        assert_eq!(divergence.actual.as_deref(), Some("2"));
+        assert!(divergence.to_string().contains("line 2"));
+
+        let short = compare_output("one\ntwo", "one").unwrap();
+        assert_eq!(short.line, 2);
+        assert!(short.actual.is_none());
+    }
+
+    #[test]
+    fn missing_expected_block_is_skipped() {
+        let doctest = Doctest {
+            block_id: "b".to_string(),
+            section: "s".to_string(),
+            language: "bash".to_string(),
+            code: "echo hi".to_string(),
+            expected: None,
+        };
+        let outcome = run_doctest(&doctest, None, DEFAULT_DOCTEST_TIMEOUT).unwrap();
+        assert!(matches!(outcome, DoctestOutcome::Skipped(_)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn runs_shell_doctest_in_temp_workspace() {
+        let mut doctest = Doctest {
+            block_id: "b".to_string(),
+            section: "s".to_string(),
+            language: "bash".to_string(),
+            code: "$ touch marker && ls".to_string(),
+            expected: Some("marker".to_string()),
+        };
+        assert_eq!(
+            run_doctest(&doctest, None, DEFAULT_DOCTEST_TIMEOUT).unwrap(),
+            DoctestOutcome::Passed
+        );
+
+        doctest.expected = Some("other".to_string());
+        match run_doctest(&doctest, None, DEFAULT_DOCTEST_TIMEOUT).unwrap() {
+            DoctestOutcome::Failed(msg) => assert!(msg.contains("line 1")),
+            other => panic!("unexpected outcome: {other:?}"),
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn stderr_is_kept_out_of_compared_output() {
+        let mut doctest = Doctest {
+            block_id: "b".to_string(),
+            section: "s".to_string(),
+            language: "bash".to_string(),
+            code: "printf out; echo warning >&2".to_string(),
+            expected: Some("out".to_string()),
+        };
+        assert_eq!(
+            run_doctest(&doctest, None, DEFAULT_DOCTEST_TIMEOUT).unwrap(),
+            DoctestOutcome::Passed
+        );
+
+        doctest.expected = Some("other".to_string());
+        match run_doctest(&doctest, None, DEFAULT_DOCTEST_TIMEOUT).unwrap() {
+            DoctestOutcome::Failed(msg) => {
+                assert!(msg.contains("got `out`"));
+                assert!(msg.ends_with("\nstderr:\nwarning"));
+            }
+            other => panic!("unexpected outcome: {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,346 @@
+//! File beads issues for failing skill tests (`ms test --file-issues`).
+//!
+//! Each failing test maps to one issue, found again on later runs by a
+//! fingerprint label derived from the skill id and test name only, so the
+//! same failure with different output updates the existing issue instead of
+//! filing a duplicate.
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::beads::{
+    BeadsOperations, CreateIssueRequest, Issue, IssueType, UpdateIssueRequest, WorkFilter,
+};
+use crate::error::Result;
+
+use super::{SkillTestReport, TestResult, TestStatus};
+
+/// Label carried by every issue this module files.
+pub const TEST_ISSUE_LABEL: &str = "ms-test";
+
+/// Bytes of failure output kept in an issue description.
+const MAX_OUTPUT_BYTES: usize = 4000;
+
+/// What filing did (or would do) for one test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueActionKind {
+    /// Filed a new issue for a failing test.
+    Create,
+    /// Refreshed the open issue of a test that still fails.
+    Update,
+    /// Noted on the open issue that the test passes again.
+    Comment,
+    /// Closed the open issue of a test that passes again.
+    Close,
+}
+
+/// One issue action, for reporting.
+#[derive(Debug, Clone, Serialize)]
+pub struct IssueAction {
+    pub kind: IssueActionKind,
+    pub skill_id: String,
+    pub test_name: String,
+    pub fingerprint: String,
+    /// Beads issue id; `None` for dry-run creations.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue_id: Option<String>,
+    pub title: String,
+}
+
+/// Stable fingerprint of a test: a hash of the skill id and test name.
+#[must_use]
+pub fn fingerprint(skill_id: &str, test_name: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(skill_id.as_bytes());
+    hasher.update([0]);
+    hasher.update(test_name.as_bytes());
+    hex::encode(hasher.finalize())[..16].to_string()
+}
+
+/// Label that identifies the issue of one test.
+#[must_use]
+pub fn fingerprint_label(fingerprint: &str) -> String {
+    format!("{TEST_ISSUE_LABEL}-{fingerprint}")
+}
+
+/// The issue to file for a failing test.
+#[must_use]
+pub fn failing_issue_request(skill_id: &str, result: &TestResult) -> CreateIssueRequest {
+    CreateIssueRequest::new(issue_title(skill_id, &result.name))
+        .with_type(IssueType::Bug)
+        .with_priority(2)
+        .with_description(issue_description(skill_id, result))
+        .with_label(TEST_ISSUE_LABEL)
+        .with_label(fingerprint_label(&fingerprint(skill_id, &result.name)))
+}
+
+/// The issues `--file-issues` would create, without calling bd.
+#[must_use]
+pub fn plan_issues(reports: &[SkillTestReport]) -> Vec<(IssueAction, CreateIssueRequest)> {
+    failing_results(reports)
+        .map(|(skill_id, result)| {
+            let request = failing_issue_request(skill_id, result);
+            let action = IssueAction {
+                kind: IssueActionKind::Create,
+                skill_id: skill_id.to_string(),
+                test_name: result.name.clone(),
+                fingerprint: fingerprint(skill_id, &result.name),
+                issue_id: None,
+                title: request.title.clone(),
+            };
+            (action, request)
+        })
+        .collect()
+}
+
+/// Create or update an issue for every failing test, and comment on or
+/// (with `close_fixed`) close the open issue of every test that passes again.
+pub fn file_issues<B: BeadsOperations + ?Sized>(
+    beads: &B,
+    reports: &[SkillTestReport],
+    close_fixed: bool,
+) -> Result<Vec<IssueAction>> {
+    let mut actions = Vec::new();
+    for report in reports {
+        for result in &report.results {
+            let skill_id = report.skill_id.as_str();
+            let fingerprint = fingerprint(skill_id, &result.name);
+            let existing = open_issue(beads, &fingerprint)?;
+            let (kind, issue) = match (is_failing(result), existing) {
+                (true, None) => (
+                    IssueActionKind::Create,
+                    beads.create(&failing_issue_request(skill_id, result))?,
+                ),
+                (true, Some(issue)) => {
+                    let update = UpdateIssueRequest::new()
+                        .with_description(issue_description(skill_id, result))
+                        .with_notes(format!("Still failing as of {}", now()));
+                    (IssueActionKind::Update, beads.update(&issue.id, &update)?)
+                }
+                (false, Some(issue)) if result.status == TestStatus::Passed => {
+                    let note = format!("Passing again as of {}", now());
+                    if close_fixed {
+                        (IssueActionKind::Close, beads.close(&issue.id, Some(&note))?)
+                    } else {
+                        let update = UpdateIssueRequest::new().with_notes(note);
+                        (IssueActionKind::Comment, beads.update(&issue.id, &update)?)
+                    }
+                }
+                _ => continue,
+            };
+            actions.push(IssueAction {
+                kind,
+                skill_id: skill_id.to_string(),
+                test_name: result.name.clone(),
+                fingerprint,
+                issue_id: Some(issue.id),
+                title: issue.title,
+            });
+        }
+    }
+    Ok(actions)
+}
+
+fn failing_results(reports: &[SkillTestReport]) -> impl Iterator<Item = (&str, &TestResult)> {
+    reports.iter().flat_map(|report| {
+        report
+            .results
+            .iter()
+            .filter(|result| is_failing(result))
+            .map(|result| (report.skill_id.as_str(), result))
+    })
+}
+
+fn is_failing(result: &TestResult) -> bool {
+    matches!(result.status, TestStatus::Failed | TestStatus::Timeout)
+}
+
+fn open_issue<B: BeadsOperations + ?Sized>(beads: &B, fingerprint: &str) -> Result<Option<Issue>> {
+    let filter = WorkFilter {
+        labels: vec![fingerprint_label(fingerprint)],
+        ..WorkFilter::default()
+    };
+    Ok(beads
+        .list(&filter)?
+        .into_iter()
+        .find(|issue| !issue.status.is_terminal()))
+}
+
+fn issue_title(skill_id: &str, test_name: &str) -> String {
+    format!("ms test failing: {skill_id} / {test_name}")
+}
+
+fn issue_description(skill_id: &str, result: &TestResult) -> String {
+    let mut output = String::new();
+    for failure in &result.failures {
+        output.push_str(failure);
+        output.push('\n');
+    }
+    for capture in result
+        .commands
+        .iter()
+        .filter(|capture| capture.timed_out || capture.exit_code != Some(0))
+    {
+        output.push_str(&format!(
+            "$ {} (exit {})\n",
+            capture.command,
+            capture
+                .exit_code
+                .map_or_else(|| "none".to_string(), |code| code.to_string())
+        ));
+        for stream in [&capture.stdout, &capture.stderr] {
+            if !stream.trim().is_empty() {
+                output.push_str(stream.trim_end());
+                output.push('\n');
+            }
+        }
+    }
+    if output.len() > MAX_OUTPUT_BYTES {
+        let mut cut = MAX_OUTPUT_BYTES;
+        while !output.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        output.truncate(cut);
+        output.push_str("\n... (truncated)\n");
+    }
+
+    format!(
+        "Skill: {skill_id}\nTest: {}\nStatus: {:?}\nFingerprint: {}\n\n\
+         Failure output:\n```\n{}```\n\nReproduce:\n```bash\nms test {skill_id} --test '{}'\n```",
+        result.name,
+        result.status,
+        fingerprint(skill_id, &result.name),
+        output,
+        result.name.replace('\'', r"'\''"),
+    )
+}
+
+fn now() -> String {
+    chrono::Utc::now().format("%Y-%m-%d %H:%M UTC").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::beads::{IssueStatus, MockBeadsClient};
+
+    fn result(name: &str, status: TestStatus, failure: &str) -> TestResult {
+        TestResult {
+            name: name.to_string(),
+            status,
+            duration_ms: 5,
+            failures: if failure.is_empty() {
+                Vec::new()
+            } else {
+                vec![failure.to_string()]
+            },
+            commands: Vec::new(),
+        }
+    }
+
+    fn report(skill_id: &str, results: Vec<TestResult>) -> SkillTestReport {
+        SkillTestReport {
+            skill_id: skill_id.to_string(),
+            tests_run: results.len(),
+            passed: results
+                .iter()
+                .filter(|r| r.status == TestStatus::Passed)
+                .count(),
+            failed: results.iter().filter(|r| is_failing(r)).count(),
+            skipped: 0,
+            duration_ms: 10,
+            results,
+            covered_blocks: Vec::new(),
+            coverage: None,
+        }
+    }
+
+    #[test]
+    fn fingerprint_ignores_output() {
+        assert_eq!(fingerprint("git", "commit"), fingerprint("git", "commit"));
+        assert_ne!(fingerprint("git", "commit"), fingerprint("git", "push"));
+        assert_ne!(fingerprint("gi", "tcommit"), fingerprint("git", "commit"));
+    }
+
+    #[test]
+    fn reruns_update_instead_of_duplicating() {
+        let beads = MockBeadsClient::new();
+        let first = [report(
+            "git",
+            vec![result("commit", TestStatus::Failed, "exit code 1")],
+        )];
+        let actions = file_issues(&beads, &first, false).unwrap();
+        assert_eq!(actions[0].kind, IssueActionKind::Create);
+        let issue = beads.show(actions[0].issue_id.as_deref().unwrap()).unwrap();
+        assert!(issue.description.contains("exit code 1"));
+        assert!(issue.description.contains("ms test git --test 'commit'"));
+
+        let second = [report(
+            "git",
+            vec![result("commit", TestStatus::Timeout, "timed out after 30s")],
+        )];
+        let actions = file_issues(&beads, &second, false).unwrap();
+        assert_eq!(actions[0].kind, IssueActionKind::Update);
+        assert_eq!(actions[0].issue_id, Some(issue.id.clone()));
+        assert_eq!(beads.list(&WorkFilter::default()).unwrap().len(), 1);
+        let issue = beads.show(&issue.id).unwrap();
+        assert!(issue.description.contains("timed out after 30s"));
+    }
+
+    #[test]
+    fn fixed_tests_are_commented_or_closed() {
+        let beads = MockBeadsClient::new();
+        let failing = [report(
+            "git",
+            vec![
+                result("commit", TestStatus::Failed, "boom"),
+                result("push", TestStatus::Failed, "boom"),
+            ],
+        )];
+        file_issues(&beads, &failing, false).unwrap();
+
+        let passing = [report(
+            "git",
+            vec![
+                result("commit", TestStatus::Passed, ""),
+                result("push", TestStatus::Skipped, ""),
+            ],
+        )];
+        let actions = file_issues(&beads, &passing, false).unwrap();
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].kind, IssueActionKind::Comment);
+        let issue = beads.show(actions[0].issue_id.as_deref().unwrap()).unwrap();
+        assert_eq!(issue.status, IssueStatus::Open);
+
+        let actions = file_issues(&beads, &passing, true).unwrap();
+        assert_eq!(actions[0].kind, IssueActionKind::Close);
+        let issue = beads.show(actions[0].issue_id.as_deref().unwrap()).unwrap();
+        assert_eq!(issue.status, IssueStatus::Closed);
+
+        // Closed issues are not reused: a new failure files a fresh one.
+        let actions = file_issues(&beads, &failing[..], false).unwrap();
+        assert_eq!(actions[0].kind, IssueActionKind::Create);
+        assert_ne!(actions[0].issue_id, Some(issue.id));
+    }
+
+    #[test]
+    fn plan_lists_failures_only() {
+        let reports = [report(
+            "git",
+            vec![
+                result("commit", TestStatus::Failed, "boom"),
+                result("push", TestStatus::Passed, ""),
+            ],
+        )];
+        let plan = plan_issues(&reports);
+        assert_eq!(plan.len(), 1);
+        assert_eq!(plan[0].0.test_name, "commit");
+        assert!(
+            plan[0]
+                .1
+                .labels
+                .contains(&fingerprint_label(&plan[0].0.fingerprint))
+        );
+    }
+}
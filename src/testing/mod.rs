@@ -3,7 +3,10 @@
 //! Provides infrastructure for running tests defined within skills and
 //! validating skill behavior.
 
+pub mod coverage;
 mod definition;
+pub mod doctest;
+pub mod issues;
 mod runner;
 mod steps;
 
@@ -7,10 +7,13 @@ use std::time::{Duration, Instant};
 
 use serde::{Deserialize, Serialize};
 
+use super::coverage::SkillCoverage;
 use super::definition::{Requirement, SkipCondition, TestDefinition};
-use super::steps::StepExecutor;
+use super::doctest::{DEFAULT_DOCTEST_TIMEOUT, DoctestOutcome, extract_doctests, run_doctest};
+use super::steps::{CommandCapture, StepExecutor};
 use crate::app::AppContext;
 use crate::error::{MsError, Result};
+use crate::security::SafetyGate;
 
 /// Options for controlling test execution.
 #[derive(Debug, Clone, Default)]
@@ -32,8 +35,24 @@ pub struct TestOptions {
 
     /// Override default test timeout.
     pub timeout_override: Option<Duration>,
+
+    /// Also run annotated doctest examples from the skill body.
+    pub doctests: bool,
+
+    /// Run gate-blocked `run_command` steps in tests marked `allow_destructive`.
+    pub allow_destructive: bool,
+
+    /// Max bytes of stdout/stderr kept per command in reports
+    /// (default: [`DEFAULT_OUTPUT_LIMIT`]).
+    pub output_limit: Option<usize>,
+
+    /// Map the tests that ran onto the skill's rule and example blocks.
+    pub coverage: bool,
 }
 
+/// Default cap on captured command output per stream in test reports.
+pub const DEFAULT_OUTPUT_LIMIT: usize = 4096;
+
 /// Status of a test execution.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -63,6 +82,10 @@ pub struct TestResult {
     /// Failure messages (if any).
     #[serde(default)]
     pub failures: Vec<String>,
+
+    /// Output of `run_command` steps, truncated to the report limit.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub commands: Vec<CommandCapture>,
 }
 
 /// Report for all tests run against a skill.
@@ -88,6 +111,14 @@ pub struct SkillTestReport {
 
     /// Individual test results.
     pub results: Vec<TestResult>,
+
+    /// Block ids exercised by passing doctests (coverage mapping).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub covered_blocks: Vec<String>,
+
+    /// Per-block coverage (with `--coverage`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coverage: Option<SkillCoverage>,
 }
 
 impl SkillTestReport {
@@ -132,6 +163,8 @@ mod tests {
             skipped: 0,
             duration_ms: 100,
             results: vec![],
+            covered_blocks: vec![],
+            coverage: None,
         };
         assert!(report.success());
     }
@@ -146,6 +179,8 @@ mod tests {
             skipped: 0,
             duration_ms: 200,
             results: vec![],
+            covered_blocks: vec![],
+            coverage: None,
         };
         assert!(!report.success());
     }
@@ -160,6 +195,8 @@ mod tests {
             skipped: 2,
             duration_ms: 0,
             results: vec![],
+            covered_blocks: vec![],
+            coverage: None,
         };
         assert!(report.success());
     }
@@ -171,6 +208,7 @@ mod tests {
             status: TestStatus::Failed,
             duration_ms: 42,
             failures: vec!["exit_code mismatch".to_string()],
+            commands: Vec::new(),
         };
         let json = serde_json::to_string(&result).unwrap();
         let parsed: TestResult = serde_json::from_str(&json).unwrap();
@@ -189,6 +227,34 @@ mod tests {
         assert!(opts.include_tags.is_empty());
         assert!(opts.exclude_tags.is_empty());
         assert!(opts.timeout_override.is_none());
+        assert!(!opts.doctests);
+        assert!(!opts.allow_destructive);
+        assert!(opts.output_limit.is_none());
+        assert!(!opts.coverage);
+    }
+
+    #[test]
+    fn test_result_omits_empty_commands() {
+        let mut result = TestResult {
+            name: "basic".to_string(),
+            status: TestStatus::Passed,
+            duration_ms: 1,
+            failures: vec![],
+            commands: vec![],
+        };
+        let json = serde_json::to_string(&result).unwrap();
+        assert!(!json.contains("commands"));
+
+        result.commands.push(CommandCapture {
+            command: "echo hi".to_string(),
+            exit_code: Some(0),
+            stdout: "hi\n".to_string(),
+            stderr: String::new(),
+            timed_out: false,
+            truncated: false,
+        });
+        let json = serde_json::to_string(&result).unwrap();
+        assert!(json.contains("\"command\":\"echo hi\""));
     }
 
     #[test]
@@ -205,7 +271,10 @@ mod tests {
                 status: TestStatus::Passed,
                 duration_ms: 50,
                 failures: vec![],
+                commands: Vec::new(),
             }],
+            covered_blocks: vec![],
+            coverage: None,
         };
         let json = serde_json::to_string(&report).unwrap();
         assert!(json.contains("\"skill_id\":\"my-skill\""));
@@ -228,7 +297,11 @@ impl<'a> SkillTestRunner<'a> {
 
     /// Run tests for all skills.
     pub fn run_all(&self) -> Result<Vec<SkillTestReport>> {
-        let skills = self.discover_skills_with_tests()?;
+        let skills = if self.options.doctests {
+            self.ctx.git.list_skill_ids()?
+        } else {
+            self.discover_skills_with_tests()?
+        };
         let mut reports = Vec::new();
 
         for skill_id in skills {
@@ -248,13 +321,20 @@ impl<'a> SkillTestRunner<'a> {
     pub fn run_for_skill(&self, skill_id: &str) -> Result<SkillTestReport> {
         let start = Instant::now();
         let tests = self.discover_tests(skill_id)?;
+        // `covers:` ids are checked against the skill even without --coverage
+        let spec = if self.options.coverage || tests.iter().any(|t| !t.covers.is_empty()) {
+            Some(self.ctx.git.read_skill(skill_id)?)
+        } else {
+            None
+        };
 
         let mut results = Vec::new();
+        let mut ran = Vec::new();
         let mut passed = 0;
         let mut failed = 0;
         let mut skipped = 0;
 
-        for test in tests {
+        for test in &tests {
             // Filter by test name if specified
             if let Some(ref name) = self.options.test_name {
                 if test.name != *name {
@@ -263,43 +343,65 @@ impl<'a> SkillTestRunner<'a> {
             }
 
             // Filter by tags
-            if !self.should_run_test(&test) {
+            if !self.should_run_test(test) {
                 skipped += 1;
                 results.push(TestResult {
                     name: test.name.clone(),
                     status: TestStatus::Skipped,
                     duration_ms: 0,
                     failures: vec!["Skipped by tag filter".to_string()],
+                    commands: Vec::new(),
                 });
                 continue;
             }
 
             // Check skip conditions
-            if self.should_skip(&test) {
+            if self.should_skip(test) {
                 skipped += 1;
                 results.push(TestResult {
                     name: test.name.clone(),
                     status: TestStatus::Skipped,
                     duration_ms: 0,
                     failures: vec!["Skipped by skip_if condition".to_string()],
+                    commands: Vec::new(),
                 });
                 continue;
             }
 
             // Check requirements
-            if let Some(missing) = self.check_requirements(&test) {
+            if let Some(missing) = self.check_requirements(test) {
                 skipped += 1;
                 results.push(TestResult {
                     name: test.name.clone(),
                     status: TestStatus::Skipped,
                     duration_ms: 0,
                     failures: vec![format!("Missing requirement: {missing}")],
+                    commands: Vec::new(),
                 });
                 continue;
             }
 
+            if let Some(spec) = &spec {
+                let unknown = test.unknown_covers(spec);
+                if !unknown.is_empty() {
+                    failed += 1;
+                    results.push(TestResult {
+                        name: test.name.clone(),
+                        status: TestStatus::Failed,
+                        duration_ms: 0,
+                        failures: vec![format!("covers unknown block(s): {}", unknown.join(", "))],
+                        commands: Vec::new(),
+                    });
+                    if self.options.fail_fast {
+                        break;
+                    }
+                    continue;
+                }
+            }
+
             // Run the test
-            let result = self.run_test(&test)?;
+            let result = self.run_test(test)?;
+            ran.push(test);
 
             match result.status {
                 TestStatus::Passed => passed += 1,
@@ -316,6 +418,29 @@ impl<'a> SkillTestRunner<'a> {
             }
         }
 
+        let mut covered_blocks = Vec::new();
+        let stop_early = self.options.fail_fast && failed > 0;
+        if self.options.doctests && !stop_early {
+            for result in self.run_doctests(skill_id, &mut covered_blocks)? {
+                match result.status {
+                    TestStatus::Passed => passed += 1,
+                    TestStatus::Failed | TestStatus::Timeout => failed += 1,
+                    TestStatus::Skipped => skipped += 1,
+                }
+                let test_failed = result.status == TestStatus::Failed;
+                results.push(result);
+                if self.options.fail_fast && test_failed {
+                    break;
+                }
+            }
+        }
+
+        let coverage = if self.options.coverage {
+            spec.as_ref()
+                .map(|spec| SkillCoverage::compute(spec, &ran, &covered_blocks))
+        } else {
+            None
+        };
         let duration = start.elapsed();
 
         Ok(SkillTestReport {
@@ -326,9 +451,58 @@ impl<'a> SkillTestRunner<'a> {
             skipped,
             duration_ms: duration.as_millis() as u64,
             results,
+            covered_blocks,
+            coverage,
         })
     }
 
+    /// Run the annotated doctest examples in a skill's body.
+    ///
+    /// Results are named `doctest:<block-id>`; passing doctests record their
+    /// block in `covered`.
+    fn run_doctests(&self, skill_id: &str, covered: &mut Vec<String>) -> Result<Vec<TestResult>> {
+        let spec = self.ctx.git.read_skill(skill_id)?;
+        let doctests = extract_doctests(&spec);
+        if doctests.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let gate = SafetyGate::from_context(self.ctx);
+        let timeout = self
+            .options
+            .timeout_override
+            .unwrap_or(DEFAULT_DOCTEST_TIMEOUT);
+        let mut results = Vec::new();
+        for doctest in doctests {
+            let name = format!("doctest:{}", doctest.block_id);
+            if let Some(ref wanted) = self.options.test_name {
+                if name != *wanted {
+                    continue;
+                }
+            }
+            if self.options.verbose {
+                eprintln!("[TEST] Running: {name}");
+            }
+            let start = Instant::now();
+            let (status, failures) = match run_doctest(&doctest, Some(&gate), timeout)? {
+                DoctestOutcome::Passed => {
+                    covered.push(doctest.block_id.clone());
+                    (TestStatus::Passed, Vec::new())
+                }
+                DoctestOutcome::Failed(reason) => (TestStatus::Failed, vec![reason]),
+                DoctestOutcome::Skipped(reason) => (TestStatus::Skipped, vec![reason]),
+            };
+            results.push(TestResult {
+                name,
+                status,
+                duration_ms: start.elapsed().as_millis() as u64,
+                failures,
+                commands: Vec::new(),
+            });
+        }
+        Ok(results)
+    }
+
     /// Discover all skills that have tests.
     fn discover_skills_with_tests(&self) -> Result<Vec<String>> {
         let mut skills = Vec::new();
@@ -526,7 +700,9 @@ impl<'a> SkillTestRunner<'a> {
             eprintln!("[TEST] Running: {}", test.name);
         }
 
-        let mut executor = StepExecutor::new(self.ctx, self.options.verbose);
+        let allow_destructive = test.allow_destructive && self.options.allow_destructive;
+        let mut executor = StepExecutor::new(self.ctx, self.options.verbose)
+            .with_command_safety(SafetyGate::from_context(self.ctx), allow_destructive);
         let mut failures = Vec::new();
 
         // Run setup steps
@@ -578,11 +754,20 @@ impl<'a> SkillTestRunner<'a> {
             TestStatus::Failed
         };
 
+        let limit = self.options.output_limit.unwrap_or(DEFAULT_OUTPUT_LIMIT);
+        let commands = executor
+            .test_context()
+            .commands
+            .iter()
+            .map(|capture| capture.truncated(limit))
+            .collect();
+
         Ok(TestResult {
             name: test.name.clone(),
             status,
             duration_ms: duration.as_millis() as u64,
             failures,
+            commands,
         })
     }
 }
@@ -5,16 +5,19 @@
 use std::collections::HashMap;
 use std::io::{Read, Write};
 use std::path::Path;
-use std::process::{Command, Stdio};
+use std::process::{Child, Command, Stdio};
 use std::time::{Duration, Instant};
 
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
 use crate::app::AppContext;
 use crate::error::{MsError, Result};
 use crate::security::SafetyGate;
 
 use super::definition::{
-    Assertions, Condition, CopyStep, IfStep, LoadSkillStep, MkdirStep, RemoveStep, RunStep,
-    SetStep, SleepStep, TestStep, WriteFileStep,
+    Assertions, Condition, CopyStep, IfStep, LoadSkillStep, MkdirStep, RemoveStep, RunCommandStep,
+    RunStep, SetStep, SleepStep, TestStep, WriteFileStep,
 };
 
 /// Step executor that manages test context and executes steps
@@ -24,6 +27,7 @@ pub struct StepExecutor<'a> {
     test_ctx: TestContext,
     verbose: bool,
     safety: Option<SafetyGate>,
+    command_safety: Option<SafetyGate>,
 }
 
 impl<'a> StepExecutor<'a> {
@@ -35,6 +39,7 @@ impl<'a> StepExecutor<'a> {
             test_ctx: TestContext::default(),
             verbose,
             safety: None,
+            command_safety: None,
         }
     }
 
@@ -48,9 +53,26 @@ impl<'a> StepExecutor<'a> {
         self
     }
 
+    /// Gate `run_command` steps through DCG.
+    ///
+    /// Commands the gate blocks still run when `allow_destructive` is set
+    /// (the test opted in and the user passed `--allow-destructive`).
+    #[must_use]
+    pub fn with_command_safety(mut self, gate: SafetyGate, allow_destructive: bool) -> Self {
+        self.command_safety = Some(gate);
+        self.test_ctx.allow_destructive = allow_destructive;
+        self
+    }
+
     /// Execute a single test step
     pub fn execute(&mut self, step: &TestStep) -> Result<()> {
-        execute_step(step, &mut self.test_ctx, self.verbose, self.safety.as_ref())
+        execute_step(
+            step,
+            &mut self.test_ctx,
+            self.verbose,
+            self.safety.as_ref(),
+            self.command_safety.as_ref(),
+        )
     }
 
     /// Get a reference to the test context
@@ -77,6 +99,57 @@ pub struct TestContext {
     pub tokens_used: usize,
     /// Retrieval rank (if applicable)
     pub retrieval_rank: Option<usize>,
+    /// Run `run_command` steps even when the safety gate blocks them
+    pub allow_destructive: bool,
+    /// Output captured from `run_command` steps
+    pub commands: Vec<CommandCapture>,
+}
+
+/// Captured result of a `run_command` step
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandCapture {
+    /// Command as executed (variables expanded)
+    pub command: String,
+    /// Exit code (None if killed by a signal)
+    pub exit_code: Option<i32>,
+    /// Captured stdout
+    pub stdout: String,
+    /// Captured stderr
+    pub stderr: String,
+    /// Whether the command hit its timeout
+    #[serde(default)]
+    pub timed_out: bool,
+    /// Whether stdout or stderr was cut to the report limit
+    #[serde(default)]
+    pub truncated: bool,
+}
+
+impl CommandCapture {
+    /// Copy with stdout and stderr cut to at most `limit` bytes each.
+    #[must_use]
+    pub fn truncated(&self, limit: usize) -> Self {
+        let (stdout, cut_stdout) = truncate_output(&self.stdout, limit);
+        let (stderr, cut_stderr) = truncate_output(&self.stderr, limit);
+        Self {
+            command: self.command.clone(),
+            exit_code: self.exit_code,
+            stdout,
+            stderr,
+            timed_out: self.timed_out,
+            truncated: self.truncated || cut_stdout || cut_stderr,
+        }
+    }
+}
+
+fn truncate_output(text: &str, limit: usize) -> (String, bool) {
+    if text.len() <= limit {
+        return (text.to_string(), false);
+    }
+    let mut end = limit;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    (text[..end].to_string(), true)
 }
 
 /// Info about a loaded skill
@@ -101,15 +174,21 @@ impl TestContext {
 }
 
 /// Execute a single test step
+///
+/// `safety` gates `run` steps and `command_safety` gates `run_command` steps.
 pub fn execute_step(
     step: &TestStep,
     ctx: &mut TestContext,
     verbose: bool,
     safety: Option<&SafetyGate>,
+    command_safety: Option<&SafetyGate>,
 ) -> Result<()> {
     match step {
         TestStep::LoadSkill { load_skill } => execute_load_skill(load_skill, ctx, verbose),
         TestStep::Run { run } => execute_run(run, ctx, verbose, safety),
+        TestStep::RunCommand { run_command } => {
+            execute_run_command(run_command, ctx, verbose, command_safety)
+        }
         TestStep::Assert { assert } => execute_assert(assert, ctx, verbose),
         TestStep::WriteFile { write_file } => execute_write_file(write_file, ctx, verbose),
         TestStep::Mkdir { mkdir } => execute_mkdir(mkdir, ctx, verbose),
@@ -117,7 +196,7 @@ pub fn execute_step(
         TestStep::Copy { copy } => execute_copy(copy, ctx, verbose),
         TestStep::Sleep { sleep } => execute_sleep(sleep, ctx, verbose),
         TestStep::Set { set } => execute_set(set, ctx, verbose),
-        TestStep::If { if_step } => execute_if(if_step, ctx, verbose, safety),
+        TestStep::If { if_step } => execute_if(if_step, ctx, verbose, safety, command_safety),
     }
 }
 
@@ -278,6 +357,198 @@ fn execute_run(
     Ok(())
 }
 
+fn execute_run_command(
+    step: &RunCommandStep,
+    ctx: &mut TestContext,
+    verbose: bool,
+    safety: Option<&SafetyGate>,
+) -> Result<()> {
+    let cmd = ctx.expand(&step.command);
+    let cwd = step.cwd.as_ref().map(|c| ctx.expand(c));
+
+    if verbose {
+        println!("[STEP] run_command: {cmd}");
+        if let Some(ref dir) = cwd {
+            println!("[STEP]   cwd: {dir}");
+        }
+    }
+
+    if let Some(gate) = safety {
+        match gate.enforce(&cmd, None) {
+            Ok(()) => {}
+            Err(MsError::DestructiveBlocked(_) | MsError::ApprovalRequired(_))
+                if ctx.allow_destructive =>
+            {
+                warn!("run_command: running gate-blocked command (--allow-destructive): {cmd}");
+            }
+            Err(err) => return Err(err),
+        }
+    }
+
+    let shell = if cfg!(windows) { "cmd" } else { "sh" };
+    let shell_arg = if cfg!(windows) { "/C" } else { "-c" };
+
+    let mut command = Command::new(shell);
+    command.arg(shell_arg).arg(&cmd);
+
+    if let Some(ref dir) = cwd {
+        command.current_dir(dir);
+    }
+
+    for (key, value) in &step.env {
+        command.env(key, ctx.expand(value));
+    }
+
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    // Own process group, so a timeout takes down everything the command started
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+
+    let timeout = step.timeout.unwrap_or(Duration::from_secs(30));
+
+    let mut child = command
+        .spawn()
+        .map_err(|err| MsError::Config(format!("failed to execute command '{cmd}': {err}")))?;
+
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| MsError::Config(format!("failed to capture stdout for '{cmd}'")))?;
+    let stderr = child
+        .stderr
+        .take()
+        .ok_or_else(|| MsError::Config(format!("failed to capture stderr for '{cmd}'")))?;
+
+    let stdout_handle = std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        let mut reader = stdout;
+        reader.read_to_end(&mut buf).map(|_| buf)
+    });
+    let stderr_handle = std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        let mut reader = stderr;
+        reader.read_to_end(&mut buf).map(|_| buf)
+    });
+
+    let start = Instant::now();
+    let mut timed_out = false;
+    let exit_status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) => {
+                if start.elapsed() > timeout {
+                    timed_out = true;
+                    kill_process_group(&mut child);
+                    break child.wait().map_err(|err| {
+                        MsError::Config(format!("failed to wait for '{cmd}': {err}"))
+                    })?;
+                }
+                std::thread::sleep(Duration::from_millis(25));
+            }
+            Err(err) => {
+                return Err(MsError::Config(format!(
+                    "failed to wait for command '{cmd}': {err}"
+                )));
+            }
+        }
+    };
+
+    let stdout_bytes = stdout_handle
+        .join()
+        .map_err(|_| MsError::Config(format!("stdout capture panicked for '{cmd}'")))?
+        .map_err(|err| MsError::Config(format!("read stdout for '{cmd}': {err}")))?;
+    let stderr_bytes = stderr_handle
+        .join()
+        .map_err(|_| MsError::Config(format!("stderr capture panicked for '{cmd}'")))?
+        .map_err(|err| MsError::Config(format!("read stderr for '{cmd}': {err}")))?;
+
+    ctx.last_stdout = String::from_utf8_lossy(&stdout_bytes).to_string();
+    ctx.last_stderr = String::from_utf8_lossy(&stderr_bytes).to_string();
+    ctx.last_exit_code = exit_status.code();
+    ctx.commands.push(CommandCapture {
+        command: cmd.clone(),
+        exit_code: ctx.last_exit_code,
+        stdout: ctx.last_stdout.clone(),
+        stderr: ctx.last_stderr.clone(),
+        timed_out,
+        truncated: false,
+    });
+
+    if verbose {
+        if !ctx.last_stdout.is_empty() {
+            println!("[STEP]   stdout: {}", ctx.last_stdout.trim());
+        }
+        if !ctx.last_stderr.is_empty() {
+            println!("[STEP]   stderr: {}", ctx.last_stderr.trim());
+        }
+        println!("[STEP]   exit: {:?}", ctx.last_exit_code);
+    }
+
+    if timed_out {
+        return Err(MsError::ValidationFailed(format!(
+            "command timed out after {timeout:?}: {cmd}"
+        )));
+    }
+
+    let mut failures = Vec::new();
+    if ctx.last_exit_code != Some(step.exit_code) {
+        failures.push(format!(
+            "exit_code: expected {}, got {:?}",
+            step.exit_code, ctx.last_exit_code
+        ));
+    }
+    if let Some(ref pattern) = step.stdout_matches {
+        check_matches("stdout_matches", pattern, &ctx.last_stdout, &mut failures)?;
+    }
+    if let Some(ref pattern) = step.stderr_matches {
+        check_matches("stderr_matches", pattern, &ctx.last_stderr, &mut failures)?;
+    }
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        if verbose {
+            for f in &failures {
+                println!("[STEP]   FAIL: {f}");
+            }
+        }
+        Err(MsError::ValidationFailed(format!(
+            "{cmd}: {}",
+            failures.join("; ")
+        )))
+    }
+}
+
+/// Kill a child and every process in its group, so background jobs it
+/// started cannot hold the output pipes open.
+fn kill_process_group(child: &mut Child) {
+    // `kill` avoids the unsafe block libc::kill would need
+    #[cfg(unix)]
+    let _ = Command::new("kill")
+        .args(["-KILL", "--", &format!("-{}", child.id())])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+    let _ = child.kill();
+}
+
+fn check_matches(label: &str, pattern: &str, text: &str, failures: &mut Vec<String>) -> Result<()> {
+    let re = regex::Regex::new(pattern).map_err(|err| {
+        MsError::ValidationFailed(format!("{label}: invalid regex '{pattern}': {err}"))
+    })?;
+    if !re.is_match(text) {
+        failures.push(format!("{label}: /{pattern}/ did not match"));
+    }
+    Ok(())
+}
+
 fn execute_assert(step: &Assertions, ctx: &mut TestContext, verbose: bool) -> Result<()> {
     if verbose {
         println!("[STEP] assert");
@@ -530,6 +801,7 @@ fn execute_if(
     ctx: &mut TestContext,
     verbose: bool,
     safety: Option<&SafetyGate>,
+    command_safety: Option<&SafetyGate>,
 ) -> Result<()> {
     if verbose {
         println!("[STEP] if condition");
@@ -547,7 +819,7 @@ fn execute_if(
     };
 
     for s in steps_to_run {
-        execute_step(s, ctx, verbose, safety)?;
+        execute_step(s, ctx, verbose, safety, command_safety)?;
     }
 
     Ok(())
@@ -888,6 +1160,86 @@ mod tests {
         assert!(ctx.last_stdout.contains("test_value_42"));
     }
 
+    fn run_command_step(command: &str) -> RunCommandStep {
+        RunCommandStep {
+            command: command.to_string(),
+            exit_code: 0,
+            stdout_matches: None,
+            stderr_matches: None,
+            cwd: None,
+            env: HashMap::new(),
+            timeout: None,
+        }
+    }
+
+    #[test]
+    fn test_execute_run_command_regex_matchers() {
+        let mut ctx = TestContext::default();
+        let step = RunCommandStep {
+            stdout_matches: Some(r"^version \d+\.\d+".to_string()),
+            stderr_matches: Some("warn".to_string()),
+            ..run_command_step("echo 'version 1.2'; echo warning >&2")
+        };
+        execute_run_command(&step, &mut ctx, false, None).unwrap();
+        assert_eq!(ctx.commands.len(), 1);
+        assert_eq!(ctx.commands[0].exit_code, Some(0));
+
+        let step = RunCommandStep {
+            stdout_matches: Some("^nope$".to_string()),
+            ..run_command_step("echo hello")
+        };
+        let err = execute_run_command(&step, &mut ctx, false, None).unwrap_err();
+        assert!(err.to_string().contains("stdout_matches"));
+    }
+
+    #[test]
+    fn test_execute_run_command_exit_code() {
+        let mut ctx = TestContext::default();
+        let step = RunCommandStep {
+            exit_code: 3,
+            ..run_command_step("exit 3")
+        };
+        execute_run_command(&step, &mut ctx, false, None).unwrap();
+
+        let err =
+            execute_run_command(&run_command_step("exit 3"), &mut ctx, false, None).unwrap_err();
+        assert!(err.to_string().contains("exit_code: expected 0"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_execute_run_command_timeout_kills_process_group() {
+        let mut ctx = TestContext::default();
+        // The background sleep inherits stdout; killing only `sh` would
+        // leave it holding the pipe open for the full 30s.
+        let step = RunCommandStep {
+            timeout: Some(Duration::from_millis(200)),
+            ..run_command_step("sleep 30 & sleep 30")
+        };
+        let start = Instant::now();
+        let err = execute_run_command(&step, &mut ctx, false, None).unwrap_err();
+        assert!(err.to_string().contains("timed out"));
+        assert!(start.elapsed() < Duration::from_secs(10));
+        assert!(ctx.commands[0].timed_out);
+    }
+
+    #[test]
+    fn test_command_capture_truncated() {
+        let capture = CommandCapture {
+            command: "echo".to_string(),
+            exit_code: Some(0),
+            stdout: "héllo world".to_string(),
+            stderr: "ok".to_string(),
+            timed_out: false,
+            truncated: false,
+        };
+        let cut = capture.truncated(2);
+        assert_eq!(cut.stdout, "h");
+        assert_eq!(cut.stderr, "ok");
+        assert!(cut.truncated);
+        assert!(!capture.truncated(64).truncated);
+    }
+
     #[test]
     fn test_execute_assert_retrieval_rank() {
         let mut ctx = TestContext::default();
@@ -21,7 +21,7 @@ use ratatui::{
 };
 
 use crate::cass::brenner::{
-    BrennerConfig, BrennerWizard, MoveDecision, SelectedSession, WizardOutput, WizardState,
+    BrennerWizard, MoveDecision, SelectedSession, WizardOutput, WizardState,
 };
 use crate::cass::{CassClient, QualityScorer};
 use crate::error::Result;
@@ -118,6 +118,8 @@ impl BuildTui {
         quality_scorer: &QualityScorer,
     ) -> Result<WizardOutput> {
         loop {
+            self.wizard.score_session_results(client, quality_scorer);
+
             // Draw UI
             terminal.draw(|f| self.draw(f))?;
 
@@ -423,8 +425,15 @@ impl BuildTui {
             }
             KeyCode::Char('c') => {
                 // Manual checkpoint
-                self.last_checkpoint = Some(chrono::Utc::now().format("%H:%M:%S").to_string());
-                self.status_message = Some("Checkpoint saved".to_string());
+                self.status_message = Some(match self.wizard.save_checkpoint() {
+                    Ok(Some(_)) => {
+                        self.last_checkpoint =
+                            Some(chrono::Utc::now().format("%H:%M:%S").to_string());
+                        "Checkpoint saved".to_string()
+                    }
+                    Ok(None) => "Nothing to checkpoint until sessions are confirmed".to_string(),
+                    Err(e) => format!("Checkpoint failed: {e}"),
+                });
                 return Ok(());
             }
             _ => {}
@@ -706,13 +715,18 @@ impl BuildTui {
                 .enumerate()
                 .map(|(i, r)| {
                     let prefix = if selected.contains(&i) { "[x]" } else { "[ ]" };
+                    let score = self
+                        .wizard
+                        .session_quality(&r.session_id)
+                        .map_or(r.score, |q| q.score);
                     (
                         format!(
-                            "{} {}",
+                            "{} {} ({})",
                             prefix,
-                            r.snippet.as_deref().unwrap_or(&r.session_id)
+                            r.snippet.as_deref().unwrap_or(&r.session_id),
+                            self.wizard.quality_badge(&r.session_id)
                         ),
-                        r.score,
+                        score,
                     )
                 })
                 .collect(),
@@ -899,14 +913,12 @@ impl Drop for TerminalGuard {
     }
 }
 
-/// Run the build TUI.
+/// Run the build TUI on a new or resumed wizard.
 pub fn run_build_tui(
-    query: &str,
-    config: BrennerConfig,
+    wizard: BrennerWizard,
     client: &CassClient,
     quality_scorer: &QualityScorer,
 ) -> Result<WizardOutput> {
-    let wizard = BrennerWizard::new(query, config);
     let tui = BuildTui::new(wizard);
     tui.run(client, quality_scorer)
 }
@@ -914,7 +926,7 @@ pub fn run_build_tui(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::cass::{CognitiveMove, CognitiveMoveTag, MoveEvidence};
+    use crate::cass::{BrennerConfig, CognitiveMove, CognitiveMoveTag, MoveEvidence};
     use crossterm::event::{KeyCode, KeyModifiers};
 
     fn make_session_match(id: &str, score: f32) -> crate::cass::client::SessionMatch {
@@ -0,0 +1,169 @@
+//! Ignore rules for skill discovery.
+//!
+//! `ms index` skips paths under a skill root that are matched by, from
+//! highest to lowest precedence: the `[skill_paths.ignore]` globs configured
+//! for the root, a `.msignore` file at the root, the built-in defaults
+//! ([`DEFAULT_IGNORE_GLOBS`]), and `.gitignore` / `.ignore` files anywhere in
+//! the tree, where a deeper file overrides its parents and `.ignore` overrides
+//! `.gitignore`. Everything uses gitignore syntax, so `!pattern` re-includes.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use ignore::Match;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+
+use crate::error::{MsError, Result};
+
+/// Ignore file read from the root of each skill path.
+pub const MSIGNORE_FILE: &str = ".msignore";
+
+/// Per-directory ignore files, lowest precedence first.
+const DIR_IGNORE_FILES: &[&str] = &[".gitignore", ".ignore"];
+
+/// Editor and tool backups that are never skills.
+pub const DEFAULT_IGNORE_GLOBS: &[&str] = &["*.bak", "*~"];
+
+/// Ignore rules of one skill root.
+pub struct IgnoreRules {
+    root: PathBuf,
+    /// Defaults, `.msignore`, then the configured globs; later lines win
+    root_rules: Gitignore,
+    /// `.gitignore` and `.ignore` of each directory, loaded on first use
+    dir_rules: HashMap<PathBuf, Gitignore>,
+}
+
+impl IgnoreRules {
+    /// Rules for `root` with the configured `globs` on top.
+    pub fn new(root: &Path, globs: &[String]) -> Result<Self> {
+        let invalid = |err: ignore::Error| {
+            MsError::Config(format!("invalid ignore rule for {}: {err}", root.display()))
+        };
+        let mut builder = GitignoreBuilder::new(root);
+        for glob in DEFAULT_IGNORE_GLOBS {
+            builder.add_line(None, glob).map_err(invalid)?;
+        }
+        let msignore = root.join(MSIGNORE_FILE);
+        if msignore.is_file()
+            && let Some(err) = builder.add(&msignore)
+        {
+            return Err(invalid(err));
+        }
+        for glob in globs {
+            builder.add_line(None, glob).map_err(invalid)?;
+        }
+        Ok(Self {
+            root: root.to_path_buf(),
+            root_rules: builder.build().map_err(invalid)?,
+            dir_rules: HashMap::new(),
+        })
+    }
+
+    /// Whether the entry at `path` is ignored, assuming its parent directory
+    /// is not (as when pruning a walk). The root itself never is.
+    pub fn is_ignored(&mut self, path: &Path, is_dir: bool) -> bool {
+        if path == self.root || !path.starts_with(&self.root) {
+            return false;
+        }
+        match self.root_rules.matched(path, is_dir) {
+            Match::Ignore(_) => return true,
+            Match::Whitelist(_) => return false,
+            Match::None => {}
+        }
+        for dir in path.ancestors().skip(1) {
+            if !dir.starts_with(&self.root) {
+                break;
+            }
+            let rules = self
+                .dir_rules
+                .entry(dir.to_path_buf())
+                .or_insert_with(|| load_dir_rules(dir));
+            match rules.matched(path, is_dir) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => {}
+            }
+        }
+        false
+    }
+
+    /// Whether `path` or any directory between it and the root is ignored.
+    pub fn is_ignored_path(&mut self, path: &Path) -> bool {
+        let Ok(relative) = path.strip_prefix(&self.root) else {
+            return false;
+        };
+        let mut current = self.root.clone();
+        let mut components = relative.components().peekable();
+        while let Some(component) = components.next() {
+            current.push(component);
+            let is_dir = components.peek().is_some() || current.is_dir();
+            if self.is_ignored(&current, is_dir) {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+/// `.gitignore` and `.ignore` of `dir`; unreadable or invalid lines are
+/// dropped, as git does.
+fn load_dir_rules(dir: &Path) -> Gitignore {
+    let mut builder = GitignoreBuilder::new(dir);
+    for name in DIR_IGNORE_FILES {
+        let file = dir.join(name);
+        if file.is_file() {
+            let _ = builder.add(file);
+        }
+    }
+    builder.build().unwrap_or_else(|_| Gitignore::empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn touch(root: &Path, relative: &str) -> PathBuf {
+        let path = root.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "x").unwrap();
+        path
+    }
+
+    #[test]
+    fn gitignore_files_apply_to_their_subtree() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::write(root.join(".gitignore"), "vendor/\n").unwrap();
+        fs::create_dir_all(root.join("pkg")).unwrap();
+        fs::write(root.join("pkg/.ignore"), "drafts/\n").unwrap();
+        let mut rules = IgnoreRules::new(root, &[]).unwrap();
+
+        assert!(rules.is_ignored(&root.join("vendor"), true));
+        assert!(rules.is_ignored(&root.join("pkg/drafts"), true));
+        assert!(!rules.is_ignored(&root.join("drafts"), true));
+        assert!(!rules.is_ignored(root, true));
+        assert!(rules.is_ignored_path(&touch(root, "vendor/x/SKILL.md")));
+        assert!(!rules.is_ignored_path(&touch(root, "skills/x/SKILL.md")));
+    }
+
+    #[test]
+    fn configured_globs_and_msignore_override_ignore_files() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::write(root.join(".gitignore"), "generated/\n").unwrap();
+        fs::write(root.join(MSIGNORE_FILE), "scratch/\n").unwrap();
+        let mut rules =
+            IgnoreRules::new(root, &["archive/**".to_string(), "!generated/".to_string()]).unwrap();
+
+        assert!(rules.is_ignored(&root.join("scratch"), true));
+        assert!(rules.is_ignored(&root.join("archive/old"), true));
+        assert!(!rules.is_ignored(&root.join("generated"), true));
+        assert!(rules.is_ignored(&root.join("x/SKILL.md~"), false));
+        assert!(rules.is_ignored(&root.join("x/notes.bak"), false));
+        assert!(IgnoreRules::new(root, &["a/**/[".to_string()]).is_err());
+    }
+}
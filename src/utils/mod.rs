@@ -3,6 +3,8 @@
 pub mod format;
 pub mod fs;
 pub mod git;
+pub mod ignore_rules;
+pub mod path;
 
 // Re-exports for convenience
 pub use format::*;
@@ -0,0 +1,188 @@
+//! Expansion of user-configured paths.
+//!
+//! Paths in config files and on the command line may start with `~` (or
+//! `~/`, `~\`) and may reference environment variables Windows-style
+//! (`%USERPROFILE%\skills`). Both are expanded the same way on every
+//! platform; on Windows forward slashes are also turned into backslashes so
+//! `C:/Users/me/skills` compares equal to what the OS reports.
+
+use std::path::{Path, PathBuf};
+
+/// Expand `~` and `%VAR%` in `input` using the current home directory and
+/// environment.
+#[must_use]
+pub fn expand_path(input: &str) -> PathBuf {
+    let home = dirs::home_dir();
+    let expanded = expand_path_with(input, home.as_deref(), |name| std::env::var(name).ok());
+    native_separators(expanded)
+}
+
+/// [`expand_path`] with an explicit home directory and variable lookup.
+///
+/// `%VAR%` references that do not resolve are left as written, as is `~`
+/// when there is no home directory.
+#[must_use]
+pub fn expand_path_with(
+    input: &str,
+    home: Option<&Path>,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> PathBuf {
+    let input = expand_env_vars(input, lookup);
+    if let Some(home) = home {
+        if input == "~" {
+            return home.to_path_buf();
+        }
+        if let Some(rest) = input
+            .strip_prefix("~/")
+            .or_else(|| input.strip_prefix("~\\"))
+        {
+            return home.join(rest);
+        }
+    }
+    PathBuf::from(input)
+}
+
+/// Whether `input` is written as a filesystem path rather than a name.
+#[must_use]
+pub fn looks_like_path(input: &str) -> bool {
+    input == "~"
+        || ["~/", "~\\", "./", ".\\", "../", "..\\", "/", "\\"]
+            .iter()
+            .any(|prefix| input.starts_with(prefix))
+        || has_drive_prefix(input)
+        || (input.starts_with('%') && input[1..].contains('%'))
+}
+
+/// `C:` at the start of `input`.
+#[must_use]
+pub fn has_drive_prefix(input: &str) -> bool {
+    let bytes = input.as_bytes();
+    bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':'
+}
+
+fn expand_env_vars(input: &str, lookup: impl Fn(&str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(start) = rest.find('%') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('%') else {
+            out.push_str(&rest[start..]);
+            return out;
+        };
+        let name = &after[..end];
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '(' || c == ')');
+        match lookup(name).filter(|_| valid) {
+            Some(value) => {
+                out.push_str(&value);
+                rest = &after[end + 1..];
+            }
+            // Keep the first `%` and retry from the second, which may open
+            // the next reference
+            None => {
+                out.push('%');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(windows)]
+fn native_separators(path: PathBuf) -> PathBuf {
+    PathBuf::from(path.to_string_lossy().replace('/', "\\"))
+}
+
+#[cfg(not(windows))]
+const fn native_separators(path: PathBuf) -> PathBuf {
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(name: &str) -> Option<String> {
+        match name {
+            "USERPROFILE" => Some(r"C:\Users\me".to_string()),
+            "ProgramFiles(x86)" => Some(r"C:\Program Files (x86)".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn expands_tilde_in_both_separator_styles() {
+        let home = Path::new("/home/me");
+        assert_eq!(expand_path_with("~", Some(home), vars), home);
+        assert_eq!(
+            expand_path_with("~/skills/a", Some(home), vars),
+            home.join("skills/a")
+        );
+        assert_eq!(
+            expand_path_with(r"~\skills", Some(home), vars),
+            home.join("skills")
+        );
+        // `~user` is not expanded, nor is `~` without a home
+        assert_eq!(
+            expand_path_with("~other/x", Some(home), vars),
+            PathBuf::from("~other/x")
+        );
+        assert_eq!(expand_path_with("~/x", None, vars), PathBuf::from("~/x"));
+    }
+
+    #[test]
+    fn expands_windows_style_variables() {
+        assert_eq!(
+            expand_path_with(r"%USERPROFILE%\skills", None, vars),
+            PathBuf::from(r"C:\Users\me\skills")
+        );
+        assert_eq!(
+            expand_path_with("%ProgramFiles(x86)%/ms", None, vars),
+            PathBuf::from(r"C:\Program Files (x86)/ms")
+        );
+        // Unknown variables and stray percent signs stay as written
+        assert_eq!(
+            expand_path_with(r"%NOPE%\x", None, vars),
+            PathBuf::from(r"%NOPE%\x")
+        );
+        assert_eq!(
+            expand_path_with("50%/%USERPROFILE%", None, vars),
+            PathBuf::from(r"50%/C:\Users\me")
+        );
+        assert_eq!(expand_path_with("100%", None, vars), PathBuf::from("100%"));
+    }
+
+    #[test]
+    fn recognizes_windows_style_paths() {
+        for path in [
+            "~",
+            r"~\skills",
+            r".\local",
+            r"..\up",
+            "C:/Users/me",
+            r"D:\skills",
+            r"\\server\share",
+            r"%USERPROFILE%\skills",
+            "/abs",
+            "./rel",
+        ] {
+            assert!(looks_like_path(path), "{path}");
+        }
+        for name in ["rust-kit", "org/bundle", "100%", "c"] {
+            assert!(!looks_like_path(name), "{name}");
+        }
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn forward_slash_drive_paths_use_native_separators() {
+        assert_eq!(
+            expand_path("C:/Users/me/skills").to_string_lossy(),
+            r"C:\Users\me\skills"
+        );
+    }
+}
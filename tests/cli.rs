@@ -27,6 +27,31 @@ fn test_cli_version() {
         .stdout(predicate::str::contains(env!("CARGO_PKG_VERSION")));
 }
 
+/// Completion runs before `ms init`: no database means no candidates and
+/// no error text that would end up in the user's command line.
+#[test]
+fn test_complete_without_database_is_silent() {
+    let dir = tempdir().unwrap();
+    let mut cmd = Command::cargo_bin("ms").unwrap();
+    cmd.env("MS_ROOT", dir.path())
+        .args(["__complete", "skill-ids", "--prefix", "ru"])
+        .assert()
+        .success()
+        .stdout("")
+        .stderr("");
+    assert!(!dir.path().join("ms.db").exists());
+}
+
+#[test]
+fn test_completions_zsh_script() {
+    let mut cmd = Command::cargo_bin("ms").unwrap();
+    cmd.args(["completions", "zsh"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("#compdef ms"))
+        .stdout(predicate::str::contains("_ms_dynamic skill-ids"));
+}
+
 #[test]
 fn test_robot_mode_global() {
     let mut cmd = Command::cargo_bin("ms").unwrap();
@@ -13,7 +13,6 @@
 
 use super::fixture::{E2EFixture, LogLevel};
 use ms::error::Result;
-use std::fs;
 
 // ============================================================================
 // Skill definitions
@@ -129,25 +128,21 @@ fn test_backup_create_auto_id() -> Result<()> {
         Some(json.clone()),
     );
 
-    // Verify backup directory was created
-    let backup_dir = fixture.ms_root.join("backups").join(backup_id);
-    assert!(
-        backup_dir.exists(),
-        "Backup directory should exist at {backup_dir:?}"
-    );
-
-    // Verify manifest exists
-    let manifest_path = backup_dir.join("manifest.json");
+    // Verify the backup archive was created
+    let archive = fixture
+        .ms_root
+        .join("backups")
+        .join(format!("{backup_id}.tar.zst"));
     assert!(
-        manifest_path.exists(),
-        "Manifest should exist at {manifest_path:?}"
+        archive.exists(),
+        "Backup archive should exist at {archive:?}"
     );
 
-    // Verify database was backed up
-    let db_backup = backup_dir.join("ms.db");
+    // Verify the database snapshot is part of it
+    let entries = json["entries"].as_array().expect("entries should be array");
     assert!(
-        db_backup.exists(),
-        "Database backup should exist at {db_backup:?}"
+        entries.iter().any(|e| e["name"].as_str() == Some("ms.db")),
+        "Backup should include ms.db"
     );
 
     fixture.generate_report();
@@ -174,21 +169,16 @@ fn test_backup_create_custom_id() -> Result<()> {
         "Backup ID should match custom value"
     );
 
-    // Verify backup directory exists with custom name
-    let backup_dir = fixture.ms_root.join("backups").join("my-custom-backup");
+    // Verify backup archive exists with custom name
+    let archive = fixture
+        .ms_root
+        .join("backups")
+        .join("my-custom-backup.tar.zst");
     assert!(
-        backup_dir.exists(),
-        "Backup directory should exist with custom name"
+        archive.exists(),
+        "Backup archive should exist with custom name"
     );
 
-    let manifest_path = backup_dir.join("manifest.json");
-    assert!(manifest_path.exists(), "Manifest should exist");
-
-    // Parse manifest and verify contents
-    let manifest_raw = fs::read_to_string(&manifest_path)?;
-    let manifest: serde_json::Value = serde_json::from_str(&manifest_raw)?;
-    assert_eq!(manifest["id"].as_str(), Some("my-custom-backup"));
-
     fixture.emit_event(
         LogLevel::Info,
         "backup",
@@ -522,8 +512,8 @@ fn test_backup_minimal_workspace() -> Result<()> {
     assert_eq!(backup_id, "minimal");
 
     // Verify backup exists
-    let backup_dir = fixture.ms_root.join("backups").join("minimal");
-    assert!(backup_dir.exists(), "Backup directory should exist");
+    let archive = fixture.ms_root.join("backups").join("minimal.tar.zst");
+    assert!(archive.exists(), "Backup archive should exist");
 
     // List should show one backup
     fixture.log_step("List backups");
@@ -586,16 +576,8 @@ fn test_backup_manifest_structure() -> Result<()> {
 
     fixture.checkpoint("backup:post-create-manifest");
 
-    // Read and parse the manifest
-    let manifest_path = fixture
-        .ms_root
-        .join("backups")
-        .join("manifest-check")
-        .join("manifest.json");
-    assert!(manifest_path.exists(), "Manifest should exist");
-
-    let manifest_raw = fs::read_to_string(&manifest_path)?;
-    let manifest: serde_json::Value = serde_json::from_str(&manifest_raw)?;
+    // The create report is the manifest stored in the archive
+    let manifest = output.json();
 
     // Verify required manifest fields
     assert!(manifest["id"].is_string(), "Manifest should have string id");
@@ -611,6 +593,18 @@ fn test_backup_manifest_structure() -> Result<()> {
         manifest["entries"].is_array(),
         "Manifest should have entries array"
     );
+    assert!(
+        manifest["ms_version"].is_string(),
+        "Manifest should have ms_version"
+    );
+    assert!(
+        manifest["schema_version"].is_u64(),
+        "Manifest should have schema_version"
+    );
+    assert!(
+        manifest["skill_count"].is_u64(),
+        "Manifest should have skill_count"
+    );
 
     let entries = manifest["entries"].as_array().unwrap();
     assert!(
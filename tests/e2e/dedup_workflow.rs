@@ -5,6 +5,7 @@
 //! - Dedup review of a specific pair
 //! - Dedup alias creation (resolution)
 //! - Dedup scan reporting (status, pair count, filter)
+//! - Dedup clusters and dismissal of "not duplicate" pairs
 
 use super::fixture::E2EFixture;
 use ms::error::Result;
@@ -366,3 +367,74 @@ fn test_dedup_review_nonexistent_skill() -> Result<()> {
     fixture.generate_report();
     Ok(())
 }
+
+/// Whether any cluster in a `dedup clusters` response pairs `a` with `b`.
+fn clusters_pair(json: &serde_json::Value, a: &str, b: &str) -> bool {
+    json["clusters"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .flat_map(|cluster| cluster["pairs"].as_array().into_iter().flatten())
+        .any(|pair| {
+            let ids = [
+                pair["skill_a_id"].as_str().unwrap_or_default(),
+                pair["skill_b_id"].as_str().unwrap_or_default(),
+            ];
+            ids.contains(&a) && ids.contains(&b)
+        })
+}
+
+#[test]
+fn test_dedup_clusters_and_dismiss() -> Result<()> {
+    let mut fixture = setup_dedup_fixture("dedup_clusters_dismiss")?;
+
+    fixture.log_step("Cluster near-duplicates at a low threshold");
+    let output = fixture.run_ms(&["--robot", "dedup", "clusters", "--threshold", "0.3"]);
+    fixture.assert_success(&output, "dedup clusters");
+    let json = output.json();
+    assert_eq!(json["status"].as_str(), Some("ok"));
+    assert_eq!(json["merged"].as_bool(), Some(false));
+    assert!(
+        clusters_pair(&json, "rust-error-handling", "rust-error-patterns"),
+        "rust error skills should cluster: {json}"
+    );
+    let cluster = json["clusters"]
+        .as_array()
+        .and_then(|clusters| clusters.first())
+        .expect("at least one cluster");
+    assert!(cluster["members"].as_array().is_some_and(|m| m.len() >= 2));
+    assert!(cluster["diff"]["differing_rules"].is_array());
+
+    fixture.log_step("Dismiss the pair as not duplicates");
+    let output = fixture.run_ms(&[
+        "--robot",
+        "dedup",
+        "dismiss",
+        "rust-error-patterns",
+        "rust-error-handling",
+        "--reason",
+        "different audiences",
+    ]);
+    fixture.assert_success(&output, "dedup dismiss");
+
+    fixture.log_step("Dismissed pair is suppressed in later runs");
+    let output = fixture.run_ms(&["--robot", "dedup", "clusters", "--threshold", "0.3"]);
+    fixture.assert_success(&output, "dedup clusters after dismiss");
+    assert!(!clusters_pair(
+        &output.json(),
+        "rust-error-handling",
+        "rust-error-patterns"
+    ));
+
+    let output = fixture.run_ms(&["--robot", "dedup", "dismiss", "--list"]);
+    fixture.assert_success(&output, "dedup dismiss --list");
+    let json = output.json();
+    assert_eq!(json["count"].as_u64(), Some(1));
+    assert_eq!(
+        json["dismissals"][0]["skill_a"].as_str(),
+        Some("rust-error-handling")
+    );
+
+    fixture.generate_report();
+    Ok(())
+}
@@ -10,6 +10,8 @@
 //! - Doctor output format and summary structure
 //! - Doctor on a workspace with no indexed skills
 //! - Doctor idempotency (running doctor twice yields consistent results)
+//! - Doctor --fix rebuilding a corrupted search index
+//! - Doctor --fix re-embedding skills after an embedding backend change
 
 use super::fixture::E2EFixture;
 use ms::error::Result;
@@ -547,3 +549,110 @@ fn test_doctor_on_fresh_workspace() -> Result<()> {
     fixture.generate_report();
     Ok(())
 }
+
+/// Doctor --fix should rebuild a corrupted search index from the database so
+/// that search works again.
+#[test]
+fn test_doctor_fix_rebuilds_corrupted_index() -> Result<()> {
+    let mut fixture = setup_healthy_workspace("doctor_fix_corrupt_index")?;
+
+    fixture.log_step("Corrupt the index: garbage meta.json and a stale writer lock");
+    let index_dir = fixture.ms_root.join("index");
+    std::fs::write(index_dir.join("meta.json"), "{ this is not an index")?;
+    std::fs::write(index_dir.join(".tantivy-writer.lock"), "")?;
+    fixture.checkpoint("doctor:corrupted-index");
+
+    fixture.log_step("Search fails on the corrupted index");
+    let output = fixture.run_ms(&["--robot", "search", "Basic"]);
+    assert!(
+        !output.success,
+        "search should fail on a corrupted index.\nStdout: {}",
+        output.stdout
+    );
+
+    fixture.log_step("Doctor reports the index without --fix");
+    let output = fixture.run_ms(&["doctor", "--check", "index"]);
+    fixture.assert_output_contains(&output, "Checking search index");
+    fixture.assert_output_contains(&output, "stale lock file");
+
+    fixture.log_step("Doctor --fix rebuilds the index");
+    let output = fixture.run_ms(&["doctor", "--fix"]);
+    fixture.assert_success(&output, "doctor --fix corrupted index");
+    fixture.assert_output_contains(&output, "Rebuilt index from 2 skill(s)");
+    fixture.assert_output_contains(&output, "Search index repaired");
+    fixture.checkpoint("doctor:index-rebuilt");
+
+    fixture.log_step("Search works again");
+    let output = fixture.run_ms(&["--robot", "search", "Basic", "--search-type", "bm25"]);
+    fixture.assert_success(&output, "search after repair");
+    let json = output.json();
+    let ids: Vec<&str> = json["results"]
+        .as_array()
+        .map(|results| results.iter().filter_map(|r| r["id"].as_str()).collect())
+        .unwrap_or_default();
+    assert!(
+        ids.contains(&"basic-skill"),
+        "search should find basic-skill after repair, got {ids:?}"
+    );
+
+    fixture.log_step("A second doctor run finds the index healthy");
+    let output = fixture.run_ms(&["doctor", "--check", "index"]);
+    fixture.assert_success(&output, "doctor --check index");
+    fixture.assert_output_contains(&output, "All checks passed");
+
+    fixture.emit_event(
+        super::fixture::LogLevel::Info,
+        "doctor",
+        "Corrupted search index rebuilt by doctor --fix",
+        None,
+    );
+
+    fixture.generate_report();
+    Ok(())
+}
+
+/// Switching the embedding dimension must make semantic search ask for a
+/// re-index instead of comparing incompatible vectors; doctor --fix re-embeds.
+#[test]
+fn test_doctor_fix_reembeds_after_backend_change() -> Result<()> {
+    let mut fixture = setup_healthy_workspace("doctor_fix_reembed")?;
+
+    fixture.log_step("Populate stored embeddings via an index rebuild");
+    let index_dir = fixture.ms_root.join("index");
+    std::fs::write(index_dir.join("meta.json"), "{ this is not an index")?;
+    let output = fixture.run_ms(&["doctor", "--fix"]);
+    fixture.assert_success(&output, "doctor --fix rebuild");
+    let output = fixture.run_ms(&["doctor", "--check", "embeddings"]);
+    fixture.assert_success(&output, "doctor --check embeddings");
+    fixture.assert_output_contains(&output, "hash (384 dims), 2 stored");
+
+    let env = [("MS_SEARCH_EMBEDDING_DIMS", "64")];
+
+    fixture.log_step("Semantic search refuses vectors of another dimension");
+    let output = fixture.run_ms_with_env(
+        &["--robot", "search", "Basic", "--search-type", "semantic"],
+        &env,
+    );
+    assert!(
+        !output.success,
+        "search should fail after a dimension change.\nStdout: {}",
+        output.stdout
+    );
+    fixture.assert_output_contains(&output, "Re-index required");
+
+    fixture.log_step("Doctor reports the mismatch, then --fix re-embeds");
+    let output = fixture.run_ms_with_env(&["doctor", "--check", "embeddings"], &env);
+    fixture.assert_output_contains(&output, "re-index required");
+    let output = fixture.run_ms_with_env(&["doctor", "--check", "embeddings", "--fix"], &env);
+    fixture.assert_success(&output, "doctor --fix embeddings");
+    fixture.assert_output_contains(&output, "Re-embedded 2 skill(s) with hash");
+
+    let output = fixture.run_ms_with_env(
+        &["--robot", "search", "Basic", "--search-type", "semantic"],
+        &env,
+    );
+    fixture.assert_success(&output, "semantic search after re-embed");
+
+    fixture.generate_report();
+    Ok(())
+}
@@ -214,12 +214,12 @@ fn test_reindex_force() -> Result<()> {
         })),
     );
 
-    // Without force, unchanged skills should be skipped, so indexed count may differ
-    // The important thing is that the command succeeds
-    assert!(
-        second_indexed <= first_indexed,
-        "Re-index without force should index same or fewer skills"
-    );
+    // Nothing changed, so every skill is skipped
+    assert_eq!(second_indexed, first_indexed);
+    assert_eq!(json["skipped"].as_u64(), Some(first_indexed));
+    assert_eq!(json["added"].as_u64(), Some(0));
+    assert_eq!(json["updated"].as_u64(), Some(0));
+    assert_eq!(json["removed"].as_u64(), Some(0));
 
     // Checkpoint: after second index
     fixture.checkpoint("index:second-pass");
@@ -246,6 +246,31 @@ fn test_reindex_force() -> Result<()> {
     // Checkpoint: after force index
     fixture.checkpoint("index:force-pass");
 
+    fixture.log_step("Edit one skill (should update exactly one)");
+    fixture.create_skill(
+        "rust-error-handling",
+        &format!("{SKILL_RUST_ERRORS}- Add context with `map_err`\n"),
+    )?;
+    let output = fixture.run_ms(&["--robot", "index"]);
+    fixture.assert_success(&output, "reindex after edit");
+    let json = output.json();
+    assert_eq!(json["updated"].as_u64(), Some(1));
+    assert_eq!(json["skipped"].as_u64(), Some(first_indexed - 1));
+    assert_eq!(json["added"].as_u64(), Some(0));
+    assert_eq!(json["removed"].as_u64(), Some(0));
+
+    fixture.log_step("Delete one skill (should remove exactly one)");
+    std::fs::remove_dir_all(fixture.skills_dirs["project"].join("python-testing"))?;
+    let output = fixture.run_ms(&["--robot", "index"]);
+    fixture.assert_success(&output, "reindex after delete");
+    let json = output.json();
+    assert_eq!(json["removed"].as_u64(), Some(1));
+    assert_eq!(json["skipped"].as_u64(), Some(first_indexed - 1));
+    assert_eq!(json["updated"].as_u64(), Some(0));
+
+    // Checkpoint: after incremental passes
+    fixture.checkpoint("index:incremental-passes");
+
     fixture.generate_report();
     Ok(())
 }
@@ -360,6 +385,65 @@ fn test_index_status_reporting() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_index_progress_events_stream_ndjson_to_stderr() -> Result<()> {
+    let mut fixture = setup_index_fixture("index_progress_events")?;
+
+    fixture.log_step("Index with progress events");
+    let output = fixture.run_ms(&["--robot", "--progress-events", "index"]);
+    fixture.assert_success(&output, "index --progress-events");
+
+    // stdout stays a single final-result document
+    let json = output.json();
+    assert_eq!(json["indexed"].as_u64(), Some(3));
+
+    let events: Vec<serde_json::Value> = output
+        .stderr
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter(|event| event.get("event").is_some())
+        .collect();
+    let indexing: Vec<&serde_json::Value> = events
+        .iter()
+        .filter(|event| event["phase"] == "indexing")
+        .collect();
+
+    assert_eq!(indexing[0]["event"], "start");
+    assert_eq!(indexing[0]["total"], 3);
+    let last_progress = indexing
+        .iter()
+        .rev()
+        .find(|event| event["event"] == "progress")
+        .expect("progress event");
+    assert_eq!(last_progress["current"], 3);
+    assert_eq!(last_progress["total"], 3);
+    assert!(last_progress["skill"].is_string());
+    let done = indexing.last().expect("indexing events");
+    assert_eq!(done["event"], "done");
+    assert!(done["duration_ms"].is_u64());
+    assert!(
+        events
+            .iter()
+            .any(|event| event["phase"] == "finalizing" && event["event"] == "done"),
+        "{events:?}"
+    );
+
+    fixture.log_step("Without the flag stderr carries no events");
+    let output = fixture.run_ms(&["--robot", "index", "--force"]);
+    fixture.assert_success(&output, "index");
+    assert!(
+        !output
+            .stderr
+            .lines()
+            .any(|line| line.starts_with("{\"event\"")),
+        "{}",
+        output.stderr
+    );
+
+    fixture.generate_report();
+    Ok(())
+}
+
 #[test]
 fn test_index_empty_workspace() -> Result<()> {
     let mut fixture = E2EFixture::new("index_empty_workspace");
@@ -470,51 +554,47 @@ fn test_index_with_multi_layer_paths() -> Result<()> {
 
 /// Regression test for issue #133.
 ///
-/// When the search index can only be opened read-only — here another process
-/// holds the Tantivy writer lock, exactly as a live `ms mcp serve` would —
-/// `ms index` must fail fast with a clear, actionable diagnostic instead of
-/// silently degrading to a read-only index and aborting mid-run with the opaque
-/// "Index opened in read-only mode" Tantivy error. It must also NOT partially
-/// write skills into the database before failing.
+/// When another process holds the search index writer — here this test
+/// process, as a concurrent `ms index` would — `ms index` must wait for it and
+/// then fail with a Busy error naming the holder, instead of aborting mid-run
+/// with the opaque "Index opened in read-only mode" Tantivy error. It must also
+/// NOT partially write skills into the database before failing.
 #[test]
-fn test_index_readonly_index_reports_clear_error() -> Result<()> {
+fn test_index_busy_writer_reports_holder() -> Result<()> {
     use ms::search::tantivy::Bm25Index;
 
-    let mut fixture = setup_index_fixture("index_readonly_guard")?;
+    let mut fixture = setup_index_fixture("index_busy_writer")?;
 
-    // Hold the Tantivy writer lock from this test process, emulating a live
-    // `ms mcp serve`. The `ms index` subprocess below will find the index busy,
-    // fall back to a read-only handle, and must reject the write up front.
     let index_dir = fixture.ms_root.join("index");
-    let _writer_guard = Bm25Index::open(&index_dir).expect("open index writable in test process");
+    let writer_guard = Bm25Index::open(&index_dir).expect("open index writable in test process");
+    writer_guard
+        .reserve_writer()
+        .expect("take the index writer in test process");
 
-    let output = fixture.run_ms(&["index"]);
+    let output = fixture.run_ms_with_env(&["index"], &[("MS_LOCK_TIMEOUT", "1")]);
 
     assert!(
         !output.success,
-        "ms index must fail when the index is read-only; got success.\nstdout={}\nstderr={}",
+        "ms index must fail while another process holds the writer; got success.\nstdout={}\nstderr={}",
         output.stdout, output.stderr
     );
 
     let combined = format!("{}\n{}", output.stdout, output.stderr);
-
-    // The clear, actionable diagnostic — not the opaque Tantivy internal error.
     assert!(
-        combined.contains("Cannot write to the search index"),
-        "expected the actionable #133 diagnostic; got:\n{combined}"
+        combined.contains("search index writer is busy"),
+        "expected the Busy error; got:\n{combined}"
     );
     assert!(
-        combined.contains("writer lock") || combined.contains("read-only filesystem"),
-        "diagnostic must name the concrete cause; got:\n{combined}"
+        combined.contains(&format!("held by pid {}", std::process::id())),
+        "Busy error must name the holder; got:\n{combined}"
     );
     assert!(
         !combined.contains("Index opened in read-only mode"),
         "must not surface the opaque Tantivy read-only error; got:\n{combined}"
     );
 
-    // The guard runs before any indexing work, so nothing should have been
-    // written to the database. (The write lock is still held here, so `list`
-    // opens the index read-only and reads skills straight from SQLite.)
+    // The writer is reserved before any indexing work, so nothing should have
+    // been written to the database. Reads never wait on the writer.
     let list = fixture.run_ms(&["--robot", "list"]);
     let count = list.json()["count"].as_u64().unwrap_or(0);
     assert_eq!(
@@ -522,7 +602,7 @@ fn test_index_readonly_index_reports_clear_error() -> Result<()> {
         "index must not partially write skills before failing; list returned {count}"
     );
 
-    drop(_writer_guard);
+    drop(writer_guard);
     fixture.generate_report();
     Ok(())
 }
@@ -205,3 +205,110 @@ Project version content (should take precedence).
     fixture.generate_report();
     Ok(())
 }
+
+const COMMUNITY_STYLE: &str = r#"---
+id: rust-style
+name: Rust Style
+description: Community Rust style guide
+tags: [rust, style]
+---
+
+# Rust Style
+
+Community Rust style guide
+
+## Rules
+
+Run rustfmt before committing.
+"#;
+
+/// Community skills are read-only: edits are refused until the skill is
+/// forked into the project, the fork then wins over its origin, and
+/// `ms diff --upstream` notices when the origin changes.
+#[test]
+fn test_read_only_community_skill_forks_on_edit() -> Result<()> {
+    let mut fixture = E2EFixture::new("read_only_community_fork");
+
+    fixture.log_step("Initialize");
+    let output = fixture.init();
+    fixture.assert_success(&output, "init");
+    let output = fixture.run_ms(&[
+        "--robot",
+        "config",
+        "skill_paths.community",
+        r#"["./global_skills"]"#,
+    ]);
+    fixture.assert_success(&output, "config skill_paths.community");
+
+    fixture.log_step("Index a community skill");
+    fixture.create_skill_in_layer("rust-style", COMMUNITY_STYLE, "global")?;
+    let origin = fixture.skills_dirs["global"].join("rust-style/SKILL.md");
+    let output = fixture.run_ms(&["--robot", "index"]);
+    fixture.assert_success(&output, "index");
+
+    fixture.log_step("Edits to the community skill are refused");
+    let output = fixture.run_ms(&[
+        "--robot",
+        "edit",
+        "rust-style",
+        "--add-rule",
+        "Prefer ? over unwrap",
+    ]);
+    assert!(!output.success, "editing a community skill should fail");
+    fixture.assert_output_contains(&output, "--fork-to project");
+    let output = fixture.run_ms(&["--robot", "fmt", origin.to_str().unwrap()]);
+    assert!(!output.success, "formatting a community skill should fail");
+    assert_eq!(std::fs::read_to_string(&origin)?, COMMUNITY_STYLE);
+
+    fixture.log_step("Fork to the project and edit the fork");
+    let output = fixture.run_ms(&[
+        "--robot",
+        "edit",
+        "rust-style",
+        "--add-rule",
+        "Prefer ? over unwrap",
+        "--fork-to",
+        "project",
+    ]);
+    fixture.assert_success(&output, "edit --fork-to project");
+    let fork = fixture.skills_dirs["project"].join("rust-style/SKILL.md");
+    let forked = std::fs::read_to_string(&fork)?;
+    assert!(forked.contains("forked_from"), "fork records its origin");
+    assert!(
+        forked.contains("Prefer ? over unwrap"),
+        "edit lands in the fork"
+    );
+    assert_eq!(std::fs::read_to_string(&origin)?, COMMUNITY_STYLE);
+
+    fixture.log_step("The fork wins over its origin");
+    let output = fixture.run_ms(&["--robot", "load", "rust-style", "--full"]);
+    fixture.assert_success(&output, "load fork");
+    fixture.assert_output_contains(&output, "Prefer ? over unwrap");
+
+    fixture.log_step("Compare the fork with its upstream");
+    let output = fixture.run_ms(&["--robot", "diff", "rust-style", "--upstream"]);
+    fixture.assert_success(&output, "diff --upstream");
+    let json = output.json();
+    assert_eq!(json["upstream"]["status"], "current");
+    assert_eq!(json["same"], false);
+
+    fixture.log_step("Upstream drift is flagged");
+    std::fs::write(
+        &origin,
+        format!("{COMMUNITY_STYLE}\n## Naming\n\nUse snake_case for functions.\n"),
+    )?;
+    let output = fixture.run_ms(&["--robot", "diff", "rust-style", "--upstream"]);
+    fixture.assert_success(&output, "diff --upstream after drift");
+    let json = output.json();
+    assert_eq!(json["upstream"]["status"], "drifted");
+    let sections = json["sections"].as_array().expect("sections");
+    assert!(
+        sections
+            .iter()
+            .any(|s| s["section_id"] == "naming" && s["status"] == "removed"),
+        "the upstream-only section shows up: {sections:?}"
+    );
+
+    fixture.generate_report();
+    Ok(())
+}
@@ -4,6 +4,7 @@
 //! - Loading a skill by ID after indexing
 //! - Loading with different disclosure levels
 //! - Loading a non-existent skill
+//! - Loading and diffing through a skill alias
 //! - Loading with --full and --complete flags
 //! - Loading with token budget (--pack)
 //! - Robot/JSON output format verification
@@ -544,3 +545,103 @@ fn test_load_human_output() -> Result<()> {
     fixture.generate_report();
     Ok(())
 }
+
+/// Aliases resolve in `ms load` and `ms diff`, ignoring case.
+#[test]
+fn test_load_and_diff_via_alias() -> Result<()> {
+    let mut fixture = setup_load_fixture("load_via_alias")?;
+
+    fixture.log_step("Add an alias");
+    let output = fixture.run_ms(&[
+        "--robot",
+        "alias",
+        "add",
+        "reh",
+        "--target",
+        "rust-error-handling",
+    ]);
+    fixture.assert_success(&output, "alias add");
+
+    fixture.log_step("Load through the alias");
+    let output = fixture.run_ms(&["--robot", "load", "REH"]);
+    fixture.assert_success(&output, "load REH");
+    let json = output.json();
+    assert_eq!(
+        json["data"]["skill_id"].as_str(),
+        Some("rust-error-handling"),
+        "alias should resolve to the canonical skill"
+    );
+
+    fixture.log_step("Diff through the alias");
+    let output = fixture.run_ms(&["--robot", "diff", "reh", "go-concurrency"]);
+    fixture.assert_success(&output, "diff reh go-concurrency");
+    let json = output.json();
+    assert!(
+        json["skill_a"]
+            .as_str()
+            .is_some_and(|label| label.contains("rust-error-handling")),
+        "diff should load the aliased skill, got {}",
+        json["skill_a"]
+    );
+
+    fixture.generate_report();
+    Ok(())
+}
+
+/// Loads and feedback show up in `ms stats`, grouped by skill or layer.
+#[test]
+fn test_stats_reports_loads_and_prune_candidates() -> Result<()> {
+    let mut fixture = setup_load_fixture("stats_usage")?;
+
+    fixture.log_step("Load one skill twice and rate another negatively");
+    for _ in 0..2 {
+        let output = fixture.run_ms(&["--robot", "load", "rust-error-handling"]);
+        fixture.assert_success(&output, "load");
+    }
+    let output = fixture.run_ms(&["--robot", "feedback", "add", "go-concurrency", "--negative"]);
+    fixture.assert_success(&output, "feedback add negative");
+
+    fixture.log_step("Per-skill usage");
+    let output = fixture.run_ms(&["--robot", "stats", "--since", "7d", "--prune-candidates"]);
+    fixture.assert_success(&output, "stats");
+    let json = output.json();
+    assert_eq!(json["group_by"], "skill");
+    assert_eq!(json["window"]["bucket"], "day");
+    let rows = json["rows"].as_array().expect("rows should be an array");
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[0]["key"], "rust-error-handling");
+    assert_eq!(rows[0]["loads"], 2);
+    assert_eq!(rows[0]["trend"].as_array().unwrap().len(), 7);
+    let never_loaded: Vec<&str> = json["never_loaded"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|id| id.as_str())
+        .collect();
+    assert_eq!(never_loaded, vec!["go-concurrency", "go-testing"]);
+    assert_eq!(
+        json["prune_candidates"],
+        serde_json::json!(["go-concurrency"])
+    );
+
+    fixture.log_step("Grouped by layer");
+    let output = fixture.run_ms(&["--robot", "stats", "--by", "layer"]);
+    fixture.assert_success(&output, "stats --by layer");
+    let json = output.json();
+    let rows = json["rows"].as_array().unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["skills"], 3);
+    assert_eq!(rows[0]["loads"], 2);
+    assert!(json.get("prune_candidates").is_none());
+
+    fixture.log_step("Human table and date validation");
+    let output = fixture.run_ms(&["stats", "--since", "2w", "--prune-candidates"]);
+    fixture.assert_success(&output, "stats (human)");
+    fixture.assert_output_contains(&output, "rust-error-handling");
+    fixture.assert_output_contains(&output, "Prune candidates (1)");
+    let output = fixture.run_ms(&["--robot", "stats", "--since", "soon"]);
+    assert!(!output.success, "invalid --since should fail");
+
+    fixture.generate_report();
+    Ok(())
+}
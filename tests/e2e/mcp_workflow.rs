@@ -594,6 +594,110 @@ fn test_mcp_load_tool() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_mcp_tools_resolve_aliases() -> Result<()> {
+    let mut fixture = setup_mcp_fixture("mcp_alias_resolution")?;
+
+    fixture.log_step("Add an alias");
+    let output = fixture.run_ms(&[
+        "--robot",
+        "alias",
+        "add",
+        "reh",
+        "--target",
+        "rust-error-handling",
+    ]);
+    fixture.assert_success(&output, "alias add");
+
+    let mut client = McpClient::spawn(&fixture, false)?;
+    client.initialize()?;
+
+    for (tool, arguments) in [
+        ("load", json!({ "skill": "REH", "full": true })),
+        ("show", json!({ "skill": "reh" })),
+        ("evidence", json!({ "skill": "reh" })),
+    ] {
+        fixture.log_step(&format!("Call {tool} through the alias"));
+        let response = client.call_tool(tool, arguments)?;
+        assert!(response.is_success(), "{tool} should succeed");
+        assert!(
+            !response.tool_is_error(),
+            "{tool} should resolve the alias: {:?}",
+            response.tool_text()
+        );
+        let text = response.tool_text().expect("Should have tool text");
+        assert!(
+            text.contains("rust-error-handling"),
+            "{tool} should report the canonical id, got: {text}"
+        );
+    }
+
+    client.kill();
+    Ok(())
+}
+
+#[test]
+fn test_mcp_search_and_list_cursor_pagination() -> Result<()> {
+    let mut fixture = setup_mcp_fixture("mcp_cursor_pagination")?;
+
+    fixture.log_step("Add a paging corpus");
+    for i in 0..5 {
+        let content = format!(
+            "---\nname: Cursor Paging {i}\ndescription: Cursor paging fixture {i}\n---\n\n\
+             # Cursor Paging {i}\n\nCursor paging fixture.\n"
+        );
+        fixture.create_skill_in_layer(&format!("cursor-paging-{i}"), &content, "project")?;
+    }
+    let output = fixture.run_ms(&["--robot", "index"]);
+    fixture.assert_success(&output, "index");
+
+    let mut client = McpClient::spawn(&fixture, false)?;
+    client.initialize()?;
+
+    for (tool, arguments, key, total) in [
+        (
+            "search",
+            json!({ "query": "paging", "limit": 3 }),
+            "results",
+            5,
+        ),
+        ("list", json!({ "limit": 3 }), "skills", 8),
+    ] {
+        fixture.log_step(&format!("Page through {tool} in pages of 3"));
+        let mut ids = Vec::new();
+        let mut arguments = arguments;
+        loop {
+            let response = client.call_tool(tool, arguments.clone())?;
+            assert!(!response.tool_is_error(), "{tool} failed");
+            let page: Value = serde_json::from_str(response.tool_text().expect("tool text"))
+                .expect("Tool output should be valid JSON");
+            let items = page[key].as_array().expect("page items");
+            assert!(items.len() <= 3, "{tool} page larger than limit");
+            ids.extend(
+                items
+                    .iter()
+                    .filter_map(|item| item["id"].as_str().map(String::from)),
+            );
+            match page["next_cursor"].as_str() {
+                Some(cursor) => arguments["cursor"] = json!(cursor),
+                None => break,
+            }
+        }
+        let count = ids.len();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), count, "{tool} returned duplicates");
+        assert_eq!(count, total, "{tool} skipped results");
+    }
+
+    fixture.log_step("Reject a page size above the maximum");
+    let response = client.call_tool("search", json!({ "query": "paging", "limit": 500 }))?;
+    assert!(response.is_error(), "oversized page accepted");
+
+    client.kill();
+    Ok(())
+}
+
 #[test]
 fn test_mcp_list_show_tools() -> Result<()> {
     let mut fixture = setup_mcp_fixture("mcp_list_show_tools")?;
@@ -686,6 +790,71 @@ fn test_mcp_list_show_tools() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_mcp_skill_resources() -> Result<()> {
+    let mut fixture = setup_mcp_fixture("mcp_skill_resources")?;
+
+    let mut client = McpClient::spawn(&fixture, false)?;
+    let init = client.initialize()?;
+    assert!(
+        init.result().expect("Should have result")["capabilities"]["resources"].is_object(),
+        "Resources capability should be present"
+    );
+
+    fixture.log_step("List skill resources");
+    let response = client.request("resources/list", json!({}))?;
+    assert!(response.is_success(), "resources/list should succeed");
+    assert!(
+        !response.contains_ansi(),
+        "Response should not contain ANSI codes"
+    );
+    let result = response.result().expect("Should have result");
+    let resources = result["resources"]
+        .as_array()
+        .expect("Should have resources array");
+    let uris: Vec<&str> = resources
+        .iter()
+        .filter_map(|resource| resource["uri"].as_str())
+        .collect();
+    let mut sorted = uris.clone();
+    sorted.sort_unstable();
+    assert_eq!(uris, sorted, "Resources should be in id order");
+    assert!(uris.contains(&"ms://skill/rust-testing"), "got {uris:?}");
+    for resource in resources {
+        assert_eq!(resource["mimeType"], "text/markdown");
+        assert!(resource["annotations"]["layer"].is_string());
+    }
+    assert!(
+        result.get("nextCursor").is_none(),
+        "A single page should have no cursor"
+    );
+
+    fixture.log_step("Read a skill resource");
+    let response = client.request(
+        "resources/read",
+        json!({ "uri": "ms://skill/rust-testing" }),
+    )?;
+    assert!(response.is_success(), "resources/read should succeed");
+    let contents = &response.result().expect("Should have result")["contents"][0];
+    assert_eq!(contents["uri"], "ms://skill/rust-testing");
+    assert_eq!(contents["mimeType"], "text/markdown");
+    let text = contents["text"].as_str().expect("Should have text");
+    assert!(text.contains("Integration Tests"), "got: {text}");
+
+    fixture.log_step("Read an unknown resource");
+    let response = client.request("resources/read", json!({ "uri": "ms://skill/nope" }))?;
+    assert!(response.is_error(), "Unknown skills should be an error");
+    assert_eq!(response.error_code(), Some(-32002));
+
+    fixture.log_step("List resource templates");
+    let response = client.request("resources/templates/list", json!({}))?;
+    let templates = &response.result().expect("Should have result")["resourceTemplates"];
+    assert_eq!(templates[0]["uriTemplate"], "ms://skill/{id}");
+
+    client.kill();
+    Ok(())
+}
+
 #[test]
 fn test_mcp_doctor_tool() -> Result<()> {
     let mut fixture = setup_mcp_fixture("mcp_doctor_tool")?;
@@ -1026,3 +1195,225 @@ fn test_mcp_index_tool() -> Result<()> {
     client.kill();
     Ok(())
 }
+
+// ============================================================================
+// HTTP Transport
+// ============================================================================
+
+/// `ms mcp serve --tcp-port 0` and the address it reported on stderr
+struct HttpMcpServer {
+    child: Child,
+    addr: std::net::SocketAddr,
+}
+
+impl HttpMcpServer {
+    fn spawn(fixture: &E2EFixture) -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_ms"))
+            .args(["mcp", "serve", "--tcp-port", "0"])
+            .env("HOME", &fixture.root)
+            .env("MS_ROOT", &fixture.ms_root)
+            .env("MS_CONFIG", &fixture.config_path)
+            .env("MS_PLAIN_OUTPUT", "1")
+            .current_dir(&fixture.root)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("Failed to spawn MCP HTTP server");
+
+        let stderr = child.stderr.take().expect("No stderr");
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stderr)
+                .lines()
+                .map_while(std::result::Result::ok)
+            {
+                let addr = line
+                    .split("http://")
+                    .nth(1)
+                    .and_then(|rest| rest.strip_suffix("/mcp"))
+                    .and_then(|addr| addr.parse().ok());
+                if let Some(addr) = addr {
+                    let _ = tx.send(addr);
+                }
+            }
+        });
+        let addr = rx
+            .recv_timeout(Duration::from_secs(10))
+            .expect("MCP HTTP server did not report its address");
+        Self { child, addr }
+    }
+
+    fn url(&self) -> String {
+        format!("http://{}/mcp", self.addr)
+    }
+
+    fn post(
+        &self,
+        body: &Value,
+        accept: &str,
+        session: Option<&str>,
+    ) -> reqwest::blocking::Response {
+        let mut request = reqwest::blocking::Client::new()
+            .post(self.url())
+            .header("Content-Type", "application/json")
+            .header("Accept", accept)
+            .body(body.to_string());
+        if let Some(session) = session {
+            request = request.header("Mcp-Session-Id", session);
+        }
+        request.send().expect("HTTP request failed")
+    }
+}
+
+impl Drop for HttpMcpServer {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn rpc(id: u64, method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+}
+
+#[test]
+fn test_mcp_http_transport_round_trips() -> Result<()> {
+    let mut fixture = setup_mcp_fixture("mcp_http_transport")?;
+
+    fixture.log_step("Start MCP server on an ephemeral port");
+    let mut server = HttpMcpServer::spawn(&fixture);
+
+    fixture.log_step("initialize");
+    let response = server.post(
+        &rpc(
+            1,
+            "initialize",
+            json!({
+                "protocolVersion": "2024-11-05",
+                "clientInfo": { "name": "ms-e2e-http", "version": "1.0.0" },
+                "capabilities": {}
+            }),
+        ),
+        "application/json",
+        None,
+    );
+    assert_eq!(response.status(), 200);
+    let session = response
+        .headers()
+        .get("Mcp-Session-Id")
+        .and_then(|value| value.to_str().ok())
+        .expect("initialize should issue a session id")
+        .to_string();
+    let raw = response.text().expect("initialize body");
+    assert!(
+        !raw.contains('\x1b'),
+        "Response should not contain ANSI codes"
+    );
+    let init: Value = serde_json::from_str(&raw).expect("initialize JSON");
+    assert_eq!(init["id"], 1);
+    assert_eq!(init["result"]["protocolVersion"], "2024-11-05");
+
+    let ack = server.post(
+        &json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }),
+        "application/json",
+        Some(&session),
+    );
+    assert_eq!(ack.status(), 202, "notifications get no body");
+
+    fixture.log_step("tools/list over SSE");
+    let response = server.post(
+        &rpc(2, "tools/list", json!({})),
+        "application/json, text/event-stream",
+        Some(&session),
+    );
+    assert_eq!(response.status(), 200);
+    assert!(
+        response.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("text/event-stream")
+    );
+    let events = response.text().expect("SSE body");
+    let data = events
+        .lines()
+        .find_map(|line| line.strip_prefix("data: "))
+        .expect("SSE data line");
+    let tools: Value = serde_json::from_str(data).expect("tools/list JSON");
+    assert_eq!(tools["id"], 2);
+    let names: Vec<&str> = tools["result"]["tools"]
+        .as_array()
+        .expect("tools array")
+        .iter()
+        .filter_map(|tool| tool["name"].as_str())
+        .collect();
+    assert!(names.contains(&"search") && names.contains(&"show"));
+
+    fixture.log_step("tools/call from two clients reusing the same id");
+    let handles: Vec<_> = ["rust-error-handling", "python-async"]
+        .into_iter()
+        .map(|skill| {
+            let url = server.url();
+            std::thread::spawn(move || {
+                let body = rpc(
+                    7,
+                    "tools/call",
+                    json!({ "name": "show", "arguments": { "skill": skill } }),
+                );
+                let text = reqwest::blocking::Client::new()
+                    .post(url)
+                    .header("Content-Type", "application/json")
+                    .header("Accept", "application/json")
+                    .body(body.to_string())
+                    .send()
+                    .expect("tools/call request")
+                    .text()
+                    .expect("tools/call body");
+                (skill, text)
+            })
+        })
+        .collect();
+    for handle in handles {
+        let (skill, raw) = handle.join().expect("client thread");
+        assert!(
+            !raw.contains('\x1b'),
+            "Response should not contain ANSI codes"
+        );
+        let response: Value = serde_json::from_str(&raw).expect("tools/call JSON");
+        assert_eq!(response["id"], 7);
+        assert_ne!(response["result"]["isError"], true, "{raw}");
+        let text = response["result"]["content"][0]["text"]
+            .as_str()
+            .expect("tool text");
+        assert!(text.contains(skill), "{skill} answered with: {text}");
+    }
+
+    fixture.log_step("Unknown sessions are rejected");
+    let stale = server.post(&rpc(9, "ping", json!({})), "application/json", Some("nope"));
+    assert_eq!(stale.status(), 404);
+
+    #[cfg(unix)]
+    {
+        fixture.log_step("SIGINT closes the listener");
+        let pid = i32::try_from(server.child.id()).expect("pid fits in i32");
+        // SAFETY: sending a signal to our own child process.
+        unsafe {
+            libc::kill(pid, libc::SIGINT);
+        }
+        let start = Instant::now();
+        let status = loop {
+            if let Some(status) = server.child.try_wait().expect("wait for server") {
+                break status;
+            }
+            assert!(
+                start.elapsed() < Duration::from_secs(10),
+                "server did not exit on SIGINT"
+            );
+            std::thread::sleep(Duration::from_millis(50));
+        };
+        assert!(status.success(), "server exited with {status}");
+        assert!(std::net::TcpStream::connect(server.addr).is_err());
+    }
+
+    Ok(())
+}
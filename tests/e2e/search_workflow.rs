@@ -2,7 +2,7 @@
 //!
 //! Covers BM25, semantic, and hybrid search modes plus filters and caching.
 
-use super::fixture::E2EFixture;
+use super::fixture::{CommandOutput, E2EFixture};
 use ms::error::Result;
 use ms::search::embeddings::HashEmbedder;
 use ms::storage::Database;
@@ -450,3 +450,104 @@ fn test_search_no_results() -> Result<()> {
 
     Ok(())
 }
+
+const SKILL_RETRY_GUIDE: &str = r#"---
+name: Retry Guide
+description: General retry advice
+tags: [retry]
+---
+
+# Retry Guide
+
+Wrap flaky calls in a backoffwidget. Tune the backoffwidget per call site.
+"#;
+
+const SKILL_APP_RETRY: &str = r#"---
+name: App Retry
+description: How the app crate retries
+tags: [retry, rust]
+---
+
+# App Retry
+
+The app crate wraps its HTTP client in a backoffwidget with three attempts.
+"#;
+
+/// A workspace with a nested crate that has its own project skills, and an
+/// unrelated project next to it.
+fn setup_context_fixture(scenario: &str) -> Result<E2EFixture> {
+    let mut fixture = E2EFixture::new(scenario);
+    let output = fixture.init();
+    fixture.assert_success(&output, "init");
+
+    fixture.log_step("Create nested projects");
+    let mono = fixture.root.join("mono");
+    std::fs::create_dir_all(mono.join("crates/app/src"))?;
+    std::fs::write(mono.join("Cargo.toml"), "[workspace]\n")?;
+    std::fs::write(mono.join("crates/app/Cargo.toml"), "[package]\n")?;
+    std::fs::create_dir_all(fixture.root.join("webapp"))?;
+    std::fs::write(fixture.root.join("webapp/package.json"), "{}\n")?;
+    let app_skill = mono.join("crates/app/skills/app-retry");
+    std::fs::create_dir_all(&app_skill)?;
+    std::fs::write(app_skill.join("SKILL.md"), SKILL_APP_RETRY)?;
+
+    for (key, value) in [
+        ("skill_paths.global", r#"["./global_skills"]"#),
+        ("skill_paths.project", r#"["./mono/crates/app/skills"]"#),
+        ("search.context.project_boost", "4.0"),
+    ] {
+        let output = fixture.run_ms(&["--robot", "config", key, value]);
+        fixture.assert_success(&output, key);
+    }
+    fixture.create_skill_in_layer("retry-guide", SKILL_RETRY_GUIDE, "global")?;
+
+    let output = fixture.run_ms(&["--robot", "index"]);
+    fixture.assert_success(&output, "index");
+    Ok(fixture)
+}
+
+fn ranked_ids(output: &CommandOutput) -> Vec<String> {
+    output.json()["results"]
+        .as_array()
+        .expect("results array")
+        .iter()
+        .map(|r| r["id"].as_str().unwrap_or_default().to_string())
+        .collect()
+}
+
+#[test]
+fn test_search_ranks_for_project_context() -> Result<()> {
+    let mut fixture = setup_context_fixture("search_project_context")?;
+    let search = [
+        "--robot",
+        "search",
+        "backoffwidget",
+        "--search-type",
+        "bm25",
+    ];
+
+    fixture.log_step("Search outside the workspace");
+    let outside = fixture.run_ms(&[&search[..], &["--context-dir", "webapp"][..]].concat());
+    fixture.assert_success(&outside, "search outside");
+    assert_eq!(ranked_ids(&outside)[0], "retry-guide");
+    assert_eq!(outside.json()["context"]["project_types"][0], "node");
+
+    fixture.log_step("Search inside the nested crate");
+    let inside =
+        fixture.run_ms(&[&search[..], &["--context-dir", "mono/crates/app/src"][..]].concat());
+    fixture.assert_success(&inside, "search inside");
+    assert_eq!(ranked_ids(&inside)[0], "app-retry");
+    let root = inside.json()["context"]["root"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+    assert!(root.ends_with("crates/app"), "context root {root}");
+
+    fixture.log_step("Same directory with --no-context");
+    let off = fixture.run_ms(&[&search[..], &["--no-context"][..]].concat());
+    fixture.assert_success(&off, "search no context");
+    assert_eq!(ranked_ids(&off)[0], "retry-guide");
+    assert!(off.json().get("context").is_none());
+
+    Ok(())
+}
@@ -10,6 +10,7 @@
 //! - Show a skill that has dependencies listed
 //! - Show in plain output format
 //! - Show in JSON output format (--robot)
+//! - Show and diff a skill under project overrides (`.ms/overrides.toml`)
 
 use super::fixture::E2EFixture;
 use ms::error::Result;
@@ -660,3 +661,175 @@ fn test_show_full_with_deps() -> Result<()> {
     fixture.generate_report();
     Ok(())
 }
+
+/// `--quality` explains each score component and flags a stale index.
+#[test]
+fn test_show_quality_breakdown() -> Result<()> {
+    let mut fixture = setup_show_fixture("show_quality_breakdown")?;
+
+    fixture.log_step("Show quality breakdown as JSON");
+    let output = fixture.run_ms(&["--robot", "show", "rust-error-handling", "--quality"]);
+    fixture.assert_success(&output, "show --quality");
+    let json = output.json();
+    let quality = &json["skill"]["quality"];
+    let components = quality["components"]
+        .as_array()
+        .expect("quality.components should be an array");
+    let names: Vec<&str> = components
+        .iter()
+        .filter_map(|c| c["name"].as_str())
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            "structure",
+            "content",
+            "evidence",
+            "usage",
+            "toolchain",
+            "freshness"
+        ]
+    );
+    let content = &components[1];
+    assert!(content["weight"].as_f64().is_some());
+    let no_examples = content["issues"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|issue| issue["kind"] == "no_examples")
+        .expect("the fixture skill has no examples");
+    assert!(
+        no_examples["section"].is_string(),
+        "issue should point at a section"
+    );
+    assert_eq!(quality["stale"], false, "{quality}");
+
+    fixture.log_step("Human output degrades to plain text");
+    let output = fixture.run_ms(&["show", "rust-error-handling", "--quality"]);
+    fixture.assert_success(&output, "show --quality (human)");
+    fixture.assert_output_contains(&output, "Recomputed");
+    fixture.assert_output_contains(&output, "structure");
+    assert!(!output.stdout.contains("\x1b["));
+
+    fixture.log_step("Edit the skill without reindexing");
+    let expanded = format!(
+        "{SKILL_RUST_ERRORS}\n## Examples\n\n```rust\nfn parse(s: &str) -> Result<u32, std::num::ParseIntError> {{\n    s.parse()\n}}\n```\n\n## Pitfalls\n\n{}\n",
+        "Avoid unwrap in library code; return errors to the caller instead. ".repeat(20)
+    );
+    fixture.create_skill("rust-error-handling", &expanded)?;
+    let output = fixture.run_ms(&["--robot", "show", "rust-error-handling", "--quality"]);
+    fixture.assert_success(&output, "show --quality after edit");
+    assert_eq!(output.json()["skill"]["quality"]["stale"], true);
+
+    let output = fixture.run_ms(&["show", "rust-error-handling", "--quality"]);
+    fixture.assert_output_contains(&output, "ms index");
+
+    fixture.generate_report();
+    Ok(())
+}
+
+const SKILL_RUST_STYLE: &str = r#"---
+name: Rust Style
+description: Community Rust style
+tags: [rust, style]
+version: 1.0.0
+---
+
+# Rust Style
+
+Community style rules.
+
+## Rules
+
+<!-- ms:anchor line-length -->
+Use 100 columns.
+
+<!-- ms:anchor tabs -->
+Indent with tabs.
+"#;
+
+const PROJECT_OVERRIDES: &str = r#"
+[override.rust-style.blocks.line-length]
+content = "Use 120 columns."
+
+[override.rust-style.blocks.tabs]
+remove = true
+
+[override.rust-style.blocks.missing]
+content = "Targets nothing."
+
+[[override.rust-style.add]]
+section = "rules"
+id = "ci-command"
+type = "command"
+content = "cargo xtask ci"
+
+[override.ghost-skill.metadata]
+description = "No such skill"
+"#;
+
+#[test]
+fn test_show_and_diff_project_overrides() -> Result<()> {
+    let mut fixture = setup_show_fixture("show_project_overrides")?;
+    fixture.create_skill("rust-style", SKILL_RUST_STYLE)?;
+    let output = fixture.run_ms(&["--robot", "index"]);
+    fixture.assert_success(&output, "index");
+
+    fixture.log_step("Write .ms/overrides.toml");
+    std::fs::write(fixture.ms_root.join("overrides.toml"), PROJECT_OVERRIDES)?;
+
+    fixture.log_step("Show the effective skill");
+    let output = fixture.run_ms(&["--robot", "show", "rust-style", "--full"]);
+    fixture.assert_success(&output, "show overridden skill");
+    let json = output.json();
+    let body = json["skill"]["body"].as_str().unwrap();
+    assert!(body.contains("Use 120 columns."), "{body}");
+    assert!(body.contains("cargo xtask ci"), "{body}");
+    assert!(!body.contains("Indent with tabs."), "{body}");
+    let overrides = &json["skill"]["overrides"];
+    let actions: Vec<(&str, &str)> = overrides["blocks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|b| {
+            (
+                b["block_id"].as_str().unwrap(),
+                b["action"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        actions,
+        [
+            ("line-length", "replaced"),
+            ("tabs", "removed"),
+            ("ci-command", "added")
+        ]
+    );
+    assert!(
+        overrides["source"]
+            .as_str()
+            .unwrap()
+            .ends_with("overrides.toml")
+    );
+
+    fixture.log_step("Skills without overrides are shown as indexed");
+    let output = fixture.run_ms(&["--robot", "show", "rust-error-handling"]);
+    assert!(output.json()["skill"].get("overrides").is_none());
+
+    fixture.log_step("Diff upstream against effective");
+    let output = fixture.run_ms(&["--robot", "diff", "rust-style", "--overrides"]);
+    fixture.assert_success(&output, "diff --overrides");
+    let json = output.json();
+    assert_eq!(json["same"], false);
+    assert_eq!(json["sections"][0]["section_id"], "rules");
+
+    fixture.log_step("Doctor warns about targets that match nothing");
+    let output = fixture.run_ms(&["doctor", "--check", "overrides"]);
+    fixture.assert_success(&output, "doctor --check overrides");
+    fixture.assert_output_contains(&output, "rust-style: block 'missing' not found");
+    fixture.assert_output_contains(&output, "ghost-skill: skill not found");
+
+    fixture.generate_report();
+    Ok(())
+}
@@ -103,6 +103,23 @@ Profile first, optimize second. Measure everything.
 - Benchmark with criterion
 "#;
 
+const SKILL_DB_MIGRATIONS: &str = r#"---
+name: Database Migrations
+description: Writing safe, reversible database schema migrations
+tags: [database, sql, migrations, postgres]
+---
+
+# Database Migrations
+
+Change the database schema in small, reversible steps.
+
+## Rules
+
+- Add columns as nullable, backfill, then add constraints
+- Never rename a column in one migration
+- Test the down migration against a copy of production data
+"#;
+
 // =============================================================================
 // HELPER: Setup fixture with skills
 // =============================================================================
@@ -701,3 +718,94 @@ fn test_suggest_explain_mode() -> Result<()> {
 
     Ok(())
 }
+
+// =============================================================================
+// TEST: Suggestions for a described task
+// =============================================================================
+
+#[test]
+fn test_suggest_task_surfaces_matching_skill() -> Result<()> {
+    let mut fixture = setup_suggest_fixture("suggest_task")?;
+
+    fixture.log_step("Add a database skill");
+    fixture.create_skill("database-migrations", SKILL_DB_MIGRATIONS)?;
+    let output = fixture.run_ms(&["--robot", "index"]);
+    fixture.assert_success(&output, "index");
+
+    fixture.log_step("Create Rust project context files");
+    std::fs::write(
+        fixture.root.join("Cargo.toml"),
+        "[package]\nname = \"test-project\"\nversion = \"0.1.0\"\n",
+    )?;
+    std::fs::create_dir_all(fixture.root.join("src"))?;
+    std::fs::write(fixture.root.join("src/main.rs"), "fn main() {}")?;
+    let cwd = fixture.root.display().to_string();
+
+    let suggested = |json: &serde_json::Value| -> Vec<String> {
+        json["suggestions"]
+            .as_array()
+            .expect("suggestions array")
+            .iter()
+            .filter_map(|item| item["skill_id"].as_str().map(str::to_string))
+            .collect()
+    };
+
+    fixture.log_step("Suggest from the Rust context alone");
+    let output = fixture.run_ms(&[
+        "--robot",
+        "suggest",
+        "--cwd",
+        &cwd,
+        "--limit",
+        "1",
+        "--reset-bandit",
+        "--reset-cooldowns",
+    ]);
+    fixture.assert_success(&output, "suggest without task");
+    let json = output.json();
+    println!("[VERIFY] Context-only suggestions: {:?}", suggested(&json));
+
+    fixture.log_step("Suggest for a database task in the same context");
+    let output = fixture.run_ms(&[
+        "--robot",
+        "suggest",
+        "--cwd",
+        &cwd,
+        "--limit",
+        "1",
+        "--task",
+        "write a database migration that adds a column to the postgres users table",
+    ]);
+    fixture.assert_success(&output, "suggest with task");
+    let json = output.json();
+    let items = json["suggestions"].as_array().expect("suggestions array");
+    assert_eq!(
+        suggested(&json),
+        ["database-migrations"],
+        "task should surface the database skill: {json}"
+    );
+    assert_eq!(items[0]["origin"], "task");
+    assert!(
+        items[0]["reason"]
+            .as_str()
+            .is_some_and(|reason| reason.contains("task match")),
+        "reason should mention the task: {json}"
+    );
+
+    fixture.log_step("Repeat the task: cooldowns don't apply");
+    let output = fixture.run_ms(&[
+        "--robot",
+        "suggest",
+        "--cwd",
+        &cwd,
+        "--limit",
+        "1",
+        "--task",
+        "write a database migration that adds a column to the postgres users table",
+    ]);
+    fixture.assert_success(&output, "suggest with task again");
+    assert_eq!(suggested(&output.json()), ["database-migrations"]);
+
+    fixture.checkpoint("suggest_task_done");
+    Ok(())
+}
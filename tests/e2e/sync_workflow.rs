@@ -7,6 +7,7 @@
 //! - Conflict detection and resolution
 //! - Dry-run mode verification
 //! - Status checking
+//! - Team git repositories (`ms sync --remote <url>`)
 //!
 //! Uses filesystem remotes for network-independent testing.
 
@@ -593,3 +594,87 @@ fn test_sync_pull_only() -> Result<()> {
 
     Ok(())
 }
+
+/// Test syncing with a team git repository.
+///
+/// Steps:
+/// 1. Initialize ms and create a shared and a private skill
+/// 2. Dry-run against an empty bare repository: only the shared skill is outgoing
+/// 3. Sync for real and verify the shared skill was pushed
+#[test]
+fn test_sync_team_remote_pushes_shared_skills() -> Result<()> {
+    let mut fixture = E2EFixture::new("sync_team_remote");
+
+    let team_dir = TempDir::new()?;
+    let team_path = team_dir.path().join("skills.git");
+    git2::Repository::init_bare(&team_path)?;
+    let team_url = team_path.to_str().unwrap();
+
+    fixture.log_step("Initialize ms directory");
+    let output = fixture.init();
+    fixture.assert_success(&output, "init");
+
+    fixture.log_step("Create shared and private skills");
+    fixture.create_skill(
+        "team-shared",
+        r#"---
+id: team-shared
+name: Team Shared
+description: Shared with the team
+share: true
+---
+
+# Team Shared
+
+## Rules
+
+Review every migration.
+"#,
+    )?;
+    fixture.create_skill(
+        "team-private",
+        r#"---
+id: team-private
+name: Team Private
+description: Stays on this machine
+---
+
+# Team Private
+"#,
+    )?;
+    let output = fixture.run_ms(&["--robot", "index"]);
+    fixture.assert_success(&output, "index");
+    fixture.checkpoint("post_index");
+
+    fixture.log_step("Dry-run sync with the team repository");
+    let output = fixture.run_ms(&["--robot", "sync", "--remote", team_url, "--dry-run"]);
+    fixture.assert_success(&output, "sync --remote --dry-run");
+    let report = &output.json()["reports"][0];
+    let pushed = report["pushed"].to_string();
+    assert!(pushed.contains("team-shared"), "outgoing: {pushed}");
+    assert!(!pushed.contains("team-private"), "outgoing: {pushed}");
+    assert!(
+        git2::Repository::open_bare(&team_path)?.is_empty()?,
+        "dry run must not push"
+    );
+
+    fixture.log_step("Sync with the team repository");
+    let output = fixture.run_ms(&["--robot", "sync", "--remote", team_url]);
+    fixture.assert_success(&output, "sync --remote");
+    fixture.checkpoint("post_team_sync");
+
+    let team = git2::Repository::open_bare(&team_path)?;
+    let tree = team.head()?.peel_to_tree()?;
+    assert!(
+        tree.get_path(std::path::Path::new(
+            "skills/by-id/team-shared/skill.spec.json"
+        ))
+        .is_ok()
+    );
+    assert!(
+        tree.get_path(std::path::Path::new("skills/by-id/team-private"))
+            .is_err()
+    );
+
+    Ok(())
+}
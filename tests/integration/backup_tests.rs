@@ -11,8 +11,8 @@ fn backup_create_and_restore_roundtrip() {
     let backup = fixture.run_ms(&["--robot", "backup", "create", "--id", "roundtrip"]);
     assert!(backup.success, "backup create failed: {}", backup.stderr);
 
-    let backup_dir = fixture.ms_root.join("backups").join("roundtrip");
-    assert!(backup_dir.exists(), "backup dir missing");
+    let archive = fixture.ms_root.join("backups").join("roundtrip.tar.zst");
+    assert!(archive.exists(), "backup archive missing");
 
     std::fs::write(&fixture.config_path, "changed = true\n").expect("write config");
 
@@ -34,3 +34,29 @@ fn backup_restore_missing_id_errors() {
     assert!(!restore.success, "restore should fail for missing backup");
     assert!(restore.stdout.contains("\"error\""));
 }
+
+#[test]
+fn restore_archive_from_file_rebuilds_index() {
+    let fixture = TestFixture::new("backup_restore_file");
+    let init = fixture.init();
+    assert!(init.success, "init failed: {}", init.stderr);
+
+    let out = fixture.root.join("elsewhere").join("ms.tar.zst");
+    let out_arg = out.to_string_lossy().to_string();
+    let backup = fixture.run_ms(&["--robot", "backup", "create", "--out", &out_arg]);
+    assert!(backup.success, "backup create failed: {}", backup.stderr);
+    let created = backup.json();
+    assert_eq!(created["format"], 2);
+    assert!(created["schema_version"].is_u64());
+    assert!(created["skill_count"].is_u64());
+    assert!(out.exists(), "archive missing at --out path");
+
+    let restore = fixture.run_ms(&["--robot", "restore", &out_arg]);
+    assert!(!restore.success, "restore without --approve should fail");
+
+    let restore = fixture.run_ms(&["--robot", "restore", &out_arg, "--approve"]);
+    assert!(restore.success, "restore failed: {}", restore.stderr);
+    let report = restore.json();
+    assert_eq!(report["status"], "ok");
+    assert_eq!(report["index"]["indexed"], report["index"]["skills"]);
+}
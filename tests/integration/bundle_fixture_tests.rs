@@ -38,6 +38,8 @@ fn minimal_manifest(skill_name: &str) -> BundleManifest {
             version: Some("1.0.0".to_string()),
             hash: None,
             optional: false,
+            changelog: Vec::new(),
+            resources: Vec::new(),
         }],
         dependencies: vec![],
         checksum: None,
@@ -66,6 +68,8 @@ fn multi_skill_manifest() -> BundleManifest {
                 version: Some("1.0.0".to_string()),
                 hash: None,
                 optional: false,
+                changelog: Vec::new(),
+                resources: Vec::new(),
             },
             BundledSkill {
                 name: "skill-two".to_string(),
@@ -73,6 +77,8 @@ fn multi_skill_manifest() -> BundleManifest {
                 version: Some("1.0.0".to_string()),
                 hash: None,
                 optional: false,
+                changelog: Vec::new(),
+                resources: Vec::new(),
             },
             BundledSkill {
                 name: "optional-skill".to_string(),
@@ -80,6 +86,8 @@ fn multi_skill_manifest() -> BundleManifest {
                 version: Some("1.0.0".to_string()),
                 hash: None,
                 optional: true,
+                changelog: Vec::new(),
+                resources: Vec::new(),
             },
         ],
         dependencies: vec![],
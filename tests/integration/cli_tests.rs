@@ -53,6 +53,66 @@ fn test_init_idempotent() {
     );
 }
 
+#[test]
+fn test_init_project_detects_assets_and_is_idempotent() {
+    let fixture = TestFixture::new("test_init_project_detects_assets_and_is_idempotent");
+    let root = &fixture.root;
+    std::fs::create_dir(root.join(".git")).unwrap();
+    std::fs::create_dir_all(root.join(".claude/skills/deploy")).unwrap();
+    std::fs::write(
+        root.join(".claude/skills/deploy/SKILL.md"),
+        "# Deploy\n\nShip it.\n",
+    )
+    .unwrap();
+    std::fs::create_dir_all(root.join(".cursor/rules")).unwrap();
+    std::fs::write(
+        root.join(".cursor/rules/errors.mdc"),
+        "---\ndescription: Error handling\n---\n- Always propagate errors with context.\n",
+    )
+    .unwrap();
+
+    let output = fixture.run_ms(&["--robot", "init"]);
+    assert!(output.success, "init failed: {}", output.stderr);
+    let json: Value = serde_json::from_str(&output.stdout).expect("init should print JSON");
+    assert_eq!(json["status"], "ok");
+    assert_eq!(json["vcs"], "git");
+    let actions: Vec<_> = json["assets"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|asset| {
+            (
+                asset["path"].as_str().unwrap(),
+                asset["action"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        actions,
+        [(".claude/skills", "register"), (".cursor/rules", "import")]
+    );
+
+    let config = std::fs::read_to_string(&fixture.config_path).unwrap();
+    assert!(config.contains(r#"project = ["./skills", ".ms/skills", ".claude/skills"]"#));
+    let gitignore = std::fs::read_to_string(root.join(".gitignore")).unwrap();
+    assert!(gitignore.contains(".ms/*\n!.ms/config.toml\n"));
+    assert!(root.join(".ms/skills/errors/SKILL.md").exists());
+
+    let again = fixture.run_ms(&["--robot", "init"]);
+    assert!(again.success, "second init failed: {}", again.stderr);
+    let json: Value = serde_json::from_str(&again.stdout).unwrap();
+    assert_eq!(json["status"], "already_initialized");
+    assert_eq!(json["changes"], serde_json::json!([]));
+    assert_eq!(
+        std::fs::read_to_string(&fixture.config_path).unwrap(),
+        config
+    );
+    assert_eq!(
+        std::fs::read_to_string(root.join(".gitignore")).unwrap(),
+        gitignore
+    );
+}
+
 #[test]
 fn test_index_empty_directory() {
     let mut fixture = TestFixture::new("test_index_empty_directory");
@@ -101,6 +161,371 @@ fn test_index_with_skills() {
     );
 }
 
+#[test]
+fn test_index_skips_ignored_skill_files() {
+    let skills = vec![TestSkill::new(
+        "kept-skill",
+        "A skill outside ignored paths",
+    )];
+    let mut fixture = TestFixture::new("test_index_skips_ignored_skill_files");
+    let init = fixture.init();
+    assert!(init.success, "init failed: {}", init.stderr);
+    for skill in &skills {
+        fixture.add_skill(skill);
+    }
+    std::fs::write(fixture.skills_dir.join(".gitignore"), "generated/\n")
+        .expect("write .gitignore");
+    let nested = fixture.skills_dir.join("generated").join("junk-skill");
+    std::fs::create_dir_all(&nested).expect("create nested dir");
+    std::fs::write(
+        nested.join("SKILL.md"),
+        TestSkill::new("junk-skill", "Generated copy that must not be indexed").to_markdown(),
+    )
+    .expect("write nested skill");
+
+    let output = fixture.run_ms(&["--robot", "index"]);
+    assert!(output.success, "index failed: {}", output.stderr);
+    assert!(output.json()["ignored"].as_u64().unwrap_or(0) >= 1);
+
+    fixture.open_db();
+    fixture.verify_db_state(
+        |db| {
+            let ids: Vec<String> = db
+                .prepare("SELECT id FROM skills")
+                .and_then(|mut stmt| {
+                    stmt.query_map([], |r| r.get(0))?
+                        .collect::<std::result::Result<Vec<String>, _>>()
+                })
+                .unwrap_or_default();
+            ids == ["kept-skill"]
+        },
+        "Only the skill outside generated/ should be indexed",
+    );
+
+    let output = fixture.run_ms(&["--robot", "index", "--no-ignore"]);
+    assert!(
+        output.success,
+        "index --no-ignore failed: {}",
+        output.stderr
+    );
+    assert_eq!(output.json()["ignored"], Value::from(0));
+    fixture.verify_db_state(
+        |db| {
+            let count: i64 = db
+                .query_row("SELECT COUNT(*) FROM skills", [], |r| r.get(0))
+                .unwrap_or(0);
+            count == 2
+        },
+        "--no-ignore should index the generated skill too",
+    );
+}
+
+#[test]
+fn test_index_records_skill_resources() {
+    let fixture = TestFixture::new("test_index_records_skill_resources");
+    let init = fixture.init();
+    assert!(init.success, "init failed: {}", init.stderr);
+    fixture.add_skill(&TestSkill::with_content(
+        "res-skill",
+        "# res-skill\n\nFill in the template.\n\n## Overview\n\nUse [the template](resources/template.md) and [the schema](schemas/input.json).\n",
+    ));
+    let skill_dir = fixture.skills_dir.join("res-skill");
+    std::fs::create_dir_all(skill_dir.join("resources")).expect("create resources");
+    std::fs::create_dir_all(skill_dir.join("schemas")).expect("create schemas");
+    std::fs::write(skill_dir.join("resources/template.md"), "## Summary\n")
+        .expect("write template");
+    std::fs::write(
+        skill_dir.join("schemas/input.json"),
+        format!("{{\"pad\": \"{}\"}}", "x".repeat(64)),
+    )
+    .expect("write schema");
+
+    let output = fixture.run_ms(&["--robot", "index"]);
+    assert!(output.success, "index failed: {}", output.stderr);
+    let archived = fixture
+        .ms_root
+        .join("archive/skills/by-id/res-skill/resources/template.md");
+    assert!(archived.exists(), "resource not archived");
+
+    let output = fixture.run_ms(&["--robot", "show", "res-skill", "--resources"]);
+    assert!(output.success, "show failed: {}", output.stderr);
+    let json = output.json();
+    let paths: Vec<&str> = json["resources"]
+        .as_array()
+        .expect("resources")
+        .iter()
+        .filter_map(|r| r["path"].as_str())
+        .collect();
+    assert_eq!(paths, ["resources/template.md", "schemas/input.json"]);
+
+    // A resource edit alone re-indexes the skill
+    std::fs::write(
+        skill_dir.join("resources/template.md"),
+        "## Summary\n\n## Risks\n",
+    )
+    .expect("edit template");
+    let output = fixture.run_ms(&["--robot", "index"]);
+    assert!(output.success, "re-index failed: {}", output.stderr);
+    assert_eq!(
+        std::fs::read_to_string(&archived).expect("read archived"),
+        "## Summary\n\n## Risks\n"
+    );
+
+    let output = fixture.run_ms(&[
+        "--robot",
+        "load",
+        "res-skill",
+        "--with-resources",
+        "--resource-cap",
+        "32",
+    ]);
+    assert!(output.success, "load failed: {}", output.stderr);
+    let resources = output.json()["data"]["resources"].clone();
+    assert_eq!(
+        resources[0]["content"].as_str(),
+        Some("## Summary\n\n## Risks\n")
+    );
+    assert_eq!(resources[1]["skipped"].as_str(), Some("over size cap"));
+}
+
+/// Every id of a robot `search`/`list` run, following `next_cursor` in
+/// pages of 3.
+fn collect_pages(fixture: &TestFixture, args: &[&str], key: &str) -> Vec<String> {
+    let mut ids = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let mut argv = vec!["--robot"];
+        argv.extend_from_slice(args);
+        argv.extend_from_slice(&["--limit", "3"]);
+        if let Some(cursor) = &cursor {
+            argv.extend_from_slice(&["--cursor", cursor]);
+        }
+        let output = fixture.run_ms(&argv);
+        assert!(output.success, "{args:?} failed: {}", output.stdout);
+        let json = output.json();
+        let page = json[key].as_array().expect("results");
+        assert!(page.len() <= 3, "page larger than --limit");
+        ids.extend(
+            page.iter()
+                .filter_map(|r| r["id"].as_str().map(String::from)),
+        );
+        match json["next_cursor"].as_str() {
+            Some(next) => cursor = Some(next.to_string()),
+            None => break,
+        }
+    }
+    ids
+}
+
+#[test]
+fn test_search_and_list_cursor_pagination() {
+    let fixture = TestFixture::new("test_search_and_list_cursor_pagination");
+    let init = fixture.init();
+    assert!(init.success, "init failed: {}", init.stderr);
+    for i in 0..8 {
+        fixture.add_skill(&TestSkill::new(
+            &format!("paging-skill-{i}"),
+            "Paging through cursor results",
+        ));
+    }
+    let output = fixture.run_ms(&["--robot", "index"]);
+    assert!(output.success, "index failed: {}", output.stderr);
+
+    let mut expected: Vec<String> = (0..8).map(|i| format!("paging-skill-{i}")).collect();
+    for (args, key) in [
+        (
+            &["search", "paging", "--search-type", "bm25", "--no-context"][..],
+            "results",
+        ),
+        (&["list"][..], "skills"),
+    ] {
+        let mut ids = collect_pages(&fixture, args, key);
+        let count = ids.len();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), count, "{args:?} returned duplicates");
+        assert_eq!(ids, expected, "{args:?} skipped results");
+    }
+
+    // A re-index moves the generation, so the old cursor is refused
+    let output = fixture.run_ms(&["--robot", "list", "--limit", "3"]);
+    let cursor = output.json()["next_cursor"]
+        .as_str()
+        .expect("next_cursor")
+        .to_string();
+    fixture.add_skill(&TestSkill::new("paging-skill-8", "Paging added later"));
+    let output = fixture.run_ms(&["--robot", "index"]);
+    assert!(output.success, "re-index failed: {}", output.stderr);
+    expected.push("paging-skill-8".to_string());
+
+    let output = fixture.run_ms(&["--robot", "list", "--limit", "3", "--cursor", &cursor]);
+    assert!(!output.success, "stale cursor accepted");
+    assert_eq!(output.json()["code"], "cursor_stale");
+    assert_eq!(
+        collect_pages(&fixture, &["list"], "skills").len(),
+        expected.len()
+    );
+}
+
+#[test]
+fn test_index_rescore_applies_changed_weights() {
+    let skills = vec![
+        TestSkill::new("rescore-one", "First skill to rescore"),
+        TestSkill::new("rescore-two", "Second skill to rescore"),
+    ];
+    let fixture = TestFixture::with_indexed_skills("test_index_rescore", &skills);
+
+    let stored = |fixture: &TestFixture| -> (f64, Option<String>) {
+        let db = fixture.db.as_ref().expect("db open");
+        let score = db
+            .query_row(
+                "SELECT quality_score FROM skills WHERE id = 'rescore-one'",
+                [],
+                |r| r.get(0),
+            )
+            .expect("stored score");
+        let hash = db
+            .query_row(
+                "SELECT value FROM config WHERE key = 'quality.weights_hash'",
+                [],
+                |r| r.get(0),
+            )
+            .ok();
+        (score, hash)
+    };
+    let (indexed_score, indexed_hash) = stored(&fixture);
+    assert!(indexed_hash.is_some(), "index should record the weights");
+
+    let mut config = std::fs::read_to_string(&fixture.config_path).expect("read config");
+    config.push_str("\n[quality]\nevidence_weight = 0.0\nusage_weight = 0.0\n");
+    std::fs::write(&fixture.config_path, config).expect("write config");
+
+    let output = fixture.run_ms(&["--robot", "index", "--rescore", "--dry-run"]);
+    assert!(output.success, "dry run failed: {}", output.stderr);
+    let json = output.json();
+    assert_eq!(json["rescore"]["dry_run"], Value::from(true));
+    assert_eq!(json["rescore"]["rescored"], Value::from(2));
+    assert_eq!(stored(&fixture), (indexed_score, indexed_hash.clone()));
+
+    let output = fixture.run_ms(&["--robot", "index", "--rescore"]);
+    assert!(output.success, "rescore failed: {}", output.stderr);
+    let json = output.json();
+    assert_eq!(json["rescore"]["changed"], Value::from(2));
+    let (score, hash) = stored(&fixture);
+    assert!(score > indexed_score, "{score} <= {indexed_score}");
+    assert_eq!(
+        hash.as_deref(),
+        json["rescore"]["weights_hash"].as_str(),
+        "rescore should record the new weights"
+    );
+    assert_ne!(hash, indexed_hash);
+}
+
+#[test]
+fn test_pack_writes_manifest_and_refreshes_on_change() {
+    let skills = vec![
+        TestSkill::with_content(
+            "pack-terraform",
+            "---\nname: pack-terraform\nrequires: [pack-shell]\n---\n\n# pack-terraform\n\nPlan before apply.\n\n## Overview\n\nRun terraform plan and review the diff.\n",
+        ),
+        TestSkill::new("pack-shell", "Shell basics for deploy scripts"),
+        TestSkill::with_content(
+            "pack-pulumi",
+            "---\nname: pack-pulumi\nconflicts_with: [pack-terraform]\n---\n\n# pack-pulumi\n\nPulumi deploys.\n\n## Overview\n\nUse pulumi up.\n",
+        ),
+    ];
+    let fixture = TestFixture::with_indexed_skills("test_pack", &skills);
+    let pack = fixture.root.join("pack.md");
+    let pack_arg = pack.to_str().expect("utf-8 path");
+
+    let output = fixture.run_ms(&[
+        "--robot",
+        "pack",
+        "--skills",
+        "pack-terraform,pack-pulumi",
+        "--budget",
+        "4000",
+        "--out",
+        pack_arg,
+    ]);
+    assert!(output.success, "pack failed: {}", output.stderr);
+    let json = output.json();
+    let members: Vec<&str> = json["pack"]["members"]
+        .as_array()
+        .expect("members")
+        .iter()
+        .filter_map(|m| m["skill_id"].as_str())
+        .collect();
+    assert_eq!(members, ["pack-terraform", "pack-shell"]);
+    assert_eq!(json["pack"]["dropped"][0]["skill_id"], "pack-pulumi");
+    assert_eq!(json["pack"]["dropped"][0]["reason"], "conflict");
+
+    let written = std::fs::read_to_string(&pack).expect("read pack");
+    assert!(written.contains("## Contents"), "{written}");
+    assert!(written.contains("(#skill-pack-shell) - dependency of pack-terraform"));
+    assert!(fixture.root.join("pack.ms-pack.json").exists());
+
+    let output = fixture.run_ms(&["--robot", "pack", "--refresh", pack_arg]);
+    assert!(output.success, "refresh failed: {}", output.stderr);
+    assert_eq!(output.json()["rebuilt"], Value::from(false));
+
+    fixture.add_skill(&TestSkill::new(
+        "pack-shell",
+        "Shell basics, now with set -euo pipefail",
+    ));
+    let output = fixture.run_ms(&["--robot", "index"]);
+    assert!(output.success, "reindex failed: {}", output.stderr);
+    let output = fixture.run_ms(&["--robot", "pack", "--refresh", pack_arg]);
+    assert!(output.success, "refresh failed: {}", output.stderr);
+    let json = output.json();
+    assert_eq!(json["rebuilt"], Value::from(true));
+    assert_eq!(json["changed"], serde_json::json!(["pack-shell"]));
+    let written = std::fs::read_to_string(&pack).expect("read pack");
+    assert!(written.contains("set -euo pipefail"), "{written}");
+}
+
+#[test]
+fn test_show_changelog_groups_changes_by_version() {
+    let skills = vec![TestSkill::with_content(
+        "changelog-skill",
+        "---\nname: changelog-skill\nversion: 1.0.0\n---\n\n# changelog-skill\n\nDeploy guidance.\n\n## Overview\n\nPlan before apply.\n",
+    )];
+    let fixture = TestFixture::with_indexed_skills("test_show_changelog", &skills);
+    fixture.add_skill(&TestSkill::with_content(
+        "changelog-skill",
+        "---\nname: changelog-skill\nversion: 1.1.0\n---\n\n# changelog-skill\n\nDeploy guidance.\n\n## Overview\n\nPlan, review, then apply.\n",
+    ));
+    let output = fixture.run_ms(&["--robot", "index"]);
+    assert!(output.success, "reindex failed: {}", output.stderr);
+
+    let output = fixture.run_ms(&["--robot", "show", "changelog-skill", "--changelog"]);
+    assert!(output.success, "show failed: {}", output.stderr);
+    let json = output.json();
+    let releases = json["changelog"].as_array().expect("changelog");
+    assert_eq!(releases[0]["version"], "1.1.0");
+    assert_eq!(releases.last().expect("release")["version"], "1.0.0");
+    let metadata = &releases[0]["entries"][0]["metadata"];
+    assert!(
+        metadata
+            .as_array()
+            .expect("metadata")
+            .iter()
+            .any(|change| change["field"] == "version" && change["after"] == "1.1.0"),
+        "{metadata}"
+    );
+
+    let output = fixture.run_ms(&[
+        "--robot",
+        "show",
+        "changelog-skill",
+        "--changelog",
+        "--since",
+        "v1.1.0",
+    ]);
+    assert!(output.success, "show failed: {}", output.stderr);
+    assert_eq!(output.json()["changelog"], serde_json::json!([]));
+}
+
 #[test]
 fn test_list_shows_indexed_skills() {
     let skills = vec![
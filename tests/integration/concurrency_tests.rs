@@ -0,0 +1,93 @@
+//! Concurrent writers against one data directory.
+//!
+//! Each thread stands in for a separate `ms` process: it opens its own
+//! database connection and search index handle on the shared paths and mixes
+//! reads with writes. SQLite busy errors must be retried and index writers
+//! serialized by the writer lock, so every operation has to succeed.
+
+use std::path::Path;
+use std::thread;
+
+use ms::search::tantivy::Bm25Index;
+use ms::storage::Database;
+use ms::storage::sqlite::SkillRecord;
+
+const WORKERS: usize = 6;
+const ROUNDS: usize = 8;
+
+fn skill(id: &str) -> SkillRecord {
+    SkillRecord {
+        id: id.to_string(),
+        name: format!("Skill {id}"),
+        description: format!("Concurrency fixture {id}"),
+        version: Some("0.1.0".to_string()),
+        author: None,
+        source_path: format!("/skills/{id}/SKILL.md"),
+        source_layer: "project".to_string(),
+        git_remote: None,
+        git_commit: None,
+        content_hash: format!("hash-{id}"),
+        body: format!("Retry the deployment when {id} reports a transient failure."),
+        metadata_json: r#"{"tags": ["concurrency"]}"#.to_string(),
+        assets_json: "{}".to_string(),
+        token_count: 12,
+        quality_score: 0.5,
+        indexed_at: "2025-01-01T00:00:00Z".to_string(),
+        modified_at: "2025-01-01T00:00:00Z".to_string(),
+        is_deprecated: false,
+        deprecation_reason: None,
+    }
+}
+
+fn worker(db_path: &Path, index_path: &Path, worker: usize) -> ms::error::Result<()> {
+    let db = Database::open(db_path)?;
+    let index = Bm25Index::open(index_path)?;
+
+    for round in 0..ROUNDS {
+        let record = skill(&format!("worker-{worker}-skill-{round}"));
+        db.upsert_skill(&record)?;
+        assert!(db.get_skill(&record.id)?.is_some());
+        db.list_skills(50, 0)?;
+
+        index.index_skill(&record)?;
+        index.commit()?;
+        index.search("deployment", 10)?;
+
+        if round % 3 == 2 {
+            db.delete_skill(&record.id)?;
+            index.delete_skill(&record.id)?;
+            index.commit()?;
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_concurrent_writers_never_fail_on_locks() {
+    let temp = tempfile::tempdir().expect("create temp dir");
+    let db_path = temp.path().join("ms.db");
+    let index_path = temp.path().join("index");
+
+    // Create the schema and the index once, as `ms init` would
+    Database::open(&db_path).expect("create database");
+    Bm25Index::open(&index_path).expect("create index");
+
+    let handles: Vec<_> = (0..WORKERS)
+        .map(|n| {
+            let db_path = db_path.clone();
+            let index_path = index_path.clone();
+            thread::spawn(move || worker(&db_path, &index_path, n))
+        })
+        .collect();
+    for (n, handle) in handles.into_iter().enumerate() {
+        let result = handle.join().expect("worker panicked");
+        assert!(result.is_ok(), "worker {n} failed: {:?}", result.err());
+    }
+
+    // Every round not deleted survived, in both stores
+    let kept = WORKERS * (ROUNDS - ROUNDS / 3);
+    let db = Database::open(&db_path).expect("reopen database");
+    assert_eq!(db.list_skills(1_000, 0).expect("list skills").len(), kept);
+    let index = Bm25Index::open(&index_path).expect("reopen index");
+    assert_eq!(index.num_docs(), kept as u64);
+}
@@ -6,6 +6,7 @@ mod beads_real_tests;
 mod bundle_fixture_tests;
 mod cli_tests;
 mod composition_tests;
+mod concurrency_tests;
 mod context_detection_tests;
 mod db_state_tests;
 mod error_handling_tests;
@@ -14,6 +15,7 @@ mod fixture_tests;
 mod migration_tests;
 mod more_cli_tests;
 mod output_format_tests;
+mod repo_rules_import_tests;
 mod security_tests;
 mod skill_md_tests;
 // The ubs_staged tests drive a POSIX shell stub, so the whole module is
@@ -34,12 +34,15 @@ fn test_list_filters() {
     assert!(!output.stdout.contains("skill-python"));
     assert!(output.stdout.contains("No skills found"));
 
-    // Filter by multiple tags (OR logic typically, or verify logic)
-    // list.rs implementation: args.tags.iter().any(|t| skill_tags.contains(t)) -> OR logic
-    let output = fixture.run_ms(&["list", "--tags", "rust", "--tags", "python"]);
+    // Repeated tags must all match
+    let output = fixture.run_ms(&["list", "--tag", "rust", "--tag", "backend"]);
     assert!(output.success);
     assert!(output.stdout.contains("skill-rust"));
-    assert!(output.stdout.contains("skill-python"));
+    assert!(!output.stdout.contains("skill-python"));
+
+    let output = fixture.run_ms(&["list", "--tags", "rust", "--tags", "python"]);
+    assert!(output.success);
+    assert!(output.stdout.contains("No skills found"));
 }
 
 #[test]
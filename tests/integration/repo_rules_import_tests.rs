@@ -0,0 +1,124 @@
+//! Import of Cursor rules and Copilot instructions using the
+//! `tests/fixtures/repo_rules` repository layout.
+
+use std::path::PathBuf;
+
+use ms::import::repo_rules::{RuleFileFormat, discover_rule_files, import_repo_rules};
+use ms::import::{GeneratorConfig, ImportHints};
+use ms::lint::rules::all_rules;
+use ms::lint::{RuleCategory, ValidationEngine};
+
+fn fixture_repo() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("repo_rules")
+}
+
+#[test]
+fn discovers_every_convention() {
+    let files = discover_rule_files(&fixture_repo()).unwrap();
+    let found: Vec<(&str, RuleFileFormat)> = files
+        .iter()
+        .map(|f| (f.relative.as_str(), f.format))
+        .collect();
+
+    assert_eq!(
+        found,
+        vec![
+            (".cursor/rules/commits.mdc", RuleFileFormat::CursorRule),
+            (".cursor/rules/testing.mdc", RuleFileFormat::CursorRule),
+            (".cursorrules", RuleFileFormat::CursorRulesFile),
+            (
+                ".github/copilot-instructions.md",
+                RuleFileFormat::CopilotInstructions
+            ),
+            (
+                ".github/instructions/docs.instructions.md",
+                RuleFileFormat::CopilotScopedInstructions
+            ),
+            (
+                "packages/web/.cursor/rules/testing.mdc",
+                RuleFileFormat::CursorRule
+            ),
+        ]
+    );
+}
+
+#[test]
+fn maps_frontmatter_and_provenance() {
+    let imports = import_repo_rules(
+        &fixture_repo(),
+        &GeneratorConfig::default(),
+        &ImportHints::default(),
+    )
+    .unwrap();
+    let by_id = |id: &str| {
+        imports
+            .iter()
+            .find(|i| i.generated.skill.metadata.id == id)
+            .unwrap_or_else(|| panic!("missing imported skill {id}"))
+    };
+
+    let testing = &by_id("cursor-rules-testing").generated.skill.metadata;
+    assert_eq!(testing.description, "Conventions for writing Rust tests");
+    assert_eq!(
+        testing.context.file_patterns,
+        vec!["src/**/*.rs", "tests/**/*.rs"]
+    );
+    let provenance = testing.imported_from.as_ref().unwrap();
+    assert_eq!(provenance.format, "cursor-rule");
+    assert_eq!(provenance.path, ".cursor/rules/testing.mdc");
+    assert!(!provenance.always_apply);
+
+    let web = &by_id("packages-web-cursor-rules-testing")
+        .generated
+        .skill
+        .metadata;
+    assert_eq!(
+        web.context.file_patterns,
+        vec!["src/**/*.test.ts", "src/**/*.test.tsx"]
+    );
+
+    let commits = &by_id("commits").generated.skill.metadata;
+    assert_eq!(commits.description, "Commit message style");
+    assert!(commits.imported_from.as_ref().unwrap().always_apply);
+
+    let docs = &by_id("docs").generated.skill.metadata;
+    assert_eq!(
+        docs.context.file_patterns,
+        vec!["docs/**/*.md", "README.md"]
+    );
+
+    assert!(by_id("cursorrules").generated.stats.rules_count > 0);
+    assert!(by_id("copilot-instructions").generated.stats.rules_count > 0);
+}
+
+#[test]
+fn generated_specs_pass_structural_validation() {
+    let imports = import_repo_rules(
+        &fixture_repo(),
+        &GeneratorConfig::default(),
+        &ImportHints::default(),
+    )
+    .unwrap();
+    assert_eq!(imports.len(), 6);
+
+    let mut engine = ValidationEngine::with_defaults();
+    for rule in all_rules() {
+        engine.register(rule);
+    }
+
+    for import in &imports {
+        let result = engine.validate(&import.generated.skill);
+        let structural_errors: Vec<_> = result
+            .errors()
+            .filter(|d| d.category == RuleCategory::Structure)
+            .collect();
+        assert!(
+            structural_errors.is_empty(),
+            "{} failed structural validation: {structural_errors:?}",
+            import.source.relative
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use std::fs;
+use std::path::PathBuf;
+
+use proptest::prelude::*;
+
+use ms::core::spec_lens::format_markdown;
+
+fn fixture_skills() -> Vec<(PathBuf, String)> {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/skills");
+    let mut paths: Vec<PathBuf> = fs::read_dir(&dir)
+        .expect("read fixture dir")
+        .map(|entry| entry.expect("fixture entry").path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "md"))
+        .collect();
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| {
+            let raw = fs::read_to_string(&path).expect("read fixture");
+            (path, raw)
+        })
+        .collect()
+}
+
+fn arb_code_line() -> impl Strategy<Value = String> {
+    (
+        0usize..8,
+        r"[a-z0-9_:#|=()\-]{0,24}",
+        prop_oneof![Just(""), Just("  "), Just("\t")],
+    )
+        .prop_map(|(indent, body, trailing)| format!("{}{body}{trailing}", " ".repeat(indent)))
+}
+
+#[test]
+fn test_fmt_is_idempotent_on_fixture_skills() {
+    let skills = fixture_skills();
+    assert!(!skills.is_empty(), "no fixture skills found");
+    for (path, raw) in skills {
+        let once = format_markdown(&raw).unwrap_or_else(|err| panic!("{}: {err}", path.display()));
+        let twice = format_markdown(&once).expect("format formatted output");
+        assert_eq!(twice, once, "{} is not stable under fmt", path.display());
+    }
+}
+
+proptest! {
+    #[test]
+    fn test_fmt_preserves_fenced_code(
+        lines in prop::collection::vec(arb_code_line(), 0..12),
+        lang in prop_oneof![Just("yaml"), Just("python"), Just("")],
+    ) {
+        let block = format!("```{lang}\n{}\n```", lines.join("\n"));
+        let md = format!("# Demo\n\nA demo.\n\n## Example\n\n{block}\n");
+        let once = format_markdown(&md).unwrap();
+        prop_assert!(once.contains(&block), "fenced block changed:\n{}", once);
+        prop_assert_eq!(format_markdown(&once).unwrap(), once);
+    }
+}
@@ -1,3 +1,4 @@
 mod determinism_tests;
+mod fmt_tests;
 mod roundtrip_tests;
 mod safety_tests;
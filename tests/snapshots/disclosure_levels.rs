@@ -4,6 +4,7 @@ use crate::snapshots::fixture::sample_skills;
 use ms::core::disclosure::{DisclosureLevel, DisclosurePlan, disclose};
 use ms::core::skill::SkillAssets;
 use ms::core::spec_lens::parse_markdown;
+use ms::core::tokenizer::HeuristicCounter;
 
 fn build_spec() -> ms::core::SkillSpec {
     let skill = sample_skills::rust_error_handling();
@@ -17,6 +18,7 @@ fn test_disclosure_minimal() {
         &spec,
         &SkillAssets::default(),
         &DisclosurePlan::Level(DisclosureLevel::Minimal),
+        &HeuristicCounter,
     );
     assert_yaml_snapshot!("disclosure_minimal", disclosed);
 }
@@ -28,6 +30,7 @@ fn test_disclosure_overview() {
         &spec,
         &SkillAssets::default(),
         &DisclosurePlan::Level(DisclosureLevel::Overview),
+        &HeuristicCounter,
     );
     assert_yaml_snapshot!("disclosure_overview", disclosed);
 }
@@ -39,6 +42,7 @@ fn test_disclosure_standard() {
         &spec,
         &SkillAssets::default(),
         &DisclosurePlan::Level(DisclosureLevel::Standard),
+        &HeuristicCounter,
     );
     assert_yaml_snapshot!("disclosure_standard", disclosed);
 }
@@ -50,6 +54,7 @@ fn test_disclosure_full() {
         &spec,
         &SkillAssets::default(),
         &DisclosurePlan::Level(DisclosureLevel::Full),
+        &HeuristicCounter,
     );
     assert_yaml_snapshot!("disclosure_full", disclosed);
 }
@@ -61,6 +66,7 @@ fn test_disclosure_complete() {
         &spec,
         &SkillAssets::default(),
         &DisclosurePlan::Level(DisclosureLevel::Complete),
+        &HeuristicCounter,
     );
     assert_yaml_snapshot!("disclosure_complete", disclosed);
 }
@@ -20,6 +20,7 @@ fn from_config_fails_when_disabled() {
         project_key: "test-project".to_string(),
         agent_name: "test-agent".to_string(),
         timeout_secs: 10,
+        max_requests_per_minute: 60,
     };
 
     let Err(err) = AgentMailClient::from_config(&config) else {
@@ -40,6 +41,7 @@ fn from_config_fails_when_endpoint_empty() {
         project_key: "test-project".to_string(),
         agent_name: "test-agent".to_string(),
         timeout_secs: 10,
+        max_requests_per_minute: 60,
     };
 
     let Err(err) = AgentMailClient::from_config(&config) else {
@@ -60,6 +62,7 @@ fn from_config_fails_when_endpoint_whitespace_only() {
         project_key: "test-project".to_string(),
         agent_name: "test-agent".to_string(),
         timeout_secs: 10,
+        max_requests_per_minute: 60,
     };
 
     let Err(err) = AgentMailClient::from_config(&config) else {
@@ -80,6 +83,7 @@ fn from_config_fails_when_project_key_empty() {
         project_key: "".to_string(),
         agent_name: "test-agent".to_string(),
         timeout_secs: 10,
+        max_requests_per_minute: 60,
     };
 
     let Err(err) = AgentMailClient::from_config(&config) else {
@@ -100,6 +104,7 @@ fn from_config_fails_when_project_key_whitespace_only() {
         project_key: "  \n  ".to_string(),
         agent_name: "test-agent".to_string(),
         timeout_secs: 10,
+        max_requests_per_minute: 60,
     };
 
     let Err(err) = AgentMailClient::from_config(&config) else {
@@ -120,6 +125,7 @@ fn from_config_fails_when_agent_name_empty() {
         project_key: "test-project".to_string(),
         agent_name: "".to_string(),
         timeout_secs: 10,
+        max_requests_per_minute: 60,
     };
 
     let Err(err) = AgentMailClient::from_config(&config) else {
@@ -140,6 +146,7 @@ fn from_config_fails_when_agent_name_whitespace_only() {
         project_key: "test-project".to_string(),
         agent_name: "\t\t".to_string(),
         timeout_secs: 10,
+        max_requests_per_minute: 60,
     };
 
     let Err(err) = AgentMailClient::from_config(&config) else {
@@ -369,6 +376,7 @@ fn agent_mail_config_deserialize_minimal() {
     assert!(config.enabled);
     assert_eq!(config.endpoint, "https://mail.example.com/mcp");
     // Other fields should be defaults (empty strings due to serde(default))
+    assert_eq!(config.max_requests_per_minute, 60);
 }
 
 #[test]
@@ -379,6 +387,7 @@ fn agent_mail_config_deserialize_full() {
         project_key = "my-project"
         agent_name = "my-agent"
         timeout_secs = 30
+        max_requests_per_minute = 120
     "#;
 
     let config: AgentMailConfig = toml::from_str(toml).expect("deserialize");
@@ -387,6 +396,7 @@ fn agent_mail_config_deserialize_full() {
     assert_eq!(config.project_key, "my-project");
     assert_eq!(config.agent_name, "my-agent");
     assert_eq!(config.timeout_secs, 30);
+    assert_eq!(config.max_requests_per_minute, 120);
 }
 
 #[test]
@@ -397,6 +407,7 @@ fn agent_mail_config_serialize_roundtrip() {
         project_key: "test-project".to_string(),
         agent_name: "test-agent".to_string(),
         timeout_secs: 60,
+        max_requests_per_minute: 30,
     };
 
     let toml_str = toml::to_string(&config).expect("serialize");
@@ -407,6 +418,10 @@ fn agent_mail_config_serialize_roundtrip() {
     assert_eq!(parsed.project_key, config.project_key);
     assert_eq!(parsed.agent_name, config.agent_name);
     assert_eq!(parsed.timeout_secs, config.timeout_secs);
+    assert_eq!(
+        parsed.max_requests_per_minute,
+        config.max_requests_per_minute
+    );
 }
 
 // ============================================================================
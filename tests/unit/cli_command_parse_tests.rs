@@ -35,6 +35,32 @@ fn parse_config_set_value() {
     }
 }
 
+#[test]
+fn parse_config_scope_and_show_origin() {
+    match parse(&[
+        "config",
+        "set",
+        "search.bm25_weight",
+        "0.4",
+        "--scope",
+        "project",
+    ]) {
+        Commands::Config(args) => {
+            assert_eq!(args.key.as_deref(), Some("set"));
+            assert_eq!(args.extra.as_deref(), Some("0.4"));
+            assert_eq!(args.scope, Some(commands::config::ConfigScope::Project));
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+    match parse(&["config", "get", "search.bm25_weight", "--show-origin"]) {
+        Commands::Config(args) => {
+            assert!(args.show_origin);
+            assert!(args.scope.is_none());
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+}
+
 #[test]
 fn parse_diff_flags() {
     match parse(&[
@@ -65,21 +91,217 @@ fn parse_fmt_flags() {
             );
             assert!(args.check);
             assert!(args.diff);
+            assert!(!args.fix_lint);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+    match parse(&["fmt", "--fix-lint"]) {
+        Commands::Fmt(args) => assert!(args.fix_lint),
+        other => panic!("unexpected command: {other:?}"),
+    }
+}
+
+#[test]
+fn parse_init_configure_agents() {
+    match parse(&["init", "--configure-agents", "--dry-run"]) {
+        Commands::Init(args) => {
+            assert!(args.configure_agents);
+            assert!(args.dry_run);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+    match parse(&["init", "--dry-run", "-y"]) {
+        Commands::Init(args) => {
+            assert!(args.dry_run);
+            assert!(args.yes);
+            assert!(!args.configure_agents);
         }
         other => panic!("unexpected command: {other:?}"),
     }
 }
 
+#[test]
+fn parse_init_hooks() {
+    match parse(&["init", "--install-hooks", "claude-code"]) {
+        Commands::Init(args) => {
+            assert_eq!(
+                args.install_hooks,
+                Some(commands::hook::HookAgent::ClaudeCode)
+            );
+            assert!(!args.remove_hooks);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+    match parse(&["init", "--remove-hooks"]) {
+        Commands::Init(args) => assert!(args.remove_hooks),
+        other => panic!("unexpected command: {other:?}"),
+    }
+    assert!(
+        Cli::try_parse_from([
+            "ms",
+            "init",
+            "--install-hooks",
+            "claude-code",
+            "--remove-hooks"
+        ])
+        .is_err()
+    );
+    assert!(Cli::try_parse_from(["ms", "init", "--install-hooks", "vim"]).is_err());
+    match parse(&["__hook", "claude-code"]) {
+        Commands::Hook(args) => assert_eq!(args.agent, commands::hook::HookAgent::ClaudeCode),
+        other => panic!("unexpected command: {other:?}"),
+    }
+}
+
 #[test]
 fn parse_edit_flags() {
     match parse(&["edit", "skill-a", "--editor", "vim", "--meta"]) {
         Commands::Edit(args) => {
-            assert_eq!(args.skill, "skill-a");
+            assert_eq!(args.skill.as_deref(), Some("skill-a"));
             assert_eq!(args.editor.as_deref(), Some("vim"));
             assert!(args.meta);
+            assert!(!args.bulk);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+}
+
+#[test]
+fn parse_edit_bulk_flags() {
+    match parse(&[
+        "edit",
+        "--bulk",
+        "--filter",
+        "tag:database layer:project",
+        "--set",
+        "metadata.author=data-team",
+        "--add-tag",
+        "reviewed-2025",
+        "--remove-tag",
+        "draft",
+        "--dry-run",
+    ]) {
+        Commands::Edit(args) => {
+            assert!(args.bulk);
+            assert!(args.skill.is_none());
+            assert_eq!(args.filter.as_deref(), Some("tag:database layer:project"));
+            assert_eq!(args.set, vec!["metadata.author=data-team"]);
+            assert_eq!(args.add_tag, vec!["reviewed-2025"]);
+            assert_eq!(args.remove_tag, vec!["draft"]);
+            assert!(args.dry_run);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+    assert!(Cli::try_parse_from(["ms", "edit"]).is_err());
+    assert!(Cli::try_parse_from(["ms", "edit", "skill-a", "--dry-run"]).is_err());
+}
+
+#[test]
+fn parse_edit_structured_flags() {
+    match parse(&[
+        "edit",
+        "rust-style",
+        "--add-rule",
+        "Prefer ? over match",
+        "--section",
+        "errors",
+        "--remove-block",
+        "rules-block-2",
+        "--set-meta",
+        "author=platform",
+        "--rename-section",
+        "pitfalls=Gotchas",
+        "--append-example",
+        "--lang",
+        "rust",
+        "--file",
+        "example.rs",
+    ]) {
+        Commands::Edit(args) => {
+            assert_eq!(args.skill.as_deref(), Some("rust-style"));
+            assert_eq!(args.add_rule, vec!["Prefer ? over match"]);
+            assert_eq!(args.section.as_deref(), Some("errors"));
+            assert_eq!(args.remove_block, vec!["rules-block-2"]);
+            assert_eq!(args.set_meta, vec!["author=platform"]);
+            assert_eq!(args.rename_section, vec!["pitfalls=Gotchas"]);
+            assert!(args.append_example);
+            assert_eq!(args.lang.as_deref(), Some("rust"));
+            assert_eq!(
+                args.file.as_deref(),
+                Some(std::path::Path::new("example.rs"))
+            );
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+    assert!(
+        Cli::try_parse_from(["ms", "edit", "s", "--append-example", "--lang", "rust"]).is_err()
+    );
+    assert!(Cli::try_parse_from(["ms", "edit", "s", "--section", "rules"]).is_err());
+    assert!(Cli::try_parse_from(["ms", "edit", "s", "--meta", "--add-rule", "x"]).is_err());
+}
+
+#[test]
+fn parse_fork_and_upstream_flags() {
+    use commands::edit::ForkTarget;
+
+    match parse(&[
+        "edit",
+        "rust-style",
+        "--add-rule",
+        "x",
+        "--fork-to",
+        "project",
+    ]) {
+        Commands::Edit(args) => assert_eq!(args.fork_to, Some(ForkTarget::Project)),
+        other => panic!("unexpected command: {other:?}"),
+    }
+    match parse(&["fmt", "rust-style", "--fork-to", "project"]) {
+        Commands::Fmt(args) => assert_eq!(args.fork_to, Some(ForkTarget::Project)),
+        other => panic!("unexpected command: {other:?}"),
+    }
+    match parse(&["diff", "rust-style", "--upstream"]) {
+        Commands::Diff(args) => {
+            assert!(args.upstream);
+            assert!(args.skill_b.is_none());
         }
         other => panic!("unexpected command: {other:?}"),
     }
+    assert!(Cli::try_parse_from(["ms", "edit", "s", "--fork-to", "user"]).is_err());
+    assert!(Cli::try_parse_from(["ms", "edit", "--bulk", "--fork-to", "project"]).is_err());
+    assert!(Cli::try_parse_from(["ms", "diff", "a", "b", "--upstream"]).is_err());
+}
+
+#[test]
+fn parse_review_flags() {
+    match parse(&[
+        "review",
+        "resolve",
+        "0b5c2f9e",
+        "--accept",
+        "--into",
+        "rust-testing",
+        "--edit",
+    ]) {
+        Commands::Review(args) => match args.command {
+            commands::review::ReviewCommand::Resolve(resolve) => {
+                assert_eq!(resolve.id, "0b5c2f9e");
+                assert!(resolve.accept && resolve.edit && !resolve.reject);
+                assert_eq!(resolve.into.as_deref(), Some("rust-testing"));
+            }
+            other => panic!("unexpected review command: {other:?}"),
+        },
+        other => panic!("unexpected command: {other:?}"),
+    }
+    let review = |extra: &[&str]| {
+        let mut argv = vec!["ms", "review", "resolve", "id"];
+        argv.extend_from_slice(extra);
+        Cli::try_parse_from(argv)
+    };
+    assert!(review(&["--reject", "--reason", "too broad"]).is_ok());
+    assert!(review(&[]).is_err());
+    assert!(review(&["--reject"]).is_err());
+    assert!(review(&["--accept", "--reject", "--reason", "x"]).is_err());
+    assert!(review(&["--accept", "--into", "a", "--skill-id", "b"]).is_err());
 }
 
 #[test]
@@ -112,6 +334,72 @@ fn parse_alias_add() {
     }
 }
 
+#[test]
+fn parse_resolve() {
+    match parse(&["resolve", "deploy", "--layer", "project", "--no-prefix"]) {
+        Commands::Resolve(args) => {
+            assert_eq!(args.name, "deploy");
+            assert_eq!(args.layer.as_deref(), Some("project"));
+            assert!(args.no_prefix);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+}
+
+#[test]
+fn parse_load_and_show_trace_resolution() {
+    match parse(&["load", "deploy", "--trace-resolution"]) {
+        Commands::Load(args) => assert!(args.trace_resolution),
+        other => panic!("unexpected command: {other:?}"),
+    }
+    match parse(&["show", "deploy", "--trace-resolution"]) {
+        Commands::Show(args) => assert!(args.trace_resolution),
+        other => panic!("unexpected command: {other:?}"),
+    }
+}
+
+#[test]
+fn parse_load_slices() {
+    match parse(&[
+        "load",
+        "rust-errors",
+        "--slices",
+        "retry on timeout",
+        "--top-k",
+        "3",
+        "--max-tokens",
+        "800",
+    ]) {
+        Commands::Load(args) => {
+            assert_eq!(args.slices.as_deref(), Some("retry on timeout"));
+            assert_eq!(args.top_k, 3);
+            assert_eq!(args.max_tokens, Some(800));
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+    match parse(&["load", "rust-errors", "--select", "pitfalls-block-3,Rules"]) {
+        Commands::Load(args) => assert_eq!(args.select, ["pitfalls-block-3", "Rules"]),
+        other => panic!("unexpected command: {other:?}"),
+    }
+    assert!(Cli::try_parse_from(["ms", "load", "x", "--slices", "y", "--select", "z"]).is_err());
+    assert!(Cli::try_parse_from(["ms", "load", "x", "--slices", "y", "--full"]).is_err());
+}
+
+#[test]
+fn parse_load_max_tokens() {
+    match parse(&["load", "deploy", "--max-tokens", "4000"]) {
+        Commands::Load(args) => {
+            assert_eq!(args.max_tokens, Some(4000));
+            assert_eq!(args.pack, None);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+    assert!(
+        Cli::try_parse_from(["ms", "load", "deploy", "--max-tokens", "10", "--pack", "10"])
+            .is_err()
+    );
+}
+
 #[test]
 fn parse_bandit_stats_path() {
     match parse(&["bandit", "stats", "--path", "/tmp/bandit.json"]) {
@@ -213,6 +501,47 @@ fn parse_security_scan_args() {
     }
 }
 
+#[test]
+fn parse_security_quarantine_release_and_purge() {
+    use commands::security::{QuarantineCommand, SecurityCommand};
+
+    match parse(&[
+        "security",
+        "quarantine",
+        "release",
+        "q-123",
+        "--i-have-reviewed",
+    ]) {
+        Commands::Security(args) => match args.command {
+            SecurityCommand::Quarantine(q) => match q.command {
+                QuarantineCommand::Release {
+                    id,
+                    i_have_reviewed,
+                } => {
+                    assert_eq!(id, "q-123");
+                    assert!(i_have_reviewed);
+                }
+                other => panic!("unexpected quarantine command: {other:?}"),
+            },
+            other => panic!("unexpected security command: {other:?}"),
+        },
+        other => panic!("unexpected command: {other:?}"),
+    }
+
+    match parse(&["security", "quarantine", "purge", "--older-than-days", "7"]) {
+        Commands::Security(args) => match args.command {
+            SecurityCommand::Quarantine(q) => match q.command {
+                QuarantineCommand::Purge { older_than_days } => {
+                    assert_eq!(older_than_days, Some(7));
+                }
+                other => panic!("unexpected quarantine command: {other:?}"),
+            },
+            other => panic!("unexpected security command: {other:?}"),
+        },
+        other => panic!("unexpected command: {other:?}"),
+    }
+}
+
 #[test]
 fn parse_template_apply_args() {
     match parse(&[
@@ -264,9 +593,210 @@ fn parse_test_args() {
             assert_eq!(args.exclude_tags.as_deref(), Some("slow"));
             assert_eq!(args.timeout.as_deref(), Some("2m"));
             assert!(args.fail_fast);
+            assert!(!args.doctests);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+}
+
+#[test]
+fn parse_list_table_export() {
+    let cli = Cli::try_parse_from([
+        "ms",
+        "-O",
+        "csv",
+        "list",
+        "--columns",
+        "id,last_used,review_state",
+        "--no-header",
+    ])
+    .unwrap();
+    assert_eq!(cli.output_format(), ms::cli::output::OutputFormat::Csv);
+    match cli.command {
+        Commands::List(args) => {
+            assert_eq!(
+                args.columns,
+                vec![
+                    commands::list::ListColumn::Id,
+                    commands::list::ListColumn::LastUsed,
+                    commands::list::ListColumn::ReviewState,
+                ]
+            );
+            assert!(args.no_header);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+    assert!(Cli::try_parse_from(["ms", "list", "--format", "csv"]).is_err());
+}
+
+#[test]
+fn parse_list_expiring() {
+    match parse(&["list", "--expiring", "30d"]) {
+        Commands::List(args) => assert_eq!(args.expiring, Some(30)),
+        other => panic!("unexpected command: {other:?}"),
+    }
+    assert!(Cli::try_parse_from(["ms", "list", "--expiring", "soon"]).is_err());
+}
+
+#[test]
+fn parse_search_and_list_cursor() {
+    match parse(&["search", "rust", "--limit", "3", "--cursor", "abc"]) {
+        Commands::Search(args) => {
+            assert_eq!(args.limit, 3);
+            assert_eq!(args.cursor.as_deref(), Some("abc"));
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+    match parse(&["list", "--cursor", "abc"]) {
+        Commands::List(args) => assert_eq!(args.cursor.as_deref(), Some("abc")),
+        other => panic!("unexpected command: {other:?}"),
+    }
+    assert!(Cli::try_parse_from(["ms", "list", "--cursor", "abc", "--offset", "3"]).is_err());
+    assert!(Cli::try_parse_from(["ms", "search", "rust", "--limit", "101"]).is_err());
+    assert!(Cli::try_parse_from(["ms", "list", "--limit", "0"]).is_err());
+}
+
+#[test]
+fn parse_list_filters() {
+    match parse(&[
+        "list",
+        "--tag",
+        "rust",
+        "--tag",
+        "cli",
+        "--layer",
+        "community",
+        "--min-quality",
+        "0.7",
+        "--deprecated",
+        "--updated-since",
+        "7d",
+        "--sort",
+        "quality",
+    ]) {
+        Commands::List(args) => {
+            assert_eq!(args.tags, vec!["rust".to_string(), "cli".to_string()]);
+            assert_eq!(args.layer, Some(commands::list::ListLayer::Community));
+            assert_eq!(args.min_quality, Some(0.7));
+            assert!(args.deprecated);
+            assert_eq!(args.updated_since.as_deref(), Some("7d"));
+            assert_eq!(args.sort, commands::list::ListSort::Quality);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+    // Legacy layer names still parse.
+    match parse(&["list", "--layer", "org"]) {
+        Commands::List(args) => assert_eq!(args.layer, Some(commands::list::ListLayer::Global)),
+        other => panic!("unexpected command: {other:?}"),
+    }
+    assert!(Cli::try_parse_from(["ms", "list", "--min-quality", "2"]).is_err());
+    assert!(Cli::try_parse_from(["ms", "list", "--deprecated", "--no-deprecated"]).is_err());
+}
+
+#[test]
+fn parse_search_rrf_overrides() {
+    match parse(&["search", "retry", "--bm25-only", "--rrf-k", "30"]) {
+        Commands::Search(args) => {
+            assert!(args.bm25_only);
+            assert!(!args.vector_only);
+            assert_eq!(args.rrf_k, Some(30.0));
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+    assert!(Cli::try_parse_from(["ms", "search", "q", "--bm25-only", "--vector-only"]).is_err());
+    assert!(Cli::try_parse_from(["ms", "search", "q", "--rrf-k", "0"]).is_err());
+}
+
+#[test]
+fn parse_search_context_flags() {
+    match parse(&["search", "retry", "--context-dir", "crates/app"]) {
+        Commands::Search(args) => {
+            assert!(!args.no_context);
+            assert_eq!(
+                args.context_dir,
+                Some(std::path::PathBuf::from("crates/app"))
+            );
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+    assert!(
+        Cli::try_parse_from(["ms", "search", "q", "--no-context", "--context-dir", "."]).is_err()
+    );
+}
+
+#[test]
+fn parse_new_args() {
+    match parse(&[
+        "new",
+        "deploy-checks",
+        "-t",
+        "playbook",
+        "--edit",
+        "--index",
+    ]) {
+        Commands::New(args) => {
+            assert_eq!(args.skill_id.as_deref(), Some("deploy-checks"));
+            assert_eq!(args.template, "playbook");
+            assert!(args.edit);
+            assert!(args.index);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+    match parse(&["new", "--list-templates"]) {
+        Commands::New(args) => {
+            assert!(args.list_templates);
+            assert_eq!(args.template, "checklist");
         }
         other => panic!("unexpected command: {other:?}"),
     }
+    assert!(Cli::try_parse_from(["ms", "new"]).is_err());
+}
+
+#[test]
+fn parse_test_doctests() {
+    match parse(&["test", "skill-a", "--doctests"]) {
+        Commands::Test(args) => {
+            assert_eq!(args.skill.as_deref(), Some("skill-a"));
+            assert!(args.doctests);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+}
+
+#[test]
+fn parse_test_allow_destructive_and_output_limit() {
+    match parse(&[
+        "test",
+        "skill-a",
+        "--allow-destructive",
+        "--output-limit",
+        "1024",
+    ]) {
+        Commands::Test(args) => {
+            assert!(args.allow_destructive);
+            assert_eq!(args.output_limit, Some(1024));
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+}
+
+#[test]
+fn parse_test_file_issues() {
+    match parse(&[
+        "test",
+        "--all",
+        "--file-issues",
+        "--close-fixed",
+        "--dry-run",
+    ]) {
+        Commands::Test(args) => {
+            assert!(args.file_issues);
+            assert!(args.close_fixed);
+            assert!(args.dry_run);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+    assert!(Cli::try_parse_from(["ms", "test", "--close-fixed"]).is_err());
 }
 
 #[test]
@@ -337,10 +867,21 @@ fn parse_remote_add_git_flags() {
 
 #[test]
 fn parse_backup_create_args() {
-    match parse(&["backup", "create", "--id", "snap-1"]) {
+    match parse(&[
+        "backup",
+        "create",
+        "--id",
+        "snap-1",
+        "--out",
+        "/tmp/ms.tar.zst",
+    ]) {
         Commands::Backup(args) => match args.command {
             commands::backup::BackupCommand::Create(create) => {
                 assert_eq!(create.id.as_deref(), Some("snap-1"));
+                assert_eq!(
+                    create.out.as_deref(),
+                    Some(std::path::Path::new("/tmp/ms.tar.zst"))
+                );
             }
             other => panic!("unexpected backup command: {other:?}"),
         },
@@ -369,9 +910,623 @@ fn parse_backup_restore_args() {
                 assert_eq!(restore.id.as_deref(), Some("snap-1"));
                 assert!(restore.approve);
                 assert!(!restore.latest);
+                assert!(!restore.force_migrate);
             }
             other => panic!("unexpected backup command: {other:?}"),
         },
         other => panic!("unexpected command: {other:?}"),
     }
 }
+
+#[test]
+fn parse_restore_file_args() {
+    match parse(&["restore", "backup.tar.zst", "--approve", "--force-migrate"]) {
+        Commands::Restore(args) => {
+            assert_eq!(args.file, std::path::PathBuf::from("backup.tar.zst"));
+            assert!(args.approve);
+            assert!(args.force_migrate);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+    assert!(Cli::try_parse_from(["ms", "restore"]).is_err());
+}
+
+#[test]
+fn parse_budget_flags() {
+    match parse(&["budget", "--top", "2", "--strict"]) {
+        Commands::Budget(args) => {
+            assert_eq!(args.top, 2);
+            assert!(args.strict);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+}
+
+#[test]
+fn parse_load_model_and_session() {
+    match parse(&[
+        "load",
+        "rust-errors",
+        "--model",
+        "claude-sonnet-4",
+        "--session-id",
+        "abc123",
+    ]) {
+        Commands::Load(args) => {
+            assert_eq!(args.model.as_deref(), Some("claude-sonnet-4"));
+            assert_eq!(args.session_id.as_deref(), Some("abc123"));
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+}
+
+#[test]
+fn parse_open_deep_link() {
+    match parse(&["open", "ms://project/rust-errors#rule-no-unwrap", "--web"]) {
+        Commands::Open(args) => {
+            assert_eq!(args.link, "ms://project/rust-errors#rule-no-unwrap");
+            assert!(args.web);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+}
+
+#[test]
+fn parse_show_anchors() {
+    match parse(&["show", "rust-errors", "--anchors"]) {
+        Commands::Show(args) => {
+            assert_eq!(args.skill, "rust-errors");
+            assert!(args.anchors);
+            assert!(!args.quality);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+    match parse(&["show", "rust-errors", "--quality"]) {
+        Commands::Show(args) => assert!(args.quality),
+        other => panic!("unexpected command: {other:?}"),
+    }
+}
+
+#[test]
+fn parse_show_effective_and_base() {
+    match parse(&["show", "rust-errors", "--effective"]) {
+        Commands::Show(args) => {
+            assert!(args.effective);
+            assert!(!args.base);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+    match parse(&["show", "rust-errors", "--base"]) {
+        Commands::Show(args) => assert!(args.base),
+        other => panic!("unexpected command: {other:?}"),
+    }
+    assert!(Cli::try_parse_from(["ms", "show", "rust-errors", "--effective", "--base"]).is_err());
+}
+
+#[test]
+fn parse_show_history() {
+    match parse(&["show", "rust-errors", "--history"]) {
+        Commands::Show(args) => {
+            assert!(args.history);
+            assert!(!args.full);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+}
+
+#[test]
+fn parse_show_changelog() {
+    match parse(&["show", "rust-errors", "--changelog", "--since", "v1.2.0"]) {
+        Commands::Show(args) => {
+            assert!(args.changelog);
+            assert_eq!(args.since.as_deref(), Some("v1.2.0"));
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+    assert!(Cli::try_parse_from(["ms", "show", "rust-errors", "--since", "30d"]).is_err());
+    assert!(
+        Cli::try_parse_from(["ms", "show", "rust-errors", "--changelog", "--history"]).is_err()
+    );
+}
+
+#[test]
+fn parse_show_resources_and_load_with_resources() {
+    match parse(&["show", "rust-errors", "--resources"]) {
+        Commands::Show(args) => assert!(args.resources),
+        other => panic!("unexpected command: {other:?}"),
+    }
+    assert!(
+        Cli::try_parse_from(["ms", "show", "rust-errors", "--resources", "--history"]).is_err()
+    );
+
+    match parse(&["load", "rust-errors", "--with-resources"]) {
+        Commands::Load(args) => {
+            assert!(args.with_resources);
+            assert_eq!(args.resource_cap, 8 * 1024);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+    match parse(&[
+        "load",
+        "rust-errors",
+        "--with-resources",
+        "--resource-cap",
+        "512",
+    ]) {
+        Commands::Load(args) => assert_eq!(args.resource_cap, 512),
+        other => panic!("unexpected command: {other:?}"),
+    }
+    assert!(Cli::try_parse_from(["ms", "load", "rust-errors", "--resource-cap", "512"]).is_err());
+}
+
+#[test]
+fn parse_stats_efficacy() {
+    match parse(&["stats", "rust-errors", "--efficacy"]) {
+        Commands::Stats(args) => {
+            assert_eq!(args.skill.as_deref(), Some("rust-errors"));
+            assert!(args.efficacy);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+}
+
+#[test]
+fn parse_stats_usage_window() {
+    match parse(&["stats"]) {
+        Commands::Stats(args) => {
+            assert_eq!(args.since, "30d");
+            assert_eq!(args.by, commands::stats::StatsGroupBy::Skill);
+            assert!(!args.prune_candidates);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+    match parse(&[
+        "stats",
+        "--since",
+        "4w",
+        "--by",
+        "layer",
+        "--prune-candidates",
+    ]) {
+        Commands::Stats(args) => {
+            assert_eq!(args.since, "4w");
+            assert_eq!(args.by, commands::stats::StatsGroupBy::Layer);
+            assert!(args.prune_candidates);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+    assert!(
+        Cli::try_parse_from(["ms", "stats", "--efficacy", "--by", "layer"]).is_err(),
+        "--efficacy does not take usage options"
+    );
+}
+
+#[test]
+fn parse_why_suggested() {
+    match parse(&[
+        "why-suggested",
+        "terraform",
+        "--session",
+        "sess-1",
+        "--at",
+        "2026-03-01",
+    ]) {
+        Commands::WhySuggested(args) => {
+            assert_eq!(args.skill, "terraform");
+            assert_eq!(args.session.as_deref(), Some("sess-1"));
+            assert_eq!(args.at.as_deref(), Some("2026-03-01"));
+            assert_eq!(args.limit, 1);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+}
+
+#[test]
+fn parse_export_site() {
+    match parse(&["export-site", "--out", "docs/public", "--include-body"]) {
+        Commands::ExportSite(args) => {
+            assert_eq!(args.out, std::path::PathBuf::from("docs/public"));
+            assert!(args.include_body);
+            assert!(!args.include_deprecated);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+}
+
+#[test]
+fn parse_mcp_serve_http() {
+    match parse(&[
+        "mcp",
+        "serve",
+        "--tcp-port",
+        "0",
+        "--bind",
+        "0.0.0.0",
+        "--auth-token",
+        "s3cret",
+    ]) {
+        Commands::Mcp(args) => match args.command {
+            commands::mcp::McpCommand::Serve(serve) => {
+                assert_eq!(serve.tcp_port, Some(0));
+                assert_eq!(serve.bind.to_string(), "0.0.0.0");
+                assert_eq!(serve.auth_token.as_deref(), Some("s3cret"));
+            }
+            other => panic!("unexpected subcommand: {other:?}"),
+        },
+        other => panic!("unexpected command: {other:?}"),
+    }
+    match parse(&["mcp", "serve"]) {
+        Commands::Mcp(args) => match args.command {
+            commands::mcp::McpCommand::Serve(serve) => {
+                assert_eq!(serve.tcp_port, None);
+                assert!(serve.bind.is_loopback());
+            }
+            other => panic!("unexpected subcommand: {other:?}"),
+        },
+        other => panic!("unexpected command: {other:?}"),
+    }
+    assert!(Cli::try_parse_from(["ms", "mcp", "serve", "--bind", "0.0.0.0"]).is_err());
+}
+
+#[test]
+fn parse_export_targets() {
+    match parse(&["export", "--target", "claude-code", "--all", "--force"]) {
+        Commands::Export(args) => {
+            assert_eq!(args.target, commands::export::ExportTargetArg::ClaudeCode);
+            assert!(args.all && args.force);
+            assert!(!args.include_deprecated);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+    match parse(&[
+        "export", "--target", "cursor", "--skill", "a", "--skill", "b",
+    ]) {
+        Commands::Export(args) => assert_eq!(args.skills, vec!["a", "b"]),
+        other => panic!("unexpected command: {other:?}"),
+    }
+    assert!(Cli::try_parse_from(["ms", "export", "--target", "cursor"]).is_err());
+    assert!(Cli::try_parse_from(["ms", "export", "--target", "zed", "--all"]).is_err());
+}
+
+#[test]
+fn parse_build_jobs() {
+    match parse(&["build", "--auto", "--from-cass", "errors", "--jobs", "4"]) {
+        Commands::Build(args) => {
+            assert!(args.auto);
+            assert_eq!(args.jobs, Some(4));
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+    match parse(&["build", "--auto", "--from-cass", "errors"]) {
+        Commands::Build(args) => assert_eq!(args.jobs, None),
+        other => panic!("unexpected command: {other:?}"),
+    }
+    assert!(Cli::try_parse_from(["ms", "build", "--jobs", "0"]).is_err());
+}
+
+#[test]
+fn parse_import_from_repo() {
+    match parse(&["import", "--from-repo", ".", "--output", "skills"]) {
+        Commands::Import(args) => {
+            assert!(args.from_repo);
+            assert_eq!(args.path, std::path::PathBuf::from("."));
+            assert_eq!(args.output, Some(std::path::PathBuf::from("skills")));
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+    assert!(Cli::try_parse_from(["ms", "import", "--from-repo", "--batch", "."]).is_err());
+}
+
+#[test]
+fn parse_doctor_conflicts_strategy() {
+    match parse(&["doctor", "conflicts", "my-skill", "--strategy", "newest"]) {
+        Commands::Doctor(args) => match args.command {
+            Some(commands::doctor::DoctorCommand::Conflicts(conflicts)) => {
+                assert_eq!(conflicts.skill.as_deref(), Some("my-skill"));
+                assert_eq!(conflicts.strategy.as_deref(), Some("newest"));
+                assert!(!conflicts.forget);
+            }
+            other => panic!("unexpected subcommand: {other:?}"),
+        },
+        other => panic!("unexpected command: {other:?}"),
+    }
+    match parse(&["doctor", "conflicts", "--forget"]) {
+        Commands::Doctor(args) => {
+            assert!(matches!(
+                args.command,
+                Some(commands::doctor::DoctorCommand::Conflicts(ref c)) if c.forget && c.skill.is_none()
+            ));
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+}
+
+#[test]
+fn parse_build_resume_optional_id() {
+    match parse(&["build", "--resume"]) {
+        Commands::Build(args) => assert_eq!(args.resume, Some(None)),
+        other => panic!("unexpected command: {other:?}"),
+    }
+    match parse(&["build", "--resume", "3f2a9c"]) {
+        Commands::Build(args) => assert_eq!(args.resume, Some(Some("3f2a9c".to_string()))),
+        other => panic!("unexpected command: {other:?}"),
+    }
+    match parse(&["build", "--guided"]) {
+        Commands::Build(args) => assert_eq!(args.resume, None),
+        other => panic!("unexpected command: {other:?}"),
+    }
+}
+
+#[test]
+fn parse_evidence_export_rows() {
+    match parse(&[
+        "evidence", "export", "--all", "--format", "csv", "--out", "e.csv", "--redact",
+    ]) {
+        Commands::Evidence(args) => match args.command {
+            commands::evidence::EvidenceCommand::Export(export) => {
+                assert!(export.all);
+                assert!(export.redact);
+                assert_eq!(export.format, "csv");
+                assert_eq!(export.output.as_deref(), Some("e.csv"));
+            }
+            other => panic!("unexpected subcommand: {other:?}"),
+        },
+        other => panic!("unexpected command: {other:?}"),
+    }
+    assert!(Cli::try_parse_from(["ms", "evidence", "export", "--all", "--skill", "x"]).is_err());
+}
+
+#[test]
+fn parse_strict_config_flag() {
+    let cli = Cli::parse_from(["ms", "--strict-config", "config", "doctor"]);
+    assert!(cli.strict_config);
+    match cli.command {
+        Commands::Config(args) => assert_eq!(args.key.as_deref(), Some("doctor")),
+        other => panic!("unexpected command: {other:?}"),
+    }
+    assert!(!Cli::parse_from(["ms", "list"]).strict_config);
+}
+
+#[test]
+fn parse_fmt_stdin_and_check() {
+    match parse(&["fmt", "-", "--stdin-filepath", "skills/demo/SKILL.md"]) {
+        Commands::Fmt(args) => {
+            assert_eq!(args.skills, vec!["-".to_string()]);
+            assert_eq!(
+                args.stdin_filepath.as_deref(),
+                Some(std::path::Path::new("skills/demo/SKILL.md"))
+            );
+            assert!(!args.check);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+    match parse(&["fmt", "--check", "a/SKILL.md", "b/SKILL.md"]) {
+        Commands::Fmt(args) => {
+            assert!(args.check);
+            assert_eq!(args.skills.len(), 2);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+}
+
+#[test]
+fn parse_graph_native_and_bv_subcommands() {
+    match parse(&[
+        "graph", "--format", "mermaid", "--root", "app", "--depth", "2", "--strict",
+    ]) {
+        Commands::Graph(args) => {
+            assert!(args.command.is_none());
+            assert_eq!(args.format, Some(commands::graph::GraphFormat::Mermaid));
+            assert_eq!(args.root.as_deref(), Some("app"));
+            assert_eq!(args.depth, Some(2));
+            assert!(args.strict);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+    match parse(&["graph", "export", "--format", "dot"]) {
+        Commands::Graph(args) => match args.command {
+            Some(commands::graph::GraphCommand::Export(export)) => {
+                assert_eq!(export.format, "dot");
+                assert!(args.format.is_none());
+            }
+            other => panic!("unexpected subcommand: {other:?}"),
+        },
+        other => panic!("unexpected command: {other:?}"),
+    }
+}
+
+#[test]
+fn parse_build_from_files() {
+    match parse(&[
+        "build",
+        "--from-file",
+        "codex.jsonl",
+        "gemini.json",
+        "--name",
+        "fixes",
+    ]) {
+        Commands::Build(args) => {
+            assert_eq!(
+                args.from_file,
+                vec![
+                    std::path::PathBuf::from("codex.jsonl"),
+                    std::path::PathBuf::from("gemini.json")
+                ]
+            );
+            assert_eq!(args.name.as_deref(), Some("fixes"));
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+    assert!(
+        Cli::try_parse_from(["ms", "build", "--from-file", "a.jsonl", "--from-cass", "q"]).is_err()
+    );
+}
+
+#[test]
+fn parse_build_session_quality_flags() {
+    match parse(&[
+        "build",
+        "--auto",
+        "--from-cass",
+        "q",
+        "--min-quality",
+        "0.4",
+    ]) {
+        Commands::Build(args) => {
+            assert_eq!(args.min_quality, Some(0.4));
+            assert!(!args.include_low_quality);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+    match parse(&[
+        "build",
+        "--auto",
+        "--from-cass",
+        "q",
+        "--include-low-quality",
+    ]) {
+        Commands::Build(args) => {
+            assert_eq!(args.min_quality, None);
+            assert!(args.include_low_quality);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+    assert!(
+        Cli::try_parse_from(["ms", "build", "--min-session-quality", "0.5"]).is_ok(),
+        "the old flag name stays as an alias"
+    );
+}
+
+#[test]
+fn parse_security_audit_and_safety_profile() {
+    let cli = Cli::parse_from([
+        "ms",
+        "--safety-profile",
+        "ci",
+        "security",
+        "audit",
+        "--since",
+        "7d",
+        "--decision",
+        "blocked",
+    ]);
+    assert_eq!(cli.safety_profile.as_deref(), Some("ci"));
+    match cli.command {
+        Commands::Security(args) => match args.command {
+            commands::security::SecurityCommand::Audit(audit) => {
+                assert_eq!(audit.since.as_deref(), Some("7d"));
+                assert_eq!(audit.decision.as_deref(), Some("blocked"));
+                assert!(audit.subcommand.is_none());
+                assert_eq!(audit.limit, 50);
+            }
+            other => panic!("unexpected security command: {other:?}"),
+        },
+        other => panic!("unexpected command: {other:?}"),
+    }
+}
+
+#[test]
+fn parse_index_compact() {
+    match parse(&["index", "--compact"]) {
+        Commands::Index(args) => {
+            assert!(args.compact);
+            assert!(!args.watch);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+    assert!(Cli::try_parse_from(["ms", "index", "--compact", "--watch"]).is_err());
+}
+
+#[test]
+fn parse_index_no_enrich() {
+    match parse(&["index", "--no-enrich", "--force"]) {
+        Commands::Index(args) => {
+            assert!(args.no_enrich);
+            assert!(args.force);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+    match parse(&["index"]) {
+        Commands::Index(args) => assert!(!args.no_enrich),
+        other => panic!("unexpected command: {other:?}"),
+    }
+}
+
+#[test]
+fn parse_index_no_ignore() {
+    match parse(&["index", "./skills", "--no-ignore"]) {
+        Commands::Index(args) => {
+            assert!(args.no_ignore);
+            assert_eq!(args.paths, ["./skills"]);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+}
+
+#[test]
+fn parse_load_trial_and_report() {
+    use commands::trial::{CliTrialOutcome, TrialCommand};
+
+    match parse(&["load", "deploy", "--trial"]) {
+        Commands::Load(args) => assert!(args.trial),
+        other => panic!("unexpected command: {other:?}"),
+    }
+    assert!(Cli::try_parse_from(["ms", "load", "--auto", "--trial"]).is_err());
+    match parse(&["trial", "report", "abc-123", "--outcome", "neutral"]) {
+        Commands::Trial(args) => match args.command {
+            TrialCommand::Report(report) => {
+                assert_eq!(report.trial_id, "abc-123");
+                assert_eq!(report.outcome, CliTrialOutcome::Neutral);
+                assert!(report.notes.is_none());
+            }
+            other @ TrialCommand::List(_) => panic!("unexpected subcommand: {other:?}"),
+        },
+        other => panic!("unexpected command: {other:?}"),
+    }
+}
+
+#[test]
+fn parse_errors_list() {
+    match parse(&["errors", "list"]) {
+        Commands::Errors(args) => {
+            let commands::errors::ErrorsCommand::List(list) = args.command;
+            assert!(list.category.is_none());
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+}
+
+#[test]
+fn parse_pack_args() {
+    match parse(&[
+        "pack",
+        "--skills",
+        "terraform,aws-iam",
+        "--query",
+        "terraform deploys",
+        "--budget",
+        "12000",
+        "--model-profile",
+        "gpt",
+        "--out",
+        "ctx.md",
+    ]) {
+        Commands::Pack(args) => {
+            assert_eq!(args.skills, ["terraform", "aws-iam"]);
+            assert_eq!(args.query.as_deref(), Some("terraform deploys"));
+            assert_eq!(args.budget, Some(12000));
+            assert_eq!(args.model_profile, commands::pack::ModelProfile::Gpt);
+            assert_eq!(args.out, std::path::PathBuf::from("ctx.md"));
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+    match parse(&["pack", "--refresh", "ctx.md", "--force"]) {
+        Commands::Pack(args) => {
+            assert_eq!(args.refresh, Some(std::path::PathBuf::from("ctx.md")));
+            assert!(args.force);
+        }
+        other => panic!("unexpected command: {other:?}"),
+    }
+    assert!(Cli::try_parse_from(["ms", "pack"]).is_err());
+    assert!(Cli::try_parse_from(["ms", "pack", "--refresh", "a.md", "--skills", "x"]).is_err());
+}